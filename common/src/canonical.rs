@@ -0,0 +1,170 @@
+//! Canonical encoding for consensus-critical structures.
+//!
+//! Anything that is hashed or signed must be encoded with
+//! [`to_canonical_json`] rather than `serde_json::to_vec` or bincode, whose
+//! output depends on field declaration order and `HashMap` iteration order.
+//! Canonical JSON (`canonical-json-v1`) is the value's serde JSON form with:
+//!
+//! - object keys sorted by their UTF-8 bytes, at every level
+//! - no whitespace
+//! - integers in plain decimal; other numbers are rejected, since float
+//!   formatting is not stable across implementations
+//! - strings escaped as `serde_json` escapes them: `"`, `\` and control
+//!   characters only, everything else as raw UTF-8
+//!
+//! Canonical JSON is still JSON, so records stored in the old encoding
+//! decode unchanged. Only hashes computed over the old bytes differ; callers
+//! that stored such hashes tag new ones with [`CANONICAL_ENCODING`] and fall
+//! back to the legacy hash for untagged records. [`canonicalize`] re-encodes
+//! stored JSON for a one-off migration.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::SerializationError;
+
+/// Name of the encoding, recorded alongside hashes computed over it
+pub const CANONICAL_ENCODING: &str = "canonical-json-v1";
+
+/// Canonical JSON encoding of `value`
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, SerializationError> {
+    let value = serde_json::to_value(value)?;
+    let mut out = Vec::new();
+    write_value(&value, &mut out)?;
+    Ok(out)
+}
+
+/// Re-encode JSON `bytes` canonically
+pub fn canonicalize(bytes: &[u8]) -> Result<Vec<u8>, SerializationError> {
+    let value: Value = serde_json::from_slice(bytes)?;
+    let mut out = Vec::new();
+    write_value(&value, &mut out)?;
+    Ok(out)
+}
+
+/// Whether `bytes` are already canonical JSON
+pub fn is_canonical(bytes: &[u8]) -> bool {
+    canonicalize(bytes).map_or(false, |canonical| canonical == bytes)
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) -> Result<(), SerializationError> {
+    match value {
+        Value::Number(number) if !(number.is_i64() || number.is_u64()) => {
+            return Err(SerializationError::InvalidFormat(format!(
+                "{} is not an integer; canonical encoding only allows integers",
+                number
+            )));
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => serde_json::to_writer(&mut *out, value)?,
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+            out.push(b'{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, key)?;
+                out.push(b':');
+                write_value(item, out)?;
+            }
+            out.push(b'}');
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::CryptoService;
+    use crate::types::{ContractId, ParticipantId, Signature, Transaction, TransactionCommand, TransactionId};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    // Golden vectors: these bytes and hashes must never change. If a
+    // refactor breaks one, it has changed the hash of stored and signed
+    // data, and needs a new encoding version and a migration instead.
+
+    #[test]
+    fn test_golden_value_encoding() {
+        let value = json!({
+            "b": 1,
+            "a": [true, null, "x\n\"é"],
+            "c": { "z": -1, "y": u64::MAX, "": {} },
+        });
+        assert_eq!(
+            String::from_utf8(to_canonical_json(&value).unwrap()).unwrap(),
+            r#"{"a":[true,null,"x\n\"é"],"b":1,"c":{"":{},"y":18446744073709551615,"z":-1}}"#
+        );
+    }
+
+    #[test]
+    fn test_golden_transaction_encoding_and_hash() {
+        let transaction = Transaction {
+            id: TransactionId(Uuid::from_u128(1)),
+            submitter: ParticipantId("alice".to_string()),
+            command: TransactionCommand::Exercise {
+                contract_id: ContractId(Uuid::from_u128(2)),
+                choice: "Transfer".to_string(),
+                argument: json!({ "to": "bob", "amount": 5 }),
+            },
+            created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            signatures: vec![Signature { algorithm: "Ed25519".to_string(), signature: vec![1, 2], public_key: vec![3, 4] }],
+            encrypted_payload: None,
+        };
+        let bytes = to_canonical_json(&transaction).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            concat!(
+                r#"{"command":{"Exercise":{"argument":{"amount":5,"to":"bob"},"choice":"Transfer","#,
+                r#""contract_id":"00000000-0000-0000-0000-000000000002"}},"created_at":"2024-01-01T00:00:00Z","#,
+                r#""encrypted_payload":null,"id":"00000000-0000-0000-0000-000000000001","#,
+                r#""signatures":[{"algorithm":"Ed25519","public_key":[3,4],"signature":[1,2]}],"submitter":"alice"}"#,
+            )
+        );
+        let hash = CryptoService::new().hash_object(&transaction).unwrap();
+        assert_eq!(
+            hash.iter().map(|b| format!("{:02x}", b)).collect::<String>(),
+            "8af657f50a613911eaa0e452c0cb6fd7cd44128e0671527f87feae144282e278"
+        );
+    }
+
+    #[test]
+    fn test_map_iteration_order_does_not_leak() {
+        let mut forward = HashMap::new();
+        let mut backward = HashMap::new();
+        for i in 0..64 {
+            forward.insert(format!("key-{}", i), i);
+            backward.insert(format!("key-{}", 63 - i), 63 - i);
+        }
+        assert_eq!(to_canonical_json(&forward).unwrap(), to_canonical_json(&backward).unwrap());
+    }
+
+    #[test]
+    fn test_floats_are_rejected() {
+        assert!(to_canonical_json(&json!({ "fee": 1.5 })).is_err());
+        assert!(to_canonical_json(&json!({ "fee": 1 })).is_ok());
+    }
+
+    #[test]
+    fn test_legacy_json_migrates_to_the_same_bytes() {
+        let legacy = serde_json::to_vec_pretty(&json!({ "b": [1, 2], "a": "x" })).unwrap();
+        assert!(!is_canonical(&legacy));
+        let migrated = canonicalize(&legacy).unwrap();
+        assert_eq!(migrated, to_canonical_json(&json!({ "a": "x", "b": [1, 2] })).unwrap());
+        assert!(is_canonical(&migrated));
+    }
+}
@@ -1,7 +1,10 @@
-use crate::types::{EncryptedData, Signature};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
+use crate::error::{SerializationError, TransactionError};
+use crate::types::{EncryptedData, ParticipantId, Signature};
 use aes_gcm::{Aes256Gcm, Key, Nonce, KeyInit};
 use aes_gcm::aead::{Aead, OsRng, AeadCore};
-use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer, Verifier};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer, Verifier, VerifyingKey};
 use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey, SharedSecret};
 use ring::digest::{Context, SHA256};
 use anyhow::{Result, anyhow};
@@ -184,13 +187,143 @@ impl CryptoService {
         context.finish().as_ref().to_vec()
     }
 
-    /// Create a deterministic hash from serializable data
+    /// SHA-256 over the canonical encoding of `obj`, see [`crate::canonical`]
     pub fn hash_object<T: Serialize>(&self, obj: &T) -> Result<Vec<u8>> {
-        let serialized = bincode::serialize(obj)?;
+        let serialized = crate::canonical::to_canonical_json(obj)?;
         Ok(self.hash(&serialized))
     }
 }
 
+/// Signatures over one message by distinct participants.
+///
+/// A participant signs at most once: adding a second signature from the
+/// same signer is an error rather than a silent overwrite. Signatures are
+/// kept in signer ID order whatever order they were collected in, so the
+/// serialized set is deterministic.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignatureSet {
+    signatures: BTreeMap<String, Signature>,
+}
+
+impl SignatureSet {
+    /// Create an empty signature set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `signer`'s signature without verifying it
+    pub fn insert(&mut self, signer: ParticipantId, signature: Signature) -> Result<(), TransactionError> {
+        match self.signatures.entry(signer.0) {
+            Entry::Occupied(entry) => Err(TransactionError::DuplicateSignature(ParticipantId(entry.key().clone()))),
+            Entry::Vacant(entry) => {
+                entry.insert(signature);
+                Ok(())
+            }
+        }
+    }
+
+    /// Add `signer`'s signature if it verifies over `message` against the
+    /// signer's registered public key
+    pub fn insert_verified(
+        &mut self,
+        signer: ParticipantId,
+        signature: Signature,
+        message: &[u8],
+        registered_key: &[u8],
+    ) -> Result<(), TransactionError> {
+        if self.contains(&signer) {
+            return Err(TransactionError::DuplicateSignature(signer));
+        }
+        if !Self::verifies(&signature, message, registered_key) {
+            return Err(TransactionError::InvalidSignature(signer));
+        }
+        self.insert(signer, signature)
+    }
+
+    /// Check every signature over `message` against its signer's
+    /// registered public key, as returned by `registered_key`
+    pub fn verify<F>(&self, message: &[u8], registered_key: F) -> Result<(), TransactionError>
+    where
+        F: Fn(&ParticipantId) -> Option<Vec<u8>>,
+    {
+        for (signer, signature) in self.iter() {
+            let key = registered_key(&signer).ok_or_else(|| TransactionError::UnknownSigner(signer.clone()))?;
+            if !Self::verifies(signature, message, &key) {
+                return Err(TransactionError::InvalidSignature(signer));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `signature` is an Ed25519 signature over `message` by
+    /// `registered_key`. A signature naming a different public key fails
+    /// even if it verifies under the key it names.
+    fn verifies(signature: &Signature, message: &[u8], registered_key: &[u8]) -> bool {
+        if signature.algorithm != "Ed25519" {
+            return false;
+        }
+        if !signature.public_key.is_empty() && !utils::constant_time_eq(&signature.public_key, registered_key) {
+            return false;
+        }
+        utils::verify_ed25519(registered_key, message, &signature.signature)
+    }
+
+    /// Whether `signer` has signed
+    pub fn contains(&self, signer: &ParticipantId) -> bool {
+        self.signatures.contains_key(&signer.0)
+    }
+
+    /// Number of signers
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Whether nobody has signed
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Signers and their signatures, in signer ID order
+    pub fn iter(&self) -> impl Iterator<Item = (ParticipantId, &Signature)> + '_ {
+        self.signatures.iter().map(|(signer, signature)| (ParticipantId(signer.clone()), signature))
+    }
+
+    /// Participants in `required` who have not signed, in the order given
+    pub fn missing(&self, required: &[ParticipantId]) -> Vec<ParticipantId> {
+        let mut seen = HashSet::new();
+        required.iter()
+            .filter(|participant| !self.contains(participant) && seen.insert(&participant.0))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether every participant in `required` has signed
+    pub fn is_complete(&self, required: &[ParticipantId]) -> bool {
+        required.iter().all(|participant| self.contains(participant))
+    }
+
+    /// Whether at least `threshold` distinct participants of `eligible`
+    /// have signed (k of n). A threshold of 0 is never met, so an unset
+    /// quorum fails closed.
+    pub fn meets_threshold(&self, threshold: usize, eligible: &[ParticipantId]) -> bool {
+        let signed: HashSet<&str> = eligible.iter()
+            .filter(|participant| self.contains(participant))
+            .map(|participant| participant.0.as_str())
+            .collect();
+        threshold > 0 && signed.len() >= threshold
+    }
+
+    /// Signatures in signer ID order, as stored on a transaction
+    pub fn to_signatures(&self) -> Vec<Signature> {
+        self.signatures.values().cloned().collect()
+    }
+
+    /// Canonical encoding of the set, see [`crate::canonical`]
+    pub fn to_canonical_bytes(&self) -> Result<Vec<u8>, SerializationError> {
+        crate::canonical::to_canonical_json(self)
+    }
+}
+
 /// Utility functions for cryptographic operations
 pub mod utils {
     use super::*;
@@ -216,6 +349,19 @@ pub mod utils {
         key
     }
 
+    /// Strict Ed25519 verification of `signature` over `message` by the
+    /// raw 32-byte `public_key`
+    pub fn verify_ed25519(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let Ok(key_bytes) = <[u8; 32]>::try_from(public_key) else {
+            return false;
+        };
+        let Ok(key) = VerifyingKey::from_bytes(&key_bytes) else {
+            return false;
+        };
+        ed25519_dalek::Signature::from_slice(signature)
+            .map_or(false, |sig| key.verify_strict(message, &sig).is_ok())
+    }
+
     /// Constant-time comparison of byte arrays
     pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
         if a.len() != b.len() {
@@ -228,6 +374,8 @@ pub mod utils {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use proptest::prelude::*;
 
     #[test]
     fn test_signing_and_verification() {
@@ -258,4 +406,104 @@ mod tests {
         
         assert_eq!(data, decrypted.as_slice());
     }
-}
\ No newline at end of file
+
+    fn signed(seed: u8, message: &[u8]) -> (ParticipantId, Vec<u8>, Signature) {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+        let public_key = key.verifying_key().to_bytes().to_vec();
+        let signature = Signature {
+            algorithm: "Ed25519".to_string(),
+            signature: key.sign(message).to_bytes().to_vec(),
+            public_key: public_key.clone(),
+        };
+        (ParticipantId(format!("participant-{}", seed)), public_key, signature)
+    }
+
+    fn collect<'a>(signed: impl Iterator<Item = &'a (ParticipantId, Vec<u8>, Signature)>) -> SignatureSet {
+        let mut set = SignatureSet::new();
+        for (signer, _, signature) in signed {
+            set.insert(signer.clone(), signature.clone()).unwrap();
+        }
+        set
+    }
+
+    fn registry(signed: &[(ParticipantId, Vec<u8>, Signature)]) -> HashMap<ParticipantId, Vec<u8>> {
+        signed.iter().map(|(signer, key, _)| (signer.clone(), key.clone())).collect()
+    }
+
+    #[test]
+    fn test_signature_set_requires_distinct_registered_signers() {
+        let message = b"transfer 10 gold";
+        let signed: Vec<_> = (1..=3).map(|seed| signed(seed, message)).collect();
+        let keys = registry(&signed);
+        let (alice, bob, carol) = (&signed[0], &signed[1], &signed[2]);
+
+        let mut set = SignatureSet::new();
+        set.insert_verified(alice.0.clone(), alice.2.clone(), message, &alice.1).unwrap();
+        assert!(matches!(
+            set.insert(alice.0.clone(), alice.2.clone()),
+            Err(TransactionError::DuplicateSignature(ref signer)) if signer == &alice.0
+        ));
+        // Bob's signature presented as carol's does not verify against carol's key
+        assert!(matches!(
+            set.insert_verified(carol.0.clone(), bob.2.clone(), message, &carol.1),
+            Err(TransactionError::InvalidSignature(_))
+        ));
+        set.insert_verified(bob.0.clone(), bob.2.clone(), message, &bob.1).unwrap();
+
+        let required = vec![alice.0.clone(), bob.0.clone(), carol.0.clone()];
+        assert!(!set.is_complete(&required));
+        assert_eq!(set.missing(&required), vec![carol.0.clone()]);
+        assert!(set.meets_threshold(2, &required));
+        assert!(!set.meets_threshold(3, &required));
+        assert!(!set.meets_threshold(0, &required));
+        assert!(set.verify(message, |signer| keys.get(signer).cloned()).is_ok());
+        assert!(matches!(set.verify(message, |_| None), Err(TransactionError::UnknownSigner(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_signature_order_does_not_matter(
+            seeds in proptest::collection::btree_set(1u8..=255, 1..6),
+            rotation in 0usize..6,
+            message in proptest::collection::vec(any::<u8>(), 0..64),
+        ) {
+            let signed: Vec<_> = seeds.iter().map(|seed| signed(*seed, &message)).collect();
+            let mut reordered = signed.clone();
+            reordered.rotate_left(rotation % signed.len());
+            reordered.reverse();
+
+            let forward = collect(signed.iter());
+            let backward = collect(reordered.iter());
+            prop_assert_eq!(forward.to_canonical_bytes().unwrap(), backward.to_canonical_bytes().unwrap());
+            let keys = registry(&signed);
+            prop_assert!(backward.verify(&message, |signer| keys.get(signer).cloned()).is_ok());
+        }
+
+        #[test]
+        fn prop_tampering_is_detected(
+            seeds in proptest::collection::btree_set(1u8..=255, 1..6),
+            message in proptest::collection::vec(any::<u8>(), 1..64),
+            position in any::<proptest::sample::Index>(),
+            victim in any::<proptest::sample::Index>(),
+            flip in 1u8..=255,
+        ) {
+            let signed: Vec<_> = seeds.iter().map(|seed| signed(*seed, &message)).collect();
+            let keys = registry(&signed);
+            let set = collect(signed.iter());
+
+            let mut tampered_message = message.clone();
+            tampered_message[position.index(message.len())] ^= flip;
+            prop_assert!(set.verify(&tampered_message, |signer| keys.get(signer).cloned()).is_err());
+
+            let mut tampered = signed.clone();
+            let (_, _, signature) = &mut tampered[victim.index(signed.len())];
+            let byte = position.index(signature.signature.len());
+            signature.signature[byte] ^= flip;
+            let set = collect(tampered.iter());
+            prop_assert!(matches!(
+                set.verify(&message, |signer| keys.get(signer).cloned()),
+                Err(TransactionError::InvalidSignature(_))
+            ));
+        }
+    }
+}
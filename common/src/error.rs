@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use crate::types::{ParticipantId, ContractId, TransactionId};
 
@@ -30,6 +31,127 @@ pub enum GarpError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("Settlement fee {required} exceeds maximum fee {max_fee}")]
+    FeeExceeded { required: u64, max_fee: u64 },
+
+    /// A request or transaction failed validation
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The request was built against state that is no longer current
+    #[error("State mismatch: {0}")]
+    StateMismatch(String),
+
+    /// A domain or service is temporarily unable to take the request
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+
+    /// The node is in maintenance mode and not accepting transactions
+    #[error("Maintenance mode: {0}")]
+    Maintenance(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
+
+    #[error("Internal error: {0}")]
+    InternalError(String),
+}
+
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $variant:ident = $code:literal, $name:literal, $retryable:literal;)*) => {
+        /// Stable, machine-readable error code reported to API clients
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum ErrorCode {
+            $($(#[$doc])* #[serde(rename = $name)] $variant,)*
+        }
+
+        impl ErrorCode {
+            /// Every error code, in table order
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant),*];
+
+            /// Numeric code, used as the JSON-RPC error code
+            pub fn code(self) -> i64 {
+                match self { $(ErrorCode::$variant => $code,)* }
+            }
+
+            /// Machine-readable name, e.g. `DOMAIN_UNAVAILABLE`
+            pub fn name(self) -> &'static str {
+                match self { $(ErrorCode::$variant => $name,)* }
+            }
+
+            /// Whether the same request may succeed if retried later
+            pub fn is_retryable(self) -> bool {
+                match self { $(ErrorCode::$variant => $retryable,)* }
+            }
+
+            pub fn from_code(code: i64) -> Option<Self> {
+                match code { $($code => Some(ErrorCode::$variant),)* _ => None }
+            }
+        }
+    };
+}
+
+// The one table of error codes. Codes and names are part of the API: never
+// renumber or rename an entry, only add new ones.
+error_codes! {
+    // JSON-RPC protocol errors
+    ParseError = -32700, "PARSE_ERROR", false;
+    InvalidRequest = -32600, "INVALID_REQUEST", false;
+    MethodNotFound = -32601, "METHOD_NOT_FOUND", false;
+    InvalidParams = -32602, "INVALID_PARAMS", false;
+    InternalError = -32603, "INTERNAL_ERROR", false;
+    Unauthorized = -32001, "UNAUTHORIZED", false;
+
+    // The request can not succeed as sent
+    ValidationFailed = 1000, "VALIDATION_FAILED", false;
+    InvalidTransaction = 1001, "INVALID_TRANSACTION", false;
+    InvalidSignature = 1002, "INVALID_SIGNATURE", false;
+    InsufficientBalance = 1003, "INSUFFICIENT_BALANCE", false;
+    FeeExceeded = 1004, "FEE_EXCEEDED", false;
+    DuplicateTransaction = 1005, "DUPLICATE_TRANSACTION", false;
+    /// Conflicts with a transaction already submitted or processed
+    Conflict = 1006, "CONFLICT", false;
+    PermissionDenied = 1007, "PERMISSION_DENIED", false;
+    /// Built against stale state; rebuild before resubmitting
+    StateMismatch = 1008, "STATE_MISMATCH", false;
+    NotFound = 1100, "NOT_FOUND", false;
+    ContractFailed = 1200, "CONTRACT_FAILED", false;
+
+    // Transient conditions; retry later
+    DomainUnavailable = 2000, "DOMAIN_UNAVAILABLE", true;
+    NetworkUnavailable = 2001, "NETWORK_UNAVAILABLE", true;
+    Timeout = 2002, "TIMEOUT", true;
+    StorageUnavailable = 2003, "STORAGE_UNAVAILABLE", true;
+    /// The node is in maintenance mode; reads still work
+    MaintenanceMode = 2004, "MAINTENANCE_MODE", true;
+
+    // Failures on the serving node
+    NetworkError = 3000, "NETWORK_ERROR", false;
+    ConsensusFailed = 3001, "CONSENSUS_FAILED", false;
+    StorageError = 3002, "STORAGE_ERROR", false;
+    ConfigError = 3003, "CONFIG_ERROR", false;
+    SerializationError = 3004, "SERIALIZATION_ERROR", false;
+    CryptoError = 3005, "CRYPTO_ERROR", false;
+}
+
+impl ErrorCode {
+    /// `data` member of a JSON-RPC error object carrying this code
+    pub fn rpc_data(self) -> serde_json::Value {
+        serde_json::json!({ "name": self.name(), "retryable": self.is_retryable() })
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 /// Cryptographic operation errors
@@ -90,6 +212,9 @@ pub enum NetworkError {
     #[error("Peer banned: {0}")]
     PeerBanned(String),
 
+    #[error("Message of {size} bytes exceeds the {limit} byte limit")]
+    MessageTooLarge { size: usize, limit: usize },
+
     #[error("Transport error: {0}")]
     TransportError(String),
 
@@ -134,12 +259,33 @@ pub enum TransactionError {
     #[error("Missing required signature from: {0:?}")]
     MissingSignature(ParticipantId),
 
+    #[error("More than one signature from: {0:?}")]
+    DuplicateSignature(ParticipantId),
+
+    #[error("Invalid signature from: {0:?}")]
+    InvalidSignature(ParticipantId),
+
+    #[error("No registered public key for signer: {0:?}")]
+    UnknownSigner(ParticipantId),
+
     #[error("Insufficient permissions for participant: {0:?}")]
     InsufficientPermissions(ParticipantId),
 
     #[error("Transaction already processed: {0}")]
     AlreadyProcessed(TransactionId),
 
+    #[error("Duplicate transaction: {0} was already submitted and has finished")]
+    DuplicateTransaction(TransactionId),
+
+    #[error("Transaction id {0} was already submitted with different content")]
+    ConflictingTransactionId(TransactionId),
+
+    #[error("Unknown transactions: {}", .0.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", "))]
+    UnknownTransactions(Vec<TransactionId>),
+
+    #[error("Invalid transactions in batch: {}", .0.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", "))]
+    InvalidBatch(Vec<TransactionId>),
+
     #[error("Double spending detected for transaction: {0}")]
     DoubleSpending(TransactionId),
 
@@ -230,17 +376,70 @@ pub type GarpResult<T> = Result<T, GarpError>;
 
 /// Utility functions for error handling
 impl GarpError {
-    /// Check if error is retryable
-    pub fn is_retryable(&self) -> bool {
+    /// Stable code reported to clients for this error
+    pub fn code(&self) -> ErrorCode {
         match self {
-            GarpError::Network(NetworkError::Timeout) => true,
-            GarpError::Network(NetworkError::ConnectionFailed { .. }) => true,
-            GarpError::Database(DatabaseError::ConnectionFailed(_)) => true,
-            GarpError::Consensus(ConsensusError::Timeout) => true,
-            _ => false,
+            GarpError::Crypto(CryptoError::InvalidSignature) => ErrorCode::InvalidSignature,
+            GarpError::Crypto(CryptoError::InvalidPublicKey | CryptoError::InvalidPrivateKey) => ErrorCode::ValidationFailed,
+            GarpError::Crypto(_) => ErrorCode::CryptoError,
+
+            GarpError::Network(NetworkError::Timeout) => ErrorCode::Timeout,
+            GarpError::Network(NetworkError::ConnectionFailed { .. } | NetworkError::PeerNotFound(_)) => ErrorCode::NetworkUnavailable,
+            GarpError::Network(NetworkError::AuthenticationFailed(_) | NetworkError::PeerBanned(_)) => ErrorCode::PermissionDenied,
+            GarpError::Network(NetworkError::InvalidMessageFormat(_) | NetworkError::MessageTooLarge { .. }) => ErrorCode::InvalidRequest,
+            GarpError::Network(_) => ErrorCode::NetworkError,
+
+            GarpError::Consensus(ConsensusError::Timeout) => ErrorCode::Timeout,
+            GarpError::Consensus(ConsensusError::InvalidProposal(_)) => ErrorCode::ValidationFailed,
+            GarpError::Consensus(_) => ErrorCode::ConsensusFailed,
+
+            GarpError::Transaction(TransactionError::NotFound(_) | TransactionError::UnknownTransactions(_)) => ErrorCode::NotFound,
+            GarpError::Transaction(
+                TransactionError::Invalid(_) | TransactionError::ValidationFailed(_) | TransactionError::InvalidBatch(_),
+            ) => ErrorCode::InvalidTransaction,
+            GarpError::Transaction(
+                TransactionError::MissingSignature(_)
+                | TransactionError::DuplicateSignature(_)
+                | TransactionError::InvalidSignature(_)
+                | TransactionError::UnknownSigner(_),
+            ) => ErrorCode::InvalidSignature,
+            GarpError::Transaction(TransactionError::InsufficientPermissions(_)) => ErrorCode::PermissionDenied,
+            GarpError::Transaction(TransactionError::DuplicateTransaction(_)) => ErrorCode::DuplicateTransaction,
+            GarpError::Transaction(
+                TransactionError::AlreadyProcessed(_)
+                | TransactionError::ConflictingTransactionId(_)
+                | TransactionError::DoubleSpending(_),
+            ) => ErrorCode::Conflict,
+            GarpError::Transaction(TransactionError::InsufficientBalance { .. }) => ErrorCode::InsufficientBalance,
+            GarpError::Transaction(TransactionError::ExecutionFailed(_) | TransactionError::AtomicityViolation) => ErrorCode::InvalidTransaction,
+
+            GarpError::Contract(ContractError::NotFound(_)) => ErrorCode::NotFound,
+            GarpError::Contract(ContractError::UnauthorizedAccess(_)) => ErrorCode::PermissionDenied,
+            GarpError::Contract(_) => ErrorCode::ContractFailed,
+
+            GarpError::Database(DatabaseError::ConnectionFailed(_)) => ErrorCode::StorageUnavailable,
+            GarpError::Database(DatabaseError::RecordNotFound(_)) => ErrorCode::NotFound,
+            GarpError::Database(DatabaseError::DuplicateKey(_)) => ErrorCode::Conflict,
+            GarpError::Database(_) => ErrorCode::StorageError,
+
+            GarpError::Serialization(_) => ErrorCode::SerializationError,
+            GarpError::FeeExceeded { .. } => ErrorCode::FeeExceeded,
+            GarpError::ValidationError(_) => ErrorCode::ValidationFailed,
+            GarpError::NotFound(_) => ErrorCode::NotFound,
+            GarpError::StateMismatch(_) => ErrorCode::StateMismatch,
+            GarpError::Unavailable(_) => ErrorCode::DomainUnavailable,
+            GarpError::Maintenance(_) => ErrorCode::MaintenanceMode,
+            GarpError::StorageError(_) => ErrorCode::StorageError,
+            GarpError::Config(_) | GarpError::ConfigError(_) => ErrorCode::ConfigError,
+            GarpError::Internal(_) | GarpError::InternalError(_) => ErrorCode::InternalError,
         }
     }
 
+    /// Check if error is retryable
+    pub fn is_retryable(&self) -> bool {
+        self.code().is_retryable()
+    }
+
     /// Get error severity level
     pub fn severity(&self) -> ErrorSeverity {
         match self {
@@ -249,7 +448,7 @@ impl GarpError {
             GarpError::Transaction(TransactionError::DoubleSpending(_)) => ErrorSeverity::Critical,
             GarpError::Database(DatabaseError::IntegrityViolation(_)) => ErrorSeverity::High,
             GarpError::Network(_) => ErrorSeverity::Medium,
-            GarpError::Config(_) => ErrorSeverity::High,
+            GarpError::Config(_) | GarpError::ConfigError(_) => ErrorSeverity::High,
             _ => ErrorSeverity::Low,
         }
     }
@@ -328,4 +527,49 @@ macro_rules! garp_bail {
             ErrorContext::new($operation).with_participant($participant)
         ))
     };
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_error_codes_are_unique() {
+        let codes: HashSet<i64> = ErrorCode::ALL.iter().map(|c| c.code()).collect();
+        let names: HashSet<&str> = ErrorCode::ALL.iter().map(|c| c.name()).collect();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+        assert_eq!(names.len(), ErrorCode::ALL.len());
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.code()), Some(*code));
+            assert_eq!(serde_json::to_value(code).unwrap(), serde_json::json!(code.name()));
+        }
+        assert_eq!(ErrorCode::from_code(42), None);
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let retryable = [
+            GarpError::Unavailable("domain-a is suspended".to_string()),
+            GarpError::Network(NetworkError::Timeout),
+            GarpError::Network(NetworkError::ConnectionFailed { peer: "p".to_string(), reason: "refused".to_string() }),
+            GarpError::Database(DatabaseError::ConnectionFailed("down".to_string())),
+            GarpError::Consensus(ConsensusError::Timeout),
+            GarpError::Maintenance("storage migration".to_string()),
+        ];
+        for error in &retryable {
+            assert!(error.is_retryable(), "{}", error);
+        }
+
+        let permanent = [
+            GarpError::ValidationError("empty data".to_string()),
+            GarpError::Transaction(TransactionError::Invalid("bad".to_string())),
+            GarpError::FeeExceeded { required: 10, max_fee: 5 },
+            GarpError::StorageError("corrupt".to_string()),
+        ];
+        for error in &permanent {
+            assert!(!error.is_retryable(), "{}", error);
+        }
+        assert_eq!(retryable[0].code(), ErrorCode::DomainUnavailable);
+        assert_eq!(permanent[1].code().rpc_data(), serde_json::json!({ "name": "INVALID_TRANSACTION", "retryable": false }));
+    }
+}
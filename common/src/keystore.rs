@@ -0,0 +1,588 @@
+//! Encrypted node keystore and signing key rotation
+//!
+//! A node keeps its Ed25519 signing keys in a keystore file. Each secret key
+//! is sealed with AES-256-GCM under its own random data key, and the data
+//! key is wrapped by a [`KeyProtector`]: [`PassphraseProtector`] derives the
+//! wrapping key from a passphrase, other implementations can hand data keys
+//! to a KMS. No secret is written in the clear.
+//!
+//! Keys are numbered by epoch. Rotating generates the next key together with
+//! a [`KeyRotation`] announcing it, signed by the current key. Peers apply
+//! the announcement to the [`KeyHistory`] they keep for the node: the new
+//! key signs from the announced time on, and the old key is still accepted
+//! for an overlap window after it, so messages in flight across the switch
+//! verify. Verifiers choose keys by the timestamp a message carries, see
+//! [`KeyHistory::verify`]. Past the overlap the old secret is retired from
+//! the keystore.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, AeadCore, OsRng};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::crypto::utils;
+use crate::error::{CryptoError, GarpError, GarpResult};
+use crate::types::EncryptedData;
+
+/// Algorithm sealed secrets are encrypted with
+const SEAL_ALGORITHM: &str = "AES-256-GCM";
+
+/// PBKDF2 iterations used by [`PassphraseProtector`] unless overridden
+pub const DEFAULT_PASSPHRASE_ITERATIONS: u32 = 210_000;
+
+const PASSPHRASE_SALT_LEN: usize = 16;
+const AES_GCM_NONCE_LEN: usize = 12;
+
+/// Public key of one epoch and the period it signs for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEpoch {
+    pub epoch: u64,
+    /// Raw Ed25519 public key
+    pub public_key: Vec<u8>,
+    /// First instant the key signs for; `None` for a node's first key
+    pub valid_from: Option<DateTime<Utc>>,
+    /// Instant the key stops being accepted; `None` while it is current
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+impl KeyEpoch {
+    /// Whether a message made at `at` may be signed with this key
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        self.valid_from.map_or(true, |from| from <= at) && self.valid_until.map_or(true, |until| at < until)
+    }
+}
+
+/// Keys a node has signed with, oldest epoch first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyHistory {
+    epochs: Vec<KeyEpoch>,
+}
+
+impl KeyHistory {
+    /// History starting at `public_key`, valid since the beginning
+    pub fn new(public_key: Vec<u8>) -> Self {
+        Self {
+            epochs: vec![KeyEpoch { epoch: 0, public_key, valid_from: None, valid_until: None }],
+        }
+    }
+
+    /// Key the node signs new messages with
+    pub fn current(&self) -> Option<&KeyEpoch> {
+        self.epochs.last()
+    }
+
+    pub fn epochs(&self) -> &[KeyEpoch] {
+        &self.epochs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+
+    /// Keys accepted for a message made at `at`, newest first. Two keys are
+    /// returned inside a rotation's overlap window.
+    pub fn keys_at(&self, at: DateTime<Utc>) -> impl Iterator<Item = &KeyEpoch> {
+        self.epochs.iter().rev().filter(move |epoch| epoch.is_valid_at(at))
+    }
+
+    /// Epoch of the key that verifies `signature` over `message`, among the
+    /// keys accepted at the message's timestamp `at`
+    pub fn verify(&self, message: &[u8], signature: &[u8], at: DateTime<Utc>) -> Option<&KeyEpoch> {
+        self.keys_at(at).find(|epoch| utils::verify_ed25519(&epoch.public_key, message, signature))
+    }
+
+    /// End of the overlap window of the latest rotation, if it is still open at `at`
+    pub fn overlap_until(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.epochs.iter().rev().skip(1).filter_map(|epoch| epoch.valid_until).find(|until| at < *until)
+    }
+
+    /// Apply `rotation` after checking it chains from the current key and is
+    /// signed by it. Applying the rotation that produced the current key
+    /// again is a no-op, so re-delivered announcements are harmless.
+    pub fn apply_rotation(&mut self, rotation: &KeyRotation) -> GarpResult<()> {
+        let current = self
+            .epochs
+            .last()
+            .ok_or_else(|| GarpError::ValidationError(format!("No key known for {}", rotation.node_id)))?;
+        if rotation.epoch == current.epoch && current.public_key == rotation.public_key {
+            return Ok(());
+        }
+        if rotation.epoch != current.epoch + 1 {
+            return Err(GarpError::ValidationError(format!(
+                "Key rotation of {} to epoch {} does not follow the current epoch {}",
+                rotation.node_id, rotation.epoch, current.epoch
+            )));
+        }
+        if !utils::constant_time_eq(&rotation.previous_public_key, &current.public_key) {
+            return Err(GarpError::ValidationError(format!(
+                "Key rotation of {} does not start from its current key",
+                rotation.node_id
+            )));
+        }
+        if current.valid_from.map_or(false, |from| rotation.effective_at < from) {
+            return Err(GarpError::ValidationError(format!(
+                "Key rotation of {} takes effect before the current key",
+                rotation.node_id
+            )));
+        }
+        if let Some(until) = self.overlap_until(rotation.effective_at) {
+            return Err(GarpError::ValidationError(format!(
+                "Previous key of {} is accepted until {}; rotate after its overlap window",
+                rotation.node_id, until
+            )));
+        }
+        if !utils::verify_ed25519(&rotation.previous_public_key, &rotation.signing_message(), &rotation.signature) {
+            return Err(CryptoError::InvalidSignature.into());
+        }
+
+        let overlap = chrono::Duration::seconds(rotation.overlap_secs.min(i64::MAX as u64) as i64);
+        if let Some(current) = self.epochs.last_mut() {
+            current.valid_until = Some(rotation.effective_at + overlap);
+        }
+        self.epochs.push(KeyEpoch {
+            epoch: rotation.epoch,
+            public_key: rotation.public_key.clone(),
+            valid_from: Some(rotation.effective_at),
+            valid_until: None,
+        });
+        Ok(())
+    }
+}
+
+/// Announcement of a node's next signing key, signed by its current key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    /// Node whose key rotates: a domain ID or a validator's participant ID
+    pub node_id: String,
+    /// Epoch of the new key
+    pub epoch: u64,
+    pub previous_public_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+    /// When the new key starts signing
+    pub effective_at: DateTime<Utc>,
+    /// How long the previous key stays valid after `effective_at`
+    pub overlap_secs: u64,
+    /// Signature by the previous key over [`KeyRotation::signing_message`]
+    pub signature: Vec<u8>,
+}
+
+impl KeyRotation {
+    /// Message the previous key signs
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!(
+            "key-rotation|{}|{}|{}|{}|{}|{}",
+            self.node_id,
+            self.epoch,
+            to_hex(&self.previous_public_key),
+            to_hex(&self.public_key),
+            self.effective_at.timestamp_millis(),
+            self.overlap_secs
+        )
+        .into_bytes()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Wraps the data keys that seal keystore secrets. Implement this to keep
+/// the wrapping key in a KMS or HSM rather than deriving it on the node.
+#[async_trait]
+pub trait KeyProtector: Send + Sync {
+    /// Name recorded with each sealed key and checked when unsealing
+    fn name(&self) -> &str;
+
+    async fn wrap_key(&self, data_key: &[u8]) -> GarpResult<Vec<u8>>;
+
+    async fn unwrap_key(&self, wrapped_key: &[u8]) -> GarpResult<Vec<u8>>;
+}
+
+/// Wraps data keys under a key derived from a passphrase with PBKDF2
+pub struct PassphraseProtector {
+    passphrase: String,
+    iterations: u32,
+}
+
+impl PassphraseProtector {
+    pub fn new(passphrase: impl Into<String>) -> GarpResult<Self> {
+        let passphrase = passphrase.into();
+        if passphrase.is_empty() {
+            return Err(GarpError::ConfigError("Keystore passphrase cannot be empty".to_string()));
+        }
+        Ok(Self { passphrase, iterations: DEFAULT_PASSPHRASE_ITERATIONS })
+    }
+
+    /// Read the passphrase from the environment variable `var`
+    pub fn from_env(var: &str) -> GarpResult<Self> {
+        let passphrase = std::env::var(var)
+            .map_err(|_| GarpError::ConfigError(format!("Keystore passphrase variable {} is not set", var)))?;
+        Self::new(passphrase)
+    }
+
+    /// PBKDF2 iterations for keys wrapped from now on; existing keys keep
+    /// the count they were wrapped with
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+}
+
+#[async_trait]
+impl KeyProtector for PassphraseProtector {
+    fn name(&self) -> &str {
+        "passphrase"
+    }
+
+    /// Output is `iterations (4, big-endian) || salt || nonce || ciphertext`
+    async fn wrap_key(&self, data_key: &[u8]) -> GarpResult<Vec<u8>> {
+        let salt = &utils::generate_random_key()[..PASSPHRASE_SALT_LEN];
+        let wrapping_key = utils::derive_key_from_password(&self.passphrase, salt, self.iterations);
+        let sealed = seal(&wrapping_key, data_key)?;
+
+        let mut wrapped = self.iterations.to_be_bytes().to_vec();
+        wrapped.extend_from_slice(salt);
+        wrapped.extend_from_slice(&sealed.nonce);
+        wrapped.extend_from_slice(&sealed.ciphertext);
+        Ok(wrapped)
+    }
+
+    async fn unwrap_key(&self, wrapped_key: &[u8]) -> GarpResult<Vec<u8>> {
+        let header_len = 4 + PASSPHRASE_SALT_LEN + AES_GCM_NONCE_LEN;
+        if wrapped_key.len() <= header_len {
+            return Err(CryptoError::DecryptionFailed("wrapped data key is truncated".to_string()).into());
+        }
+        let (iterations, rest) = wrapped_key.split_at(4);
+        let (salt, rest) = rest.split_at(PASSPHRASE_SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(AES_GCM_NONCE_LEN);
+        let iterations = u32::from_be_bytes(iterations.try_into().expect("4-byte prefix"));
+
+        let wrapping_key = utils::derive_key_from_password(&self.passphrase, salt, iterations.max(1));
+        open(&wrapping_key, &EncryptedData {
+            ciphertext: ciphertext.to_vec(),
+            nonce: nonce.to_vec(),
+            algorithm: SEAL_ALGORITHM.to_string(),
+        })
+        .map_err(|_| CryptoError::DecryptionFailed("wrong keystore passphrase or corrupted key".to_string()).into())
+    }
+}
+
+fn seal(key: &[u8], plaintext: &[u8]) -> GarpResult<EncryptedData> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+    Ok(EncryptedData { ciphertext, nonce: nonce.to_vec(), algorithm: SEAL_ALGORITHM.to_string() })
+}
+
+fn open(key: &[u8], sealed: &EncryptedData) -> GarpResult<Vec<u8>> {
+    if sealed.algorithm != SEAL_ALGORITHM {
+        return Err(CryptoError::UnsupportedAlgorithm(sealed.algorithm.clone()).into());
+    }
+    if sealed.nonce.len() != AES_GCM_NONCE_LEN {
+        return Err(CryptoError::DecryptionFailed("invalid nonce length".to_string()).into());
+    }
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(&sealed.nonce), sealed.ciphertext.as_ref())
+        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()).into())
+}
+
+fn generate_signing_key() -> SigningKey {
+    let secret: [u8; 32] = utils::generate_random_key().try_into().expect("32-byte key");
+    SigningKey::from_bytes(&secret)
+}
+
+/// Secret key of one epoch, sealed at rest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SealedKey {
+    epoch: u64,
+    /// [`KeyProtector::name`] of the protector that wrapped the data key
+    protector: String,
+    wrapped_data_key: Vec<u8>,
+    /// Secret key encrypted under the data key
+    secret: EncryptedData,
+}
+
+/// On-disk keystore layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeystoreFile {
+    node_id: String,
+    history: KeyHistory,
+    keys: Vec<SealedKey>,
+}
+
+/// A node's signing keys, encrypted at rest
+pub struct Keystore {
+    path: PathBuf,
+    protector: Arc<dyn KeyProtector>,
+    file: KeystoreFile,
+    /// Unsealed secret keys by epoch
+    signing_keys: BTreeMap<u64, SigningKey>,
+}
+
+impl Keystore {
+    /// Open the keystore at `path` and unseal its keys. If there is no
+    /// keystore yet one is created holding `initial_secret`, to migrate a key
+    /// kept in plaintext, or else a freshly generated key.
+    pub async fn open_or_create(
+        path: impl Into<PathBuf>,
+        node_id: &str,
+        protector: Arc<dyn KeyProtector>,
+        initial_secret: Option<[u8; 32]>,
+    ) -> GarpResult<Self> {
+        let path = path.into();
+        if path.exists() {
+            return Self::open(path, node_id, protector).await;
+        }
+
+        let signing_key = initial_secret.map_or_else(generate_signing_key, |secret| SigningKey::from_bytes(&secret));
+        let mut keystore = Self {
+            path,
+            protector,
+            file: KeystoreFile {
+                node_id: node_id.to_string(),
+                history: KeyHistory::new(signing_key.verifying_key().to_bytes().to_vec()),
+                keys: Vec::new(),
+            },
+            signing_keys: BTreeMap::new(),
+        };
+        let sealed = keystore.seal(0, &signing_key).await?;
+        keystore.file.keys.push(sealed);
+        keystore.signing_keys.insert(0, signing_key);
+        write_file(&keystore.path, &keystore.file)?;
+        info!("Created keystore for {} at {}", node_id, keystore.path.display());
+        Ok(keystore)
+    }
+
+    async fn open(path: PathBuf, node_id: &str, protector: Arc<dyn KeyProtector>) -> GarpResult<Self> {
+        let bytes = std::fs::read(&path)
+            .map_err(|e| GarpError::StorageError(format!("Failed to read keystore {}: {}", path.display(), e)))?;
+        let file: KeystoreFile = serde_json::from_slice(&bytes)
+            .map_err(|e| GarpError::StorageError(format!("Invalid keystore {}: {}", path.display(), e)))?;
+        if file.node_id != node_id {
+            return Err(GarpError::ConfigError(format!(
+                "Keystore {} belongs to {}, not {}",
+                path.display(), file.node_id, node_id
+            )));
+        }
+
+        let mut signing_keys = BTreeMap::new();
+        for sealed in &file.keys {
+            if sealed.protector != protector.name() {
+                return Err(GarpError::ConfigError(format!(
+                    "Key epoch {} is protected by {}, not {}",
+                    sealed.epoch, sealed.protector, protector.name()
+                )));
+            }
+            let data_key = protector.unwrap_key(&sealed.wrapped_data_key).await?;
+            let secret: [u8; 32] = open(&data_key, &sealed.secret)?
+                .try_into()
+                .map_err(|_| CryptoError::InvalidPrivateKey)?;
+            let signing_key = SigningKey::from_bytes(&secret);
+            let expected = file.history.epochs().iter().find(|epoch| epoch.epoch == sealed.epoch);
+            if expected.map_or(true, |epoch| epoch.public_key != signing_key.verifying_key().to_bytes()) {
+                return Err(CryptoError::InvalidPrivateKey.into());
+            }
+            signing_keys.insert(sealed.epoch, signing_key);
+        }
+
+        let current = file.history.current().map(|epoch| epoch.epoch);
+        if current.map_or(true, |epoch| !signing_keys.contains_key(&epoch)) {
+            return Err(GarpError::StorageError(format!("Keystore {} has no current key", path.display())));
+        }
+        Ok(Self { path, protector, file, signing_keys })
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.file.node_id
+    }
+
+    /// Epoch of the current key
+    pub fn epoch(&self) -> u64 {
+        self.file.history.current().map_or(0, |epoch| epoch.epoch)
+    }
+
+    /// Key new messages are signed with
+    pub fn signing_key(&self) -> &SigningKey {
+        self.signing_keys.get(&self.epoch()).expect("keystore holds its current key")
+    }
+
+    /// Public keys this node has signed with
+    pub fn history(&self) -> &KeyHistory {
+        &self.file.history
+    }
+
+    /// Generate the next key, effective at `now`, keeping the current one
+    /// valid for `overlap_secs` after it. Returns the announcement for peers.
+    pub async fn rotate(&mut self, now: DateTime<Utc>, overlap_secs: u64) -> GarpResult<KeyRotation> {
+        self.retire_expired(now).await?;
+
+        let next = generate_signing_key();
+        let current = self.signing_key();
+        let mut rotation = KeyRotation {
+            node_id: self.file.node_id.clone(),
+            epoch: self.epoch() + 1,
+            previous_public_key: current.verifying_key().to_bytes().to_vec(),
+            public_key: next.verifying_key().to_bytes().to_vec(),
+            effective_at: now,
+            overlap_secs,
+            signature: Vec::new(),
+        };
+        rotation.signature = current.sign(&rotation.signing_message()).to_bytes().to_vec();
+
+        let mut file = self.file.clone();
+        file.history.apply_rotation(&rotation)?;
+        file.keys.push(self.seal(rotation.epoch, &next).await?);
+        write_file(&self.path, &file)?;
+
+        self.file = file;
+        self.signing_keys.insert(rotation.epoch, next);
+        info!("Rotated signing key of {} to epoch {}", rotation.node_id, rotation.epoch);
+        Ok(rotation)
+    }
+
+    /// Drop the secrets of keys whose overlap window ended by `now`,
+    /// returning their epochs. Their public keys stay in the history.
+    pub async fn retire_expired(&mut self, now: DateTime<Utc>) -> GarpResult<Vec<u64>> {
+        let retired: Vec<u64> = self
+            .file
+            .history
+            .epochs()
+            .iter()
+            .filter(|epoch| epoch.valid_until.map_or(false, |until| until <= now))
+            .map(|epoch| epoch.epoch)
+            .filter(|epoch| self.signing_keys.contains_key(epoch))
+            .collect();
+        if retired.is_empty() {
+            return Ok(retired);
+        }
+
+        let mut file = self.file.clone();
+        file.keys.retain(|sealed| !retired.contains(&sealed.epoch));
+        write_file(&self.path, &file)?;
+        self.file = file;
+        for epoch in &retired {
+            self.signing_keys.remove(epoch);
+        }
+        info!("Retired signing key epochs {:?} of {}", retired, self.file.node_id);
+        Ok(retired)
+    }
+
+    async fn seal(&self, epoch: u64, signing_key: &SigningKey) -> GarpResult<SealedKey> {
+        let data_key = utils::generate_random_key();
+        Ok(SealedKey {
+            epoch,
+            protector: self.protector.name().to_string(),
+            wrapped_data_key: self.protector.wrap_key(&data_key).await?,
+            secret: seal(&data_key, &signing_key.to_bytes())?,
+        })
+    }
+}
+
+/// Replace the keystore file, writing a temporary file first so a crash
+/// never leaves a partial keystore behind
+fn write_file(path: &Path, file: &KeystoreFile) -> GarpResult<()> {
+    let bytes = serde_json::to_vec_pretty(file)
+        .map_err(|e| GarpError::StorageError(format!("Failed to encode keystore: {}", e)))?;
+    let tmp = path.with_extension("tmp");
+    let write = || -> std::io::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&tmp, &bytes)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp, path)
+    };
+    write().map_err(|e| GarpError::StorageError(format!("Failed to write keystore {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protector(passphrase: &str) -> Arc<dyn KeyProtector> {
+        Arc::new(PassphraseProtector::new(passphrase).unwrap().with_iterations(1_000))
+    }
+
+    fn keystore_path() -> PathBuf {
+        std::env::temp_dir().join(format!("garp-keystore-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_keystore_is_encrypted_and_reopens() {
+        let path = keystore_path();
+        let secret = [7u8; 32];
+        let keystore = Keystore::open_or_create(&path, "node-1", protector("correct horse"), Some(secret)).await.unwrap();
+        assert_eq!(keystore.signing_key().to_bytes(), secret);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains(&serde_json::to_string(&secret.to_vec()).unwrap()));
+
+        assert!(Keystore::open_or_create(&path, "node-1", protector("wrong"), None).await.is_err());
+        assert!(Keystore::open_or_create(&path, "node-2", protector("correct horse"), None).await.is_err());
+        let reopened = Keystore::open_or_create(&path, "node-1", protector("correct horse"), None).await.unwrap();
+        assert_eq!(reopened.signing_key().to_bytes(), secret);
+        assert_eq!(reopened.history(), keystore.history());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verification_during_and_after_overlap() {
+        let path = keystore_path();
+        let mut keystore = Keystore::open_or_create(&path, "node-1", protector("pw"), None).await.unwrap();
+        let old_key = keystore.signing_key().clone();
+        let mut peer_view = KeyHistory::new(old_key.verifying_key().to_bytes().to_vec());
+
+        let rotated_at = Utc::now();
+        let rotation = keystore.rotate(rotated_at, 3600).await.unwrap();
+        let new_key = keystore.signing_key().clone();
+        assert_eq!(keystore.epoch(), 1);
+
+        // A rotation signed by anything but the old key is refused
+        let mut forged = rotation.clone();
+        forged.signature = new_key.sign(&forged.signing_message()).to_bytes().to_vec();
+        assert!(peer_view.clone().apply_rotation(&forged).is_err());
+        peer_view.apply_rotation(&rotation).unwrap();
+        peer_view.apply_rotation(&rotation).unwrap();
+        assert_eq!(&peer_view, keystore.history());
+
+        let message = b"vote";
+        let old_sig = old_key.sign(message).to_bytes();
+        let new_sig = new_key.sign(message).to_bytes();
+        let minutes = |m: i64| rotated_at + chrono::Duration::minutes(m);
+
+        // Before the rotation only the old key signs
+        assert_eq!(peer_view.verify(message, &old_sig, minutes(-1)).map(|e| e.epoch), Some(0));
+        assert!(peer_view.verify(message, &new_sig, minutes(-1)).is_none());
+        // Inside the overlap window both keys are accepted
+        assert_eq!(peer_view.verify(message, &old_sig, minutes(30)).map(|e| e.epoch), Some(0));
+        assert_eq!(peer_view.verify(message, &new_sig, minutes(30)).map(|e| e.epoch), Some(1));
+        assert_eq!(peer_view.keys_at(minutes(30)).count(), 2);
+        // Afterwards the old key is retired
+        assert!(peer_view.verify(message, &old_sig, minutes(61)).is_none());
+        assert_eq!(peer_view.verify(message, &new_sig, minutes(61)).map(|e| e.epoch), Some(1));
+
+        // No second rotation while the first overlap is open
+        assert!(keystore.rotate(minutes(30), 3600).await.is_err());
+        assert!(keystore.retire_expired(minutes(59)).await.unwrap().is_empty());
+        assert_eq!(keystore.retire_expired(minutes(60)).await.unwrap(), vec![0]);
+
+        let reopened = Keystore::open_or_create(&path, "node-1", protector("pw"), None).await.unwrap();
+        assert_eq!(reopened.signing_key().to_bytes(), new_key.to_bytes());
+        assert_eq!(reopened.signing_keys.len(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
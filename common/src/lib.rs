@@ -1,17 +1,24 @@
 pub mod types;
+pub mod canonical;
 pub mod crypto;
+pub mod keystore;
 pub mod error;
 pub mod network;
 pub mod consensus;
 pub mod timing;
 pub mod validator;
 pub mod consensus_manager;
+pub mod settlement;
+pub mod reputation;
 
 pub use types::*;
 pub use crypto::*;
+pub use keystore::*;
 pub use error::*;
 pub use network::*;
 pub use consensus::*;
 pub use timing::*;
 pub use validator::*;
-pub use consensus_manager::*;
\ No newline at end of file
+pub use consensus_manager::*;
+pub use settlement::*;
+pub use reputation::*;
\ No newline at end of file
@@ -0,0 +1,376 @@
+//! Reputation scores accumulated from observed behaviour
+//!
+//! Each signal moves a subject's score (a participant, validator or domain)
+//! by its configured weight, within `0..=max_score`. Between signals the
+//! score decays exponentially back towards `initial_score`, so old faults
+//! and old good behaviour both fade. Subjects whose score falls below the
+//! suspension threshold are not eligible for leader selection and are
+//! suspended by the component tracking them.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::error::GarpResult;
+
+/// Observed behaviour that moves a reputation score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationSignal {
+    /// Did not vote in a consensus round before it timed out
+    MissedVote,
+
+    /// Did not answer a mediation round before it timed out
+    MediationTimeout,
+
+    /// Sent a message whose signature did not verify
+    InvalidSignature,
+
+    /// Did not answer a heartbeat
+    HeartbeatFailure,
+
+    /// Took part in a transaction that was finalized
+    TransactionFinalized,
+}
+
+/// Score change per signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReputationWeights {
+    pub missed_vote: f64,
+    pub mediation_timeout: f64,
+    pub invalid_signature: f64,
+    pub heartbeat_failure: f64,
+    pub transaction_finalized: f64,
+}
+
+impl ReputationWeights {
+    /// Score change for `signal`
+    pub fn weight(&self, signal: ReputationSignal) -> f64 {
+        match signal {
+            ReputationSignal::MissedVote => self.missed_vote,
+            ReputationSignal::MediationTimeout => self.mediation_timeout,
+            ReputationSignal::InvalidSignature => self.invalid_signature,
+            ReputationSignal::HeartbeatFailure => self.heartbeat_failure,
+            ReputationSignal::TransactionFinalized => self.transaction_finalized,
+        }
+    }
+}
+
+impl Default for ReputationWeights {
+    fn default() -> Self {
+        Self {
+            missed_vote: -2.0,
+            mediation_timeout: -3.0,
+            invalid_signature: -10.0,
+            heartbeat_failure: -1.0,
+            transaction_finalized: 0.5,
+        }
+    }
+}
+
+/// Reputation scoring configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReputationConfig {
+    /// Score of a subject without history, and the score decay returns to
+    pub initial_score: f64,
+
+    /// Highest score a subject can reach
+    pub max_score: f64,
+
+    /// Score change per signal
+    pub weights: ReputationWeights,
+
+    /// Time for the distance to `initial_score` to halve; 0 disables decay
+    pub decay_half_life_secs: u64,
+
+    /// Subjects scoring below this are suspended and cannot lead; `None`
+    /// disables suspension
+    pub suspension_threshold: Option<f64>,
+
+    /// How long a subject falling below the threshold is suspended for
+    pub suspension_duration_secs: u64,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            initial_score: 50.0,
+            max_score: 100.0,
+            weights: ReputationWeights::default(),
+            decay_half_life_secs: 86_400,
+            suspension_threshold: Some(20.0),
+            suspension_duration_secs: 3_600,
+        }
+    }
+}
+
+impl ReputationConfig {
+    /// Whether `score` is below the suspension threshold
+    pub fn is_below_threshold(&self, score: f64) -> bool {
+        self.suspension_threshold.map_or(false, |threshold| score < threshold)
+    }
+}
+
+/// Reputation of one subject
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationScore {
+    /// Participant, validator or domain ID
+    pub subject: String,
+
+    /// Score as of `updated_at`
+    pub score: f64,
+
+    /// Last time a signal was recorded
+    pub updated_at: DateTime<Utc>,
+
+    /// Signals recorded, by kind
+    pub signals: BTreeMap<ReputationSignal, u64>,
+}
+
+impl ReputationScore {
+    fn new(subject: &str, config: &ReputationConfig, now: DateTime<Utc>) -> Self {
+        Self {
+            subject: subject.to_string(),
+            score: config.initial_score,
+            updated_at: now,
+            signals: BTreeMap::new(),
+        }
+    }
+
+    /// The score decayed from `updated_at` to `now`
+    pub fn decayed(&self, config: &ReputationConfig, now: DateTime<Utc>) -> f64 {
+        if config.decay_half_life_secs == 0 {
+            return self.score;
+        }
+        let elapsed = (now - self.updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+        let remaining = 0.5f64.powf(elapsed / config.decay_half_life_secs as f64);
+        config.initial_score + (self.score - config.initial_score) * remaining
+    }
+}
+
+/// Result of recording a signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReputationUpdate {
+    /// Score after the signal
+    pub score: ReputationScore,
+
+    /// Whether this signal took the score below the suspension threshold
+    pub fell_below_threshold: bool,
+}
+
+/// Persists reputation scores
+#[async_trait]
+pub trait ReputationStore: Send + Sync {
+    /// Every stored score
+    async fn load_all(&self) -> GarpResult<Vec<ReputationScore>>;
+
+    /// Store a subject's score, replacing the previous one
+    async fn save(&self, score: &ReputationScore) -> GarpResult<()>;
+}
+
+/// Store keeping scores in memory only
+#[derive(Default)]
+pub struct InMemoryReputationStore {
+    scores: RwLock<HashMap<String, ReputationScore>>,
+}
+
+#[async_trait]
+impl ReputationStore for InMemoryReputationStore {
+    async fn load_all(&self) -> GarpResult<Vec<ReputationScore>> {
+        Ok(self.scores.read().await.values().cloned().collect())
+    }
+
+    async fn save(&self, score: &ReputationScore) -> GarpResult<()> {
+        self.scores.write().await.insert(score.subject.clone(), score.clone());
+        Ok(())
+    }
+}
+
+/// Accumulates reputation scores from signals
+pub struct ReputationTracker {
+    config: ReputationConfig,
+    store: Arc<dyn ReputationStore>,
+    scores: RwLock<HashMap<String, ReputationScore>>,
+}
+
+impl ReputationTracker {
+    /// Tracker keeping scores in memory only
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            store: Arc::new(InMemoryReputationStore::default()),
+            scores: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Tracker persisting scores to `store`, starting from the scores
+    /// already in it
+    pub async fn load(config: ReputationConfig, store: Arc<dyn ReputationStore>) -> GarpResult<Self> {
+        let scores = store.load_all().await?
+            .into_iter()
+            .map(|score| (score.subject.clone(), score))
+            .collect();
+        Ok(Self { config, store, scores: RwLock::new(scores) })
+    }
+
+    pub fn config(&self) -> &ReputationConfig {
+        &self.config
+    }
+
+    /// Record a signal for `subject` now
+    pub async fn record(&self, subject: &str, signal: ReputationSignal) -> GarpResult<ReputationUpdate> {
+        self.record_at(subject, signal, Utc::now()).await
+    }
+
+    /// Record a signal for `subject` at `now`, decaying its score up to
+    /// `now` first
+    pub async fn record_at(&self, subject: &str, signal: ReputationSignal, now: DateTime<Utc>) -> GarpResult<ReputationUpdate> {
+        let mut scores = self.scores.write().await;
+        let entry = scores.entry(subject.to_string())
+            .or_insert_with(|| ReputationScore::new(subject, &self.config, now));
+        let before = entry.decayed(&self.config, now);
+        let after = (before + self.config.weights.weight(signal)).clamp(0.0, self.config.max_score);
+
+        let mut score = entry.clone();
+        score.score = after;
+        score.updated_at = now.max(entry.updated_at);
+        *score.signals.entry(signal).or_insert(0) += 1;
+        self.store.save(&score).await?;
+        *entry = score.clone();
+
+        let fell_below_threshold = !self.config.is_below_threshold(before) && self.config.is_below_threshold(after);
+        Ok(ReputationUpdate { score, fell_below_threshold })
+    }
+
+    /// Current reputation of `subject`
+    pub async fn get(&self, subject: &str) -> ReputationScore {
+        self.get_at(subject, Utc::now()).await
+    }
+
+    /// Reputation of `subject` at `now`; subjects without history have the
+    /// initial score
+    pub async fn get_at(&self, subject: &str, now: DateTime<Utc>) -> ReputationScore {
+        match self.scores.read().await.get(subject) {
+            Some(score) => ReputationScore {
+                score: score.decayed(&self.config, now),
+                ..score.clone()
+            },
+            None => ReputationScore::new(subject, &self.config, now),
+        }
+    }
+
+    /// Whether `subject` scores at or above the suspension threshold, and so
+    /// may be selected as leader
+    pub async fn is_eligible(&self, subject: &str) -> bool {
+        !self.config.is_below_threshold(self.get(subject).await.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn config() -> ReputationConfig {
+        ReputationConfig {
+            decay_half_life_secs: 3_600,
+            suspension_threshold: Some(40.0),
+            ..ReputationConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_each_signal_moves_the_score_by_its_weight() {
+        let tracker = ReputationTracker::new(config());
+        let now = Utc::now();
+        let signals = [
+            (ReputationSignal::MissedVote, 48.0),
+            (ReputationSignal::MediationTimeout, 47.0),
+            (ReputationSignal::InvalidSignature, 40.0),
+            (ReputationSignal::HeartbeatFailure, 49.0),
+            (ReputationSignal::TransactionFinalized, 50.5),
+        ];
+        for (signal, expected) in signals {
+            let subject = format!("{:?}", signal);
+            let update = tracker.record_at(&subject, signal, now).await.unwrap();
+            assert_eq!(update.score.score, expected, "{:?}", signal);
+            assert_eq!(update.score.signals[&signal], 1);
+            assert_eq!(tracker.get_at(&subject, now).await.score, expected);
+        }
+
+        // Unknown subjects start at the initial score
+        assert_eq!(tracker.get_at("unknown", now).await.score, 50.0);
+    }
+
+    #[tokio::test]
+    async fn test_scores_are_bounded_and_cross_the_threshold_once() {
+        let tracker = ReputationTracker::new(ReputationConfig { decay_half_life_secs: 0, ..config() });
+        let now = Utc::now();
+
+        let update = tracker.record_at("p", ReputationSignal::InvalidSignature, now).await.unwrap();
+        assert_eq!(update.score.score, 40.0);
+        assert!(!update.fell_below_threshold);
+        assert!(tracker.is_eligible("p").await);
+
+        let update = tracker.record_at("p", ReputationSignal::HeartbeatFailure, now).await.unwrap();
+        assert!(update.fell_below_threshold);
+        assert!(!tracker.is_eligible("p").await);
+        let update = tracker.record_at("p", ReputationSignal::HeartbeatFailure, now).await.unwrap();
+        assert!(!update.fell_below_threshold);
+
+        for _ in 0..10 {
+            tracker.record_at("p", ReputationSignal::InvalidSignature, now).await.unwrap();
+        }
+        assert_eq!(tracker.get_at("p", now).await.score, 0.0);
+        for _ in 0..300 {
+            tracker.record_at("q", ReputationSignal::TransactionFinalized, now).await.unwrap();
+        }
+        assert_eq!(tracker.get_at("q", now).await.score, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_scores_decay_towards_the_initial_score() {
+        let tracker = ReputationTracker::new(config());
+        let now = Utc::now();
+        tracker.record_at("p", ReputationSignal::InvalidSignature, now).await.unwrap();
+        tracker.record_at("p", ReputationSignal::InvalidSignature, now).await.unwrap();
+        assert_eq!(tracker.get_at("p", now).await.score, 30.0);
+
+        // Half the distance to 50 remains after each half-life
+        let score = tracker.get_at("p", now + Duration::hours(1)).await.score;
+        assert!((score - 40.0).abs() < 1e-9, "{}", score);
+        let score = tracker.get_at("p", now + Duration::hours(2)).await.score;
+        assert!((score - 45.0).abs() < 1e-9, "{}", score);
+
+        // The next signal applies to the decayed score
+        let update = tracker.record_at("p", ReputationSignal::MissedVote, now + Duration::hours(1)).await.unwrap();
+        assert!((update.score.score - 38.0).abs() < 1e-9);
+
+        // Good behaviour decays too
+        let tracker = ReputationTracker::new(config());
+        for _ in 0..20 {
+            tracker.record_at("q", ReputationSignal::TransactionFinalized, now).await.unwrap();
+        }
+        let score = tracker.get_at("q", now + Duration::hours(1)).await.score;
+        assert!((score - 55.0).abs() < 1e-9, "{}", score);
+    }
+
+    #[tokio::test]
+    async fn test_scores_survive_a_restart() {
+        let store = Arc::new(InMemoryReputationStore::default());
+        let tracker = ReputationTracker::load(config(), store.clone()).await.unwrap();
+        let now = Utc::now();
+        tracker.record_at("p", ReputationSignal::MissedVote, now).await.unwrap();
+
+        let reloaded = ReputationTracker::load(config(), store).await.unwrap();
+        let score = reloaded.get_at("p", now).await;
+        assert_eq!(score.score, 48.0);
+        assert_eq!(score.signals[&ReputationSignal::MissedVote], 1);
+    }
+}
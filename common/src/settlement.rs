@@ -0,0 +1,190 @@
+//! Verifiable settlement proofs
+//!
+//! A proof binds a settlement to the domains that confirmed it and to the
+//! finality certificate of the block containing it, signed by the global
+//! synchronizer node. Third parties check it with [`verify_settlement_proof`]
+//! using only the validator public keys.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{PublicKey, Verifier};
+use ring::digest::{Context, SHA256};
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::SigningKeyPair;
+use crate::error::CryptoError;
+use crate::types::{Signature, TransactionId};
+
+/// Prefix of every signed proof digest
+const PROOF_DOMAIN_TAG: &[u8] = b"garp-settlement-proof-v1";
+
+/// Finality certificate of the block containing a settlement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalityReference {
+    /// Block height
+    pub height: u64,
+
+    /// Block hash
+    pub block_hash: String,
+
+    /// Hash of the validator set that signed the certificate
+    pub validator_set_hash: String,
+}
+
+/// Evidence that a settlement completed across its domains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementProof {
+    /// Settlement ID
+    pub settlement_id: String,
+
+    /// Settled transaction
+    pub transaction_id: TransactionId,
+
+    /// Confirmation hash per domain, see [`domain_confirmation_hash`]
+    pub domain_confirmations: BTreeMap<String, Vec<u8>>,
+
+    /// Finality certificate reference
+    pub finality: FinalityReference,
+
+    /// Synchronizer node signature over [`SettlementProof::signing_digest`]
+    pub signature: Signature,
+
+    /// Creation time; not covered by the signature
+    pub created_at: DateTime<Utc>,
+}
+
+/// Length-prefix `bytes` so adjacent fields cannot run together
+fn put(context: &mut Context, bytes: &[u8]) {
+    context.update(&(bytes.len() as u64).to_be_bytes());
+    context.update(bytes);
+}
+
+/// Hash a domain commits to when it confirms its part of a settlement
+pub fn domain_confirmation_hash(settlement_id: &str, domain_id: &str, settlement_data: &[u8]) -> Vec<u8> {
+    let mut context = Context::new(&SHA256);
+    put(&mut context, settlement_id.as_bytes());
+    put(&mut context, domain_id.as_bytes());
+    put(&mut context, settlement_data);
+    context.finish().as_ref().to_vec()
+}
+
+impl SettlementProof {
+    /// Build and sign a proof
+    pub fn new(
+        settlement_id: String,
+        transaction_id: TransactionId,
+        domain_confirmations: BTreeMap<String, Vec<u8>>,
+        finality: FinalityReference,
+        signer: &SigningKeyPair,
+    ) -> Self {
+        let digest = Self::digest(&settlement_id, &transaction_id, &domain_confirmations, &finality);
+        Self {
+            settlement_id,
+            transaction_id,
+            domain_confirmations,
+            finality,
+            signature: signer.sign(&digest),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// SHA-256 digest of the signed fields
+    pub fn signing_digest(&self) -> Vec<u8> {
+        Self::digest(&self.settlement_id, &self.transaction_id, &self.domain_confirmations, &self.finality)
+    }
+
+    fn digest(
+        settlement_id: &str,
+        transaction_id: &TransactionId,
+        domain_confirmations: &BTreeMap<String, Vec<u8>>,
+        finality: &FinalityReference,
+    ) -> Vec<u8> {
+        let mut context = Context::new(&SHA256);
+        context.update(PROOF_DOMAIN_TAG);
+        put(&mut context, settlement_id.as_bytes());
+        put(&mut context, transaction_id.0.as_bytes());
+        context.update(&(domain_confirmations.len() as u64).to_be_bytes());
+        for (domain_id, hash) in domain_confirmations {
+            put(&mut context, domain_id.as_bytes());
+            put(&mut context, hash);
+        }
+        context.update(&finality.height.to_be_bytes());
+        put(&mut context, finality.block_hash.as_bytes());
+        put(&mut context, finality.validator_set_hash.as_bytes());
+        context.finish().as_ref().to_vec()
+    }
+}
+
+/// Check that `proof` was signed by one of `validator_keys` (raw Ed25519
+/// public keys) and has not been altered since.
+pub fn verify_settlement_proof(proof: &SettlementProof, validator_keys: &[Vec<u8>]) -> Result<(), CryptoError> {
+    if proof.signature.algorithm != "Ed25519" {
+        return Err(CryptoError::UnsupportedAlgorithm(proof.signature.algorithm.clone()));
+    }
+    if proof.domain_confirmations.is_empty() {
+        return Err(CryptoError::InvalidSignature);
+    }
+    if !validator_keys.iter().any(|key| key == &proof.signature.public_key) {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+
+    let public_key = PublicKey::from_bytes(&proof.signature.public_key).map_err(|_| CryptoError::InvalidPublicKey)?;
+    let signature = ed25519_dalek::Signature::from_bytes(&proof.signature.signature)
+        .map_err(|_| CryptoError::InvalidSignature)?;
+    public_key
+        .verify(&proof.signing_digest(), &signature)
+        .map_err(|_| CryptoError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn signed_proof(signer: &SigningKeyPair) -> SettlementProof {
+        let confirmations = ["a", "b"]
+            .iter()
+            .map(|d| (d.to_string(), domain_confirmation_hash("s1", d, b"data")))
+            .collect();
+        SettlementProof::new(
+            "s1".to_string(),
+            TransactionId(Uuid::nil()),
+            confirmations,
+            FinalityReference { height: 7, block_hash: "ab".to_string(), validator_set_hash: "cd".to_string() },
+            signer,
+        )
+    }
+
+    #[test]
+    fn test_proof_verifies_against_validator_keys() {
+        let signer = SigningKeyPair::generate();
+        let proof = signed_proof(&signer);
+        assert!(verify_settlement_proof(&proof, &[signer.public_key_bytes()]).is_ok());
+
+        let other = SigningKeyPair::generate();
+        assert!(matches!(
+            verify_settlement_proof(&proof, &[other.public_key_bytes()]),
+            Err(CryptoError::InvalidPublicKey)
+        ));
+    }
+
+    #[test]
+    fn test_tampered_proof_is_rejected() {
+        let signer = SigningKeyPair::generate();
+        let keys = [signer.public_key_bytes()];
+
+        let mut proof = signed_proof(&signer);
+        proof.finality.height = 8;
+        assert!(matches!(verify_settlement_proof(&proof, &keys), Err(CryptoError::InvalidSignature)));
+
+        let mut proof = signed_proof(&signer);
+        proof.domain_confirmations.remove("b");
+        assert!(verify_settlement_proof(&proof, &keys).is_err());
+
+        // Timestamps are informational only
+        let mut proof = signed_proof(&signer);
+        proof.created_at = Utc::now() + chrono::Duration::days(1);
+        assert!(verify_settlement_proof(&proof, &keys).is_ok());
+    }
+}
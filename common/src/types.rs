@@ -59,6 +59,28 @@ pub struct Transaction {
     pub encrypted_payload: Option<EncryptedData>,
 }
 
+impl Transaction {
+    /// Bytes every signatory signs: the canonical encoding of the
+    /// transaction without its signatures, see [`crate::canonical`]
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, crate::error::SerializationError> {
+        #[derive(Serialize)]
+        struct Unsigned<'a> {
+            id: &'a TransactionId,
+            submitter: &'a ParticipantId,
+            command: &'a TransactionCommand,
+            created_at: &'a DateTime<Utc>,
+            encrypted_payload: &'a Option<EncryptedData>,
+        }
+        crate::canonical::to_canonical_json(&Unsigned {
+            id: &self.id,
+            submitter: &self.submitter,
+            command: &self.command,
+            created_at: &self.created_at,
+            encrypted_payload: &self.encrypted_payload,
+        })
+    }
+}
+
 /// Commands that can be executed in a transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionCommand {
@@ -0,0 +1,107 @@
+//! 1 000 concurrent cross-domain submissions against the validation worker
+//! pool, at growing `cross_domain.validation_parallelism`.
+//!
+//! Run with `cargo bench -p global-synchronizer --bench cross_domain_validation`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use garp_common::types::TransactionId;
+use global_synchronizer::config::GlobalSyncConfig;
+use global_synchronizer::consensus::ConsensusEngine;
+use global_synchronizer::cross_domain::{
+    CrossDomainCoordinator, CrossDomainTransaction, CrossDomainTransactionType, TransactionStatus,
+};
+use global_synchronizer::discovery::{DomainAnnouncement, DomainDiscovery};
+use global_synchronizer::network::NetworkManager;
+use global_synchronizer::storage::GlobalStorage;
+
+const SUBMISSIONS: usize = 1_000;
+const PARALLELISM: [usize; 4] = [1, 4, 16, 64];
+
+async fn coordinator(parallelism: usize) -> Arc<CrossDomainCoordinator> {
+    let mut config = GlobalSyncConfig::default();
+    config.cross_domain.validation_parallelism = parallelism;
+    let config = Arc::new(config);
+    let coordinator = CrossDomainCoordinator::new(
+        config.clone(),
+        Arc::new(GlobalStorage::new(config.clone()).await.unwrap()),
+        Arc::new(NetworkManager::new(config.clone()).await.unwrap()),
+        Arc::new(DomainDiscovery::new(config.clone()).await.unwrap()),
+        Arc::new(ConsensusEngine::new(config).await.unwrap()),
+    )
+    .await
+    .unwrap();
+    let target = "target".to_string();
+    coordinator
+        .register_domain(DomainAnnouncement {
+            domain_id: target.clone(),
+            endpoint: "target.example:9000".to_string(),
+            public_key: Vec::new(),
+            capabilities: Vec::new(),
+            ttl_secs: None,
+        })
+        .await
+        .unwrap();
+    coordinator.reload_domain(&target).await.unwrap();
+    Arc::new(coordinator)
+}
+
+/// Time-locked, so admission queues it without contacting the domain
+fn transaction() -> CrossDomainTransaction {
+    let now = chrono::Utc::now();
+    CrossDomainTransaction {
+        transaction_id: TransactionId::new(),
+        source_domain: "source".to_string(),
+        target_domains: vec!["target".to_string()],
+        transaction_type: CrossDomainTransactionType::AssetTransfer {
+            asset_id: "asset".to_string(),
+            amount: 1_000,
+            from_address: "from".to_string(),
+            to_address: "to".to_string(),
+        },
+        data: vec![1, 2, 3],
+        dependencies: Vec::new(),
+        required_confirmations: 1,
+        confirmations: HashMap::new(),
+        status: TransactionStatus::Pending,
+        created_at: now,
+        updated_at: now,
+        timeout_at: now + chrono::Duration::seconds(300),
+        not_before: Some(now + chrono::Duration::seconds(3600)),
+        max_fee: 0,
+        delegated_by: None,
+        metadata: HashMap::new(),
+    }
+}
+
+async fn submit_concurrently(coordinator: &Arc<CrossDomainCoordinator>) {
+    let handles: Vec<_> = (0..SUBMISSIONS)
+        .map(|_| {
+            let coordinator = coordinator.clone();
+            tokio::spawn(async move { coordinator.submit_transaction(transaction()).await.unwrap() })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
+
+fn bench_concurrent_validation(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+
+    let mut group = c.benchmark_group("concurrent_validation");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(SUBMISSIONS as u64));
+    for parallelism in PARALLELISM {
+        let coordinator = rt.block_on(coordinator(parallelism));
+        group.bench_with_input(BenchmarkId::from_parameter(parallelism), &coordinator, |b, coordinator| {
+            b.iter(|| rt.block_on(submit_concurrently(coordinator)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_validation);
+criterion_main!(benches);
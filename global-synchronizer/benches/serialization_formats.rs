@@ -0,0 +1,70 @@
+//! JSON against CBOR for `CrossDomainTransaction` payloads of growing size.
+//!
+//! Run with `cargo bench -p global-synchronizer --bench serialization_formats`.
+
+use std::collections::HashMap;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use garp_common::types::TransactionId;
+use global_synchronizer::config::SerializationFormat;
+use global_synchronizer::cross_domain::{CrossDomainTransaction, CrossDomainTransactionType, TransactionStatus};
+
+const PAYLOAD_SIZES: [usize; 3] = [64, 4096, 65536];
+
+fn transaction(payload_size: usize) -> CrossDomainTransaction {
+    let now = chrono::Utc::now();
+    CrossDomainTransaction {
+        transaction_id: TransactionId::new(),
+        source_domain: "domain-a".to_string(),
+        target_domains: vec!["domain-b".to_string(), "domain-c".to_string()],
+        transaction_type: CrossDomainTransactionType::AssetTransfer {
+            asset_id: "asset".to_string(),
+            amount: 1_000,
+            from_address: "from".to_string(),
+            to_address: "to".to_string(),
+        },
+        data: (0..payload_size).map(|i| i as u8).collect(),
+        dependencies: Vec::new(),
+        required_confirmations: 2,
+        confirmations: HashMap::new(),
+        status: TransactionStatus::Pending,
+        created_at: now,
+        updated_at: now,
+        timeout_at: now + chrono::Duration::seconds(300),
+        not_before: None,
+        max_fee: 0,
+        delegated_by: None,
+        metadata: HashMap::new(),
+    }
+}
+
+fn bench_formats(c: &mut Criterion) {
+    let formats = [("json", SerializationFormat::Json), ("cbor", SerializationFormat::Cbor)];
+
+    let mut encode = c.benchmark_group("encode_cross_domain_transaction");
+    for size in PAYLOAD_SIZES {
+        let tx = transaction(size);
+        encode.throughput(Throughput::Bytes(size as u64));
+        for (name, format) in formats {
+            encode.bench_with_input(BenchmarkId::new(name, size), &tx, |b, tx| b.iter(|| format.encode(tx).unwrap()));
+        }
+    }
+    encode.finish();
+
+    let mut decode = c.benchmark_group("decode_cross_domain_transaction");
+    for size in PAYLOAD_SIZES {
+        let tx = transaction(size);
+        decode.throughput(Throughput::Bytes(size as u64));
+        for (name, format) in formats {
+            let bytes = format.encode(&tx).unwrap();
+            println!("{} encoding of a {} byte payload: {} bytes", name, size, bytes.len());
+            decode.bench_with_input(BenchmarkId::new(name, size), &bytes, |b, bytes| {
+                b.iter(|| format.decode::<CrossDomainTransaction>(bytes).unwrap())
+            });
+        }
+    }
+    decode.finish();
+}
+
+criterion_group!(benches, bench_formats);
+criterion_main!(benches);
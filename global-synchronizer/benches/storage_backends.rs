@@ -0,0 +1,38 @@
+//! Sequential write throughput of the SQLite backend against the in-memory backend.
+//!
+//! Run with `cargo bench -p global-synchronizer --bench storage_backends`.
+
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use global_synchronizer::config::GlobalSyncConfig;
+use global_synchronizer::storage::{MemoryStorageBackend, SqliteStorageBackend, StorageBackend};
+
+const WRITES: u64 = 10_000;
+
+async fn sequential_writes(backend: &dyn StorageBackend) {
+    for i in 0..WRITES {
+        backend.set(&format!("key_{}", i), i.to_le_bytes().to_vec()).await.unwrap();
+    }
+}
+
+fn bench_sequential_writes(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut config = GlobalSyncConfig::default();
+    config.database.url = format!("sqlite://{}", dir.path().join("bench.db").display());
+    config.database.min_connections = 1;
+    let sqlite = rt.block_on(SqliteStorageBackend::new(Arc::new(config))).unwrap();
+    let memory = MemoryStorageBackend::new();
+
+    let mut group = c.benchmark_group("sequential_writes");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(WRITES));
+    group.bench_function("memory", |b| b.iter(|| rt.block_on(sequential_writes(&memory))));
+    group.bench_function("sqlite", |b| b.iter(|| rt.block_on(sequential_writes(&sqlite))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_sequential_writes);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+// Browser client for the global synchronizer's gRPC-Web endpoint.
+//
+// Generate stubs from global-synchronizer/proto/garp.proto with protobuf-ts:
+//
+//   npm install @protobuf-ts/runtime @protobuf-ts/runtime-rpc @protobuf-ts/grpcweb-transport
+//   npx protoc --ts_out ./generated --proto_path ../proto ../proto/garp.proto
+//
+// then bundle this file for the browser (e.g. with vite or esbuild).
+
+import { GrpcWebFetchTransport } from "@protobuf-ts/grpcweb-transport";
+import { GlobalSynchronizerClient } from "./generated/garp.client";
+
+const SYNC_URL = "http://localhost:8080/grpc-web";
+const API_TOKEN = "<SYNC_API_TOKEN>";
+
+const transport = new GrpcWebFetchTransport({
+  baseUrl: SYNC_URL,
+  meta: { authorization: `Bearer ${API_TOKEN}` },
+});
+const client = new GlobalSynchronizerClient(transport);
+
+async function main(): Promise<void> {
+  const { response: status } = await client.getNodeStatus({});
+  console.log(`node ${status.status} at height ${status.blockHeight}`);
+
+  const payload = new TextEncoder().encode(
+    JSON.stringify({ source_domain: "domain-a", target_domains: ["domain-b"] }),
+  );
+  const { response: submitted } = await client.submitTransaction({ transactionData: payload });
+  console.log(`submitted ${submitted.transactionId} (${submitted.status})`);
+
+  // The proto has no event stream yet, so poll for the outcome
+  for (let attempt = 0; attempt < 10; attempt++) {
+    const { response } = await client.getTransactionStatus({ transactionId: submitted.transactionId });
+    console.log(`status: ${response.status}`);
+    if (response.status !== "pending" && response.status !== "submitted") {
+      break;
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  }
+}
+
+main().catch((err) => console.error("gRPC-Web request failed", err));
@@ -1,3 +1,5 @@
+use garp_common::{ErrorCode, GarpError};
+
 pub fn create_router(sync: Arc<GlobalSynchronizer>) -> Router {
     Router::new()
         .route("/health", get(health))
@@ -10,6 +12,7 @@ pub fn create_router(sync: Arc<GlobalSynchronizer>) -> Router {
         .route("/api/v1/blocks/:height/details", get(block_details_handler(sync.clone())))
         .route("/api/v1/blocks/:height/transactions", get(block_transactions_handler(sync.clone())))
         .route("/api/v1/mempool", get(mempool_handler(sync.clone())))
+        .route("/api/v1/mempool/stats", get(mempool_stats_handler(sync.clone())))
         .route("/api/v1/transactions/:id/status", get(tx_status_handler(sync.clone())))
         .route("/api/v1/transactions/:id/details", get(tx_details_handler(sync.clone())))
         .route("/api/v1/transactions", post(submit_transaction_handler(sync.clone())))
@@ -17,6 +20,9 @@ pub fn create_router(sync: Arc<GlobalSynchronizer>) -> Router {
         .route("/api/v1/validators", get(validators_list_handler(sync.clone())).post(validators_add_handler(sync.clone())))
         .route("/api/v1/validators/:id", axum::routing::delete(validators_remove_handler(sync.clone())))
         .route("/api/v1/validators/:id/status", axum::routing::patch(validators_update_status_handler(sync.clone())))
+        .route("/api/v1/validators/:id/slashing-history", get(slashing_history_handler(sync.clone())))
+        .route("/api/v1/validators/:id/fee-earnings", get(fee_earnings_handler(sync.clone())))
+        .route("/api/v1/reputation/:id", get(reputation_handler(sync.clone())))
         // Bridge endpoints
         .route("/api/v1/bridge/transfer", post(initiate_bridge_transfer_handler(sync.clone())))
         .route("/api/v1/bridge/transfer/:id", get(get_bridge_transfer_handler(sync.clone())))
@@ -29,6 +35,30 @@ pub fn create_router(sync: Arc<GlobalSynchronizer>) -> Router {
         .route("/api/v1/wallets", post(create_wallet_handler(sync.clone())))
         .route("/api/v1/wallets/:id", get(get_wallet_handler(sync.clone())))
         .route("/api/v1/wallets", get(list_wallets_handler(sync.clone())))
+        // Domain endpoints
+        .route("/api/v1/domains/:id/metrics", get(domain_metrics_handler(sync.clone())))
+        .route("/api/v1/domains/:id/state", get(domain_state_handler(sync.clone())))
+        // Timelock endpoints
+        .route("/api/v1/timelock/queue", get(timelock_queue_handler(sync.clone())))
+        // Settlement endpoints
+        .route("/api/v1/settlements/:id/proof", get(settlement_zk_proof_handler(sync.clone())))
+        .route("/api/v1/transactions/:id/receipt", get(transaction_receipt_handler(sync.clone())))
+        // Compliance endpoints
+        .route("/api/v1/compliance/export", post(compliance_export_handler(sync.clone())))
+        // Ledger checkpoint endpoints
+        .route("/api/v1/ledger/checkpoint", get(ledger_checkpoint_handler(sync.clone())))
+        .route("/api/v1/ledger/checkpoint/latest", get(latest_ledger_checkpoint_handler(sync.clone())))
+        // Governance endpoints
+        .route("/api/v1/governance/:id", get(governance_proposal_handler(sync.clone())))
+        // Delegation endpoints; issuing and revoking open governance proposals
+        .route("/api/v1/delegations", get(list_delegations_handler(sync.clone())).post(issue_delegation_handler(sync.clone())))
+        .route("/api/v1/delegations/:delegator/:delegate", get(get_delegation_handler(sync.clone())).delete(revoke_delegation_handler(sync.clone())))
+        // Admin endpoints
+        .route("/api/v1/admin/domains/:id/ban", post(ban_domain_handler(sync.clone())).delete(unban_domain_handler(sync.clone())))
+        .route("/api/v1/admin/domains/:id/suspend", post(suspend_domain_handler(sync.clone())).delete(resume_domain_handler(sync.clone())))
+        .route("/api/v1/admin/settlement/batching", get(settlement_batching_handler(sync.clone())).put(set_settlement_batching_handler(sync.clone())))
+        .route("/api/v1/admin/settlement/rollbacks", get(list_settlement_rollbacks_handler(sync.clone())))
+        .route("/api/v1/admin/settlement/rollbacks/:id", get(settlement_rollback_handler(sync.clone())))
         // Oracle endpoints
         .route("/api/v1/oracle/price/:symbol", get(get_asset_price_handler(sync.clone())))
         .route("/api/v1/oracle/prices", get(get_all_prices_handler(sync.clone())))
@@ -39,11 +69,1302 @@ pub fn create_router(sync: Arc<GlobalSynchronizer>) -> Router {
         .route("/api/v1/pool/swap", post(swap_tokens_handler(sync.clone())))
         .route("/api/v1/pool/info", get(get_pool_info_handler(sync.clone())))
         .route("/api/v1/pool/tvl", get(get_tvl_handler(sync.clone())))
+        // Token wrapping endpoints
+        .route("/api/v1/tokens/wrap", post(wrap_token_handler(sync.clone())))
+        .route("/api/v1/tokens/unwrap", post(unwrap_token_handler(sync.clone())))
+        .route("/api/v1/tokens/wrapped", get(list_wrapped_tokens_handler(sync.clone())))
+        // JSON-RPC
+        .route("/rpc", post(json_rpc_handler(sync.clone())))
+        // Block and transaction subscriptions
+        .merge(crate::ws::router(sync.ws_state()))
+        // gRPC-Web for browser clients (see proto/garp.proto)
+        .nest_service("/grpc-web", crate::grpc::grpc_web_service(sync.clone()))
+        // CBOR request/response bodies on top of the JSON handlers
+        .layer(middleware::from_fn(crate::wire::content_negotiation_middleware))
         // Security: simple bearer token auth and concurrency limits
         .layer(middleware::from_fn(auth_middleware))
+        // Node administration, authenticated by its own JWTs
+        .nest("/admin", admin_router(sync.clone()))
         .layer(tower::limit::ConcurrencyLimitLayer::new(64))
 }
 
+// Domain API handlers
+fn domain_metrics_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(domain_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_domain_performance_metrics(&domain_id).await {
+                Ok(metrics) => Json(ApiResponse { success: true, data: Some(metrics), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::DomainPerfMetrics> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct DomainStateQuery {
+    /// Read the state as of this version; latest when omitted
+    version: Option<u64>,
+}
+
+fn domain_state_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>, Query<DomainStateQuery>), axum::body::Body> {
+    axum::routing::get(move |Path(domain_id): Path<String>, Query(query): Query<DomainStateQuery>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_domain_state_version(&domain_id, query.version).await {
+                Ok(state) => Json(ApiResponse { success: true, data: Some(state), error: None }),
+                Err(e) => Json(ApiResponse::<crate::storage::DomainState> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+// Ledger checkpoint API handlers
+#[derive(Deserialize)]
+struct LedgerCheckpointQuery {
+    height: u64,
+}
+
+fn ledger_checkpoint_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Query<LedgerCheckpointQuery>,), axum::body::Body> {
+    axum::routing::get(move |Query(query): Query<LedgerCheckpointQuery>| {
+        let sync = sync.clone();
+        async move {
+            // Collects validator signatures if the height has no checkpoint yet
+            match sync.create_ledger_checkpoint(query.height).await {
+                Ok(checkpoint) => Json(ApiResponse { success: true, data: Some(checkpoint), error: None }),
+                Err(e) => Json(ApiResponse::<crate::consensus::checkpoint::LedgerCheckpoint> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn latest_ledger_checkpoint_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            // `data` is null until a checkpoint has been created
+            match sync.get_latest_ledger_checkpoint().await {
+                Ok(checkpoint) => Json(ApiResponse { success: true, data: checkpoint, error: None }),
+                Err(e) => Json(ApiResponse::<crate::consensus::checkpoint::LedgerCheckpoint> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+// Governance API handlers
+fn governance_proposal_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(proposal_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_governance_proposal(&proposal_id).await {
+                Ok(proposal) => Json(ApiResponse { success: true, data: Some(proposal), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::governance::GovernanceProposal> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+// Delegation API handlers
+fn list_delegations_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            match sync.list_delegations().await {
+                Ok(delegations) => Json(ApiResponse { success: true, data: Some(delegations), error: None }),
+                Err(e) => Json(ApiResponse::<Vec<crate::cross_domain::delegation::DomainDelegation>> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn get_delegation_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<(String, String)>,), axum::body::Body> {
+    axum::routing::get(move |Path((delegator, delegate)): Path<(String, String)>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_delegation(&delegator, &delegate).await {
+                Ok(delegation) => Json(ApiResponse { success: true, data: Some(delegation), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::delegation::DomainDelegation> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct IssueDelegationRequest {
+    /// Domain opening the governance proposal
+    proposer: String,
+    delegator: String,
+    delegate: String,
+    permissions: Vec<crate::cross_domain::delegation::DelegationPermission>,
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    description: String,
+}
+
+/// Open a governance proposal issuing a delegation; it takes effect once approved
+fn issue_delegation_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::post(move |AxumJson(request): AxumJson<IssueDelegationRequest>| {
+        let sync = sync.clone();
+        async move {
+            let action = crate::cross_domain::governance::GovernanceAction::IssueDelegation {
+                delegator: request.delegator,
+                delegate: request.delegate,
+                permissions: request.permissions,
+                expires_at: request.expires_at,
+            };
+            match sync.submit_governance_proposal(&request.proposer, action, request.description).await {
+                Ok(proposal) => Json(ApiResponse { success: true, data: Some(proposal), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::governance::GovernanceProposal> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct RevokeDelegationRequest {
+    /// Domain opening the governance proposal
+    proposer: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Open a governance proposal revoking a delegation; it takes effect once approved
+fn revoke_delegation_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<(String, String)>,), axum::body::Body> {
+    axum::routing::delete(move |Path((delegator, delegate)): Path<(String, String)>, AxumJson(request): AxumJson<RevokeDelegationRequest>| {
+        let sync = sync.clone();
+        async move {
+            let action = crate::cross_domain::governance::GovernanceAction::RevokeDelegation { delegator, delegate };
+            match sync.submit_governance_proposal(&request.proposer, action, request.description).await {
+                Ok(proposal) => Json(ApiResponse { success: true, data: Some(proposal), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::governance::GovernanceProposal> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct ComplianceExportRequest {
+    #[serde(default)]
+    filter: crate::compliance::HistoryFilter,
+    /// Fields replaced with `[REDACTED]`
+    #[serde(default)]
+    redact: Vec<crate::compliance::RedactedField>,
+    #[serde(default)]
+    format: crate::compliance::ExportFormat,
+}
+
+/// Stream the transaction history matching a filter as JSON, CSV or NDJSON
+fn compliance_export_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::post(move |AxumJson(request): AxumJson<ComplianceExportRequest>| {
+        let sync = sync.clone();
+        async move {
+            use axum::response::IntoResponse;
+            match sync.stream_transaction_history(request.filter, request.redact).await {
+                Ok(records) => (
+                    [(axum::http::header::CONTENT_TYPE, request.format.content_type())],
+                    axum::body::Body::from_stream(crate::compliance::encode(records, request.format)),
+                )
+                    .into_response(),
+                Err(e) => Json(ApiResponse::<()> { success: false, data: None, error: Some(format!("{}", e)) }).into_response(),
+            }
+        }
+    })
+}
+
+// Validator API handlers
+fn slashing_history_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(validator_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_slashing_history(&validator_id).await {
+                Ok(history) => Json(ApiResponse { success: true, data: Some(history), error: None }),
+                Err(e) => Json(ApiResponse::<Vec<crate::storage::SlashingRecord>> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+/// Reputation of a domain or validator
+fn reputation_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(participant_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            let score = sync.get_reputation(&participant_id).await;
+            Json(ApiResponse { success: true, data: Some(score), error: None })
+        }
+    })
+}
+
+#[derive(Serialize)]
+struct MempoolStatsDto {
+    pooled: usize,
+    total_added: u64,
+    total_removed: u64,
+    avg_pool_size: f64,
+    avg_wait_time_ms: u64,
+    utilization: f64,
+}
+
+fn mempool_stats_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            let (pooled, stats) = sync.get_pool_stats().await;
+            Json(ApiResponse {
+                success: true,
+                data: Some(MempoolStatsDto {
+                    pooled,
+                    total_added: stats.total_added,
+                    total_removed: stats.total_removed,
+                    avg_pool_size: stats.avg_pool_size,
+                    avg_wait_time_ms: stats.avg_wait_time.as_millis() as u64,
+                    utilization: stats.utilization,
+                }),
+                error: None,
+            })
+        }
+    })
+}
+
+#[derive(Serialize)]
+struct FeeEarningsDto {
+    validator_id: String,
+    fee_earnings: u64,
+}
+
+fn fee_earnings_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(validator_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_validator_fee_earnings(&validator_id).await {
+                Ok(fee_earnings) => Json(ApiResponse { success: true, data: Some(FeeEarningsDto { validator_id, fee_earnings }), error: None }),
+                Err(e) => Json(ApiResponse::<FeeEarningsDto> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+// JSON-RPC handlers
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    pub method: String,
+    pub params: Option<serde_json::Value>,
+    pub id: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    /// Name and retryability of `code`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+    pub id: Option<serde_json::Value>,
+}
+
+fn rpc_result(result: serde_json::Value, id: Option<serde_json::Value>) -> JsonRpcResponse {
+    JsonRpcResponse { jsonrpc: "2.0".to_string(), result: Some(result), error: None, id }
+}
+
+fn rpc_error(code: ErrorCode, message: impl Into<String>, id: Option<serde_json::Value>) -> JsonRpcResponse {
+    let error = JsonRpcError { code: code.code(), message: message.into(), data: Some(code.rpc_data()) };
+    JsonRpcResponse { jsonrpc: "2.0".to_string(), result: None, error: Some(error), id }
+}
+
+/// Report `error` under its stable error code
+fn rpc_failure(error: GarpError, id: Option<serde_json::Value>) -> JsonRpcResponse {
+    rpc_error(error.code(), error.to_string(), id)
+}
+
+/// Validator mutations require an `X-Admin-Token` header matching
+/// `SYNC_ADMIN_TOKEN`; they are disabled while the token is unset
+fn is_admin(headers: &axum::http::HeaderMap) -> bool {
+    let expected = match std::env::var("SYNC_ADMIN_TOKEN") {
+        Ok(token) if !token.is_empty() => token,
+        _ => return false,
+    };
+    headers
+        .get("x-admin-token")
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |token| token == expected)
+}
+
+#[derive(Deserialize)]
+struct AddValidatorParams {
+    id: String,
+    public_key_hex: String,
+    stake: u64,
+}
+
+#[derive(Deserialize)]
+struct ViewChangesParams {
+    #[serde(default)]
+    from_view: u64,
+    /// Defaults to the current view
+    to_view: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RemoveValidatorParams {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct SettlementProofParams {
+    transaction_id: String,
+}
+
+#[derive(Deserialize)]
+struct TransactionDetailsParams {
+    transaction_id: String,
+    /// Include individual consensus votes instead of only their summary
+    #[serde(default)]
+    include_votes: bool,
+}
+
+#[derive(Deserialize)]
+struct ProposalParams {
+    proposal_id: String,
+}
+
+#[derive(Deserialize)]
+struct CastVoteParams {
+    proposal_id: String,
+    domain_id: String,
+    approve: bool,
+}
+
+#[derive(Deserialize)]
+struct CompensationChainParams {
+    transaction_id: String,
+}
+
+#[derive(Deserialize)]
+struct StateRootParams {
+    height: u64,
+}
+
+#[derive(Deserialize)]
+struct StateRootProofParams {
+    height: u64,
+    domain_id: String,
+}
+
+#[derive(Deserialize)]
+struct StateHistoryParams {
+    domain_id: String,
+    key: String,
+    #[serde(default = "default_state_history_limit")]
+    limit: usize,
+}
+
+fn default_state_history_limit() -> usize {
+    50
+}
+
+#[derive(Deserialize)]
+struct SwapStatusParams {
+    swap_id: String,
+}
+
+#[derive(Deserialize)]
+struct CrossDomainStatusParams {
+    transaction_id: String,
+}
+
+#[derive(Deserialize)]
+struct DomainStateParams {
+    domain_id: String,
+    /// Read the state as of this version; latest when omitted
+    #[serde(default)]
+    version: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RevealSwapPreimageParams {
+    swap_id: String,
+    /// Hex-encoded preimage
+    preimage: String,
+}
+
+#[derive(Deserialize)]
+struct ConnectPeerParams {
+    address: String,
+}
+
+#[derive(Deserialize)]
+struct PeerParams {
+    peer_id: String,
+}
+
+#[derive(Deserialize)]
+struct DomainHeartbeatParams {
+    domain_id: String,
+}
+
+#[derive(Deserialize)]
+struct BanPeerParams {
+    peer_id: String,
+    /// Ban duration in seconds; omitted for an indefinite ban
+    duration_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SetMaintenanceModeParams {
+    enabled: bool,
+    /// Required when enabling
+    #[serde(default)]
+    reason: String,
+    /// Recorded as who set the mode
+    #[serde(default = "default_maintenance_set_by")]
+    set_by: String,
+}
+
+fn default_maintenance_set_by() -> String {
+    "rpc".to_string()
+}
+
+#[derive(Deserialize)]
+struct UpdateValidatorStakeParams {
+    id: String,
+    stake: u64,
+}
+
+fn rpc_params<T: serde::de::DeserializeOwned>(params: Option<serde_json::Value>) -> Result<T, String> {
+    serde_json::from_value(params.unwrap_or(serde_json::Value::Null)).map_err(|e| format!("Invalid params: {}", e))
+}
+
+fn scheduled(result: garp_common::GarpResult<u64>, id: Option<serde_json::Value>) -> JsonRpcResponse {
+    match result {
+        Ok(epoch) => rpc_result(serde_json::json!({ "effective_epoch": epoch }), id),
+        Err(e) => rpc_failure(e, id),
+    }
+}
+
+async fn handle_rpc(sync: &GlobalSynchronizer, headers: &axum::http::HeaderMap, req: JsonRpcRequest) -> JsonRpcResponse {
+    let id = req.id;
+    match req.method.as_str() {
+        "getValidators" => match sync.list_validators().await {
+            Ok(validators) => rpc_result(serde_json::json!({
+                "epoch": sync.current_validator_epoch().await,
+                "validators": validators,
+                "pending_changes": sync.pending_validator_changes().await,
+            }), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "getViewChanges" => match rpc_params::<ViewChangesParams>(req.params.or(Some(serde_json::json!({})))) {
+            Ok(p) => {
+                let to_view = match p.to_view {
+                    Some(v) => v,
+                    None => sync.get_consensus_view().await,
+                };
+                match sync.get_view_changes(p.from_view, to_view).await {
+                    Ok(records) => rpc_result(serde_json::json!(records), id),
+                    Err(e) => rpc_failure(e, id),
+                }
+            }
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getSettlementProof" => match rpc_params::<SettlementProofParams>(req.params) {
+            Ok(p) => match sync.get_settlement_proof(&p.transaction_id).await {
+                Ok(proof) => rpc_result(serde_json::json!(proof), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getTransactionDetails" => match rpc_params::<TransactionDetailsParams>(req.params) {
+            Ok(p) => match sync.get_transaction_details(&p.transaction_id, p.include_votes).await {
+                Ok(details) => rpc_result(serde_json::json!(details), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "listProposals" => match sync.list_governance_proposals().await {
+            Ok(proposals) => rpc_result(serde_json::json!(proposals), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "getProposal" => match rpc_params::<ProposalParams>(req.params) {
+            Ok(p) => match sync.get_governance_proposal(&p.proposal_id).await {
+                Ok(proposal) => rpc_result(serde_json::json!(proposal), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getCompensationChain" => match rpc_params::<CompensationChainParams>(req.params) {
+            Ok(p) => match sync.get_compensation_chain(&p.transaction_id).await {
+                Ok(chain) => rpc_result(serde_json::json!(chain), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getStateRoot" => match rpc_params::<StateRootParams>(req.params) {
+            Ok(p) => match sync.get_state_root(p.height).await {
+                Ok(record) => rpc_result(serde_json::json!({
+                    "height": record.height,
+                    "state_root": hex::encode(&record.root),
+                    "domain_count": record.domain_hashes.len(),
+                }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getStateRootProof" => match rpc_params::<StateRootProofParams>(req.params) {
+            Ok(p) => match sync.get_state_root_proof(p.height, &p.domain_id).await {
+                Ok(proof) => rpc_result(serde_json::json!(proof), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getStateHistory" => match rpc_params::<StateHistoryParams>(req.params) {
+            Ok(p) => match sync.get_state_history(&p.domain_id, &p.key, p.limit).await {
+                Ok(history) => rpc_result(serde_json::json!(history), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getSwapStatus" => match rpc_params::<SwapStatusParams>(req.params) {
+            Ok(p) => match sync.get_swap_status(&p.swap_id).await {
+                Ok(swap) => rpc_result(serde_json::json!(swap), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "revealSwapPreimage" => match rpc_params::<RevealSwapPreimageParams>(req.params) {
+            Ok(p) => match hex::decode(&p.preimage) {
+                Ok(preimage) => match sync.reveal_swap_preimage(&p.swap_id, &preimage).await {
+                    Ok(swap) => rpc_result(serde_json::json!(swap), id),
+                    Err(e) => rpc_failure(e, id),
+                },
+                Err(e) => rpc_error(ErrorCode::InvalidParams, format!("Invalid preimage: {}", e), id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "submitCrossDomainTransaction" => match rpc_params::<crate::synchronizer::CrossDomainSubmission>(req.params) {
+            Ok(submission) => match sync.submit_cross_domain(submission).await {
+                Ok(transaction_id) => rpc_result(serde_json::json!({ "transaction_id": transaction_id.to_string() }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getCrossDomainStatus" => match rpc_params::<CrossDomainStatusParams>(req.params) {
+            Ok(p) => match sync.get_cross_domain_status(&p.transaction_id).await {
+                Ok(status) => rpc_result(serde_json::json!(status), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "listActiveDomains" => rpc_result(serde_json::json!(sync.list_active_domains().await), id),
+        "getDomainState" => match rpc_params::<DomainStateParams>(req.params) {
+            Ok(p) => match sync.get_domain_state_version(&p.domain_id, p.version).await {
+                Ok(state) => rpc_result(serde_json::json!(state), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "addValidator" | "removeValidator" | "updateValidatorStake"
+        | "listPeers" | "connectPeer" | "disconnectPeer" | "banPeer"
+        | "registerDomain" | "domainHeartbeat" | "listRegisteredDomains"
+        | "startFastSync" | "castVote" | "getEmergencyAuditLog"
+        | "reloadConfig" | "getConfigReloadLog" | "setMaintenanceMode"
+        | "rotateNodeKey" if !is_admin(headers) => {
+            rpc_error(ErrorCode::Unauthorized, "Admin token required", id)
+        }
+        "getVersion" => rpc_result(serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "maintenance": sync.get_maintenance_mode().await,
+        }), id),
+        "getHealth" => rpc_result(serde_json::json!(sync.get_health_status().await), id),
+        "setMaintenanceMode" => match rpc_params::<SetMaintenanceModeParams>(req.params) {
+            Ok(p) => match sync.set_maintenance_mode(p.enabled, p.reason, &p.set_by).await {
+                Ok(mode) => rpc_result(serde_json::json!({ "enabled": mode.is_some(), "maintenance": mode }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "reloadConfig" => match sync.reload_config("rpc").await {
+            Ok(record) => rpc_result(serde_json::json!({ "message": record.summary(), "record": record }), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "getConfigReloadLog" => match sync.get_config_reload_log().await {
+            Ok(records) => rpc_result(serde_json::json!(records), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "getEmergencyAuditLog" => match sync.get_emergency_audit_log().await {
+            Ok(records) => rpc_result(serde_json::json!(records), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "castVote" => match rpc_params::<CastVoteParams>(req.params) {
+            Ok(p) => match sync.cast_governance_vote(&p.proposal_id, &p.domain_id, p.approve).await {
+                Ok(proposal) => rpc_result(serde_json::json!(proposal), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "listPeers" => rpc_result(serde_json::json!(sync.list_peers().await), id),
+        "connectPeer" => match rpc_params::<ConnectPeerParams>(req.params) {
+            Ok(p) => match sync.connect_peer(&p.address).await {
+                Ok(peer_id) => rpc_result(serde_json::json!({ "peer_id": peer_id }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "disconnectPeer" => match rpc_params::<PeerParams>(req.params) {
+            Ok(p) => match sync.disconnect_peer(&p.peer_id).await {
+                Ok(()) => rpc_result(serde_json::json!({ "peer_id": p.peer_id }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "banPeer" => match rpc_params::<BanPeerParams>(req.params) {
+            Ok(p) => {
+                let duration = p.duration_secs.map(std::time::Duration::from_secs);
+                match sync.ban_domain(&p.peer_id, duration).await {
+                    Ok(()) => rpc_result(serde_json::json!({ "peer_id": p.peer_id, "duration_secs": p.duration_secs }), id),
+                    Err(e) => rpc_failure(e, id),
+                }
+            }
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "registerDomain" => match rpc_params::<crate::discovery::DomainAnnouncement>(req.params) {
+            Ok(p) => match sync.register_domain(p).await {
+                Ok(registration) => rpc_result(serde_json::json!(registration), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "domainHeartbeat" => match rpc_params::<DomainHeartbeatParams>(req.params) {
+            Ok(p) => match sync.domain_heartbeat(&p.domain_id).await {
+                Ok(registration) => rpc_result(serde_json::json!({
+                    "domain_id": registration.domain_id,
+                    "expires_at": registration.expires_at(),
+                }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "listRegisteredDomains" => rpc_result(serde_json::json!(sync.list_registered_domains().await), id),
+        "rotateNodeKey" => match sync.rotate_node_key("rpc").await {
+            Ok(rotation) => rpc_result(serde_json::json!(rotation), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "getNodeKeyHistory" => rpc_result(serde_json::json!(sync.node_key_history().await), id),
+        // Self-authenticating: the announcement is signed by the node's current key
+        "announceKeyRotation" => match rpc_params::<garp_common::KeyRotation>(req.params) {
+            Ok(rotation) => match sync.accept_key_rotation(&rotation).await {
+                Ok(()) => rpc_result(serde_json::json!({ "node_id": rotation.node_id, "epoch": rotation.epoch }), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "startFastSync" => match rpc_params::<ConnectPeerParams>(req.params) {
+            Ok(p) => match sync.start_fast_sync(&p.address).await {
+                Ok(()) => rpc_result(serde_json::json!(sync.get_fast_sync_state().await), id),
+                Err(e) => rpc_failure(e, id),
+            },
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "getFastSyncStatus" => rpc_result(serde_json::json!(sync.get_fast_sync_state().await), id),
+        "getConsensusMetrics" => match sync.get_consensus_metrics().await {
+            Ok(snapshot) => rpc_result(serde_json::json!(snapshot), id),
+            Err(e) => rpc_failure(e, id),
+        },
+        "addValidator" => match rpc_params::<AddValidatorParams>(req.params) {
+            Ok(p) => {
+                let info = crate::validator::ValidatorInfo::new(garp_common::types::ParticipantId::new(&p.id), p.public_key_hex, p.stake);
+                scheduled(sync.add_validator(info).await, id)
+            }
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "removeValidator" => match rpc_params::<RemoveValidatorParams>(req.params) {
+            Ok(p) => scheduled(sync.remove_validator(&p.id).await, id),
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        "updateValidatorStake" => match rpc_params::<UpdateValidatorStakeParams>(req.params) {
+            Ok(p) => scheduled(sync.update_validator_stake(&p.id, p.stake).await, id),
+            Err(e) => rpc_error(ErrorCode::InvalidParams, e, id),
+        },
+        other => rpc_error(ErrorCode::MethodNotFound, format!("Method not found: {}", other), id),
+    }
+}
+
+fn json_rpc_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::post(move |headers: axum::http::HeaderMap, AxumJson(request): AxumJson<JsonRpcRequest>| {
+        let sync = sync.clone();
+        async move { Json(handle_rpc(&sync, &headers, request).await) }
+    })
+}
+
+// Timelock API handlers
+fn timelock_queue_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            let queue = sync.get_timelock_queue().await;
+            Json(ApiResponse { success: true, data: Some(queue), error: None })
+        }
+    })
+}
+
+fn prometheus_metrics_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            let domain_metrics = sync.get_all_domain_performance_metrics().await;
+            let mut domains: Vec<_> = domain_metrics.into_iter().collect();
+            domains.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut out = String::new();
+            out.push_str("# TYPE garp_domain_confirmations_received_total counter\n");
+            for (domain_id, m) in &domains {
+                out.push_str(&format!("garp_domain_confirmations_received_total{{domain_id=\"{}\"}} {}\n", domain_id, m.confirmations_received));
+            }
+            out.push_str("# TYPE garp_domain_failed_confirmations_total counter\n");
+            for (domain_id, m) in &domains {
+                out.push_str(&format!("garp_domain_failed_confirmations_total{{domain_id=\"{}\"}} {}\n", domain_id, m.failed_confirmations));
+            }
+            out.push_str("# TYPE garp_domain_avg_confirmation_latency_ms gauge\n");
+            for (domain_id, m) in &domains {
+                out.push_str(&format!("garp_domain_avg_confirmation_latency_ms{{domain_id=\"{}\"}} {}\n", domain_id, m.avg_confirmation_latency_ms));
+            }
+            out.push_str("# TYPE garp_domain_last_activity_timestamp_seconds gauge\n");
+            for (domain_id, m) in &domains {
+                if let Some(ts) = m.last_activity {
+                    out.push_str(&format!("garp_domain_last_activity_timestamp_seconds{{domain_id=\"{}\"}} {}\n", domain_id, ts.timestamp()));
+                }
+            }
+            let peers = sync.list_peers().await;
+            out.push_str("# TYPE garp_peer_connected gauge\n");
+            for peer in &peers {
+                let connected = peer.status == crate::network::ConnectionStatus::Connected;
+                out.push_str(&format!("garp_peer_connected{{peer_id=\"{}\"}} {}\n", peer.peer_id, connected as u8));
+            }
+            out.push_str("# TYPE garp_peer_messages_sent_total counter\n");
+            for peer in &peers {
+                out.push_str(&format!("garp_peer_messages_sent_total{{peer_id=\"{}\"}} {}\n", peer.peer_id, peer.messages_sent));
+            }
+            out.push_str("# TYPE garp_peer_messages_received_total counter\n");
+            for peer in &peers {
+                out.push_str(&format!("garp_peer_messages_received_total{{peer_id=\"{}\"}} {}\n", peer.peer_id, peer.messages_received));
+            }
+            out.push_str("# TYPE garp_peer_latency_avg_ms gauge\n");
+            for peer in &peers {
+                out.push_str(&format!("garp_peer_latency_avg_ms{{peer_id=\"{}\"}} {}\n", peer.peer_id, peer.latency.avg_ms));
+            }
+            let network = sync.get_network_metrics().await;
+            out.push_str("# TYPE garp_network_compression_ratio gauge\n");
+            out.push_str(&format!("garp_network_compression_ratio {}\n", network.compression_ratio().await));
+            out.push_str("# TYPE garp_network_oversize_rejected_total counter\n");
+            out.push_str(&format!("garp_network_oversize_rejected_total {}\n", *network.oversize_rejected.read().await));
+            out.push_str("# TYPE garp_network_tls_handshake_failures_total counter\n");
+            out.push_str(&format!("garp_network_tls_handshake_failures_total {}\n", *network.tls_handshake_failures.read().await));
+            if let Ok(consensus) = sync.get_consensus_metrics().await {
+                out.push_str("# TYPE garp_consensus_phase_latency_ms summary\n");
+                for (phase, latency) in &consensus.rounds.phase_latency {
+                    for (quantile, value) in [("0.5", latency.p50_ms), ("0.95", latency.p95_ms), ("0.99", latency.p99_ms)] {
+                        out.push_str(&format!("garp_consensus_phase_latency_ms{{phase=\"{}\",quantile=\"{}\"}} {}\n", phase, quantile, value));
+                    }
+                    out.push_str(&format!("garp_consensus_phase_latency_ms_count{{phase=\"{}\"}} {}\n", phase, latency.count));
+                }
+                out.push_str("# TYPE garp_consensus_rounds_total counter\n");
+                out.push_str(&format!("garp_consensus_rounds_total{{path=\"happy\"}} {}\n", consensus.rounds.happy_path_rounds));
+                out.push_str(&format!("garp_consensus_rounds_total{{path=\"view_change\"}} {}\n", consensus.rounds.view_change_rounds));
+                out.push_str("# TYPE garp_consensus_view_changes_total counter\n");
+                out.push_str(&format!("garp_consensus_view_changes_total {}\n", consensus.view_changes));
+                out.push_str("# TYPE garp_consensus_current_view gauge\n");
+                out.push_str(&format!("garp_consensus_current_view {}\n", consensus.current_view));
+                // Label only the least participating validators to bound cardinality
+                let top_k = sync.current_config().consensus.metrics_top_validators;
+                out.push_str("# TYPE garp_consensus_validator_participation gauge\n");
+                for (validator, rate) in crate::consensus::telemetry::top_k_participation(&consensus.rounds.validator_participation, top_k) {
+                    out.push_str(&format!("garp_consensus_validator_participation{{validator=\"{}\"}} {}\n", validator, rate));
+                }
+            }
+            (
+                [(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+                out,
+            )
+        }
+    })
+}
+
+// Admin API: node control under /admin, for holders of an Admin role JWT
+fn admin_router(sync: Arc<GlobalSynchronizer>) -> Router {
+    let jwt_secret: Option<Arc<str>> = sync.jwt_secret().map(Arc::from);
+    Router::new()
+        .route("/health", get(admin_health_handler(sync.clone())))
+        .route("/shutdown", post(admin_shutdown_handler(sync.clone())))
+        .route("/domains", get(admin_domains_handler(sync.clone())))
+        .route("/domains/:id/reload", post(admin_reload_domain_handler(sync.clone())))
+        .route("/mempool", get(admin_mempool_handler(sync.clone())))
+        .route("/mempool/:id", axum::routing::delete(admin_remove_mempool_handler(sync.clone())))
+        .route("/consensus/view-change", post(admin_view_change_handler(sync.clone())))
+        .route("/config/reload", post(admin_reload_config_handler(sync.clone())))
+        .route("/config/reloads", get(admin_config_reloads_handler(sync.clone())))
+        .route("/maintenance", get(admin_maintenance_handler(sync.clone())).put(admin_set_maintenance_handler(sync.clone())))
+        .route("/replication/alerts", get(admin_replication_alerts_handler(sync.clone())))
+        .route("/keys", get(admin_node_keys_handler(sync.clone())))
+        .route("/keys/rotate", post(admin_rotate_node_key_handler(sync.clone())))
+        .layer(middleware::from_fn_with_state(jwt_secret, admin_auth_middleware))
+}
+
+/// Role an admin API token must carry
+const ADMIN_ROLE: &str = "Admin";
+
+/// Claims of an admin API token
+#[derive(Debug, Clone, Deserialize)]
+struct AdminClaims {
+    /// Token holder, logged with every admin action
+    sub: String,
+    /// Roles granted to the holder
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+/// Decode an HS256 token signed with `secret`, requiring the Admin role
+fn verify_admin_token(token: &str, secret: &str) -> Result<AdminClaims, (axum::http::StatusCode, String)> {
+    let claims = jsonwebtoken::decode::<AdminClaims>(
+        token,
+        &jsonwebtoken::DecodingKey::from_secret(secret.as_bytes()),
+        &jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256),
+    )
+    .map_err(|e| (axum::http::StatusCode::UNAUTHORIZED, format!("invalid token: {}", e)))?
+    .claims;
+    if !claims.roles.iter().any(|role| role == ADMIN_ROLE) {
+        return Err((axum::http::StatusCode::FORBIDDEN, format!("{} lacks the {} role", claims.sub, ADMIN_ROLE)));
+    }
+    Ok(claims)
+}
+
+/// Admin requests need `Authorization: Bearer <jwt>` with the Admin role,
+/// signed with `api.jwt_secret`; the admin API is disabled while it is unset
+async fn admin_auth_middleware<B>(
+    State(jwt_secret): State<Option<Arc<str>>>,
+    mut req: axum::http::Request<B>,
+    next: middleware::Next<B>,
+) -> Result<axum::response::Response, axum::http::StatusCode> {
+    let path = req.uri().path().to_string();
+    let Some(secret) = jwt_secret else {
+        tracing::warn!(path = %path, "Admin request rejected: no JWT secret configured");
+        return Err(axum::http::StatusCode::UNAUTHORIZED);
+    };
+    let token = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    match verify_admin_token(token, &secret) {
+        Ok(claims) => {
+            req.extensions_mut().insert(claims);
+            Ok(next.run(req).await)
+        }
+        Err((status, reason)) => {
+            tracing::warn!(path = %path, "Admin request rejected: {}", reason);
+            Err(status)
+        }
+    }
+}
+
+fn admin_health_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            tracing::info!(admin = %admin.sub, "Admin requested deep health");
+            Json(ApiResponse { success: true, data: Some(sync.get_health_status().await), error: None })
+        }
+    })
+}
+
+fn admin_shutdown_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::post(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            tracing::warn!(admin = %admin.sub, "Admin requested shutdown");
+            sync.request_shutdown();
+            Json(ApiResponse { success: true, data: Some("Shutdown requested".to_string()), error: None })
+        }
+    })
+}
+
+#[derive(Serialize)]
+struct AdminDomainDto {
+    domain_id: String,
+    status: crate::cross_domain::DomainStatus,
+    endpoint: String,
+    last_block_height: u64,
+    last_block_hash: String,
+    state_root: String,
+    validators: usize,
+    capabilities: crate::cross_domain::DomainCapabilities,
+    last_updated_secs_ago: u64,
+}
+
+impl From<crate::cross_domain::DomainState> for AdminDomainDto {
+    fn from(state: crate::cross_domain::DomainState) -> Self {
+        Self {
+            domain_id: state.domain_id,
+            status: state.status,
+            endpoint: state.endpoint,
+            last_block_height: state.last_block_height,
+            last_block_hash: state.last_block_hash,
+            state_root: state.state_root,
+            validators: state.validators.len(),
+            capabilities: state.capabilities,
+            last_updated_secs_ago: state.last_updated.elapsed().as_secs(),
+        }
+    }
+}
+
+fn admin_domains_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            tracing::info!(admin = %admin.sub, "Admin listed domain states");
+            let domains: Vec<AdminDomainDto> = sync.list_domain_states().await.into_iter().map(AdminDomainDto::from).collect();
+            Json(ApiResponse { success: true, data: Some(domains), error: None })
+        }
+    })
+}
+
+fn admin_reload_domain_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>, Path<String>), axum::body::Body> {
+    axum::routing::post(move |Extension(admin): Extension<AdminClaims>, Path(domain_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            tracing::warn!(admin = %admin.sub, "Admin reloading domain {}", domain_id);
+            match sync.reload_domain(&domain_id).await {
+                Ok(state) => Json(ApiResponse { success: true, data: Some(AdminDomainDto::from(state)), error: None }),
+                Err(e) => Json(ApiResponse::<AdminDomainDto> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn admin_mempool_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            tracing::info!(admin = %admin.sub, "Admin dumped the mempool");
+            match sync.get_mempool_entries().await {
+                Ok(entries) => Json(ApiResponse { success: true, data: Some(entries), error: None }),
+                Err(e) => Json(ApiResponse::<Vec<crate::synchronizer::MempoolEntry>> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn admin_remove_mempool_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>, Path<String>), axum::body::Body> {
+    axum::routing::delete(move |Extension(admin): Extension<AdminClaims>, Path(transaction_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            tracing::warn!(admin = %admin.sub, "Admin removing transaction {} from the mempool", transaction_id);
+            match sync.remove_mempool_transaction(&transaction_id).await {
+                Ok(()) => Json(ApiResponse { success: true, data: Some(format!("Transaction {} removed from the mempool", transaction_id)), error: None }),
+                Err(e) => Json(ApiResponse::<String> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct ViewChangeRequest {
+    /// Why the view is being changed; recorded with the view change
+    reason: Option<String>,
+}
+
+fn admin_view_change_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::post(move |Extension(admin): Extension<AdminClaims>, request: Option<AxumJson<ViewChangeRequest>>| {
+        let sync = sync.clone();
+        async move {
+            let reason = request
+                .and_then(|AxumJson(r)| r.reason)
+                .unwrap_or_else(|| format!("forced by admin {}", admin.sub));
+            tracing::warn!(admin = %admin.sub, "Admin forcing a view change: {}", reason);
+            match sync.force_view_change(reason).await {
+                Ok(view) => Json(ApiResponse { success: true, data: Some(view), error: None }),
+                Err(e) => Json(ApiResponse::<u64> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct MaintenanceModeRequest {
+    enabled: bool,
+    /// Reported to rejected submitters; required when enabling
+    #[serde(default)]
+    reason: String,
+}
+
+fn admin_maintenance_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(_admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            // `data` is null while transactions are accepted
+            Json(ApiResponse { success: true, data: sync.get_maintenance_mode().await, error: None })
+        }
+    })
+}
+
+fn admin_set_maintenance_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::put(move |Extension(admin): Extension<AdminClaims>, AxumJson(request): AxumJson<MaintenanceModeRequest>| {
+        let sync = sync.clone();
+        async move {
+            match sync.set_maintenance_mode(request.enabled, request.reason, &admin.sub).await {
+                Ok(mode) => Json(ApiResponse { success: true, data: mode, error: None }),
+                Err(e) => Json(ApiResponse::<crate::MaintenanceMode> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn admin_reload_config_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::post(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            match sync.reload_config(&admin.sub).await {
+                Ok(record) => Json(ApiResponse { success: true, data: Some(record), error: None }),
+                Err(e) => Json(ApiResponse::<crate::config::ConfigReloadRecord> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn admin_config_reloads_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            tracing::info!(admin = %admin.sub, "Admin listed configuration reloads");
+            match sync.get_config_reload_log().await {
+                Ok(records) => Json(ApiResponse { success: true, data: Some(records), error: None }),
+                Err(e) => Json(ApiResponse::<Vec<crate::config::ConfigReloadRecord>> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn admin_replication_alerts_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            tracing::info!(admin = %admin.sub, "Admin listed replication alerts");
+            Json(ApiResponse { success: true, data: Some(sync.get_replication_alerts().await), error: None })
+        }
+    })
+}
+
+fn admin_node_keys_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::get(move |Extension(_admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            // `data` is null while the node signs without a keystore
+            Json(ApiResponse { success: true, data: sync.node_key_history().await, error: None })
+        }
+    })
+}
+
+fn admin_rotate_node_key_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Extension<AdminClaims>,), axum::body::Body> {
+    axum::routing::post(move |Extension(admin): Extension<AdminClaims>| {
+        let sync = sync.clone();
+        async move {
+            match sync.rotate_node_key(&admin.sub).await {
+                Ok(rotation) => Json(ApiResponse { success: true, data: Some(rotation), error: None }),
+                Err(e) => Json(ApiResponse::<garp_common::KeyRotation> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+// Admin API handlers
+#[derive(Deserialize)]
+struct BanDomainRequest {
+    /// Ban duration in seconds; omitted for an indefinite ban
+    duration_secs: Option<u64>,
+}
+
+fn ban_domain_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::post(move |Path(domain_id): Path<String>, request: Option<AxumJson<BanDomainRequest>>| {
+        let sync = sync.clone();
+        async move {
+            let duration = request
+                .and_then(|AxumJson(r)| r.duration_secs)
+                .map(std::time::Duration::from_secs);
+            match sync.ban_domain(&domain_id, duration).await {
+                Ok(()) => Json(ApiResponse { success: true, data: Some(format!("Domain {} banned", domain_id)), error: None }),
+                Err(e) => Json(ApiResponse::<String> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn unban_domain_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::delete(move |Path(domain_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.unban_domain(&domain_id).await {
+                Ok(()) => Json(ApiResponse { success: true, data: Some(format!("Domain {} unbanned", domain_id)), error: None }),
+                Err(e) => Json(ApiResponse::<String> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct SuspendDomainRequest {
+    /// Why the domain is going into maintenance
+    reason: String,
+    /// Minimum suspension duration in seconds
+    duration_secs: u64,
+}
+
+fn suspend_domain_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::post(move |Path(domain_id): Path<String>, AxumJson(request): AxumJson<SuspendDomainRequest>| {
+        let sync = sync.clone();
+        async move {
+            let duration = std::time::Duration::from_secs(request.duration_secs);
+            match sync.suspend_domain(&domain_id, request.reason, duration).await {
+                Ok(suspension) => Json(ApiResponse { success: true, data: Some(suspension), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::DomainSuspension> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn resume_domain_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::delete(move |Path(domain_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.resume_domain(&domain_id).await {
+                Ok(suspension) => Json(ApiResponse {
+                    success: true,
+                    data: Some(format!("Domain {} resumed, {} transactions queued for retry", domain_id, suspension.queued.len())),
+                    error: None,
+                }),
+                Err(e) => Json(ApiResponse::<String> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+#[derive(Serialize)]
+struct SettlementBatchingDto {
+    enabled: bool,
+    metrics: crate::storage::SettlementBatchMetrics,
+}
+
+#[derive(Deserialize)]
+struct SetSettlementBatchingRequest {
+    enabled: bool,
+}
+
+fn settlement_batching_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            let status = SettlementBatchingDto {
+                enabled: sync.is_settlement_batching_enabled(),
+                metrics: sync.get_settlement_batch_metrics().await,
+            };
+            Json(ApiResponse { success: true, data: Some(status), error: None })
+        }
+    })
+}
+
+fn set_settlement_batching_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::put(move |AxumJson(request): AxumJson<SetSettlementBatchingRequest>| {
+        let sync = sync.clone();
+        async move {
+            sync.set_settlement_batching(request.enabled).await;
+            let state = if request.enabled { "enabled" } else { "disabled" };
+            Json(ApiResponse { success: true, data: Some(format!("Settlement batching {}", state)), error: None })
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct RollbackQuery {
+    /// Only rollbacks in this status; all when omitted
+    status: Option<crate::storage::RollbackStatus>,
+}
+
+fn list_settlement_rollbacks_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Query<RollbackQuery>,), axum::body::Body> {
+    axum::routing::get(move |Query(query): Query<RollbackQuery>| {
+        let sync = sync.clone();
+        async move {
+            match sync.list_settlement_rollbacks(query.status).await {
+                Ok(rollbacks) => Json(ApiResponse { success: true, data: Some(rollbacks), error: None }),
+                Err(e) => Json(ApiResponse::<Vec<crate::storage::RollbackRecord>> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn settlement_rollback_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(rollback_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_settlement_rollback(&rollback_id).await {
+                Ok(rollback) => Json(ApiResponse { success: true, data: Some(rollback), error: None }),
+                Err(e) => Json(ApiResponse::<crate::storage::RollbackRecord> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn settlement_zk_proof_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(settlement_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            match sync.get_settlement_zk_proof(&settlement_id).await {
+                Ok(proof) => Json(ApiResponse { success: true, data: Some(proof), error: None }),
+                Err(e) => Json(ApiResponse::<crate::settlement::zk::ZkProof> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn transaction_receipt_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(Path<String>,), axum::body::Body> {
+    axum::routing::get(move |Path(transaction_id): Path<String>| {
+        let sync = sync.clone();
+        async move {
+            // `data` is null until the transaction's block is certified
+            match sync.get_transaction_receipt(&transaction_id).await {
+                Ok(receipt) => Json(ApiResponse { success: true, data: receipt, error: None }),
+                Err(e) => Json(ApiResponse::<crate::receipt::TransactionReceipt> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+/// Submit a cross-domain transaction. The response carries the
+/// correlation ID to search logs and traces for.
+fn submit_transaction_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::post(move |AxumJson(transaction): AxumJson<crate::cross_domain::CrossDomainTransaction>| {
+        let sync = sync.clone();
+        async move {
+            match sync.submit_transaction(transaction).await {
+                Ok(transaction_id) => {
+                    let dto = serde_json::json!({
+                        "transaction_id": transaction_id.to_string(),
+                        "correlation_id": transaction_id.to_string(),
+                    });
+                    Json(ApiResponse { success: true, data: Some(dto), error: None })
+                }
+                Err(e) => Json(ApiResponse::<serde_json::Value> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
 // Oracle API handlers
 #[derive(Serialize)]
 struct PriceDto {
@@ -218,6 +1539,59 @@ fn get_tvl_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler
     })
 }
 
+// Token wrapping API handlers
+#[derive(Deserialize)]
+struct WrapTokenRequest {
+    token: String,
+    amount: u64,
+    from_domain: String,
+    to_domain: String,
+}
+
+#[derive(Deserialize)]
+struct UnwrapTokenRequest {
+    wrapped_token: String,
+    amount: u64,
+    /// Domain the token was wrapped onto
+    to_domain: String,
+}
+
+fn wrap_token_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::post(move |AxumJson(request): AxumJson<WrapTokenRequest>| {
+        let sync = sync.clone();
+        async move {
+            match sync.wrap_token(&request.token, request.amount, &request.from_domain, &request.to_domain).await {
+                Ok(wrapping) => Json(ApiResponse { success: true, data: Some(wrapping), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::wrapping::TokenWrapping> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn unwrap_token_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::post(move |AxumJson(request): AxumJson<UnwrapTokenRequest>| {
+        let sync = sync.clone();
+        async move {
+            match sync.unwrap_token(&request.wrapped_token, request.amount, &request.to_domain).await {
+                Ok(wrapping) => Json(ApiResponse { success: true, data: Some(wrapping), error: None }),
+                Err(e) => Json(ApiResponse::<crate::cross_domain::wrapping::TokenWrapping> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
+fn list_wrapped_tokens_handler(sync: Arc<GlobalSynchronizer>) -> impl axum::handler::Handler<(), axum::body::Body> {
+    axum::routing::get(move || {
+        let sync = sync.clone();
+        async move {
+            match sync.list_wrapped_tokens().await {
+                Ok(tokens) => Json(ApiResponse { success: true, data: Some(tokens), error: None }),
+                Err(e) => Json(ApiResponse::<Vec<crate::cross_domain::wrapping::WrappedToken>> { success: false, data: None, error: Some(format!("{}", e)) }),
+            }
+        }
+    })
+}
+
 async fn auth_middleware<B>(req: axum::http::Request<B>, next: middleware::Next<B>) -> Result<axum::response::Response, axum::http::StatusCode> {
     // Per-IP throttle (simple in-memory)
     static IP_THROTTLE: OnceLock<Mutex<HashMap<String, (u32, i64)>>> = OnceLock::new();
@@ -277,4 +1651,49 @@ async fn auth_middleware<B>(req: axum::http::Request<B>, next: middleware::Next<
     }
     // If no token configured, allow
     Ok(next.run(req).await)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    fn token(secret: &str, roles: &[&str]) -> String {
+        let claims = serde_json::json!({
+            "sub": "ops@example.com",
+            "roles": roles,
+            "exp": Utc::now().timestamp() + 60,
+        });
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn test_admin_token_requires_admin_role_and_secret() {
+        let claims = verify_admin_token(&token("secret", &["Admin"]), "secret").unwrap();
+        assert_eq!(claims.sub, "ops@example.com");
+
+        let (status, _) = verify_admin_token(&token("secret", &["Operator"]), "secret").unwrap_err();
+        assert_eq!(status, axum::http::StatusCode::FORBIDDEN);
+        let (status, _) = verify_admin_token(&token("other", &["Admin"]), "secret").unwrap_err();
+        assert_eq!(status, axum::http::StatusCode::UNAUTHORIZED);
+        let (status, _) = verify_admin_token("", "secret").unwrap_err();
+        assert_eq!(status, axum::http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_rpc_errors_carry_stable_codes() {
+        let unavailable = serde_json::to_value(rpc_failure(GarpError::Unavailable("Domain d1 is not active".to_string()), Some(serde_json::json!(7)))).unwrap();
+        assert_eq!(unavailable["error"]["code"], 2000);
+        assert_eq!(unavailable["error"]["message"], "Unavailable: Domain d1 is not active");
+        assert_eq!(unavailable["error"]["data"], serde_json::json!({ "name": "DOMAIN_UNAVAILABLE", "retryable": true }));
+        assert_eq!(unavailable["id"], 7);
+
+        let invalid = serde_json::to_value(rpc_failure(GarpError::ValidationError("Transaction data is empty".to_string()), None)).unwrap();
+        assert_eq!(invalid["error"]["code"], 1000);
+        assert_eq!(invalid["error"]["data"]["retryable"], false);
+
+        let unknown = serde_json::to_value(rpc_error(ErrorCode::MethodNotFound, "Method not found: nope", None)).unwrap();
+        assert_eq!(unknown["error"]["code"], -32601);
+        assert_eq!(unknown["error"]["data"]["name"], "METHOD_NOT_FOUND");
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::interval;
+use tracing::{debug, error, info};
+
+use garp_common::GarpResult;
+use garp_common::types::{BlockHeader, TransactionId};
+
+use crate::config::{ConsensusConfig, GlobalSyncConfig};
+use crate::consensus::ConsensusEngine;
+use crate::consensus::batch::BatchProposal;
+use crate::receipt;
+use crate::storage::{GlobalBlock, GlobalStorage};
+use crate::synchronizer::GlobalSyncEvent;
+
+/// Decides whether the leader proposes a block on a given tick
+#[derive(Debug, Clone)]
+pub struct EmptyBlockPolicy {
+    /// Skip proposing while there is nothing to include
+    suppress_empty_blocks: bool,
+
+    /// Longest gap allowed between blocks while suppressing
+    max_empty_block_interval: Duration,
+}
+
+impl EmptyBlockPolicy {
+    /// Build the policy from consensus configuration
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        Self {
+            suppress_empty_blocks: config.suppress_empty_blocks,
+            max_empty_block_interval: Duration::from_secs(config.max_empty_block_interval_secs),
+        }
+    }
+
+    /// Whether to propose given the number of pending transactions and the
+    /// time since the last proposed block
+    pub fn should_propose(&self, pending: usize, since_last_block: Duration) -> bool {
+        pending > 0
+            || !self.suppress_empty_blocks
+            || since_last_block >= self.max_empty_block_interval
+    }
+}
+
+/// Proposes blocks from the mempool on every block-time tick
+pub struct BlockProducer {
+    /// Configuration
+    config: Arc<GlobalSyncConfig>,
+
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Consensus engine
+    consensus_engine: Arc<ConsensusEngine>,
+
+    /// Mempool transaction IDs
+    mempool: Arc<RwLock<Vec<TransactionId>>>,
+
+    /// Synchronizer event sender
+    event_tx: mpsc::UnboundedSender<GlobalSyncEvent>,
+
+    /// Empty block policy
+    policy: EmptyBlockPolicy,
+
+    /// Time of the last proposal
+    last_block_at: RwLock<Instant>,
+
+    /// Height of the last proposal
+    last_height: RwLock<u64>,
+}
+
+impl BlockProducer {
+    /// Create new block producer
+    pub fn new(
+        config: Arc<GlobalSyncConfig>,
+        storage: Arc<GlobalStorage>,
+        consensus_engine: Arc<ConsensusEngine>,
+        mempool: Arc<RwLock<Vec<TransactionId>>>,
+        event_tx: mpsc::UnboundedSender<GlobalSyncEvent>,
+    ) -> Self {
+        let policy = EmptyBlockPolicy::from_config(&config.consensus);
+        Self {
+            config,
+            storage,
+            consensus_engine,
+            mempool,
+            event_tx,
+            policy,
+            last_block_at: RwLock::new(Instant::now()),
+            last_height: RwLock::new(0),
+        }
+    }
+
+    /// Spawn the block production loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(self.config.block_time());
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = self.run_once(Instant::now()).await {
+                    error!("Block production tick failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Run a single production tick, returning the proposed block if any
+    pub async fn run_once(&self, now: Instant) -> GarpResult<Option<GlobalBlock>> {
+        if !self.consensus_engine.is_leader().await {
+            return Ok(None);
+        }
+
+        let (pending, _) = self.storage.pool_stats().await;
+        let since_last_block = now.saturating_duration_since(*self.last_block_at.read().await);
+        if !self.policy.should_propose(pending, since_last_block) {
+            // Keep the view alive so skipping an empty block does not trigger a view change
+            self.consensus_engine.touch_view_timer().await;
+            debug!("Suppressed empty block ({}ms since last block)", since_last_block.as_millis());
+            return Ok(None);
+        }
+
+        // Highest priority first, dependencies before their dependents
+        let consensus = &self.config.consensus;
+        let (max_transactions, max_bytes) = if consensus.batch_proposals {
+            (
+                consensus.max_transactions_per_block.min(consensus.max_batch_transactions),
+                consensus.max_block_bytes.min(consensus.max_batch_bytes),
+            )
+        } else {
+            (consensus.max_transactions_per_block, consensus.max_block_bytes)
+        };
+        let entries = self.storage.select_for_block(max_transactions, max_bytes).await;
+        let sizes: Vec<usize> = entries.iter().map(|entry| entry.size).collect();
+        let tx_ids: Vec<TransactionId> = entries.into_iter().map(|entry| entry.transaction_id).collect();
+        if !tx_ids.is_empty() {
+            let included: HashSet<&TransactionId> = tx_ids.iter().collect();
+            self.mempool.write().await.retain(|tid| !included.contains(tid));
+        }
+
+        let block = self.build_block(&tx_ids).await;
+        *self.last_block_at.write().await = now;
+        *self.last_height.write().await = block.header.slot;
+
+        info!("Proposing block {} with {} transactions", block.header.slot, tx_ids.len());
+        if consensus.batch_proposals && !tx_ids.is_empty() {
+            // One consensus round decides every transaction in the block
+            let batch = BatchProposal::new(
+                block.header.slot,
+                block.header.parent_hash.clone(),
+                tx_ids.iter().cloned().zip(sizes),
+            );
+            self.consensus_engine.propose_batch(batch).await?;
+        }
+        self.event_tx.send(GlobalSyncEvent::BlockProposed(block.clone()))?;
+        Ok(Some(block))
+    }
+
+    /// Assemble a block on top of the current head.
+    ///
+    /// Transaction bodies travel with their consensus sessions; the block
+    /// commits to the included transaction IDs through `tx_root`, a Merkle
+    /// root that transaction receipts prove inclusion against, and to the
+    /// global state through `state_root`, recorded at the block's height so
+    /// domain state proofs can be served against it.
+    async fn build_block(&self, tx_ids: &[TransactionId]) -> GlobalBlock {
+        let chain = self.storage.get_blockchain().await;
+        let slot = chain.height.max(*self.last_height.read().await) + 1;
+        let state_root = match self.storage.commit_state_root(slot).await {
+            Ok(record) => record.root,
+            Err(e) => {
+                error!("Failed to compute state root for block {}: {}", slot, e);
+                Vec::new()
+            }
+        };
+
+        let tx_root = receipt::transaction_root(tx_ids);
+
+        let header = BlockHeader {
+            parent_hash: chain.head,
+            slot,
+            epoch: 0,
+            proposer: garp_common::types::ParticipantId::new(&self.config.node.node_id),
+            state_root,
+            tx_root,
+            receipt_root: Vec::new(),
+        };
+
+        GlobalBlock {
+            hash: receipt::block_hash(&header.parent_hash, header.slot, &header.tx_root),
+            header,
+            timestamp: chrono::Utc::now(),
+            transactions: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::storage::{PoolTransaction, POOL_PRIORITY_METADATA_KEY};
+
+    async fn producer(suppress: bool) -> (
+        BlockProducer,
+        Arc<GlobalStorage>,
+        Arc<RwLock<Vec<TransactionId>>>,
+        mpsc::UnboundedReceiver<GlobalSyncEvent>,
+    ) {
+        let mut config = GlobalSyncConfig::default();
+        config.consensus.suppress_empty_blocks = suppress;
+        config.consensus.max_empty_block_interval_secs = 10;
+        let config = Arc::new(config);
+
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let mempool = Arc::new(RwLock::new(Vec::new()));
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        let producer = BlockProducer::new(config, storage.clone(), consensus_engine, mempool.clone(), event_tx);
+        (producer, storage, mempool, event_rx)
+    }
+
+    #[test]
+    fn test_policy_allows_empty_block_after_max_interval() {
+        let policy = EmptyBlockPolicy {
+            suppress_empty_blocks: true,
+            max_empty_block_interval: Duration::from_secs(10),
+        };
+        assert!(!policy.should_propose(0, Duration::from_secs(9)));
+        assert!(policy.should_propose(0, Duration::from_secs(10)));
+        assert!(policy.should_propose(1, Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_no_blocks_below_suppression_threshold() {
+        let (producer, storage, _mempool, mut event_rx) = producer(true).await;
+        let start = Instant::now();
+
+        for secs in 1..10 {
+            let proposed = producer.run_once(start + Duration::from_secs(secs)).await.unwrap();
+            assert!(proposed.is_none());
+        }
+        assert!(event_rx.try_recv().is_err());
+        assert!(storage.get_block_by_height(1).await.unwrap().is_none());
+        assert_eq!(storage.get_blockchain().await.stats.total_blocks, 0);
+
+        // Liveness: an empty block is proposed once the interval elapses
+        let proposed = producer.run_once(start + Duration::from_secs(10)).await.unwrap();
+        assert_eq!(proposed.unwrap().header.slot, 1);
+        assert!(matches!(event_rx.try_recv(), Ok(GlobalSyncEvent::BlockProposed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_pending_transactions_are_proposed_immediately() {
+        let (producer, storage, mempool, _event_rx) = producer(true).await;
+        let tx_id = TransactionId::new();
+        mempool.write().await.push(tx_id.clone());
+        storage.add_to_pool(PoolTransaction::new(tx_id, &HashMap::new(), Vec::new(), 10, Instant::now())).await.unwrap();
+
+        let block = producer.run_once(Instant::now()).await.unwrap().expect("block proposed");
+        assert_eq!(block.header.slot, 1);
+        assert!(mempool.read().await.is_empty());
+        assert_eq!(storage.pool_stats().await.0, 0);
+    }
+
+    #[tokio::test]
+    async fn test_blocks_take_pooled_transactions_by_priority() {
+        let (producer, storage, _mempool, _event_rx) = producer(true).await;
+        let now = Instant::now();
+        let pooled = |priority: &str, dependencies: Vec<TransactionId>| {
+            let metadata = HashMap::from([(POOL_PRIORITY_METADATA_KEY.to_string(), priority.to_string())]);
+            PoolTransaction::new(TransactionId::new(), &metadata, dependencies, 10, now)
+        };
+        let low = pooled("1", Vec::new());
+        let high = pooled("9", Vec::new());
+        let dependent = pooled("5", vec![low.transaction_id.clone()]);
+        for entry in [low.clone(), high.clone(), dependent.clone()] {
+            storage.add_to_pool(entry).await.unwrap();
+        }
+
+        // The low priority dependency is pulled ahead of its dependent
+        let block = producer.run_once(now).await.unwrap().expect("block proposed");
+        let order = [high.transaction_id, low.transaction_id, dependent.transaction_id];
+        assert_eq!(block.header.tx_root, receipt::transaction_root(&order));
+        let (pooled, stats) = storage.pool_stats().await;
+        assert_eq!((pooled, stats.total_added, stats.total_removed), (0, 3, 3));
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transactions_are_proposed_as_one_batch() {
+        let (producer, storage, _mempool, _event_rx) = producer(true).await;
+        for _ in 0..3 {
+            storage.add_to_pool(PoolTransaction::new(TransactionId::new(), &HashMap::new(), Vec::new(), 10, Instant::now())).await.unwrap();
+        }
+
+        producer.run_once(Instant::now()).await.unwrap().expect("block proposed");
+        let metrics = producer.consensus_engine.get_metrics_snapshot().await.unwrap();
+        assert_eq!((metrics.total_proposals, metrics.batches_proposed), (1, 1));
+        assert_eq!(metrics.avg_batch_size, 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_empty_blocks_proposed_without_suppression() {
+        let (producer, _storage, _mempool, _event_rx) = producer(false).await;
+        let block = producer.run_once(Instant::now()).await.unwrap();
+        assert!(block.is_some());
+    }
+}
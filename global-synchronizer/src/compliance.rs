@@ -0,0 +1,456 @@
+//! Transaction history exports for compliance.
+//!
+//! An export streams persisted transactions through a [`HistoryFilter`],
+//! replaces each requested [`RedactedField`] with [`REDACTED`] and encodes
+//! records one at a time, so no export holds the whole history in memory.
+//! Records come out in storage order, not by time.
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize, Serializer};
+
+use garp_common::types::{Transaction, TransactionCommand};
+use garp_common::{GarpError, GarpResult};
+
+use crate::config::SerializationFormat;
+use crate::storage::{DomainId, StoredTransaction, TransactionStatus};
+
+/// Replaces the value of every redacted field
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Metadata key recording the encoding of a stored transaction payload
+const ENCODING_METADATA_KEY: &str = "encoding";
+
+/// Which transactions an export includes; unset criteria match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoryFilter {
+    /// Created at or after this time, in unix milliseconds
+    #[serde(default)]
+    pub from_ms: Option<u64>,
+
+    /// Created at or before this time, in unix milliseconds
+    #[serde(default)]
+    pub to_ms: Option<u64>,
+
+    /// Source or target domain
+    #[serde(default)]
+    pub domain: Option<DomainId>,
+
+    /// Any of these statuses
+    #[serde(default)]
+    pub statuses: Vec<TransactionStatus>,
+
+    /// Submitter, signatory or observer of the transaction payload
+    #[serde(default)]
+    pub participant: Option<String>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, stored: &StoredTransaction, payload: Option<&Transaction>) -> bool {
+        let created_ms = unix_ms(stored.created_at);
+        self.from_ms.map_or(true, |from| created_ms >= from)
+            && self.to_ms.map_or(true, |to| created_ms <= to)
+            && self.domain.as_ref().map_or(true, |domain| {
+                stored.source_domain == *domain || stored.target_domains.contains(domain)
+            })
+            && (self.statuses.is_empty() || self.statuses.contains(&stored.status))
+            && self.participant.as_ref().map_or(true, |participant| {
+                payload.map_or(false, |tx| {
+                    tx.submitter.0 == *participant || payload_parties(tx).iter().any(|p| p == participant)
+                })
+            })
+    }
+}
+
+/// Fields an export can redact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactedField {
+    /// Raw transaction payload
+    Data,
+    /// Transaction metadata
+    Metadata,
+    /// Participant that submitted the payload
+    SourceAddress,
+    /// Signatories and observers of the payload
+    Participants,
+    SourceDomain,
+    TargetDomains,
+}
+
+/// A field value, or [`REDACTED`] in its place
+#[derive(Debug, Clone, PartialEq)]
+pub enum Redactable<T> {
+    Value(T),
+    Redacted,
+}
+
+impl<T> Redactable<T> {
+    fn new(field: RedactedField, redact: &[RedactedField], value: impl FnOnce() -> T) -> Self {
+        if redact.contains(&field) {
+            Redactable::Redacted
+        } else {
+            Redactable::Value(value())
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Redactable<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Redactable::Value(value) => value.serialize(serializer),
+            Redactable::Redacted => serializer.serialize_str(REDACTED),
+        }
+    }
+}
+
+/// One exported transaction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RedactedTransaction {
+    pub transaction_id: String,
+    pub transaction_type: String,
+    pub status: TransactionStatus,
+    pub source_domain: Redactable<DomainId>,
+    pub target_domains: Redactable<Vec<DomainId>>,
+    /// Submitter of the payload, when the payload could be decoded
+    pub source_address: Redactable<Option<String>>,
+    pub participants: Redactable<Vec<String>>,
+    /// Hex-encoded payload
+    pub data: Redactable<String>,
+    pub metadata: Redactable<BTreeMap<String, String>>,
+    pub block_height: Option<u64>,
+    pub created_at_ms: u64,
+    pub updated_at_ms: u64,
+}
+
+impl RedactedTransaction {
+    fn new(stored: StoredTransaction, payload: Option<&Transaction>, redact: &[RedactedField]) -> Self {
+        Self {
+            transaction_id: stored.transaction_id.to_string(),
+            transaction_type: stored.transaction_type,
+            status: stored.status,
+            source_domain: Redactable::new(RedactedField::SourceDomain, redact, || stored.source_domain),
+            target_domains: Redactable::new(RedactedField::TargetDomains, redact, || stored.target_domains),
+            source_address: Redactable::new(RedactedField::SourceAddress, redact, || payload.map(|tx| tx.submitter.0.clone())),
+            participants: Redactable::new(RedactedField::Participants, redact, || payload.map(payload_parties).unwrap_or_default()),
+            data: Redactable::new(RedactedField::Data, redact, || hex::encode(&stored.transaction_data)),
+            metadata: Redactable::new(RedactedField::Metadata, redact, || stored.metadata.into_iter().collect()),
+            block_height: stored.block_height,
+            created_at_ms: unix_ms(stored.created_at),
+            updated_at_ms: unix_ms(stored.updated_at),
+        }
+    }
+
+    /// Flat CSV row in [`CSV_HEADER`] order
+    fn csv_row(&self) -> GarpResult<String> {
+        let list = |items: &[String]| items.join(";");
+        let cells = [
+            self.transaction_id.clone(),
+            self.transaction_type.clone(),
+            serde_json::to_value(&self.status).map_err(encode_error)?.as_str().unwrap_or_default().to_string(),
+            csv_cell(&self.source_domain, Clone::clone),
+            csv_cell(&self.target_domains, |domains| list(domains)),
+            csv_cell(&self.source_address, |address| address.clone().unwrap_or_default()),
+            csv_cell(&self.participants, |participants| list(participants)),
+            csv_cell(&self.data, Clone::clone),
+            match &self.metadata {
+                Redactable::Value(metadata) => serde_json::to_string(metadata).map_err(encode_error)?,
+                Redactable::Redacted => REDACTED.to_string(),
+            },
+            self.block_height.map(|h| h.to_string()).unwrap_or_default(),
+            self.created_at_ms.to_string(),
+            self.updated_at_ms.to_string(),
+        ];
+        let escaped: Vec<String> = cells.iter().map(|cell| csv_escape(cell)).collect();
+        Ok(escaped.join(","))
+    }
+}
+
+const CSV_HEADER: &str = "transaction_id,transaction_type,status,source_domain,target_domains,source_address,participants,data,metadata,block_height,created_at_ms,updated_at_ms";
+
+fn csv_cell<T>(value: &Redactable<T>, format: impl FnOnce(&T) -> String) -> String {
+    match value {
+        Redactable::Value(value) => format(value),
+        Redactable::Redacted => REDACTED.to_string(),
+    }
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Encoding of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A single JSON array
+    #[default]
+    Json,
+    /// One flat row per transaction, after a header row
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// MIME type of the format
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "text/csv",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+/// Filter and redact a stream of stored transactions
+pub fn export(
+    transactions: BoxStream<'static, GarpResult<StoredTransaction>>,
+    filter: HistoryFilter,
+    redact: Vec<RedactedField>,
+) -> BoxStream<'static, GarpResult<RedactedTransaction>> {
+    transactions
+        .filter_map(move |stored| {
+            let record = stored.map(|stored| {
+                let payload = decode_payload(&stored);
+                filter
+                    .matches(&stored, payload.as_ref())
+                    .then(|| RedactedTransaction::new(stored, payload.as_ref(), &redact))
+            });
+            async move { record.transpose() }
+        })
+        .boxed()
+}
+
+/// Encode exported records as `format`, one chunk per record
+pub fn encode(
+    records: BoxStream<'static, GarpResult<RedactedTransaction>>,
+    format: ExportFormat,
+) -> BoxStream<'static, GarpResult<Vec<u8>>> {
+    let (header, footer): (&[u8], &[u8]) = match format {
+        ExportFormat::Json => (b"[", b"]"),
+        ExportFormat::Csv => (CSV_HEADER.as_bytes(), b""),
+        ExportFormat::Ndjson => (b"", b""),
+    };
+    let header = match format {
+        ExportFormat::Csv => [header, b"\n"].concat(),
+        _ => header.to_vec(),
+    };
+    let body = records.enumerate().map(move |(i, record)| {
+        let record = record?;
+        let mut chunk = Vec::new();
+        match format {
+            ExportFormat::Json => {
+                if i > 0 {
+                    chunk.push(b',');
+                }
+                serde_json::to_writer(&mut chunk, &record).map_err(encode_error)?;
+            }
+            ExportFormat::Csv => {
+                chunk.extend_from_slice(record.csv_row()?.as_bytes());
+                chunk.push(b'\n');
+            }
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(&mut chunk, &record).map_err(encode_error)?;
+                chunk.push(b'\n');
+            }
+        }
+        Ok(chunk)
+    });
+    stream::once(async move { Ok(header) })
+        .chain(body)
+        .chain(stream::once(async move { Ok(footer.to_vec()) }))
+        .filter(|chunk| std::future::ready(!matches!(chunk, Ok(bytes) if bytes.is_empty())))
+        .boxed()
+}
+
+fn encode_error(e: serde_json::Error) -> GarpError {
+    GarpError::Internal(format!("Failed to encode export record: {}", e))
+}
+
+/// The `garp_common::Transaction` payload of a stored transaction, if it has one
+fn decode_payload(stored: &StoredTransaction) -> Option<Transaction> {
+    let format = stored
+        .metadata
+        .get(ENCODING_METADATA_KEY)
+        .and_then(|encoding| SerializationFormat::from_header(encoding))
+        .unwrap_or_default();
+    format.decode(&stored.transaction_data).ok()
+}
+
+/// Signatories and observers named by a payload
+fn payload_parties(transaction: &Transaction) -> Vec<String> {
+    match &transaction.command {
+        TransactionCommand::Create { signatories, observers, .. } => {
+            signatories.iter().chain(observers).map(|p| p.0.clone()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GlobalSyncConfig;
+    use crate::storage::{ConsensusState, GlobalStorage, SettlementState};
+    use futures::TryStreamExt;
+    use garp_common::types::{ParticipantId, TransactionId};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn stored(source: &str, target: &str, status: TransactionStatus, created_at: SystemTime) -> StoredTransaction {
+        let id = TransactionId::new();
+        let payload = Transaction {
+            id: id.clone(),
+            submitter: ParticipantId::new(source),
+            command: TransactionCommand::Create {
+                template_id: "cross_domain".to_string(),
+                argument: serde_json::json!({}),
+                signatories: vec![ParticipantId::new(source)],
+                observers: vec![ParticipantId::new(target)],
+            },
+            created_at: chrono::Utc::now(),
+            signatures: Vec::new(),
+            encrypted_payload: None,
+        };
+        StoredTransaction {
+            transaction_id: id,
+            transaction_data: serde_json::to_vec(&payload).unwrap(),
+            transaction_type: "common_tx".to_string(),
+            source_domain: source.to_string(),
+            target_domains: vec![target.to_string()],
+            status,
+            consensus_state: ConsensusState {
+                phase: "received".to_string(),
+                votes: HashMap::new(),
+                required_votes: 0,
+                result: None,
+                proof: None,
+                started_at: created_at,
+                completed_at: None,
+            },
+            settlement_state: SettlementState {
+                settlement_id: None,
+                settlement_type: "none".to_string(),
+                domain_settlements: HashMap::new(),
+                proof: None,
+                started_at: None,
+                completed_at: None,
+            },
+            created_at,
+            updated_at: created_at,
+            block_height: None,
+            block_hash: None,
+            metadata: [
+                ("encoding".to_string(), "application/json".to_string()),
+                ("memo".to_string(), "a, \"quoted\" note".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        }
+    }
+
+    async fn storage_with_history() -> (GlobalStorage, Vec<StoredTransaction>) {
+        let storage = GlobalStorage::new(Arc::new(GlobalSyncConfig::default())).await.unwrap();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let history = vec![
+            stored("bank-a", "bank-b", TransactionStatus::Settled, t0),
+            stored("bank-a", "bank-c", TransactionStatus::Failed, t0 + Duration::from_secs(60)),
+            stored("bank-b", "bank-c", TransactionStatus::Settled, t0 + Duration::from_secs(120)),
+        ];
+        for transaction in &history {
+            storage.store_transaction(transaction.clone()).await.unwrap();
+        }
+        (storage, history)
+    }
+
+    async fn run(storage: &GlobalStorage, filter: HistoryFilter, redact: Vec<RedactedField>) -> Vec<RedactedTransaction> {
+        let mut records: Vec<RedactedTransaction> =
+            export(storage.stream_transactions().await.unwrap(), filter, redact).try_collect().await.unwrap();
+        records.sort_by_key(|record| record.created_at_ms);
+        records
+    }
+
+    #[tokio::test]
+    async fn test_export_filters_and_redacts() {
+        let (storage, history) = storage_with_history().await;
+        let t0_ms = unix_ms(history[0].created_at);
+
+        let all = run(&storage, HistoryFilter::default(), Vec::new()).await;
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].source_address, Redactable::Value(Some("bank-a".to_string())));
+        assert_eq!(all[0].data, Redactable::Value(hex::encode(&history[0].transaction_data)));
+
+        let by_domain = run(&storage, HistoryFilter { domain: Some("bank-c".to_string()), ..Default::default() }, Vec::new()).await;
+        assert_eq!(by_domain.iter().map(|r| r.created_at_ms - t0_ms).collect::<Vec<_>>(), vec![60_000, 120_000]);
+
+        let settled_since = HistoryFilter {
+            from_ms: Some(t0_ms + 1),
+            statuses: vec![TransactionStatus::Settled],
+            ..Default::default()
+        };
+        let records = run(&storage, settled_since, Vec::new()).await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].transaction_id, history[2].transaction_id.to_string());
+
+        let by_participant = HistoryFilter { participant: Some("bank-b".to_string()), to_ms: Some(t0_ms + 60_000), ..Default::default() };
+        let records = run(&storage, by_participant, vec![RedactedField::Data, RedactedField::Metadata, RedactedField::SourceAddress]).await;
+        assert_eq!(records.len(), 1);
+        let json = serde_json::to_value(&records[0]).unwrap();
+        assert_eq!(json["data"], REDACTED);
+        assert_eq!(json["metadata"], REDACTED);
+        assert_eq!(json["source_address"], REDACTED);
+        assert_eq!(json["source_domain"], "bank-a");
+        assert_eq!(json["target_domains"], serde_json::json!(["bank-b"]));
+        assert_eq!(json["status"], "Settled");
+    }
+
+    #[tokio::test]
+    async fn test_export_formats() {
+        let (storage, history) = storage_with_history().await;
+        let filter = HistoryFilter { domain: Some("bank-b".to_string()), ..Default::default() };
+        let redact = vec![RedactedField::Data];
+        let (storage, filter, redact) = (&storage, &filter, &redact);
+        let encoded = move |format| async move {
+            let records = export(storage.stream_transactions().await.unwrap(), filter.clone(), redact.clone());
+            let chunks: Vec<Vec<u8>> = encode(records, format).try_collect().await.unwrap();
+            String::from_utf8(chunks.concat()).unwrap()
+        };
+
+        let json: Vec<serde_json::Value> = serde_json::from_str(&encoded(ExportFormat::Json).await).unwrap();
+        assert_eq!(json.len(), 2);
+        assert!(json.iter().all(|record| record["data"] == REDACTED));
+
+        let ndjson = encoded(ExportFormat::Ndjson).await;
+        let lines: Vec<serde_json::Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|record| record["data"] == REDACTED));
+
+        let csv = encoded(ExportFormat::Csv).await;
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], CSV_HEADER);
+        assert_eq!(rows.len(), 3);
+        let first = rows.iter().find(|row| row.starts_with(&history[0].transaction_id.to_string())).unwrap();
+        assert!(first.contains(",Settled,bank-a,bank-b,bank-a,bank-a;bank-b,[REDACTED],"));
+        // Metadata is a JSON cell, quoted because it holds commas and quotes
+        assert!(first.contains(r#""{""encoding"":""application/json"",""memo"":""a, \""quoted\"" note""}""#));
+
+        let empty = HistoryFilter { domain: Some("nobody".to_string()), ..Default::default() };
+        let records = export(storage.stream_transactions().await.unwrap(), empty, Vec::new());
+        let chunks: Vec<Vec<u8>> = encode(records, ExportFormat::Json).try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), b"[]");
+    }
+}
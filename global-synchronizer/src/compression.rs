@@ -0,0 +1,160 @@
+//! Payload compression for network messages
+//!
+//! Every frame starts with a one-byte codec tag followed by the (possibly
+//! compressed) payload, so receivers can decode without out-of-band state.
+//! Senders only compress payloads above `network.compression_threshold_bytes`
+//! and only with codecs the peer lists in [`PeerCapabilities::features`]
+//! (see [`Codec::feature`]).
+//!
+//! [`PeerCapabilities::features`]: crate::network::PeerCapabilities
+
+use std::io::{Read, Write};
+
+use garp_common::error::NetworkError;
+use garp_common::GarpResult;
+
+pub use crate::config::CompressionAlgorithm as Codec;
+
+const TAG_NONE: u8 = 0;
+const TAG_LZ4: u8 = 1;
+const TAG_ZSTD: u8 = 2;
+const TAG_GZIP: u8 = 3;
+
+/// zstd level; favours speed over ratio for on-path compression
+const ZSTD_LEVEL: i32 = 3;
+
+impl Codec {
+    /// Every codec this node can decode
+    pub const SUPPORTED: [Codec; 3] = [Codec::LZ4, Codec::Zstd, Codec::Gzip];
+
+    /// Peer capability feature advertising support for the codec
+    pub fn feature(self) -> &'static str {
+        match self {
+            Self::None => "compression/none",
+            Self::LZ4 => "compression/lz4",
+            Self::Zstd => "compression/zstd",
+            Self::Gzip => "compression/gzip",
+        }
+    }
+
+    fn tag(self) -> u8 {
+        match self {
+            Self::None => TAG_NONE,
+            Self::LZ4 => TAG_LZ4,
+            Self::Zstd => TAG_ZSTD,
+            Self::Gzip => TAG_GZIP,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            TAG_NONE => Some(Self::None),
+            TAG_LZ4 => Some(Self::LZ4),
+            TAG_ZSTD => Some(Self::Zstd),
+            TAG_GZIP => Some(Self::Gzip),
+            _ => None,
+        }
+    }
+}
+
+fn codec_error(codec: Codec, e: std::io::Error) -> NetworkError {
+    NetworkError::InvalidMessageFormat(format!("{:?} codec failed: {}", codec, e))
+}
+
+/// Frame `payload` with `codec`
+pub fn encode_frame(payload: &[u8], codec: Codec) -> GarpResult<Vec<u8>> {
+    let mut frame = vec![codec.tag()];
+    let result = match codec {
+        Codec::None => {
+            frame.extend_from_slice(payload);
+            Ok(())
+        }
+        Codec::LZ4 => lz4::EncoderBuilder::new().build(&mut frame).and_then(|mut encoder| {
+            encoder.write_all(payload)?;
+            encoder.finish().1
+        }),
+        Codec::Zstd => zstd::stream::copy_encode(payload, &mut frame, ZSTD_LEVEL),
+        Codec::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(&mut frame, flate2::Compression::fast());
+            encoder.write_all(payload).and_then(|_| encoder.finish().map(|_| ()))
+        }
+    };
+    result.map_err(|e| codec_error(codec, e))?;
+    Ok(frame)
+}
+
+/// Codec of an encoded frame
+pub fn frame_codec(frame: &[u8]) -> GarpResult<Codec> {
+    frame
+        .first()
+        .and_then(|tag| Codec::from_tag(*tag))
+        .ok_or_else(|| NetworkError::InvalidMessageFormat("Unknown frame codec".to_string()).into())
+}
+
+/// Decode a frame, refusing payloads that expand beyond `max_size` bytes
+pub fn decode_frame(frame: &[u8], max_size: usize) -> GarpResult<Vec<u8>> {
+    let codec = frame_codec(frame)?;
+    let body = &frame[1..];
+    if codec == Codec::None {
+        if body.len() > max_size {
+            return Err(NetworkError::MessageTooLarge { size: body.len(), limit: max_size }.into());
+        }
+        return Ok(body.to_vec());
+    }
+
+    let reader: Box<dyn Read + '_> = match codec {
+        Codec::LZ4 => Box::new(lz4::Decoder::new(body).map_err(|e| codec_error(codec, e))?),
+        Codec::Zstd => Box::new(zstd::stream::read::Decoder::new(body).map_err(|e| codec_error(codec, e))?),
+        Codec::Gzip => Box::new(flate2::read::GzDecoder::new(body)),
+        Codec::None => unreachable!(),
+    };
+
+    // Read one byte past the limit to detect oversized payloads without
+    // inflating all of them
+    let mut payload = Vec::new();
+    reader
+        .take((max_size as u64).saturating_add(1))
+        .read_to_end(&mut payload)
+        .map_err(|e| codec_error(codec, e))?;
+    if payload.len() > max_size {
+        return Err(NetworkError::MessageTooLarge { size: payload.len(), limit: max_size }.into());
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use garp_common::GarpError;
+
+    fn payload() -> Vec<u8> {
+        (0..64 * 1024).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_round_trip_all_codecs() {
+        let payload = payload();
+        for codec in [Codec::None, Codec::LZ4, Codec::Zstd, Codec::Gzip] {
+            let frame = encode_frame(&payload, codec).unwrap();
+            assert_eq!(frame_codec(&frame).unwrap(), codec);
+            if codec != Codec::None {
+                assert!(frame.len() < payload.len(), "{:?} did not compress", codec);
+            }
+            assert_eq!(decode_frame(&frame, payload.len()).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_decode_enforces_expanded_size() {
+        let payload = payload();
+        for codec in [Codec::None, Codec::Zstd] {
+            let frame = encode_frame(&payload, codec).unwrap();
+            assert!(matches!(
+                decode_frame(&frame, payload.len() - 1),
+                Err(GarpError::Network(NetworkError::MessageTooLarge { .. }))
+            ));
+        }
+        assert!(decode_frame(&[0xff, 1, 2], 16).is_err());
+        assert!(decode_frame(&[], 16).is_err());
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::Duration;
-use garp_common::GarpResult;
+use chrono::{DateTime, Utc};
+use garp_common::{GarpError, GarpResult, ReputationConfig};
 
 /// Global Synchronizer configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +39,14 @@ pub struct GlobalSyncConfig {
     
     /// Monitoring configuration
     pub monitoring: MonitoringConfig,
+    
+    /// Mempool configuration
+    #[serde(default)]
+    pub mempool: MempoolConfig,
+    
+    /// Domain and validator reputation scoring
+    #[serde(default)]
+    pub reputation: ReputationConfig,
 }
 
 /// Node configuration
@@ -96,26 +105,114 @@ pub struct ConsensusConfig {
     /// Block time in milliseconds
     pub block_time_ms: u64,
     
+    /// Skip proposing blocks while the mempool is empty
+    #[serde(default)]
+    pub suppress_empty_blocks: bool,
+    
+    /// Longest gap between blocks while suppressing empty blocks; an empty
+    /// block is proposed once this elapses to keep the chain live
+    #[serde(default = "default_max_empty_block_interval_secs")]
+    pub max_empty_block_interval_secs: u64,
+    
     /// Timeout for consensus rounds
     pub consensus_timeout_ms: u64,
     
     /// Maximum transactions per block
     pub max_transactions_per_block: usize,
     
+    /// Maximum total transaction bytes per block
+    #[serde(default = "default_max_block_bytes")]
+    pub max_block_bytes: usize,
+    
+    /// Run one consensus round per block for the whole batch of pooled
+    /// transactions, instead of one round per transaction
+    #[serde(default = "default_batch_proposals")]
+    pub batch_proposals: bool,
+    
+    /// Maximum transactions in a batch proposal
+    #[serde(default = "default_max_batch_transactions")]
+    pub max_batch_transactions: usize,
+    
+    /// Maximum total transaction bytes in a batch proposal
+    #[serde(default = "default_max_batch_bytes")]
+    pub max_batch_bytes: usize,
+    
     /// Byzantine fault tolerance threshold (f in 3f+1)
     pub byzantine_threshold: usize,
     
     /// Enable fast path optimization
     pub enable_fast_path: bool,
     
-    /// Checkpoint interval
+    /// Checkpoint interval; the state is snapshotted every this many blocks
     pub checkpoint_interval: u64,
+    
+    /// State snapshots retained; older ones are pruned
+    #[serde(default = "default_snapshot_retention")]
+    pub snapshot_retention: usize,
+    
+    /// Blocks per epoch; validator set changes take effect at epoch boundaries
+    #[serde(default = "default_epoch_length_blocks")]
+    pub epoch_length_blocks: u64,
+    
+    /// Blocks this far below the head are final; blocks above it may still
+    /// be orphaned by a reorganization or a finality certificate
+    #[serde(default = "default_finality_depth")]
+    pub finality_depth: u64,
+    
+    /// Rounds over which validator vote participation is measured
+    #[serde(default = "default_participation_window_rounds")]
+    pub participation_window_rounds: usize,
+    
+    /// Validators labelled individually in the Prometheus participation
+    /// export; the rest are aggregated as "other"
+    #[serde(default = "default_metrics_top_validators")]
+    pub metrics_top_validators: usize,
     /// Detailed consensus parameters
     pub params: ConsensusParams,
     /// Network limits applied to consensus gossip/vote channels
     pub network_limits: ConsensusNetworkLimits,
 }
 
+fn default_max_empty_block_interval_secs() -> u64 {
+    30
+}
+
+fn default_max_block_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_batch_proposals() -> bool {
+    true
+}
+
+fn default_max_batch_transactions() -> usize {
+    500
+}
+
+fn default_max_batch_bytes() -> usize {
+    512 * 1024
+}
+
+fn default_epoch_length_blocks() -> u64 {
+    100
+}
+
+fn default_finality_depth() -> u64 {
+    2
+}
+
+fn default_snapshot_retention() -> usize {
+    2
+}
+
+fn default_participation_window_rounds() -> usize {
+    100
+}
+
+fn default_metrics_top_validators() -> usize {
+    10
+}
+
 /// Consensus algorithm
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConsensusAlgorithm {
@@ -190,6 +287,123 @@ pub struct CrossDomainConfig {
     
     /// Health check interval
     pub health_check_interval_ms: u64,
+    
+    /// Interval at which time-locked transactions are checked for maturity
+    #[serde(default = "default_timelock_check_interval_ms")]
+    pub timelock_check_interval_ms: u64,
+    
+    /// How long the counterparty may challenge a state channel close
+    #[serde(default = "default_channel_challenge_window_secs")]
+    pub channel_challenge_window_secs: u64,
+    
+    /// Interval at which closing state channels are checked for settlement
+    #[serde(default = "default_channel_settlement_interval_ms")]
+    pub channel_settlement_interval_ms: u64,
+    
+    /// How long governance proposals accept domain votes
+    #[serde(default = "default_governance_voting_period_secs")]
+    pub governance_voting_period_secs: u64,
+    
+    /// Share of total domain weight (in thousandths) approving votes must exceed
+    #[serde(default = "default_governance_approval_threshold_thousandths")]
+    pub governance_approval_threshold_thousandths: u64,
+    
+    /// Interval at which governance proposals are tallied
+    #[serde(default = "default_governance_check_interval_ms")]
+    pub governance_check_interval_ms: u64,
+    
+    /// Share of total domain weight (in thousandths) that must vote for a
+    /// proposal's result to count
+    #[serde(default = "default_governance_quorum_thousandths")]
+    pub governance_quorum_thousandths: u64,
+    
+    /// Interval at which atomic swaps past their timelock are refunded
+    #[serde(default = "default_swap_check_interval_ms")]
+    pub swap_check_interval_ms: u64,
+    
+    /// Interval at which aborted transactions are compensated
+    #[serde(default = "default_compensation_check_interval_ms")]
+    pub compensation_check_interval_ms: u64,
+    
+    /// Most submitted transactions validated at once
+    #[serde(default = "default_validation_parallelism")]
+    pub validation_parallelism: usize,
+}
+
+fn default_timelock_check_interval_ms() -> u64 {
+    1000
+}
+
+fn default_channel_challenge_window_secs() -> u64 {
+    3600
+}
+
+fn default_channel_settlement_interval_ms() -> u64 {
+    5000
+}
+
+fn default_batching_enabled() -> bool {
+    true
+}
+
+fn default_batch_window_ms() -> u64 {
+    500
+}
+
+fn default_compensation_max_retries() -> u32 {
+    3
+}
+
+fn default_compensation_retry_interval_ms() -> u64 {
+    5000
+}
+
+fn default_zk_params_path() -> String {
+    "keys/settlement-zk.params".to_string()
+}
+
+fn default_escrow_poll_interval_ms() -> u64 {
+    5000
+}
+
+fn default_compression_threshold_bytes() -> usize {
+    4096
+}
+
+fn default_max_message_bytes() -> usize {
+    4 * 1024 * 1024
+}
+
+fn default_state_sync_chunk_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_governance_voting_period_secs() -> u64 {
+    86400
+}
+
+fn default_governance_approval_threshold_thousandths() -> u64 {
+    500
+}
+
+fn default_governance_quorum_thousandths() -> u64 {
+    334
+}
+
+fn default_governance_check_interval_ms() -> u64 {
+    5000
+}
+
+fn default_swap_check_interval_ms() -> u64 {
+    1000
+}
+
+fn default_compensation_check_interval_ms() -> u64 {
+    1000
+}
+
+fn default_validation_parallelism() -> usize {
+    8
 }
 
 /// Domain information
@@ -244,6 +458,22 @@ pub struct DomainDiscoveryConfig {
     
     /// Maximum domains to discover
     pub max_discovered_domains: usize,
+    
+    /// Lifetime of a domain registration between heartbeats
+    #[serde(default = "default_registration_ttl_secs")]
+    pub registration_ttl_secs: u64,
+    
+    /// Interval at which lapsed registrations are expired
+    #[serde(default = "default_registration_expiry_check_interval_ms")]
+    pub expiry_check_interval_ms: u64,
+}
+
+fn default_registration_ttl_secs() -> u64 {
+    90
+}
+
+fn default_registration_expiry_check_interval_ms() -> u64 {
+    5000
 }
 
 /// Discovery protocol
@@ -290,6 +520,45 @@ pub struct SettlementConfig {
     /// Batch size for settlement
     pub batch_size: usize,
     
+    /// Group settlements per target domain into batches
+    #[serde(default = "default_batching_enabled")]
+    pub batching_enabled: bool,
+    
+    /// How long a domain's batch stays open before it is submitted
+    #[serde(default = "default_batch_window_ms")]
+    pub batch_window_ms: u64,
+    
+    /// Net opposing pending asset transfers between the same pair of domains
+    /// into a single transfer
+    #[serde(default)]
+    pub enable_netting: bool,
+    
+    /// Settlement fee charged per participating domain
+    #[serde(default)]
+    pub fee_per_domain: u64,
+    
+    /// Settlement fee charged per byte of transaction data
+    #[serde(default)]
+    pub fee_per_byte: u64,
+    
+    /// Attempts to deliver a rollback compensation to a domain before escalating
+    #[serde(default = "default_compensation_max_retries")]
+    pub compensation_max_retries: u32,
+    
+    /// How long to wait for a compensation acknowledgement before resending
+    #[serde(default = "default_compensation_retry_interval_ms")]
+    pub compensation_retry_interval_ms: u64,
+    
+    /// Groth16 parameters for zero-knowledge settlement proofs; generated
+    /// and written here on first use if missing
+    #[serde(default = "default_zk_params_path")]
+    pub zk_params_path: String,
+    
+    /// How often the conditions of escrowed settlements are checked,
+    /// including polls of their oracles
+    #[serde(default = "default_escrow_poll_interval_ms")]
+    pub escrow_poll_interval_ms: u64,
+    
     /// Settlement timeout
     pub settlement_timeout_ms: u64,
     
@@ -365,15 +634,51 @@ pub struct NetworkConfig {
     /// TLS private key path
     pub tls_key_path: Option<String>,
     
+    /// CA certificate that signs every node's TLS certificate
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    
     /// Enable compression
     pub enable_compression: bool,
     
     /// Compression algorithm
     pub compression_algorithm: CompressionAlgorithm,
+    
+    /// Payloads above this size are compressed
+    #[serde(default = "default_compression_threshold_bytes")]
+    pub compression_threshold_bytes: usize,
+    
+    /// Largest frame this node sends; larger messages are rejected
+    #[serde(default = "default_max_message_bytes")]
+    pub max_send_message_bytes: usize,
+    
+    /// Largest payload this node accepts, after decompression
+    #[serde(default = "default_max_message_bytes")]
+    pub max_receive_message_bytes: usize,
+    
+    /// Target size of a state sync response chunk
+    #[serde(default = "default_state_sync_chunk_bytes")]
+    pub state_sync_chunk_bytes: usize,
+    
+    /// How cross-domain messages reach domains without a direct connection
+    #[serde(default)]
+    pub routing: DirectOrDht,
+}
+
+/// Routing of cross-domain messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DirectOrDht {
+    /// Send to the target domain only, which needs a full mesh of connections
+    #[default]
+    Direct,
+    
+    /// Relay through the closest peers in a Kademlia-style DHT when the
+    /// target is not connected
+    Dht,
 }
 
 /// Compression algorithm
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CompressionAlgorithm {
     None,
     LZ4,
@@ -514,6 +819,108 @@ pub struct DatabaseConfig {
     
     /// Enable connection pooling
     pub enable_pooling: bool,
+    
+    /// Domain state versions retained per domain for as-of reads
+    #[serde(default = "default_max_retained_versions")]
+    pub max_retained_versions: usize,
+    
+    /// Days of key history the Postgres backend keeps for point-in-time
+    /// recovery; 0 keeps it forever
+    #[serde(default = "default_history_retention_days")]
+    pub history_retention_days: u64,
+    
+    /// In-memory LSM backend tuning, used with `url = "lsm://local"`
+    #[serde(default)]
+    pub lsm: LsmConfig,
+    
+    /// Bloom filter answering transaction existence checks
+    #[serde(default)]
+    pub existence_filter: ExistenceFilterConfig,
+    
+    /// Lag thresholds replication targets are checked against
+    #[serde(default)]
+    pub replication: ReplicationLagConfig,
+}
+
+fn default_max_retained_versions() -> usize {
+    16
+}
+
+fn default_history_retention_days() -> u64 {
+    7
+}
+
+/// In-memory LSM storage backend configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LsmConfig {
+    /// Memtable size that triggers a flush to a new SSTable
+    pub memtable_max_bytes: usize,
+    
+    /// SSTables smaller than this are merged by compaction
+    pub compaction_threshold_bytes: usize,
+}
+
+impl Default for LsmConfig {
+    fn default() -> Self {
+        Self {
+            memtable_max_bytes: 4 * 1024 * 1024,
+            compaction_threshold_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Sizing of the bloom filter in front of stored transactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExistenceFilterConfig {
+    /// Transactions the filter is sized for
+    pub expected_items: usize,
+    
+    /// False-positive rate at `expected_items`; sets the bit array size
+    /// unless `bitmap_bytes` is given
+    pub false_positive_rate: f64,
+    
+    /// Bit array size in bytes, overriding the size derived from
+    /// `false_positive_rate`
+    #[serde(default)]
+    pub bitmap_bytes: Option<usize>,
+}
+
+impl Default for ExistenceFilterConfig {
+    fn default() -> Self {
+        Self {
+            expected_items: 1_000_000,
+            false_positive_rate: 0.01,
+            bitmap_bytes: None,
+        }
+    }
+}
+
+/// Replication lag alerting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicationLagConfig {
+    /// Lag above which a target raises a warning alert
+    pub max_acceptable_lag_ms: u64,
+    
+    /// Lag above which a target raises a critical alert; on a primary
+    /// replica this also degrades storage health
+    pub critical_lag_threshold_ms: u64,
+    
+    /// Interval between lag checks
+    pub check_interval_ms: u64,
+    
+    /// Alerts kept for the admin API, oldest dropped first
+    pub max_buffered_alerts: usize,
+}
+
+impl Default for ReplicationLagConfig {
+    fn default() -> Self {
+        Self {
+            max_acceptable_lag_ms: 5_000,
+            critical_lag_threshold_ms: 30_000,
+            check_interval_ms: 10_000,
+            max_buffered_alerts: 1_000,
+        }
+    }
 }
 
 /// API configuration
@@ -548,6 +955,34 @@ pub struct ApiConfig {
     
     /// JWT secret
     pub jwt_secret: Option<String>,
+    
+    /// Encoding of stored transaction payloads and default API encoding
+    #[serde(default)]
+    pub serialization_format: SerializationFormat,
+    
+    /// Maximum live subscriptions per `/ws` connection
+    #[serde(default = "default_ws_max_subscriptions")]
+    pub ws_max_subscriptions: usize,
+    
+    /// Keep maintenance mode across restarts
+    #[serde(default)]
+    pub persist_maintenance_mode: bool,
+}
+
+fn default_ws_max_subscriptions() -> usize {
+    16
+}
+
+/// Wire encoding for transactions and API payloads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SerializationFormat {
+    /// JSON
+    #[default]
+    Json,
+    
+    /// CBOR (RFC 8949)
+    Cbor,
 }
 
 /// Security configuration
@@ -571,8 +1006,52 @@ pub struct SecurityConfig {
     /// Key rotation interval in hours
     pub key_rotation_interval_hours: u64,
     
+    /// Encrypted keystore holding the node signing keys. Created on first
+    /// start, importing `SYNC_NODE_ED25519_SK_HEX` if set; unset keeps the
+    /// plaintext key from the environment and disables rotation.
+    #[serde(default)]
+    pub keystore_path: Option<String>,
+    
+    /// Environment variable holding the keystore passphrase
+    #[serde(default = "default_keystore_passphrase_env")]
+    pub keystore_passphrase_env: String,
+    
+    /// Seconds peers keep accepting the previous key after a rotation
+    #[serde(default = "default_key_rotation_overlap_secs")]
+    pub key_rotation_overlap_secs: u64,
+    
     /// Enable audit logging
     pub enable_audit_logging: bool,
+    
+    /// Keys allowed to sign emergency actions
+    #[serde(default)]
+    pub emergency_keys: Vec<EmergencyKey>,
+    
+    /// Distinct emergency key signatures an emergency action needs
+    #[serde(default = "default_emergency_quorum")]
+    pub emergency_quorum: usize,
+}
+
+fn default_emergency_quorum() -> usize {
+    2
+}
+
+fn default_keystore_passphrase_env() -> String {
+    "GARP_KEYSTORE_PASSPHRASE".to_string()
+}
+
+fn default_key_rotation_overlap_secs() -> u64 {
+    3600
+}
+
+/// Designated emergency signing key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyKey {
+    /// Key ID that signatures refer to
+    pub key_id: String,
+    
+    /// Hex-encoded Ed25519 public key
+    pub public_key: String,
 }
 
 /// Encryption algorithm
@@ -646,6 +1125,40 @@ pub struct BatchProcessingConfig {
     pub max_concurrent_batches: usize,
 }
 
+/// Mempool configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolConfig {
+    /// Seconds a dependency may stay unresolved before it is treated as timed out
+    pub max_dependency_staleness_secs: u64,
+    
+    /// Transactions the pool holds; beyond this the lowest priority ones are evicted
+    #[serde(default = "default_max_pool_size")]
+    pub max_pool_size: usize,
+    
+    /// Seconds of waiting that raise a pooled transaction's priority by one,
+    /// so low priority transactions are eventually proposed; 0 disables aging
+    #[serde(default = "default_pool_age_boost_secs")]
+    pub pool_age_boost_secs: u64,
+}
+
+fn default_max_pool_size() -> usize {
+    10000
+}
+
+fn default_pool_age_boost_secs() -> u64 {
+    10
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            max_dependency_staleness_secs: 300,
+            max_pool_size: default_max_pool_size(),
+            pool_age_boost_secs: default_pool_age_boost_secs(),
+        }
+    }
+}
+
 /// Monitoring configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitoringConfig {
@@ -699,6 +1212,7 @@ pub struct LoggingConfig {
 /// Log format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LogFormat {
+    /// One JSON object per line, with every event field at the top level
     Json,
     Pretty,
     Compact,
@@ -763,6 +1277,15 @@ impl GlobalSyncConfig {
                         self.consensus.min_validators, min_nodes_for_bft, self.consensus.byzantine_threshold)
             ));
         }
+        if self.consensus.max_batch_transactions == 0 || self.consensus.max_batch_bytes == 0 {
+            return Err(garp_common::GarpError::ConfigError("max_batch_transactions and max_batch_bytes must be > 0".to_string()));
+        }
+        if self.consensus.participation_window_rounds == 0 {
+            return Err(garp_common::GarpError::ConfigError("participation_window_rounds must be > 0".to_string()));
+        }
+        if self.consensus.epoch_length_blocks == 0 {
+            return Err(garp_common::GarpError::ConfigError("epoch_length_blocks must be > 0".to_string()));
+        }
 
         // Validate extended consensus params
         if self.consensus.params.quorum_ratio_thousandths == 0 || self.consensus.params.quorum_ratio_thousandths > 1000 {
@@ -785,11 +1308,81 @@ impl GlobalSyncConfig {
         if self.consensus.network_limits.burst_capacity == 0 {
             return Err(garp_common::GarpError::ConfigError("burst_capacity must be > 0".to_string()));
         }
+
+        // Validate settlement batching
+        if self.settlement.batch_size == 0 {
+            return Err(garp_common::GarpError::ConfigError("settlement batch_size must be > 0".to_string()));
+        }
+        if self.settlement.batch_window_ms == 0 {
+            return Err(garp_common::GarpError::ConfigError("settlement batch_window_ms must be > 0".to_string()));
+        }
+        if self.settlement.compensation_max_retries == 0 {
+            return Err(garp_common::GarpError::ConfigError("settlement compensation_max_retries must be > 0".to_string()));
+        }
+        if self.settlement.compensation_retry_interval_ms == 0 {
+            return Err(garp_common::GarpError::ConfigError("settlement compensation_retry_interval_ms must be > 0".to_string()));
+        }
+        if self.settlement.escrow_poll_interval_ms == 0 {
+            return Err(garp_common::GarpError::ConfigError("settlement escrow_poll_interval_ms must be > 0".to_string()));
+        }
+        
+        // Validate cross-domain governance
+        if self.cross_domain.governance_voting_period_secs == 0 {
+            return Err(garp_common::GarpError::ConfigError("governance_voting_period_secs must be > 0".to_string()));
+        }
+        if self.cross_domain.governance_approval_threshold_thousandths >= 1000 {
+            return Err(garp_common::GarpError::ConfigError("governance_approval_threshold_thousandths must be < 1000".to_string()));
+        }
+        if self.cross_domain.discovery.registration_ttl_secs == 0 {
+            return Err(garp_common::GarpError::ConfigError("discovery registration_ttl_secs must be > 0".to_string()));
+        }
+        if self.cross_domain.discovery.expiry_check_interval_ms == 0 {
+            return Err(garp_common::GarpError::ConfigError("discovery expiry_check_interval_ms must be > 0".to_string()));
+        }
+        if self.cross_domain.validation_parallelism == 0 {
+            return Err(garp_common::GarpError::ConfigError("cross_domain validation_parallelism must be > 0".to_string()));
+        }
+        
+        // Validate network message limits
+        if self.network.max_send_message_bytes == 0 || self.network.max_receive_message_bytes == 0 {
+            return Err(garp_common::GarpError::ConfigError("network message size limits must be > 0".to_string()));
+        }
+        if self.network.state_sync_chunk_bytes == 0 || self.network.state_sync_chunk_bytes > self.network.max_send_message_bytes {
+            return Err(garp_common::GarpError::ConfigError("state_sync_chunk_bytes must be in (0, max_send_message_bytes]".to_string()));
+        }
+        if self.api.ws_max_subscriptions == 0 {
+            return Err(garp_common::GarpError::ConfigError("api.ws_max_subscriptions must be > 0".to_string()));
+        }
+        
+        // Validate reputation scoring
+        if self.reputation.max_score <= 0.0 {
+            return Err(garp_common::GarpError::ConfigError("reputation max_score must be > 0".to_string()));
+        }
+        if !(0.0..=self.reputation.max_score).contains(&self.reputation.initial_score) {
+            return Err(garp_common::GarpError::ConfigError("reputation initial_score must be in [0, max_score]".to_string()));
+        }
         
         // Validate database URL
         if self.database.url.is_empty() {
             return Err(garp_common::GarpError::ConfigError("Database URL cannot be empty".to_string()));
         }
+        if self.database.lsm.memtable_max_bytes == 0 {
+            return Err(garp_common::GarpError::ConfigError("lsm memtable_max_bytes must be > 0".to_string()));
+        }
+        let filter = &self.database.existence_filter;
+        if filter.expected_items == 0 || filter.bitmap_bytes == Some(0) {
+            return Err(garp_common::GarpError::ConfigError("existence_filter expected_items and bitmap_bytes must be > 0".to_string()));
+        }
+        if !(filter.false_positive_rate > 0.0 && filter.false_positive_rate < 1.0) {
+            return Err(garp_common::GarpError::ConfigError("existence_filter false_positive_rate must be in (0, 1)".to_string()));
+        }
+        let replication = &self.database.replication;
+        if replication.max_acceptable_lag_ms == 0 || replication.check_interval_ms == 0 {
+            return Err(garp_common::GarpError::ConfigError("replication max_acceptable_lag_ms and check_interval_ms must be > 0".to_string()));
+        }
+        if replication.critical_lag_threshold_ms < replication.max_acceptable_lag_ms {
+            return Err(garp_common::GarpError::ConfigError("replication critical_lag_threshold_ms must be >= max_acceptable_lag_ms".to_string()));
+        }
         
         // Validate Kafka configuration
         if self.kafka.bootstrap_servers.is_empty() {
@@ -805,6 +1398,10 @@ impl GlobalSyncConfig {
             return Err(garp_common::GarpError::ConfigError("Public key path cannot be empty".to_string()));
         }
         
+        if self.security.key_rotation_overlap_secs == 0 {
+            return Err(garp_common::GarpError::ConfigError("security key_rotation_overlap_secs must be > 0".to_string()));
+        }
+        
         Ok(())
     }
     
@@ -829,6 +1426,143 @@ impl GlobalSyncConfig {
     }
 }
 
+/// Parameters a running node applies on reload, as dotted paths covering
+/// everything nested beneath them. Anything else needs a restart.
+pub const DYNAMIC_CONFIG_PARAMS: &[&str] = &[
+    "cross_domain.transaction_timeout_ms",
+    "cross_domain.timelock_check_interval_ms",
+    "cross_domain.channel_settlement_interval_ms",
+    "cross_domain.governance_check_interval_ms",
+    "cross_domain.swap_check_interval_ms",
+    "cross_domain.compensation_check_interval_ms",
+    "mempool.max_pool_size",
+    "mempool.pool_age_boost_secs",
+    "performance.cache.cache_size_mb",
+    "performance.cache.cache_ttl_seconds",
+    "monitoring.logging.level",
+];
+
+/// Whether a dotted parameter path can change without a restart
+pub fn is_dynamic_param(path: &str) -> bool {
+    DYNAMIC_CONFIG_PARAMS.iter().any(|param| {
+        path.strip_prefix(param).map_or(false, |rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+/// Changed configuration parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// Dotted parameter path
+    pub path: String,
+    
+    /// Running value
+    pub old: serde_json::Value,
+    
+    /// Reloaded value
+    pub new: serde_json::Value,
+}
+
+/// Audit entry of a configuration reload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigReloadRecord {
+    /// Position in the reload log, starting at 0
+    pub sequence: u64,
+    
+    /// Who asked for the reload
+    pub requested_by: String,
+    
+    /// Dynamic parameters now in effect
+    pub applied: Vec<ConfigChange>,
+    
+    /// Changed static parameters left at their running values. Values are
+    /// not recorded as they may be secrets.
+    pub ignored: Vec<String>,
+    
+    /// Recorded timestamp
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ConfigReloadRecord {
+    /// Summary for logs and operators, naming what was applied and ignored
+    pub fn summary(&self) -> String {
+        let applied: Vec<&str> = self.applied.iter().map(|change| change.path.as_str()).collect();
+        let mut summary = if applied.is_empty() {
+            "no dynamic parameters changed".to_string()
+        } else {
+            format!("applied {}", applied.join(", "))
+        };
+        if !self.ignored.is_empty() {
+            summary.push_str(&format!("; ignored {} (restart required)", self.ignored.join(", ")));
+        }
+        summary
+    }
+}
+
+fn config_value(config: &GlobalSyncConfig) -> GarpResult<serde_json::Value> {
+    serde_json::to_value(config)
+        .map_err(|e| GarpError::ConfigError(format!("Failed to encode configuration: {}", e)))
+}
+
+/// Collect leaves that differ between two encoded configurations. Arrays
+/// compare as a whole.
+fn diff_values(path: &str, old: &serde_json::Value, new: &serde_json::Value, changes: &mut Vec<ConfigChange>) {
+    match (old, new) {
+        (serde_json::Value::Object(old_fields), serde_json::Value::Object(new_fields)) => {
+            let mut keys: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                let null = serde_json::Value::Null;
+                diff_values(&child, old_fields.get(key).unwrap_or(&null), new_fields.get(key).unwrap_or(&null), changes);
+            }
+        }
+        _ if old != new => changes.push(ConfigChange { path: path.to_string(), old: old.clone(), new: new.clone() }),
+        _ => {}
+    }
+}
+
+impl GlobalSyncConfig {
+    /// Parameters that differ in `other`, by dotted path
+    pub fn diff(&self, other: &Self) -> GarpResult<Vec<ConfigChange>> {
+        let mut changes = Vec::new();
+        diff_values("", &config_value(self)?, &config_value(other)?, &mut changes);
+        Ok(changes)
+    }
+    
+    /// Apply the dynamic parameters of a reloaded configuration to this
+    /// running one. Returns the resulting configuration and an audit record
+    /// listing what was applied and which static changes were ignored.
+    pub fn apply_reload(&self, reloaded: &Self, requested_by: &str) -> GarpResult<(Self, ConfigReloadRecord)> {
+        reloaded.validate()?;
+        
+        let (applied, ignored): (Vec<ConfigChange>, Vec<ConfigChange>) = self.diff(reloaded)?
+            .into_iter()
+            .partition(|change| is_dynamic_param(&change.path));
+        
+        let mut value = config_value(self)?;
+        for change in &applied {
+            let pointer = format!("/{}", change.path.replace('.', "/"));
+            match value.pointer_mut(&pointer) {
+                Some(slot) => *slot = change.new.clone(),
+                None => return Err(GarpError::ConfigError(format!("Unknown configuration parameter {}", change.path))),
+            }
+        }
+        let updated: Self = serde_json::from_value(value)
+            .map_err(|e| GarpError::ConfigError(format!("Failed to apply reloaded configuration: {}", e)))?;
+        updated.validate()?;
+        
+        let record = ConfigReloadRecord {
+            sequence: 0,
+            requested_by: requested_by.to_string(),
+            applied,
+            ignored: ignored.into_iter().map(|change| change.path).collect(),
+            recorded_at: Utc::now(),
+        };
+        Ok((updated, record))
+    }
+}
+
 impl Default for GlobalSyncConfig {
     fn default() -> Self {
         Self {
@@ -850,11 +1584,22 @@ impl Default for GlobalSyncConfig {
                 min_validators: 4,
                 max_validators: 100,
                 block_time_ms: 1000,
+                suppress_empty_blocks: false,
+                max_empty_block_interval_secs: default_max_empty_block_interval_secs(),
                 consensus_timeout_ms: 5000,
                 max_transactions_per_block: 1000,
+                max_block_bytes: default_max_block_bytes(),
+                batch_proposals: default_batch_proposals(),
+                max_batch_transactions: default_max_batch_transactions(),
+                max_batch_bytes: default_max_batch_bytes(),
                 byzantine_threshold: 1,
                 enable_fast_path: true,
                 checkpoint_interval: 100,
+                snapshot_retention: default_snapshot_retention(),
+                epoch_length_blocks: default_epoch_length_blocks(),
+                finality_depth: default_finality_depth(),
+                participation_window_rounds: default_participation_window_rounds(),
+                metrics_top_validators: default_metrics_top_validators(),
                 params: ConsensusParams {
                     protocol: "tendermint-like".to_string(),
                     quorum_ratio_thousandths: 667,
@@ -879,6 +1624,8 @@ impl Default for GlobalSyncConfig {
                     discovery_interval_ms: 30000,
                     bootstrap_nodes: Vec::new(),
                     max_discovered_domains: 100,
+                    registration_ttl_secs: default_registration_ttl_secs(),
+                    expiry_check_interval_ms: default_registration_expiry_check_interval_ms(),
                 },
                 transaction_timeout_ms: 30000,
                 max_concurrent_transactions: 1000,
@@ -891,10 +1638,29 @@ impl Default for GlobalSyncConfig {
                 },
                 enable_health_monitoring: true,
                 health_check_interval_ms: 10000,
+                timelock_check_interval_ms: default_timelock_check_interval_ms(),
+                channel_challenge_window_secs: default_channel_challenge_window_secs(),
+                channel_settlement_interval_ms: default_channel_settlement_interval_ms(),
+                governance_voting_period_secs: default_governance_voting_period_secs(),
+                governance_approval_threshold_thousandths: default_governance_approval_threshold_thousandths(),
+                governance_check_interval_ms: default_governance_check_interval_ms(),
+                governance_quorum_thousandths: default_governance_quorum_thousandths(),
+                swap_check_interval_ms: default_swap_check_interval_ms(),
+                compensation_check_interval_ms: default_compensation_check_interval_ms(),
+                validation_parallelism: default_validation_parallelism(),
             },
             settlement: SettlementConfig {
                 mode: SettlementMode::Batched,
                 batch_size: 100,
+                batching_enabled: default_batching_enabled(),
+                batch_window_ms: default_batch_window_ms(),
+                enable_netting: false,
+                fee_per_domain: 0,
+                fee_per_byte: 0,
+                compensation_max_retries: default_compensation_max_retries(),
+                compensation_retry_interval_ms: default_compensation_retry_interval_ms(),
+                zk_params_path: default_zk_params_path(),
+                escrow_poll_interval_ms: default_escrow_poll_interval_ms(),
                 settlement_timeout_ms: 10000,
                 enable_atomic_settlement: true,
                 finality_blocks: 6,
@@ -915,8 +1681,14 @@ impl Default for GlobalSyncConfig {
                 enable_tls: false,
                 tls_cert_path: None,
                 tls_key_path: None,
+                ca_cert_path: None,
                 enable_compression: true,
                 compression_algorithm: CompressionAlgorithm::LZ4,
+                compression_threshold_bytes: default_compression_threshold_bytes(),
+                max_send_message_bytes: default_max_message_bytes(),
+                max_receive_message_bytes: default_max_message_bytes(),
+                state_sync_chunk_bytes: default_state_sync_chunk_bytes(),
+                routing: DirectOrDht::Direct,
             },
             kafka: KafkaConfig {
                 bootstrap_servers: vec!["localhost:9092".to_string()],
@@ -962,6 +1734,11 @@ impl Default for GlobalSyncConfig {
                 idle_timeout_ms: 600000,
                 enable_migrations: true,
                 enable_pooling: true,
+                max_retained_versions: default_max_retained_versions(),
+                history_retention_days: default_history_retention_days(),
+                lsm: LsmConfig::default(),
+                existence_filter: ExistenceFilterConfig::default(),
+                replication: ReplicationLagConfig::default(),
             },
             api: ApiConfig {
                 port: 8000,
@@ -974,6 +1751,9 @@ impl Default for GlobalSyncConfig {
                 rate_limit_rpm: 1000,
                 enable_auth: false,
                 jwt_secret: None,
+                serialization_format: SerializationFormat::Json,
+                ws_max_subscriptions: default_ws_max_subscriptions(),
+                persist_maintenance_mode: false,
             },
             security: SecurityConfig {
                 private_key_path: "keys/global-sync-private.pem".to_string(),
@@ -982,7 +1762,12 @@ impl Default for GlobalSyncConfig {
                 enable_encryption: true,
                 encryption_algorithm: EncryptionAlgorithm::ChaCha20Poly1305,
                 key_rotation_interval_hours: 24,
+                keystore_path: None,
+                keystore_passphrase_env: default_keystore_passphrase_env(),
+                key_rotation_overlap_secs: default_key_rotation_overlap_secs(),
                 enable_audit_logging: true,
+                emergency_keys: Vec::new(),
+                emergency_quorum: default_emergency_quorum(),
             },
             performance: PerformanceConfig {
                 worker_threads: None,
@@ -1024,6 +1809,62 @@ impl Default for GlobalSyncConfig {
                     },
                 },
             },
+            mempool: MempoolConfig::default(),
+            reputation: ReputationConfig::default(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_diff_reports_changed_leaves() {
+        let running = GlobalSyncConfig::default();
+        let mut reloaded = running.clone();
+        reloaded.mempool.max_pool_size = 5;
+        reloaded.consensus.cluster_peers.push("localhost:7003".to_string());
+        
+        let changes = running.diff(&reloaded).unwrap();
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(paths, vec!["consensus.cluster_peers", "mempool.max_pool_size"]);
+        assert_eq!(changes[1].old, serde_json::json!(10000));
+        assert_eq!(changes[1].new, serde_json::json!(5));
+        assert!(running.diff(&running).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_apply_reload_keeps_static_parameters() {
+        let running = GlobalSyncConfig::default();
+        let mut reloaded = running.clone();
+        reloaded.cross_domain.transaction_timeout_ms += 1000;
+        reloaded.monitoring.logging.level = "debug".to_string();
+        reloaded.node.node_id = "other-node".to_string();
+        reloaded.api.port += 1;
+        
+        let (updated, record) = running.apply_reload(&reloaded, "operator").unwrap();
+        assert_eq!(updated.cross_domain.transaction_timeout_ms, reloaded.cross_domain.transaction_timeout_ms);
+        assert_eq!(updated.monitoring.logging.level, "debug");
+        assert_eq!(updated.node.node_id, running.node.node_id);
+        assert_eq!(updated.api.port, running.api.port);
+        
+        let applied: Vec<&str> = record.applied.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(applied, vec!["cross_domain.transaction_timeout_ms", "monitoring.logging.level"]);
+        assert_eq!(record.ignored, vec!["api.port", "node.node_id"]);
+        assert_eq!(record.requested_by, "operator");
+        assert!(record.summary().contains("ignored api.port, node.node_id (restart required)"));
+        
+        // An invalid file changes nothing
+        reloaded.node.node_id.clear();
+        assert!(running.apply_reload(&reloaded, "operator").is_err());
+    }
+    
+    #[test]
+    fn test_dynamic_params_match_whole_path_segments() {
+        assert!(is_dynamic_param("mempool.max_pool_size"));
+        assert!(is_dynamic_param("performance.cache.cache_size_mb"));
+        assert!(!is_dynamic_param("mempool.max_pool_size_extra"));
+        assert!(!is_dynamic_param("database.url"));
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::hash::Hash;
-use tokio::sync::{RwLock, Mutex, mpsc, oneshot};
+use tokio::sync::{RwLock, Mutex, broadcast, mpsc, oneshot};
 use tokio::time::{interval, timeout};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
@@ -10,9 +10,22 @@ use tracing::{info, warn, error, debug};
 use ed25519_dalek::{SigningKey, Signer, Verifier, Signature, PublicKey};
 use hex;
 use garp_common::{ConsensusManager, ConsensusEngineType, ConsensusParams, ValidatorInfo, ValidatorStatus, EvidenceType};
+use garp_common::{KeyRotation, ReputationSignal, ReputationTracker};
 
 // --- Canonicalization and signing helpers (module-level) ---
-fn node_sign(message: &[u8]) -> Option<Vec<u8>> {
+
+/// Node key loaded from the keystore, taking precedence over the environment
+static KEYSTORE_SIGNING_KEY: std::sync::RwLock<Option<SigningKey>> = std::sync::RwLock::new(None);
+
+/// Sign with `key` from now on, e.g. after a key rotation
+pub(crate) fn install_node_signing_key(key: SigningKey) {
+    *KEYSTORE_SIGNING_KEY.write().unwrap_or_else(|e| e.into_inner()) = Some(key);
+}
+
+pub(crate) fn node_signing_key() -> Option<SigningKey> {
+    if let Some(key) = KEYSTORE_SIGNING_KEY.read().unwrap_or_else(|e| e.into_inner()).clone() {
+        return Some(key);
+    }
     let signer = std::env::var("SYNC_SIGNER").unwrap_or_else(|_| "env".to_string());
     match signer.as_str() {
         // Future: integrate KMS/Vault/HSM providers here
@@ -36,7 +49,11 @@ fn node_sign(message: &[u8]) -> Option<Vec<u8>> {
             return None;
         }
     };
-    let sk = SigningKey::from_bytes(&arr);
+    Some(SigningKey::from_bytes(&arr))
+}
+
+fn node_sign(message: &[u8]) -> Option<Vec<u8>> {
+    let sk = node_signing_key()?;
     let sig = sk.sign(message);
     Some(sig.to_bytes().to_vec())
 }
@@ -63,6 +80,14 @@ fn canonical_proposal_message(p: &ConsensusProposal) -> Vec<u8> {
             s.push_str("batch:");
             s.push_str(&txs.len().to_string());
         }
+        ProposalType::Batch(b) => {
+            s.push_str("pool_batch:");
+            s.push_str(&b.height.to_string());
+            s.push('|');
+            s.push_str(&hex::encode(&b.parent_hash));
+            s.push('|');
+            s.push_str(&b.transaction_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","));
+        }
         ProposalType::ValidatorSetChange(v) => {
             s.push_str("vset:");
             s.push_str(&v.effective_height.to_string());
@@ -94,6 +119,10 @@ fn canonical_vote_message(v: &ConsensusVote) -> Vec<u8> {
     s.push_str(&v.view.to_string());
     s.push('|');
     s.push_str(&v.timestamp.timestamp_millis().to_string());
+    if !v.rejected_transactions.is_empty() {
+        s.push('|');
+        s.push_str(&v.rejected_transactions.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","));
+    }
     s.into_bytes()
 }
 
@@ -139,22 +168,42 @@ fn canonical_consensus_message(m: &ConsensusMessage) -> Vec<u8> {
             inner
         }
         ConsensusMessageType::SyncResponse(_) => "sync_resp".to_string(),
+        ConsensusMessageType::NewEpoch(ne) => {
+            let mut inner = String::from("new_epoch:");
+            inner.push_str(&ne.epoch.to_string());
+            inner
+        }
     });
     s.into_bytes()
 }
 
 use garp_common::{GarpResult, GarpError};
+use garp_common::canonical;
 use garp_common::crypto::CryptoService;
 use garp_common::types::{TransactionId, ParticipantId};
 use garp_common::consensus::{ValidationResult};
 
 use crate::config::{GlobalSyncConfig, ConsensusAlgorithm};
 use crate::cross_domain::{CrossDomainTransaction, CrossDomainTransactionType};
-use crate::storage::{GlobalStorage, GlobalBlock, BlockHeader};
-use crate::validator::{ValidatorInfo, ValidatorStatus};
+use crate::storage::{GlobalStorage, GlobalBlock, BlockHeader, SlashingRecord};
+use crate::validator::{ValidatorChange, ValidatorInfo, ValidatorRegistry, ValidatorSetUpdate, ValidatorStatus};
 use crate::network::NetworkManager;
 use crate::network::InboundMessage;
 
+pub mod batch;
+pub mod checkpoint;
+pub mod sync;
+pub mod telemetry;
+pub mod vrf;
+
+use batch::{BatchLimits, BatchProposal};
+use telemetry::{ConsensusTelemetry, ConsensusTelemetrySnapshot, RoundPhase};
+
+use vrf::VrfProof;
+
+/// Decided proposals buffered for slow result subscribers
+const CONSENSUS_RESULTS_CAPACITY: usize = 1024;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Hash)]
     struct BlockHashInput {
         height: u64,
@@ -196,6 +245,18 @@ pub struct ConsensusEngine {
     
     /// Metrics
     metrics: Arc<ConsensusMetrics>,
+    
+    /// Verified slashing evidence awaiting the acceptance threshold
+    pending_slashing: Arc<RwLock<HashMap<String, PendingSlashing>>>,
+    
+    /// Epoch-scoped validator set used for finality quorum
+    validator_registry: Arc<RwLock<ValidatorRegistry>>,
+    
+    /// Validator reputation, consulted by leader election
+    reputation: Arc<ReputationTracker>,
+    
+    /// Decided proposals, for subscribers acting on the outcome
+    results_tx: broadcast::Sender<ConsensusResult>,
 }
 
 /// Consensus state
@@ -224,6 +285,12 @@ pub struct ConsensusState {
     
     /// Last updated
     pub last_updated: Instant,
+    
+    /// When the current view began
+    pub view_started_at: Instant,
+    
+    /// Verified VRF proofs for the current view, by validator
+    pub vrf_proofs: HashMap<ParticipantId, VrfProof>,
 }
 
 /// Consensus phase
@@ -274,6 +341,12 @@ pub struct ConsensusSession {
     
     /// Last activity
     pub last_activity: Instant,
+    
+    /// When the first vote arrived, ending the propose phase
+    pub first_vote_at: Option<Instant>,
+    
+    /// When quorum was reached, ending the vote phase
+    pub quorum_at: Option<Instant>,
 }
 
 /// Consensus proposal
@@ -299,6 +372,10 @@ pub struct ConsensusProposal {
     
     /// Signature
     pub signature: Vec<u8>,
+    
+    /// Proposer's VRF proof for the view
+    #[serde(default)]
+    pub vrf_proof: Option<VrfProof>,
 }
 
 /// Proposal type
@@ -310,6 +387,9 @@ pub enum ProposalType {
     /// Transaction batch
     TransactionBatch(Vec<CrossDomainTransaction>),
     
+    /// Pooled transactions proposed together for one block
+    Batch(BatchProposal),
+    
     /// Validator set change
     ValidatorSetChange(ValidatorSetChange),
     
@@ -346,6 +426,10 @@ pub struct ConsensusVote {
     
     /// Signature
     pub signature: Vec<u8>,
+    
+    /// Transactions a batch was rejected for
+    #[serde(default)]
+    pub rejected_transactions: Vec<TransactionId>,
 }
 
 /// Vote type
@@ -515,6 +599,9 @@ pub enum ConsensusMessageType {
     
     /// Sync response
     SyncResponse(SyncResponseMessage),
+    
+    /// New epoch announcement
+    NewEpoch(NewEpochEvent),
 }
 
 /// New view message
@@ -530,6 +617,22 @@ pub struct NewViewMessage {
     pub new_proposal: Option<ConsensusProposal>,
 }
 
+/// Validator set rotation at an epoch boundary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewEpochEvent {
+    /// Epoch that started
+    pub epoch: u64,
+    
+    /// Validators active in the previous epoch
+    pub old_validators: Vec<ValidatorInfo>,
+    
+    /// Validators active from this epoch on
+    pub new_validators: Vec<ValidatorInfo>,
+    
+    /// First block height certified by the new validator set
+    pub effective_at_height: u64,
+}
+
 /// Heartbeat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HeartbeatMessage {
@@ -541,6 +644,10 @@ pub struct HeartbeatMessage {
     
     /// Node status
     pub status: String,
+    
+    /// Sender's VRF proof for the current view
+    #[serde(default)]
+    pub vrf_proof: Option<VrfProof>,
 }
 
 /// Sync request message
@@ -566,7 +673,7 @@ pub struct SyncResponseMessage {
 /// Consensus result
 #[derive(Debug, Clone)]
 pub struct ConsensusResult {
-    /// Transaction ID
+    /// Transaction ID (the first transaction, for batch proposals)
     pub transaction_id: TransactionId,
     
     /// Approved or rejected
@@ -580,6 +687,12 @@ pub struct ConsensusResult {
     
     /// Finalized at
     pub finalized_at: Instant,
+    
+    /// Batch decided by this result, for batch proposals
+    pub batch: Option<BatchProposal>,
+    
+    /// Transactions rejecting votes named as invalid
+    pub rejected_transactions: Vec<TransactionId>,
 }
 
 /// Consensus proof
@@ -607,8 +720,8 @@ pub enum ConsensusEvent {
     /// Vote received
     VoteReceived(ConsensusVote),
     
-    /// View change initiated
-    ViewChangeInitiated(u64),
+    /// View change initiated, with the reason
+    ViewChangeInitiated(u64, String),
     
     /// New view established
     NewViewEstablished(u64),
@@ -628,6 +741,12 @@ pub enum ConsensusEvent {
     /// Validator left
     ValidatorLeft(ParticipantId),
     
+    /// Validator set changed at an epoch boundary
+    ValidatorSetChanged(ValidatorSetUpdate),
+    
+    /// New epoch started
+    NewEpoch(NewEpochEvent),
+    
     /// Sync required
     SyncRequired(u64),
     
@@ -658,6 +777,21 @@ pub struct ConsensusMetrics {
     
     /// Active sessions
     pub active_sessions: Arc<RwLock<usize>>,
+    
+    /// Batches proposed
+    pub batches_proposed: Arc<RwLock<u64>>,
+    
+    /// Average transactions per proposed batch
+    pub avg_batch_size: Arc<RwLock<f64>>,
+    
+    /// Batches decided
+    pub batches_decided: Arc<RwLock<u64>>,
+    
+    /// Average time from batch proposal to decision, in milliseconds
+    pub avg_batch_latency_ms: Arc<RwLock<f64>>,
+    
+    /// Phase latencies, vote participation and round outcomes
+    pub rounds: Arc<RwLock<ConsensusTelemetry>>,
 }
 
 /// Lightweight snapshot for API serialization
@@ -670,6 +804,14 @@ pub struct ConsensusMetricsSnapshot {
     pub avg_consensus_time_ms: f64,
     pub current_view: u64,
     pub active_sessions: usize,
+    pub batches_proposed: u64,
+    pub avg_batch_size: f64,
+    pub batches_decided: u64,
+    pub avg_batch_latency_ms: f64,
+    /// View changes per finished round
+    pub view_change_rate: f64,
+    #[serde(flatten)]
+    pub rounds: ConsensusTelemetrySnapshot,
     pub current_phase: String,
     pub current_leader: Option<String>,
     pub last_committed_block: u64,
@@ -690,6 +832,8 @@ impl ConsensusEngine {
             view_change_in_progress: false,
             view_change_votes: HashMap::new(),
             last_updated: Instant::now(),
+            view_started_at: Instant::now(),
+            vrf_proofs: HashMap::new(),
         }));
         
         // Initialize consensus manager with Tendermint consensus as default for BFT
@@ -714,18 +858,24 @@ impl ConsensusEngine {
             consensus_params
         ));
         
+        let initial_validators = config
+            .consensus
+            .cluster_peers
+            .iter()
+            .map(|peer| ValidatorInfo::new(ParticipantId::new(peer.clone()), String::new(), 1))
+            .collect();
+        let validator_registry = Arc::new(RwLock::new(ValidatorRegistry::new(
+            config.consensus.epoch_length_blocks,
+            config.consensus.params.quorum_ratio_thousandths,
+            initial_validators,
+        )));
+        
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         let event_rx = Arc::new(Mutex::new(event_rx));
         
-        let metrics = Arc::new(ConsensusMetrics {
-            total_proposals: Arc::new(RwLock::new(0)),
-            successful_consensus: Arc::new(RwLock::new(0)),
-            failed_consensus: Arc::new(RwLock::new(0)),
-            view_changes: Arc::new(RwLock::new(0)),
-            avg_consensus_time: Arc::new(RwLock::new(0.0)),
-            current_view: Arc::new(RwLock::new(0)),
-            active_sessions: Arc::new(RwLock::new(0)),
-        });
+        let metrics = Arc::new(ConsensusMetrics::with_participation_window(config.consensus.participation_window_rounds));
+        
+        let reputation = Arc::new(ReputationTracker::new(config.reputation.clone()));
         
         Ok(Self {
             config,
@@ -739,9 +889,24 @@ impl ConsensusEngine {
             event_rx,
             shutdown_tx: None,
             metrics,
+            pending_slashing: Arc::new(RwLock::new(HashMap::new())),
+            validator_registry,
+            reputation,
+            results_tx: broadcast::channel(CONSENSUS_RESULTS_CAPACITY).0,
         })
     }
     
+    /// Observe proposals as they are decided
+    pub fn subscribe_results(&self) -> broadcast::Receiver<ConsensusResult> {
+        self.results_tx.subscribe()
+    }
+    
+    /// Share a reputation tracker with the rest of the node
+    pub fn with_reputation(mut self, reputation: Arc<ReputationTracker>) -> Self {
+        self.reputation = reputation;
+        self
+    }
+    
     /// Start the consensus engine
     pub async fn start(&self) -> GarpResult<()> {
         info!("Starting Consensus Engine");
@@ -754,6 +919,8 @@ impl ConsensusEngine {
         let active_sessions = self.active_sessions.clone();
         let consensus_state = self.consensus_state.clone();
         let metrics = self.metrics.clone();
+        let validator_registry = self.validator_registry.clone();
+        let reputation = self.reputation.clone();
         self.network_manager.register_message_handler(
             "consensus".to_string(),
             move |inbound: &InboundMessage| {
@@ -762,6 +929,8 @@ impl ConsensusEngine {
                 let active_sessions = active_sessions.clone();
                 let consensus_state = consensus_state.clone();
                 let metrics = metrics.clone();
+                let validator_registry = validator_registry.clone();
+                let reputation = reputation.clone();
                 tokio::spawn(async move {
                     // Parse consensus message
                     let parsed: Result<ConsensusMessage, serde_json::Error> = serde_json::from_slice(&data);
@@ -773,12 +942,14 @@ impl ConsensusEngine {
                         }
                     };
 
-                    // Resolve sender's public key
+                    // Resolve the sender's key for the message timestamp; inside
+                    // a key rotation overlap either of its keys may have signed
                     let sender_id = message.sender_id.clone();
-                    let public_key_hex = {
-                        let vs = validator_set.read().await;
-                        vs.validators.get(&sender_id).map(|vi| vi.public_key_hex.clone())
-                    };
+                    let env_bytes = canonical_consensus_message(&message);
+                    let public_key_hex = validator_registry
+                        .read()
+                        .await
+                        .public_key_at(&sender_id, &env_bytes, &message.signature, message.timestamp);
                     let public_key_hex = match public_key_hex {
                         Some(h) => h,
                         None => {
@@ -825,9 +996,9 @@ impl ConsensusEngine {
                             return;
                         }
                     };
-                    let env_bytes = canonical_consensus_message(&message);
                     if let Err(e) = pk.verify_strict(&env_bytes, &env_sig) {
                         warn!("Consensus envelope signature verification failed: {}", e);
+                        Self::record_reputation(&reputation, &sender_id, ReputationSignal::InvalidSignature).await;
                         return;
                     }
 
@@ -855,6 +1026,16 @@ impl ConsensusEngine {
                             let msg_bytes = canonical_proposal_message(&p);
                             if let Err(e) = pk.verify_strict(&msg_bytes, &sig) {
                                 warn!("Proposal signature verification failed: {}", e);
+                                Self::record_reputation(&reputation, &sender_id, ReputationSignal::InvalidSignature).await;
+                                return;
+                            }
+                            // Only the VRF-elected leader may propose once a leader is known
+                            let leader = match p.vrf_proof.clone() {
+                                Some(proof) => Self::record_vrf_proof(&sender_id, proof, &consensus_state, &validator_registry, &reputation).await,
+                                None => consensus_state.read().await.current_leader.clone(),
+                            };
+                            if leader.as_ref().map_or(false, |leader| *leader != p.proposer_id) {
+                                warn!("Dropping proposal {} from {}: not the elected leader of view {}", p.proposal_id, p.proposer_id.0, p.view);
                                 return;
                             }
                             Self::handle_proposal_received(p, &active_sessions, &consensus_state, &metrics, &validator_set).await;
@@ -881,10 +1062,27 @@ impl ConsensusEngine {
                             let msg_bytes = canonical_vote_message(&v);
                             if let Err(e) = pk.verify_strict(&msg_bytes, &sig) {
                                 warn!("Vote signature verification failed: {}", e);
+                                Self::record_reputation(&reputation, &sender_id, ReputationSignal::InvalidSignature).await;
                                 return;
                             }
                             Self::handle_vote_received(v, &active_sessions, &consensus_state, &metrics).await;
                         }
+                        ConsensusMessageType::Heartbeat(h) => {
+                            if let Some(proof) = h.vrf_proof {
+                                Self::record_vrf_proof(&sender_id, proof, &consensus_state, &validator_registry, &reputation).await;
+                            }
+                        }
+                        ConsensusMessageType::NewEpoch(e) => {
+                            // Epochs advance locally with finalized blocks; a
+                            // peer ahead of us means we are behind on blocks
+                            let local_epoch = validator_registry.read().await.current_epoch();
+                            if e.epoch > local_epoch {
+                                info!(
+                                    "Validator {} announced epoch {} from height {}; local epoch is {}",
+                                    sender_id.0, e.epoch, e.effective_at_height, local_epoch
+                                );
+                            }
+                        }
                         other => {
                             debug!("Inbound consensus message ignored for now: {:?}", other);
                         }
@@ -931,14 +1129,16 @@ impl ConsensusEngine {
         debug!("Starting consensus for transaction: {}", transaction.transaction_id);
         
         // Create proposal
+        let view = self.get_current_view().await;
         let proposal = ConsensusProposal {
             proposal_id: proposal_id.clone(),
             proposal_type: ProposalType::TransactionBatch(vec![transaction]),
             data: Vec::new(), // Serialized transaction data
             proposer_id: self.get_node_id().await,
-            view: self.get_current_view().await,
+            view,
             timestamp: chrono::Utc::now(),
             signature: Vec::new(), // TODO: Sign proposal
+            vrf_proof: self.own_vrf_proof(view).await,
         };
         
         // Create consensus session
@@ -952,6 +1152,8 @@ impl ConsensusEngine {
             timeout_at: Instant::now() + self.config.consensus_timeout(),
             created_at: Instant::now(),
             last_activity: Instant::now(),
+            first_vote_at: None,
+            quorum_at: None,
         };
         
         // Store session
@@ -989,6 +1191,7 @@ impl ConsensusEngine {
             view: self.get_current_view().await,
             timestamp: chrono::Utc::now(),
             signature: Vec::new(),
+            rejected_transactions: Vec::new(),
         };
 
         // Sign vote (end-to-end)
@@ -1005,6 +1208,89 @@ impl ConsensusEngine {
         Ok(())
     }
     
+    /// Propose pooled transactions for one block as a single batch,
+    /// returning the proposal ID
+    pub async fn propose_batch(&self, batch: BatchProposal) -> GarpResult<String> {
+        batch.validate(BatchLimits::from_config(&self.config.consensus), |_| true)?;
+        
+        let proposal_id = Uuid::new_v4().to_string();
+        let batch_size = batch.len();
+        debug!("Proposing batch of {} transactions for block {}", batch_size, batch.height);
+        
+        let view = self.get_current_view().await;
+        let proposal = ConsensusProposal {
+            proposal_id: proposal_id.clone(),
+            proposal_type: ProposalType::Batch(batch),
+            data: Vec::new(),
+            proposer_id: self.get_node_id().await,
+            view,
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            vrf_proof: self.own_vrf_proof(view).await,
+        };
+        
+        // Votes look sessions up by proposal ID
+        let session = ConsensusSession {
+            session_id: proposal_id.clone(),
+            proposal: proposal.clone(),
+            phase: ConsensusPhase::Prepare,
+            view,
+            votes: HashMap::new(),
+            required_votes: self.get_required_votes().await,
+            timeout_at: Instant::now() + self.config.consensus_timeout(),
+            created_at: Instant::now(),
+            last_activity: Instant::now(),
+            first_vote_at: None,
+            quorum_at: None,
+        };
+        self.active_sessions.write().await.insert(proposal_id.clone(), session);
+        
+        self.broadcast_proposal(proposal).await?;
+        
+        {
+            let mut total_proposals = self.metrics.total_proposals.write().await;
+            *total_proposals += 1;
+        }
+        self.metrics.record_batch_proposed(batch_size).await;
+        
+        Ok(proposal_id)
+    }
+    
+    /// Vote on a batch proposal. A batch with transactions `is_valid`
+    /// refuses is rejected, naming them so the proposer can re-propose
+    /// the rest.
+    pub async fn vote_on_batch(
+        &self,
+        proposal_id: String,
+        batch: &BatchProposal,
+        is_valid: impl Fn(&TransactionId) -> bool,
+    ) -> GarpResult<()> {
+        let validation = batch.validate(BatchLimits::from_config(&self.config.consensus), is_valid);
+        
+        let mut vote = ConsensusVote {
+            voter_id: self.get_node_id().await,
+            proposal_id,
+            vote_type: VoteType::Prepare,
+            vote: validation.is_ok(),
+            reason: validation.as_ref().err().map(|e| e.to_string()),
+            view: self.get_current_view().await,
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            rejected_transactions: validation.as_ref().err().map(|e| batch::offending_transactions(e).to_vec()).unwrap_or_default(),
+        };
+        
+        let vote_bytes = canonical_vote_message(&vote);
+        if let Some(sig) = node_sign(&vote_bytes) {
+            vote.signature = sig;
+        } else {
+            warn!("No node signing key configured; broadcasting unsigned vote");
+        }
+        
+        self.broadcast_vote(vote).await?;
+        
+        Ok(())
+    }
+    
     /// Get required votes for consensus
     pub async fn get_required_votes(&self) -> usize {
         let validator_set = self.validator_set.read().await;
@@ -1016,9 +1302,91 @@ impl ConsensusEngine {
         let state = self.consensus_state.read().await;
         state.current_view
     }
+    
+    /// Whether this node leads the current view; with no leader elected yet
+    /// every node may propose
+    pub async fn is_leader(&self) -> bool {
+        let node_id = self.get_node_id().await;
+        let state = self.consensus_state.read().await;
+        state.current_leader.as_ref().map_or(true, |leader| *leader == node_id)
+    }
+    
+    /// This node's VRF proof for `view` in the current epoch, if a node key
+    /// is configured
+    async fn own_vrf_proof(&self, view: u64) -> Option<VrfProof> {
+        let epoch = self.validator_registry.read().await.current_epoch();
+        node_signing_key().map(|sk| vrf::prove(&sk, view, epoch))
+    }
+    
+    /// Verify a validator's VRF proof for the current view, record it and
+    /// re-run the leader election. Returns the elected leader.
+    ///
+    /// Validators whose reputation is below the suspension threshold are not
+    /// elected, unless no active validator is eligible.
+    async fn record_vrf_proof(
+        validator_id: &ParticipantId,
+        proof: VrfProof,
+        consensus_state: &Arc<RwLock<ConsensusState>>,
+        validator_registry: &Arc<RwLock<ValidatorRegistry>>,
+        reputation: &Arc<ReputationTracker>,
+    ) -> Option<ParticipantId> {
+        let registry = validator_registry.read().await;
+        let mut state = consensus_state.write().await;
+        let epoch = registry.current_epoch();
+        if proof.view != state.current_view || proof.epoch != epoch {
+            debug!(
+                "Ignoring VRF proof from {} for view {} epoch {} (current view {} epoch {})",
+                validator_id.0, proof.view, proof.epoch, state.current_view, epoch
+            );
+            return state.current_leader.clone();
+        }
+        let valid = registry
+            .get(validator_id)
+            .map_or(false, |validator| vrf::verify(&validator.public_key_hex, &proof).is_some());
+        if !valid {
+            warn!("Invalid VRF proof from {} for view {}", validator_id.0, proof.view);
+            return state.current_leader.clone();
+        }
+        
+        state.vrf_proofs.insert(validator_id.clone(), proof);
+        let mut validators = Vec::new();
+        for validator in registry.active_validators() {
+            if reputation.is_eligible(&validator.id.0).await {
+                validators.push(validator);
+            }
+        }
+        if validators.is_empty() {
+            validators = registry.active_validators();
+        }
+        let leader = vrf::vrf_election(&validators, state.current_view, epoch, &state.vrf_proofs).map(|v| v.id.clone());
+        if leader != state.current_leader {
+            if let Some(leader) = &leader {
+                debug!("VRF elected {} leader of view {}", leader.0, state.current_view);
+            }
+            state.current_leader = leader.clone();
+        }
+        leader
+    }
+    
+    /// Record a signal against a validator's reputation
+    async fn record_reputation(reputation: &ReputationTracker, validator_id: &ParticipantId, signal: ReputationSignal) {
+        if let Err(e) = reputation.record(&validator_id.0, signal).await {
+            warn!("Failed to record {:?} for validator {}: {}", signal, validator_id.0, e);
+        }
+    }
+    
+    /// Advance the view timer without a proposal, so a leader that skips an
+    /// empty block is not mistaken for an inactive one
+    pub async fn touch_view_timer(&self) {
+        let mut state = self.consensus_state.write().await;
+        state.last_updated = Instant::now();
+    }
 
     /// Get metrics snapshot for API
     pub async fn get_metrics_snapshot(&self) -> GarpResult<ConsensusMetricsSnapshot> {
+        let rounds = self.metrics.rounds.read().await.snapshot();
+        let finished = rounds.happy_path_rounds + rounds.view_change_rounds;
+        let view_change_rate = *self.metrics.view_changes.read().await as f64 / finished.max(1) as f64;
         let state = self.consensus_state.read().await;
         Ok(ConsensusMetricsSnapshot {
             total_proposals: *self.metrics.total_proposals.read().await,
@@ -1028,25 +1396,180 @@ impl ConsensusEngine {
             avg_consensus_time_ms: *self.metrics.avg_consensus_time.read().await,
             current_view: state.current_view,
             active_sessions: *self.metrics.active_sessions.read().await,
+            batches_proposed: *self.metrics.batches_proposed.read().await,
+            avg_batch_size: *self.metrics.avg_batch_size.read().await,
+            batches_decided: *self.metrics.batches_decided.read().await,
+            avg_batch_latency_ms: *self.metrics.avg_batch_latency_ms.read().await,
+            view_change_rate,
+            rounds,
             current_phase: format!("{:?}", state.current_phase),
             current_leader: state.current_leader.as_ref().map(|p| p.0.clone()),
             last_committed_block: state.last_committed_block,
         })
     }
 
-    /// List current validators
+    /// List validators active in the current epoch
     pub async fn list_validators(&self) -> GarpResult<Vec<ValidatorInfo>> {
-        Ok(self.consensus_manager.get_active_validators().await)
+        Ok(self.validator_registry.read().await.active_validators())
+    }
+
+    /// Get a validator active in the current epoch
+    pub async fn get_validator(&self, id: &ParticipantId) -> Option<ValidatorInfo> {
+        self.validator_registry.read().await.get(id).cloned()
+    }
+
+    /// Schedule a validator to join at the next epoch boundary, returning that epoch
+    pub async fn add_validator(&self, v: ValidatorInfo) -> GarpResult<u64> {
+        self.validator_registry.write().await.schedule(ValidatorChange::Add(v))
+    }
+
+    /// Record a validator's signing key rotation
+    pub async fn apply_key_rotation(&self, rotation: &KeyRotation) -> GarpResult<()> {
+        self.validator_registry.write().await.apply_key_rotation(rotation)
+    }
+
+    /// Hex key of validator `id` to check a message made at `at` against,
+    /// see [`ValidatorRegistry::public_key_at`]
+    pub async fn validator_key_at(
+        &self,
+        id: &ParticipantId,
+        message: &[u8],
+        signature: &[u8],
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Option<String> {
+        self.validator_registry.read().await.public_key_at(id, message, signature, at)
     }
 
-    /// Add a validator to the set
-    pub async fn add_validator(&self, v: ValidatorInfo) -> GarpResult<()> {
-        self.consensus_manager.add_validator(v).await
+    /// Schedule a validator to leave at the next epoch boundary, returning that epoch
+    pub async fn remove_validator(&self, id: ParticipantId) -> GarpResult<u64> {
+        self.validator_registry.write().await.schedule(ValidatorChange::Remove { id })
     }
 
-    /// Remove a validator from the set
-    pub async fn remove_validator(&self, id: ParticipantId) -> GarpResult<()> {
-        self.consensus_manager.remove_validator(&id).await
+    /// Schedule a stake (voting power) change for the next epoch boundary,
+    /// returning that epoch
+    pub async fn update_validator_stake(&self, id: ParticipantId, stake: u64) -> GarpResult<u64> {
+        self.validator_registry.write().await.schedule(ValidatorChange::UpdateStake { id, stake })
+    }
+
+    /// Recorded view changes into views `from_view..=to_view`
+    pub async fn get_view_changes(&self, from_view: u64, to_view: u64) -> GarpResult<Vec<crate::storage::ViewChangeRecord>> {
+        self.storage.get_view_changes(from_view, to_view).await
+    }
+
+    /// Move to the next view now rather than waiting for the view change
+    /// monitor to find the current one stalled. Returns the new view.
+    pub async fn force_view_change(&self, reason: String) -> GarpResult<u64> {
+        let new_view = self.get_current_view().await + 1;
+        let node_id = self.get_node_id().await;
+        Self::handle_view_change_initiated(
+            new_view,
+            reason,
+            &node_id,
+            &self.consensus_state,
+            &self.metrics,
+            &self.storage,
+        ).await;
+        Ok(new_view)
+    }
+
+    /// Validator changes waiting for the next epoch boundary
+    pub async fn pending_validator_changes(&self) -> Vec<ValidatorChange> {
+        self.validator_registry.read().await.pending_changes().to_vec()
+    }
+
+    /// Current validator epoch
+    pub async fn current_epoch(&self) -> u64 {
+        self.validator_registry.read().await.current_epoch()
+    }
+
+    /// Advance the validator set to the epoch of a finalized block. Crossing
+    /// an epoch boundary emits a `NewEpoch` event, also broadcast to the
+    /// other validators. When the boundary applies pending changes, the new
+    /// set replaces the one in state storage in a single update and a
+    /// `ValidatorSetChanged` event is emitted.
+    pub async fn advance_epoch(&self, height: u64) -> GarpResult<Option<ValidatorSetUpdate>> {
+        let (update, new_epoch) = {
+            let mut registry = self.validator_registry.write().await;
+            if registry.epoch_for_height(height) <= registry.current_epoch() {
+                return Ok(None);
+            }
+            let old_validators = registry.active_validators();
+            let update = registry.advance_to_height(height);
+            let epoch = registry.current_epoch();
+            let new_epoch = NewEpochEvent {
+                epoch,
+                old_validators,
+                new_validators: registry.active_validators(),
+                effective_at_height: registry.epoch_start_height(epoch),
+            };
+            (update, new_epoch)
+        };
+
+        if let Some(update) = &update {
+            let now = std::time::SystemTime::now();
+            let persisted = update
+                .validators
+                .iter()
+                .map(|v| crate::storage::ValidatorInfo {
+                    validator_id: v.id.0.clone(),
+                    public_key: hex::decode(&v.public_key_hex).unwrap_or_default(),
+                    stake: v.voting_power,
+                    voting_power: v.voting_power,
+                    status: crate::storage::ValidatorStatus::Active,
+                    performance: crate::storage::ValidatorPerformance {
+                        blocks_proposed: 0,
+                        blocks_validated: 0,
+                        votes_cast: 0,
+                        missed_votes: 0,
+                        uptime_percentage: 100.0,
+                        performance_score: 1.0,
+                    },
+                    joined_at: now,
+                    last_activity: now,
+                    joining_epoch: None,
+                    leaving_epoch: None,
+                })
+                .collect();
+            self.storage.replace_validator_set(update.epoch, persisted).await?;
+
+            info!(
+                "Validator set changed at epoch {}: {} validators, quorum power {}/{}",
+                update.epoch,
+                update.validators.len(),
+                update.quorum_power,
+                update.total_voting_power
+            );
+            let _ = self.event_tx.send(ConsensusEvent::ValidatorSetChanged(update.clone()));
+        }
+
+        info!("Epoch {} started at height {}", new_epoch.epoch, new_epoch.effective_at_height);
+        let _ = self.event_tx.send(ConsensusEvent::NewEpoch(new_epoch.clone()));
+        if let Err(e) = self.broadcast_new_epoch(new_epoch).await {
+            warn!("Failed to broadcast new epoch: {}", e);
+        }
+        Ok(update)
+    }
+
+    /// Whether the validator set of `epoch` has certified a block yet: a
+    /// finality certificate exists at or above the epoch's first height and
+    /// carries quorum from that epoch's set
+    pub async fn is_epoch_finalized(&self, epoch: u64) -> GarpResult<bool> {
+        let start_height = self.validator_registry.read().await.epoch_start_height(epoch);
+        match self.storage.get_latest_finality_certificate().await? {
+            Some(cert) if cert.height >= start_height => Ok(self.verify_finality_certificate(&cert).await),
+            _ => Ok(false),
+        }
+    }
+
+    /// Check that a finality certificate carries quorum voting power from
+    /// the validator set active in its block's epoch
+    pub async fn verify_finality_certificate(&self, cert: &FinalityCertificate) -> bool {
+        let signers = cert
+            .signatures
+            .iter()
+            .filter(|(_, sig)| !sig.is_empty())
+            .map(|(id, _)| id);
+        self.validator_registry.read().await.has_quorum(cert.height, signers)
     }
 
     /// Update a validator's status
@@ -1105,9 +1628,12 @@ impl ConsensusEngine {
         }
         
         // Validate block hash
-        let calculated_hash = self.calculate_block_hash(block).await?;
-        if calculated_hash != hex::encode(&block.hash) {
-            return Ok(false);
+        let block_hash = hex::encode(&block.hash);
+        if self.calculate_block_hash(block).await? != block_hash {
+            if Self::legacy_block_hash(block)? != block_hash {
+                return Ok(false);
+            }
+            debug!("Block at slot {} carries a legacy-encoded hash", block.header.slot);
         }
         
         Ok(true)
@@ -1228,6 +1754,19 @@ impl ConsensusEngine {
                     return Ok(false);
                 }
             }
+            CrossDomainTransactionType::ChannelLock { capacity, .. } => {
+                if capacity == 0 {
+                    warn!("Invalid channel lock: zero capacity");
+                    return Ok(false);
+                }
+            }
+            CrossDomainTransactionType::ChannelSettlement { channel_id, .. } => {
+                if channel_id.is_empty() {
+                    warn!("Invalid channel settlement: empty channel ID");
+                    return Ok(false);
+                }
+            }
+            CrossDomainTransactionType::Compensation { .. } => {}
         }
         
         // Check transaction data size limits
@@ -1248,31 +1787,36 @@ impl ConsensusEngine {
         Ok(!inactive_domains.contains(&domain_id))
     }
     
-    /// Calculate block hash using SHA-256
-    async fn calculate_block_hash(&self, block: &GlobalBlock) -> GarpResult<String> {
-        debug!("Calculating hash for block: epoch={}, slot={}", block.header.epoch, block.header.slot);
-        
-        // Create a deterministic representation of the block for hashing
-        let hash_input = BlockHashInput {
+    /// Fields of a block covered by its hash
+    fn block_hash_input(block: &GlobalBlock) -> BlockHashInput {
+        BlockHashInput {
             height: block.header.slot,
             previous_hash: hex::encode(&block.header.parent_hash),
             timestamp: block.timestamp,
             merkle_root: hex::encode(&block.header.tx_root),
             transactions: block.transactions.iter().map(|tx| tx.id.0.to_string()).collect(),
-        };
-        
-        // Serialize the hash input
-        let serialized = bincode::serialize(&hash_input)
-            .map_err(|e| GarpError::Internal(format!("Block serialization failed: {}", e)))?;
+        }
+    }
+    
+    /// Calculate block hash: SHA-256 over the canonical encoding of the
+    /// block's hash input
+    async fn calculate_block_hash(&self, block: &GlobalBlock) -> GarpResult<String> {
+        debug!("Calculating hash for block: epoch={}, slot={}", block.header.epoch, block.header.slot);
         
-        // Calculate hash using the common CryptoService (SHA-256)
-        let crypto_service = CryptoService::new();
-        let hash_bytes = crypto_service.hash(&serialized);
-        let hash_hex = hex::encode(hash_bytes);
+        let serialized = canonical::to_canonical_json(&Self::block_hash_input(block))?;
+        let hash_hex = hex::encode(CryptoService::new().hash(&serialized));
         debug!("Calculated block hash: {}", hash_hex);
         Ok(hash_hex)
     }
     
+    /// Block hash over the bincode encoding used before the canonical one,
+    /// still accepted for blocks produced by older nodes
+    fn legacy_block_hash(block: &GlobalBlock) -> GarpResult<String> {
+        let serialized = bincode::serialize(&Self::block_hash_input(block))
+            .map_err(|e| GarpError::Internal(format!("Block serialization failed: {}", e)))?;
+        Ok(hex::encode(CryptoService::new().hash(&serialized)))
+    }
+    
     /// Broadcast proposal
     async fn broadcast_proposal(&self, proposal: ConsensusProposal) -> GarpResult<()> {
         // Sign proposal itself
@@ -1326,6 +1870,27 @@ impl ConsensusEngine {
         Ok(())
     }
     
+    /// Broadcast a new epoch
+    async fn broadcast_new_epoch(&self, new_epoch: NewEpochEvent) -> GarpResult<()> {
+        let mut message = ConsensusMessage {
+            message_id: Uuid::new_v4().to_string(),
+            message_type: ConsensusMessageType::NewEpoch(new_epoch),
+            sender_id: self.get_node_id().await,
+            view: self.get_current_view().await,
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+        };
+
+        // Sign envelope
+        let envelope_bytes = canonical_consensus_message(&message);
+        if let Some(sig) = node_sign(&envelope_bytes) {
+            message.signature = sig;
+        }
+        
+        self.network_manager.broadcast_consensus_message(message).await?;
+        Ok(())
+    }
+    
     /// Start message processor
     async fn start_message_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let event_rx = self.event_rx.clone();
@@ -1335,6 +1900,8 @@ impl ConsensusEngine {
         let validator_set = self.validator_set.clone();
         let storage = self.storage.clone();
         let metrics = self.metrics.clone();
+        let results_tx = self.results_tx.clone();
+        let node_id = self.get_node_id().await;
         
         let handle = tokio::spawn(async move {
             let mut event_rx = event_rx.lock().await;
@@ -1359,6 +1926,12 @@ impl ConsensusEngine {
                             &metrics,
                         ).await;
 
+                        if let Some(result) = Self::decide_batch(&vote, &active_sessions, &metrics).await {
+                            if let Err(e) = event_tx.send(ConsensusEvent::ConsensusReached(result)) {
+                                warn!("Failed to emit ConsensusReached event: {}", e);
+                            }
+                        }
+
                         // After processing the vote, check if approval consensus was reached
                         // and persist a finality certificate for block proposals.
                         {
@@ -1398,6 +1971,7 @@ impl ConsensusEngine {
                                             signatures,
                                             validator_set_hash: vset_hash,
                                             timestamp: chrono::Utc::now(),
+                                            state_root: hex::encode(&block.header.state_root),
                                         };
 
                                         if let Err(e) = storage.store_finality_certificate(certificate).await {
@@ -1407,6 +1981,12 @@ impl ConsensusEngine {
                                                 e
                                             );
                                         } else {
+                                            // Only the vote completing quorum commits the block
+                                            if approve_votes == required_votes {
+                                                if let Some(quorum_at) = session.quorum_at {
+                                                    metrics.record_phase(RoundPhase::Commit, quorum_at.elapsed()).await;
+                                                }
+                                            }
                                             info!(
                                                 "Stored finality certificate for block {} at height {}",
                                                 block_hash_hex,
@@ -1436,6 +2016,8 @@ impl ConsensusEngine {
                                                 proof,
                                                 validators,
                                                 finalized_at: Instant::now(),
+                                                batch: None,
+                                                rejected_transactions: Vec::new(),
                                             };
                                             if let Err(e) = event_tx.send(ConsensusEvent::ConsensusReached(result)) {
                                                 warn!("Failed to emit ConsensusReached event: {}", e);
@@ -1447,14 +2029,22 @@ impl ConsensusEngine {
                         }
                     }
                     
-                    ConsensusEvent::ViewChangeInitiated(new_view) => {
+                    ConsensusEvent::ViewChangeInitiated(new_view, reason) => {
                         Self::handle_view_change_initiated(
                             new_view,
+                            reason,
+                            &node_id,
                             &consensus_state,
                             &metrics,
+                            &storage,
                         ).await;
                     }
                     
+                    ConsensusEvent::ConsensusReached(result) => {
+                        // No subscribers is not an error
+                        let _ = results_tx.send(result);
+                    }
+                    
                     ConsensusEvent::Shutdown => {
                         info!("Received shutdown signal in consensus message processor");
                         break;
@@ -1498,6 +2088,8 @@ impl ConsensusEngine {
             timeout_at: Instant::now() + Duration::from_secs(30),
             created_at: Instant::now(),
             last_activity: Instant::now(),
+            first_vote_at: None,
+            quorum_at: None,
         };
         
         {
@@ -1524,8 +2116,13 @@ impl ConsensusEngine {
         let mut sessions = active_sessions.write().await;
         if let Some(session) = sessions.get_mut(&vote.proposal_id) {
             // Add vote to session
+            let now = Instant::now();
             session.votes.insert(vote.voter_id.clone(), vote.clone());
-            session.last_activity = Instant::now();
+            session.last_activity = now;
+            if session.first_vote_at.is_none() {
+                session.first_vote_at = Some(now);
+                metrics.record_phase(RoundPhase::Propose, now - session.created_at).await;
+            }
             
             // Check if consensus reached
             let required_votes = validator_set.get_required_votes().await;
@@ -1533,6 +2130,10 @@ impl ConsensusEngine {
             let approve_votes = session.votes.values().filter(|v| v.vote).count();
             let reject_votes = session.votes.values().filter(|v| !v.vote).count();
             
+            if session.quorum_at.is_none() && (approve_votes >= required_votes || reject_votes >= required_votes) {
+                Self::record_quorum(session, &vote, validator_set, metrics, now).await;
+            }
+            
             if approve_votes >= required_votes {
                 // Consensus reached - approved
                 info!("Consensus reached for proposal: {} (approved)", vote.proposal_id);
@@ -1564,19 +2165,129 @@ impl ConsensusEngine {
         }
     }
     
+    /// Record the end of a round's vote phase, and which validators took
+    /// part. A round whose quorum came from votes cast in a later view
+    /// than it was proposed in finished only after a view change.
+    async fn record_quorum(
+        session: &mut ConsensusSession,
+        vote: &ConsensusVote,
+        validator_set: &Arc<RwLock<ValidatorSet>>,
+        metrics: &Arc<ConsensusMetrics>,
+        now: Instant,
+    ) {
+        session.quorum_at = Some(now);
+        if let Some(first_vote_at) = session.first_vote_at {
+            metrics.record_phase(RoundPhase::Vote, now - first_vote_at).await;
+        }
+        let eligible: Vec<ParticipantId> = validator_set.read().await.validators.keys().cloned().collect();
+        metrics.rounds.write().await.record_round(
+            eligible,
+            session.votes.keys().cloned(),
+            vote.view > session.view,
+        );
+    }
+    
+    /// Decide the batch proposal `vote` was cast on once enough validators
+    /// agree either way. A rejection carries the transactions rejecting
+    /// votes named, in batch order.
+    async fn decide_batch(
+        vote: &ConsensusVote,
+        active_sessions: &Arc<RwLock<HashMap<String, ConsensusSession>>>,
+        metrics: &Arc<ConsensusMetrics>,
+    ) -> Option<ConsensusResult> {
+        let mut sessions = active_sessions.write().await;
+        let session = sessions.get(&vote.proposal_id)?;
+        if !matches!(session.proposal.proposal_type, ProposalType::Batch(_)) {
+            return None;
+        }
+        
+        let approve_votes = session.votes.values().filter(|v| v.vote).count();
+        let reject_votes = session.votes.len() - approve_votes;
+        let approved = approve_votes >= session.required_votes;
+        if !approved && reject_votes < session.required_votes {
+            return None;
+        }
+        
+        let session = sessions.remove(&vote.proposal_id)?;
+        let ProposalType::Batch(batch) = session.proposal.proposal_type else {
+            return None;
+        };
+        metrics.record_batch_decided(session.created_at.elapsed()).await;
+        if let Some(quorum_at) = session.quorum_at {
+            metrics.record_phase(RoundPhase::Commit, quorum_at.elapsed()).await;
+        }
+        
+        let rejected: HashSet<&TransactionId> = session.votes.values()
+            .filter(|v| !v.vote)
+            .flat_map(|v| v.rejected_transactions.iter())
+            .collect();
+        let rejected_transactions = batch.transaction_ids.iter()
+            .filter(|id| rejected.contains(id))
+            .cloned()
+            .collect();
+        let votes: Vec<ConsensusVote> = session.votes.values()
+            .filter(|v| v.vote == approved)
+            .cloned()
+            .collect();
+        
+        info!(
+            "Batch of {} transactions for block {} {}",
+            batch.len(),
+            batch.height,
+            if approved { "approved" } else { "rejected" }
+        );
+        Some(ConsensusResult {
+            transaction_id: batch.transaction_ids.first().cloned()?,
+            approved,
+            validators: votes.iter().map(|v| v.voter_id.clone()).collect(),
+            proof: ConsensusProof {
+                proposal_id: session.proposal.proposal_id,
+                view: session.view,
+                votes,
+                aggregated_signature: Vec::new(),
+            },
+            finalized_at: Instant::now(),
+            batch: Some(batch),
+            rejected_transactions,
+        })
+    }
+    
     /// Handle view change initiated
     async fn handle_view_change_initiated(
         new_view: u64,
+        reason: String,
+        node_id: &ParticipantId,
         consensus_state: &Arc<RwLock<ConsensusState>>,
         metrics: &Arc<ConsensusMetrics>,
+        storage: &Arc<GlobalStorage>,
     ) {
-        info!("View change initiated to view: {}", new_view);
+        info!("View change initiated to view {}: {}", new_view, reason);
         
-        {
+        let record = {
             let mut state = consensus_state.write().await;
+            let now = Instant::now();
+            let record = crate::storage::ViewChangeRecord {
+                view: new_view,
+                previous_view: state.current_view,
+                reason,
+                initiator: node_id.0.clone(),
+                participants: state.view_change_votes.keys().map(|p| p.0.clone()).collect(),
+                timestamp: std::time::SystemTime::now(),
+                leader: state.current_leader.as_ref().map(|p| p.0.clone()),
+                previous_view_duration_ms: now.duration_since(state.view_started_at).as_millis() as u64,
+            };
             state.view_change_in_progress = true;
             state.current_view = new_view;
-            state.last_updated = Instant::now();
+            state.last_updated = now;
+            state.view_started_at = now;
+            // The leader of the new view is elected from fresh proofs
+            state.vrf_proofs.clear();
+            state.current_leader = None;
+            record
+        };
+        
+        if let Err(e) = storage.store_view_change(record).await {
+            error!("Failed to record view change to view {}: {}", new_view, e);
         }
         
         // Update metrics
@@ -1592,7 +2303,9 @@ impl ConsensusEngine {
     /// Start view change monitor
     async fn start_view_change_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let consensus_state = self.consensus_state.clone();
+        let active_sessions = self.active_sessions.clone();
         let event_tx = self.event_tx.clone();
+        let view_change_timeout = Duration::from_millis(self.config.consensus.params.view_change_timeout_ms);
         
         let handle = tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(10));
@@ -1600,20 +2313,25 @@ impl ConsensusEngine {
             loop {
                 interval.tick().await;
                 
-                // Check if view change is needed
-                let needs_view_change = {
+                // A view stalls when proposals are outstanding but nothing has
+                // moved for the view change timeout
+                let pending = active_sessions.read().await.len();
+                let stalled = {
                     let state = consensus_state.read().await;
-                    // TODO: Implement view change detection logic
-                    false
+                    let idle = state.last_updated.elapsed();
+                    (pending > 0 && idle >= view_change_timeout).then(|| (state.current_view, idle))
                 };
                 
-                if needs_view_change {
-                    let new_view = {
-                        let state = consensus_state.read().await;
-                        state.current_view + 1
-                    };
+                if let Some((current_view, idle)) = stalled {
+                    let new_view = current_view + 1;
+                    let reason = format!(
+                        "no progress for {}ms in view {} with {} pending proposals",
+                        idle.as_millis(),
+                        current_view,
+                        pending
+                    );
                     
-                    if let Err(e) = event_tx.send(ConsensusEvent::ViewChangeInitiated(new_view)) {
+                    if let Err(e) = event_tx.send(ConsensusEvent::ViewChangeInitiated(new_view, reason)) {
                         error!("Failed to send view change event: {}", e);
                         break;
                     }
@@ -1628,7 +2346,10 @@ impl ConsensusEngine {
     async fn start_heartbeat_sender(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let network_manager = self.network_manager.clone();
         let consensus_state = self.consensus_state.clone();
+        let validator_registry = self.validator_registry.clone();
+        let reputation = self.reputation.clone();
         let node_id = self.get_node_id().await;
+        let signing_key = node_signing_key();
         
         let handle = tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(5));
@@ -1641,10 +2362,22 @@ impl ConsensusEngine {
                     (state.current_view, state.last_committed_block)
                 };
                 
+                // Publish this node's VRF proof so peers can elect the view leader
+                let vrf_proof = match &signing_key {
+                    Some(sk) => {
+                        let epoch = validator_registry.read().await.current_epoch();
+                        let proof = vrf::prove(sk, current_view, epoch);
+                        Self::record_vrf_proof(&node_id, proof.clone(), &consensus_state, &validator_registry, &reputation).await;
+                        Some(proof)
+                    }
+                    None => None,
+                };
+                
                 let heartbeat = HeartbeatMessage {
                     current_view,
                     last_committed_block,
                     status: "active".to_string(),
+                    vrf_proof,
                 };
                 
                 let message = ConsensusMessage {
@@ -1707,6 +2440,11 @@ impl ConsensusEngine {
 impl ConsensusMetrics {
     /// Create new consensus metrics
     pub fn new() -> Self {
+        Self::with_participation_window(telemetry::DEFAULT_PARTICIPATION_WINDOW)
+    }
+    
+    /// Metrics measuring vote participation over the last `window` rounds
+    pub fn with_participation_window(window: usize) -> Self {
         Self {
             total_proposals: Arc::new(RwLock::new(0)),
             successful_consensus: Arc::new(RwLock::new(0)),
@@ -1715,9 +2453,35 @@ impl ConsensusMetrics {
             avg_consensus_time: Arc::new(RwLock::new(0.0)),
             current_view: Arc::new(RwLock::new(0)),
             active_sessions: Arc::new(RwLock::new(0)),
+            batches_proposed: Arc::new(RwLock::new(0)),
+            avg_batch_size: Arc::new(RwLock::new(0.0)),
+            batches_decided: Arc::new(RwLock::new(0)),
+            avg_batch_latency_ms: Arc::new(RwLock::new(0.0)),
+            rounds: Arc::new(RwLock::new(ConsensusTelemetry::new(window))),
         }
     }
     
+    /// Record the time a round spent in `phase`
+    pub async fn record_phase(&self, phase: RoundPhase, latency: Duration) {
+        self.rounds.write().await.record_phase(phase, latency);
+    }
+    
+    /// Record a proposed batch of `size` transactions
+    pub async fn record_batch_proposed(&self, size: usize) {
+        let mut proposed = self.batches_proposed.write().await;
+        let mut avg = self.avg_batch_size.write().await;
+        *proposed += 1;
+        *avg += (size as f64 - *avg) / *proposed as f64;
+    }
+    
+    /// Record a batch decided `latency` after it was proposed
+    pub async fn record_batch_decided(&self, latency: Duration) {
+        let mut decided = self.batches_decided.write().await;
+        let mut avg = self.avg_batch_latency_ms.write().await;
+        *decided += 1;
+        *avg += (latency.as_secs_f64() * 1000.0 - *avg) / *decided as f64;
+    }
+    
     /// Get success rate
     pub async fn get_success_rate(&self) -> f64 {
         let successful = *self.successful_consensus.read().await;
@@ -1755,6 +2519,258 @@ mod tests {
         assert!(!validator_set.validators.is_empty());
     }
     
+    #[tokio::test]
+    async fn test_view_changes_are_recorded() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let engine = ConsensusEngine::new(config).await.unwrap();
+        let node_id = engine.get_node_id().await;
+        
+        for view in 1..=2 {
+            ConsensusEngine::handle_view_change_initiated(
+                view,
+                format!("timeout in view {}", view - 1),
+                &node_id,
+                &engine.consensus_state,
+                &engine.metrics,
+                &engine.storage,
+            ).await;
+        }
+        
+        let history = engine.get_view_changes(0, 10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[1].previous_view, history[1].view), (1, 2));
+        assert_eq!(history[1].reason, "timeout in view 1");
+        assert_eq!(history[0].initiator, node_id.0);
+        assert_eq!(engine.get_current_view().await, 2);
+        assert_eq!(engine.get_view_changes(2, 2).await.unwrap().len(), 1);
+        
+        assert_eq!(engine.force_view_change("forced by admin".to_string()).await.unwrap(), 3);
+        let forced = engine.get_view_changes(3, 3).await.unwrap();
+        assert_eq!((forced[0].previous_view, forced[0].reason.as_str()), (2, "forced by admin"));
+    }
+    
+    #[tokio::test]
+    async fn test_batch_rejections_name_offending_transactions() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let engine = ConsensusEngine::new(config).await.unwrap();
+        let batch = BatchProposal::new(1, Vec::new(), (0..3).map(|_| (TransactionId::new(), 10)));
+        let bad = batch.transaction_ids[2].clone();
+        
+        let vote = |voter: &str, approve: bool| ConsensusVote {
+            voter_id: ParticipantId::new(voter),
+            proposal_id: "batch-1".to_string(),
+            vote_type: VoteType::Prepare,
+            vote: approve,
+            reason: None,
+            view: 0,
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            rejected_transactions: if approve { Vec::new() } else { vec![bad.clone()] },
+        };
+        let session = ConsensusSession {
+            session_id: "batch-1".to_string(),
+            proposal: ConsensusProposal {
+                proposal_id: "batch-1".to_string(),
+                proposal_type: ProposalType::Batch(batch.clone()),
+                data: Vec::new(),
+                proposer_id: ParticipantId::new("leader"),
+                view: 0,
+                timestamp: chrono::Utc::now(),
+                signature: Vec::new(),
+                vrf_proof: None,
+            },
+            phase: ConsensusPhase::Prepare,
+            view: 0,
+            votes: HashMap::new(),
+            required_votes: 2,
+            timeout_at: Instant::now() + Duration::from_secs(30),
+            created_at: Instant::now(),
+            last_activity: Instant::now(),
+            first_vote_at: None,
+            quorum_at: None,
+        };
+        engine.active_sessions.write().await.insert("batch-1".to_string(), session);
+        
+        for (voter, approve) in [("v1", true), ("v2", false)] {
+            let vote = vote(voter, approve);
+            engine.active_sessions.write().await.get_mut("batch-1").unwrap().votes.insert(vote.voter_id.clone(), vote.clone());
+            assert!(ConsensusEngine::decide_batch(&vote, &engine.active_sessions, &engine.metrics).await.is_none());
+        }
+        let vote = vote("v3", false);
+        engine.active_sessions.write().await.get_mut("batch-1").unwrap().votes.insert(vote.voter_id.clone(), vote.clone());
+        let result = ConsensusEngine::decide_batch(&vote, &engine.active_sessions, &engine.metrics).await.unwrap();
+        
+        assert!(!result.approved);
+        assert_eq!(result.rejected_transactions, vec![bad.clone()]);
+        assert_eq!(result.batch.unwrap().without(&result.rejected_transactions).len(), 2);
+        assert!(engine.active_sessions.read().await.is_empty());
+        assert_eq!(*engine.metrics.batches_decided.read().await, 1);
+    }
+    
+    #[tokio::test]
+    async fn test_vrf_proofs_elect_leader_for_current_view() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let engine = ConsensusEngine::new(config).await.unwrap();
+        let keys: Vec<SigningKey> = (1..=3u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let validators: Vec<ValidatorInfo> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, sk)| ValidatorInfo::new(ParticipantId::new(format!("validator-{}", i)), hex::encode(sk.verifying_key().to_bytes()), 1))
+            .collect();
+        *engine.validator_registry.write().await = ValidatorRegistry::new(10, 667, validators.clone());
+        
+        let mut leader = None;
+        for (sk, validator) in keys.iter().zip(&validators) {
+            leader = ConsensusEngine::record_vrf_proof(
+                &validator.id,
+                vrf::prove(sk, 0, 0),
+                &engine.consensus_state,
+                &engine.validator_registry,
+                &engine.reputation,
+            ).await;
+        }
+        let expected = keys
+            .iter()
+            .zip(&validators)
+            .min_by_key(|(sk, _)| vrf::verify(&hex::encode(sk.verifying_key().to_bytes()), &vrf::prove(sk, 0, 0)).unwrap())
+            .map(|(_, v)| v.id.clone());
+        assert_eq!(leader, expected);
+        
+        // A proof signed with another validator's key is rejected
+        let outsider = SigningKey::from_bytes(&[9; 32]);
+        let before = engine.consensus_state.read().await.vrf_proofs.len();
+        ConsensusEngine::record_vrf_proof(&validators[0].id, vrf::prove(&outsider, 0, 0), &engine.consensus_state, &engine.validator_registry, &engine.reputation).await;
+        assert_eq!(engine.consensus_state.read().await.vrf_proofs.len(), before);
+        
+        let node_id = engine.get_node_id().await;
+        ConsensusEngine::handle_view_change_initiated(1, "timeout".to_string(), &node_id, &engine.consensus_state, &engine.metrics, &engine.storage).await;
+        let state = engine.consensus_state.read().await;
+        assert!(state.current_leader.is_none());
+        assert!(state.vrf_proofs.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_low_reputation_validators_are_not_elected() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let engine = ConsensusEngine::new(config).await.unwrap();
+        let keys: Vec<SigningKey> = (1..=3u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let validators: Vec<ValidatorInfo> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, sk)| ValidatorInfo::new(ParticipantId::new(format!("validator-{}", i)), hex::encode(sk.verifying_key().to_bytes()), 1))
+            .collect();
+        *engine.validator_registry.write().await = ValidatorRegistry::new(10, 667, validators.clone());
+        let elect = |engine: &ConsensusEngine| {
+            let keys = keys.clone();
+            let validators = validators.clone();
+            let consensus_state = engine.consensus_state.clone();
+            let validator_registry = engine.validator_registry.clone();
+            let reputation = engine.reputation.clone();
+            async move {
+                let mut leader = None;
+                for (sk, validator) in keys.iter().zip(&validators) {
+                    leader = ConsensusEngine::record_vrf_proof(&validator.id, vrf::prove(sk, 0, 0), &consensus_state, &validator_registry, &reputation).await;
+                }
+                leader.unwrap()
+            }
+        };
+        
+        let first = elect(&engine).await;
+        // Invalid signatures drop the winner below the suspension threshold
+        for _ in 0..4 {
+            ConsensusEngine::record_reputation(&engine.reputation, &first, ReputationSignal::InvalidSignature).await;
+        }
+        assert!(!engine.reputation.is_eligible(&first.0).await);
+        let second = elect(&engine).await;
+        assert_ne!(second, first);
+        
+        // With nobody eligible the election falls back to every validator
+        for validator in &validators {
+            for _ in 0..4 {
+                ConsensusEngine::record_reputation(&engine.reputation, &validator.id, ReputationSignal::InvalidSignature).await;
+            }
+        }
+        assert_eq!(elect(&engine).await, first);
+    }
+    
+    #[tokio::test]
+    async fn test_validator_join_takes_effect_at_epoch_boundary() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let engine = ConsensusEngine::new(config).await.unwrap();
+        let cert = |height: u64, signers: &[&str]| FinalityCertificate {
+            height,
+            block_hash: String::new(),
+            signatures: signers.iter().map(|s| (ParticipantId::new(*s), vec![1u8])).collect(),
+            validator_set_hash: String::new(),
+            timestamp: chrono::Utc::now(),
+            state_root: String::new(),
+        };
+        let three = ["localhost:7000", "localhost:7001", "localhost:7002"];
+        
+        let epoch = engine
+            .add_validator(ValidatorInfo::new(ParticipantId::new("localhost:7003"), String::new(), 1))
+            .await
+            .unwrap();
+        assert_eq!(epoch, 1);
+        assert!(engine.advance_epoch(50).await.unwrap().is_none());
+        assert_eq!(engine.list_validators().await.unwrap().len(), 3);
+        assert!(engine.verify_finality_certificate(&cert(50, &three)).await);
+        
+        let update = engine.advance_epoch(100).await.unwrap().expect("validator set changed");
+        assert_eq!(update.validators.len(), 4);
+        assert_eq!(engine.storage.list_validators().await.unwrap().len(), 4);
+        assert!(engine.verify_finality_certificate(&cert(100, &three)).await);
+        assert!(!engine.verify_finality_certificate(&cert(100, &three[..2])).await);
+        assert!(engine.verify_finality_certificate(&cert(50, &three)).await);
+    }    }
+    
+    #[tokio::test]
+    async fn test_epoch_rotation_waits_for_new_set_finality() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let engine = ConsensusEngine::new(config).await.unwrap();
+        let cert = |height: u64, signers: &[&str]| FinalityCertificate {
+            height,
+            block_hash: String::new(),
+            signatures: signers.iter().map(|s| (ParticipantId::new(*s), vec![1u8])).collect(),
+            validator_set_hash: String::new(),
+            timestamp: chrono::Utc::now(),
+            state_root: String::new(),
+        };
+        
+        engine.add_validator(ValidatorInfo::new(ParticipantId::new("localhost:7003"), String::new(), 1)).await.unwrap();
+        engine.advance_epoch(100).await.unwrap().expect("validator set changed");
+        engine.remove_validator(ParticipantId::new("localhost:7000")).await.unwrap();
+        while engine.event_rx.lock().await.try_recv().is_ok() {}
+        
+        assert!(engine.advance_epoch(250).await.unwrap().is_some());
+        let mut new_epoch = None;
+        while let Ok(event) = engine.event_rx.lock().await.try_recv() {
+            if let ConsensusEvent::NewEpoch(event) = event {
+                new_epoch = Some(event);
+            }
+        }
+        let new_epoch = new_epoch.expect("new epoch event");
+        assert_eq!((new_epoch.epoch, new_epoch.effective_at_height), (2, 200));
+        assert_eq!(new_epoch.old_validators.len(), 4);
+        assert_eq!(new_epoch.new_validators.len(), 3);
+        
+        // Both sides of the rotation are recorded in state storage
+        let left = engine.storage.get_validator(&"localhost:7000".to_string()).await.unwrap().unwrap();
+        assert_eq!(left.status, crate::storage::ValidatorStatus::Inactive);
+        assert_eq!((left.joining_epoch, left.leaving_epoch), (Some(1), Some(2)));
+        let joined = engine.storage.get_validator(&"localhost:7003".to_string()).await.unwrap().unwrap();
+        assert_eq!((joined.joining_epoch, joined.leaving_epoch), (Some(1), None));
+        
+        // Only a certificate from the new set at or past the boundary finalizes the epoch
+        assert!(!engine.is_epoch_finalized(2).await.unwrap());
+        engine.storage.store_finality_certificate(cert(150, &["localhost:7001", "localhost:7002", "localhost:7003"])).await.unwrap();
+        assert!(!engine.is_epoch_finalized(2).await.unwrap());
+        engine.storage.store_finality_certificate(cert(200, &["localhost:7000", "localhost:7001", "localhost:7002"])).await.unwrap();
+        assert!(!engine.is_epoch_finalized(2).await.unwrap());
+        engine.storage.store_finality_certificate(cert(201, &["localhost:7001", "localhost:7002", "localhost:7003"])).await.unwrap();
+        assert!(engine.is_epoch_finalized(2).await.unwrap());
+    }
+    
     #[tokio::test]
     async fn test_consensus_metrics() {
         let metrics = ConsensusMetrics::new();
@@ -1814,6 +2830,9 @@ pub struct FinalityCertificate {
     pub signatures: Vec<(garp_common::types::ParticipantId, Vec<u8>)>,
     pub validator_set_hash: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Hex-encoded state root from the block header
+    #[serde(default)]
+    pub state_root: String,
 }
 
 /// Evidence for slashing conditions.
@@ -1903,6 +2922,7 @@ impl ConsensusEngine {
             signatures: sigs,
             validator_set_hash: vset_hash,
             timestamp: chrono::Utc::now(),
+            state_root: String::new(),
         }
     }
 
@@ -1912,4 +2932,278 @@ impl ConsensusEngine {
         if candidate.height < current.height { return current; }
         if candidate.signatures.len() >= current.signatures.len() { candidate } else { current }
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// Slashing evidence submission and adjudication
+// -----------------------------------------------------------------------------
+
+/// Kind of misbehavior proven by slashing evidence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SlashingEvidenceType {
+    /// Two conflicting proposals for the same view
+    Equivocation,
+    /// Two conflicting votes of the same type for the same view
+    DoubleVote,
+    /// A signed block proposal that fails validation
+    InvalidProposal,
+}
+
+impl SlashingEvidenceType {
+    /// Stake penalty in basis points
+    pub fn penalty_bp(&self) -> u32 {
+        match self {
+            SlashingEvidenceType::Equivocation => 500,
+            SlashingEvidenceType::DoubleVote => 500,
+            SlashingEvidenceType::InvalidProposal => 100,
+        }
+    }
+}
+
+/// Evidence that a validator misbehaved.
+///
+/// `proof` is the JSON encoding of the signed messages proving the fault:
+/// a `(ConsensusProposal, ConsensusProposal)` pair for `Equivocation`, a
+/// `(ConsensusVote, ConsensusVote)` pair for `DoubleVote`, and a single
+/// `ConsensusProposal` for `InvalidProposal`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingEvidence {
+    /// Accused validator
+    pub validator_id: ParticipantId,
+    /// Kind of misbehavior
+    pub evidence_type: SlashingEvidenceType,
+    /// Encoded proof of misbehavior
+    pub proof: Vec<u8>,
+    /// Validator submitting (and thereby accepting) the evidence
+    pub reporter_id: ParticipantId,
+}
+
+impl SlashingEvidence {
+    /// Stable identifier for this piece of evidence, independent of the reporter
+    pub fn evidence_id(&self) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(self.validator_id.0.as_bytes());
+        hasher.update(format!("{:?}", self.evidence_type).as_bytes());
+        hasher.update(&self.proof);
+        hasher.finalize().to_hex().to_string()
+    }
+}
+
+/// Outcome of submitting slashing evidence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SlashingResult {
+    /// Evidence verified; waiting for more validators to accept it
+    Pending {
+        evidence_id: String,
+        acceptances: usize,
+        required: usize,
+    },
+    /// Threshold reached and the validator was slashed
+    Slashed(SlashingRecord),
+}
+
+/// Verified evidence and the validators that have accepted it
+#[derive(Debug, Clone)]
+struct PendingSlashing {
+    evidence: SlashingEvidence,
+    accepted_by: HashSet<ParticipantId>,
+}
+
+/// Acceptances required from `eligible` non-accused validators: ceil(2/3 * eligible)
+fn slashing_threshold(eligible: usize) -> usize {
+    (eligible * 2).div_ceil(3).max(1)
+}
+
+/// Verify an ed25519 signature made by a validator's hex-encoded public key
+pub(crate) fn verify_validator_signature(public_key_hex: &str, message: &[u8], signature: &[u8]) -> bool {
+    let Ok(pk_bytes) = hex::decode(public_key_hex) else { return false };
+    let Ok(pk_arr) = <[u8; 32]>::try_from(pk_bytes.as_slice()) else { return false };
+    let Ok(pk) = PublicKey::from_bytes(&pk_arr) else { return false };
+    let Ok(sig_arr) = <[u8; 64]>::try_from(signature) else { return false };
+    let Ok(sig) = Signature::from_bytes(&sig_arr) else { return false };
+    pk.verify_strict(message, &sig).is_ok()
+}
+
+impl ConsensusEngine {
+    /// Submit evidence of validator misbehavior.
+    ///
+    /// The proof is verified against the accused validator's key before the
+    /// reporter's acceptance is counted. Once 2/3 of the non-accused active
+    /// validators have accepted the same evidence, the validator is marked
+    /// `Slashed` in state storage, its stake is reduced, and a slashing record
+    /// is stored.
+    pub async fn submit_slashing_evidence(&self, evidence: SlashingEvidence) -> GarpResult<SlashingResult> {
+        let validators = self.list_validators().await?;
+        let accused = validators.iter()
+            .find(|v| v.id == evidence.validator_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Unknown validator: {}", evidence.validator_id.0)))?;
+        let eligible: Vec<&ParticipantId> = validators.iter()
+            .filter(|v| v.id != evidence.validator_id && v.status == ValidatorStatus::Active)
+            .map(|v| &v.id)
+            .collect();
+        if !eligible.contains(&&evidence.reporter_id) {
+            return Err(GarpError::ValidationError(format!(
+                "Reporter {} is not an active non-accused validator", evidence.reporter_id.0
+            )));
+        }
+
+        self.verify_slashing_proof(&evidence, &accused.public_key_hex).await?;
+
+        let evidence_id = evidence.evidence_id();
+        let required = slashing_threshold(eligible.len());
+        let acceptances = {
+            let mut pending = self.pending_slashing.write().await;
+            let entry = pending.entry(evidence_id.clone()).or_insert_with(|| PendingSlashing {
+                evidence: evidence.clone(),
+                accepted_by: HashSet::new(),
+            });
+            entry.accepted_by.insert(evidence.reporter_id.clone());
+            entry.accepted_by.len()
+        };
+
+        if acceptances < required {
+            debug!("Slashing evidence {} accepted by {}/{}", evidence_id, acceptances, required);
+            return Ok(SlashingResult::Pending { evidence_id, acceptances, required });
+        }
+
+        let Some(pending) = self.pending_slashing.write().await.remove(&evidence_id) else {
+            // Another submission crossed the threshold concurrently
+            return Ok(SlashingResult::Pending { evidence_id, acceptances, required });
+        };
+
+        let validator_id = pending.evidence.validator_id.0.clone();
+        let (stake_before, stake_after) = self.storage
+            .slash_validator(&validator_id, pending.evidence.evidence_type.penalty_bp())
+            .await?;
+        let mut accepted_by: Vec<String> = pending.accepted_by.into_iter().map(|id| id.0).collect();
+        accepted_by.sort();
+
+        let record = SlashingRecord {
+            evidence_id,
+            validator_id,
+            evidence_type: pending.evidence.evidence_type,
+            stake_before,
+            stake_after,
+            accepted_by,
+            timestamp: std::time::SystemTime::now(),
+        };
+        self.storage.store_slashing_record(record.clone()).await?;
+
+        warn!(
+            "Validator {} slashed for {:?}: stake {} -> {}",
+            record.validator_id, record.evidence_type, stake_before, stake_after
+        );
+        Ok(SlashingResult::Slashed(record))
+    }
+
+    /// Get the slashing history of a validator
+    pub async fn get_slashing_history(&self, validator_id: &ParticipantId) -> GarpResult<Vec<SlashingRecord>> {
+        self.storage.get_slashing_history(&validator_id.0).await
+    }
+
+    /// Check that the proof demonstrates the claimed misbehavior by the accused
+    async fn verify_slashing_proof(&self, evidence: &SlashingEvidence, public_key_hex: &str) -> GarpResult<()> {
+        let invalid = |reason: &str| GarpError::ValidationError(format!("Invalid slashing proof: {}", reason));
+        let signed_proposal = |p: &ConsensusProposal| {
+            p.proposer_id == evidence.validator_id
+                && verify_validator_signature(public_key_hex, &canonical_proposal_message(p), &p.signature)
+        };
+
+        match evidence.evidence_type {
+            SlashingEvidenceType::Equivocation => {
+                let (a, b): (ConsensusProposal, ConsensusProposal) = serde_json::from_slice(&evidence.proof)
+                    .map_err(|e| invalid(&e.to_string()))?;
+                if !signed_proposal(&a) || !signed_proposal(&b) {
+                    return Err(invalid("proposals are not signed by the accused validator"));
+                }
+                if a.view != b.view || canonical_proposal_message(&a) == canonical_proposal_message(&b) {
+                    return Err(invalid("proposals do not conflict"));
+                }
+            }
+            SlashingEvidenceType::DoubleVote => {
+                let (a, b): (ConsensusVote, ConsensusVote) = serde_json::from_slice(&evidence.proof)
+                    .map_err(|e| invalid(&e.to_string()))?;
+                for vote in [&a, &b] {
+                    if vote.voter_id != evidence.validator_id
+                        || !verify_validator_signature(public_key_hex, &canonical_vote_message(vote), &vote.signature)
+                    {
+                        return Err(invalid("votes are not signed by the accused validator"));
+                    }
+                }
+                let same_round = a.view == b.view
+                    && std::mem::discriminant(&a.vote_type) == std::mem::discriminant(&b.vote_type);
+                if !same_round || (a.proposal_id == b.proposal_id && a.vote == b.vote) {
+                    return Err(invalid("votes do not conflict"));
+                }
+            }
+            SlashingEvidenceType::InvalidProposal => {
+                let proposal: ConsensusProposal = serde_json::from_slice(&evidence.proof)
+                    .map_err(|e| invalid(&e.to_string()))?;
+                if !signed_proposal(&proposal) {
+                    return Err(invalid("proposal is not signed by the accused validator"));
+                }
+                let ProposalType::Block(block) = &proposal.proposal_type else {
+                    return Err(invalid("only block proposals can be proven invalid"));
+                };
+                if self.validate_block(block).await? {
+                    return Err(invalid("proposed block is valid"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod slashing_tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    fn signed_vote(key: &SigningKey, voter: &ParticipantId, proposal_id: &str) -> ConsensusVote {
+        let mut vote = ConsensusVote {
+            voter_id: voter.clone(),
+            proposal_id: proposal_id.to_string(),
+            vote_type: VoteType::Prepare,
+            vote: true,
+            reason: None,
+            view: 7,
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            rejected_transactions: Vec::new(),
+        };
+        vote.signature = key.sign(&canonical_vote_message(&vote)).to_bytes().to_vec();
+        vote
+    }
+
+    #[test]
+    fn test_slashing_threshold() {
+        assert_eq!(slashing_threshold(1), 1);
+        assert_eq!(slashing_threshold(3), 2);
+        assert_eq!(slashing_threshold(4), 3);
+        assert_eq!(slashing_threshold(6), 4);
+    }
+
+    #[test]
+    fn test_evidence_id_ignores_reporter() {
+        let evidence = |reporter: &str| SlashingEvidence {
+            validator_id: ParticipantId::new("v1"),
+            evidence_type: SlashingEvidenceType::DoubleVote,
+            proof: vec![1, 2, 3],
+            reporter_id: ParticipantId::new(reporter),
+        };
+        assert_eq!(evidence("a").evidence_id(), evidence("b").evidence_id());
+    }
+
+    #[test]
+    fn test_double_vote_signatures_verify() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key_hex = hex::encode(key.verifying_key().to_bytes());
+        let voter = ParticipantId::new("v1");
+
+        let a = signed_vote(&key, &voter, "proposal-a");
+        let b = signed_vote(&key, &voter, "proposal-b");
+        assert!(verify_validator_signature(&public_key_hex, &canonical_vote_message(&a), &a.signature));
+        assert!(verify_validator_signature(&public_key_hex, &canonical_vote_message(&b), &b.signature));
+        assert!(!verify_validator_signature(&public_key_hex, &canonical_vote_message(&a), &b.signature));
+    }
+}
@@ -0,0 +1,256 @@
+//! Batch proposals
+//!
+//! Instead of one consensus round per transaction, the block producer
+//! proposes the transactions it draws from the pool for a block as one
+//! batch. Validators vote once on the whole batch; approval moves every
+//! included transaction to consensus-reached and assigns them all to the
+//! batch's block. A validator rejecting a batch names the transactions it
+//! could not accept, so the proposer can re-propose the batch without them.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use garp_common::error::TransactionError;
+use garp_common::types::TransactionId;
+use garp_common::{GarpError, GarpResult};
+
+use crate::config::ConsensusConfig;
+use crate::receipt;
+
+/// Bounds on the size of a batch proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchLimits {
+    /// Maximum transactions in a batch
+    pub max_transactions: usize,
+
+    /// Maximum total transaction bytes in a batch
+    pub max_bytes: usize,
+}
+
+impl BatchLimits {
+    /// Build the limits from consensus configuration
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        Self {
+            max_transactions: config.max_batch_transactions,
+            max_bytes: config.max_batch_bytes,
+        }
+    }
+}
+
+/// Pooled transactions proposed together for one block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchProposal {
+    /// Height of the block the batch is assigned to
+    pub height: u64,
+
+    /// Hash of that block's parent
+    pub parent_hash: Vec<u8>,
+
+    /// Transactions in the order they are applied
+    pub transaction_ids: Vec<TransactionId>,
+
+    /// Size in bytes of each transaction, in the same order
+    pub sizes: Vec<usize>,
+}
+
+impl BatchProposal {
+    /// Batch of `(transaction, size)` pairs for the block at `height`
+    pub fn new(height: u64, parent_hash: Vec<u8>, transactions: impl IntoIterator<Item = (TransactionId, usize)>) -> Self {
+        let (transaction_ids, sizes) = transactions.into_iter().unzip();
+        Self { height, parent_hash, transaction_ids, sizes }
+    }
+
+    /// Number of transactions in the batch
+    pub fn len(&self) -> usize {
+        self.transaction_ids.len()
+    }
+
+    /// Whether the batch has no transactions
+    pub fn is_empty(&self) -> bool {
+        self.transaction_ids.is_empty()
+    }
+
+    /// Total transaction bytes in the batch
+    pub fn total_bytes(&self) -> usize {
+        self.sizes.iter().sum()
+    }
+
+    /// Hash of the block the batch is assigned to, committing to the
+    /// transaction IDs through their Merkle root
+    pub fn block_hash(&self) -> Vec<u8> {
+        receipt::block_hash(&self.parent_hash, self.height, &receipt::transaction_root(&self.transaction_ids))
+    }
+
+    /// The batch without `excluded`, keeping the order of the rest
+    pub fn without(&self, excluded: &[TransactionId]) -> Self {
+        let excluded: HashSet<&TransactionId> = excluded.iter().collect();
+        let transactions = self.transaction_ids.iter()
+            .zip(&self.sizes)
+            .filter(|(id, _)| !excluded.contains(id))
+            .map(|(id, size)| (id.clone(), *size));
+        Self::new(self.height, self.parent_hash.clone(), transactions)
+    }
+
+    /// Check the batch against `limits`. Transactions `is_valid` refuses,
+    /// and repeated ones, fail the batch with
+    /// [`TransactionError::InvalidBatch`] naming them; a batch over the
+    /// limits fails as a whole.
+    pub fn validate(&self, limits: BatchLimits, is_valid: impl Fn(&TransactionId) -> bool) -> GarpResult<()> {
+        if self.sizes.len() != self.transaction_ids.len() {
+            return Err(GarpError::ValidationError(format!(
+                "Batch lists {} transactions but {} sizes",
+                self.transaction_ids.len(),
+                self.sizes.len()
+            )));
+        }
+        if self.is_empty() {
+            return Err(GarpError::ValidationError("Batch has no transactions".to_string()));
+        }
+        if self.len() > limits.max_transactions {
+            return Err(GarpError::ValidationError(format!(
+                "Batch has {} transactions, limit is {}",
+                self.len(),
+                limits.max_transactions
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let offending: Vec<TransactionId> = self.transaction_ids.iter()
+            .filter(|id| !seen.insert(*id) || !is_valid(id))
+            .cloned()
+            .collect();
+        if !offending.is_empty() {
+            return Err(GarpError::Transaction(TransactionError::InvalidBatch(offending)));
+        }
+
+        if self.total_bytes() > limits.max_bytes {
+            return Err(GarpError::ValidationError(format!(
+                "Batch has {} bytes of transactions, limit is {}",
+                self.total_bytes(),
+                limits.max_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Transactions a batch validation error names as offending
+pub fn offending_transactions(error: &GarpError) -> &[TransactionId] {
+    match error {
+        GarpError::Transaction(TransactionError::InvalidBatch(offending)) => offending,
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
+    use garp_common::types::ParticipantId;
+
+    use crate::config::GlobalSyncConfig;
+    use crate::consensus::{canonical_vote_message, ConsensusVote, VoteType};
+
+    const LIMITS: BatchLimits = BatchLimits { max_transactions: 4, max_bytes: 1000 };
+
+    fn batch(sizes: &[usize]) -> BatchProposal {
+        BatchProposal::new(7, vec![0xaa; 32], sizes.iter().map(|size| (TransactionId::new(), *size)))
+    }
+
+    #[test]
+    fn test_batches_are_bounded_by_count_and_bytes() {
+        assert!(batch(&[100, 200, 300]).validate(LIMITS, |_| true).is_ok());
+        assert!(batch(&[250; 4]).validate(LIMITS, |_| true).is_ok());
+
+        let too_many = batch(&[1; 5]).validate(LIMITS, |_| true).unwrap_err();
+        assert!(too_many.to_string().contains("5 transactions, limit is 4"));
+        let too_big = batch(&[500, 501]).validate(LIMITS, |_| true).unwrap_err();
+        assert!(too_big.to_string().contains("1001 bytes"));
+        assert!(batch(&[]).validate(LIMITS, |_| true).is_err());
+
+        let limits = BatchLimits::from_config(&GlobalSyncConfig::default().consensus);
+        assert_eq!(limits, BatchLimits { max_transactions: 500, max_bytes: 512 * 1024 });
+    }
+
+    #[test]
+    fn test_invalid_transactions_are_named_and_can_be_dropped() {
+        let mut proposal = batch(&[10, 20, 30, 40]);
+        let unknown = proposal.transaction_ids[1].clone();
+        // A transaction listed twice
+        proposal.transaction_ids[3] = proposal.transaction_ids[0].clone();
+
+        let err = proposal.validate(LIMITS, |id| *id != unknown).unwrap_err();
+        let offending = offending_transactions(&err).to_vec();
+        assert_eq!(offending, vec![unknown.clone(), proposal.transaction_ids[0].clone()]);
+
+        // Dropping every copy of a repeated transaction leaves a valid batch
+        let retry = proposal.without(&offending);
+        assert_eq!(retry.transaction_ids, vec![proposal.transaction_ids[2].clone()]);
+        assert_eq!(retry.sizes, vec![30]);
+        assert!(retry.validate(LIMITS, |id| *id != unknown).is_ok());
+        assert_ne!(retry.block_hash(), proposal.block_hash());
+        assert!(offending_transactions(&GarpError::ValidationError("x".to_string())).is_empty());
+    }
+
+    /// One consensus round: every validator signs a vote on the proposal,
+    /// which is verified before the round is counted, plus a fixed network
+    /// latency
+    async fn consensus_round(validators: &[SigningKey], proposal_id: &str, latency: Duration) {
+        for key in validators {
+            let mut vote = ConsensusVote {
+                voter_id: ParticipantId(hex::encode(key.verifying_key().to_bytes())),
+                proposal_id: proposal_id.to_string(),
+                vote_type: VoteType::Prepare,
+                vote: true,
+                reason: None,
+                view: 0,
+                timestamp: chrono::Utc::now(),
+                signature: Vec::new(),
+                rejected_transactions: Vec::new(),
+            };
+            let message = canonical_vote_message(&vote);
+            vote.signature = key.sign(&message).to_bytes().to_vec();
+            let signature = ed25519_dalek::Signature::from_slice(&vote.signature).unwrap();
+            key.verifying_key().verify(&message, &signature).unwrap();
+        }
+        tokio::time::sleep(latency).await;
+    }
+
+    #[tokio::test]
+    async fn test_batching_raises_throughput_over_per_transaction_rounds() {
+        let validators: Vec<SigningKey> = (1..=4u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let transactions: Vec<(TransactionId, usize)> = (0..200).map(|_| (TransactionId::new(), 256)).collect();
+        let latency = Duration::from_millis(2);
+        let limits = BatchLimits::from_config(&GlobalSyncConfig::default().consensus);
+
+        let started = Instant::now();
+        for (transaction_id, _) in &transactions {
+            consensus_round(&validators, &transaction_id.to_string(), latency).await;
+        }
+        let per_transaction = started.elapsed();
+
+        let started = Instant::now();
+        let mut rounds = 0;
+        for chunk in transactions.chunks(50) {
+            let proposal = BatchProposal::new(1, Vec::new(), chunk.iter().cloned());
+            proposal.validate(limits, |_| true).unwrap();
+            consensus_round(&validators, &hex::encode(proposal.block_hash()), latency).await;
+            rounds += 1;
+        }
+        let batched = started.elapsed();
+
+        let throughput = |elapsed: Duration| transactions.len() as f64 / elapsed.as_secs_f64();
+        println!(
+            "per-transaction: {} rounds, {:.0} tx/s; batched: {} rounds, {:.0} tx/s",
+            transactions.len(),
+            throughput(per_transaction),
+            rounds,
+            throughput(batched)
+        );
+        assert_eq!(rounds, 4);
+        assert!(throughput(batched) > 10.0 * throughput(per_transaction));
+    }
+}
@@ -0,0 +1,429 @@
+//! Signed ledger checkpoints for auditors
+//!
+//! A checkpoint attests the state root and block hash of a finalized height
+//! so the ledger state there can be verified without replaying every
+//! transaction. The requesting node asks the active validators to sign
+//! `(height, state_root, block_hash)` with their Ed25519 keys. Each validator
+//! only signs what its own finality certificate for the height says. Once
+//! signatures from 2/3 of the validator set have been verified the
+//! checkpoint is stored in consensus storage next to the certificate.
+//!
+//! ```text
+//! requester                           validator
+//!   SignRequest { height, .. }  ->
+//!                               <-  Signature { height, validator_id, .. }
+//!                                   or Refused { height, reason }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, Notify};
+use tracing::{debug, error, info, warn};
+
+use garp_common::types::{NodeId, ParticipantId, Signature};
+use garp_common::{GarpError, GarpResult};
+
+use crate::config::GlobalSyncConfig;
+use crate::network::{InboundMessage, MessageDestination, MessagePriority, NetworkManager};
+use crate::storage::GlobalStorage;
+
+use super::{node_signing_key, verify_validator_signature, ConsensusEngine};
+
+/// Network message type for checkpoint signing traffic
+pub const LEDGER_CHECKPOINT_MESSAGE_TYPE: &str = "ledger_checkpoint";
+
+/// Algorithm named in checkpoint signatures
+const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Ledger state at a finalized height, signed by a 2/3 quorum of validators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerCheckpoint {
+    pub height: u64,
+    /// Hex-encoded state root
+    pub state_root: String,
+    /// Hex-encoded block hash
+    pub block_hash: String,
+    /// Validator signatures over [`LedgerCheckpoint::signing_message`]
+    pub validator_signatures: Vec<Signature>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LedgerCheckpoint {
+    /// Message validators sign for a checkpoint
+    pub fn signing_message(height: u64, state_root: &str, block_hash: &str) -> Vec<u8> {
+        format!("ledger-checkpoint|{}|{}|{}", height, state_root, block_hash).into_bytes()
+    }
+}
+
+/// Checkpoint signing protocol message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CheckpointMessage {
+    /// Ask a validator to sign the checkpoint at `height`
+    SignRequest { height: u64, state_root: String, block_hash: String },
+
+    /// A validator's signature over the checkpoint
+    Signature { height: u64, validator_id: ParticipantId, signature: Vec<u8> },
+
+    /// The validator will not sign
+    Refused { height: u64, reason: String },
+}
+
+/// Checkpoint collecting signatures
+struct PendingCheckpoint {
+    state_root: String,
+    block_hash: String,
+    required: usize,
+    signatures: HashMap<ParticipantId, Signature>,
+    complete: Arc<Notify>,
+}
+
+/// Creates ledger checkpoints and signs those requested by peers
+pub struct LedgerCheckpointer {
+    storage: Arc<GlobalStorage>,
+    consensus_engine: Arc<ConsensusEngine>,
+    network_manager: Arc<NetworkManager>,
+    /// Key overriding the node key, which follows key rotations
+    signing_key: Option<SigningKey>,
+    timeout: Duration,
+    pending: Mutex<HashMap<u64, PendingCheckpoint>>,
+}
+
+impl LedgerCheckpointer {
+    pub fn new(
+        config: Arc<GlobalSyncConfig>,
+        storage: Arc<GlobalStorage>,
+        consensus_engine: Arc<ConsensusEngine>,
+        network_manager: Arc<NetworkManager>,
+    ) -> Self {
+        Self {
+            storage,
+            consensus_engine,
+            network_manager,
+            signing_key: None,
+            timeout: config.consensus_timeout(),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sign with `signing_key` instead of the node key
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Handle checkpoint messages from peers
+    pub async fn register_handler(self: &Arc<Self>) -> GarpResult<()> {
+        let checkpointer = self.clone();
+        self.network_manager
+            .register_message_handler(LEDGER_CHECKPOINT_MESSAGE_TYPE.to_string(), move |inbound: &InboundMessage| {
+                let checkpointer = checkpointer.clone();
+                let source = inbound.source.clone();
+                let data = inbound.data.clone();
+                tokio::spawn(async move {
+                    let message = match serde_json::from_slice::<CheckpointMessage>(&data) {
+                        Ok(message) => message,
+                        Err(e) => {
+                            warn!("Invalid checkpoint message from {}: {}", source, e);
+                            return;
+                        }
+                    };
+                    match checkpointer.handle_message(&source, message).await {
+                        Ok(Some(reply)) => {
+                            if let Err(e) = checkpointer.send(MessageDestination::Peer(source.clone()), &reply).await {
+                                error!("Failed to send checkpoint message to {}: {}", source, e);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!("Checkpoint message from {} rejected: {}", source, e),
+                    }
+                });
+                Ok(())
+            })
+            .await
+    }
+
+    /// Create the checkpoint at a finalized `height`, or return the one
+    /// already stored. Fails if 2/3 of the validators have not signed
+    /// within the consensus timeout.
+    pub async fn create(&self, height: u64) -> GarpResult<LedgerCheckpoint> {
+        if let Some(checkpoint) = self.storage.get_ledger_checkpoint(height).await? {
+            return Ok(checkpoint);
+        }
+        let request = self.begin(height).await?;
+        if let Err(e) = self.send(MessageDestination::Broadcast, &request).await {
+            warn!("Failed to request checkpoint signatures for height {}: {}", height, e);
+        }
+        self.finish(height).await
+    }
+
+    /// Start collecting signatures for `height`, signing it ourselves if
+    /// this node is a validator. Returns the request to send to validators.
+    async fn begin(&self, height: u64) -> GarpResult<CheckpointMessage> {
+        let certificate = self.storage.get_finality_certificate_by_height(height).await?
+            .ok_or_else(|| GarpError::NotFound(format!("No finalized block at height {}", height)))?;
+        let validators = self.consensus_engine.list_validators().await?;
+        if validators.is_empty() {
+            return Err(GarpError::ValidationError("No active validators to sign the checkpoint".to_string()));
+        }
+
+        {
+            let mut pending = self.pending.lock().await;
+            if pending.contains_key(&height) {
+                return Err(GarpError::ValidationError(format!("Checkpoint at height {} is already being created", height)));
+            }
+            pending.insert(height, PendingCheckpoint {
+                state_root: certificate.state_root.clone(),
+                block_hash: certificate.block_hash.clone(),
+                required: (validators.len() * 2).div_ceil(3),
+                signatures: HashMap::new(),
+                complete: Arc::new(Notify::new()),
+            });
+        }
+        info!("Collecting validator signatures for the checkpoint at height {}", height);
+
+        let request = CheckpointMessage::SignRequest {
+            height,
+            state_root: certificate.state_root,
+            block_hash: certificate.block_hash,
+        };
+        if let Some(CheckpointMessage::Signature { height, validator_id, signature }) = self.sign(&request).await {
+            self.accept_signature(height, validator_id, signature).await?;
+        }
+        Ok(request)
+    }
+
+    /// Wait for the quorum of `height`, then store the checkpoint
+    async fn finish(&self, height: u64) -> GarpResult<LedgerCheckpoint> {
+        let complete = match self.pending.lock().await.get(&height) {
+            Some(pending) => pending.complete.clone(),
+            None => return Err(GarpError::NotFound(format!("No checkpoint pending at height {}", height))),
+        };
+        let signed = tokio::time::timeout(self.timeout, complete.notified()).await.is_ok();
+
+        let pending = self.pending.lock().await.remove(&height)
+            .ok_or_else(|| GarpError::NotFound(format!("No checkpoint pending at height {}", height)))?;
+        if !signed && pending.signatures.len() < pending.required {
+            return Err(GarpError::ValidationError(format!(
+                "Checkpoint at height {} has {} of {} required validator signatures",
+                height,
+                pending.signatures.len(),
+                pending.required
+            )));
+        }
+
+        let checkpoint = LedgerCheckpoint {
+            height,
+            state_root: pending.state_root,
+            block_hash: pending.block_hash,
+            validator_signatures: pending.signatures.into_values().collect(),
+            created_at: Utc::now(),
+        };
+        self.storage.store_ledger_checkpoint(checkpoint.clone()).await?;
+        info!(
+            "Stored ledger checkpoint at height {} with {} validator signatures",
+            height,
+            checkpoint.validator_signatures.len()
+        );
+        Ok(checkpoint)
+    }
+
+    /// Process a message from `source`, returning the reply to send back
+    pub async fn handle_message(&self, source: &NodeId, message: CheckpointMessage) -> GarpResult<Option<CheckpointMessage>> {
+        match message {
+            request @ CheckpointMessage::SignRequest { .. } => Ok(self.sign(&request).await),
+            CheckpointMessage::Signature { height, validator_id, signature } => {
+                self.accept_signature(height, validator_id, signature).await?;
+                Ok(None)
+            }
+            CheckpointMessage::Refused { height, reason } => {
+                debug!("{} refused to sign the checkpoint at height {}: {}", source, height, reason);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Sign a checkpoint request if it matches our own finality certificate
+    async fn sign(&self, request: &CheckpointMessage) -> Option<CheckpointMessage> {
+        let CheckpointMessage::SignRequest { height, state_root, block_hash } = request else {
+            return None;
+        };
+        let refuse = |reason: String| Some(CheckpointMessage::Refused { height: *height, reason });
+
+        let validator_id = self.consensus_engine.get_node_id().await;
+        if self.consensus_engine.get_validator(&validator_id).await.is_none() {
+            return refuse("not an active validator".to_string());
+        }
+        let Some(signing_key) = self.signing_key.clone().or_else(node_signing_key) else {
+            return refuse("no signing key configured".to_string());
+        };
+        let certificate = match self.storage.get_finality_certificate_by_height(*height).await {
+            Ok(Some(certificate)) => certificate,
+            Ok(None) => return refuse(format!("height {} is not finalized here", height)),
+            Err(e) => return refuse(e.to_string()),
+        };
+        if certificate.block_hash != *block_hash || certificate.state_root != *state_root {
+            return refuse(format!("checkpoint does not match the finalized block at height {}", height));
+        }
+
+        let signature = signing_key.sign(&LedgerCheckpoint::signing_message(*height, state_root, block_hash));
+        Some(CheckpointMessage::Signature {
+            height: *height,
+            validator_id,
+            signature: signature.to_bytes().to_vec(),
+        })
+    }
+
+    /// Count a validator's signature after verifying it against its key
+    async fn accept_signature(&self, height: u64, validator_id: ParticipantId, signature: Vec<u8>) -> GarpResult<()> {
+        let validator = self.consensus_engine.get_validator(&validator_id).await
+            .ok_or_else(|| GarpError::ValidationError(format!("{} is not an active validator", validator_id.0)))?;
+
+        let mut pending = self.pending.lock().await;
+        let Some(checkpoint) = pending.get_mut(&height) else {
+            debug!("Ignoring signature from {} for height {}: no checkpoint pending", validator_id.0, height);
+            return Ok(());
+        };
+        let message = LedgerCheckpoint::signing_message(height, &checkpoint.state_root, &checkpoint.block_hash);
+        // Signatures carry no timestamp, so they are checked against the
+        // keys accepted now, both keys inside a rotation's overlap window
+        let public_key_hex = self.consensus_engine
+            .validator_key_at(&validator.id, &message, &signature, Utc::now())
+            .await
+            .unwrap_or(validator.public_key_hex);
+        if !verify_validator_signature(&public_key_hex, &message, &signature) {
+            return Err(GarpError::ValidationError(format!(
+                "Invalid checkpoint signature from {} for height {}",
+                validator_id.0, height
+            )));
+        }
+
+        checkpoint.signatures.insert(validator_id, Signature {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            signature,
+            public_key: hex::decode(&public_key_hex).unwrap_or_default(),
+        });
+        if checkpoint.signatures.len() == checkpoint.required {
+            checkpoint.complete.notify_one();
+        }
+        Ok(())
+    }
+
+    async fn send(&self, destination: MessageDestination, message: &CheckpointMessage) -> GarpResult<()> {
+        let data = serde_json::to_vec(message)
+            .map_err(|e| GarpError::InternalError(format!("Failed to encode checkpoint message: {}", e)))?;
+        self.network_manager
+            .send_message(destination, LEDGER_CHECKPOINT_MESSAGE_TYPE.to_string(), data, MessagePriority::Normal)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::FinalityCertificate;
+    use crate::validator::{ValidatorInfo, ValidatorRegistry};
+
+    const STATE_ROOT: &str = "aa";
+    const BLOCK_HASH: &str = "bb";
+
+    /// Checkpointer for validator `index` of a three validator set, with a
+    /// finality certificate at height 5
+    async fn validator(index: usize) -> (LedgerCheckpointer, Vec<SigningKey>) {
+        let keys: Vec<SigningKey> = (1..=3u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let mut config = GlobalSyncConfig::default();
+        config.node.node_id = format!("validator-{}", index);
+        config.consensus.consensus_timeout_ms = 100;
+        let config = Arc::new(config);
+
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let validators: Vec<ValidatorInfo> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, sk)| ValidatorInfo::new(ParticipantId::new(format!("validator-{}", i)), hex::encode(sk.verifying_key().to_bytes()), 1))
+            .collect();
+        *consensus_engine.validator_registry.write().await = ValidatorRegistry::new(10, 667, validators);
+        storage
+            .store_finality_certificate(FinalityCertificate {
+                height: 5,
+                block_hash: BLOCK_HASH.to_string(),
+                signatures: Vec::new(),
+                validator_set_hash: String::new(),
+                timestamp: Utc::now(),
+                state_root: STATE_ROOT.to_string(),
+            })
+            .await
+            .unwrap();
+
+        let checkpointer = LedgerCheckpointer::new(config, storage, consensus_engine, network_manager)
+            .with_signing_key(keys[index].clone());
+        (checkpointer, keys)
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_needs_two_thirds_of_validators() {
+        let (requester, keys) = validator(0).await;
+        let (peer, _) = validator(1).await;
+        let peer_id = "validator-1".to_string();
+
+        // Our own signature is one of the two required
+        let request = requester.begin(5).await.unwrap();
+        assert!(requester.begin(5).await.is_err());
+
+        // A signature made with another key does not count
+        let forged = keys[2].sign(&LedgerCheckpoint::signing_message(5, STATE_ROOT, BLOCK_HASH));
+        let forged = CheckpointMessage::Signature { height: 5, validator_id: ParticipantId::new("validator-1"), signature: forged.to_bytes().to_vec() };
+        assert!(requester.handle_message(&peer_id, forged).await.is_err());
+
+        let reply = peer.handle_message(&"validator-0".to_string(), request).await.unwrap().unwrap();
+        assert!(requester.handle_message(&peer_id, reply).await.unwrap().is_none());
+
+        let checkpoint = requester.finish(5).await.unwrap();
+        assert_eq!((checkpoint.height, checkpoint.state_root.as_str(), checkpoint.block_hash.as_str()), (5, STATE_ROOT, BLOCK_HASH));
+        assert_eq!(checkpoint.validator_signatures.len(), 2);
+        let message = LedgerCheckpoint::signing_message(5, STATE_ROOT, BLOCK_HASH);
+        for signature in &checkpoint.validator_signatures {
+            assert!(verify_validator_signature(&hex::encode(&signature.public_key), &message, &signature.signature));
+        }
+
+        // Stored, and returned again instead of being re-signed
+        assert_eq!(requester.storage.get_latest_ledger_checkpoint().await.unwrap().unwrap().height, 5);
+        assert_eq!(requester.create(5).await.unwrap().created_at, checkpoint.created_at);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_fails_without_quorum() {
+        let (requester, _) = validator(0).await;
+        assert!(matches!(requester.create(6).await, Err(GarpError::NotFound(_))));
+
+        // Only our own signature arrives before the timeout
+        let error = requester.create(5).await.unwrap_err();
+        assert!(error.to_string().contains("1 of 2 required"));
+        assert!(requester.storage.get_ledger_checkpoint(5).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_validators_refuse_mismatched_checkpoints() {
+        let (peer, _) = validator(1).await;
+        let source = "validator-0".to_string();
+        let request = |height: u64, state_root: &str| CheckpointMessage::SignRequest {
+            height,
+            state_root: state_root.to_string(),
+            block_hash: BLOCK_HASH.to_string(),
+        };
+
+        for request in [request(5, "cc"), request(6, STATE_ROOT)] {
+            let reply = peer.handle_message(&source, request).await.unwrap();
+            assert!(matches!(reply, Some(CheckpointMessage::Refused { .. })));
+        }
+        let reply = peer.handle_message(&source, request(5, STATE_ROOT)).await.unwrap();
+        assert!(matches!(reply, Some(CheckpointMessage::Signature { height: 5, .. })));
+    }
+}
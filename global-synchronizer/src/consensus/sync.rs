@@ -0,0 +1,623 @@
+//! Snapshot-based fast sync
+//!
+//! A joining node asks a trusted peer for its latest [`StateSnapshot`]. The
+//! peer answers with a [`SnapshotManifest`] carrying the finality certificate
+//! at the snapshot height. Once the joining node has checked the manifest
+//! against the certificate, the peer streams the snapshot in chunks of
+//! `network.state_sync_chunk_bytes`, which may arrive in any order. The
+//! reassembled snapshot is loaded into state storage and only the blocks
+//! above the snapshot height are replayed, after which consensus takes over
+//! as usual.
+//!
+//! ```text
+//! joining                              peer
+//!   SnapshotRequest          ->
+//!                            <-  SnapshotManifest
+//!   SnapshotAccepted         ->
+//!                            <-  SnapshotChunk x total_chunks
+//!   BlocksRequest { from }   ->
+//!                            <-  Blocks { complete: false }   (repeated)
+//!                            <-  Blocks { complete: true }
+//! ```
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{error, info, warn};
+
+use garp_common::types::NodeId;
+use garp_common::{GarpError, GarpResult};
+
+use crate::config::GlobalSyncConfig;
+use crate::network::{InboundMessage, MessageDestination, MessagePriority, NetworkManager};
+use crate::storage::{BlockHash, BlockInfo, GlobalStorage, StateSnapshot};
+
+use super::{ConsensusEngine, FinalityCertificate};
+
+/// Network message type for fast sync traffic
+pub const FAST_SYNC_MESSAGE_TYPE: &str = "fast_sync";
+
+/// Blocks sent per `Blocks` message during replay
+const BLOCKS_PER_MESSAGE: usize = 256;
+
+/// Fast sync protocol message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FastSyncMessage {
+    /// Ask for the peer's latest snapshot
+    SnapshotRequest,
+
+    /// Snapshot the peer can stream
+    SnapshotManifest(SnapshotManifest),
+
+    /// Manifest verified; stream the snapshot
+    SnapshotAccepted { snapshot_id: String },
+
+    /// One piece of the snapshot data
+    SnapshotChunk { snapshot_id: String, index: u32, data: Vec<u8> },
+
+    /// Ask for blocks from `from_height` upwards
+    BlocksRequest { from_height: u64 },
+
+    /// Blocks in height order; `complete` once the peer's head is included
+    Blocks { blocks: Vec<BlockInfo>, complete: bool },
+
+    /// The peer cannot serve the request
+    Unavailable { reason: String },
+}
+
+/// Description of a snapshot and the certificate finalizing its block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub snapshot_id: String,
+    pub block_height: u64,
+    pub block_hash: BlockHash,
+    pub state_root: Vec<u8>,
+    /// Checksum of the reassembled snapshot data
+    #[serde(default)]
+    pub checksum: Vec<u8>,
+    pub total_chunks: u32,
+    pub total_bytes: u64,
+    pub created_at: SystemTime,
+    pub certificate: FinalityCertificate,
+}
+
+/// Fast sync phase
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FastSyncPhase {
+    /// No fast sync started
+    Idle,
+
+    /// Waiting for the peer's manifest
+    AwaitingManifest,
+
+    /// Receiving snapshot chunks
+    DownloadingSnapshot,
+
+    /// Snapshot applied, replaying later blocks
+    ReplayingBlocks,
+
+    /// Caught up with the peer; normal sync continues
+    Complete,
+
+    /// Fast sync stopped
+    Failed(String),
+}
+
+/// Progress of a fast sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FastSyncState {
+    pub phase: FastSyncPhase,
+    pub peer: Option<NodeId>,
+    pub snapshot_height: Option<u64>,
+    pub chunks_received: u32,
+    pub total_chunks: u32,
+    pub bytes_received: u64,
+    pub blocks_replayed: u64,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Default for FastSyncState {
+    fn default() -> Self {
+        Self {
+            phase: FastSyncPhase::Idle,
+            peer: None,
+            snapshot_height: None,
+            chunks_received: 0,
+            total_chunks: 0,
+            bytes_received: 0,
+            blocks_replayed: 0,
+            started_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+impl FastSyncState {
+    /// Whether a fast sync is under way
+    pub fn in_progress(&self) -> bool {
+        matches!(
+            self.phase,
+            FastSyncPhase::AwaitingManifest | FastSyncPhase::DownloadingSnapshot | FastSyncPhase::ReplayingBlocks
+        )
+    }
+}
+
+/// Snapshot being received
+struct Download {
+    manifest: SnapshotManifest,
+    chunks: BTreeMap<u32, Vec<u8>>,
+    bytes: u64,
+}
+
+/// Serves snapshots to joining nodes and fast-syncs this node from a peer
+pub struct FastSync {
+    storage: Arc<GlobalStorage>,
+    consensus_engine: Arc<ConsensusEngine>,
+    network_manager: Arc<NetworkManager>,
+    chunk_bytes: usize,
+    state: RwLock<FastSyncState>,
+    download: Mutex<Option<Download>>,
+}
+
+impl FastSync {
+    pub fn new(
+        config: Arc<GlobalSyncConfig>,
+        storage: Arc<GlobalStorage>,
+        consensus_engine: Arc<ConsensusEngine>,
+        network_manager: Arc<NetworkManager>,
+    ) -> Self {
+        Self {
+            storage,
+            consensus_engine,
+            network_manager,
+            chunk_bytes: config.network.state_sync_chunk_bytes.max(1),
+            state: RwLock::new(FastSyncState::default()),
+            download: Mutex::new(None),
+        }
+    }
+
+    /// Handle fast sync messages from peers
+    pub async fn register_handler(self: &Arc<Self>) -> GarpResult<()> {
+        let fast_sync = self.clone();
+        self.network_manager
+            .register_message_handler(FAST_SYNC_MESSAGE_TYPE.to_string(), move |inbound: &InboundMessage| {
+                let fast_sync = fast_sync.clone();
+                let source = inbound.source.clone();
+                let data = inbound.data.clone();
+                tokio::spawn(async move {
+                    let message = match serde_json::from_slice::<FastSyncMessage>(&data) {
+                        Ok(message) => message,
+                        Err(e) => {
+                            warn!("Invalid fast sync message from {}: {}", source, e);
+                            return;
+                        }
+                    };
+                    match fast_sync.handle_message(&source, message).await {
+                        Ok(replies) => {
+                            for reply in replies {
+                                if let Err(e) = fast_sync.send(&source, &reply).await {
+                                    error!("Failed to send fast sync message to {}: {}", source, e);
+                                    break;
+                                }
+                            }
+                        }
+                        Err(e) => error!("Fast sync with {} failed: {}", source, e),
+                    }
+                });
+                Ok(())
+            })
+            .await
+    }
+
+    /// Connect to `peer_address` and request its latest snapshot
+    pub async fn start(&self, peer_address: SocketAddr) -> GarpResult<()> {
+        let peer = self.network_manager.connect_peer(peer_address).await?;
+        let request = self.begin(peer.clone()).await?;
+        self.send(&peer, &request).await
+    }
+
+    /// Current progress
+    pub async fn state(&self) -> FastSyncState {
+        self.state.read().await.clone()
+    }
+
+    async fn begin(&self, peer: NodeId) -> GarpResult<FastSyncMessage> {
+        let mut state = self.state.write().await;
+        if state.in_progress() {
+            return Err(GarpError::ValidationError(format!(
+                "Fast sync from {} already in progress",
+                state.peer.as_deref().unwrap_or("unknown peer")
+            )));
+        }
+        info!("Starting fast sync from {}", peer);
+        let now = chrono::Utc::now();
+        *state = FastSyncState {
+            phase: FastSyncPhase::AwaitingManifest,
+            peer: Some(peer),
+            started_at: Some(now),
+            updated_at: Some(now),
+            ..FastSyncState::default()
+        };
+        *self.download.lock().await = None;
+        Ok(FastSyncMessage::SnapshotRequest)
+    }
+
+    async fn send(&self, peer: &NodeId, message: &FastSyncMessage) -> GarpResult<()> {
+        let data = serde_json::to_vec(message)
+            .map_err(|e| GarpError::InternalError(format!("Failed to encode fast sync message: {}", e)))?;
+        self.network_manager
+            .send_message(MessageDestination::Peer(peer.clone()), FAST_SYNC_MESSAGE_TYPE.to_string(), data, MessagePriority::Normal)
+            .await
+            .map(|_| ())
+    }
+
+    /// Process a message from `source`, returning the replies to send back
+    pub async fn handle_message(&self, source: &NodeId, message: FastSyncMessage) -> GarpResult<Vec<FastSyncMessage>> {
+        match message {
+            FastSyncMessage::SnapshotRequest => self.serve_manifest().await,
+            FastSyncMessage::SnapshotAccepted { snapshot_id } => self.serve_chunks(&snapshot_id).await,
+            FastSyncMessage::BlocksRequest { from_height } => self.serve_blocks(from_height).await,
+            message => {
+                if self.state.read().await.peer.as_ref() != Some(source) {
+                    warn!("Ignoring fast sync message from {}: not the sync peer", source);
+                    return Ok(Vec::new());
+                }
+                let result = match message {
+                    FastSyncMessage::SnapshotManifest(manifest) => self.on_manifest(manifest).await,
+                    FastSyncMessage::SnapshotChunk { snapshot_id, index, data } => self.on_chunk(&snapshot_id, index, data).await,
+                    FastSyncMessage::Blocks { blocks, complete } => self.on_blocks(blocks, complete).await,
+                    FastSyncMessage::Unavailable { reason } => {
+                        Err(GarpError::NotFound(format!("Peer cannot serve fast sync: {}", reason)))
+                    }
+                    FastSyncMessage::SnapshotRequest
+                    | FastSyncMessage::SnapshotAccepted { .. }
+                    | FastSyncMessage::BlocksRequest { .. } => unreachable!(),
+                };
+                if let Err(e) = &result {
+                    self.fail(e.to_string()).await;
+                }
+                result
+            }
+        }
+    }
+
+    async fn fail(&self, reason: String) {
+        warn!("Fast sync failed: {}", reason);
+        *self.download.lock().await = None;
+        let mut state = self.state.write().await;
+        state.phase = FastSyncPhase::Failed(reason);
+        state.updated_at = Some(chrono::Utc::now());
+    }
+
+    async fn expect_phase(&self, phase: FastSyncPhase) -> GarpResult<()> {
+        let state = self.state.read().await;
+        if state.phase != phase {
+            return Err(GarpError::ValidationError(format!(
+                "Unexpected fast sync message in phase {:?}",
+                state.phase
+            )));
+        }
+        Ok(())
+    }
+
+    /// Manifest of the latest snapshot
+    async fn serve_manifest(&self) -> GarpResult<Vec<FastSyncMessage>> {
+        let unavailable = |reason: String| Ok(vec![FastSyncMessage::Unavailable { reason }]);
+        let snapshot = match self.storage.get_latest_state_snapshot().await {
+            Some(snapshot) => snapshot,
+            None => return unavailable("no state snapshot".to_string()),
+        };
+        let certificate = match self.storage.get_finality_certificate_by_height(snapshot.block_height).await? {
+            Some(certificate) => certificate,
+            None => return unavailable(format!("no finality certificate at height {}", snapshot.block_height)),
+        };
+        Ok(vec![FastSyncMessage::SnapshotManifest(SnapshotManifest {
+            total_chunks: snapshot.snapshot_data.chunks(self.chunk_bytes).count() as u32,
+            total_bytes: snapshot.snapshot_data.len() as u64,
+            snapshot_id: snapshot.snapshot_id,
+            block_height: snapshot.block_height,
+            block_hash: snapshot.block_hash,
+            state_root: snapshot.state_root,
+            checksum: snapshot.checksum,
+            created_at: snapshot.created_at,
+            certificate,
+        })])
+    }
+
+    /// Chunks of the snapshot announced in an earlier manifest
+    async fn serve_chunks(&self, snapshot_id: &str) -> GarpResult<Vec<FastSyncMessage>> {
+        let snapshot = match self.storage.get_latest_state_snapshot().await {
+            Some(snapshot) if snapshot.snapshot_id == snapshot_id => snapshot,
+            _ => {
+                return Ok(vec![FastSyncMessage::Unavailable {
+                    reason: format!("snapshot {} is no longer the latest", snapshot_id),
+                }])
+            }
+        };
+        Ok(snapshot
+            .snapshot_data
+            .chunks(self.chunk_bytes)
+            .enumerate()
+            .map(|(index, data)| FastSyncMessage::SnapshotChunk {
+                snapshot_id: snapshot.snapshot_id.clone(),
+                index: index as u32,
+                data: data.to_vec(),
+            })
+            .collect())
+    }
+
+    async fn serve_blocks(&self, from_height: u64) -> GarpResult<Vec<FastSyncMessage>> {
+        let blocks = self.storage.get_blocks_from(from_height, BLOCKS_PER_MESSAGE).await?;
+        let head = self.storage.get_blockchain().await.height;
+        let complete = blocks.last().map_or(true, |block| block.height >= head);
+        Ok(vec![FastSyncMessage::Blocks { blocks, complete }])
+    }
+
+    /// Check that the certificate finalizes the snapshot's block and commits
+    /// to its state root
+    async fn verify_manifest(&self, manifest: &SnapshotManifest) -> GarpResult<()> {
+        let certificate = &manifest.certificate;
+        if certificate.height != manifest.block_height {
+            return Err(GarpError::ValidationError(format!(
+                "Certificate height {} does not match snapshot height {}",
+                certificate.height, manifest.block_height
+            )));
+        }
+        if certificate.block_hash != hex::encode(&manifest.block_hash) {
+            return Err(GarpError::ValidationError(format!(
+                "Certificate does not finalize snapshot block at height {}",
+                manifest.block_height
+            )));
+        }
+        if certificate.state_root != hex::encode(&manifest.state_root) {
+            return Err(GarpError::ValidationError(format!(
+                "Snapshot state root does not match the certificate at height {}",
+                manifest.block_height
+            )));
+        }
+        if !self.consensus_engine.verify_finality_certificate(certificate).await {
+            return Err(GarpError::ValidationError(format!(
+                "Finality certificate at height {} lacks a validator quorum",
+                manifest.block_height
+            )));
+        }
+        Ok(())
+    }
+
+    async fn on_manifest(&self, manifest: SnapshotManifest) -> GarpResult<Vec<FastSyncMessage>> {
+        self.expect_phase(FastSyncPhase::AwaitingManifest).await?;
+        self.verify_manifest(&manifest).await?;
+        info!(
+            "Downloading snapshot {} at height {} ({} bytes in {} chunks)",
+            manifest.snapshot_id, manifest.block_height, manifest.total_bytes, manifest.total_chunks
+        );
+        {
+            let mut state = self.state.write().await;
+            state.phase = FastSyncPhase::DownloadingSnapshot;
+            state.snapshot_height = Some(manifest.block_height);
+            state.total_chunks = manifest.total_chunks;
+            state.updated_at = Some(chrono::Utc::now());
+        }
+        let snapshot_id = manifest.snapshot_id.clone();
+        *self.download.lock().await = Some(Download { manifest, chunks: BTreeMap::new(), bytes: 0 });
+        Ok(vec![FastSyncMessage::SnapshotAccepted { snapshot_id }])
+    }
+
+    async fn on_chunk(&self, snapshot_id: &str, index: u32, data: Vec<u8>) -> GarpResult<Vec<FastSyncMessage>> {
+        self.expect_phase(FastSyncPhase::DownloadingSnapshot).await?;
+        let mut download = self.download.lock().await;
+        let current = download
+            .as_mut()
+            .ok_or_else(|| GarpError::InternalError("No snapshot download in progress".to_string()))?;
+        if current.manifest.snapshot_id != snapshot_id || index >= current.manifest.total_chunks {
+            return Err(GarpError::ValidationError(format!(
+                "Unexpected chunk {} of snapshot {} while downloading {}",
+                index, snapshot_id, current.manifest.snapshot_id
+            )));
+        }
+        if current.chunks.contains_key(&index) {
+            warn!("Duplicate chunk {} of snapshot {}", index, snapshot_id);
+            return Ok(Vec::new());
+        }
+        current.bytes += data.len() as u64;
+        if current.bytes > current.manifest.total_bytes {
+            return Err(GarpError::ValidationError(format!("Snapshot {} exceeds its announced size", snapshot_id)));
+        }
+        {
+            let mut state = self.state.write().await;
+            state.chunks_received += 1;
+            state.bytes_received += data.len() as u64;
+            state.updated_at = Some(chrono::Utc::now());
+        }
+        current.chunks.insert(index, data);
+        if current.chunks.len() < current.manifest.total_chunks as usize {
+            return Ok(Vec::new());
+        }
+
+        let Download { manifest, chunks, .. } = download.take().expect("download checked above");
+        drop(download);
+        let data: Vec<u8> = chunks.into_values().flatten().collect();
+        let height = manifest.block_height;
+        self.storage
+            .load_state_snapshot(StateSnapshot {
+                snapshot_id: manifest.snapshot_id,
+                block_height: height,
+                block_hash: manifest.block_hash,
+                state_root: manifest.state_root,
+                snapshot_data: data,
+                checksum: manifest.checksum,
+                created_at: manifest.created_at,
+                metadata: Default::default(),
+            })
+            .await?;
+        self.storage.store_finality_certificate(manifest.certificate).await?;
+        self.consensus_engine.advance_epoch(height).await?;
+        info!("Loaded snapshot at height {}; replaying later blocks", height);
+
+        {
+            let mut state = self.state.write().await;
+            state.phase = FastSyncPhase::ReplayingBlocks;
+            state.updated_at = Some(chrono::Utc::now());
+        }
+        Ok(vec![FastSyncMessage::BlocksRequest { from_height: height + 1 }])
+    }
+
+    async fn on_blocks(&self, blocks: Vec<BlockInfo>, complete: bool) -> GarpResult<Vec<FastSyncMessage>> {
+        self.expect_phase(FastSyncPhase::ReplayingBlocks).await?;
+        let mut next_height = self.storage.get_blockchain().await.height.max(self.state.read().await.snapshot_height.unwrap_or(0)) + 1;
+        let replayed = blocks.len() as u64;
+        for block in blocks {
+            if block.height != next_height {
+                return Err(GarpError::ValidationError(format!(
+                    "Expected block at height {}, got {}",
+                    next_height, block.height
+                )));
+            }
+            next_height += 1;
+            let height = block.height;
+            self.storage.store_block(block.block_hash.clone(), block).await?;
+            self.consensus_engine.advance_epoch(height).await?;
+        }
+
+        let mut state = self.state.write().await;
+        state.blocks_replayed += replayed;
+        state.updated_at = Some(chrono::Utc::now());
+        if complete {
+            info!("Fast sync complete at height {} ({} blocks replayed)", next_height - 1, state.blocks_replayed);
+            state.phase = FastSyncPhase::Complete;
+            return Ok(Vec::new());
+        }
+        Ok(vec![FastSyncMessage::BlocksRequest { from_height: next_height }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::DomainState;
+    use garp_common::types::ParticipantId;
+    use std::collections::{HashMap, VecDeque};
+    use std::time::Duration;
+
+    async fn node(chunk_bytes: usize) -> (FastSync, Arc<GlobalStorage>) {
+        let mut config = GlobalSyncConfig::default();
+        config.network.state_sync_chunk_bytes = chunk_bytes;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        (FastSync::new(config, storage.clone(), consensus_engine, network_manager), storage)
+    }
+
+    fn block(height: u64) -> BlockInfo {
+        BlockInfo {
+            block_hash: vec![height as u8; 32],
+            height,
+            parent_hash: vec![height.saturating_sub(1) as u8; 32],
+            transaction_count: 0,
+            size: 100,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 + height),
+            difficulty: 0,
+            nonce: 0,
+            merkle_root: Vec::new(),
+            state_root: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Serving node with blocks 1..=5 and a certified snapshot at height 3
+    async fn serving_node(state_root_override: Option<String>) -> (FastSync, Arc<GlobalStorage>) {
+        let (server, storage) = node(64).await;
+        for height in 1..=3 {
+            storage.store_block(block(height).block_hash, block(height)).await.unwrap();
+        }
+        storage
+            .update_state(&"domain1".to_string(), DomainState {
+                domain_id: "domain1".to_string(),
+                version: 1,
+                state_data: vec![7; 300],
+                state_hash: Vec::new(),
+                last_block_height: 3,
+                last_block_hash: block(3).block_hash,
+                pending_transactions: Vec::new(),
+                last_updated: SystemTime::UNIX_EPOCH,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let snapshot = storage.create_state_snapshot(3, block(3).block_hash).await.unwrap();
+        storage
+            .store_finality_certificate(FinalityCertificate {
+                height: 3,
+                block_hash: hex::encode(block(3).block_hash),
+                signatures: ["localhost:7000", "localhost:7001", "localhost:7002"]
+                    .iter()
+                    .map(|id| (ParticipantId::new(*id), vec![1u8]))
+                    .collect(),
+                validator_set_hash: String::new(),
+                timestamp: chrono::Utc::now(),
+                state_root: state_root_override.unwrap_or_else(|| hex::encode(&snapshot.state_root)),
+            })
+            .await
+            .unwrap();
+        for height in 4..=5 {
+            storage.store_block(block(height).block_hash, block(height)).await.unwrap();
+        }
+        (server, storage)
+    }
+
+    /// Deliver messages between the two nodes until neither has anything to send
+    async fn run(client: &FastSync, server: &FastSync) {
+        let (client_id, server_id) = ("client".to_string(), "server".to_string());
+        let mut to_server: VecDeque<FastSyncMessage> = VecDeque::from([client.begin(server_id.clone()).await.unwrap()]);
+        let mut to_client = VecDeque::new();
+        while !to_server.is_empty() || !to_client.is_empty() {
+            if let Some(message) = to_server.pop_front() {
+                to_client.extend(server.handle_message(&client_id, message).await.unwrap());
+            }
+            if let Some(message) = to_client.pop_front() {
+                match client.handle_message(&server_id, message).await {
+                    Ok(replies) => to_server.extend(replies),
+                    Err(_) => return,
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fast_sync_loads_snapshot_and_replays_later_blocks() {
+        let (server, server_storage) = serving_node(None).await;
+        let (client, client_storage) = node(64).await;
+        run(&client, &server).await;
+
+        let state = client.state().await;
+        assert_eq!(state.phase, FastSyncPhase::Complete);
+        assert_eq!(state.snapshot_height, Some(3));
+        assert!(state.total_chunks > 1);
+        assert_eq!(state.chunks_received, state.total_chunks);
+        assert_eq!(state.blocks_replayed, 2);
+
+        // Only blocks after the snapshot were replayed
+        assert!(client_storage.get_block_by_height(3).await.unwrap().is_none());
+        assert_eq!(client_storage.get_blockchain().await.height, 5);
+        assert_eq!(client_storage.get_state_root().await.unwrap(), server_storage.get_state_root().await.unwrap());
+        let domain = client_storage.get_state(&"domain1".to_string(), None).await.unwrap().unwrap();
+        assert_eq!(domain.state_data, vec![7; 300]);
+    }
+
+    #[tokio::test]
+    async fn test_fast_sync_rejects_snapshot_not_matching_certificate() {
+        let (server, _) = serving_node(Some(hex::encode([0u8; 32]))).await;
+        let (client, client_storage) = node(64).await;
+        run(&client, &server).await;
+
+        assert!(matches!(client.state().await.phase, FastSyncPhase::Failed(_)));
+        assert!(client_storage.get_state(&"domain1".to_string(), None).await.unwrap().is_none());
+        assert_eq!(client_storage.get_blockchain().await.height, 0);
+    }
+}
@@ -0,0 +1,328 @@
+//! Consensus round telemetry
+//!
+//! Each round moves through three phases: propose (proposal created until
+//! the first vote arrives), vote (first vote until quorum) and commit
+//! (quorum until the decision is committed). Phase latencies feed
+//! fixed-bucket histograms, so percentiles come from bucket counts rather
+//! than retained samples. Rounds also record which validators voted, for
+//! participation rates over a rolling window, and whether they finished
+//! in the view they were proposed in or only after a view change.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use garp_common::types::ParticipantId;
+
+/// Upper bound of the first histogram bucket, in milliseconds
+const HISTOGRAM_MIN_MS: f64 = 0.5;
+
+/// Ratio between consecutive bucket bounds
+const HISTOGRAM_GROWTH: f64 = 1.25;
+
+/// Finite buckets; the last bound is about 2 minutes
+const HISTOGRAM_BUCKETS: usize = 56;
+
+/// Rounds participation is measured over unless configured
+pub const DEFAULT_PARTICIPATION_WINDOW: usize = 100;
+
+/// Phase of a consensus round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundPhase {
+    /// Proposal created until the first vote arrives
+    Propose,
+
+    /// First vote until quorum
+    Vote,
+
+    /// Quorum until the decision is committed
+    Commit,
+}
+
+impl RoundPhase {
+    pub const ALL: [RoundPhase; 3] = [RoundPhase::Propose, RoundPhase::Vote, RoundPhase::Commit];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoundPhase::Propose => "propose",
+            RoundPhase::Vote => "vote",
+            RoundPhase::Commit => "commit",
+        }
+    }
+}
+
+/// Streaming latency histogram with geometrically growing buckets
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    /// Observations per finite bucket, plus one overflow bucket
+    counts: Vec<u64>,
+
+    /// Total observations
+    count: u64,
+
+    /// Largest observation, bounding the overflow bucket
+    max_ms: f64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self { counts: vec![0; HISTOGRAM_BUCKETS + 1], count: 0, max_ms: 0.0 }
+    }
+}
+
+impl LatencyHistogram {
+    fn upper_bound(bucket: usize) -> f64 {
+        HISTOGRAM_MIN_MS * HISTOGRAM_GROWTH.powi(bucket as i32)
+    }
+
+    fn bucket(ms: f64) -> usize {
+        if ms <= HISTOGRAM_MIN_MS {
+            return 0;
+        }
+        let bucket = ((ms / HISTOGRAM_MIN_MS).ln() / HISTOGRAM_GROWTH.ln()).ceil() as usize;
+        bucket.min(HISTOGRAM_BUCKETS)
+    }
+
+    /// Record one observation
+    pub fn record(&mut self, latency: Duration) {
+        let ms = latency.as_secs_f64() * 1000.0;
+        self.counts[Self::bucket(ms)] += 1;
+        self.count += 1;
+        self.max_ms = self.max_ms.max(ms);
+    }
+
+    /// Observations recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Estimate the `q` quantile (0 to 1) in milliseconds, interpolating
+    /// within the bucket it falls in; 0 when empty
+    pub fn percentile(&self, q: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let rank = (q.clamp(0.0, 1.0) * self.count as f64).max(1.0);
+        let mut seen = 0u64;
+        for (bucket, &count) in self.counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            if (seen + count) as f64 >= rank {
+                let lower = if bucket == 0 { 0.0 } else { Self::upper_bound(bucket - 1) };
+                let upper = if bucket == HISTOGRAM_BUCKETS {
+                    self.max_ms
+                } else {
+                    Self::upper_bound(bucket).min(self.max_ms)
+                };
+                let fraction = (rank - seen as f64) / count as f64;
+                return lower + (upper - lower).max(0.0) * fraction;
+            }
+            seen += count;
+        }
+        self.max_ms
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            count: self.count,
+            p50_ms: self.percentile(0.50),
+            p95_ms: self.percentile(0.95),
+            p99_ms: self.percentile(0.99),
+        }
+    }
+}
+
+/// Latency percentiles of one phase
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// Validators eligible in, and voting in, one round
+#[derive(Debug, Clone)]
+struct RoundParticipation {
+    eligible: Vec<ParticipantId>,
+    voters: HashSet<ParticipantId>,
+}
+
+/// Phase latencies, vote participation and round outcomes
+#[derive(Debug, Clone)]
+pub struct ConsensusTelemetry {
+    phases: BTreeMap<RoundPhase, LatencyHistogram>,
+
+    /// Most recent rounds, oldest first
+    rounds: VecDeque<RoundParticipation>,
+
+    /// Rounds kept for participation rates
+    window: usize,
+
+    /// Rounds finished in the view they were proposed in
+    happy_path_rounds: u64,
+
+    /// Rounds finished only after a view change
+    view_change_rounds: u64,
+}
+
+impl ConsensusTelemetry {
+    /// Telemetry measuring participation over the last `window` rounds
+    pub fn new(window: usize) -> Self {
+        Self {
+            phases: RoundPhase::ALL.into_iter().map(|phase| (phase, LatencyHistogram::default())).collect(),
+            rounds: VecDeque::with_capacity(window),
+            window: window.max(1),
+            happy_path_rounds: 0,
+            view_change_rounds: 0,
+        }
+    }
+
+    /// Record the time a round spent in `phase`
+    pub fn record_phase(&mut self, phase: RoundPhase, latency: Duration) {
+        self.phases.entry(phase).or_default().record(latency);
+    }
+
+    /// Record a finished round: the validators eligible to vote, those
+    /// that did, and whether it finished only after a view change
+    pub fn record_round(
+        &mut self,
+        eligible: impl IntoIterator<Item = ParticipantId>,
+        voters: impl IntoIterator<Item = ParticipantId>,
+        after_view_change: bool,
+    ) {
+        if self.rounds.len() == self.window {
+            self.rounds.pop_front();
+        }
+        self.rounds.push_back(RoundParticipation {
+            eligible: eligible.into_iter().collect(),
+            voters: voters.into_iter().collect(),
+        });
+        if after_view_change {
+            self.view_change_rounds += 1;
+        } else {
+            self.happy_path_rounds += 1;
+        }
+    }
+
+    /// Share of the windowed rounds each validator was eligible in that it
+    /// voted in
+    pub fn participation(&self) -> BTreeMap<String, f64> {
+        let mut tallies: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        for round in &self.rounds {
+            for validator in &round.eligible {
+                let tally = tallies.entry(validator.0.clone()).or_default();
+                tally.1 += 1;
+                if round.voters.contains(validator) {
+                    tally.0 += 1;
+                }
+            }
+        }
+        tallies.into_iter().map(|(validator, (voted, eligible))| (validator, voted as f64 / eligible as f64)).collect()
+    }
+
+    pub fn snapshot(&self) -> ConsensusTelemetrySnapshot {
+        ConsensusTelemetrySnapshot {
+            phase_latency: self.phases.iter().map(|(phase, histogram)| (phase.as_str().to_string(), histogram.summary())).collect(),
+            validator_participation: self.participation(),
+            participation_window_rounds: self.rounds.len(),
+            happy_path_rounds: self.happy_path_rounds,
+            view_change_rounds: self.view_change_rounds,
+        }
+    }
+}
+
+/// Serializable view of [`ConsensusTelemetry`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsensusTelemetrySnapshot {
+    /// Latency percentiles keyed by phase
+    pub phase_latency: BTreeMap<String, LatencySummary>,
+
+    /// Participation rate per validator over the window
+    pub validator_participation: BTreeMap<String, f64>,
+
+    /// Rounds the participation rates cover
+    pub participation_window_rounds: usize,
+
+    pub happy_path_rounds: u64,
+    pub view_change_rounds: u64,
+}
+
+/// Bound the label cardinality of a participation export: the `k`
+/// validators with the lowest participation, then "other" with the mean
+/// rate of the rest
+pub fn top_k_participation(participation: &BTreeMap<String, f64>, k: usize) -> Vec<(String, f64)> {
+    let mut rates: Vec<(String, f64)> = participation.iter().map(|(id, rate)| (id.clone(), *rate)).collect();
+    rates.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    if rates.len() <= k {
+        return rates;
+    }
+    let rest = rates.split_off(k);
+    let mean = rest.iter().map(|(_, rate)| rate).sum::<f64>() / rest.len() as f64;
+    rates.push(("other".to_string(), mean));
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(ids: &[&str]) -> Vec<ParticipantId> {
+        ids.iter().map(|id| ParticipantId::new(*id)).collect()
+    }
+
+    #[test]
+    fn test_histogram_percentiles_stay_within_bucket_error() {
+        let mut histogram = LatencyHistogram::default();
+        for ms in 1..=1000u64 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        assert_eq!(histogram.count(), 1000);
+        for (q, exact) in [(0.50, 500.0), (0.95, 950.0), (0.99, 990.0)] {
+            let estimate = histogram.percentile(q);
+            assert!((estimate - exact).abs() / exact < HISTOGRAM_GROWTH - 1.0, "p{} = {}", q * 100.0, estimate);
+        }
+        assert!(histogram.percentile(1.0) <= 1000.0);
+        assert_eq!(LatencyHistogram::default().percentile(0.5), 0.0);
+
+        // Observations beyond the last bucket are bounded by the maximum
+        let mut slow = LatencyHistogram::default();
+        slow.record(Duration::from_secs(600));
+        assert_eq!(slow.percentile(0.99), 600_000.0);
+    }
+
+    #[test]
+    fn test_participation_covers_the_rolling_window() {
+        let mut telemetry = ConsensusTelemetry::new(4);
+        let all = validators(&["v1", "v2", "v3"]);
+        // v3 misses the first two rounds, which fall out of the window
+        for _ in 0..2 {
+            telemetry.record_round(all.clone(), validators(&["v1", "v2"]), false);
+        }
+        for round in 0..4 {
+            let voters = if round == 0 { validators(&["v1", "v3"]) } else { all.clone() };
+            telemetry.record_round(all.clone(), voters, round == 3);
+        }
+
+        let snapshot = telemetry.snapshot();
+        assert_eq!(snapshot.participation_window_rounds, 4);
+        assert_eq!(snapshot.validator_participation["v1"], 1.0);
+        assert_eq!(snapshot.validator_participation["v2"], 0.75);
+        assert_eq!(snapshot.validator_participation["v3"], 1.0);
+        assert_eq!((snapshot.happy_path_rounds, snapshot.view_change_rounds), (5, 1));
+        assert_eq!(snapshot.phase_latency.len(), 3);
+    }
+
+    #[test]
+    fn test_top_k_participation_aggregates_the_rest() {
+        let participation: BTreeMap<String, f64> =
+            [("a", 1.0), ("b", 0.25), ("c", 0.75), ("d", 0.5)].into_iter().map(|(id, rate)| (id.to_string(), rate)).collect();
+
+        let bounded = top_k_participation(&participation, 2);
+        assert_eq!(bounded, vec![("b".to_string(), 0.25), ("d".to_string(), 0.5), ("other".to_string(), 0.875)]);
+        assert_eq!(top_k_participation(&participation, 10).len(), 4);
+    }
+}
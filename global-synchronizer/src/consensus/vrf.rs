@@ -0,0 +1,236 @@
+//! VRF-based proposer election
+//!
+//! Each validator evaluates ECVRF-EDWARDS25519-SHA512-TAI (RFC 9381) with
+//! its Ed25519 key on `view || epoch` and publishes the proof. The leader of
+//! the view is the validator with the smallest output among verified
+//! proofs, so the schedule cannot be known before validators reveal their
+//! proofs, and anyone holding the validator public keys can check it.
+
+use std::collections::HashMap;
+
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use curve25519_dalek::traits::IsIdentity;
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+use garp_common::types::ParticipantId;
+
+use crate::validator::ValidatorInfo;
+
+/// ECVRF-EDWARDS25519-SHA512-TAI suite identifier
+const SUITE: u8 = 0x03;
+
+/// Encoded proof length: Gamma (32) || c (16) || s (32)
+pub const PROOF_LEN: usize = 80;
+
+/// VRF output (beta)
+pub type VrfOutput = [u8; 64];
+
+/// A validator's VRF proof for one view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VrfProof {
+    /// View the proof was evaluated for
+    pub view: u64,
+
+    /// Validator epoch the proof was evaluated for
+    pub epoch: u64,
+
+    /// Encoded proof
+    pub proof: Vec<u8>,
+}
+
+/// VRF input for a view
+pub fn vrf_input(view: u64, epoch: u64) -> [u8; 16] {
+    let mut alpha = [0u8; 16];
+    alpha[..8].copy_from_slice(&view.to_be_bytes());
+    alpha[8..].copy_from_slice(&epoch.to_be_bytes());
+    alpha
+}
+
+/// Try-and-increment hash of `alpha` onto the curve
+fn hash_to_curve(public_key: &[u8; 32], alpha: &[u8]) -> Option<EdwardsPoint> {
+    (0..=u8::MAX).find_map(|ctr| {
+        let hash = Sha512::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public_key)
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&hash[..32]);
+        CompressedEdwardsY(candidate).decompress().map(|point| point.mul_by_cofactor())
+    })
+}
+
+/// Truncated challenge hash over the proof points
+fn challenge(points: [&EdwardsPoint; 5]) -> [u8; 16] {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE, 0x02]);
+    for point in points {
+        hasher.update(point.compress().as_bytes());
+    }
+    hasher.update([0x00]);
+    let mut c = [0u8; 16];
+    c.copy_from_slice(&hasher.finalize()[..16]);
+    c
+}
+
+fn challenge_scalar(c: &[u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+fn proof_to_output(gamma: &EdwardsPoint) -> VrfOutput {
+    let hash = Sha512::new()
+        .chain_update([SUITE, 0x03])
+        .chain_update(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain_update([0x00])
+        .finalize();
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&hash);
+    output
+}
+
+/// Evaluate the VRF for `view` and `epoch` with a validator's key
+pub fn prove(signing_key: &SigningKey, view: u64, epoch: u64) -> VrfProof {
+    let hashed_key = Sha512::digest(signing_key.to_bytes());
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&hashed_key[..32]);
+    let x = Scalar::from_bytes_mod_order(clamp_integer(scalar_bytes));
+    let y = EdwardsPoint::mul_base(&x);
+    let public_key = y.compress().to_bytes();
+
+    let alpha = vrf_input(view, epoch);
+    // Fails only with negligible probability over all 256 counters
+    let h = hash_to_curve(&public_key, &alpha).expect("VRF input maps to the curve");
+    let gamma = x * h;
+
+    // Deterministic nonce as in RFC 8032
+    let nonce_hash = Sha512::new()
+        .chain_update(&hashed_key[32..])
+        .chain_update(h.compress().as_bytes())
+        .finalize();
+    let mut nonce_bytes = [0u8; 64];
+    nonce_bytes.copy_from_slice(&nonce_hash);
+    let k = Scalar::from_bytes_mod_order_wide(&nonce_bytes);
+    let c = challenge([&y, &h, &gamma, &EdwardsPoint::mul_base(&k), &(k * h)]);
+    let s = k + challenge_scalar(&c) * x;
+
+    let mut proof = Vec::with_capacity(PROOF_LEN);
+    proof.extend_from_slice(gamma.compress().as_bytes());
+    proof.extend_from_slice(&c);
+    proof.extend_from_slice(s.as_bytes());
+    VrfProof { view, epoch, proof }
+}
+
+/// Verify `proof` against a hex-encoded Ed25519 public key, returning the
+/// VRF output if it is valid
+pub fn verify(public_key_hex: &str, proof: &VrfProof) -> Option<VrfOutput> {
+    let public_key: [u8; 32] = hex::decode(public_key_hex).ok()?.try_into().ok()?;
+    if proof.proof.len() != PROOF_LEN {
+        return None;
+    }
+    let y = CompressedEdwardsY(public_key).decompress()?;
+    if y.is_small_order() {
+        return None;
+    }
+
+    let gamma = CompressedEdwardsY(proof.proof[..32].try_into().ok()?).decompress()?;
+    let c: [u8; 16] = proof.proof[32..48].try_into().ok()?;
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(proof.proof[48..].try_into().ok()?))?;
+
+    let h = hash_to_curve(&public_key, &vrf_input(proof.view, proof.epoch))?;
+    let c_scalar = challenge_scalar(&c);
+    let u = EdwardsPoint::mul_base(&s) - c_scalar * y;
+    let v = s * h - c_scalar * gamma;
+    if challenge([&y, &h, &gamma, &u, &v]) != c || gamma.is_identity() {
+        return None;
+    }
+    Some(proof_to_output(&gamma))
+}
+
+/// Elect the leader of `view` in `epoch`: the validator with the smallest
+/// output among verified proofs. Outputs depend on each validator's private
+/// key, so only validators that have published a proof for the view are
+/// candidates; `None` until at least one valid proof is known.
+pub fn vrf_election<'a>(
+    validators: &'a [ValidatorInfo],
+    view: u64,
+    epoch: u64,
+    proofs: &HashMap<ParticipantId, VrfProof>,
+) -> Option<&'a ValidatorInfo> {
+    validators
+        .iter()
+        .filter_map(|validator| {
+            let proof = proofs.get(&validator.id)?;
+            if proof.view != view || proof.epoch != epoch {
+                return None;
+            }
+            verify(&validator.public_key_hex, proof).map(|output| (output, validator))
+        })
+        .min_by(|(a, va), (b, vb)| a.cmp(b).then_with(|| va.id.0.cmp(&vb.id.0)))
+        .map(|(_, validator)| validator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(seed: u8) -> (SigningKey, ValidatorInfo) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let info = ValidatorInfo::new(
+            ParticipantId::new(format!("validator-{}", seed)),
+            hex::encode(key.verifying_key().to_bytes()),
+            1,
+        );
+        (key, info)
+    }
+
+    #[test]
+    fn test_proof_verifies_only_for_its_key_and_view() {
+        let (key, info) = validator(1);
+        let (_, other) = validator(2);
+        let proof = prove(&key, 7, 1);
+        assert_eq!(proof.proof.len(), PROOF_LEN);
+
+        let output = verify(&info.public_key_hex, &proof).expect("valid proof");
+        assert_eq!(prove(&key, 7, 1), proof, "proofs are deterministic");
+        assert_ne!(verify(&info.public_key_hex, &prove(&key, 8, 1)), Some(output));
+        assert!(verify(&other.public_key_hex, &proof).is_none());
+
+        let mut wrong_view = proof.clone();
+        wrong_view.view = 8;
+        assert!(verify(&info.public_key_hex, &wrong_view).is_none());
+
+        let mut tampered = proof;
+        tampered.proof[40] ^= 1;
+        assert!(verify(&info.public_key_hex, &tampered).is_none());
+    }
+
+    #[test]
+    fn test_election_picks_smallest_verified_output() {
+        let validators: Vec<(SigningKey, ValidatorInfo)> = (1..=4).map(validator).collect();
+        let infos: Vec<ValidatorInfo> = validators.iter().map(|(_, info)| info.clone()).collect();
+        let (view, epoch) = (3, 0);
+
+        let mut proofs: HashMap<ParticipantId, VrfProof> = validators
+            .iter()
+            .map(|(key, info)| (info.id.clone(), prove(key, view, epoch)))
+            .collect();
+        let expected = infos
+            .iter()
+            .min_by_key(|info| verify(&info.public_key_hex, &proofs[&info.id]).unwrap())
+            .unwrap();
+        assert_eq!(vrf_election(&infos, view, epoch, &proofs).unwrap().id, expected.id);
+
+        // Proofs for another view do not count
+        let (stale_key, stale) = validators.iter().find(|(_, info)| info.id == expected.id).unwrap();
+        proofs.insert(stale.id.clone(), prove(stale_key, view + 1, epoch));
+        assert_ne!(vrf_election(&infos, view, epoch, &proofs).unwrap().id, expected.id);
+
+        assert!(vrf_election(&infos, view, epoch, &HashMap::new()).is_none());
+    }
+}
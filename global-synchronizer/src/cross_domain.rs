@@ -1,20 +1,42 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::ops::Bound;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Mutex, mpsc, oneshot};
 use tokio::time::{interval, timeout};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
-use tracing::{info, warn, error, debug};
+use tracing::{info, warn, error, debug, instrument};
 
-use garp_common::{GarpResult, GarpError};
+use garp_common::{GarpResult, GarpError, KeyRotation, ReputationSignal, ReputationTracker};
+use garp_common::canonical::{self, CANONICAL_ENCODING};
+use garp_common::error::TransactionError;
 use garp_common::types::{TransactionId, ParticipantId};
 
 use crate::config::GlobalSyncConfig;
-use crate::storage::{GlobalStorage, GlobalBlock, DomainId};
-use crate::network::NetworkManager;
-use crate::discovery::DomainDiscovery;
+use crate::storage::{GlobalStorage, GlobalBlock, DomainId, StateSyncOutcome, StateTransition};
+use crate::network::{MessagePriority, NetworkManager, PeerStatusChange};
+use crate::discovery::{DiscoveryEvent, DomainAnnouncement, DomainDiscovery, DomainRegistration, RegisteredDomain};
 use crate::consensus::{ConsensusEngine, ConsensusResult};
+use crate::settlement::compensation::{CompensationChain, CompensationEngine};
+use crate::timelock::{TimelockEntry, TimelockQueue};
+use crate::validator::ValidatorInfo;
+
+pub mod channel;
+pub mod delegation;
+pub mod emergency;
+pub mod governance;
+pub mod swap;
+pub mod validation;
+pub mod wrapping;
+
+use channel::{ChannelId, ChannelUpdate, StateChannel, StateChannelManager};
+use delegation::DomainDelegation;
+use emergency::{AuthorizedEmergency, EmergencyAuditRecord, EmergencyAuthorizer, EmergencySignature};
+use governance::{GovernanceAction, GovernanceEngine, GovernanceProposal, ProposalId, COORDINATION_TIMEOUT_PARAMETER};
+use validation::{TransactionValidator, ValidationWorkerPool};
+use swap::{AtomicSwap, AtomicSwapManager, SwapId, SwapInstruction, SwapInstructions, SwapStatus, SWAP_HASHLOCK_METADATA_KEY};
+use wrapping::{TokenWrapper, TokenWrapping, WrappedToken, TOKEN_WRAPPING_METADATA_KEY};
 
 /// Cross-domain coordinator for managing transactions across multiple domains
 pub struct CrossDomainCoordinator {
@@ -54,8 +76,56 @@ pub struct CrossDomainCoordinator {
     
     /// Metrics
     metrics: Arc<CrossDomainMetrics>,
+    
+    /// Per-domain performance metrics
+    domain_metrics: DomainMetricsMap,
+    
+    /// Transactions waiting for their `not_before` time
+    timelock_queue: Arc<TimelockQueue>,
+    
+    /// State channels between domains
+    channel_manager: Arc<StateChannelManager>,
+    
+    /// Cross-domain governance proposals
+    governance_engine: Arc<GovernanceEngine>,
+    
+    /// Hashed-timelock atomic swaps
+    swap_manager: Arc<AtomicSwapManager>,
+    
+    /// Token wrappings carried out by atomic swaps
+    token_wrapper: Arc<TokenWrapper>,
+    
+    /// Designated emergency keys
+    emergency_authorizer: Arc<EmergencyAuthorizer>,
+    
+    /// Reverses aborted transactions on domains that had confirmed
+    compensation_engine: Arc<CompensationEngine>,
+    
+    /// Domains in maintenance mode
+    suspensions: Arc<RwLock<HashMap<DomainId, DomainSuspension>>>,
+    
+    /// Content hashes of submissions between their duplicate check and
+    /// admission
+    submissions_in_flight: Arc<Mutex<HashMap<TransactionId, [u8; 32]>>>,
+    
+    /// Domain reputation, fed by heartbeats and completed transactions
+    reputation: Arc<ReputationTracker>,
+    
+    /// Validates submissions concurrently
+    validation_pool: Arc<ValidationWorkerPool>,
 }
 
+/// Metadata key under which stored transactions record their content hash
+pub const CONTENT_HASH_METADATA_KEY: &str = "content_hash";
+
+/// Metadata key recording the encoding the stored content hash was computed
+/// over; hashes stored without it use [`CrossDomainTransaction::legacy_content_hash`]
+pub const CONTENT_HASH_ENCODING_METADATA_KEY: &str = "content_hash_encoding";
+
+/// Optional metadata key holding a JSON object of target domain ID to the
+/// hex state root the transaction was built against
+pub const REQUIRED_STATE_ROOT_METADATA_KEY: &str = "required_state_root";
+
 /// Cross-domain transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrossDomainTransaction {
@@ -95,10 +165,94 @@ pub struct CrossDomainTransaction {
     /// Timeout
     pub timeout_at: chrono::DateTime<chrono::Utc>,
     
+    /// Earliest time at which coordination may start
+    #[serde(default)]
+    pub not_before: Option<chrono::DateTime<chrono::Utc>>,
+    
+    /// Maximum settlement fee the submitter is willing to pay
+    #[serde(default)]
+    pub max_fee: u64,
+    
+    /// Domain the source domain submits on behalf of, under a delegation
+    /// granting it `SubmitTransactions`
+    #[serde(default)]
+    pub delegated_by: Option<DomainId>,
+    
     /// Metadata
     pub metadata: HashMap<String, String>,
 }
 
+impl CrossDomainTransaction {
+    /// Hash of the submitted content an ID is bound to: source and target
+    /// domains, type and data, in the canonical encoding
+    pub fn content_hash(&self) -> GarpResult<[u8; 32]> {
+        let content = canonical::to_canonical_json(&self.hashed_content())?;
+        Ok(*blake3::hash(&content).as_bytes())
+    }
+    
+    /// [`Self::content_hash`] as computed before the canonical encoding, to
+    /// compare against hashes stored without an encoding tag
+    pub fn legacy_content_hash(&self) -> GarpResult<[u8; 32]> {
+        let content = serde_json::to_vec(&self.hashed_content())?;
+        Ok(*blake3::hash(&content).as_bytes())
+    }
+    
+    fn hashed_content(&self) -> (&DomainId, &Vec<DomainId>, &CrossDomainTransactionType, &Vec<u8>) {
+        (&self.source_domain, &self.target_domains, &self.transaction_type, &self.data)
+    }
+    
+    /// State roots the transaction requires of its target domains, from
+    /// its `required_state_root` metadata
+    pub fn required_state_roots(&self) -> GarpResult<HashMap<DomainId, String>> {
+        match self.metadata.get(REQUIRED_STATE_ROOT_METADATA_KEY) {
+            Some(roots) => serde_json::from_str(roots).map_err(|e| {
+                GarpError::ValidationError(format!("Invalid {} metadata: {}", REQUIRED_STATE_ROOT_METADATA_KEY, e))
+            }),
+            None => Ok(HashMap::new()),
+        }
+    }
+    
+    /// Require `domain_id` to be at `state_root` when the transaction is
+    /// accepted
+    pub fn require_state_root(&mut self, domain_id: &DomainId, state_root: &str) -> GarpResult<()> {
+        let mut roots = self.required_state_roots()?;
+        roots.insert(domain_id.clone(), state_root.to_string());
+        self.metadata.insert(REQUIRED_STATE_ROOT_METADATA_KEY.to_string(), serde_json::to_string(&roots)?);
+        Ok(())
+    }
+}
+
+/// Outcome of an accepted submission
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmissionOutcome {
+    /// New transaction, now coordinating or waiting for its time lock
+    Submitted(TransactionId),
+    
+    /// The same transaction is already in flight; nothing new was started
+    AlreadySubmitted(TransactionId),
+}
+
+impl SubmissionOutcome {
+    /// ID of the submitted transaction
+    pub fn transaction_id(&self) -> &TransactionId {
+        match self {
+            Self::Submitted(id) | Self::AlreadySubmitted(id) => id,
+        }
+    }
+}
+
+/// An earlier submission under the same transaction ID
+struct PriorSubmission {
+    /// Content hash, if known
+    content_hash: Option<[u8; 32]>,
+    
+    /// Whether `content_hash` predates the canonical encoding
+    legacy_hash: bool,
+    
+    /// Whether it has finished
+    terminal: bool,
+}
+
 /// Cross-domain transaction type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CrossDomainTransactionType {
@@ -140,14 +294,45 @@ pub enum CrossDomainTransactionType {
         proposal_data: Vec<u8>,
     },
     
-    /// Emergency action
+    /// Emergency action, signed by a quorum of designated emergency keys
     EmergencyAction {
         action_type: String,
         action_data: Vec<u8>,
         justification: String,
+        #[serde(default)]
+        signatures: Vec<EmergencySignature>,
+    },
+    
+    /// Lock collateral for a state channel
+    ChannelLock {
+        channel_id: String,
+        capacity: u64,
+    },
+    
+    /// Settle a closed state channel
+    ChannelSettlement {
+        channel_id: String,
+        sequence: u64,
+        balance_a: u64,
+        balance_b: u64,
+    },
+    
+    /// Undo a domain's effects from an aborted transaction
+    Compensation {
+        original_transaction_id: TransactionId,
     },
 }
 
+/// Outcome of validating a submitted transaction
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TransactionAdmission {
+    /// Coordination can start immediately
+    Ready,
+    
+    /// Coordination must wait until the given time
+    Timelocked(chrono::DateTime<chrono::Utc>),
+}
+
 /// Transaction status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TransactionStatus {
@@ -173,6 +358,13 @@ pub enum TransactionStatus {
     Cancelled,
 }
 
+impl TransactionStatus {
+    /// Whether the transaction can no longer change status
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Rejected | Self::TimedOut | Self::Failed | Self::Cancelled)
+    }
+}
+
 /// Domain confirmation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomainConfirmation {
@@ -193,6 +385,11 @@ pub struct DomainConfirmation {
     
     /// Validator info
     pub validator_info: Option<String>,
+    
+    /// Increases with each vote the domain casts, so a redelivered
+    /// confirmation can be told apart from a new one
+    #[serde(default)]
+    pub vote_sequence_number: u64,
 }
 
 /// Confirmation status
@@ -229,6 +426,10 @@ pub struct DomainState {
     /// State root
     pub state_root: String,
     
+    /// Hex state root reported by the domain's latest state sync response;
+    /// empty until the first
+    pub state_root_hash: String,
+    
     /// Active validators
     pub validators: Vec<ParticipantId>,
     
@@ -267,6 +468,25 @@ pub enum DomainStatus {
     Faulty,
 }
 
+/// Maintenance window of a suspended domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainSuspension {
+    /// Suspended domain
+    pub domain_id: DomainId,
+    
+    /// Why the domain was suspended
+    pub reason: String,
+    
+    /// Time the suspension started
+    pub suspended_at: chrono::DateTime<chrono::Utc>,
+    
+    /// Earliest time the domain monitor lifts the suspension
+    pub resume_at: chrono::DateTime<chrono::Utc>,
+    
+    /// Transactions held back for retry once the suspension lifts
+    pub queued: Vec<CrossDomainTransaction>,
+}
+
 /// Domain capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomainCapabilities {
@@ -381,6 +601,10 @@ pub struct CoordinationVote {
     
     /// Timestamp
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    
+    /// Sequence number of the vote among the domain's votes
+    #[serde(default)]
+    pub vote_sequence_number: u64,
 }
 
 /// Coordination result
@@ -425,6 +649,27 @@ pub struct CrossDomainMessage {
     
     /// Signature
     pub signature: Vec<u8>,
+    
+    /// Correlation ID of the transaction this message belongs to, so the
+    /// receiving domain can continue the same trace
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+impl CrossDomainMessage {
+    /// Span for handling this message, carrying its correlation ID
+    pub fn span(&self) -> tracing::Span {
+        let span = tracing::info_span!(
+            "cross_domain_message",
+            message_id = %self.message_id,
+            source_domain = %self.source_domain,
+            correlation_id = tracing::field::Empty,
+        );
+        if let Some(correlation_id) = &self.correlation_id {
+            span.record("correlation_id", correlation_id.as_str());
+        }
+        span
+    }
 }
 
 /// Cross-domain message type
@@ -453,6 +698,51 @@ pub enum CrossDomainMessageType {
     
     /// Emergency notification
     EmergencyNotification(EmergencyNotification),
+    
+    /// Governance proposal open for domain votes
+    GovernanceProposal(GovernanceProposal),
+    
+    /// Domain vote on a governance proposal
+    GovernanceVote(GovernanceVoteMessage),
+    
+    /// Claim or refund instruction for a domain's atomic swap leg
+    SwapInstruction(SwapInstruction),
+    
+    /// State synchronization rejected as stale; the source domain should rebase
+    StateVersionConflict(StateVersionConflict),
+}
+
+/// A state synchronization whose version was not newer than a target
+/// domain's stored version of the key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateVersionConflict {
+    /// Rejected transaction
+    pub transaction_id: TransactionId,
+    
+    /// Target domain holding the newer version
+    pub domain_id: DomainId,
+    
+    /// State key
+    pub state_key: String,
+    
+    /// Version stored on the target domain
+    pub stored_version: u64,
+    
+    /// Version the transaction carried
+    pub incoming_version: u64,
+}
+
+/// Domain vote on a governance proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceVoteMessage {
+    /// Proposal ID
+    pub proposal_id: ProposalId,
+    
+    /// Voting domain
+    pub domain_id: DomainId,
+    
+    /// Whether the domain approves the proposal
+    pub approve: bool,
 }
 
 /// State synchronization request
@@ -469,6 +759,36 @@ pub struct StateSyncRequest {
     
     /// To height
     pub to_height: u64,
+    
+    /// Continue after this state key, from a previous response's `next_cursor`
+    #[serde(default)]
+    pub cursor: Option<String>,
+    
+    /// Largest response chunk the requester wants; 0 leaves it to the responder
+    #[serde(default)]
+    pub max_response_bytes: usize,
+}
+
+impl StateSyncRequest {
+    /// Whether `key` is covered by the requested state keys. A trailing `*`
+    /// matches any key with that prefix.
+    pub fn matches_key(&self, key: &str) -> bool {
+        self.state_keys.iter().any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => key.starts_with(prefix),
+            None => pattern == key,
+        })
+    }
+    
+    /// Request for the chunk after `response`, if it has more data
+    pub fn continuation(&self, response: &StateSyncResponse) -> Option<Self> {
+        if !response.has_more {
+            return None;
+        }
+        Some(Self {
+            cursor: Some(response.next_cursor.clone()?),
+            ..self.clone()
+        })
+    }
 }
 
 /// State synchronization response
@@ -485,6 +805,64 @@ pub struct StateSyncResponse {
     
     /// Has more data
     pub has_more: bool,
+    
+    /// Cursor for the next chunk when `has_more` is set
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+    
+    /// Hex state root of the responding domain at the end of `height_range`
+    #[serde(default)]
+    pub state_root: Option<String>,
+}
+
+impl StateSyncResponse {
+    /// Answer `request` with the next chunk of `state`. Entries are taken in
+    /// key order after the request cursor until their keys and values would
+    /// exceed `max_chunk_bytes` (or the request's smaller limit); a chunk
+    /// always carries at least one entry so oversized values still progress.
+    pub fn chunk(
+        request: &StateSyncRequest,
+        state: &BTreeMap<String, Vec<u8>>,
+        height_range: (u64, u64),
+        max_chunk_bytes: usize,
+    ) -> Self {
+        let limit = match request.max_response_bytes {
+            0 => max_chunk_bytes,
+            requested => requested.min(max_chunk_bytes),
+        };
+        let start = match &request.cursor {
+            Some(cursor) => Bound::Excluded(cursor.clone()),
+            None => Bound::Unbounded,
+        };
+        
+        let mut entries = state
+            .range((start, Bound::Unbounded))
+            .filter(|(key, _)| request.matches_key(key))
+            .peekable();
+        let mut state_data = HashMap::new();
+        let mut size = 0;
+        let mut last_key = None;
+        while let Some((key, value)) = entries.peek() {
+            let entry_size = key.len() + value.len();
+            if !state_data.is_empty() && size + entry_size > limit {
+                break;
+            }
+            size += entry_size;
+            state_data.insert((*key).clone(), (*value).clone());
+            last_key = Some((*key).clone());
+            entries.next();
+        }
+        let has_more = entries.peek().is_some();
+        
+        Self {
+            request_id: request.request_id.clone(),
+            state_data,
+            height_range,
+            has_more,
+            next_cursor: if has_more { last_key } else { None },
+            state_root: None,
+        }
+    }
 }
 
 /// Domain status update
@@ -567,6 +945,9 @@ pub enum EmergencyType {
     
     /// Security breach
     SecurityBreach,
+    
+    /// A settlement rollback could not be compensated on every domain
+    CompensationFailed,
 }
 
 /// Emergency severity
@@ -612,6 +993,9 @@ pub enum CrossDomainEvent {
     /// State synchronization required
     StateSynchronizationRequired(DomainId),
     
+    /// State synchronization rejected by version conflict
+    StateVersionConflict(StateVersionConflict),
+    
     /// Emergency detected
     EmergencyDetected(EmergencyNotification),
     
@@ -644,6 +1028,40 @@ pub struct CrossDomainMetrics {
     pub cross_domain_throughput: Arc<RwLock<f64>>,
 }
 
+/// Per-domain performance metrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DomainPerfMetrics {
+    /// Confirmations received from the domain
+    pub confirmations_received: u64,
+    
+    /// Average latency between transaction creation and confirmation
+    pub avg_confirmation_latency_ms: f64,
+    
+    /// Confirmations that were rejected or failed
+    pub failed_confirmations: u64,
+    
+    /// Last confirmation received
+    pub last_activity: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Shared per-domain metrics map
+pub type DomainMetricsMap = Arc<RwLock<HashMap<DomainId, DomainPerfMetrics>>>;
+
+impl DomainPerfMetrics {
+    /// Record a confirmation, folding its latency into the running average
+    pub fn record_confirmation(&mut self, confirmation: &DomainConfirmation, latency_ms: Option<f64>) {
+        self.confirmations_received += 1;
+        if matches!(confirmation.status, ConfirmationStatus::Rejected | ConfirmationStatus::Failed) {
+            self.failed_confirmations += 1;
+        }
+        if let Some(latency_ms) = latency_ms {
+            let n = self.confirmations_received as f64;
+            self.avg_confirmation_latency_ms += (latency_ms - self.avg_confirmation_latency_ms) / n;
+        }
+        self.last_activity = Some(confirmation.timestamp);
+    }
+}
+
 impl CrossDomainCoordinator {
     /// Create new cross-domain coordinator
     pub async fn new(
@@ -666,23 +1084,64 @@ impl CrossDomainCoordinator {
             cross_domain_throughput: Arc::new(RwLock::new(0.0)),
         });
         
+        let channel_manager = Arc::new(StateChannelManager::new(config.clone(), storage.clone()));
+        let governance_engine = Arc::new(GovernanceEngine::new(config.clone(), storage.clone()));
+        let swap_manager = Arc::new(AtomicSwapManager::new(storage.clone()));
+        let token_wrapper = Arc::new(TokenWrapper::new(storage.clone()));
+        let emergency_authorizer = Arc::new(EmergencyAuthorizer::new(&config.security)?);
+        let compensation_engine = Arc::new(CompensationEngine::new(storage.clone()));
+        let reputation = Arc::new(ReputationTracker::new(config.reputation.clone()));
+        
+        let active_transactions = Arc::new(RwLock::new(HashMap::new()));
+        let domain_states = Arc::new(RwLock::new(HashMap::new()));
+        let suspensions = Arc::new(RwLock::new(HashMap::new()));
+        let validation_pool = Arc::new(ValidationWorkerPool::new(
+            TransactionValidator {
+                storage: storage.clone(),
+                active_transactions: active_transactions.clone(),
+                domain_states: domain_states.clone(),
+                suspensions: suspensions.clone(),
+                swap_manager: swap_manager.clone(),
+                governance_engine: governance_engine.clone(),
+            },
+            config.cross_domain.validation_parallelism,
+        ));
+        
         Ok(Self {
             config,
             storage,
             network_manager,
             domain_discovery,
             consensus_engine,
-            active_transactions: Arc::new(RwLock::new(HashMap::new())),
-            domain_states: Arc::new(RwLock::new(HashMap::new())),
+            active_transactions,
+            domain_states,
             coordination_sessions: Arc::new(RwLock::new(HashMap::new())),
             message_queue: Arc::new(Mutex::new(VecDeque::new())),
             event_tx,
             event_rx,
             shutdown_tx: None,
             metrics,
+            domain_metrics: Arc::new(RwLock::new(HashMap::new())),
+            timelock_queue: Arc::new(TimelockQueue::new()),
+            channel_manager,
+            governance_engine,
+            swap_manager,
+            token_wrapper,
+            emergency_authorizer,
+            compensation_engine,
+            suspensions,
+            submissions_in_flight: Arc::new(Mutex::new(HashMap::new())),
+            reputation,
+            validation_pool,
         })
     }
     
+    /// Share a reputation tracker with the rest of the node
+    pub fn with_reputation(mut self, reputation: Arc<ReputationTracker>) -> Self {
+        self.reputation = reputation;
+        self
+    }
+    
     /// Start the cross-domain coordinator
     pub async fn start(&self) -> GarpResult<()> {
         info!("Starting Cross-Domain Coordinator");
@@ -696,6 +1155,16 @@ impl CrossDomainCoordinator {
         // Start domain monitor
         let domain_monitor = self.start_domain_monitor().await?;
         
+        // Follow peer topology changes
+        let topology_monitor = self.start_topology_monitor().await?;
+        
+        // Follow peer connection state between heartbeats
+        let peer_status_monitor = self.start_peer_status_monitor().await?;
+        
+        // Follow domain registrations and expiries
+        self.domain_discovery.start().await?;
+        let discovery_monitor = self.start_discovery_monitor().await?;
+        
         // Start coordination session monitor
         let session_monitor = self.start_coordination_session_monitor().await?;
         
@@ -719,21 +1188,96 @@ impl CrossDomainCoordinator {
         Ok(())
     }
     
-    /// Submit cross-domain transaction
-    pub async fn submit_transaction(&self, transaction: CrossDomainTransaction) -> GarpResult<()> {
-        info!("Submitting cross-domain transaction: {}", transaction.transaction_id);
+    /// Submit cross-domain transaction. Resubmitting a transaction that is
+    /// still in flight returns `AlreadySubmitted` without starting another
+    /// session; resubmitting a finished one, or reusing its ID for different
+    /// content, is rejected.
+    #[instrument(skip_all, fields(correlation_id = %transaction.transaction_id))]
+    pub async fn submit_transaction(&self, transaction: CrossDomainTransaction) -> GarpResult<SubmissionOutcome> {
+        info!(transaction_id = %transaction.transaction_id, source_domain = %transaction.source_domain, "Submitting cross-domain transaction");
         
-        // Validate transaction
-        self.validate_transaction(&transaction).await?;
+        let transaction_id = transaction.transaction_id.clone();
+        let content_hash = transaction.content_hash()?;
         
-        // Store transaction
+        // Check and reserve the ID atomically so concurrent duplicates see
+        // each other
         {
-            let mut transactions = self.active_transactions.write().await;
-            transactions.insert(transaction.transaction_id.clone(), transaction.clone());
+            let mut in_flight = self.submissions_in_flight.lock().await;
+            let prior = match in_flight.get(&transaction_id) {
+                Some(hash) => Some(PriorSubmission { content_hash: Some(*hash), legacy_hash: false, terminal: false }),
+                None => self.prior_submission(&transaction_id).await?,
+            };
+            if let Some(prior) = prior {
+                let expected = if prior.legacy_hash { transaction.legacy_content_hash()? } else { content_hash };
+                if prior.content_hash.map_or(false, |hash| hash != expected) {
+                    return Err(TransactionError::ConflictingTransactionId(transaction_id).into());
+                }
+                if prior.terminal {
+                    return Err(TransactionError::DuplicateTransaction(transaction_id).into());
+                }
+                debug!("Transaction {} already submitted", transaction_id);
+                return Ok(SubmissionOutcome::AlreadySubmitted(transaction_id));
+            }
+            in_flight.insert(transaction_id.clone(), content_hash);
         }
         
-        // Start coordination session
-        self.start_coordination_session(&transaction).await?;
+        let result = self.admit_transaction(transaction).await;
+        self.submissions_in_flight.lock().await.remove(&transaction_id);
+        result.map(|()| SubmissionOutcome::Submitted(transaction_id))
+    }
+    
+    /// Find an earlier submission under `transaction_id`, whether it is
+    /// coordinating, waiting in a queue or only known to storage
+    async fn prior_submission(&self, transaction_id: &TransactionId) -> GarpResult<Option<PriorSubmission>> {
+        if let Some(transaction) = self.active_transactions.read().await.get(transaction_id) {
+            return Ok(Some(PriorSubmission {
+                content_hash: Some(transaction.content_hash()?),
+                legacy_hash: false,
+                terminal: transaction.status.is_terminal(),
+            }));
+        }
+        
+        let queued = match self.timelock_queue.get(transaction_id).await {
+            Some(transaction) => Some(transaction),
+            None => self.suspensions.read().await.values()
+                .flat_map(|suspension| suspension.queued.iter())
+                .find(|transaction| &transaction.transaction_id == transaction_id)
+                .cloned(),
+        };
+        if let Some(transaction) = queued {
+            return Ok(Some(PriorSubmission { content_hash: Some(transaction.content_hash()?), legacy_hash: false, terminal: false }));
+        }
+        
+        Ok(self.storage.get_transaction(transaction_id).await?.map(|stored| PriorSubmission {
+            content_hash: stored.metadata.get(CONTENT_HASH_METADATA_KEY)
+                .and_then(|hash| hex::decode(hash).ok())
+                .and_then(|hash| hash.try_into().ok()),
+            legacy_hash: stored.metadata.get(CONTENT_HASH_ENCODING_METADATA_KEY).map(String::as_str) != Some(CANONICAL_ENCODING),
+            terminal: stored.status.is_terminal(),
+        }))
+    }
+    
+    /// Validate a new transaction and start or time-lock its coordination
+    async fn admit_transaction(&self, transaction: CrossDomainTransaction) -> GarpResult<()> {
+        // Emergency actions need a quorum of designated keys before anything else
+        let emergency = match transaction.transaction_type {
+            CrossDomainTransactionType::EmergencyAction { .. } => Some(self.authorize_emergency_action(&transaction).await?),
+            _ => None,
+        };
+        
+        // Validate transaction
+        let admission = match self.validate_transaction(&transaction).await {
+            Ok(admission) => admission,
+            Err(e) => {
+                if let Some(authorized) = emergency {
+                    self.audit_emergency_action(&transaction, Some(e.to_string()), authorized.key_ids).await?;
+                }
+                return Err(e);
+            }
+        };
+        if let Some(authorized) = emergency {
+            self.audit_emergency_action(&transaction, None, authorized.key_ids).await?;
+        }
         
         // Update metrics
         {
@@ -741,579 +1285,2443 @@ impl CrossDomainCoordinator {
             *total += 1;
         }
         
-        // Emit event
-        self.event_tx.send(CrossDomainEvent::TransactionSubmitted(transaction))?;
+        if let TransactionAdmission::Timelocked(unlock_at) = admission {
+            info!(transaction_id = %transaction.transaction_id, unlock_at = %unlock_at, "Transaction is time-locked");
+            self.timelock_queue.enqueue(transaction).await;
+            return Ok(());
+        }
         
-        Ok(())
-    }
-    
-    /// Get transaction status
-    pub async fn get_transaction_status(&self, transaction_id: &TransactionId) -> Option<TransactionStatus> {
-        let transactions = self.active_transactions.read().await;
-        transactions.get(transaction_id).map(|tx| tx.status.clone())
+        self.activate_transaction(transaction).await
     }
     
-    /// Get domain state
-    pub async fn get_domain_state(&self, domain_id: &DomainId) -> Option<DomainState> {
-        let states = self.domain_states.read().await;
-        states.get(domain_id).cloned()
+    /// Check an emergency action's signatures. Rejections are audited and
+    /// raised as a security breach.
+    async fn authorize_emergency_action(&self, transaction: &CrossDomainTransaction) -> GarpResult<AuthorizedEmergency> {
+        let rejection = match self.emergency_authorizer.authorize(transaction) {
+            Ok(authorized) => return Ok(authorized),
+            Err(rejection) => rejection,
+        };
+        
+        warn!("Rejected emergency action {}: {}", transaction.transaction_id, rejection.reason);
+        self.audit_emergency_action(transaction, Some(rejection.reason.clone()), rejection.key_ids).await?;
+        self.event_tx.send(CrossDomainEvent::EmergencyDetected(EmergencyNotification {
+            notification_id: Uuid::new_v4().to_string(),
+            emergency_type: EmergencyType::SecurityBreach,
+            affected_domains: transaction.target_domains.clone(),
+            description: format!(
+                "Unauthorized emergency action {} from domain {}: {}",
+                transaction.transaction_id, transaction.source_domain, rejection.reason
+            ),
+            severity: EmergencySeverity::High,
+            action_required: true,
+        }))?;
+        
+        Err(GarpError::ValidationError(format!("Emergency action not authorized: {}", rejection.reason)))
     }
     
-    /// Get active domains
-    pub async fn get_active_domains(&self) -> Vec<DomainId> {
-        let states = self.domain_states.read().await;
-        states.values()
-            .filter(|state| state.status == DomainStatus::Active)
-            .map(|state| state.domain_id.clone())
-            .collect()
+    /// Record an emergency action decision; `rejection` is `None` when accepted
+    async fn audit_emergency_action(
+        &self,
+        transaction: &CrossDomainTransaction,
+        rejection: Option<String>,
+        key_ids: Vec<String>,
+    ) -> GarpResult<()> {
+        let action_type = match &transaction.transaction_type {
+            CrossDomainTransactionType::EmergencyAction { action_type, .. } => action_type.clone(),
+            _ => String::new(),
+        };
+        self.storage.append_emergency_audit(EmergencyAuditRecord {
+            sequence: 0,
+            transaction_id: transaction.transaction_id.clone(),
+            source_domain: transaction.source_domain.clone(),
+            action_type,
+            accepted: rejection.is_none(),
+            reason: rejection,
+            key_ids,
+            recorded_at: chrono::Utc::now(),
+        }).await?;
+        Ok(())
     }
     
-    /// Get metrics
-    pub async fn get_metrics(&self) -> CrossDomainMetrics {
-        self.metrics.clone()
+    /// Emergency action audit log in append order
+    pub async fn list_emergency_audit(&self) -> GarpResult<Vec<EmergencyAuditRecord>> {
+        self.storage.list_emergency_audit().await
     }
     
-    /// Validate transaction
-    async fn validate_transaction(&self, transaction: &CrossDomainTransaction) -> GarpResult<()> {
-        // Check if target domains are available
-        for domain_id in &transaction.target_domains {
-            let domain_state = self.get_domain_state(domain_id).await;
-            match domain_state {
-                Some(state) if state.status == DomainStatus::Active => continue,
-                Some(_) => return Err(GarpError::ValidationError(
-                    format!("Domain {} is not active", domain_id)
-                )),
-                None => return Err(GarpError::ValidationError(
-                    format!("Domain {} not found", domain_id)
-                )),
-            }
-        }
-        
-        // Validate transaction data
-        if transaction.data.is_empty() {
-            return Err(GarpError::ValidationError("Transaction data is empty".to_string()));
+    /// Store a transaction and start its coordination session
+    #[instrument(skip_all, fields(correlation_id = %transaction.transaction_id))]
+    async fn activate_transaction(&self, mut transaction: CrossDomainTransaction) -> GarpResult<()> {
+        // Open the swap so domains can lock under its hashlock
+        if let CrossDomainTransactionType::AtomicSwap { .. } = transaction.transaction_type {
+            let swap = self.swap_manager.open(&transaction).await?;
+            transaction.metadata.insert(SWAP_HASHLOCK_METADATA_KEY.to_string(), swap.hashlock);
         }
         
-        // Check dependencies
-        for dep_id in &transaction.dependencies {
-            let dep_status = self.get_transaction_status(dep_id).await;
-            match dep_status {
-                Some(TransactionStatus::Completed) => continue,
-                Some(status) => return Err(GarpError::ValidationError(
-                    format!("Dependency {} is not completed (status: {:?})", dep_id, status)
-                )),
-                None => return Err(GarpError::ValidationError(
-                    format!("Dependency {} not found", dep_id)
-                )),
-            }
+        // Open the proposal for domain votes
+        if let CrossDomainTransactionType::GovernanceProposal { proposal_id, proposal_type, proposal_data } = &transaction.transaction_type {
+            let action = GovernanceAction::from_proposal(proposal_type, proposal_data)?;
+            let description = transaction.metadata.get("description").cloned().unwrap_or_default();
+            self.open_governance_proposal(proposal_id.clone(), &transaction.source_domain, action, description).await?;
         }
         
-        Ok(())
-    }
-    
-    /// Start coordination session
-    async fn start_coordination_session(&self, transaction: &CrossDomainTransaction) -> GarpResult<()> {
-        let session_id = Uuid::new_v4().to_string();
-        
-        let session = CoordinationSession {
-            session_id: session_id.clone(),
-            transaction_id: transaction.transaction_id.clone(),
-            participating_domains: transaction.target_domains.clone(),
-            phase: CoordinationPhase::Prepare,
-            votes: HashMap::new(),
-            required_votes: transaction.required_confirmations,
-            timeout_at: Instant::now() + Duration::from_secs(self.config.cross_domain.coordination_timeout),
-            created_at: Instant::now(),
-            last_activity: Instant::now(),
-            result: None,
-        };
-        
-        // Store session
+        // Store transaction
         {
-            let mut sessions = self.coordination_sessions.write().await;
-            sessions.insert(session_id.clone(), session);
+            let mut transactions = self.active_transactions.write().await;
+            transactions.insert(transaction.transaction_id.clone(), transaction.clone());
         }
         
-        // Send transaction proposals to target domains
-        for domain_id in &transaction.target_domains {
-            self.send_transaction_proposal(domain_id, transaction).await?;
-        }
+        // Start coordination session
+        self.start_coordination_session(&transaction).await?;
         
         // Emit event
-        self.event_tx.send(CrossDomainEvent::CoordinationSessionStarted(session_id))?;
-        
-        Ok(())
-    }
-    
-    /// Send transaction proposal to domain
-    async fn send_transaction_proposal(&self, domain_id: &DomainId, transaction: &CrossDomainTransaction) -> GarpResult<()> {
-        let message = CrossDomainMessage {
-            message_id: Uuid::new_v4().to_string(),
-            message_type: CrossDomainMessageType::TransactionProposal(transaction.clone()),
-            source_domain: "global-synchronizer".to_string(),
-            target_domain: domain_id.clone(),
-            timestamp: chrono::Utc::now(),
-            signature: Vec::new(), // TODO: Sign message
-        };
+        self.event_tx.send(CrossDomainEvent::TransactionSubmitted(transaction))?;
         
-        self.network_manager.send_cross_domain_message(domain_id, message).await?;
         Ok(())
     }
     
-    /// Initialize domain states
-    async fn initialize_domain_states(&self) -> GarpResult<()> {
-        let discovered_domains = self.domain_discovery.get_discovered_domains().await;
+    /// Move every time-locked transaction that has matured by `now` into coordination
+    pub async fn release_matured_timelocks(&self, now: chrono::DateTime<chrono::Utc>) -> GarpResult<Vec<TransactionId>> {
+        let mut released = Vec::new();
         
-        let mut states = self.domain_states.write().await;
-        for domain_info in discovered_domains {
-            let state = DomainState {
-                domain_id: domain_info.domain_id.clone(),
-                status: DomainStatus::Active,
-                last_block_height: 0,
-                last_block_hash: String::new(),
-                state_root: String::new(),
-                validators: Vec::new(),
-                last_updated: Instant::now(),
-                endpoint: domain_info.endpoint,
-                capabilities: DomainCapabilities {
-                    supported_transaction_types: Vec::new(),
-                    max_transaction_size: 1024 * 1024, // 1MB default
-                    consensus_algorithm: "unknown".to_string(),
-                    finality_time: 30, // 30 seconds default
-                    throughput: 100, // 100 TPS default
-                    features: Vec::new(),
-                },
-                metrics: DomainMetrics {
-                    transaction_count: 0,
-                    avg_confirmation_time: 0.0,
-                    success_rate: 0.0,
-                    last_response_time: Duration::from_secs(0),
-                    uptime_percentage: 0.0,
-                },
+        for transaction in self.timelock_queue.take_matured(now).await {
+            let transaction = match Self::hold_for_suspension(transaction, &self.suspensions).await {
+                Some(transaction) => transaction,
+                None => continue,
             };
-            
-            states.insert(domain_info.domain_id, state);
-        }
-        
-        // Update metrics
-        {
-            let mut active_domains = self.metrics.active_domains.write().await;
-            *active_domains = states.len();
+            let tx_id = transaction.transaction_id.clone();
+            match self.activate_transaction(transaction).await {
+                Ok(()) => released.push(tx_id),
+                Err(e) => {
+                    warn!("Failed to activate time-locked transaction {}: {}", tx_id, e);
+                    let mut failed = self.metrics.failed_transactions.write().await;
+                    *failed += 1;
+                    let _ = self.event_tx.send(CrossDomainEvent::TransactionFailed(tx_id, e.to_string()));
+                }
+            }
         }
         
-        info!("Initialized {} domain states", states.len());
-        Ok(())
+        Ok(released)
     }
     
-    /// Start message processor
-    async fn start_message_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let event_rx = self.event_rx.clone();
-        let active_transactions = self.active_transactions.clone();
-        let coordination_sessions = self.coordination_sessions.clone();
-        let domain_states = self.domain_states.clone();
-        let metrics = self.metrics.clone();
+    /// Cancel a time-locked transaction before it unlocks
+    pub async fn cancel_timelocked_transaction(&self, transaction_id: &TransactionId) -> GarpResult<()> {
+        match self.timelock_queue.cancel(transaction_id).await {
+            Some(_) => {
+                info!(transaction_id = %transaction_id, "Cancelled time-locked transaction");
+                self.event_tx.send(CrossDomainEvent::TransactionFailed(
+                    transaction_id.clone(),
+                    "cancelled before unlock".to_string(),
+                ))?;
+                Ok(())
+            }
+            None => Err(GarpError::NotFound(format!(
+                "No time-locked transaction: {}", transaction_id
+            ))),
+        }
+    }
+    
+    /// Compensate transactions whose coordination aborted after some domains
+    /// confirmed, and track compensations already submitted, escalating any
+    /// that fail. Returns the transactions compensation started for.
+    pub async fn process_compensations(&self) -> GarpResult<Vec<TransactionId>> {
+        let aborted: Vec<CoordinationSession> = {
+            let mut sessions = self.coordination_sessions.write().await;
+            let session_ids: Vec<String> = sessions.iter()
+                .filter(|(_, session)| session.phase == CoordinationPhase::Abort)
+                .map(|(session_id, _)| session_id.clone())
+                .collect();
+            session_ids.iter().filter_map(|session_id| sessions.remove(session_id)).collect()
+        };
         
-        let handle = tokio::spawn(async move {
-            let mut event_rx = event_rx.lock().await;
+        let mut compensated = Vec::new();
+        for session in aborted {
+            self.storage.prune_coordination_votes(&session.session_id).await;
+            let transaction = {
+                let mut transactions = self.active_transactions.write().await;
+                let Some(transaction) = transactions.get_mut(&session.transaction_id) else {
+                    continue;
+                };
+                if !transaction.status.is_terminal() {
+                    transaction.status = TransactionStatus::Rejected;
+                    transaction.updated_at = chrono::Utc::now();
+                    let mut failed = self.metrics.failed_transactions.write().await;
+                    *failed += 1;
+                }
+                transaction.clone()
+            };
             
-            while let Some(event) = event_rx.recv().await {
-                match event {
-                    CrossDomainEvent::TransactionConfirmed(tx_id, confirmation) => {
-                        Self::handle_transaction_confirmed(
-                            tx_id,
-                            confirmation,
-                            &active_transactions,
-                            &coordination_sessions,
-                            &metrics,
-                        ).await;
-                    }
-                    
-                    CrossDomainEvent::DomainStatusChanged(domain_id, status) => {
-                        Self::handle_domain_status_changed(
-                            domain_id,
-                            status,
-                            &domain_states,
-                            &metrics,
-                        ).await;
-                    }
-                    
-                    CrossDomainEvent::EmergencyDetected(notification) => {
-                        Self::handle_emergency_detected(notification).await;
-                    }
-                    
-                    CrossDomainEvent::Shutdown => {
-                        info!("Received shutdown signal in cross-domain message processor");
-                        break;
-                    }
-                    
-                    _ => {
-                        debug!("Received cross-domain event: {:?}", event);
+            // Swaps refund through their hashlock; compensations escalate below
+            if CompensationEngine::is_compensation(&transaction)
+                || matches!(transaction.transaction_type, CrossDomainTransactionType::AtomicSwap { .. })
+            {
+                continue;
+            }
+            
+            let reason = format!("coordination aborted ({:?})", transaction.status);
+            let compensations = match self.compensation_engine.begin(&transaction, &reason).await {
+                Ok(Some(compensations)) => compensations,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Failed to compensate transaction {}: {}", transaction.transaction_id, e);
+                    continue;
+                }
+            };
+            for compensation in compensations {
+                if let Err(e) = self.activate_transaction(compensation.clone()).await {
+                    warn!("Failed to submit compensation {}: {}", compensation.transaction_id, e);
+                    if let Some(notification) = self.compensation_engine.record_outcome(&compensation, &TransactionStatus::Failed).await? {
+                        self.event_tx.send(CrossDomainEvent::EmergencyDetected(notification))?;
                     }
                 }
             }
-        });
+            compensated.push(transaction.transaction_id);
+        }
         
-        Ok(handle)
+        let finished: Vec<CrossDomainTransaction> = {
+            let transactions = self.active_transactions.read().await;
+            transactions.values()
+                .filter(|transaction| CompensationEngine::is_compensation(transaction) && transaction.status.is_terminal())
+                .cloned()
+                .collect()
+        };
+        for compensation in finished {
+            if let Some(notification) = self.compensation_engine.record_outcome(&compensation, &compensation.status).await? {
+                self.event_tx.send(CrossDomainEvent::EmergencyDetected(notification))?;
+            }
+        }
+        
+        Ok(compensated)
     }
     
-    /// Handle transaction confirmed
-    async fn handle_transaction_confirmed(
-        tx_id: TransactionId,
-        confirmation: DomainConfirmation,
+    /// Get the compensation chain of an aborted transaction
+    pub async fn get_compensation_chain(&self, transaction_id: &TransactionId) -> GarpResult<Option<CompensationChain>> {
+        self.compensation_engine.get_chain(transaction_id).await
+    }
+    
+    /// Refund every atomic swap whose timelock has passed by `now`
+    pub async fn refund_expired_swaps(&self, now: chrono::DateTime<chrono::Utc>) -> GarpResult<Vec<SwapId>> {
+        let mut refunded = Vec::new();
+        
+        for (swap, instructions) in self.swap_manager.refund_expired(now).await? {
+            Self::send_swap_instructions(&self.network_manager, &swap.transaction_id, instructions).await;
+            Self::finish_swap_transaction(&swap, &self.token_wrapper, &self.active_transactions, &self.coordination_sessions, &self.metrics).await;
+            refunded.push(swap.swap_id);
+        }
+        
+        Ok(refunded)
+    }
+    
+    /// Supply the preimage of an atomic swap submitted with its own hashlock
+    pub async fn reveal_swap_preimage(&self, swap_id: &SwapId, preimage: &[u8]) -> GarpResult<AtomicSwap> {
+        let (swap, instructions) = self.swap_manager.reveal(swap_id, preimage, chrono::Utc::now()).await?;
+        Self::send_swap_instructions(&self.network_manager, &swap.transaction_id, instructions).await;
+        Self::finish_swap_transaction(&swap, &self.token_wrapper, &self.active_transactions, &self.coordination_sessions, &self.metrics).await;
+        Ok(swap)
+    }
+    
+    /// Get an atomic swap
+    pub async fn get_swap(&self, swap_id: &SwapId) -> GarpResult<Option<AtomicSwap>> {
+        self.swap_manager.get_swap(swap_id).await
+    }
+    
+    /// Lock `amount` of `token` on `from_domain` and mint as much of its
+    /// wrapped token on `to_domain`, in one atomic swap
+    pub async fn wrap_token(&self, token: &str, amount: u64, from_domain: &DomainId, to_domain: &DomainId) -> GarpResult<TokenWrapping> {
+        let wrapping = self.token_wrapper.wrap(token, amount, from_domain, to_domain, TransactionId::new()).await?;
+        self.submit_token_wrapping(wrapping).await
+    }
+    
+    /// Burn `amount` of `wrapped_token` on `to_domain` and release as much
+    /// of the original on its origin domain, in one atomic swap
+    pub async fn unwrap_token(&self, wrapped_token: &str, amount: u64, to_domain: &DomainId) -> GarpResult<TokenWrapping> {
+        let wrapping = self.token_wrapper.unwrap(wrapped_token, amount, to_domain, TransactionId::new()).await?;
+        self.submit_token_wrapping(wrapping).await
+    }
+    
+    /// Submit the swap transaction of a pending wrapping, dropping the
+    /// wrapping if it is not accepted
+    async fn submit_token_wrapping(&self, wrapping: TokenWrapping) -> GarpResult<TokenWrapping> {
+        let (domain_a, domain_b) = wrapping.leg_domains();
+        let mut transaction = self.channel_transaction(
+            wrapping.transaction_id.clone(),
+            domain_a,
+            domain_b,
+            wrapping.transaction_type(),
+            serde_json::to_vec(&wrapping)?,
+        );
+        transaction.metadata.insert(TOKEN_WRAPPING_METADATA_KEY.to_string(), wrapping.kind.as_str().to_string());
+        
+        if let Err(e) = self.submit_transaction(transaction).await {
+            self.token_wrapper.abandon(&wrapping.wrap_id).await?;
+            return Err(e);
+        }
+        Ok(wrapping)
+    }
+    
+    /// Get a wrapped token
+    pub async fn get_wrapped_token(&self, wrapped_token_id: &str) -> GarpResult<Option<WrappedToken>> {
+        self.storage.get_wrapped_token(wrapped_token_id).await
+    }
+    
+    /// List wrapped tokens
+    pub async fn list_wrapped_tokens(&self) -> GarpResult<Vec<WrappedToken>> {
+        self.storage.list_wrapped_tokens().await
+    }
+    
+    /// Get the queued time-locked transactions
+    pub async fn get_timelock_queue(&self) -> Vec<TimelockEntry> {
+        self.timelock_queue.entries().await
+    }
+    
+    /// Put a domain into maintenance mode for at least `duration`. New
+    /// transactions targeting it are rejected and its pending ones are
+    /// queued until the suspension lifts. Suspending an already suspended
+    /// domain updates the reason and extends the window.
+    pub async fn suspend_domain(&self, domain_id: &DomainId, reason: String, duration: Duration) -> GarpResult<DomainSuspension> {
+        Self::place_suspension(
+            domain_id,
+            reason,
+            duration,
+            &self.domain_states,
+            &self.active_transactions,
+            &self.coordination_sessions,
+            &self.suspensions,
+            &self.event_tx,
+        )
+        .await
+    }
+    
+    /// Suspend a domain; see `suspend_domain`
+    #[allow(clippy::too_many_arguments)]
+    async fn place_suspension(
+        domain_id: &DomainId,
+        reason: String,
+        duration: Duration,
+        domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
         active_transactions: &Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
         coordination_sessions: &Arc<RwLock<HashMap<String, CoordinationSession>>>,
-        metrics: &Arc<CrossDomainMetrics>,
-    ) {
-        debug!("Handling transaction confirmation: {} from {}", tx_id, confirmation.domain_id);
-        
-        // Update transaction
+        suspensions: &Arc<RwLock<HashMap<DomainId, DomainSuspension>>>,
+        event_tx: &mpsc::UnboundedSender<CrossDomainEvent>,
+    ) -> GarpResult<DomainSuspension> {
         {
+            let mut states = domain_states.write().await;
+            let state = states.get_mut(domain_id)
+                .ok_or_else(|| GarpError::NotFound(format!("Domain not found: {}", domain_id)))?;
+            state.status = DomainStatus::Maintenance;
+            state.last_updated = Instant::now();
+        }
+        
+        // Pull pending transactions for the domain out of coordination
+        let held: Vec<CrossDomainTransaction> = {
             let mut transactions = active_transactions.write().await;
-            if let Some(transaction) = transactions.get_mut(&tx_id) {
-                transaction.confirmations.insert(confirmation.domain_id.clone(), confirmation.clone());
-                transaction.updated_at = chrono::Utc::now();
-                
-                // Check if transaction is complete
-                let confirmed_count = transaction.confirmations.values()
-                    .filter(|c| c.status == ConfirmationStatus::Confirmed)
-                    .count();
-                
-                if confirmed_count >= transaction.required_confirmations {
-                    transaction.status = TransactionStatus::Completed;
-                    
-                    // Update metrics
-                    {
-                        let mut successful = metrics.successful_transactions.write().await;
-                        *successful += 1;
-                    }
-                }
+            let held_ids: Vec<TransactionId> = transactions.values()
+                .filter(|tx| tx.status == TransactionStatus::Pending && tx.target_domains.contains(domain_id))
+                .map(|tx| tx.transaction_id.clone())
+                .collect();
+            held_ids.iter().filter_map(|id| transactions.remove(id)).collect()
+        };
+        if !held.is_empty() {
+            let mut sessions = coordination_sessions.write().await;
+            sessions.retain(|_, session| !held.iter().any(|tx| tx.transaction_id == session.transaction_id));
+        }
+        
+        let now = chrono::Utc::now();
+        let resume_at = now + chrono::Duration::from_std(duration)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid suspension duration: {}", e)))?;
+        let suspension = {
+            let mut suspensions = suspensions.write().await;
+            let suspension = suspensions.entry(domain_id.clone()).or_insert_with(|| DomainSuspension {
+                domain_id: domain_id.clone(),
+                reason: String::new(),
+                suspended_at: now,
+                resume_at,
+                queued: Vec::new(),
+            });
+            suspension.reason = reason;
+            suspension.resume_at = suspension.resume_at.max(resume_at);
+            suspension.queued.extend(held.into_iter().map(|mut tx| {
+                tx.confirmations.clear();
+                tx
+            }));
+            suspension.clone()
+        };
+        
+        info!(
+            domain_id = %domain_id,
+            resume_at = %suspension.resume_at,
+            reason = %suspension.reason,
+            queued = suspension.queued.len(),
+            "Suspended domain"
+        );
+        event_tx.send(CrossDomainEvent::DomainStatusChanged(domain_id.clone(), DomainStatus::Maintenance))?;
+        Ok(suspension)
+    }
+    
+    /// Domain reputation
+    pub fn reputation(&self) -> Arc<ReputationTracker> {
+        self.reputation.clone()
+    }
+    
+    /// Record a failure against a domain's reputation, suspending the domain
+    /// once its score falls below the configured threshold
+    #[allow(clippy::too_many_arguments)]
+    async fn penalize_domain(
+        domain_id: &DomainId,
+        signal: ReputationSignal,
+        reputation: &Arc<ReputationTracker>,
+        domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
+        active_transactions: &Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
+        coordination_sessions: &Arc<RwLock<HashMap<String, CoordinationSession>>>,
+        suspensions: &Arc<RwLock<HashMap<DomainId, DomainSuspension>>>,
+        event_tx: &mpsc::UnboundedSender<CrossDomainEvent>,
+    ) {
+        let update = match reputation.record(domain_id, signal).await {
+            Ok(update) => update,
+            Err(e) => {
+                warn!("Failed to record {:?} for domain {}: {}", signal, domain_id, e);
+                return;
             }
+        };
+        if !update.fell_below_threshold {
+            return;
         }
         
-        // Update coordination session
-        {
-            let mut sessions = coordination_sessions.write().await;
-            for session in sessions.values_mut() {
-                if session.transaction_id == tx_id {
-                    let vote = CoordinationVote {
-                        domain_id: confirmation.domain_id.clone(),
-                        vote: confirmation.status == ConfirmationStatus::Confirmed,
-                        reason: None,
-                        data: confirmation.data.clone(),
-                        signature: confirmation.signature.clone(),
-                        timestamp: confirmation.timestamp,
-                    };
-                    
-                    session.votes.insert(confirmation.domain_id, vote);
-                    session.last_activity = Instant::now();
-                    
-                    // Check if coordination is complete
-                    let approve_votes = session.votes.values().filter(|v| v.vote).count();
-                    if approve_votes >= session.required_votes {
-                        session.phase = CoordinationPhase::Completed;
-                    }
-                    
-                    break;
-                }
+        let reason = format!("Reputation fell to {:.1} after {:?}", update.score.score, signal);
+        let duration = Duration::from_secs(reputation.config().suspension_duration_secs);
+        if let Err(e) = Self::place_suspension(
+            domain_id,
+            reason,
+            duration,
+            domain_states,
+            active_transactions,
+            coordination_sessions,
+            suspensions,
+            event_tx,
+        ).await {
+            error!("Failed to suspend domain {} for low reputation: {}", domain_id, e);
+        }
+    }
+    
+    /// Lift a domain suspension immediately, retrying its queued transactions
+    pub async fn resume_domain(&self, domain_id: &DomainId) -> GarpResult<DomainSuspension> {
+        Self::lift_suspension(
+            domain_id,
+            &self.domain_states,
+            &self.suspensions,
+            &self.timelock_queue,
+            &self.event_tx,
+        )
+        .await
+        .ok_or_else(|| GarpError::NotFound(format!("Domain {} is not suspended", domain_id)))
+    }
+    
+    /// Current domain suspensions
+    pub async fn get_domain_suspensions(&self) -> Vec<DomainSuspension> {
+        self.suspensions.read().await.values().cloned().collect()
+    }
+    
+    /// Return `transaction` if none of its target domains is suspended,
+    /// otherwise queue it on the first suspended one
+    async fn hold_for_suspension(
+        transaction: CrossDomainTransaction,
+        suspensions: &Arc<RwLock<HashMap<DomainId, DomainSuspension>>>,
+    ) -> Option<CrossDomainTransaction> {
+        let mut suspensions = suspensions.write().await;
+        match transaction.target_domains.iter().find(|domain_id| suspensions.contains_key(*domain_id)) {
+            Some(domain_id) => {
+                debug!("Holding transaction {} for suspended domain {}", transaction.transaction_id, domain_id);
+                let domain_id = domain_id.clone();
+                suspensions.get_mut(&domain_id)?.queued.push(transaction);
+                None
             }
+            None => Some(transaction),
         }
     }
     
-    /// Handle domain status changed
-    async fn handle_domain_status_changed(
-        domain_id: DomainId,
-        status: DomainStatus,
+    /// Remove a domain's suspension and bring it back to Active. Queued
+    /// transactions go through the timelock queue, which starts their
+    /// coordination on its next pass, unless another of their target domains
+    /// is still suspended.
+    async fn lift_suspension(
+        domain_id: &DomainId,
         domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
-        metrics: &Arc<CrossDomainMetrics>,
-    ) {
-        info!("Domain {} status changed to {:?}", domain_id, status);
+        suspensions: &Arc<RwLock<HashMap<DomainId, DomainSuspension>>>,
+        timelock_queue: &Arc<TimelockQueue>,
+        event_tx: &mpsc::UnboundedSender<CrossDomainEvent>,
+    ) -> Option<DomainSuspension> {
+        let suspension = suspensions.write().await.remove(domain_id)?;
+        
+        for transaction in suspension.queued.iter().cloned() {
+            if let Some(transaction) = Self::hold_for_suspension(transaction, suspensions).await {
+                timelock_queue.enqueue(transaction).await;
+            }
+        }
         
         {
             let mut states = domain_states.write().await;
-            if let Some(state) = states.get_mut(&domain_id) {
-                state.status = status.clone();
+            if let Some(state) = states.get_mut(domain_id) {
+                state.status = DomainStatus::Active;
                 state.last_updated = Instant::now();
             }
         }
         
-        // Update metrics
-        {
-            let states = domain_states.read().await;
-            let active_count = states.values()
-                .filter(|state| state.status == DomainStatus::Active)
-                .count();
-            
-            let mut active_domains = metrics.active_domains.write().await;
-            *active_domains = active_count;
+        info!(domain_id = %domain_id, queued = suspension.queued.len(), "Lifted domain suspension, retrying queued transactions");
+        if let Err(e) = event_tx.send(CrossDomainEvent::DomainStatusChanged(domain_id.clone(), DomainStatus::Active)) {
+            error!("Failed to send domain status change event: {}", e);
         }
+        Some(suspension)
     }
     
-    /// Handle emergency detected
-    async fn handle_emergency_detected(notification: EmergencyNotification) {
-        error!("Emergency detected: {:?} - {}", notification.emergency_type, notification.description);
+    /// Open a state channel, locking `capacity` collateral from `domain_a` on-chain
+    pub async fn open_channel(&self, domain_a: &DomainId, domain_b: &DomainId, capacity: u64) -> GarpResult<StateChannel> {
+        let lock = self.channel_manager.open_channel(domain_a, domain_b, capacity).await?;
+        let transaction = self.channel_transaction(
+            lock.transaction_id.clone(),
+            &lock.domain_a,
+            &lock.domain_b,
+            CrossDomainTransactionType::ChannelLock {
+                channel_id: lock.channel_id.clone(),
+                capacity: lock.capacity,
+            },
+            serde_json::to_vec(&lock)?,
+        );
         
-        // TODO: Implement emergency response procedures
-        match notification.emergency_type {
-            EmergencyType::NetworkPartition => {
-                // Handle network partition
-            }
-            EmergencyType::ByzantineBehavior => {
-                // Handle Byzantine behavior
-            }
-            EmergencyType::CriticalVulnerability => {
-                // Handle critical vulnerability
-            }
-            _ => {
-                // Handle other emergencies
-            }
+        if let Err(e) = self.submit_transaction(transaction).await {
+            self.channel_manager.discard_channel(&lock.channel_id).await?;
+            return Err(e);
         }
+        
+        self.channel_manager.get_channel(&lock.channel_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("State channel not found: {}", lock.channel_id)))
     }
     
-    /// Start domain monitor
-    async fn start_domain_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let domain_states = self.domain_states.clone();
-        let network_manager = self.network_manager.clone();
-        let event_tx = self.event_tx.clone();
+    /// Record an off-chain channel update reported by the parties
+    pub async fn apply_channel_update(&self, update: ChannelUpdate) -> GarpResult<StateChannel> {
+        self.channel_manager.apply_update(update).await
+    }
+    
+    /// Submit the final mutually-signed state, starting the challenge window
+    pub async fn close_channel(&self, channel_id: &ChannelId, final_state: ChannelUpdate) -> GarpResult<StateChannel> {
+        self.channel_manager.close_channel(channel_id, final_state, chrono::Utc::now()).await
+    }
+    
+    /// Challenge a closing channel with a higher-sequence state
+    pub async fn challenge_channel(&self, channel_id: &ChannelId, state: ChannelUpdate) -> GarpResult<StateChannel> {
+        self.channel_manager.challenge(channel_id, state, chrono::Utc::now()).await
+    }
+    
+    /// Submit settlement transactions for channels whose challenge window has ended
+    pub async fn settle_expired_channels(&self, now: chrono::DateTime<chrono::Utc>) -> GarpResult<Vec<ChannelId>> {
+        let mut settled = Vec::new();
         
-        let handle = tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(30));
+        for channel in self.channel_manager.settle_expired(now).await? {
+            let state = &channel.latest_state;
+            let transaction = self.channel_transaction(
+                TransactionId::new(),
+                &channel.domain_a,
+                &channel.domain_b,
+                CrossDomainTransactionType::ChannelSettlement {
+                    channel_id: channel.channel_id.clone(),
+                    sequence: state.sequence,
+                    balance_a: state.balance_a,
+                    balance_b: state.balance_b,
+                },
+                serde_json::to_vec(state)?,
+            );
             
-            loop {
-                interval.tick().await;
-                
-                // Check domain health
-                let domains_to_check: Vec<DomainId> = {
-                    let states = domain_states.read().await;
-                    states.keys().cloned().collect()
-                };
-                
-                for domain_id in domains_to_check {
-                    // Send heartbeat request
-                    let heartbeat = HeartbeatMessage {
-                        domain_id: "global-synchronizer".to_string(),
-                        block_height: 0,
-                        transaction_count: 0,
-                        status: DomainStatus::Active,
-                        timestamp: chrono::Utc::now(),
-                    };
-                    
-                    let message = CrossDomainMessage {
-                        message_id: Uuid::new_v4().to_string(),
-                        message_type: CrossDomainMessageType::Heartbeat(heartbeat),
-                        source_domain: "global-synchronizer".to_string(),
-                        target_domain: domain_id.clone(),
-                        timestamp: chrono::Utc::now(),
-                        signature: Vec::new(),
-                    };
-                    
-                    // Check if domain responds
-                    match timeout(Duration::from_secs(10), 
-                        network_manager.send_cross_domain_message(&domain_id, message)).await {
-                        Ok(Ok(_)) => {
-                            // Domain is responsive
-                        }
-                        _ => {
-                            // Domain is unresponsive
-                            if let Err(e) = event_tx.send(CrossDomainEvent::DomainStatusChanged(
-                                domain_id, DomainStatus::Unavailable)) {
-                                error!("Failed to send domain status change event: {}", e);
-                            }
-                        }
-                    }
-                }
+            match self.submit_transaction(transaction).await {
+                Ok(_) => settled.push(channel.channel_id),
+                Err(e) => warn!("Failed to submit settlement for channel {}: {}", channel.channel_id, e),
             }
-        });
+        }
         
-        Ok(handle)
+        Ok(settled)
     }
     
-    /// Start coordination session monitor
-    async fn start_coordination_session_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let coordination_sessions = self.coordination_sessions.clone();
-        let active_transactions = self.active_transactions.clone();
+    /// Get the state channel manager
+    pub fn channel_manager(&self) -> Arc<StateChannelManager> {
+        self.channel_manager.clone()
+    }
+    
+    /// Open a governance proposal and broadcast it to every active domain.
+    ///
+    /// Each active domain may vote, weighted by its advertised throughput.
+    pub async fn submit_governance_proposal(
+        &self,
+        proposer: &DomainId,
+        action: GovernanceAction,
+        description: String,
+    ) -> GarpResult<GovernanceProposal> {
+        self.open_governance_proposal(Uuid::new_v4().to_string(), proposer, action, description).await
+    }
+    
+    /// Open a proposal under `proposal_id` and broadcast it to every active domain
+    async fn open_governance_proposal(
+        &self,
+        proposal_id: ProposalId,
+        proposer: &DomainId,
+        action: GovernanceAction,
+        description: String,
+    ) -> GarpResult<GovernanceProposal> {
+        let eligible_weights: HashMap<DomainId, u64> = {
+            let states = self.domain_states.read().await;
+            states.values()
+                .filter(|state| state.status == DomainStatus::Active)
+                .map(|state| (state.domain_id.clone(), state.capabilities.throughput))
+                .collect()
+        };
+        
+        let proposal = self.governance_engine
+            .open_proposal(proposal_id, proposer, action, description, eligible_weights, chrono::Utc::now())
+            .await?;
+        
+        for domain_id in proposal.eligible_weights.keys() {
+            let message = CrossDomainMessage {
+                message_id: Uuid::new_v4().to_string(),
+                message_type: CrossDomainMessageType::GovernanceProposal(proposal.clone()),
+                source_domain: "global-synchronizer".to_string(),
+                target_domain: domain_id.clone(),
+                timestamp: chrono::Utc::now(),
+                signature: Vec::new(),
+                correlation_id: None,
+            };
+            if let Err(e) = self.network_manager.send_cross_domain_message(domain_id, message).await {
+                warn!("Failed to send governance proposal {} to {}: {}", proposal.proposal_id, domain_id, e);
+            }
+        }
+        
+        Ok(proposal)
+    }
+    
+    /// Record a domain's vote on a governance proposal
+    pub async fn record_governance_vote(&self, vote: GovernanceVoteMessage) -> GarpResult<GovernanceProposal> {
+        self.governance_engine
+            .cast_vote(&vote.proposal_id, &vote.domain_id, vote.approve, chrono::Utc::now())
+            .await
+    }
+    
+    /// Tally proposals whose voting period has ended and apply the approved
+    /// ones, returning the IDs of those executed successfully
+    pub async fn finalize_governance_proposals(&self, now: chrono::DateTime<chrono::Utc>) -> GarpResult<Vec<ProposalId>> {
+        let mut executed = Vec::new();
+        
+        for proposal in self.governance_engine.tally_expired(now).await? {
+            let outcome = self.execute_governance_action(&proposal.action).await
+                .map_err(|e| e.to_string());
+            if let Err(reason) = &outcome {
+                warn!("Failed to execute governance proposal {}: {}", proposal.proposal_id, reason);
+            } else {
+                executed.push(proposal.proposal_id.clone());
+            }
+            self.governance_engine.record_execution(&proposal.proposal_id, outcome, now).await?;
+        }
+        
+        Ok(executed)
+    }
+    
+    /// Get a governance proposal
+    pub async fn get_governance_proposal(&self, proposal_id: &ProposalId) -> GarpResult<Option<GovernanceProposal>> {
+        self.governance_engine.get_proposal(proposal_id).await
+    }
+    
+    /// List governance proposals, oldest first
+    pub async fn list_governance_proposals(&self) -> GarpResult<Vec<GovernanceProposal>> {
+        self.governance_engine.list_proposals().await
+    }
+    
+    /// Delegation from `delegator` to `delegate`, whether or not it has expired
+    pub async fn get_delegation(&self, delegator: &DomainId, delegate: &DomainId) -> GarpResult<Option<DomainDelegation>> {
+        self.storage.get_domain_delegation(delegator, delegate).await
+    }
+    
+    /// List delegations, ordered by delegator then delegate
+    pub async fn list_delegations(&self) -> GarpResult<Vec<DomainDelegation>> {
+        let mut delegations = self.storage.list_domain_delegations().await?;
+        delegations.sort_by(|a, b| (&a.delegator, &a.delegate).cmp(&(&b.delegator, &b.delegate)));
+        Ok(delegations)
+    }
+    
+    /// Apply an approved governance action
+    async fn execute_governance_action(&self, action: &GovernanceAction) -> GarpResult<()> {
+        match action {
+            GovernanceAction::UpdateConfig { key, value } => {
+                self.storage.set_governance_parameter(key, value).await?;
+                info!(key = %key, value = %value, "Governance set parameter");
+            }
+            GovernanceAction::AddDomain { domain_id, endpoint, capabilities } => {
+                let mut states = self.domain_states.write().await;
+                if states.contains_key(domain_id) {
+                    return Err(GarpError::ValidationError(format!("Domain already registered: {}", domain_id)));
+                }
+                states.insert(domain_id.clone(), DomainState {
+                    domain_id: domain_id.clone(),
+                    status: DomainStatus::Active,
+                    last_block_height: 0,
+                    last_block_hash: String::new(),
+                    state_root: String::new(),
+                    state_root_hash: String::new(),
+                    validators: Vec::new(),
+                    last_updated: Instant::now(),
+                    endpoint: endpoint.clone(),
+                    capabilities: capabilities.clone(),
+                    metrics: DomainMetrics {
+                        transaction_count: 0,
+                        avg_confirmation_time: 0.0,
+                        success_rate: 0.0,
+                        last_response_time: Duration::from_secs(0),
+                        uptime_percentage: 0.0,
+                    },
+                });
+                *self.metrics.active_domains.write().await = states.len();
+                info!(domain_id = %domain_id, "Governance added domain");
+            }
+            GovernanceAction::SlashValidator { validator_id, penalty_bps } => {
+                let validator = self.consensus_engine.get_validator(validator_id).await
+                    .ok_or_else(|| GarpError::NotFound(format!("Validator not found: {}", validator_id.0)))?;
+                let penalty = (validator.voting_power as u128 * *penalty_bps as u128 / 10_000) as u64;
+                let remaining = validator.voting_power - penalty;
+                let epoch = if remaining == 0 {
+                    self.consensus_engine.remove_validator(validator_id.clone()).await?
+                } else {
+                    self.consensus_engine.update_validator_stake(validator_id.clone(), remaining).await?
+                };
+                info!(validator_id = %validator_id.0, penalty, epoch, "Governance slashed validator");
+            }
+            GovernanceAction::AddValidator { validator_id, public_key, voting_power } => {
+                let epoch = self.consensus_engine
+                    .add_validator(ValidatorInfo::new(validator_id.clone(), public_key.clone(), *voting_power))
+                    .await?;
+                info!(validator_id = %validator_id.0, epoch, "Governance added validator");
+            }
+            GovernanceAction::RemoveValidator { validator_id } => {
+                let epoch = self.consensus_engine.remove_validator(validator_id.clone()).await?;
+                info!(validator_id = %validator_id.0, epoch, "Governance removed validator");
+            }
+            GovernanceAction::IssueDelegation { delegator, delegate, .. } => {
+                if let Some(delegation) = action.delegation() {
+                    self.storage.store_domain_delegation(delegation).await?;
+                }
+                info!(delegator = %delegator, delegate = %delegate, "Governance issued delegation");
+            }
+            GovernanceAction::RevokeDelegation { delegator, delegate } => {
+                self.storage.remove_domain_delegation(delegator, delegate).await?
+                    .ok_or_else(|| GarpError::NotFound(format!("No delegation from {} to {}", delegator, delegate)))?;
+                info!(delegator = %delegator, delegate = %delegate, "Governance revoked delegation");
+            }
+            GovernanceAction::Custom { proposal_type, .. } => {
+                return Err(GarpError::ValidationError(format!(
+                    "Custom proposal type {} cannot be executed", proposal_type
+                )));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Coordination timeout, as overridden by governance if set
+    async fn coordination_timeout(&self) -> Duration {
+        let overridden = match self.storage.get_governance_parameter(COORDINATION_TIMEOUT_PARAMETER).await {
+            Ok(value) => value.and_then(|value| value.parse::<u64>().ok()),
+            Err(e) => {
+                warn!("Failed to read governance parameter {}: {}", COORDINATION_TIMEOUT_PARAMETER, e);
+                None
+            }
+        };
+        Duration::from_secs(overridden.unwrap_or(self.config.cross_domain.coordination_timeout))
+    }
+    
+    /// Build a transaction confirmed by both of two domains, as used for
+    /// channels and token wrappings
+    fn channel_transaction(
+        &self,
+        transaction_id: TransactionId,
+        domain_a: &DomainId,
+        domain_b: &DomainId,
+        transaction_type: CrossDomainTransactionType,
+        data: Vec<u8>,
+    ) -> CrossDomainTransaction {
+        let now = chrono::Utc::now();
+        CrossDomainTransaction {
+            transaction_id,
+            source_domain: domain_a.clone(),
+            target_domains: vec![domain_a.clone(), domain_b.clone()],
+            transaction_type,
+            data,
+            dependencies: Vec::new(),
+            required_confirmations: 2,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::milliseconds(self.config.cross_domain.transaction_timeout_ms as i64),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+    
+    /// Get transaction status
+    pub async fn get_transaction_status(&self, transaction_id: &TransactionId) -> Option<TransactionStatus> {
+        let transactions = self.active_transactions.read().await;
+        transactions.get(transaction_id).map(|tx| tx.status.clone())
+    }
+    
+    /// Get domain state
+    pub async fn get_domain_state(&self, domain_id: &DomainId) -> Option<DomainState> {
+        let states = self.domain_states.read().await;
+        states.get(domain_id).cloned()
+    }
+    
+    /// Record the state root a domain reported in a state sync response
+    pub async fn apply_state_sync_response(&self, domain_id: &DomainId, response: &StateSyncResponse) -> GarpResult<()> {
+        let Some(state_root) = &response.state_root else {
+            return Ok(());
+        };
+        let mut states = self.domain_states.write().await;
+        let state = states.get_mut(domain_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Domain {} is not registered", domain_id)))?;
+        if state.state_root_hash != *state_root {
+            debug!("Domain {} state root is now {} as of height {}", domain_id, state_root, response.height_range.1);
+            state.state_root_hash = state_root.clone();
+        }
+        state.last_updated = Instant::now();
+        Ok(())
+    }
+    
+    /// States of all known domains, ordered by domain ID
+    pub async fn list_domain_states(&self) -> Vec<DomainState> {
+        let mut states: Vec<DomainState> = self.domain_states.read().await.values().cloned().collect();
+        states.sort_by(|a, b| a.domain_id.cmp(&b.domain_id));
+        states
+    }
+    
+    /// Re-initialize a domain's state from its current discovery
+    /// registration, dropping what was learned about the domain since
+    pub async fn reload_domain(&self, domain_id: &DomainId) -> GarpResult<DomainState> {
+        let registration = self.domain_discovery.get_discovered_domains().await
+            .into_iter()
+            .find(|registration| &registration.domain_id == domain_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Domain {} is not registered", domain_id)))?;
+        
+        let state = Self::new_domain_state(&registration);
+        let mut states = self.domain_states.write().await;
+        states.insert(domain_id.clone(), state.clone());
+        *self.metrics.active_domains.write().await = states.len();
+        info!(domain_id = %domain_id, endpoint = %registration.endpoint, "Reloaded domain from its registration");
+        Ok(state)
+    }
+    
+    /// Get active domains
+    pub async fn get_active_domains(&self) -> Vec<DomainId> {
+        let states = self.domain_states.read().await;
+        states.values()
+            .filter(|state| state.status == DomainStatus::Active)
+            .map(|state| state.domain_id.clone())
+            .collect()
+    }
+    
+    /// Get a sender for cross-domain events
+    pub fn event_sender(&self) -> mpsc::UnboundedSender<CrossDomainEvent> {
+        self.event_tx.clone()
+    }
+    
+    /// Get shared per-domain metrics map
+    pub fn domain_metrics(&self) -> DomainMetricsMap {
+        self.domain_metrics.clone()
+    }
+    
+    /// Get metrics
+    pub async fn get_metrics(&self) -> CrossDomainMetrics {
+        self.metrics.clone()
+    }
+    
+    /// Validate transaction on the validation worker pool
+    async fn validate_transaction(&self, transaction: &CrossDomainTransaction) -> GarpResult<TransactionAdmission> {
+        self.validation_pool.validate(transaction.clone()).await
+    }
+    
+    /// Pool validating submissions, for monitoring its load
+    pub fn validation_pool(&self) -> Arc<ValidationWorkerPool> {
+        self.validation_pool.clone()
+    }
+    
+    /// Start coordination session
+    #[instrument(skip_all, fields(correlation_id = %transaction.transaction_id))]
+    async fn start_coordination_session(&self, transaction: &CrossDomainTransaction) -> GarpResult<()> {
+        let session_id = Uuid::new_v4().to_string();
+        let coordination_timeout = self.coordination_timeout().await;
+        
+        let session = CoordinationSession {
+            session_id: session_id.clone(),
+            transaction_id: transaction.transaction_id.clone(),
+            participating_domains: transaction.target_domains.clone(),
+            phase: CoordinationPhase::Prepare,
+            votes: HashMap::new(),
+            required_votes: transaction.required_confirmations,
+            timeout_at: Instant::now() + coordination_timeout,
+            created_at: Instant::now(),
+            last_activity: Instant::now(),
+            result: None,
+        };
+        
+        // Store session
+        {
+            let mut sessions = self.coordination_sessions.write().await;
+            sessions.insert(session_id.clone(), session);
+        }
+        
+        // Send transaction proposals to target domains
+        for domain_id in &transaction.target_domains {
+            self.send_transaction_proposal(domain_id, transaction).await?;
+        }
+        
+        // Emit event
+        self.event_tx.send(CrossDomainEvent::CoordinationSessionStarted(session_id))?;
+        
+        Ok(())
+    }
+    
+    /// Send transaction proposal to domain
+    async fn send_transaction_proposal(&self, domain_id: &DomainId, transaction: &CrossDomainTransaction) -> GarpResult<()> {
+        let message = CrossDomainMessage {
+            message_id: Uuid::new_v4().to_string(),
+            message_type: CrossDomainMessageType::TransactionProposal(transaction.clone()),
+            source_domain: "global-synchronizer".to_string(),
+            target_domain: domain_id.clone(),
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(), // TODO: Sign message
+            correlation_id: Some(transaction.transaction_id.to_string()),
+        };
+        
+        let priority = if CompensationEngine::is_compensation(transaction) {
+            MessagePriority::High
+        } else {
+            MessagePriority::Normal
+        };
+        self.network_manager.send_cross_domain_message_with_priority(domain_id, message, priority).await?;
+        Ok(())
+    }
+    
+    /// Initialize domain states
+    async fn initialize_domain_states(&self) -> GarpResult<()> {
+        let count = Self::sync_domain_states(&self.domain_discovery, &self.domain_states, &self.metrics).await;
+        info!(domains = count, "Initialized domain states");
+        Ok(())
+    }
+    
+    /// Add a state for every discovered domain that has none yet, keeping
+    /// existing states. Returns the number of known domains.
+    async fn sync_domain_states(
+        domain_discovery: &Arc<DomainDiscovery>,
+        domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+    ) -> usize {
+        let discovered_domains = domain_discovery.get_discovered_domains().await;
+        
+        let mut states = domain_states.write().await;
+        for registration in discovered_domains {
+            if !states.contains_key(&registration.domain_id) {
+                states.insert(registration.domain_id.clone(), Self::new_domain_state(&registration));
+            }
+        }
+        
+        // Update metrics
+        {
+            let mut active_domains = metrics.active_domains.write().await;
+            *active_domains = states.len();
+        }
+        
+        states.len()
+    }
+    
+    /// Fresh state for a registered domain
+    fn new_domain_state(registration: &DomainRegistration) -> DomainState {
+        DomainState {
+            domain_id: registration.domain_id.clone(),
+            status: DomainStatus::Active,
+            last_block_height: 0,
+            last_block_hash: String::new(),
+            state_root: String::new(),
+            state_root_hash: String::new(),
+            validators: Vec::new(),
+            last_updated: Instant::now(),
+            endpoint: registration.endpoint.clone(),
+            capabilities: DomainCapabilities {
+                supported_transaction_types: Vec::new(),
+                max_transaction_size: 1024 * 1024, // 1MB default
+                consensus_algorithm: "unknown".to_string(),
+                finality_time: 30, // 30 seconds default
+                throughput: 100, // 100 TPS default
+                features: registration.capabilities.clone(),
+            },
+            metrics: DomainMetrics {
+                transaction_count: 0,
+                avg_confirmation_time: 0.0,
+                success_rate: 0.0,
+                last_response_time: Duration::from_secs(0),
+                uptime_percentage: 0.0,
+            },
+        }
+    }
+    
+    /// Add or update the state of a registered domain, bringing an Offline
+    /// domain back to Active
+    async fn apply_registration(
+        registration: &DomainRegistration,
+        domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+    ) {
+        let mut states = domain_states.write().await;
+        match states.get_mut(&registration.domain_id) {
+            Some(state) => {
+                state.endpoint = registration.endpoint.clone();
+                state.capabilities.features = registration.capabilities.clone();
+                if state.status == DomainStatus::Offline {
+                    state.status = DomainStatus::Active;
+                }
+                state.last_updated = Instant::now();
+            }
+            None => {
+                states.insert(registration.domain_id.clone(), Self::new_domain_state(registration));
+                info!(domain_id = %registration.domain_id, "Coordinating with newly registered domain");
+            }
+        }
+        *metrics.active_domains.write().await = states.len();
+    }
+    
+    /// Drop the states of departed peers
+    async fn remove_domain_states(
+        removed_peers: &[DomainId],
+        domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+    ) -> usize {
+        let mut states = domain_states.write().await;
+        let before = states.len();
+        for peer_id in removed_peers {
+            states.remove(peer_id);
+        }
+        *metrics.active_domains.write().await = states.len();
+        before - states.len()
+    }
+    
+    /// Start topology monitor, which picks up domain states for newly
+    /// discovered peers and drops those of departed ones
+    async fn start_topology_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let mut changes = self.network_manager.subscribe_topology_changes();
+        let domain_discovery = self.domain_discovery.clone();
+        let domain_states = self.domain_states.clone();
         let metrics = self.metrics.clone();
         
         let handle = tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(5));
-            
-            loop {
-                interval.tick().await;
-                
-                let now = Instant::now();
-                let mut completed_sessions = Vec::new();
-                let mut timed_out_sessions = Vec::new();
-                
-                // Check session timeouts and completion
-                {
-                    let sessions = coordination_sessions.read().await;
-                    for (session_id, session) in sessions.iter() {
-                        if session.phase == CoordinationPhase::Completed {
-                            completed_sessions.push(session_id.clone());
-                        } else if now > session.timeout_at {
-                            timed_out_sessions.push(session_id.clone());
-                        }
-                    }
-                }
-                
-                // Handle completed sessions
-                for session_id in completed_sessions {
-                    let mut sessions = coordination_sessions.write().await;
-                    if let Some(session) = sessions.remove(&session_id) {
-                        info!("Coordination session completed: {}", session_id);
-                        
-                        // Update transaction status
-                        {
-                            let mut transactions = active_transactions.write().await;
-                            if let Some(transaction) = transactions.get_mut(&session.transaction_id) {
-                                transaction.status = TransactionStatus::Completed;
-                            }
-                        }
-                    }
+            while let Some(change) = changes.recv().await {
+                if !change.added_peers.is_empty() {
+                    let count = Self::sync_domain_states(&domain_discovery, &domain_states, &metrics).await;
+                    debug!("Topology added {} peers, {} domain states known", change.added_peers.len(), count);
                 }
-                
-                // Handle timed out sessions
-                for session_id in timed_out_sessions {
-                    let mut sessions = coordination_sessions.write().await;
-                    if let Some(session) = sessions.remove(&session_id) {
-                        warn!("Coordination session timed out: {}", session_id);
-                        
-                        // Update transaction status
-                        {
-                            let mut transactions = active_transactions.write().await;
-                            if let Some(transaction) = transactions.get_mut(&session.transaction_id) {
-                                transaction.status = TransactionStatus::TimedOut;
-                            }
-                        }
-                        
-                        // Update metrics
-                        {
-                            let mut failed = metrics.failed_transactions.write().await;
-                            *failed += 1;
-                        }
+                if !change.removed_peers.is_empty() {
+                    let removed = Self::remove_domain_states(&change.removed_peers, &domain_states, &metrics).await;
+                    if removed > 0 {
+                        info!(removed, "Removed domain states for departed peers");
                     }
                 }
-                
-                // Update active sessions metric
-                {
-                    let sessions = coordination_sessions.read().await;
-                    let mut active_sessions = metrics.active_coordination_sessions.write().await;
-                    *active_sessions = sessions.len();
-                }
             }
         });
         
-        Ok(handle)
+        Ok(handle)
+    }
+    
+    /// Start discovery monitor, which adds states for domains registering
+    /// at runtime and marks expired ones Offline
+    async fn start_discovery_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let mut events = self.domain_discovery.subscribe();
+        let domain_discovery = self.domain_discovery.clone();
+        let domain_states = self.domain_states.clone();
+        let metrics = self.metrics.clone();
+        let event_tx = self.event_tx.clone();
+        
+        let handle = tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(DiscoveryEvent::DomainRegistered(registration)) => {
+                        Self::apply_registration(&registration, &domain_states, &metrics).await;
+                    }
+                    Ok(DiscoveryEvent::DomainExpired(domain_id)) => {
+                        if let Err(e) = event_tx.send(CrossDomainEvent::DomainStatusChanged(domain_id, DomainStatus::Offline)) {
+                            error!("Failed to send domain status change event: {}", e);
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Discovery monitor lagged by {} events, resynchronizing", skipped);
+                        Self::sync_domain_states(&domain_discovery, &domain_states, &metrics).await;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Register a sync domain, or refresh its registration
+    pub async fn register_domain(&self, announcement: DomainAnnouncement) -> GarpResult<DomainRegistration> {
+        self.domain_discovery.register(announcement).await
+    }
+    
+    /// Refresh the registration TTL of a domain
+    pub async fn domain_heartbeat(&self, domain_id: &DomainId) -> GarpResult<DomainRegistration> {
+        self.domain_discovery.heartbeat(domain_id).await
+    }
+    
+    /// Known domains with their remaining registration TTL
+    pub async fn list_registered_domains(&self) -> Vec<RegisteredDomain> {
+        self.domain_discovery.list_registered_domains().await
+    }
+    
+    /// Apply a signing key rotation announced by a domain
+    pub async fn apply_domain_key_rotation(&self, rotation: &KeyRotation) -> GarpResult<DomainRegistration> {
+        self.domain_discovery.apply_key_rotation(rotation).await
+    }
+    
+    /// Start message processor
+    async fn start_message_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let event_rx = self.event_rx.clone();
+        let active_transactions = self.active_transactions.clone();
+        let coordination_sessions = self.coordination_sessions.clone();
+        let domain_states = self.domain_states.clone();
+        let metrics = self.metrics.clone();
+        let domain_metrics = self.domain_metrics.clone();
+        let swap_manager = self.swap_manager.clone();
+        let token_wrapper = self.token_wrapper.clone();
+        let network_manager = self.network_manager.clone();
+        let storage = self.storage.clone();
+        let event_tx = self.event_tx.clone();
+        let reputation = self.reputation.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut event_rx = event_rx.lock().await;
+            
+            while let Some(event) = event_rx.recv().await {
+                match event {
+                    CrossDomainEvent::TransactionConfirmed(tx_id, confirmation) => {
+                        let swap_id = active_transactions.read().await.get(&tx_id)
+                            .and_then(|transaction| AtomicSwapManager::swap_id(transaction).cloned());
+                        if let Some(swap_id) = swap_id {
+                            Self::handle_swap_confirmation(
+                                tx_id,
+                                swap_id,
+                                confirmation,
+                                &swap_manager,
+                                &token_wrapper,
+                                &network_manager,
+                                &active_transactions,
+                                &coordination_sessions,
+                                &metrics,
+                            ).await;
+                            continue;
+                        }
+                        
+                        Self::handle_transaction_confirmed(
+                            tx_id,
+                            confirmation,
+                            &active_transactions,
+                            &coordination_sessions,
+                            &metrics,
+                            &domain_metrics,
+                            &storage,
+                            &network_manager,
+                            &event_tx,
+                            &reputation,
+                        ).await;
+                    }
+                    
+                    CrossDomainEvent::DomainStatusChanged(domain_id, status) => {
+                        Self::handle_domain_status_changed(
+                            domain_id,
+                            status,
+                            &domain_states,
+                            &metrics,
+                        ).await;
+                    }
+                    
+                    CrossDomainEvent::EmergencyDetected(notification) => {
+                        Self::handle_emergency_detected(notification).await;
+                    }
+                    
+                    CrossDomainEvent::Shutdown => {
+                        info!("Received shutdown signal in cross-domain message processor");
+                        break;
+                    }
+                    
+                    _ => {
+                        debug!("Received cross-domain event: {:?}", event);
+                    }
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Handle transaction confirmed
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(correlation_id = %tx_id, domain_id = %confirmation.domain_id))]
+    async fn handle_transaction_confirmed(
+        tx_id: TransactionId,
+        confirmation: DomainConfirmation,
+        active_transactions: &Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
+        coordination_sessions: &Arc<RwLock<HashMap<String, CoordinationSession>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+        domain_metrics: &DomainMetricsMap,
+        storage: &Arc<GlobalStorage>,
+        network_manager: &Arc<NetworkManager>,
+        event_tx: &mpsc::UnboundedSender<CrossDomainEvent>,
+        reputation: &Arc<ReputationTracker>,
+    ) {
+        debug!("Handling transaction confirmation: {} from {}", tx_id, confirmation.domain_id);
+        
+        // A redelivered vote must not be counted again
+        let session_id = {
+            let sessions = coordination_sessions.read().await;
+            sessions.values()
+                .find(|session| session.transaction_id == tx_id)
+                .map(|session| session.session_id.clone())
+        };
+        if let Some(session_id) = &session_id {
+            if !storage.record_coordination_vote(session_id, &confirmation.domain_id, confirmation.vote_sequence_number).await {
+                debug!(
+                    "Ignoring duplicate vote {} from {} in session {}",
+                    confirmation.vote_sequence_number, confirmation.domain_id, session_id
+                );
+                return;
+            }
+        }
+        
+        // Update transaction
+        let mut latency_ms = None;
+        let mut state_sync = None;
+        let mut finalized_by = Vec::new();
+        {
+            let mut transactions = active_transactions.write().await;
+            if let Some(transaction) = transactions.get_mut(&tx_id) {
+                latency_ms = Some((confirmation.timestamp - transaction.created_at).num_milliseconds().max(0) as f64);
+                transaction.confirmations.insert(confirmation.domain_id.clone(), confirmation.clone());
+                transaction.updated_at = chrono::Utc::now();
+                
+                // Check if transaction is complete
+                let confirmed_count = transaction.confirmations.values()
+                    .filter(|c| c.status == ConfirmationStatus::Confirmed)
+                    .count();
+                
+                if confirmed_count >= transaction.required_confirmations && !transaction.status.is_terminal() {
+                    if let CrossDomainTransactionType::StateSynchronization { .. } = transaction.transaction_type {
+                        // Completes once applied to state storage
+                        state_sync = Some(transaction.clone());
+                    } else {
+                        transaction.status = TransactionStatus::Completed;
+                        finalized_by = transaction.confirmations.values()
+                            .filter(|c| c.status == ConfirmationStatus::Confirmed)
+                            .map(|c| c.domain_id.clone())
+                            .collect();
+                        
+                        // Update metrics
+                        {
+                            let mut successful = metrics.successful_transactions.write().await;
+                            *successful += 1;
+                        }
+                    }
+                }
+            }
+        }
+        
+        if let Some(transaction) = state_sync {
+            Self::apply_state_synchronization(transaction, active_transactions, metrics, storage, network_manager, event_tx).await;
+        }
+        
+        // Credit the domains that confirmed a completed transaction
+        for domain_id in finalized_by {
+            if let Err(e) = reputation.record(&domain_id, ReputationSignal::TransactionFinalized).await {
+                warn!("Failed to record finalized transaction for domain {}: {}", domain_id, e);
+            }
+        }
+        
+        // Update per-domain metrics
+        {
+            let mut domain_metrics = domain_metrics.write().await;
+            domain_metrics
+                .entry(confirmation.domain_id.clone())
+                .or_default()
+                .record_confirmation(&confirmation, latency_ms);
+        }
+        
+        // Update coordination session
+        {
+            let mut sessions = coordination_sessions.write().await;
+            for session in sessions.values_mut() {
+                if session.transaction_id == tx_id {
+                    let vote = CoordinationVote {
+                        domain_id: confirmation.domain_id.clone(),
+                        vote: confirmation.status == ConfirmationStatus::Confirmed,
+                        reason: None,
+                        data: confirmation.data.clone(),
+                        signature: confirmation.signature.clone(),
+                        timestamp: confirmation.timestamp,
+                        vote_sequence_number: confirmation.vote_sequence_number,
+                    };
+                    
+                    session.votes.insert(confirmation.domain_id, vote);
+                    session.last_activity = Instant::now();
+                    
+                    // Check if coordination is complete, or can no longer complete
+                    let approve_votes = session.votes.values().filter(|v| v.vote).count();
+                    let outstanding = session.participating_domains.iter()
+                        .filter(|domain_id| !session.votes.contains_key(*domain_id))
+                        .count();
+                    if approve_votes >= session.required_votes {
+                        session.phase = CoordinationPhase::Completed;
+                    } else if approve_votes + outstanding < session.required_votes {
+                        session.phase = CoordinationPhase::Abort;
+                    }
+                    
+                    break;
+                }
+            }
+        }
+    }
+    
+    /// Apply a confirmed state synchronization to its target domains' state.
+    /// A target already holding the same or a newer version of the key fails
+    /// the transaction, and the source domain is told so it can rebase.
+    async fn apply_state_synchronization(
+        transaction: CrossDomainTransaction,
+        active_transactions: &Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+        storage: &Arc<GlobalStorage>,
+        network_manager: &Arc<NetworkManager>,
+        event_tx: &mpsc::UnboundedSender<CrossDomainEvent>,
+    ) {
+        let CrossDomainTransactionType::StateSynchronization { state_key, state_value, version } = &transaction.transaction_type else {
+            return;
+        };
+        let tx_id = transaction.transaction_id.clone();
+        let block_height = match storage.get_latest_block().await {
+            Ok(block) => block.map_or(0, |block| block.height),
+            Err(e) => {
+                warn!("Failed to read latest block for state synchronization {}: {}", tx_id, e);
+                0
+            }
+        };
+        
+        let (status, failure) = match storage.apply_state_sync(
+            &transaction.target_domains,
+            state_key,
+            state_value,
+            *version,
+            &tx_id,
+            block_height,
+        ).await {
+            Ok(StateSyncOutcome::Applied(transitions)) => {
+                debug!("Applied state synchronization {} to {} domains", tx_id, transitions.len());
+                (TransactionStatus::Completed, None)
+            }
+            Ok(StateSyncOutcome::VersionConflict { domain_id, stored_version }) => {
+                warn!(
+                    "State synchronization {} of {} rejected: domain {} has version {}, got {}",
+                    tx_id, state_key, domain_id, stored_version, version
+                );
+                let conflict = StateVersionConflict {
+                    transaction_id: tx_id.clone(),
+                    domain_id,
+                    state_key: state_key.clone(),
+                    stored_version,
+                    incoming_version: *version,
+                };
+                let reason = format!(
+                    "VersionConflict: domain {} has {} at version {}, transaction carried version {}",
+                    conflict.domain_id, conflict.state_key, conflict.stored_version, conflict.incoming_version
+                );
+                let message = CrossDomainMessage {
+                    message_id: Uuid::new_v4().to_string(),
+                    message_type: CrossDomainMessageType::StateVersionConflict(conflict.clone()),
+                    source_domain: "global-synchronizer".to_string(),
+                    target_domain: transaction.source_domain.clone(),
+                    timestamp: chrono::Utc::now(),
+                    signature: Vec::new(),
+                    correlation_id: Some(tx_id.to_string()),
+                };
+                if let Err(e) = network_manager.send_cross_domain_message(&transaction.source_domain, message).await {
+                    warn!("Failed to send state version conflict to {}: {}", transaction.source_domain, e);
+                }
+                let _ = event_tx.send(CrossDomainEvent::StateVersionConflict(conflict));
+                (TransactionStatus::Failed, Some(reason))
+            }
+            Err(e) => {
+                error!("Failed to apply state synchronization {}: {}", tx_id, e);
+                (TransactionStatus::Failed, Some(e.to_string()))
+            }
+        };
+        
+        {
+            let mut transactions = active_transactions.write().await;
+            if let Some(transaction) = transactions.get_mut(&tx_id) {
+                transaction.status = status;
+                transaction.updated_at = chrono::Utc::now();
+                if let Some(reason) = &failure {
+                    transaction.metadata.insert("failure_reason".to_string(), reason.clone());
+                }
+            }
+        }
+        
+        match failure {
+            None => *metrics.successful_transactions.write().await += 1,
+            Some(reason) => {
+                *metrics.failed_transactions.write().await += 1;
+                let _ = event_tx.send(CrossDomainEvent::TransactionFailed(tx_id, reason));
+            }
+        }
+    }
+    
+    /// History of a synchronized state key on a domain, newest first
+    pub async fn get_state_history(&self, domain_id: &DomainId, key: &str, limit: usize) -> GarpResult<Vec<StateTransition>> {
+        self.storage.get_state_history(domain_id, key, limit).await
+    }
+    
+    /// Handle a domain's lock confirmation for an atomic swap leg. The
+    /// transaction completes once the preimage is revealed and fails if the
+    /// swap is refunded instead.
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_swap_confirmation(
+        tx_id: TransactionId,
+        swap_id: SwapId,
+        confirmation: DomainConfirmation,
+        swap_manager: &Arc<AtomicSwapManager>,
+        token_wrapper: &Arc<TokenWrapper>,
+        network_manager: &Arc<NetworkManager>,
+        active_transactions: &Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
+        coordination_sessions: &Arc<RwLock<HashMap<String, CoordinationSession>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+    ) {
+        debug!("Handling swap {} confirmation from {}", swap_id, confirmation.domain_id);
+        
+        let (swap, instructions) = match swap_manager.record_lock(&swap_id, &confirmation, chrono::Utc::now()).await {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Ignoring confirmation from {} for swap {}: {}", confirmation.domain_id, swap_id, e);
+                return;
+            }
+        };
+        
+        {
+            let mut transactions = active_transactions.write().await;
+            if let Some(transaction) = transactions.get_mut(&tx_id) {
+                transaction.confirmations.insert(confirmation.domain_id.clone(), confirmation);
+                transaction.updated_at = chrono::Utc::now();
+            }
+        }
+        
+        Self::send_swap_instructions(network_manager, &swap.transaction_id, instructions).await;
+        Self::finish_swap_transaction(&swap, token_wrapper, active_transactions, coordination_sessions, metrics).await;
+    }
+    
+    /// Move a swap's transaction to the status matching the swap outcome,
+    /// settling the token wrapping it carries out, if any
+    async fn finish_swap_transaction(
+        swap: &AtomicSwap,
+        token_wrapper: &Arc<TokenWrapper>,
+        active_transactions: &Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
+        coordination_sessions: &Arc<RwLock<HashMap<String, CoordinationSession>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+    ) {
+        let (status, phase) = match swap.status {
+            SwapStatus::AwaitingLocks => return,
+            SwapStatus::Revealed => (TransactionStatus::Completed, CoordinationPhase::Completed),
+            SwapStatus::Refunded if swap.refund_after <= swap.updated_at => (TransactionStatus::TimedOut, CoordinationPhase::Abort),
+            SwapStatus::Refunded => (TransactionStatus::Rejected, CoordinationPhase::Abort),
+        };
+        
+        if let Err(e) = token_wrapper.settle(swap).await {
+            error!("Failed to settle token wrapping of swap {}: {}", swap.swap_id, e);
+        }
+        
+        {
+            let mut transactions = active_transactions.write().await;
+            match transactions.get_mut(&swap.transaction_id) {
+                Some(transaction) if !transaction.status.is_terminal() => {
+                    transaction.status = status.clone();
+                    transaction.updated_at = chrono::Utc::now();
+                }
+                _ => return,
+            }
+        }
+        
+        if status == TransactionStatus::Completed {
+            let mut successful = metrics.successful_transactions.write().await;
+            *successful += 1;
+        } else {
+            let mut failed = metrics.failed_transactions.write().await;
+            *failed += 1;
+        }
+        
+        let mut sessions = coordination_sessions.write().await;
+        for session in sessions.values_mut() {
+            if session.transaction_id == swap.transaction_id {
+                session.phase = phase;
+                session.last_activity = Instant::now();
+                break;
+            }
+        }
+    }
+    
+    /// Send swap claim and refund instructions to their domains
+    async fn send_swap_instructions(
+        network_manager: &Arc<NetworkManager>,
+        transaction_id: &TransactionId,
+        instructions: SwapInstructions,
+    ) {
+        for (domain_id, instruction) in instructions {
+            let message = CrossDomainMessage {
+                message_id: Uuid::new_v4().to_string(),
+                message_type: CrossDomainMessageType::SwapInstruction(instruction),
+                source_domain: "global-synchronizer".to_string(),
+                target_domain: domain_id.clone(),
+                timestamp: chrono::Utc::now(),
+                signature: Vec::new(),
+                correlation_id: Some(transaction_id.to_string()),
+            };
+            if let Err(e) = network_manager.send_cross_domain_message(&domain_id, message).await {
+                warn!("Failed to send swap instruction to {}: {}", domain_id, e);
+            }
+        }
+    }
+    
+    /// Handle domain status changed
+    async fn handle_domain_status_changed(
+        domain_id: DomainId,
+        status: DomainStatus,
+        domain_states: &Arc<RwLock<HashMap<DomainId, DomainState>>>,
+        metrics: &Arc<CrossDomainMetrics>,
+    ) {
+        info!(domain_id = %domain_id, status = ?status, "Domain status changed");
+        
+        {
+            let mut states = domain_states.write().await;
+            if let Some(state) = states.get_mut(&domain_id) {
+                state.status = status.clone();
+                state.last_updated = Instant::now();
+            }
+        }
+        
+        // Update metrics
+        {
+            let states = domain_states.read().await;
+            let active_count = states.values()
+                .filter(|state| state.status == DomainStatus::Active)
+                .count();
+            
+            let mut active_domains = metrics.active_domains.write().await;
+            *active_domains = active_count;
+        }
+    }
+    
+    /// Handle emergency detected
+    async fn handle_emergency_detected(notification: EmergencyNotification) {
+        error!("Emergency detected: {:?} - {}", notification.emergency_type, notification.description);
+        
+        // TODO: Implement emergency response procedures
+        match notification.emergency_type {
+            EmergencyType::NetworkPartition => {
+                // Handle network partition
+            }
+            EmergencyType::ByzantineBehavior => {
+                // Handle Byzantine behavior
+            }
+            EmergencyType::CriticalVulnerability => {
+                // Handle critical vulnerability
+            }
+            _ => {
+                // Handle other emergencies
+            }
+        }
+    }
+    
+    /// Start domain monitor, which marks unresponsive domains Unavailable
+    /// and lifts expired suspensions once the domain responds again
+    async fn start_domain_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let domain_states = self.domain_states.clone();
+        let network_manager = self.network_manager.clone();
+        let event_tx = self.event_tx.clone();
+        let suspensions = self.suspensions.clone();
+        let timelock_queue = self.timelock_queue.clone();
+        let active_transactions = self.active_transactions.clone();
+        let coordination_sessions = self.coordination_sessions.clone();
+        let reputation = self.reputation.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(30));
+            
+            loop {
+                interval.tick().await;
+                
+                // Check domain health
+                let domains_to_check: Vec<DomainId> = {
+                    let states = domain_states.read().await;
+                    states.keys().cloned().collect()
+                };
+                
+                for domain_id in domains_to_check {
+                    // Send heartbeat request
+                    let heartbeat = HeartbeatMessage {
+                        domain_id: "global-synchronizer".to_string(),
+                        block_height: 0,
+                        transaction_count: 0,
+                        status: DomainStatus::Active,
+                        timestamp: chrono::Utc::now(),
+                    };
+                    
+                    let message = CrossDomainMessage {
+                        message_id: Uuid::new_v4().to_string(),
+                        message_type: CrossDomainMessageType::Heartbeat(heartbeat),
+                        source_domain: "global-synchronizer".to_string(),
+                        target_domain: domain_id.clone(),
+                        timestamp: chrono::Utc::now(),
+                        signature: Vec::new(),
+                        correlation_id: None,
+                    };
+                    
+                    let resume_at = suspensions.read().await.get(&domain_id).map(|s| s.resume_at);
+                    
+                    // Check if domain responds
+                    match timeout(Duration::from_secs(10), 
+                        network_manager.send_cross_domain_message(&domain_id, message)).await {
+                        Ok(Ok(_)) => {
+                            // Domain is responsive
+                            if resume_at.map_or(false, |resume_at| resume_at <= chrono::Utc::now()) {
+                                Self::lift_suspension(
+                                    &domain_id,
+                                    &domain_states,
+                                    &suspensions,
+                                    &timelock_queue,
+                                    &event_tx,
+                                ).await;
+                            }
+                        }
+                        _ if resume_at.is_some() => {
+                            // Suspended domains are expected to be unreachable
+                            debug!("Suspended domain {} did not respond to heartbeat", domain_id);
+                        }
+                        _ => {
+                            // Domain is unresponsive
+                            if let Err(e) = event_tx.send(CrossDomainEvent::DomainStatusChanged(
+                                domain_id.clone(), DomainStatus::Unavailable)) {
+                                error!("Failed to send domain status change event: {}", e);
+                            }
+                            Self::penalize_domain(
+                                &domain_id,
+                                ReputationSignal::HeartbeatFailure,
+                                &reputation,
+                                &domain_states,
+                                &active_transactions,
+                                &coordination_sessions,
+                                &suspensions,
+                                &event_tx,
+                            ).await;
+                        }
+                    }
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start peer status monitor, which marks a domain Unavailable as soon
+    /// as its peer disconnects or is banned, and Active again on reconnect
+    async fn start_peer_status_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let mut changes = self.network_manager.subscribe_peer_status();
+        let domain_states = self.domain_states.clone();
+        let event_tx = self.event_tx.clone();
+        
+        let handle = tokio::spawn(async move {
+            while let Some(change) = changes.recv().await {
+                let domain_id = change.peer_id().clone();
+                let current = match domain_states.read().await.get(&domain_id) {
+                    Some(state) => state.status.clone(),
+                    None => continue,
+                };
+                let status = match change {
+                    PeerStatusChange::Connected(_) if current == DomainStatus::Unavailable => DomainStatus::Active,
+                    PeerStatusChange::Disconnected(_) | PeerStatusChange::Banned(_) if current == DomainStatus::Active => {
+                        DomainStatus::Unavailable
+                    }
+                    _ => continue,
+                };
+                if let Err(e) = event_tx.send(CrossDomainEvent::DomainStatusChanged(domain_id, status)) {
+                    error!("Failed to send domain status change event: {}", e);
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start coordination session monitor
+    async fn start_coordination_session_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let coordination_sessions = self.coordination_sessions.clone();
+        let active_transactions = self.active_transactions.clone();
+        let metrics = self.metrics.clone();
+        let storage = self.storage.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(5));
+            
+            loop {
+                interval.tick().await;
+                
+                let now = Instant::now();
+                let mut completed_sessions = Vec::new();
+                let mut timed_out_sessions = Vec::new();
+                
+                // Check session timeouts and completion
+                {
+                    let sessions = coordination_sessions.read().await;
+                    for (session_id, session) in sessions.iter() {
+                        if session.phase == CoordinationPhase::Completed {
+                            completed_sessions.push(session_id.clone());
+                        } else if session.phase != CoordinationPhase::Abort && now > session.timeout_at {
+                            timed_out_sessions.push(session_id.clone());
+                        }
+                    }
+                }
+                
+                // Handle completed sessions
+                for session_id in completed_sessions {
+                    let mut sessions = coordination_sessions.write().await;
+                    if let Some(session) = sessions.remove(&session_id) {
+                        info!(session_id = %session_id, "Coordination session completed");
+                        storage.prune_coordination_votes(&session_id).await;
+                        
+                        // Update transaction status
+                        {
+                            let mut transactions = active_transactions.write().await;
+                            if let Some(transaction) = transactions.get_mut(&session.transaction_id) {
+                                // Keep a status already settled by confirmation handling
+                                if !transaction.status.is_terminal() {
+                                    transaction.status = TransactionStatus::Completed;
+                                }
+                            }
+                        }
+                    }
+                }
+                
+                // Abort timed out sessions, leaving them for compensation
+                for session_id in timed_out_sessions {
+                    let mut sessions = coordination_sessions.write().await;
+                    if let Some(session) = sessions.get_mut(&session_id) {
+                        warn!("Coordination session timed out: {}", session_id);
+                        session.phase = CoordinationPhase::Abort;
+                        
+                        // Update transaction status
+                        {
+                            let mut transactions = active_transactions.write().await;
+                            if let Some(transaction) = transactions.get_mut(&session.transaction_id) {
+                                transaction.status = TransactionStatus::TimedOut;
+                            }
+                        }
+                        
+                        // Update metrics
+                        {
+                            let mut failed = metrics.failed_transactions.write().await;
+                            *failed += 1;
+                        }
+                    }
+                }
+                
+                // Update active sessions metric
+                {
+                    let sessions = coordination_sessions.read().await;
+                    let mut active_sessions = metrics.active_coordination_sessions.write().await;
+                    *active_sessions = sessions.len();
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start state synchronizer
+    async fn start_state_synchronizer(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let domain_states = self.domain_states.clone();
+        let network_manager = self.network_manager.clone();
+        let chunk_bytes = self.config.network.state_sync_chunk_bytes;
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(60));
+            
+            loop {
+                interval.tick().await;
+                
+                // Check if state synchronization is needed
+                let domains_to_sync: Vec<DomainId> = {
+                    let states = domain_states.read().await;
+                    states.values()
+                        .filter(|state| state.status == DomainStatus::Synchronizing)
+                        .map(|state| state.domain_id.clone())
+                        .collect()
+                };
+                
+                for domain_id in domains_to_sync {
+                    // Request state synchronization
+                    let sync_request = StateSyncRequest {
+                        request_id: Uuid::new_v4().to_string(),
+                        state_keys: vec!["*".to_string()], // Request all state
+                        from_height: 0,
+                        to_height: u64::MAX,
+                        cursor: None,
+                        max_response_bytes: chunk_bytes,
+                    };
+                    
+                    let message = CrossDomainMessage {
+                        message_id: Uuid::new_v4().to_string(),
+                        message_type: CrossDomainMessageType::StateSyncRequest(sync_request),
+                        source_domain: "global-synchronizer".to_string(),
+                        target_domain: domain_id.clone(),
+                        timestamp: chrono::Utc::now(),
+                        signature: Vec::new(),
+                        correlation_id: None,
+                    };
+                    
+                    if let Err(e) = network_manager.send_cross_domain_message(&domain_id, message).await {
+                        error!("Failed to send state sync request to {}: {}", domain_id, e);
+                    }
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+}
+
+impl CrossDomainMetrics {
+    /// Create new cross-domain metrics
+    pub fn new() -> Self {
+        Self {
+            total_transactions: Arc::new(RwLock::new(0)),
+            successful_transactions: Arc::new(RwLock::new(0)),
+            failed_transactions: Arc::new(RwLock::new(0)),
+            active_domains: Arc::new(RwLock::new(0)),
+            avg_coordination_time: Arc::new(RwLock::new(0.0)),
+            active_coordination_sessions: Arc::new(RwLock::new(0)),
+            cross_domain_throughput: Arc::new(RwLock::new(0.0)),
+        }
+    }
+    
+    /// Get success rate
+    pub async fn get_success_rate(&self) -> f64 {
+        let successful = *self.successful_transactions.read().await;
+        let total = *self.total_transactions.read().await;
+        
+        if total > 0 {
+            successful as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GlobalSyncConfig;
+    use crate::storage::GlobalStorage;
+    
+    #[tokio::test]
+    async fn test_cross_domain_coordinator_creation() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage,
+            network_manager,
+            domain_discovery,
+            consensus_engine,
+        ).await;
+        
+        assert!(coordinator.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_transaction_validation() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage,
+            network_manager,
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let transaction = CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["target".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: vec![1, 2, 3],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        
+        // This should fail because target domain is not available
+        let result = coordinator.validate_transaction(&transaction).await;
+        assert!(result.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_unauthorized_emergency_action_is_audited() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage,
+            network_manager,
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let transaction = CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["target".to_string()],
+            transaction_type: CrossDomainTransactionType::EmergencyAction {
+                action_type: "halt_domain".to_string(),
+                action_data: br#"{"domain_id": "target"}"#.to_vec(),
+                justification: "compromised".to_string(),
+                signatures: vec![EmergencySignature { key_id: "unknown".to_string(), signature: vec![0; 64] }],
+            },
+            data: vec![1, 2, 3],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        
+        assert!(coordinator.admit_transaction(transaction.clone()).await.is_err());
+        
+        let audit = coordinator.list_emergency_audit().await.unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].transaction_id, transaction.transaction_id);
+        assert!(!audit[0].accepted);
+        
+        let event = coordinator.event_rx.lock().await.try_recv().unwrap();
+        assert!(matches!(
+            event,
+            CrossDomainEvent::EmergencyDetected(EmergencyNotification { emergency_type: EmergencyType::SecurityBreach, .. })
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_aborted_session_is_compensated() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage,
+            network_manager,
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let confirmation = |domain_id: &str, status: ConfirmationStatus| DomainConfirmation {
+            domain_id: domain_id.to_string(),
+            status,
+            data: Vec::new(),
+            signature: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            validator_info: None,
+            vote_sequence_number: 0,
+        };
+        let transaction = CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["a".to_string(), "b".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: vec![1, 2, 3],
+            dependencies: Vec::new(),
+            required_confirmations: 2,
+            confirmations: HashMap::from([
+                ("a".to_string(), confirmation("a", ConfirmationStatus::Confirmed)),
+                ("b".to_string(), confirmation("b", ConfirmationStatus::Rejected)),
+            ]),
+            status: TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        coordinator.active_transactions.write().await.insert(transaction.transaction_id.clone(), transaction.clone());
+        coordinator.coordination_sessions.write().await.insert("session".to_string(), CoordinationSession {
+            session_id: "session".to_string(),
+            transaction_id: transaction.transaction_id.clone(),
+            participating_domains: transaction.target_domains.clone(),
+            phase: CoordinationPhase::Abort,
+            votes: HashMap::new(),
+            required_votes: 2,
+            timeout_at: Instant::now() + Duration::from_secs(300),
+            created_at: Instant::now(),
+            last_activity: Instant::now(),
+            result: None,
+        });
+        
+        let compensated = coordinator.process_compensations().await.unwrap();
+        assert_eq!(compensated, vec![transaction.transaction_id.clone()]);
+        assert_eq!(coordinator.get_transaction_status(&transaction.transaction_id).await, Some(TransactionStatus::Rejected));
+        
+        let chain = coordinator.get_compensation_chain(&transaction.transaction_id).await.unwrap().unwrap();
+        assert_eq!(chain.links.len(), 1);
+        assert_eq!(chain.links[0].domain_id, "a");
+        
+        // The compensation itself times out on domain a
+        {
+            let mut transactions = coordinator.active_transactions.write().await;
+            let compensation = transactions.get_mut(&chain.links[0].transaction_id).unwrap();
+            assert_eq!(compensation.metadata["compensation_for"], transaction.transaction_id.to_string());
+            compensation.status = TransactionStatus::TimedOut;
+        }
+        assert!(coordinator.process_compensations().await.unwrap().is_empty());
+        
+        let mut events = coordinator.event_rx.lock().await;
+        let mut escalated = false;
+        while let Ok(event) = events.try_recv() {
+            if let CrossDomainEvent::EmergencyDetected(notification) = event {
+                escalated |= matches!(notification.emergency_type, EmergencyType::DataCorruption);
+            }
+        }
+        assert!(escalated);
+    }
+    
+    #[tokio::test]
+    async fn test_duplicate_votes_are_counted_once() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage.clone(),
+            network_manager.clone(),
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let transaction = CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["a".to_string(), "b".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: Vec::new(),
+            dependencies: Vec::new(),
+            required_confirmations: 2,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        let tx_id = transaction.transaction_id.clone();
+        coordinator.active_transactions.write().await.insert(tx_id.clone(), transaction.clone());
+        coordinator.coordination_sessions.write().await.insert("session".to_string(), CoordinationSession {
+            session_id: "session".to_string(),
+            transaction_id: tx_id.clone(),
+            participating_domains: transaction.target_domains.clone(),
+            phase: CoordinationPhase::Vote,
+            votes: HashMap::new(),
+            required_votes: 2,
+            timeout_at: Instant::now() + Duration::from_secs(300),
+            created_at: Instant::now(),
+            last_activity: Instant::now(),
+            result: None,
+        });
+        
+        let vote = |domain_id: &str, status: ConfirmationStatus, vote_sequence_number: u64| DomainConfirmation {
+            domain_id: domain_id.to_string(),
+            status,
+            data: Vec::new(),
+            signature: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            validator_info: None,
+            vote_sequence_number,
+        };
+        let deliver = |confirmation: DomainConfirmation| CrossDomainCoordinator::handle_transaction_confirmed(
+            tx_id.clone(),
+            confirmation,
+            &coordinator.active_transactions,
+            &coordinator.coordination_sessions,
+            &coordinator.metrics,
+            &coordinator.domain_metrics,
+            &storage,
+            &network_manager,
+            &coordinator.event_tx,
+            &coordinator.reputation,
+        );
+        let approve_votes = || async {
+            let sessions = coordinator.coordination_sessions.read().await;
+            let session = &sessions["session"];
+            (session.votes.values().filter(|v| v.vote).count(), session.phase.clone())
+        };
+        
+        // Every vote arrives twice; domain a first rejects, then approves
+        deliver(vote("a", ConfirmationStatus::Rejected, 1)).await;
+        deliver(vote("a", ConfirmationStatus::Rejected, 1)).await;
+        assert_eq!(approve_votes().await, (0, CoordinationPhase::Vote));
+        deliver(vote("a", ConfirmationStatus::Confirmed, 2)).await;
+        deliver(vote("a", ConfirmationStatus::Confirmed, 2)).await;
+        assert_eq!(approve_votes().await, (1, CoordinationPhase::Vote));
+        assert_eq!(coordinator.domain_metrics.read().await["a"].confirmations_received, 2);
+        
+        deliver(vote("b", ConfirmationStatus::Confirmed, 1)).await;
+        deliver(vote("b", ConfirmationStatus::Confirmed, 1)).await;
+        assert_eq!(approve_votes().await, (2, CoordinationPhase::Completed));
+        assert_eq!(coordinator.domain_metrics.read().await["b"].confirmations_received, 1);
+        
+        // Both confirming domains are credited once for the completed transaction
+        for domain_id in ["a", "b"] {
+            let score = coordinator.reputation.get(domain_id).await;
+            assert_eq!(score.signals[&ReputationSignal::TransactionFinalized], 1);
+        }
+        
+        // Finalizing the session forgets its votes
+        assert_eq!(storage.prune_coordination_votes("session").await, 3);
+        assert!(storage.record_coordination_vote("session", &"b".to_string(), 1).await);
+    }
+    
+    #[tokio::test]
+    async fn test_stale_state_synchronization_fails_with_version_conflict() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage.clone(),
+            network_manager.clone(),
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let state_sync = |version: u64| CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["a".to_string()],
+            transaction_type: CrossDomainTransactionType::StateSynchronization {
+                state_key: "balance".to_string(),
+                state_value: version.to_le_bytes().to_vec(),
+                version,
+            },
+            data: Vec::new(),
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        
+        for (transaction, expected) in [
+            (state_sync(5), TransactionStatus::Completed),
+            (state_sync(4), TransactionStatus::Failed),
+        ] {
+            let tx_id = transaction.transaction_id.clone();
+            coordinator.active_transactions.write().await.insert(tx_id.clone(), transaction);
+            CrossDomainCoordinator::handle_transaction_confirmed(
+                tx_id.clone(),
+                DomainConfirmation {
+                    domain_id: "a".to_string(),
+                    status: ConfirmationStatus::Confirmed,
+                    data: Vec::new(),
+                    signature: Vec::new(),
+                    timestamp: chrono::Utc::now(),
+                    validator_info: None,
+                    vote_sequence_number: 0,
+                },
+                &coordinator.active_transactions,
+                &coordinator.coordination_sessions,
+                &coordinator.metrics,
+                &coordinator.domain_metrics,
+                &storage,
+                &network_manager,
+                &coordinator.event_tx,
+                &coordinator.reputation,
+            ).await;
+            
+            let transaction = coordinator.active_transactions.read().await[&tx_id].clone();
+            assert_eq!(transaction.status, expected);
+            if expected == TransactionStatus::Failed {
+                assert!(transaction.metadata["failure_reason"].starts_with("VersionConflict"));
+            }
+        }
+        
+        let history = coordinator.get_state_history(&"a".to_string(), "balance", 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].to_state, 5u64.to_le_bytes().to_vec());
+        assert_eq!(*coordinator.metrics.failed_transactions.read().await, 1);
+    }
+    
+    #[tokio::test]
+    async fn test_cross_domain_metrics() {
+        let metrics = CrossDomainMetrics::new();
+        
+        {
+            let mut total = metrics.total_transactions.write().await;
+            *total = 10;
+        }
+        
+        {
+            let mut successful = metrics.successful_transactions.write().await;
+            *successful = 8;
+        }
+        
+        let success_rate = metrics.get_success_rate().await;
+        assert_eq!(success_rate, 0.8);
     }
     
-    /// Start state synchronizer
-    async fn start_state_synchronizer(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let domain_states = self.domain_states.clone();
-        let network_manager = self.network_manager.clone();
+    #[test]
+    fn test_domain_perf_metrics_recording() {
+        let confirmation = |status: ConfirmationStatus| DomainConfirmation {
+            domain_id: "domain-a".to_string(),
+            status,
+            data: Vec::new(),
+            signature: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            validator_info: None,
+            vote_sequence_number: 0,
+        };
         
-        let handle = tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(60));
-            
-            loop {
-                interval.tick().await;
-                
-                // Check if state synchronization is needed
-                let domains_to_sync: Vec<DomainId> = {
-                    let states = domain_states.read().await;
-                    states.values()
-                        .filter(|state| state.status == DomainStatus::Synchronizing)
-                        .map(|state| state.domain_id.clone())
-                        .collect()
-                };
-                
-                for domain_id in domains_to_sync {
-                    // Request state synchronization
-                    let sync_request = StateSyncRequest {
-                        request_id: Uuid::new_v4().to_string(),
-                        state_keys: vec!["*".to_string()], // Request all state
-                        from_height: 0,
-                        to_height: u64::MAX,
-                    };
-                    
-                    let message = CrossDomainMessage {
-                        message_id: Uuid::new_v4().to_string(),
-                        message_type: CrossDomainMessageType::StateSyncRequest(sync_request),
-                        source_domain: "global-synchronizer".to_string(),
-                        target_domain: domain_id.clone(),
-                        timestamp: chrono::Utc::now(),
-                        signature: Vec::new(),
-                    };
-                    
-                    if let Err(e) = network_manager.send_cross_domain_message(&domain_id, message).await {
-                        error!("Failed to send state sync request to {}: {}", domain_id, e);
-                    }
-                }
-            }
-        });
+        let mut metrics = DomainPerfMetrics::default();
+        metrics.record_confirmation(&confirmation(ConfirmationStatus::Confirmed), Some(100.0));
+        metrics.record_confirmation(&confirmation(ConfirmationStatus::Rejected), Some(300.0));
         
-        Ok(handle)
+        assert_eq!(metrics.confirmations_received, 2);
+        assert_eq!(metrics.failed_confirmations, 1);
+        assert_eq!(metrics.avg_confirmation_latency_ms, 200.0);
+        assert!(metrics.last_activity.is_some());
     }
-}
-
-impl CrossDomainMetrics {
-    /// Create new cross-domain metrics
-    pub fn new() -> Self {
-        Self {
-            total_transactions: Arc::new(RwLock::new(0)),
-            successful_transactions: Arc::new(RwLock::new(0)),
-            failed_transactions: Arc::new(RwLock::new(0)),
-            active_domains: Arc::new(RwLock::new(0)),
-            avg_coordination_time: Arc::new(RwLock::new(0.0)),
-            active_coordination_sessions: Arc::new(RwLock::new(0)),
-            cross_domain_throughput: Arc::new(RwLock::new(0.0)),
+    
+    #[test]
+    fn test_state_sync_chunks_follow_cursor() {
+        let state: BTreeMap<String, Vec<u8>> = (0..50)
+            .map(|i| (format!("account/{:02}", i), vec![i as u8; 100]))
+            .chain(std::iter::once(("other".to_string(), vec![0; 100])))
+            .collect();
+        let mut request = StateSyncRequest {
+            request_id: "sync-1".to_string(),
+            state_keys: vec!["account/*".to_string()],
+            from_height: 0,
+            to_height: u64::MAX,
+            cursor: None,
+            max_response_bytes: 1000,
+        };
+        
+        let mut received = BTreeMap::new();
+        let mut chunks = 0;
+        loop {
+            let response = StateSyncResponse::chunk(&request, &state, (0, 10), 4096);
+            assert!(response.state_data.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>() <= 1000);
+            received.extend(response.state_data.clone());
+            chunks += 1;
+            match request.continuation(&response) {
+                Some(next) => request = next,
+                None => break,
+            }
         }
+        
+        assert_eq!(chunks, 6);
+        assert_eq!(received.len(), 50);
+        assert!(!received.contains_key("other"));
+        assert!(received.iter().all(|(k, v)| state.get(k) == Some(v)));
     }
     
-    /// Get success rate
-    pub async fn get_success_rate(&self) -> f64 {
-        let successful = *self.successful_transactions.read().await;
-        let total = *self.total_transactions.read().await;
+    #[tokio::test]
+    async fn test_registration_revives_offline_domain() {
+        let states = Arc::new(RwLock::new(HashMap::new()));
+        let metrics = Arc::new(CrossDomainMetrics::new());
+        let now = chrono::Utc::now();
+        let mut registration = DomainRegistration {
+            domain_id: "domain-a".to_string(),
+            endpoint: "a.example:9000".to_string(),
+            public_key: Vec::new(),
+            key_history: Default::default(),
+            capabilities: vec!["asset_transfer".to_string()],
+            ttl_secs: Some(60),
+            registered_at: now,
+            refreshed_at: now,
+        };
         
-        if total > 0 {
-            successful as f64 / total as f64
-        } else {
-            0.0
-        }
+        CrossDomainCoordinator::apply_registration(&registration, &states, &metrics).await;
+        assert_eq!(*metrics.active_domains.read().await, 1);
+        states.write().await.get_mut("domain-a").unwrap().status = DomainStatus::Offline;
+        
+        registration.endpoint = "a.example:9100".to_string();
+        CrossDomainCoordinator::apply_registration(&registration, &states, &metrics).await;
+        let states = states.read().await;
+        let state = &states["domain-a"];
+        assert_eq!(state.status, DomainStatus::Active);
+        assert_eq!(state.endpoint, "a.example:9100");
+        assert_eq!(state.capabilities.features, vec!["asset_transfer".to_string()]);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::GlobalSyncConfig;
-    use crate::storage::GlobalStorage;
     
     #[tokio::test]
-    async fn test_cross_domain_coordinator_creation() {
+    async fn test_required_state_root_must_match_last_synced_root() {
         let config = Arc::new(GlobalSyncConfig::default());
         let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
         let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
         let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
         let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
-        
         let coordinator = CrossDomainCoordinator::new(
             config,
             storage,
             network_manager,
             domain_discovery,
             consensus_engine,
-        ).await;
+        ).await.unwrap();
         
-        assert!(coordinator.is_ok());
+        let now = chrono::Utc::now();
+        let target = "target".to_string();
+        let registration = DomainRegistration {
+            domain_id: target.clone(),
+            endpoint: "target.example:9000".to_string(),
+            public_key: Vec::new(),
+            key_history: Default::default(),
+            capabilities: Vec::new(),
+            ttl_secs: None,
+            registered_at: now,
+            refreshed_at: now,
+        };
+        coordinator.domain_states.write().await
+            .insert(target.clone(), CrossDomainCoordinator::new_domain_state(&registration));
+        
+        let mut transaction = CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec![target.clone()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: vec![1, 2, 3],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        assert!(coordinator.validate_transaction(&transaction).await.is_ok());
+        
+        // No root is known for the domain yet
+        transaction.require_state_root(&target, "aa11").unwrap();
+        let err = coordinator.validate_transaction(&transaction).await.unwrap_err();
+        assert!(matches!(err, GarpError::StateMismatch(_)));
+        
+        let response = |state_root: Option<&str>| StateSyncResponse {
+            request_id: "sync-1".to_string(),
+            state_data: HashMap::new(),
+            height_range: (0, 10),
+            has_more: false,
+            next_cursor: None,
+            state_root: state_root.map(str::to_string),
+        };
+        coordinator.apply_state_sync_response(&target, &response(Some("AA11"))).await.unwrap();
+        assert!(coordinator.validate_transaction(&transaction).await.is_ok());
+        
+        // A response without a root keeps the last one
+        coordinator.apply_state_sync_response(&target, &response(None)).await.unwrap();
+        assert_eq!(coordinator.get_domain_state(&target).await.unwrap().state_root_hash, "AA11");
+        
+        coordinator.apply_state_sync_response(&target, &response(Some("bb22"))).await.unwrap();
+        let err = coordinator.validate_transaction(&transaction).await.unwrap_err();
+        assert!(matches!(err, GarpError::StateMismatch(ref reason) if reason.contains("bb22")));
+        assert!(coordinator.apply_state_sync_response(&"unknown".to_string(), &response(Some("bb22"))).await.is_err());
+        
+        transaction.metadata.insert(REQUIRED_STATE_ROOT_METADATA_KEY.to_string(), "not json".to_string());
+        assert!(matches!(coordinator.validate_transaction(&transaction).await, Err(GarpError::ValidationError(_))));
     }
     
     #[tokio::test]
-    async fn test_transaction_validation() {
+    async fn test_suspended_domain_rejects_and_queues_transactions() {
         let config = Arc::new(GlobalSyncConfig::default());
         let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
         let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
         let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
         let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
-        
         let coordinator = CrossDomainCoordinator::new(
             config,
             storage,
@@ -1322,6 +3730,20 @@ mod tests {
             consensus_engine,
         ).await.unwrap();
         
+        let now = chrono::Utc::now();
+        let registration = DomainRegistration {
+            domain_id: "target".to_string(),
+            endpoint: "target.example:9000".to_string(),
+            public_key: Vec::new(),
+            key_history: Default::default(),
+            capabilities: Vec::new(),
+            ttl_secs: None,
+            registered_at: now,
+            refreshed_at: now,
+        };
+        coordinator.domain_states.write().await
+            .insert("target".to_string(), CrossDomainCoordinator::new_domain_state(&registration));
+        
         let transaction = CrossDomainTransaction {
             transaction_id: TransactionId::new(),
             source_domain: "source".to_string(),
@@ -1337,32 +3759,430 @@ mod tests {
             required_confirmations: 1,
             confirmations: HashMap::new(),
             status: TransactionStatus::Pending,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
             metadata: HashMap::new(),
         };
+        let pending_id = transaction.transaction_id.clone();
+        coordinator.active_transactions.write().await.insert(pending_id.clone(), transaction.clone());
         
-        // This should fail because target domain is not available
-        let result = coordinator.validate_transaction(&transaction).await;
-        assert!(result.is_err());
+        let suspension = coordinator
+            .suspend_domain(&"target".to_string(), "upgrade".to_string(), Duration::from_secs(600))
+            .await
+            .unwrap();
+        assert_eq!(suspension.queued.len(), 1);
+        assert_eq!(coordinator.get_domain_state(&"target".to_string()).await.unwrap().status, DomainStatus::Maintenance);
+        assert!(coordinator.get_transaction_status(&pending_id).await.is_none());
+        
+        let mut new_transaction = transaction;
+        new_transaction.transaction_id = TransactionId::new();
+        let err = coordinator.validate_transaction(&new_transaction).await.unwrap_err();
+        assert!(err.to_string().contains("suspended for maintenance"));
+        assert!(err.is_retryable());
+        
+        coordinator.resume_domain(&"target".to_string()).await.unwrap();
+        assert_eq!(coordinator.get_domain_state(&"target".to_string()).await.unwrap().status, DomainStatus::Active);
+        assert!(coordinator.timelock_queue.contains(&pending_id).await);
+        assert!(coordinator.validate_transaction(&new_transaction).await.is_ok());
+        assert!(coordinator.resume_domain(&"target".to_string()).await.is_err());
     }
     
     #[tokio::test]
-    async fn test_cross_domain_metrics() {
-        let metrics = CrossDomainMetrics::new();
+    async fn test_heartbeat_failures_suspend_a_domain_below_the_reputation_threshold() {
+        let coordinator = coordinator_with_target().await;
+        let target = "target".to_string();
+        let penalize = || CrossDomainCoordinator::penalize_domain(
+            &target,
+            ReputationSignal::HeartbeatFailure,
+            &coordinator.reputation,
+            &coordinator.domain_states,
+            &coordinator.active_transactions,
+            &coordinator.coordination_sessions,
+            &coordinator.suspensions,
+            &coordinator.event_tx,
+        );
         
-        {
-            let mut total = metrics.total_transactions.write().await;
-            *total = 10;
+        // Default weights take the score from 50 down to the threshold of 20
+        for _ in 0..30 {
+            penalize().await;
+        }
+        assert!(coordinator.get_domain_suspensions().await.is_empty());
+        assert_eq!(coordinator.reputation.get(&target).await.signals[&ReputationSignal::HeartbeatFailure], 30);
+        
+        penalize().await;
+        let suspensions = coordinator.get_domain_suspensions().await;
+        assert_eq!(suspensions.len(), 1);
+        assert!(suspensions[0].reason.contains("Reputation"));
+        assert!(suspensions[0].resume_at >= suspensions[0].suspended_at + chrono::Duration::seconds(3_600));
+        assert_eq!(coordinator.get_domain_state(&target).await.unwrap().status, DomainStatus::Maintenance);
+        
+        // Further failures while suspended do not suspend it again
+        coordinator.resume_domain(&target).await.unwrap();
+        penalize().await;
+        assert!(coordinator.get_domain_suspensions().await.is_empty());
+    }
+    
+    async fn coordinator_with_target() -> CrossDomainCoordinator {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage,
+            network_manager,
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let now = chrono::Utc::now();
+        let registration = DomainRegistration {
+            domain_id: "target".to_string(),
+            endpoint: "target.example:9000".to_string(),
+            public_key: Vec::new(),
+            key_history: Default::default(),
+            capabilities: Vec::new(),
+            ttl_secs: None,
+            registered_at: now,
+            refreshed_at: now,
+        };
+        coordinator.domain_states.write().await
+            .insert("target".to_string(), CrossDomainCoordinator::new_domain_state(&registration));
+        coordinator
+    }
+    
+    /// Time-locked so that admission queues it without contacting domains
+    fn timelocked_transaction() -> CrossDomainTransaction {
+        let now = chrono::Utc::now();
+        CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["target".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: vec![1, 2, 3],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::seconds(300),
+            not_before: Some(now + chrono::Duration::seconds(3600)),
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_delegated_submission_follows_governance() {
+        let coordinator = coordinator_with_target().await;
+        let target = "target".to_string();
+        let pass = |action: GovernanceAction| {
+            let coordinator = &coordinator;
+            let target = target.clone();
+            async move {
+                let proposal = coordinator.submit_governance_proposal(&target, action, String::new()).await.unwrap();
+                coordinator.record_governance_vote(GovernanceVoteMessage {
+                    proposal_id: proposal.proposal_id.clone(),
+                    domain_id: target,
+                    approve: true,
+                }).await.unwrap();
+                let executed = coordinator.finalize_governance_proposals(chrono::Utc::now() + chrono::Duration::days(2)).await.unwrap();
+                assert_eq!(executed, vec![proposal.proposal_id]);
+            }
+        };
+        let delegated = || {
+            let mut transaction = timelocked_transaction();
+            transaction.delegated_by = Some("treasury".to_string());
+            transaction
+        };
+        
+        // Nothing is delegated until governance approves it
+        assert!(coordinator.submit_transaction(delegated()).await.is_err());
+        pass(GovernanceAction::IssueDelegation {
+            delegator: "treasury".to_string(),
+            delegate: "source".to_string(),
+            permissions: vec![delegation::DelegationPermission::SubmitTransactions],
+            expires_at: None,
+        }).await;
+        assert_eq!(coordinator.list_delegations().await.unwrap().len(), 1);
+        assert!(coordinator.submit_transaction(delegated()).await.is_ok());
+        
+        pass(GovernanceAction::RevokeDelegation { delegator: "treasury".to_string(), delegate: "source".to_string() }).await;
+        assert!(coordinator.get_delegation(&"treasury".to_string(), &"source".to_string()).await.unwrap().is_none());
+        assert!(coordinator.submit_transaction(delegated()).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_resubmission_is_idempotent() {
+        let coordinator = coordinator_with_target().await;
+        let transaction = timelocked_transaction();
+        let tx_id = transaction.transaction_id.clone();
+        
+        assert_eq!(
+            coordinator.submit_transaction(transaction.clone()).await.unwrap(),
+            SubmissionOutcome::Submitted(tx_id.clone())
+        );
+        assert_eq!(
+            coordinator.submit_transaction(transaction.clone()).await.unwrap(),
+            SubmissionOutcome::AlreadySubmitted(tx_id.clone())
+        );
+        assert_eq!(coordinator.timelock_queue.len().await, 1);
+        assert_eq!(*coordinator.metrics.total_transactions.read().await, 1);
+        
+        // Metadata and timestamps are not part of the content
+        let mut retried = transaction.clone();
+        retried.metadata.insert("retry".to_string(), "1".to_string());
+        retried.updated_at = chrono::Utc::now();
+        assert_eq!(
+            coordinator.submit_transaction(retried).await.unwrap(),
+            SubmissionOutcome::AlreadySubmitted(tx_id.clone())
+        );
+        
+        let mut conflicting = transaction;
+        conflicting.data = vec![4, 5, 6];
+        assert!(matches!(
+            coordinator.submit_transaction(conflicting).await,
+            Err(GarpError::Transaction(TransactionError::ConflictingTransactionId(id))) if id == tx_id
+        ));
+    }
+    
+    #[test]
+    fn test_content_hash_uses_canonical_encoding() {
+        let transaction = timelocked_transaction();
+        // Golden vector: changing these bytes changes every content hash
+        let content = concat!(
+            r#"["source",["target"],{"AssetTransfer":{"amount":100,"asset_id":"test-asset","#,
+            r#""from_address":"from","to_address":"to"}},[1,2,3]]"#,
+        );
+        assert_eq!(transaction.content_hash().unwrap(), *blake3::hash(content.as_bytes()).as_bytes());
+        
+        // Hashes stored before the canonical encoding followed field order
+        assert_ne!(transaction.legacy_content_hash().unwrap(), transaction.content_hash().unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_resubmitting_finished_transaction_is_rejected() {
+        let coordinator = coordinator_with_target().await;
+        let mut transaction = timelocked_transaction();
+        transaction.status = TransactionStatus::Completed;
+        let tx_id = transaction.transaction_id.clone();
+        coordinator.active_transactions.write().await.insert(tx_id.clone(), transaction.clone());
+        
+        transaction.status = TransactionStatus::Pending;
+        assert!(matches!(
+            coordinator.submit_transaction(transaction).await,
+            Err(GarpError::Transaction(TransactionError::DuplicateTransaction(id))) if id == tx_id
+        ));
+        assert_eq!(coordinator.timelock_queue.len().await, 0);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_double_submit_starts_one_submission() {
+        let coordinator = Arc::new(coordinator_with_target().await);
+        let transaction = timelocked_transaction();
+        
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let coordinator = coordinator.clone();
+                let transaction = transaction.clone();
+                tokio::spawn(async move { coordinator.submit_transaction(transaction).await.unwrap() })
+            })
+            .collect();
+        let mut submitted = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                SubmissionOutcome::Submitted(_) => submitted += 1,
+                SubmissionOutcome::AlreadySubmitted(id) => assert_eq!(id, transaction.transaction_id),
+            }
+        }
+        
+        assert_eq!(submitted, 1);
+        assert_eq!(coordinator.timelock_queue.len().await, 1);
+        assert_eq!(*coordinator.metrics.total_transactions.read().await, 1);
+        assert!(coordinator.submissions_in_flight.lock().await.is_empty());
+    }    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_submissions_validate_within_parallelism() {
+        let coordinator = Arc::new(coordinator_with_target().await);
+        let pool = coordinator.validation_pool();
+        
+        let handles: Vec<_> = (0..1000)
+            .map(|i| {
+                let coordinator = coordinator.clone();
+                let mut transaction = timelocked_transaction();
+                if i % 2 == 1 {
+                    transaction.data.clear();
+                }
+                tokio::spawn(async move { coordinator.submit_transaction(transaction).await })
+            })
+            .collect();
+        let mut submitted = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(SubmissionOutcome::Submitted(_)) => submitted += 1,
+                Ok(outcome) => panic!("unexpected outcome {:?}", outcome),
+                Err(e) => assert!(matches!(e, GarpError::ValidationError(_)), "unexpected error {}", e),
+            }
         }
         
+        assert_eq!(submitted, 500);
+        assert_eq!(coordinator.timelock_queue.len().await, 500);
+        assert_eq!(pool.in_flight(), 0);
+        assert!((1..=pool.parallelism()).contains(&pool.peak_in_flight()));
+    }
+    
+    #[tokio::test]
+    async fn test_reload_domain_reinitializes_from_registration() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let coordinator = CrossDomainCoordinator::new(
+            config,
+            storage,
+            network_manager,
+            domain_discovery,
+            consensus_engine,
+        ).await.unwrap();
+        
+        let domain_id = "domain1".to_string();
+        assert!(matches!(coordinator.reload_domain(&domain_id).await, Err(GarpError::NotFound(_))));
+        
+        coordinator.register_domain(DomainAnnouncement {
+            domain_id: domain_id.clone(),
+            endpoint: "http://domain1:8080".to_string(),
+            public_key: Vec::new(),
+            capabilities: vec!["asset_transfer".to_string()],
+            ttl_secs: None,
+        }).await.unwrap();
+        coordinator.initialize_domain_states().await.unwrap();
         {
-            let mut successful = metrics.successful_transactions.write().await;
-            *successful = 8;
+            let mut states = coordinator.domain_states.write().await;
+            let state = states.get_mut(&domain_id).unwrap();
+            state.status = DomainStatus::Offline;
+            state.last_block_height = 42;
         }
         
-        let success_rate = metrics.get_success_rate().await;
-        assert_eq!(success_rate, 0.8);
+        let state = coordinator.reload_domain(&domain_id).await.unwrap();
+        assert_eq!((state.status, state.last_block_height), (DomainStatus::Active, 0));
+        assert_eq!(state.endpoint, "http://domain1:8080");
+        let states = coordinator.list_domain_states().await;
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].status, DomainStatus::Active);
+    }
+    
+    /// Records, for every event, the correlation ID of its innermost span
+    /// that has one
+    struct CorrelationCapture(Arc<std::sync::Mutex<Vec<Option<String>>>>);
+    
+    struct CorrelationId(String);
+    
+    struct CorrelationVisitor(Option<String>);
+    
+    impl tracing::field::Visit for CorrelationVisitor {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "correlation_id" {
+                self.0 = Some(format!("{:?}", value));
+            }
+        }
+        
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            if field.name() == "correlation_id" {
+                self.0 = Some(value.to_string());
+            }
+        }
+    }
+    
+    impl<S> tracing_subscriber::Layer<S> for CorrelationCapture
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            let mut visitor = CorrelationVisitor(None);
+            attrs.record(&mut visitor);
+            if let (Some(correlation_id), Some(span)) = (visitor.0, ctx.span(id)) {
+                span.extensions_mut().insert(CorrelationId(correlation_id));
+            }
+        }
+        
+        fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            let correlation_id = ctx.event_scope(event).and_then(|scope| {
+                scope.into_iter().find_map(|span| span.extensions().get::<CorrelationId>().map(|id| id.0.clone()))
+            });
+            self.0.lock().unwrap().push(correlation_id);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_log_records_of_a_transaction_share_its_correlation_id() {
+        use tracing_subscriber::layer::SubscriberExt;
+        
+        let coordinator = coordinator_with_target().await;
+        let transaction = timelocked_transaction();
+        let tx_id = transaction.transaction_id.clone();
+        
+        let captured = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(CorrelationCapture(captured.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        
+        coordinator.submit_transaction(transaction).await.unwrap();
+        CrossDomainCoordinator::handle_transaction_confirmed(
+            tx_id.clone(),
+            DomainConfirmation {
+                domain_id: "target".to_string(),
+                status: ConfirmationStatus::Confirmed,
+                data: Vec::new(),
+                signature: Vec::new(),
+                timestamp: chrono::Utc::now(),
+                validator_info: None,
+                vote_sequence_number: 1,
+            },
+            &coordinator.active_transactions,
+            &coordinator.coordination_sessions,
+            &coordinator.metrics,
+            &coordinator.domain_metrics,
+            &coordinator.storage,
+            &coordinator.network_manager,
+            &coordinator.event_tx,
+            &coordinator.reputation,
+        ).await;
+        
+        let captured = captured.lock().unwrap();
+        assert!(captured.len() >= 2);
+        assert!(captured.iter().all(|id| id.as_deref() == Some(tx_id.to_string().as_str())));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_message_correlation_id_is_optional_on_the_wire() {
+        let message = CrossDomainMessage {
+            message_id: "m1".to_string(),
+            message_type: CrossDomainMessageType::TransactionProposal(timelocked_transaction()),
+            source_domain: "global-synchronizer".to_string(),
+            target_domain: "target".to_string(),
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            correlation_id: Some("tx-1".to_string()),
+        };
+        let mut encoded = serde_json::to_value(&message).unwrap();
+        let decoded: CrossDomainMessage = serde_json::from_value(encoded.clone()).unwrap();
+        assert_eq!(decoded.correlation_id.as_deref(), Some("tx-1"));
+        
+        // Messages from senders that predate the field still decode
+        encoded.as_object_mut().unwrap().remove("correlation_id");
+        let decoded: CrossDomainMessage = serde_json::from_value(encoded).unwrap();
+        assert_eq!(decoded.correlation_id, None);
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::time::interval;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use garp_common::{GarpResult, GarpError};
+use garp_common::types::TransactionId;
+
+use crate::config::GlobalSyncConfig;
+use crate::consensus::verify_validator_signature;
+use crate::cross_domain::CrossDomainCoordinator;
+use crate::storage::{DomainId, GlobalStorage};
+
+/// State channel identifier
+pub type ChannelId = String;
+
+/// Domain separator for channel update signatures
+const CHANNEL_UPDATE_DOMAIN: &[u8] = b"garp-channel-update";
+
+/// State channel between two domains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateChannel {
+    /// Channel ID
+    pub channel_id: ChannelId,
+
+    /// Funding domain
+    pub domain_a: DomainId,
+
+    /// Counterparty domain
+    pub domain_b: DomainId,
+
+    /// Collateral locked by `domain_a`
+    pub capacity: u64,
+
+    /// Channel status
+    pub status: ChannelStatus,
+
+    /// Highest-sequence state known on-chain
+    pub latest_state: ChannelUpdate,
+
+    /// Transaction that locked the collateral
+    pub lock_transaction_id: TransactionId,
+
+    /// Opened timestamp
+    pub opened_at: DateTime<Utc>,
+
+    /// End of the challenge window once closing
+    pub challenge_deadline: Option<DateTime<Utc>>,
+}
+
+/// State channel status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ChannelStatus {
+    /// Accepting off-chain updates
+    Open,
+
+    /// Close submitted; waiting for the challenge window to pass
+    Closing,
+
+    /// Final state settled on-chain
+    Settled,
+}
+
+/// Mutually-signed channel state, exchanged off-chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChannelUpdate {
+    /// Channel ID
+    pub channel_id: ChannelId,
+
+    /// Monotonic sequence number; higher states supersede lower ones
+    pub sequence: u64,
+
+    /// Balance owed to `domain_a`
+    pub balance_a: u64,
+
+    /// Balance owed to `domain_b`
+    pub balance_b: u64,
+
+    /// `domain_a` signature over [`ChannelUpdate::signing_message`]
+    pub signature_a: Vec<u8>,
+
+    /// `domain_b` signature over [`ChannelUpdate::signing_message`]
+    pub signature_b: Vec<u8>,
+}
+
+/// On-chain collateral lock opening a channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelLockTransaction {
+    /// Channel ID
+    pub channel_id: ChannelId,
+
+    /// Funding domain
+    pub domain_a: DomainId,
+
+    /// Counterparty domain
+    pub domain_b: DomainId,
+
+    /// Collateral locked
+    pub capacity: u64,
+
+    /// Lock transaction ID
+    pub transaction_id: TransactionId,
+
+    /// Lock timestamp
+    pub locked_at: DateTime<Utc>,
+}
+
+impl ChannelUpdate {
+    /// Canonical bytes both parties sign
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(CHANNEL_UPDATE_DOMAIN.len() + self.channel_id.len() + 24);
+        message.extend_from_slice(CHANNEL_UPDATE_DOMAIN);
+        message.extend_from_slice(self.channel_id.as_bytes());
+        message.extend_from_slice(&self.sequence.to_le_bytes());
+        message.extend_from_slice(&self.balance_a.to_le_bytes());
+        message.extend_from_slice(&self.balance_b.to_le_bytes());
+        message
+    }
+}
+
+/// Tracks state channels and enforces the open/close/challenge protocol
+pub struct StateChannelManager {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Public keys of known domains
+    domain_keys: HashMap<DomainId, Vec<u8>>,
+
+    /// Challenge window after a close is submitted
+    challenge_window: chrono::Duration,
+
+    /// Serializes read-modify-write of channel records
+    lock: Mutex<()>,
+}
+
+impl StateChannelManager {
+    /// Create new state channel manager
+    pub fn new(config: Arc<GlobalSyncConfig>, storage: Arc<GlobalStorage>) -> Self {
+        let domain_keys = config.cross_domain.known_domains
+            .iter()
+            .map(|d| (d.domain_id.clone(), d.public_key.clone()))
+            .collect();
+
+        Self {
+            storage,
+            domain_keys,
+            challenge_window: chrono::Duration::seconds(config.cross_domain.channel_challenge_window_secs as i64),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Open a channel funded by `domain_a` with `capacity` collateral.
+    ///
+    /// The initial state (sequence 0) assigns the full capacity to `domain_a`.
+    pub async fn open_channel(&self, domain_a: &DomainId, domain_b: &DomainId, capacity: u64) -> GarpResult<ChannelLockTransaction> {
+        if domain_a == domain_b {
+            return Err(GarpError::ValidationError("Channel endpoints must be different domains".to_string()));
+        }
+        if capacity == 0 {
+            return Err(GarpError::ValidationError("Channel capacity must be positive".to_string()));
+        }
+        for domain_id in [domain_a, domain_b] {
+            if !self.domain_keys.contains_key(domain_id) {
+                return Err(GarpError::ValidationError(format!("Unknown domain: {}", domain_id)));
+            }
+        }
+
+        let channel_id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let lock = ChannelLockTransaction {
+            channel_id: channel_id.clone(),
+            domain_a: domain_a.clone(),
+            domain_b: domain_b.clone(),
+            capacity,
+            transaction_id: TransactionId::new(),
+            locked_at: now,
+        };
+
+        let channel = StateChannel {
+            channel_id: channel_id.clone(),
+            domain_a: domain_a.clone(),
+            domain_b: domain_b.clone(),
+            capacity,
+            status: ChannelStatus::Open,
+            latest_state: ChannelUpdate {
+                channel_id: channel_id.clone(),
+                sequence: 0,
+                balance_a: capacity,
+                balance_b: 0,
+                signature_a: Vec::new(),
+                signature_b: Vec::new(),
+            },
+            lock_transaction_id: lock.transaction_id.clone(),
+            opened_at: now,
+            challenge_deadline: None,
+        };
+
+        self.storage.store_state_channel(channel).await?;
+        info!("Opened state channel {} between {} and {} (capacity {})", channel_id, domain_a, domain_b, capacity);
+        Ok(lock)
+    }
+
+    /// Drop a channel whose lock transaction could not be submitted
+    pub async fn discard_channel(&self, channel_id: &ChannelId) -> GarpResult<()> {
+        let _guard = self.lock.lock().await;
+        self.storage.remove_state_channel(channel_id).await?;
+        Ok(())
+    }
+
+    /// Record a newer off-chain update reported by the parties
+    pub async fn apply_update(&self, update: ChannelUpdate) -> GarpResult<StateChannel> {
+        let _guard = self.lock.lock().await;
+        let mut channel = self.load(&update.channel_id).await?;
+
+        if channel.status != ChannelStatus::Open {
+            return Err(GarpError::ValidationError(format!("Channel {} is not open", channel.channel_id)));
+        }
+        self.verify_update(&channel, &update)?;
+        if update.sequence <= channel.latest_state.sequence {
+            return Err(GarpError::ValidationError(format!(
+                "Stale channel update: sequence {} <= {}", update.sequence, channel.latest_state.sequence
+            )));
+        }
+
+        debug!("Channel {} advanced to sequence {}", channel.channel_id, update.sequence);
+        channel.latest_state = update;
+        self.storage.store_state_channel(channel.clone()).await?;
+        Ok(channel)
+    }
+
+    /// Submit a final state and start the challenge window
+    pub async fn close_channel(&self, channel_id: &ChannelId, final_state: ChannelUpdate, now: DateTime<Utc>) -> GarpResult<StateChannel> {
+        let _guard = self.lock.lock().await;
+        let mut channel = self.load(channel_id).await?;
+
+        if channel.status != ChannelStatus::Open {
+            return Err(GarpError::ValidationError(format!("Channel {} is not open", channel_id)));
+        }
+        self.verify_update(&channel, &final_state)?;
+        if final_state.sequence < channel.latest_state.sequence {
+            return Err(GarpError::ValidationError(format!(
+                "Final state sequence {} is older than recorded sequence {}",
+                final_state.sequence, channel.latest_state.sequence
+            )));
+        }
+
+        let deadline = now + self.challenge_window;
+        info!("Closing state channel {} at sequence {}; challenge window ends {}", channel_id, final_state.sequence, deadline);
+        channel.latest_state = final_state;
+        channel.status = ChannelStatus::Closing;
+        channel.challenge_deadline = Some(deadline);
+        self.storage.store_state_channel(channel.clone()).await?;
+        Ok(channel)
+    }
+
+    /// Replace a closing channel's state with a higher-sequence one during the challenge window
+    pub async fn challenge(&self, channel_id: &ChannelId, state: ChannelUpdate, now: DateTime<Utc>) -> GarpResult<StateChannel> {
+        let _guard = self.lock.lock().await;
+        let mut channel = self.load(channel_id).await?;
+
+        let deadline = match (&channel.status, channel.challenge_deadline) {
+            (ChannelStatus::Closing, Some(deadline)) => deadline,
+            _ => return Err(GarpError::ValidationError(format!("Channel {} is not closing", channel_id))),
+        };
+        if now > deadline {
+            return Err(GarpError::ValidationError(format!("Challenge window for channel {} has ended", channel_id)));
+        }
+        self.verify_update(&channel, &state)?;
+        if state.sequence <= channel.latest_state.sequence {
+            return Err(GarpError::ValidationError(format!(
+                "Challenge sequence {} does not exceed submitted sequence {}",
+                state.sequence, channel.latest_state.sequence
+            )));
+        }
+
+        info!("Channel {} challenged: sequence {} replaces {}", channel_id, state.sequence, channel.latest_state.sequence);
+        channel.latest_state = state;
+        self.storage.store_state_channel(channel.clone()).await?;
+        Ok(channel)
+    }
+
+    /// Settle every closing channel whose challenge window ended before `now`
+    pub async fn settle_expired(&self, now: DateTime<Utc>) -> GarpResult<Vec<StateChannel>> {
+        let _guard = self.lock.lock().await;
+        let mut settled = Vec::new();
+
+        for mut channel in self.storage.list_state_channels().await? {
+            let expired = channel.status == ChannelStatus::Closing
+                && channel.challenge_deadline.map_or(false, |deadline| deadline < now);
+            if !expired {
+                continue;
+            }
+            channel.status = ChannelStatus::Settled;
+            self.storage.store_state_channel(channel.clone()).await?;
+            settled.push(channel);
+        }
+
+        Ok(settled)
+    }
+
+    /// Get a channel
+    pub async fn get_channel(&self, channel_id: &ChannelId) -> GarpResult<Option<StateChannel>> {
+        self.storage.get_state_channel(channel_id).await
+    }
+
+    /// List channels that are open or closing
+    pub async fn list_active_channels(&self) -> GarpResult<Vec<StateChannel>> {
+        let channels = self.storage.list_state_channels().await?;
+        Ok(channels.into_iter().filter(|c| c.status != ChannelStatus::Settled).collect())
+    }
+
+    async fn load(&self, channel_id: &ChannelId) -> GarpResult<StateChannel> {
+        self.storage.get_state_channel(channel_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("State channel not found: {}", channel_id)))
+    }
+
+    /// Check an update belongs to the channel, conserves capacity and is signed by both parties
+    fn verify_update(&self, channel: &StateChannel, update: &ChannelUpdate) -> GarpResult<()> {
+        if update.channel_id != channel.channel_id {
+            return Err(GarpError::ValidationError("Update is for a different channel".to_string()));
+        }
+        if update.balance_a.checked_add(update.balance_b) != Some(channel.capacity) {
+            return Err(GarpError::ValidationError(format!(
+                "Balances {} + {} do not match capacity {}", update.balance_a, update.balance_b, channel.capacity
+            )));
+        }
+
+        let message = update.signing_message();
+        for (domain_id, signature) in [(&channel.domain_a, &update.signature_a), (&channel.domain_b, &update.signature_b)] {
+            let public_key = self.domain_keys.get(domain_id)
+                .ok_or_else(|| GarpError::ValidationError(format!("Unknown domain: {}", domain_id)))?;
+            if !verify_validator_signature(&hex::encode(public_key), &message, signature) {
+                return Err(GarpError::ValidationError(format!("Invalid channel signature from {}", domain_id)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Periodically settles channels whose challenge window has ended
+pub struct ChannelSettlementMonitor {
+    /// Cross-domain coordinator owning the channel manager
+    coordinator: Arc<CrossDomainCoordinator>,
+
+    /// Interval between settlement checks
+    check_interval: Duration,
+}
+
+impl ChannelSettlementMonitor {
+    /// Create new channel settlement monitor
+    pub fn new(coordinator: Arc<CrossDomainCoordinator>, check_interval: Duration) -> Self {
+        Self {
+            coordinator,
+            check_interval,
+        }
+    }
+
+    /// Spawn the periodic settlement loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(self.check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match self.coordinator.settle_expired_channels(Utc::now()).await {
+                    Ok(settled) if !settled.is_empty() => {
+                        info!("Settled {} state channels", settled.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!("Channel settlement pass failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DomainInfo, TrustLevel};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn domain(domain_id: &str, key: &SigningKey) -> DomainInfo {
+        DomainInfo {
+            domain_id: domain_id.to_string(),
+            endpoints: Vec::new(),
+            public_key: key.verifying_key().to_bytes().to_vec(),
+            capabilities: Vec::new(),
+            trust_level: TrustLevel::Trusted,
+        }
+    }
+
+    fn signed(channel_id: &str, sequence: u64, balance_a: u64, balance_b: u64) -> ChannelUpdate {
+        let mut update = ChannelUpdate {
+            channel_id: channel_id.to_string(),
+            sequence,
+            balance_a,
+            balance_b,
+            signature_a: Vec::new(),
+            signature_b: Vec::new(),
+        };
+        let message = update.signing_message();
+        update.signature_a = key(1).sign(&message).to_bytes().to_vec();
+        update.signature_b = key(2).sign(&message).to_bytes().to_vec();
+        update
+    }
+
+    async fn manager() -> StateChannelManager {
+        let mut config = GlobalSyncConfig::default();
+        config.cross_domain.known_domains = vec![domain("a", &key(1)), domain("b", &key(2))];
+        config.cross_domain.channel_challenge_window_secs = 60;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        StateChannelManager::new(config, storage)
+    }
+
+    #[tokio::test]
+    async fn test_open_update_and_settle() {
+        let manager = manager().await;
+        let lock = manager.open_channel(&"a".to_string(), &"b".to_string(), 100).await.unwrap();
+        let id = lock.channel_id.clone();
+
+        manager.apply_update(signed(&id, 1, 90, 10)).await.unwrap();
+        assert!(manager.apply_update(signed(&id, 1, 80, 20)).await.is_err());
+        assert!(manager.apply_update(signed(&id, 2, 80, 30)).await.is_err());
+
+        let now = Utc::now();
+        let channel = manager.close_channel(&id, signed(&id, 2, 70, 30), now).await.unwrap();
+        assert_eq!(channel.status, ChannelStatus::Closing);
+
+        assert!(manager.settle_expired(now + chrono::Duration::seconds(30)).await.unwrap().is_empty());
+        let settled = manager.settle_expired(now + chrono::Duration::seconds(61)).await.unwrap();
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].latest_state.balance_b, 30);
+        assert!(manager.list_active_channels().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stale_close_is_challenged() {
+        let manager = manager().await;
+        let id = manager.open_channel(&"a".to_string(), &"b".to_string(), 100).await.unwrap().channel_id;
+
+        // `a` closes with an old state that favours it
+        let now = Utc::now();
+        manager.close_channel(&id, signed(&id, 3, 90, 10), now).await.unwrap();
+
+        // `b` answers with the newer state; an equal sequence does not count
+        assert!(manager.challenge(&id, signed(&id, 3, 50, 50), now).await.is_err());
+        let channel = manager.challenge(&id, signed(&id, 7, 40, 60), now + chrono::Duration::seconds(10)).await.unwrap();
+        assert_eq!(channel.latest_state.sequence, 7);
+
+        // Too late once the window has passed
+        assert!(manager.challenge(&id, signed(&id, 8, 30, 70), now + chrono::Duration::seconds(61)).await.is_err());
+
+        let settled = manager.settle_expired(now + chrono::Duration::seconds(61)).await.unwrap();
+        assert_eq!(settled[0].latest_state.balance_b, 60);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_signatures_and_unknown_domains() {
+        let manager = manager().await;
+        assert!(manager.open_channel(&"a".to_string(), &"c".to_string(), 100).await.is_err());
+        assert!(manager.open_channel(&"a".to_string(), &"a".to_string(), 100).await.is_err());
+
+        let id = manager.open_channel(&"a".to_string(), &"b".to_string(), 100).await.unwrap().channel_id;
+        let mut forged = signed(&id, 1, 0, 100);
+        forged.signature_b = key(3).sign(&forged.signing_message()).to_bytes().to_vec();
+        assert!(manager.apply_update(forged.clone()).await.is_err());
+        assert!(manager.close_channel(&id, forged, Utc::now()).await.is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use garp_common::{GarpResult, GarpError};
+
+use crate::cross_domain::{CrossDomainTransaction, CrossDomainTransactionType};
+use crate::storage::DomainId;
+
+/// Action a delegate may take on its delegator's behalf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegationPermission {
+    /// Submit cross-domain transactions
+    SubmitTransactions,
+
+    /// Submit governance proposal transactions; needs `SubmitTransactions` too
+    SubmitGovernanceProposals,
+}
+
+/// Authority granted by governance to `delegate` to act for `delegator`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainDelegation {
+    /// Domain on whose behalf the delegate acts
+    pub delegator: DomainId,
+
+    /// Domain acting on the delegator's behalf
+    pub delegate: DomainId,
+
+    /// Actions granted
+    pub permissions: Vec<DelegationPermission>,
+
+    /// End of the delegation; `None` lasts until revoked
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl DomainDelegation {
+    /// Whether the delegation is still in force at `now`
+    pub fn is_current(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+
+    /// Whether the delegation grants `permission` at `now`
+    pub fn grants(&self, permission: DelegationPermission, now: DateTime<Utc>) -> bool {
+        self.is_current(now) && self.permissions.contains(&permission)
+    }
+
+    /// Reject delegations that could never be used
+    pub fn validate(&self) -> GarpResult<()> {
+        if self.delegator.is_empty() || self.delegate.is_empty() {
+            return Err(GarpError::ValidationError("Delegator and delegate cannot be empty".to_string()));
+        }
+        if self.delegator == self.delegate {
+            return Err(GarpError::ValidationError(format!("Domain {} cannot delegate to itself", self.delegator)));
+        }
+        if self.permissions.is_empty() {
+            return Err(GarpError::ValidationError("Delegation must grant at least one permission".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Permissions `transaction`'s source domain needs from its delegator
+pub fn required_permissions(transaction: &CrossDomainTransaction) -> Vec<DelegationPermission> {
+    match transaction.transaction_type {
+        CrossDomainTransactionType::GovernanceProposal { .. } => {
+            vec![DelegationPermission::SubmitTransactions, DelegationPermission::SubmitGovernanceProposals]
+        }
+        _ => vec![DelegationPermission::SubmitTransactions],
+    }
+}
+
+/// Check that `delegation`, the record from `transaction.delegated_by` to
+/// its source domain, authorizes the submission at `now`
+pub fn authorize(
+    transaction: &CrossDomainTransaction,
+    delegation: Option<&DomainDelegation>,
+    now: DateTime<Utc>,
+) -> GarpResult<()> {
+    let delegator = match &transaction.delegated_by {
+        Some(delegator) => delegator,
+        None => return Ok(()),
+    };
+    if delegator == &transaction.source_domain {
+        return Err(GarpError::ValidationError(format!("Domain {} cannot act as its own delegate", delegator)));
+    }
+
+    let delegation = delegation
+        .filter(|delegation| delegation.is_current(now))
+        .ok_or_else(|| GarpError::ValidationError(format!(
+            "Domain {} holds no current delegation from {}", transaction.source_domain, delegator
+        )))?;
+    if let Some(missing) = required_permissions(transaction).into_iter().find(|p| !delegation.grants(*p, now)) {
+        return Err(GarpError::ValidationError(format!(
+            "Delegation from {} to {} does not grant {:?}", delegator, transaction.source_domain, missing
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use garp_common::types::TransactionId;
+    use crate::cross_domain::TransactionStatus;
+
+    fn delegation(permissions: Vec<DelegationPermission>, expires_at: Option<DateTime<Utc>>) -> DomainDelegation {
+        DomainDelegation { delegator: "treasury".to_string(), delegate: "ops".to_string(), permissions, expires_at }
+    }
+
+    fn transaction(transaction_type: CrossDomainTransactionType, delegated_by: Option<&str>) -> CrossDomainTransaction {
+        let now = Utc::now();
+        CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "ops".to_string(),
+            target_domains: vec!["domain1".to_string()],
+            transaction_type,
+            data: vec![1],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: delegated_by.map(str::to_string),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn transfer(delegated_by: Option<&str>) -> CrossDomainTransaction {
+        transaction(
+            CrossDomainTransactionType::AssetTransfer {
+                asset_id: "asset".to_string(),
+                amount: 10,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            delegated_by,
+        )
+    }
+
+    #[test]
+    fn test_delegated_submissions_need_a_current_grant() {
+        let now = Utc::now();
+        let submit = delegation(vec![DelegationPermission::SubmitTransactions], Some(now + chrono::Duration::hours(1)));
+
+        // Undelegated transactions need no record
+        assert!(authorize(&transfer(None), None, now).is_ok());
+
+        assert!(authorize(&transfer(Some("treasury")), Some(&submit), now).is_ok());
+        assert!(authorize(&transfer(Some("treasury")), None, now).is_err());
+        assert!(authorize(&transfer(Some("treasury")), Some(&submit), now + chrono::Duration::hours(2)).is_err());
+        assert!(authorize(&transfer(Some("ops")), Some(&submit), now).is_err());
+
+        // Governance proposals need the extra permission
+        let proposal = transaction(
+            CrossDomainTransactionType::GovernanceProposal {
+                proposal_id: "p1".to_string(),
+                proposal_type: "custom".to_string(),
+                proposal_data: Vec::new(),
+            },
+            Some("treasury"),
+        );
+        assert!(authorize(&proposal, Some(&submit), now).is_err());
+        let both = delegation(
+            vec![DelegationPermission::SubmitTransactions, DelegationPermission::SubmitGovernanceProposals],
+            None,
+        );
+        assert!(authorize(&proposal, Some(&both), now).is_ok());
+    }
+
+    #[test]
+    fn test_unusable_delegations_are_rejected() {
+        assert!(delegation(vec![DelegationPermission::SubmitTransactions], None).validate().is_ok());
+        assert!(delegation(Vec::new(), None).validate().is_err());
+
+        let mut to_self = delegation(vec![DelegationPermission::SubmitTransactions], None);
+        to_self.delegate = to_self.delegator.clone();
+        assert!(to_self.validate().is_err());
+    }
+}
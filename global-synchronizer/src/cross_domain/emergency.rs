@@ -0,0 +1,320 @@
+use std::collections::{BTreeSet, HashMap};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use garp_common::{GarpResult, GarpError};
+use garp_common::types::TransactionId;
+
+use crate::config::SecurityConfig;
+use crate::cross_domain::{CrossDomainTransaction, CrossDomainTransactionType, EmergencySeverity};
+use crate::storage::DomainId;
+
+/// Prefix of every signed emergency action digest
+const EMERGENCY_DOMAIN_TAG: &[u8] = b"garp-emergency-action-v1";
+
+/// Signature by a designated emergency key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmergencySignature {
+    /// ID of the signing key in `security.emergency_keys`
+    pub key_id: String,
+
+    /// Ed25519 signature over [`signing_digest`]
+    pub signature: Vec<u8>,
+}
+
+/// Stop coordinating transactions for a domain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HaltDomain {
+    pub domain_id: DomainId,
+}
+
+/// Resume a halted domain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResumeDomain {
+    pub domain_id: DomainId,
+}
+
+/// Stop transfers of an asset, on every domain unless one is given
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FreezeAsset {
+    pub asset_id: String,
+    #[serde(default)]
+    pub domain_id: Option<DomainId>,
+}
+
+/// Alert every domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BroadcastAlert {
+    pub message: String,
+    pub severity: EmergencySeverity,
+}
+
+/// Emergency action with its typed payload. Only these `action_type`s are
+/// accepted; `action_data` must be the JSON payload of the type.
+#[derive(Debug, Clone)]
+pub enum EmergencyActionKind {
+    HaltDomain(HaltDomain),
+    ResumeDomain(ResumeDomain),
+    FreezeAsset(FreezeAsset),
+    BroadcastAlert(BroadcastAlert),
+}
+
+fn payload<T: DeserializeOwned>(action_type: &str, action_data: &[u8]) -> GarpResult<T> {
+    serde_json::from_slice(action_data)
+        .map_err(|e| GarpError::ValidationError(format!("Invalid {} payload: {}", action_type, e)))
+}
+
+impl EmergencyActionKind {
+    /// Decode an emergency action from its type and payload
+    pub fn parse(action_type: &str, action_data: &[u8]) -> GarpResult<Self> {
+        match action_type {
+            "halt_domain" => Ok(Self::HaltDomain(payload(action_type, action_data)?)),
+            "resume_domain" => Ok(Self::ResumeDomain(payload(action_type, action_data)?)),
+            "freeze_asset" => Ok(Self::FreezeAsset(payload(action_type, action_data)?)),
+            "broadcast_alert" => Ok(Self::BroadcastAlert(payload(action_type, action_data)?)),
+            other => Err(GarpError::ValidationError(format!("Emergency action type not allowed: {}", other))),
+        }
+    }
+}
+
+/// Length-prefix `bytes` so adjacent fields cannot run together
+fn put(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_be_bytes());
+    hasher.update(bytes);
+}
+
+/// SHA-256 digest emergency keys sign: the tag, the 16 transaction UUID
+/// bytes, then the length-prefixed action type, payload and justification
+pub fn signing_digest(transaction_id: &TransactionId, action_type: &str, action_data: &[u8], justification: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(EMERGENCY_DOMAIN_TAG);
+    hasher.update(transaction_id.0.as_bytes());
+    put(&mut hasher, action_type.as_bytes());
+    put(&mut hasher, action_data);
+    put(&mut hasher, justification.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Emergency action that passed authorization
+#[derive(Debug, Clone)]
+pub struct AuthorizedEmergency {
+    /// Decoded action
+    pub action: EmergencyActionKind,
+
+    /// Designated keys whose signatures verified
+    pub key_ids: Vec<String>,
+}
+
+/// Why an emergency action was refused, with the keys that did verify
+#[derive(Debug, Clone)]
+pub struct EmergencyRejection {
+    /// Reason
+    pub reason: String,
+
+    /// Designated keys whose signatures verified
+    pub key_ids: Vec<String>,
+}
+
+/// Entry of the append-only emergency action audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyAuditRecord {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+
+    /// Emergency transaction
+    pub transaction_id: TransactionId,
+
+    /// Submitting domain
+    pub source_domain: DomainId,
+
+    /// Requested action type
+    pub action_type: String,
+
+    /// Whether the action was accepted
+    pub accepted: bool,
+
+    /// Why it was rejected
+    pub reason: Option<String>,
+
+    /// Designated keys whose signatures verified
+    pub key_ids: Vec<String>,
+
+    /// Recorded timestamp
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Checks emergency actions against the designated keys in
+/// `security.emergency_keys`
+pub struct EmergencyAuthorizer {
+    /// Designated keys by ID
+    keys: HashMap<String, VerifyingKey>,
+
+    /// Distinct designated signatures required
+    quorum: usize,
+}
+
+impl EmergencyAuthorizer {
+    /// Load the designated keys; a malformed key is a configuration error
+    pub fn new(config: &SecurityConfig) -> GarpResult<Self> {
+        let mut keys = HashMap::new();
+        for key in &config.emergency_keys {
+            let bytes: [u8; 32] = hex::decode(&key.public_key).ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| GarpError::ConfigError(format!("Emergency key {} is not a 32-byte hex key", key.key_id)))?;
+            let verifying_key = VerifyingKey::from_bytes(&bytes)
+                .map_err(|e| GarpError::ConfigError(format!("Emergency key {} is invalid: {}", key.key_id, e)))?;
+            keys.insert(key.key_id.clone(), verifying_key);
+        }
+        Ok(Self {
+            keys,
+            quorum: config.emergency_quorum,
+        })
+    }
+
+    /// Decode an emergency action transaction and check it carries valid
+    /// signatures from a quorum of designated keys. A signature from an
+    /// unknown key or one that does not verify rejects the action outright.
+    pub fn authorize(&self, transaction: &CrossDomainTransaction) -> Result<AuthorizedEmergency, EmergencyRejection> {
+        let CrossDomainTransactionType::EmergencyAction { action_type, action_data, justification, signatures } = &transaction.transaction_type else {
+            return Err(EmergencyRejection { reason: "Not an emergency action".to_string(), key_ids: Vec::new() });
+        };
+        let digest = signing_digest(&transaction.transaction_id, action_type, action_data, justification);
+
+        let mut verified = BTreeSet::new();
+        for signature in signatures {
+            let Some(key) = self.keys.get(&signature.key_id) else {
+                return Err(EmergencyRejection {
+                    reason: format!("Signature from non-designated key {}", signature.key_id),
+                    key_ids: verified.into_iter().collect(),
+                });
+            };
+            let valid = Signature::from_slice(&signature.signature)
+                .map_or(false, |sig| key.verify(&digest, &sig).is_ok());
+            if !valid {
+                return Err(EmergencyRejection {
+                    reason: format!("Invalid signature from key {}", signature.key_id),
+                    key_ids: verified.into_iter().collect(),
+                });
+            }
+            verified.insert(signature.key_id.clone());
+        }
+
+        let key_ids: Vec<String> = verified.into_iter().collect();
+        if self.quorum == 0 || key_ids.len() < self.quorum {
+            return Err(EmergencyRejection {
+                reason: format!("{} of {} required emergency signatures", key_ids.len(), self.quorum),
+                key_ids,
+            });
+        }
+        if justification.trim().is_empty() {
+            return Err(EmergencyRejection { reason: "Missing justification".to_string(), key_ids });
+        }
+        match EmergencyActionKind::parse(action_type, action_data) {
+            Ok(action) => Ok(AuthorizedEmergency { action, key_ids }),
+            Err(e) => Err(EmergencyRejection { reason: e.to_string(), key_ids }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use crate::config::{EmergencyKey, GlobalSyncConfig};
+    use crate::cross_domain::TransactionStatus;
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn authorizer(quorum: usize) -> EmergencyAuthorizer {
+        let mut config = GlobalSyncConfig::default().security;
+        config.emergency_keys = (1..=3)
+            .map(|seed| EmergencyKey {
+                key_id: format!("key-{}", seed),
+                public_key: hex::encode(signing_key(seed).verifying_key().to_bytes()),
+            })
+            .collect();
+        config.emergency_quorum = quorum;
+        EmergencyAuthorizer::new(&config).unwrap()
+    }
+
+    fn halt(signers: &[(&str, u8)]) -> CrossDomainTransaction {
+        let transaction_id = TransactionId::new();
+        let action_data = br#"{"domain_id": "domain1"}"#.to_vec();
+        let digest = signing_digest(&transaction_id, "halt_domain", &action_data, "validator keys leaked");
+        let now = Utc::now();
+        CrossDomainTransaction {
+            transaction_id,
+            source_domain: "domain2".to_string(),
+            target_domains: vec!["domain1".to_string()],
+            transaction_type: CrossDomainTransactionType::EmergencyAction {
+                action_type: "halt_domain".to_string(),
+                action_data,
+                justification: "validator keys leaked".to_string(),
+                signatures: signers.iter()
+                    .map(|(key_id, seed)| EmergencySignature {
+                        key_id: key_id.to_string(),
+                        signature: signing_key(*seed).sign(&digest).to_bytes().to_vec(),
+                    })
+                    .collect(),
+            },
+            data: vec![1],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::minutes(5),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_quorum_of_designated_keys_authorizes() {
+        let authorized = authorizer(2).authorize(&halt(&[("key-1", 1), ("key-3", 3)])).unwrap();
+        assert_eq!(authorized.key_ids, vec!["key-1".to_string(), "key-3".to_string()]);
+        assert!(matches!(authorized.action, EmergencyActionKind::HaltDomain(HaltDomain { ref domain_id }) if domain_id == "domain1"));
+    }
+
+    #[test]
+    fn test_insufficient_signatures_are_rejected() {
+        let authorizer = authorizer(2);
+        let rejection = authorizer.authorize(&halt(&[("key-1", 1)])).unwrap_err();
+        assert_eq!(rejection.key_ids, vec!["key-1".to_string()]);
+
+        // The same key signing twice counts once
+        assert!(authorizer.authorize(&halt(&[("key-1", 1), ("key-1", 1)])).is_err());
+        assert!(authorizer.authorize(&halt(&[])).is_err());
+    }
+
+    #[test]
+    fn test_non_designated_key_is_rejected() {
+        let authorizer = authorizer(2);
+        let rejection = authorizer.authorize(&halt(&[("key-1", 1), ("key-2", 2), ("key-9", 9)])).unwrap_err();
+        assert!(rejection.reason.contains("key-9"));
+
+        // A designated ID signed with another key does not verify
+        let rejection = authorizer.authorize(&halt(&[("key-1", 1), ("key-2", 9)])).unwrap_err();
+        assert!(rejection.reason.contains("Invalid signature"));
+    }
+
+    #[test]
+    fn test_payloads_are_typed() {
+        assert!(EmergencyActionKind::parse("freeze_asset", br#"{"asset_id": "gold"}"#).is_ok());
+        assert!(EmergencyActionKind::parse("freeze_asset", br#"{"asset": "gold"}"#).is_err());
+        assert!(EmergencyActionKind::parse("broadcast_alert", br#"{"message": "upgrade", "severity": "High"}"#).is_ok());
+        assert!(EmergencyActionKind::parse("drain_treasury", b"{}").is_err());
+    }
+}
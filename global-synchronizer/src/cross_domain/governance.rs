@@ -0,0 +1,677 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::time::interval;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use garp_common::{GarpResult, GarpError};
+use garp_common::types::ParticipantId;
+
+use crate::config::GlobalSyncConfig;
+use crate::cross_domain::{CrossDomainCoordinator, DomainCapabilities};
+use crate::cross_domain::delegation::{DelegationPermission, DomainDelegation};
+use crate::storage::{DomainId, GlobalStorage};
+
+/// Governance proposal identifier
+pub type ProposalId = String;
+
+/// Basis points in one whole
+const MAX_BASIS_POINTS: u32 = 10_000;
+
+/// Governance parameter overriding `cross_domain.coordination_timeout`
+pub const COORDINATION_TIMEOUT_PARAMETER: &str = "coordination_timeout";
+
+/// Change applied when a proposal is approved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GovernanceAction {
+    /// Set a governance-controlled configuration parameter
+    UpdateConfig {
+        key: String,
+        value: String,
+    },
+
+    /// Admit a new sync domain
+    AddDomain {
+        domain_id: DomainId,
+        endpoint: String,
+        capabilities: DomainCapabilities,
+    },
+
+    /// Cut a validator's voting power by `penalty_bps` basis points
+    SlashValidator {
+        validator_id: ParticipantId,
+        penalty_bps: u32,
+    },
+
+    /// Add a validator to the consensus set
+    AddValidator {
+        validator_id: ParticipantId,
+        public_key: String,
+        voting_power: u64,
+    },
+
+    /// Remove a validator from the consensus set
+    RemoveValidator {
+        validator_id: ParticipantId,
+    },
+
+    /// Let `delegate` act for `delegator`, replacing any earlier delegation
+    /// between them
+    IssueDelegation {
+        delegator: DomainId,
+        delegate: DomainId,
+        permissions: Vec<DelegationPermission>,
+        #[serde(default)]
+        expires_at: Option<DateTime<Utc>>,
+    },
+
+    /// Withdraw the delegation from `delegator` to `delegate`
+    RevokeDelegation {
+        delegator: DomainId,
+        delegate: DomainId,
+    },
+
+    /// Proposal of a type the synchronizer does not apply; recorded for
+    /// off-chain action
+    Custom {
+        proposal_type: String,
+        proposal_data: Vec<u8>,
+    },
+}
+
+impl GovernanceAction {
+    /// Action of a `GovernanceProposal` transaction. Known types carry their
+    /// fields as a JSON object in `proposal_data`; any other type is kept
+    /// as a custom proposal.
+    pub fn from_proposal(proposal_type: &str, proposal_data: &[u8]) -> GarpResult<Self> {
+        const KNOWN_TYPES: [&str; 7] = [
+            "update_config",
+            "add_domain",
+            "slash_validator",
+            "add_validator",
+            "remove_validator",
+            "issue_delegation",
+            "revoke_delegation",
+        ];
+        if !KNOWN_TYPES.contains(&proposal_type) {
+            return Ok(GovernanceAction::Custom {
+                proposal_type: proposal_type.to_string(),
+                proposal_data: proposal_data.to_vec(),
+            });
+        }
+
+        let mut fields: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(proposal_data)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid {} proposal data: {}", proposal_type, e)))?;
+        fields.insert("type".to_string(), serde_json::Value::String(proposal_type.to_string()));
+        serde_json::from_value(serde_json::Value::Object(fields))
+            .map_err(|e| GarpError::ValidationError(format!("Invalid {} proposal data: {}", proposal_type, e)))
+    }
+
+    /// Delegation an `IssueDelegation` action grants
+    pub fn delegation(&self) -> Option<DomainDelegation> {
+        match self {
+            GovernanceAction::IssueDelegation { delegator, delegate, permissions, expires_at } => Some(DomainDelegation {
+                delegator: delegator.clone(),
+                delegate: delegate.clone(),
+                permissions: permissions.clone(),
+                expires_at: *expires_at,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether the synchronizer applies the action itself once approved
+    pub fn is_automatic(&self) -> bool {
+        !matches!(self, GovernanceAction::Custom { .. })
+    }
+}
+
+/// Governance proposal status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ProposalStatus {
+    /// Accepting domain votes
+    Voting,
+
+    /// Passed the approval threshold; waiting to be executed
+    Approved,
+
+    /// Did not reach the approval threshold
+    Rejected,
+
+    /// Action applied
+    Executed,
+
+    /// Approved, but the action could not be applied
+    Failed,
+
+    /// Approved custom proposal, left for off-chain action
+    Recorded,
+}
+
+/// Vote cast by a domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainVote {
+    /// Voting domain
+    pub domain_id: DomainId,
+
+    /// Whether the domain approves the proposal
+    pub approve: bool,
+
+    /// Vote weight (the domain's throughput when the proposal opened)
+    pub weight: u64,
+
+    /// Cast timestamp
+    pub cast_at: DateTime<Utc>,
+}
+
+/// Final vote count of a proposal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProposalTally {
+    /// Weight of approving votes
+    pub approve_weight: u64,
+
+    /// Weight of rejecting votes
+    pub reject_weight: u64,
+
+    /// Combined weight of every eligible domain
+    pub total_weight: u64,
+
+    /// Whether enough weight voted for the result to count
+    pub quorum_met: bool,
+}
+
+/// Governance proposal voted on by every active domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceProposal {
+    /// Proposal ID
+    pub proposal_id: ProposalId,
+
+    /// Proposing domain
+    pub proposer: DomainId,
+
+    /// Action applied on approval
+    pub action: GovernanceAction,
+
+    /// Human-readable rationale
+    pub description: String,
+
+    /// Proposal status
+    pub status: ProposalStatus,
+
+    /// Vote weight of each domain eligible to vote, fixed when the proposal opened
+    pub eligible_weights: HashMap<DomainId, u64>,
+
+    /// Votes cast so far
+    pub votes: HashMap<DomainId, DomainVote>,
+
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// End of the voting period
+    pub voting_ends_at: DateTime<Utc>,
+
+    /// When the action was applied (or failed to apply)
+    pub executed_at: Option<DateTime<Utc>>,
+
+    /// Why execution failed
+    pub failure_reason: Option<String>,
+
+    /// Vote count at the end of the voting period
+    #[serde(default)]
+    pub tally: Option<ProposalTally>,
+}
+
+impl GovernanceProposal {
+    /// Combined weight of every eligible domain
+    pub fn total_weight(&self) -> u64 {
+        self.eligible_weights.values().sum()
+    }
+
+    /// Weight of approving votes
+    pub fn approve_weight(&self) -> u64 {
+        self.votes.values().filter(|v| v.approve).map(|v| v.weight).sum()
+    }
+
+    /// Weight of rejecting votes
+    pub fn reject_weight(&self) -> u64 {
+        self.votes.values().filter(|v| !v.approve).map(|v| v.weight).sum()
+    }
+}
+
+/// Runs the proposal lifecycle: open, collect weighted votes, tally
+pub struct GovernanceEngine {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// How long proposals accept votes
+    voting_period: chrono::Duration,
+
+    /// Share of total weight (in thousandths) approving votes must exceed
+    approval_threshold_thousandths: u64,
+
+    /// Share of total weight (in thousandths) that must vote
+    quorum_thousandths: u64,
+
+    /// Serializes read-modify-write of proposal records
+    lock: Mutex<()>,
+}
+
+impl GovernanceEngine {
+    /// Create new governance engine
+    pub fn new(config: Arc<GlobalSyncConfig>, storage: Arc<GlobalStorage>) -> Self {
+        Self {
+            storage,
+            voting_period: chrono::Duration::seconds(config.cross_domain.governance_voting_period_secs as i64),
+            approval_threshold_thousandths: config.cross_domain.governance_approval_threshold_thousandths,
+            quorum_thousandths: config.cross_domain.governance_quorum_thousandths,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Open a proposal; `eligible_weights` maps each voting domain to its weight
+    pub async fn create_proposal(
+        &self,
+        proposer: &DomainId,
+        action: GovernanceAction,
+        description: String,
+        eligible_weights: HashMap<DomainId, u64>,
+        now: DateTime<Utc>,
+    ) -> GarpResult<GovernanceProposal> {
+        self.open_proposal(Uuid::new_v4().to_string(), proposer, action, description, eligible_weights, now).await
+    }
+
+    /// Open a proposal under a caller-chosen ID, as submitted in a
+    /// `GovernanceProposal` transaction
+    pub async fn open_proposal(
+        &self,
+        proposal_id: ProposalId,
+        proposer: &DomainId,
+        action: GovernanceAction,
+        description: String,
+        eligible_weights: HashMap<DomainId, u64>,
+        now: DateTime<Utc>,
+    ) -> GarpResult<GovernanceProposal> {
+        Self::validate_action(&action)?;
+        if proposal_id.is_empty() {
+            return Err(GarpError::ValidationError("Proposal ID cannot be empty".to_string()));
+        }
+        if eligible_weights.values().sum::<u64>() == 0 {
+            return Err(GarpError::ValidationError("No active domains with voting weight".to_string()));
+        }
+
+        let _guard = self.lock.lock().await;
+        if self.storage.get_governance_proposal(&proposal_id).await?.is_some() {
+            return Err(GarpError::ValidationError(format!("Proposal {} already exists", proposal_id)));
+        }
+
+        let proposal = GovernanceProposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            action,
+            description,
+            status: ProposalStatus::Voting,
+            eligible_weights,
+            votes: HashMap::new(),
+            created_at: now,
+            voting_ends_at: now + self.voting_period,
+            executed_at: None,
+            failure_reason: None,
+            tally: None,
+        };
+
+        self.storage.store_governance_proposal(proposal.clone()).await?;
+        info!(
+            "Opened governance proposal {} from {}; voting ends {}",
+            proposal.proposal_id, proposer, proposal.voting_ends_at
+        );
+        Ok(proposal)
+    }
+
+    /// Record a domain's vote; each eligible domain votes once
+    pub async fn cast_vote(&self, proposal_id: &ProposalId, domain_id: &DomainId, approve: bool, now: DateTime<Utc>) -> GarpResult<GovernanceProposal> {
+        let _guard = self.lock.lock().await;
+        let mut proposal = self.load(proposal_id).await?;
+
+        if proposal.status != ProposalStatus::Voting || now > proposal.voting_ends_at {
+            return Err(GarpError::ValidationError(format!("Proposal {} is not open for voting", proposal_id)));
+        }
+        let weight = *proposal.eligible_weights.get(domain_id)
+            .ok_or_else(|| GarpError::ValidationError(format!("Domain {} is not eligible to vote on {}", domain_id, proposal_id)))?;
+        if proposal.votes.contains_key(domain_id) {
+            return Err(GarpError::ValidationError(format!("Domain {} already voted on {}", domain_id, proposal_id)));
+        }
+
+        proposal.votes.insert(domain_id.clone(), DomainVote {
+            domain_id: domain_id.clone(),
+            approve,
+            weight,
+            cast_at: now,
+        });
+        self.storage.store_governance_proposal(proposal.clone()).await?;
+        Ok(proposal)
+    }
+
+    /// Close every proposal whose voting period ended before `now`, returning
+    /// the approved ones the synchronizer should execute. A proposal passes
+    /// if the votes cast meet the quorum, approvals outweigh rejections and
+    /// approvals exceed the approval threshold. Approved custom proposals are
+    /// only recorded.
+    pub async fn tally_expired(&self, now: DateTime<Utc>) -> GarpResult<Vec<GovernanceProposal>> {
+        let _guard = self.lock.lock().await;
+        let mut approved = Vec::new();
+
+        for mut proposal in self.storage.list_governance_proposals().await? {
+            if proposal.status != ProposalStatus::Voting || proposal.voting_ends_at >= now {
+                continue;
+            }
+
+            let tally = ProposalTally {
+                approve_weight: proposal.approve_weight(),
+                reject_weight: proposal.reject_weight(),
+                total_weight: proposal.total_weight(),
+                quorum_met: (proposal.approve_weight() + proposal.reject_weight()) as u128 * 1000
+                    >= proposal.total_weight() as u128 * self.quorum_thousandths as u128,
+            };
+            let passed = tally.quorum_met
+                && tally.approve_weight > tally.reject_weight
+                && tally.approve_weight as u128 * 1000 > tally.total_weight as u128 * self.approval_threshold_thousandths as u128;
+            proposal.status = match passed {
+                false => ProposalStatus::Rejected,
+                true if proposal.action.is_automatic() => ProposalStatus::Approved,
+                true => ProposalStatus::Recorded,
+            };
+            info!(
+                "Governance proposal {} {:?}: {} approve / {} reject of {} (quorum {})",
+                proposal.proposal_id,
+                proposal.status,
+                tally.approve_weight,
+                tally.reject_weight,
+                tally.total_weight,
+                if tally.quorum_met { "met" } else { "not met" }
+            );
+            proposal.tally = Some(tally);
+
+            self.storage.store_governance_proposal(proposal.clone()).await?;
+            if proposal.status == ProposalStatus::Approved {
+                approved.push(proposal);
+            }
+        }
+
+        Ok(approved)
+    }
+
+    /// Record the outcome of applying an approved proposal's action
+    pub async fn record_execution(&self, proposal_id: &ProposalId, outcome: Result<(), String>, now: DateTime<Utc>) -> GarpResult<GovernanceProposal> {
+        let _guard = self.lock.lock().await;
+        let mut proposal = self.load(proposal_id).await?;
+
+        if proposal.status != ProposalStatus::Approved {
+            return Err(GarpError::ValidationError(format!("Proposal {} is not approved", proposal_id)));
+        }
+        match outcome {
+            Ok(()) => proposal.status = ProposalStatus::Executed,
+            Err(reason) => {
+                proposal.status = ProposalStatus::Failed;
+                proposal.failure_reason = Some(reason);
+            }
+        }
+        proposal.executed_at = Some(now);
+        self.storage.store_governance_proposal(proposal.clone()).await?;
+        Ok(proposal)
+    }
+
+    /// Get a proposal
+    pub async fn get_proposal(&self, proposal_id: &ProposalId) -> GarpResult<Option<GovernanceProposal>> {
+        self.storage.get_governance_proposal(proposal_id).await
+    }
+
+    /// List proposals, oldest first
+    pub async fn list_proposals(&self) -> GarpResult<Vec<GovernanceProposal>> {
+        let mut proposals = self.storage.list_governance_proposals().await?;
+        proposals.sort_by_key(|proposal| proposal.created_at);
+        Ok(proposals)
+    }
+
+    async fn load(&self, proposal_id: &ProposalId) -> GarpResult<GovernanceProposal> {
+        self.storage.get_governance_proposal(proposal_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Governance proposal not found: {}", proposal_id)))
+    }
+
+    fn validate_action(action: &GovernanceAction) -> GarpResult<()> {
+        match action {
+            GovernanceAction::UpdateConfig { key, .. } if key.is_empty() => {
+                Err(GarpError::ValidationError("Config key cannot be empty".to_string()))
+            }
+            GovernanceAction::AddDomain { domain_id, .. } if domain_id.is_empty() => {
+                Err(GarpError::ValidationError("Domain ID cannot be empty".to_string()))
+            }
+            GovernanceAction::UpdateConfig { key, value }
+                if key == COORDINATION_TIMEOUT_PARAMETER && value.parse::<u64>().map_or(true, |secs| secs == 0) =>
+            {
+                Err(GarpError::ValidationError(format!("{} must be a positive number of seconds", key)))
+            }
+            GovernanceAction::SlashValidator { penalty_bps, .. } if *penalty_bps == 0 || *penalty_bps > MAX_BASIS_POINTS => {
+                Err(GarpError::ValidationError(format!("Slash penalty must be in (0, {}] basis points", MAX_BASIS_POINTS)))
+            }
+            GovernanceAction::AddValidator { voting_power, .. } if *voting_power == 0 => {
+                Err(GarpError::ValidationError("Validator voting power must be positive".to_string()))
+            }
+            GovernanceAction::IssueDelegation { .. } => action.delegation().map_or(Ok(()), |delegation| delegation.validate()),
+            GovernanceAction::RevokeDelegation { delegator, delegate } if delegator.is_empty() || delegate.is_empty() => {
+                Err(GarpError::ValidationError("Delegator and delegate cannot be empty".to_string()))
+            }
+            GovernanceAction::Custom { proposal_type, .. } if proposal_type.is_empty() => {
+                Err(GarpError::ValidationError("Proposal type cannot be empty".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Periodically tallies proposals whose voting period has ended
+pub struct GovernanceMonitor {
+    /// Cross-domain coordinator owning the governance engine
+    coordinator: Arc<CrossDomainCoordinator>,
+
+    /// Interval between tally passes
+    check_interval: Duration,
+}
+
+impl GovernanceMonitor {
+    /// Create new governance monitor
+    pub fn new(coordinator: Arc<CrossDomainCoordinator>, check_interval: Duration) -> Self {
+        Self {
+            coordinator,
+            check_interval,
+        }
+    }
+
+    /// Spawn the periodic tally loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(self.check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match self.coordinator.finalize_governance_proposals(Utc::now()).await {
+                    Ok(executed) if !executed.is_empty() => {
+                        info!("Executed {} governance proposals", executed.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!("Governance tally pass failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn engine() -> GovernanceEngine {
+        engine_with_threshold(500).await
+    }
+
+    async fn engine_with_threshold(approval_threshold_thousandths: u64) -> GovernanceEngine {
+        let mut config = GlobalSyncConfig::default();
+        config.cross_domain.governance_voting_period_secs = 60;
+        config.cross_domain.governance_approval_threshold_thousandths = approval_threshold_thousandths;
+        config.cross_domain.governance_quorum_thousandths = 400;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        GovernanceEngine::new(config, storage)
+    }
+
+    fn weights() -> HashMap<DomainId, u64> {
+        [("a", 100), ("b", 300), ("c", 200)]
+            .into_iter()
+            .map(|(d, w)| (d.to_string(), w))
+            .collect()
+    }
+
+    fn update_config() -> GovernanceAction {
+        GovernanceAction::UpdateConfig { key: "max_batch".to_string(), value: "64".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_votes_are_weighted_by_throughput() {
+        let engine = engine().await;
+        let now = Utc::now();
+        let id = engine.create_proposal(&"a".to_string(), update_config(), String::new(), weights(), now)
+            .await.unwrap().proposal_id;
+
+        // `a` and `c` approve with 300 of 600
+        engine.cast_vote(&id, &"a".to_string(), true, now).await.unwrap();
+        engine.cast_vote(&id, &"c".to_string(), true, now).await.unwrap();
+        assert!(engine.cast_vote(&id, &"a".to_string(), false, now).await.is_err());
+        assert!(engine.cast_vote(&id, &"z".to_string(), true, now).await.is_err());
+
+        // Still within the voting period
+        assert!(engine.tally_expired(now + chrono::Duration::seconds(30)).await.unwrap().is_empty());
+        engine.cast_vote(&id, &"b".to_string(), true, now).await.unwrap();
+        assert!(engine.cast_vote(&id, &"b".to_string(), true, now + chrono::Duration::seconds(61)).await.is_err());
+
+        let approved = engine.tally_expired(now + chrono::Duration::seconds(61)).await.unwrap();
+        assert_eq!(approved.len(), 1);
+        assert_eq!(approved[0].approve_weight(), 600);
+
+        let executed = engine.record_execution(&id, Ok(()), now).await.unwrap();
+        assert_eq!(executed.status, ProposalStatus::Executed);
+        assert!(engine.record_execution(&id, Ok(()), now).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_half_approval_is_rejected() {
+        let engine = engine().await;
+        let now = Utc::now();
+        let id = engine.create_proposal(&"a".to_string(), update_config(), String::new(), weights(), now)
+            .await.unwrap().proposal_id;
+
+        // Exactly half of the weight is not a majority
+        engine.cast_vote(&id, &"a".to_string(), true, now).await.unwrap();
+        engine.cast_vote(&id, &"c".to_string(), true, now).await.unwrap();
+
+        assert!(engine.tally_expired(now + chrono::Duration::seconds(61)).await.unwrap().is_empty());
+        let proposal = engine.get_proposal(&id).await.unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_quorum_not_met_is_rejected() {
+        let engine = engine().await;
+        let now = Utc::now();
+        let id = engine.create_proposal(&"a".to_string(), update_config(), String::new(), weights(), now)
+            .await.unwrap().proposal_id;
+
+        // `c` alone approves with 200 of 600, short of the 240 quorum
+        engine.cast_vote(&id, &"c".to_string(), true, now).await.unwrap();
+
+        assert!(engine.tally_expired(now + chrono::Duration::seconds(61)).await.unwrap().is_empty());
+        let proposal = engine.get_proposal(&id).await.unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        assert_eq!(proposal.tally, Some(ProposalTally {
+            approve_weight: 200,
+            reject_weight: 0,
+            total_weight: 600,
+            quorum_met: false,
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_tie_is_rejected() {
+        let engine = engine_with_threshold(100).await;
+        let now = Utc::now();
+        let id = engine.create_proposal(&"a".to_string(), update_config(), String::new(), weights(), now)
+            .await.unwrap().proposal_id;
+
+        // 300 for and 300 against clears the 100 approval threshold but is no majority
+        engine.cast_vote(&id, &"a".to_string(), true, now).await.unwrap();
+        engine.cast_vote(&id, &"c".to_string(), true, now).await.unwrap();
+        engine.cast_vote(&id, &"b".to_string(), false, now).await.unwrap();
+
+        assert!(engine.tally_expired(now + chrono::Duration::seconds(61)).await.unwrap().is_empty());
+        let proposal = engine.get_proposal(&id).await.unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        let tally = proposal.tally.unwrap();
+        assert!(tally.quorum_met);
+        assert_eq!((tally.approve_weight, tally.reject_weight), (300, 300));
+    }
+
+    #[tokio::test]
+    async fn test_proposal_transactions_map_to_actions() {
+        let engine = engine().await;
+        let now = Utc::now();
+        let data = br#"{"key": "coordination_timeout", "value": "45"}"#;
+        let action = GovernanceAction::from_proposal("update_config", data).unwrap();
+        assert!(matches!(&action, GovernanceAction::UpdateConfig { key, value } if key == COORDINATION_TIMEOUT_PARAMETER && value == "45"));
+        assert!(GovernanceAction::from_proposal("update_config", b"{}").is_err());
+        let zero = GovernanceAction::from_proposal("update_config", br#"{"key": "coordination_timeout", "value": "0"}"#).unwrap();
+        assert!(engine.create_proposal(&"a".to_string(), zero, String::new(), weights(), now).await.is_err());
+
+        // Unknown types are recorded, not executed
+        let custom = GovernanceAction::from_proposal("treasury_grant", b"opaque").unwrap();
+        assert!(!custom.is_automatic());
+        let id = "grant-1".to_string();
+        engine.open_proposal(id.clone(), &"a".to_string(), custom, String::new(), weights(), now).await.unwrap();
+        assert!(engine.open_proposal(id.clone(), &"a".to_string(), update_config(), String::new(), weights(), now).await.is_err());
+        for domain in ["a", "b", "c"] {
+            engine.cast_vote(&id, &domain.to_string(), true, now).await.unwrap();
+        }
+        assert!(engine.tally_expired(now + chrono::Duration::seconds(61)).await.unwrap().is_empty());
+        assert_eq!(engine.get_proposal(&id).await.unwrap().unwrap().status, ProposalStatus::Recorded);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_proposals_are_refused() {
+        let engine = engine().await;
+        let now = Utc::now();
+        let slash = GovernanceAction::SlashValidator { validator_id: ParticipantId::new("v1"), penalty_bps: 10_001 };
+        assert!(engine.create_proposal(&"a".to_string(), slash, String::new(), weights(), now).await.is_err());
+        assert!(engine.create_proposal(&"a".to_string(), update_config(), String::new(), HashMap::new(), now).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delegation_proposals_map_to_actions() {
+        let engine = engine().await;
+        let now = Utc::now();
+        let data = br#"{"delegator": "a", "delegate": "b", "permissions": ["submit_transactions"]}"#;
+        let issue = GovernanceAction::from_proposal("issue_delegation", data).unwrap();
+        let delegation = issue.delegation().unwrap();
+        assert_eq!(delegation.permissions, vec![DelegationPermission::SubmitTransactions]);
+        assert!(delegation.expires_at.is_none());
+        assert!(engine.create_proposal(&"a".to_string(), issue, String::new(), weights(), now).await.is_ok());
+
+        let to_self = GovernanceAction::from_proposal("issue_delegation", br#"{"delegator": "a", "delegate": "a", "permissions": ["submit_transactions"]}"#).unwrap();
+        assert!(engine.create_proposal(&"a".to_string(), to_self, String::new(), weights(), now).await.is_err());
+        let revoke = GovernanceAction::from_proposal("revoke_delegation", br#"{"delegator": "a", "delegate": "b"}"#).unwrap();
+        assert!(matches!(revoke, GovernanceAction::RevokeDelegation { .. }));
+        assert!(revoke.is_automatic());
+    }
+}
@@ -0,0 +1,592 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use garp_common::{GarpResult, GarpError};
+use garp_common::types::TransactionId;
+
+use crate::cross_domain::{
+    ConfirmationStatus, CrossDomainCoordinator, CrossDomainTransaction, CrossDomainTransactionType, DomainConfirmation,
+};
+use crate::storage::{DomainId, GlobalStorage};
+
+/// Atomic swap identifier
+pub type SwapId = String;
+
+/// Metadata key carrying the hex-encoded SHA-256 hashlock of a swap. Set by
+/// the submitter to use their own secret, otherwise derived on submission.
+pub const SWAP_HASHLOCK_METADATA_KEY: &str = "swap_hashlock";
+
+/// Hashed-timelock swap of `asset_a` on the first target domain for
+/// `asset_b` on the second
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtomicSwap {
+    /// Swap ID
+    pub swap_id: SwapId,
+
+    /// Swap transaction
+    pub transaction_id: TransactionId,
+
+    /// Hex-encoded SHA-256 of the preimage
+    pub hashlock: String,
+
+    /// Hex-encoded preimage, published once both legs are locked
+    pub preimage: Option<String>,
+
+    /// The two legs, in target domain order
+    pub legs: Vec<SwapLeg>,
+
+    /// Swap status
+    pub status: SwapStatus,
+
+    /// Locks are refunded if the swap has not been revealed by this time
+    pub refund_after: DateTime<Utc>,
+
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+
+    /// Preimage known to the coordinator but not yet published
+    #[serde(skip)]
+    secret: Option<Vec<u8>>,
+}
+
+/// One domain's side of a swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapLeg {
+    /// Domain locking the asset
+    pub domain_id: DomainId,
+
+    /// Asset locked
+    pub asset: String,
+
+    /// Amount locked
+    pub amount: u64,
+
+    /// Leg status
+    pub status: SwapLegStatus,
+
+    /// Lock proof the domain confirmed with
+    pub lock_proof: Option<SwapLockProof>,
+
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Swap status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SwapStatus {
+    /// Waiting for both legs to lock and the preimage to be known
+    AwaitingLocks,
+
+    /// Preimage published; both legs can be claimed
+    Revealed,
+
+    /// Aborted; every lock is refunded
+    Refunded,
+}
+
+/// Swap leg status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SwapLegStatus {
+    /// Not locked yet
+    Pending,
+
+    /// Asset locked under the hashlock
+    Locked,
+
+    /// Preimage sent; the counterparty can claim the asset
+    Revealed,
+
+    /// Lock released back to its owner
+    Refunded,
+
+    /// Swap aborted before this leg locked
+    Aborted,
+}
+
+/// Lock a domain reports in the data of its swap confirmation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapLockProof {
+    /// Swap ID
+    pub swap_id: SwapId,
+
+    /// Hashlock the asset is locked under
+    pub hashlock: String,
+
+    /// Asset locked
+    pub asset: String,
+
+    /// Amount locked
+    pub amount: u64,
+
+    /// Domain-side reference to the lock
+    pub lock_reference: String,
+}
+
+/// Instruction sent to a domain about its leg
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SwapInstruction {
+    /// Release the lock to the counterparty, proven by the preimage
+    Claim {
+        swap_id: SwapId,
+        preimage: Vec<u8>,
+    },
+
+    /// Release the lock back to its owner, or do not lock at all
+    Refund {
+        swap_id: SwapId,
+    },
+}
+
+/// Instructions for domains after a swap changed
+pub type SwapInstructions = Vec<(DomainId, SwapInstruction)>;
+
+fn hashlock_of(preimage: &[u8]) -> String {
+    hex::encode(Sha256::digest(preimage))
+}
+
+impl AtomicSwap {
+    /// Publish the preimage if both legs are locked and it is known
+    fn try_reveal(&mut self, now: DateTime<Utc>) -> SwapInstructions {
+        let all_locked = self.legs.iter().all(|leg| leg.status == SwapLegStatus::Locked);
+        let Some(secret) = self.secret.clone().filter(|_| all_locked && self.status == SwapStatus::AwaitingLocks) else {
+            return Vec::new();
+        };
+        self.status = SwapStatus::Revealed;
+        self.preimage = Some(hex::encode(&secret));
+        self.updated_at = now;
+        self.legs.iter_mut()
+            .map(|leg| {
+                leg.status = SwapLegStatus::Revealed;
+                leg.updated_at = now;
+                (leg.domain_id.clone(), SwapInstruction::Claim { swap_id: self.swap_id.clone(), preimage: secret.clone() })
+            })
+            .collect()
+    }
+
+    /// Abort the swap, refunding locked legs and telling pending ones not to lock
+    fn refund(&mut self, now: DateTime<Utc>) -> SwapInstructions {
+        self.status = SwapStatus::Refunded;
+        self.updated_at = now;
+        self.legs.iter_mut()
+            .filter(|leg| matches!(leg.status, SwapLegStatus::Pending | SwapLegStatus::Locked))
+            .map(|leg| {
+                leg.status = match leg.status {
+                    SwapLegStatus::Locked => SwapLegStatus::Refunded,
+                    _ => SwapLegStatus::Aborted,
+                };
+                leg.updated_at = now;
+                (leg.domain_id.clone(), SwapInstruction::Refund { swap_id: self.swap_id.clone() })
+            })
+            .collect()
+    }
+}
+
+/// Runs the hashed-timelock protocol for atomic swap transactions.
+///
+/// A swap reveals its preimage only after both domains confirm a lock under
+/// its hashlock, so neither side can claim without the other having locked.
+/// If the timelock passes first, every lock is refunded.
+pub struct AtomicSwapManager {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Serializes read-modify-write of swap records
+    lock: Mutex<()>,
+}
+
+impl AtomicSwapManager {
+    /// Create new atomic swap manager
+    pub fn new(storage: Arc<GlobalStorage>) -> Self {
+        Self {
+            storage,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Swap ID of an atomic swap transaction
+    pub fn swap_id(transaction: &CrossDomainTransaction) -> Option<&SwapId> {
+        match &transaction.transaction_type {
+            CrossDomainTransactionType::AtomicSwap { swap_id, .. } => Some(swap_id),
+            _ => None,
+        }
+    }
+
+    /// Check a swap transaction before admission
+    pub async fn validate(&self, transaction: &CrossDomainTransaction) -> GarpResult<()> {
+        let CrossDomainTransactionType::AtomicSwap { swap_id, amount_a, amount_b, .. } = &transaction.transaction_type else {
+            return Err(GarpError::ValidationError("Not an atomic swap transaction".to_string()));
+        };
+        if transaction.target_domains.len() != 2 || transaction.target_domains[0] == transaction.target_domains[1] {
+            return Err(GarpError::ValidationError("An atomic swap needs exactly two distinct target domains".to_string()));
+        }
+        if *amount_a == 0 || *amount_b == 0 {
+            return Err(GarpError::ValidationError("Swap amounts must be positive".to_string()));
+        }
+        if transaction.timeout_at <= Utc::now() {
+            return Err(GarpError::ValidationError(format!("Swap {} timelock has already expired", swap_id)));
+        }
+        if let Some(hashlock) = transaction.metadata.get(SWAP_HASHLOCK_METADATA_KEY) {
+            if hex::decode(hashlock).map_or(true, |bytes| bytes.len() != 32) {
+                return Err(GarpError::ValidationError(format!("Invalid swap hashlock: {}", hashlock)));
+            }
+        }
+        match self.storage.get_atomic_swap(swap_id).await? {
+            Some(swap) if swap.transaction_id != transaction.transaction_id => {
+                Err(GarpError::ValidationError(format!("Swap {} already exists", swap_id)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Open the swap of a transaction entering coordination, deriving a
+    /// hashlock unless the submitter supplied one. Reopening returns the
+    /// existing swap.
+    pub async fn open(&self, transaction: &CrossDomainTransaction) -> GarpResult<AtomicSwap> {
+        let CrossDomainTransactionType::AtomicSwap { swap_id, asset_a, asset_b, amount_a, amount_b } = &transaction.transaction_type else {
+            return Err(GarpError::ValidationError("Not an atomic swap transaction".to_string()));
+        };
+        let _guard = self.lock.lock().await;
+        if let Some(swap) = self.storage.get_atomic_swap(swap_id).await? {
+            return Ok(swap);
+        }
+
+        let (hashlock, secret) = match transaction.metadata.get(SWAP_HASHLOCK_METADATA_KEY) {
+            Some(hashlock) => (hashlock.to_lowercase(), None),
+            None => {
+                let mut secret = vec![0u8; 32];
+                rand::thread_rng().fill_bytes(&mut secret);
+                (hashlock_of(&secret), Some(secret))
+            }
+        };
+        let now = Utc::now();
+        let leg = |domain_id: &DomainId, asset: &String, amount: u64| SwapLeg {
+            domain_id: domain_id.clone(),
+            asset: asset.clone(),
+            amount,
+            status: SwapLegStatus::Pending,
+            lock_proof: None,
+            updated_at: now,
+        };
+        let swap = AtomicSwap {
+            swap_id: swap_id.clone(),
+            transaction_id: transaction.transaction_id.clone(),
+            hashlock,
+            preimage: None,
+            legs: vec![
+                leg(&transaction.target_domains[0], asset_a, *amount_a),
+                leg(&transaction.target_domains[1], asset_b, *amount_b),
+            ],
+            status: SwapStatus::AwaitingLocks,
+            refund_after: transaction.timeout_at,
+            created_at: now,
+            updated_at: now,
+            secret,
+        };
+        self.storage.store_atomic_swap(swap.clone()).await?;
+        info!("Opened atomic swap {} until {}", swap_id, swap.refund_after);
+        Ok(swap)
+    }
+
+    /// Record a domain's confirmation of its leg. A lock arriving after the
+    /// swap was refunded is refunded straight away.
+    pub async fn record_lock(
+        &self,
+        swap_id: &SwapId,
+        confirmation: &DomainConfirmation,
+        now: DateTime<Utc>,
+    ) -> GarpResult<(AtomicSwap, SwapInstructions)> {
+        let _guard = self.lock.lock().await;
+        let mut swap = self.load(swap_id).await?;
+        let leg_index = swap.legs.iter().position(|leg| leg.domain_id == confirmation.domain_id)
+            .ok_or_else(|| GarpError::ValidationError(format!(
+                "Domain {} is not a party to swap {}", confirmation.domain_id, swap_id
+            )))?;
+
+        if confirmation.status != ConfirmationStatus::Confirmed {
+            if swap.status != SwapStatus::AwaitingLocks {
+                return Ok((swap, Vec::new()));
+            }
+            warn!("Domain {} refused to lock swap {}, aborting", confirmation.domain_id, swap_id);
+            let instructions = swap.refund(now);
+            self.storage.store_atomic_swap(swap.clone()).await?;
+            return Ok((swap, instructions));
+        }
+
+        let proof: SwapLockProof = serde_json::from_slice(&confirmation.data)?;
+        let leg = &swap.legs[leg_index];
+        if proof.swap_id != swap.swap_id || !proof.hashlock.eq_ignore_ascii_case(&swap.hashlock)
+            || proof.asset != leg.asset || proof.amount != leg.amount
+        {
+            return Err(GarpError::ValidationError(format!(
+                "Lock proof from {} does not match swap {}", confirmation.domain_id, swap_id
+            )));
+        }
+
+        let mut instructions = Vec::new();
+        let leg = &mut swap.legs[leg_index];
+        match leg.status {
+            SwapLegStatus::Pending if swap.status == SwapStatus::AwaitingLocks => {
+                debug!("Domain {} locked its leg of swap {}", leg.domain_id, swap_id);
+                leg.status = SwapLegStatus::Locked;
+                leg.lock_proof = Some(proof);
+                leg.updated_at = now;
+                if now >= swap.refund_after {
+                    instructions = swap.refund(now);
+                } else {
+                    instructions = swap.try_reveal(now);
+                }
+            }
+            SwapLegStatus::Aborted => {
+                info!("Late lock from {} on refunded swap {}, refunding it", leg.domain_id, swap_id);
+                leg.status = SwapLegStatus::Refunded;
+                leg.lock_proof = Some(proof);
+                leg.updated_at = now;
+                instructions.push((leg.domain_id.clone(), SwapInstruction::Refund { swap_id: swap_id.clone() }));
+            }
+            // Duplicate confirmation
+            _ => return Ok((swap, instructions)),
+        }
+        swap.updated_at = now;
+        self.storage.store_atomic_swap(swap.clone()).await?;
+        Ok((swap, instructions))
+    }
+
+    /// Supply the preimage of a swap whose hashlock was chosen by the
+    /// submitter. It is published once both legs are locked.
+    pub async fn reveal(&self, swap_id: &SwapId, preimage: &[u8], now: DateTime<Utc>) -> GarpResult<(AtomicSwap, SwapInstructions)> {
+        let _guard = self.lock.lock().await;
+        let mut swap = self.load(swap_id).await?;
+        if swap.status != SwapStatus::AwaitingLocks {
+            return Err(GarpError::ValidationError(format!("Swap {} is {:?}", swap_id, swap.status)));
+        }
+        if hashlock_of(preimage) != swap.hashlock {
+            return Err(GarpError::ValidationError(format!("Preimage does not match the hashlock of swap {}", swap_id)));
+        }
+        swap.secret = Some(preimage.to_vec());
+        let instructions = swap.try_reveal(now);
+        self.storage.store_atomic_swap(swap.clone()).await?;
+        Ok((swap, instructions))
+    }
+
+    /// Refund every unrevealed swap whose timelock has passed by `now`
+    pub async fn refund_expired(&self, now: DateTime<Utc>) -> GarpResult<Vec<(AtomicSwap, SwapInstructions)>> {
+        let _guard = self.lock.lock().await;
+        let mut refunded = Vec::new();
+
+        for mut swap in self.storage.list_atomic_swaps().await? {
+            if swap.status != SwapStatus::AwaitingLocks || swap.refund_after > now {
+                continue;
+            }
+            info!("Swap {} timelock expired, refunding", swap.swap_id);
+            let instructions = swap.refund(now);
+            self.storage.store_atomic_swap(swap.clone()).await?;
+            refunded.push((swap, instructions));
+        }
+
+        Ok(refunded)
+    }
+
+    /// Get a swap
+    pub async fn get_swap(&self, swap_id: &SwapId) -> GarpResult<Option<AtomicSwap>> {
+        self.storage.get_atomic_swap(swap_id).await
+    }
+
+    async fn load(&self, swap_id: &SwapId) -> GarpResult<AtomicSwap> {
+        self.storage.get_atomic_swap(swap_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Atomic swap not found: {}", swap_id)))
+    }
+}
+
+/// Periodically refunds swaps whose timelock has passed
+pub struct SwapTimeoutMonitor {
+    /// Cross-domain coordinator owning the swap manager
+    coordinator: Arc<CrossDomainCoordinator>,
+
+    /// Interval between timelock checks
+    check_interval: Duration,
+}
+
+impl SwapTimeoutMonitor {
+    /// Create new swap timeout monitor
+    pub fn new(coordinator: Arc<CrossDomainCoordinator>, check_interval: Duration) -> Self {
+        Self {
+            coordinator,
+            check_interval,
+        }
+    }
+
+    /// Spawn the periodic refund loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(self.check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match self.coordinator.refund_expired_swaps(Utc::now()).await {
+                    Ok(refunded) if !refunded.is_empty() => {
+                        info!("Refunded {} expired atomic swaps", refunded.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!("Atomic swap refund pass failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::config::GlobalSyncConfig;
+    use crate::cross_domain::TransactionStatus;
+
+    async fn manager() -> AtomicSwapManager {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config).await.unwrap());
+        AtomicSwapManager::new(storage)
+    }
+
+    fn swap_transaction(timeout: chrono::Duration) -> CrossDomainTransaction {
+        let now = Utc::now();
+        CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "a".to_string(),
+            target_domains: vec!["a".to_string(), "b".to_string()],
+            transaction_type: CrossDomainTransactionType::AtomicSwap {
+                swap_id: "swap-1".to_string(),
+                asset_a: "gold".to_string(),
+                asset_b: "silver".to_string(),
+                amount_a: 10,
+                amount_b: 250,
+            },
+            data: vec![1],
+            dependencies: Vec::new(),
+            required_confirmations: 2,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + timeout,
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn lock(swap: &AtomicSwap, domain_id: &str) -> DomainConfirmation {
+        let leg = swap.legs.iter().find(|leg| leg.domain_id == domain_id).unwrap();
+        let proof = SwapLockProof {
+            swap_id: swap.swap_id.clone(),
+            hashlock: swap.hashlock.clone(),
+            asset: leg.asset.clone(),
+            amount: leg.amount,
+            lock_reference: format!("lock-{}", domain_id),
+        };
+        DomainConfirmation {
+            domain_id: domain_id.to_string(),
+            status: ConfirmationStatus::Confirmed,
+            data: serde_json::to_vec(&proof).unwrap(),
+            signature: Vec::new(),
+            timestamp: Utc::now(),
+            validator_info: None,
+            vote_sequence_number: 0,
+        }
+    }
+
+    fn statuses(swap: &AtomicSwap) -> Vec<SwapLegStatus> {
+        swap.legs.iter().map(|leg| leg.status.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_preimage_revealed_once_both_legs_lock() {
+        let manager = manager().await;
+        let transaction = swap_transaction(chrono::Duration::minutes(5));
+        manager.validate(&transaction).await.unwrap();
+        let swap = manager.open(&transaction).await.unwrap();
+        let id = swap.swap_id.clone();
+        let now = Utc::now();
+
+        let mut wrong_amount = lock(&swap, "b");
+        let mut proof: SwapLockProof = serde_json::from_slice(&wrong_amount.data).unwrap();
+        proof.amount -= 1;
+        wrong_amount.data = serde_json::to_vec(&proof).unwrap();
+        assert!(manager.record_lock(&id, &wrong_amount, now).await.is_err());
+
+        let (swap, instructions) = manager.record_lock(&id, &lock(&swap, "a"), now).await.unwrap();
+        assert!(instructions.is_empty());
+        assert!(swap.preimage.is_none(), "nothing is revealed while one side is unlocked");
+
+        let (swap, instructions) = manager.record_lock(&id, &lock(&swap, "b"), now).await.unwrap();
+        assert_eq!(swap.status, SwapStatus::Revealed);
+        assert_eq!(statuses(&swap), vec![SwapLegStatus::Revealed, SwapLegStatus::Revealed]);
+        let preimage = hex::decode(swap.preimage.as_ref().unwrap()).unwrap();
+        assert_eq!(hashlock_of(&preimage), swap.hashlock);
+        assert_eq!(instructions.len(), 2);
+        assert!(instructions.iter().all(|(_, i)| *i == SwapInstruction::Claim { swap_id: id.clone(), preimage: preimage.clone() }));
+
+        // Persisted, and the preimage stays with the swap
+        let stored = manager.get_swap(&id).await.unwrap().unwrap();
+        assert_eq!(stored.preimage, swap.preimage);
+        assert!(manager.refund_expired(now + chrono::Duration::minutes(10)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_one_sided_lock_is_refunded_after_timelock() {
+        let manager = manager().await;
+        let mut transaction = swap_transaction(chrono::Duration::minutes(5));
+        let secret = b"submitter secret".to_vec();
+        transaction.metadata.insert(SWAP_HASHLOCK_METADATA_KEY.to_string(), hashlock_of(&secret));
+        let swap = manager.open(&transaction).await.unwrap();
+        let id = swap.swap_id.clone();
+        let now = Utc::now();
+
+        manager.record_lock(&id, &lock(&swap, "a"), now).await.unwrap();
+        assert!(manager.reveal(&id, b"wrong secret", now).await.is_err());
+        let (swap, instructions) = manager.reveal(&id, &secret, now).await.unwrap();
+        assert!(instructions.is_empty() && swap.preimage.is_none());
+
+        assert!(manager.refund_expired(now).await.unwrap().is_empty());
+        let refunded = manager.refund_expired(now + chrono::Duration::minutes(6)).await.unwrap();
+        let (swap, instructions) = &refunded[0];
+        assert_eq!(swap.status, SwapStatus::Refunded);
+        assert_eq!(statuses(swap), vec![SwapLegStatus::Refunded, SwapLegStatus::Aborted]);
+        assert!(swap.preimage.is_none());
+        assert_eq!(instructions.len(), 2);
+        assert!(instructions.iter().all(|(_, i)| matches!(i, SwapInstruction::Refund { .. })));
+        assert!(manager.reveal(&id, &secret, now).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_late_lock_after_refund_is_refunded() {
+        let manager = manager().await;
+        let swap = manager.open(&swap_transaction(chrono::Duration::minutes(5))).await.unwrap();
+        let id = swap.swap_id.clone();
+        let now = Utc::now();
+
+        manager.record_lock(&id, &lock(&swap, "a"), now).await.unwrap();
+        manager.refund_expired(now + chrono::Duration::minutes(6)).await.unwrap();
+
+        let (swap, instructions) = manager.record_lock(&id, &lock(&swap, "b"), now + chrono::Duration::minutes(7)).await.unwrap();
+        assert_eq!(swap.status, SwapStatus::Refunded);
+        assert_eq!(statuses(&swap), vec![SwapLegStatus::Refunded, SwapLegStatus::Refunded]);
+        assert!(swap.preimage.is_none());
+        assert_eq!(instructions, vec![("b".to_string(), SwapInstruction::Refund { swap_id: id.clone() })]);
+
+        // Repeated late confirmations change nothing
+        let (_, instructions) = manager.record_lock(&id, &lock(&swap, "b"), now + chrono::Duration::minutes(8)).await.unwrap();
+        assert!(instructions.is_empty());
+    }
+}
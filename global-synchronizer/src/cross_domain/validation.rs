@@ -0,0 +1,195 @@
+//! Parallel validation of submitted cross-domain transactions.
+//!
+//! Submissions are queued on a bounded channel to a dispatcher, which runs
+//! each validation as its own task once a semaphore permit is free, so at
+//! most `cross_domain.validation_parallelism` transactions are validated at
+//! a time. Validators share the coordinator's state maps through `Arc`s and
+//! report back on a oneshot channel.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, oneshot, RwLock, Semaphore};
+use tracing::debug;
+
+use garp_common::types::TransactionId;
+use garp_common::{GarpError, GarpResult};
+
+use crate::cross_domain::governance::{GovernanceAction, GovernanceEngine};
+use crate::cross_domain::swap::AtomicSwapManager;
+use crate::cross_domain::{
+    delegation, CrossDomainTransaction, CrossDomainTransactionType, DomainState, DomainStatus, DomainSuspension,
+    TransactionAdmission, TransactionStatus,
+};
+use crate::storage::{DomainId, GlobalStorage};
+
+/// Submissions that may wait for a validator before submitters block
+const VALIDATION_QUEUE_DEPTH: usize = 1024;
+
+/// Coordinator state a validation reads
+#[derive(Clone)]
+pub(crate) struct TransactionValidator {
+    pub(crate) storage: Arc<GlobalStorage>,
+    pub(crate) active_transactions: Arc<RwLock<HashMap<TransactionId, CrossDomainTransaction>>>,
+    pub(crate) domain_states: Arc<RwLock<HashMap<DomainId, DomainState>>>,
+    pub(crate) suspensions: Arc<RwLock<HashMap<DomainId, DomainSuspension>>>,
+    pub(crate) swap_manager: Arc<AtomicSwapManager>,
+    pub(crate) governance_engine: Arc<GovernanceEngine>,
+}
+
+impl TransactionValidator {
+    /// Check that `transaction` can be coordinated now, or once its time
+    /// lock expires
+    pub(crate) async fn validate(&self, transaction: &CrossDomainTransaction) -> GarpResult<TransactionAdmission> {
+        let required_state_roots = transaction.required_state_roots()?;
+
+        // Submissions on another domain's behalf need its current delegation
+        if let Some(delegator) = &transaction.delegated_by {
+            let delegation = self.storage.get_domain_delegation(delegator, &transaction.source_domain).await?;
+            delegation::authorize(transaction, delegation.as_ref(), chrono::Utc::now())?;
+        }
+
+        // Check if target domains are available; unavailable domains may
+        // accept the transaction when retried later
+        for domain_id in &transaction.target_domains {
+            if let Some(suspension) = self.suspensions.read().await.get(domain_id) {
+                return Err(GarpError::Unavailable(format!(
+                    "Domain {} is suspended for maintenance until {}: {}",
+                    domain_id, suspension.resume_at, suspension.reason
+                )));
+            }
+            let domain_state = self.domain_states.read().await.get(domain_id).cloned();
+            match domain_state {
+                Some(state) if state.status == DomainStatus::Active => {
+                    // Transactions built against stale state are rejected
+                    if let Some(required) = required_state_roots.get(domain_id) {
+                        if !required.eq_ignore_ascii_case(&state.state_root_hash) {
+                            return Err(GarpError::StateMismatch(format!(
+                                "Transaction requires state root {} of domain {}, but its last known root is {}",
+                                required,
+                                domain_id,
+                                if state.state_root_hash.is_empty() { "unknown" } else { state.state_root_hash.as_str() }
+                            )));
+                        }
+                    }
+                }
+                Some(_) => return Err(GarpError::Unavailable(
+                    format!("Domain {} is not active", domain_id)
+                )),
+                None => return Err(GarpError::ValidationError(
+                    format!("Domain {} not found", domain_id)
+                )),
+            }
+        }
+
+        // Validate transaction data
+        if transaction.data.is_empty() {
+            return Err(GarpError::ValidationError("Transaction data is empty".to_string()));
+        }
+
+        match &transaction.transaction_type {
+            CrossDomainTransactionType::AtomicSwap { .. } => self.swap_manager.validate(transaction).await?,
+            CrossDomainTransactionType::GovernanceProposal { proposal_id, proposal_type, proposal_data } => {
+                GovernanceAction::from_proposal(proposal_type, proposal_data)?;
+                if self.governance_engine.get_proposal(proposal_id).await?.is_some() {
+                    return Err(GarpError::ValidationError(format!("Proposal {} already exists", proposal_id)));
+                }
+            }
+            _ => {}
+        }
+
+        // Check dependencies
+        for dep_id in &transaction.dependencies {
+            let dep_status = self.active_transactions.read().await.get(dep_id).map(|tx| tx.status.clone());
+            match dep_status {
+                Some(TransactionStatus::Completed) => continue,
+                Some(status) => return Err(GarpError::ValidationError(
+                    format!("Dependency {} is not completed (status: {:?})", dep_id, status)
+                )),
+                None => return Err(GarpError::ValidationError(
+                    format!("Dependency {} not found", dep_id)
+                )),
+            }
+        }
+
+        // Defer coordination until the time lock expires
+        if let Some(not_before) = transaction.not_before {
+            if not_before > chrono::Utc::now() {
+                return Ok(TransactionAdmission::Timelocked(not_before));
+            }
+        }
+
+        Ok(TransactionAdmission::Ready)
+    }
+}
+
+struct ValidationJob {
+    transaction: CrossDomainTransaction,
+    reply: oneshot::Sender<GarpResult<TransactionAdmission>>,
+}
+
+/// Validates submissions concurrently, up to a fixed number at a time
+pub struct ValidationWorkerPool {
+    jobs: mpsc::Sender<ValidationJob>,
+    permits: Arc<Semaphore>,
+    parallelism: usize,
+    peak_in_flight: Arc<AtomicUsize>,
+}
+
+impl ValidationWorkerPool {
+    /// Start the dispatcher; it stops once the pool is dropped
+    pub(crate) fn new(validator: TransactionValidator, parallelism: usize) -> Self {
+        let parallelism = parallelism.max(1);
+        let (jobs, mut queue) = mpsc::channel::<ValidationJob>(VALIDATION_QUEUE_DEPTH);
+        let permits = Arc::new(Semaphore::new(parallelism));
+        let peak_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let validator = Arc::new(validator);
+        let dispatch_permits = permits.clone();
+        let peak = peak_in_flight.clone();
+        tokio::spawn(async move {
+            while let Some(job) = queue.recv().await {
+                let Ok(permit) = dispatch_permits.clone().acquire_owned().await else { break };
+                peak.fetch_max(parallelism - dispatch_permits.available_permits(), Ordering::Relaxed);
+                let validator = validator.clone();
+                tokio::spawn(async move {
+                    let result = validator.validate(&job.transaction).await;
+                    drop(permit);
+                    if job.reply.send(result).is_err() {
+                        debug!("Submitter of {} stopped waiting for its validation", job.transaction.transaction_id);
+                    }
+                });
+            }
+        });
+
+        Self { jobs, permits, parallelism, peak_in_flight }
+    }
+
+    /// Validate `transaction` once a worker is free
+    pub(crate) async fn validate(&self, transaction: CrossDomainTransaction) -> GarpResult<TransactionAdmission> {
+        let (reply, result) = oneshot::channel();
+        self.jobs
+            .send(ValidationJob { transaction, reply })
+            .await
+            .map_err(|_| GarpError::Internal("Validation worker pool has stopped".to_string()))?;
+        result
+            .await
+            .map_err(|_| GarpError::Internal("Validation task ended without a result".to_string()))?
+    }
+
+    /// Most validations allowed to run at once
+    pub fn parallelism(&self) -> usize {
+        self.parallelism
+    }
+
+    /// Validations running right now
+    pub fn in_flight(&self) -> usize {
+        self.parallelism - self.permits.available_permits()
+    }
+
+    /// Most validations seen running at once
+    pub fn peak_in_flight(&self) -> usize {
+        self.peak_in_flight.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,458 @@
+//! Cross-domain token wrapping.
+//!
+//! Wrapping locks a token on its origin domain and mints `wrapped_<token>`
+//! on another domain; unwrapping burns the wrapped token and releases the
+//! original. Both run as atomic swap transactions, so the lock and the mint
+//! (or the burn and the release) happen under one hashlock: either both
+//! domains claim once the preimage is revealed, or both are refunded when
+//! the timelock passes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use uuid::Uuid;
+
+use garp_common::{GarpResult, GarpError};
+use garp_common::types::TransactionId;
+
+use crate::cross_domain::CrossDomainTransactionType;
+use crate::cross_domain::swap::{AtomicSwap, SwapId, SwapStatus};
+use crate::storage::{DomainId, GlobalStorage};
+
+/// Token wrapping identifier, shared with its atomic swap
+pub type WrapId = SwapId;
+
+/// Prefix of the ID a token is minted under on other domains
+pub const WRAPPED_TOKEN_PREFIX: &str = "wrapped_";
+
+/// Metadata key marking a swap transaction as a wrap or an unwrap
+pub const TOKEN_WRAPPING_METADATA_KEY: &str = "token_wrapping";
+
+/// ID of the wrapped form of `token`
+pub fn wrapped_token_id(token: &str) -> String {
+    format!("{}{}", WRAPPED_TOKEN_PREFIX, token)
+}
+
+/// Direction of a token wrapping
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WrappingKind {
+    /// Lock on the origin domain, mint on the wrapped domain
+    Wrap,
+
+    /// Burn on the wrapped domain, release on the origin domain
+    Unwrap,
+}
+
+impl WrappingKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WrappingKind::Wrap => "wrap",
+            WrappingKind::Unwrap => "unwrap",
+        }
+    }
+}
+
+/// Token wrapping status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WrappingStatus {
+    /// Swap in progress; supplies are unchanged
+    Pending,
+
+    /// Swap revealed; supplies updated
+    Completed,
+
+    /// Swap refunded; supplies unchanged
+    Refunded,
+}
+
+/// One wrap or unwrap of a token between its origin domain and a wrapped domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenWrapping {
+    /// Wrapping ID, also the swap ID
+    pub wrap_id: WrapId,
+
+    /// Wrap or unwrap
+    pub kind: WrappingKind,
+
+    /// Wrapped token ID
+    pub wrapped_token_id: String,
+
+    /// Token on its origin domain
+    pub original_token: String,
+
+    /// Domain holding the locked original
+    pub origin_domain: DomainId,
+
+    /// Domain the wrapped token is minted on or burned from
+    pub wrapped_domain: DomainId,
+
+    /// Amount wrapped or unwrapped
+    pub amount: u64,
+
+    /// Wrapping status
+    pub status: WrappingStatus,
+
+    /// Swap transaction
+    pub transaction_id: TransactionId,
+
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TokenWrapping {
+    /// Domains of the two swap legs: the one giving up its tokens first
+    pub fn leg_domains(&self) -> (&DomainId, &DomainId) {
+        match self.kind {
+            WrappingKind::Wrap => (&self.origin_domain, &self.wrapped_domain),
+            WrappingKind::Unwrap => (&self.wrapped_domain, &self.origin_domain),
+        }
+    }
+
+    /// Atomic swap carrying out the wrapping
+    pub fn transaction_type(&self) -> CrossDomainTransactionType {
+        let (asset_a, asset_b) = match self.kind {
+            WrappingKind::Wrap => (&self.original_token, &self.wrapped_token_id),
+            WrappingKind::Unwrap => (&self.wrapped_token_id, &self.original_token),
+        };
+        CrossDomainTransactionType::AtomicSwap {
+            swap_id: self.wrap_id.clone(),
+            asset_a: asset_a.clone(),
+            asset_b: asset_b.clone(),
+            amount_a: self.amount,
+            amount_b: self.amount,
+        }
+    }
+}
+
+/// A token wrapped onto other domains and the supply backing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedToken {
+    /// Wrapped token ID
+    pub wrapped_token_id: String,
+
+    /// Token on its origin domain
+    pub original_token: String,
+
+    /// Domain holding the locked original
+    pub origin_domain: DomainId,
+
+    /// Original tokens locked on the origin domain
+    pub locked_supply: u64,
+
+    /// Wrapped tokens minted, by domain
+    pub wrapped_supply: HashMap<DomainId, u64>,
+
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WrappedToken {
+    /// Wrapped tokens minted across all domains
+    pub fn total_wrapped_supply(&self) -> u64 {
+        self.wrapped_supply.values().sum()
+    }
+}
+
+/// Wrapped tokens by ID and the wrappings that change their supply.
+///
+/// Supplies only change when a wrapping completes, and never so that more
+/// wrapped tokens exist than originals are locked.
+#[derive(Debug, Default)]
+pub struct WrappedTokenRegistry {
+    tokens: HashMap<String, WrappedToken>,
+    wrappings: HashMap<WrapId, TokenWrapping>,
+}
+
+impl WrappedTokenRegistry {
+    /// Record a pending wrapping. An unwrap may only burn wrapped tokens not
+    /// already claimed by another pending unwrap on the same domain.
+    pub fn begin(&mut self, wrapping: TokenWrapping) -> GarpResult<()> {
+        if self.wrappings.contains_key(&wrapping.wrap_id) {
+            return Err(GarpError::ValidationError(format!("Token wrapping {} already exists", wrapping.wrap_id)));
+        }
+        if wrapping.amount == 0 {
+            return Err(GarpError::ValidationError("Wrapping amount must be positive".to_string()));
+        }
+        if wrapping.origin_domain == wrapping.wrapped_domain {
+            return Err(GarpError::ValidationError(format!(
+                "Cannot wrap {} onto its origin domain {}", wrapping.original_token, wrapping.origin_domain
+            )));
+        }
+
+        let token = self.tokens.get(&wrapping.wrapped_token_id);
+        match wrapping.kind {
+            WrappingKind::Wrap => {
+                if let Some(token) = token.filter(|token| token.origin_domain != wrapping.origin_domain) {
+                    return Err(GarpError::ValidationError(format!(
+                        "{} is already wrapped from domain {}", token.original_token, token.origin_domain
+                    )));
+                }
+            }
+            WrappingKind::Unwrap => {
+                let token = token.ok_or_else(|| GarpError::NotFound(format!(
+                    "Wrapped token not found: {}", wrapping.wrapped_token_id
+                )))?;
+                let minted = token.wrapped_supply.get(&wrapping.wrapped_domain).copied().unwrap_or(0);
+                let pending: u64 = self.wrappings.values()
+                    .filter(|pending| pending.kind == WrappingKind::Unwrap && pending.status == WrappingStatus::Pending)
+                    .filter(|pending| pending.wrapped_token_id == wrapping.wrapped_token_id && pending.wrapped_domain == wrapping.wrapped_domain)
+                    .map(|pending| pending.amount)
+                    .sum();
+                let available = minted.saturating_sub(pending);
+                if wrapping.amount > available {
+                    return Err(GarpError::ValidationError(format!(
+                        "Cannot unwrap {} {} on domain {}: only {} available",
+                        wrapping.amount, wrapping.wrapped_token_id, wrapping.wrapped_domain, available
+                    )));
+                }
+            }
+        }
+
+        self.wrappings.insert(wrapping.wrap_id.clone(), wrapping);
+        Ok(())
+    }
+
+    /// Apply a wrapping whose swap was revealed. Completing it again
+    /// returns it unchanged.
+    pub fn complete(&mut self, wrap_id: &WrapId, now: DateTime<Utc>) -> GarpResult<TokenWrapping> {
+        let wrapping = self.wrappings.get(wrap_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Token wrapping not found: {}", wrap_id)))?;
+        match wrapping.status {
+            WrappingStatus::Pending => {}
+            WrappingStatus::Completed => return Ok(wrapping.clone()),
+            WrappingStatus::Refunded => {
+                return Err(GarpError::ValidationError(format!("Token wrapping {} was refunded", wrap_id)));
+            }
+        }
+
+        let mut token = self.tokens.get(&wrapping.wrapped_token_id).cloned().unwrap_or_else(|| WrappedToken {
+            wrapped_token_id: wrapping.wrapped_token_id.clone(),
+            original_token: wrapping.original_token.clone(),
+            origin_domain: wrapping.origin_domain.clone(),
+            locked_supply: 0,
+            wrapped_supply: HashMap::new(),
+            updated_at: now,
+        });
+        // Wraps of the same token from two domains may both be pending
+        if token.origin_domain != wrapping.origin_domain {
+            return Err(GarpError::ValidationError(format!(
+                "{} is already wrapped from domain {}", token.original_token, token.origin_domain
+            )));
+        }
+        let minted = token.wrapped_supply.entry(wrapping.wrapped_domain.clone()).or_insert(0);
+        let supplies = match wrapping.kind {
+            WrappingKind::Wrap => token.locked_supply.checked_add(wrapping.amount).zip(minted.checked_add(wrapping.amount)),
+            WrappingKind::Unwrap => token.locked_supply.checked_sub(wrapping.amount).zip(minted.checked_sub(wrapping.amount)),
+        };
+        let Some((locked, wrapped)) = supplies else {
+            return Err(GarpError::Internal(format!("Token wrapping {} would overflow the supply of {}", wrap_id, token.wrapped_token_id)));
+        };
+        *minted = wrapped;
+        token.locked_supply = locked;
+        token.updated_at = now;
+        if token.total_wrapped_supply() > token.locked_supply {
+            return Err(GarpError::Internal(format!(
+                "Token wrapping {} would mint more {} than is locked", wrap_id, token.wrapped_token_id
+            )));
+        }
+
+        let wrapping = self.wrappings.get_mut(wrap_id).expect("wrapping looked up above");
+        wrapping.status = WrappingStatus::Completed;
+        wrapping.updated_at = now;
+        info!(
+            "{} {} {} between {} and {}",
+            if wrapping.kind == WrappingKind::Wrap { "Wrapped" } else { "Unwrapped" },
+            wrapping.amount, wrapping.original_token, wrapping.origin_domain, wrapping.wrapped_domain
+        );
+        self.tokens.insert(token.wrapped_token_id.clone(), token);
+        Ok(wrapping.clone())
+    }
+
+    /// Drop a wrapping whose swap was refunded or never submitted. Refunding
+    /// it again returns it unchanged.
+    pub fn refund(&mut self, wrap_id: &WrapId, now: DateTime<Utc>) -> GarpResult<TokenWrapping> {
+        let wrapping = self.wrappings.get_mut(wrap_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Token wrapping not found: {}", wrap_id)))?;
+        match wrapping.status {
+            WrappingStatus::Pending => {
+                wrapping.status = WrappingStatus::Refunded;
+                wrapping.updated_at = now;
+                Ok(wrapping.clone())
+            }
+            WrappingStatus::Refunded => Ok(wrapping.clone()),
+            WrappingStatus::Completed => {
+                Err(GarpError::ValidationError(format!("Token wrapping {} already completed", wrap_id)))
+            }
+        }
+    }
+
+    /// Get a wrapped token
+    pub fn get_token(&self, wrapped_token_id: &str) -> Option<WrappedToken> {
+        self.tokens.get(wrapped_token_id).cloned()
+    }
+
+    /// List wrapped tokens
+    pub fn list_tokens(&self) -> Vec<WrappedToken> {
+        self.tokens.values().cloned().collect()
+    }
+
+    /// Get a token wrapping
+    pub fn get_wrapping(&self, wrap_id: &WrapId) -> Option<TokenWrapping> {
+        self.wrappings.get(wrap_id).cloned()
+    }
+}
+
+/// Records token wrappings and settles them from the outcome of their swaps
+pub struct TokenWrapper {
+    /// Storage layer holding the wrapped token registry
+    storage: Arc<GlobalStorage>,
+}
+
+impl TokenWrapper {
+    /// Create new token wrapper
+    pub fn new(storage: Arc<GlobalStorage>) -> Self {
+        Self { storage }
+    }
+
+    /// Record a pending wrap of `amount` of `token` from `from_domain` to
+    /// `to_domain`, to be carried out by `transaction_id`
+    pub async fn wrap(
+        &self,
+        token: &str,
+        amount: u64,
+        from_domain: &DomainId,
+        to_domain: &DomainId,
+        transaction_id: TransactionId,
+    ) -> GarpResult<TokenWrapping> {
+        if token.is_empty() {
+            return Err(GarpError::ValidationError("Token to wrap is empty".to_string()));
+        }
+        if token.starts_with(WRAPPED_TOKEN_PREFIX) {
+            return Err(GarpError::ValidationError(format!("{} is already wrapped; unwrap it instead", token)));
+        }
+        let now = Utc::now();
+        let wrapping = TokenWrapping {
+            wrap_id: format!("wrap-{}", Uuid::new_v4()),
+            kind: WrappingKind::Wrap,
+            wrapped_token_id: wrapped_token_id(token),
+            original_token: token.to_string(),
+            origin_domain: from_domain.clone(),
+            wrapped_domain: to_domain.clone(),
+            amount,
+            status: WrappingStatus::Pending,
+            transaction_id,
+            created_at: now,
+            updated_at: now,
+        };
+        self.storage.begin_token_wrapping(wrapping.clone()).await?;
+        Ok(wrapping)
+    }
+
+    /// Record a pending unwrap of `amount` of `wrapped_token` held on
+    /// `to_domain`, releasing the original on its origin domain
+    pub async fn unwrap(
+        &self,
+        wrapped_token: &str,
+        amount: u64,
+        to_domain: &DomainId,
+        transaction_id: TransactionId,
+    ) -> GarpResult<TokenWrapping> {
+        let token = self.storage.get_wrapped_token(wrapped_token).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Wrapped token not found: {}", wrapped_token)))?;
+        let now = Utc::now();
+        let wrapping = TokenWrapping {
+            wrap_id: format!("unwrap-{}", Uuid::new_v4()),
+            kind: WrappingKind::Unwrap,
+            wrapped_token_id: token.wrapped_token_id,
+            original_token: token.original_token,
+            origin_domain: token.origin_domain,
+            wrapped_domain: to_domain.clone(),
+            amount,
+            status: WrappingStatus::Pending,
+            transaction_id,
+            created_at: now,
+            updated_at: now,
+        };
+        self.storage.begin_token_wrapping(wrapping.clone()).await?;
+        Ok(wrapping)
+    }
+
+    /// Drop a wrapping whose transaction was not accepted
+    pub async fn abandon(&self, wrap_id: &WrapId) -> GarpResult<TokenWrapping> {
+        self.storage.refund_token_wrapping(wrap_id, Utc::now()).await
+    }
+
+    /// Complete or refund the wrapping carried out by `swap` once the swap
+    /// is decided. Returns `None` for swaps that are not token wrappings.
+    pub async fn settle(&self, swap: &AtomicSwap) -> GarpResult<Option<TokenWrapping>> {
+        if self.storage.get_token_wrapping(&swap.swap_id).await?.is_none() {
+            return Ok(None);
+        }
+        let now = Utc::now();
+        match swap.status {
+            SwapStatus::AwaitingLocks => Ok(None),
+            SwapStatus::Revealed => self.storage.complete_token_wrapping(&swap.swap_id, now).await.map(Some),
+            SwapStatus::Refunded => self.storage.refund_token_wrapping(&swap.swap_id, now).await.map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GlobalSyncConfig;
+
+    async fn wrapper() -> (TokenWrapper, Arc<GlobalStorage>) {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config).await.unwrap());
+        (TokenWrapper::new(storage.clone()), storage)
+    }
+
+    #[tokio::test]
+    async fn test_wrapped_supply_never_exceeds_locked_supply() {
+        let (wrapper, storage) = wrapper().await;
+        let (a, b, c) = ("a".to_string(), "b".to_string(), "c".to_string());
+        let now = Utc::now();
+
+        assert!(wrapper.wrap("gold", 100, &a, &a, TransactionId::new()).await.is_err());
+        assert!(wrapper.wrap("wrapped_gold", 100, &a, &b, TransactionId::new()).await.is_err());
+        let wrap = wrapper.wrap("gold", 100, &a, &b, TransactionId::new()).await.unwrap();
+        assert!(matches!(
+            wrap.transaction_type(),
+            CrossDomainTransactionType::AtomicSwap { swap_id, asset_a, asset_b, amount_a: 100, amount_b: 100 }
+                if swap_id == wrap.wrap_id && asset_a == "gold" && asset_b == "wrapped_gold"
+        ));
+        // Nothing is minted until the swap is revealed
+        assert!(storage.get_wrapped_token("wrapped_gold").await.unwrap().is_none());
+        storage.complete_token_wrapping(&wrap.wrap_id, now).await.unwrap();
+        storage.complete_token_wrapping(&wrap.wrap_id, now).await.unwrap();
+        assert!(storage.refund_token_wrapping(&wrap.wrap_id, now).await.is_err());
+
+        let refunded = wrapper.wrap("gold", 50, &a, &c, TransactionId::new()).await.unwrap();
+        wrapper.abandon(&refunded.wrap_id).await.unwrap();
+        assert!(storage.complete_token_wrapping(&refunded.wrap_id, now).await.is_err());
+        assert!(wrapper.wrap("gold", 10, &b, &c, TransactionId::new()).await.is_err(), "gold is wrapped from a");
+
+        let token = storage.get_wrapped_token("wrapped_gold").await.unwrap().unwrap();
+        assert_eq!((token.original_token.as_str(), token.origin_domain.as_str()), ("gold", "a"));
+        assert_eq!((token.locked_supply, token.total_wrapped_supply()), (100, 100));
+
+        // Pending unwraps reserve the wrapped tokens they burn
+        assert!(wrapper.unwrap("wrapped_gold", 10, &c, TransactionId::new()).await.is_err());
+        let unwrap = wrapper.unwrap("wrapped_gold", 60, &b, TransactionId::new()).await.unwrap();
+        assert_eq!(unwrap.leg_domains(), (&b, &a));
+        assert!(wrapper.unwrap("wrapped_gold", 50, &b, TransactionId::new()).await.is_err());
+        let second = wrapper.unwrap("wrapped_gold", 40, &b, TransactionId::new()).await.unwrap();
+        wrapper.abandon(&second.wrap_id).await.unwrap();
+        storage.complete_token_wrapping(&unwrap.wrap_id, now).await.unwrap();
+
+        let token = storage.get_wrapped_token("wrapped_gold").await.unwrap().unwrap();
+        assert_eq!((token.locked_supply, token.total_wrapped_supply()), (40, 40));
+        assert_eq!(storage.list_wrapped_tokens().await.unwrap().len(), 1);
+    }
+}
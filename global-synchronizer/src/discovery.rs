@@ -0,0 +1,493 @@
+//! Sync domain discovery
+//!
+//! Domains come from `cross_domain.known_domains` and from runtime
+//! registrations. A registration lives for its TTL unless refreshed by a
+//! heartbeat or a repeated registration; configured domains never expire.
+//! Registrations are persisted so a restarted node remembers the fleet.
+//!
+//! A domain's public key is tracked by key epoch. Once known, it only
+//! changes through a [`KeyRotation`] signed by the current key; signatures
+//! are checked against the keys accepted at the time the message was made.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, RwLock};
+use tokio::time::interval;
+use tracing::{info, warn, error};
+
+use garp_common::{GarpError, GarpResult, KeyHistory, KeyRotation};
+
+use crate::config::GlobalSyncConfig;
+use crate::storage::{DomainId, GlobalStorage};
+
+/// Buffer of the discovery event channel
+const DISCOVERY_EVENT_CAPACITY: usize = 64;
+
+/// Registration request sent by a sync domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainAnnouncement {
+    /// Domain ID
+    pub domain_id: DomainId,
+
+    /// Endpoint the coordinator reaches the domain at
+    pub endpoint: String,
+
+    /// Domain public key
+    #[serde(default)]
+    pub public_key: Vec<u8>,
+
+    /// Domain capabilities
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+
+    /// Requested TTL; defaults to `discovery.registration_ttl_secs`
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+/// A known sync domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainRegistration {
+    /// Domain ID
+    pub domain_id: DomainId,
+
+    /// Endpoint
+    pub endpoint: String,
+
+    /// Current domain public key
+    pub public_key: Vec<u8>,
+
+    /// Keys the domain has signed with; empty until it announces a key
+    #[serde(default)]
+    pub key_history: KeyHistory,
+
+    /// Domain capabilities
+    pub capabilities: Vec<String>,
+
+    /// Lifetime after each refresh; `None` for configured domains
+    pub ttl_secs: Option<u64>,
+
+    /// First registration
+    pub registered_at: DateTime<Utc>,
+
+    /// Last registration or heartbeat
+    pub refreshed_at: DateTime<Utc>,
+}
+
+impl DomainRegistration {
+    /// Time the registration lapses without a refresh
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.ttl_secs.map(|ttl| self.refreshed_at + chrono::Duration::seconds(ttl as i64))
+    }
+
+    /// Lifetime left at `now`, `None` if the registration never expires
+    pub fn ttl_remaining(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.expires_at().map(|expires_at| (expires_at - now).to_std().unwrap_or_default())
+    }
+
+    /// Whether the registration has lapsed at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at().map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+/// Registration with its remaining lifetime
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisteredDomain {
+    #[serde(flatten)]
+    pub registration: DomainRegistration,
+
+    /// Seconds until expiry, `None` for configured domains
+    pub ttl_remaining_secs: Option<u64>,
+}
+
+/// Discovery table change
+#[derive(Debug, Clone)]
+pub enum DiscoveryEvent {
+    /// A domain registered or re-registered
+    DomainRegistered(DomainRegistration),
+
+    /// A registration lapsed and the domain was forgotten
+    DomainExpired(DomainId),
+}
+
+/// Discovery table of sync domains
+pub struct DomainDiscovery {
+    /// Configuration
+    config: Arc<GlobalSyncConfig>,
+
+    /// Known domains
+    domains: Arc<RwLock<HashMap<DomainId, DomainRegistration>>>,
+
+    /// Persistence for runtime registrations
+    storage: Option<Arc<GlobalStorage>>,
+
+    /// Discovery events
+    event_tx: broadcast::Sender<DiscoveryEvent>,
+}
+
+impl DomainDiscovery {
+    /// Create a discovery table holding the configured domains
+    pub async fn new(config: Arc<GlobalSyncConfig>) -> GarpResult<Self> {
+        let now = Utc::now();
+        let domains = config.cross_domain.known_domains.iter()
+            .map(|domain| {
+                let registration = DomainRegistration {
+                    domain_id: domain.domain_id.clone(),
+                    endpoint: domain.endpoints.first().cloned().unwrap_or_default(),
+                    public_key: domain.public_key.clone(),
+                    key_history: Self::initial_keys(&domain.public_key),
+                    capabilities: domain.capabilities.clone(),
+                    ttl_secs: None,
+                    registered_at: now,
+                    refreshed_at: now,
+                };
+                (domain.domain_id.clone(), registration)
+            })
+            .collect();
+
+        Ok(Self {
+            config,
+            domains: Arc::new(RwLock::new(domains)),
+            storage: None,
+            event_tx: broadcast::channel(DISCOVERY_EVENT_CAPACITY).0,
+        })
+    }
+
+    /// Create a discovery table that persists registrations to `storage`
+    /// and reloads those still live from earlier runs
+    pub async fn with_storage(config: Arc<GlobalSyncConfig>, storage: Arc<GlobalStorage>) -> GarpResult<Self> {
+        let mut discovery = Self::new(config).await?;
+
+        let now = Utc::now();
+        let mut restored = 0;
+        {
+            let mut domains = discovery.domains.write().await;
+            for registration in storage.list_domain_registrations().await? {
+                if registration.is_expired(now) {
+                    storage.remove_domain_registration(&registration.domain_id).await?;
+                    continue;
+                }
+                match domains.get_mut(&registration.domain_id) {
+                    // Configuration wins, but rotations of the configured key are kept
+                    Some(configured) => {
+                        let configured_key = configured.key_history.epochs().first().map(|epoch| &epoch.public_key);
+                        if configured_key == registration.key_history.epochs().first().map(|epoch| &epoch.public_key) {
+                            configured.public_key = registration.public_key;
+                            configured.key_history = registration.key_history;
+                        }
+                    }
+                    None => {
+                        domains.insert(registration.domain_id.clone(), registration);
+                    }
+                }
+                restored += 1;
+            }
+        }
+        if restored > 0 {
+            info!("Restored {} domain registrations", restored);
+        }
+
+        discovery.storage = Some(storage);
+        Ok(discovery)
+    }
+
+    /// Start expiring lapsed registrations
+    pub async fn start(&self) -> GarpResult<()> {
+        let domains = self.domains.clone();
+        let storage = self.storage.clone();
+        let event_tx = self.event_tx.clone();
+        let check_interval = Duration::from_millis(self.config.cross_domain.discovery.expiry_check_interval_ms);
+
+        tokio::spawn(async move {
+            let mut interval = interval(check_interval);
+
+            loop {
+                interval.tick().await;
+                Self::expire_registrations(&domains, &storage, &event_tx, Utc::now()).await;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Insert or refresh a domain registration
+    pub async fn register(&self, announcement: DomainAnnouncement) -> GarpResult<DomainRegistration> {
+        if announcement.domain_id.is_empty() || announcement.endpoint.is_empty() {
+            return Err(GarpError::ValidationError("Domain ID and endpoint are required".to_string()));
+        }
+        let ttl_secs = announcement.ttl_secs.unwrap_or(self.config.cross_domain.discovery.registration_ttl_secs);
+        if ttl_secs == 0 {
+            return Err(GarpError::ValidationError("Registration TTL must be > 0".to_string()));
+        }
+
+        let now = Utc::now();
+        let registration = {
+            let mut domains = self.domains.write().await;
+            let existing = domains.get(&announcement.domain_id);
+            if existing.is_none() && domains.len() >= self.config.cross_domain.discovery.max_discovered_domains {
+                return Err(GarpError::ValidationError(format!(
+                    "Discovery table is full ({} domains)", domains.len()
+                )));
+            }
+
+            // A known key only changes through a signed rotation
+            let (public_key, key_history) = match existing {
+                Some(r) if !r.key_history.is_empty() => {
+                    if !announcement.public_key.is_empty() && announcement.public_key != r.public_key {
+                        return Err(GarpError::ValidationError(format!(
+                            "Domain {} cannot change its key by registering; announce a key rotation",
+                            announcement.domain_id
+                        )));
+                    }
+                    (r.public_key.clone(), r.key_history.clone())
+                }
+                _ => (announcement.public_key.clone(), Self::initial_keys(&announcement.public_key)),
+            };
+
+            let registration = DomainRegistration {
+                domain_id: announcement.domain_id.clone(),
+                endpoint: announcement.endpoint,
+                public_key,
+                key_history,
+                capabilities: announcement.capabilities,
+                // Configured domains stay permanent
+                ttl_secs: existing.map_or(Some(ttl_secs), |r| r.ttl_secs.map(|_| ttl_secs)),
+                registered_at: existing.map_or(now, |r| r.registered_at),
+                refreshed_at: now,
+            };
+            domains.insert(registration.domain_id.clone(), registration.clone());
+            registration
+        };
+
+        if let Some(storage) = &self.storage {
+            storage.store_domain_registration(registration.clone()).await?;
+        }
+        info!("Registered domain {} at {}", registration.domain_id, registration.endpoint);
+        let _ = self.event_tx.send(DiscoveryEvent::DomainRegistered(registration.clone()));
+        Ok(registration)
+    }
+
+    /// Refresh the TTL of a registered domain
+    pub async fn heartbeat(&self, domain_id: &DomainId) -> GarpResult<DomainRegistration> {
+        let registration = {
+            let mut domains = self.domains.write().await;
+            let registration = domains.get_mut(domain_id)
+                .ok_or_else(|| GarpError::NotFound(format!("Domain {} is not registered", domain_id)))?;
+            registration.refreshed_at = Utc::now();
+            registration.clone()
+        };
+
+        if let Some(storage) = &self.storage {
+            storage.store_domain_registration(registration.clone()).await?;
+        }
+        Ok(registration)
+    }
+
+    /// Apply a key rotation announced by a domain, signed with its current key
+    pub async fn apply_key_rotation(&self, rotation: &KeyRotation) -> GarpResult<DomainRegistration> {
+        let registration = {
+            let mut domains = self.domains.write().await;
+            let registration = domains.get_mut(&rotation.node_id)
+                .ok_or_else(|| GarpError::NotFound(format!("Domain {} is not registered", rotation.node_id)))?;
+            registration.key_history.apply_rotation(rotation)?;
+            registration.public_key = rotation.public_key.clone();
+            registration.clone()
+        };
+
+        if let Some(storage) = &self.storage {
+            storage.store_domain_registration(registration.clone()).await?;
+        }
+        info!("Domain {} rotated to key epoch {}", rotation.node_id, rotation.epoch);
+        Ok(registration)
+    }
+
+    /// Whether `signature` over `message`, made at `at`, is by a key of
+    /// `domain_id` accepted at that time
+    pub async fn verify_domain_signature(
+        &self,
+        domain_id: &DomainId,
+        message: &[u8],
+        signature: &[u8],
+        at: DateTime<Utc>,
+    ) -> GarpResult<bool> {
+        let domains = self.domains.read().await;
+        let registration = domains.get(domain_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Domain {} is not registered", domain_id)))?;
+        Ok(registration.key_history.verify(message, signature, at).is_some())
+    }
+
+    fn initial_keys(public_key: &[u8]) -> KeyHistory {
+        if public_key.is_empty() {
+            KeyHistory::default()
+        } else {
+            KeyHistory::new(public_key.to_vec())
+        }
+    }
+
+    /// Forget registrations lapsed at `now`, returning their domain IDs
+    pub async fn expire(&self, now: DateTime<Utc>) -> Vec<DomainId> {
+        Self::expire_registrations(&self.domains, &self.storage, &self.event_tx, now).await
+    }
+
+    async fn expire_registrations(
+        domains: &Arc<RwLock<HashMap<DomainId, DomainRegistration>>>,
+        storage: &Option<Arc<GlobalStorage>>,
+        event_tx: &broadcast::Sender<DiscoveryEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<DomainId> {
+        let expired: Vec<DomainId> = {
+            let mut domains = domains.write().await;
+            let expired: Vec<DomainId> = domains.values()
+                .filter(|registration| registration.is_expired(now))
+                .map(|registration| registration.domain_id.clone())
+                .collect();
+            for domain_id in &expired {
+                domains.remove(domain_id);
+            }
+            expired
+        };
+
+        for domain_id in &expired {
+            warn!("Registration of domain {} expired", domain_id);
+            if let Some(storage) = storage {
+                if let Err(e) = storage.remove_domain_registration(domain_id).await {
+                    error!("Failed to remove registration of domain {}: {}", domain_id, e);
+                }
+            }
+            let _ = event_tx.send(DiscoveryEvent::DomainExpired(domain_id.clone()));
+        }
+        expired
+    }
+
+    /// All known domains
+    pub async fn get_discovered_domains(&self) -> Vec<DomainRegistration> {
+        self.domains.read().await.values().cloned().collect()
+    }
+
+    /// All known domains with their remaining TTL, ordered by domain ID
+    pub async fn list_registered_domains(&self) -> Vec<RegisteredDomain> {
+        let now = Utc::now();
+        let mut registered: Vec<RegisteredDomain> = self.domains.read().await.values()
+            .map(|registration| RegisteredDomain {
+                ttl_remaining_secs: registration.ttl_remaining(now).map(|ttl| ttl.as_secs()),
+                registration: registration.clone(),
+            })
+            .collect();
+        registered.sort_by(|a, b| a.registration.domain_id.cmp(&b.registration.domain_id));
+        registered
+    }
+
+    /// Subscribe to registrations and expiries
+    pub fn subscribe(&self) -> broadcast::Receiver<DiscoveryEvent> {
+        self.event_tx.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn announcement(domain_id: &str, ttl_secs: u64) -> DomainAnnouncement {
+        DomainAnnouncement {
+            domain_id: domain_id.to_string(),
+            endpoint: format!("{}.example:9000", domain_id),
+            public_key: vec![1; 32],
+            capabilities: vec!["asset_transfer".to_string()],
+            ttl_secs: Some(ttl_secs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registration_expires_without_heartbeat() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let discovery = DomainDiscovery::new(config).await.unwrap();
+        let mut events = discovery.subscribe();
+
+        let registration = discovery.register(announcement("domain-a", 30)).await.unwrap();
+        assert!(matches!(events.recv().await.unwrap(), DiscoveryEvent::DomainRegistered(r) if r.domain_id == "domain-a"));
+        let listed = discovery.list_registered_domains().await;
+        assert!(listed.iter().any(|d| d.registration.domain_id == "domain-a" && d.ttl_remaining_secs.unwrap() <= 30));
+
+        // A heartbeat pushes expiry out
+        let refreshed = discovery.heartbeat(&"domain-a".to_string()).await.unwrap();
+        assert!(refreshed.expires_at() >= registration.expires_at());
+        assert!(discovery.expire(Utc::now() + chrono::Duration::seconds(20)).await.is_empty());
+
+        let expired = discovery.expire(Utc::now() + chrono::Duration::seconds(31)).await;
+        assert_eq!(expired, vec!["domain-a".to_string()]);
+        assert!(matches!(events.recv().await.unwrap(), DiscoveryEvent::DomainExpired(id) if id == "domain-a"));
+        assert!(discovery.heartbeat(&"domain-a".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_registrations_survive_restart() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+
+        let discovery = DomainDiscovery::with_storage(config.clone(), storage.clone()).await.unwrap();
+        discovery.register(announcement("domain-a", 300)).await.unwrap();
+        assert!(discovery.register(announcement("", 300)).await.is_err());
+        assert!(discovery.register(announcement("domain-b", 0)).await.is_err());
+
+        let restarted = DomainDiscovery::with_storage(config, storage).await.unwrap();
+        let domains = restarted.get_discovered_domains().await;
+        let restored = domains.iter().find(|d| d.domain_id == "domain-a").unwrap();
+        assert_eq!(restored.endpoint, "domain-a.example:9000");
+        assert_eq!(restored.ttl_secs, Some(300));
+    }
+
+    #[tokio::test]
+    async fn test_domain_key_rotation() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let discovery = DomainDiscovery::with_storage(config.clone(), storage.clone()).await.unwrap();
+        let old_key = SigningKey::from_bytes(&[3; 32]);
+        let new_key = SigningKey::from_bytes(&[4; 32]);
+        let mut registration = announcement("domain-a", 300);
+        registration.public_key = old_key.verifying_key().to_bytes().to_vec();
+        discovery.register(registration.clone()).await.unwrap();
+
+        let mut rekeyed = registration.clone();
+        rekeyed.public_key = new_key.verifying_key().to_bytes().to_vec();
+        assert!(discovery.register(rekeyed.clone()).await.is_err());
+
+        let rotated_at = Utc::now();
+        let mut rotation = KeyRotation {
+            node_id: "domain-a".to_string(),
+            epoch: 1,
+            previous_public_key: registration.public_key.clone(),
+            public_key: rekeyed.public_key.clone(),
+            effective_at: rotated_at,
+            overlap_secs: 60,
+            signature: Vec::new(),
+        };
+        rotation.signature = new_key.sign(&rotation.signing_message()).to_bytes().to_vec();
+        assert!(discovery.apply_key_rotation(&rotation).await.is_err());
+        rotation.signature = old_key.sign(&rotation.signing_message()).to_bytes().to_vec();
+        discovery.apply_key_rotation(&rotation).await.unwrap();
+        discovery.register(rekeyed).await.unwrap();
+
+        let domain_id = "domain-a".to_string();
+        let message = b"state root";
+        let old_sig = old_key.sign(message).to_bytes();
+        let new_sig = new_key.sign(message).to_bytes();
+        let during = rotated_at + chrono::Duration::seconds(30);
+        let after = rotated_at + chrono::Duration::seconds(61);
+        assert!(discovery.verify_domain_signature(&domain_id, message, &old_sig, during).await.unwrap());
+        assert!(discovery.verify_domain_signature(&domain_id, message, &new_sig, during).await.unwrap());
+        assert!(!discovery.verify_domain_signature(&domain_id, message, &old_sig, after).await.unwrap());
+        assert!(discovery.verify_domain_signature(&domain_id, message, &new_sig, after).await.unwrap());
+
+        // Rotations are persisted with the registration
+        let restarted = DomainDiscovery::with_storage(config, storage).await.unwrap();
+        assert!(!restarted.verify_domain_signature(&domain_id, message, &old_sig, after).await.unwrap());
+        assert!(restarted.verify_domain_signature(&domain_id, message, &new_sig, after).await.unwrap());
+    }
+}
@@ -1,6 +1,17 @@
+//! gRPC interface of the global synchronizer.
+//!
+//! The service is defined in `global-synchronizer/proto/garp.proto` (package
+//! `garp`, service `GlobalSynchronizer`); clients should generate stubs from
+//! that file. The same service is served as native gRPC by
+//! [`start_grpc_server`] and as gRPC-Web under `/grpc-web` on the REST API
+//! server for browser clients.
+
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tonic::{transport::Server, Request, Response, Status};
+use tonic_web::{GrpcWebLayer, GrpcWebService};
+use tower::Layer;
+use tower_http::cors::{Any, Cors, CorsLayer};
 use tracing::{info, error};
 
 use crate::{GlobalSynchronizer, bridge::{BridgeTransactionStatus, AssetMapping}};
@@ -278,4 +289,19 @@ pub async fn start_grpc_server(
         .await?;
         
     Ok(())
-}
\ No newline at end of file
+}
+
+/// gRPC-Web wrapper around the synchronizer service, for mounting on the
+/// REST API router. CORS is permissive so browser frontends on other origins
+/// can reach it; authentication is still enforced by the router's middleware.
+pub fn grpc_web_service(
+    synchronizer: Arc<GlobalSynchronizer>,
+) -> Cors<GrpcWebService<global_synchronizer_server::GlobalSynchronizerServer<GlobalSynchronizerService>>> {
+    let server = global_synchronizer_server::GlobalSynchronizerServer::new(GlobalSynchronizerService::new(synchronizer));
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_headers(Any)
+        .expose_headers(Any);
+
+    cors.layer(GrpcWebLayer::new().layer(server))
+}
@@ -7,7 +7,7 @@
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::RwLock;
-use tracing::{info, error, warn};
+use tracing::{info, error, warn, instrument};
 use garp_common::{
     config::GlobalSyncConfig,
     types::*,
@@ -15,19 +15,30 @@ use garp_common::{
 };
 
 pub mod api;
+pub mod block_producer;
+pub mod compliance;
+pub mod compression;
 pub mod config;
 pub mod consensus;
 pub mod consensus_example;
 pub mod cross_domain;
 pub mod discovery;
+pub mod logging;
+pub mod mempool;
 pub mod network;
+pub mod node_keys;
+pub mod receipt;
 pub mod security;
 pub mod settlement;
+pub mod state_root;
 pub mod storage;
 pub mod synchronizer;
+pub mod timelock;
 pub mod validator;
 pub mod bridge;
 pub mod grpc;
+pub mod wire;
+pub mod ws;
 
 // Re-export for convenience
 pub use synchronizer::GlobalSynchronizer;
@@ -40,6 +51,7 @@ use settlement::SettlementEngine;
 use network::{NetworkManager, NetworkTopology};
 use storage::GlobalStorage;
 use crate::discovery::DomainDiscovery;
+use crate::mempool::DependencyEvictionTask;
 use serde_json::json;
 
 /// Main Global Synchronizer service
@@ -53,6 +65,7 @@ pub struct GlobalSynchronizer {
     is_running: Arc<RwLock<bool>>,
     metrics: Arc<GlobalSyncMetrics>,
     mempool: Arc<RwLock<Vec<TransactionId>>>,
+    dependency_eviction: Arc<DependencyEvictionTask>,
 }
 
 /// Global synchronizer metrics
@@ -112,7 +125,7 @@ impl GlobalSynchronizer {
         ).await?);
         
         // Initialize cross-domain coordinator
-        let domain_discovery = Arc::new(DomainDiscovery::new(config.clone()).await?);
+        let domain_discovery = Arc::new(DomainDiscovery::with_storage(config.clone(), storage.clone()).await?);
         let cross_domain_coordinator = Arc::new(CrossDomainCoordinator::new(
             config.clone(),
             storage.clone(),
@@ -123,6 +136,14 @@ impl GlobalSynchronizer {
         
         let metrics = Arc::new(GlobalSyncMetrics::default());
         
+        let mempool = Arc::new(RwLock::new(Vec::new()));
+        let dependency_eviction = Arc::new(DependencyEvictionTask::new(
+            storage.clone(),
+            mempool.clone(),
+            cross_domain_coordinator.event_sender(),
+            std::time::Duration::from_secs(config.mempool.max_dependency_staleness_secs),
+        ));
+        
         Ok(Self {
             config,
             consensus_engine,
@@ -132,7 +153,8 @@ impl GlobalSynchronizer {
             storage,
             is_running: Arc::new(RwLock::new(false)),
             metrics,
-            mempool: Arc::new(RwLock::new(Vec::new())),
+            mempool,
+            dependency_eviction,
         })
     }
     
@@ -162,6 +184,9 @@ impl GlobalSynchronizer {
         self.cross_domain_coordinator.start().await?;
         info!("Cross-domain coordinator started");
         
+        self.dependency_eviction.clone().spawn();
+        info!("Dependency eviction task started");
+        
         // Start metrics collection
         self.start_metrics_collection().await?;
         
@@ -209,13 +234,14 @@ impl GlobalSynchronizer {
     }
     
     /// Submit a cross-domain transaction
+    #[instrument(skip_all, fields(correlation_id = %transaction.transaction_id))]
     pub async fn submit_transaction(&self, transaction: CrossDomainTransaction) -> GarpResult<TransactionId> {
         let running = self.is_running.read().await;
         if !*running {
             return Err(GarpError::ServiceNotRunning("Global Synchronizer not running".to_string()));
         }
         
-        info!("Submitting cross-domain transaction: {:?}", transaction.transaction_id);
+        info!(transaction_id = %transaction.transaction_id, source_domain = %transaction.source_domain, "Submitting cross-domain transaction");
         
         // Update metrics
         {
@@ -227,15 +253,25 @@ impl GlobalSynchronizer {
         let result = self.cross_domain_coordinator.submit_transaction(transaction.clone()).await;
         
         match &result {
-            Ok(tid) => {
-                info!("Transaction submitted successfully");
+            Ok(cross_domain::SubmissionOutcome::AlreadySubmitted(tid)) => {
+                info!(transaction_id = %tid, "Transaction was already submitted");
+            }
+            Ok(cross_domain::SubmissionOutcome::Submitted(tid)) => {
+                info!(transaction_id = %tid, source_domain = %transaction.source_domain, "Transaction submitted");
                 // Track in mempool
                 let mut mp = self.mempool.write().await;
                 mp.push(tid.clone());
 
-                // Normalize and persist transaction payload as garp_common::Transaction JSON in storage
+                // Normalize and persist transaction payload as garp_common::Transaction in storage
                 let common_tx = Self::convert_to_common_transaction(&transaction);
-                let serialized = serde_json::to_vec(&common_tx).unwrap_or_default();
+                let format = self.config.api.serialization_format;
+                let serialized = match format.encode(&common_tx) {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        warn!("Failed to encode submitted transaction as {:?}: {}", format, e);
+                        Vec::new()
+                    }
+                };
                 let now = std::time::SystemTime::now();
                 let stored = storage::StoredTransaction {
                     transaction_id: tid.clone(),
@@ -265,7 +301,16 @@ impl GlobalSynchronizer {
                     updated_at: now,
                     block_height: None,
                     block_hash: None,
-                    metadata: std::collections::HashMap::new(),
+                    metadata: [("encoding".to_string(), format.content_type().to_string())]
+                        .into_iter()
+                        .chain(transaction.content_hash().ok().into_iter().flat_map(|hash| [
+                            (cross_domain::CONTENT_HASH_METADATA_KEY.to_string(), hex::encode(hash)),
+                            (
+                                cross_domain::CONTENT_HASH_ENCODING_METADATA_KEY.to_string(),
+                                garp_common::canonical::CANONICAL_ENCODING.to_string(),
+                            ),
+                        ]))
+                        .collect(),
                     dependencies: transaction.dependencies.clone(),
                     dependents: Vec::new(),
                 };
@@ -280,7 +325,7 @@ impl GlobalSynchronizer {
             }
         }
         
-        result
+        result.map(|outcome| outcome.transaction_id().clone())
     }
     
     /// Get transaction status
@@ -303,10 +348,55 @@ impl GlobalSynchronizer {
         self.consensus_engine.get_current_view().await
     }
     
+    /// Get recorded view changes into views `from_view..=to_view`
+    pub async fn get_view_changes(&self, from_view: u64, to_view: u64) -> GarpResult<Vec<storage::ViewChangeRecord>> {
+        self.consensus_engine.get_view_changes(from_view, to_view).await
+    }
+    
+    /// Turn settlement batching on or off at runtime
+    pub async fn set_settlement_batching(&self, enabled: bool) {
+        self.settlement_engine.set_batching_enabled(enabled).await;
+    }
+    
+    /// Whether settlement batching is enabled
+    pub fn is_settlement_batching_enabled(&self) -> bool {
+        self.settlement_engine.is_batching_enabled()
+    }
+    
+    /// Get the settlement proof of a transaction
+    pub async fn get_settlement_proof(&self, transaction_id: &TransactionId) -> GarpResult<Option<garp_common::settlement::SettlementProof>> {
+        self.settlement_engine.get_settlement_proof(transaction_id).await
+    }
+    
+    /// Get a settlement rollback
+    pub async fn get_settlement_rollback(&self, rollback_id: &str) -> GarpResult<Option<storage::RollbackRecord>> {
+        self.settlement_engine.get_rollback(rollback_id).await
+    }
+    
+    /// List settlement rollbacks, optionally only those in `status`
+    pub async fn list_settlement_rollbacks(&self, status: Option<storage::RollbackStatus>) -> GarpResult<Vec<storage::RollbackRecord>> {
+        self.settlement_engine.list_rollbacks(status).await
+    }
+    
     /// Get network topology
     pub async fn get_network_topology(&self) -> GarpResult<NetworkTopology> {
         self.network_manager.get_network_topology().await
     }
+    
+    /// Peers with their connection health
+    pub async fn list_peers(&self) -> Vec<network::PeerStatus> {
+        self.network_manager.list_peers().await
+    }
+
+    /// Known domains with their remaining registration TTL
+    pub async fn list_registered_domains(&self) -> Vec<discovery::RegisteredDomain> {
+        self.cross_domain_coordinator.list_registered_domains().await
+    }
+
+    /// Network-wide message metrics
+    pub async fn get_network_metrics(&self) -> network::NetworkMetrics {
+        self.network_manager.get_metrics().await
+    }
 
     /// Get mempool transaction IDs
     pub async fn get_mempool(&self) -> Vec<String> {
@@ -324,16 +414,21 @@ impl GlobalSynchronizer {
         self.consensus_engine.list_validators().await
     }
 
-    /// Validator management: add validator
-    pub async fn add_validator(&self, v: crate::validator::ValidatorInfo) -> GarpResult<()> {
+    /// Validator management: add validator at the next epoch boundary
+    pub async fn add_validator(&self, v: crate::validator::ValidatorInfo) -> GarpResult<u64> {
         self.consensus_engine.add_validator(v).await
     }
 
-    /// Validator management: remove validator
-    pub async fn remove_validator(&self, id: ParticipantId) -> GarpResult<()> {
+    /// Validator management: remove validator at the next epoch boundary
+    pub async fn remove_validator(&self, id: ParticipantId) -> GarpResult<u64> {
         self.consensus_engine.remove_validator(id).await
     }
 
+    /// Validator management: update validator stake at the next epoch boundary
+    pub async fn update_validator_stake(&self, id: ParticipantId, stake: u64) -> GarpResult<u64> {
+        self.consensus_engine.update_validator_stake(id, stake).await
+    }
+
     /// Validator management: update validator status
     pub async fn update_validator_status(&self, id: ParticipantId, status: crate::validator::ValidatorStatus) -> GarpResult<()> {
         self.consensus_engine.update_validator_status(id, status).await
@@ -400,6 +495,7 @@ impl GlobalSynchronizer {
                 message: "Service is not running".to_string(),
                 components: Vec::new(),
                 timestamp: std::time::SystemTime::now(),
+                maintenance: None,
             });
         }
         
@@ -450,6 +546,7 @@ impl GlobalSynchronizer {
             message: "All components are operational".to_string(),
             components,
             timestamp: std::time::SystemTime::now(),
+            maintenance: None,
         })
     }
     
@@ -481,16 +578,30 @@ impl GlobalSynchronizer {
 }
 
 /// Service health status
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ServiceHealth {
     pub status: HealthStatus,
     pub message: String,
     pub components: Vec<ComponentHealth>,
     pub timestamp: std::time::SystemTime,
+    /// Set while transaction intake is paused
+    pub maintenance: Option<MaintenanceMode>,
+}
+
+/// Transaction intake paused for maintenance. Reads, status queries and
+/// transactions already in flight are unaffected.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MaintenanceMode {
+    /// Why intake is paused, reported to rejected submitters
+    pub reason: String,
+    /// Who enabled maintenance mode
+    pub set_by: String,
+    /// When maintenance mode was enabled
+    pub since: chrono::DateTime<chrono::Utc>,
 }
 
 /// Component health status
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ComponentHealth {
     pub name: String,
     pub status: HealthStatus,
@@ -498,8 +609,20 @@ pub struct ComponentHealth {
     pub metrics: HashMap<String, f64>,
 }
 
+impl ComponentHealth {
+    /// Health of a component that failed its check
+    pub fn down(name: &str, error: impl std::fmt::Display) -> Self {
+        Self {
+            name: name.to_string(),
+            status: HealthStatus::Down,
+            message: error.to_string(),
+            metrics: HashMap::new(),
+        }
+    }
+}
+
 /// Health status enumeration
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub enum HealthStatus {
     Up,
     Down,
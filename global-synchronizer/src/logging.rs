@@ -0,0 +1,188 @@
+//! Log output formats
+//!
+//! Text output uses the `tracing_subscriber` formatter. JSON output, for log
+//! shippers such as ELK or Loki, writes one object per line with the
+//! event's `timestamp`, `level`, `target`, innermost `span` and every named
+//! field at the top level.
+
+use std::io::Write;
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Keys the layer writes itself, which event fields cannot replace
+const RESERVED_KEYS: [&str; 4] = ["timestamp", "level", "target", "span"];
+
+/// Writes every event as a single-line JSON object
+pub struct JsonFormatterLayer<W> {
+    make_writer: W,
+}
+
+impl JsonFormatterLayer<fn() -> std::io::Stdout> {
+    /// JSON lines on standard output
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout)
+    }
+}
+
+impl<W> JsonFormatterLayer<W>
+where
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    pub fn new(make_writer: W) -> Self {
+        Self { make_writer }
+    }
+}
+
+/// Fields of a span, recorded when it is created or updated
+struct SpanFields(Map<String, Value>);
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::String(format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        // NaN and infinities have no JSON number
+        let value = serde_json::Number::from_f64(value).map(Value::Number).unwrap_or_else(|| Value::String(value.to_string()));
+        self.0.insert(field.name().to_string(), value);
+    }
+}
+
+impl<S, W> Layer<S> for JsonFormatterLayer<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = Map::new();
+        attrs.record(&mut JsonVisitor(&mut fields));
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut JsonVisitor(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut object = Map::new();
+        event.record(&mut JsonVisitor(&mut object));
+        for key in RESERVED_KEYS {
+            object.remove(key);
+        }
+
+        object.insert(
+            "timestamp".to_string(),
+            Value::String(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true)),
+        );
+        object.insert("level".to_string(), Value::String(metadata.level().as_str().to_string()));
+        object.insert("target".to_string(), Value::String(metadata.target().to_string()));
+        if let Some(span) = ctx.event_span(event) {
+            let mut span_object = span.extensions().get::<SpanFields>()
+                .map(|SpanFields(fields)| fields.clone())
+                .unwrap_or_default();
+            span_object.insert("name".to_string(), Value::String(span.name().to_string()));
+            object.insert("span".to_string(), Value::Object(span_object));
+        }
+
+        let Ok(mut line) = serde_json::to_vec(&object) else { return };
+        line.push(b'\n');
+        let _ = self.make_writer.make_writer_for(metadata).write_all(&line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::{info, info_span, warn};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_json_output_is_one_object_per_line() {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry().with(JsonFormatterLayer::new(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            info!(transaction_id = "tx-1", source_domain = "domain-a", amount = 100u64, "Transaction submitted");
+            let span = info_span!("settlement", correlation_id = "tx-1");
+            let _entered = span.enter();
+            warn!(retry = true, "Settlement \"delayed\"\nretrying");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output.lines()
+            .map(|line| serde_json::from_str(line).expect("log line is not valid JSON"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        let submitted = &lines[0];
+        assert!(submitted["timestamp"].is_string());
+        assert_eq!(submitted["level"], "INFO");
+        assert_eq!(submitted["target"], module_path!());
+        assert_eq!(submitted["message"], "Transaction submitted");
+        assert_eq!(submitted["transaction_id"], "tx-1");
+        assert_eq!(submitted["source_domain"], "domain-a");
+        assert_eq!(submitted["amount"], 100);
+        assert!(submitted.get("span").is_none());
+
+        let delayed = &lines[1];
+        assert_eq!(delayed["level"], "WARN");
+        assert_eq!(delayed["message"], "Settlement \"delayed\"\nretrying");
+        assert_eq!(delayed["retry"], true);
+        assert_eq!(delayed["span"]["name"], "settlement");
+        assert_eq!(delayed["span"]["correlation_id"], "tx-1");
+    }
+}
@@ -1,9 +1,49 @@
-use clap::{Arg, Command};
-use global_synchronizer::{GlobalSynchronizer, config::GlobalSyncConfig, api::create_router, consensus_example};
+use clap::{Arg, ArgMatches, Command};
+use global_synchronizer::{GlobalSynchronizer, config::{GlobalSyncConfig, LogFormat}, api::create_router, consensus_example};
+use global_synchronizer::logging::JsonFormatterLayer;
+use garp_common::{GarpError, GarpResult};
 use std::sync::Arc;
 use axum::Router;
-use tracing::{info, error};
-use std::path::PathBuf;
+use tracing::{info, warn, error};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
+use std::path::{Path, PathBuf};
+
+/// Load the configuration file, or defaults if it is missing, with command
+/// line arguments applied on top
+fn load_config(config_path: &Path, matches: &ArgMatches) -> GarpResult<GlobalSyncConfig> {
+    let mut config = if config_path.exists() {
+        GlobalSyncConfig::load(config_path)?
+    } else {
+        GlobalSyncConfig::default()
+    };
+
+    // Override configuration with command line arguments
+    config.node.node_id = matches.get_one::<String>("node-id").unwrap().clone();
+    
+    if let Some(peers) = matches.get_one::<String>("cluster-peers") {
+        config.consensus.cluster_peers = peers
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .collect();
+    }
+    
+    if let Some(brokers) = matches.get_one::<String>("kafka-brokers") {
+        config.kafka.bootstrap_servers = brokers
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .collect();
+    }
+    
+    let port = |name: &str| {
+        matches.get_one::<String>(name).unwrap().parse::<u16>()
+            .map_err(|e| GarpError::ConfigError(format!("Invalid --{}: {}", name, e)))
+    };
+    config.database.url = matches.get_one::<String>("database-url").unwrap().clone();
+    config.api.port = port("port")?;
+    config.consensus.port = port("consensus-port")?;
+    config.monitoring.enable_metrics = matches.get_flag("enable-metrics");
+    Ok(config)
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -82,51 +122,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .get_matches();
 
-    // Initialize tracing
+    // Load configuration
+    let config_path = PathBuf::from(matches.get_one::<String>("config").unwrap());
+    let config = load_config(&config_path, &matches)?;
+
+    // Initialize tracing in the configured format, with a reloadable filter
     let log_level = matches.get_one::<String>("log-level").unwrap();
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(log_level));
-    
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true)
-        .init();
-
-    info!("Starting Global Synchronizer v0.1.0");
-
-    // Load configuration
-    let config_path = PathBuf::from(matches.get_one::<String>("config").unwrap());
-    let mut config = if config_path.exists() {
-        GlobalSyncConfig::load(&config_path)?
-    } else {
-        info!("Configuration file not found, using default configuration");
-        GlobalSyncConfig::default()
+    let (filter, log_filter) = tracing_subscriber::reload::Layer::new(filter);
+    let output = match config.monitoring.logging.format {
+        LogFormat::Json => JsonFormatterLayer::stdout().boxed(),
+        LogFormat::Compact => tracing_subscriber::fmt::layer().compact().with_target(false).boxed(),
+        LogFormat::Pretty => tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_thread_ids(true)
+            .with_file(true)
+            .with_line_number(true)
+            .boxed(),
     };
+    tracing_subscriber::registry().with(filter).with(output).init();
 
-    // Override configuration with command line arguments
-    config.node.node_id = matches.get_one::<String>("node-id").unwrap().clone();
-    
-    if let Some(peers) = matches.get_one::<String>("cluster-peers") {
-        config.consensus.cluster_peers = peers
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect();
-    }
-    
-    if let Some(brokers) = matches.get_one::<String>("kafka-brokers") {
-        config.kafka.bootstrap_servers = brokers
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect();
+    info!("Starting Global Synchronizer v0.1.0");
+    if !config_path.exists() {
+        info!(config_path = %config_path.display(), "Configuration file not found, using default configuration");
     }
-    
-    config.database.url = matches.get_one::<String>("database-url").unwrap().clone();
-    config.api.port = matches.get_one::<String>("port").unwrap().parse()?;
-    config.consensus.port = matches.get_one::<String>("consensus-port").unwrap().parse()?;
-    config.monitoring.enable_metrics = matches.get_flag("enable-metrics");
 
     // Validate configuration
     if let Err(e) = config.validate() {
@@ -134,11 +154,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         std::process::exit(1);
     }
 
-    info!("Configuration loaded successfully");
-    info!("Node ID: {}", config.node.node_id);
-    info!("Cluster peers: {:?}", config.consensus.cluster_peers);
-    info!("API port: {}", config.api.port);
-    info!("Consensus port: {}", config.consensus.port);
+    info!(
+        node_id = %config.node.node_id,
+        cluster_peers = ?config.consensus.cluster_peers,
+        api_port = config.api.port,
+        consensus_port = config.consensus.port,
+        "Configuration loaded successfully"
+    );
     
     // Demonstrate the new consensus system
     info!("Demonstrating new consensus system...");
@@ -158,12 +180,57 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
     let sync_arc = Arc::new(global_sync);
 
-    // Set up graceful shutdown
-    let shutdown_signal = async {
-        tokio::signal::ctrl_c()
-            .await
-            .expect("Failed to install CTRL+C signal handler");
-        info!("Received shutdown signal");
+    // Reloads re-read the same file with the same command line overrides
+    let reload_matches = matches.clone();
+    sync_arc.set_config_source(Arc::new(move || load_config(&config_path, &reload_matches))).await;
+
+    // Apply reloaded log levels
+    let mut config_updates = sync_arc.subscribe_config();
+    tokio::spawn(async move {
+        let mut level = config_updates.borrow_and_update().monitoring.logging.level.clone();
+        while config_updates.changed().await.is_ok() {
+            let reloaded = config_updates.borrow_and_update().monitoring.logging.level.clone();
+            if reloaded == level {
+                continue;
+            }
+            match tracing_subscriber::EnvFilter::try_new(&reloaded) {
+                Ok(filter) => match log_filter.reload(filter) {
+                    Ok(()) => info!("Log filter changed to {}", reloaded),
+                    Err(e) => warn!("Failed to apply log filter {}: {}", reloaded, e),
+                },
+                Err(e) => warn!("Ignoring invalid log filter {}: {}", reloaded, e),
+            }
+            level = reloaded;
+        }
+    });
+
+    // Reload the configuration on SIGHUP
+    #[cfg(unix)]
+    {
+        let reload_sync = sync_arc.clone();
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                info!("Received SIGHUP, reloading configuration");
+                if let Err(e) = reload_sync.reload_config("SIGHUP").await {
+                    error!("Configuration reload failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Set up graceful shutdown, on CTRL+C or through the admin API
+    let shutdown_sync = sync_arc.clone();
+    let shutdown_signal = async move {
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result.expect("Failed to install CTRL+C signal handler");
+                info!("Received shutdown signal");
+            }
+            _ = shutdown_sync.shutdown_requested() => {
+                info!("Shutdown requested through the admin API");
+            }
+        }
     };
 
     // Start API server
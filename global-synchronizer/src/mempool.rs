@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use garp_common::GarpResult;
+use garp_common::types::TransactionId;
+
+use crate::cross_domain::CrossDomainEvent;
+use crate::storage::{GlobalStorage, StoredTransaction, TransactionStatus};
+
+/// Interval between dependency eviction passes
+const EVICTION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Evicts mempool transactions whose dependencies can no longer succeed
+pub struct DependencyEvictionTask {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Mempool transaction IDs
+    mempool: Arc<RwLock<Vec<TransactionId>>>,
+
+    /// Cross-domain event sender
+    event_tx: mpsc::UnboundedSender<CrossDomainEvent>,
+
+    /// Age after which an unresolved dependency is treated as timed out
+    max_dependency_staleness: Duration,
+}
+
+impl DependencyEvictionTask {
+    /// Create new eviction task
+    pub fn new(
+        storage: Arc<GlobalStorage>,
+        mempool: Arc<RwLock<Vec<TransactionId>>>,
+        event_tx: mpsc::UnboundedSender<CrossDomainEvent>,
+        max_dependency_staleness: Duration,
+    ) -> Self {
+        Self {
+            storage,
+            mempool,
+            event_tx,
+            max_dependency_staleness,
+        }
+    }
+
+    /// Spawn the periodic eviction loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(EVICTION_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = self.run_once().await {
+                    error!("Dependency eviction pass failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Run a single eviction pass, returning the evicted transaction IDs.
+    ///
+    /// Repeats until no further evictions happen so that chains of
+    /// dependents are evicted in one pass.
+    pub async fn run_once(&self) -> GarpResult<Vec<TransactionId>> {
+        let mut evicted = Vec::new();
+
+        loop {
+            let candidates: Vec<TransactionId> = self.mempool.read().await.clone();
+            let mut evicted_this_round = HashSet::new();
+
+            for tx_id in candidates {
+                let Some(stored) = self.storage.get_transaction(&tx_id).await? else {
+                    continue;
+                };
+
+                if let Some(reason) = self.failed_dependency(&stored).await? {
+                    self.cancel(stored, &reason).await?;
+                    evicted_this_round.insert(tx_id);
+                }
+            }
+
+            if evicted_this_round.is_empty() {
+                break;
+            }
+
+            self.mempool.write().await.retain(|id| !evicted_this_round.contains(id));
+            for id in &evicted_this_round {
+                self.storage.remove_from_pool(id).await;
+            }
+            evicted.extend(evicted_this_round);
+        }
+
+        if !evicted.is_empty() {
+            info!("Evicted {} transactions with failed dependencies", evicted.len());
+        }
+        Ok(evicted)
+    }
+
+    /// Find the first dependency that can no longer succeed
+    async fn failed_dependency(&self, transaction: &StoredTransaction) -> GarpResult<Option<String>> {
+        let now = SystemTime::now();
+
+        for dep_id in &transaction.dependencies {
+            let Some(dep) = self.storage.get_transaction(dep_id).await? else {
+                continue;
+            };
+
+            match dep.status {
+                TransactionStatus::TimedOut
+                | TransactionStatus::Failed
+                | TransactionStatus::Rejected
+                | TransactionStatus::Cancelled => {
+                    return Ok(Some(format!("dependency {} is {:?}", dep_id, dep.status)));
+                }
+                TransactionStatus::Settled => {}
+                _ => {
+                    let age = now.duration_since(dep.created_at).unwrap_or_default();
+                    if age > self.max_dependency_staleness {
+                        return Ok(Some(format!("dependency {} unresolved after {}s", dep_id, age.as_secs())));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Mark a transaction as cancelled and emit a failure event
+    async fn cancel(&self, mut transaction: StoredTransaction, reason: &str) -> GarpResult<()> {
+        debug!("Cancelling transaction {}: {}", transaction.transaction_id, reason);
+
+        let tx_id = transaction.transaction_id.clone();
+        transaction.status = TransactionStatus::Cancelled;
+        transaction.updated_at = SystemTime::now();
+        self.storage.store_transaction(transaction).await?;
+
+        if let Err(e) = self.event_tx.send(CrossDomainEvent::TransactionFailed(tx_id, reason.to_string())) {
+            warn!("Failed to send transaction failed event: {}", e);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::config::GlobalSyncConfig;
+    use crate::storage::{ConsensusState, SettlementState};
+
+    fn stored(id: &TransactionId, status: TransactionStatus, dependencies: Vec<TransactionId>) -> StoredTransaction {
+        let now = SystemTime::now();
+        StoredTransaction {
+            transaction_id: id.clone(),
+            transaction_data: Vec::new(),
+            transaction_type: "test".to_string(),
+            source_domain: "domain-a".to_string(),
+            target_domains: Vec::new(),
+            status,
+            consensus_state: ConsensusState {
+                phase: "received".to_string(),
+                votes: HashMap::new(),
+                required_votes: 0,
+                result: None,
+                proof: None,
+                started_at: now,
+                completed_at: None,
+            },
+            settlement_state: SettlementState {
+                settlement_id: None,
+                settlement_type: "none".to_string(),
+                domain_settlements: HashMap::new(),
+                proof: None,
+                started_at: None,
+                completed_at: None,
+            },
+            created_at: now,
+            updated_at: now,
+            block_height: None,
+            block_hash: None,
+            metadata: HashMap::new(),
+            dependencies,
+            dependents: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_reaction_eviction() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config).await.unwrap());
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+        let a = TransactionId::new();
+        let b = TransactionId::new();
+        let c = TransactionId::new();
+        let unrelated = TransactionId::new();
+
+        storage.store_transaction(stored(&c, TransactionStatus::TimedOut, vec![])).await.unwrap();
+        storage.store_transaction(stored(&b, TransactionStatus::Pending, vec![c.clone()])).await.unwrap();
+        storage.store_transaction(stored(&a, TransactionStatus::Pending, vec![b.clone()])).await.unwrap();
+        storage.store_transaction(stored(&unrelated, TransactionStatus::Pending, vec![])).await.unwrap();
+
+        // A is checked before B, so evicting A requires a second round
+        let mempool = Arc::new(RwLock::new(vec![a.clone(), b.clone(), unrelated.clone()]));
+        let task = DependencyEvictionTask::new(storage.clone(), mempool.clone(), event_tx, Duration::from_secs(300));
+
+        let evicted = task.run_once().await.unwrap();
+        assert_eq!(evicted.len(), 2);
+        assert!(evicted.contains(&a) && evicted.contains(&b));
+        assert_eq!(*mempool.read().await, vec![unrelated]);
+
+        for id in [&a, &b] {
+            let tx = storage.get_transaction(id).await.unwrap().unwrap();
+            assert_eq!(tx.status, TransactionStatus::Cancelled);
+        }
+
+        let mut failed = Vec::new();
+        while let Ok(CrossDomainEvent::TransactionFailed(id, _)) = event_rx.try_recv() {
+            failed.push(id);
+        }
+        assert_eq!(failed.len(), 2);
+    }
+}
@@ -2,17 +2,44 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::net::SocketAddr;
-use tokio::sync::{RwLock, Mutex, mpsc, oneshot};
+use tokio::sync::{RwLock, Mutex, broadcast, mpsc, oneshot};
 use tokio::time::{interval, timeout};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error, debug};
 
 use garp_common::{GarpResult, GarpError};
+use garp_common::error::NetworkError;
 use garp_common::types::{ParticipantId, DomainId, NodeId};
 
-use crate::config::GlobalSyncConfig;
+use crate::compression::{self, Codec};
+use crate::config::{DirectOrDht, GlobalSyncConfig};
 use crate::consensus::ConsensusMessage;
+use crate::cross_domain::CrossDomainMessage;
+
+pub mod dht;
+pub mod tls;
+
+use dht::{DhtKey, DhtRelay, DhtRouter, FindNode};
+use tls::{TlsSession, TlsTransport};
+
+/// Consecutive invalid signatures from one domain that trigger an automatic ban
+const INVALID_SIGNATURE_BAN_THRESHOLD: usize = 3;
+
+/// Window within which the invalid signatures must occur
+const INVALID_SIGNATURE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Duration of an automatic ban
+const AUTO_BAN_DURATION: Duration = Duration::from_secs(3600);
+
+/// Topology changes buffered per subscriber before older ones are dropped
+const TOPOLOGY_CHANGE_CAPACITY: usize = 64;
+
+/// Peer status changes buffered per subscriber before older ones are dropped
+const PEER_STATUS_CAPACITY: usize = 256;
+
+/// Latency samples kept per peer for rolling statistics
+const LATENCY_WINDOW: usize = 32;
 
 /// Network manager for peer-to-peer communication
 pub struct NetworkManager {
@@ -55,6 +82,27 @@ pub struct NetworkManager {
     
     /// Metrics
     metrics: Arc<NetworkMetrics>,
+    
+    /// Banned domains
+    ban_list: Arc<RwLock<BanList>>,
+    
+    /// Topology changes published by the topology updater
+    topology_tx: broadcast::Sender<TopologyChange>,
+    
+    /// Last known state of peers that disconnected
+    disconnected_peers: Arc<RwLock<HashMap<NodeId, PeerConnection>>>,
+    
+    /// Peer connection state changes
+    peer_status_tx: broadcast::Sender<PeerStatusChange>,
+    
+    /// Mutual TLS setup, when `network.enable_tls` is set
+    tls: Option<Arc<TlsTransport>>,
+    
+    /// Authenticated streams of peers connected over TLS
+    tls_sessions: Arc<Mutex<HashMap<NodeId, TlsSession>>>,
+    
+    /// Routing table of connected domains, when `network.routing` is `Dht`
+    dht: Option<Arc<DhtRouter>>,
 }
 
 /// Peer connection
@@ -95,10 +143,119 @@ pub struct PeerConnection {
     
     /// Message statistics
     pub message_stats: MessageStats,
+    
+    /// Most recent latency samples, oldest first
+    pub latency_samples: VecDeque<Duration>,
+}
+
+impl PeerConnection {
+    /// Record a round-trip sample; `latency` becomes the latest sample and
+    /// `bandwidth.avg_latency` the mean of the last `LATENCY_WINDOW` samples
+    pub fn record_latency(&mut self, sample: Duration) {
+        if self.latency_samples.len() == LATENCY_WINDOW {
+            self.latency_samples.pop_front();
+        }
+        self.latency_samples.push_back(sample);
+        self.latency = sample;
+        self.bandwidth.avg_latency = self.latency_samples.iter().sum::<Duration>() / self.latency_samples.len() as u32;
+    }
+    
+    /// Snapshot for the peer management API
+    pub fn status(&self, banned: bool, now: Instant) -> PeerStatus {
+        let as_ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+        PeerStatus {
+            peer_id: self.peer_id.clone(),
+            address: self.address.to_string(),
+            status: if banned { ConnectionStatus::Banned } else { self.status.clone() },
+            banned,
+            last_seen_ms_ago: now.saturating_duration_since(self.last_seen).as_millis() as u64,
+            messages_sent: self.message_stats.messages_sent,
+            messages_received: self.message_stats.messages_received,
+            bytes_sent: self.message_stats.bytes_sent,
+            bytes_received: self.message_stats.bytes_received,
+            failed_messages: self.message_stats.failed_messages,
+            latency: LatencyStats {
+                last_ms: as_ms(&self.latency),
+                avg_ms: as_ms(&self.bandwidth.avg_latency),
+                min_ms: self.latency_samples.iter().min().map_or(0.0, as_ms),
+                max_ms: self.latency_samples.iter().max().map_or(0.0, as_ms),
+                samples: self.latency_samples.len(),
+            },
+        }
+    }
+}
+
+/// Connection health of a peer as reported by `NetworkManager::list_peers`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerStatus {
+    /// Peer ID
+    pub peer_id: NodeId,
+    
+    /// Socket address
+    pub address: String,
+    
+    /// Connection status; `Banned` while a ban is in force
+    pub status: ConnectionStatus,
+    
+    /// Whether the peer is banned
+    pub banned: bool,
+    
+    /// Milliseconds since the peer was last seen
+    pub last_seen_ms_ago: u64,
+    
+    /// Messages sent to the peer
+    pub messages_sent: u64,
+    
+    /// Messages received from the peer
+    pub messages_received: u64,
+    
+    /// Bytes sent to the peer
+    pub bytes_sent: u64,
+    
+    /// Bytes received from the peer
+    pub bytes_received: u64,
+    
+    /// Failed messages
+    pub failed_messages: u64,
+    
+    /// Rolling latency statistics
+    pub latency: LatencyStats,
+}
+
+/// Latency over the last `LATENCY_WINDOW` samples
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub last_ms: f64,
+    pub avg_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub samples: usize,
+}
+
+/// Connection state change of a single peer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerStatusChange {
+    /// Peer connected
+    Connected(NodeId),
+    
+    /// Peer disconnected or timed out
+    Disconnected(NodeId),
+    
+    /// Peer banned; its traffic is refused until the ban ends
+    Banned(NodeId),
+}
+
+impl PeerStatusChange {
+    /// Peer the change is about
+    pub fn peer_id(&self) -> &NodeId {
+        match self {
+            Self::Connected(peer_id) | Self::Disconnected(peer_id) | Self::Banned(peer_id) => peer_id,
+        }
+    }
 }
 
 /// Connection status
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConnectionStatus {
     /// Connecting
     Connecting,
@@ -133,7 +290,7 @@ pub enum ConnectionType {
 }
 
 /// Peer capabilities
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PeerCapabilities {
     /// Supported protocols
     pub protocols: Vec<String>,
@@ -799,6 +956,46 @@ pub struct NetworkTopology {
     pub last_updated: Instant,
 }
 
+/// Peers that joined, left or changed capabilities between two topology updates
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyChange {
+    /// Newly discovered peers
+    pub added_peers: Vec<NodeId>,
+    
+    /// Departed peers
+    pub removed_peers: Vec<NodeId>,
+    
+    /// Peers whose capabilities changed
+    pub updated_peers: Vec<NodeId>,
+}
+
+impl TopologyChange {
+    /// Diff two node sets; peer lists are sorted
+    pub fn between(previous: &HashMap<NodeId, NetworkNode>, current: &HashMap<NodeId, NetworkNode>) -> Self {
+        let mut change = Self::default();
+        for (node_id, node) in current {
+            match previous.get(node_id) {
+                None => change.added_peers.push(node_id.clone()),
+                Some(old) if old.capabilities != node.capabilities => change.updated_peers.push(node_id.clone()),
+                Some(_) => {}
+            }
+        }
+        change.removed_peers = previous.keys()
+            .filter(|node_id| !current.contains_key(*node_id))
+            .cloned()
+            .collect();
+        change.added_peers.sort();
+        change.removed_peers.sort();
+        change.updated_peers.sort();
+        change
+    }
+    
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added_peers.is_empty() && self.removed_peers.is_empty() && self.updated_peers.is_empty()
+    }
+}
+
 /// Network node
 #[derive(Debug, Clone)]
 pub struct NetworkNode {
@@ -894,6 +1091,23 @@ pub enum EdgeType {
     Virtual,
 }
 
+/// Answers find-node queries from the gossiped topology: the connections
+/// of the queried domain, closest to the target first
+struct TopologyFindNode(Arc<RwLock<NetworkTopology>>);
+
+#[async_trait::async_trait]
+impl FindNode for TopologyFindNode {
+    async fn find_node(&self, domain_id: &DomainId, target: &DhtKey) -> GarpResult<Vec<DomainId>> {
+        let topology = self.0.read().await;
+        let node = topology.nodes.get(domain_id)
+            .ok_or_else(|| NetworkError::PeerNotFound(domain_id.clone()))?;
+        let mut connections: Vec<DomainId> = node.connections.iter().cloned().collect();
+        connections.sort_by_key(|connection| DhtKey::of(connection).distance(target));
+        connections.truncate(dht::K);
+        Ok(connections)
+    }
+}
+
 /// Message handler
 pub type MessageHandler = Box<dyn Fn(&InboundMessage) -> GarpResult<()> + Send + Sync>;
 
@@ -1007,7 +1221,7 @@ pub enum NetworkEvent {
     MessageFailed(String, String),
     
     /// Topology changed
-    TopologyChanged,
+    TopologyChanged(TopologyChange),
     
     /// Discovery completed
     DiscoveryCompleted(Vec<PeerInfo>),
@@ -1045,6 +1259,28 @@ pub struct NetworkMetrics {
     
     /// Connection success rate
     pub connection_success_rate: Arc<RwLock<f64>>,
+    
+    /// Payload bytes of compressed messages, before compression
+    pub uncompressed_bytes: Arc<RwLock<u64>>,
+    
+    /// Frame bytes of compressed messages, after compression
+    pub compressed_bytes: Arc<RwLock<u64>>,
+    
+    /// Messages refused for exceeding a send or receive size limit
+    pub oversize_rejected: Arc<RwLock<u64>>,
+    
+    /// TLS handshakes that failed or authenticated the wrong peer
+    pub tls_handshake_failures: Arc<RwLock<u64>>,
+}
+
+/// Ban list for misbehaving domains
+#[derive(Debug, Default)]
+pub struct BanList {
+    /// Banned domains with optional expiry (`None` bans until explicitly lifted)
+    entries: HashMap<DomainId, Option<Instant>>,
+    
+    /// Recent consecutive invalid-signature timestamps per domain
+    invalid_signatures: HashMap<DomainId, VecDeque<Instant>>,
 }
 
 impl NetworkManager {
@@ -1058,6 +1294,11 @@ impl NetworkManager {
         let peer_discovery = Arc::new(PeerDiscovery::new(config.clone()).await?);
         let message_router = Arc::new(MessageRouter::new(config.clone()).await?);
         let connection_manager = Arc::new(ConnectionManager::new(config.clone()).await?);
+        let tls = TlsTransport::from_config(&config.network)?.map(Arc::new);
+        let dht = match config.network.routing {
+            DirectOrDht::Direct => None,
+            DirectOrDht::Dht => Some(Arc::new(DhtRouter::new(node_id.clone()))),
+        };
         
         let metrics = Arc::new(NetworkMetrics {
             messages_sent: Arc::new(RwLock::new(0)),
@@ -1068,6 +1309,10 @@ impl NetworkManager {
             message_throughput: Arc::new(RwLock::new(0.0)),
             network_latency: Arc::new(RwLock::new(0.0)),
             connection_success_rate: Arc::new(RwLock::new(0.0)),
+            uncompressed_bytes: Arc::new(RwLock::new(0)),
+            compressed_bytes: Arc::new(RwLock::new(0)),
+            oversize_rejected: Arc::new(RwLock::new(0)),
+            tls_handshake_failures: Arc::new(RwLock::new(0)),
         });
         
         Ok(Self {
@@ -1085,6 +1330,13 @@ impl NetworkManager {
             event_rx,
             shutdown_tx: None,
             metrics,
+            ban_list: Arc::new(RwLock::new(BanList::new())),
+            topology_tx: broadcast::channel(TOPOLOGY_CHANGE_CAPACITY).0,
+            disconnected_peers: Arc::new(RwLock::new(HashMap::new())),
+            peer_status_tx: broadcast::channel(PEER_STATUS_CAPACITY).0,
+            tls,
+            tls_sessions: Arc::new(Mutex::new(HashMap::new())),
+            dht,
         })
     }
     
@@ -1144,6 +1396,14 @@ impl NetworkManager {
         data: Vec<u8>,
         priority: MessagePriority,
     ) -> GarpResult<String> {
+        match &destination {
+            MessageDestination::Peer(peer_id) if self.is_peer_banned(peer_id).await => {
+                return Err(NetworkError::PeerBanned(peer_id.clone()).into());
+            }
+            _ => {}
+        }
+        
+        let data = self.encode_payload(data).await?;
         let message_id = Uuid::new_v4().to_string();
         
         let message = OutboundMessage {
@@ -1168,6 +1428,29 @@ impl NetworkManager {
         Ok(message_id)
     }
     
+    /// Frame a payload for the wire, compressing it above the configured
+    /// threshold and rejecting frames over the send limit
+    async fn encode_payload(&self, data: Vec<u8>) -> GarpResult<Vec<u8>> {
+        let network = &self.config.network;
+        let codec = if network.enable_compression && data.len() > network.compression_threshold_bytes {
+            network.compression_algorithm
+        } else {
+            Codec::None
+        };
+        
+        let frame = compression::encode_frame(&data, codec)?;
+        if frame.len() > network.max_send_message_bytes {
+            *self.metrics.oversize_rejected.write().await += 1;
+            return Err(NetworkError::MessageTooLarge { size: frame.len(), limit: network.max_send_message_bytes }.into());
+        }
+        
+        if codec != Codec::None {
+            *self.metrics.uncompressed_bytes.write().await += data.len() as u64;
+            *self.metrics.compressed_bytes.write().await += frame.len() as u64;
+        }
+        Ok(frame)
+    }
+    
     /// Register message handler
     pub async fn register_message_handler<F>(&self, message_type: String, handler: F) -> GarpResult<()>
     where
@@ -1189,32 +1472,124 @@ impl NetworkManager {
         ).await
     }
     
-    /// Connect to peer
-    pub async fn connect_peer(&self, peer_id: &NodeId, address: SocketAddr) -> GarpResult<()> {
-        info!("Connecting to peer: {} at {}", peer_id, address);
+    /// Connect to the peer at `address`. Until the handshake reports the
+    /// remote node ID, the peer is identified by its address.
+    pub async fn connect_peer(&self, address: SocketAddr) -> GarpResult<NodeId> {
+        let peer_id: NodeId = address.to_string();
+        info!(peer_id = %peer_id, address = %address, "Connecting to peer");
+        
+        if self.is_peer_banned(&peer_id).await {
+            return Err(NetworkError::PeerBanned(peer_id).into());
+        }
         
         // Check if already connected
         {
             let peers = self.connected_peers.read().await;
-            if peers.contains_key(peer_id) {
-                return Ok(());
+            if peers.contains_key(&peer_id) {
+                return Ok(peer_id);
             }
         }
         
-        // Create peer connection
+        // TODO: Implement actual connection logic
+        
+        self.register_peer(peer_id.clone(), address, ConnectionType::Outbound, HashMap::new()).await?;
+        
+        info!(peer_id = %peer_id, "Connected to peer");
+        Ok(peer_id)
+    }
+    
+    /// Dial the node of `domain_id` at `address` over mutual TLS, presenting
+    /// this node's certificate and checking the peer's subject is `domain_id`
+    pub async fn dial_peer(&self, address: SocketAddr, domain_id: &DomainId) -> GarpResult<NodeId> {
+        let tls = self.require_tls()?;
+        if self.is_peer_banned(domain_id).await {
+            return Err(NetworkError::PeerBanned(domain_id.clone()).into());
+        }
+        if self.connected_peers.read().await.contains_key(domain_id) {
+            return Ok(domain_id.clone());
+        }
+        
+        let connect_timeout = Duration::from_millis(self.config.network.connection_timeout_ms);
+        let stream = match timeout(connect_timeout, tokio::net::TcpStream::connect(address)).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => return Err(NetworkError::ConnectionFailed { peer: domain_id.clone(), reason: e.to_string() }.into()),
+            Err(_) => return Err(NetworkError::Timeout.into()),
+        };
+        let stream = match tls.connect(stream, domain_id).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                *self.metrics.tls_handshake_failures.write().await += 1;
+                return Err(e);
+            }
+        };
+        
+        self.tls_sessions.lock().await.insert(domain_id.clone(), TlsSession::Outbound(stream));
+        let metadata = HashMap::from([("tls_subject".to_string(), domain_id.clone())]);
+        self.register_peer(domain_id.clone(), address, ConnectionType::Outbound, metadata).await?;
+        
+        info!(domain_id = %domain_id, address = %address, "Connected to peer over TLS");
+        Ok(domain_id.clone())
+    }
+    
+    /// Authenticate an inbound connection from the node of `domain_id`. The
+    /// client certificate must be signed by the CA and have `domain_id` as
+    /// its subject.
+    pub async fn accept_peer(&self, stream: tokio::net::TcpStream, domain_id: &DomainId) -> GarpResult<NodeId> {
+        let tls = self.require_tls()?;
+        if self.is_peer_banned(domain_id).await {
+            return Err(NetworkError::PeerBanned(domain_id.clone()).into());
+        }
+        let address = stream.peer_addr()
+            .map_err(|e| NetworkError::ConnectionFailed { peer: domain_id.clone(), reason: e.to_string() })?;
+        let stream = match tls.accept(stream, domain_id).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                *self.metrics.tls_handshake_failures.write().await += 1;
+                return Err(e);
+            }
+        };
+        
+        self.tls_sessions.lock().await.insert(domain_id.clone(), TlsSession::Inbound(stream));
+        let metadata = HashMap::from([("tls_subject".to_string(), domain_id.clone())]);
+        self.register_peer(domain_id.clone(), address, ConnectionType::Inbound, metadata).await?;
+        
+        info!(domain_id = %domain_id, address = %address, "Accepted TLS connection from peer");
+        Ok(domain_id.clone())
+    }
+    
+    /// Load a new node certificate and key signed by the same CA. New
+    /// handshakes present it; established sessions are kept.
+    pub async fn rotate_tls_certificate(&self, new_cert_path: &str, new_key_path: &str) -> GarpResult<()> {
+        self.require_tls()?.rotate(new_cert_path, new_key_path).await
+    }
+    
+    fn require_tls(&self) -> GarpResult<&Arc<TlsTransport>> {
+        self.tls.as_ref()
+            .ok_or_else(|| GarpError::ConfigError("TLS is not enabled (network.enable_tls)".to_string()))
+    }
+    
+    /// Record a peer as connected and announce it
+    async fn register_peer(
+        &self,
+        peer_id: NodeId,
+        address: SocketAddr,
+        connection_type: ConnectionType,
+        metadata: HashMap<String, String>,
+    ) -> GarpResult<()> {
         let connection = PeerConnection {
             peer_id: peer_id.clone(),
-            status: ConnectionStatus::Connecting,
+            status: ConnectionStatus::Connected,
             address,
-            connection_type: ConnectionType::Outbound,
+            connection_type,
             protocol_version: "1.0".to_string(),
             capabilities: PeerCapabilities::default(),
-            metadata: HashMap::new(),
+            metadata,
             last_seen: Instant::now(),
             connected_at: Instant::now(),
             latency: Duration::from_millis(0),
             bandwidth: BandwidthInfo::default(),
             message_stats: MessageStats::default(),
+            latency_samples: VecDeque::new(),
         };
         
         // Store connection
@@ -1222,27 +1597,20 @@ impl NetworkManager {
             let mut peers = self.connected_peers.write().await;
             peers.insert(peer_id.clone(), connection);
         }
-        
-        // TODO: Implement actual connection logic
-        
-        // Update connection status
-        {
-            let mut peers = self.connected_peers.write().await;
-            if let Some(peer) = peers.get_mut(peer_id) {
-                peer.status = ConnectionStatus::Connected;
-            }
+        self.disconnected_peers.write().await.remove(&peer_id);
+        if let Some(dht) = &self.dht {
+            dht.add_node(&peer_id).await;
         }
         
         // Emit event
         self.event_tx.send(NetworkEvent::PeerConnected(peer_id.clone()))?;
-        
-        info!("Successfully connected to peer: {}", peer_id);
+        let _ = self.peer_status_tx.send(PeerStatusChange::Connected(peer_id));
         Ok(())
     }
     
     /// Disconnect from peer
     pub async fn disconnect_peer(&self, peer_id: &NodeId) -> GarpResult<()> {
-        info!("Disconnecting from peer: {}", peer_id);
+        info!(peer_id = %peer_id, "Disconnecting from peer");
         
         // Update connection status
         {
@@ -1252,21 +1620,61 @@ impl NetworkManager {
             }
         }
         
-        // TODO: Implement actual disconnection logic
+        // Closing the stream ends the TLS session
+        self.tls_sessions.lock().await.remove(peer_id);
         
         // Remove connection
-        {
+        let removed = {
             let mut peers = self.connected_peers.write().await;
-            peers.remove(peer_id);
+            peers.remove(peer_id)
+        };
+        let Some(mut connection) = removed else {
+            return Err(GarpError::NotFound(format!("Peer {} is not connected", peer_id)));
+        };
+        if let Some(dht) = &self.dht {
+            dht.remove_node(peer_id).await;
         }
+        connection.status = ConnectionStatus::Disconnected;
+        self.disconnected_peers.write().await.insert(peer_id.clone(), connection);
         
         // Emit event
         self.event_tx.send(NetworkEvent::PeerDisconnected(peer_id.clone()))?;
+        let _ = self.peer_status_tx.send(PeerStatusChange::Disconnected(peer_id.clone()));
         
-        info!("Successfully disconnected from peer: {}", peer_id);
+        info!(peer_id = %peer_id, "Disconnected from peer");
         Ok(())
     }
     
+    /// Connected and previously connected peers with their connection
+    /// health, sorted by peer ID
+    pub async fn list_peers(&self) -> Vec<PeerStatus> {
+        let now = Instant::now();
+        let mut ban_list = self.ban_list.write().await;
+        let connected = self.connected_peers.read().await;
+        let disconnected = self.disconnected_peers.read().await;
+        
+        let mut peers: Vec<PeerStatus> = connected.values()
+            .chain(disconnected.values().filter(|peer| !connected.contains_key(&peer.peer_id)))
+            .map(|peer| peer.status(ban_list.is_banned(&peer.peer_id, now), now))
+            .collect();
+        peers.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+        peers
+    }
+    
+    /// Record a round-trip latency sample for a connected peer
+    pub async fn record_peer_latency(&self, peer_id: &NodeId, sample: Duration) {
+        let mut peers = self.connected_peers.write().await;
+        if let Some(peer) = peers.get_mut(peer_id) {
+            peer.record_latency(sample);
+            peer.last_seen = Instant::now();
+        }
+    }
+    
+    /// Receive peer connection state changes from now on
+    pub fn subscribe_peer_status(&self) -> mpsc::Receiver<PeerStatusChange> {
+        forward_broadcast(self.peer_status_tx.subscribe(), PEER_STATUS_CAPACITY, "Peer status")
+    }
+    
     /// Get connected peers
     pub async fn get_connected_peers(&self) -> Vec<NodeId> {
         let peers = self.connected_peers.read().await;
@@ -1285,11 +1693,129 @@ impl NetworkManager {
         topology.clone()
     }
     
+    /// Receive every non-empty topology change from now on
+    pub fn subscribe_topology_changes(&self) -> mpsc::Receiver<TopologyChange> {
+        forward_broadcast(self.topology_tx.subscribe(), TOPOLOGY_CHANGE_CAPACITY, "Topology")
+    }
+    
     /// Get metrics
     pub async fn get_metrics(&self) -> NetworkMetrics {
         self.metrics.clone()
     }
     
+    /// Send a cross-domain message to the given domain
+    pub async fn send_cross_domain_message(
+        &self,
+        domain_id: &DomainId,
+        message: CrossDomainMessage,
+    ) -> GarpResult<String> {
+        self.send_cross_domain_message_with_priority(domain_id, message, MessagePriority::Normal).await
+    }
+    
+    /// Send a cross-domain message at the given priority. With DHT routing,
+    /// a message for a domain that is not connected is relayed through the
+    /// connected domain on the path to it.
+    pub async fn send_cross_domain_message_with_priority(
+        &self,
+        domain_id: &DomainId,
+        message: CrossDomainMessage,
+        priority: MessagePriority,
+    ) -> GarpResult<String> {
+        if self.is_peer_banned(domain_id).await {
+            return Err(NetworkError::PeerBanned(domain_id.clone()).into());
+        }
+        
+        let data = serde_json::to_vec(&message)?;
+        let connected = self.connected_peers.read().await.contains_key(domain_id);
+        if let (Some(dht), false) = (&self.dht, connected) {
+            let finder = TopologyFindNode(self.network_topology.clone());
+            match dht.find_peer(domain_id, &finder).await {
+                Some(route) => {
+                    debug!(domain_id = %domain_id, next_hop = %route.next_hop, closest = %route.closest, "Relaying cross-domain message");
+                    let relay = DhtRelay {
+                        target_domain: domain_id.clone(),
+                        message_type: "cross_domain".to_string(),
+                        payload: data,
+                        hops_remaining: dht::MAX_RELAY_HOPS,
+                    };
+                    return self.send_message(
+                        MessageDestination::Peer(route.next_hop),
+                        dht::RELAY_MESSAGE_TYPE.to_string(),
+                        serde_json::to_vec(&relay)?,
+                        priority,
+                    ).await;
+                }
+                None => debug!(domain_id = %domain_id, "No DHT route, sending directly"),
+            }
+        }
+        
+        self.send_message(
+            MessageDestination::Domain(domain_id.clone()),
+            "cross_domain".to_string(),
+            data,
+            priority,
+        ).await
+    }
+    
+    /// Ban a domain, optionally for a limited duration. Its connection is
+    /// dropped and traffic in both directions is refused while the ban lasts.
+    pub async fn ban_peer(&self, domain_id: &DomainId, duration: Option<Duration>) -> GarpResult<()> {
+        warn!("Banning domain {} (duration: {:?})", domain_id, duration);
+        {
+            let mut ban_list = self.ban_list.write().await;
+            ban_list.ban(domain_id.clone(), duration, Instant::now());
+        }
+        self.drop_banned_peer(domain_id).await;
+        Ok(())
+    }
+    
+    /// Close the connection of a freshly banned peer and announce the ban
+    async fn drop_banned_peer(&self, peer_id: &NodeId) {
+        if let Some(dht) = &self.dht {
+            dht.remove_node(peer_id).await;
+        }
+        if let Some(mut connection) = self.connected_peers.write().await.remove(peer_id) {
+            connection.status = ConnectionStatus::Banned;
+            self.disconnected_peers.write().await.insert(peer_id.clone(), connection);
+            if let Err(e) = self.event_tx.send(NetworkEvent::PeerDisconnected(peer_id.clone())) {
+                error!("Failed to send peer disconnected event: {}", e);
+            }
+        }
+        let _ = self.peer_status_tx.send(PeerStatusChange::Banned(peer_id.clone()));
+    }
+    
+    /// Lift a ban on a domain
+    pub async fn unban_peer(&self, domain_id: &DomainId) -> GarpResult<()> {
+        info!(domain_id = %domain_id, "Unbanning domain");
+        let mut ban_list = self.ban_list.write().await;
+        ban_list.unban(domain_id);
+        Ok(())
+    }
+    
+    /// Check whether a domain is currently banned
+    pub async fn is_peer_banned(&self, domain_id: &DomainId) -> bool {
+        let mut ban_list = self.ban_list.write().await;
+        ban_list.is_banned(domain_id, Instant::now())
+    }
+    
+    /// Record the outcome of verifying a message signature from a domain.
+    /// Returns `true` if the domain was banned as a result.
+    pub async fn record_signature_check(&self, domain_id: &DomainId, valid: bool) -> bool {
+        let banned = {
+            let mut ban_list = self.ban_list.write().await;
+            if valid {
+                ban_list.clear_invalid_signatures(domain_id);
+                return false;
+            }
+            ban_list.record_invalid_signature(domain_id, Instant::now())
+        };
+        if banned {
+            warn!("Auto-banned domain {} after repeated invalid signatures", domain_id);
+            self.drop_banned_peer(domain_id).await;
+        }
+        banned
+    }
+    
     /// Start message processor
     async fn start_message_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let outbound_queue = self.outbound_queue.clone();
@@ -1297,8 +1823,13 @@ impl NetworkManager {
         let message_handlers = self.message_handlers.clone();
         let message_router = self.message_router.clone();
         let connected_peers = self.connected_peers.clone();
+        let ban_list = self.ban_list.clone();
         let event_tx = self.event_tx.clone();
         let metrics = self.metrics.clone();
+        let max_send_message_bytes = self.config.network.max_send_message_bytes;
+        let max_receive_message_bytes = self.config.network.max_receive_message_bytes;
+        let node_id = self.node_id.clone();
+        let dht = self.dht.clone();
         
         let handle = tokio::spawn(async move {
             let mut interval = interval(Duration::from_millis(10));
@@ -1318,8 +1849,10 @@ impl NetworkManager {
                         message.clone(),
                         &message_router,
                         &connected_peers,
+                        &ban_list,
                         &event_tx,
                         &metrics,
+                        max_send_message_bytes,
                     ).await {
                         error!("Failed to route outbound message {}: {}", message.message_id, e);
                         
@@ -1337,13 +1870,24 @@ impl NetworkManager {
                     queue.pop_front()
                 };
                 
+                // Relayed messages for other domains are passed on
+                let inbound_message = match inbound_message {
+                    Some(message) if message.message_type == dht::RELAY_MESSAGE_TYPE => {
+                        Self::relay_inbound_message(message, &node_id, dht.as_deref(), &connected_peers, &outbound_queue, max_receive_message_bytes).await
+                    }
+                    other => other,
+                };
+                
                 if let Some(message) = inbound_message {
                     // Handle message
                     if let Err(e) = Self::handle_inbound_message(
                         message.clone(),
                         &message_handlers,
+                        &connected_peers,
+                        &ban_list,
                         &event_tx,
                         &metrics,
+                        max_receive_message_bytes,
                     ).await {
                         error!("Failed to handle inbound message {}: {}", message.message_id, e);
                     }
@@ -1359,8 +1903,10 @@ impl NetworkManager {
         message: OutboundMessage,
         message_router: &Arc<MessageRouter>,
         connected_peers: &Arc<RwLock<HashMap<NodeId, PeerConnection>>>,
+        ban_list: &Arc<RwLock<BanList>>,
         event_tx: &mpsc::UnboundedSender<NetworkEvent>,
         metrics: &Arc<NetworkMetrics>,
+        max_send_message_bytes: usize,
     ) -> GarpResult<()> {
         debug!("Routing outbound message: {}", message.message_id);
         
@@ -1384,18 +1930,62 @@ impl NetworkManager {
             }
         };
         
+        // Banned peers get nothing
+        let target_peers: Vec<NodeId> = {
+            let mut ban_list = ban_list.write().await;
+            let now = Instant::now();
+            target_peers.into_iter().filter(|peer_id| !ban_list.is_banned(peer_id, now)).collect()
+        };
+        
+        // Peers that do not advertise the frame's codec get it uncompressed
+        let codec = compression::frame_codec(&message.data)?;
+        let mut plain_frame: Option<GarpResult<Vec<u8>>> = None;
+        
         // Send to target peers
         for peer_id in target_peers {
+            let accepts_codec = codec == Codec::None || connected_peers.read().await
+                .get(&peer_id)
+                .map_or(false, |peer| peer.capabilities.features.contains(codec.feature()));
+            
+            let frame = if accepts_codec {
+                &message.data
+            } else {
+                let plain = plain_frame.get_or_insert_with(|| {
+                    let payload = compression::decode_frame(&message.data, max_send_message_bytes)?;
+                    let frame = compression::encode_frame(&payload, Codec::None)?;
+                    if frame.len() > max_send_message_bytes {
+                        return Err(NetworkError::MessageTooLarge { size: frame.len(), limit: max_send_message_bytes }.into());
+                    }
+                    Ok(frame)
+                });
+                match plain {
+                    Ok(frame) => &*frame,
+                    Err(e) => {
+                        warn!("Not sending message {} to peer {} without {:?}: {}", message.message_id, peer_id, codec, e);
+                        if matches!(e, GarpError::Network(NetworkError::MessageTooLarge { .. })) {
+                            *metrics.oversize_rejected.write().await += 1;
+                        }
+                        continue;
+                    }
+                }
+            };
+            
             // TODO: Implement actual message sending
             debug!("Sending message {} to peer {}", message.message_id, peer_id);
             
+            if let Some(peer) = connected_peers.write().await.get_mut(&peer_id) {
+                peer.message_stats.messages_sent += 1;
+                peer.message_stats.bytes_sent += frame.len() as u64;
+                peer.message_stats.last_message_at = Some(Instant::now());
+            }
+            
             // Update metrics
             {
                 let mut sent = metrics.messages_sent.write().await;
                 *sent += 1;
                 
                 let mut bytes = metrics.bytes_sent.write().await;
-                *bytes += message.data.len() as u64;
+                *bytes += frame.len() as u64;
             }
         }
         
@@ -1407,15 +1997,106 @@ impl NetworkManager {
         Ok(())
     }
     
+    /// Unwrap a relayed message addressed to this node, or pass it one hop
+    /// closer to its target: straight to the target when connected, otherwise
+    /// to the connected domain closest to it.
+    async fn relay_inbound_message(
+        message: InboundMessage,
+        node_id: &NodeId,
+        dht: Option<&DhtRouter>,
+        connected_peers: &Arc<RwLock<HashMap<NodeId, PeerConnection>>>,
+        outbound_queue: &Arc<Mutex<VecDeque<OutboundMessage>>>,
+        max_receive_message_bytes: usize,
+    ) -> Option<InboundMessage> {
+        let relay = compression::decode_frame(&message.data, max_receive_message_bytes)
+            .and_then(|payload| Ok(serde_json::from_slice::<DhtRelay>(&payload)?));
+        let mut relay = match relay {
+            Ok(relay) => relay,
+            Err(e) => {
+                warn!("Dropping malformed relayed message {} from {}: {}", message.message_id, message.source, e);
+                return None;
+            }
+        };
+        
+        if &relay.target_domain == node_id {
+            let data = compression::encode_frame(&relay.payload, Codec::None).ok()?;
+            return Some(InboundMessage { message_type: relay.message_type, data, ..message });
+        }
+        if relay.hops_remaining == 0 {
+            warn!("Dropping relayed message {} for {}: hop limit reached", message.message_id, relay.target_domain);
+            return None;
+        }
+        
+        let next_hop = if connected_peers.read().await.contains_key(&relay.target_domain) {
+            Some(relay.target_domain.clone())
+        } else if let Some(dht) = dht {
+            dht.closest(&DhtKey::of(&relay.target_domain), 2).await
+                .into_iter()
+                .find(|peer_id| *peer_id != message.source)
+        } else {
+            None
+        };
+        let Some(next_hop) = next_hop else {
+            warn!("Dropping relayed message {} for {}: no route", message.message_id, relay.target_domain);
+            return None;
+        };
+        
+        relay.hops_remaining -= 1;
+        let data = serde_json::to_vec(&relay).ok()
+            .and_then(|payload| compression::encode_frame(&payload, Codec::None).ok())?;
+        debug!("Relaying message {} for {} to {}", message.message_id, relay.target_domain, next_hop);
+        outbound_queue.lock().await.push_back(OutboundMessage {
+            message_id: message.message_id,
+            destination: MessageDestination::Peer(next_hop),
+            message_type: dht::RELAY_MESSAGE_TYPE.to_string(),
+            data,
+            priority: MessagePriority::Normal,
+            timeout: Duration::from_secs(30),
+            retry_count: 0,
+            max_retries: 3,
+            created_at: Instant::now(),
+            metadata: message.metadata,
+        });
+        None
+    }
+    
     /// Handle inbound message
     async fn handle_inbound_message(
         message: InboundMessage,
         message_handlers: &Arc<RwLock<HashMap<String, MessageHandler>>>,
+        connected_peers: &Arc<RwLock<HashMap<NodeId, PeerConnection>>>,
+        ban_list: &Arc<RwLock<BanList>>,
         event_tx: &mpsc::UnboundedSender<NetworkEvent>,
         metrics: &Arc<NetworkMetrics>,
+        max_receive_message_bytes: usize,
     ) -> GarpResult<()> {
         debug!("Handling inbound message: {} of type {}", message.message_id, message.message_type);
         
+        if ban_list.write().await.is_banned(&message.source, Instant::now()) {
+            debug!("Dropping message {} from banned peer {}", message.message_id, message.source);
+            return Ok(());
+        }
+        
+        let wire_len = message.data.len() as u64;
+        if let Some(peer) = connected_peers.write().await.get_mut(&message.source) {
+            peer.message_stats.messages_received += 1;
+            peer.message_stats.bytes_received += wire_len;
+            peer.message_stats.last_message_at = Some(message.received_at);
+            peer.last_seen = message.received_at;
+        }
+        
+        // Handlers see the decompressed payload
+        let mut message = message;
+        message.data = match compression::decode_frame(&message.data, max_receive_message_bytes) {
+            Ok(payload) => payload,
+            Err(e) => {
+                if matches!(e, GarpError::Network(NetworkError::MessageTooLarge { .. })) {
+                    *metrics.oversize_rejected.write().await += 1;
+                }
+                return Err(e);
+            }
+        };
+        
         // Find handler
         let handler = {
             let handlers = message_handlers.read().await;
@@ -1438,7 +2119,7 @@ impl NetworkManager {
             *received += 1;
             
             let mut bytes = metrics.bytes_received.write().await;
-            *bytes += message.data.len() as u64;
+            *bytes += wire_len;
         }
         
         // Emit event
@@ -1452,6 +2133,8 @@ impl NetworkManager {
     /// Start connection monitor
     async fn start_connection_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let connected_peers = self.connected_peers.clone();
+        let departed_peers = self.disconnected_peers.clone();
+        let peer_status_tx = self.peer_status_tx.clone();
         let event_tx = self.event_tx.clone();
         let config = self.config.clone();
         
@@ -1479,13 +2162,17 @@ impl NetworkManager {
                     }
                     
                     // Remove disconnected peers
+                    let mut departed = departed_peers.write().await;
                     for peer_id in &disconnected_peers {
-                        peers.remove(peer_id);
+                        if let Some(peer) = peers.remove(peer_id) {
+                            departed.insert(peer_id.clone(), peer);
+                        }
                     }
                 }
                 
                 // Emit disconnection events
                 for peer_id in disconnected_peers {
+                    let _ = peer_status_tx.send(PeerStatusChange::Disconnected(peer_id.clone()));
                     if let Err(e) = event_tx.send(NetworkEvent::PeerDisconnected(peer_id)) {
                         error!("Failed to send peer disconnected event: {}", e);
                     }
@@ -1501,6 +2188,7 @@ impl NetworkManager {
         let network_topology = self.network_topology.clone();
         let connected_peers = self.connected_peers.clone();
         let event_tx = self.event_tx.clone();
+        let topology_tx = self.topology_tx.clone();
         
         let handle = tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(30));
@@ -1509,12 +2197,12 @@ impl NetworkManager {
                 interval.tick().await;
                 
                 // Update topology
-                {
+                let change = {
                     let peers = connected_peers.read().await;
                     let mut topology = network_topology.write().await;
                     
                     // Update nodes
-                    topology.nodes.clear();
+                    let mut nodes = HashMap::new();
                     for (peer_id, peer_connection) in peers.iter() {
                         let node = NetworkNode {
                             node_id: peer_id.clone(),
@@ -1525,14 +2213,24 @@ impl NetworkManager {
                             last_seen: peer_connection.last_seen,
                         };
                         
-                        topology.nodes.insert(peer_id.clone(), node);
+                        nodes.insert(peer_id.clone(), node);
                     }
                     
+                    let change = TopologyChange::between(&topology.nodes, &nodes);
+                    topology.nodes = nodes;
                     topology.last_updated = Instant::now();
+                    change
+                };
+                
+                if change.is_empty() {
+                    continue;
                 }
                 
+                // No subscribers is not an error
+                let _ = topology_tx.send(change.clone());
+                
                 // Emit topology change event
-                if let Err(e) = event_tx.send(NetworkEvent::TopologyChanged) {
+                if let Err(e) = event_tx.send(NetworkEvent::TopologyChanged(change)) {
                     error!("Failed to send topology changed event: {}", e);
                 }
             }
@@ -1691,7 +2389,7 @@ impl Default for PeerCapabilities {
             max_message_size: 1024 * 1024, // 1MB
             consensus_algorithms: vec!["pbft".to_string()],
             supported_domains: Vec::new(),
-            features: HashSet::new(),
+            features: Codec::SUPPORTED.iter().map(|codec| codec.feature().to_string()).collect(),
             version: "1.0.0".to_string(),
         }
     }
@@ -1733,6 +2431,33 @@ impl Default for NodeMetrics {
     }
 }
 
+/// Relay a broadcast subscription into a bounded channel. A subscriber that
+/// falls more than `capacity` items behind skips the oldest ones; the relay
+/// ends when the returned receiver is dropped.
+fn forward_broadcast<T: Clone + Send + 'static>(
+    mut source: broadcast::Receiver<T>,
+    capacity: usize,
+    label: &'static str,
+) -> mpsc::Receiver<T> {
+    let (tx, rx) = mpsc::channel(capacity);
+    tokio::spawn(async move {
+        loop {
+            match source.recv().await {
+                Ok(item) => {
+                    if tx.send(item).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("{} subscriber lagged, skipped {} changes", label, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    rx
+}
+
 impl NetworkTopology {
     /// Create new network topology
     pub fn new() -> Self {
@@ -1777,6 +2502,73 @@ impl ConnectionPool {
     }
 }
 
+impl NetworkMetrics {
+    /// Compressed over uncompressed size of all compressed messages sent,
+    /// or 1.0 before any were
+    pub async fn compression_ratio(&self) -> f64 {
+        let uncompressed = *self.uncompressed_bytes.read().await;
+        if uncompressed == 0 {
+            return 1.0;
+        }
+        *self.compressed_bytes.read().await as f64 / uncompressed as f64
+    }
+}
+
+impl BanList {
+    /// Create empty ban list
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Ban a domain until `now + duration`, or indefinitely
+    pub fn ban(&mut self, domain_id: DomainId, duration: Option<Duration>, now: Instant) {
+        self.invalid_signatures.remove(&domain_id);
+        self.entries.insert(domain_id, duration.map(|d| now + d));
+    }
+    
+    /// Remove a domain from the ban list
+    pub fn unban(&mut self, domain_id: &DomainId) {
+        self.entries.remove(domain_id);
+        self.invalid_signatures.remove(domain_id);
+    }
+    
+    /// Check whether a domain is banned, dropping the entry if it has expired
+    pub fn is_banned(&mut self, domain_id: &DomainId, now: Instant) -> bool {
+        match self.entries.get(domain_id) {
+            Some(Some(expires_at)) if *expires_at <= now => {
+                self.entries.remove(domain_id);
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+    
+    /// Record an invalid signature; bans the domain once the threshold is reached
+    pub fn record_invalid_signature(&mut self, domain_id: &DomainId, now: Instant) -> bool {
+        let recent = self.invalid_signatures.entry(domain_id.clone()).or_default();
+        recent.push_back(now);
+        while let Some(first) = recent.front() {
+            if now.duration_since(*first) > INVALID_SIGNATURE_WINDOW {
+                recent.pop_front();
+            } else {
+                break;
+            }
+        }
+        
+        if recent.len() >= INVALID_SIGNATURE_BAN_THRESHOLD {
+            self.ban(domain_id.clone(), Some(AUTO_BAN_DURATION), now);
+            return true;
+        }
+        false
+    }
+    
+    /// Reset the invalid-signature streak for a domain
+    pub fn clear_invalid_signatures(&mut self, domain_id: &DomainId) {
+        self.invalid_signatures.remove(domain_id);
+    }
+}
+
 impl DiscoveryState {
     /// Create new discovery state
     pub fn new() -> Self {
@@ -1799,6 +2591,7 @@ impl DiscoveryState {
 mod tests {
     use super::*;
     use crate::config::GlobalSyncConfig;
+    use crate::cross_domain::{CrossDomainMessageType, DomainStatus, HeartbeatMessage, StateSyncResponse};
     
     #[tokio::test]
     async fn test_network_manager_creation() {
@@ -1827,4 +2620,275 @@ mod tests {
         let manager = ConnectionManager::new(config).await;
         assert!(manager.is_ok());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_banned_peer_rejects_cross_domain_message() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let manager = NetworkManager::new(config).await.unwrap();
+        let domain: DomainId = "domain-a".to_string();
+        
+        manager.ban_peer(&domain, None).await.unwrap();
+        let message = CrossDomainMessage {
+            message_id: Uuid::new_v4().to_string(),
+            message_type: CrossDomainMessageType::Heartbeat(HeartbeatMessage {
+                domain_id: "global-synchronizer".to_string(),
+                block_height: 0,
+                transaction_count: 0,
+                status: DomainStatus::Active,
+                timestamp: chrono::Utc::now(),
+            }),
+            source_domain: "global-synchronizer".to_string(),
+            target_domain: domain.clone(),
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            correlation_id: None,
+        };
+        assert!(manager.send_cross_domain_message(&domain, message.clone()).await.is_err());
+        
+        manager.unban_peer(&domain).await.unwrap();
+        assert!(manager.send_cross_domain_message(&domain, message).await.is_ok());
+    }
+    
+    fn node(node_id: &str, version: &str) -> NetworkNode {
+        NetworkNode {
+            node_id: node_id.to_string(),
+            node_type: NetworkNodeType::FullNode,
+            capabilities: PeerCapabilities { version: version.to_string(), ..PeerCapabilities::default() },
+            connections: HashSet::new(),
+            metrics: NodeMetrics::default(),
+            last_seen: Instant::now(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_topology_change_diff_and_subscription() {
+        let previous: HashMap<NodeId, NetworkNode> = [("a", "1"), ("b", "1"), ("c", "1")]
+            .into_iter()
+            .map(|(id, v)| (id.to_string(), node(id, v)))
+            .collect();
+        let current: HashMap<NodeId, NetworkNode> = [("b", "1"), ("c", "2"), ("d", "1")]
+            .into_iter()
+            .map(|(id, v)| (id.to_string(), node(id, v)))
+            .collect();
+        
+        let change = TopologyChange::between(&previous, &current);
+        assert_eq!(change.added_peers, vec!["d".to_string()]);
+        assert_eq!(change.removed_peers, vec!["a".to_string()]);
+        assert_eq!(change.updated_peers, vec!["c".to_string()]);
+        assert!(TopologyChange::between(&current, &current).is_empty());
+        
+        let json = serde_json::to_value(&change).unwrap();
+        assert_eq!(json["added_peers"], serde_json::json!(["d"]));
+        
+        let manager = NetworkManager::new(Arc::new(GlobalSyncConfig::default())).await.unwrap();
+        let mut changes = manager.subscribe_topology_changes();
+        manager.topology_tx.send(change.clone()).unwrap();
+        assert_eq!(changes.recv().await, Some(change));
+    }
+    
+    #[tokio::test]
+    async fn test_peer_lifecycle_and_status_events() {
+        let manager = NetworkManager::new(Arc::new(GlobalSyncConfig::default())).await.unwrap();
+        let mut events = manager.subscribe_peer_status();
+        
+        let peer_id = manager.connect_peer("127.0.0.1:7000".parse().unwrap()).await.unwrap();
+        assert_eq!(events.recv().await, Some(PeerStatusChange::Connected(peer_id.clone())));
+        
+        manager.record_peer_latency(&peer_id, Duration::from_millis(10)).await;
+        manager.record_peer_latency(&peer_id, Duration::from_millis(30)).await;
+        let peers = manager.list_peers().await;
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].status, ConnectionStatus::Connected);
+        assert_eq!(peers[0].latency.samples, 2);
+        assert_eq!(peers[0].latency.avg_ms, 20.0);
+        assert_eq!(peers[0].latency.max_ms, 30.0);
+        
+        manager.disconnect_peer(&peer_id).await.unwrap();
+        assert_eq!(events.recv().await, Some(PeerStatusChange::Disconnected(peer_id.clone())));
+        assert_eq!(manager.list_peers().await[0].status, ConnectionStatus::Disconnected);
+        assert!(manager.disconnect_peer(&peer_id).await.is_err());
+        
+        // Banned peers cannot reconnect or be messaged
+        manager.ban_peer(&peer_id, Some(Duration::from_secs(60))).await.unwrap();
+        assert_eq!(events.recv().await, Some(PeerStatusChange::Banned(peer_id.clone())));
+        assert!(manager.connect_peer("127.0.0.1:7000".parse().unwrap()).await.is_err());
+        assert!(manager.send_message(
+            MessageDestination::Peer(peer_id.clone()), "ping".to_string(), Vec::new(), MessagePriority::Normal,
+        ).await.is_err());
+        assert!(manager.list_peers().await[0].banned);
+    }
+    
+    #[test]
+    fn test_ban_list_expiry_and_auto_ban() {
+        let mut ban_list = BanList::new();
+        let domain: DomainId = "domain-b".to_string();
+        let now = Instant::now();
+        
+        ban_list.ban(domain.clone(), Some(Duration::from_secs(10)), now);
+        assert!(ban_list.is_banned(&domain, now + Duration::from_secs(5)));
+        assert!(!ban_list.is_banned(&domain, now + Duration::from_secs(11)));
+        
+        // A valid signature in between resets the streak
+        assert!(!ban_list.record_invalid_signature(&domain, now));
+        assert!(!ban_list.record_invalid_signature(&domain, now + Duration::from_secs(1)));
+        ban_list.clear_invalid_signatures(&domain);
+        assert!(!ban_list.record_invalid_signature(&domain, now + Duration::from_secs(2)));
+        
+        // Invalid signatures spread beyond the window do not trigger a ban
+        assert!(!ban_list.record_invalid_signature(&domain, now + Duration::from_secs(70)));
+        assert!(!ban_list.record_invalid_signature(&domain, now + Duration::from_secs(140)));
+        assert!(!ban_list.is_banned(&domain, now + Duration::from_secs(140)));
+        
+        assert!(!ban_list.record_invalid_signature(&domain, now + Duration::from_secs(141)));
+        assert!(ban_list.record_invalid_signature(&domain, now + Duration::from_secs(142)));
+        assert!(ban_list.is_banned(&domain, now + Duration::from_secs(143)));
+    }
+    
+    fn state_sync_message(entries: usize) -> CrossDomainMessage {
+        let state_data = (0..entries).map(|i| (format!("key-{}", i), vec![(i % 7) as u8; 512])).collect();
+        CrossDomainMessage {
+            message_id: Uuid::new_v4().to_string(),
+            message_type: CrossDomainMessageType::StateSyncResponse(StateSyncResponse {
+                request_id: "sync-1".to_string(),
+                state_data,
+                height_range: (0, 10),
+                has_more: false,
+                next_cursor: None,
+                state_root: None,
+            }),
+            source_domain: "domain-a".to_string(),
+            target_domain: "global-synchronizer".to_string(),
+            timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+            correlation_id: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_compressed_message_round_trip() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let manager = NetworkManager::new(config.clone()).await.unwrap();
+        let domain: DomainId = "domain-a".to_string();
+        let message = state_sync_message(64);
+        let payload = serde_json::to_vec(&message).unwrap();
+        
+        manager.send_cross_domain_message(&domain, message).await.unwrap();
+        let frame = manager.outbound_queue.lock().await.pop_front().unwrap().data;
+        assert_eq!(compression::frame_codec(&frame).unwrap(), config.network.compression_algorithm);
+        assert!(frame.len() < payload.len());
+        assert!(manager.metrics.compression_ratio().await < 1.0);
+        
+        // The receiving side hands handlers the original bytes
+        let delivered = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = delivered.clone();
+        manager.register_message_handler("cross_domain".to_string(), move |message| {
+            *sink.lock().unwrap() = message.data.clone();
+            Ok(())
+        }).await.unwrap();
+        let inbound = InboundMessage {
+            message_id: "m-1".to_string(),
+            source: domain,
+            message_type: "cross_domain".to_string(),
+            data: frame,
+            received_at: Instant::now(),
+            metadata: HashMap::new(),
+        };
+        NetworkManager::handle_inbound_message(
+            inbound,
+            &manager.message_handlers,
+            &manager.connected_peers,
+            &manager.ban_list,
+            &manager.event_tx,
+            &manager.metrics,
+            config.network.max_receive_message_bytes,
+        ).await.unwrap();
+        assert_eq!(*delivered.lock().unwrap(), payload);
+        let decoded: CrossDomainMessage = serde_json::from_slice(&payload).unwrap();
+        assert!(matches!(decoded.message_type, CrossDomainMessageType::StateSyncResponse(ref r) if r.state_data.len() == 64));
+    }
+    
+    #[tokio::test]
+    async fn test_dht_relays_messages_for_unconnected_domains() {
+        let mut config = GlobalSyncConfig::default();
+        config.network.enable_compression = false;
+        config.network.routing = DirectOrDht::Dht;
+        let config = Arc::new(config);
+        let manager = NetworkManager::new(config.clone()).await.unwrap();
+        let target: DomainId = "target".to_string();
+        
+        // "a" is connected and knows the target
+        manager.register_peer("a".to_string(), "127.0.0.1:7000".parse().unwrap(), ConnectionType::Outbound, HashMap::new()).await.unwrap();
+        let mut via = node("a", "1");
+        via.connections.insert(target.clone());
+        manager.network_topology.write().await.nodes.insert("a".to_string(), via);
+        
+        let message = state_sync_message(1);
+        let payload = serde_json::to_vec(&message).unwrap();
+        manager.send_cross_domain_message(&target, message).await.unwrap();
+        let outbound = manager.outbound_queue.lock().await.pop_front().unwrap();
+        assert!(matches!(outbound.destination, MessageDestination::Peer(ref peer) if peer == "a"));
+        assert_eq!(outbound.message_type, dht::RELAY_MESSAGE_TYPE);
+        let relay: DhtRelay = serde_json::from_slice(
+            &compression::decode_frame(&outbound.data, config.network.max_receive_message_bytes).unwrap(),
+        ).unwrap();
+        assert_eq!(relay.target_domain, target);
+        assert_eq!(relay.payload, payload);
+        
+        // At the target the relay is unwrapped into the original message
+        let relay = DhtRelay { target_domain: manager.node_id.clone(), ..relay };
+        let inbound = InboundMessage {
+            message_id: "m-1".to_string(),
+            source: "a".to_string(),
+            message_type: dht::RELAY_MESSAGE_TYPE.to_string(),
+            data: compression::encode_frame(&serde_json::to_vec(&relay).unwrap(), Codec::None).unwrap(),
+            received_at: Instant::now(),
+            metadata: HashMap::new(),
+        };
+        let delivered = NetworkManager::relay_inbound_message(
+            inbound,
+            &manager.node_id,
+            manager.dht.as_deref(),
+            &manager.connected_peers,
+            &manager.outbound_queue,
+            config.network.max_receive_message_bytes,
+        ).await.unwrap();
+        assert_eq!(delivered.message_type, "cross_domain");
+        assert_eq!(compression::decode_frame(&delivered.data, config.network.max_receive_message_bytes).unwrap(), payload);
+        assert!(manager.outbound_queue.lock().await.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_oversized_messages_are_rejected() {
+        let mut config = GlobalSyncConfig::default();
+        config.network.enable_compression = false;
+        config.network.max_send_message_bytes = 1024;
+        config.network.max_receive_message_bytes = 1024;
+        let config = Arc::new(config);
+        let manager = NetworkManager::new(config.clone()).await.unwrap();
+        let domain: DomainId = "domain-a".to_string();
+        
+        let result = manager.send_cross_domain_message(&domain, state_sync_message(4)).await;
+        assert!(matches!(result, Err(GarpError::Network(NetworkError::MessageTooLarge { limit: 1024, .. }))));
+        assert!(manager.outbound_queue.lock().await.is_empty());
+        
+        let frame = compression::encode_frame(&vec![0u8; 4096], Codec::Zstd).unwrap();
+        let inbound = InboundMessage {
+            message_id: "m-1".to_string(),
+            source: domain,
+            message_type: "cross_domain".to_string(),
+            data: frame,
+            received_at: Instant::now(),
+            metadata: HashMap::new(),
+        };
+        assert!(NetworkManager::handle_inbound_message(
+            inbound,
+            &manager.message_handlers,
+            &manager.connected_peers,
+            &manager.ban_list,
+            &manager.event_tx,
+            &manager.metrics,
+            config.network.max_receive_message_bytes,
+        ).await.is_err());
+        assert_eq!(*manager.metrics.oversize_rejected.read().await, 2);
+    }
+}
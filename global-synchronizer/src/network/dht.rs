@@ -0,0 +1,315 @@
+//! Kademlia-style routing for clusters too large for a full mesh.
+//!
+//! Each domain ID is hashed with SHA-256 to a 256-bit key, and the distance
+//! between two domains is the XOR of their keys. The routing table holds the
+//! domains this node is directly connected to, in [`KBucket`]s of up to
+//! [`K`] domains: bucket `i` holds the domains whose key shares exactly `i`
+//! leading bits with this node's. A lookup asks the closest known domains
+//! for domains closer still until the [`K`] closest found have all been
+//! asked, and a message for a domain without a direct connection is relayed
+//! through the connected domain on the path to it.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use garp_common::GarpResult;
+use garp_common::types::DomainId;
+
+/// Domains kept per bucket and returned per find-node query
+pub const K: usize = 20;
+
+/// Domains queried per lookup round
+pub const ALPHA: usize = 3;
+
+/// Relays a message may take before it is dropped
+pub const MAX_RELAY_HOPS: u8 = 8;
+
+/// Message type of relayed messages
+pub const RELAY_MESSAGE_TYPE: &str = "dht_relay";
+
+/// Bits in a key, and so buckets in the routing table
+const KEY_BITS: usize = 256;
+
+/// Position of a domain in the DHT key space. Keys order as big-endian
+/// integers, so distances compare directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DhtKey([u8; 32]);
+
+impl DhtKey {
+    /// Key of a domain ID
+    pub fn of(domain_id: &str) -> Self {
+        Self(Sha256::digest(domain_id.as_bytes()).into())
+    }
+
+    /// XOR distance to another key
+    pub fn distance(&self, other: &DhtKey) -> DhtKey {
+        let mut distance = [0u8; 32];
+        for (d, (a, b)) in distance.iter_mut().zip(self.0.iter().zip(other.0.iter())) {
+            *d = a ^ b;
+        }
+        DhtKey(distance)
+    }
+
+    /// Leading bits shared with `other`; `None` for the same key
+    fn common_prefix_len(&self, other: &DhtKey) -> Option<usize> {
+        let distance = self.distance(other);
+        distance.0.iter()
+            .position(|byte| *byte != 0)
+            .map(|i| i * 8 + distance.0[i].leading_zeros() as usize)
+    }
+}
+
+/// Domains at one distance range from this node, least recently seen first
+#[derive(Debug, Clone, Default)]
+pub struct KBucket {
+    nodes: VecDeque<DomainId>,
+}
+
+impl KBucket {
+    /// Record a domain as seen, moving it to the back. A full bucket keeps
+    /// its long-lived domains and turns a newcomer away, as in Kademlia.
+    /// Returns whether the domain is in the bucket.
+    fn insert(&mut self, domain_id: &DomainId) -> bool {
+        if let Some(position) = self.nodes.iter().position(|d| d == domain_id) {
+            let seen = self.nodes.remove(position).expect("position is in range");
+            self.nodes.push_back(seen);
+            return true;
+        }
+        if self.nodes.len() >= K {
+            return false;
+        }
+        self.nodes.push_back(domain_id.clone());
+        true
+    }
+
+    fn remove(&mut self, domain_id: &DomainId) {
+        self.nodes.retain(|d| d != domain_id);
+    }
+
+    /// Domains in the bucket, least recently seen first
+    pub fn nodes(&self) -> impl Iterator<Item = &DomainId> {
+        self.nodes.iter()
+    }
+}
+
+/// Answers find-node queries: the domains another domain knows closest to
+/// a key
+#[async_trait::async_trait]
+pub trait FindNode: Send + Sync {
+    async fn find_node(&self, domain_id: &DomainId, target: &DhtKey) -> GarpResult<Vec<DomainId>>;
+}
+
+/// Result of a lookup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DhtRoute {
+    /// Closest domain to the target found, the target itself if it was found
+    pub closest: DomainId,
+
+    /// Directly connected domain the path to `closest` starts at
+    pub next_hop: DomainId,
+}
+
+/// Message on its way to a domain this node has no connection to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DhtRelay {
+    /// Domain the message is for
+    pub target_domain: DomainId,
+
+    /// Message type delivered to the target
+    pub message_type: String,
+
+    /// Message payload
+    pub payload: Vec<u8>,
+
+    /// Relays left before the message is dropped
+    pub hops_remaining: u8,
+}
+
+/// Kademlia routing table of directly connected domains
+pub struct DhtRouter {
+    /// This node's domain
+    own_id: DomainId,
+
+    /// This node's key
+    own_key: DhtKey,
+
+    /// One bucket per shared prefix length
+    buckets: RwLock<Vec<KBucket>>,
+}
+
+impl DhtRouter {
+    pub fn new(own_id: DomainId) -> Self {
+        Self {
+            own_key: DhtKey::of(&own_id),
+            own_id,
+            buckets: RwLock::new(vec![KBucket::default(); KEY_BITS]),
+        }
+    }
+
+    /// Add a connected domain, or mark it as recently seen. Returns whether
+    /// it is in the routing table.
+    pub async fn add_node(&self, domain_id: &DomainId) -> bool {
+        let Some(bucket) = self.own_key.common_prefix_len(&DhtKey::of(domain_id)) else {
+            return false;
+        };
+        self.buckets.write().await[bucket].insert(domain_id)
+    }
+
+    /// Remove a domain that is no longer connected
+    pub async fn remove_node(&self, domain_id: &DomainId) {
+        if let Some(bucket) = self.own_key.common_prefix_len(&DhtKey::of(domain_id)) {
+            self.buckets.write().await[bucket].remove(domain_id);
+        }
+    }
+
+    /// Up to `count` domains in the routing table, closest to `target` first
+    pub async fn closest(&self, target: &DhtKey, count: usize) -> Vec<DomainId> {
+        let buckets = self.buckets.read().await;
+        let mut nodes: Vec<DomainId> = buckets.iter().flat_map(|bucket| bucket.nodes().cloned()).collect();
+        nodes.sort_by_key(|domain_id| DhtKey::of(domain_id).distance(target));
+        nodes.truncate(count);
+        nodes
+    }
+
+    /// Locate a domain by iterative find-node queries, [`ALPHA`] at a time,
+    /// starting from the closest domains in the routing table. Returns the
+    /// closest domain found with the connected domain to send through, or
+    /// `None` if the routing table is empty.
+    pub async fn find_peer<Q: FindNode + ?Sized>(&self, target_domain_id: &DomainId, rpc: &Q) -> Option<DhtRoute> {
+        let target = DhtKey::of(target_domain_id);
+
+        // Domains found, each with the connected domain its path starts at
+        let mut found: HashMap<DomainId, DomainId> = self.closest(&target, K).await
+            .into_iter()
+            .map(|domain_id| (domain_id.clone(), domain_id))
+            .collect();
+        let mut queried = HashSet::new();
+
+        while !found.contains_key(target_domain_id) {
+            let round: Vec<DomainId> = Self::by_distance(found.keys(), &target)
+                .into_iter()
+                .take(K)
+                .filter(|domain_id| !queried.contains(domain_id))
+                .take(ALPHA)
+                .collect();
+            if round.is_empty() {
+                break;
+            }
+
+            for domain_id in round {
+                queried.insert(domain_id.clone());
+                let next_hop = found[&domain_id].clone();
+                match rpc.find_node(&domain_id, &target).await {
+                    Ok(nodes) => {
+                        for node in nodes {
+                            if node != self.own_id {
+                                found.entry(node).or_insert_with(|| next_hop.clone());
+                            }
+                        }
+                    }
+                    Err(e) => debug!("Find-node query to {} failed: {}", domain_id, e),
+                }
+            }
+        }
+
+        let closest = Self::by_distance(found.keys(), &target).into_iter().next()?;
+        Some(DhtRoute { next_hop: found[&closest].clone(), closest })
+    }
+
+    fn by_distance<'a>(domains: impl Iterator<Item = &'a DomainId>, target: &DhtKey) -> Vec<DomainId> {
+        let mut domains: Vec<DomainId> = domains.cloned().collect();
+        domains.sort_by_key(|domain_id| DhtKey::of(domain_id).distance(target));
+        domains
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Find-node answers from a fixed neighbour graph
+    struct Graph {
+        neighbours: HashMap<&'static str, Vec<&'static str>>,
+        queries: Mutex<Vec<DomainId>>,
+    }
+
+    impl Graph {
+        fn new(edges: &[(&'static str, &[&'static str])]) -> Self {
+            Self {
+                neighbours: edges.iter().map(|(node, neighbours)| (*node, neighbours.to_vec())).collect(),
+                queries: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FindNode for Graph {
+        async fn find_node(&self, domain_id: &DomainId, _target: &DhtKey) -> GarpResult<Vec<DomainId>> {
+            self.queries.lock().unwrap().push(domain_id.clone());
+            Ok(self.neighbours.get(domain_id.as_str())
+                .map(|neighbours| neighbours.iter().map(|n| n.to_string()).collect())
+                .unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn test_xor_distance() {
+        let a = DhtKey::of("domain-a");
+        let b = DhtKey::of("domain-b");
+        assert_eq!(a.distance(&a), DhtKey([0; 32]));
+        assert_eq!(a.distance(&b), b.distance(&a));
+        assert_eq!(a.common_prefix_len(&a), None);
+        assert!(a.common_prefix_len(&b).unwrap() < KEY_BITS);
+    }
+
+    #[tokio::test]
+    async fn test_full_bucket_keeps_long_lived_domains() {
+        let mut bucket = KBucket::default();
+        for i in 0..K {
+            assert!(bucket.insert(&format!("domain-{}", i)));
+        }
+        assert!(!bucket.insert(&"newcomer".to_string()));
+
+        // Seeing a domain again moves it to the back
+        assert!(bucket.insert(&"domain-0".to_string()));
+        assert_eq!(bucket.nodes().last().unwrap(), "domain-0");
+
+        let router = DhtRouter::new("self".to_string());
+        assert!(!router.add_node(&"self".to_string()).await);
+        assert!(router.add_node(&"domain-a".to_string()).await);
+        router.remove_node(&"domain-a".to_string()).await;
+        assert!(router.closest(&DhtKey::of("domain-a"), K).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_peer_routes_through_the_connected_domain_on_the_path() {
+        let router = DhtRouter::new("self".to_string());
+        assert_eq!(router.find_peer(&"target".to_string(), &Graph::new(&[])).await, None);
+
+        router.add_node(&"a".to_string()).await;
+        router.add_node(&"b".to_string()).await;
+        let graph = Graph::new(&[
+            ("a", &["self", "c"]),
+            ("b", &["self"]),
+            ("c", &["a", "target"]),
+        ]);
+
+        let route = router.find_peer(&"target".to_string(), &graph).await.unwrap();
+        assert_eq!(route, DhtRoute { closest: "target".to_string(), next_hop: "a".to_string() });
+        // The target itself is never asked, nor this node
+        let queries = graph.queries.lock().unwrap().clone();
+        assert!(!queries.contains(&"target".to_string()));
+        assert!(!queries.contains(&"self".to_string()));
+
+        // An unreachable target resolves to the closest domain found
+        let route = router.find_peer(&"elsewhere".to_string(), &graph).await.unwrap();
+        let mut candidates = vec!["a", "b", "c", "target"];
+        candidates.sort_by_key(|d| DhtKey::of(d).distance(&DhtKey::of("elsewhere")));
+        assert_eq!(route.closest, candidates[0]);
+    }
+}
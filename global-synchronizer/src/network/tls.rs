@@ -0,0 +1,302 @@
+//! Mutual TLS for peer connections.
+//!
+//! Every node holds a certificate signed by the CA at
+//! `network.ca_cert_path`, with its domain ID as the subject common name and
+//! as a DNS subject alternative name. Both sides of a connection present
+//! their certificate and verify the other's against the CA; the side that
+//! knows which domain it expects then checks the peer's subject. The server
+//! and client configurations are swapped as a unit on rotation, so new
+//! handshakes use the new certificate while established sessions continue
+//! on the old one. A QUIC transport would take the same configurations from
+//! [`TlsTransport::server_config`] and [`TlsTransport::client_config`].
+
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+use tokio::net::TcpStream;
+use tokio::sync::RwLock;
+use tokio_rustls::{client, server, TlsAcceptor, TlsConnector};
+use tracing::{info, warn};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use garp_common::{GarpResult, GarpError};
+use garp_common::error::NetworkError;
+use garp_common::types::DomainId;
+
+use crate::config::NetworkConfig;
+
+/// Tracing target of TLS handshake failures, so they can be filtered apart
+/// from other network logs
+pub const TLS_HANDSHAKE_LOG_TARGET: &str = "garp::network::tls";
+
+/// Authenticated stream to a peer
+pub enum TlsSession {
+    /// Connection the peer dialed
+    Inbound(server::TlsStream<TcpStream>),
+
+    /// Connection this node dialed
+    Outbound(client::TlsStream<TcpStream>),
+}
+
+/// Server and client configuration built from one node certificate
+struct TlsConfigs {
+    server: Arc<ServerConfig>,
+    client: Arc<ClientConfig>,
+}
+
+/// Mutual TLS setup shared by every peer transport
+pub struct TlsTransport {
+    /// Trusted CA
+    roots: Arc<RootCertStore>,
+
+    /// Current configurations
+    configs: RwLock<TlsConfigs>,
+}
+
+fn load_certs(path: &str) -> GarpResult<Vec<CertificateDer<'static>>> {
+    let file = File::open(path)
+        .map_err(|e| GarpError::ConfigError(format!("Failed to open certificate {}: {}", path, e)))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| GarpError::ConfigError(format!("Failed to read certificate {}: {}", path, e)))?;
+    if certs.is_empty() {
+        return Err(GarpError::ConfigError(format!("No certificate in {}", path)));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &str) -> GarpResult<PrivateKeyDer<'static>> {
+    let file = File::open(path)
+        .map_err(|e| GarpError::ConfigError(format!("Failed to open private key {}: {}", path, e)))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| GarpError::ConfigError(format!("Failed to read private key {}: {}", path, e)))?
+        .ok_or_else(|| GarpError::ConfigError(format!("No private key in {}", path)))
+}
+
+fn build_configs(roots: &Arc<RootCertStore>, cert_path: &str, key_path: &str) -> GarpResult<TlsConfigs> {
+    let certs = load_certs(cert_path)?;
+    let key = load_key(key_path)?;
+
+    let verifier = WebPkiClientVerifier::builder(roots.clone())
+        .build()
+        .map_err(|e| GarpError::ConfigError(format!("Invalid TLS CA: {}", e)))?;
+    let server = ServerConfig::builder()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(certs.clone(), key.clone_key())
+        .map_err(|e| GarpError::ConfigError(format!("Invalid TLS certificate {}: {}", cert_path, e)))?;
+    let client = ClientConfig::builder()
+        .with_root_certificates(roots.clone())
+        .with_client_auth_cert(certs, key)
+        .map_err(|e| GarpError::ConfigError(format!("Invalid TLS certificate {}: {}", cert_path, e)))?;
+
+    Ok(TlsConfigs {
+        server: Arc::new(server),
+        client: Arc::new(client),
+    })
+}
+
+/// Subject common name of the first certificate in `certs`
+pub fn peer_subject(certs: Option<&[CertificateDer<'_>]>) -> Option<String> {
+    let (_, cert) = X509Certificate::from_der(certs?.first()?.as_ref()).ok()?;
+    let common_name = cert.subject().iter_common_name().next()?;
+    common_name.as_str().ok().map(str::to_string)
+}
+
+fn check_subject(subject: Option<String>, expected: &DomainId) -> GarpResult<()> {
+    match subject {
+        Some(subject) if &subject == expected => Ok(()),
+        Some(subject) => {
+            warn!(target: TLS_HANDSHAKE_LOG_TARGET, "Peer certificate subject {} does not match expected domain {}", subject, expected);
+            Err(NetworkError::AuthenticationFailed(format!("certificate subject {} is not domain {}", subject, expected)).into())
+        }
+        None => {
+            warn!(target: TLS_HANDSHAKE_LOG_TARGET, "Peer certificate for {} has no subject common name", expected);
+            Err(NetworkError::AuthenticationFailed(format!("certificate for {} has no subject", expected)).into())
+        }
+    }
+}
+
+impl TlsTransport {
+    /// Load the CA and node certificate
+    pub fn new(ca_cert_path: &str, cert_path: &str, key_path: &str) -> GarpResult<Self> {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(ca_cert_path)? {
+            roots.add(cert)
+                .map_err(|e| GarpError::ConfigError(format!("Invalid CA certificate {}: {}", ca_cert_path, e)))?;
+        }
+        let roots = Arc::new(roots);
+        let configs = build_configs(&roots, cert_path, key_path)?;
+
+        Ok(Self {
+            roots,
+            configs: RwLock::new(configs),
+        })
+    }
+
+    /// Transport for the network configuration, or `None` if TLS is disabled
+    pub fn from_config(config: &NetworkConfig) -> GarpResult<Option<Self>> {
+        if !config.enable_tls {
+            return Ok(None);
+        }
+        let path = |value: &Option<String>, name: &str| {
+            value.clone().ok_or_else(|| GarpError::ConfigError(format!("network.{} is required when TLS is enabled", name)))
+        };
+        let ca_cert_path = path(&config.ca_cert_path, "ca_cert_path")?;
+        let cert_path = path(&config.tls_cert_path, "tls_cert_path")?;
+        let key_path = path(&config.tls_key_path, "tls_key_path")?;
+        Self::new(&ca_cert_path, &cert_path, &key_path).map(Some)
+    }
+
+    /// Replace the node certificate. Handshakes already in progress finish
+    /// with the previous one.
+    pub async fn rotate(&self, cert_path: &str, key_path: &str) -> GarpResult<()> {
+        let configs = build_configs(&self.roots, cert_path, key_path)?;
+        *self.configs.write().await = configs;
+        info!("Rotated TLS certificate to {}", cert_path);
+        Ok(())
+    }
+
+    /// Server configuration requiring CA-signed client certificates
+    pub async fn server_config(&self) -> Arc<ServerConfig> {
+        self.configs.read().await.server.clone()
+    }
+
+    /// Client configuration presenting the node certificate
+    pub async fn client_config(&self) -> Arc<ClientConfig> {
+        self.configs.read().await.client.clone()
+    }
+
+    /// Complete the server side of a handshake and check the client is
+    /// `expected`
+    pub async fn accept(&self, stream: TcpStream, expected: &DomainId) -> GarpResult<server::TlsStream<TcpStream>> {
+        let peer_address = stream.peer_addr().ok();
+        let acceptor = TlsAcceptor::from(self.server_config().await);
+        let stream = acceptor.accept(stream).await.map_err(|e| {
+            warn!(target: TLS_HANDSHAKE_LOG_TARGET, "Inbound TLS handshake from {:?} failed: {}", peer_address, e);
+            GarpError::from(NetworkError::AuthenticationFailed(format!("TLS handshake with {} failed: {}", expected, e)))
+        })?;
+        check_subject(peer_subject(stream.get_ref().1.peer_certificates()), expected)?;
+        Ok(stream)
+    }
+
+    /// Complete the client side of a handshake with `expected`, which must
+    /// also be a DNS name on its certificate
+    pub async fn connect(&self, stream: TcpStream, expected: &DomainId) -> GarpResult<client::TlsStream<TcpStream>> {
+        let server_name = ServerName::try_from(expected.as_str())
+            .map_err(|e| NetworkError::AuthenticationFailed(format!("{} is not a valid TLS server name: {}", expected, e)))?
+            .to_owned();
+        let connector = TlsConnector::from(self.client_config().await);
+        let stream = connector.connect(server_name, stream).await.map_err(|e| {
+            warn!(target: TLS_HANDSHAKE_LOG_TARGET, "Outbound TLS handshake with {} failed: {}", expected, e);
+            GarpError::from(NetworkError::AuthenticationFailed(format!("TLS handshake with {} failed: {}", expected, e)))
+        })?;
+        check_subject(peer_subject(stream.get_ref().1.peer_certificates()), expected)?;
+        Ok(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, Certificate, CertificateParams, DnType, IsCa};
+    use std::path::Path;
+    use tokio::net::TcpListener;
+
+    fn ca() -> Certificate {
+        let mut params = CertificateParams::new(Vec::new());
+        params.distinguished_name.push(DnType::CommonName, "garp test ca");
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        Certificate::from_params(params).unwrap()
+    }
+
+    /// Write a certificate for `domain` signed by `ca`, returning its
+    /// certificate and key paths
+    fn node_cert(dir: &Path, ca: &Certificate, domain: &str, name: &str) -> (String, String) {
+        let mut params = CertificateParams::new(vec![domain.to_string()]);
+        params.distinguished_name.push(DnType::CommonName, domain);
+        let cert = Certificate::from_params(params).unwrap();
+        let cert_path = dir.join(format!("{}.pem", name));
+        let key_path = dir.join(format!("{}.key", name));
+        std::fs::write(&cert_path, cert.serialize_pem_with_signer(ca).unwrap()).unwrap();
+        std::fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+        (cert_path.to_string_lossy().into_owned(), key_path.to_string_lossy().into_owned())
+    }
+
+    fn transport(dir: &Path, ca: &Certificate, domain: &str) -> TlsTransport {
+        let ca_path = dir.join("ca.pem");
+        std::fs::write(&ca_path, ca.serialize_pem().unwrap()).unwrap();
+        let (cert_path, key_path) = node_cert(dir, ca, domain, domain);
+        TlsTransport::new(ca_path.to_str().unwrap(), &cert_path, &key_path).unwrap()
+    }
+
+    /// Handshake `client` with `server`, each expecting the given domain of
+    /// the other
+    async fn handshake(
+        server: Arc<TlsTransport>,
+        server_expects: &str,
+        client: &TlsTransport,
+        client_expects: &str,
+    ) -> (GarpResult<()>, GarpResult<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server_expects = server_expects.to_string();
+        let accepted = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            server.accept(stream, &server_expects).await.map(|_| ())
+        });
+
+        let stream = TcpStream::connect(address).await.unwrap();
+        let connected = client.connect(stream, &client_expects.to_string()).await.map(|_| ());
+        (accepted.await.unwrap(), connected)
+    }
+
+    #[tokio::test]
+    async fn test_mutual_authentication() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = ca();
+        let server = Arc::new(transport(dir.path(), &ca, "domain1"));
+        let client = transport(dir.path(), &ca, "domain2");
+
+        let (accepted, connected) = handshake(server.clone(), "domain2", &client, "domain1").await;
+        assert!(accepted.is_ok());
+        assert!(connected.is_ok());
+
+        // The client is authentic but not the domain the server expects
+        let (accepted, _) = handshake(server, "domain3", &client, "domain1").await;
+        assert!(accepted.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_certificate_from_other_ca() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = Arc::new(transport(dir.path(), &ca(), "domain1"));
+        let other_dir = tempfile::tempdir().unwrap();
+        let client = transport(other_dir.path(), &ca(), "domain2");
+
+        let (accepted, connected) = handshake(server, "domain2", &client, "domain1").await;
+        assert!(accepted.is_err());
+        assert!(connected.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rotated_certificate_is_presented() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = ca();
+        let server = Arc::new(transport(dir.path(), &ca, "domain1"));
+        let client = transport(dir.path(), &ca, "domain2");
+
+        // Rotating to a certificate for another domain changes the subject
+        // new handshakes present
+        let (cert_path, key_path) = node_cert(dir.path(), &ca, "domain1-next", "rotated");
+        server.rotate(&cert_path, &key_path).await.unwrap();
+        let (accepted, connected) = handshake(server.clone(), "domain2", &client, "domain1").await;
+        assert!(accepted.is_err() || connected.is_err());
+        let (accepted, connected) = handshake(server, "domain2", &client, "domain1-next").await;
+        assert!(accepted.is_ok());
+        assert!(connected.is_ok());
+    }
+}
@@ -0,0 +1,183 @@
+//! Node signing key lifecycle
+//!
+//! With `security.keystore_path` set, the node signing key is loaded from an
+//! encrypted [`Keystore`] at startup and installed as the key consensus
+//! messages, VRF proofs and ledger checkpoints are signed with. A key found
+//! in `SYNC_NODE_ED25519_SK_HEX` is imported into a new keystore, so nodes
+//! keep their identity when they move off the plaintext key.
+//!
+//! A rotation, triggered through the admin API, switches the node to a new
+//! key at once and broadcasts the [`KeyRotation`] to peers. Peers keep
+//! accepting the old key for `security.key_rotation_overlap_secs`; after
+//! that its secret is retired from the keystore. Announcements from peers
+//! are applied to the validator set and to domain discovery, whichever
+//! knows the rotating node.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::Mutex;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use garp_common::{GarpError, GarpResult, KeyHistory, KeyRotation, Keystore, PassphraseProtector};
+
+use crate::config::GlobalSyncConfig;
+use crate::consensus::{install_node_signing_key, node_signing_key, ConsensusEngine};
+use crate::cross_domain::CrossDomainCoordinator;
+use crate::network::{InboundMessage, MessageDestination, MessagePriority, NetworkManager};
+
+/// Network message type for key rotation announcements
+pub const KEY_ROTATION_MESSAGE_TYPE: &str = "key_rotation";
+
+/// How often retired keys are dropped from the keystore
+const RETIREMENT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Loads, rotates and retires the node signing key, and applies the key
+/// rotations announced by peers
+pub struct NodeKeyManager {
+    /// Keystore, `None` when the node signs with the plaintext key
+    keystore: Option<Mutex<Keystore>>,
+    overlap_secs: u64,
+    consensus_engine: Arc<ConsensusEngine>,
+    cross_domain_coordinator: Arc<CrossDomainCoordinator>,
+    network_manager: Arc<NetworkManager>,
+}
+
+impl NodeKeyManager {
+    /// Open the configured keystore, creating it on first start, and sign
+    /// with its current key
+    pub async fn new(
+        config: Arc<GlobalSyncConfig>,
+        consensus_engine: Arc<ConsensusEngine>,
+        cross_domain_coordinator: Arc<CrossDomainCoordinator>,
+        network_manager: Arc<NetworkManager>,
+    ) -> GarpResult<Self> {
+        let keystore = match &config.security.keystore_path {
+            Some(path) => {
+                let protector = Arc::new(PassphraseProtector::from_env(&config.security.keystore_passphrase_env)?);
+                let plaintext_key = node_signing_key().map(|key| key.to_bytes());
+                let mut keystore = Keystore::open_or_create(path, &config.node.node_id, protector, plaintext_key).await?;
+                keystore.retire_expired(Utc::now()).await?;
+                install_node_signing_key(keystore.signing_key().clone());
+                info!("Signing with key epoch {} from keystore {}", keystore.epoch(), path);
+                Some(Mutex::new(keystore))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            keystore,
+            overlap_secs: config.security.key_rotation_overlap_secs,
+            consensus_engine,
+            cross_domain_coordinator,
+            network_manager,
+        })
+    }
+
+    /// Public keys this node has signed with, `None` without a keystore
+    pub async fn history(&self) -> Option<KeyHistory> {
+        match &self.keystore {
+            Some(keystore) => Some(keystore.lock().await.history().clone()),
+            None => None,
+        }
+    }
+
+    /// Switch to a new signing key now and announce it to peers. Refused
+    /// while the previous rotation's overlap window is still open.
+    pub async fn rotate(&self) -> GarpResult<KeyRotation> {
+        let keystore = self.keystore.as_ref()
+            .ok_or_else(|| GarpError::ConfigError("Key rotation needs security.keystore_path".to_string()))?;
+        let rotation = {
+            let mut keystore = keystore.lock().await;
+            let rotation = keystore.rotate(Utc::now(), self.overlap_secs).await?;
+            install_node_signing_key(keystore.signing_key().clone());
+            rotation
+        };
+
+        // Our own view of the node follows the rotation too
+        if let Err(e) = self.accept(&rotation).await {
+            debug!("Own key rotation not applied locally: {}", e);
+        }
+        let data = serde_json::to_vec(&rotation)
+            .map_err(|e| GarpError::InternalError(format!("Failed to encode key rotation: {}", e)))?;
+        if let Err(e) = self.network_manager
+            .send_message(MessageDestination::Broadcast, KEY_ROTATION_MESSAGE_TYPE.to_string(), data, MessagePriority::High)
+            .await
+        {
+            warn!("Failed to announce key epoch {}: {}", rotation.epoch, e);
+        }
+        Ok(rotation)
+    }
+
+    /// Apply a key rotation announced by a validator or domain. Fails if the
+    /// node is unknown or the announcement is not signed by its current key.
+    pub async fn accept(&self, rotation: &KeyRotation) -> GarpResult<()> {
+        let mut known = false;
+        match self.consensus_engine.apply_key_rotation(rotation).await {
+            Ok(()) => known = true,
+            Err(GarpError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match self.cross_domain_coordinator.apply_domain_key_rotation(rotation).await {
+            Ok(_) => known = true,
+            Err(GarpError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        if !known {
+            return Err(GarpError::NotFound(format!("No validator or domain {}", rotation.node_id)));
+        }
+        info!(
+            "{} rotated to key epoch {}; previous key accepted for {}s",
+            rotation.node_id, rotation.epoch, rotation.overlap_secs
+        );
+        Ok(())
+    }
+
+    /// Drop keystore secrets whose overlap window has passed
+    pub async fn retire_expired(&self) -> GarpResult<Vec<u64>> {
+        match &self.keystore {
+            Some(keystore) => keystore.lock().await.retire_expired(Utc::now()).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Apply key rotations broadcast by peers
+    pub async fn register_handler(self: &Arc<Self>) -> GarpResult<()> {
+        let manager = self.clone();
+        self.network_manager
+            .register_message_handler(KEY_ROTATION_MESSAGE_TYPE.to_string(), move |inbound: &InboundMessage| {
+                let manager = manager.clone();
+                let source = inbound.source.clone();
+                let data = inbound.data.clone();
+                tokio::spawn(async move {
+                    match serde_json::from_slice::<KeyRotation>(&data) {
+                        Ok(rotation) => {
+                            if let Err(e) = manager.accept(&rotation).await {
+                                warn!("Key rotation of {} from {} rejected: {}", rotation.node_id, source, e);
+                            }
+                        }
+                        Err(e) => warn!("Invalid key rotation message from {}: {}", source, e),
+                    }
+                });
+                Ok(())
+            })
+            .await
+    }
+
+    /// Periodically retire keys past their overlap window
+    pub fn spawn_retirement(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        self.keystore.as_ref()?;
+        let manager = self.clone();
+        Some(tokio::spawn(async move {
+            let mut interval = interval(RETIREMENT_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = manager.retire_expired().await {
+                    error!("Failed to retire expired signing keys: {}", e);
+                }
+            }
+        }))
+    }
+}
@@ -0,0 +1,267 @@
+//! Transaction receipts with inclusion proofs.
+//!
+//! A block commits to its transactions through `tx_root`, a SHA-256 Merkle
+//! root over the transaction IDs in block order, and its hash is
+//! `blake3(parent_hash || slot_le || tx_root)`. A receipt carries the block
+//! parent hash and an inclusion proof, so anyone holding the block's
+//! finality certificate can recompute the certified hash and check the
+//! transaction is in that block without contacting a node.
+//!
+//! Proofs use the participant node's encoding, which the SDK verifies: leaves
+//! are SHA-256 over the 16 UUID bytes, parents are `sha256(left || right)`
+//! with the last node duplicated on odd levels, and every hash is
+//! hex-encoded. `directions[i]` is `"right"` when the running hash is the
+//! right child at level `i` and `"left"` otherwise.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use garp_common::types::TransactionId;
+
+use crate::consensus::FinalityCertificate;
+use crate::settlement::zk::ZkProof;
+use crate::storage::DomainId;
+
+/// Inclusion proof for a single transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Hex-encoded hash of the block containing the transaction
+    pub block_hash: String,
+
+    /// Transaction ID
+    pub tx_id: String,
+
+    /// Hex-encoded leaf hash
+    pub leaf_hash: String,
+
+    /// Hex-encoded transaction root
+    pub root: String,
+
+    /// Hex-encoded sibling hashes from the leaf up
+    pub path: Vec<String>,
+
+    /// Side of the running hash at each level
+    pub directions: Vec<String>,
+}
+
+/// Verifiable evidence that a transaction is in a finalized block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    /// Transaction ID
+    pub transaction_id: TransactionId,
+
+    /// Hex-encoded block hash
+    pub block_hash: String,
+
+    /// Block height
+    pub block_height: u64,
+
+    /// Hex-encoded parent hash, needed to recompute the block hash
+    pub parent_hash: String,
+
+    /// Inclusion proof against the block's transaction root
+    pub merkle_proof: MerkleProof,
+
+    /// Zero-knowledge settlement proof, if one was generated
+    pub settlement_proof: Option<ZkProof>,
+
+    /// Time the block's finality certificate was issued
+    pub finalized_at: DateTime<Utc>,
+
+    /// Domains that confirmed the settlement
+    pub domains_confirmed: Vec<DomainId>,
+}
+
+/// Leaf hash of a transaction ID
+pub fn leaf_hash(transaction_id: &TransactionId) -> Vec<u8> {
+    Sha256::digest(transaction_id.0.as_bytes()).to_vec()
+}
+
+fn parent(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().to_vec()
+}
+
+/// Merkle tree levels from the leaves up to the root
+pub(crate) fn merkle_levels(leaves: Vec<Vec<u8>>) -> Vec<Vec<Vec<u8>>> {
+    let mut levels = vec![leaves];
+    while levels.last().map_or(false, |level| level.len() > 1) {
+        let level = levels.last().unwrap();
+        let next = level.chunks(2)
+            .map(|pair| parent(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Transaction root of a block. An empty block commits to the hash of no
+/// input.
+pub fn transaction_root(transaction_ids: &[TransactionId]) -> Vec<u8> {
+    if transaction_ids.is_empty() {
+        return Sha256::digest([]).to_vec();
+    }
+    merkle_levels(transaction_ids.iter().map(leaf_hash).collect()).pop().unwrap().remove(0)
+}
+
+/// Hash of a block with the given parent, height and transaction root
+pub fn block_hash(parent_hash: &[u8], height: u64, tx_root: &[u8]) -> Vec<u8> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(parent_hash);
+    hasher.update(&height.to_le_bytes());
+    hasher.update(tx_root);
+    hasher.finalize().as_bytes().to_vec()
+}
+
+/// Inclusion proof for `transaction_id` among a block's transactions, or
+/// `None` if it is not one of them
+pub fn merkle_proof(block_hash: &[u8], transaction_ids: &[TransactionId], transaction_id: &TransactionId) -> Option<MerkleProof> {
+    let index = transaction_ids.iter().position(|id| id == transaction_id)?;
+    let levels = merkle_levels(transaction_ids.iter().map(leaf_hash).collect());
+    let (path, directions) = merkle_path(&levels, index);
+
+    Some(MerkleProof {
+        block_hash: hex::encode(block_hash),
+        tx_id: transaction_id.to_string(),
+        leaf_hash: hex::encode(leaf_hash(transaction_id)),
+        root: hex::encode(&levels.last()?[0]),
+        path,
+        directions,
+    })
+}
+
+/// Hex-encoded sibling hashes and directions from the leaf at `index` up
+pub(crate) fn merkle_path(levels: &[Vec<Vec<u8>>], mut index: usize) -> (Vec<String>, Vec<String>) {
+    let mut path = Vec::new();
+    let mut directions = Vec::new();
+
+    for level in &levels[..levels.len() - 1] {
+        let (sibling, direction) = if index % 2 == 1 {
+            (&level[index - 1], "right")
+        } else {
+            (level.get(index + 1).unwrap_or(&level[index]), "left")
+        };
+        path.push(hex::encode(sibling));
+        directions.push(direction.to_string());
+        index /= 2;
+    }
+    (path, directions)
+}
+
+/// Root reached from `leaf` along a hex-encoded path, or `None` if the path
+/// is malformed
+pub(crate) fn merkle_path_root(leaf: Vec<u8>, path: &[String], directions: &[String]) -> Option<Vec<u8>> {
+    if path.len() != directions.len() {
+        return None;
+    }
+    let mut current = leaf;
+    for (sibling, direction) in path.iter().zip(directions) {
+        let sibling = hex::decode(sibling).ok()?;
+        current = match direction.as_str() {
+            "right" => parent(&sibling, &current),
+            "left" => parent(&current, &sibling),
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Recompute the root from `proof`, ignoring its `root` field
+fn proof_root(proof: &MerkleProof) -> Option<Vec<u8>> {
+    merkle_path_root(hex::decode(&proof.leaf_hash).ok()?, &proof.path, &proof.directions)
+}
+
+impl TransactionReceipt {
+    /// Check the receipt against the finality certificate of its block:
+    /// the certificate covers this block, the block hash follows from the
+    /// parent hash and the proven transaction root, and the proof's leaf is
+    /// this transaction. The certificate's signatures are not checked here.
+    pub fn verify(&self, certificate: &FinalityCertificate) -> bool {
+        if certificate.height != self.block_height
+            || !certificate.block_hash.eq_ignore_ascii_case(&self.block_hash)
+            || self.merkle_proof.block_hash != self.block_hash
+        {
+            return false;
+        }
+        if hex::decode(&self.merkle_proof.leaf_hash).ok() != Some(leaf_hash(&self.transaction_id)) {
+            return false;
+        }
+        let (Some(root), Ok(parent_hash), Ok(block)) = (
+            proof_root(&self.merkle_proof),
+            hex::decode(&self.parent_hash),
+            hex::decode(&self.block_hash),
+        ) else {
+            return false;
+        };
+        block_hash(&parent_hash, self.block_height, &root) == block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt(ids: &[TransactionId], index: usize) -> (TransactionReceipt, FinalityCertificate) {
+        let parent_hash = vec![7u8; 32];
+        let hash = block_hash(&parent_hash, 5, &transaction_root(ids));
+        let receipt = TransactionReceipt {
+            transaction_id: ids[index].clone(),
+            block_hash: hex::encode(&hash),
+            block_height: 5,
+            parent_hash: hex::encode(&parent_hash),
+            merkle_proof: merkle_proof(&hash, ids, &ids[index]).unwrap(),
+            settlement_proof: None,
+            finalized_at: Utc::now(),
+            domains_confirmed: vec!["domain1".to_string()],
+        };
+        let certificate = FinalityCertificate {
+            height: 5,
+            block_hash: hex::encode(&hash),
+            signatures: Vec::new(),
+            validator_set_hash: String::new(),
+            timestamp: receipt.finalized_at,
+            state_root: String::new(),
+        };
+        (receipt, certificate)
+    }
+
+    #[test]
+    fn test_receipts_verify_against_certificate() {
+        let ids: Vec<TransactionId> = (0..5).map(|_| TransactionId::new()).collect();
+        for index in 0..ids.len() {
+            let (receipt, certificate) = receipt(&ids, index);
+            assert_eq!(hex::decode(&receipt.merkle_proof.root).unwrap(), transaction_root(&ids));
+            assert!(receipt.verify(&certificate), "receipt {} should verify", index);
+        }
+
+        let (single, certificate) = receipt(&ids[..1], 0);
+        assert!(single.merkle_proof.path.is_empty());
+        assert!(single.verify(&certificate));
+        assert!(merkle_proof(&[0u8; 32], &ids[..2], &ids[3]).is_none());
+    }
+
+    #[test]
+    fn test_tampered_receipts_fail() {
+        let ids: Vec<TransactionId> = (0..4).map(|_| TransactionId::new()).collect();
+        let (receipt, certificate) = receipt(&ids, 2);
+
+        let mut other_tx = receipt.clone();
+        other_tx.transaction_id = ids[1].clone();
+        assert!(!other_tx.verify(&certificate));
+
+        let mut flipped = receipt.clone();
+        flipped.merkle_proof.directions[0] = "right".to_string();
+        assert!(!flipped.verify(&certificate));
+
+        let mut wrong_parent = receipt.clone();
+        wrong_parent.parent_hash = hex::encode([8u8; 32]);
+        assert!(!wrong_parent.verify(&certificate));
+
+        let mut other_block = certificate.clone();
+        other_block.height = 6;
+        assert!(!receipt.verify(&other_block));
+    }
+}
@@ -1,21 +1,34 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::{RwLock, Mutex, mpsc, oneshot};
 use tokio::time::{interval, timeout};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
-use tracing::{info, warn, error, debug};
+use tracing::{info, warn, error, debug, instrument};
 
 use garp_common::{GarpResult, GarpError};
+use garp_common::crypto::SigningKeyPair;
+use garp_common::settlement::{domain_confirmation_hash, FinalityReference, SettlementProof};
 use garp_common::types::{TransactionId, ParticipantId};
 
 use crate::config::GlobalSyncConfig;
-use crate::storage::{GlobalStorage, GlobalBlock, DomainId};
+use crate::storage::{GlobalStorage, GlobalBlock, DomainId, NodeId, EscrowRecord, EscrowStatus, RollbackRecord, RollbackStatus};
 use crate::network::NetworkManager;
-use crate::cross_domain::{CrossDomainTransaction, DomainConfirmation, ConfirmationStatus};
+use crate::cross_domain::{CrossDomainTransaction, CrossDomainTransactionType, DomainConfirmation, ConfirmationStatus};
 use crate::consensus::{ConsensusEngine, ConsensusResult};
 
+pub mod batch;
+pub mod compensation;
+pub mod escrow;
+pub mod rollback;
+pub mod zk;
+
+use batch::{NetworkSettlementSubmitter, PendingSettlement, SettlementBatcher};
+use escrow::{ConditionMonitor, ConditionType, EscrowDecision, EscrowLock, HttpConditionOracle};
+use rollback::{NetworkCompensationSender, RollbackCoordinator};
+use zk::{SettlementPublicInputs, SettlementWitness, ZkProof, ZkSettlementKeys};
+
 /// Settlement engine for finalizing cross-domain transactions
 pub struct SettlementEngine {
     /// Configuration
@@ -33,8 +46,14 @@ pub struct SettlementEngine {
     /// Active settlements
     active_settlements: Arc<RwLock<HashMap<TransactionId, Settlement>>>,
     
-    /// Settlement batches
-    settlement_batches: Arc<RwLock<HashMap<String, SettlementBatch>>>,
+    /// Per-domain settlement batching
+    batcher: Arc<SettlementBatcher>,
+    
+    /// Compensation of partially completed settlements
+    rollbacks: Arc<RollbackCoordinator>,
+    
+    /// Conditions of escrowed settlements
+    condition_monitor: Arc<ConditionMonitor>,
     
     /// Pending rollbacks
     pending_rollbacks: Arc<RwLock<HashMap<TransactionId, RollbackRequest>>>,
@@ -51,6 +70,12 @@ pub struct SettlementEngine {
     
     /// Metrics
     metrics: Arc<SettlementMetrics>,
+    
+    /// Node key signing settlement proofs
+    signing_key: Arc<SigningKeyPair>,
+    
+    /// Zero-knowledge proof parameters, loaded on first use
+    zk_keys: Arc<tokio::sync::OnceCell<Arc<ZkSettlementKeys>>>,
 }
 
 /// Settlement for a cross-domain transaction
@@ -77,9 +102,15 @@ pub struct Settlement {
     /// Settlement proof
     pub settlement_proof: Option<SettlementProof>,
     
+    /// Zero-knowledge settlement proof, generated on request
+    pub proof: Option<ZkProof>,
+    
     /// Rollback plan
     pub rollback_plan: Option<RollbackPlan>,
     
+    /// Rollback compensating the domains that settled before a failure
+    pub rollback_id: Option<String>,
+    
     /// Created timestamp
     pub created_at: Instant,
     
@@ -94,6 +125,65 @@ pub struct Settlement {
     
     /// Max retries
     pub max_retries: u32,
+    
+    /// Fee charged for the settlement, distributed to validators on completion
+    pub actual_fee: u64,
+    
+    /// Shares the fee was distributed to validators in
+    pub fee_shares: Vec<(NodeId, u64)>,
+    
+    /// Asset moved by the settlement, for asset transfers
+    pub asset_flow: Option<AssetFlow>,
+}
+
+/// Asset moved from one domain to another by a settlement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetFlow {
+    /// Domain the asset leaves
+    pub from_domain: DomainId,
+    
+    /// Domain the asset arrives at
+    pub to_domain: DomainId,
+    
+    /// Asset ID
+    pub asset_id: String,
+    
+    /// Amount moved
+    pub amount: u64,
+}
+
+impl AssetFlow {
+    /// Flow of an asset transfer to a single target domain. Other
+    /// transactions move no asset between a pair of domains.
+    pub fn of(transaction: &CrossDomainTransaction) -> Option<Self> {
+        let CrossDomainTransactionType::AssetTransfer { asset_id, amount, .. } = &transaction.transaction_type else {
+            return None;
+        };
+        match transaction.target_domains.as_slice() {
+            [to_domain] => Some(Self {
+                from_domain: transaction.source_domain.clone(),
+                to_domain: to_domain.clone(),
+                asset_id: asset_id.clone(),
+                amount: *amount,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Single transfer replacing two opposing settlements between the same pair
+/// of domains
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetSettlement {
+    /// Net settlement ID
+    pub net_settlement_id: String,
+    
+    /// IDs of the two settlements netted, in batch order
+    pub settlement_ids: [String; 2],
+    
+    /// Net transfer, in the direction of the larger settlement. Settlements
+    /// of equal amounts net to zero.
+    pub flow: AssetFlow,
 }
 
 /// Settlement type
@@ -110,6 +200,14 @@ pub enum SettlementType {
     
     /// Immediate settlement (no confirmation wait)
     Immediate,
+    
+    /// Escrowed settlement, released to the target domains once the
+    /// condition is met and refunded if `deadline` passes first
+    Conditional {
+        condition_id: String,
+        condition_type: ConditionType,
+        deadline: SystemTime,
+    },
 }
 
 /// Settlement status
@@ -124,6 +222,9 @@ pub enum SettlementStatus {
     /// Committing settlement
     Committing,
     
+    /// Assets held in escrow until the settlement's condition is met
+    Escrowed,
+    
     /// Settlement completed
     Completed,
     
@@ -187,72 +288,6 @@ pub enum DomainSettlementStatus {
     RolledBack,
 }
 
-/// Settlement proof
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SettlementProof {
-    /// Settlement ID
-    pub settlement_id: String,
-    
-    /// Transaction ID
-    pub transaction_id: TransactionId,
-    
-    /// Proof type
-    pub proof_type: SettlementProofType,
-    
-    /// Proof data
-    pub proof_data: Vec<u8>,
-    
-    /// Merkle root
-    pub merkle_root: String,
-    
-    /// Domain proofs
-    pub domain_proofs: HashMap<DomainId, DomainProof>,
-    
-    /// Aggregated signature
-    pub aggregated_signature: Vec<u8>,
-    
-    /// Created timestamp
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-
-/// Settlement proof type
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SettlementProofType {
-    /// Merkle proof
-    Merkle,
-    
-    /// Signature aggregation proof
-    SignatureAggregation,
-    
-    /// Zero-knowledge proof
-    ZeroKnowledge,
-    
-    /// Multi-signature proof
-    MultiSignature,
-}
-
-/// Domain proof
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DomainProof {
-    /// Domain ID
-    pub domain_id: DomainId,
-    
-    /// Proof data
-    pub proof_data: Vec<u8>,
-    
-    /// Block height
-    pub block_height: u64,
-    
-    /// Block hash
-    pub block_hash: String,
-    
-    /// Signature
-    pub signature: Vec<u8>,
-    
-    /// Timestamp
-    pub timestamp: chrono::DateTime<chrono::Utc>,
-}
-
 /// Rollback plan
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RollbackPlan {
@@ -351,47 +386,6 @@ pub enum CompensationStatus {
     Failed,
 }
 
-/// Settlement batch
-#[derive(Debug, Clone)]
-pub struct SettlementBatch {
-    /// Batch ID
-    pub batch_id: String,
-    
-    /// Settlements in batch
-    pub settlements: Vec<TransactionId>,
-    
-    /// Batch status
-    pub status: SettlementBatchStatus,
-    
-    /// Batch size
-    pub batch_size: usize,
-    
-    /// Created timestamp
-    pub created_at: Instant,
-    
-    /// Processing started
-    pub processing_started_at: Option<Instant>,
-    
-    /// Completed timestamp
-    pub completed_at: Option<Instant>,
-}
-
-/// Settlement batch status
-#[derive(Debug, Clone, PartialEq)]
-pub enum SettlementBatchStatus {
-    /// Pending
-    Pending,
-    
-    /// Processing
-    Processing,
-    
-    /// Completed
-    Completed,
-    
-    /// Failed
-    Failed,
-}
-
 /// Settlement request
 #[derive(Debug, Clone)]
 pub struct SettlementRequest {
@@ -404,9 +398,15 @@ pub struct SettlementRequest {
     /// Priority
     pub priority: SettlementPriority,
     
+    /// Settlement fee, within the transaction's `max_fee`
+    pub fee: u64,
+    
     /// Requested timestamp
     pub requested_at: Instant,
     
+    /// Validator epoch the settlement was requested in
+    pub epoch: u64,
+    
     /// Timeout
     pub timeout_at: Instant,
 }
@@ -481,7 +481,7 @@ pub enum SettlementEvent {
     SettlementStarted(TransactionId),
     
     /// Settlement completed
-    SettlementCompleted(TransactionId, SettlementProof),
+    SettlementCompleted(TransactionId, Option<SettlementProof>),
     
     /// Settlement failed
     SettlementFailed(TransactionId, String),
@@ -552,22 +552,66 @@ impl SettlementEngine {
             avg_batch_processing_time: Arc::new(RwLock::new(0.0)),
         });
         
+        let batcher = Arc::new(SettlementBatcher::new(
+            config.clone(),
+            storage.clone(),
+            Arc::new(NetworkSettlementSubmitter::new(network_manager.clone())),
+        ));
+        
+        let rollbacks = Arc::new(RollbackCoordinator::new(
+            config.clone(),
+            storage.clone(),
+            Arc::new(NetworkCompensationSender::new(network_manager.clone())),
+        ));
+        
+        let condition_monitor = Arc::new(ConditionMonitor::new(
+            storage.clone(),
+            Arc::new(HttpConditionOracle::new(Duration::from_millis(config.settlement.escrow_poll_interval_ms))?),
+        ));
+        
+        let signing_key = Arc::new(Self::load_signing_key(&config.security.private_key_path));
+        
         Ok(Self {
             config,
             storage,
             network_manager,
             consensus_engine,
             active_settlements: Arc::new(RwLock::new(HashMap::new())),
-            settlement_batches: Arc::new(RwLock::new(HashMap::new())),
+            batcher,
+            rollbacks,
+            condition_monitor,
             pending_rollbacks: Arc::new(RwLock::new(HashMap::new())),
             settlement_queue: Arc::new(Mutex::new(VecDeque::new())),
             event_tx,
             event_rx,
             shutdown_tx: None,
             metrics,
+            signing_key,
+            zk_keys: Arc::new(tokio::sync::OnceCell::new()),
         })
     }
     
+    /// Load the proof signing key from `path`, stored as 32 raw or
+    /// hex-encoded bytes. Falls back to an ephemeral key, whose proofs only
+    /// verify against this run's public key.
+    fn load_signing_key(path: &str) -> SigningKeyPair {
+        let loaded = std::fs::read(path).ok().and_then(|bytes| {
+            let secret = if bytes.len() == 32 {
+                bytes
+            } else {
+                hex::decode(String::from_utf8_lossy(&bytes).trim()).ok()?
+            };
+            SigningKeyPair::from_bytes(&secret).ok()
+        });
+        match loaded {
+            Some(key) => key,
+            None => {
+                warn!("No usable settlement signing key at {}, generating an ephemeral key", path);
+                SigningKeyPair::generate()
+            }
+        }
+    }
+    
     /// Start the settlement engine
     pub async fn start(&self) -> GarpResult<()> {
         info!("Starting Settlement Engine");
@@ -584,6 +628,9 @@ impl SettlementEngine {
         // Start settlement monitor
         let settlement_monitor = self.start_settlement_monitor().await?;
         
+        // Start escrow condition monitor
+        let condition_monitor = self.start_condition_monitor().await?;
+        
         info!("Settlement Engine started successfully");
         Ok(())
     }
@@ -608,13 +655,28 @@ impl SettlementEngine {
         settlement_type: SettlementType,
         priority: SettlementPriority,
     ) -> GarpResult<()> {
-        info!("Requesting settlement for transaction: {}", transaction.transaction_id);
+        info!(transaction_id = %transaction.transaction_id, source_domain = %transaction.source_domain, "Requesting settlement");
+        
+        if let SettlementType::Conditional { condition_id, .. } = &settlement_type {
+            EscrowLock::of(&transaction, condition_id)?;
+        }
+        
+        let fee = self.estimate_fee(&transaction);
+        if fee > transaction.max_fee {
+            warn!(
+                "Aborting settlement for {}: fee {} exceeds max fee {}",
+                transaction.transaction_id, fee, transaction.max_fee
+            );
+            return Err(GarpError::FeeExceeded { required: fee, max_fee: transaction.max_fee });
+        }
         
         let request = SettlementRequest {
             transaction,
             settlement_type,
             priority,
+            fee,
             requested_at: Instant::now(),
+            epoch: self.consensus_engine.current_epoch().await,
             timeout_at: Instant::now() + Duration::from_secs(self.config.settlement.settlement_timeout),
         };
         
@@ -630,6 +692,14 @@ impl SettlementEngine {
         Ok(())
     }
     
+    /// Settlement fee for a transaction: a charge per participating domain
+    /// plus a charge per byte of transaction data
+    pub fn estimate_fee(&self, transaction: &CrossDomainTransaction) -> u64 {
+        let settlement = &self.config.settlement;
+        settlement.fee_per_domain.saturating_mul(transaction.target_domains.len() as u64)
+            .saturating_add(settlement.fee_per_byte.saturating_mul(transaction.data.len() as u64))
+    }
+    
     /// Get settlement status
     pub async fn get_settlement_status(&self, transaction_id: &TransactionId) -> Option<SettlementStatus> {
         let settlements = self.active_settlements.read().await;
@@ -642,7 +712,7 @@ impl SettlementEngine {
         transaction_id: TransactionId,
         reason: RollbackReason,
     ) -> GarpResult<()> {
-        info!("Requesting rollback for transaction: {} (reason: {:?})", transaction_id, reason);
+        info!(transaction_id = %transaction_id, reason = ?reason, "Requesting rollback");
         
         // Create rollback plan
         let rollback_plan = self.create_rollback_plan(&transaction_id, &reason).await?;
@@ -672,6 +742,375 @@ impl SettlementEngine {
         self.metrics.clone()
     }
     
+    /// Net opposing asset transfers in a batch of settlements. Two
+    /// settlements net when both are pending and move the same asset in
+    /// opposite directions between the same pair of domains; each is netted
+    /// at most once, pairing settlements in batch order. Settlements left
+    /// out of every pair are settled as usual. Returns nothing when netting
+    /// is disabled.
+    pub fn net_settlements(&self, batch: Vec<Settlement>) -> Vec<NetSettlement> {
+        if !self.config.settlement.enable_netting {
+            return Vec::new();
+        }
+        
+        // Unmatched settlements by (from, to, asset)
+        let mut unmatched: HashMap<(DomainId, DomainId, String), VecDeque<Settlement>> = HashMap::new();
+        let mut netted = Vec::new();
+        for settlement in batch {
+            if settlement.status != SettlementStatus::Pending {
+                continue;
+            }
+            let Some(flow) = settlement.asset_flow.clone() else { continue };
+            
+            let opposing = (flow.to_domain.clone(), flow.from_domain.clone(), flow.asset_id.clone());
+            match unmatched.get_mut(&opposing).and_then(VecDeque::pop_front) {
+                Some(earlier) => {
+                    let earlier_flow = earlier.asset_flow.clone().expect("unmatched settlements have a flow");
+                    let net_flow = if earlier_flow.amount >= flow.amount {
+                        AssetFlow { amount: earlier_flow.amount - flow.amount, ..earlier_flow }
+                    } else {
+                        AssetFlow { amount: flow.amount - earlier_flow.amount, ..flow }
+                    };
+                    debug!(
+                        "Netted settlements {} and {} into {} {} from {} to {}",
+                        earlier.settlement_id, settlement.settlement_id,
+                        net_flow.amount, net_flow.asset_id, net_flow.from_domain, net_flow.to_domain
+                    );
+                    netted.push(NetSettlement {
+                        net_settlement_id: Uuid::new_v4().to_string(),
+                        settlement_ids: [earlier.settlement_id, settlement.settlement_id],
+                        flow: net_flow,
+                    });
+                }
+                None => {
+                    unmatched.entry((flow.from_domain, flow.to_domain, flow.asset_id))
+                        .or_default()
+                        .push_back(settlement);
+                }
+            }
+        }
+        netted
+    }
+    
+    /// Turn settlement batching on or off without a restart
+    pub async fn set_batching_enabled(&self, enabled: bool) {
+        self.batcher.set_enabled(enabled).await;
+    }
+    
+    /// Whether settlement batching is enabled
+    pub fn is_batching_enabled(&self) -> bool {
+        self.batcher.is_enabled()
+    }
+    
+    /// Get a settlement batch
+    pub async fn get_settlement_batch(&self, batch_id: &str) -> GarpResult<Option<crate::storage::SettlementBatch>> {
+        self.storage.get_settlement_batch(batch_id).await
+    }
+    
+    /// Settlement batch counters
+    pub async fn get_batch_metrics(&self) -> crate::storage::SettlementBatchMetrics {
+        self.storage.settlement_batch_metrics().await
+    }
+    
+    /// Record a domain's acknowledgement of its rollback compensation. When
+    /// the last domain acknowledges, the settlement is marked rolled back.
+    pub async fn acknowledge_compensation(&self, rollback_id: &str, domain_id: &DomainId) -> GarpResult<RollbackRecord> {
+        let record = self.rollbacks.acknowledge(rollback_id, domain_id).await?;
+        if record.status != RollbackStatus::Completed {
+            return Ok(record);
+        }
+        
+        if let Some(transaction_id) = &record.transaction_id {
+            {
+                let mut settlements = self.active_settlements.write().await;
+                if let Some(settlement) = settlements.get_mut(transaction_id) {
+                    settlement.status = SettlementStatus::RolledBack;
+                    settlement.updated_at = Instant::now();
+                    for domain_settlement in settlement.domain_settlements.values_mut() {
+                        if domain_settlement.status == DomainSettlementStatus::Confirmed {
+                            domain_settlement.status = DomainSettlementStatus::RolledBack;
+                        }
+                    }
+                }
+            }
+            
+            {
+                let mut rolled_back = self.metrics.rolled_back_settlements.write().await;
+                *rolled_back += 1;
+            }
+            
+            self.event_tx.send(SettlementEvent::RollbackCompleted(transaction_id.clone()))?;
+        }
+        Ok(record)
+    }
+    
+    /// Get the proof of a completed settlement
+    pub async fn get_settlement_proof(&self, transaction_id: &TransactionId) -> GarpResult<Option<SettlementProof>> {
+        self.storage.get_settlement_proof(transaction_id).await
+    }
+    
+    /// Public key that settlement proofs are signed with
+    pub fn proof_public_key(&self) -> Vec<u8> {
+        self.signing_key.public_key_bytes()
+    }
+    
+    /// Generate a zero-knowledge proof that a completed settlement's
+    /// transaction is in a finalized block, that every participating domain
+    /// confirmed it and that its fee was charged and distributed per the fee
+    /// schedule. The proof is kept on the settlement.
+    pub async fn generate_settlement_proof(&self, settlement_id: &str) -> GarpResult<ZkProof> {
+        let settlement = {
+            let settlements = self.active_settlements.read().await;
+            settlements.values()
+                .find(|s| s.settlement_id == settlement_id)
+                .cloned()
+                .ok_or_else(|| GarpError::NotFound(format!("Settlement {} not found", settlement_id)))?
+        };
+        if settlement.status != SettlementStatus::Completed {
+            return Err(GarpError::ValidationError(format!(
+                "Settlement {} is {:?}, only completed settlements can be proven", settlement_id, settlement.status
+            )));
+        }
+        
+        let block_height = self.storage.get_transaction(&settlement.transaction_id).await?
+            .and_then(|stored| stored.block_height)
+            .ok_or_else(|| GarpError::NotFound(format!(
+                "Transaction {} is not in a block", settlement.transaction_id
+            )))?;
+        if self.storage.get_finality_certificate_by_height(block_height).await?.is_none() {
+            return Err(GarpError::ValidationError(format!("Block {} is not finalized", block_height)));
+        }
+        
+        let witness = SettlementWitness {
+            settlement_id: settlement_id.to_string(),
+            transaction_id: settlement.transaction_id.clone(),
+            block_transactions: self.storage.get_transactions_by_height(block_height).await?,
+            domains: settlement.participating_domains.iter()
+                .map(|domain_id| {
+                    let confirmed = settlement.domain_settlements.get(domain_id)
+                        .map_or(false, |ds| ds.status == DomainSettlementStatus::Confirmed);
+                    (domain_id.clone(), confirmed)
+                })
+                .collect(),
+            total_fee: settlement.actual_fee,
+            fee_per_domain: self.config.settlement.fee_per_domain,
+            fee_per_byte: self.config.settlement.fee_per_byte,
+            fee_shares: settlement.fee_shares.iter().map(|(_, share)| *share).collect(),
+        };
+        
+        // Proving takes seconds, keep it off the runtime threads
+        let keys = self.zk_keys().await?;
+        let proof = tokio::task::spawn_blocking(move || {
+            keys.prove(&witness, block_height, &mut rand::rngs::OsRng)
+        })
+        .await
+        .map_err(|e| GarpError::InternalError(format!("Settlement proving task failed: {}", e)))??;
+        
+        if let Some(settlement) = self.active_settlements.write().await.get_mut(&settlement.transaction_id) {
+            settlement.proof = Some(proof.clone());
+        }
+        self.storage.store_settlement_zk_proof(settlement.transaction_id.clone(), proof.clone()).await?;
+        Ok(proof)
+    }
+    
+    /// Verify a zero-knowledge settlement proof against `public_inputs`
+    pub async fn verify_settlement_proof(&self, proof: &ZkProof, public_inputs: &SettlementPublicInputs) -> bool {
+        match self.zk_keys().await {
+            Ok(keys) => keys.verify(proof, public_inputs),
+            Err(e) => {
+                warn!("Settlement proof parameters unavailable: {}", e);
+                false
+            }
+        }
+    }
+    
+    /// Get the zero-knowledge proof kept on a settlement
+    pub async fn get_settlement_zk_proof(&self, settlement_id: &str) -> Option<ZkProof> {
+        let settlements = self.active_settlements.read().await;
+        settlements.values()
+            .find(|s| s.settlement_id == settlement_id)
+            .and_then(|s| s.proof.clone())
+    }
+    
+    /// Zero-knowledge proof parameters, loaded from the configured path. If
+    /// there are none they are generated and saved, and proofs only verify
+    /// against nodes sharing them.
+    async fn zk_keys(&self) -> GarpResult<Arc<ZkSettlementKeys>> {
+        let path = self.config.settlement.zk_params_path.clone();
+        let keys = self.zk_keys.get_or_try_init(|| async move {
+            tokio::task::spawn_blocking(move || {
+                if let Ok(bytes) = std::fs::read(&path) {
+                    return ZkSettlementKeys::from_bytes(&bytes).map(Arc::new);
+                }
+                warn!("No settlement proof parameters at {}, running a local setup", path);
+                let keys = ZkSettlementKeys::setup(&mut rand::rngs::OsRng)?;
+                let bytes = keys.to_bytes()?;
+                let saved = std::path::Path::new(&path).parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|_| std::fs::write(&path, bytes));
+                if let Err(e) = saved {
+                    warn!("Failed to save settlement proof parameters to {}: {}", path, e);
+                }
+                Ok(Arc::new(keys))
+            })
+            .await
+            .map_err(|e| GarpError::InternalError(format!("Settlement proof setup task failed: {}", e)))?
+        }).await?;
+        Ok(keys.clone())
+    }
+    
+    /// Get a settlement rollback
+    pub async fn get_rollback(&self, rollback_id: &str) -> GarpResult<Option<RollbackRecord>> {
+        self.storage.get_rollback_record(rollback_id).await
+    }
+    
+    /// List settlement rollbacks, optionally only those in `status`
+    pub async fn list_rollbacks(&self, status: Option<RollbackStatus>) -> GarpResult<Vec<RollbackRecord>> {
+        self.storage.list_rollback_records(status.as_ref()).await
+    }
+    
+    /// Get the escrow of a conditional settlement
+    pub async fn get_escrow(&self, settlement_id: &str) -> GarpResult<Option<EscrowRecord>> {
+        self.storage.get_escrow_record(settlement_id).await
+    }
+    
+    /// Record a signer's sign-off on a `SignatureThreshold` condition. The
+    /// escrow is released on the condition monitor's next check once enough
+    /// signers signed.
+    pub async fn record_escrow_signature(&self, settlement_id: &str, signer: &str) -> GarpResult<EscrowRecord> {
+        let mut record = Self::locked_escrow(&self.storage, settlement_id).await?;
+        let ConditionType::SignatureThreshold { signers, .. } = &record.condition_type else {
+            return Err(GarpError::ValidationError(format!(
+                "Settlement {} is not released by signatures", settlement_id
+            )));
+        };
+        if !signers.iter().any(|s| s == signer) {
+            return Err(GarpError::ValidationError(format!(
+                "{} is not a signer of settlement {}", signer, settlement_id
+            )));
+        }
+        
+        record.signatures.insert(signer.to_string());
+        self.storage.store_escrow_record(record.clone()).await?;
+        Ok(record)
+    }
+    
+    /// Release an escrowed settlement whose condition is met, submitting it
+    /// to its target domains
+    pub async fn release_escrow(&self, settlement_id: &str) -> GarpResult<()> {
+        Self::release(
+            settlement_id,
+            &self.storage,
+            &self.active_settlements,
+            &self.batcher,
+            &self.rollbacks,
+            &self.signing_key,
+            &self.event_tx,
+        ).await
+    }
+    
+    /// Expire an escrowed settlement whose deadline passed, refunding the
+    /// locked assets to the source domain
+    pub async fn expire_escrow(&self, settlement_id: &str) -> GarpResult<()> {
+        Self::expire(settlement_id, &self.storage, &self.active_settlements, &self.rollbacks, &self.event_tx).await
+    }
+    
+    /// Escrow of a settlement that is still locked
+    async fn locked_escrow(storage: &Arc<GlobalStorage>, settlement_id: &str) -> GarpResult<EscrowRecord> {
+        let record = storage.get_escrow_record(settlement_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("No escrow for settlement {}", settlement_id)))?;
+        if record.status != EscrowStatus::Locked {
+            return Err(GarpError::ValidationError(format!(
+                "Escrow of settlement {} is already {:?}", settlement_id, record.status
+            )));
+        }
+        Ok(record)
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    async fn release(
+        settlement_id: &str,
+        storage: &Arc<GlobalStorage>,
+        active_settlements: &Arc<RwLock<HashMap<TransactionId, Settlement>>>,
+        batcher: &Arc<SettlementBatcher>,
+        rollbacks: &Arc<RollbackCoordinator>,
+        signing_key: &Arc<SigningKeyPair>,
+        event_tx: &mpsc::UnboundedSender<SettlementEvent>,
+    ) -> GarpResult<()> {
+        let mut record = Self::locked_escrow(storage, settlement_id).await?;
+        record.status = EscrowStatus::Released;
+        record.resolved_at = Some(SystemTime::now());
+        storage.store_escrow_record(record.clone()).await?;
+        info!(settlement_id = %settlement_id, condition_id = %record.condition_id, "Releasing escrowed settlement");
+        
+        // The settlement gets a full timeout window from its release
+        if let Some(settlement) = active_settlements.write().await.get_mut(&record.transaction_id) {
+            let window = settlement.timeout_at.saturating_duration_since(settlement.created_at);
+            settlement.status = SettlementStatus::Committing;
+            settlement.timeout_at = Instant::now() + window;
+            settlement.updated_at = Instant::now();
+        }
+        
+        let active_settlements = active_settlements.clone();
+        let batcher = batcher.clone();
+        let rollbacks = rollbacks.clone();
+        let storage = storage.clone();
+        let signing_key = signing_key.clone();
+        let event_tx = event_tx.clone();
+        tokio::spawn(async move {
+            let transaction_id = record.transaction_id.clone();
+            if let Err(e) = Self::commit_settlement(
+                record.transaction_id,
+                record.settlement_id,
+                &record.target_domains,
+                &record.payload,
+                &active_settlements,
+                &batcher,
+                &rollbacks,
+                &storage,
+                &signing_key,
+                &event_tx,
+            ).await {
+                error!("Failed to commit released settlement for {}: {}", transaction_id, e);
+            }
+        });
+        Ok(())
+    }
+    
+    async fn expire(
+        settlement_id: &str,
+        storage: &Arc<GlobalStorage>,
+        active_settlements: &Arc<RwLock<HashMap<TransactionId, Settlement>>>,
+        rollbacks: &Arc<RollbackCoordinator>,
+        event_tx: &mpsc::UnboundedSender<SettlementEvent>,
+    ) -> GarpResult<()> {
+        let mut record = Self::locked_escrow(storage, settlement_id).await?;
+        record.status = EscrowStatus::Expired;
+        record.resolved_at = Some(SystemTime::now());
+        storage.store_escrow_record(record.clone()).await?;
+        warn!(settlement_id = %settlement_id, condition_id = %record.condition_id, "Escrow deadline passed, refunding");
+        
+        // The source domain unlocks the assets as a compensation; the
+        // settlement is rolled back once it acknowledges
+        let refund = rollbacks.begin(
+            settlement_id,
+            &record.transaction_id,
+            &[record.source_domain.clone()],
+            "Escrow condition not met before the deadline",
+        ).await?;
+        if let Some(settlement) = active_settlements.write().await.get_mut(&record.transaction_id) {
+            settlement.status = SettlementStatus::Cancelled;
+            settlement.rollback_id = Some(refund.rollback_id);
+            settlement.updated_at = Instant::now();
+        }
+        
+        if let Err(e) = event_tx.send(SettlementEvent::SettlementFailed(
+            record.transaction_id, "Escrow expired".to_string())) {
+            error!("Failed to send settlement failed event: {}", e);
+        }
+        Ok(())
+    }
+    
     /// Create rollback plan
     async fn create_rollback_plan(
         &self,
@@ -738,11 +1177,15 @@ impl SettlementEngine {
     async fn start_settlement_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let settlement_queue = self.settlement_queue.clone();
         let active_settlements = self.active_settlements.clone();
+        let batcher = self.batcher.clone();
+        let rollbacks = self.rollbacks.clone();
+        let storage = self.storage.clone();
         let network_manager = self.network_manager.clone();
         let consensus_engine = self.consensus_engine.clone();
         let event_tx = self.event_tx.clone();
         let metrics = self.metrics.clone();
         let config = self.config.clone();
+        let signing_key = self.signing_key.clone();
         
         let handle = tokio::spawn(async move {
             let mut interval = interval(Duration::from_millis(100));
@@ -757,6 +1200,11 @@ impl SettlementEngine {
                 };
                 
                 if let Some(request) = request {
+                    if Self::awaits_epoch_finality(&request, &consensus_engine).await {
+                        settlement_queue.lock().await.push_back(request);
+                        continue;
+                    }
+                    
                     let settlement_id = Uuid::new_v4().to_string();
                     let transaction_id = request.transaction.transaction_id.clone();
                     
@@ -769,12 +1217,17 @@ impl SettlementEngine {
                         participating_domains: request.transaction.target_domains.clone(),
                         domain_settlements: HashMap::new(),
                         settlement_proof: None,
+                        proof: None,
                         rollback_plan: None,
+                        rollback_id: None,
                         created_at: Instant::now(),
                         updated_at: Instant::now(),
                         timeout_at: request.timeout_at,
                         retry_count: 0,
                         max_retries: config.settlement.max_retries,
+                        actual_fee: request.fee,
+                        fee_shares: Vec::new(),
+                        asset_flow: AssetFlow::of(&request.transaction),
                     };
                     
                     // Store settlement
@@ -786,10 +1239,15 @@ impl SettlementEngine {
                     // Start settlement process
                     if let Err(e) = Self::process_settlement(
                         transaction_id.clone(),
+                        settlement_id,
                         request.transaction,
                         &active_settlements,
+                        &batcher,
+                        &rollbacks,
+                        &storage,
                         &network_manager,
                         &consensus_engine,
+                        &signing_key,
                         &event_tx,
                     ).await {
                         error!("Failed to process settlement for {}: {}", transaction_id, e);
@@ -829,13 +1287,43 @@ impl SettlementEngine {
         Ok(handle)
     }
     
+    /// Whether a settlement requested in an earlier epoch must wait for the
+    /// current epoch's validator set to certify a block before it settles
+    async fn awaits_epoch_finality(request: &SettlementRequest, consensus_engine: &Arc<ConsensusEngine>) -> bool {
+        let epoch = consensus_engine.current_epoch().await;
+        if epoch <= request.epoch {
+            return false;
+        }
+        match consensus_engine.is_epoch_finalized(epoch).await {
+            Ok(true) => false,
+            Ok(false) => {
+                debug!(
+                    "Settlement of {} waits for the first finality certificate of epoch {}",
+                    request.transaction.transaction_id, epoch
+                );
+                true
+            }
+            Err(e) => {
+                warn!("Failed to check finality of epoch {}: {}", epoch, e);
+                true
+            }
+        }
+    }
+    
     /// Process settlement
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(correlation_id = %transaction_id, settlement_id = %settlement_id))]
     async fn process_settlement(
         transaction_id: TransactionId,
+        settlement_id: String,
         transaction: CrossDomainTransaction,
         active_settlements: &Arc<RwLock<HashMap<TransactionId, Settlement>>>,
+        batcher: &Arc<SettlementBatcher>,
+        rollbacks: &Arc<RollbackCoordinator>,
+        storage: &Arc<GlobalStorage>,
         network_manager: &Arc<NetworkManager>,
         consensus_engine: &Arc<ConsensusEngine>,
+        signing_key: &Arc<SigningKeyPair>,
         event_tx: &mpsc::UnboundedSender<SettlementEvent>,
     ) -> GarpResult<()> {
         debug!("Processing settlement for transaction: {}", transaction_id);
@@ -849,6 +1337,18 @@ impl SettlementEngine {
             }
         }
         
+        // Conditional settlements lock the assets until their condition is met
+        let condition = match active_settlements.read().await.get(&transaction_id).map(|s| &s.settlement_type) {
+            Some(SettlementType::Conditional { condition_id, condition_type, deadline }) => {
+                Some((condition_id.clone(), condition_type.clone(), *deadline))
+            }
+            _ => None,
+        };
+        let settlement_data = match &condition {
+            Some((condition_id, ..)) => serde_json::to_vec(&EscrowLock::of(&transaction, condition_id)?)?,
+            None => Vec::new(), // TODO: Generate settlement data
+        };
+        
         // Prepare domain settlements
         let mut domain_settlements = HashMap::new();
         
@@ -856,7 +1356,7 @@ impl SettlementEngine {
             let domain_settlement = DomainSettlement {
                 domain_id: domain_id.clone(),
                 status: DomainSettlementStatus::Pending,
-                settlement_data: Vec::new(), // TODO: Generate settlement data
+                settlement_data: settlement_data.clone(),
                 settlement_hash: "pending".to_string(),
                 block_height: 0,
                 block_hash: String::new(),
@@ -874,15 +1374,71 @@ impl SettlementEngine {
             let mut settlements = active_settlements.write().await;
             if let Some(settlement) = settlements.get_mut(&transaction_id) {
                 settlement.domain_settlements = domain_settlements;
-                settlement.status = SettlementStatus::Committing;
+                settlement.status = match condition {
+                    Some(_) => SettlementStatus::Escrowed,
+                    None => SettlementStatus::Committing,
+                };
                 settlement.updated_at = Instant::now();
             }
         }
         
-        // Send settlement requests to domains
-        for domain_id in &transaction.target_domains {
-            // TODO: Send settlement request to domain
-            debug!("Sending settlement request to domain: {}", domain_id);
+        // Hold escrowed settlements for the condition monitor
+        if let Some((condition_id, condition_type, deadline)) = condition {
+            debug!("Escrowing settlement {} until condition {} is met", settlement_id, condition_id);
+            return storage.store_escrow_record(EscrowRecord {
+                settlement_id,
+                transaction_id,
+                condition_id,
+                condition_type,
+                deadline,
+                source_domain: transaction.source_domain,
+                target_domains: transaction.target_domains,
+                payload: transaction.data,
+                signatures: Default::default(),
+                status: EscrowStatus::Locked,
+                created_at: SystemTime::now(),
+                resolved_at: None,
+            }).await;
+        }
+        
+        Self::commit_settlement(
+            transaction_id,
+            settlement_id,
+            &transaction.target_domains,
+            &transaction.data,
+            active_settlements,
+            batcher,
+            rollbacks,
+            storage,
+            signing_key,
+            event_tx,
+        ).await
+    }
+    
+    /// Submit a settlement to its target domains and complete it once they
+    /// all confirm, compensating those that did if any fail
+    #[allow(clippy::too_many_arguments)]
+    async fn commit_settlement(
+        transaction_id: TransactionId,
+        settlement_id: String,
+        target_domains: &[DomainId],
+        data: &[u8],
+        active_settlements: &Arc<RwLock<HashMap<TransactionId, Settlement>>>,
+        batcher: &Arc<SettlementBatcher>,
+        rollbacks: &Arc<RollbackCoordinator>,
+        storage: &Arc<GlobalStorage>,
+        signing_key: &Arc<SigningKeyPair>,
+        event_tx: &mpsc::UnboundedSender<SettlementEvent>,
+    ) -> GarpResult<()> {
+        // Queue settlement requests to domains; the batch processor submits them
+        for domain_id in target_domains {
+            debug!("Queueing settlement request to domain: {}", domain_id);
+            batcher.enqueue(PendingSettlement {
+                settlement_id: settlement_id.clone(),
+                transaction_id: transaction_id.clone(),
+                target_domain: domain_id.clone(),
+                data: data.to_vec(),
+            }).await;
         }
         
         // Wait for confirmations (simplified)
@@ -900,17 +1456,45 @@ impl SettlementEngine {
         };
         
         if all_confirmed {
-            // Generate settlement proof
-            let settlement_proof = Self::generate_settlement_proof(&transaction_id, &transaction).await?;
+            // Generate settlement proof; the settlement stands without one
+            let settlement_proof = match Self::sign_settlement_proof(
+                &transaction_id,
+                &settlement_id,
+                active_settlements,
+                storage,
+                signing_key,
+            ).await {
+                Ok(proof) => {
+                    storage.store_settlement_proof(proof.clone()).await?;
+                    Some(proof)
+                }
+                Err(e) => {
+                    warn!("Failed to generate settlement proof for {}: {}", transaction_id, e);
+                    None
+                }
+            };
             
             // Update settlement status
-            {
+            let actual_fee = {
                 let mut settlements = active_settlements.write().await;
-                if let Some(settlement) = settlements.get_mut(&transaction_id) {
-                    settlement.status = SettlementStatus::Completed;
-                    settlement.settlement_proof = Some(settlement_proof.clone());
-                    settlement.updated_at = Instant::now();
+                match settlements.get_mut(&transaction_id) {
+                    Some(settlement) => {
+                        settlement.status = SettlementStatus::Completed;
+                        settlement.settlement_proof = settlement_proof.clone();
+                        settlement.updated_at = Instant::now();
+                        settlement.actual_fee
+                    }
+                    None => 0,
                 }
+            };
+            
+            // Pay the validators that executed the settlement
+            let shares = storage.distribute_validator_fees(actual_fee).await?;
+            if !shares.is_empty() {
+                debug!("Distributed settlement fee {} for {} to {} validators", actual_fee, transaction_id, shares.len());
+            }
+            if let Some(settlement) = active_settlements.write().await.get_mut(&transaction_id) {
+                settlement.fee_shares = shares;
             }
             
             // Emit completion event
@@ -927,6 +1511,9 @@ impl SettlementEngine {
                 }
             }
             
+            // Undo the domains that did settle
+            Self::compensate_partial_settlement(&transaction_id, active_settlements, rollbacks, "Domain confirmation failed").await?;
+            
             // Emit failure event
             if let Err(e) = event_tx.send(SettlementEvent::SettlementFailed(
                 transaction_id, "Domain confirmation failed".to_string())) {
@@ -937,105 +1524,133 @@ impl SettlementEngine {
         Ok(())
     }
     
-    /// Generate settlement proof
-    async fn generate_settlement_proof(
+    /// Open a rollback for a failed settlement on which only some domains
+    /// settled, compensating those domains. Returns the rollback ID, or
+    /// `None` if there is nothing to compensate.
+    async fn compensate_partial_settlement(
         transaction_id: &TransactionId,
-        transaction: &CrossDomainTransaction,
-    ) -> GarpResult<SettlementProof> {
-        let settlement_id = Uuid::new_v4().to_string();
+        active_settlements: &Arc<RwLock<HashMap<TransactionId, Settlement>>>,
+        rollbacks: &Arc<RollbackCoordinator>,
+        reason: &str,
+    ) -> GarpResult<Option<String>> {
+        let mut settlements = active_settlements.write().await;
+        let Some(settlement) = settlements.get_mut(transaction_id) else {
+            return Ok(None);
+        };
+        if settlement.rollback_id.is_some() {
+            return Ok(None);
+        }
         
-        // Generate domain proofs
-        let mut domain_proofs = HashMap::new();
+        let mut settled: Vec<DomainId> = settlement.domain_settlements.values()
+            .filter(|ds| ds.status == DomainSettlementStatus::Confirmed)
+            .map(|ds| ds.domain_id.clone())
+            .collect();
+        if settled.is_empty() || settled.len() == settlement.domain_settlements.len() {
+            return Ok(None);
+        }
+        settled.sort();
         
-        for domain_id in &transaction.target_domains {
-            let domain_proof = DomainProof {
-                domain_id: domain_id.clone(),
-                proof_data: Vec::new(), // TODO: Generate actual proof
-                block_height: 100, // TODO: Get actual block height
-                block_hash: "dummy_hash".to_string(), // TODO: Get actual block hash
-                signature: Vec::new(), // TODO: Generate signature
-                timestamp: chrono::Utc::now(),
-            };
-            
-            domain_proofs.insert(domain_id.clone(), domain_proof);
+        let record = rollbacks.begin(&settlement.settlement_id, transaction_id, &settled, reason).await?;
+        settlement.rollback_id = Some(record.rollback_id.clone());
+        Ok(Some(record.rollback_id))
+    }
+    
+    /// Generate the signed proof of a completed settlement. Each domain's
+    /// confirmation hash is recorded on its domain settlement, and the proof
+    /// references the finality certificate of the block holding the
+    /// transaction, or the latest one if it is not in a block yet.
+    async fn sign_settlement_proof(
+        transaction_id: &TransactionId,
+        settlement_id: &str,
+        active_settlements: &Arc<RwLock<HashMap<TransactionId, Settlement>>>,
+        storage: &Arc<GlobalStorage>,
+        signing_key: &Arc<SigningKeyPair>,
+    ) -> GarpResult<SettlementProof> {
+        let block_height = storage.get_transaction(transaction_id).await?
+            .and_then(|stored| stored.block_height);
+        let certificate = match block_height {
+            Some(height) => storage.get_finality_certificate_by_height(height).await?,
+            None => storage.get_latest_finality_certificate().await?,
         }
+        .ok_or_else(|| GarpError::NotFound(format!("No finality certificate for settlement {}", settlement_id)))?;
         
-        Ok(SettlementProof {
-            settlement_id,
-            transaction_id: transaction_id.clone(),
-            proof_type: SettlementProofType::Merkle,
-            proof_data: Vec::new(), // TODO: Generate proof data
-            merkle_root: "dummy_root".to_string(), // TODO: Calculate merkle root
-            domain_proofs,
-            aggregated_signature: Vec::new(), // TODO: Generate aggregated signature
-            created_at: chrono::Utc::now(),
-        })
+        let mut domain_confirmations = std::collections::BTreeMap::new();
+        {
+            let mut settlements = active_settlements.write().await;
+            let settlement = settlements.get_mut(transaction_id)
+                .ok_or_else(|| GarpError::NotFound(format!("Settlement for {} not found", transaction_id)))?;
+            for (domain_id, domain_settlement) in settlement.domain_settlements.iter_mut() {
+                let hash = domain_confirmation_hash(settlement_id, domain_id, &domain_settlement.settlement_data);
+                domain_settlement.settlement_hash = hex::encode(&hash);
+                domain_confirmations.insert(domain_id.clone(), hash);
+            }
+        }
+        
+        Ok(SettlementProof::new(
+            settlement_id.to_string(),
+            transaction_id.clone(),
+            domain_confirmations,
+            FinalityReference {
+                height: certificate.height,
+                block_hash: certificate.block_hash,
+                validator_set_hash: certificate.validator_set_hash,
+            },
+            signing_key,
+        ))
     }
     
     /// Start batch processor
     async fn start_batch_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let settlement_batches = self.settlement_batches.clone();
+        let batcher = self.batcher.clone();
         let active_settlements = self.active_settlements.clone();
         let event_tx = self.event_tx.clone();
         let metrics = self.metrics.clone();
-        let config = self.config.clone();
+        let tick = Duration::from_millis(self.config.settlement.batch_window_ms.min(100));
         
         let handle = tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(config.settlement.batch_interval));
+            let mut interval = interval(tick);
             
             loop {
                 interval.tick().await;
                 
-                // Create batch from pending settlements
-                let pending_settlements: Vec<TransactionId> = {
-                    let settlements = active_settlements.read().await;
-                    settlements.iter()
-                        .filter(|(_, s)| s.status == SettlementStatus::Pending)
-                        .take(config.settlement.batch_size)
-                        .map(|(id, _)| id.clone())
-                        .collect()
+                let started = Instant::now();
+                let report = match batcher.flush_due(started).await {
+                    Ok(report) => report,
+                    Err(e) => {
+                        error!("Settlement batch flush failed: {}", e);
+                        continue;
+                    }
                 };
                 
-                if !pending_settlements.is_empty() {
-                    let batch_id = Uuid::new_v4().to_string();
-                    let batch = SettlementBatch {
-                        batch_id: batch_id.clone(),
-                        settlements: pending_settlements.clone(),
-                        status: SettlementBatchStatus::Processing,
-                        batch_size: pending_settlements.len(),
-                        created_at: Instant::now(),
-                        processing_started_at: Some(Instant::now()),
-                        completed_at: None,
-                    };
-                    
-                    // Store batch
-                    {
-                        let mut batches = settlement_batches.write().await;
-                        batches.insert(batch_id.clone(), batch);
-                    }
-                    
-                    // Process batch (simplified)
-                    tokio::time::sleep(Duration::from_secs(2)).await;
-                    
-                    // Mark batch as completed
-                    {
-                        let mut batches = settlement_batches.write().await;
-                        if let Some(batch) = batches.get_mut(&batch_id) {
-                            batch.status = SettlementBatchStatus::Completed;
-                            batch.completed_at = Some(Instant::now());
+                // Record per-domain outcomes on their settlements
+                {
+                    let mut settlements = active_settlements.write().await;
+                    let outcomes = report.settled.iter().map(|item| (item, DomainSettlementStatus::Confirmed))
+                        .chain(report.failed.iter().map(|(item, _)| (item, DomainSettlementStatus::Failed)));
+                    for (item, status) in outcomes {
+                        if let Some(domain_settlement) = settlements.get_mut(&item.transaction_id)
+                            .and_then(|s| s.domain_settlements.get_mut(&item.target_domain))
+                        {
+                            domain_settlement.status = status;
+                            domain_settlement.settlement_timestamp = chrono::Utc::now();
                         }
                     }
-                    
-                    // Update metrics
-                    if let Some(batch) = settlement_batches.read().await.get(&batch_id) {
-                        if let (Some(start), Some(end)) = (batch.processing_started_at, batch.completed_at) {
-                            let processing_time = end.duration_since(start).as_secs_f64();
-                            let mut avg_time = metrics.avg_batch_processing_time.write().await;
-                            *avg_time = (*avg_time + processing_time) / 2.0;
-                        }
+                }
+                
+                for item in &report.settled {
+                    if let Err(e) = event_tx.send(SettlementEvent::DomainSettlementConfirmed(
+                        item.transaction_id.clone(), item.target_domain.clone())) {
+                        error!("Failed to send domain settlement confirmed event: {}", e);
                     }
-                    
-                    // Emit event
+                }
+                
+                if !report.batches.is_empty() {
+                    let processing_time = started.elapsed().as_secs_f64();
+                    let mut avg_time = metrics.avg_batch_processing_time.write().await;
+                    *avg_time = (*avg_time + processing_time) / 2.0;
+                }
+                
+                for batch_id in report.batches {
                     if let Err(e) = event_tx.send(SettlementEvent::BatchProcessed(batch_id)) {
                         error!("Failed to send batch processed event: {}", e);
                     }
@@ -1049,6 +1664,7 @@ impl SettlementEngine {
     /// Start rollback processor
     async fn start_rollback_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let pending_rollbacks = self.pending_rollbacks.clone();
+        let rollbacks = self.rollbacks.clone();
         let active_settlements = self.active_settlements.clone();
         let network_manager = self.network_manager.clone();
         let event_tx = self.event_tx.clone();
@@ -1060,6 +1676,16 @@ impl SettlementEngine {
             loop {
                 interval.tick().await;
                 
+                // Send due compensations and escalate exhausted rollbacks
+                match rollbacks.process_due(std::time::SystemTime::now()).await {
+                    Ok(escalated) => {
+                        for record in escalated {
+                            warn!("Rollback {} of settlement {} escalated", record.rollback_id, record.settlement_id);
+                        }
+                    }
+                    Err(e) => error!("Failed to process settlement rollbacks: {}", e),
+                }
+                
                 // Process pending rollbacks
                 let rollback_requests: Vec<(TransactionId, RollbackRequest)> = {
                     let rollbacks = pending_rollbacks.read().await;
@@ -1156,6 +1782,7 @@ impl SettlementEngine {
     /// Start settlement monitor
     async fn start_settlement_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let active_settlements = self.active_settlements.clone();
+        let rollbacks = self.rollbacks.clone();
         let event_tx = self.event_tx.clone();
         let metrics = self.metrics.clone();
         
@@ -1173,6 +1800,7 @@ impl SettlementEngine {
                     let settlements = active_settlements.read().await;
                     for (transaction_id, settlement) in settlements.iter() {
                         if now > settlement.timeout_at && 
+                           settlement.status != SettlementStatus::Escrowed &&
                            settlement.status != SettlementStatus::Completed &&
                            settlement.status != SettlementStatus::Failed &&
                            settlement.status != SettlementStatus::RolledBack {
@@ -1194,6 +1822,10 @@ impl SettlementEngine {
                         }
                     }
                     
+                    if let Err(e) = Self::compensate_partial_settlement(&transaction_id, &active_settlements, &rollbacks, "Settlement timeout").await {
+                        error!("Failed to start rollback for {}: {}", transaction_id, e);
+                    }
+                    
                     // Emit failure event
                     if let Err(e) = event_tx.send(SettlementEvent::SettlementFailed(
                         transaction_id, "Settlement timeout".to_string())) {
@@ -1227,6 +1859,50 @@ impl SettlementEngine {
         
         Ok(handle)
     }
+    
+    /// Start escrow condition monitor
+    async fn start_condition_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let condition_monitor = self.condition_monitor.clone();
+        let storage = self.storage.clone();
+        let active_settlements = self.active_settlements.clone();
+        let batcher = self.batcher.clone();
+        let rollbacks = self.rollbacks.clone();
+        let signing_key = self.signing_key.clone();
+        let event_tx = self.event_tx.clone();
+        let poll_interval = Duration::from_millis(self.config.settlement.escrow_poll_interval_ms);
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(poll_interval);
+            
+            loop {
+                interval.tick().await;
+                
+                let decisions = match condition_monitor.check(SystemTime::now()).await {
+                    Ok(decisions) => decisions,
+                    Err(e) => {
+                        error!("Failed to check escrow conditions: {}", e);
+                        continue;
+                    }
+                };
+                
+                for decision in decisions {
+                    let result = match &decision {
+                        EscrowDecision::Release(settlement_id) => Self::release(
+                            settlement_id, &storage, &active_settlements, &batcher, &rollbacks, &signing_key, &event_tx,
+                        ).await,
+                        EscrowDecision::Expire(settlement_id) => Self::expire(
+                            settlement_id, &storage, &active_settlements, &rollbacks, &event_tx,
+                        ).await,
+                    };
+                    if let Err(e) = result {
+                        error!("Failed to apply escrow decision {:?}: {}", decision, e);
+                    }
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
 }
 
 impl SettlementMetrics {
@@ -1313,6 +1989,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
             metadata: HashMap::new(),
         };
         
@@ -1325,6 +2004,347 @@ mod tests {
         assert!(result.is_ok());
     }
     
+    fn transaction(max_fee: u64) -> CrossDomainTransaction {
+        CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["a".to_string(), "b".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: vec![0; 5],
+            dependencies: Vec::new(),
+            required_confirmations: 2,
+            confirmations: HashMap::new(),
+            status: crate::cross_domain::TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(300),
+            not_before: None,
+            max_fee,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_settlement_aborted_when_fee_exceeds_max() {
+        let mut config = GlobalSyncConfig::default();
+        config.settlement.fee_per_domain = 10;
+        config.settlement.fee_per_byte = 2;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let engine = SettlementEngine::new(config, storage, network_manager, consensus_engine).await.unwrap();
+        
+        // 2 domains * 10 + 5 bytes * 2
+        assert_eq!(engine.estimate_fee(&transaction(0)), 30);
+        let result = engine.request_settlement(transaction(29), SettlementType::Atomic, SettlementPriority::Normal).await;
+        assert!(matches!(result, Err(GarpError::FeeExceeded { required: 30, max_fee: 29 })));
+        assert!(engine.request_settlement(transaction(30), SettlementType::Atomic, SettlementPriority::Normal).await.is_ok());
+    }
+    
+    fn domain_settlement(domain_id: &str, status: DomainSettlementStatus) -> DomainSettlement {
+        DomainSettlement {
+            domain_id: domain_id.to_string(),
+            status,
+            settlement_data: Vec::new(),
+            settlement_hash: String::new(),
+            block_height: 0,
+            block_hash: String::new(),
+            confirmation_count: 0,
+            required_confirmations: 1,
+            settlement_timestamp: chrono::Utc::now(),
+            signature: Vec::new(),
+        }
+    }
+    
+    fn transfer(settlement_id: &str, from: &str, to: &str, asset_id: &str, amount: u64, status: SettlementStatus) -> Settlement {
+        Settlement {
+            transaction_id: TransactionId::new(),
+            settlement_id: settlement_id.to_string(),
+            settlement_type: SettlementType::Atomic,
+            status,
+            participating_domains: vec![to.to_string()],
+            domain_settlements: HashMap::new(),
+            settlement_proof: None,
+            proof: None,
+            rollback_plan: None,
+            rollback_id: None,
+            created_at: Instant::now(),
+            updated_at: Instant::now(),
+            timeout_at: Instant::now(),
+            retry_count: 0,
+            max_retries: 0,
+            actual_fee: 0,
+            fee_shares: Vec::new(),
+            asset_flow: Some(AssetFlow {
+                from_domain: from.to_string(),
+                to_domain: to.to_string(),
+                asset_id: asset_id.to_string(),
+                amount,
+            }),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_opposing_settlements_are_netted() {
+        let mut config = GlobalSyncConfig::default();
+        config.settlement.enable_netting = true;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let engine = SettlementEngine::new(config, storage, network_manager, consensus_engine).await.unwrap();
+        
+        let batch = vec![
+            transfer("a-to-b", "a", "b", "usd", 100, SettlementStatus::Pending),
+            transfer("b-to-a", "b", "a", "usd", 80, SettlementStatus::Pending),
+            // Different asset, already committing, or a third party: not netted
+            transfer("b-to-a-eur", "b", "a", "eur", 50, SettlementStatus::Pending),
+            transfer("b-to-a-committing", "b", "a", "usd", 30, SettlementStatus::Committing),
+            transfer("c-to-a", "c", "a", "usd", 30, SettlementStatus::Pending),
+            // Second pair on a -> b, where the later settlement is larger
+            transfer("a-to-b-2", "a", "b", "usd", 10, SettlementStatus::Pending),
+            transfer("b-to-a-2", "b", "a", "usd", 25, SettlementStatus::Pending),
+        ];
+        let netted = engine.net_settlements(batch.clone());
+        
+        assert_eq!(netted.len(), 2);
+        assert_eq!(netted[0].settlement_ids, ["a-to-b".to_string(), "b-to-a".to_string()]);
+        assert_eq!(netted[0].flow, AssetFlow {
+            from_domain: "a".to_string(),
+            to_domain: "b".to_string(),
+            asset_id: "usd".to_string(),
+            amount: 20,
+        });
+        assert_eq!(netted[1].settlement_ids, ["a-to-b-2".to_string(), "b-to-a-2".to_string()]);
+        assert_eq!((netted[1].flow.from_domain.as_str(), netted[1].flow.amount), ("b", 15));
+        
+        // Netting is off by default
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let engine = SettlementEngine::new(config, storage, network_manager, consensus_engine).await.unwrap();
+        assert!(engine.net_settlements(batch).is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_conditional_settlement_escrow_release_and_expiry() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let engine = SettlementEngine::new(config, storage, network_manager, consensus_engine).await.unwrap();
+        let hour = Duration::from_secs(3600);
+        
+        let escrow = |settlement_id: &str, condition_type: ConditionType| {
+            let engine = &engine;
+            let settlement_id = settlement_id.to_string();
+            async move {
+                let transaction = transaction(100);
+                let mut settlement = transfer(&settlement_id, "source", "a", "test-asset", 100, SettlementStatus::Pending);
+                settlement.transaction_id = transaction.transaction_id.clone();
+                settlement.settlement_type = SettlementType::Conditional {
+                    condition_id: format!("{}-condition", settlement_id),
+                    condition_type,
+                    deadline: SystemTime::now() + hour,
+                };
+                let transaction_id = transaction.transaction_id.clone();
+                engine.active_settlements.write().await.insert(transaction_id.clone(), settlement);
+                SettlementEngine::process_settlement(
+                    transaction_id.clone(),
+                    settlement_id,
+                    transaction,
+                    &engine.active_settlements,
+                    &engine.batcher,
+                    &engine.rollbacks,
+                    &engine.storage,
+                    &engine.network_manager,
+                    &engine.consensus_engine,
+                    &engine.signing_key,
+                    &engine.event_tx,
+                ).await.unwrap();
+                transaction_id
+            }
+        };
+        
+        // Escrowed settlements wait with the locked amount on each domain settlement
+        let signed = escrow("signed", ConditionType::SignatureThreshold {
+            signers: vec!["alice".to_string(), "bob".to_string(), "carol".to_string()],
+            threshold: 2,
+        }).await;
+        assert_eq!(engine.get_settlement_status(&signed).await, Some(SettlementStatus::Escrowed));
+        let lock: EscrowLock = serde_json::from_slice(
+            &engine.active_settlements.read().await[&signed].domain_settlements["b"].settlement_data,
+        ).unwrap();
+        assert_eq!((lock.from_domain.as_str(), lock.amount), ("source", 100));
+        assert_eq!(engine.get_escrow("signed").await.unwrap().unwrap().status, EscrowStatus::Locked);
+        
+        // Two of the three signers release it
+        engine.record_escrow_signature("signed", "alice").await.unwrap();
+        assert!(engine.record_escrow_signature("signed", "mallory").await.is_err());
+        assert!(engine.condition_monitor.check(SystemTime::now()).await.unwrap().is_empty());
+        engine.record_escrow_signature("signed", "bob").await.unwrap();
+        assert_eq!(
+            engine.condition_monitor.check(SystemTime::now()).await.unwrap(),
+            vec![EscrowDecision::Release("signed".to_string())],
+        );
+        engine.release_escrow("signed").await.unwrap();
+        assert_eq!(engine.get_settlement_status(&signed).await, Some(SettlementStatus::Committing));
+        assert_eq!(engine.get_escrow("signed").await.unwrap().unwrap().status, EscrowStatus::Released);
+        assert!(engine.release_escrow("signed").await.is_err());
+        
+        // An escrow still locked at its deadline is refunded to the source domain
+        let locked = escrow("locked", ConditionType::TimeLocked { release_at: SystemTime::now() + 2 * hour }).await;
+        assert_eq!(
+            engine.condition_monitor.check(SystemTime::now() + hour).await.unwrap(),
+            vec![EscrowDecision::Expire("locked".to_string())],
+        );
+        engine.expire_escrow("locked").await.unwrap();
+        assert_eq!(engine.get_settlement_status(&locked).await, Some(SettlementStatus::Cancelled));
+        assert_eq!(engine.get_escrow("locked").await.unwrap().unwrap().status, EscrowStatus::Expired);
+        let rollback_id = engine.active_settlements.read().await[&locked].rollback_id.clone().unwrap();
+        let refund = engine.get_rollback(&rollback_id).await.unwrap().unwrap();
+        assert_eq!(refund.compensations.keys().collect::<Vec<_>>(), vec!["source"]);
+        
+        // Only asset transfers can be escrowed
+        let mut swap = transaction(100);
+        swap.transaction_type = CrossDomainTransactionType::StateSynchronization {
+            state_key: "key".to_string(),
+            state_value: Vec::new(),
+            version: 1,
+        };
+        let conditional = SettlementType::Conditional {
+            condition_id: "c".to_string(),
+            condition_type: ConditionType::TimeLocked { release_at: SystemTime::now() },
+            deadline: SystemTime::now() + hour,
+        };
+        assert!(matches!(
+            engine.request_settlement(swap, conditional, SettlementPriority::Normal).await,
+            Err(GarpError::ValidationError(_))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_partial_settlement_failure_rolls_back() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let engine = SettlementEngine::new(config, storage, network_manager, consensus_engine).await.unwrap();
+        
+        // Domain `a` settled, `b` failed
+        let transaction_id = TransactionId::new();
+        let settlement = Settlement {
+            transaction_id: transaction_id.clone(),
+            settlement_id: "s1".to_string(),
+            settlement_type: SettlementType::Atomic,
+            status: SettlementStatus::Failed,
+            participating_domains: vec!["a".to_string(), "b".to_string()],
+            domain_settlements: [
+                ("a".to_string(), domain_settlement("a", DomainSettlementStatus::Confirmed)),
+                ("b".to_string(), domain_settlement("b", DomainSettlementStatus::Failed)),
+            ].into_iter().collect(),
+            settlement_proof: None,
+            proof: None,
+            rollback_plan: None,
+            rollback_id: None,
+            created_at: Instant::now(),
+            updated_at: Instant::now(),
+            timeout_at: Instant::now(),
+            retry_count: 0,
+            max_retries: 0,
+            actual_fee: 0,
+            fee_shares: Vec::new(),
+            asset_flow: None,
+        };
+        engine.active_settlements.write().await.insert(transaction_id.clone(), settlement);
+        
+        let rollback_id = SettlementEngine::compensate_partial_settlement(
+            &transaction_id, &engine.active_settlements, &engine.rollbacks, "test",
+        ).await.unwrap().unwrap();
+        // A second failure report does not open another rollback
+        assert!(SettlementEngine::compensate_partial_settlement(
+            &transaction_id, &engine.active_settlements, &engine.rollbacks, "test",
+        ).await.unwrap().is_none());
+        
+        let record = engine.get_rollback(&rollback_id).await.unwrap().unwrap();
+        assert_eq!(record.compensations.keys().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(engine.list_rollbacks(Some(RollbackStatus::Pending)).await.unwrap().len(), 1);
+        
+        let record = engine.acknowledge_compensation(&rollback_id, &"a".to_string()).await.unwrap();
+        assert_eq!(record.status, RollbackStatus::Completed);
+        assert_eq!(engine.get_settlement_status(&transaction_id).await, Some(SettlementStatus::RolledBack));
+        assert_eq!(*engine.metrics.rolled_back_settlements.read().await, 1);
+        assert!(engine.list_rollbacks(Some(RollbackStatus::Pending)).await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_completed_settlement_proof_verifies() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let network_manager = Arc::new(NetworkManager::new(config.clone()).await.unwrap());
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await.unwrap());
+        let engine = SettlementEngine::new(config, storage.clone(), network_manager, consensus_engine).await.unwrap();
+        
+        let transaction_id = TransactionId::new();
+        let mut settlement = Settlement {
+            transaction_id: transaction_id.clone(),
+            settlement_id: "s1".to_string(),
+            settlement_type: SettlementType::Atomic,
+            status: SettlementStatus::Committing,
+            participating_domains: vec!["a".to_string(), "b".to_string()],
+            domain_settlements: HashMap::new(),
+            settlement_proof: None,
+            proof: None,
+            rollback_plan: None,
+            rollback_id: None,
+            created_at: Instant::now(),
+            updated_at: Instant::now(),
+            timeout_at: Instant::now(),
+            retry_count: 0,
+            max_retries: 0,
+            actual_fee: 0,
+            fee_shares: Vec::new(),
+            asset_flow: None,
+        };
+        for domain in ["a", "b"] {
+            settlement.domain_settlements.insert(domain.to_string(), domain_settlement(domain, DomainSettlementStatus::Confirmed));
+        }
+        engine.active_settlements.write().await.insert(transaction_id.clone(), settlement);
+        
+        // Nothing is finalized yet
+        assert!(SettlementEngine::sign_settlement_proof(
+            &transaction_id, "s1", &engine.active_settlements, &storage, &engine.signing_key,
+        ).await.is_err());
+        
+        storage.store_finality_certificate(crate::consensus::FinalityCertificate {
+            height: 4,
+            block_hash: "b4".to_string(),
+            signatures: Vec::new(),
+            validator_set_hash: "vs".to_string(),
+            timestamp: chrono::Utc::now(),
+            state_root: String::new(),
+        }).await.unwrap();
+        let proof = SettlementEngine::sign_settlement_proof(
+            &transaction_id, "s1", &engine.active_settlements, &storage, &engine.signing_key,
+        ).await.unwrap();
+        assert_eq!(proof.finality.height, 4);
+        assert_eq!(proof.domain_confirmations.len(), 2);
+        
+        let keys = [engine.signing_key.public_key_bytes()];
+        assert!(garp_common::settlement::verify_settlement_proof(&proof, &keys).is_ok());
+        
+        storage.store_settlement_proof(proof).await.unwrap();
+        assert_eq!(storage.get_settlement_proof(&transaction_id).await.unwrap().unwrap().settlement_id, "s1");
+    }
+    
     #[tokio::test]
     async fn test_settlement_metrics() {
         let metrics = SettlementMetrics::new();
@@ -0,0 +1,396 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use garp_common::GarpResult;
+use garp_common::types::TransactionId;
+
+use crate::config::GlobalSyncConfig;
+use crate::network::{MessageDestination, MessagePriority, NetworkManager};
+use crate::storage::{BatchItemStatus, BatchStatus, DomainId, GlobalStorage, SettlementBatch};
+
+/// Settlement of one transaction on one target domain, waiting to be submitted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSettlement {
+    /// Settlement ID
+    pub settlement_id: String,
+
+    /// Transaction being settled
+    pub transaction_id: TransactionId,
+
+    /// Domain the settlement is submitted to
+    pub target_domain: DomainId,
+
+    /// Settlement payload
+    pub data: Vec<u8>,
+}
+
+/// Delivers settlements to target domains
+#[async_trait::async_trait]
+pub trait SettlementSubmitter: Send + Sync {
+    /// Submit a batch, returning the outcome of each settlement by ID.
+    /// An `Err` means the batch as a whole was not accepted.
+    async fn submit_batch(&self, batch: &SettlementBatch, items: &[PendingSettlement]) -> GarpResult<HashMap<String, Result<(), String>>>;
+
+    /// Submit a single settlement
+    async fn submit_one(&self, item: &PendingSettlement) -> GarpResult<()>;
+}
+
+/// Submits settlements to domains through the network manager
+pub struct NetworkSettlementSubmitter {
+    /// Network manager
+    network_manager: Arc<NetworkManager>,
+}
+
+impl NetworkSettlementSubmitter {
+    /// Create new network settlement submitter
+    pub fn new(network_manager: Arc<NetworkManager>) -> Self {
+        Self { network_manager }
+    }
+}
+
+#[async_trait::async_trait]
+impl SettlementSubmitter for NetworkSettlementSubmitter {
+    async fn submit_batch(&self, batch: &SettlementBatch, items: &[PendingSettlement]) -> GarpResult<HashMap<String, Result<(), String>>> {
+        let data = serde_json::to_vec(&(batch.batch_id.as_str(), items))?;
+        self.network_manager.send_message(
+            MessageDestination::Domain(batch.target_domain.clone()),
+            "settlement_batch".to_string(),
+            data,
+            MessagePriority::Normal,
+        ).await?;
+        Ok(items.iter().map(|item| (item.settlement_id.clone(), Ok(()))).collect())
+    }
+
+    async fn submit_one(&self, item: &PendingSettlement) -> GarpResult<()> {
+        self.network_manager.send_message(
+            MessageDestination::Domain(item.target_domain.clone()),
+            "settlement".to_string(),
+            serde_json::to_vec(item)?,
+            MessagePriority::Normal,
+        ).await?;
+        Ok(())
+    }
+}
+
+/// Outcome of one flush pass
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    /// Batches submitted
+    pub batches: Vec<String>,
+
+    /// Settlements accepted by their target domain
+    pub settled: Vec<PendingSettlement>,
+
+    /// Settlements that failed individual submission
+    pub failed: Vec<(PendingSettlement, String)>,
+
+    /// Batch items re-queued for individual submission
+    pub requeued: usize,
+}
+
+/// Settlements accumulating for one domain
+struct OpenBatch {
+    opened_at: Instant,
+    items: Vec<PendingSettlement>,
+}
+
+/// Groups settlements per target domain and submits each group as a single
+/// [`SettlementBatch`] once its window elapses or it reaches the size limit.
+///
+/// Items that fail within a batch are re-queued for individual submission.
+/// Batching can be switched off at runtime, in which case every settlement
+/// is submitted on its own.
+pub struct SettlementBatcher {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Delivery to target domains
+    submitter: Arc<dyn SettlementSubmitter>,
+
+    /// Operator toggle for batching
+    enabled: AtomicBool,
+
+    /// How long a domain's batch stays open
+    window: Duration,
+
+    /// Batch size that triggers submission before the window elapses
+    max_batch_size: usize,
+
+    /// Open batches by target domain
+    open_batches: Mutex<HashMap<DomainId, OpenBatch>>,
+
+    /// Settlements to submit one at a time
+    individual: Mutex<VecDeque<PendingSettlement>>,
+}
+
+impl SettlementBatcher {
+    /// Create new settlement batcher
+    pub fn new(config: Arc<GlobalSyncConfig>, storage: Arc<GlobalStorage>, submitter: Arc<dyn SettlementSubmitter>) -> Self {
+        Self {
+            storage,
+            submitter,
+            enabled: AtomicBool::new(config.settlement.batching_enabled),
+            window: Duration::from_millis(config.settlement.batch_window_ms),
+            max_batch_size: config.settlement.batch_size.max(1),
+            open_batches: Mutex::new(HashMap::new()),
+            individual: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Whether batching is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Turn batching on or off. Disabling moves settlements from open batches
+    /// to individual submission so none are held back.
+    pub async fn set_enabled(&self, enabled: bool) {
+        if self.enabled.swap(enabled, Ordering::SeqCst) == enabled {
+            return;
+        }
+        info!("Settlement batching {}", if enabled { "enabled" } else { "disabled" });
+
+        if !enabled {
+            let drained: Vec<PendingSettlement> = self.open_batches.lock().await
+                .drain()
+                .flat_map(|(_, batch)| batch.items)
+                .collect();
+            self.individual.lock().await.extend(drained);
+        }
+    }
+
+    /// Queue a settlement for its target domain
+    pub async fn enqueue(&self, item: PendingSettlement) {
+        if !self.is_enabled() {
+            self.individual.lock().await.push_back(item);
+            return;
+        }
+
+        let mut open_batches = self.open_batches.lock().await;
+        open_batches
+            .entry(item.target_domain.clone())
+            .or_insert_with(|| OpenBatch { opened_at: Instant::now(), items: Vec::new() })
+            .items
+            .push(item);
+    }
+
+    /// Submit every batch that is full or whose window ended by `now`, then
+    /// every settlement queued for individual submission
+    pub async fn flush_due(&self, now: Instant) -> GarpResult<FlushReport> {
+        let mut report = FlushReport::default();
+
+        let due: Vec<(DomainId, Vec<PendingSettlement>)> = {
+            let mut open_batches = self.open_batches.lock().await;
+            let due_domains: Vec<DomainId> = open_batches.iter()
+                .filter(|(_, batch)| {
+                    batch.items.len() >= self.max_batch_size
+                        || now.saturating_duration_since(batch.opened_at) >= self.window
+                })
+                .map(|(domain_id, _)| domain_id.clone())
+                .collect();
+            due_domains.into_iter()
+                .filter_map(|domain_id| open_batches.remove(&domain_id).map(|batch| (domain_id, batch.items)))
+                .collect()
+        };
+
+        for (domain_id, items) in due {
+            for chunk in items.chunks(self.max_batch_size) {
+                self.submit_batch(&domain_id, chunk.to_vec(), &mut report).await?;
+            }
+        }
+
+        let individual: Vec<PendingSettlement> = self.individual.lock().await.drain(..).collect();
+        for item in individual {
+            match self.submitter.submit_one(&item).await {
+                Ok(()) => report.settled.push(item),
+                Err(e) => {
+                    warn!("Settlement {} to {} failed: {}", item.settlement_id, item.target_domain, e);
+                    report.failed.push((item, e.to_string()));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Submit one batch, persisting each status transition, and re-queue
+    /// failed items for individual submission
+    async fn submit_batch(&self, domain_id: &DomainId, items: Vec<PendingSettlement>, report: &mut FlushReport) -> GarpResult<()> {
+        let mut batch = SettlementBatch {
+            batch_id: Uuid::new_v4().to_string(),
+            settlement_ids: items.iter().map(|item| item.settlement_id.clone()).collect(),
+            target_domain: domain_id.clone(),
+            item_status: items.iter()
+                .map(|item| (item.settlement_id.clone(), BatchItemStatus::Pending))
+                .collect(),
+            status: BatchStatus::Pending,
+            created_at: SystemTime::now(),
+            processed_at: None,
+            metadata: HashMap::new(),
+        };
+        self.storage.store_settlement_batch(batch.clone()).await?;
+
+        batch.status = BatchStatus::Processing;
+        self.storage.store_settlement_batch(batch.clone()).await?;
+
+        let outcomes = match self.submitter.submit_batch(&batch, &items).await {
+            Ok(outcomes) => outcomes,
+            Err(e) => {
+                warn!("Settlement batch {} to {} failed: {}", batch.batch_id, domain_id, e);
+                items.iter().map(|item| (item.settlement_id.clone(), Err(e.to_string()))).collect()
+            }
+        };
+
+        let mut failed = Vec::new();
+        for item in items {
+            let status = match outcomes.get(&item.settlement_id) {
+                Some(Ok(())) => BatchItemStatus::Settled,
+                Some(Err(reason)) => BatchItemStatus::Failed(reason.clone()),
+                None => BatchItemStatus::Failed("no result reported".to_string()),
+            };
+            if status == BatchItemStatus::Settled {
+                report.settled.push(item.clone());
+            } else {
+                failed.push(item.clone());
+            }
+            batch.item_status.insert(item.settlement_id, status);
+        }
+
+        batch.status = if failed.is_empty() {
+            BatchStatus::Completed
+        } else if failed.len() == batch.settlement_ids.len() {
+            BatchStatus::Failed
+        } else {
+            BatchStatus::PartiallyFailed
+        };
+        batch.processed_at = Some(SystemTime::now());
+        self.storage.store_settlement_batch(batch.clone()).await?;
+
+        debug!(
+            "Settlement batch {} to {}: {:?} ({} items, {} re-queued)",
+            batch.batch_id, domain_id, batch.status, batch.settlement_ids.len(), failed.len()
+        );
+        report.batches.push(batch.batch_id);
+        report.requeued += failed.len();
+        self.individual.lock().await.extend(failed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use garp_common::GarpError;
+
+    /// Rejects the listed settlement IDs, within batches and individually
+    struct RejectingSubmitter {
+        reject: HashSet<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl SettlementSubmitter for RejectingSubmitter {
+        async fn submit_batch(&self, _batch: &SettlementBatch, items: &[PendingSettlement]) -> GarpResult<HashMap<String, Result<(), String>>> {
+            Ok(items.iter()
+                .map(|item| {
+                    let outcome = if self.reject.contains(&item.settlement_id) { Err("rejected".to_string()) } else { Ok(()) };
+                    (item.settlement_id.clone(), outcome)
+                })
+                .collect())
+        }
+
+        async fn submit_one(&self, item: &PendingSettlement) -> GarpResult<()> {
+            if self.reject.contains(&item.settlement_id) {
+                return Err(GarpError::InternalError("rejected".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn item(id: &str, domain: &str) -> PendingSettlement {
+        PendingSettlement {
+            settlement_id: id.to_string(),
+            transaction_id: TransactionId::new(),
+            target_domain: domain.to_string(),
+            data: Vec::new(),
+        }
+    }
+
+    async fn batcher(reject: &[&str]) -> (SettlementBatcher, Arc<GlobalStorage>) {
+        let mut config = GlobalSyncConfig::default();
+        config.settlement.batch_size = 3;
+        config.settlement.batch_window_ms = 500;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        let submitter = Arc::new(RejectingSubmitter { reject: reject.iter().map(|s| s.to_string()).collect() });
+        (SettlementBatcher::new(config, storage.clone(), submitter), storage)
+    }
+
+    #[tokio::test]
+    async fn test_batches_flush_on_size_or_window() {
+        let (batcher, storage) = batcher(&[]).await;
+        for id in ["s1", "s2", "s3"] {
+            batcher.enqueue(item(id, "a")).await;
+        }
+        batcher.enqueue(item("s4", "b")).await;
+
+        // `a` is full; `b` waits for its window
+        let report = batcher.flush_due(Instant::now()).await.unwrap();
+        assert_eq!(report.batches.len(), 1);
+        assert_eq!(report.settled.len(), 3);
+
+        let report = batcher.flush_due(Instant::now() + Duration::from_millis(500)).await.unwrap();
+        assert_eq!(report.settled.len(), 1);
+
+        let batch = storage.get_settlement_batch(&report.batches[0]).await.unwrap().unwrap();
+        assert_eq!(batch.target_domain, "b");
+        assert_eq!(batch.status, BatchStatus::Completed);
+
+        let metrics = storage.settlement_batch_metrics().await;
+        assert_eq!(metrics.batches_created, 2);
+        assert_eq!(metrics.avg_batch_size, 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_partial_failure_requeues_only_failed_items() {
+        let (batcher, storage) = batcher(&["s2"]).await;
+        for id in ["s1", "s2", "s3"] {
+            batcher.enqueue(item(id, "a")).await;
+        }
+
+        let report = batcher.flush_due(Instant::now()).await.unwrap();
+        assert_eq!(report.requeued, 1);
+        let settled: Vec<&str> = report.settled.iter().map(|i| i.settlement_id.as_str()).collect();
+        assert_eq!(settled, vec!["s1", "s3"]);
+        // The re-queued item was retried individually and failed again
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0.settlement_id, "s2");
+
+        let batch = storage.get_settlement_batch(&report.batches[0]).await.unwrap().unwrap();
+        assert_eq!(batch.status, BatchStatus::PartiallyFailed);
+        assert_eq!(batch.item_status["s1"], BatchItemStatus::Settled);
+        assert_eq!(batch.item_status["s2"], BatchItemStatus::Failed("rejected".to_string()));
+        assert_eq!(storage.settlement_batch_metrics().await.partial_batch_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_disabling_batching_releases_open_batches() {
+        let (batcher, storage) = batcher(&[]).await;
+        batcher.enqueue(item("s1", "a")).await;
+
+        batcher.set_enabled(false).await;
+        assert!(!batcher.is_enabled());
+        batcher.enqueue(item("s2", "a")).await;
+
+        let report = batcher.flush_due(Instant::now()).await.unwrap();
+        assert!(report.batches.is_empty());
+        assert_eq!(report.settled.len(), 2);
+        assert!(storage.list_settlement_batches().await.unwrap().is_empty());
+    }
+}
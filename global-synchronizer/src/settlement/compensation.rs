@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use garp_common::{GarpError, GarpResult};
+use garp_common::types::TransactionId;
+
+use crate::cross_domain::{
+    ConfirmationStatus, CrossDomainCoordinator, CrossDomainTransaction, CrossDomainTransactionType,
+    EmergencyNotification, EmergencySeverity, EmergencyType, TransactionStatus,
+};
+use crate::storage::{DomainId, GlobalStorage};
+
+/// Metadata key linking a compensation transaction to the transaction it reverses
+pub const COMPENSATION_FOR_METADATA_KEY: &str = "compensation_for";
+
+/// Metadata key carrying a transaction's delivery priority
+pub const PRIORITY_METADATA_KEY: &str = "priority";
+
+/// Source domain of transactions issued by the synchronizer itself
+const SYNCHRONIZER_DOMAIN: &str = "global-synchronizer";
+
+/// Progress of a compensation chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CompensationStatus {
+    /// Some compensations have not finished
+    InProgress,
+
+    /// Every confirmed domain reversed its effects
+    Completed,
+
+    /// A compensation failed and was escalated
+    Escalated,
+}
+
+/// Compensation sent to one domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationLink {
+    /// Domain that had confirmed the original transaction
+    pub domain_id: DomainId,
+
+    /// Compensation transaction
+    pub transaction_id: TransactionId,
+
+    /// Compensation transaction status
+    pub status: TransactionStatus,
+}
+
+/// Compensations issued for one aborted transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationChain {
+    /// Aborted transaction
+    pub original_transaction_id: TransactionId,
+
+    /// Why the original transaction aborted
+    pub reason: String,
+
+    /// One compensation per domain that had confirmed
+    pub links: Vec<CompensationLink>,
+
+    /// Chain status
+    pub status: CompensationStatus,
+
+    /// Escalation raised for a failed compensation
+    pub emergency_notification: Option<String>,
+
+    /// Created timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Reverses the effects of cross-domain transactions that aborted after
+/// some domains had confirmed.
+///
+/// Each confirming domain gets its own compensation transaction, tagged with
+/// [`COMPENSATION_FOR_METADATA_KEY`] and delivered at high priority. Asset
+/// transfers are reversed with the opposite transfer; other transactions ask
+/// the domain to undo what it applied. Compensations are never compensated
+/// themselves: a failed compensation leaves the domain's state unknown, so
+/// the chain is escalated as data corruption instead.
+pub struct CompensationEngine {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Serializes updates to compensation chains
+    lock: Mutex<()>,
+}
+
+impl CompensationEngine {
+    /// Create new compensation engine
+    pub fn new(storage: Arc<GlobalStorage>) -> Self {
+        Self {
+            storage,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Whether `transaction` compensates another transaction
+    pub fn is_compensation(transaction: &CrossDomainTransaction) -> bool {
+        transaction.metadata.contains_key(COMPENSATION_FOR_METADATA_KEY)
+    }
+
+    /// Transaction type reversing `original` on one domain
+    fn reversal(original: &CrossDomainTransaction) -> CrossDomainTransactionType {
+        match &original.transaction_type {
+            CrossDomainTransactionType::AssetTransfer { asset_id, amount, from_address, to_address } => {
+                CrossDomainTransactionType::AssetTransfer {
+                    asset_id: asset_id.clone(),
+                    amount: *amount,
+                    from_address: to_address.clone(),
+                    to_address: from_address.clone(),
+                }
+            }
+            _ => CrossDomainTransactionType::Compensation {
+                original_transaction_id: original.transaction_id.clone(),
+            },
+        }
+    }
+
+    /// Compensation transactions for every domain that confirmed `original`,
+    /// in domain order
+    pub fn build_compensations(original: &CrossDomainTransaction, now: DateTime<Utc>) -> Vec<CrossDomainTransaction> {
+        let mut domains: Vec<&DomainId> = original.confirmations.values()
+            .filter(|confirmation| confirmation.status == ConfirmationStatus::Confirmed)
+            .map(|confirmation| &confirmation.domain_id)
+            .collect();
+        domains.sort();
+
+        let lifetime = (original.timeout_at - original.created_at).max(chrono::Duration::zero());
+        domains.into_iter()
+            .map(|domain_id| CrossDomainTransaction {
+                transaction_id: TransactionId::new(),
+                source_domain: SYNCHRONIZER_DOMAIN.to_string(),
+                target_domains: vec![domain_id.clone()],
+                transaction_type: Self::reversal(original),
+                data: original.transaction_id.to_string().into_bytes(),
+                dependencies: Vec::new(),
+                required_confirmations: 1,
+                confirmations: HashMap::new(),
+                status: TransactionStatus::Pending,
+                created_at: now,
+                updated_at: now,
+                timeout_at: now + lifetime,
+                not_before: None,
+                max_fee: 0,
+                delegated_by: None,
+                metadata: HashMap::from([
+                    (COMPENSATION_FOR_METADATA_KEY.to_string(), original.transaction_id.to_string()),
+                    (PRIORITY_METADATA_KEY.to_string(), "high".to_string()),
+                ]),
+            })
+            .collect()
+    }
+
+    /// Open a compensation chain for an aborted transaction. Returns the
+    /// compensations to submit, or `None` if no domain had confirmed.
+    pub async fn begin(&self, original: &CrossDomainTransaction, reason: &str) -> GarpResult<Option<Vec<CrossDomainTransaction>>> {
+        if Self::is_compensation(original) {
+            return Err(GarpError::ValidationError(format!(
+                "Transaction {} is a compensation and cannot be compensated", original.transaction_id
+            )));
+        }
+
+        let _guard = self.lock.lock().await;
+        if self.storage.get_compensation_chain(&original.transaction_id).await?.is_some() {
+            return Err(GarpError::ValidationError(format!(
+                "Transaction {} is already being compensated", original.transaction_id
+            )));
+        }
+
+        let now = Utc::now();
+        let compensations = Self::build_compensations(original, now);
+        if compensations.is_empty() {
+            return Ok(None);
+        }
+
+        let chain = CompensationChain {
+            original_transaction_id: original.transaction_id.clone(),
+            reason: reason.to_string(),
+            links: compensations.iter()
+                .map(|compensation| CompensationLink {
+                    domain_id: compensation.target_domains[0].clone(),
+                    transaction_id: compensation.transaction_id.clone(),
+                    status: TransactionStatus::Pending,
+                })
+                .collect(),
+            status: CompensationStatus::InProgress,
+            emergency_notification: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.storage.store_compensation_chain(chain).await?;
+
+        info!(
+            "Compensating transaction {} on {} domains: {}",
+            original.transaction_id, compensations.len(), reason
+        );
+        Ok(Some(compensations))
+    }
+
+    /// Record the status of a compensation transaction. Returns the
+    /// notification to raise if it failed.
+    pub async fn record_outcome(
+        &self,
+        compensation: &CrossDomainTransaction,
+        status: &TransactionStatus,
+    ) -> GarpResult<Option<EmergencyNotification>> {
+        let original_id = compensation.metadata.get(COMPENSATION_FOR_METADATA_KEY)
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .map(TransactionId)
+            .ok_or_else(|| GarpError::ValidationError(format!(
+                "Transaction {} is not a compensation", compensation.transaction_id
+            )))?;
+
+        let _guard = self.lock.lock().await;
+        let mut chain = self.storage.get_compensation_chain(&original_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("No compensation chain for {}", original_id)))?;
+        let link = chain.links.iter_mut()
+            .find(|link| link.transaction_id == compensation.transaction_id)
+            .ok_or_else(|| GarpError::NotFound(format!(
+                "Compensation {} is not part of the chain for {}", compensation.transaction_id, original_id
+            )))?;
+        if link.status == *status {
+            return Ok(None);
+        }
+        link.status = status.clone();
+        let failed_domain = (status.is_terminal() && *status != TransactionStatus::Completed)
+            .then(|| link.domain_id.clone());
+
+        let mut notification = None;
+        if let Some(domain_id) = failed_domain {
+            if chain.status != CompensationStatus::Escalated {
+                let escalation = EmergencyNotification {
+                    notification_id: Uuid::new_v4().to_string(),
+                    emergency_type: EmergencyType::DataCorruption,
+                    affected_domains: vec![domain_id.clone()],
+                    description: format!(
+                        "Compensation {} of transaction {} on domain {} ended {:?}; the domain's state is unknown",
+                        compensation.transaction_id, original_id, domain_id, status
+                    ),
+                    severity: EmergencySeverity::Critical,
+                    action_required: true,
+                };
+                error!("{}", escalation.description);
+                chain.status = CompensationStatus::Escalated;
+                chain.emergency_notification = Some(escalation.notification_id.clone());
+                notification = Some(escalation);
+            } else {
+                warn!("Compensation {} of transaction {} on domain {} also failed", compensation.transaction_id, original_id, domain_id);
+            }
+        } else if chain.status == CompensationStatus::InProgress
+            && chain.links.iter().all(|link| link.status == TransactionStatus::Completed)
+        {
+            chain.status = CompensationStatus::Completed;
+            info!("Transaction {} fully compensated", original_id);
+        }
+
+        chain.updated_at = Utc::now();
+        self.storage.store_compensation_chain(chain).await?;
+        Ok(notification)
+    }
+
+    /// Compensation chain of an aborted transaction
+    pub async fn get_chain(&self, original_transaction_id: &TransactionId) -> GarpResult<Option<CompensationChain>> {
+        self.storage.get_compensation_chain(original_transaction_id).await
+    }
+}
+
+/// Periodically compensates aborted coordination sessions and tracks
+/// compensation outcomes
+pub struct CompensationMonitor {
+    /// Cross-domain coordinator owning the coordination sessions
+    coordinator: Arc<CrossDomainCoordinator>,
+
+    /// Interval between passes
+    check_interval: Duration,
+}
+
+impl CompensationMonitor {
+    /// Create new compensation monitor
+    pub fn new(coordinator: Arc<CrossDomainCoordinator>, check_interval: Duration) -> Self {
+        Self {
+            coordinator,
+            check_interval,
+        }
+    }
+
+    /// Spawn the periodic compensation loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(self.check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match self.coordinator.process_compensations().await {
+                    Ok(compensated) if !compensated.is_empty() => {
+                        info!("Started compensation of {} aborted transactions", compensated.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!("Compensation pass failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GlobalSyncConfig;
+    use crate::cross_domain::DomainConfirmation;
+
+    fn confirmation(domain_id: &str, status: ConfirmationStatus) -> DomainConfirmation {
+        DomainConfirmation {
+            domain_id: domain_id.to_string(),
+            status,
+            data: Vec::new(),
+            signature: Vec::new(),
+            timestamp: Utc::now(),
+            validator_info: None,
+            vote_sequence_number: 0,
+        }
+    }
+
+    fn transfer() -> CrossDomainTransaction {
+        let now = Utc::now();
+        CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "a".to_string(),
+            target_domains: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "gold".to_string(),
+                amount: 10,
+                from_address: "alice".to_string(),
+                to_address: "bob".to_string(),
+            },
+            data: vec![1],
+            dependencies: Vec::new(),
+            required_confirmations: 3,
+            confirmations: HashMap::from([
+                ("a".to_string(), confirmation("a", ConfirmationStatus::Confirmed)),
+                ("b".to_string(), confirmation("b", ConfirmationStatus::Rejected)),
+                ("c".to_string(), confirmation("c", ConfirmationStatus::Confirmed)),
+            ]),
+            status: TransactionStatus::Rejected,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::minutes(5),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    async fn engine() -> CompensationEngine {
+        let config = Arc::new(GlobalSyncConfig::default());
+        CompensationEngine::new(Arc::new(GlobalStorage::new(config).await.unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_domains_are_compensated() {
+        let engine = engine().await;
+        let original = transfer();
+        let compensations = engine.begin(&original, "rejected by b").await.unwrap().unwrap();
+
+        let targets: Vec<&str> = compensations.iter().map(|tx| tx.target_domains[0].as_str()).collect();
+        assert_eq!(targets, vec!["a", "c"]);
+        for compensation in &compensations {
+            assert_eq!(compensation.metadata[COMPENSATION_FOR_METADATA_KEY], original.transaction_id.to_string());
+            assert!(matches!(
+                &compensation.transaction_type,
+                CrossDomainTransactionType::AssetTransfer { from_address, to_address, .. } if from_address == "bob" && to_address == "alice"
+            ));
+        }
+        assert!(engine.begin(&original, "again").await.is_err());
+        assert!(engine.begin(&compensations[0], "nested").await.is_err());
+
+        for compensation in &compensations {
+            assert!(engine.record_outcome(compensation, &TransactionStatus::Completed).await.unwrap().is_none());
+        }
+        let chain = engine.get_chain(&original.transaction_id).await.unwrap().unwrap();
+        assert_eq!(chain.status, CompensationStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_failed_compensation_escalates() {
+        let engine = engine().await;
+        let original = transfer();
+        let compensations = engine.begin(&original, "rejected by b").await.unwrap().unwrap();
+
+        engine.record_outcome(&compensations[0], &TransactionStatus::Completed).await.unwrap();
+        let notification = engine.record_outcome(&compensations[1], &TransactionStatus::TimedOut).await.unwrap().unwrap();
+        assert!(matches!(notification.emergency_type, EmergencyType::DataCorruption));
+        assert_eq!(notification.affected_domains, vec!["c".to_string()]);
+
+        let chain = engine.get_chain(&original.transaction_id).await.unwrap().unwrap();
+        assert_eq!(chain.status, CompensationStatus::Escalated);
+        assert_eq!(chain.emergency_notification, Some(notification.notification_id));
+    }
+
+    #[tokio::test]
+    async fn test_nothing_to_compensate_without_confirmations() {
+        let engine = engine().await;
+        let mut original = transfer();
+        original.confirmations.clear();
+        assert!(engine.begin(&original, "timed out").await.unwrap().is_none());
+        assert!(engine.get_chain(&original.transaction_id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use garp_common::{GarpError, GarpResult};
+
+use crate::cross_domain::{CrossDomainTransaction, CrossDomainTransactionType};
+use crate::storage::{DomainId, EscrowRecord, EscrowStatus, GlobalStorage};
+
+/// Condition releasing the assets of a conditional settlement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConditionType {
+    /// Met once the oracle at `endpoint` reports the condition fulfilled,
+    /// e.g. a document signing confirmation
+    OracleCallback { endpoint: String },
+
+    /// Met at `release_at`
+    TimeLocked { release_at: SystemTime },
+
+    /// Met once `threshold` of `signers` have signed off on the condition
+    SignatureThreshold { signers: Vec<String>, threshold: usize },
+}
+
+/// Assets locked by a conditional settlement, kept in the settlement data
+/// of each target domain's settlement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscrowLock {
+    /// Condition releasing the assets
+    pub condition_id: String,
+
+    /// Domain the assets are locked in
+    pub from_domain: DomainId,
+
+    /// Asset ID
+    pub asset_id: String,
+
+    /// Amount locked
+    pub amount: u64,
+
+    /// Sending address
+    pub from_address: String,
+
+    /// Receiving address
+    pub to_address: String,
+}
+
+impl EscrowLock {
+    /// Lock for an asset transfer. Only asset transfers can be escrowed.
+    pub fn of(transaction: &CrossDomainTransaction, condition_id: &str) -> GarpResult<Self> {
+        match &transaction.transaction_type {
+            CrossDomainTransactionType::AssetTransfer { asset_id, amount, from_address, to_address } => Ok(Self {
+                condition_id: condition_id.to_string(),
+                from_domain: transaction.source_domain.clone(),
+                asset_id: asset_id.clone(),
+                amount: *amount,
+                from_address: from_address.clone(),
+                to_address: to_address.clone(),
+            }),
+            other => Err(GarpError::ValidationError(format!(
+                "Only asset transfers can be settled conditionally, not {:?}", other
+            ))),
+        }
+    }
+}
+
+/// Reports whether oracle-backed conditions are fulfilled
+#[async_trait::async_trait]
+pub trait ConditionOracle: Send + Sync {
+    /// Whether the oracle at `endpoint` reports `condition_id` fulfilled
+    async fn is_fulfilled(&self, endpoint: &str, condition_id: &str) -> GarpResult<bool>;
+}
+
+/// Oracle answering `GET <endpoint>?condition_id=<id>` with
+/// `{"fulfilled": bool}`
+pub struct HttpConditionOracle {
+    /// HTTP client
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct OracleResponse {
+    fulfilled: bool,
+}
+
+impl HttpConditionOracle {
+    /// Create new HTTP condition oracle
+    pub fn new(timeout: Duration) -> GarpResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| GarpError::ConfigError(format!("Failed to build oracle client: {}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait::async_trait]
+impl ConditionOracle for HttpConditionOracle {
+    async fn is_fulfilled(&self, endpoint: &str, condition_id: &str) -> GarpResult<bool> {
+        let response = self.client.get(endpoint)
+            .query(&[("condition_id", condition_id)])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| GarpError::Unavailable(format!("Oracle {} unreachable: {}", endpoint, e)))?;
+        let response: OracleResponse = response.json().await
+            .map_err(|e| GarpError::Unavailable(format!("Oracle {} sent an invalid response: {}", endpoint, e)))?;
+        Ok(response.fulfilled)
+    }
+}
+
+/// What to do with a locked escrow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EscrowDecision {
+    /// The condition is met, release the assets to the target domains
+    Release(String),
+
+    /// The deadline passed first, refund the assets to the source domain
+    Expire(String),
+}
+
+/// Checks the conditions of locked escrows
+pub struct ConditionMonitor {
+    /// Storage holding escrow records
+    storage: Arc<GlobalStorage>,
+
+    /// Oracle for `OracleCallback` conditions
+    oracle: Arc<dyn ConditionOracle>,
+}
+
+impl ConditionMonitor {
+    /// Create new condition monitor
+    pub fn new(storage: Arc<GlobalStorage>, oracle: Arc<dyn ConditionOracle>) -> Self {
+        Self { storage, oracle }
+    }
+
+    /// Decide every locked escrow whose condition is met or whose deadline
+    /// has passed at `now`. A condition met only after the deadline does
+    /// not release the escrow.
+    pub async fn check(&self, now: SystemTime) -> GarpResult<Vec<EscrowDecision>> {
+        let mut decisions = Vec::new();
+        for record in self.storage.list_escrow_records(Some(&EscrowStatus::Locked)).await? {
+            if now >= record.deadline {
+                decisions.push(EscrowDecision::Expire(record.settlement_id));
+            } else if self.is_met(&record, now).await {
+                decisions.push(EscrowDecision::Release(record.settlement_id));
+            }
+        }
+        Ok(decisions)
+    }
+
+    async fn is_met(&self, record: &EscrowRecord, now: SystemTime) -> bool {
+        match &record.condition_type {
+            ConditionType::OracleCallback { endpoint } => {
+                match self.oracle.is_fulfilled(endpoint, &record.condition_id).await {
+                    Ok(fulfilled) => fulfilled,
+                    Err(e) => {
+                        debug!("Condition {} of settlement {} not checked: {}", record.condition_id, record.settlement_id, e);
+                        false
+                    }
+                }
+            }
+            ConditionType::TimeLocked { release_at } => now >= *release_at,
+            ConditionType::SignatureThreshold { signers, threshold } => {
+                signers.iter().filter(|signer| record.signatures.contains(*signer)).count() >= *threshold
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use crate::config::GlobalSyncConfig;
+    use garp_common::types::TransactionId;
+
+    /// Reports every condition as fulfilled, or fails every query
+    struct FixedOracle(Option<bool>);
+
+    #[async_trait::async_trait]
+    impl ConditionOracle for FixedOracle {
+        async fn is_fulfilled(&self, _endpoint: &str, _condition_id: &str) -> GarpResult<bool> {
+            self.0.ok_or_else(|| GarpError::Unavailable("oracle down".to_string()))
+        }
+    }
+
+    fn escrow(settlement_id: &str, condition_type: ConditionType, deadline: SystemTime) -> EscrowRecord {
+        EscrowRecord {
+            settlement_id: settlement_id.to_string(),
+            transaction_id: TransactionId::new(),
+            condition_id: format!("{}-condition", settlement_id),
+            condition_type,
+            deadline,
+            source_domain: "source".to_string(),
+            target_domains: vec!["target".to_string()],
+            payload: Vec::new(),
+            signatures: BTreeSet::new(),
+            status: EscrowStatus::Locked,
+            created_at: SystemTime::now(),
+            resolved_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_condition_monitor_releases_met_conditions_and_expires_late_ones() {
+        let storage = Arc::new(GlobalStorage::new(Arc::new(GlobalSyncConfig::default())).await.unwrap());
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        let oracle = ConditionType::OracleCallback { endpoint: "http://oracle.invalid/conditions".to_string() };
+
+        storage.store_escrow_record(escrow("oracle", oracle.clone(), now + hour)).await.unwrap();
+        storage.store_escrow_record(escrow("unlocked", ConditionType::TimeLocked { release_at: now - hour }, now + hour)).await.unwrap();
+        storage.store_escrow_record(escrow("still-locked", ConditionType::TimeLocked { release_at: now + hour }, now + 2 * hour)).await.unwrap();
+        // Met, but only after the deadline
+        storage.store_escrow_record(escrow("late", ConditionType::TimeLocked { release_at: now - hour }, now - hour)).await.unwrap();
+        let mut released = escrow("released", oracle, now - hour);
+        released.status = EscrowStatus::Released;
+        storage.store_escrow_record(released).await.unwrap();
+
+        let monitor = ConditionMonitor::new(storage.clone(), Arc::new(FixedOracle(Some(true))));
+        let mut decisions = monitor.check(now).await.unwrap();
+        decisions.sort_by_key(|d| format!("{:?}", d));
+        assert_eq!(decisions, vec![
+            EscrowDecision::Expire("late".to_string()),
+            EscrowDecision::Release("oracle".to_string()),
+            EscrowDecision::Release("unlocked".to_string()),
+        ]);
+
+        // An unreachable oracle leaves the escrow locked until its deadline
+        let monitor = ConditionMonitor::new(storage, Arc::new(FixedOracle(None)));
+        let decisions = monitor.check(now).await.unwrap();
+        assert!(!decisions.contains(&EscrowDecision::Release("oracle".to_string())));
+        assert!(monitor.check(now + 2 * hour).await.unwrap().contains(&EscrowDecision::Expire("oracle".to_string())));
+    }
+}
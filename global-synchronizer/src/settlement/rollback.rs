@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use garp_common::{GarpError, GarpResult};
+use garp_common::types::TransactionId;
+
+use crate::config::GlobalSyncConfig;
+use crate::cross_domain::{EmergencyNotification, EmergencySeverity, EmergencyType};
+use crate::network::{MessageDestination, MessagePriority, NetworkManager};
+use crate::storage::{DomainCompensation, DomainId, GlobalStorage, RollbackRecord, RollbackStatus, TransactionStatus};
+
+/// Request for a domain to reverse a settlement it already applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationInstruction {
+    /// Rollback this compensation belongs to
+    pub rollback_id: String,
+
+    /// Settlement being reversed
+    pub settlement_id: String,
+
+    /// Transaction being rolled back
+    pub transaction_id: Option<TransactionId>,
+
+    /// Domain asked to compensate
+    pub domain_id: DomainId,
+
+    /// Why the settlement is rolled back
+    pub reason: String,
+}
+
+/// Delivers compensations to domains and escalates rollbacks that cannot finish
+#[async_trait::async_trait]
+pub trait CompensationSender: Send + Sync {
+    /// Send a compensating settlement instruction to its domain
+    async fn send_compensation(&self, instruction: &CompensationInstruction) -> GarpResult<()>;
+
+    /// Raise an emergency for a rollback that exhausted its retries
+    async fn escalate(&self, notification: &EmergencyNotification) -> GarpResult<()>;
+}
+
+/// Sends compensations and escalations through the network manager
+pub struct NetworkCompensationSender {
+    /// Network manager
+    network_manager: Arc<NetworkManager>,
+}
+
+impl NetworkCompensationSender {
+    /// Create new network compensation sender
+    pub fn new(network_manager: Arc<NetworkManager>) -> Self {
+        Self { network_manager }
+    }
+}
+
+#[async_trait::async_trait]
+impl CompensationSender for NetworkCompensationSender {
+    async fn send_compensation(&self, instruction: &CompensationInstruction) -> GarpResult<()> {
+        self.network_manager.send_message(
+            MessageDestination::Domain(instruction.domain_id.clone()),
+            "settlement_compensation".to_string(),
+            serde_json::to_vec(instruction)?,
+            MessagePriority::High,
+        ).await?;
+        Ok(())
+    }
+
+    async fn escalate(&self, notification: &EmergencyNotification) -> GarpResult<()> {
+        self.network_manager.send_message(
+            MessageDestination::Validators,
+            "emergency_notification".to_string(),
+            serde_json::to_vec(notification)?,
+            MessagePriority::Critical,
+        ).await?;
+        Ok(())
+    }
+}
+
+/// Drives rollbacks of settlements that failed after some domains had
+/// already settled.
+///
+/// Each settled domain is sent a compensation until it acknowledges it,
+/// resending after the retry interval. A rollback completes once every
+/// domain has acknowledged; if a domain is still silent after the last
+/// attempt the rollback fails and an [`EmergencyNotification`] is raised.
+pub struct RollbackCoordinator {
+    /// Storage layer
+    storage: Arc<GlobalStorage>,
+
+    /// Delivery to domains
+    sender: Arc<dyn CompensationSender>,
+
+    /// Delivery attempts per domain before escalating
+    max_attempts: u32,
+
+    /// Wait for an acknowledgement before resending
+    retry_interval: Duration,
+
+    /// Serializes updates to rollback records
+    lock: Mutex<()>,
+}
+
+impl RollbackCoordinator {
+    /// Create new rollback coordinator
+    pub fn new(config: Arc<GlobalSyncConfig>, storage: Arc<GlobalStorage>, sender: Arc<dyn CompensationSender>) -> Self {
+        Self {
+            storage,
+            sender,
+            max_attempts: config.settlement.compensation_max_retries.max(1),
+            retry_interval: Duration::from_millis(config.settlement.compensation_retry_interval_ms),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Open a rollback compensating `settled_domains`
+    pub async fn begin(
+        &self,
+        settlement_id: &str,
+        transaction_id: &TransactionId,
+        settled_domains: &[DomainId],
+        reason: &str,
+    ) -> GarpResult<RollbackRecord> {
+        if settled_domains.is_empty() {
+            return Err(GarpError::ValidationError(format!(
+                "Settlement {} has no settled domains to compensate", settlement_id
+            )));
+        }
+
+        let record = RollbackRecord {
+            rollback_id: Uuid::new_v4().to_string(),
+            settlement_id: settlement_id.to_string(),
+            transaction_id: Some(transaction_id.clone()),
+            reason: reason.to_string(),
+            rollback_data: Vec::new(),
+            status: RollbackStatus::Pending,
+            compensations: settled_domains.iter()
+                .map(|domain_id| (domain_id.clone(), DomainCompensation {
+                    attempts: 0,
+                    last_sent_at: None,
+                    acknowledged: false,
+                    last_error: None,
+                }))
+                .collect(),
+            created_at: SystemTime::now(),
+            completed_at: None,
+            metadata: HashMap::new(),
+        };
+        self.storage.store_rollback_record(record.clone()).await?;
+
+        info!(
+            "Rollback {} opened for settlement {} ({} domains to compensate)",
+            record.rollback_id, settlement_id, settled_domains.len()
+        );
+        Ok(record)
+    }
+
+    /// Send compensations that are due by `now` and escalate rollbacks whose
+    /// domains stayed silent through every attempt. Returns the escalated rollbacks.
+    pub async fn process_due(&self, now: SystemTime) -> GarpResult<Vec<RollbackRecord>> {
+        let _guard = self.lock.lock().await;
+        let mut escalated = Vec::new();
+
+        let mut open = self.storage.list_rollback_records(Some(&RollbackStatus::Pending)).await?;
+        open.extend(self.storage.list_rollback_records(Some(&RollbackStatus::Processing)).await?);
+
+        for mut record in open {
+            let mut exhausted = Vec::new();
+            let mut domains: Vec<DomainId> = record.compensations.iter()
+                .filter(|(_, compensation)| !compensation.acknowledged)
+                .map(|(domain_id, _)| domain_id.clone())
+                .collect();
+            domains.sort();
+
+            for domain_id in domains {
+                let Some(compensation) = record.compensations.get_mut(&domain_id) else {
+                    continue;
+                };
+                let due = match compensation.last_sent_at {
+                    Some(sent_at) => now.duration_since(sent_at).unwrap_or_default() >= self.retry_interval,
+                    None => true,
+                };
+                if !due {
+                    continue;
+                }
+                if compensation.attempts >= self.max_attempts {
+                    exhausted.push(domain_id);
+                    continue;
+                }
+
+                let instruction = CompensationInstruction {
+                    rollback_id: record.rollback_id.clone(),
+                    settlement_id: record.settlement_id.clone(),
+                    transaction_id: record.transaction_id.clone(),
+                    domain_id: domain_id.clone(),
+                    reason: record.reason.clone(),
+                };
+                compensation.attempts += 1;
+                compensation.last_sent_at = Some(now);
+                match self.sender.send_compensation(&instruction).await {
+                    Ok(()) => {
+                        compensation.last_error = None;
+                        debug!("Sent compensation for rollback {} to {} (attempt {})", record.rollback_id, domain_id, compensation.attempts);
+                    }
+                    Err(e) => {
+                        warn!("Compensation for rollback {} to {} failed: {}", record.rollback_id, domain_id, e);
+                        compensation.last_error = Some(e.to_string());
+                    }
+                }
+            }
+
+            if exhausted.is_empty() {
+                record.status = RollbackStatus::Processing;
+            } else {
+                let notification = EmergencyNotification {
+                    notification_id: Uuid::new_v4().to_string(),
+                    emergency_type: EmergencyType::CompensationFailed,
+                    affected_domains: exhausted.clone(),
+                    description: format!(
+                        "Rollback {} of settlement {} was not acknowledged by {} after {} attempts",
+                        record.rollback_id, record.settlement_id, exhausted.join(", "), self.max_attempts
+                    ),
+                    severity: EmergencySeverity::Critical,
+                    action_required: true,
+                };
+                error!("{}", notification.description);
+                if let Err(e) = self.sender.escalate(&notification).await {
+                    error!("Failed to escalate rollback {}: {}", record.rollback_id, e);
+                }
+
+                record.status = RollbackStatus::Failed;
+                record.completed_at = Some(now);
+                record.metadata.insert("emergency_notification".to_string(), notification.notification_id);
+                escalated.push(record.clone());
+            }
+            self.storage.store_rollback_record(record).await?;
+        }
+
+        Ok(escalated)
+    }
+
+    /// Record a domain's confirmation of its compensation. Once every domain
+    /// has confirmed, the rollback completes and the original transaction is
+    /// marked rolled back.
+    pub async fn acknowledge(&self, rollback_id: &str, domain_id: &DomainId) -> GarpResult<RollbackRecord> {
+        let _guard = self.lock.lock().await;
+
+        let mut record = self.storage.get_rollback_record(rollback_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Rollback {} not found", rollback_id)))?;
+        if !matches!(record.status, RollbackStatus::Pending | RollbackStatus::Processing) {
+            return Err(GarpError::ValidationError(format!(
+                "Rollback {} is {:?} and no longer accepts acknowledgements", rollback_id, record.status
+            )));
+        }
+        let compensation = record.compensations.get_mut(domain_id)
+            .ok_or_else(|| GarpError::ValidationError(format!(
+                "Domain {} has no compensation in rollback {}", domain_id, rollback_id
+            )))?;
+        compensation.acknowledged = true;
+        compensation.last_error = None;
+
+        if record.compensations.values().all(|c| c.acknowledged) {
+            record.status = RollbackStatus::Completed;
+            record.completed_at = Some(SystemTime::now());
+            if let Some(transaction_id) = &record.transaction_id {
+                self.mark_rolled_back(transaction_id).await?;
+            }
+            info!("Rollback {} of settlement {} completed", record.rollback_id, record.settlement_id);
+        }
+
+        self.storage.store_rollback_record(record.clone()).await?;
+        Ok(record)
+    }
+
+    /// Set the stored transaction's status to `RolledBack`, if it is stored
+    async fn mark_rolled_back(&self, transaction_id: &TransactionId) -> GarpResult<()> {
+        match self.storage.get_transaction(transaction_id).await? {
+            Some(mut transaction) => {
+                transaction.status = TransactionStatus::RolledBack;
+                transaction.updated_at = SystemTime::now();
+                self.storage.store_transaction(transaction).await
+            }
+            None => {
+                debug!("Rolled back transaction {} is not in storage", transaction_id);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{ConsensusState, SettlementState, StoredTransaction};
+
+    /// Records deliveries; fails compensations to the listed domains
+    #[derive(Default)]
+    struct RecordingSender {
+        unreachable: Vec<DomainId>,
+        sent: std::sync::Mutex<Vec<DomainId>>,
+        escalations: std::sync::Mutex<Vec<EmergencyNotification>>,
+    }
+
+    #[async_trait::async_trait]
+    impl CompensationSender for RecordingSender {
+        async fn send_compensation(&self, instruction: &CompensationInstruction) -> GarpResult<()> {
+            self.sent.lock().unwrap().push(instruction.domain_id.clone());
+            if self.unreachable.contains(&instruction.domain_id) {
+                return Err(GarpError::InternalError("unreachable".to_string()));
+            }
+            Ok(())
+        }
+
+        async fn escalate(&self, notification: &EmergencyNotification) -> GarpResult<()> {
+            self.escalations.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    fn stored(id: &TransactionId) -> StoredTransaction {
+        let now = SystemTime::now();
+        StoredTransaction {
+            transaction_id: id.clone(),
+            transaction_data: Vec::new(),
+            transaction_type: "test".to_string(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            status: TransactionStatus::InSettlement,
+            consensus_state: ConsensusState {
+                phase: "committed".to_string(),
+                votes: HashMap::new(),
+                required_votes: 0,
+                result: None,
+                proof: None,
+                started_at: now,
+                completed_at: None,
+            },
+            settlement_state: SettlementState {
+                settlement_id: None,
+                settlement_type: "atomic".to_string(),
+                domain_settlements: HashMap::new(),
+                proof: None,
+                started_at: None,
+                completed_at: None,
+            },
+            created_at: now,
+            updated_at: now,
+            block_height: None,
+            block_hash: None,
+            metadata: HashMap::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        }
+    }
+
+    async fn coordinator(sender: Arc<RecordingSender>) -> (RollbackCoordinator, Arc<GlobalStorage>) {
+        let mut config = GlobalSyncConfig::default();
+        config.settlement.compensation_max_retries = 2;
+        config.settlement.compensation_retry_interval_ms = 1000;
+        let config = Arc::new(config);
+        let storage = Arc::new(GlobalStorage::new(config.clone()).await.unwrap());
+        (RollbackCoordinator::new(config, storage.clone(), sender), storage)
+    }
+
+    #[tokio::test]
+    async fn test_partial_settlement_is_compensated() {
+        let sender = Arc::new(RecordingSender::default());
+        let (coordinator, storage) = coordinator(sender.clone()).await;
+        let tx = TransactionId::new();
+        storage.store_transaction(stored(&tx)).await.unwrap();
+
+        // `a` and `b` settled before `c` failed
+        let domains = vec!["a".to_string(), "b".to_string()];
+        let record = coordinator.begin("s1", &tx, &domains, "domain c failed").await.unwrap();
+        let now = SystemTime::now();
+        assert!(coordinator.process_due(now).await.unwrap().is_empty());
+        assert_eq!(*sender.sent.lock().unwrap(), domains);
+
+        // Nothing is resent before the retry interval
+        coordinator.process_due(now + Duration::from_millis(500)).await.unwrap();
+        assert_eq!(sender.sent.lock().unwrap().len(), 2);
+
+        let record = coordinator.acknowledge(&record.rollback_id, &"a".to_string()).await.unwrap();
+        assert_eq!(record.status, RollbackStatus::Processing);
+        assert!(coordinator.acknowledge(&record.rollback_id, &"c".to_string()).await.is_err());
+
+        let record = coordinator.acknowledge(&record.rollback_id, &"b".to_string()).await.unwrap();
+        assert_eq!(record.status, RollbackStatus::Completed);
+        assert!(record.completed_at.is_some());
+
+        let transaction = storage.get_transaction(&tx).await.unwrap().unwrap();
+        assert_eq!(transaction.status, TransactionStatus::RolledBack);
+        assert_eq!(storage.list_rollback_records(Some(&RollbackStatus::Completed)).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unacknowledged_compensation_escalates() {
+        let sender = Arc::new(RecordingSender { unreachable: vec!["b".to_string()], ..Default::default() });
+        let (coordinator, storage) = coordinator(sender.clone()).await;
+        let tx = TransactionId::new();
+
+        let record = coordinator.begin("s1", &tx, &["a".to_string(), "b".to_string()], "timeout").await.unwrap();
+        let start = SystemTime::now();
+        coordinator.process_due(start).await.unwrap();
+        coordinator.acknowledge(&record.rollback_id, &"a".to_string()).await.unwrap();
+
+        // Second and last attempt to `b`
+        let retry = start + Duration::from_secs(1);
+        assert!(coordinator.process_due(retry).await.unwrap().is_empty());
+        let stored = storage.get_rollback_record(&record.rollback_id).await.unwrap().unwrap();
+        assert_eq!(stored.compensations["b"].attempts, 2);
+        assert!(stored.compensations["b"].last_error.is_some());
+
+        let escalated = coordinator.process_due(retry + Duration::from_secs(1)).await.unwrap();
+        assert_eq!(escalated.len(), 1);
+        assert_eq!(escalated[0].status, RollbackStatus::Failed);
+
+        let escalations = sender.escalations.lock().unwrap();
+        assert_eq!(escalations.len(), 1);
+        assert_eq!(escalations[0].affected_domains, vec!["b".to_string()]);
+        assert_eq!(sender.sent.lock().unwrap().len(), 3);
+        assert!(storage.list_rollback_records(Some(&RollbackStatus::Processing)).await.unwrap().is_empty());
+    }
+}
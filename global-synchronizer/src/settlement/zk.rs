@@ -0,0 +1,607 @@
+//! Zero-knowledge settlement proofs
+//!
+//! A Groth16 proof over BN254 lets auditors check a settlement without
+//! seeing its transaction. The prover shows it knows a transaction that
+//! (a) is a leaf of the block's transaction tree, (b) was confirmed by every
+//! participating domain and (c) was charged the scheduled fee, distributed
+//! to validators in shares that sum to it.
+//!
+//! Hashing inside the circuit is MiMC-5 in Miyaguchi-Preneel mode. The
+//! transaction tree is rebuilt with it over the block's transaction IDs, so
+//! anyone holding those IDs can recompute [`transaction_root`].
+
+use std::sync::OnceLock;
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof, ProvingKey};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::{CircuitSpecificSetupSNARK, SNARK};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use garp_common::{GarpError, GarpResult};
+use garp_common::types::TransactionId;
+
+use crate::storage::DomainId;
+
+/// Depth of the block transaction tree, enough for 1024 transactions
+pub const TREE_DEPTH: usize = 10;
+
+/// Participating domains a proof can cover
+pub const MAX_DOMAINS: usize = 8;
+
+/// Validator fee shares a proof can cover
+pub const MAX_FEE_SHARES: usize = 32;
+
+/// MiMC-5 rounds for a 254-bit field
+const MIMC_ROUNDS: usize = 110;
+
+/// Bits allowed for the hidden transaction data length
+const DATA_LEN_BITS: usize = 32;
+
+/// A settlement proof with the public inputs it was made for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkProof {
+    /// Settlement the proof covers
+    pub settlement_id: String,
+
+    /// Height of the block the transaction was committed in
+    pub block_height: u64,
+
+    /// Compressed Groth16 proof
+    pub proof: Vec<u8>,
+
+    /// Public inputs
+    pub public_inputs: SettlementPublicInputs,
+
+    /// Time the proof was generated
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Public inputs of a settlement proof. Field elements are hex-encoded
+/// little-endian.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementPublicInputs {
+    /// Root of the block's transaction tree
+    pub transaction_root: String,
+
+    /// Tag of the settlement ID, see [`settlement_tag`]
+    pub settlement_tag: String,
+
+    /// Commitment binding the hidden transaction to the settlement
+    pub transaction_commitment: String,
+
+    /// Commitment to the sorted participating domains, see [`domains_commitment`]
+    pub domains_commitment: String,
+
+    /// Number of participating domains
+    pub domain_count: u64,
+
+    /// Fee charged for the settlement
+    pub total_fee: u64,
+
+    /// Fee schedule: charge per participating domain
+    pub fee_per_domain: u64,
+
+    /// Fee schedule: charge per byte of transaction data
+    pub fee_per_byte: u64,
+}
+
+/// Everything the prover knows about a completed settlement
+#[derive(Debug, Clone)]
+pub struct SettlementWitness {
+    /// Settlement ID
+    pub settlement_id: String,
+
+    /// Settled transaction
+    pub transaction_id: TransactionId,
+
+    /// Transactions of the block the transaction was committed in, in block order
+    pub block_transactions: Vec<TransactionId>,
+
+    /// Participating domains and whether each confirmed
+    pub domains: Vec<(DomainId, bool)>,
+
+    /// Fee charged for the settlement
+    pub total_fee: u64,
+
+    /// Fee schedule: charge per participating domain
+    pub fee_per_domain: u64,
+
+    /// Fee schedule: charge per byte of transaction data
+    pub fee_per_byte: u64,
+
+    /// Shares the fee was distributed to validators in
+    pub fee_shares: Vec<u64>,
+}
+
+fn zk_error(e: impl std::fmt::Display) -> GarpError {
+    GarpError::InternalError(format!("Settlement proof error: {}", e))
+}
+
+fn round_constants() -> &'static [Fr] {
+    static CONSTANTS: OnceLock<Vec<Fr>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..MIMC_ROUNDS as u64)
+            .map(|round| hash_to_field(b"garp-settlement-mimc", &round.to_le_bytes()))
+            .collect()
+    })
+}
+
+fn hash_to_field(domain: &[u8], value: &[u8]) -> Fr {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(domain);
+    hasher.update(value);
+    Fr::from_le_bytes_mod_order(hasher.finalize().as_bytes())
+}
+
+fn mimc(mut x: Fr, key: Fr) -> Fr {
+    for c in round_constants() {
+        let t = x + key + c;
+        x = t.square().square() * t;
+    }
+    x + key
+}
+
+/// Two-to-one MiMC hash
+pub fn hash2(left: Fr, right: Fr) -> Fr {
+    [left, right].into_iter().fold(Fr::zero(), |h, m| mimc(m, h) + h + m)
+}
+
+fn mimc_var(x: &FpVar<Fr>, key: &FpVar<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut x = x.clone();
+    for c in round_constants() {
+        let t = &x + key + *c;
+        x = t.square()?.square()? * &t;
+    }
+    Ok(x + key)
+}
+
+fn hash2_var(left: &FpVar<Fr>, right: &FpVar<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut h = FpVar::zero();
+    for m in [left, right] {
+        h = mimc_var(m, &h)? + &h + m;
+    }
+    Ok(h)
+}
+
+/// Leaf of a transaction in the block transaction tree
+pub fn transaction_leaf(transaction_id: &TransactionId) -> Fr {
+    Fr::from(transaction_id.0.as_u128())
+}
+
+/// Tag of a settlement ID
+pub fn settlement_tag(settlement_id: &str) -> Fr {
+    hash_to_field(b"garp-settlement-id", settlement_id.as_bytes())
+}
+
+/// Tag of a domain ID
+pub fn domain_tag(domain_id: &DomainId) -> Fr {
+    hash_to_field(b"garp-settlement-domain", domain_id.as_bytes())
+}
+
+/// Commitment to participating domains, given sorted
+pub fn domains_commitment(domain_ids: &[DomainId]) -> Fr {
+    (0..MAX_DOMAINS).fold(Fr::zero(), |acc, i| {
+        hash2(acc, domain_ids.get(i).map(domain_tag).unwrap_or_else(Fr::zero))
+    })
+}
+
+/// Root of the transaction tree over a block's transactions, in block order
+pub fn transaction_root(block_transactions: &[TransactionId]) -> GarpResult<Fr> {
+    Ok(TransactionTree::new(block_transactions)?.root())
+}
+
+fn field_to_hex(value: &Fr) -> String {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).expect("writing to a Vec cannot fail");
+    hex::encode(bytes)
+}
+
+fn field_from_hex(value: &str) -> Option<Fr> {
+    Fr::deserialize_compressed(hex::decode(value).ok()?.as_slice()).ok()
+}
+
+impl SettlementPublicInputs {
+    /// Inputs in circuit order, `None` if a field element is malformed
+    fn field_elements(&self) -> Option<Vec<Fr>> {
+        Some(vec![
+            field_from_hex(&self.transaction_root)?,
+            field_from_hex(&self.settlement_tag)?,
+            field_from_hex(&self.transaction_commitment)?,
+            field_from_hex(&self.domains_commitment)?,
+            Fr::from(self.domain_count),
+            Fr::from(self.total_fee),
+            Fr::from(self.fee_per_domain),
+            Fr::from(self.fee_per_byte),
+        ])
+    }
+}
+
+/// Block transaction tree, zero-padded to `2^TREE_DEPTH` leaves
+struct TransactionTree {
+    levels: Vec<Vec<Fr>>,
+}
+
+impl TransactionTree {
+    fn new(block_transactions: &[TransactionId]) -> GarpResult<Self> {
+        if block_transactions.len() > 1 << TREE_DEPTH {
+            return Err(GarpError::ValidationError(format!(
+                "Block has {} transactions, settlement proofs cover at most {}",
+                block_transactions.len(),
+                1 << TREE_DEPTH
+            )));
+        }
+        let mut leaves: Vec<Fr> = block_transactions.iter().map(transaction_leaf).collect();
+        leaves.resize(1 << TREE_DEPTH, Fr::zero());
+
+        let mut levels = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let parents = level.chunks(2).map(|pair| hash2(pair[0], pair[1])).collect();
+            levels.push(parents);
+        }
+        Ok(Self { levels })
+    }
+
+    fn root(&self) -> Fr {
+        self.levels[TREE_DEPTH][0]
+    }
+
+    /// Siblings from the leaf up, and whether the path node is a right child
+    fn path(&self, mut index: usize) -> (Vec<Fr>, Vec<bool>) {
+        self.levels[..TREE_DEPTH]
+            .iter()
+            .map(|level| {
+                let step = (level[index ^ 1], index & 1 == 1);
+                index >>= 1;
+                step
+            })
+            .unzip()
+    }
+}
+
+/// The settlement relation. Public inputs are allocated in the order of
+/// [`SettlementPublicInputs::field_elements`].
+#[derive(Clone)]
+struct SettlementCircuit {
+    transaction_root: Fr,
+    settlement_tag: Fr,
+    transaction_commitment: Fr,
+    domains_commitment: Fr,
+    domain_count: u64,
+    total_fee: u64,
+    fee_per_domain: u64,
+    fee_per_byte: u64,
+
+    leaf: Fr,
+    siblings: Vec<Fr>,
+    is_right: Vec<bool>,
+    domain_tags: Vec<Fr>,
+    active: Vec<bool>,
+    confirmed: Vec<bool>,
+    data_len: u64,
+    fee_shares: Vec<u64>,
+}
+
+impl SettlementCircuit {
+    /// Circuit shape for parameter generation
+    fn blank() -> Self {
+        Self {
+            transaction_root: Fr::zero(),
+            settlement_tag: Fr::zero(),
+            transaction_commitment: Fr::zero(),
+            domains_commitment: Fr::zero(),
+            domain_count: 0,
+            total_fee: 0,
+            fee_per_domain: 0,
+            fee_per_byte: 0,
+            leaf: Fr::zero(),
+            siblings: vec![Fr::zero(); TREE_DEPTH],
+            is_right: vec![false; TREE_DEPTH],
+            domain_tags: vec![Fr::zero(); MAX_DOMAINS],
+            active: vec![false; MAX_DOMAINS],
+            confirmed: vec![false; MAX_DOMAINS],
+            data_len: 0,
+            fee_shares: vec![0; MAX_FEE_SHARES],
+        }
+    }
+
+    /// Build the circuit for a witness, checking natively that the relation
+    /// holds since Groth16 would otherwise produce a proof that fails to verify
+    fn new(witness: &SettlementWitness) -> GarpResult<(Self, SettlementPublicInputs)> {
+        let index = witness.block_transactions.iter()
+            .position(|id| *id == witness.transaction_id)
+            .ok_or_else(|| GarpError::ValidationError(format!(
+                "Transaction {} is not in its block", witness.transaction_id
+            )))?;
+        let tree = TransactionTree::new(&witness.block_transactions)?;
+        let (siblings, is_right) = tree.path(index);
+
+        let mut domains = witness.domains.clone();
+        domains.sort();
+        if domains.len() > MAX_DOMAINS {
+            return Err(GarpError::ValidationError(format!(
+                "Settlement has {} domains, proofs cover at most {}", domains.len(), MAX_DOMAINS
+            )));
+        }
+        if let Some((domain_id, _)) = domains.iter().find(|(_, confirmed)| !confirmed) {
+            return Err(GarpError::ValidationError(format!("Domain {} has not confirmed", domain_id)));
+        }
+
+        if witness.fee_shares.len() > MAX_FEE_SHARES {
+            return Err(GarpError::ValidationError(format!(
+                "Fee was split into {} shares, proofs cover at most {}", witness.fee_shares.len(), MAX_FEE_SHARES
+            )));
+        }
+        let distributed: u128 = witness.fee_shares.iter().map(|share| *share as u128).sum();
+        if distributed != witness.total_fee as u128 {
+            return Err(GarpError::ValidationError(format!(
+                "Fee shares sum to {}, fee is {}", distributed, witness.total_fee
+            )));
+        }
+        let domain_charge = witness.fee_per_domain as u128 * domains.len() as u128;
+        let byte_charge = (witness.total_fee as u128).checked_sub(domain_charge);
+        let data_len = match (byte_charge, witness.fee_per_byte) {
+            (Some(0), _) => 0,
+            (Some(charge), per_byte) if per_byte > 0 && charge % per_byte as u128 == 0 => charge / per_byte as u128,
+            _ => {
+                return Err(GarpError::ValidationError(format!(
+                    "Fee {} does not match the fee schedule for {} domains", witness.total_fee, domains.len()
+                )))
+            }
+        };
+        if data_len >= 1 << DATA_LEN_BITS {
+            return Err(GarpError::ValidationError(format!("Transaction data length {} is out of range", data_len)));
+        }
+
+        let domain_ids: Vec<DomainId> = domains.iter().map(|(domain_id, _)| domain_id.clone()).collect();
+        let leaf = transaction_leaf(&witness.transaction_id);
+        let tag = settlement_tag(&witness.settlement_id);
+        let circuit = Self {
+            transaction_root: tree.root(),
+            settlement_tag: tag,
+            transaction_commitment: hash2(leaf, tag),
+            domains_commitment: domains_commitment(&domain_ids),
+            domain_count: domains.len() as u64,
+            total_fee: witness.total_fee,
+            fee_per_domain: witness.fee_per_domain,
+            fee_per_byte: witness.fee_per_byte,
+            leaf,
+            siblings,
+            is_right,
+            domain_tags: (0..MAX_DOMAINS)
+                .map(|i| domain_ids.get(i).map(domain_tag).unwrap_or_else(Fr::zero))
+                .collect(),
+            active: (0..MAX_DOMAINS).map(|i| i < domains.len()).collect(),
+            confirmed: (0..MAX_DOMAINS).map(|i| i < domains.len()).collect(),
+            data_len: data_len as u64,
+            fee_shares: (0..MAX_FEE_SHARES).map(|i| witness.fee_shares.get(i).copied().unwrap_or(0)).collect(),
+        };
+        let inputs = SettlementPublicInputs {
+            transaction_root: field_to_hex(&circuit.transaction_root),
+            settlement_tag: field_to_hex(&circuit.settlement_tag),
+            transaction_commitment: field_to_hex(&circuit.transaction_commitment),
+            domains_commitment: field_to_hex(&circuit.domains_commitment),
+            domain_count: circuit.domain_count,
+            total_fee: circuit.total_fee,
+            fee_per_domain: circuit.fee_per_domain,
+            fee_per_byte: circuit.fee_per_byte,
+        };
+        Ok((circuit, inputs))
+    }
+}
+
+fn witness_bits(cs: &ConstraintSystemRef<Fr>, value: u64, bits: usize) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    (0..bits)
+        .map(|i| Boolean::new_witness(cs.clone(), || Ok((value >> i) & 1 == 1)))
+        .collect()
+}
+
+impl ConstraintSynthesizer<Fr> for SettlementCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let input = |value: Fr| FpVar::new_input(cs.clone(), || Ok(value));
+        let transaction_root = input(self.transaction_root)?;
+        let settlement_tag = input(self.settlement_tag)?;
+        let transaction_commitment = input(self.transaction_commitment)?;
+        let domains_commitment = input(self.domains_commitment)?;
+        let domain_count = input(Fr::from(self.domain_count))?;
+        let total_fee = input(Fr::from(self.total_fee))?;
+        let fee_per_domain = input(Fr::from(self.fee_per_domain))?;
+        let fee_per_byte = input(Fr::from(self.fee_per_byte))?;
+
+        // (a) The transaction is in the block and bound to this settlement
+        let leaf = FpVar::new_witness(cs.clone(), || Ok(self.leaf))?;
+        let mut node = leaf.clone();
+        for (sibling, is_right) in self.siblings.iter().zip(&self.is_right) {
+            let sibling = FpVar::new_witness(cs.clone(), || Ok(*sibling))?;
+            let is_right = Boolean::new_witness(cs.clone(), || Ok(*is_right))?;
+            let left = is_right.select(&sibling, &node)?;
+            let right = is_right.select(&node, &sibling)?;
+            node = hash2_var(&left, &right)?;
+        }
+        node.enforce_equal(&transaction_root)?;
+        hash2_var(&leaf, &settlement_tag)?.enforce_equal(&transaction_commitment)?;
+
+        // (b) Every committed domain confirmed
+        let mut commitment = FpVar::zero();
+        let mut active_count = FpVar::zero();
+        for i in 0..MAX_DOMAINS {
+            let tag = FpVar::new_witness(cs.clone(), || Ok(self.domain_tags[i]))?;
+            let active = Boolean::new_witness(cs.clone(), || Ok(self.active[i]))?;
+            let confirmed = Boolean::new_witness(cs.clone(), || Ok(self.confirmed[i]))?;
+            active.and(&confirmed.not())?.enforce_equal(&Boolean::FALSE)?;
+            commitment = hash2_var(&commitment, &active.select(&tag, &FpVar::zero())?)?;
+            active_count += FpVar::from(active);
+        }
+        commitment.enforce_equal(&domains_commitment)?;
+        active_count.enforce_equal(&domain_count)?;
+
+        // (c) The fee follows the schedule and the shares add up to it. All
+        // terms are range-checked, so nothing wraps around the field.
+        let data_len = Boolean::le_bits_to_fp_var(&witness_bits(&cs, self.data_len, DATA_LEN_BITS)?)?;
+        (&fee_per_domain * &domain_count + &fee_per_byte * &data_len).enforce_equal(&total_fee)?;
+        let mut distributed = FpVar::zero();
+        for share in &self.fee_shares {
+            distributed += Boolean::le_bits_to_fp_var(&witness_bits(&cs, *share, 64)?)?;
+        }
+        distributed.enforce_equal(&total_fee)?;
+
+        Ok(())
+    }
+}
+
+/// Groth16 parameters for the settlement circuit
+pub struct ZkSettlementKeys {
+    proving_key: ProvingKey<Bn254>,
+    verifying_key: PreparedVerifyingKey<Bn254>,
+}
+
+impl ZkSettlementKeys {
+    /// Run a fresh circuit-specific setup. Whoever runs it must discard the
+    /// randomness, or they can forge proofs.
+    pub fn setup<R: RngCore + CryptoRng>(rng: &mut R) -> GarpResult<Self> {
+        let (proving_key, _) = Groth16::<Bn254>::circuit_specific_setup(SettlementCircuit::blank(), rng)
+            .map_err(zk_error)?;
+        Ok(Self::from_proving_key(proving_key))
+    }
+
+    fn from_proving_key(proving_key: ProvingKey<Bn254>) -> Self {
+        let verifying_key = prepare_verifying_key(&proving_key.vk);
+        Self { proving_key, verifying_key }
+    }
+
+    /// Load parameters written by [`Self::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> GarpResult<Self> {
+        let proving_key = ProvingKey::deserialize_compressed(bytes).map_err(zk_error)?;
+        Ok(Self::from_proving_key(proving_key))
+    }
+
+    /// Serialize the parameters, including the proving key
+    pub fn to_bytes(&self) -> GarpResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.proving_key.serialize_compressed(&mut bytes).map_err(zk_error)?;
+        Ok(bytes)
+    }
+
+    /// Serialized verifying key for auditors
+    pub fn verifying_key_bytes(&self) -> GarpResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.proving_key.vk.serialize_compressed(&mut bytes).map_err(zk_error)?;
+        Ok(bytes)
+    }
+
+    /// Prove a completed settlement
+    pub fn prove<R: RngCore + CryptoRng>(
+        &self,
+        witness: &SettlementWitness,
+        block_height: u64,
+        rng: &mut R,
+    ) -> GarpResult<ZkProof> {
+        let (circuit, public_inputs) = SettlementCircuit::new(witness)?;
+        let proof = Groth16::<Bn254>::prove(&self.proving_key, circuit, rng).map_err(zk_error)?;
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).map_err(zk_error)?;
+        Ok(ZkProof {
+            settlement_id: witness.settlement_id.clone(),
+            block_height,
+            proof: bytes,
+            public_inputs,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Check `proof` against `public_inputs`
+    pub fn verify(&self, proof: &ZkProof, public_inputs: &SettlementPublicInputs) -> bool {
+        let Some(inputs) = public_inputs.field_elements() else {
+            return false;
+        };
+        let Ok(proof) = Proof::<Bn254>::deserialize_compressed(proof.proof.as_slice()) else {
+            return false;
+        };
+        Groth16::<Bn254>::verify_with_processed_vk(&self.verifying_key, &inputs, &proof).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_relations::r1cs::ConstraintSystem;
+    use rand::rngs::OsRng;
+
+    fn witness() -> SettlementWitness {
+        let transaction_id = TransactionId::new();
+        SettlementWitness {
+            settlement_id: "settlement-1".to_string(),
+            transaction_id: transaction_id.clone(),
+            block_transactions: vec![TransactionId::new(), transaction_id, TransactionId::new()],
+            domains: vec![("domain-b".to_string(), true), ("domain-a".to_string(), true)],
+            total_fee: 2 * 10 + 3 * 7,
+            fee_per_domain: 10,
+            fee_per_byte: 3,
+            fee_shares: vec![30, 11],
+        }
+    }
+
+    fn is_satisfied(circuit: SettlementCircuit) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn test_relation_holds_only_for_sound_settlements() {
+        let witness = witness();
+        let (circuit, inputs) = SettlementCircuit::new(&witness).unwrap();
+        assert_eq!(
+            inputs.transaction_root,
+            field_to_hex(&transaction_root(&witness.block_transactions).unwrap())
+        );
+        assert_eq!(
+            inputs.domains_commitment,
+            field_to_hex(&domains_commitment(&["domain-a".to_string(), "domain-b".to_string()]))
+        );
+        assert!(is_satisfied(circuit.clone()));
+
+        let mut unconfirmed = circuit.clone();
+        unconfirmed.confirmed[1] = false;
+        assert!(!is_satisfied(unconfirmed));
+
+        let mut wrong_shares = circuit.clone();
+        wrong_shares.fee_shares[0] -= 1;
+        assert!(!is_satisfied(wrong_shares));
+
+        let mut wrong_block = circuit;
+        wrong_block.leaf = transaction_leaf(&TransactionId::new());
+        assert!(!is_satisfied(wrong_block));
+
+        let mut pending = witness.clone();
+        pending.domains[0].1 = false;
+        assert!(SettlementCircuit::new(&pending).is_err());
+        let mut overcharged = witness;
+        overcharged.total_fee += 1;
+        overcharged.fee_shares[0] += 1;
+        assert!(SettlementCircuit::new(&overcharged).is_err());
+    }
+
+    #[test]
+    fn test_proof_verifies_against_its_public_inputs() {
+        let keys = ZkSettlementKeys::setup(&mut OsRng).unwrap();
+        let proof = keys.prove(&witness(), 7, &mut OsRng).unwrap();
+        assert!(keys.verify(&proof, &proof.public_inputs));
+
+        let mut other_fee = proof.public_inputs.clone();
+        other_fee.total_fee += 1;
+        assert!(!keys.verify(&proof, &other_fee));
+
+        let mut other_settlement = proof.public_inputs.clone();
+        other_settlement.settlement_tag = field_to_hex(&settlement_tag("settlement-2"));
+        assert!(!keys.verify(&proof, &other_settlement));
+
+        let reloaded = ZkSettlementKeys::from_bytes(&keys.to_bytes().unwrap()).unwrap();
+        assert!(reloaded.verify(&proof, &proof.public_inputs));
+    }
+}
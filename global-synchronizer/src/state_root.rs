@@ -0,0 +1,184 @@
+//! Global state root.
+//!
+//! Each domain's state is committed to by its state hash, the BLAKE3 hash of
+//! its `state_data`. The global state root is a Merkle tree over the domains
+//! sorted by ID, so every node holding the same domain states computes the
+//! same root whatever order it learned them in. Leaves are
+//! `sha256(len_le(domain_id) || domain_id || state_hash)` and the tree is
+//! built like the transaction tree in [`crate::receipt`]: parents are
+//! `sha256(left || right)` with the last node duplicated on odd levels, and
+//! with no domains the root is the SHA-256 hash of no input.
+//!
+//! Block headers and finality certificates carry the root, and a
+//! [`StateRootProof`] shows a domain's state hash is included in it.
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::receipt::{merkle_levels, merkle_path, merkle_path_root};
+use crate::storage::DomainId;
+
+/// Proof that a domain's state hash is included in a global state root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateRootProof {
+    /// Domain ID
+    pub domain_id: DomainId,
+
+    /// Hex-encoded domain state hash
+    pub state_hash: String,
+
+    /// Hex-encoded global state root
+    pub root: String,
+
+    /// Hex-encoded sibling hashes from the leaf up
+    pub path: Vec<String>,
+
+    /// Side of the running hash at each level
+    pub directions: Vec<String>,
+}
+
+/// Global state root committed at a block height, with the domain state
+/// hashes it was built from so inclusion proofs can be served later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateRootRecord {
+    /// Block height
+    pub height: u64,
+
+    /// Global state root
+    pub root: Vec<u8>,
+
+    /// State hash of each domain
+    pub domain_hashes: BTreeMap<DomainId, Vec<u8>>,
+
+    /// Time the root was recorded
+    pub recorded_at: SystemTime,
+}
+
+/// State hash of a domain's `state_data`
+pub fn domain_state_hash(state_data: &[u8]) -> Vec<u8> {
+    blake3::hash(state_data).as_bytes().to_vec()
+}
+
+/// Leaf of a domain in the global state tree
+pub fn domain_leaf_hash(domain_id: &str, state_hash: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update((domain_id.len() as u64).to_le_bytes());
+    hasher.update(domain_id.as_bytes());
+    hasher.update(state_hash);
+    hasher.finalize().to_vec()
+}
+
+/// Leaves in domain order
+fn sorted_leaves<'a>(domain_hashes: impl IntoIterator<Item = (&'a DomainId, &'a Vec<u8>)>) -> Vec<(&'a DomainId, Vec<u8>)> {
+    let mut leaves: Vec<_> = domain_hashes.into_iter()
+        .map(|(domain_id, state_hash)| (domain_id, domain_leaf_hash(domain_id, state_hash)))
+        .collect();
+    leaves.sort_by(|a, b| a.0.cmp(b.0));
+    leaves
+}
+
+/// Global state root over the given domain state hashes
+pub fn global_state_root<'a>(domain_hashes: impl IntoIterator<Item = (&'a DomainId, &'a Vec<u8>)>) -> Vec<u8> {
+    let leaves = sorted_leaves(domain_hashes);
+    if leaves.is_empty() {
+        return Sha256::digest([]).to_vec();
+    }
+    merkle_levels(leaves.into_iter().map(|(_, leaf)| leaf).collect()).pop().unwrap().remove(0)
+}
+
+/// Inclusion proof for `domain_id` in the root over `domain_hashes`, or
+/// `None` if the domain is not one of them
+pub fn state_root_proof<'a>(
+    domain_hashes: impl IntoIterator<Item = (&'a DomainId, &'a Vec<u8>)> + Clone,
+    domain_id: &str,
+) -> Option<StateRootProof> {
+    let state_hash = domain_hashes.clone().into_iter().find(|(id, _)| id.as_str() == domain_id)?.1.clone();
+    let leaves = sorted_leaves(domain_hashes);
+    let index = leaves.iter().position(|(id, _)| id.as_str() == domain_id)?;
+    let levels = merkle_levels(leaves.into_iter().map(|(_, leaf)| leaf).collect());
+    let (path, directions) = merkle_path(&levels, index);
+
+    Some(StateRootProof {
+        domain_id: domain_id.to_string(),
+        state_hash: hex::encode(state_hash),
+        root: hex::encode(&levels.last()?[0]),
+        path,
+        directions,
+    })
+}
+
+impl StateRootRecord {
+    /// Inclusion proof for a domain's state hash in this root
+    pub fn proof(&self, domain_id: &str) -> Option<StateRootProof> {
+        state_root_proof(&self.domain_hashes, domain_id)
+    }
+}
+
+impl StateRootProof {
+    /// Check the proof leads from the domain's state hash to `root`, for
+    /// example the state root of a finality certificate
+    pub fn verify(&self, root: &[u8]) -> bool {
+        let Ok(state_hash) = hex::decode(&self.state_hash) else {
+            return false;
+        };
+        merkle_path_root(domain_leaf_hash(&self.domain_id, &state_hash), &self.path, &self.directions)
+            .map_or(false, |computed| computed == root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domain_hashes(count: usize) -> Vec<(DomainId, Vec<u8>)> {
+        (0..count)
+            .map(|i| (format!("domain{}", i), domain_state_hash(format!("state {}", i).as_bytes())))
+            .collect()
+    }
+
+    #[test]
+    fn test_root_is_independent_of_input_order() {
+        let hashes = domain_hashes(7);
+        let mut shuffled = hashes.clone();
+        shuffled.reverse();
+        shuffled.swap(1, 4);
+        shuffled.swap(0, 5);
+        assert_ne!(hashes, shuffled);
+
+        let root = global_state_root(hashes.iter().map(|(id, hash)| (id, hash)));
+        assert_eq!(root, global_state_root(shuffled.iter().map(|(id, hash)| (id, hash))));
+
+        let map: std::collections::HashMap<_, _> = shuffled.into_iter().collect();
+        assert_eq!(root, global_state_root(&map));
+
+        let mut changed = hashes.clone();
+        changed[3].1 = domain_state_hash(b"other");
+        assert_ne!(root, global_state_root(changed.iter().map(|(id, hash)| (id, hash))));
+        assert_eq!(global_state_root(std::iter::empty()), Sha256::digest([]).to_vec());
+    }
+
+    #[test]
+    fn test_state_root_proofs_verify() {
+        let hashes: BTreeMap<_, _> = domain_hashes(5).into_iter().collect();
+        let root = global_state_root(&hashes);
+        for domain_id in hashes.keys() {
+            let proof = state_root_proof(&hashes, domain_id).unwrap();
+            assert_eq!(hex::decode(&proof.root).unwrap(), root);
+            assert!(proof.verify(&root), "proof for {} should verify", domain_id);
+        }
+        assert!(state_root_proof(&hashes, "unknown").is_none());
+
+        let proof = state_root_proof(&hashes, "domain2").unwrap();
+        let mut other_domain = proof.clone();
+        other_domain.domain_id = "domain3".to_string();
+        assert!(!other_domain.verify(&root));
+
+        let mut other_hash = proof.clone();
+        other_hash.state_hash = hex::encode(domain_state_hash(b"forged"));
+        assert!(!other_hash.verify(&root));
+        assert!(!proof.verify(&[0u8; 32]));
+    }
+}
@@ -2,15 +2,22 @@ use std::collections::{HashMap, BTreeMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime};
 use std::path::PathBuf;
-use tokio::sync::{RwLock, Mutex, mpsc, oneshot};
+use tokio::sync::{broadcast, RwLock, Mutex, mpsc, oneshot};
 use tokio::time::{interval, timeout};
 use uuid::Uuid;
+use futures::stream::{self, BoxStream, StreamExt};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error, debug};
-use sqlx::{Pool, Postgres, Row};
-
-use garp_common::{GarpResult, GarpError};
+use bloomfilter::Bloom;
+use sqlx::{Pool, Postgres, Row, Sqlite};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
+use std::str::FromStr;
+
+use garp_common::{GarpResult, GarpError, ReputationScore, ReputationStore};
+use garp_common::canonical;
+use garp_common::error::TransactionError;
 use garp_common::types::{ParticipantId, TransactionId, Block, Transaction};
+use garp_common::settlement::SettlementProof;
 // Re-export canonical block header from garp_common
 pub use garp_common::types::BlockHeader;
 // Canonical type aliases to align with garp_common
@@ -21,8 +28,32 @@ pub type NodeId = String;
 pub type DomainId = String;
 pub type BlockHash = Vec<u8>;
 
-use crate::config::GlobalSyncConfig;
-use crate::consensus::FinalityCertificate;
+use crate::config::{ConfigReloadRecord, ExistenceFilterConfig, GlobalSyncConfig};
+use crate::consensus::{FinalityCertificate, SlashingEvidenceType};
+use crate::consensus::checkpoint::LedgerCheckpoint;
+use crate::cross_domain::channel::{ChannelId, StateChannel};
+use crate::cross_domain::delegation::DomainDelegation;
+use crate::cross_domain::emergency::EmergencyAuditRecord;
+use crate::cross_domain::governance::{GovernanceProposal, ProposalId};
+use crate::cross_domain::swap::{AtomicSwap, SwapId};
+use crate::cross_domain::wrapping::{TokenWrapping, WrapId, WrappedToken, WrappedTokenRegistry};
+use crate::discovery::DomainRegistration;
+use crate::receipt::{self, TransactionReceipt};
+use crate::settlement::compensation::CompensationChain;
+use crate::settlement::escrow::ConditionType;
+use crate::settlement::zk::ZkProof;
+use crate::state_root::{self, StateRootProof, StateRootRecord};
+use crate::MaintenanceMode;
+
+pub mod lsm;
+
+pub use lsm::LsmStorageBackend;
+
+/// Finalized block notifications buffered per subscriber
+const FINALIZED_BLOCK_CAPACITY: usize = 256;
+
+/// Orphaned block notifications buffered per subscriber
+const ORPHANED_BLOCK_CAPACITY: usize = 64;
 
 /// Global storage manager for distributed data persistence
 pub struct GlobalStorage {
@@ -69,6 +100,12 @@ pub struct GlobalStorage {
     event_tx: mpsc::UnboundedSender<StorageEvent>,
     event_rx: Arc<Mutex<mpsc::UnboundedReceiver<StorageEvent>>>,
     
+    /// Blocks as they are stored, for API subscribers
+    block_tx: broadcast::Sender<BlockInfo>,
+    
+    /// Blocks dropped from the chain, for re-queueing their transactions
+    orphan_tx: broadcast::Sender<OrphanedBlock>,
+    
     /// Shutdown signal
     shutdown_tx: Option<oneshot::Sender<()>>,
 }
@@ -81,6 +118,10 @@ pub struct TransactionStorage {
     /// Active transactions
     active_transactions: Arc<RwLock<HashMap<TransactionId, StoredTransaction>>>,
     
+    /// Bloom filter over `active_transactions` keys, so existence checks
+    /// for absent transactions skip the map
+    existence_filter: Arc<parking_lot::RwLock<Bloom<TransactionId>>>,
+    
     /// Transaction history
     transaction_history: Arc<RwLock<BTreeMap<u64, Vec<TransactionId>>>>,
     
@@ -178,6 +219,22 @@ pub enum TransactionStatus {
     
     /// Rolled back
     RolledBack,
+    
+    /// Timed out
+    TimedOut,
+    
+    /// Cancelled (e.g. evicted because a dependency failed)
+    Cancelled,
+}
+
+impl TransactionStatus {
+    /// Whether the transaction can no longer change status
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Settled | Self::Failed | Self::Rejected | Self::RolledBack | Self::TimedOut | Self::Cancelled
+        )
+    }
 }
 
 /// Consensus state
@@ -246,13 +303,228 @@ pub struct DomainSettlementState {
     pub timestamp: SystemTime,
 }
 
+/// Everything known about a transaction, as served by `getTransactionDetails`.
+/// Timestamps are milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionDetails {
+    /// Transaction ID
+    pub transaction_id: TransactionId,
+    
+    /// Transaction type
+    pub transaction_type: String,
+    
+    /// Source domain
+    pub source_domain: DomainId,
+    
+    /// Target domains
+    pub target_domains: Vec<DomainId>,
+    
+    /// Transaction status
+    pub status: TransactionStatus,
+    
+    /// Consensus progress
+    pub consensus: ConsensusDetails,
+    
+    /// Settlement progress
+    pub settlement: SettlementDetails,
+    
+    /// Block height, once assigned
+    pub block_height: Option<u64>,
+    
+    /// Hex-encoded block hash, once assigned
+    pub block_hash: Option<String>,
+    
+    /// Transactions this one depends on
+    pub dependencies: Vec<TransactionId>,
+    
+    /// Transactions depending on this one
+    pub dependents: Vec<TransactionId>,
+    
+    /// Metadata
+    pub metadata: HashMap<String, String>,
+    
+    /// Phase transitions, oldest first
+    pub timeline: Vec<PhaseTransition>,
+    
+    /// Created timestamp
+    pub created_at_ms: u64,
+    
+    /// Updated timestamp
+    pub updated_at_ms: u64,
+}
+
+/// Consensus section of [`TransactionDetails`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusDetails {
+    /// Current phase
+    pub phase: String,
+    
+    /// Required votes
+    pub required_votes: u32,
+    
+    /// Vote counts
+    pub votes: VoteSummary,
+    
+    /// Individual votes, only when requested
+    pub vote_details: Option<HashMap<NodeId, bool>>,
+    
+    /// Consensus result
+    pub result: Option<bool>,
+    
+    /// Whether a consensus proof is stored
+    pub has_proof: bool,
+}
+
+/// Vote counts with the voters that rejected
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoteSummary {
+    /// Approving votes
+    pub approvals: u32,
+    
+    /// Rejecting votes
+    pub rejections: u32,
+    
+    /// Voters that rejected, sorted
+    pub rejected_by: Vec<NodeId>,
+}
+
+/// Settlement section of [`TransactionDetails`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementDetails {
+    /// Settlement ID
+    pub settlement_id: Option<String>,
+    
+    /// Settlement type
+    pub settlement_type: String,
+    
+    /// Per-domain settlement state, sorted by domain
+    pub domains: Vec<DomainSettlementDetails>,
+    
+    /// Whether a settlement proof is stored
+    pub has_proof: bool,
+}
+
+/// Settlement state of one domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainSettlementDetails {
+    /// Domain ID
+    pub domain_id: DomainId,
+    
+    /// Settlement status
+    pub status: String,
+    
+    /// Whether the domain supplied a settlement proof
+    pub has_proof: bool,
+    
+    /// Last update
+    pub updated_at_ms: u64,
+}
+
+/// A phase a transaction entered and when
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTransition {
+    /// Phase name, e.g. `consensus_started` or `settled:<domain>`
+    pub phase: String,
+    
+    /// Time the phase was entered
+    pub at_ms: u64,
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+impl VoteSummary {
+    /// Summarize a vote map
+    pub fn from_votes(votes: &HashMap<NodeId, bool>) -> Self {
+        let mut summary = Self::default();
+        for (voter, approve) in votes {
+            if *approve {
+                summary.approvals += 1;
+            } else {
+                summary.rejections += 1;
+                summary.rejected_by.push(voter.clone());
+            }
+        }
+        summary.rejected_by.sort();
+        summary
+    }
+}
+
+impl StoredTransaction {
+    /// Build the detail view of this transaction, including individual
+    /// consensus votes only when `include_votes` is set
+    pub fn details(&self, include_votes: bool) -> TransactionDetails {
+        let consensus = &self.consensus_state;
+        let settlement = &self.settlement_state;
+        
+        let mut domains: Vec<DomainSettlementDetails> = settlement.domain_settlements.values()
+            .map(|domain| DomainSettlementDetails {
+                domain_id: domain.domain_id.clone(),
+                status: domain.status.clone(),
+                has_proof: domain.proof.is_some(),
+                updated_at_ms: unix_ms(domain.timestamp),
+            })
+            .collect();
+        domains.sort_by(|a, b| a.domain_id.cmp(&b.domain_id));
+        
+        let mut timeline = vec![
+            PhaseTransition { phase: "created".to_string(), at_ms: unix_ms(self.created_at) },
+            PhaseTransition { phase: "consensus_started".to_string(), at_ms: unix_ms(consensus.started_at) },
+        ];
+        let optional_phases = [
+            ("consensus_completed", consensus.completed_at),
+            ("settlement_started", settlement.started_at),
+            ("settlement_completed", settlement.completed_at),
+        ];
+        timeline.extend(optional_phases.iter().filter_map(|(phase, at)| {
+            at.map(|at| PhaseTransition { phase: phase.to_string(), at_ms: unix_ms(at) })
+        }));
+        timeline.extend(domains.iter().map(|domain| PhaseTransition {
+            phase: format!("settled:{}", domain.domain_id),
+            at_ms: domain.updated_at_ms,
+        }));
+        timeline.sort_by_key(|transition| transition.at_ms);
+        
+        TransactionDetails {
+            transaction_id: self.transaction_id.clone(),
+            transaction_type: self.transaction_type.clone(),
+            source_domain: self.source_domain.clone(),
+            target_domains: self.target_domains.clone(),
+            status: self.status.clone(),
+            consensus: ConsensusDetails {
+                phase: consensus.phase.clone(),
+                required_votes: consensus.required_votes,
+                votes: VoteSummary::from_votes(&consensus.votes),
+                vote_details: include_votes.then(|| consensus.votes.clone()),
+                result: consensus.result,
+                has_proof: consensus.proof.is_some(),
+            },
+            settlement: SettlementDetails {
+                settlement_id: settlement.settlement_id.clone(),
+                settlement_type: settlement.settlement_type.clone(),
+                domains,
+                has_proof: settlement.proof.is_some(),
+            },
+            block_height: self.block_height,
+            block_hash: self.block_hash.as_ref().map(hex::encode),
+            dependencies: self.dependencies.clone(),
+            dependents: self.dependents.clone(),
+            metadata: self.metadata.clone(),
+            timeline,
+            created_at_ms: unix_ms(self.created_at),
+            updated_at_ms: unix_ms(self.updated_at),
+        }
+    }
+}
+
 /// Transaction pool
 #[derive(Debug, Clone)]
 pub struct TransactionPool {
     /// Pool transactions
     pub transactions: HashMap<TransactionId, PoolTransaction>,
     
-    /// Priority queue
+    /// Transaction IDs by base priority, in insertion order
     pub priority_queue: BTreeMap<u64, Vec<TransactionId>>,
     
     /// Pool size
@@ -261,6 +533,9 @@ pub struct TransactionPool {
     /// Max pool size
     pub max_size: usize,
     
+    /// Waiting this long raises a transaction's priority by one
+    pub age_boost_interval: Duration,
+    
     /// Pool statistics
     pub stats: PoolStats,
 }
@@ -282,10 +557,13 @@ pub struct PoolTransaction {
     
     /// Dependencies
     pub dependencies: Vec<TransactionId>,
+    
+    /// Transaction size in bytes
+    pub size: usize,
 }
 
 /// Pool statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PoolStats {
     /// Total transactions added
     pub total_added: u64,
@@ -303,6 +581,152 @@ pub struct PoolStats {
     pub utilization: f64,
 }
 
+/// Metadata key holding a transaction's explicit pool priority; higher is
+/// proposed first
+pub const POOL_PRIORITY_METADATA_KEY: &str = "priority";
+
+impl PoolTransaction {
+    /// Pool entry prioritized by the `priority` metadata of the transaction,
+    /// 0 if absent or not a number
+    pub fn new(
+        transaction_id: TransactionId,
+        metadata: &HashMap<String, String>,
+        dependencies: Vec<TransactionId>,
+        size: usize,
+        added_at: Instant,
+    ) -> Self {
+        let priority = metadata.get(POOL_PRIORITY_METADATA_KEY)
+            .and_then(|priority| priority.parse().ok())
+            .unwrap_or(0);
+        Self { transaction_id, priority, added_at, retry_count: 0, dependencies, size }
+    }
+}
+
+impl TransactionPool {
+    pub fn new(max_size: usize, age_boost_interval: Duration) -> Self {
+        Self {
+            transactions: HashMap::new(),
+            priority_queue: BTreeMap::new(),
+            size: 0,
+            max_size,
+            age_boost_interval,
+            stats: PoolStats::default(),
+        }
+    }
+    
+    /// Priority including one level for every `age_boost_interval` waited
+    pub fn effective_priority(&self, entry: &PoolTransaction, now: Instant) -> u64 {
+        let waited = now.saturating_duration_since(entry.added_at);
+        let boost = waited.as_millis().checked_div(self.age_boost_interval.as_millis()).unwrap_or(0);
+        entry.priority.saturating_add(boost.min(u64::MAX as u128) as u64)
+    }
+    
+    /// Lowest effective priority entry; of equals the most recently added
+    fn lowest(&self, now: Instant) -> Option<&PoolTransaction> {
+        self.transactions.values()
+            .min_by_key(|entry| (self.effective_priority(entry, now), std::cmp::Reverse(entry.added_at)))
+    }
+    
+    /// Admit a transaction. A full pool evicts its lowest priority
+    /// transaction, which is returned, unless the new one ranks no higher.
+    pub fn insert(&mut self, entry: PoolTransaction, now: Instant) -> GarpResult<Option<PoolTransaction>> {
+        if self.transactions.contains_key(&entry.transaction_id) {
+            return Ok(None);
+        }
+        let mut evicted = None;
+        if self.size >= self.max_size {
+            let Some(lowest) = self.lowest(now) else {
+                return Err(GarpError::ValidationError("Transaction pool has no capacity".to_string()));
+            };
+            let lowest_priority = self.effective_priority(lowest, now);
+            if self.effective_priority(&entry, now) <= lowest_priority {
+                return Err(GarpError::ValidationError(format!(
+                    "Transaction pool is full and {} does not outrank priority {}",
+                    entry.transaction_id, lowest_priority
+                )));
+            }
+            let lowest_id = lowest.transaction_id.clone();
+            evicted = self.remove(&lowest_id, now);
+        }
+        
+        self.priority_queue.entry(entry.priority).or_default().push(entry.transaction_id.clone());
+        self.transactions.insert(entry.transaction_id.clone(), entry);
+        self.size += 1;
+        self.stats.total_added += 1;
+        self.record_size();
+        Ok(evicted)
+    }
+    
+    /// Remove a transaction, recording how long it waited
+    pub fn remove(&mut self, transaction_id: &TransactionId, now: Instant) -> Option<PoolTransaction> {
+        let entry = self.transactions.remove(transaction_id)?;
+        if let Some(ids) = self.priority_queue.get_mut(&entry.priority) {
+            ids.retain(|id| id != transaction_id);
+            if ids.is_empty() {
+                self.priority_queue.remove(&entry.priority);
+            }
+        }
+        self.size -= 1;
+        
+        let stats = &mut self.stats;
+        stats.total_removed += 1;
+        let waited = now.saturating_duration_since(entry.added_at);
+        let removed = stats.total_removed as u32;
+        stats.avg_wait_time = (stats.avg_wait_time * (removed - 1) + waited) / removed;
+        self.record_size();
+        Some(entry)
+    }
+    
+    /// Take up to `max_count` transactions totalling at most `max_bytes`
+    /// for a block, highest effective priority first. A transaction is only
+    /// taken after any of its dependencies still in the pool.
+    pub fn select_for_block(&mut self, max_count: usize, max_bytes: usize, now: Instant) -> Vec<PoolTransaction> {
+        // Base priority order, oldest first among equals, then by effective priority
+        let mut candidates: Vec<&PoolTransaction> = self.priority_queue.values()
+            .rev()
+            .flatten()
+            .filter_map(|id| self.transactions.get(id))
+            .collect();
+        candidates.sort_by_key(|entry| (std::cmp::Reverse(self.effective_priority(entry, now)), entry.added_at));
+        let mut candidates: Vec<TransactionId> = candidates.into_iter().map(|entry| entry.transaction_id.clone()).collect();
+        
+        let mut selected = Vec::new();
+        let mut selected_ids = HashSet::new();
+        let mut bytes = 0usize;
+        while selected.len() < max_count {
+            let next = candidates.iter().position(|id| {
+                let entry = &self.transactions[id];
+                bytes + entry.size <= max_bytes
+                    && entry.dependencies.iter().all(|dep| selected_ids.contains(dep) || !self.transactions.contains_key(dep))
+            });
+            let Some(position) = next else {
+                break;
+            };
+            let id = candidates.remove(position);
+            bytes += self.transactions[&id].size;
+            selected_ids.insert(id.clone());
+            selected.push(id);
+        }
+        
+        selected.iter().filter_map(|id| self.remove(id, now)).collect()
+    }
+    
+    /// Change the capacity and aging rate. A pool above its new capacity
+    /// evicts as transactions are admitted.
+    pub fn set_limits(&mut self, max_size: usize, age_boost_interval: Duration) {
+        self.max_size = max_size;
+        self.age_boost_interval = age_boost_interval;
+        self.stats.utilization = if max_size == 0 { 1.0 } else { self.size as f64 / max_size as f64 };
+    }
+    
+    fn record_size(&mut self) {
+        let stats = &mut self.stats;
+        let samples = (stats.total_added + stats.total_removed) as f64;
+        stats.avg_pool_size += (self.size as f64 - stats.avg_pool_size) / samples;
+        stats.utilization = if self.max_size == 0 { 1.0 } else { self.size as f64 / self.max_size as f64 };
+    }
+}
+
 /// Transaction storage metrics
 #[derive(Debug, Clone)]
 pub struct TransactionStorageMetrics {
@@ -339,6 +763,9 @@ pub struct BlockStorage {
     /// Pending blocks
     pending_blocks: Arc<RwLock<HashMap<BlockHash, PendingBlock>>>,
     
+    /// Blocks that are not final yet, including competing forks
+    fork_cache: Arc<RwLock<HashMap<BlockHash, BlockInfo>>>,
+    
     /// Storage backend
     backend: Arc<dyn StorageBackend>,
     
@@ -358,6 +785,9 @@ pub struct BlockChain {
     /// Current height
     pub height: u64,
     
+    /// Height of the highest final block, if any
+    pub finalized_height: Option<u64>,
+    
     /// Total difficulty
     pub total_difficulty: u64,
     
@@ -382,6 +812,63 @@ pub struct ChainStats {
     
     /// Chain size
     pub chain_size: u64,
+    
+    /// Blocks stored at a height that already had a block
+    pub forks_detected: u64,
+}
+
+/// Summary of the canonical chain, see [`GlobalStorage::get_chain_info`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainInfo {
+    /// Hex-encoded first block stored
+    pub genesis_block: String,
+    
+    /// Hex-encoded canonical head
+    pub head: String,
+    
+    /// Height of the canonical head
+    pub height: u64,
+    
+    /// Height of the highest final block, if any
+    pub finalized_height: Option<u64>,
+    
+    /// Blocks stored, including unfinalized forks
+    pub total_blocks: u64,
+    
+    /// Transactions in stored blocks
+    pub total_transactions: u64,
+    
+    /// Average time between canonical blocks in milliseconds
+    pub avg_block_time_ms: u64,
+    
+    /// Average block size in bytes
+    pub avg_block_size: usize,
+    
+    /// Forks detected since startup
+    pub forks_detected: u64,
+    
+    /// Unfinalized heights with competing blocks
+    pub fork_heights: Vec<u64>,
+}
+
+/// Outcome of [`BlockStorage::store_block`]
+#[derive(Debug, Clone, Default)]
+pub struct StoredBlock {
+    /// Blocks orphaned by blocks that became final
+    pub orphaned: Vec<BlockInfo>,
+    
+    /// Block already stored at the same height, if the new block forks from it
+    pub fork_of: Option<BlockHash>,
+}
+
+/// A block dropped from the chain and the transactions it held
+#[derive(Debug, Clone)]
+pub struct OrphanedBlock {
+    /// Orphaned block
+    pub block: BlockInfo,
+    
+    /// Transactions that were assigned to the block, in block order
+    pub transactions: Vec<TransactionId>,
 }
 
 /// Block information
@@ -505,8 +992,8 @@ pub struct StateStorage {
     /// Global state
     global_state: Arc<RwLock<GlobalState>>,
     
-    /// Domain states
-    domain_states: Arc<RwLock<HashMap<DomainId, DomainState>>>,
+    /// Domain states, one entry per retained version
+    domain_states: Arc<RwLock<HashMap<DomainId, BTreeMap<u64, Arc<DomainState>>>>>,
     
     /// State snapshots
     state_snapshots: Arc<RwLock<HashMap<u64, StateSnapshot>>>,
@@ -514,6 +1001,12 @@ pub struct StateStorage {
     /// State transitions
     state_transitions: Arc<RwLock<VecDeque<StateTransition>>>,
     
+    /// Global state roots committed by blocks, by height
+    state_roots: Arc<RwLock<BTreeMap<u64, StateRootRecord>>>,
+    
+    /// Settlement fees credited to each validator
+    fee_earnings: Arc<RwLock<HashMap<NodeId, u64>>>,
+    
     /// Storage backend
     backend: Arc<dyn StorageBackend>,
     
@@ -606,6 +1099,14 @@ pub struct ValidatorInfo {
     
     /// Last activity
     pub last_activity: SystemTime,
+    
+    /// Epoch the validator joined the set in
+    #[serde(default)]
+    pub joining_epoch: Option<u64>,
+    
+    /// Epoch the validator left the set in
+    #[serde(default)]
+    pub leaving_epoch: Option<u64>,
 }
 
 /// Validator status
@@ -667,6 +1168,10 @@ pub struct StateSnapshot {
     /// Snapshot data
     pub snapshot_data: Vec<u8>,
     
+    /// BLAKE3 checksum of `snapshot_data`, verified on restore
+    #[serde(default)]
+    pub checksum: Vec<u8>,
+    
     /// Created timestamp
     pub created_at: SystemTime,
     
@@ -674,6 +1179,77 @@ pub struct StateSnapshot {
     pub metadata: HashMap<String, String>,
 }
 
+impl StateSnapshot {
+    /// Checksum of snapshot data
+    pub fn checksum_of(snapshot_data: &[u8]) -> Vec<u8> {
+        blake3::hash(snapshot_data).as_bytes().to_vec()
+    }
+}
+
+/// State captured by a [`StateSnapshot`]; `snapshot_data` is its canonical
+/// JSON encoding and `state_root` the global state root of its domain states
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotContents {
+    /// Global state
+    pub global_state: GlobalState,
+    
+    /// Latest version of each domain's state, ordered by domain
+    pub domain_states: Vec<DomainState>,
+}
+
+impl SnapshotContents {
+    /// Canonical encoding, see [`garp_common::canonical`]
+    pub fn encode(&self) -> GarpResult<Vec<u8>> {
+        canonical::to_canonical_json(self).map_err(|e| GarpError::InternalError(format!("Failed to encode snapshot: {}", e)))
+    }
+    
+    /// State hash of each domain, recomputed from its state data
+    pub fn domain_hashes(&self) -> BTreeMap<DomainId, Vec<u8>> {
+        self.domain_states.iter()
+            .map(|state| (state.domain_id.clone(), state_root::domain_state_hash(&state.state_data)))
+            .collect()
+    }
+    
+    /// State root committed to by block headers and finality certificates
+    pub fn state_root(&self) -> Vec<u8> {
+        state_root::global_state_root(&self.domain_hashes())
+    }
+}
+
+/// Value and version of a key set by state synchronization. A domain's
+/// synchronized state is stored as the JSON map of its keys in
+/// `DomainState::state_data`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateEntry {
+    /// Value
+    pub value: Vec<u8>,
+    
+    /// Version the source domain assigned
+    pub version: u64,
+}
+
+/// Result of applying a state synchronization
+#[derive(Debug, Clone)]
+pub enum StateSyncOutcome {
+    /// Applied to every target domain, one transition each
+    Applied(Vec<StateTransition>),
+    
+    /// A target domain already holds this or a newer version of the key;
+    /// nothing was applied
+    VersionConflict {
+        domain_id: DomainId,
+        stored_version: u64,
+    },
+}
+
+/// Metadata keys identifying what a state synchronization transition changed
+pub const TRANSITION_DOMAIN_METADATA_KEY: &str = "domain_id";
+pub const TRANSITION_STATE_KEY_METADATA_KEY: &str = "state_key";
+pub const TRANSITION_VERSION_METADATA_KEY: &str = "version";
+
+/// State transitions retained for history queries
+const MAX_STATE_TRANSITIONS: usize = 10_000;
+
 /// State transition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateTransition {
@@ -732,8 +1308,8 @@ pub struct ConsensusStorage {
     /// Vote records
     vote_records: Arc<RwLock<HashMap<String, VoteRecord>>>,
     
-    /// View changes
-    view_changes: Arc<RwLock<HashMap<u64, ViewChangeRecord>>>,
+    /// View changes indexed by view number, mirrored to the backend
+    view_changes: Arc<RwLock<BTreeMap<u64, ViewChangeRecord>>>,
     
     /// Finality certificates indexed by block hash (string)
     finality_by_hash: Arc<RwLock<HashMap<String, FinalityCertificate>>>,
@@ -741,6 +1317,15 @@ pub struct ConsensusStorage {
     /// Finality certificates indexed by block height
     finality_by_height: Arc<RwLock<BTreeMap<u64, FinalityCertificate>>>,
     
+    /// Validator-signed ledger checkpoints indexed by height, mirrored to the backend
+    ledger_checkpoints: Arc<RwLock<BTreeMap<u64, LedgerCheckpoint>>>,
+    
+    /// Slashing records indexed by validator
+    slashing_records: Arc<RwLock<HashMap<NodeId, Vec<SlashingRecord>>>>,
+    
+    /// Coordination votes already counted
+    vote_deduplicator: Arc<RwLock<VoteDeduplicator>>,
+    
     /// Storage backend
     backend: Arc<dyn StorageBackend>,
     
@@ -906,6 +1491,70 @@ pub struct ViewChangeRecord {
     
     /// Timestamp
     pub timestamp: SystemTime,
+    
+    /// Leader of the new view, if one was elected
+    #[serde(default)]
+    pub leader: Option<NodeId>,
+    
+    /// How long the previous view lasted
+    #[serde(default)]
+    pub previous_view_duration_ms: u64,
+}
+
+/// Slashing record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingRecord {
+    /// Evidence ID
+    pub evidence_id: String,
+    
+    /// Slashed validator
+    pub validator_id: NodeId,
+    
+    /// Kind of misbehavior proven
+    pub evidence_type: SlashingEvidenceType,
+    
+    /// Stake before slashing
+    pub stake_before: u64,
+    
+    /// Stake after slashing
+    pub stake_after: u64,
+    
+    /// Validators that accepted the evidence
+    pub accepted_by: Vec<NodeId>,
+    
+    /// Timestamp
+    pub timestamp: SystemTime,
+}
+
+/// Coordination votes seen per session, so a vote delivered twice is only
+/// counted once
+#[derive(Debug, Default)]
+pub struct VoteDeduplicator {
+    /// Session ID, voter domain and the voter's sequence number
+    seen: HashSet<(String, DomainId, u64)>,
+}
+
+impl VoteDeduplicator {
+    /// Record a vote, returning false if it was seen before
+    pub fn insert(&mut self, session_id: &str, voter: &DomainId, sequence_number: u64) -> bool {
+        self.seen.insert((session_id.to_string(), voter.clone(), sequence_number))
+    }
+    
+    /// Forget the votes of a finalized session, returning how many were dropped
+    pub fn prune_session(&mut self, session_id: &str) -> usize {
+        let before = self.seen.len();
+        self.seen.retain(|(seen_session, _, _)| seen_session != session_id);
+        before - self.seen.len()
+    }
+    
+    /// Number of votes tracked
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
 }
 
 /// Consensus storage metrics
@@ -941,6 +1590,27 @@ pub struct CrossDomainStorage {
     /// State synchronization
     state_synchronization: Arc<RwLock<HashMap<String, SyncSession>>>,
     
+    /// State channels
+    state_channels: Arc<RwLock<HashMap<ChannelId, StateChannel>>>,
+    
+    /// Governance proposals
+    governance_proposals: Arc<RwLock<HashMap<ProposalId, GovernanceProposal>>>,
+    
+    /// Parameters set by executed governance proposals
+    governance_parameters: Arc<RwLock<HashMap<String, String>>>,
+    
+    /// Atomic swaps
+    atomic_swaps: Arc<RwLock<HashMap<SwapId, AtomicSwap>>>,
+    
+    /// Wrapped tokens and the wrappings changing their supply
+    wrapped_tokens: Arc<RwLock<WrappedTokenRegistry>>,
+    
+    /// Compensation chains by aborted transaction
+    compensation_chains: Arc<RwLock<HashMap<TransactionId, CompensationChain>>>,
+    
+    /// Domain delegations by delegator and delegate
+    domain_delegations: Arc<RwLock<HashMap<(DomainId, DomainId), DomainDelegation>>>,
+    
     /// Storage backend
     backend: Arc<dyn StorageBackend>,
     
@@ -1147,6 +1817,15 @@ pub struct SettlementStorage {
     /// Rollback records
     rollback_records: Arc<RwLock<HashMap<String, RollbackRecord>>>,
     
+    /// Proofs of completed settlements
+    settlement_proofs: Arc<RwLock<HashMap<TransactionId, SettlementProof>>>,
+    
+    /// Zero-knowledge proofs of completed settlements
+    zk_proofs: Arc<RwLock<HashMap<TransactionId, ZkProof>>>,
+    
+    /// Escrows of conditional settlements, by settlement ID
+    escrow_records: Arc<RwLock<HashMap<String, EscrowRecord>>>,
+    
     /// Storage backend
     backend: Arc<dyn StorageBackend>,
     
@@ -1235,6 +1914,14 @@ pub struct SettlementBatch {
     /// Settlement IDs
     pub settlement_ids: Vec<String>,
     
+    /// Domain the batch is submitted to
+    #[serde(default)]
+    pub target_domain: DomainId,
+    
+    /// Per-settlement status within the batch
+    #[serde(default)]
+    pub item_status: HashMap<String, BatchItemStatus>,
+    
     /// Batch status
     pub status: BatchStatus,
     
@@ -1260,10 +1947,26 @@ pub enum BatchStatus {
     /// Completed
     Completed,
     
+    /// Some items failed and were re-queued individually
+    PartiallyFailed,
+    
     /// Failed
     Failed,
 }
 
+/// Status of one settlement within a batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BatchItemStatus {
+    /// Awaiting the batch result
+    Pending,
+    
+    /// Settled as part of the batch
+    Settled,
+    
+    /// Rejected by the target domain
+    Failed(String),
+}
+
 /// Rollback record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RollbackRecord {
@@ -1273,6 +1976,10 @@ pub struct RollbackRecord {
     /// Settlement ID
     pub settlement_id: String,
     
+    /// Transaction being rolled back
+    #[serde(default)]
+    pub transaction_id: Option<TransactionId>,
+    
     /// Rollback reason
     pub reason: String,
     
@@ -1282,6 +1989,10 @@ pub struct RollbackRecord {
     /// Rollback status
     pub status: RollbackStatus,
     
+    /// Compensation progress per domain that had already settled
+    #[serde(default)]
+    pub compensations: HashMap<DomainId, DomainCompensation>,
+    
     /// Created timestamp
     pub created_at: SystemTime,
     
@@ -1292,11 +2003,27 @@ pub struct RollbackRecord {
     pub metadata: HashMap<String, String>,
 }
 
-/// Rollback status
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum RollbackStatus {
-    /// Pending
-    Pending,
+/// Compensation sent to one domain as part of a rollback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainCompensation {
+    /// Delivery attempts so far
+    pub attempts: u32,
+    
+    /// Last delivery attempt
+    pub last_sent_at: Option<SystemTime>,
+    
+    /// Whether the domain confirmed the compensation
+    pub acknowledged: bool,
+    
+    /// Last delivery error
+    pub last_error: Option<String>,
+}
+
+/// Rollback status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RollbackStatus {
+    /// Pending
+    Pending,
     
     /// Processing
     Processing,
@@ -1308,6 +2035,60 @@ pub enum RollbackStatus {
     Failed,
 }
 
+/// Assets held by a conditional settlement until its condition is met
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscrowRecord {
+    /// Settlement ID
+    pub settlement_id: String,
+    
+    /// Transaction ID
+    pub transaction_id: TransactionId,
+    
+    /// Condition ID
+    pub condition_id: String,
+    
+    /// Condition releasing the assets
+    pub condition_type: ConditionType,
+    
+    /// Time after which the assets are refunded
+    pub deadline: SystemTime,
+    
+    /// Domain the assets are locked in, refunded on expiry
+    pub source_domain: DomainId,
+    
+    /// Domains the assets are released to
+    pub target_domains: Vec<DomainId>,
+    
+    /// Transaction data submitted to the target domains on release
+    pub payload: Vec<u8>,
+    
+    /// Signers of a `SignatureThreshold` condition so far
+    #[serde(default)]
+    pub signatures: BTreeSet<String>,
+    
+    /// Escrow status
+    pub status: EscrowStatus,
+    
+    /// Created timestamp
+    pub created_at: SystemTime,
+    
+    /// Released or expired timestamp
+    pub resolved_at: Option<SystemTime>,
+}
+
+/// Escrow status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EscrowStatus {
+    /// Assets locked, condition not met yet
+    Locked,
+    
+    /// Condition met, assets released to the target domains
+    Released,
+    
+    /// Deadline passed, assets refunded to the source domain
+    Expired,
+}
+
 /// Settlement storage metrics
 #[derive(Debug, Clone)]
 pub struct SettlementStorageMetrics {
@@ -1325,6 +2106,28 @@ pub struct SettlementStorageMetrics {
     
     /// Average settlement time
     pub avg_settlement_time: Arc<RwLock<f64>>,
+    
+    /// Settlement batches created
+    pub batches_created: Arc<RwLock<u64>>,
+    
+    /// Average settlements per batch
+    pub avg_batch_size: Arc<RwLock<f64>>,
+    
+    /// Batches where only some items settled
+    pub partial_batch_failures: Arc<RwLock<u64>>,
+}
+
+/// Snapshot of settlement batch counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettlementBatchMetrics {
+    /// Settlement batches created
+    pub batches_created: u64,
+    
+    /// Average settlements per batch
+    pub avg_batch_size: f64,
+    
+    /// Batches where only some items settled
+    pub partial_batch_failures: u64,
 }
 
 /// Metadata storage
@@ -1341,6 +2144,18 @@ pub struct MetadataStorage {
     /// System metadata
     system_metadata: Arc<RwLock<HashMap<String, SystemMetadata>>>,
     
+    /// Runtime domain registrations
+    domain_registrations: Arc<RwLock<HashMap<DomainId, DomainRegistration>>>,
+    
+    /// Append-only log of emergency action decisions
+    emergency_audit: Arc<RwLock<Vec<EmergencyAuditRecord>>>,
+    
+    /// Append-only log of configuration reloads
+    config_reloads: Arc<RwLock<Vec<ConfigReloadRecord>>>,
+    
+    /// Reputation scores by domain or validator
+    reputation_scores: Arc<RwLock<HashMap<String, ReputationScore>>>,
+    
     /// Storage backend
     backend: Arc<dyn StorageBackend>,
     
@@ -1471,6 +2286,62 @@ pub struct CacheLayer {
     
     /// Layer statistics
     pub stats: LayerStats,
+    
+    /// Deadlines of entries with a TTL
+    expiry: ExpiryIndex,
+}
+
+/// Expiry deadlines of keys, ordered so expired keys are found without
+/// scanning every key
+#[derive(Debug, Clone, Default)]
+struct ExpiryIndex {
+    /// Keys by deadline
+    by_deadline: BTreeMap<Instant, Vec<String>>,
+    
+    /// Deadline of each key
+    deadlines: HashMap<String, Instant>,
+}
+
+impl ExpiryIndex {
+    /// Expire `key` after `ttl`, replacing any earlier deadline. A TTL too
+    /// large to represent never expires.
+    fn schedule(&mut self, key: &str, ttl: Duration) {
+        self.clear(key);
+        if let Some(deadline) = Instant::now().checked_add(ttl) {
+            self.by_deadline.entry(deadline).or_default().push(key.to_string());
+            self.deadlines.insert(key.to_string(), deadline);
+        }
+    }
+    
+    /// Stop expiring `key`
+    fn clear(&mut self, key: &str) {
+        let Some(deadline) = self.deadlines.remove(key) else { return };
+        if let Some(keys) = self.by_deadline.get_mut(&deadline) {
+            keys.retain(|k| k != key);
+            if keys.is_empty() {
+                self.by_deadline.remove(&deadline);
+            }
+        }
+    }
+    
+    fn is_expired(&self, key: &str, now: Instant) -> bool {
+        self.deadlines.get(key).is_some_and(|deadline| *deadline <= now)
+    }
+    
+    /// Remove and return every key whose deadline has passed
+    fn take_expired(&mut self, now: Instant) -> Vec<String> {
+        let mut expired = Vec::new();
+        while let Some(entry) = self.by_deadline.first_entry() {
+            if *entry.key() > now {
+                break;
+            }
+            for key in entry.remove() {
+                self.deadlines.remove(&key);
+                expired.push(key);
+            }
+        }
+        expired
+    }
 }
 
 /// Cache entry
@@ -1894,6 +2765,15 @@ pub struct ReplicationManager {
     /// Replication queue
     replication_queue: Arc<RwLock<VecDeque<ReplicationTask>>>,
     
+    /// Lag alerts raised by health checks, oldest first
+    alerts: Arc<RwLock<VecDeque<ReplicationAlert>>>,
+    
+    /// Primary replicas currently lagging past the critical threshold
+    lagging_primaries: Arc<RwLock<HashSet<String>>>,
+    
+    /// Storage event channel critical lag is reported on
+    event_tx: mpsc::UnboundedSender<StorageEvent>,
+    
     /// Metrics
     metrics: Arc<ReplicationMetrics>,
 }
@@ -2078,6 +2958,35 @@ pub struct ReplicationMetrics {
     pub replication_lag: Arc<RwLock<f64>>,
 }
 
+/// How far past its threshold a lagging target is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplicationAlertSeverity {
+    /// Lag above `max_acceptable_lag_ms`
+    Warning,
+    
+    /// Lag above `critical_lag_threshold_ms`
+    Critical,
+}
+
+/// A replication target found lagging by a health check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicationAlert {
+    /// Target ID
+    pub target_id: String,
+    
+    /// Lag when the check ran
+    pub lag: Duration,
+    
+    /// Threshold the lag exceeded
+    pub threshold: Duration,
+    
+    /// Severity
+    pub severity: ReplicationAlertSeverity,
+    
+    /// When the check ran
+    pub raised_at: SystemTime,
+}
+
 /// Storage backend trait
 #[async_trait::async_trait]
 pub trait StorageBackend: Send + Sync {
@@ -2107,6 +3016,13 @@ pub trait StorageBackend: Send + Sync {
     
     /// Get storage statistics
     async fn get_stats(&self) -> GarpResult<StorageStats>;
+    
+    /// Set data by key, expiring it after `ttl`. Backends without native
+    /// expiry store the key like `set` and keep it until it is deleted.
+    async fn set_with_ttl(&self, key: &str, value: Vec<u8>, ttl: Duration) -> GarpResult<()> {
+        let _ = ttl;
+        self.set(key, value).await
+    }
 }
 
 /// Batch operation
@@ -2150,6 +3066,12 @@ pub enum StorageEvent {
     /// Block stored
     BlockStored(BlockHash),
     
+    /// Block orphaned by a competing final block
+    BlockOrphaned(BlockHash),
+    
+    /// Second block stored at a height, both branches are kept until one is final
+    ForkDetected { height: u64, existing: BlockHash, competing: BlockHash },
+    
     /// State updated
     StateUpdated(DomainId),
     
@@ -2192,6 +3114,13 @@ pub struct StorageMetrics {
     
     /// Storage errors
     pub storage_errors: Arc<RwLock<u64>>,
+    
+    /// Index inconsistencies detected, such as a block whose transaction
+    /// count disagrees with the transactions assigned to it
+    pub consistency_warnings: Arc<RwLock<u64>>,
+    
+    /// Transaction pool statistics as of the last pool change
+    pub transaction_pool: Arc<RwLock<PoolStats>>,
 }
 
 impl GlobalStorage {
@@ -2205,6 +3134,12 @@ impl GlobalStorage {
         let backend: Arc<dyn StorageBackend> = if config.database.url.starts_with("postgres://") || config.database.url.starts_with("postgresql://") {
             info!("Using PostgresStorageBackend for persistence");
             Arc::new(PostgresStorageBackend::new(config.clone()).await?)
+        } else if config.database.url.starts_with("sqlite://") {
+            info!("Using SqliteStorageBackend for persistence");
+            Arc::new(SqliteStorageBackend::new(config.clone()).await?)
+        } else if config.database.url.starts_with("lsm://") {
+            info!("Using in-memory LsmStorageBackend");
+            Arc::new(LsmStorageBackend::new(config.clone()))
         } else {
             warn!("Unknown database URL '{}', falling back to in-memory storage", config.database.url);
             Arc::new(MemoryStorageBackend::new())
@@ -2220,7 +3155,7 @@ impl GlobalStorage {
         
         let cache_manager = Arc::new(CacheManager::new(config.clone()).await?);
         let backup_manager = Arc::new(BackupManager::new(config.clone()).await?);
-        let replication_manager = Arc::new(ReplicationManager::new(config.clone()).await?);
+        let replication_manager = Arc::new(ReplicationManager::new(config.clone(), event_tx.clone()).await?);
         
         let metrics = Arc::new(StorageMetrics {
             total_operations: Arc::new(RwLock::new(0)),
@@ -2229,6 +3164,8 @@ impl GlobalStorage {
             write_ops_per_sec: Arc::new(RwLock::new(0.0)),
             avg_operation_time: Arc::new(RwLock::new(0.0)),
             storage_errors: Arc::new(RwLock::new(0)),
+            consistency_warnings: Arc::new(RwLock::new(0)),
+            transaction_pool: Arc::new(RwLock::new(PoolStats::default())),
         });
         
         Ok(Self {
@@ -2247,6 +3184,8 @@ impl GlobalStorage {
             metrics,
             event_tx,
             event_rx,
+            block_tx: broadcast::channel(FINALIZED_BLOCK_CAPACITY).0,
+            orphan_tx: broadcast::channel(ORPHANED_BLOCK_CAPACITY).0,
             shutdown_tx: None,
         })
     }
@@ -2259,11 +3198,13 @@ impl GlobalStorage {
         self.cache_manager.start().await?;
         self.backup_manager.start().await?;
         self.replication_manager.start().await?;
+        self.transaction_storage.rebuild_existence_filter().await;
         
         // Start background tasks
         let event_processor = self.start_event_processor().await?;
         let metrics_collector = self.start_metrics_collector().await?;
         let maintenance_task = self.start_maintenance_task().await?;
+        let replication_monitor = self.start_replication_monitor().await?;
         
         info!("Global Storage started successfully");
         Ok(())
@@ -2292,9 +3233,99 @@ impl GlobalStorage {
         self.transaction_storage.get_transaction(transaction_id).await
     }
     
-    /// Store block
+    /// Every persisted transaction, read from the backend one at a time
+    pub async fn stream_transactions(&self) -> GarpResult<BoxStream<'static, GarpResult<StoredTransaction>>> {
+        self.transaction_storage.stream_transactions().await
+    }
+    
+    /// Admit a transaction to the transaction pool. A full pool evicts its
+    /// lowest priority transaction, returned here, or rejects the new one
+    /// if it ranks no higher.
+    pub async fn add_to_pool(&self, entry: PoolTransaction) -> GarpResult<Option<PoolTransaction>> {
+        let evicted = self.transaction_storage.add_to_pool(entry).await;
+        self.refresh_pool_metrics().await;
+        evicted
+    }
+    
+    /// Take the highest priority pooled transactions for a block, never
+    /// ahead of their pooled dependencies
+    pub async fn select_for_block(&self, max_count: usize, max_bytes: usize) -> Vec<PoolTransaction> {
+        let selected = self.transaction_storage.select_for_block(max_count, max_bytes).await;
+        self.refresh_pool_metrics().await;
+        selected
+    }
+    
+    /// Drop a transaction from the transaction pool
+    pub async fn remove_from_pool(&self, transaction_id: &TransactionId) -> Option<PoolTransaction> {
+        let removed = self.transaction_storage.remove_from_pool(transaction_id).await;
+        self.refresh_pool_metrics().await;
+        removed
+    }
+    
+    /// Change the transaction pool capacity and aging rate
+    pub async fn set_pool_limits(&self, max_size: usize, age_boost_interval: Duration) {
+        self.transaction_storage.set_pool_limits(max_size, age_boost_interval).await;
+        self.refresh_pool_metrics().await;
+    }
+    
+    /// Number of pooled transactions and the pool statistics
+    pub async fn pool_stats(&self) -> (usize, PoolStats) {
+        self.transaction_storage.pool_stats().await
+    }
+    
+    async fn refresh_pool_metrics(&self) {
+        let (_, stats) = self.transaction_storage.pool_stats().await;
+        *self.metrics.transaction_pool.write().await = stats;
+    }
+    
+    /// Store a block and notify block subscribers. Blocks this finalizes a
+    /// competitor of are orphaned, see [`Self::subscribe_orphaned_blocks`].
     pub async fn store_block(&self, block_hash: BlockHash, block_info: BlockInfo) -> GarpResult<()> {
-        self.block_storage.store_block(block_hash, block_info).await
+        let height = block_info.height;
+        let StoredBlock { mut orphaned, fork_of } = self.block_storage.store_block(block_hash.clone(), block_info.clone()).await?;
+        if let Some(existing) = fork_of {
+            warn!(
+                "Fork detected at height {}: block {} competes with {}",
+                height, hex::encode(&block_hash), hex::encode(&existing)
+            );
+            let _ = self.event_tx.send(StorageEvent::ForkDetected { height, existing, competing: block_hash.clone() });
+        }
+        // No receivers is not an error
+        let _ = self.block_tx.send(block_info);
+        
+        // The finality certificate may have arrived before its block
+        let certified = self.get_finality_certificate_by_height(height).await?
+            .map_or(false, |cert| cert.block_hash == hex::encode(&block_hash));
+        if certified {
+            orphaned.extend(self.block_storage.finalize_block(&block_hash).await);
+        }
+        self.release_orphaned_blocks(orphaned).await
+    }
+    
+    /// Blocks stored from now on, in storage order
+    pub fn subscribe_blocks(&self) -> broadcast::Receiver<BlockInfo> {
+        self.block_tx.subscribe()
+    }
+    
+    /// Blocks orphaned from now on. Their transactions are no longer
+    /// assigned to a block and should be proposed again.
+    pub fn subscribe_orphaned_blocks(&self) -> broadcast::Receiver<OrphanedBlock> {
+        self.orphan_tx.subscribe()
+    }
+    
+    /// Evict the transactions of orphaned blocks from their blocks and
+    /// announce the blocks
+    async fn release_orphaned_blocks(&self, blocks: Vec<BlockInfo>) -> GarpResult<()> {
+        for block in blocks {
+            let transactions = self.transaction_storage.unassign_block(block.height, &block.block_hash).await?;
+            warn!(
+                "Block {} at height {} was orphaned, releasing {} transactions",
+                hex::encode(&block.block_hash), block.height, transactions.len()
+            );
+            let _ = self.event_tx.send(StorageEvent::BlockOrphaned(block.block_hash.clone()));
+            let _ = self.orphan_tx.send(OrphanedBlock { block, transactions });
+        }
+        Ok(())
     }
     
     /// Get block
@@ -2302,6 +3333,16 @@ impl GlobalStorage {
         self.block_storage.get_block(block_hash).await
     }
     
+    /// Get chain head and statistics
+    pub async fn get_blockchain(&self) -> BlockChain {
+        self.block_storage.get_blockchain().await
+    }
+    
+    /// Canonical head, finality and fork summary of the chain
+    pub async fn get_chain_info(&self) -> ChainInfo {
+        self.block_storage.get_chain_info().await
+    }
+    
     /// Get block by height
     pub async fn get_block_by_height(&self, height: u64) -> GarpResult<Option<BlockInfo>> {
         let height_index = self.block_storage.height_index.read().await;
@@ -2312,6 +3353,21 @@ impl GlobalStorage {
         Ok(None)
     }
     
+    /// Up to `limit` blocks from `from_height` upwards, in height order
+    pub async fn get_blocks_from(&self, from_height: u64, limit: usize) -> GarpResult<Vec<BlockInfo>> {
+        let hashes: Vec<BlockHash> = {
+            let height_index = self.block_storage.height_index.read().await;
+            height_index.range(from_height..).take(limit).map(|(_, hash)| hash.clone()).collect()
+        };
+        let mut blocks = Vec::with_capacity(hashes.len());
+        for hash in &hashes {
+            if let Some(block) = self.get_block(hash).await? {
+                blocks.push(block);
+            }
+        }
+        Ok(blocks)
+    }
+    
     /// Get latest block info
     pub async fn get_latest_block(&self) -> GarpResult<Option<BlockInfo>> {
         let height_index = self.block_storage.height_index.read().await;
@@ -2328,213 +3384,859 @@ impl GlobalStorage {
     }
     
     /// Update state
-    pub async fn update_state(&self, domain_id: &DomainId, state: DomainState) -> GarpResult<()> {
+    pub async fn update_state(&self, domain_id: &DomainId, state: DomainState) -> GarpResult<u64> {
         self.state_storage.update_domain_state(domain_id, state).await
     }
     
     /// Get state
-    pub async fn get_state(&self, domain_id: &DomainId) -> GarpResult<Option<DomainState>> {
-        self.state_storage.get_domain_state(domain_id).await
+    pub async fn get_state(&self, domain_id: &DomainId, as_of_version: Option<u64>) -> GarpResult<Option<DomainState>> {
+        self.state_storage.get_domain_state(domain_id, as_of_version).await
     }
-
-    /// Assign transactions to a finalized block
-    pub async fn assign_block_transactions(
+    
+    /// Apply a state synchronization to its target domains
+    pub async fn apply_state_sync(
         &self,
-        height: u64,
-        block_hash: BlockHash,
-        tx_ids: &[TransactionId],
-    ) -> GarpResult<()> {
-        self.transaction_storage.assign_block(height, block_hash, tx_ids).await
+        domain_ids: &[DomainId],
+        key: &str,
+        value: &[u8],
+        version: u64,
+        transaction_id: &TransactionId,
+        block_height: u64,
+    ) -> GarpResult<StateSyncOutcome> {
+        self.state_storage.apply_state_sync(domain_ids, key, value, version, transaction_id, block_height).await
     }
     
-    /// Get metrics
-    pub async fn get_metrics(&self) -> StorageMetrics {
-        self.metrics.clone()
+    /// History of a synchronized key on a domain, newest first
+    pub async fn get_state_history(&self, domain_id: &DomainId, key: &str, limit: usize) -> GarpResult<Vec<StateTransition>> {
+        self.state_storage.get_state_history(domain_id, key, limit).await
     }
     
-    /// Start event processor
-    async fn start_event_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let event_rx = self.event_rx.clone();
-        let metrics = self.metrics.clone();
-        
-        let handle = tokio::spawn(async move {
-            let mut event_rx = event_rx.lock().await;
-            
-            while let Some(event) = event_rx.recv().await {
-                match event {
-                    StorageEvent::TransactionStored(_) => {
-                        // Handle transaction stored event
-                    }
-                    StorageEvent::BlockStored(_) => {
-                        // Handle block stored event
-                    }
-                    StorageEvent::StateUpdated(_) => {
-                        // Handle state updated event
-                    }
-                    StorageEvent::StorageError(error) => {
-                        error!("Storage error: {}", error);
-                        let mut errors = metrics.storage_errors.write().await;
-                        *errors += 1;
-                    }
-                    StorageEvent::Shutdown => {
-                        info!("Storage event processor shutting down");
-                        break;
-                    }
-                    _ => {
-                        // Handle other events
-                    }
-                }
-            }
-        });
-        
-        Ok(handle)
+    /// Global state root of the current state
+    pub async fn get_state_root(&self) -> GarpResult<Vec<u8>> {
+        self.state_storage.state_root().await
     }
     
-    /// Start metrics collector
-    async fn start_metrics_collector(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let metrics = self.metrics.clone();
-        
-        let handle = tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(5));
-            
-            loop {
-                interval.tick().await;
-                
-                // TODO: Collect and update metrics
-            }
-        });
-        
-        Ok(handle)
+    /// Record the current global state root as the one committed at `height`
+    pub async fn commit_state_root(&self, height: u64) -> GarpResult<StateRootRecord> {
+        self.state_storage.commit_state_root(height).await
     }
     
-    /// Start maintenance task
-    async fn start_maintenance_task(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
-        let cache_manager = self.cache_manager.clone();
-        
-        let handle = tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(60));
-            
-            loop {
-                interval.tick().await;
-                
-                // Perform cache cleanup
-                if let Err(e) = cache_manager.cleanup().await {
-                    error!("Cache cleanup failed: {}", e);
-                }
-                
-                // TODO: Perform other maintenance tasks
-            }
-        });
-        
-        Ok(handle)
+    /// Global state root committed at `height`
+    pub async fn get_state_root_at(&self, height: u64) -> Option<StateRootRecord> {
+        self.state_storage.get_state_root_record(height).await
     }
-
-    /// Store a finality certificate via consensus storage
-    pub async fn store_finality_certificate(&self, cert: FinalityCertificate) -> GarpResult<()> {
-        self.consensus_storage.store_finality_certificate(cert).await
+    
+    /// Proof that a domain's state hash is included in the root committed at `height`
+    pub async fn get_state_root_proof(&self, height: u64, domain_id: &DomainId) -> Option<StateRootProof> {
+        self.state_storage.state_root_proof(height, domain_id).await
     }
-
-    /// Get a finality certificate by block hash string
-    pub async fn get_finality_certificate_by_hash(&self, hash: &str) -> GarpResult<Option<FinalityCertificate>> {
-        self.consensus_storage.get_finality_certificate_by_hash(hash).await
+    
+    /// Snapshot the current state as of the block at `block_height`
+    pub async fn create_state_snapshot(&self, block_height: u64, block_hash: BlockHash) -> GarpResult<StateSnapshot> {
+        self.state_storage.create_snapshot(block_height, block_hash).await
     }
-
-    /// Get a finality certificate by block height
-    pub async fn get_finality_certificate_by_height(&self, height: u64) -> GarpResult<Option<FinalityCertificate>> {
-        self.consensus_storage.get_finality_certificate_by_height(height).await
+    
+    /// Most recent state snapshot
+    pub async fn get_latest_state_snapshot(&self) -> Option<StateSnapshot> {
+        self.state_storage.latest_snapshot().await
     }
-}
-
-// Implementation stubs for storage components
-impl TransactionStorage {
-    pub async fn new(config: Arc<GlobalSyncConfig>, backend: Arc<dyn StorageBackend>) -> GarpResult<Self> {
-        let metrics = Arc::new(TransactionStorageMetrics {
-            total_transactions: Arc::new(RwLock::new(0)),
-            active_transactions: Arc::new(RwLock::new(0)),
-            storage_ops_per_sec: Arc::new(RwLock::new(0.0)),
-            avg_storage_time: Arc::new(RwLock::new(0.0)),
-            storage_errors: Arc::new(RwLock::new(0)),
-        });
-        
-        Ok(Self {
-            config,
-            active_transactions: Arc::new(RwLock::new(HashMap::new())),
-            transaction_history: Arc::new(RwLock::new(BTreeMap::new())),
-            transaction_index: Arc::new(RwLock::new(HashMap::new())),
-            pending_transactions: Arc::new(RwLock::new(VecDeque::new())),
-            transaction_pool: Arc::new(RwLock::new(TransactionPool {
-                transactions: HashMap::new(),
-                priority_queue: BTreeMap::new(),
-                size: 0,
-                max_size: 10000,
-                stats: PoolStats {
-                    total_added: 0,
-                    total_removed: 0,
-                    avg_pool_size: 0.0,
-                    avg_wait_time: Duration::from_secs(0),
-                    utilization: 0.0,
-                },
-            })),
-            backend,
-            metrics,
-        })
+    
+    /// Replace the current state with a snapshot's
+    pub async fn load_state_snapshot(&self, snapshot: StateSnapshot) -> GarpResult<()> {
+        self.state_storage.load_snapshot(snapshot).await
     }
     
-    pub async fn store_transaction(&self, transaction: StoredTransaction) -> GarpResult<()> {
-        let mut active = self.active_transactions.write().await;
-        active.insert(transaction.transaction_id.clone(), transaction);
-        Ok(())
+    /// Replace the current state with the retained snapshot taken at `height`
+    pub async fn restore_state_from_snapshot(&self, height: u64) -> GarpResult<StateSnapshot> {
+        self.state_storage.restore_from_snapshot(height).await
     }
     
-    pub async fn get_transaction(&self, transaction_id: &TransactionId) -> GarpResult<Option<StoredTransaction>> {
-        let active = self.active_transactions.read().await;
-        Ok(active.get(transaction_id).cloned())
+    /// Heights of the retained state snapshots, oldest first
+    pub async fn get_state_snapshot_heights(&self) -> Vec<u64> {
+        self.state_storage.snapshot_heights().await
     }
-
-    /// Assign a set of transactions to a finalized block height and hash
-    pub async fn assign_block(
-        &self,
-        height: u64,
-        block_hash: BlockHash,
+    
+    /// Whether local state is missing or behind the snapshot at `height`
+    pub async fn is_state_behind(&self, height: u64) -> bool {
+        self.state_storage.is_state_empty().await
+            || self.state_storage.latest_state_root_height().await.map_or(false, |committed| committed < height)
+    }
+    
+    /// Store state channel
+    pub async fn store_state_channel(&self, channel: StateChannel) -> GarpResult<()> {
+        self.cross_domain_storage.store_channel(channel).await
+    }
+    
+    /// Get state channel
+    pub async fn get_state_channel(&self, channel_id: &ChannelId) -> GarpResult<Option<StateChannel>> {
+        self.cross_domain_storage.get_channel(channel_id).await
+    }
+    
+    /// Remove state channel
+    pub async fn remove_state_channel(&self, channel_id: &ChannelId) -> GarpResult<Option<StateChannel>> {
+        self.cross_domain_storage.remove_channel(channel_id).await
+    }
+    
+    /// List state channels
+    pub async fn list_state_channels(&self) -> GarpResult<Vec<StateChannel>> {
+        self.cross_domain_storage.list_channels().await
+    }
+    
+    /// Store atomic swap
+    pub async fn store_atomic_swap(&self, swap: AtomicSwap) -> GarpResult<()> {
+        self.cross_domain_storage.store_swap(swap).await
+    }
+    
+    /// Get atomic swap
+    pub async fn get_atomic_swap(&self, swap_id: &SwapId) -> GarpResult<Option<AtomicSwap>> {
+        self.cross_domain_storage.get_swap(swap_id).await
+    }
+    
+    /// List atomic swaps
+    pub async fn list_atomic_swaps(&self) -> GarpResult<Vec<AtomicSwap>> {
+        self.cross_domain_storage.list_swaps().await
+    }
+    
+    /// Record a pending token wrapping
+    pub async fn begin_token_wrapping(&self, wrapping: TokenWrapping) -> GarpResult<()> {
+        self.cross_domain_storage.begin_wrapping(wrapping).await
+    }
+    
+    /// Complete a token wrapping, updating the wrapped token supply
+    pub async fn complete_token_wrapping(&self, wrap_id: &WrapId, now: chrono::DateTime<chrono::Utc>) -> GarpResult<TokenWrapping> {
+        self.cross_domain_storage.complete_wrapping(wrap_id, now).await
+    }
+    
+    /// Refund a pending token wrapping
+    pub async fn refund_token_wrapping(&self, wrap_id: &WrapId, now: chrono::DateTime<chrono::Utc>) -> GarpResult<TokenWrapping> {
+        self.cross_domain_storage.refund_wrapping(wrap_id, now).await
+    }
+    
+    /// Get token wrapping
+    pub async fn get_token_wrapping(&self, wrap_id: &WrapId) -> GarpResult<Option<TokenWrapping>> {
+        self.cross_domain_storage.get_wrapping(wrap_id).await
+    }
+    
+    /// Get wrapped token
+    pub async fn get_wrapped_token(&self, wrapped_token_id: &str) -> GarpResult<Option<WrappedToken>> {
+        self.cross_domain_storage.get_wrapped_token(wrapped_token_id).await
+    }
+    
+    /// List wrapped tokens
+    pub async fn list_wrapped_tokens(&self) -> GarpResult<Vec<WrappedToken>> {
+        self.cross_domain_storage.list_wrapped_tokens().await
+    }
+    
+    /// Store compensation chain
+    pub async fn store_compensation_chain(&self, chain: CompensationChain) -> GarpResult<()> {
+        self.cross_domain_storage.store_compensation_chain(chain).await
+    }
+    
+    /// Get compensation chain
+    pub async fn get_compensation_chain(&self, transaction_id: &TransactionId) -> GarpResult<Option<CompensationChain>> {
+        self.cross_domain_storage.get_compensation_chain(transaction_id).await
+    }
+    
+    /// List compensation chains
+    pub async fn list_compensation_chains(&self) -> GarpResult<Vec<CompensationChain>> {
+        self.cross_domain_storage.list_compensation_chains().await
+    }
+    
+    /// Store domain delegation
+    pub async fn store_domain_delegation(&self, delegation: DomainDelegation) -> GarpResult<()> {
+        self.cross_domain_storage.store_delegation(delegation).await
+    }
+    
+    /// Get domain delegation
+    pub async fn get_domain_delegation(&self, delegator: &DomainId, delegate: &DomainId) -> GarpResult<Option<DomainDelegation>> {
+        self.cross_domain_storage.get_delegation(delegator, delegate).await
+    }
+    
+    /// Remove domain delegation
+    pub async fn remove_domain_delegation(&self, delegator: &DomainId, delegate: &DomainId) -> GarpResult<Option<DomainDelegation>> {
+        self.cross_domain_storage.remove_delegation(delegator, delegate).await
+    }
+    
+    /// List domain delegations
+    pub async fn list_domain_delegations(&self) -> GarpResult<Vec<DomainDelegation>> {
+        self.cross_domain_storage.list_delegations().await
+    }
+    
+    /// Persist a settlement batch
+    pub async fn store_settlement_batch(&self, batch: SettlementBatch) -> GarpResult<()> {
+        self.settlement_storage.store_batch(batch).await
+    }
+    
+    /// Get settlement batch
+    pub async fn get_settlement_batch(&self, batch_id: &str) -> GarpResult<Option<SettlementBatch>> {
+        self.settlement_storage.get_batch(batch_id).await
+    }
+    
+    /// List settlement batches
+    pub async fn list_settlement_batches(&self) -> GarpResult<Vec<SettlementBatch>> {
+        self.settlement_storage.list_batches().await
+    }
+    
+    /// Settlement batch counters
+    pub async fn settlement_batch_metrics(&self) -> SettlementBatchMetrics {
+        self.settlement_storage.batch_metrics().await
+    }
+    
+    /// Persist a rollback record
+    pub async fn store_rollback_record(&self, record: RollbackRecord) -> GarpResult<()> {
+        self.settlement_storage.store_rollback(record).await
+    }
+    
+    /// Get rollback record
+    pub async fn get_rollback_record(&self, rollback_id: &str) -> GarpResult<Option<RollbackRecord>> {
+        self.settlement_storage.get_rollback(rollback_id).await
+    }
+    
+    /// List rollback records, optionally filtered by status
+    pub async fn list_rollback_records(&self, status: Option<&RollbackStatus>) -> GarpResult<Vec<RollbackRecord>> {
+        self.settlement_storage.list_rollbacks(status).await
+    }
+    
+    /// Persist an escrow record
+    pub async fn store_escrow_record(&self, record: EscrowRecord) -> GarpResult<()> {
+        self.settlement_storage.store_escrow(record).await
+    }
+    
+    /// Get the escrow of a conditional settlement
+    pub async fn get_escrow_record(&self, settlement_id: &str) -> GarpResult<Option<EscrowRecord>> {
+        self.settlement_storage.get_escrow(settlement_id).await
+    }
+    
+    /// List escrow records, optionally filtered by status
+    pub async fn list_escrow_records(&self, status: Option<&EscrowStatus>) -> GarpResult<Vec<EscrowRecord>> {
+        self.settlement_storage.list_escrows(status).await
+    }
+    
+    /// Persist a settlement proof and attach it to the stored transaction
+    pub async fn store_settlement_proof(&self, proof: SettlementProof) -> GarpResult<()> {
+        if let Some(mut transaction) = self.get_transaction(&proof.transaction_id).await? {
+            let bytes = serde_json::to_vec(&proof)
+                .map_err(|e| GarpError::StorageError(format!("Failed to encode settlement proof: {}", e)))?;
+            transaction.settlement_state.settlement_id = Some(proof.settlement_id.clone());
+            transaction.settlement_state.proof = Some(bytes);
+            self.store_transaction(transaction).await?;
+        }
+        self.settlement_storage.store_proof(proof).await
+    }
+    
+    /// Get the settlement proof of a transaction
+    pub async fn get_settlement_proof(&self, transaction_id: &TransactionId) -> GarpResult<Option<SettlementProof>> {
+        self.settlement_storage.get_proof(transaction_id).await
+    }
+    
+    /// Persist the zero-knowledge proof of a transaction's settlement
+    pub async fn store_settlement_zk_proof(&self, transaction_id: TransactionId, proof: ZkProof) -> GarpResult<()> {
+        self.settlement_storage.store_zk_proof(transaction_id, proof).await
+    }
+    
+    /// Get the zero-knowledge proof of a transaction's settlement
+    pub async fn get_settlement_zk_proof(&self, transaction_id: &TransactionId) -> GarpResult<Option<ZkProof>> {
+        self.settlement_storage.get_zk_proof(transaction_id).await
+    }
+    
+    /// Assemble the receipt of a transaction in a certified block. Returns
+    /// `None` until the transaction's block has a finality certificate.
+    pub async fn get_transaction_receipt(&self, transaction_id: &TransactionId) -> GarpResult<Option<TransactionReceipt>> {
+        let Some((block_height, block_hash)) = self.get_transaction(transaction_id).await?
+            .and_then(|stored| stored.block_height.zip(stored.block_hash))
+        else {
+            return Ok(None);
+        };
+        let Some(certificate) = self.get_finality_certificate_by_hash(&hex::encode(&block_hash)).await? else {
+            return Ok(None);
+        };
+        let block = self.get_block(&block_hash).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Block not found: {}", hex::encode(&block_hash))))?;
+        
+        let transaction_ids = self.transaction_storage.get_block_transactions(block_height, &block_hash).await?;
+        if receipt::transaction_root(&transaction_ids) != block.merkle_root {
+            return Err(GarpError::InternalError(format!(
+                "Block {} transactions do not match its transaction root", hex::encode(&block_hash)
+            )));
+        }
+        let merkle_proof = receipt::merkle_proof(&block_hash, &transaction_ids, transaction_id)
+            .ok_or_else(|| GarpError::InternalError(format!(
+                "Transaction {} is missing from block {}", transaction_id, hex::encode(&block_hash)
+            )))?;
+        
+        let domains_confirmed = self.get_settlement_proof(transaction_id).await?
+            .map(|proof| proof.domain_confirmations.into_keys().collect())
+            .unwrap_or_default();
+        
+        Ok(Some(TransactionReceipt {
+            transaction_id: transaction_id.clone(),
+            block_hash: hex::encode(&block_hash),
+            block_height,
+            parent_hash: hex::encode(&block.parent_hash),
+            merkle_proof,
+            settlement_proof: self.get_settlement_zk_proof(transaction_id).await?,
+            finalized_at: certificate.timestamp,
+            domains_confirmed,
+        }))
+    }
+    
+    /// Store governance proposal
+    pub async fn store_governance_proposal(&self, proposal: GovernanceProposal) -> GarpResult<()> {
+        self.cross_domain_storage.store_proposal(proposal).await
+    }
+    
+    /// Get governance proposal
+    pub async fn get_governance_proposal(&self, proposal_id: &ProposalId) -> GarpResult<Option<GovernanceProposal>> {
+        self.cross_domain_storage.get_proposal(proposal_id).await
+    }
+    
+    /// List governance proposals
+    pub async fn list_governance_proposals(&self) -> GarpResult<Vec<GovernanceProposal>> {
+        self.cross_domain_storage.list_proposals().await
+    }
+    
+    /// Set governance parameter
+    pub async fn set_governance_parameter(&self, key: &str, value: &str) -> GarpResult<()> {
+        self.cross_domain_storage.set_parameter(key, value).await
+    }
+    
+    /// Get governance parameter
+    pub async fn get_governance_parameter(&self, key: &str) -> GarpResult<Option<String>> {
+        self.cross_domain_storage.get_parameter(key).await
+    }
+
+    /// Persist a domain registration
+    pub async fn store_domain_registration(&self, registration: DomainRegistration) -> GarpResult<()> {
+        self.metadata_storage.store_registration(registration).await
+    }
+    
+    /// Remove a domain registration
+    pub async fn remove_domain_registration(&self, domain_id: &DomainId) -> GarpResult<()> {
+        self.metadata_storage.remove_registration(domain_id).await
+    }
+    
+    /// List persisted domain registrations
+    pub async fn list_domain_registrations(&self) -> GarpResult<Vec<DomainRegistration>> {
+        self.metadata_storage.list_registrations().await
+    }
+    
+    /// Append to the emergency action audit log
+    pub async fn append_emergency_audit(&self, record: EmergencyAuditRecord) -> GarpResult<EmergencyAuditRecord> {
+        self.metadata_storage.append_emergency_audit(record).await
+    }
+    
+    /// Emergency action audit log in append order
+    pub async fn list_emergency_audit(&self) -> GarpResult<Vec<EmergencyAuditRecord>> {
+        self.metadata_storage.list_emergency_audit().await
+    }
+    
+    /// Record a configuration reload
+    pub async fn append_config_reload(&self, record: ConfigReloadRecord) -> GarpResult<ConfigReloadRecord> {
+        self.metadata_storage.append_config_reload(record).await
+    }
+    
+    /// Configuration reloads, oldest first
+    pub async fn list_config_reloads(&self) -> GarpResult<Vec<ConfigReloadRecord>> {
+        self.metadata_storage.list_config_reloads().await
+    }
+    
+    /// Store a domain or validator reputation score
+    pub async fn store_reputation(&self, score: ReputationScore) -> GarpResult<()> {
+        self.metadata_storage.store_reputation(score).await
+    }
+    
+    /// Stored reputation scores
+    pub async fn list_reputation(&self) -> GarpResult<Vec<ReputationScore>> {
+        self.metadata_storage.list_reputation().await
+    }
+    
+    /// Persist maintenance mode, or clear it with `None`
+    pub async fn store_maintenance_mode(&self, mode: Option<&MaintenanceMode>) -> GarpResult<()> {
+        self.metadata_storage.store_maintenance_mode(mode).await
+    }
+    
+    /// Persisted maintenance mode, if any
+    pub async fn get_maintenance_mode(&self) -> GarpResult<Option<MaintenanceMode>> {
+        self.metadata_storage.get_maintenance_mode().await
+    }
+
+    /// Assign transactions to a finalized block. Fails with
+    /// [`TransactionError::UnknownTransactions`] without assigning any of
+    /// them if some are not stored. With `fetch_missing`, transactions
+    /// missing from memory are first loaded from the backend, as when
+    /// replaying blocks after a restart.
+    pub async fn assign_block_transactions(
+        &self,
+        height: u64,
+        block_hash: BlockHash,
+        tx_ids: &[TransactionId],
+        fetch_missing: bool,
+    ) -> GarpResult<()> {
+        let expected = self.block_storage.get_block(&block_hash).await?
+            .map(|block| block.transaction_count as usize);
+        self.transaction_storage.assign_block(height, block_hash.clone(), tx_ids, fetch_missing).await?;
+        for tx_id in tx_ids {
+            debug!(correlation_id = %tx_id, height, "Assigned transaction to block {}", hex::encode(&block_hash));
+        }
+        
+        let assigned = tx_ids.iter().collect::<HashSet<_>>().len();
+        if let Some(expected) = expected.filter(|&expected| expected != assigned) {
+            warn!(
+                "Block {} at height {} declares {} transactions but {} were assigned",
+                hex::encode(&block_hash), height, expected, assigned
+            );
+            *self.metrics.consistency_warnings.write().await += 1;
+        }
+        Ok(())
+    }
+    
+    /// Get metrics
+    pub async fn get_metrics(&self) -> StorageMetrics {
+        self.metrics.clone()
+    }
+    
+    /// Register a replication target
+    pub async fn add_replication_target(&self, target: ReplicationTarget) {
+        self.replication_manager.add_target(target).await
+    }
+    
+    /// Record how far a replication target is behind this node
+    pub async fn record_replication_lag(&self, target_id: &str, lag: Duration) -> GarpResult<()> {
+        self.replication_manager.record_lag(target_id, lag).await
+    }
+    
+    /// Check replication targets' lag now, see
+    /// [`ReplicationManager::check_replication_health`]
+    pub async fn check_replication_health(&self) -> Vec<ReplicationAlert> {
+        self.replication_manager.check_replication_health().await
+    }
+    
+    /// Buffered replication lag alerts, oldest first
+    pub async fn get_replication_alerts(&self) -> Vec<ReplicationAlert> {
+        self.replication_manager.get_alerts().await
+    }
+    
+    /// Primary replicas past the critical lag threshold
+    pub async fn lagging_primary_replicas(&self) -> Vec<String> {
+        self.replication_manager.lagging_primaries().await
+    }
+    
+    /// Start event processor
+    async fn start_event_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let event_rx = self.event_rx.clone();
+        let metrics = self.metrics.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut event_rx = event_rx.lock().await;
+            
+            while let Some(event) = event_rx.recv().await {
+                match event {
+                    StorageEvent::TransactionStored(_) => {
+                        // Handle transaction stored event
+                    }
+                    StorageEvent::BlockStored(_) => {
+                        // Handle block stored event
+                    }
+                    StorageEvent::StateUpdated(_) => {
+                        // Handle state updated event
+                    }
+                    StorageEvent::StorageError(error) => {
+                        error!("Storage error: {}", error);
+                        let mut errors = metrics.storage_errors.write().await;
+                        *errors += 1;
+                    }
+                    StorageEvent::Shutdown => {
+                        info!("Storage event processor shutting down");
+                        break;
+                    }
+                    _ => {
+                        // Handle other events
+                    }
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start metrics collector
+    async fn start_metrics_collector(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let metrics = self.metrics.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(5));
+            
+            loop {
+                interval.tick().await;
+                
+                // TODO: Collect and update metrics
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start replication lag monitor
+    async fn start_replication_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let replication_manager = self.replication_manager.clone();
+        let check_interval = Duration::from_millis(self.config.database.replication.check_interval_ms);
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(check_interval);
+            
+            loop {
+                interval.tick().await;
+                
+                let alerts = replication_manager.check_replication_health().await;
+                if !alerts.is_empty() {
+                    debug!("{} replication targets are lagging", alerts.len());
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start maintenance task
+    async fn start_maintenance_task(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let cache_manager = self.cache_manager.clone();
+        let state_storage = self.state_storage.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(60));
+            
+            loop {
+                interval.tick().await;
+                
+                // Perform cache cleanup
+                if let Err(e) = cache_manager.cleanup().await {
+                    error!("Cache cleanup failed: {}", e);
+                }
+                
+                // Garbage-collect old domain state versions
+                let pruned = state_storage.prune_domain_state_versions().await;
+                if pruned > 0 {
+                    debug!("Pruned {} old domain state versions", pruned);
+                }
+                
+                // TODO: Perform other maintenance tasks
+            }
+        });
+        
+        Ok(handle)
+    }
+
+    /// Store a finality certificate via consensus storage. The certified
+    /// block becomes final and competing blocks are orphaned.
+    pub async fn store_finality_certificate(&self, cert: FinalityCertificate) -> GarpResult<()> {
+        let block_hash = hex::decode(&cert.block_hash).ok();
+        self.consensus_storage.store_finality_certificate(cert).await?;
+        if let Some(block_hash) = block_hash {
+            let orphaned = self.block_storage.finalize_block(&block_hash).await;
+            self.release_orphaned_blocks(orphaned).await?;
+        }
+        Ok(())
+    }
+
+    /// Get a finality certificate by block hash string
+    pub async fn get_finality_certificate_by_hash(&self, hash: &str) -> GarpResult<Option<FinalityCertificate>> {
+        self.consensus_storage.get_finality_certificate_by_hash(hash).await
+    }
+
+    /// Get a finality certificate by block height
+    pub async fn get_finality_certificate_by_height(&self, height: u64) -> GarpResult<Option<FinalityCertificate>> {
+        self.consensus_storage.get_finality_certificate_by_height(height).await
+    }
+    
+    /// Get the finality certificate of the highest finalized block
+    pub async fn get_latest_finality_certificate(&self) -> GarpResult<Option<FinalityCertificate>> {
+        self.consensus_storage.get_latest_finality_certificate().await
+    }
+
+    /// Store a signed ledger checkpoint via consensus storage
+    pub async fn store_ledger_checkpoint(&self, checkpoint: LedgerCheckpoint) -> GarpResult<()> {
+        self.consensus_storage.store_ledger_checkpoint(checkpoint).await
+    }
+
+    /// Get the ledger checkpoint at a height
+    pub async fn get_ledger_checkpoint(&self, height: u64) -> GarpResult<Option<LedgerCheckpoint>> {
+        self.consensus_storage.get_ledger_checkpoint(height).await
+    }
+
+    /// Get the ledger checkpoint with the greatest height
+    pub async fn get_latest_ledger_checkpoint(&self) -> GarpResult<Option<LedgerCheckpoint>> {
+        self.consensus_storage.get_latest_ledger_checkpoint().await
+    }
+
+    /// Get a validator from the global state
+    pub async fn get_validator(&self, validator_id: &NodeId) -> GarpResult<Option<ValidatorInfo>> {
+        self.state_storage.get_validator(validator_id).await
+    }
+
+    /// List validators in the global state
+    pub async fn list_validators(&self) -> GarpResult<Vec<ValidatorInfo>> {
+        self.state_storage.list_validators().await
+    }
+
+    /// Persist the validator set that took effect at an epoch boundary
+    pub async fn replace_validator_set(&self, epoch: u64, validators: Vec<ValidatorInfo>) -> GarpResult<()> {
+        self.state_storage.replace_validator_set(epoch, validators).await
+    }
+
+    /// Mark a validator as slashed and burn `penalty_bp` basis points of its stake
+    pub async fn slash_validator(&self, validator_id: &NodeId, penalty_bp: u32) -> GarpResult<(u64, u64)> {
+        self.state_storage.slash_validator(validator_id, penalty_bp).await
+    }
+
+    /// Credit a settlement fee to active validators in proportion to stake
+    pub async fn distribute_validator_fees(&self, fee: u64) -> GarpResult<Vec<(NodeId, u64)>> {
+        self.state_storage.distribute_validator_fees(fee).await
+    }
+
+    /// Total settlement fees credited to a validator
+    pub async fn get_validator_fee_earnings(&self, validator_id: &NodeId) -> GarpResult<u64> {
+        self.state_storage.get_fee_earnings(validator_id).await
+    }
+
+    /// Record a view change via consensus storage
+    pub async fn store_view_change(&self, record: ViewChangeRecord) -> GarpResult<()> {
+        self.consensus_storage.store_view_change(record).await
+    }
+
+    /// Get view changes into views `from_view..=to_view`
+    pub async fn get_view_changes(&self, from_view: u64, to_view: u64) -> GarpResult<Vec<ViewChangeRecord>> {
+        self.consensus_storage.get_view_changes(from_view, to_view).await
+    }
+
+    /// Store a slashing record via consensus storage
+    pub async fn store_slashing_record(&self, record: SlashingRecord) -> GarpResult<()> {
+        self.consensus_storage.store_slashing_record(record).await
+    }
+
+    /// Get the slashing history of a validator, oldest first
+    pub async fn get_slashing_history(&self, validator_id: &NodeId) -> GarpResult<Vec<SlashingRecord>> {
+        self.consensus_storage.get_slashing_history(validator_id).await
+    }
+
+    /// Record a coordination vote via consensus storage, returning false if
+    /// the same vote was already counted for the session
+    pub async fn record_coordination_vote(&self, session_id: &str, voter: &DomainId, sequence_number: u64) -> bool {
+        self.consensus_storage.record_coordination_vote(session_id, voter, sequence_number).await
+    }
+
+    /// Forget the coordination votes of a finalized session
+    pub async fn prune_coordination_votes(&self, session_id: &str) -> usize {
+        self.consensus_storage.prune_coordination_votes(session_id).await
+    }
+}
+
+#[async_trait::async_trait]
+impl ReputationStore for GlobalStorage {
+    async fn load_all(&self) -> GarpResult<Vec<ReputationScore>> {
+        self.list_reputation().await
+    }
+    
+    async fn save(&self, score: &ReputationScore) -> GarpResult<()> {
+        self.store_reputation(score.clone()).await
+    }
+}
+
+// Implementation stubs for storage components
+impl TransactionStorage {
+    pub async fn new(config: Arc<GlobalSyncConfig>, backend: Arc<dyn StorageBackend>) -> GarpResult<Self> {
+        let metrics = Arc::new(TransactionStorageMetrics {
+            total_transactions: Arc::new(RwLock::new(0)),
+            active_transactions: Arc::new(RwLock::new(0)),
+            storage_ops_per_sec: Arc::new(RwLock::new(0.0)),
+            avg_storage_time: Arc::new(RwLock::new(0.0)),
+            storage_errors: Arc::new(RwLock::new(0)),
+        });
+        let transaction_pool = TransactionPool::new(
+            config.mempool.max_pool_size,
+            Duration::from_secs(config.mempool.pool_age_boost_secs),
+        );
+        
+        let existence_filter = Arc::new(parking_lot::RwLock::new(new_existence_filter(&config.database.existence_filter)));
+        
+        Ok(Self {
+            config,
+            active_transactions: Arc::new(RwLock::new(HashMap::new())),
+            existence_filter,
+            transaction_history: Arc::new(RwLock::new(BTreeMap::new())),
+            transaction_index: Arc::new(RwLock::new(HashMap::new())),
+            pending_transactions: Arc::new(RwLock::new(VecDeque::new())),
+            transaction_pool: Arc::new(RwLock::new(transaction_pool)),
+            backend,
+            metrics,
+        })
+    }
+    
+    pub async fn store_transaction(&self, transaction: StoredTransaction) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&transaction)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode transaction: {}", e)))?;
+        self.backend.set(&transaction_key(&transaction.transaction_id), bytes).await?;
+        let mut active = self.active_transactions.write().await;
+        self.existence_filter.write().set(&transaction.transaction_id);
+        active.insert(transaction.transaction_id.clone(), transaction);
+        Ok(())
+    }
+    
+    pub async fn get_transaction(&self, transaction_id: &TransactionId) -> GarpResult<Option<StoredTransaction>> {
+        if !self.might_exist(transaction_id) {
+            return Ok(None);
+        }
+        let active = self.active_transactions.read().await;
+        Ok(active.get(transaction_id).cloned())
+    }
+    
+    /// Every persisted transaction in backend key order. Only the keys are
+    /// listed up front; records are read and decoded as the stream is polled.
+    pub async fn stream_transactions(&self) -> GarpResult<BoxStream<'static, GarpResult<StoredTransaction>>> {
+        let keys = self.backend.list_keys(TRANSACTION_KEY_PREFIX).await?;
+        let backend = self.backend.clone();
+        Ok(stream::iter(keys)
+            .then(move |key| {
+                let backend = backend.clone();
+                async move {
+                    // Skip records deleted since the keys were listed
+                    let Some(bytes) = backend.get(&key).await? else { return Ok(None) };
+                    serde_json::from_slice::<StoredTransaction>(&bytes)
+                        .map(Some)
+                        .map_err(|e| GarpError::StorageError(format!("Failed to decode transaction {}: {}", key, e)))
+                }
+            })
+            .filter_map(|record| async move { record.transpose() })
+            .boxed())
+    }
+    
+    /// Whether `transaction_id` may be stored. `false` means it definitely
+    /// is not, answered without touching the transaction map; `true` may be
+    /// a false positive at the configured rate.
+    pub fn might_exist(&self, transaction_id: &TransactionId) -> bool {
+        self.existence_filter.read().check(transaction_id)
+    }
+    
+    /// Rebuild the existence filter from the transaction map, e.g. on startup
+    /// or after the filter has filled past its configured size
+    pub async fn rebuild_existence_filter(&self) {
+        let active = self.active_transactions.read().await;
+        let mut filter = new_existence_filter(&self.config.database.existence_filter);
+        for transaction_id in active.keys() {
+            filter.set(transaction_id);
+        }
+        *self.existence_filter.write() = filter;
+        debug!("Rebuilt transaction existence filter over {} transactions", active.len());
+    }
+    
+    /// Admit a transaction to the pool, returning the transaction evicted
+    /// to make room, if any
+    pub async fn add_to_pool(&self, entry: PoolTransaction) -> GarpResult<Option<PoolTransaction>> {
+        self.transaction_pool.write().await.insert(entry, Instant::now())
+    }
+    
+    /// Take transactions for the next block, see [`TransactionPool::select_for_block`]
+    pub async fn select_for_block(&self, max_count: usize, max_bytes: usize) -> Vec<PoolTransaction> {
+        self.transaction_pool.write().await.select_for_block(max_count, max_bytes, Instant::now())
+    }
+    
+    /// Drop a transaction from the pool
+    pub async fn remove_from_pool(&self, transaction_id: &TransactionId) -> Option<PoolTransaction> {
+        self.transaction_pool.write().await.remove(transaction_id, Instant::now())
+    }
+    
+    /// Change the pool capacity and aging rate
+    pub async fn set_pool_limits(&self, max_size: usize, age_boost_interval: Duration) {
+        self.transaction_pool.write().await.set_limits(max_size, age_boost_interval);
+    }
+    
+    /// Number of pooled transactions and the pool statistics
+    pub async fn pool_stats(&self) -> (usize, PoolStats) {
+        let pool = self.transaction_pool.read().await;
+        (pool.size, pool.stats.clone())
+    }
+    
+    /// Load transactions persisted by [`Self::store_transaction`] from the
+    /// backend. IDs with no stored transaction are skipped.
+    async fn load_transactions(&self, tx_ids: &[TransactionId]) -> GarpResult<HashMap<TransactionId, StoredTransaction>> {
+        let mut loaded = HashMap::new();
+        for tid in tx_ids {
+            if let Some(bytes) = self.backend.get(&transaction_key(tid)).await? {
+                let transaction: StoredTransaction = serde_json::from_slice(&bytes)
+                    .map_err(|e| GarpError::StorageError(format!("Failed to decode transaction {}: {}", tid, e)))?;
+                loaded.insert(tid.clone(), transaction);
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Assign a set of transactions to a finalized block height and hash.
+    /// Either every index is updated or, if any transaction is unknown,
+    /// none is and the unknown IDs are returned in
+    /// [`TransactionError::UnknownTransactions`]. With `fetch_missing`,
+    /// transactions not in memory are loaded from the backend first.
+    pub async fn assign_block(
+        &self,
+        height: u64,
+        block_hash: BlockHash,
         tx_ids: &[TransactionId],
+        fetch_missing: bool,
     ) -> GarpResult<()> {
-        // Update height -> tx_ids index
+        let mut recovered = if fetch_missing {
+            let absent: Vec<TransactionId> = {
+                let active = self.active_transactions.read().await;
+                tx_ids.iter().filter(|tid| !active.contains_key(tid)).cloned().collect()
+            };
+            self.load_transactions(&absent).await?
+        } else {
+            HashMap::new()
+        };
+        
+        // Hold every index for the whole assignment so readers never see it half done
+        let mut active = self.active_transactions.write().await;
+        let mut history = self.transaction_history.write().await;
+        let mut index = self.transaction_index.write().await;
+        
+        let mut missing = Vec::new();
+        for tid in tx_ids {
+            if !active.contains_key(tid) && !recovered.contains_key(tid) && !missing.contains(tid) {
+                missing.push(tid.clone());
+            }
+        }
+        if !missing.is_empty() {
+            return Err(GarpError::Transaction(TransactionError::UnknownTransactions(missing)));
+        }
         {
-            let mut history = self.transaction_history.write().await;
-            let entry = history.entry(height).or_insert_with(Vec::new);
-            for tid in tx_ids {
-                if !entry.contains(tid) {
-                    entry.push(tid.clone());
-                }
+            let mut filter = self.existence_filter.write();
+            for (tid, transaction) in recovered.drain() {
+                filter.set(&tid);
+                active.entry(tid).or_insert(transaction);
+            }
+        }
+        
+        // Update height -> tx_ids index
+        let entry = history.entry(height).or_insert_with(Vec::new);
+        for tid in tx_ids {
+            if !entry.contains(tid) {
+                entry.push(tid.clone());
             }
         }
 
-        // Tag active transactions with block metadata and mark as settled
-        {
-            let mut active = self.active_transactions.write().await;
-            for tid in tx_ids {
-                if let Some(tx) = active.get_mut(tid) {
-                    tx.block_height = Some(height);
-                    tx.block_hash = Some(block_hash.clone());
-                    tx.updated_at = SystemTime::now();
-                    // Mark transaction as settled upon finalization
-                    tx.status = TransactionStatus::Settled;
-                }
+        // Tag transactions with block metadata and mark as settled
+        for tid in tx_ids {
+            if let Some(tx) = active.get_mut(tid) {
+                tx.block_height = Some(height);
+                tx.block_hash = Some(block_hash.clone());
+                tx.updated_at = SystemTime::now();
+                // Mark transaction as settled upon finalization
+                tx.status = TransactionStatus::Settled;
             }
         }
 
         // Maintain generic index keys for convenience (height and block hash)
-        {
-            let mut index = self.transaction_index.write().await;
-            let height_key = format!("height:{}", height);
-            let block_key = format!("block:{}", hex::encode(&block_hash));
-
-            let height_set = index.entry(height_key).or_insert_with(HashSet::new);
-            let block_set = index.entry(block_key).or_insert_with(HashSet::new);
-            for tid in tx_ids {
-                height_set.insert(tid.clone());
-                block_set.insert(tid.clone());
-            }
+        let height_key = format!("height:{}", height);
+        let block_key = format!("block:{}", hex::encode(&block_hash));
+        for key in [height_key, block_key] {
+            index.entry(key).or_insert_with(HashSet::new).extend(tx_ids.iter().cloned());
         }
 
         Ok(())
@@ -2545,6 +4247,65 @@ impl TransactionStorage {
         let history = self.transaction_history.read().await;
         Ok(history.get(&height).cloned().unwrap_or_default())
     }
+    
+    /// Get the transaction IDs assigned to a block, in block order
+    pub async fn get_block_transactions(&self, height: u64, block_hash: &BlockHash) -> GarpResult<Vec<TransactionId>> {
+        let in_block = {
+            let index = self.transaction_index.read().await;
+            index.get(&format!("block:{}", hex::encode(block_hash))).cloned().unwrap_or_default()
+        };
+        let history = self.transaction_history.read().await;
+        Ok(history.get(&height)
+            .map(|ids| ids.iter().filter(|tid| in_block.contains(tid)).cloned().collect())
+            .unwrap_or_default())
+    }
+    
+    /// Undo [`Self::assign_block`] for an orphaned block, returning its
+    /// transactions to pending. Returns the released transaction IDs in
+    /// block order.
+    pub async fn unassign_block(&self, height: u64, block_hash: &BlockHash) -> GarpResult<Vec<TransactionId>> {
+        let in_block = {
+            let mut index = self.transaction_index.write().await;
+            let in_block = index.remove(&format!("block:{}", hex::encode(block_hash))).unwrap_or_default();
+            if let Some(height_set) = index.get_mut(&format!("height:{}", height)) {
+                height_set.retain(|tid| !in_block.contains(tid));
+            }
+            in_block
+        };
+        
+        let released = {
+            let mut history = self.transaction_history.write().await;
+            let entry = history.entry(height).or_default();
+            let (released, kept): (Vec<TransactionId>, Vec<TransactionId>) =
+                entry.drain(..).partition(|tid| in_block.contains(tid));
+            *entry = kept;
+            if entry.is_empty() {
+                history.remove(&height);
+            }
+            released
+        };
+        
+        let mut active = self.active_transactions.write().await;
+        for tid in &released {
+            if let Some(tx) = active.get_mut(tid) {
+                if tx.block_hash.as_ref() == Some(block_hash) {
+                    tx.block_height = None;
+                    tx.block_hash = None;
+                    tx.status = TransactionStatus::Pending;
+                    tx.updated_at = SystemTime::now();
+                }
+            }
+        }
+        Ok(released)
+    }
+}
+
+/// Empty existence filter sized by `config`
+fn new_existence_filter(config: &ExistenceFilterConfig) -> Bloom<TransactionId> {
+    match config.bitmap_bytes {
+        Some(bitmap_bytes) => Bloom::new(bitmap_bytes, config.expected_items),
+        None => Bloom::new_for_fp_rate(config.expected_items, config.false_positive_rate),
+    }
 }
 
 impl BlockStorage {
@@ -2563,6 +4324,7 @@ impl BlockStorage {
                 genesis_block: BlockHash::default(),
                 head: BlockHash::default(),
                 height: 0,
+                finalized_height: None,
                 total_difficulty: 0,
                 stats: ChainStats {
                     total_blocks: 0,
@@ -2570,30 +4332,229 @@ impl BlockStorage {
                     avg_block_time: Duration::from_secs(0),
                     avg_block_size: 0,
                     chain_size: 0,
+                    forks_detected: 0,
                 },
             })),
             block_index: Arc::new(RwLock::new(HashMap::new())),
             height_index: Arc::new(RwLock::new(BTreeMap::new())),
             pending_blocks: Arc::new(RwLock::new(HashMap::new())),
+            fork_cache: Arc::new(RwLock::new(HashMap::new())),
             backend,
             metrics,
         })
     }
     
-    pub async fn store_block(&self, block_hash: BlockHash, block_info: BlockInfo) -> GarpResult<()> {
+    /// Store a block. Apart from the first block stored, the parent must be
+    /// known and the block one above it. A second block at a known height
+    /// is kept as a fork; the head only moves to its branch once the branch
+    /// is final. Blocks stay in the fork cache until they are
+    /// `finality_depth` below the head or certified.
+    pub async fn store_block(&self, block_hash: BlockHash, block_info: BlockInfo) -> GarpResult<StoredBlock> {
         let mut index = self.block_index.write().await;
         let mut height_index = self.height_index.write().await;
+        let mut fork_cache = self.fork_cache.write().await;
+        let mut blockchain = self.blockchain.write().await;
+        
+        if index.contains_key(&block_hash) {
+            return Ok(StoredBlock::default());
+        }
+        if blockchain.finalized_height.map_or(false, |finalized| block_info.height <= finalized) {
+            return Err(GarpError::ValidationError(format!(
+                "Block {} at height {} conflicts with the finalized chain",
+                hex::encode(&block_hash), block_info.height
+            )));
+        }
+        if blockchain.stats.total_blocks > 0 {
+            let Some(parent) = index.get(&block_info.parent_hash) else {
+                return Err(GarpError::ValidationError(format!(
+                    "Block {} at height {} has unknown parent {}",
+                    hex::encode(&block_hash), block_info.height, hex::encode(&block_info.parent_hash)
+                )));
+            };
+            if block_info.height != parent.height + 1 {
+                return Err(GarpError::ValidationError(format!(
+                    "Block {} at height {} does not follow its parent at height {}",
+                    hex::encode(&block_hash), block_info.height, parent.height
+                )));
+            }
+        }
+        
+        let fork_of = height_index.get(&block_info.height).cloned().or_else(|| {
+            fork_cache.iter()
+                .find(|(_, block)| block.height == block_info.height)
+                .map(|(hash, _)| hash.clone())
+        });
+        
+        let extends_chain = blockchain.stats.total_blocks == 0 || block_info.parent_hash == blockchain.head;
+        if extends_chain {
+            // Measure from the previous head so gaps left by suppressed empty
+            // blocks are reflected in the average block time
+            let gap = index
+                .get(&blockchain.head)
+                .and_then(|head| block_info.timestamp.duration_since(head.timestamp).ok());
+            if let Some(gap) = gap {
+                let intervals = blockchain.stats.total_blocks as u32;
+                let avg = blockchain.stats.avg_block_time;
+                blockchain.stats.avg_block_time = (avg * (intervals - 1) + gap) / intervals;
+            }
+            blockchain.head = block_hash.clone();
+            blockchain.height = block_info.height;
+            if blockchain.stats.total_blocks == 0 {
+                blockchain.genesis_block = block_hash.clone();
+            }
+        }
+        
+        let stats = &mut blockchain.stats;
+        stats.total_blocks += 1;
+        stats.total_transactions += block_info.transaction_count as u64;
+        stats.chain_size += block_info.size as u64;
+        stats.avg_block_size = (stats.chain_size / stats.total_blocks) as usize;
+        if fork_of.is_some() {
+            stats.forks_detected += 1;
+        }
         
         index.insert(block_hash.clone(), block_info.clone());
-        height_index.insert(block_info.height, block_hash);
+        // Only the canonical branch is indexed by height
+        if extends_chain {
+            height_index.insert(block_info.height, block_hash.clone());
+        }
+        fork_cache.insert(block_hash, block_info);
         
-        Ok(())
+        let mut stored = StoredBlock { orphaned: Vec::new(), fork_of };
+        let Some(final_height) = blockchain.height.checked_sub(self.config.consensus.finality_depth) else {
+            return Ok(stored);
+        };
+        if blockchain.finalized_height.map_or(false, |finalized| final_height <= finalized) {
+            return Ok(stored);
+        }
+        if let Some(final_hash) = height_index.range(..=final_height).next_back().map(|(_, hash)| hash.clone()) {
+            stored.orphaned = Self::finalize(&final_hash, &mut index, &mut height_index, &mut fork_cache, &mut blockchain);
+        }
+        Ok(stored)
+    }
+    
+    /// Confirm a block, e.g. on its finality certificate, returning the
+    /// blocks orphaned by it. Unknown or already final blocks orphan nothing.
+    pub async fn finalize_block(&self, block_hash: &BlockHash) -> Vec<BlockInfo> {
+        let mut index = self.block_index.write().await;
+        let mut height_index = self.height_index.write().await;
+        let mut fork_cache = self.fork_cache.write().await;
+        let mut blockchain = self.blockchain.write().await;
+        Self::finalize(block_hash, &mut index, &mut height_index, &mut fork_cache, &mut blockchain)
+    }
+    
+    /// Make `block_hash` and its ancestors final. Cached blocks at or below
+    /// its height that are not its ancestors are orphaned, along with their
+    /// descendants.
+    fn finalize(
+        block_hash: &BlockHash,
+        index: &mut HashMap<BlockHash, BlockInfo>,
+        height_index: &mut BTreeMap<u64, BlockHash>,
+        fork_cache: &mut HashMap<BlockHash, BlockInfo>,
+        blockchain: &mut BlockChain,
+    ) -> Vec<BlockInfo> {
+        let Some(height) = index.get(block_hash).map(|block| block.height) else {
+            return Vec::new();
+        };
+        if blockchain.finalized_height.map_or(false, |finalized| height <= finalized) {
+            return Vec::new();
+        }
+        
+        let mut ancestors = HashMap::new();
+        let mut cursor = fork_cache.get(block_hash).map(|block| (block_hash.clone(), block));
+        while let Some((hash, block)) = cursor {
+            cursor = fork_cache.get(&block.parent_hash).map(|parent| (block.parent_hash.clone(), parent));
+            ancestors.insert(block.height, hash);
+        }
+        
+        let mut orphaned: HashSet<BlockHash> = fork_cache.iter()
+            .filter(|(hash, block)| block.height <= height && ancestors.get(&block.height) != Some(*hash))
+            .map(|(hash, _)| hash.clone())
+            .collect();
+        loop {
+            let descendants: Vec<BlockHash> = fork_cache.iter()
+                .filter(|(hash, block)| !orphaned.contains(*hash) && orphaned.contains(&block.parent_hash))
+                .map(|(hash, _)| hash.clone())
+                .collect();
+            if descendants.is_empty() {
+                break;
+            }
+            orphaned.extend(descendants);
+        }
+        
+        for (ancestor_height, hash) in ancestors {
+            fork_cache.remove(&hash);
+            height_index.insert(ancestor_height, hash);
+        }
+        let mut removed = Vec::with_capacity(orphaned.len());
+        for hash in &orphaned {
+            fork_cache.remove(hash);
+            if let Some(block) = index.remove(hash) {
+                if height_index.get(&block.height) == Some(hash) {
+                    height_index.remove(&block.height);
+                }
+                let stats = &mut blockchain.stats;
+                stats.total_blocks -= 1;
+                stats.total_transactions = stats.total_transactions.saturating_sub(block.transaction_count as u64);
+                stats.chain_size = stats.chain_size.saturating_sub(block.size as u64);
+                stats.avg_block_size = stats.chain_size.checked_div(stats.total_blocks).unwrap_or(0) as usize;
+                removed.push(block);
+            }
+        }
+        
+        blockchain.finalized_height = Some(height);
+        if orphaned.contains(&blockchain.head) {
+            // Follow the final branch to its highest block, which every
+            // block left in the fork cache descends from
+            let (head_height, head) = fork_cache.iter()
+                .map(|(hash, block)| (block.height, hash.clone()))
+                .max()
+                .unwrap_or((height, block_hash.clone()));
+            height_index.retain(|&indexed_height, _| indexed_height <= height);
+            let mut cursor = fork_cache.get(&head).map(|block| (head.clone(), block));
+            while let Some((hash, block)) = cursor {
+                cursor = fork_cache.get(&block.parent_hash).map(|parent| (block.parent_hash.clone(), parent));
+                height_index.insert(block.height, hash);
+            }
+            blockchain.head = head;
+            blockchain.height = head_height;
+        }
+        removed.sort_by_key(|block| block.height);
+        removed
     }
     
     pub async fn get_block(&self, block_hash: &BlockHash) -> GarpResult<Option<BlockInfo>> {
         let index = self.block_index.read().await;
         Ok(index.get(block_hash).cloned())
     }
+    
+    pub async fn get_blockchain(&self) -> BlockChain {
+        self.blockchain.read().await.clone()
+    }
+    
+    pub async fn get_chain_info(&self) -> ChainInfo {
+        let fork_heights: Vec<u64> = {
+            let fork_cache = self.fork_cache.read().await;
+            let mut counts: BTreeMap<u64, usize> = BTreeMap::new();
+            for block in fork_cache.values() {
+                *counts.entry(block.height).or_default() += 1;
+            }
+            counts.into_iter().filter(|(_, count)| *count > 1).map(|(height, _)| height).collect()
+        };
+        let chain = self.blockchain.read().await;
+        ChainInfo {
+            genesis_block: hex::encode(&chain.genesis_block),
+            head: hex::encode(&chain.head),
+            height: chain.height,
+            finalized_height: chain.finalized_height,
+            total_blocks: chain.stats.total_blocks,
+            total_transactions: chain.stats.total_transactions,
+            avg_block_time_ms: chain.stats.avg_block_time.as_millis() as u64,
+            avg_block_size: chain.stats.avg_block_size,
+            forks_detected: chain.stats.forks_detected,
+            fork_heights,
+        }
+    }
 }
 
 impl StateStorage {
@@ -2606,11 +4567,37 @@ impl StateStorage {
             storage_errors: Arc::new(RwLock::new(0)),
         });
         
+        // Reload snapshots persisted by earlier runs
+        let mut state_snapshots = HashMap::new();
+        for key in backend.list_keys(STATE_SNAPSHOT_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<StateSnapshot>(&bytes) {
+                    Ok(snapshot) => {
+                        state_snapshots.insert(snapshot.block_height, snapshot);
+                    }
+                    Err(e) => warn!("Skipping unreadable state snapshot {}: {}", key, e),
+                }
+            }
+        }
+        
+        // Reload state roots recorded by earlier runs
+        let mut state_roots = BTreeMap::new();
+        for key in backend.list_keys(STATE_ROOT_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<StateRootRecord>(&bytes) {
+                    Ok(record) => {
+                        state_roots.insert(record.height, record);
+                    }
+                    Err(e) => warn!("Skipping unreadable state root record {}: {}", key, e),
+                }
+            }
+        }
+        
         Ok(Self {
             config,
             global_state: Arc::new(RwLock::new(GlobalState {
                 version: 0,
-                root_hash: Vec::new(),
+                root_hash: state_root::global_state_root(std::iter::empty()),
                 domain_states: HashMap::new(),
                 global_variables: HashMap::new(),
                 validator_set: HashMap::new(),
@@ -2619,23 +4606,570 @@ impl StateStorage {
                 metadata: HashMap::new(),
             })),
             domain_states: Arc::new(RwLock::new(HashMap::new())),
-            state_snapshots: Arc::new(RwLock::new(HashMap::new())),
+            state_snapshots: Arc::new(RwLock::new(state_snapshots)),
             state_transitions: Arc::new(RwLock::new(VecDeque::new())),
+            state_roots: Arc::new(RwLock::new(state_roots)),
+            fee_earnings: Arc::new(RwLock::new(HashMap::new())),
             backend,
             metrics,
         })
     }
     
-    pub async fn update_domain_state(&self, domain_id: &DomainId, state: DomainState) -> GarpResult<()> {
+    /// Write a new version of a domain's state, returning the version assigned.
+    ///
+    /// Earlier versions stay readable until pruned, so readers pinned to a
+    /// version see a consistent state while writers move ahead. The state
+    /// hash is computed from `state_data` and the global state root follows.
+    pub async fn update_domain_state(&self, domain_id: &DomainId, mut state: DomainState) -> GarpResult<u64> {
         let mut states = self.domain_states.write().await;
-        states.insert(domain_id.clone(), state);
-        Ok(())
+        let versions = states.entry(domain_id.clone()).or_default();
+        let latest = versions.keys().next_back().copied().unwrap_or(0);
+        let version = state.version.max(latest + 1);
+        state.version = version;
+        state.state_hash = state_root::domain_state_hash(&state.state_data);
+        let state_hash = state.state_hash.clone();
+        versions.insert(version, Arc::new(state));
+        
+        self.refresh_state_root(vec![(domain_id.clone(), state_hash)], SystemTime::now()).await;
+        *self.metrics.state_updates.write().await += 1;
+        Ok(version)
+    }
+    
+    /// Record new domain state hashes and recompute the global state root.
+    /// Callers hold the domain states lock so hashes land in update order.
+    async fn refresh_state_root(&self, domain_hashes: Vec<(DomainId, Vec<u8>)>, now: SystemTime) {
+        let mut global_state = self.global_state.write().await;
+        global_state.domain_states.extend(domain_hashes);
+        let root_hash = state_root::global_state_root(&global_state.domain_states);
+        global_state.root_hash = root_hash;
+        global_state.version += 1;
+        global_state.last_updated = now;
     }
     
-    pub async fn get_domain_state(&self, domain_id: &DomainId) -> GarpResult<Option<DomainState>> {
+    /// Read a domain's state at the highest version `<= as_of_version`, or the
+    /// latest version when `as_of_version` is `None`
+    pub async fn get_domain_state(&self, domain_id: &DomainId, as_of_version: Option<u64>) -> GarpResult<Option<DomainState>> {
         let states = self.domain_states.read().await;
-        Ok(states.get(domain_id).cloned())
+        let Some(versions) = states.get(domain_id) else {
+            return Ok(None);
+        };
+        let state = match as_of_version {
+            Some(version) => versions.range(..=version).next_back(),
+            None => versions.iter().next_back(),
+        };
+        Ok(state.map(|(_, state)| state.as_ref().clone()))
+    }
+    
+    /// Set `key` to `value` at `version` on every domain in `domain_ids`.
+    ///
+    /// All domains are checked before any is written: if one already holds
+    /// `version` or newer for the key, nothing changes and the conflict is
+    /// returned. Otherwise each domain gets a new state version and a
+    /// transition, and the global state version and root hash move forward.
+    pub async fn apply_state_sync(
+        &self,
+        domain_ids: &[DomainId],
+        key: &str,
+        value: &[u8],
+        version: u64,
+        transaction_id: &TransactionId,
+        block_height: u64,
+    ) -> GarpResult<StateSyncOutcome> {
+        let now = SystemTime::now();
+        let mut states = self.domain_states.write().await;
+        
+        let mut updates = Vec::with_capacity(domain_ids.len());
+        for domain_id in domain_ids {
+            let latest = states.get(domain_id)
+                .and_then(|versions| versions.values().next_back())
+                .map(|state| state.as_ref().clone());
+            let entries: BTreeMap<String, StateEntry> = match &latest {
+                Some(state) if !state.state_data.is_empty() => serde_json::from_slice(&state.state_data)
+                    .map_err(|e| GarpError::StorageError(format!("Domain {} state is not synchronized key-value state: {}", domain_id, e)))?,
+                _ => BTreeMap::new(),
+            };
+            let stored_version = entries.get(key).map_or(0, |entry| entry.version);
+            if version <= stored_version {
+                return Ok(StateSyncOutcome::VersionConflict { domain_id: domain_id.clone(), stored_version });
+            }
+            updates.push((domain_id, latest, entries));
+        }
+        
+        let mut transitions = Vec::with_capacity(updates.len());
+        let mut hashes = Vec::with_capacity(updates.len());
+        for (domain_id, latest, mut entries) in updates {
+            let previous = entries.insert(key.to_string(), StateEntry { value: value.to_vec(), version });
+            let state_data = canonical::to_canonical_json(&entries)
+                .map_err(|e| GarpError::StorageError(format!("Failed to encode domain {} state: {}", domain_id, e)))?;
+            let state_hash = state_root::domain_state_hash(&state_data);
+            
+            let versions = states.entry(domain_id.clone()).or_default();
+            let mut state = latest.unwrap_or_else(|| DomainState {
+                domain_id: domain_id.clone(),
+                version: 0,
+                state_data: Vec::new(),
+                state_hash: Vec::new(),
+                last_block_height: 0,
+                last_block_hash: Vec::new(),
+                pending_transactions: Vec::new(),
+                last_updated: now,
+                metadata: HashMap::new(),
+            });
+            state.version = versions.keys().next_back().copied().unwrap_or(0) + 1;
+            state.state_data = state_data;
+            state.state_hash = state_hash.clone();
+            state.last_block_height = state.last_block_height.max(block_height);
+            state.last_updated = now;
+            versions.insert(state.version, Arc::new(state));
+            
+            let mut metadata = HashMap::from([
+                (TRANSITION_DOMAIN_METADATA_KEY.to_string(), domain_id.clone()),
+                (TRANSITION_STATE_KEY_METADATA_KEY.to_string(), key.to_string()),
+                (TRANSITION_VERSION_METADATA_KEY.to_string(), version.to_string()),
+            ]);
+            if let Some(previous) = &previous {
+                metadata.insert("previous_version".to_string(), previous.version.to_string());
+            }
+            transitions.push(StateTransition {
+                transition_id: Uuid::new_v4().to_string(),
+                from_state: previous.map(|entry| entry.value).unwrap_or_default(),
+                to_state: value.to_vec(),
+                transaction_id: transaction_id.clone(),
+                block_height,
+                timestamp: now,
+                metadata,
+            });
+            hashes.push((domain_id.clone(), state_hash));
+        }
+        self.refresh_state_root(hashes, now).await;
+        drop(states);
+        
+        {
+            let mut history = self.state_transitions.write().await;
+            history.extend(transitions.iter().cloned());
+            while history.len() > MAX_STATE_TRANSITIONS {
+                history.pop_front();
+            }
+        }
+        *self.metrics.state_updates.write().await += 1;
+        
+        Ok(StateSyncOutcome::Applied(transitions))
+    }
+    
+    /// Up to `limit` transitions of a synchronized key on a domain, newest first
+    pub async fn get_state_history(&self, domain_id: &DomainId, key: &str, limit: usize) -> GarpResult<Vec<StateTransition>> {
+        let history = self.state_transitions.read().await;
+        Ok(history.iter()
+            .rev()
+            .filter(|transition| {
+                transition.metadata.get(TRANSITION_DOMAIN_METADATA_KEY) == Some(domain_id)
+                    && transition.metadata.get(TRANSITION_STATE_KEY_METADATA_KEY).map(String::as_str) == Some(key)
+            })
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+    
+    /// Current global state version and root hash
+    pub async fn get_global_state_version(&self) -> (u64, Vec<u8>) {
+        let global_state = self.global_state.read().await;
+        (global_state.version, global_state.root_hash.clone())
+    }
+    
+    /// Drop all but the newest `max_retained_versions` versions of each domain's
+    /// state, returning the number of versions removed
+    pub async fn prune_domain_state_versions(&self) -> usize {
+        let keep = self.config.database.max_retained_versions.max(1);
+        let mut states = self.domain_states.write().await;
+        let mut pruned = 0;
+        for versions in states.values_mut() {
+            while versions.len() > keep {
+                versions.pop_first();
+                pruned += 1;
+            }
+        }
+        pruned
+    }
+
+    pub async fn get_validator(&self, validator_id: &NodeId) -> GarpResult<Option<ValidatorInfo>> {
+        let state = self.global_state.read().await;
+        Ok(state.validator_set.get(validator_id).cloned())
+    }
+
+    pub async fn list_validators(&self) -> GarpResult<Vec<ValidatorInfo>> {
+        let state = self.global_state.read().await;
+        Ok(state.validator_set.values().cloned().collect())
+    }
+
+    /// Replace the validator set with `validators`, the set taking effect at
+    /// `epoch`, in a single update of the global state. Validators that stay
+    /// in the set keep their status, performance and join time, validators
+    /// entering it get a `joining_epoch`, and validators dropped from it stay
+    /// on record as inactive with a `leaving_epoch`. Settlement fees a
+    /// validator has earned stay credited to its stake.
+    pub async fn replace_validator_set(&self, epoch: u64, validators: Vec<ValidatorInfo>) -> GarpResult<()> {
+        let mut state = self.global_state.write().await;
+        let earnings = self.fee_earnings.read().await;
+        let mut next = HashMap::with_capacity(validators.len());
+        for mut validator in validators {
+            match state.validator_set.remove(&validator.validator_id) {
+                Some(existing) if existing.leaving_epoch.is_none() => {
+                    validator.status = existing.status;
+                    validator.performance = existing.performance;
+                    validator.joined_at = existing.joined_at;
+                    validator.joining_epoch = existing.joining_epoch;
+                }
+                _ => validator.joining_epoch = Some(epoch),
+            }
+            validator.leaving_epoch = None;
+            if let Some(earned) = earnings.get(&validator.validator_id) {
+                validator.stake = validator.stake.saturating_add(*earned);
+            }
+            next.insert(validator.validator_id.clone(), validator);
+        }
+        for (validator_id, mut validator) in state.validator_set.drain() {
+            if validator.leaving_epoch.is_none() {
+                if validator.status == ValidatorStatus::Active {
+                    validator.status = ValidatorStatus::Inactive;
+                }
+                validator.leaving_epoch = Some(epoch);
+            }
+            next.insert(validator_id, validator);
+        }
+        state.validator_set = next;
+
+        state.version += 1;
+        state.last_updated = SystemTime::now();
+        Ok(())
+    }
+
+    /// Mark a validator as slashed and burn `penalty_bp` basis points of its
+    /// stake, returning the stake before and after
+    pub async fn slash_validator(&self, validator_id: &NodeId, penalty_bp: u32) -> GarpResult<(u64, u64)> {
+        let mut state = self.global_state.write().await;
+        let validator = state.validator_set.get_mut(validator_id).ok_or_else(|| {
+            GarpError::NotFound(format!("Validator not found: {}", validator_id))
+        })?;
+
+        let stake_before = validator.stake;
+        let penalty = (stake_before as u128 * penalty_bp.min(10_000) as u128 / 10_000) as u64;
+        validator.stake = stake_before - penalty;
+        validator.voting_power = validator.voting_power.min(validator.stake);
+        validator.status = ValidatorStatus::Slashed;
+        let stake_after = validator.stake;
+
+        state.version += 1;
+        state.last_updated = SystemTime::now();
+        Ok((stake_before, stake_after))
+    }
+
+    /// Credit `fee` to active validators in proportion to their stake,
+    /// returning each validator's share. Rounding dust goes to the validator
+    /// with the largest stake.
+    pub async fn distribute_validator_fees(&self, fee: u64) -> GarpResult<Vec<(NodeId, u64)>> {
+        let mut state = self.global_state.write().await;
+        let mut recipients: Vec<(NodeId, u64)> = state.validator_set.values()
+            .filter(|v| v.status == ValidatorStatus::Active && v.stake > 0)
+            .map(|v| (v.validator_id.clone(), v.stake))
+            .collect();
+        if fee == 0 || recipients.is_empty() {
+            return Ok(Vec::new());
+        }
+        recipients.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let total_stake: u128 = recipients.iter().map(|(_, stake)| *stake as u128).sum();
+        let mut shares: Vec<(NodeId, u64)> = recipients.into_iter()
+            .map(|(id, stake)| (id, (fee as u128 * stake as u128 / total_stake) as u64))
+            .collect();
+        let distributed: u64 = shares.iter().map(|(_, share)| share).sum();
+        shares[0].1 += fee - distributed;
+
+        let mut earnings = self.fee_earnings.write().await;
+        for (validator_id, share) in &shares {
+            if let Some(validator) = state.validator_set.get_mut(validator_id) {
+                validator.stake = validator.stake.saturating_add(*share);
+            }
+            *earnings.entry(validator_id.clone()).or_insert(0) += share;
+        }
+
+        state.version += 1;
+        state.last_updated = SystemTime::now();
+        Ok(shares)
+    }
+
+    /// Total settlement fees credited to a validator
+    pub async fn get_fee_earnings(&self, validator_id: &NodeId) -> GarpResult<u64> {
+        let earnings = self.fee_earnings.read().await;
+        Ok(earnings.get(validator_id).copied().unwrap_or(0))
+    }
+    
+    /// Consistent copy of the current state. Only the global state and the
+    /// pointers to each domain's latest version are copied under the locks,
+    /// so writers are held up for a bounded time whatever the state size.
+    async fn snapshot_contents(&self) -> SnapshotContents {
+        let (global_state, latest) = {
+            let states = self.domain_states.read().await;
+            let global_state = self.global_state.read().await.clone();
+            let latest: Vec<Arc<DomainState>> = states
+                .values()
+                .filter_map(|versions| versions.values().next_back().cloned())
+                .collect();
+            (global_state, latest)
+        };
+        let mut domain_states: Vec<DomainState> = latest.iter().map(|state| state.as_ref().clone()).collect();
+        domain_states.sort_by(|a, b| a.domain_id.cmp(&b.domain_id));
+        SnapshotContents { global_state, domain_states }
+    }
+    
+    /// Global state root of the current state
+    pub async fn state_root(&self) -> GarpResult<Vec<u8>> {
+        Ok(self.global_state.read().await.root_hash.clone())
+    }
+    
+    /// Record the current global state root as the one committed at `height`
+    pub async fn commit_state_root(&self, height: u64) -> GarpResult<StateRootRecord> {
+        let global_state = self.global_state.read().await;
+        let record = StateRootRecord {
+            height,
+            root: global_state.root_hash.clone(),
+            domain_hashes: global_state.domain_states.iter().map(|(id, hash)| (id.clone(), hash.clone())).collect(),
+            recorded_at: SystemTime::now(),
+        };
+        drop(global_state);
+        self.store_state_root(record.clone()).await?;
+        Ok(record)
+    }
+    
+    async fn store_state_root(&self, record: StateRootRecord) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&record)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode state root at height {}: {}", record.height, e)))?;
+        self.backend.set(&state_root_key(record.height), bytes).await?;
+        self.state_roots.write().await.insert(record.height, record);
+        Ok(())
+    }
+    
+    /// Global state root committed at `height`
+    pub async fn get_state_root_record(&self, height: u64) -> Option<StateRootRecord> {
+        self.state_roots.read().await.get(&height).cloned()
+    }
+    
+    /// Proof that a domain's state hash is included in the root committed at
+    /// `height`, or `None` if no root is recorded there or the domain had no
+    /// state at that height
+    pub async fn state_root_proof(&self, height: u64, domain_id: &DomainId) -> Option<StateRootProof> {
+        self.state_roots.read().await.get(&height)?.proof(domain_id)
+    }
+    
+    /// Snapshot the current state and persist it, keeping the newest
+    /// `consensus.snapshot_retention` snapshots. Serialization runs on the
+    /// blocking pool, off the state locks.
+    pub async fn create_snapshot(&self, block_height: u64, block_hash: BlockHash) -> GarpResult<StateSnapshot> {
+        let contents = self.snapshot_contents().await;
+        let (snapshot_data, state_root) = tokio::task::spawn_blocking(move || {
+            contents.encode().map(|data| (data, contents.state_root()))
+        })
+        .await
+        .map_err(|e| GarpError::InternalError(format!("Snapshot serialization failed: {}", e)))??;
+        let snapshot = StateSnapshot {
+            snapshot_id: format!("snapshot-{}", block_height),
+            block_height,
+            block_hash,
+            state_root,
+            checksum: StateSnapshot::checksum_of(&snapshot_data),
+            snapshot_data,
+            created_at: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        self.store_snapshot(snapshot.clone()).await?;
+        *self.metrics.snapshots_created.write().await += 1;
+        Ok(snapshot)
+    }
+    
+    /// Persist and index a snapshot, pruning beyond the retention count
+    async fn store_snapshot(&self, snapshot: StateSnapshot) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&snapshot)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode snapshot {}: {}", snapshot.snapshot_id, e)))?;
+        self.backend.set(&state_snapshot_key(snapshot.block_height), bytes).await?;
+        
+        let retention = self.config.consensus.snapshot_retention.max(1);
+        let mut snapshots = self.state_snapshots.write().await;
+        snapshots.insert(snapshot.block_height, snapshot);
+        while snapshots.len() > retention {
+            let Some(oldest) = snapshots.keys().min().copied() else {
+                break;
+            };
+            snapshots.remove(&oldest);
+            self.backend.delete(&state_snapshot_key(oldest)).await?;
+        }
+        Ok(())
     }
+    
+    /// Heights of the retained snapshots, oldest first
+    pub async fn snapshot_heights(&self) -> Vec<u64> {
+        let mut heights: Vec<u64> = self.state_snapshots.read().await.keys().copied().collect();
+        heights.sort_unstable();
+        heights
+    }
+    
+    /// Replace the current state with the retained snapshot taken at `height`
+    pub async fn restore_from_snapshot(&self, height: u64) -> GarpResult<StateSnapshot> {
+        let snapshot = self.state_snapshots.read().await.get(&height).cloned()
+            .ok_or_else(|| GarpError::NotFound(format!("No state snapshot at height {}", height)))?;
+        self.load_snapshot(snapshot.clone()).await?;
+        Ok(snapshot)
+    }
+    
+    /// Whether no state has been written or loaded since startup
+    pub async fn is_state_empty(&self) -> bool {
+        self.domain_states.read().await.is_empty() && self.global_state.read().await.version == 0
+    }
+    
+    /// Height of the newest committed state root
+    pub async fn latest_state_root_height(&self) -> Option<u64> {
+        self.state_roots.read().await.keys().next_back().copied()
+    }
+    
+    pub async fn latest_snapshot(&self) -> Option<StateSnapshot> {
+        let snapshots = self.state_snapshots.read().await;
+        snapshots.keys().max().and_then(|height| snapshots.get(height)).cloned()
+    }
+    
+    /// Replace the global and domain states with a snapshot's after checking
+    /// its checksum and the domain states against its state root
+    pub async fn load_snapshot(&self, snapshot: StateSnapshot) -> GarpResult<()> {
+        if StateSnapshot::checksum_of(&snapshot.snapshot_data) != snapshot.checksum {
+            return Err(GarpError::ValidationError(format!(
+                "Snapshot {} data does not match its checksum",
+                snapshot.snapshot_id
+            )));
+        }
+        let mut contents: SnapshotContents = serde_json::from_slice(&snapshot.snapshot_data)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid snapshot {}: {}", snapshot.snapshot_id, e)))?;
+        let domain_hashes = contents.domain_hashes();
+        let root = state_root::global_state_root(&domain_hashes);
+        if root != snapshot.state_root {
+            return Err(GarpError::ValidationError(format!(
+                "Snapshot {} data does not match its state root",
+                snapshot.snapshot_id
+            )));
+        }
+        
+        contents.global_state.domain_states = domain_hashes.clone().into_iter().collect();
+        contents.global_state.root_hash = root.clone();
+        *self.global_state.write().await = contents.global_state;
+        let mut states = self.domain_states.write().await;
+        states.clear();
+        for mut state in contents.domain_states {
+            state.state_hash = domain_hashes[&state.domain_id].clone();
+            let mut versions = BTreeMap::new();
+            versions.insert(state.version, Arc::new(state.clone()));
+            states.insert(state.domain_id, versions);
+        }
+        drop(states);
+        
+        self.store_state_root(StateRootRecord {
+            height: snapshot.block_height,
+            root,
+            domain_hashes,
+            recorded_at: SystemTime::now(),
+        }).await?;
+        if !self.state_snapshots.read().await.contains_key(&snapshot.block_height) {
+            self.store_snapshot(snapshot).await?;
+        }
+        Ok(())
+    }
+}
+
+const TRANSACTION_KEY_PREFIX: &str = "transaction:";
+
+fn transaction_key(transaction_id: &TransactionId) -> String {
+    format!("{}{}", TRANSACTION_KEY_PREFIX, transaction_id)
+}
+
+const STATE_SNAPSHOT_KEY_PREFIX: &str = "state_snapshot:";
+
+/// Zero-padded so backend keys sort in height order
+fn state_snapshot_key(height: u64) -> String {
+    format!("{}{:020}", STATE_SNAPSHOT_KEY_PREFIX, height)
+}
+
+const STATE_ROOT_KEY_PREFIX: &str = "state_root:";
+
+/// Zero-padded so backend keys sort in height order
+fn state_root_key(height: u64) -> String {
+    format!("{}{:020}", STATE_ROOT_KEY_PREFIX, height)
+}
+
+const VIEW_CHANGE_KEY_PREFIX: &str = "view_change:";
+
+/// Zero-padded so backend keys sort in view order
+fn view_change_key(view: u64) -> String {
+    format!("{}{:020}", VIEW_CHANGE_KEY_PREFIX, view)
+}
+
+const LEDGER_CHECKPOINT_KEY_PREFIX: &str = "ledger_checkpoint:";
+
+/// Zero-padded so backend keys sort in height order
+fn ledger_checkpoint_key(height: u64) -> String {
+    format!("{}{:020}", LEDGER_CHECKPOINT_KEY_PREFIX, height)
+}
+
+const SETTLEMENT_BATCH_KEY_PREFIX: &str = "settlement_batch:";
+
+fn settlement_batch_key(batch_id: &str) -> String {
+    format!("{}{}", SETTLEMENT_BATCH_KEY_PREFIX, batch_id)
+}
+
+const ROLLBACK_RECORD_KEY_PREFIX: &str = "settlement_rollback:";
+
+fn rollback_record_key(rollback_id: &str) -> String {
+    format!("{}{}", ROLLBACK_RECORD_KEY_PREFIX, rollback_id)
+}
+
+const ESCROW_RECORD_KEY_PREFIX: &str = "settlement_escrow:";
+
+fn escrow_record_key(settlement_id: &str) -> String {
+    format!("{}{}", ESCROW_RECORD_KEY_PREFIX, settlement_id)
+}
+
+const SETTLEMENT_PROOF_KEY_PREFIX: &str = "settlement_proof:";
+
+fn settlement_proof_key(transaction_id: &TransactionId) -> String {
+    format!("{}{}", SETTLEMENT_PROOF_KEY_PREFIX, transaction_id)
+}
+
+const SETTLEMENT_ZK_PROOF_KEY_PREFIX: &str = "settlement_zk_proof:";
+
+fn settlement_zk_proof_key(transaction_id: &TransactionId) -> String {
+    format!("{}{}", SETTLEMENT_ZK_PROOF_KEY_PREFIX, transaction_id)
+}
+
+const DOMAIN_REGISTRATION_KEY_PREFIX: &str = "domain_registration:";
+
+fn domain_registration_key(domain_id: &DomainId) -> String {
+    format!("{}{}", DOMAIN_REGISTRATION_KEY_PREFIX, domain_id)
+}
+
+const EMERGENCY_AUDIT_KEY_PREFIX: &str = "emergency_audit:";
+
+/// Zero-padded so keys sort in log order
+fn emergency_audit_key(sequence: u64) -> String {
+    format!("{}{:020}", EMERGENCY_AUDIT_KEY_PREFIX, sequence)
+}
+
+const CONFIG_RELOAD_KEY_PREFIX: &str = "config_reload:";
+
+fn config_reload_key(sequence: u64) -> String {
+    format!("{}{:020}", CONFIG_RELOAD_KEY_PREFIX, sequence)
+}
+
+const REPUTATION_SCORE_KEY_PREFIX: &str = "reputation:";
+
+const MAINTENANCE_MODE_KEY: &str = "maintenance_mode";
+
+fn reputation_score_key(subject: &str) -> String {
+    format!("{}{}", REPUTATION_SCORE_KEY_PREFIX, subject)
 }
 
 impl ConsensusStorage {
@@ -2648,19 +5182,88 @@ impl ConsensusStorage {
             avg_consensus_time: Arc::new(RwLock::new(0.0)),
         });
         
+        // Reload view change history persisted by earlier runs
+        let mut view_changes = BTreeMap::new();
+        for key in backend.list_keys(VIEW_CHANGE_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<ViewChangeRecord>(&bytes) {
+                    Ok(record) => {
+                        view_changes.insert(record.view, record);
+                    }
+                    Err(e) => warn!("Skipping unreadable view change record {}: {}", key, e),
+                }
+            }
+        }
+        
+        let mut ledger_checkpoints = BTreeMap::new();
+        for key in backend.list_keys(LEDGER_CHECKPOINT_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<LedgerCheckpoint>(&bytes) {
+                    Ok(checkpoint) => {
+                        ledger_checkpoints.insert(checkpoint.height, checkpoint);
+                    }
+                    Err(e) => warn!("Skipping unreadable ledger checkpoint {}: {}", key, e),
+                }
+            }
+        }
+        
         Ok(Self {
             config,
             consensus_sessions: Arc::new(RwLock::new(HashMap::new())),
             consensus_history: Arc::new(RwLock::new(VecDeque::new())),
             vote_records: Arc::new(RwLock::new(HashMap::new())),
-            view_changes: Arc::new(RwLock::new(HashMap::new())),
+            view_changes: Arc::new(RwLock::new(view_changes)),
             finality_by_hash: Arc::new(RwLock::new(HashMap::new())),
             finality_by_height: Arc::new(RwLock::new(BTreeMap::new())),
+            ledger_checkpoints: Arc::new(RwLock::new(ledger_checkpoints)),
+            slashing_records: Arc::new(RwLock::new(HashMap::new())),
+            vote_deduplicator: Arc::new(RwLock::new(VoteDeduplicator::default())),
             backend,
             metrics,
         })
     }
 
+    /// Record a view change, keyed by the new view number
+    pub async fn store_view_change(&self, record: ViewChangeRecord) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&record)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode view change: {}", e)))?;
+        self.backend.set(&view_change_key(record.view), bytes).await?;
+        self.view_changes.write().await.insert(record.view, record);
+        Ok(())
+    }
+
+    /// View changes into views `from_view..=to_view`, in view order
+    pub async fn get_view_changes(&self, from_view: u64, to_view: u64) -> GarpResult<Vec<ViewChangeRecord>> {
+        if from_view > to_view {
+            return Ok(Vec::new());
+        }
+        let view_changes = self.view_changes.read().await;
+        Ok(view_changes.range(from_view..=to_view).map(|(_, r)| r.clone()).collect())
+    }
+
+    /// Append a slashing record to the validator's history
+    pub async fn store_slashing_record(&self, record: SlashingRecord) -> GarpResult<()> {
+        let mut records = self.slashing_records.write().await;
+        records.entry(record.validator_id.clone()).or_default().push(record);
+        Ok(())
+    }
+
+    /// Get the slashing history of a validator, oldest first
+    pub async fn get_slashing_history(&self, validator_id: &NodeId) -> GarpResult<Vec<SlashingRecord>> {
+        let records = self.slashing_records.read().await;
+        Ok(records.get(validator_id).cloned().unwrap_or_default())
+    }
+
+    /// Record a coordination vote, returning false if it was already counted
+    pub async fn record_coordination_vote(&self, session_id: &str, voter: &DomainId, sequence_number: u64) -> bool {
+        self.vote_deduplicator.write().await.insert(session_id, voter, sequence_number)
+    }
+
+    /// Forget the coordination votes of a finalized session
+    pub async fn prune_coordination_votes(&self, session_id: &str) -> usize {
+        self.vote_deduplicator.write().await.prune_session(session_id)
+    }
+
     /// Store a finality certificate and index it by hash and height
     pub async fn store_finality_certificate(&self, cert: FinalityCertificate) -> GarpResult<()> {
         let mut by_hash = self.finality_by_hash.write().await;
@@ -2681,6 +5284,31 @@ impl ConsensusStorage {
         let by_height = self.finality_by_height.read().await;
         Ok(by_height.get(&height).cloned())
     }
+
+    /// Retrieve the finality certificate with the greatest height
+    pub async fn get_latest_finality_certificate(&self) -> GarpResult<Option<FinalityCertificate>> {
+        let by_height = self.finality_by_height.read().await;
+        Ok(by_height.values().next_back().cloned())
+    }
+
+    /// Store a signed ledger checkpoint, keyed by height
+    pub async fn store_ledger_checkpoint(&self, checkpoint: LedgerCheckpoint) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&checkpoint)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode ledger checkpoint: {}", e)))?;
+        self.backend.set(&ledger_checkpoint_key(checkpoint.height), bytes).await?;
+        self.ledger_checkpoints.write().await.insert(checkpoint.height, checkpoint);
+        Ok(())
+    }
+
+    /// Retrieve the ledger checkpoint at a height
+    pub async fn get_ledger_checkpoint(&self, height: u64) -> GarpResult<Option<LedgerCheckpoint>> {
+        Ok(self.ledger_checkpoints.read().await.get(&height).cloned())
+    }
+
+    /// Retrieve the ledger checkpoint with the greatest height
+    pub async fn get_latest_ledger_checkpoint(&self) -> GarpResult<Option<LedgerCheckpoint>> {
+        Ok(self.ledger_checkpoints.read().await.values().next_back().cloned())
+    }
 }
 
 impl CrossDomainStorage {
@@ -2698,10 +5326,167 @@ impl CrossDomainStorage {
             cross_domain_transactions: Arc::new(RwLock::new(HashMap::new())),
             domain_coordination: Arc::new(RwLock::new(HashMap::new())),
             state_synchronization: Arc::new(RwLock::new(HashMap::new())),
+            state_channels: Arc::new(RwLock::new(HashMap::new())),
+            governance_proposals: Arc::new(RwLock::new(HashMap::new())),
+            governance_parameters: Arc::new(RwLock::new(HashMap::new())),
+            atomic_swaps: Arc::new(RwLock::new(HashMap::new())),
+            wrapped_tokens: Arc::new(RwLock::new(WrappedTokenRegistry::default())),
+            compensation_chains: Arc::new(RwLock::new(HashMap::new())),
+            domain_delegations: Arc::new(RwLock::new(HashMap::new())),
             backend,
             metrics,
         })
     }
+    
+    /// Insert or replace a state channel
+    pub async fn store_channel(&self, channel: StateChannel) -> GarpResult<()> {
+        let mut channels = self.state_channels.write().await;
+        channels.insert(channel.channel_id.clone(), channel);
+        Ok(())
+    }
+    
+    /// Get a state channel
+    pub async fn get_channel(&self, channel_id: &ChannelId) -> GarpResult<Option<StateChannel>> {
+        let channels = self.state_channels.read().await;
+        Ok(channels.get(channel_id).cloned())
+    }
+    
+    /// Remove a state channel
+    pub async fn remove_channel(&self, channel_id: &ChannelId) -> GarpResult<Option<StateChannel>> {
+        let mut channels = self.state_channels.write().await;
+        Ok(channels.remove(channel_id))
+    }
+    
+    /// List all state channels
+    pub async fn list_channels(&self) -> GarpResult<Vec<StateChannel>> {
+        let channels = self.state_channels.read().await;
+        Ok(channels.values().cloned().collect())
+    }
+    
+    /// Insert or replace an atomic swap
+    pub async fn store_swap(&self, swap: AtomicSwap) -> GarpResult<()> {
+        let mut swaps = self.atomic_swaps.write().await;
+        swaps.insert(swap.swap_id.clone(), swap);
+        Ok(())
+    }
+    
+    /// Get an atomic swap
+    pub async fn get_swap(&self, swap_id: &SwapId) -> GarpResult<Option<AtomicSwap>> {
+        let swaps = self.atomic_swaps.read().await;
+        Ok(swaps.get(swap_id).cloned())
+    }
+    
+    /// List all atomic swaps
+    pub async fn list_swaps(&self) -> GarpResult<Vec<AtomicSwap>> {
+        let swaps = self.atomic_swaps.read().await;
+        Ok(swaps.values().cloned().collect())
+    }
+    
+    /// Record a pending token wrapping
+    pub async fn begin_wrapping(&self, wrapping: TokenWrapping) -> GarpResult<()> {
+        self.wrapped_tokens.write().await.begin(wrapping)
+    }
+    
+    /// Apply a token wrapping to the wrapped token supply
+    pub async fn complete_wrapping(&self, wrap_id: &WrapId, now: chrono::DateTime<chrono::Utc>) -> GarpResult<TokenWrapping> {
+        self.wrapped_tokens.write().await.complete(wrap_id, now)
+    }
+    
+    /// Drop a pending token wrapping
+    pub async fn refund_wrapping(&self, wrap_id: &WrapId, now: chrono::DateTime<chrono::Utc>) -> GarpResult<TokenWrapping> {
+        self.wrapped_tokens.write().await.refund(wrap_id, now)
+    }
+    
+    /// Get a token wrapping
+    pub async fn get_wrapping(&self, wrap_id: &WrapId) -> GarpResult<Option<TokenWrapping>> {
+        Ok(self.wrapped_tokens.read().await.get_wrapping(wrap_id))
+    }
+    
+    /// Get a wrapped token
+    pub async fn get_wrapped_token(&self, wrapped_token_id: &str) -> GarpResult<Option<WrappedToken>> {
+        Ok(self.wrapped_tokens.read().await.get_token(wrapped_token_id))
+    }
+    
+    /// List all wrapped tokens
+    pub async fn list_wrapped_tokens(&self) -> GarpResult<Vec<WrappedToken>> {
+        Ok(self.wrapped_tokens.read().await.list_tokens())
+    }
+    
+    /// Insert or replace a compensation chain
+    pub async fn store_compensation_chain(&self, chain: CompensationChain) -> GarpResult<()> {
+        let mut chains = self.compensation_chains.write().await;
+        chains.insert(chain.original_transaction_id.clone(), chain);
+        Ok(())
+    }
+    
+    /// Get the compensation chain of an aborted transaction
+    pub async fn get_compensation_chain(&self, transaction_id: &TransactionId) -> GarpResult<Option<CompensationChain>> {
+        let chains = self.compensation_chains.read().await;
+        Ok(chains.get(transaction_id).cloned())
+    }
+    
+    /// List all compensation chains
+    pub async fn list_compensation_chains(&self) -> GarpResult<Vec<CompensationChain>> {
+        let chains = self.compensation_chains.read().await;
+        Ok(chains.values().cloned().collect())
+    }
+    
+    /// Insert or replace the delegation between its delegator and delegate
+    pub async fn store_delegation(&self, delegation: DomainDelegation) -> GarpResult<()> {
+        let mut delegations = self.domain_delegations.write().await;
+        delegations.insert((delegation.delegator.clone(), delegation.delegate.clone()), delegation);
+        Ok(())
+    }
+    
+    /// Get the delegation from `delegator` to `delegate`
+    pub async fn get_delegation(&self, delegator: &DomainId, delegate: &DomainId) -> GarpResult<Option<DomainDelegation>> {
+        let delegations = self.domain_delegations.read().await;
+        Ok(delegations.get(&(delegator.clone(), delegate.clone())).cloned())
+    }
+    
+    /// Remove the delegation from `delegator` to `delegate`
+    pub async fn remove_delegation(&self, delegator: &DomainId, delegate: &DomainId) -> GarpResult<Option<DomainDelegation>> {
+        let mut delegations = self.domain_delegations.write().await;
+        Ok(delegations.remove(&(delegator.clone(), delegate.clone())))
+    }
+    
+    /// List all delegations
+    pub async fn list_delegations(&self) -> GarpResult<Vec<DomainDelegation>> {
+        let delegations = self.domain_delegations.read().await;
+        Ok(delegations.values().cloned().collect())
+    }
+    
+    /// Insert or replace a governance proposal
+    pub async fn store_proposal(&self, proposal: GovernanceProposal) -> GarpResult<()> {
+        let mut proposals = self.governance_proposals.write().await;
+        proposals.insert(proposal.proposal_id.clone(), proposal);
+        Ok(())
+    }
+    
+    /// Get a governance proposal
+    pub async fn get_proposal(&self, proposal_id: &ProposalId) -> GarpResult<Option<GovernanceProposal>> {
+        let proposals = self.governance_proposals.read().await;
+        Ok(proposals.get(proposal_id).cloned())
+    }
+    
+    /// List all governance proposals
+    pub async fn list_proposals(&self) -> GarpResult<Vec<GovernanceProposal>> {
+        let proposals = self.governance_proposals.read().await;
+        Ok(proposals.values().cloned().collect())
+    }
+    
+    /// Set a governance parameter
+    pub async fn set_parameter(&self, key: &str, value: &str) -> GarpResult<()> {
+        let mut parameters = self.governance_parameters.write().await;
+        parameters.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+    
+    /// Get a governance parameter
+    pub async fn get_parameter(&self, key: &str) -> GarpResult<Option<String>> {
+        let parameters = self.governance_parameters.read().await;
+        Ok(parameters.get(key).cloned())
+    }
 }
 
 impl SettlementStorage {
@@ -2712,17 +5497,216 @@ impl SettlementStorage {
             failed_settlements: Arc::new(RwLock::new(0)),
             rollbacks: Arc::new(RwLock::new(0)),
             avg_settlement_time: Arc::new(RwLock::new(0.0)),
+            batches_created: Arc::new(RwLock::new(0)),
+            avg_batch_size: Arc::new(RwLock::new(0.0)),
+            partial_batch_failures: Arc::new(RwLock::new(0)),
         });
         
+        // Reload batches persisted by earlier runs
+        let mut settlement_batches = HashMap::new();
+        for key in backend.list_keys(SETTLEMENT_BATCH_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<SettlementBatch>(&bytes) {
+                    Ok(batch) => {
+                        settlement_batches.insert(batch.batch_id.clone(), batch);
+                    }
+                    Err(e) => warn!("Skipping unreadable settlement batch {}: {}", key, e),
+                }
+            }
+        }
+        
+        let mut rollback_records = HashMap::new();
+        for key in backend.list_keys(ROLLBACK_RECORD_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<RollbackRecord>(&bytes) {
+                    Ok(record) => {
+                        rollback_records.insert(record.rollback_id.clone(), record);
+                    }
+                    Err(e) => warn!("Skipping unreadable rollback record {}: {}", key, e),
+                }
+            }
+        }
+        
+        let mut escrow_records = HashMap::new();
+        for key in backend.list_keys(ESCROW_RECORD_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<EscrowRecord>(&bytes) {
+                    Ok(record) => {
+                        escrow_records.insert(record.settlement_id.clone(), record);
+                    }
+                    Err(e) => warn!("Skipping unreadable escrow record {}: {}", key, e),
+                }
+            }
+        }
+        
+        let mut settlement_proofs = HashMap::new();
+        for key in backend.list_keys(SETTLEMENT_PROOF_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<SettlementProof>(&bytes) {
+                    Ok(proof) => {
+                        settlement_proofs.insert(proof.transaction_id.clone(), proof);
+                    }
+                    Err(e) => warn!("Skipping unreadable settlement proof {}: {}", key, e),
+                }
+            }
+        }
+        
+        let mut zk_proofs = HashMap::new();
+        for key in backend.list_keys(SETTLEMENT_ZK_PROOF_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<(TransactionId, ZkProof)>(&bytes) {
+                    Ok((transaction_id, proof)) => {
+                        zk_proofs.insert(transaction_id, proof);
+                    }
+                    Err(e) => warn!("Skipping unreadable settlement zk proof {}: {}", key, e),
+                }
+            }
+        }
+        
         Ok(Self {
             config,
             settlements: Arc::new(RwLock::new(HashMap::new())),
-            settlement_batches: Arc::new(RwLock::new(HashMap::new())),
-            rollback_records: Arc::new(RwLock::new(HashMap::new())),
+            settlement_batches: Arc::new(RwLock::new(settlement_batches)),
+            rollback_records: Arc::new(RwLock::new(rollback_records)),
+            settlement_proofs: Arc::new(RwLock::new(settlement_proofs)),
+            zk_proofs: Arc::new(RwLock::new(zk_proofs)),
+            escrow_records: Arc::new(RwLock::new(escrow_records)),
             backend,
             metrics,
         })
     }
+    
+    /// Persist a batch and its current status. Batch counters are updated on
+    /// creation and on the transition into `PartiallyFailed`.
+    pub async fn store_batch(&self, batch: SettlementBatch) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&batch)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode settlement batch: {}", e)))?;
+        self.backend.set(&settlement_batch_key(&batch.batch_id), bytes).await?;
+        
+        let mut batches = self.settlement_batches.write().await;
+        let previous_status = batches.get(&batch.batch_id).map(|b| b.status.clone());
+        
+        if previous_status.is_none() {
+            let mut created = self.metrics.batches_created.write().await;
+            let mut avg_size = self.metrics.avg_batch_size.write().await;
+            *created += 1;
+            *avg_size += (batch.settlement_ids.len() as f64 - *avg_size) / *created as f64;
+        }
+        if batch.status == BatchStatus::PartiallyFailed && previous_status != Some(BatchStatus::PartiallyFailed) {
+            *self.metrics.partial_batch_failures.write().await += 1;
+        }
+        
+        batches.insert(batch.batch_id.clone(), batch);
+        Ok(())
+    }
+    
+    /// Get a settlement batch
+    pub async fn get_batch(&self, batch_id: &str) -> GarpResult<Option<SettlementBatch>> {
+        let batches = self.settlement_batches.read().await;
+        Ok(batches.get(batch_id).cloned())
+    }
+    
+    /// List settlement batches
+    pub async fn list_batches(&self) -> GarpResult<Vec<SettlementBatch>> {
+        let batches = self.settlement_batches.read().await;
+        Ok(batches.values().cloned().collect())
+    }
+    
+    /// Snapshot of batch counters
+    pub async fn batch_metrics(&self) -> SettlementBatchMetrics {
+        SettlementBatchMetrics {
+            batches_created: *self.metrics.batches_created.read().await,
+            avg_batch_size: *self.metrics.avg_batch_size.read().await,
+            partial_batch_failures: *self.metrics.partial_batch_failures.read().await,
+        }
+    }
+    
+    /// Persist a rollback record, counting it on creation
+    pub async fn store_rollback(&self, record: RollbackRecord) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&record)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode rollback record: {}", e)))?;
+        self.backend.set(&rollback_record_key(&record.rollback_id), bytes).await?;
+        
+        let mut records = self.rollback_records.write().await;
+        if !records.contains_key(&record.rollback_id) {
+            *self.metrics.rollbacks.write().await += 1;
+        }
+        records.insert(record.rollback_id.clone(), record);
+        Ok(())
+    }
+    
+    /// Get a rollback record
+    pub async fn get_rollback(&self, rollback_id: &str) -> GarpResult<Option<RollbackRecord>> {
+        let records = self.rollback_records.read().await;
+        Ok(records.get(rollback_id).cloned())
+    }
+    
+    /// List rollback records, optionally only those in `status`, oldest first
+    pub async fn list_rollbacks(&self, status: Option<&RollbackStatus>) -> GarpResult<Vec<RollbackRecord>> {
+        let records = self.rollback_records.read().await;
+        let mut matching: Vec<RollbackRecord> = records.values()
+            .filter(|record| status.map_or(true, |s| &record.status == s))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|record| record.created_at);
+        Ok(matching)
+    }
+    
+    /// Persist an escrow record and its current status
+    pub async fn store_escrow(&self, record: EscrowRecord) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&record)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode escrow record: {}", e)))?;
+        self.backend.set(&escrow_record_key(&record.settlement_id), bytes).await?;
+        self.escrow_records.write().await.insert(record.settlement_id.clone(), record);
+        Ok(())
+    }
+    
+    /// Get the escrow of a settlement
+    pub async fn get_escrow(&self, settlement_id: &str) -> GarpResult<Option<EscrowRecord>> {
+        let records = self.escrow_records.read().await;
+        Ok(records.get(settlement_id).cloned())
+    }
+    
+    /// List escrow records, optionally only those in `status`, oldest first
+    pub async fn list_escrows(&self, status: Option<&EscrowStatus>) -> GarpResult<Vec<EscrowRecord>> {
+        let records = self.escrow_records.read().await;
+        let mut matching: Vec<EscrowRecord> = records.values()
+            .filter(|record| status.map_or(true, |s| &record.status == s))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|record| record.created_at);
+        Ok(matching)
+    }
+    
+    /// Persist the proof of a completed settlement
+    pub async fn store_proof(&self, proof: SettlementProof) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&proof)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode settlement proof: {}", e)))?;
+        self.backend.set(&settlement_proof_key(&proof.transaction_id), bytes).await?;
+        self.settlement_proofs.write().await.insert(proof.transaction_id.clone(), proof);
+        Ok(())
+    }
+    
+    /// Get the settlement proof of a transaction
+    pub async fn get_proof(&self, transaction_id: &TransactionId) -> GarpResult<Option<SettlementProof>> {
+        let proofs = self.settlement_proofs.read().await;
+        Ok(proofs.get(transaction_id).cloned())
+    }
+    
+    /// Persist the zero-knowledge proof of a transaction's settlement
+    pub async fn store_zk_proof(&self, transaction_id: TransactionId, proof: ZkProof) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&(&transaction_id, &proof))
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode settlement zk proof: {}", e)))?;
+        self.backend.set(&settlement_zk_proof_key(&transaction_id), bytes).await?;
+        self.zk_proofs.write().await.insert(transaction_id, proof);
+        Ok(())
+    }
+    
+    /// Get the zero-knowledge proof of a transaction's settlement
+    pub async fn get_zk_proof(&self, transaction_id: &TransactionId) -> GarpResult<Option<ZkProof>> {
+        let proofs = self.zk_proofs.read().await;
+        Ok(proofs.get(transaction_id).cloned())
+    }
 }
 
 impl MetadataStorage {
@@ -2735,15 +5719,153 @@ impl MetadataStorage {
             storage_errors: Arc::new(RwLock::new(0)),
         });
         
+        let mut domain_registrations = HashMap::new();
+        for key in backend.list_keys(DOMAIN_REGISTRATION_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<DomainRegistration>(&bytes) {
+                    Ok(registration) => {
+                        domain_registrations.insert(registration.domain_id.clone(), registration);
+                    }
+                    Err(e) => warn!("Skipping unreadable domain registration {}: {}", key, e),
+                }
+            }
+        }
+        
+        let mut emergency_audit = Vec::new();
+        for key in backend.list_keys(EMERGENCY_AUDIT_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                let record = serde_json::from_slice::<EmergencyAuditRecord>(&bytes)
+                    .map_err(|e| GarpError::StorageError(format!("Unreadable emergency audit record {}: {}", key, e)))?;
+                emergency_audit.push(record);
+            }
+        }
+        emergency_audit.sort_by_key(|record: &EmergencyAuditRecord| record.sequence);
+        
+        let mut config_reloads = Vec::new();
+        for key in backend.list_keys(CONFIG_RELOAD_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                let record = serde_json::from_slice::<ConfigReloadRecord>(&bytes)
+                    .map_err(|e| GarpError::StorageError(format!("Unreadable config reload record {}: {}", key, e)))?;
+                config_reloads.push(record);
+            }
+        }
+        config_reloads.sort_by_key(|record: &ConfigReloadRecord| record.sequence);
+        
+        let mut reputation_scores = HashMap::new();
+        for key in backend.list_keys(REPUTATION_SCORE_KEY_PREFIX).await? {
+            if let Some(bytes) = backend.get(&key).await? {
+                match serde_json::from_slice::<ReputationScore>(&bytes) {
+                    Ok(score) => {
+                        reputation_scores.insert(score.subject.clone(), score);
+                    }
+                    Err(e) => warn!("Skipping unreadable reputation score {}: {}", key, e),
+                }
+            }
+        }
+        
         Ok(Self {
             config,
             node_metadata: Arc::new(RwLock::new(HashMap::new())),
             domain_metadata: Arc::new(RwLock::new(HashMap::new())),
             system_metadata: Arc::new(RwLock::new(HashMap::new())),
+            domain_registrations: Arc::new(RwLock::new(domain_registrations)),
+            emergency_audit: Arc::new(RwLock::new(emergency_audit)),
+            config_reloads: Arc::new(RwLock::new(config_reloads)),
+            reputation_scores: Arc::new(RwLock::new(reputation_scores)),
             backend,
             metrics,
         })
     }
+    
+    /// Persist a domain registration
+    pub async fn store_registration(&self, registration: DomainRegistration) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&registration)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode domain registration: {}", e)))?;
+        self.backend.set(&domain_registration_key(&registration.domain_id), bytes).await?;
+        self.domain_registrations.write().await.insert(registration.domain_id.clone(), registration);
+        Ok(())
+    }
+    
+    /// Remove a domain registration
+    pub async fn remove_registration(&self, domain_id: &DomainId) -> GarpResult<()> {
+        self.backend.delete(&domain_registration_key(domain_id)).await?;
+        self.domain_registrations.write().await.remove(domain_id);
+        Ok(())
+    }
+    
+    /// List domain registrations
+    pub async fn list_registrations(&self) -> GarpResult<Vec<DomainRegistration>> {
+        let registrations = self.domain_registrations.read().await;
+        Ok(registrations.values().cloned().collect())
+    }
+    
+    /// Append a record to the emergency audit log, assigning its sequence
+    pub async fn append_emergency_audit(&self, mut record: EmergencyAuditRecord) -> GarpResult<EmergencyAuditRecord> {
+        let mut log = self.emergency_audit.write().await;
+        record.sequence = log.len() as u64;
+        let bytes = serde_json::to_vec(&record)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode emergency audit record: {}", e)))?;
+        self.backend.set(&emergency_audit_key(record.sequence), bytes).await?;
+        log.push(record.clone());
+        Ok(record)
+    }
+    
+    /// Emergency audit log in append order
+    pub async fn list_emergency_audit(&self) -> GarpResult<Vec<EmergencyAuditRecord>> {
+        Ok(self.emergency_audit.read().await.clone())
+    }
+    
+    /// Append a record to the configuration reload log, assigning its sequence
+    pub async fn append_config_reload(&self, mut record: ConfigReloadRecord) -> GarpResult<ConfigReloadRecord> {
+        let mut log = self.config_reloads.write().await;
+        record.sequence = log.len() as u64;
+        let bytes = serde_json::to_vec(&record)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode config reload record: {}", e)))?;
+        self.backend.set(&config_reload_key(record.sequence), bytes).await?;
+        log.push(record.clone());
+        Ok(record)
+    }
+    
+    /// Configuration reload log in append order
+    pub async fn list_config_reloads(&self) -> GarpResult<Vec<ConfigReloadRecord>> {
+        Ok(self.config_reloads.read().await.clone())
+    }
+    
+    /// Persist a reputation score, replacing the subject's previous score
+    pub async fn store_reputation(&self, score: ReputationScore) -> GarpResult<()> {
+        let bytes = serde_json::to_vec(&score)
+            .map_err(|e| GarpError::StorageError(format!("Failed to encode reputation score: {}", e)))?;
+        self.backend.set(&reputation_score_key(&score.subject), bytes).await?;
+        self.reputation_scores.write().await.insert(score.subject.clone(), score);
+        Ok(())
+    }
+    
+    /// List reputation scores
+    pub async fn list_reputation(&self) -> GarpResult<Vec<ReputationScore>> {
+        Ok(self.reputation_scores.read().await.values().cloned().collect())
+    }
+    
+    /// Persist maintenance mode, or clear it with `None`
+    pub async fn store_maintenance_mode(&self, mode: Option<&MaintenanceMode>) -> GarpResult<()> {
+        match mode {
+            Some(mode) => {
+                let bytes = serde_json::to_vec(mode)
+                    .map_err(|e| GarpError::StorageError(format!("Failed to encode maintenance mode: {}", e)))?;
+                self.backend.set(MAINTENANCE_MODE_KEY, bytes).await
+            }
+            None => self.backend.delete(MAINTENANCE_MODE_KEY).await,
+        }
+    }
+    
+    /// Persisted maintenance mode, if any
+    pub async fn get_maintenance_mode(&self) -> GarpResult<Option<MaintenanceMode>> {
+        match self.backend.get(MAINTENANCE_MODE_KEY).await? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| GarpError::StorageError(format!("Unreadable maintenance mode: {}", e))),
+            None => Ok(None),
+        }
+    }
 }
 
 impl CacheManager {
@@ -2777,8 +5899,89 @@ impl CacheManager {
         Ok(())
     }
     
+    /// Add an empty cache layer, replacing any layer with the same name
+    pub async fn create_layer(&self, name: &str, config: CacheLayerConfig) {
+        let layer = CacheLayer {
+            name: name.to_string(),
+            entries: HashMap::new(),
+            config,
+            stats: LayerStats { hits: 0, misses: 0, evictions: 0, current_size: 0, current_entries: 0 },
+            expiry: ExpiryIndex::default(),
+        };
+        self.cache_layers.write().await.insert(name.to_string(), layer);
+    }
+    
+    /// Cache a value in a layer. Without an explicit `ttl` the layer's
+    /// default TTL applies; an entry with neither never expires.
+    pub async fn put(&self, layer: &str, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> GarpResult<()> {
+        let mut layers = self.cache_layers.write().await;
+        let layer = layers.get_mut(layer)
+            .ok_or_else(|| GarpError::NotFound(format!("Cache layer {}", layer)))?;
+        
+        let ttl = ttl.or(layer.config.default_ttl);
+        match ttl {
+            Some(ttl) => layer.expiry.schedule(key, ttl),
+            None => layer.expiry.clear(key),
+        }
+        let now = Instant::now();
+        let entry = CacheEntry {
+            key: key.to_string(),
+            value,
+            metadata: HashMap::new(),
+            created_at: now,
+            last_accessed: now,
+            access_count: 0,
+            ttl,
+        };
+        if let Some(replaced) = layer.entries.insert(key.to_string(), entry) {
+            layer.stats.current_size -= replaced.value.len();
+        }
+        layer.stats.current_size += layer.entries[key].value.len();
+        layer.stats.current_entries = layer.entries.len();
+        Ok(())
+    }
+    
+    /// Cached value of a key, if present and not expired
+    pub async fn get(&self, layer: &str, key: &str) -> Option<Vec<u8>> {
+        let mut layers = self.cache_layers.write().await;
+        let layer = layers.get_mut(layer)?;
+        let now = Instant::now();
+        let value = match layer.entries.get_mut(key) {
+            Some(entry) if !layer.expiry.is_expired(key, now) => {
+                entry.last_accessed = now;
+                entry.access_count += 1;
+                Some(entry.value.clone())
+            }
+            _ => None,
+        };
+        
+        if value.is_some() {
+            layer.stats.hits += 1;
+        } else {
+            layer.stats.misses += 1;
+        }
+        value
+    }
+    
+    /// Drop expired entries from every layer
     pub async fn cleanup(&self) -> GarpResult<()> {
-        // Perform cache cleanup
+        let now = Instant::now();
+        let mut evicted = 0;
+        for layer in self.cache_layers.write().await.values_mut() {
+            for key in layer.expiry.take_expired(now) {
+                if let Some(entry) = layer.entries.remove(&key) {
+                    layer.stats.current_size -= entry.value.len();
+                    layer.stats.evictions += 1;
+                    evicted += 1;
+                }
+            }
+            layer.stats.current_entries = layer.entries.len();
+        }
+        
+        if evicted > 0 {
+            self.cache_stats.write().await.total_evictions += evicted;
+            debug!("Evicted {} expired cache entries", evicted);
+        }
         Ok(())
     }
 }
@@ -2809,7 +6012,7 @@ impl BackupManager {
 }
 
 impl ReplicationManager {
-    pub async fn new(config: Arc<GlobalSyncConfig>) -> GarpResult<Self> {
+    pub async fn new(config: Arc<GlobalSyncConfig>, event_tx: mpsc::UnboundedSender<StorageEvent>) -> GarpResult<Self> {
         let metrics = Arc::new(ReplicationMetrics {
             total_replications: Arc::new(RwLock::new(0)),
             successful_replications: Arc::new(RwLock::new(0)),
@@ -2818,31 +6021,186 @@ impl ReplicationManager {
             replication_lag: Arc::new(RwLock::new(0.0)),
         });
         
-        Ok(Self {
-            config,
-            replication_targets: Arc::new(RwLock::new(HashMap::new())),
-            replication_status: Arc::new(RwLock::new(HashMap::new())),
-            replication_queue: Arc::new(RwLock::new(VecDeque::new())),
-            metrics,
-        })
+        Ok(Self {
+            config,
+            replication_targets: Arc::new(RwLock::new(HashMap::new())),
+            replication_status: Arc::new(RwLock::new(HashMap::new())),
+            replication_queue: Arc::new(RwLock::new(VecDeque::new())),
+            alerts: Arc::new(RwLock::new(VecDeque::new())),
+            lagging_primaries: Arc::new(RwLock::new(HashSet::new())),
+            event_tx,
+            metrics,
+        })
+    }
+    
+    pub async fn start(&self) -> GarpResult<()> {
+        info!("Starting Replication Manager");
+        Ok(())
+    }
+    
+    /// Register `target`, replacing any target with the same ID
+    pub async fn add_target(&self, target: ReplicationTarget) {
+        self.replication_status.write().await.insert(target.target_id.clone(), ReplicationStatus {
+            target_id: target.target_id.clone(),
+            last_sync: target.last_sync,
+            sync_progress: 0.0,
+            lag: Duration::ZERO,
+            error_count: 0,
+            last_error: None,
+        });
+        self.replication_targets.write().await.insert(target.target_id.clone(), target);
+    }
+    
+    /// Record how far `target_id` is behind this node
+    pub async fn record_lag(&self, target_id: &str, lag: Duration) -> GarpResult<()> {
+        let mut statuses = self.replication_status.write().await;
+        let status = statuses.get_mut(target_id)
+            .ok_or_else(|| GarpError::NotFound(format!("Replication target {} not found", target_id)))?;
+        status.lag = lag;
+        if lag.is_zero() {
+            status.last_sync = Some(SystemTime::now());
+        }
+        Ok(())
+    }
+    
+    /// Compare each enabled target's lag against the configured thresholds,
+    /// buffering an alert for every target past one. A primary replica past
+    /// the critical threshold raises a storage error the first time it is
+    /// seen there, and keeps storage degraded until it catches up.
+    pub async fn check_replication_health(&self) -> Vec<ReplicationAlert> {
+        let thresholds = &self.config.database.replication;
+        let max_acceptable = Duration::from_millis(thresholds.max_acceptable_lag_ms);
+        let critical = Duration::from_millis(thresholds.critical_lag_threshold_ms);
+        let now = SystemTime::now();
+        
+        let targets = self.replication_targets.read().await;
+        let statuses = self.replication_status.read().await;
+        let mut alerts = Vec::new();
+        let mut lagging_primaries = HashSet::new();
+        let mut max_lag = Duration::ZERO;
+        for (target_id, status) in statuses.iter() {
+            let Some(target) = targets.get(target_id) else { continue };
+            if target.status == TargetStatus::Disabled {
+                continue;
+            }
+            max_lag = max_lag.max(status.lag);
+            let (threshold, severity) = if status.lag > critical {
+                (critical, ReplicationAlertSeverity::Critical)
+            } else if status.lag > max_acceptable {
+                (max_acceptable, ReplicationAlertSeverity::Warning)
+            } else {
+                continue;
+            };
+            if severity == ReplicationAlertSeverity::Critical && matches!(target.target_type, ReplicationTargetType::Primary) {
+                lagging_primaries.insert(target_id.clone());
+            }
+            alerts.push(ReplicationAlert {
+                target_id: target_id.clone(),
+                lag: status.lag,
+                threshold,
+                severity,
+                raised_at: now,
+            });
+        }
+        drop(statuses);
+        drop(targets);
+        alerts.sort_by(|a, b| a.target_id.cmp(&b.target_id));
+        *self.metrics.replication_lag.write().await = max_lag.as_secs_f64();
+        
+        let mut previous = self.lagging_primaries.write().await;
+        for target_id in lagging_primaries.difference(&previous) {
+            let lag = alerts.iter().find(|alert| &alert.target_id == target_id).map_or(Duration::ZERO, |alert| alert.lag);
+            warn!("Primary replica {} is {:?} behind, past the critical threshold of {:?}", target_id, lag, critical);
+            let _ = self.event_tx.send(StorageEvent::StorageError(format!(
+                "Primary replica {} lag {}ms exceeds critical threshold {}ms",
+                target_id, lag.as_millis(), critical.as_millis()
+            )));
+        }
+        for target_id in previous.difference(&lagging_primaries) {
+            info!("Primary replica {} is back within the critical lag threshold", target_id);
+        }
+        *previous = lagging_primaries;
+        drop(previous);
+        
+        if !alerts.is_empty() {
+            let mut buffer = self.alerts.write().await;
+            buffer.extend(alerts.iter().cloned());
+            let excess = buffer.len().saturating_sub(thresholds.max_buffered_alerts);
+            buffer.drain(..excess);
+        }
+        alerts
     }
     
-    pub async fn start(&self) -> GarpResult<()> {
-        info!("Starting Replication Manager");
-        Ok(())
+    /// Buffered alerts, oldest first
+    pub async fn get_alerts(&self) -> Vec<ReplicationAlert> {
+        self.alerts.read().await.iter().cloned().collect()
+    }
+    
+    /// Primary replicas past the critical lag threshold at the last check
+    pub async fn lagging_primaries(&self) -> Vec<String> {
+        let mut targets: Vec<String> = self.lagging_primaries.read().await.iter().cloned().collect();
+        targets.sort();
+        targets
     }
 }
 
+/// Interval between sweeps for expired keys in `MemoryStorageBackend`
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Memory storage backend for testing
 pub struct MemoryStorageBackend {
     data: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+    
+    /// Deadlines of keys written with `set_with_ttl`
+    expiry: Arc<RwLock<ExpiryIndex>>,
 }
 
 impl MemoryStorageBackend {
+    /// Create an empty backend. Inside a Tokio runtime this also starts the
+    /// sweep for expired keys, which stops once the backend is dropped;
+    /// expired keys are never returned either way.
     pub fn new() -> Self {
-        Self {
+        let backend = Self {
             data: Arc::new(RwLock::new(HashMap::new())),
+            expiry: Arc::new(RwLock::new(ExpiryIndex::default())),
+        };
+        if tokio::runtime::Handle::try_current().is_ok() {
+            backend.spawn_expiry_sweep();
+        }
+        backend
+    }
+    
+    fn spawn_expiry_sweep(&self) -> tokio::task::JoinHandle<()> {
+        let data = Arc::downgrade(&self.data);
+        let expiry = Arc::downgrade(&self.expiry);
+        
+        tokio::spawn(async move {
+            let mut interval = interval(EXPIRY_SWEEP_INTERVAL);
+            
+            loop {
+                interval.tick().await;
+                
+                let (Some(data), Some(expiry)) = (data.upgrade(), expiry.upgrade()) else { break };
+                let removed = Self::remove_expired(&data, &expiry, Instant::now()).await;
+                if removed > 0 {
+                    debug!("Removed {} expired keys", removed);
+                }
+            }
+        })
+    }
+    
+    /// Remove keys whose TTL has run out by `now`, returning how many
+    async fn remove_expired(
+        data: &RwLock<HashMap<String, Vec<u8>>>,
+        expiry: &RwLock<ExpiryIndex>,
+        now: Instant,
+    ) -> usize {
+        let mut data = data.write().await;
+        let expired = expiry.write().await.take_expired(now);
+        for key in &expired {
+            data.remove(key);
         }
+        expired.len()
     }
 }
 
@@ -2850,43 +6208,61 @@ impl MemoryStorageBackend {
 impl StorageBackend for MemoryStorageBackend {
     async fn get(&self, key: &str) -> GarpResult<Option<Vec<u8>>> {
         let data = self.data.read().await;
+        if self.expiry.read().await.is_expired(key, Instant::now()) {
+            return Ok(None);
+        }
         Ok(data.get(key).cloned())
     }
     
     async fn set(&self, key: &str, value: Vec<u8>) -> GarpResult<()> {
         let mut data = self.data.write().await;
+        self.expiry.write().await.clear(key);
+        data.insert(key.to_string(), value);
+        Ok(())
+    }
+    
+    async fn set_with_ttl(&self, key: &str, value: Vec<u8>, ttl: Duration) -> GarpResult<()> {
+        let mut data = self.data.write().await;
+        self.expiry.write().await.schedule(key, ttl);
         data.insert(key.to_string(), value);
         Ok(())
     }
     
     async fn delete(&self, key: &str) -> GarpResult<()> {
         let mut data = self.data.write().await;
+        self.expiry.write().await.clear(key);
         data.remove(key);
         Ok(())
     }
     
     async fn exists(&self, key: &str) -> GarpResult<bool> {
         let data = self.data.read().await;
-        Ok(data.contains_key(key))
+        let expired = self.expiry.read().await.is_expired(key, Instant::now());
+        Ok(data.contains_key(key) && !expired)
     }
     
     async fn list_keys(&self, prefix: &str) -> GarpResult<Vec<String>> {
         let data = self.data.read().await;
+        let expiry = self.expiry.read().await;
+        let now = Instant::now();
         Ok(data.keys()
-            .filter(|k| k.starts_with(prefix))
+            .filter(|k| k.starts_with(prefix) && !expiry.is_expired(k, now))
             .cloned()
             .collect())
     }
     
     async fn batch(&self, operations: Vec<BatchOperation>) -> GarpResult<()> {
         let mut data = self.data.write().await;
+        let mut expiry = self.expiry.write().await;
         
         for op in operations {
             match op {
                 BatchOperation::Set { key, value } => {
+                    expiry.clear(&key);
                     data.insert(key, value);
                 }
                 BatchOperation::Delete { key } => {
+                    expiry.clear(&key);
                     data.remove(&key);
                 }
             }
@@ -2924,8 +6300,20 @@ impl StorageBackend for MemoryStorageBackend {
 // Postgres storage backend
 // ---------------------------
 
+/// Interval between pruning passes over `kv_history`
+const HISTORY_PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Postgres storage backend with application-level point-in-time recovery.
+///
+/// Every write and delete is also appended to `kv_history`, a delete as a
+/// NULL value, so `restore_to_timestamp` can rebuild `kv_store` as of any
+/// moment within `history_retention_days`.
+#[derive(Clone)]
 pub struct PostgresStorageBackend {
     pool: Pool<Postgres>,
+    
+    /// How long history is kept; zero keeps it forever
+    history_retention: Duration,
 }
 
 impl PostgresStorageBackend {
@@ -2940,12 +6328,118 @@ impl PostgresStorageBackend {
             .await
             .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres connect error: {}", e)))?;
 
-        let backend = Self { pool };
+        let backend = Self {
+            pool,
+            history_retention: Duration::from_secs(config.database.history_retention_days * 24 * 60 * 60),
+        };
         if config.database.enable_migrations {
             backend.run_migrations().await?;
         }
+        if !backend.history_retention.is_zero() {
+            backend.clone().spawn_history_pruning();
+        }
         Ok(backend)
     }
+    
+    /// Periodically drop history older than the retention period
+    fn spawn_history_pruning(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(HISTORY_PRUNE_INTERVAL);
+            
+            loop {
+                interval.tick().await;
+                
+                match self.prune_history(SystemTime::now() - self.history_retention).await {
+                    Ok(pruned) if pruned > 0 => debug!("Pruned {} key history rows", pruned),
+                    Ok(_) => {}
+                    Err(e) => error!("Key history pruning failed: {}", e),
+                }
+            }
+        })
+    }
+    
+    /// Delete history rows written before `cutoff` that no restore at or
+    /// after `cutoff` needs: all but the last version of each key, and that
+    /// one too if it is a delete. Returns the number of rows removed.
+    pub async fn prune_history(&self, cutoff: SystemTime) -> GarpResult<u64> {
+        let result = sqlx::query(r#"
+            DELETE FROM kv_history h
+            WHERE h.written_at < $1
+              AND (h.value IS NULL OR EXISTS (
+                  SELECT 1 FROM kv_history newer
+                  WHERE newer.key = h.key
+                    AND newer.written_at < $1
+                    AND (newer.written_at, newer.id) > (h.written_at, h.id)
+              ))
+        "#)
+            .bind(chrono::DateTime::<chrono::Utc>::from(cutoff))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres prune history error: {}", e)))?;
+        Ok(result.rows_affected())
+    }
+    
+    /// Roll `kv_store` back (or forward) to its contents as of `ts`, using
+    /// `kv_history`. The rewrite is itself recorded in history, so a later
+    /// restore to a time after this one sees the restored state.
+    pub async fn restore_to_timestamp(&self, ts: SystemTime) -> GarpResult<()> {
+        if !self.history_retention.is_zero() && ts < SystemTime::now() - self.history_retention {
+            return Err(garp_common::GarpError::ValidationError(format!(
+                "Cannot restore to {}, history is only retained for {} days",
+                chrono::DateTime::<chrono::Utc>::from(ts),
+                self.history_retention.as_secs() / (24 * 60 * 60)
+            )));
+        }
+        
+        let mut tx = self.pool.begin().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres begin restore tx error: {}", e)))?;
+        sqlx::query("DELETE FROM kv_store")
+            .execute(&mut tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres clear kv_store error: {}", e)))?;
+        sqlx::query(r#"
+            INSERT INTO kv_store(key, value, created_at, updated_at)
+            SELECT key, value, written_at, written_at
+            FROM (
+                SELECT DISTINCT ON (key) key, value, written_at
+                FROM kv_history
+                WHERE written_at <= $1
+                ORDER BY key, written_at DESC, id DESC
+            ) latest
+            WHERE value IS NOT NULL
+        "#)
+            .bind(chrono::DateTime::<chrono::Utc>::from(ts))
+            .execute(&mut tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres restore history error: {}", e)))?;
+        Self::record_rewrite(&mut tx).await?;
+        tx.commit().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres commit restore tx error: {}", e)))?;
+        info!("Restored storage to {}", chrono::DateTime::<chrono::Utc>::from(ts));
+        Ok(())
+    }
+    
+    /// Append the current contents of `kv_store` to history after it was
+    /// rewritten wholesale, with deletes for keys it no longer holds
+    async fn record_rewrite(tx: &mut sqlx::Transaction<'_, Postgres>) -> GarpResult<()> {
+        sqlx::query(r#"
+            INSERT INTO kv_history(key, value, written_at)
+            SELECT key, value, NOW() FROM kv_store
+            UNION ALL
+            SELECT latest.key, NULL, NOW()
+            FROM (
+                SELECT DISTINCT ON (key) key, value
+                FROM kv_history
+                ORDER BY key, written_at DESC, id DESC
+            ) latest
+            WHERE latest.value IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM kv_store s WHERE s.key = latest.key)
+        "#)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres record history error: {}", e)))?;
+        Ok(())
+    }
 
     async fn run_migrations(&self) -> GarpResult<()> {
         // Create a simple key-value store and snapshot tables if they do not exist
@@ -2956,6 +6450,14 @@ impl PostgresStorageBackend {
                     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
                     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
                 )"#,
+            r#"CREATE TABLE IF NOT EXISTS kv_history (
+                    id BIGSERIAL PRIMARY KEY,
+                    key TEXT NOT NULL,
+                    value BYTEA,
+                    written_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )"#,
+            r#"CREATE INDEX IF NOT EXISTS kv_history_key_written_at ON kv_history(key, written_at, id)"#,
+            r#"CREATE INDEX IF NOT EXISTS kv_history_written_at ON kv_history(written_at)"#,
             r#"CREATE TABLE IF NOT EXISTS kv_snapshots (
                     snapshot_id TEXT PRIMARY KEY,
                     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
@@ -2991,26 +6493,11 @@ impl StorageBackend for PostgresStorageBackend {
     }
 
     async fn set(&self, key: &str, value: Vec<u8>) -> GarpResult<()> {
-        sqlx::query(r#"
-            INSERT INTO kv_store(key, value, created_at, updated_at)
-            VALUES ($1, $2, NOW(), NOW())
-            ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
-        "#)
-            .bind(key)
-            .bind(value)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres set error: {}", e)))?;
-        Ok(())
+        self.batch(vec![BatchOperation::Set { key: key.to_string(), value }]).await
     }
 
     async fn delete(&self, key: &str) -> GarpResult<()> {
-        sqlx::query("DELETE FROM kv_store WHERE key = $1")
-            .bind(key)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres delete error: {}", e)))?;
-        Ok(())
+        self.batch(vec![BatchOperation::Delete { key: key.to_string() }]).await
     }
 
     async fn exists(&self, key: &str) -> GarpResult<bool> {
@@ -3048,13 +6535,26 @@ impl StorageBackend for PostgresStorageBackend {
                         .execute(&mut tx)
                         .await
                         .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres batch set error: {}", e)))?;
+                    sqlx::query("INSERT INTO kv_history(key, value, written_at) VALUES ($1, $2, NOW())")
+                        .bind(&key)
+                        .bind(&value)
+                        .execute(&mut tx)
+                        .await
+                        .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres batch history error: {}", e)))?;
                 }
                 BatchOperation::Delete { key } => {
-                    sqlx::query("DELETE FROM kv_store WHERE key = $1")
+                    let deleted = sqlx::query("DELETE FROM kv_store WHERE key = $1")
                         .bind(&key)
                         .execute(&mut tx)
                         .await
                         .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres batch delete error: {}", e)))?;
+                    if deleted.rows_affected() > 0 {
+                        sqlx::query("INSERT INTO kv_history(key, value, written_at) VALUES ($1, NULL, NOW())")
+                            .bind(&key)
+                            .execute(&mut tx)
+                            .await
+                            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres batch history error: {}", e)))?;
+                    }
                 }
             }
         }
@@ -3100,11 +6600,278 @@ impl StorageBackend for PostgresStorageBackend {
             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
         "#)
             .bind(snapshot_id)
-            .execute(&mut tx)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres restore entries error: {}", e)))?;
+        Self::record_rewrite(&mut tx).await?;
+        tx.commit().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres commit restore tx error: {}", e)))?;
+        Ok(())
+    }
+
+    async fn get_stats(&self) -> GarpResult<StorageStats> {
+        let total_keys = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kv_store")
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or(0);
+        // Size info is not trivial; approximate by sum of octet_length(value)
+        let total_size = sqlx::query_scalar::<_, Option<i64>>("SELECT SUM(octet_length(value)) FROM kv_store")
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or(None)
+            .unwrap_or(0);
+        Ok(StorageStats {
+            total_keys: total_keys as u64,
+            total_size: total_size as u64,
+            free_space: 0,
+            read_ops: 0,
+            write_ops: 0,
+            delete_ops: 0,
+        })
+    }
+}
+
+// ---------------------------
+// SQLite storage backend
+// ---------------------------
+
+/// Attempts made by `SqliteStorageBackend::batch` before reporting a conflict
+const SQLITE_BATCH_MAX_ATTEMPTS: usize = 3;
+
+/// Single-file storage backend for embedded and edge deployments.
+///
+/// SQLite has no advisory locks, so `batch` uses optimistic locking: the
+/// ROWIDs of all touched keys are read up front and re-checked inside the
+/// write transaction. Every write replaces the row, and `id` is
+/// `AUTOINCREMENT`, so a changed ROWID means another writer got there first.
+pub struct SqliteStorageBackend {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteStorageBackend {
+    pub async fn new(config: Arc<GlobalSyncConfig>) -> GarpResult<Self> {
+        let options = SqliteConnectOptions::from_str(&config.database.url)
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite URL error: {}", e)))?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(std::time::Duration::from_millis(config.database.connect_timeout_ms));
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(config.database.max_connections)
+            .min_connections(config.database.min_connections)
+            .idle_timeout(std::time::Duration::from_millis(config.database.idle_timeout_ms))
+            .connect_with(options)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite connect error: {}", e)))?;
+
+        let backend = Self { pool };
+        if config.database.enable_migrations {
+            backend.run_migrations().await?;
+        }
+        Ok(backend)
+    }
+
+    async fn run_migrations(&self) -> GarpResult<()> {
+        // Mirrors the Postgres schema; `id` aliases ROWID for optimistic locking
+        let queries = [
+            r#"CREATE TABLE IF NOT EXISTS kv_store (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    key TEXT NOT NULL UNIQUE,
+                    value BLOB NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                )"#,
+            r#"CREATE TABLE IF NOT EXISTS kv_snapshots (
+                    snapshot_id TEXT PRIMARY KEY,
+                    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                )"#,
+            r#"CREATE TABLE IF NOT EXISTS kv_snapshot_entries (
+                    snapshot_id TEXT NOT NULL,
+                    key TEXT NOT NULL,
+                    value BLOB NOT NULL,
+                    PRIMARY KEY (snapshot_id, key),
+                    FOREIGN KEY (snapshot_id) REFERENCES kv_snapshots(snapshot_id) ON DELETE CASCADE
+                )"#,
+        ];
+
+        for q in queries {
+            sqlx::query(q)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| garp_common::GarpError::StorageError(format!("Migration error: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Current ROWID of each key, `None` if absent
+    async fn row_ids<'e, E>(executor: E, keys: &[&str]) -> GarpResult<HashMap<String, i64>>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let placeholders = vec!["?"; keys.len()].join(", ");
+        let sql = format!("SELECT key, id FROM kv_store WHERE key IN ({})", placeholders);
+        let mut query = sqlx::query(&sql);
+        for key in keys {
+            query = query.bind(*key);
+        }
+        let rows = query
+            .fetch_all(executor)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite rowid check error: {}", e)))?;
+        Ok(rows.into_iter().map(|r| (r.get::<String, _>("key"), r.get::<i64, _>("id"))).collect())
+    }
+
+    async fn try_batch(&self, operations: &[BatchOperation]) -> GarpResult<bool> {
+        let keys: Vec<&str> = operations
+            .iter()
+            .map(|op| match op {
+                BatchOperation::Set { key, .. } | BatchOperation::Delete { key } => key.as_str(),
+            })
+            .collect();
+        let expected = Self::row_ids(&self.pool, &keys).await?;
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite begin tx error: {}", e)))?;
+
+        if Self::row_ids(&mut *tx, &keys).await? != expected {
+            // Dropping the transaction rolls it back
+            return Ok(false);
+        }
+
+        for op in operations {
+            match op {
+                BatchOperation::Set { key, value } => {
+                    sqlx::query(SQLITE_REPLACE)
+                        .bind(key)
+                        .bind(value)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite batch set error: {}", e)))?;
+                }
+                BatchOperation::Delete { key } => {
+                    sqlx::query("DELETE FROM kv_store WHERE key = ?")
+                        .bind(key)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite batch delete error: {}", e)))?;
+                }
+            }
+        }
+
+        tx.commit().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite commit tx error: {}", e)))?;
+        Ok(true)
+    }
+}
+
+/// Replace a row so it gets a fresh ROWID, keeping the original `created_at`
+const SQLITE_REPLACE: &str = r#"
+    INSERT OR REPLACE INTO kv_store(key, value, created_at, updated_at)
+    VALUES (?1, ?2, COALESCE((SELECT created_at FROM kv_store WHERE key = ?1), CURRENT_TIMESTAMP), CURRENT_TIMESTAMP)
+"#;
+
+#[async_trait::async_trait]
+impl StorageBackend for SqliteStorageBackend {
+    async fn get(&self, key: &str) -> GarpResult<Option<Vec<u8>>> {
+        let res = sqlx::query("SELECT value FROM kv_store WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite get error: {}", e)))?;
+        Ok(res.map(|row| row.get::<Vec<u8>, _>("value")))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>) -> GarpResult<()> {
+        sqlx::query(SQLITE_REPLACE)
+            .bind(key)
+            .bind(value)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite set error: {}", e)))?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> GarpResult<()> {
+        sqlx::query("DELETE FROM kv_store WHERE key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite delete error: {}", e)))?;
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> GarpResult<bool> {
+        let res = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM kv_store WHERE key = ?")
+            .bind(key)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite exists error: {}", e)))?;
+        Ok(res > 0)
+    }
+
+    async fn list_keys(&self, prefix: &str) -> GarpResult<Vec<String>> {
+        // substr avoids LIKE treating `%` and `_` in keys as wildcards
+        let rows = sqlx::query("SELECT key FROM kv_store WHERE substr(key, 1, length(?1)) = ?1")
+            .bind(prefix)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite list_keys error: {}", e)))?;
+        Ok(rows.into_iter().map(|r| r.get::<String, _>("key")).collect())
+    }
+
+    async fn batch(&self, operations: Vec<BatchOperation>) -> GarpResult<()> {
+        for attempt in 1..=SQLITE_BATCH_MAX_ATTEMPTS {
+            if self.try_batch(&operations).await? {
+                return Ok(());
+            }
+            debug!("SQLite batch conflict on attempt {}, retrying", attempt);
+        }
+        Err(garp_common::GarpError::StorageError(format!(
+            "SQLite batch aborted: keys modified concurrently {} times", SQLITE_BATCH_MAX_ATTEMPTS
+        )))
+    }
+
+    async fn create_snapshot(&self, snapshot_id: &str) -> GarpResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite begin snapshot tx error: {}", e)))?;
+        sqlx::query("INSERT OR IGNORE INTO kv_snapshots(snapshot_id) VALUES (?)")
+            .bind(snapshot_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite snapshot header error: {}", e)))?;
+        sqlx::query(r#"
+            INSERT OR REPLACE INTO kv_snapshot_entries(snapshot_id, key, value)
+            SELECT ?, key, value FROM kv_store
+        "#)
+            .bind(snapshot_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite snapshot entries error: {}", e)))?;
+        tx.commit().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite commit snapshot tx error: {}", e)))?;
+        Ok(())
+    }
+
+    async fn restore_snapshot(&self, snapshot_id: &str) -> GarpResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite begin restore tx error: {}", e)))?;
+        sqlx::query("DELETE FROM kv_store")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite clear kv_store error: {}", e)))?;
+        sqlx::query(r#"
+            INSERT INTO kv_store(key, value)
+            SELECT key, value FROM kv_snapshot_entries WHERE snapshot_id = ?
+        "#)
+            .bind(snapshot_id)
+            .execute(&mut *tx)
             .await
-            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres restore entries error: {}", e)))?;
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite restore entries error: {}", e)))?;
         tx.commit().await
-            .map_err(|e| garp_common::GarpError::StorageError(format!("Postgres commit restore tx error: {}", e)))?;
+            .map_err(|e| garp_common::GarpError::StorageError(format!("SQLite commit restore tx error: {}", e)))?;
         Ok(())
     }
 
@@ -3113,8 +6880,7 @@ impl StorageBackend for PostgresStorageBackend {
             .fetch_one(&self.pool)
             .await
             .unwrap_or(0);
-        // Size info is not trivial; approximate by sum of octet_length(value)
-        let total_size = sqlx::query_scalar::<_, Option<i64>>("SELECT SUM(octet_length(value)) FROM kv_store")
+        let total_size = sqlx::query_scalar::<_, Option<i64>>("SELECT SUM(length(value)) FROM kv_store")
             .fetch_one(&self.pool)
             .await
             .unwrap_or(None)
@@ -3189,6 +6955,779 @@ mod tests {
         assert_eq!(retrieved.unwrap().transaction_id, tx_id);
     }
     
+    fn pool_entry(priority: u64, dependencies: Vec<TransactionId>, added_at: Instant) -> PoolTransaction {
+        let metadata = HashMap::from([(POOL_PRIORITY_METADATA_KEY.to_string(), priority.to_string())]);
+        PoolTransaction::new(TransactionId::new(), &metadata, dependencies, 100, added_at)
+    }
+    
+    #[test]
+    fn test_full_pool_evicts_lowest_priority() {
+        let now = Instant::now();
+        let mut pool = TransactionPool::new(2, Duration::ZERO);
+        let low = pool_entry(1, Vec::new(), now);
+        let mid = pool_entry(5, Vec::new(), now);
+        assert!(pool.insert(low.clone(), now).unwrap().is_none());
+        assert!(pool.insert(mid.clone(), now).unwrap().is_none());
+        assert_eq!(pool.stats.utilization, 1.0);
+        
+        // Ranking no higher than the lowest is rejected, ranking higher evicts it
+        assert!(pool.insert(pool_entry(1, Vec::new(), now), now).is_err());
+        let evicted = pool.insert(pool_entry(3, Vec::new(), now), now).unwrap().unwrap();
+        assert_eq!(evicted.transaction_id, low.transaction_id);
+        assert_eq!(pool.size, 2);
+        assert!(pool.transactions.contains_key(&mid.transaction_id));
+        assert_eq!(pool.priority_queue.keys().copied().collect::<Vec<_>>(), vec![3, 5]);
+        assert_eq!((pool.stats.total_added, pool.stats.total_removed), (3, 1));
+    }
+    
+    #[test]
+    fn test_waiting_transactions_are_not_starved() {
+        let start = Instant::now();
+        let mut pool = TransactionPool::new(10, Duration::from_secs(10));
+        let old = pool_entry(1, Vec::new(), start);
+        pool.insert(old.clone(), start).unwrap();
+        
+        // A steady stream of higher priority transactions arrives later
+        let later = start + Duration::from_secs(45);
+        let fresh = pool_entry(4, Vec::new(), later);
+        pool.insert(fresh.clone(), later).unwrap();
+        assert_eq!(pool.effective_priority(&pool.transactions[&old.transaction_id], later), 5);
+        
+        let selected = pool.select_for_block(1, usize::MAX, later);
+        assert_eq!(selected[0].transaction_id, old.transaction_id);
+        assert_eq!(pool.stats.avg_wait_time, Duration::from_secs(45));
+        
+        // Without aging the fresh transaction would have gone first
+        let mut pool = TransactionPool::new(10, Duration::ZERO);
+        pool.insert(old.clone(), start).unwrap();
+        pool.insert(fresh.clone(), later).unwrap();
+        assert_eq!(pool.select_for_block(1, usize::MAX, later)[0].transaction_id, fresh.transaction_id);
+    }
+    
+    #[test]
+    fn test_block_selection_respects_dependencies_and_limits() {
+        let now = Instant::now();
+        let mut pool = TransactionPool::new(10, Duration::ZERO);
+        let parent = pool_entry(1, Vec::new(), now);
+        let child = pool_entry(9, vec![parent.transaction_id.clone()], now);
+        let grandchild = pool_entry(8, vec![child.transaction_id.clone()], now);
+        let independent = pool_entry(5, vec![TransactionId::new()], now);
+        for entry in [grandchild.clone(), child.clone(), independent.clone(), parent.clone()] {
+            pool.insert(entry, now).unwrap();
+        }
+        
+        // Dependencies outside the pool do not hold a transaction back
+        let ids = |entries: Vec<PoolTransaction>| entries.into_iter().map(|entry| entry.transaction_id).collect::<Vec<_>>();
+        assert_eq!(ids(pool.select_for_block(1, usize::MAX, now)), vec![independent.transaction_id.clone()]);
+        
+        // Byte limit fits two transactions
+        assert_eq!(ids(pool.select_for_block(10, 250, now)), vec![parent.transaction_id.clone(), child.transaction_id.clone()]);
+        assert_eq!(ids(pool.select_for_block(10, 250, now)), vec![grandchild.transaction_id.clone()]);
+        assert!(pool.select_for_block(10, 250, now).is_empty());
+        assert_eq!(pool.size, 0);
+        assert!(pool.priority_queue.is_empty());
+    }
+    
+    #[test]
+    fn test_transaction_details_summarize_votes_and_phases() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs: u64| t0 + Duration::from_secs(secs);
+        let settled = |domain: &str, secs: u64| (domain.to_string(), DomainSettlementState {
+            domain_id: domain.to_string(),
+            status: "settled".to_string(),
+            data: Vec::new(),
+            proof: Some(vec![1]),
+            timestamp: at(secs),
+        });
+        let transaction = StoredTransaction {
+            transaction_id: TransactionId(uuid::Uuid::new_v4()),
+            transaction_data: vec![1, 2, 3],
+            transaction_type: "test".to_string(),
+            source_domain: "domain1".to_string(),
+            target_domains: vec!["domain2".to_string(), "domain3".to_string()],
+            status: TransactionStatus::InSettlement,
+            consensus_state: ConsensusState {
+                phase: "commit".to_string(),
+                votes: [("v1", true), ("v2", false), ("v3", true), ("v0", false)]
+                    .into_iter()
+                    .map(|(voter, vote)| (voter.to_string(), vote))
+                    .collect(),
+                required_votes: 3,
+                result: Some(true),
+                proof: None,
+                started_at: at(1),
+                completed_at: Some(at(2)),
+            },
+            settlement_state: SettlementState {
+                settlement_id: Some("s-1".to_string()),
+                settlement_type: "atomic".to_string(),
+                domain_settlements: [settled("domain3", 4), settled("domain2", 5)].into_iter().collect(),
+                proof: None,
+                started_at: Some(at(3)),
+                completed_at: None,
+            },
+            created_at: t0,
+            updated_at: at(5),
+            block_height: Some(7),
+            block_hash: Some(vec![0xab; 2]),
+            metadata: HashMap::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        };
+        
+        let details = transaction.details(false);
+        assert_eq!(details.consensus.votes, VoteSummary {
+            approvals: 2,
+            rejections: 2,
+            rejected_by: vec!["v0".to_string(), "v2".to_string()],
+        });
+        assert!(details.consensus.vote_details.is_none());
+        assert_eq!(details.block_hash.as_deref(), Some("abab"));
+        assert_eq!(
+            details.settlement.domains.iter().map(|d| d.domain_id.as_str()).collect::<Vec<_>>(),
+            vec!["domain2", "domain3"]
+        );
+        assert_eq!(
+            details.timeline.iter().map(|t| t.phase.as_str()).collect::<Vec<_>>(),
+            vec!["created", "consensus_started", "consensus_completed", "settlement_started", "settled:domain3", "settled:domain2"]
+        );
+        assert_eq!(transaction.details(true).consensus.vote_details.unwrap().len(), 4);
+    }
+    
+    fn block_info(height: u64, timestamp: SystemTime) -> BlockInfo {
+        BlockInfo {
+            block_hash: vec![height as u8; 32],
+            height,
+            parent_hash: vec![height.saturating_sub(1) as u8; 32],
+            transaction_count: 1,
+            size: 100,
+            timestamp,
+            difficulty: 0,
+            nonce: 0,
+            merkle_root: Vec::new(),
+            state_root: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_avg_block_time_includes_gaps() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = BlockStorage::new(config, backend).await.unwrap();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        
+        // Second gap is a run of suppressed empty blocks
+        for (height, offset) in [(1, 0), (2, 1), (3, 11)] {
+            let info = block_info(height, t0 + Duration::from_secs(offset));
+            storage.store_block(info.block_hash.clone(), info).await.unwrap();
+        }
+        
+        let chain = storage.get_blockchain().await;
+        assert_eq!(chain.height, 3);
+        assert_eq!(chain.head, vec![3u8; 32]);
+        assert_eq!(chain.stats.total_blocks, 3);
+        assert_eq!(chain.stats.total_transactions, 3);
+        assert_eq!(chain.stats.avg_block_time, Duration::from_millis(5_500));
+    }
+    
+    #[tokio::test]
+    async fn test_store_block_validates_parent_and_records_forks() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = BlockStorage::new(config, backend).await.unwrap();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let block = |hash: u8, height: u64, parent: u8| BlockInfo {
+            block_hash: vec![hash; 32],
+            parent_hash: vec![parent; 32],
+            ..block_info(height, t0)
+        };
+        
+        // Normal extension
+        for info in [block_info(1, t0), block_info(2, t0)] {
+            let stored = storage.store_block(info.block_hash.clone(), info).await.unwrap();
+            assert!(stored.fork_of.is_none() && stored.orphaned.is_empty());
+        }
+        let chain = storage.get_blockchain().await;
+        assert_eq!((chain.genesis_block, chain.head, chain.height), (vec![1u8; 32], vec![2u8; 32], 2));
+        
+        // Orphan blocks and height gaps are rejected
+        assert!(storage.store_block(vec![0xa5; 32], block(0xa5, 5, 0x99)).await.is_err());
+        assert!(storage.store_block(vec![0xa4; 32], block(0xa4, 4, 2)).await.is_err());
+        assert!(storage.store_block(vec![0xa1; 32], block(0xa1, 1, 2)).await.is_err());
+        assert!(storage.get_block(&vec![0xa5; 32]).await.unwrap().is_none());
+        assert_eq!(storage.get_blockchain().await.stats.total_blocks, 2);
+        
+        // A second block at height 2 forks, both branches are kept
+        let stored = storage.store_block(vec![0xb2; 32], block(0xb2, 2, 1)).await.unwrap();
+        assert_eq!(stored.fork_of, Some(vec![2u8; 32]));
+        let stored = storage.store_block(vec![0xb3; 32], block(0xb3, 3, 0xb2)).await.unwrap();
+        assert!(stored.fork_of.is_none());
+        assert!(storage.get_block(&vec![2u8; 32]).await.unwrap().is_some());
+        assert!(storage.get_block(&vec![0xb2; 32]).await.unwrap().is_some());
+        let info = storage.get_chain_info().await;
+        assert_eq!((info.head, info.height), (hex::encode([2u8; 32]), 2));
+        assert_eq!((info.total_blocks, info.forks_detected, info.fork_heights), (4, 1, vec![2]));
+        
+        // Finalizing the other branch moves the head to its tip
+        let orphaned = storage.finalize_block(&vec![0xb3; 32]).await;
+        assert_eq!(orphaned.iter().map(|block| block.block_hash.clone()).collect::<Vec<_>>(), vec![vec![2u8; 32]]);
+        let chain = storage.get_blockchain().await;
+        assert_eq!((chain.head, chain.height, chain.finalized_height), (vec![0xb3; 32], 3, Some(3)));
+        let height_index = storage.height_index.read().await;
+        assert_eq!(height_index.get(&2), Some(&vec![0xb2; 32]));
+        assert_eq!(height_index.get(&3), Some(&vec![0xb3; 32]));
+    }
+    
+    #[tokio::test]
+    async fn test_finality_certificate_orphans_competing_blocks() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = GlobalStorage::new(config).await.unwrap();
+        let mut orphans = storage.subscribe_orphaned_blocks();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let competitor = |hash: u8, height: u64, parent: u8| BlockInfo {
+            block_hash: vec![hash; 32],
+            parent_hash: vec![parent; 32],
+            ..block_info(height, t0)
+        };
+        
+        // A fork at height 2 whose branch is longer than the canonical one
+        for info in [block_info(1, t0), block_info(2, t0), competitor(0xb2, 2, 1), competitor(0xb3, 3, 0xb2)] {
+            storage.store_block(info.block_hash.clone(), info).await.unwrap();
+        }
+        let forked = TransactionId::new();
+        storage.store_transaction(stored_transaction(&forked)).await.unwrap();
+        storage.assign_block_transactions(2, vec![0xb2; 32], &[forked.clone()], false).await.unwrap();
+        let chain = storage.get_blockchain().await;
+        assert_eq!(chain.head, vec![2u8; 32], "the head does not switch to an uncertified branch");
+        assert_eq!(chain.finalized_height, None);
+        let info = storage.get_chain_info().await;
+        assert_eq!((info.forks_detected, info.fork_heights), (1, vec![2]));
+        
+        storage.store_finality_certificate(FinalityCertificate {
+            height: 2,
+            block_hash: hex::encode([2u8; 32]),
+            signatures: Vec::new(),
+            validator_set_hash: String::new(),
+            timestamp: chrono::Utc::now(),
+            state_root: String::new(),
+        }).await.unwrap();
+        
+        // The losing block and its child are orphaned and release their transactions
+        let orphan = orphans.recv().await.unwrap();
+        assert_eq!(orphan.block.block_hash, vec![0xb2; 32]);
+        assert_eq!(orphan.transactions, vec![forked]);
+        assert_eq!(orphans.recv().await.unwrap().block.block_hash, vec![0xb3; 32]);
+        assert!(storage.get_transactions_by_height(2).await.unwrap().is_empty());
+        assert!(storage.get_block(&vec![0xb3; 32]).await.unwrap().is_none());
+        
+        let chain = storage.get_blockchain().await;
+        assert_eq!((chain.head, chain.height), (vec![2u8; 32], 2));
+        assert_eq!(chain.finalized_height, Some(2));
+        assert_eq!(chain.stats.total_blocks, 2);
+        assert!(storage.store_block(vec![0xc2; 32], competitor(0xc2, 2, 1)).await.is_err());
+    }
+    
+    fn stored_transaction(id: &TransactionId) -> StoredTransaction {
+        StoredTransaction {
+            transaction_id: id.clone(),
+            transaction_data: vec![1],
+            transaction_type: "test".to_string(),
+            source_domain: "domain1".to_string(),
+            target_domains: vec!["domain2".to_string()],
+            status: TransactionStatus::Pending,
+            consensus_state: ConsensusState {
+                phase: "commit".to_string(),
+                votes: HashMap::new(),
+                required_votes: 1,
+                result: Some(true),
+                proof: None,
+                started_at: SystemTime::now(),
+                completed_at: None,
+            },
+            settlement_state: SettlementState {
+                settlement_id: None,
+                settlement_type: "atomic".to_string(),
+                domain_settlements: HashMap::new(),
+                proof: None,
+                started_at: None,
+                completed_at: None,
+            },
+            created_at: SystemTime::now(),
+            updated_at: SystemTime::now(),
+            block_height: None,
+            block_hash: None,
+            metadata: HashMap::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_transaction_receipt_after_certificate() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = GlobalStorage::new(config).await.unwrap();
+        let ids: Vec<TransactionId> = (0..3).map(|_| TransactionId::new()).collect();
+        for id in &ids {
+            storage.store_transaction(stored_transaction(id)).await.unwrap();
+        }
+        
+        let merkle_root = receipt::transaction_root(&ids);
+        let block_hash = receipt::block_hash(&[0u8; 32], 1, &merkle_root);
+        let info = BlockInfo {
+            block_hash: block_hash.clone(),
+            merkle_root,
+            transaction_count: ids.len() as u32,
+            ..block_info(1, SystemTime::now())
+        };
+        storage.store_block(block_hash.clone(), info).await.unwrap();
+        storage.assign_block_transactions(1, block_hash.clone(), &ids, false).await.unwrap();
+        assert_eq!(*storage.metrics.consistency_warnings.read().await, 0);
+        assert!(storage.get_transaction_receipt(&ids[1]).await.unwrap().is_none(), "no receipt before finality");
+        
+        let certificate = FinalityCertificate {
+            height: 1,
+            block_hash: hex::encode(&block_hash),
+            signatures: Vec::new(),
+            validator_set_hash: String::new(),
+            timestamp: chrono::Utc::now(),
+            state_root: String::new(),
+        };
+        storage.store_finality_certificate(certificate.clone()).await.unwrap();
+        
+        let receipt = storage.get_transaction_receipt(&ids[1]).await.unwrap().unwrap();
+        assert_eq!(receipt.block_height, 1);
+        assert_eq!(receipt.finalized_at, certificate.timestamp);
+        assert!(receipt.settlement_proof.is_none() && receipt.domains_confirmed.is_empty());
+        assert!(receipt.verify(&certificate));
+        assert!(storage.get_transaction_receipt(&TransactionId::new()).await.unwrap().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_assign_block_rejects_unknown_transactions_atomically() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = TransactionStorage::new(config.clone(), backend.clone()).await.unwrap();
+        let known = TransactionId::new();
+        let unknown = TransactionId::new();
+        storage.store_transaction(stored_transaction(&known)).await.unwrap();
+        
+        let block_hash = vec![1u8; 32];
+        let ids = [known.clone(), unknown.clone(), unknown.clone()];
+        match storage.assign_block(1, block_hash.clone(), &ids, false).await {
+            Err(GarpError::Transaction(TransactionError::UnknownTransactions(missing))) => {
+                assert_eq!(missing, vec![unknown.clone()]);
+            }
+            other => panic!("expected unknown transactions error, got {:?}", other),
+        }
+        
+        // Nothing was assigned, not even the known transaction
+        let tx = storage.get_transaction(&known).await.unwrap().unwrap();
+        assert_eq!((tx.block_height, tx.status), (None, TransactionStatus::Pending));
+        assert!(storage.get_transactions_by_height(1).await.unwrap().is_empty());
+        assert!(storage.get_block_transactions(1, &block_hash).await.unwrap().is_empty());
+        assert!(storage.transaction_index.read().await.is_empty());
+        
+        // After a restart only the backend has the transaction
+        let restarted = TransactionStorage::new(config, backend).await.unwrap();
+        assert!(restarted.get_transaction(&known).await.unwrap().is_none());
+        assert!(restarted.assign_block(1, block_hash.clone(), &[known.clone()], false).await.is_err());
+        assert!(restarted.assign_block(1, block_hash.clone(), &ids, true).await.is_err());
+        assert!(restarted.get_transaction(&known).await.unwrap().is_none());
+        
+        restarted.assign_block(1, block_hash.clone(), &[known.clone()], true).await.unwrap();
+        let tx = restarted.get_transaction(&known).await.unwrap().unwrap();
+        assert_eq!((tx.block_height, tx.status), (Some(1), TransactionStatus::Settled));
+        assert_eq!(restarted.get_block_transactions(1, &block_hash).await.unwrap(), vec![known]);
+    }
+    
+    #[tokio::test]
+    async fn test_existence_filter_tracks_stored_transactions() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = TransactionStorage::new(config.clone(), backend.clone()).await.unwrap();
+        let stored = TransactionId::new();
+        let absent = TransactionId::new();
+        storage.store_transaction(stored_transaction(&stored)).await.unwrap();
+        assert!(storage.might_exist(&stored));
+        assert!(!storage.might_exist(&absent));
+        assert!(storage.get_transaction(&absent).await.unwrap().is_none());
+        
+        // Transactions recovered from the backend are added too
+        let restarted = TransactionStorage::new(config, backend).await.unwrap();
+        assert!(!restarted.might_exist(&stored));
+        restarted.assign_block(1, vec![1u8; 32], &[stored.clone()], true).await.unwrap();
+        assert!(restarted.might_exist(&stored));
+        
+        restarted.rebuild_existence_filter().await;
+        assert!(restarted.might_exist(&stored));
+        assert!(!restarted.might_exist(&absent));
+        assert!(restarted.get_transaction(&stored).await.unwrap().is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_assign_block_warns_on_transaction_count_mismatch() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let storage = GlobalStorage::new(config).await.unwrap();
+        let ids: Vec<TransactionId> = (0..2).map(|_| TransactionId::new()).collect();
+        for id in &ids {
+            storage.store_transaction(stored_transaction(id)).await.unwrap();
+        }
+        
+        // The block declares a single transaction
+        let info = block_info(1, SystemTime::now());
+        storage.store_block(info.block_hash.clone(), info.clone()).await.unwrap();
+        storage.assign_block_transactions(1, info.block_hash.clone(), &ids[..1], false).await.unwrap();
+        assert_eq!(*storage.metrics.consistency_warnings.read().await, 0);
+        
+        storage.assign_block_transactions(1, info.block_hash.clone(), &ids, false).await.unwrap();
+        assert_eq!(*storage.metrics.consistency_warnings.read().await, 1);
+        assert_eq!(storage.get_transactions_by_height(1).await.unwrap(), ids);
+    }
+    
+    fn domain_state(version: u64, data: u8) -> DomainState {
+        DomainState {
+            domain_id: "domain1".to_string(),
+            version,
+            state_data: vec![data],
+            state_hash: Vec::new(),
+            last_block_height: 0,
+            last_block_hash: Vec::new(),
+            pending_transactions: Vec::new(),
+            last_updated: SystemTime::now(),
+            metadata: HashMap::new(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_view_changes_survive_restart() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend: Arc<dyn StorageBackend> = Arc::new(MemoryStorageBackend::new());
+        let storage = ConsensusStorage::new(config.clone(), backend.clone()).await.unwrap();
+        
+        for view in [1u64, 2, 10] {
+            storage.store_view_change(ViewChangeRecord {
+                view,
+                previous_view: view - 1,
+                reason: "liveness timeout".to_string(),
+                initiator: "node-1".to_string(),
+                participants: vec!["node-1".to_string()],
+                timestamp: SystemTime::now(),
+                leader: None,
+                previous_view_duration_ms: 5000,
+            }).await.unwrap();
+        }
+        
+        let restarted = ConsensusStorage::new(config, backend).await.unwrap();
+        let views: Vec<u64> = restarted.get_view_changes(2, 10).await.unwrap().iter().map(|r| r.view).collect();
+        assert_eq!(views, vec![2, 10]);
+        assert!(restarted.get_view_changes(3, 9).await.unwrap().is_empty());
+        assert!(restarted.get_view_changes(5, 1).await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_reputation_scores_survive_restart() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend: Arc<dyn StorageBackend> = Arc::new(MemoryStorageBackend::new());
+        let storage = MetadataStorage::new(config.clone(), backend.clone()).await.unwrap();
+        
+        let tracker = garp_common::ReputationTracker::new(config.reputation.clone());
+        let mut score = tracker.get("domain-1").await;
+        score.score = 12.5;
+        storage.store_reputation(score.clone()).await.unwrap();
+        score.score = 7.5;
+        storage.store_reputation(score).await.unwrap();
+        
+        let restarted = MetadataStorage::new(config, backend).await.unwrap();
+        let scores = restarted.list_reputation().await.unwrap();
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].subject, "domain-1");
+        assert_eq!(scores[0].score, 7.5);
+    }
+    
+    #[tokio::test]
+    async fn test_domain_state_versioned_reads() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = StateStorage::new(config, backend).await.unwrap();
+        let domain_id = "domain1".to_string();
+        
+        let v1 = storage.update_domain_state(&domain_id, domain_state(0, 1)).await.unwrap();
+        let v2 = storage.update_domain_state(&domain_id, domain_state(0, 2)).await.unwrap();
+        let v5 = storage.update_domain_state(&domain_id, domain_state(5, 5)).await.unwrap();
+        assert_eq!((v1, v2, v5), (1, 2, 5));
+        
+        let latest = storage.get_domain_state(&domain_id, None).await.unwrap().unwrap();
+        assert_eq!(latest.state_data, vec![5]);
+        let as_of_4 = storage.get_domain_state(&domain_id, Some(4)).await.unwrap().unwrap();
+        assert_eq!(as_of_4.version, 2);
+        assert!(storage.get_domain_state(&domain_id, Some(0)).await.unwrap().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_state_root_tracks_domain_updates() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = StateStorage::new(config.clone(), backend.clone()).await.unwrap();
+        let state = |domain_id: &str, data: &[u8]| DomainState {
+            domain_id: domain_id.to_string(),
+            version: 0,
+            state_data: data.to_vec(),
+            state_hash: Vec::new(),
+            last_block_height: 0,
+            last_block_hash: Vec::new(),
+            pending_transactions: Vec::new(),
+            last_updated: SystemTime::UNIX_EPOCH,
+            metadata: HashMap::new(),
+        };
+        
+        let empty_root = storage.state_root().await.unwrap();
+        storage.update_domain_state(&"b".to_string(), state("b", b"two")).await.unwrap();
+        storage.update_domain_state(&"a".to_string(), state("a", b"one")).await.unwrap();
+        let root = storage.state_root().await.unwrap();
+        assert_ne!(root, empty_root);
+        let stored = storage.get_domain_state(&"a".to_string(), None).await.unwrap().unwrap();
+        assert_eq!(stored.state_hash, state_root::domain_state_hash(b"one"));
+        
+        let record = storage.commit_state_root(4).await.unwrap();
+        assert_eq!(record.root, root);
+        storage.update_domain_state(&"a".to_string(), state("a", b"changed")).await.unwrap();
+        assert_ne!(storage.state_root().await.unwrap(), root);
+        
+        // Proofs are served against the root committed at the height
+        let proof = storage.state_root_proof(4, &"a".to_string()).await.unwrap();
+        assert!(proof.verify(&root));
+        assert!(storage.state_root_proof(5, &"a".to_string()).await.is_none());
+        
+        // Roots survive a restart
+        let reloaded = StateStorage::new(config, backend).await.unwrap();
+        assert_eq!(reloaded.get_state_root_record(4).await.unwrap().root, root);
+        
+        let mut snapshot = storage.create_snapshot(6, vec![6; 32]).await.unwrap();
+        assert_eq!(snapshot.state_root, storage.state_root().await.unwrap());
+        reloaded.load_snapshot(snapshot.clone()).await.unwrap();
+        assert_eq!(reloaded.state_root().await.unwrap(), snapshot.state_root);
+        
+        snapshot.state_root = root;
+        assert!(reloaded.load_snapshot(snapshot).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_snapshots_persist_prune_and_restore() {
+        let mut config = GlobalSyncConfig::default();
+        config.consensus.snapshot_retention = 2;
+        let config = Arc::new(config);
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = StateStorage::new(config.clone(), backend.clone()).await.unwrap();
+        let domain_id = "domain1".to_string();
+        let state = |data: &[u8]| DomainState {
+            domain_id: domain_id.clone(),
+            version: 0,
+            state_data: data.to_vec(),
+            state_hash: Vec::new(),
+            last_block_height: 0,
+            last_block_hash: Vec::new(),
+            pending_transactions: Vec::new(),
+            last_updated: SystemTime::UNIX_EPOCH,
+            metadata: HashMap::new(),
+        };
+        
+        for (height, data) in [(10, b"a"), (20, b"b"), (30, b"c")] {
+            storage.update_domain_state(&domain_id, state(data)).await.unwrap();
+            let snapshot = storage.create_snapshot(height, vec![height as u8; 32]).await.unwrap();
+            assert_eq!(snapshot.checksum, StateSnapshot::checksum_of(&snapshot.snapshot_data));
+        }
+        assert_eq!(storage.snapshot_heights().await, vec![20, 30]);
+        assert_eq!(backend.list_keys(STATE_SNAPSHOT_KEY_PREFIX).await.unwrap().len(), 2);
+        
+        // A restarted node finds the snapshots and restores from one
+        let restarted = StateStorage::new(config, backend.clone()).await.unwrap();
+        assert!(restarted.is_state_empty().await);
+        assert_eq!(restarted.snapshot_heights().await, vec![20, 30]);
+        restarted.restore_from_snapshot(20).await.unwrap();
+        let restored = restarted.get_domain_state(&domain_id, None).await.unwrap().unwrap();
+        assert_eq!(restored.state_data, b"b".to_vec());
+        assert!(restarted.restore_from_snapshot(10).await.is_err());
+        
+        // Corrupted snapshot data fails the checksum
+        let mut corrupted = restarted.latest_snapshot().await.unwrap();
+        corrupted.snapshot_data.push(b' ');
+        let error = restarted.load_snapshot(corrupted).await.unwrap_err();
+        assert!(error.to_string().contains("checksum"));
+    }
+    
+    #[tokio::test]
+    async fn test_state_sync_rejects_stale_versions() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = StateStorage::new(config, backend).await.unwrap();
+        let domains = vec!["domain1".to_string(), "domain2".to_string()];
+        let tx_id = TransactionId::new();
+        
+        let applied = storage.apply_state_sync(&domains, "balance", b"10", 2, &tx_id, 7).await.unwrap();
+        assert!(matches!(applied, StateSyncOutcome::Applied(ref transitions) if transitions.len() == 2));
+        let (version, root_hash) = storage.get_global_state_version().await;
+        assert_eq!(version, 1);
+        assert!(!root_hash.is_empty());
+        
+        // Equal and older versions conflict and leave the state untouched
+        for stale in [2, 1] {
+            match storage.apply_state_sync(&domains, "balance", b"5", stale, &tx_id, 8).await.unwrap() {
+                StateSyncOutcome::VersionConflict { domain_id, stored_version } => {
+                    assert_eq!(domain_id, "domain1");
+                    assert_eq!(stored_version, 2);
+                }
+                other => panic!("expected a version conflict, got {:?}", other),
+            }
+        }
+        assert_eq!(storage.get_global_state_version().await.0, 1);
+        
+        storage.apply_state_sync(&domains[..1], "balance", b"12", 3, &tx_id, 9).await.unwrap();
+        storage.apply_state_sync(&domains[..1], "other", b"x", 1, &tx_id, 9).await.unwrap();
+        let history = storage.get_state_history(&domains[0], "balance", 10).await.unwrap();
+        let values: Vec<&[u8]> = history.iter().map(|t| t.to_state.as_slice()).collect();
+        assert_eq!(values, vec![b"12".as_slice(), b"10".as_slice()]);
+        assert_eq!(history[0].from_state, b"10".to_vec());
+        assert_eq!(storage.get_state_history(&domains[0], "balance", 1).await.unwrap().len(), 1);
+        assert_eq!(storage.get_state_history(&domains[1], "balance", 10).await.unwrap().len(), 1);
+        
+        let state = storage.get_domain_state(&domains[0], None).await.unwrap().unwrap();
+        let entries: BTreeMap<String, StateEntry> = serde_json::from_slice(&state.state_data).unwrap();
+        assert_eq!(entries["balance"], StateEntry { value: b"12".to_vec(), version: 3 });
+        
+        // Golden vector for the state hash input. It matches the encoding
+        // used before the canonical one, so stored state hashes still hold.
+        let golden = r#"{"balance":{"value":[49,50],"version":3},"other":{"value":[120],"version":1}}"#;
+        assert_eq!(state.state_data, golden.as_bytes());
+        assert_eq!(serde_json::to_vec(&entries).unwrap(), state.state_data);
+    }
+    
+    fn validator(id: &str, stake: u64) -> ValidatorInfo {
+        ValidatorInfo {
+            validator_id: id.to_string(),
+            public_key: Vec::new(),
+            stake,
+            voting_power: stake,
+            status: ValidatorStatus::Active,
+            performance: ValidatorPerformance {
+                blocks_proposed: 0,
+                blocks_validated: 0,
+                votes_cast: 0,
+                missed_votes: 0,
+                uptime_percentage: 100.0,
+                performance_score: 1.0,
+            },
+            joined_at: SystemTime::now(),
+            last_activity: SystemTime::now(),
+            joining_epoch: None,
+            leaving_epoch: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_fees_distributed_by_stake() {
+        let config = Arc::new(GlobalSyncConfig::default());
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = StateStorage::new(config, backend).await.unwrap();
+        storage.replace_validator_set(1, vec![validator("v1", 100), validator("v2", 200)]).await.unwrap();
+        
+        // 100 splits 33/66; the rounding unit goes to the larger stake
+        let shares = storage.distribute_validator_fees(100).await.unwrap();
+        assert_eq!(shares, vec![("v2".to_string(), 67), ("v1".to_string(), 33)]);
+        assert_eq!(storage.get_fee_earnings(&"v1".to_string()).await.unwrap(), 33);
+        assert_eq!(storage.get_validator(&"v2".to_string()).await.unwrap().unwrap().stake, 267);
+        
+        // Earned fees survive an epoch's validator set replacement
+        storage.replace_validator_set(2, vec![validator("v1", 100)]).await.unwrap();
+        assert_eq!(storage.get_validator(&"v1".to_string()).await.unwrap().unwrap().stake, 133);
+        
+        // The validator that left stays on record but no longer earns fees
+        let v2 = storage.get_validator(&"v2".to_string()).await.unwrap().unwrap();
+        assert_eq!((v2.status, v2.joining_epoch, v2.leaving_epoch), (ValidatorStatus::Inactive, Some(1), Some(2)));
+        let shares = storage.distribute_validator_fees(10).await.unwrap();
+        assert_eq!(shares, vec![("v1".to_string(), 10)]);
+    }
+    
+    #[tokio::test]
+    async fn test_domain_state_version_pruning() {
+        let mut config = GlobalSyncConfig::default();
+        config.database.max_retained_versions = 2;
+        let backend = Arc::new(MemoryStorageBackend::new());
+        let storage = StateStorage::new(Arc::new(config), backend).await.unwrap();
+        let domain_id = "domain1".to_string();
+        
+        for i in 0..4 {
+            storage.update_domain_state(&domain_id, domain_state(0, i)).await.unwrap();
+        }
+        assert_eq!(storage.prune_domain_state_versions().await, 2);
+        assert!(storage.get_domain_state(&domain_id, Some(2)).await.unwrap().is_none());
+        assert_eq!(storage.get_domain_state(&domain_id, Some(3)).await.unwrap().unwrap().state_data, vec![2]);
+    }
+    
+    fn replication_target(target_id: &str, target_type: ReplicationTargetType) -> ReplicationTarget {
+        ReplicationTarget {
+            target_id: target_id.to_string(),
+            address: format!("{}:5432", target_id),
+            target_type,
+            mode: ReplicationMode::Asynchronous,
+            config: ReplicationConfig {
+                batch_size: 100,
+                sync_interval: Duration::from_secs(1),
+                retry_attempts: 3,
+                timeout: Duration::from_secs(5),
+                compression: false,
+                encryption: false,
+            },
+            status: TargetStatus::Online,
+            last_sync: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_replication_lag_alerts() {
+        let mut config = GlobalSyncConfig::default();
+        config.database.replication.max_acceptable_lag_ms = 1_000;
+        config.database.replication.critical_lag_threshold_ms = 10_000;
+        config.database.replication.max_buffered_alerts = 3;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let manager = ReplicationManager::new(Arc::new(config), event_tx).await.unwrap();
+        manager.add_target(replication_target("primary", ReplicationTargetType::Primary)).await;
+        manager.add_target(replication_target("secondary", ReplicationTargetType::Secondary)).await;
+        assert!(manager.record_lag("unknown", Duration::ZERO).await.is_err());
+        
+        assert!(manager.check_replication_health().await.is_empty());
+        
+        manager.record_lag("primary", Duration::from_secs(2)).await.unwrap();
+        manager.record_lag("secondary", Duration::from_secs(60)).await.unwrap();
+        let alerts = manager.check_replication_health().await;
+        let summary: Vec<(&str, ReplicationAlertSeverity, Duration)> = alerts.iter()
+            .map(|a| (a.target_id.as_str(), a.severity, a.threshold))
+            .collect();
+        assert_eq!(summary, vec![
+            ("primary", ReplicationAlertSeverity::Warning, Duration::from_secs(1)),
+            ("secondary", ReplicationAlertSeverity::Critical, Duration::from_secs(10)),
+        ]);
+        // Only a lagging primary degrades storage
+        assert!(manager.lagging_primaries().await.is_empty());
+        assert!(event_rx.try_recv().is_err());
+        
+        manager.record_lag("primary", Duration::from_secs(30)).await.unwrap();
+        manager.check_replication_health().await;
+        manager.check_replication_health().await;
+        assert_eq!(manager.lagging_primaries().await, vec!["primary".to_string()]);
+        assert!(matches!(event_rx.try_recv(), Ok(StorageEvent::StorageError(message)) if message.contains("primary")));
+        assert!(event_rx.try_recv().is_err(), "a primary that stays behind is reported once");
+        
+        // The buffer keeps the newest alerts
+        let buffered = manager.get_alerts().await;
+        assert_eq!(buffered.len(), 3);
+        assert!(buffered.iter().all(|a| a.severity == ReplicationAlertSeverity::Critical));
+        
+        manager.record_lag("primary", Duration::ZERO).await.unwrap();
+        manager.check_replication_health().await;
+        assert!(manager.lagging_primaries().await.is_empty());
+    }
+    
     #[tokio::test]
     async fn test_memory_storage_backend() {
         let backend = MemoryStorageBackend::new();
@@ -3206,4 +7745,130 @@ mod tests {
         backend.delete("key1").await.unwrap();
         assert!(!backend.exists("key1").await.unwrap());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_memory_storage_keys_expire_after_ttl() {
+        let backend = MemoryStorageBackend::new();
+        backend.set_with_ttl("session", vec![1], Duration::from_millis(20)).await.unwrap();
+        backend.set_with_ttl("renewed", vec![2], Duration::from_millis(20)).await.unwrap();
+        backend.set("renewed", vec![3]).await.unwrap();
+        backend.set_with_ttl("long_lived", vec![4], Duration::from_secs(3600)).await.unwrap();
+        assert_eq!(backend.get("session").await.unwrap(), Some(vec![1]));
+        
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        
+        // Expired keys are hidden before the sweep removes them
+        assert_eq!(backend.get("session").await.unwrap(), None);
+        assert!(!backend.exists("session").await.unwrap());
+        assert_eq!(backend.list_keys("").await.unwrap().len(), 2);
+        assert_eq!(backend.get_stats().await.unwrap().total_keys, 3);
+        
+        let removed = MemoryStorageBackend::remove_expired(&backend.data, &backend.expiry, Instant::now()).await;
+        assert_eq!(removed, 1);
+        assert_eq!(backend.get_stats().await.unwrap().total_keys, 2);
+        assert_eq!(backend.get("renewed").await.unwrap(), Some(vec![3]));
+        assert_eq!(backend.get("long_lived").await.unwrap(), Some(vec![4]));
+    }
+    
+    #[tokio::test]
+    async fn test_cache_entries_expire_after_ttl() {
+        let cache = CacheManager::new(Arc::new(GlobalSyncConfig::default())).await.unwrap();
+        cache.create_layer("blocks", CacheLayerConfig {
+            max_entries: 100,
+            max_size: 1 << 20,
+            default_ttl: Some(Duration::from_millis(20)),
+            eviction_policy: EvictionPolicy::TTL,
+        }).await;
+        cache.put("blocks", "default_ttl", vec![1], None).await.unwrap();
+        cache.put("blocks", "explicit_ttl", vec![2], Some(Duration::from_secs(3600))).await.unwrap();
+        assert!(cache.put("missing", "key", vec![3], None).await.is_err());
+        
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(cache.get("blocks", "default_ttl").await, None);
+        assert_eq!(cache.get("blocks", "explicit_ttl").await, Some(vec![2]));
+        
+        cache.cleanup().await.unwrap();
+        let layers = cache.cache_layers.read().await;
+        let layer = &layers["blocks"];
+        assert!(!layer.entries.contains_key("default_ttl"));
+        assert_eq!(layer.stats.evictions, 1);
+        assert_eq!(layer.stats.current_entries, 1);
+        assert_eq!(layer.stats.current_size, 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_storage_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = GlobalSyncConfig::default();
+        config.database.url = format!("sqlite://{}", dir.path().join("kv.db").display());
+        config.database.min_connections = 1;
+        let backend = SqliteStorageBackend::new(Arc::new(config)).await.unwrap();
+
+        backend.set("tx_1", vec![1]).await.unwrap();
+        backend.set("tx_1", vec![2]).await.unwrap();
+        backend.set("txa1", vec![3]).await.unwrap();
+        assert_eq!(backend.get("tx_1").await.unwrap(), Some(vec![2]));
+
+        // `_` in the prefix must not act as a wildcard
+        assert_eq!(backend.list_keys("tx_").await.unwrap(), vec!["tx_1".to_string()]);
+
+        backend.create_snapshot("snap").await.unwrap();
+        backend.batch(vec![
+            BatchOperation::Set { key: "tx_2".to_string(), value: vec![4] },
+            BatchOperation::Delete { key: "tx_1".to_string() },
+        ]).await.unwrap();
+        assert!(!backend.exists("tx_1").await.unwrap());
+        assert_eq!(backend.get_stats().await.unwrap().total_keys, 2);
+
+        backend.restore_snapshot("snap").await.unwrap();
+        assert_eq!(backend.get("tx_1").await.unwrap(), Some(vec![2]));
+        assert!(!backend.exists("tx_2").await.unwrap());
+    }
+    /// Needs a scratch PostgreSQL database, as restores rewrite the whole
+    /// store: `GARP_TEST_POSTGRES_URL=postgresql://... cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_postgres_restore_to_timestamp() {
+        let Ok(url) = std::env::var("GARP_TEST_POSTGRES_URL") else {
+            return;
+        };
+        let mut config = GlobalSyncConfig::default();
+        config.database.url = url;
+        config.database.min_connections = 1;
+        let backend = PostgresStorageBackend::new(Arc::new(config)).await.unwrap();
+        
+        backend.set("pitr:a", vec![1]).await.unwrap();
+        backend.set("pitr:b", vec![1]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let before_batch = SystemTime::now();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        backend.batch(vec![
+            BatchOperation::Set { key: "pitr:a".to_string(), value: vec![2] },
+            BatchOperation::Set { key: "pitr:c".to_string(), value: vec![2] },
+            BatchOperation::Delete { key: "pitr:b".to_string() },
+        ]).await.unwrap();
+        
+        backend.restore_to_timestamp(before_batch).await.unwrap();
+        assert_eq!(backend.get("pitr:a").await.unwrap(), Some(vec![1]));
+        assert_eq!(backend.get("pitr:b").await.unwrap(), Some(vec![1]));
+        assert!(!backend.exists("pitr:c").await.unwrap());
+        
+        // The rollback is part of history, so restoring to now keeps it
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        backend.restore_to_timestamp(SystemTime::now()).await.unwrap();
+        assert_eq!(backend.get("pitr:a").await.unwrap(), Some(vec![1]));
+        assert!(!backend.exists("pitr:c").await.unwrap());
+        
+        // Pruning keeps what a restore to the cutoff needs
+        let cutoff = SystemTime::now();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(backend.prune_history(cutoff).await.unwrap() > 0);
+        backend.restore_to_timestamp(cutoff).await.unwrap();
+        assert_eq!(backend.get("pitr:a").await.unwrap(), Some(vec![1]));
+        assert_eq!(backend.get("pitr:b").await.unwrap(), Some(vec![1]));
+        
+        // History beyond the retention period is gone
+        let too_old = SystemTime::now() - Duration::from_secs(8 * 24 * 60 * 60);
+        assert!(backend.restore_to_timestamp(too_old).await.is_err());
+    }
+}
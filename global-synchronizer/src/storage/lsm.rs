@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use garp_common::{GarpError, GarpResult};
+
+use super::{BatchOperation, StorageBackend, StorageStats};
+use crate::config::GlobalSyncConfig;
+
+/// Sorted run of entries; `None` is a tombstone
+type Table = BTreeMap<String, Option<Vec<u8>>>;
+
+fn entry_size(key: &str, value: &Option<Vec<u8>>) -> usize {
+    key.len() + value.as_ref().map_or(0, |v| v.len())
+}
+
+fn table_size(table: &Table) -> usize {
+    table.iter().map(|(key, value)| entry_size(key, value)).sum()
+}
+
+#[derive(Clone, Default)]
+struct LsmState {
+    /// Mutable memtable
+    memtable: Table,
+
+    /// Approximate memtable size in bytes
+    memtable_bytes: usize,
+
+    /// Immutable SSTables, oldest first
+    sstables: Vec<Table>,
+}
+
+impl LsmState {
+    /// Newest entry for `key`, including tombstones
+    fn lookup(&self, key: &str) -> Option<&Option<Vec<u8>>> {
+        self.memtable.get(key)
+            .or_else(|| self.sstables.iter().rev().find_map(|table| table.get(key)))
+    }
+
+    /// Live entries with `prefix`, newest version of each key
+    fn scan(&self, prefix: &str) -> BTreeMap<String, Vec<u8>> {
+        let mut merged: Table = BTreeMap::new();
+        for table in self.sstables.iter().chain(std::iter::once(&self.memtable)) {
+            for (key, value) in table.range(prefix.to_string()..).take_while(|(key, _)| key.starts_with(prefix)) {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+        merged.into_iter().filter_map(|(key, value)| value.map(|v| (key, v))).collect()
+    }
+
+    fn write(&mut self, key: String, value: Option<Vec<u8>>) {
+        let added = entry_size(&key, &value);
+        if let Some(previous) = self.memtable.insert(key.clone(), value) {
+            self.memtable_bytes -= entry_size(&key, &previous);
+        }
+        self.memtable_bytes += added;
+    }
+}
+
+/// In-memory log-structured merge tree for tests and development.
+///
+/// Writes go to a sorted memtable that is frozen into an immutable SSTable
+/// once it exceeds `memtable_max_bytes`. Reads scan the memtable and then the
+/// SSTables newest first, so deletes are recorded as tombstones until
+/// compaction merges them away. After each flush, the newest run of SSTables
+/// smaller than `compaction_threshold_bytes` is merged into one.
+pub struct LsmStorageBackend {
+    state: Arc<RwLock<LsmState>>,
+    snapshots: Arc<RwLock<HashMap<String, LsmState>>>,
+    memtable_max_bytes: usize,
+    compaction_threshold_bytes: usize,
+    read_ops: AtomicU64,
+    write_ops: AtomicU64,
+    delete_ops: AtomicU64,
+}
+
+impl LsmStorageBackend {
+    pub fn new(config: Arc<GlobalSyncConfig>) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(LsmState::default())),
+            snapshots: Arc::new(RwLock::new(HashMap::new())),
+            memtable_max_bytes: config.database.lsm.memtable_max_bytes.max(1),
+            compaction_threshold_bytes: config.database.lsm.compaction_threshold_bytes,
+            read_ops: AtomicU64::new(0),
+            write_ops: AtomicU64::new(0),
+            delete_ops: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of immutable SSTables
+    pub async fn sstable_count(&self) -> usize {
+        self.state.read().await.sstables.len()
+    }
+
+    /// Flush the memtable if it is over the limit, then compact
+    fn maybe_flush(&self, state: &mut LsmState) {
+        if state.memtable_bytes < self.memtable_max_bytes {
+            return;
+        }
+        let table = std::mem::take(&mut state.memtable);
+        debug!("Flushing LSM memtable ({} entries, {} bytes)", table.len(), state.memtable_bytes);
+        state.memtable_bytes = 0;
+        state.sstables.push(table);
+        self.compact(state);
+    }
+
+    /// Merge the newest run of SSTables below the compaction threshold.
+    /// Tombstones are dropped when the run reaches the oldest table, since
+    /// there is nothing beneath it left to shadow.
+    fn compact(&self, state: &mut LsmState) {
+        let run_start = state.sstables.iter()
+            .rposition(|table| table_size(table) >= self.compaction_threshold_bytes)
+            .map_or(0, |i| i + 1);
+        if state.sstables.len() - run_start < 2 {
+            return;
+        }
+
+        let run = state.sstables.split_off(run_start);
+        let tables = run.len();
+        let mut merged = Table::new();
+        for table in run {
+            merged.extend(table);
+        }
+        if run_start == 0 {
+            merged.retain(|_, value| value.is_some());
+        }
+        debug!("Compacted {} LSM SSTables into one of {} entries", tables, merged.len());
+        state.sstables.push(merged);
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageBackend for LsmStorageBackend {
+    async fn get(&self, key: &str) -> GarpResult<Option<Vec<u8>>> {
+        self.read_ops.fetch_add(1, Ordering::Relaxed);
+        let state = self.state.read().await;
+        Ok(state.lookup(key).cloned().flatten())
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>) -> GarpResult<()> {
+        self.write_ops.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.state.write().await;
+        state.write(key.to_string(), Some(value));
+        self.maybe_flush(&mut state);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> GarpResult<()> {
+        self.delete_ops.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.state.write().await;
+        state.write(key.to_string(), None);
+        self.maybe_flush(&mut state);
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> GarpResult<bool> {
+        self.read_ops.fetch_add(1, Ordering::Relaxed);
+        let state = self.state.read().await;
+        Ok(matches!(state.lookup(key), Some(Some(_))))
+    }
+
+    async fn list_keys(&self, prefix: &str) -> GarpResult<Vec<String>> {
+        self.read_ops.fetch_add(1, Ordering::Relaxed);
+        let state = self.state.read().await;
+        Ok(state.scan(prefix).into_keys().collect())
+    }
+
+    async fn batch(&self, operations: Vec<BatchOperation>) -> GarpResult<()> {
+        let mut state = self.state.write().await;
+        for op in operations {
+            match op {
+                BatchOperation::Set { key, value } => {
+                    self.write_ops.fetch_add(1, Ordering::Relaxed);
+                    state.write(key, Some(value));
+                }
+                BatchOperation::Delete { key } => {
+                    self.delete_ops.fetch_add(1, Ordering::Relaxed);
+                    state.write(key, None);
+                }
+            }
+        }
+        self.maybe_flush(&mut state);
+        Ok(())
+    }
+
+    async fn create_snapshot(&self, snapshot_id: &str) -> GarpResult<()> {
+        let state = self.state.read().await.clone();
+        self.snapshots.write().await.insert(snapshot_id.to_string(), state);
+        Ok(())
+    }
+
+    async fn restore_snapshot(&self, snapshot_id: &str) -> GarpResult<()> {
+        let snapshot = self.snapshots.read().await.get(snapshot_id).cloned()
+            .ok_or_else(|| GarpError::NotFound(format!("Snapshot {} not found", snapshot_id)))?;
+        *self.state.write().await = snapshot;
+        Ok(())
+    }
+
+    async fn get_stats(&self) -> GarpResult<StorageStats> {
+        let state = self.state.read().await;
+        let live = state.scan("");
+        let total_size = live.iter().map(|(key, value)| (key.len() + value.len()) as u64).sum();
+
+        Ok(StorageStats {
+            total_keys: live.len() as u64,
+            total_size,
+            free_space: u64::MAX - total_size,
+            read_ops: self.read_ops.load(Ordering::Relaxed),
+            write_ops: self.write_ops.load(Ordering::Relaxed),
+            delete_ops: self.delete_ops.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(memtable_max_bytes: usize, compaction_threshold_bytes: usize) -> LsmStorageBackend {
+        let mut config = GlobalSyncConfig::default();
+        config.database.lsm.memtable_max_bytes = memtable_max_bytes;
+        config.database.lsm.compaction_threshold_bytes = compaction_threshold_bytes;
+        LsmStorageBackend::new(Arc::new(config))
+    }
+
+    #[tokio::test]
+    async fn test_flush_and_newest_first_reads() {
+        // Every write flushes; no compaction
+        let backend = backend(1, 0);
+        backend.set("k1", vec![1]).await.unwrap();
+        backend.set("k1", vec![2]).await.unwrap();
+        backend.set("k2", vec![3]).await.unwrap();
+        assert_eq!(backend.sstable_count().await, 3);
+
+        assert_eq!(backend.get("k1").await.unwrap(), Some(vec![2]));
+
+        // The tombstone shadows the value in an older SSTable
+        backend.delete("k1").await.unwrap();
+        assert_eq!(backend.get("k1").await.unwrap(), None);
+        assert!(!backend.exists("k1").await.unwrap());
+        assert_eq!(backend.list_keys("k").await.unwrap(), vec!["k2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_compaction_merges_small_tables_and_drops_tombstones() {
+        let backend = backend(1, 1024);
+        backend.set("a", vec![1]).await.unwrap();
+        backend.set("b", vec![2]).await.unwrap();
+        backend.delete("a").await.unwrap();
+        assert_eq!(backend.sstable_count().await, 1);
+
+        let state = backend.state.read().await;
+        assert!(!state.sstables[0].contains_key("a"));
+        assert_eq!(state.sstables[0].get("b"), Some(&Some(vec![2])));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_restore() {
+        let backend = backend(16, 1024);
+        backend.set("x", vec![1]).await.unwrap();
+        backend.create_snapshot("s").await.unwrap();
+        backend.batch(vec![
+            BatchOperation::Delete { key: "x".to_string() },
+            BatchOperation::Set { key: "y".to_string(), value: vec![2] },
+        ]).await.unwrap();
+        assert_eq!(backend.get_stats().await.unwrap().total_keys, 1);
+
+        backend.restore_snapshot("s").await.unwrap();
+        assert_eq!(backend.get("x").await.unwrap(), Some(vec![1]));
+        assert!(backend.restore_snapshot("missing").await.is_err());
+    }
+}
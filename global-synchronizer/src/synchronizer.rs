@@ -1,27 +1,63 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{RwLock, Mutex, mpsc, oneshot};
+use tokio::sync::{broadcast, watch, Notify, RwLock, Mutex, mpsc, oneshot};
 use tokio::time::interval;
-use tracing::{info, warn, error, debug};
+use tracing::{info, warn, error, debug, instrument};
+use futures::stream::{BoxStream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use garp_common::{GarpResult, GarpError};
+use garp_common::{GarpResult, GarpError, KeyHistory, KeyRotation, ReputationScore, ReputationTracker};
 use garp_common::types::{TransactionId, Block, ParticipantId};
 
-use crate::config::GlobalSyncConfig;
-use crate::storage::{GlobalStorage, BlockInfo, TransactionInfo, DomainId};
-use crate::network::NetworkManager;
-use crate::consensus::ConsensusEngine;
-use crate::cross_domain::CrossDomainCoordinator;
-use crate::validator::{ValidatorManager, ValidatorInfo};
+use crate::config::{ConfigReloadRecord, GlobalSyncConfig};
+use crate::storage::{GlobalStorage, BlockInfo, PoolTransaction, TransactionInfo, DomainId, DomainState, SlashingRecord, StateTransition, TransactionDetails, ViewChangeRecord};
+use crate::network::{MessageDestination, MessagePriority, NetworkManager};
+use crate::consensus::{ConsensusEngine, ConsensusMetricsSnapshot, ConsensusProof, ConsensusResult};
+use crate::consensus::batch::BatchProposal;
+use crate::consensus::checkpoint::{LedgerCheckpoint, LedgerCheckpointer};
+use crate::consensus::sync::{FastSync, FastSyncState};
+use crate::discovery::{DiscoveryEvent, DomainAnnouncement, DomainDiscovery, DomainRegistration, RegisteredDomain};
+use crate::cross_domain::{ConfirmationStatus, CrossDomainCoordinator, CrossDomainTransaction, CrossDomainTransactionType, DomainMetricsMap, DomainPerfMetrics, DomainSuspension, GovernanceVoteMessage};
+use crate::timelock::{TimelockEntry, TimelockMonitor};
+use crate::cross_domain::channel::ChannelSettlementMonitor;
+use crate::cross_domain::delegation::DomainDelegation;
+use crate::cross_domain::emergency::EmergencyAuditRecord;
+use crate::cross_domain::governance::{GovernanceAction, GovernanceMonitor, GovernanceProposal};
+use crate::cross_domain::swap::{AtomicSwap, SwapTimeoutMonitor};
+use crate::cross_domain::wrapping::{TokenWrapping, WrappedToken};
+use crate::block_producer::BlockProducer;
+use crate::node_keys::NodeKeyManager;
+use crate::compliance::{self, HistoryFilter, RedactedField, RedactedTransaction};
+use crate::receipt::TransactionReceipt;
+use crate::settlement::SettlementEngine;
+use crate::settlement::compensation::{CompensationChain, CompensationMonitor};
+use crate::state_root::{StateRootProof, StateRootRecord};
+use crate::validator::{ValidatorChange, ValidatorManager, ValidatorInfo, ValidatorSetUpdate};
+use crate::{ComponentHealth, HealthStatus, MaintenanceMode, ServiceHealth};
 use crate::bridge::{CrossChainBridge, BridgeTransaction, BridgeTransactionStatus, AssetMapping, BridgeValidator};
 
+/// Transaction status changes buffered per subscriber
+const TRANSACTION_STATUS_CAPACITY: usize = 1024;
+
+/// Reads the configuration a reload applies, including any command line
+/// overrides the node was started with
+pub type ConfigSource = Arc<dyn Fn() -> GarpResult<GlobalSyncConfig> + Send + Sync>;
+
 /// Global synchronizer for coordinating cross-domain transactions and state
 pub struct GlobalSynchronizer {
-    /// Configuration
+    /// Configuration the node was started with
     config: Arc<GlobalSyncConfig>,
     
+    /// Running configuration, replaced when a reload changes dynamic parameters
+    config_tx: watch::Sender<Arc<GlobalSyncConfig>>,
+    
+    /// Source of configuration reloads, locked for the length of a reload
+    config_source: Mutex<Option<ConfigSource>>,
+    
+    /// Coordinator monitors, respawned when their intervals are reloaded
+    monitor_tasks: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+    
     /// Storage layer
     storage: Arc<GlobalStorage>,
     
@@ -34,21 +70,45 @@ pub struct GlobalSynchronizer {
     /// Cross-domain coordinator
     cross_domain_coordinator: Arc<CrossDomainCoordinator>,
     
+    /// Settlement engine
+    settlement_engine: Arc<SettlementEngine>,
+    
     /// Validator manager
     validator_manager: Arc<ValidatorManager>,
     
     /// Cross-chain bridge
     bridge: Arc<CrossChainBridge>,
     
+    /// Snapshot fast sync, both serving and joining
+    fast_sync: Arc<FastSync>,
+    
+    /// Validator-signed ledger checkpoints
+    ledger_checkpointer: Arc<LedgerCheckpointer>,
+    
+    /// Node signing key and peer key rotations
+    node_keys: Arc<NodeKeyManager>,
+    
+    /// Domain and validator reputation
+    reputation: Arc<ReputationTracker>,
+    
     /// Active transactions
     active_transactions: Arc<RwLock<HashMap<TransactionId, ActiveTransaction>>>,
     
     /// Pending blocks
     pending_blocks: Arc<RwLock<HashMap<String, PendingBlock>>>,
     
+    /// Mempool transaction IDs awaiting inclusion in a block
+    mempool: Arc<RwLock<Vec<TransactionId>>>,
+    
+    /// Set while new transactions are refused for maintenance
+    maintenance: Arc<RwLock<Option<MaintenanceMode>>>,
+    
     /// Metrics
     metrics: Arc<GlobalSyncMetrics>,
     
+    /// Per-domain performance metrics (shared with the cross-domain coordinator)
+    domain_metrics: DomainMetricsMap,
+    
     /// State
     state: Arc<RwLock<GlobalSyncState>>,
     
@@ -56,8 +116,28 @@ pub struct GlobalSynchronizer {
     event_tx: mpsc::UnboundedSender<GlobalSyncEvent>,
     event_rx: Arc<Mutex<mpsc::UnboundedReceiver<GlobalSyncEvent>>>,
     
+    /// Transaction status changes, for API subscribers
+    status_tx: broadcast::Sender<TransactionStatusUpdate>,
+    
     /// Shutdown signal
     shutdown_tx: Option<oneshot::Sender<()>>,
+    
+    /// Shutdown requested through the admin API
+    shutdown_requested: Arc<Notify>,
+}
+
+/// Mempool transaction with its stored payload
+#[derive(Debug, Clone, Serialize)]
+pub struct MempoolEntry {
+    /// Position in the mempool, in submission order; blocks take
+    /// transactions by pool priority
+    pub position: usize,
+    
+    /// Transaction ID
+    pub transaction_id: TransactionId,
+    
+    /// Stored transaction, if it was persisted
+    pub transaction: Option<crate::storage::StoredTransaction>,
 }
 
 /// Active transaction in the global synchronizer
@@ -95,7 +175,7 @@ pub struct ActiveTransaction {
 }
 
 /// Transaction status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransactionStatus {
     /// Transaction received and validated
     Received,
@@ -119,6 +199,64 @@ pub enum TransactionStatus {
     TimedOut,
 }
 
+impl TransactionStatus {
+    /// Whether the transaction can no longer change status
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Finalized | Self::Failed(_) | Self::TimedOut)
+    }
+}
+
+/// A transaction moving to a new status
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionStatusUpdate {
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Cross-domain transaction as submitted over JSON-RPC; the synchronizer
+/// assigns the ID, timestamps and timeout
+#[derive(Debug, Clone, Deserialize)]
+pub struct CrossDomainSubmission {
+    pub source_domain: DomainId,
+    pub target_domains: Vec<DomainId>,
+    pub transaction_type: CrossDomainTransactionType,
+    #[serde(default)]
+    pub data: Vec<u8>,
+    #[serde(default)]
+    pub dependencies: Vec<TransactionId>,
+    /// Confirmations needed; every target domain when omitted
+    #[serde(default)]
+    pub required_confirmations: Option<usize>,
+    #[serde(default)]
+    pub not_before: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub max_fee: u64,
+    #[serde(default)]
+    pub delegated_by: Option<DomainId>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// One target domain's confirmation of a cross-domain transaction
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainConfirmationSummary {
+    pub domain_id: DomainId,
+    /// `Pending` until the domain responds
+    pub status: ConfirmationStatus,
+    pub confirmed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Status of a submitted cross-domain transaction, with a confirmation
+/// entry per target domain
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossDomainStatus {
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+    pub required_confirmations: usize,
+    pub confirmations: Vec<DomainConfirmationSummary>,
+}
+
 /// Consensus vote
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusVote {
@@ -284,6 +422,12 @@ pub enum GlobalSyncEvent {
     /// Validator left
     ValidatorLeft(ParticipantId),
     
+    /// Validator set changed at an epoch boundary
+    ValidatorSetChanged(ValidatorSetUpdate),
+    
+    /// Maintenance mode enabled, or disabled with `None`
+    MaintenanceModeChanged(Option<MaintenanceMode>),
+    
     /// Health check
     HealthCheck,
     
@@ -302,15 +446,36 @@ impl GlobalSynchronizer {
         // Initialize network manager
         let network_manager = Arc::new(NetworkManager::new(config.clone()).await?);
         
+        // Restore reputation scores
+        let reputation = Arc::new(ReputationTracker::load(config.reputation.clone(), storage.clone()).await?);
+        
         // Initialize consensus engine
-        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await?);
+        let consensus_engine = Arc::new(ConsensusEngine::new(config.clone()).await?.with_reputation(reputation.clone()));
         
         // Initialize cross-domain coordinator
+        let domain_discovery = Arc::new(DomainDiscovery::with_storage(config.clone(), storage.clone()).await?);
         let cross_domain_coordinator = Arc::new(CrossDomainCoordinator::new(
             config.clone(),
             storage.clone(),
             network_manager.clone(),
+            domain_discovery,
+            consensus_engine.clone(),
+        ).await?.with_reputation(reputation.clone()));
+        
+        // Initialize settlement engine
+        let settlement_engine = Arc::new(SettlementEngine::new(
+            config.clone(),
+            storage.clone(),
+            network_manager.clone(),
+            consensus_engine.clone(),
+        ).await?);
+        
+        // Load the node signing key from the keystore
+        let node_keys = Arc::new(NodeKeyManager::new(
+            config.clone(),
             consensus_engine.clone(),
+            cross_domain_coordinator.clone(),
+            network_manager.clone(),
         ).await?);
         
         // Initialize validator manager
@@ -323,24 +488,65 @@ impl GlobalSynchronizer {
             network_manager.clone(),
         ).await?);
         
+        // Initialize snapshot fast sync
+        let fast_sync = Arc::new(FastSync::new(
+            config.clone(),
+            storage.clone(),
+            consensus_engine.clone(),
+            network_manager.clone(),
+        ));
+        
+        // Initialize ledger checkpoint signing
+        let ledger_checkpointer = Arc::new(LedgerCheckpointer::new(
+            config.clone(),
+            storage.clone(),
+            consensus_engine.clone(),
+            network_manager.clone(),
+        ));
+        
+        // Restore maintenance mode left on by an earlier run
+        let maintenance = if config.api.persist_maintenance_mode {
+            storage.get_maintenance_mode().await?
+        } else {
+            None
+        };
+        if let Some(mode) = &maintenance {
+            warn!(set_by = %mode.set_by, "Starting in maintenance mode: {}", mode.reason);
+        }
+        
         // Create event channels
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         
+        let domain_metrics = cross_domain_coordinator.domain_metrics();
+        
         let synchronizer = Self {
+            config_tx: watch::channel(config.clone()).0,
+            config_source: Mutex::new(None),
+            monitor_tasks: Arc::new(Mutex::new(Vec::new())),
             config,
             storage,
             network_manager,
             consensus_engine,
             cross_domain_coordinator,
+            settlement_engine,
             validator_manager,
             bridge,
+            fast_sync,
+            ledger_checkpointer,
+            node_keys,
+            reputation,
             active_transactions: Arc::new(RwLock::new(HashMap::new())),
             pending_blocks: Arc::new(RwLock::new(HashMap::new())),
+            mempool: Arc::new(RwLock::new(Vec::new())),
+            maintenance: Arc::new(RwLock::new(maintenance)),
             metrics: Arc::new(GlobalSyncMetrics::default()),
+            domain_metrics,
             state: Arc::new(RwLock::new(GlobalSyncState::default())),
             event_tx,
             event_rx: Arc::new(Mutex::new(event_rx)),
+            status_tx: broadcast::channel(TRANSACTION_STATUS_CAPACITY).0,
             shutdown_tx: None,
+            shutdown_requested: Arc::new(Notify::new()),
         };
         
         Ok(synchronizer)
@@ -357,12 +563,39 @@ impl GlobalSynchronizer {
             state.last_updated = Instant::now();
         }
         
+        // Recover state from the latest local snapshot if it is missing or behind
+        if let Some(height) = self.storage.get_state_snapshot_heights().await.last().copied() {
+            if self.storage.is_state_behind(height).await {
+                if let Err(e) = self.restore_state_from_snapshot(height).await {
+                    warn!("Failed to restore state from snapshot at height {}: {}", height, e);
+                }
+            }
+        }
+        
         // Start components
         self.consensus_engine.start().await?;
         self.cross_domain_coordinator.start().await?;
+        self.settlement_engine.start().await?;
         self.network_manager.start().await?;
         self.validator_manager.start().await?;
         self.bridge.start().await?;
+        self.fast_sync.register_handler().await?;
+        self.ledger_checkpointer.register_handler().await?;
+        self.node_keys.register_handler().await?;
+        self.node_keys.spawn_retirement();
+        
+        // Start timelock release, state channel settlement, governance
+        // tallying, atomic swap refunds and compensation of aborted transactions
+        *self.monitor_tasks.lock().await = Self::spawn_monitors(&self.cross_domain_coordinator, &self.current_config());
+        
+        // Start block production
+        Arc::new(BlockProducer::new(
+            self.config.clone(),
+            self.storage.clone(),
+            self.consensus_engine.clone(),
+            self.mempool.clone(),
+            self.event_tx.clone(),
+        )).spawn();
         
         // Start event processing
         let event_processor = self.start_event_processor().await?;
@@ -372,6 +605,8 @@ impl GlobalSynchronizer {
         let health_checker = self.start_health_checker().await?;
         let transaction_monitor = self.start_transaction_monitor().await?;
         let block_processor = self.start_block_processor().await?;
+        let orphan_handler = self.start_orphan_handler().await?;
+        let config_watcher = self.start_config_watcher().await?;
         
         // Update state to active
         {
@@ -405,6 +640,7 @@ impl GlobalSynchronizer {
         self.validator_manager.stop().await?;
         self.network_manager.stop().await?;
         self.cross_domain_coordinator.stop().await?;
+        self.settlement_engine.stop().await?;
         self.consensus_engine.stop().await?;
         
         // Update state to stopped
@@ -418,12 +654,20 @@ impl GlobalSynchronizer {
         Ok(())
     }
     
-    /// Submit cross-domain transaction
+    /// Submit cross-domain transaction. The returned ID is also the
+    /// correlation ID recorded on every log line about the transaction.
+    /// Refused with a retryable error while in maintenance mode.
+    #[instrument(skip_all, fields(correlation_id))]
     pub async fn submit_transaction(
         &self,
         transaction: CrossDomainTransaction,
     ) -> GarpResult<TransactionId> {
+        if let Some(mode) = self.maintenance.read().await.as_ref() {
+            return Err(GarpError::Maintenance(mode.reason.clone()));
+        }
+        
         let transaction_id = TransactionId::new();
+        tracing::Span::current().record("correlation_id", tracing::field::display(&transaction_id));
         
         debug!("Submitting cross-domain transaction: {}", transaction_id);
         
@@ -437,10 +681,20 @@ impl GlobalSynchronizer {
             settlement_status: SettlementStatus::NotStarted,
             created_at: Instant::now(),
             updated_at: Instant::now(),
-            timeout_at: Instant::now() + self.config.transaction_timeout(),
+            timeout_at: Instant::now() + self.current_config().transaction_timeout(),
             retry_count: 0,
         };
         
+        // Admit to the transaction pool, which may evict a lower priority transaction
+        let entry = PoolTransaction::new(
+            transaction_id.clone(),
+            &transaction.metadata,
+            transaction.dependencies.clone(),
+            transaction.data.len(),
+            Instant::now(),
+        );
+        let evicted = self.storage.add_to_pool(entry).await?;
+        
         // Store active transaction
         {
             let mut active_transactions = self.active_transactions.write().await;
@@ -449,6 +703,10 @@ impl GlobalSynchronizer {
         // Add to mempool
         {
             let mut mem = self.mempool.write().await;
+            if let Some(evicted) = &evicted {
+                warn!("Transaction pool full, evicted {} for {}", evicted.transaction_id, transaction_id);
+                mem.retain(|tid| *tid != evicted.transaction_id);
+            }
             mem.push(transaction_id.clone());
         }
         
@@ -461,6 +719,141 @@ impl GlobalSynchronizer {
         Ok(transaction_id)
     }
     
+    /// Submit a cross-domain transaction built from `submission`, timing
+    /// out after the configured transaction timeout
+    pub async fn submit_cross_domain(&self, submission: CrossDomainSubmission) -> GarpResult<TransactionId> {
+        if submission.target_domains.is_empty() {
+            return Err(GarpError::ValidationError("Cross-domain transaction needs at least one target domain".to_string()));
+        }
+        let now = chrono::Utc::now();
+        let timeout_ms = self.current_config().cross_domain.transaction_timeout_ms;
+        let required_confirmations = submission.required_confirmations.unwrap_or(submission.target_domains.len());
+        self.submit_transaction(CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: submission.source_domain,
+            target_domains: submission.target_domains,
+            transaction_type: submission.transaction_type,
+            data: submission.data,
+            dependencies: submission.dependencies,
+            required_confirmations,
+            confirmations: HashMap::new(),
+            status: crate::cross_domain::TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            timeout_at: now + chrono::Duration::milliseconds(timeout_ms as i64),
+            not_before: submission.not_before,
+            max_fee: submission.max_fee,
+            delegated_by: submission.delegated_by,
+            metadata: submission.metadata,
+        })
+        .await
+    }
+    
+    /// Status of a cross-domain transaction submitted to this node, or
+    /// `None` if it is unknown
+    pub async fn get_cross_domain_status(&self, transaction_id: &str) -> GarpResult<Option<CrossDomainStatus>> {
+        let id = Uuid::parse_str(transaction_id)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid transaction id {}: {}", transaction_id, e)))?;
+        let active_transactions = self.active_transactions.read().await;
+        Ok(active_transactions.get(&TransactionId(id)).map(|active| {
+            let transaction = &active.cross_domain_tx;
+            let confirmations = transaction.target_domains.iter().map(|domain_id| match transaction.confirmations.get(domain_id) {
+                Some(confirmation) => DomainConfirmationSummary {
+                    domain_id: domain_id.clone(),
+                    status: confirmation.status.clone(),
+                    confirmed_at: Some(confirmation.timestamp),
+                },
+                None => DomainConfirmationSummary {
+                    domain_id: domain_id.clone(),
+                    status: ConfirmationStatus::Pending,
+                    confirmed_at: None,
+                },
+            }).collect();
+            CrossDomainStatus {
+                transaction_id: active.transaction_id.clone(),
+                status: active.status.clone(),
+                required_confirmations: transaction.required_confirmations,
+                confirmations,
+            }
+        }))
+    }
+    
+    /// IDs of the active domains, sorted
+    pub async fn list_active_domains(&self) -> Vec<DomainId> {
+        let mut domains = self.cross_domain_coordinator.get_active_domains().await;
+        domains.sort();
+        domains
+    }
+    
+    /// Pause (`enabled`) or resume transaction intake. While paused, new
+    /// submissions are refused with `reason`; reads and transactions already
+    /// in flight carry on. Persisted when `api.persist_maintenance_mode` is set.
+    pub async fn set_maintenance_mode(&self, enabled: bool, reason: String, set_by: &str) -> GarpResult<Option<MaintenanceMode>> {
+        let mode = if enabled {
+            if reason.trim().is_empty() {
+                return Err(GarpError::ValidationError("Maintenance mode needs a reason".to_string()));
+            }
+            Some(MaintenanceMode { reason, set_by: set_by.to_string(), since: chrono::Utc::now() })
+        } else {
+            None
+        };
+        
+        let mut current = self.maintenance.write().await;
+        if self.config.api.persist_maintenance_mode {
+            self.storage.store_maintenance_mode(mode.as_ref()).await?;
+        }
+        match &mode {
+            Some(mode) => warn!(set_by = %set_by, "Maintenance mode enabled, refusing new transactions: {}", mode.reason),
+            None => info!(set_by = %set_by, "Maintenance mode disabled, accepting transactions"),
+        }
+        *current = mode.clone();
+        
+        if let Err(e) = self.event_tx.send(GlobalSyncEvent::MaintenanceModeChanged(mode.clone())) {
+            warn!("Failed to publish maintenance mode change: {}", e);
+        }
+        Ok(mode)
+    }
+    
+    /// Current maintenance mode, if transaction intake is paused
+    pub async fn get_maintenance_mode(&self) -> Option<MaintenanceMode> {
+        self.maintenance.read().await.clone()
+    }
+    
+    /// Replication lag alerts buffered by storage, oldest first
+    pub async fn get_replication_alerts(&self) -> Vec<crate::storage::ReplicationAlert> {
+        self.storage.get_replication_alerts().await
+    }
+    
+    /// Fast sync this node from the peer at `peer_address`, replacing its
+    /// state with the peer's latest certified snapshot
+    pub async fn start_fast_sync(&self, peer_address: &str) -> GarpResult<()> {
+        let address = peer_address
+            .parse()
+            .map_err(|e| GarpError::ValidationError(format!("Invalid peer address {}: {}", peer_address, e)))?;
+        self.fast_sync.start(address).await
+    }
+    
+    /// Progress of the current or last fast sync
+    pub async fn get_fast_sync_state(&self) -> FastSyncState {
+        self.fast_sync.state().await
+    }
+    
+    /// Create a ledger checkpoint at a finalized height, signed by 2/3 of
+    /// the validators, or return the one already stored there
+    pub async fn create_ledger_checkpoint(&self, height: u64) -> GarpResult<LedgerCheckpoint> {
+        self.ledger_checkpointer.create(height).await
+    }
+    
+    /// Get the stored ledger checkpoint at a height
+    pub async fn get_ledger_checkpoint(&self, height: u64) -> GarpResult<Option<LedgerCheckpoint>> {
+        self.storage.get_ledger_checkpoint(height).await
+    }
+    
+    /// Get the most recent stored ledger checkpoint
+    pub async fn get_latest_ledger_checkpoint(&self) -> GarpResult<Option<LedgerCheckpoint>> {
+        self.storage.get_latest_ledger_checkpoint().await
+    }
+    
     /// Get transaction status
     pub async fn get_transaction_status(
         &self,
@@ -470,6 +863,295 @@ impl GlobalSynchronizer {
         Ok(active_transactions.get(transaction_id).map(|tx| tx.status.clone()))
     }
     
+    /// Sources for the `/ws` subscription endpoint
+    pub fn ws_state(&self) -> crate::ws::WsState {
+        crate::ws::WsState::new(
+            self.storage.clone(),
+            self.active_transactions.clone(),
+            self.status_tx.clone(),
+            self.config.api.ws_max_subscriptions,
+        )
+    }
+    
+    /// Get a domain's state, optionally as of an earlier version
+    pub async fn get_domain_state_version(&self, domain_id: &str, version: Option<u64>) -> GarpResult<DomainState> {
+        self.storage
+            .get_state(&domain_id.to_string(), version)
+            .await?
+            .ok_or_else(|| match version {
+                Some(v) => GarpError::NotFound(format!("No state for domain {} at version {}", domain_id, v)),
+                None => GarpError::NotFound(format!("No state for domain: {}", domain_id)),
+            })
+    }
+    
+    /// Current consensus view
+    pub async fn get_consensus_view(&self) -> u64 {
+        self.consensus_engine.get_current_view().await
+    }
+    
+    /// Consensus metrics, with phase latency percentiles and validator
+    /// participation over the configured window
+    pub async fn get_consensus_metrics(&self) -> GarpResult<ConsensusMetricsSnapshot> {
+        self.consensus_engine.get_metrics_snapshot().await
+    }
+    
+    /// Recorded view changes into views `from_view..=to_view`
+    pub async fn get_view_changes(&self, from_view: u64, to_view: u64) -> GarpResult<Vec<ViewChangeRecord>> {
+        self.consensus_engine.get_view_changes(from_view, to_view).await
+    }
+    
+    /// List validators active in the current epoch
+    pub async fn list_validators(&self) -> GarpResult<Vec<ValidatorInfo>> {
+        self.consensus_engine.list_validators().await
+    }
+    
+    /// Schedule a validator to join at the next epoch boundary, returning that epoch
+    pub async fn add_validator(&self, validator: ValidatorInfo) -> GarpResult<u64> {
+        self.consensus_engine.add_validator(validator).await
+    }
+    
+    /// Schedule a validator to leave at the next epoch boundary, returning that epoch
+    pub async fn remove_validator(&self, validator_id: &str) -> GarpResult<u64> {
+        self.consensus_engine.remove_validator(ParticipantId::new(validator_id)).await
+    }
+    
+    /// Schedule a validator stake change for the next epoch boundary, returning that epoch
+    pub async fn update_validator_stake(&self, validator_id: &str, stake: u64) -> GarpResult<u64> {
+        self.consensus_engine.update_validator_stake(ParticipantId::new(validator_id), stake).await
+    }
+    
+    /// Validator changes waiting for the next epoch boundary
+    pub async fn pending_validator_changes(&self) -> Vec<ValidatorChange> {
+        self.consensus_engine.pending_validator_changes().await
+    }
+    
+    /// Current validator epoch
+    pub async fn current_validator_epoch(&self) -> u64 {
+        self.consensus_engine.current_epoch().await
+    }
+    
+    /// Total settlement fees credited to a validator
+    pub async fn get_validator_fee_earnings(&self, validator_id: &str) -> GarpResult<u64> {
+        self.storage.get_validator_fee_earnings(&validator_id.to_string()).await
+    }
+    
+    /// Get the slashing history of a validator
+    pub async fn get_slashing_history(&self, validator_id: &str) -> GarpResult<Vec<SlashingRecord>> {
+        self.consensus_engine.get_slashing_history(&ParticipantId::new(validator_id)).await
+    }
+    
+    /// Cancel a time-locked cross-domain transaction before it unlocks
+    pub async fn cancel_timelocked_transaction(&self, transaction_id: &TransactionId) -> GarpResult<()> {
+        self.cross_domain_coordinator.cancel_timelocked_transaction(transaction_id).await
+    }
+    
+    /// Get the queue of time-locked cross-domain transactions
+    pub async fn get_timelock_queue(&self) -> Vec<TimelockEntry> {
+        self.cross_domain_coordinator.get_timelock_queue().await
+    }
+    
+    /// Turn settlement batching on or off at runtime
+    pub async fn set_settlement_batching(&self, enabled: bool) {
+        self.settlement_engine.set_batching_enabled(enabled).await;
+    }
+    
+    /// Whether settlement batching is enabled
+    pub fn is_settlement_batching_enabled(&self) -> bool {
+        self.settlement_engine.is_batching_enabled()
+    }
+    
+    /// Settlement batch counters
+    pub async fn get_settlement_batch_metrics(&self) -> crate::storage::SettlementBatchMetrics {
+        self.settlement_engine.get_batch_metrics().await
+    }
+    
+    /// Get the settlement proof of a transaction, given its UUID
+    pub async fn get_settlement_proof(&self, transaction_id: &str) -> GarpResult<garp_common::settlement::SettlementProof> {
+        let id = Uuid::parse_str(transaction_id)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid transaction id {}: {}", transaction_id, e)))?;
+        self.settlement_engine.get_settlement_proof(&TransactionId(id)).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Settlement proof for {} not found", transaction_id)))
+    }
+    
+    /// Get the zero-knowledge proof of a completed settlement, generating
+    /// it on first request
+    pub async fn get_settlement_zk_proof(&self, settlement_id: &str) -> GarpResult<crate::settlement::zk::ZkProof> {
+        match self.settlement_engine.get_settlement_zk_proof(settlement_id).await {
+            Some(proof) => Ok(proof),
+            None => self.settlement_engine.generate_settlement_proof(settlement_id).await,
+        }
+    }
+    
+    /// Receipt proving a transaction is in a finalized block, or `None`
+    /// until its block is certified
+    pub async fn get_transaction_receipt(&self, transaction_id: &str) -> GarpResult<Option<TransactionReceipt>> {
+        let id = Uuid::parse_str(transaction_id)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid transaction id {}: {}", transaction_id, e)))?;
+        self.storage.get_transaction_receipt(&TransactionId(id)).await
+    }
+    
+    /// Persisted transaction history matching `filter`, with the `redact`
+    /// fields replaced by `[REDACTED]`
+    pub async fn export_transaction_history(
+        &self,
+        filter: HistoryFilter,
+        redact: Vec<RedactedField>,
+    ) -> GarpResult<Vec<RedactedTransaction>> {
+        self.stream_transaction_history(filter, redact).await?.try_collect().await
+    }
+    
+    /// Streaming form of [`Self::export_transaction_history`], reading one
+    /// stored transaction at a time
+    pub async fn stream_transaction_history(
+        &self,
+        filter: HistoryFilter,
+        redact: Vec<RedactedField>,
+    ) -> GarpResult<BoxStream<'static, GarpResult<RedactedTransaction>>> {
+        Ok(compliance::export(self.storage.stream_transactions().await?, filter, redact))
+    }
+    
+    /// Compensations issued for an aborted transaction, or `None` if it was
+    /// not compensated
+    pub async fn get_compensation_chain(&self, transaction_id: &str) -> GarpResult<Option<CompensationChain>> {
+        let id = Uuid::parse_str(transaction_id)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid transaction id {}: {}", transaction_id, e)))?;
+        self.cross_domain_coordinator.get_compensation_chain(&TransactionId(id)).await
+    }
+    
+    /// Up to `limit` transitions of a synchronized state key on a domain, newest first
+    pub async fn get_state_history(&self, domain_id: &str, key: &str, limit: usize) -> GarpResult<Vec<StateTransition>> {
+        self.cross_domain_coordinator.get_state_history(&domain_id.to_string(), key, limit).await
+    }
+    
+    /// Replace local state with the snapshot taken at `height`, then replay
+    /// the locally stored blocks above it, returning the number replayed.
+    /// The snapshot's checksum and state root are verified before loading.
+    pub async fn restore_state_from_snapshot(&self, height: u64) -> GarpResult<u64> {
+        let snapshot = self.storage.restore_state_from_snapshot(height).await?;
+        self.consensus_engine.advance_epoch(snapshot.block_height).await?;
+        
+        let mut replayed = 0;
+        let mut next_height = snapshot.block_height + 1;
+        while let Some(block) = self.storage.get_block_by_height(next_height).await? {
+            self.consensus_engine.advance_epoch(block.height).await?;
+            replayed += 1;
+            next_height += 1;
+        }
+        info!(snapshot_height = snapshot.block_height, replayed_blocks = replayed, "Restored state from snapshot");
+        Ok(replayed)
+    }
+    
+    /// Global state root committed by the block at `height`
+    pub async fn get_state_root(&self, height: u64) -> GarpResult<StateRootRecord> {
+        self.storage.get_state_root_at(height).await
+            .ok_or_else(|| GarpError::NotFound(format!("No state root recorded at height {}", height)))
+    }
+    
+    /// Proof that a domain's state hash is included in the global state root
+    /// committed at `height`
+    pub async fn get_state_root_proof(&self, height: u64, domain_id: &str) -> GarpResult<StateRootProof> {
+        self.storage.get_state_root_proof(height, &domain_id.to_string()).await
+            .ok_or_else(|| GarpError::NotFound(format!("No state for domain {} in the state root at height {}", domain_id, height)))
+    }
+    
+    /// Everything known about a transaction, or `None` if it is unknown
+    pub async fn get_transaction_details(&self, transaction_id: &str, include_votes: bool) -> GarpResult<Option<TransactionDetails>> {
+        let id = Uuid::parse_str(transaction_id)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid transaction id {}: {}", transaction_id, e)))?;
+        Ok(self.storage.get_transaction(&TransactionId(id)).await?
+            .map(|transaction| transaction.details(include_votes)))
+    }
+    
+    /// Get a settlement rollback
+    pub async fn get_settlement_rollback(&self, rollback_id: &str) -> GarpResult<crate::storage::RollbackRecord> {
+        self.settlement_engine.get_rollback(rollback_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Rollback {} not found", rollback_id)))
+    }
+    
+    /// List settlement rollbacks, optionally only those in `status`
+    pub async fn list_settlement_rollbacks(&self, status: Option<crate::storage::RollbackStatus>) -> GarpResult<Vec<crate::storage::RollbackRecord>> {
+        self.settlement_engine.list_rollbacks(status).await
+    }
+    
+    /// Open a cross-domain governance proposal and broadcast it to active domains
+    pub async fn submit_governance_proposal(
+        &self,
+        proposer: &DomainId,
+        action: GovernanceAction,
+        description: String,
+    ) -> GarpResult<GovernanceProposal> {
+        self.cross_domain_coordinator.submit_governance_proposal(proposer, action, description).await
+    }
+    
+    /// Get a governance proposal
+    pub async fn get_governance_proposal(&self, proposal_id: &str) -> GarpResult<GovernanceProposal> {
+        self.cross_domain_coordinator.get_governance_proposal(&proposal_id.to_string()).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Governance proposal not found: {}", proposal_id)))
+    }
+    
+    /// List governance proposals, oldest first
+    pub async fn list_governance_proposals(&self) -> GarpResult<Vec<GovernanceProposal>> {
+        self.cross_domain_coordinator.list_governance_proposals().await
+    }
+    
+    /// List domain delegations
+    pub async fn list_delegations(&self) -> GarpResult<Vec<DomainDelegation>> {
+        self.cross_domain_coordinator.list_delegations().await
+    }
+    
+    /// Get the delegation from `delegator` to `delegate`
+    pub async fn get_delegation(&self, delegator: &str, delegate: &str) -> GarpResult<DomainDelegation> {
+        self.cross_domain_coordinator.get_delegation(&delegator.to_string(), &delegate.to_string()).await?
+            .ok_or_else(|| GarpError::NotFound(format!("No delegation from {} to {}", delegator, delegate)))
+    }
+    
+    /// Cast a domain's vote on a governance proposal
+    pub async fn cast_governance_vote(&self, proposal_id: &str, domain_id: &DomainId, approve: bool) -> GarpResult<GovernanceProposal> {
+        self.cross_domain_coordinator.record_governance_vote(GovernanceVoteMessage {
+            proposal_id: proposal_id.to_string(),
+            domain_id: domain_id.clone(),
+            approve,
+        }).await
+    }
+    
+    /// Emergency action audit log in append order
+    pub async fn get_emergency_audit_log(&self) -> GarpResult<Vec<EmergencyAuditRecord>> {
+        self.cross_domain_coordinator.list_emergency_audit().await
+    }
+    
+    /// Get an atomic swap with the state of each leg
+    pub async fn get_swap_status(&self, swap_id: &str) -> GarpResult<AtomicSwap> {
+        self.cross_domain_coordinator.get_swap(&swap_id.to_string()).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Atomic swap not found: {}", swap_id)))
+    }
+    
+    /// Supply the preimage of an atomic swap submitted with its own hashlock
+    pub async fn reveal_swap_preimage(&self, swap_id: &str, preimage: &[u8]) -> GarpResult<AtomicSwap> {
+        self.cross_domain_coordinator.reveal_swap_preimage(&swap_id.to_string(), preimage).await
+    }
+    
+    /// Wrap `amount` of `token` from `from_domain` onto `to_domain`
+    pub async fn wrap_token(&self, token: &str, amount: u64, from_domain: &DomainId, to_domain: &DomainId) -> GarpResult<TokenWrapping> {
+        if let Some(mode) = self.maintenance.read().await.as_ref() {
+            return Err(GarpError::Maintenance(mode.reason.clone()));
+        }
+        self.cross_domain_coordinator.wrap_token(token, amount, from_domain, to_domain).await
+    }
+    
+    /// Unwrap `amount` of `wrapped_token` held on `to_domain` back to its
+    /// origin domain
+    pub async fn unwrap_token(&self, wrapped_token: &str, amount: u64, to_domain: &DomainId) -> GarpResult<TokenWrapping> {
+        if let Some(mode) = self.maintenance.read().await.as_ref() {
+            return Err(GarpError::Maintenance(mode.reason.clone()));
+        }
+        self.cross_domain_coordinator.unwrap_token(wrapped_token, amount, to_domain).await
+    }
+    
+    /// List wrapped tokens with their locked and minted supply
+    pub async fn list_wrapped_tokens(&self) -> GarpResult<Vec<WrappedToken>> {
+        self.cross_domain_coordinator.list_wrapped_tokens().await
+    }
+    
     /// Get current state
     pub async fn get_state(&self) -> GlobalSyncState {
         self.state.read().await.clone()
@@ -480,6 +1162,258 @@ impl GlobalSynchronizer {
         self.metrics.clone()
     }
     
+    /// Get performance metrics for a single domain
+    pub async fn get_domain_performance_metrics(&self, domain_id: &str) -> GarpResult<DomainPerfMetrics> {
+        let domain_metrics = self.domain_metrics.read().await;
+        domain_metrics
+            .get(domain_id)
+            .cloned()
+            .ok_or_else(|| GarpError::NotFound(format!("No metrics for domain: {}", domain_id)))
+    }
+    
+    /// Get performance metrics for all domains
+    pub async fn get_all_domain_performance_metrics(&self) -> HashMap<DomainId, DomainPerfMetrics> {
+        self.domain_metrics.read().await.clone()
+    }
+    
+    /// Health of the synchronizer and each of its components, with the
+    /// metrics each component's status is judged on
+    pub async fn get_health_status(&self) -> ServiceHealth {
+        let state = self.get_state().await;
+        let maintenance = self.get_maintenance_mode().await;
+        if !matches!(state.status, SyncStatus::Active | SyncStatus::Degraded) {
+            return ServiceHealth {
+                status: HealthStatus::Down,
+                message: format!("Synchronizer is {:?}", state.status),
+                components: Vec::new(),
+                timestamp: std::time::SystemTime::now(),
+                maintenance,
+            };
+        }
+        
+        let mut components = Vec::new();
+        
+        components.push(match self.consensus_engine.get_metrics_snapshot().await {
+            Ok(snapshot) => ComponentHealth {
+                name: "consensus_engine".to_string(),
+                status: HealthStatus::Up,
+                message: format!("View {} in phase {}", snapshot.current_view, snapshot.current_phase),
+                metrics: HashMap::from([
+                    ("current_view".to_string(), snapshot.current_view as f64),
+                    ("view_changes".to_string(), snapshot.view_changes as f64),
+                    ("active_sessions".to_string(), snapshot.active_sessions as f64),
+                    ("last_committed_block".to_string(), snapshot.last_committed_block as f64),
+                    ("validator_epoch".to_string(), self.consensus_engine.current_epoch().await as f64),
+                ]),
+            },
+            Err(e) => ComponentHealth::down("consensus_engine", e),
+        });
+        
+        let cross_domain = self.cross_domain_coordinator.get_metrics().await;
+        let active_domains = *cross_domain.active_domains.read().await;
+        components.push(ComponentHealth {
+            name: "cross_domain_coordinator".to_string(),
+            status: if active_domains > 0 { HealthStatus::Up } else { HealthStatus::Degraded },
+            message: format!("{} active domains", active_domains),
+            metrics: HashMap::from([
+                ("active_domains".to_string(), active_domains as f64),
+                ("total_transactions".to_string(), *cross_domain.total_transactions.read().await as f64),
+                ("failed_transactions".to_string(), *cross_domain.failed_transactions.read().await as f64),
+                ("active_coordination_sessions".to_string(), *cross_domain.active_coordination_sessions.read().await as f64),
+            ]),
+        });
+        
+        let settlement = self.settlement_engine.get_metrics().await;
+        let active_settlements = *settlement.active_settlements.read().await;
+        components.push(ComponentHealth {
+            name: "settlement_engine".to_string(),
+            status: HealthStatus::Up,
+            message: format!("{} settlements in progress", active_settlements),
+            metrics: HashMap::from([
+                ("active_settlements".to_string(), active_settlements as f64),
+                ("total_settlements".to_string(), *settlement.total_settlements.read().await as f64),
+                ("failed_settlements".to_string(), *settlement.failed_settlements.read().await as f64),
+                ("rolled_back_settlements".to_string(), *settlement.rolled_back_settlements.read().await as f64),
+            ]),
+        });
+        
+        let peers = self.network_manager.list_peers().await;
+        let connected = peers.iter()
+            .filter(|peer| peer.status == crate::network::ConnectionStatus::Connected)
+            .count();
+        components.push(ComponentHealth {
+            name: "network_manager".to_string(),
+            status: if connected > 0 || peers.is_empty() { HealthStatus::Up } else { HealthStatus::Degraded },
+            message: format!("{} of {} peers connected", connected, peers.len()),
+            metrics: HashMap::from([
+                ("peers".to_string(), peers.len() as f64),
+                ("connected_peers".to_string(), connected as f64),
+            ]),
+        });
+        
+        let lagging_primaries = self.storage.lagging_primary_replicas().await;
+        components.push(match self.storage.get_latest_block().await {
+            Ok(latest) => ComponentHealth {
+                name: "storage".to_string(),
+                status: if lagging_primaries.is_empty() { HealthStatus::Up } else { HealthStatus::Degraded },
+                message: if lagging_primaries.is_empty() {
+                    "Storage is readable".to_string()
+                } else {
+                    format!("Primary replicas past the critical lag threshold: {}", lagging_primaries.join(", "))
+                },
+                metrics: HashMap::from([
+                    ("latest_block_height".to_string(), latest.map_or(0, |block| block.height) as f64),
+                    ("state_snapshots".to_string(), self.storage.get_state_snapshot_heights().await.len() as f64),
+                    ("lagging_primary_replicas".to_string(), lagging_primaries.len() as f64),
+                ]),
+            },
+            Err(e) => ComponentHealth::down("storage", e),
+        });
+        
+        components.push(ComponentHealth {
+            name: "mempool".to_string(),
+            status: if maintenance.is_some() { HealthStatus::Degraded } else { HealthStatus::Up },
+            message: match &maintenance {
+                Some(mode) => format!("Maintenance mode set by {}, not accepting transactions: {}", mode.set_by, mode.reason),
+                None => "Mempool is accepting transactions".to_string(),
+            },
+            metrics: HashMap::from([
+                ("transactions".to_string(), self.mempool.read().await.len() as f64),
+                ("maintenance_mode".to_string(), if maintenance.is_some() { 1.0 } else { 0.0 }),
+            ]),
+        });
+        
+        let status = if components.iter().any(|c| c.status == HealthStatus::Down) {
+            HealthStatus::Down
+        } else if components.iter().any(|c| c.status != HealthStatus::Up) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Up
+        };
+        let unhealthy: Vec<&str> = components.iter()
+            .filter(|c| c.status != HealthStatus::Up)
+            .map(|c| c.name.as_str())
+            .collect();
+        ServiceHealth {
+            message: if unhealthy.is_empty() {
+                "All components are operational".to_string()
+            } else {
+                format!("Unhealthy components: {}", unhealthy.join(", "))
+            },
+            status,
+            components,
+            timestamp: std::time::SystemTime::now(),
+            maintenance,
+        }
+    }
+    
+    /// Running configuration, including reloaded dynamic parameters
+    pub fn current_config(&self) -> Arc<GlobalSyncConfig> {
+        self.config_tx.borrow().clone()
+    }
+    
+    /// Observe the running configuration as reloads change it
+    pub fn subscribe_config(&self) -> watch::Receiver<Arc<GlobalSyncConfig>> {
+        self.config_tx.subscribe()
+    }
+    
+    /// Set where reloads read the configuration from
+    pub async fn set_config_source(&self, source: ConfigSource) {
+        *self.config_source.lock().await = Some(source);
+    }
+    
+    /// Re-read the configuration and apply its dynamic parameters. Changes
+    /// to static parameters are left out and listed in the returned record,
+    /// which is appended to the reload log.
+    pub async fn reload_config(&self, requested_by: &str) -> GarpResult<ConfigReloadRecord> {
+        let source = self.config_source.lock().await;
+        let Some(load) = source.as_ref() else {
+            return Err(GarpError::ConfigError("No configuration source to reload from".to_string()));
+        };
+        
+        let (updated, record) = match load().and_then(|reloaded| self.current_config().apply_reload(&reloaded, requested_by)) {
+            Ok(reload) => reload,
+            Err(e) => {
+                warn!(requested_by = %requested_by, "Configuration reload rejected: {}", e);
+                return Err(e);
+            }
+        };
+        
+        let record = self.storage.append_config_reload(record).await?;
+        if !record.applied.is_empty() {
+            self.config_tx.send_replace(Arc::new(updated));
+        }
+        if record.ignored.is_empty() {
+            info!(requested_by = %requested_by, changes = %record.summary(), "Configuration reloaded");
+        } else {
+            warn!(requested_by = %requested_by, "Configuration reloaded: {}", record.summary());
+        }
+        Ok(record)
+    }
+    
+    /// Configuration reloads, oldest first
+    pub async fn get_config_reload_log(&self) -> GarpResult<Vec<ConfigReloadRecord>> {
+        self.storage.list_config_reloads().await
+    }
+    
+    /// Secret admin API tokens are signed with, if admin access is enabled
+    pub fn jwt_secret(&self) -> Option<String> {
+        self.config.api.jwt_secret.clone()
+    }
+    
+    /// Ask the process running the synchronizer to stop it gracefully
+    pub fn request_shutdown(&self) {
+        self.shutdown_requested.notify_one();
+    }
+    
+    /// Wait until a shutdown is requested
+    pub async fn shutdown_requested(&self) {
+        self.shutdown_requested.notified().await;
+    }
+    
+    /// States of all domains the coordinator knows, ordered by domain ID
+    pub async fn list_domain_states(&self) -> Vec<crate::cross_domain::DomainState> {
+        self.cross_domain_coordinator.list_domain_states().await
+    }
+    
+    /// Re-discover a domain and re-initialize its state
+    pub async fn reload_domain(&self, domain_id: &str) -> GarpResult<crate::cross_domain::DomainState> {
+        self.cross_domain_coordinator.reload_domain(&domain_id.to_string()).await
+    }
+    
+    /// Number of pooled transactions and the transaction pool statistics
+    pub async fn get_pool_stats(&self) -> (usize, crate::storage::PoolStats) {
+        self.storage.pool_stats().await
+    }
+    
+    /// Mempool transactions in submission order, with their stored payloads
+    pub async fn get_mempool_entries(&self) -> GarpResult<Vec<MempoolEntry>> {
+        let transaction_ids = self.mempool.read().await.clone();
+        let mut entries = Vec::with_capacity(transaction_ids.len());
+        for (position, transaction_id) in transaction_ids.into_iter().enumerate() {
+            let transaction = self.storage.get_transaction(&transaction_id).await?;
+            entries.push(MempoolEntry { position, transaction_id, transaction });
+        }
+        Ok(entries)
+    }
+    
+    /// Remove a transaction from the mempool, given its UUID
+    pub async fn remove_mempool_transaction(&self, transaction_id: &str) -> GarpResult<()> {
+        let id = TransactionId(Uuid::parse_str(transaction_id)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid transaction id {}: {}", transaction_id, e)))?);
+        let mut mempool = self.mempool.write().await;
+        let position = mempool.iter().position(|tid| *tid == id)
+            .ok_or_else(|| GarpError::NotFound(format!("Transaction {} is not in the mempool", transaction_id)))?;
+        mempool.remove(position);
+        self.storage.remove_from_pool(&id).await;
+        Ok(())
+    }
+    
+    /// Force the consensus engine into the next view, returning it
+    pub async fn force_view_change(&self, reason: String) -> GarpResult<u64> {
+        self.consensus_engine.force_view_change(reason).await
+    }
+    
     /// Start event processor
     async fn start_event_processor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let event_rx = self.event_rx.clone();
@@ -492,18 +1426,45 @@ impl GlobalSynchronizer {
         let state = self.state.clone();
         
         let storage = self.storage.clone();
+        let network_manager = self.network_manager.clone();
+        let event_tx = self.event_tx.clone();
+        let status_tx = self.status_tx.clone();
+        let checkpoint_interval = self.config.consensus.checkpoint_interval;
+        let batch_proposals = self.config.consensus.batch_proposals;
+        
+        // Feed decided proposals back in as consensus results
+        let mut consensus_results = consensus_engine.subscribe_results();
+        let results_tx = event_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                match consensus_results.recv().await {
+                    Ok(result) => {
+                        if results_tx.send(GlobalSyncEvent::ConsensusResult(result)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Dropped {} consensus results", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        
         let handle = tokio::spawn(async move {
             let mut event_rx = event_rx.lock().await;
             
             while let Some(event) = event_rx.recv().await {
                 match event {
                     GlobalSyncEvent::NewTransaction(transaction) => {
-                        // Start consensus for the transaction
-                        if let Err(e) = consensus_engine.start_consensus(transaction.clone()).await {
-                            error!("Failed to start consensus for transaction: {}", e);
+                        // Batched transactions reach consensus with their block
+                        if !batch_proposals {
+                            if let Err(e) = consensus_engine.start_consensus(transaction.clone()).await {
+                                error!("Failed to start consensus for transaction: {}", e);
+                            }
                         }
                         // Gossip stub: broadcast proposal to peers (placeholder)
-                        info!("Broadcasting proposal for transaction: {:?}", transaction.transaction_id);
+                        info!(transaction_id = %transaction.transaction_id, "Broadcasting transaction proposal");
                     }
                     
                     GlobalSyncEvent::ConsensusResult(result) => {
@@ -512,7 +1473,10 @@ impl GlobalSynchronizer {
                             result,
                             &active_transactions,
                             &settlement_engine,
+                            &consensus_engine,
+                            &storage,
                             &metrics,
+                            &status_tx,
                         ).await;
                     }
                     
@@ -522,6 +1486,7 @@ impl GlobalSynchronizer {
                             result,
                             &active_transactions,
                             &metrics,
+                            &status_tx,
                         ).await;
                     }
                     
@@ -535,13 +1500,31 @@ impl GlobalSynchronizer {
                     }
                     
                     GlobalSyncEvent::BlockFinalized(block) => {
+                        let height = block.header.slot;
                         // Handle block finalization
                         Self::handle_block_finalization(
                             block,
                             &state,
                             &metrics,
                             &storage,
+                            checkpoint_interval,
                         ).await;
+                        
+                        // Apply validator set changes scheduled for this epoch
+                        match consensus_engine.advance_epoch(height).await {
+                            Ok(Some(update)) => {
+                                if let Err(e) = event_tx.send(GlobalSyncEvent::ValidatorSetChanged(update)) {
+                                    error!("Failed to send validator set change event: {}", e);
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => error!("Failed to advance validator epoch at height {}: {}", height, e),
+                        }
+                    }
+                    
+                    GlobalSyncEvent::ValidatorSetChanged(update) => {
+                        // Announce the new active set so sync domains can track it
+                        Self::announce_validator_set(update, &network_manager).await;
                     }
                     
                     GlobalSyncEvent::Shutdown => {
@@ -560,40 +1543,129 @@ impl GlobalSynchronizer {
         Ok(handle)
     }
     
+    /// Move a transaction to `status`, publishing the change to subscribers
+    fn set_transaction_status(
+        active_tx: &mut ActiveTransaction,
+        status: TransactionStatus,
+        status_tx: &broadcast::Sender<TransactionStatusUpdate>,
+    ) {
+        active_tx.updated_at = Instant::now();
+        if active_tx.status == status {
+            return;
+        }
+        active_tx.status = status.clone();
+        // No subscribers is not an error
+        let _ = status_tx.send(TransactionStatusUpdate {
+            transaction_id: active_tx.transaction_id.clone(),
+            status,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+    
     /// Handle consensus result
     async fn handle_consensus_result(
         result: ConsensusResult,
         active_transactions: &Arc<RwLock<HashMap<TransactionId, ActiveTransaction>>>,
         settlement_engine: &Arc<SettlementEngine>,
+        consensus_engine: &Arc<ConsensusEngine>,
+        storage: &Arc<GlobalStorage>,
         metrics: &Arc<GlobalSyncMetrics>,
+        status_tx: &broadcast::Sender<TransactionStatusUpdate>,
     ) {
+        if let Some(batch) = result.batch.clone() {
+            Self::handle_batch_result(result, batch, active_transactions, settlement_engine, consensus_engine, storage, metrics, status_tx).await;
+            return;
+        }
+        
         let mut active_txs = active_transactions.write().await;
         
         if let Some(active_tx) = active_txs.get_mut(&result.transaction_id) {
-            if result.approved {
-                active_tx.status = TransactionStatus::ConsensusReached;
-                active_tx.updated_at = Instant::now();
-                
-                // Start settlement
-                let settlement_request = SettlementRequest {
-                    transaction_id: result.transaction_id.clone(),
-                    cross_domain_tx: active_tx.cross_domain_tx.clone(),
-                    consensus_proof: result.proof,
-                };
-                
-                if let Err(e) = settlement_engine.start_settlement(settlement_request).await {
-                    error!("Failed to start settlement: {}", e);
-                    active_tx.status = TransactionStatus::Failed(e.to_string());
-                } else {
-                    active_tx.status = TransactionStatus::SettlementInProgress;
-                    active_tx.settlement_status = SettlementStatus::InProgress;
+            Self::apply_consensus_decision(active_tx, result.approved, result.proof, settlement_engine, metrics, status_tx).await;
+        }
+    }
+    
+    /// Handle the decision on a batch proposal. An approved batch moves
+    /// every included transaction to settlement and assigns them all to
+    /// the batch's block. A batch rejected for named transactions fails
+    /// those and is re-proposed without them; any other rejection fails
+    /// the whole batch.
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_batch_result(
+        result: ConsensusResult,
+        batch: BatchProposal,
+        active_transactions: &Arc<RwLock<HashMap<TransactionId, ActiveTransaction>>>,
+        settlement_engine: &Arc<SettlementEngine>,
+        consensus_engine: &Arc<ConsensusEngine>,
+        storage: &Arc<GlobalStorage>,
+        metrics: &Arc<GlobalSyncMetrics>,
+        status_tx: &broadcast::Sender<TransactionStatusUpdate>,
+    ) {
+        let decided = if result.approved || result.rejected_transactions.is_empty() {
+            &batch.transaction_ids
+        } else {
+            &result.rejected_transactions
+        };
+        {
+            let mut active_txs = active_transactions.write().await;
+            for transaction_id in decided {
+                if let Some(active_tx) = active_txs.get_mut(transaction_id) {
+                    Self::apply_consensus_decision(active_tx, result.approved, result.proof.clone(), settlement_engine, metrics, status_tx).await;
                 }
-                
-                metrics.increment_consensus_reached().await;
+            }
+        }
+        
+        if result.approved {
+            if let Err(e) = storage.assign_block_transactions(batch.height, batch.block_hash(), &batch.transaction_ids, false).await {
+                error!("Failed to assign batch transactions to block {}: {}", batch.height, e);
+            }
+        } else if !result.rejected_transactions.is_empty() {
+            let retry = batch.without(&result.rejected_transactions);
+            if retry.is_empty() {
+                return;
+            }
+            info!(
+                "Re-proposing batch for block {} without {} rejected transactions",
+                batch.height,
+                result.rejected_transactions.len()
+            );
+            if let Err(e) = consensus_engine.propose_batch(retry).await {
+                error!("Failed to re-propose batch for block {}: {}", batch.height, e);
+            }
+        }
+    }
+    
+    /// Move a transaction on from consensus: into settlement when
+    /// approved, to failed when rejected
+    async fn apply_consensus_decision(
+        active_tx: &mut ActiveTransaction,
+        approved: bool,
+        proof: ConsensusProof,
+        settlement_engine: &Arc<SettlementEngine>,
+        metrics: &Arc<GlobalSyncMetrics>,
+        status_tx: &broadcast::Sender<TransactionStatusUpdate>,
+    ) {
+        if approved {
+            Self::set_transaction_status(active_tx, TransactionStatus::ConsensusReached, status_tx);
+            
+            // Start settlement
+            let settlement_request = SettlementRequest {
+                transaction_id: active_tx.transaction_id.clone(),
+                cross_domain_tx: active_tx.cross_domain_tx.clone(),
+                consensus_proof: proof,
+            };
+            
+            if let Err(e) = settlement_engine.start_settlement(settlement_request).await {
+                error!("Failed to start settlement: {}", e);
+                Self::set_transaction_status(active_tx, TransactionStatus::Failed(e.to_string()), status_tx);
             } else {
-                active_tx.status = TransactionStatus::Failed("Consensus rejected".to_string());
-                metrics.increment_consensus_rejected().await;
+                Self::set_transaction_status(active_tx, TransactionStatus::SettlementInProgress, status_tx);
+                active_tx.settlement_status = SettlementStatus::InProgress;
             }
+            
+            metrics.increment_consensus_reached().await;
+        } else {
+            Self::set_transaction_status(active_tx, TransactionStatus::Failed("Consensus rejected".to_string()), status_tx);
+            metrics.increment_consensus_rejected().await;
         }
     }
     
@@ -602,16 +1674,17 @@ impl GlobalSynchronizer {
         result: SettlementResult,
         active_transactions: &Arc<RwLock<HashMap<TransactionId, ActiveTransaction>>>,
         metrics: &Arc<GlobalSyncMetrics>,
+        status_tx: &broadcast::Sender<TransactionStatusUpdate>,
     ) {
         let mut active_txs = active_transactions.write().await;
         
         if let Some(active_tx) = active_txs.get_mut(&result.transaction_id) {
             if result.success {
-                active_tx.status = TransactionStatus::Finalized;
+                Self::set_transaction_status(active_tx, TransactionStatus::Finalized, status_tx);
                 active_tx.settlement_status = SettlementStatus::Completed;
                 metrics.increment_transactions_finalized().await;
             } else {
-                active_tx.status = TransactionStatus::Failed(result.error.unwrap_or_default());
+                Self::set_transaction_status(active_tx, TransactionStatus::Failed(result.error.clone().unwrap_or_default()), status_tx);
                 active_tx.settlement_status = SettlementStatus::Failed(
                     result.error.unwrap_or_default()
                 );
@@ -650,12 +1723,31 @@ impl GlobalSynchronizer {
         }
     }
     
+    /// Broadcast a validator set change to connected domains
+    async fn announce_validator_set(update: ValidatorSetUpdate, network_manager: &Arc<NetworkManager>) {
+        info!(epoch = update.epoch, validators = update.validators.len(), "Announcing validator set");
+        let data = match serde_json::to_vec(&update) {
+            Ok(data) => data,
+            Err(e) => {
+                error!("Failed to serialize validator set update: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = network_manager
+            .send_message(MessageDestination::Broadcast, "validator_set".to_string(), data, MessagePriority::High)
+            .await
+        {
+            warn!("Failed to broadcast validator set for epoch {}: {}", update.epoch, e);
+        }
+    }
+    
     /// Handle block finalization
     async fn handle_block_finalization(
         block: GlobalBlock,
         state: &Arc<RwLock<GlobalSyncState>>, 
         metrics: &Arc<GlobalSyncMetrics>,
         storage: &Arc<GlobalStorage>,
+        checkpoint_interval: u64,
     ) {
         let mut state = state.write().await;
         // Align with canonical fields: slot as height, hex-encoded hash for display/state
@@ -693,26 +1785,39 @@ impl GlobalSynchronizer {
         if let Err(e) = storage.store_block(block_hash.clone(), info).await {
             error!("Failed to store finalized block: {}", e);
         }
+        
+        // Snapshot state at checkpoints so joining and restarting nodes can
+        // skip replaying from genesis. Serialization runs in the background.
+        if checkpoint_interval > 0 && block.header.slot % checkpoint_interval == 0 {
+            let storage = storage.clone();
+            let height = block.header.slot;
+            let block_hash = block_hash.clone();
+            tokio::spawn(async move {
+                if let Err(e) = storage.create_state_snapshot(height, block_hash).await {
+                    error!("Failed to snapshot state at height {}: {}", height, e);
+                }
+            });
+        }
 
         // Try to load and log the finality certificate for this block
         let block_hash_hex = hex::encode(&block_hash);
         match storage.get_finality_certificate_by_height(block.header.slot).await {
             Ok(Some(cert)) => {
                 info!(
-                    "Finality certificate found for block {} (height {}), validators: {}",
-                    block_hash_hex,
-                    cert.height,
-                    cert.signatures.len()
+                    block_hash = %block_hash_hex,
+                    height = cert.height,
+                    validators = cert.signatures.len(),
+                    "Finality certificate found"
                 );
             }
             Ok(None) => {
                 match storage.get_finality_certificate_by_hash(block_hash_hex.clone()).await {
                     Ok(Some(cert)) => {
                         info!(
-                            "Finality certificate found by hash for block {} (height {}), validators: {}",
-                            block_hash_hex,
-                            cert.height,
-                            cert.signatures.len()
+                            block_hash = %block_hash_hex,
+                            height = cert.height,
+                            validators = cert.signatures.len(),
+                            "Finality certificate found by hash"
                         );
                     }
                     Ok(None) => {
@@ -739,13 +1844,14 @@ impl GlobalSynchronizer {
             }
         }
 
-        // Tag transactions in this block with height/hash and record index
+        // Tag transactions in this block with height/hash and record index.
+        // Transactions stored before a restart are reloaded from the backend.
         let tx_ids: Vec<TransactionId> = block
             .transactions
             .iter()
             .map(|t| t.id.clone())
             .collect();
-        if let Err(e) = storage.assign_block_transactions(block.header.slot, block_hash.clone(), &tx_ids).await {
+        if let Err(e) = storage.assign_block_transactions(block.header.slot, block_hash.clone(), &tx_ids, true).await {
             error!("Failed to assign transactions to block {}: {}", block.header.slot, e);
         }
     }
@@ -811,6 +1917,7 @@ impl GlobalSynchronizer {
     async fn start_transaction_monitor(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
         let active_transactions = self.active_transactions.clone();
         let metrics = self.metrics.clone();
+        let status_tx = self.status_tx.clone();
         
         let handle = tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(5));
@@ -827,8 +1934,7 @@ impl GlobalSynchronizer {
                     
                     for (tx_id, active_tx) in active_txs.iter_mut() {
                         if now > active_tx.timeout_at {
-                            active_tx.status = TransactionStatus::TimedOut;
-                            active_tx.updated_at = now;
+                            Self::set_transaction_status(active_tx, TransactionStatus::TimedOut, &status_tx);
                             timed_out_transactions.push(tx_id.clone());
                         }
                     }
@@ -899,6 +2005,143 @@ impl GlobalSynchronizer {
         Ok(handle)
     }
     
+    /// Spawn the periodic coordinator monitors with the intervals of `config`
+    fn spawn_monitors(coordinator: &Arc<CrossDomainCoordinator>, config: &GlobalSyncConfig) -> Vec<tokio::task::JoinHandle<()>> {
+        let intervals = &config.cross_domain;
+        vec![
+            Arc::new(TimelockMonitor::new(
+                coordinator.clone(),
+                Duration::from_millis(intervals.timelock_check_interval_ms),
+            )).spawn(),
+            Arc::new(ChannelSettlementMonitor::new(
+                coordinator.clone(),
+                Duration::from_millis(intervals.channel_settlement_interval_ms),
+            )).spawn(),
+            Arc::new(GovernanceMonitor::new(
+                coordinator.clone(),
+                Duration::from_millis(intervals.governance_check_interval_ms),
+            )).spawn(),
+            Arc::new(SwapTimeoutMonitor::new(
+                coordinator.clone(),
+                Duration::from_millis(intervals.swap_check_interval_ms),
+            )).spawn(),
+            Arc::new(CompensationMonitor::new(
+                coordinator.clone(),
+                Duration::from_millis(intervals.compensation_check_interval_ms),
+            )).spawn(),
+        ]
+    }
+    
+    /// Start config watcher, applying reloaded parameters to components that
+    /// fixed them at construction
+    async fn start_config_watcher(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let mut updates = self.subscribe_config();
+        let coordinator = self.cross_domain_coordinator.clone();
+        let storage = self.storage.clone();
+        let monitor_tasks = self.monitor_tasks.clone();
+        
+        let handle = tokio::spawn(async move {
+            let mut running = updates.borrow_and_update().clone();
+            while updates.changed().await.is_ok() {
+                let config = updates.borrow_and_update().clone();
+                
+                if config.mempool.max_pool_size != running.mempool.max_pool_size
+                    || config.mempool.pool_age_boost_secs != running.mempool.pool_age_boost_secs
+                {
+                    storage.set_pool_limits(
+                        config.mempool.max_pool_size,
+                        Duration::from_secs(config.mempool.pool_age_boost_secs),
+                    ).await;
+                }
+                
+                let intervals = |c: &GlobalSyncConfig| {
+                    let c = &c.cross_domain;
+                    [
+                        c.timelock_check_interval_ms,
+                        c.channel_settlement_interval_ms,
+                        c.governance_check_interval_ms,
+                        c.swap_check_interval_ms,
+                        c.compensation_check_interval_ms,
+                    ]
+                };
+                if intervals(&config) != intervals(&running) {
+                    let mut tasks = monitor_tasks.lock().await;
+                    for task in tasks.drain(..) {
+                        task.abort();
+                    }
+                    *tasks = Self::spawn_monitors(&coordinator, &config);
+                    info!("Restarted coordinator monitors with reloaded intervals");
+                }
+                
+                running = config;
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Start orphan handler, returning the transactions of orphaned blocks
+    /// to the mempool
+    async fn start_orphan_handler(&self) -> GarpResult<tokio::task::JoinHandle<()>> {
+        let mut orphans = self.storage.subscribe_orphaned_blocks();
+        let mempool = self.mempool.clone();
+        let storage = self.storage.clone();
+        
+        let handle = tokio::spawn(async move {
+            loop {
+                match orphans.recv().await {
+                    Ok(orphan) => Self::requeue_orphaned_transactions(&orphan.transactions, &mempool, &storage).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Orphan handler lagged, {} orphaned blocks were not re-queued", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        
+        Ok(handle)
+    }
+    
+    /// Put transactions of an orphaned block back at the front of the
+    /// mempool, in block order, and pool them at top priority so they are
+    /// proposed first
+    async fn requeue_orphaned_transactions(
+        transactions: &[TransactionId],
+        mempool: &Arc<RwLock<Vec<TransactionId>>>,
+        storage: &Arc<GlobalStorage>,
+    ) {
+        let requeued: Vec<TransactionId> = {
+            let mut mempool = mempool.write().await;
+            let requeued: Vec<TransactionId> = transactions.iter()
+                .filter(|tid| !mempool.contains(tid))
+                .cloned()
+                .collect();
+            mempool.splice(0..0, requeued.clone());
+            requeued
+        };
+        if requeued.is_empty() {
+            return;
+        }
+        info!(transactions = requeued.len(), "Re-queueing transactions from an orphaned block");
+        
+        let added_at = Instant::now();
+        for tid in requeued {
+            let stored = match storage.get_transaction(&tid).await {
+                Ok(stored) => stored,
+                Err(e) => {
+                    warn!("Failed to load orphaned transaction {}: {}", tid, e);
+                    None
+                }
+            };
+            let (dependencies, size) = stored.map_or((Vec::new(), 0), |tx| (tx.dependencies, tx.transaction_data.len()));
+            let mut entry = PoolTransaction::new(tid.clone(), &HashMap::new(), dependencies, size, added_at);
+            entry.priority = u64::MAX;
+            if let Err(e) = storage.add_to_pool(entry).await {
+                warn!("Failed to re-pool orphaned transaction {}: {}", tid, e);
+            }
+        }
+    }
+    
     /// Create wallet
     pub async fn create_wallet(&self, request: wallet::CreateWalletRequest) -> GarpResult<wallet::CreateWalletResponse> {
         self.bridge.wallet_manager.create_wallet(request).await
@@ -968,6 +2211,85 @@ impl GlobalSynchronizer {
         self.bridge.get_validator(validator_id).await
     }
     
+    /// Peers with their connection health
+    pub async fn list_peers(&self) -> Vec<crate::network::PeerStatus> {
+        self.network_manager.list_peers().await
+    }
+    
+    /// Register a sync domain at runtime, or refresh its registration
+    pub async fn register_domain(&self, announcement: DomainAnnouncement) -> GarpResult<DomainRegistration> {
+        self.cross_domain_coordinator.register_domain(announcement).await
+    }
+    
+    /// Refresh a domain's registration TTL
+    pub async fn domain_heartbeat(&self, domain_id: &str) -> GarpResult<DomainRegistration> {
+        self.cross_domain_coordinator.domain_heartbeat(&domain_id.to_string()).await
+    }
+    
+    /// Known domains with their remaining registration TTL
+    pub async fn list_registered_domains(&self) -> Vec<RegisteredDomain> {
+        self.cross_domain_coordinator.list_registered_domains().await
+    }
+    
+    /// Rotate the node signing key and announce the new key to peers
+    pub async fn rotate_node_key(&self, requested_by: &str) -> GarpResult<KeyRotation> {
+        let rotation = self.node_keys.rotate().await?;
+        warn!(requested_by = %requested_by, "Node signing key rotated to epoch {}", rotation.epoch);
+        Ok(rotation)
+    }
+    
+    /// Keys the node has signed with, `None` without a keystore
+    pub async fn node_key_history(&self) -> Option<KeyHistory> {
+        self.node_keys.history().await
+    }
+    
+    /// Apply a key rotation announced by a validator or domain
+    pub async fn accept_key_rotation(&self, rotation: &KeyRotation) -> GarpResult<()> {
+        self.node_keys.accept(rotation).await
+    }
+    
+    /// Network-wide message metrics
+    pub async fn get_network_metrics(&self) -> crate::network::NetworkMetrics {
+        self.network_manager.get_metrics().await
+    }
+    
+    /// Connect to a peer by socket address, returning its peer ID
+    pub async fn connect_peer(&self, address: &str) -> GarpResult<String> {
+        let address: std::net::SocketAddr = address.parse()
+            .map_err(|e| GarpError::ValidationError(format!("Invalid peer address {}: {}", address, e)))?;
+        self.network_manager.connect_peer(address).await
+    }
+    
+    /// Disconnect a peer
+    pub async fn disconnect_peer(&self, peer_id: &str) -> GarpResult<()> {
+        self.network_manager.disconnect_peer(&peer_id.to_string()).await
+    }
+    
+    /// Ban a domain, optionally for a limited duration
+    pub async fn ban_domain(&self, domain_id: &str, duration: Option<Duration>) -> GarpResult<()> {
+        self.network_manager.ban_peer(&domain_id.to_string(), duration).await
+    }
+    
+    /// Lift a domain ban
+    pub async fn unban_domain(&self, domain_id: &str) -> GarpResult<()> {
+        self.network_manager.unban_peer(&domain_id.to_string()).await
+    }
+    
+    /// Put a domain into maintenance mode for at least `duration`
+    pub async fn suspend_domain(&self, domain_id: &str, reason: String, duration: Duration) -> GarpResult<DomainSuspension> {
+        self.cross_domain_coordinator.suspend_domain(&domain_id.to_string(), reason, duration).await
+    }
+    
+    /// End a domain's maintenance mode early
+    pub async fn resume_domain(&self, domain_id: &str) -> GarpResult<DomainSuspension> {
+        self.cross_domain_coordinator.resume_domain(&domain_id.to_string()).await
+    }
+    
+    /// Current reputation of a domain or validator
+    pub async fn get_reputation(&self, participant_id: &str) -> ReputationScore {
+        self.reputation.get(participant_id).await
+    }
+    
     /// Get asset price
     pub async fn get_asset_price(&self, symbol: &str) -> GarpResult<Option<f64>> {
         Ok(self.bridge.price_oracle.get_price(symbol).await)
@@ -1066,12 +2388,39 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             timeout_at: chrono::Utc::now(),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
             metadata: HashMap::new(),
         };
         
         let result = synchronizer.submit_transaction(transaction).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_cross_domain_submission_reports_per_domain_confirmations() {
+        let synchronizer = GlobalSynchronizer::new(GlobalSyncConfig::default()).await.unwrap();
+        let submission: CrossDomainSubmission = serde_json::from_value(serde_json::json!({
+            "source_domain": "domain1",
+            "target_domains": ["domain2", "domain3"],
+            "transaction_type": { "AtomicSwap": { "swap_id": "s1", "asset_a": "A", "asset_b": "B", "amount_a": 1, "amount_b": 2 } },
+        })).unwrap();
+        let transaction_id = synchronizer.submit_cross_domain(submission.clone()).await.unwrap();
+
+        let status = synchronizer.get_cross_domain_status(&transaction_id.to_string()).await.unwrap().unwrap();
+        assert_eq!(status.status, TransactionStatus::Received);
+        assert_eq!(status.required_confirmations, 2);
+        let domains: Vec<&str> = status.confirmations.iter().map(|c| c.domain_id.as_str()).collect();
+        assert_eq!(domains, vec!["domain2", "domain3"]);
+        assert!(status.confirmations.iter().all(|c| c.status == ConfirmationStatus::Pending && c.confirmed_at.is_none()));
+
+        assert!(synchronizer.get_cross_domain_status(&Uuid::new_v4().to_string()).await.unwrap().is_none());
+        assert!(synchronizer.get_cross_domain_status("not-a-uuid").await.is_err());
+
+        let no_targets = CrossDomainSubmission { target_domains: Vec::new(), ..submission };
+        assert!(synchronizer.submit_cross_domain(no_targets).await.is_err());
+    }
     
     #[tokio::test]
     async fn test_state_management() {
@@ -1083,5 +2432,130 @@ mod tests {
         let state = synchronizer.get_state().await;
         assert_eq!(state.status, SyncStatus::Starting);
         assert_eq!(state.block_height, 0);
+    }    
+    #[tokio::test]
+    async fn test_lagging_primary_replica_degrades_health() {
+        let synchronizer = GlobalSynchronizer::new(GlobalSyncConfig::default()).await.unwrap();
+        synchronizer.state.write().await.status = SyncStatus::Active;
+        synchronizer.storage.add_replication_target(crate::storage::ReplicationTarget {
+            target_id: "replica-1".to_string(),
+            address: "replica-1:5432".to_string(),
+            target_type: crate::storage::ReplicationTargetType::Primary,
+            mode: crate::storage::ReplicationMode::Synchronous,
+            config: crate::storage::ReplicationConfig {
+                batch_size: 100,
+                sync_interval: Duration::from_secs(1),
+                retry_attempts: 3,
+                timeout: Duration::from_secs(5),
+                compression: false,
+                encryption: false,
+            },
+            status: crate::storage::TargetStatus::Online,
+            last_sync: None,
+        }).await;
+        let storage_status = |health: &ServiceHealth| {
+            health.components.iter().find(|c| c.name == "storage").unwrap().status.clone()
+        };
+        
+        let critical = Duration::from_millis(synchronizer.config.database.replication.critical_lag_threshold_ms);
+        synchronizer.storage.record_replication_lag("replica-1", critical * 2).await.unwrap();
+        synchronizer.storage.check_replication_health().await;
+        let health = synchronizer.get_health_status().await;
+        assert_eq!(storage_status(&health), HealthStatus::Degraded);
+        let alerts = synchronizer.get_replication_alerts().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, crate::storage::ReplicationAlertSeverity::Critical);
+        
+        synchronizer.storage.record_replication_lag("replica-1", Duration::ZERO).await.unwrap();
+        synchronizer.storage.check_replication_health().await;
+        assert_eq!(storage_status(&synchronizer.get_health_status().await), HealthStatus::Up);
+    }
+    
+    #[tokio::test]
+    async fn test_maintenance_mode_refuses_new_transactions_only() {
+        let mut config = GlobalSyncConfig::default();
+        config.api.persist_maintenance_mode = true;
+        let synchronizer = GlobalSynchronizer::new(config).await.unwrap();
+        synchronizer.state.write().await.status = SyncStatus::Active;
+        let transaction = |data: Vec<u8>| CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "domain1".to_string(),
+            target_domains: vec!["domain2".to_string()],
+            transaction_type: crate::cross_domain::CrossDomainTransactionType::AssetTransfer { asset_id: "asset".into(), amount: 1, from_address: "a".into(), to_address: "b".into() },
+            data,
+            dependencies: vec![],
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: crate::cross_domain::TransactionStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now(),
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        };
+        let in_flight = synchronizer.submit_transaction(transaction(vec![1])).await.unwrap();
+        
+        assert!(synchronizer.set_maintenance_mode(true, " ".to_string(), "operator").await.is_err());
+        synchronizer.set_maintenance_mode(true, "storage migration".to_string(), "operator").await.unwrap();
+        let err = synchronizer.submit_transaction(transaction(vec![2])).await.unwrap_err();
+        assert!(matches!(&err, GarpError::Maintenance(reason) if reason == "storage migration"));
+        assert!(err.is_retryable());
+        assert_eq!(synchronizer.mempool.read().await.len(), 1);
+        
+        let health = synchronizer.get_health_status().await;
+        let mode = health.maintenance.expect("maintenance reported in health");
+        assert_eq!((mode.reason.as_str(), mode.set_by.as_str()), ("storage migration", "operator"));
+        let mempool = health.components.iter().find(|c| c.name == "mempool").unwrap();
+        assert_eq!(mempool.status, HealthStatus::Degraded);
+        assert_eq!(synchronizer.storage.get_maintenance_mode().await.unwrap(), Some(mode));
+        
+        // The transaction accepted before maintenance still settles
+        GlobalSynchronizer::handle_settlement_result(
+            SettlementResult { transaction_id: in_flight.clone(), success: true, error: None },
+            &synchronizer.active_transactions,
+            &synchronizer.metrics,
+            &synchronizer.status_tx,
+        ).await;
+        assert_eq!(synchronizer.get_transaction_status(&in_flight).await.unwrap(), Some(TransactionStatus::Finalized));
+        
+        synchronizer.set_maintenance_mode(false, String::new(), "operator").await.unwrap();
+        assert!(synchronizer.get_health_status().await.maintenance.is_none());
+        assert!(synchronizer.storage.get_maintenance_mode().await.unwrap().is_none());
+        assert!(synchronizer.submit_transaction(transaction(vec![3])).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_reload_config_applies_dynamic_parameters() {
+        let config = GlobalSyncConfig::default();
+        let synchronizer = GlobalSynchronizer::new(config.clone()).await.unwrap();
+        assert!(synchronizer.reload_config("operator").await.is_err());
+        
+        let mut reloaded = config.clone();
+        reloaded.cross_domain.transaction_timeout_ms = 1234;
+        reloaded.database.url = "postgresql://elsewhere/global_sync".to_string();
+        synchronizer.set_config_source(Arc::new(move || Ok(reloaded.clone()))).await;
+        
+        let mut updates = synchronizer.subscribe_config();
+        let record = synchronizer.reload_config("operator").await.unwrap();
+        assert_eq!(record.sequence, 0);
+        assert_eq!(record.applied.len(), 1);
+        assert_eq!(record.ignored, vec!["database.url"]);
+        
+        assert!(updates.has_changed().unwrap());
+        let running = synchronizer.current_config();
+        assert_eq!(running.transaction_timeout(), Duration::from_millis(1234));
+        assert_eq!(running.database.url, config.database.url);
+        
+        // Reloading an unchanged file is still audited, without an update
+        updates.borrow_and_update();
+        let record = synchronizer.reload_config("SIGHUP").await.unwrap();
+        assert_eq!(record.sequence, 1);
+        assert!(record.applied.is_empty());
+        assert!(!updates.has_changed().unwrap());
+        
+        let log = synchronizer.get_config_reload_log().await.unwrap();
+        assert_eq!(log.iter().map(|r| r.requested_by.as_str()).collect::<Vec<_>>(), vec!["operator", "SIGHUP"]);
     }
 }
\ No newline at end of file
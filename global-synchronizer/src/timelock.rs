@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{debug, error, info};
+
+use garp_common::GarpResult;
+use garp_common::types::TransactionId;
+
+use crate::cross_domain::{CrossDomainCoordinator, CrossDomainTransaction};
+use crate::storage::DomainId;
+
+/// Cross-domain transactions held back until their `not_before` time
+#[derive(Debug, Default)]
+pub struct TimelockQueue {
+    /// Queued transactions keyed by ID
+    entries: RwLock<HashMap<TransactionId, CrossDomainTransaction>>,
+}
+
+/// Summary of a queued time-locked transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelockEntry {
+    /// Transaction ID
+    pub transaction_id: TransactionId,
+
+    /// Source domain
+    pub source_domain: DomainId,
+
+    /// Target domains
+    pub target_domains: Vec<DomainId>,
+
+    /// Time at which the transaction unlocks
+    pub unlock_at: DateTime<Utc>,
+
+    /// Time the transaction was queued
+    pub queued_at: DateTime<Utc>,
+}
+
+impl TimelockQueue {
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a transaction until its `not_before` time
+    pub async fn enqueue(&self, transaction: CrossDomainTransaction) {
+        let mut entries = self.entries.write().await;
+        entries.insert(transaction.transaction_id.clone(), transaction);
+    }
+
+    /// Remove a queued transaction before it unlocks
+    pub async fn cancel(&self, transaction_id: &TransactionId) -> Option<CrossDomainTransaction> {
+        self.entries.write().await.remove(transaction_id)
+    }
+
+    /// A queued transaction
+    pub async fn get(&self, transaction_id: &TransactionId) -> Option<CrossDomainTransaction> {
+        self.entries.read().await.get(transaction_id).cloned()
+    }
+
+    /// Whether a transaction is currently queued
+    pub async fn contains(&self, transaction_id: &TransactionId) -> bool {
+        self.entries.read().await.contains_key(transaction_id)
+    }
+
+    /// Remove and return every transaction whose unlock time is at or before `now`,
+    /// earliest unlock first
+    pub async fn take_matured(&self, now: DateTime<Utc>) -> Vec<CrossDomainTransaction> {
+        let mut entries = self.entries.write().await;
+        let matured_ids: Vec<TransactionId> = entries
+            .values()
+            .filter(|tx| tx.not_before.map_or(true, |t| t <= now))
+            .map(|tx| tx.transaction_id.clone())
+            .collect();
+
+        let mut matured: Vec<CrossDomainTransaction> = matured_ids
+            .iter()
+            .filter_map(|id| entries.remove(id))
+            .collect();
+        matured.sort_by_key(|tx| tx.not_before);
+        matured
+    }
+
+    /// Snapshot of the queue, earliest unlock first
+    pub async fn entries(&self) -> Vec<TimelockEntry> {
+        let entries = self.entries.read().await;
+        let mut out: Vec<TimelockEntry> = entries
+            .values()
+            .map(|tx| TimelockEntry {
+                transaction_id: tx.transaction_id.clone(),
+                source_domain: tx.source_domain.clone(),
+                target_domains: tx.target_domains.clone(),
+                unlock_at: tx.not_before.unwrap_or(tx.created_at),
+                queued_at: tx.created_at,
+            })
+            .collect();
+        out.sort_by_key(|e| e.unlock_at);
+        out
+    }
+
+    /// Number of queued transactions
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+}
+
+/// Periodically releases matured time-locked transactions into coordination
+pub struct TimelockMonitor {
+    /// Cross-domain coordinator owning the queue
+    coordinator: Arc<CrossDomainCoordinator>,
+
+    /// Interval between maturity checks
+    check_interval: Duration,
+}
+
+impl TimelockMonitor {
+    /// Create new timelock monitor
+    pub fn new(coordinator: Arc<CrossDomainCoordinator>, check_interval: Duration) -> Self {
+        Self {
+            coordinator,
+            check_interval,
+        }
+    }
+
+    /// Spawn the periodic release loop
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(self.check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match self.run_once().await {
+                    Ok(released) if !released.is_empty() => {
+                        info!("Released {} time-locked transactions", released.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!("Timelock release pass failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Run a single release pass, returning the released transaction IDs
+    pub async fn run_once(&self) -> GarpResult<Vec<TransactionId>> {
+        let released = self.coordinator.release_matured_timelocks(Utc::now()).await?;
+        for tx_id in &released {
+            debug!("Time-locked transaction {} unlocked", tx_id);
+        }
+        Ok(released)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_domain::{CrossDomainTransactionType, TransactionStatus};
+
+    fn transaction(not_before: Option<DateTime<Utc>>) -> CrossDomainTransaction {
+        CrossDomainTransaction {
+            transaction_id: TransactionId::new(),
+            source_domain: "source".to_string(),
+            target_domains: vec!["target".to_string()],
+            transaction_type: CrossDomainTransactionType::AssetTransfer {
+                asset_id: "test-asset".to_string(),
+                amount: 100,
+                from_address: "from".to_string(),
+                to_address: "to".to_string(),
+            },
+            data: vec![1, 2, 3],
+            dependencies: Vec::new(),
+            required_confirmations: 1,
+            confirmations: HashMap::new(),
+            status: TransactionStatus::Pending,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            timeout_at: Utc::now() + chrono::Duration::seconds(300),
+            not_before,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_take_matured_releases_only_unlocked() {
+        let queue = TimelockQueue::new();
+        let now = Utc::now();
+        let early = transaction(Some(now - chrono::Duration::seconds(10)));
+        let later = transaction(Some(now + chrono::Duration::hours(1)));
+        queue.enqueue(early.clone()).await;
+        queue.enqueue(later.clone()).await;
+
+        let matured = queue.take_matured(now).await;
+        assert_eq!(matured.len(), 1);
+        assert_eq!(matured[0].transaction_id, early.transaction_id);
+        assert!(queue.contains(&later.transaction_id).await);
+        assert_eq!(queue.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_removes_entry() {
+        let queue = TimelockQueue::new();
+        let tx = transaction(Some(Utc::now() + chrono::Duration::hours(1)));
+        queue.enqueue(tx.clone()).await;
+
+        assert!(queue.cancel(&tx.transaction_id).await.is_some());
+        assert!(queue.cancel(&tx.transaction_id).await.is_none());
+        assert!(queue.entries().await.is_empty());
+    }
+}
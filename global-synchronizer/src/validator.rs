@@ -1,7 +1,8 @@
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
-use chrono::Utc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use chrono::{DateTime, Utc};
 use garp_common::types::ParticipantId;
+use garp_common::{GarpError, GarpResult, KeyHistory, KeyRotation};
 
 /// Validator status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -33,4 +34,402 @@ impl ValidatorInfo {
             metadata: HashMap::new(),
         }
     }
-}
\ No newline at end of file
+}
+/// Change to the validator set, applied at the next epoch boundary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ValidatorChange {
+    Add(ValidatorInfo),
+    Remove { id: ParticipantId },
+    UpdateStake { id: ParticipantId, stake: u64 },
+}
+
+impl ValidatorChange {
+    /// Validator the change applies to
+    pub fn validator_id(&self) -> &ParticipantId {
+        match self {
+            ValidatorChange::Add(info) => &info.id,
+            ValidatorChange::Remove { id } | ValidatorChange::UpdateStake { id, .. } => id,
+        }
+    }
+}
+
+/// Validator set that took effect at an epoch boundary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorSetUpdate {
+    pub epoch: u64,
+    pub changes: Vec<ValidatorChange>,
+    pub validators: Vec<ValidatorInfo>,
+    pub total_voting_power: u64,
+    pub quorum_power: u64,
+}
+
+/// Epoch-scoped validator set.
+///
+/// Changes are queued and only applied when the chain crosses into a new
+/// epoch, so every block in an epoch is certified against the same set.
+/// Voting power equals stake.
+///
+/// Signing keys are tracked by key epoch rather than per validator epoch:
+/// a validator may rotate its key at any time, see
+/// [`ValidatorRegistry::apply_key_rotation`].
+#[derive(Debug, Clone)]
+pub struct ValidatorRegistry {
+    epoch_length: u64,
+    quorum_ratio_thousandths: u64,
+    current_epoch: u64,
+    active: HashMap<ParticipantId, ValidatorInfo>,
+    pending: Vec<ValidatorChange>,
+    /// Voting power by validator for each epoch the set changed in
+    history: BTreeMap<u64, HashMap<ParticipantId, u64>>,
+    /// Signing keys by validator, seeded from `public_key_hex` on joining
+    keys: HashMap<ParticipantId, KeyHistory>,
+}
+
+impl ValidatorRegistry {
+    pub fn new(epoch_length: u64, quorum_ratio_thousandths: u32, validators: Vec<ValidatorInfo>) -> Self {
+        let active: HashMap<ParticipantId, ValidatorInfo> =
+            validators.into_iter().map(|v| (v.id.clone(), v)).collect();
+        let keys = active.values().map(|v| (v.id.clone(), Self::initial_keys(v))).collect();
+        let mut registry = Self {
+            epoch_length: epoch_length.max(1),
+            quorum_ratio_thousandths: quorum_ratio_thousandths as u64,
+            current_epoch: 0,
+            active,
+            pending: Vec::new(),
+            history: BTreeMap::new(),
+            keys,
+        };
+        registry.snapshot();
+        registry
+    }
+
+    /// Epoch containing `height`
+    pub fn epoch_for_height(&self, height: u64) -> u64 {
+        height / self.epoch_length
+    }
+
+    /// First block height of `epoch`
+    pub fn epoch_start_height(&self, epoch: u64) -> u64 {
+        epoch.saturating_mul(self.epoch_length)
+    }
+
+    pub fn current_epoch(&self) -> u64 {
+        self.current_epoch
+    }
+
+    /// Queue a change for the next epoch, returning the epoch it takes effect in
+    pub fn schedule(&mut self, change: ValidatorChange) -> GarpResult<u64> {
+        let projected = self.projected_set();
+        match &change {
+            ValidatorChange::Add(info) => {
+                if projected.contains_key(&info.id) {
+                    return Err(GarpError::ValidationError(format!("Validator {} already in set", info.id.0)));
+                }
+                if info.voting_power == 0 {
+                    return Err(GarpError::ValidationError("Validator stake must be > 0".to_string()));
+                }
+            }
+            ValidatorChange::Remove { id } => {
+                if !projected.contains_key(id) {
+                    return Err(GarpError::NotFound(format!("Validator {} not in set", id.0)));
+                }
+                if projected.len() == 1 {
+                    return Err(GarpError::ValidationError("Cannot remove the last validator".to_string()));
+                }
+            }
+            ValidatorChange::UpdateStake { id, stake } => {
+                if !projected.contains_key(id) {
+                    return Err(GarpError::NotFound(format!("Validator {} not in set", id.0)));
+                }
+                if *stake == 0 {
+                    return Err(GarpError::ValidationError("Validator stake must be > 0; remove the validator instead".to_string()));
+                }
+            }
+        }
+        self.pending.push(change);
+        Ok(self.current_epoch + 1)
+    }
+
+    /// Changes waiting for the next epoch boundary
+    pub fn pending_changes(&self) -> &[ValidatorChange] {
+        &self.pending
+    }
+
+    /// Move to the epoch containing `height`, applying queued changes when a
+    /// boundary is crossed. Returns the new set if it changed.
+    pub fn advance_to_height(&mut self, height: u64) -> Option<ValidatorSetUpdate> {
+        let epoch = self.epoch_for_height(height);
+        if epoch <= self.current_epoch {
+            return None;
+        }
+        self.current_epoch = epoch;
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        let changes = std::mem::take(&mut self.pending);
+        for change in &changes {
+            Self::apply(&mut self.active, change);
+            if let ValidatorChange::Add(info) = change {
+                self.keys.entry(info.id.clone()).or_insert_with(|| Self::initial_keys(info));
+            }
+        }
+        self.snapshot();
+
+        Some(ValidatorSetUpdate {
+            epoch,
+            changes,
+            validators: self.active_validators(),
+            total_voting_power: self.total_voting_power(),
+            quorum_power: self.quorum_power(),
+        })
+    }
+
+    /// Validators active in the current epoch, ordered by ID
+    pub fn active_validators(&self) -> Vec<ValidatorInfo> {
+        let mut validators: Vec<ValidatorInfo> = self.active.values().cloned().collect();
+        validators.sort_by(|a, b| a.id.0.cmp(&b.id.0));
+        validators
+    }
+
+    pub fn get(&self, id: &ParticipantId) -> Option<&ValidatorInfo> {
+        self.active.get(id)
+    }
+
+    pub fn total_voting_power(&self) -> u64 {
+        self.total_voting_power_at(self.current_epoch)
+    }
+
+    /// Voting power needed for quorum in the current epoch
+    pub fn quorum_power(&self) -> u64 {
+        self.quorum_power_at(self.current_epoch)
+    }
+
+    pub fn total_voting_power_at(&self, epoch: u64) -> u64 {
+        self.set_at(epoch).map(|set| set.values().sum()).unwrap_or(0)
+    }
+
+    /// Voting power needed for quorum in `epoch`
+    pub fn quorum_power_at(&self, epoch: u64) -> u64 {
+        let total = self.total_voting_power_at(epoch);
+        (total * self.quorum_ratio_thousandths + 999) / 1000
+    }
+
+    /// Whether `signers` hold quorum voting power in the set active at `height`.
+    /// Duplicate signers and validators outside that set are ignored.
+    pub fn has_quorum<'a>(&self, height: u64, signers: impl IntoIterator<Item = &'a ParticipantId>) -> bool {
+        let epoch = self.epoch_for_height(height);
+        let set = match self.set_at(epoch) {
+            Some(set) => set,
+            None => return false,
+        };
+        let mut seen = HashSet::new();
+        let signed: u64 = signers
+            .into_iter()
+            .filter(|id| seen.insert((*id).clone()))
+            .filter_map(|id| set.get(id))
+            .sum();
+        let quorum = self.quorum_power_at(epoch);
+        quorum > 0 && signed >= quorum
+    }
+
+    /// Record a validator's key rotation. The new key becomes the
+    /// validator's `public_key_hex`; its previous key keeps verifying
+    /// messages made before the end of the overlap window.
+    pub fn apply_key_rotation(&mut self, rotation: &KeyRotation) -> GarpResult<()> {
+        let id = ParticipantId::new(rotation.node_id.clone());
+        if !self.keys.contains_key(&id) {
+            if let Some(info) = self.projected_set().get(&id) {
+                self.keys.insert(id.clone(), Self::initial_keys(info));
+            }
+        }
+        let keys = self.keys.get_mut(&id)
+            .ok_or_else(|| GarpError::NotFound(format!("Validator {} not in set", id.0)))?;
+        keys.apply_rotation(rotation)?;
+
+        let public_key_hex = hex::encode(&rotation.public_key);
+        if let Some(info) = self.active.get_mut(&id) {
+            info.public_key_hex = public_key_hex.clone();
+        }
+        for change in &mut self.pending {
+            if let ValidatorChange::Add(info) = change {
+                if info.id == id {
+                    info.public_key_hex = public_key_hex.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Signing keys of a validator
+    pub fn key_history(&self, id: &ParticipantId) -> Option<&KeyHistory> {
+        self.keys.get(id)
+    }
+
+    /// Hex key of `id` to check a message made at `at` against: the accepted
+    /// key that verifies `signature` over `message`, otherwise the newest key
+    /// accepted at `at`. `None` if no key of `id` is accepted at `at`.
+    pub fn public_key_at(&self, id: &ParticipantId, message: &[u8], signature: &[u8], at: DateTime<Utc>) -> Option<String> {
+        let keys = self.keys.get(id)?;
+        keys.verify(message, signature, at)
+            .or_else(|| keys.keys_at(at).next())
+            .map(|epoch| hex::encode(&epoch.public_key))
+    }
+
+    fn initial_keys(info: &ValidatorInfo) -> KeyHistory {
+        KeyHistory::new(hex::decode(&info.public_key_hex).unwrap_or_default())
+    }
+
+    fn set_at(&self, epoch: u64) -> Option<&HashMap<ParticipantId, u64>> {
+        self.history.range(..=epoch).next_back().map(|(_, set)| set)
+    }
+
+    fn projected_set(&self) -> HashMap<ParticipantId, ValidatorInfo> {
+        let mut set = self.active.clone();
+        for change in &self.pending {
+            Self::apply(&mut set, change);
+        }
+        set
+    }
+
+    fn apply(set: &mut HashMap<ParticipantId, ValidatorInfo>, change: &ValidatorChange) {
+        match change {
+            ValidatorChange::Add(info) => {
+                set.insert(info.id.clone(), info.clone());
+            }
+            ValidatorChange::Remove { id } => {
+                set.remove(id);
+            }
+            ValidatorChange::UpdateStake { id, stake } => {
+                if let Some(v) = set.get_mut(id) {
+                    v.voting_power = *stake;
+                }
+            }
+        }
+    }
+
+    fn snapshot(&mut self) {
+        let powers = self
+            .active
+            .values()
+            .filter(|v| v.status == ValidatorStatus::Active)
+            .map(|v| (v.id.clone(), v.voting_power))
+            .collect();
+        self.history.insert(self.current_epoch, powers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(id: &str) -> ValidatorInfo {
+        ValidatorInfo::new(ParticipantId::new(id), String::new(), 1)
+    }
+
+    fn registry(n: usize) -> ValidatorRegistry {
+        let validators = (0..n).map(|i| validator(&format!("v{}", i))).collect();
+        ValidatorRegistry::new(10, 667, validators)
+    }
+
+    fn ids(names: &[&str]) -> Vec<ParticipantId> {
+        names.iter().map(|n| ParticipantId::new(*n)).collect()
+    }
+
+    #[test]
+    fn test_quorum_recomputed_after_join() {
+        let mut registry = registry(4);
+        assert_eq!(registry.quorum_power(), 3);
+
+        assert_eq!(registry.schedule(ValidatorChange::Add(validator("v4"))).unwrap(), 1);
+        assert!(registry.schedule(ValidatorChange::Add(validator("v4"))).is_err());
+        assert_eq!(registry.quorum_power(), 3);
+        assert!(registry.advance_to_height(9).is_none());
+
+        let update = registry.advance_to_height(10).expect("set changed at epoch 1");
+        assert_eq!(update.epoch, 1);
+        assert_eq!(update.validators.len(), 5);
+        assert_eq!((update.total_voting_power, update.quorum_power), (5, 4));
+
+        // Three signatures certified epoch 0 but no longer reach quorum in epoch 1
+        let three = ids(&["v0", "v1", "v2"]);
+        assert!(registry.has_quorum(5, &three));
+        assert!(!registry.has_quorum(12, &three));
+        assert!(registry.has_quorum(12, &ids(&["v0", "v1", "v2", "v4"])));
+    }
+
+    #[test]
+    fn test_removal_mid_epoch_applies_at_boundary() {
+        let mut registry = registry(5);
+        assert!(registry.advance_to_height(14).is_none());
+        assert_eq!(registry.current_epoch(), 1);
+
+        registry.schedule(ValidatorChange::Remove { id: ParticipantId::new("v4") }).unwrap();
+        assert!(registry.schedule(ValidatorChange::UpdateStake { id: ParticipantId::new("v4"), stake: 2 }).is_err());
+
+        // Still in epoch 1: the removed validator counts and quorum is unchanged
+        assert!(registry.advance_to_height(19).is_none());
+        assert_eq!(registry.quorum_power(), 4);
+        assert!(registry.has_quorum(17, &ids(&["v1", "v2", "v3", "v4"])));
+
+        let update = registry.advance_to_height(20).expect("set changed at epoch 2");
+        assert_eq!((update.total_voting_power, update.quorum_power), (4, 3));
+        assert!(registry.get(&ParticipantId::new("v4")).is_none());
+        assert!(!registry.has_quorum(21, &ids(&["v2", "v3", "v4"])));
+        assert!(registry.has_quorum(21, &ids(&["v1", "v2", "v3"])));
+        assert!(registry.has_quorum(17, &ids(&["v1", "v2", "v3", "v4"])));
+    }
+
+    #[test]
+    fn test_key_rotation_overlap() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let old_key = SigningKey::from_bytes(&[1; 32]);
+        let new_key = SigningKey::from_bytes(&[2; 32]);
+        let info = ValidatorInfo::new(ParticipantId::new("v0"), hex::encode(old_key.verifying_key().to_bytes()), 1);
+        let mut registry = ValidatorRegistry::new(10, 667, vec![info, validator("v1")]);
+
+        let rotated_at = Utc::now();
+        let mut rotation = KeyRotation {
+            node_id: "v0".to_string(),
+            epoch: 1,
+            previous_public_key: old_key.verifying_key().to_bytes().to_vec(),
+            public_key: new_key.verifying_key().to_bytes().to_vec(),
+            effective_at: rotated_at,
+            overlap_secs: 600,
+            signature: Vec::new(),
+        };
+        rotation.signature = old_key.sign(&rotation.signing_message()).to_bytes().to_vec();
+        let mut unknown = rotation.clone();
+        unknown.node_id = "v9".to_string();
+        assert!(registry.apply_key_rotation(&unknown).is_err());
+        registry.apply_key_rotation(&rotation).unwrap();
+
+        let id = ParticipantId::new("v0");
+        let old_hex = hex::encode(old_key.verifying_key().to_bytes());
+        let new_hex = hex::encode(new_key.verifying_key().to_bytes());
+        assert_eq!(registry.get(&id).unwrap().public_key_hex, new_hex);
+
+        let message = b"vote";
+        let old_sig = old_key.sign(message).to_bytes();
+        let during = rotated_at + chrono::Duration::seconds(300);
+        let after = rotated_at + chrono::Duration::seconds(601);
+        assert_eq!(registry.public_key_at(&id, message, &old_sig, during), Some(old_hex));
+        assert_eq!(registry.public_key_at(&id, message, &new_key.sign(message).to_bytes(), during), Some(new_hex.clone()));
+        // Past the overlap the old signature is checked against the new key and fails
+        assert_eq!(registry.public_key_at(&id, message, &old_sig, after), Some(new_hex));
+        assert!(registry.public_key_at(&ParticipantId::new("v9"), message, &old_sig, after).is_none());
+    }
+
+    #[test]
+    fn test_stake_update_reweights_quorum() {
+        let mut registry = registry(4);
+        registry.schedule(ValidatorChange::UpdateStake { id: ParticipantId::new("v0"), stake: 4 }).unwrap();
+        registry.advance_to_height(10);
+
+        assert_eq!((registry.total_voting_power(), registry.quorum_power()), (7, 5));
+        assert!(registry.has_quorum(10, &ids(&["v0", "v1"])));
+        assert!(!registry.has_quorum(10, &ids(&["v1", "v2", "v3"])));
+        assert!(!registry.has_quorum(10, &ids(&["v0", "v0"])));
+    }
+}
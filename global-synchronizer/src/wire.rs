@@ -0,0 +1,165 @@
+//! JSON and CBOR wire encodings
+//!
+//! Stored transaction payloads use `api.serialization_format`. API handlers
+//! speak JSON; [`content_negotiation_middleware`] transcodes CBOR request
+//! bodies (`Content-Type: application/cbor`) to JSON, and JSON responses to
+//! CBOR for clients sending `Accept: application/cbor`.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::error;
+
+use garp_common::error::SerializationError;
+use garp_common::GarpResult;
+
+pub use crate::config::SerializationFormat;
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Largest body the middleware will transcode
+const MAX_TRANSCODE_BYTES: usize = 16 * 1024 * 1024;
+
+impl SerializationFormat {
+    /// MIME type of the format
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => JSON_CONTENT_TYPE,
+            Self::Cbor => CBOR_CONTENT_TYPE,
+        }
+    }
+
+    /// First format named by a `Content-Type` or `Accept` header value
+    pub fn from_header(value: &str) -> Option<Self> {
+        value.split(',').find_map(|part| match part.split(';').next().map(str::trim) {
+            Some(CBOR_CONTENT_TYPE) => Some(Self::Cbor),
+            Some(JSON_CONTENT_TYPE) => Some(Self::Json),
+            _ => None,
+        })
+    }
+
+    /// Serialize `value`
+    pub fn encode<T: Serialize>(self, value: &T) -> GarpResult<Vec<u8>> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(value).map_err(SerializationError::JsonFailed)?),
+            Self::Cbor => {
+                let mut out = Vec::new();
+                ciborium::into_writer(value, &mut out)
+                    .map_err(|e| SerializationError::InvalidFormat(format!("CBOR encoding failed: {}", e)))?;
+                Ok(out)
+            }
+        }
+    }
+
+    /// Deserialize `bytes`
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> GarpResult<T> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(bytes).map_err(SerializationError::JsonFailed)?),
+            Self::Cbor => Ok(ciborium::from_reader(bytes)
+                .map_err(|e| SerializationError::InvalidFormat(format!("CBOR decoding failed: {}", e)))?),
+        }
+    }
+}
+
+/// Re-encode a CBOR document as JSON
+fn cbor_to_json(bytes: &[u8]) -> GarpResult<Vec<u8>> {
+    let value: ciborium::value::Value = SerializationFormat::Cbor.decode(bytes)?;
+    SerializationFormat::Json.encode(&value)
+}
+
+/// Re-encode a JSON document as CBOR
+fn json_to_cbor(bytes: &[u8]) -> GarpResult<Vec<u8>> {
+    let value: serde_json::Value = SerializationFormat::Json.decode(bytes)?;
+    SerializationFormat::Cbor.encode(&value)
+}
+
+fn header_format(headers: &axum::http::HeaderMap, name: header::HeaderName) -> Option<SerializationFormat> {
+    headers.get(name).and_then(|v| v.to_str().ok()).and_then(SerializationFormat::from_header)
+}
+
+/// Axum middleware translating CBOR requests and responses
+pub async fn content_negotiation_middleware(req: Request, next: Next) -> Response {
+    let wants_cbor = header_format(req.headers(), header::ACCEPT) == Some(SerializationFormat::Cbor);
+
+    let req = if header_format(req.headers(), header::CONTENT_TYPE) == Some(SerializationFormat::Cbor) {
+        let (mut parts, body) = req.into_parts();
+        let bytes = match axum::body::to_bytes(body, MAX_TRANSCODE_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+        };
+        let json = match cbor_to_json(&bytes) {
+            Ok(json) => json,
+            Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        };
+        parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(JSON_CONTENT_TYPE));
+        parts.headers.remove(header::CONTENT_LENGTH);
+        Request::from_parts(parts, Body::from(json))
+    } else {
+        req
+    };
+
+    let response = next.run(req).await;
+    if !wants_cbor || header_format(response.headers(), header::CONTENT_TYPE) != Some(SerializationFormat::Json) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let cbor = match axum::body::to_bytes(body, MAX_TRANSCODE_BYTES).await {
+        Ok(bytes) => json_to_cbor(&bytes),
+        Err(e) => Err(SerializationError::InvalidFormat(e.to_string()).into()),
+    };
+    match cbor {
+        Ok(cbor) => {
+            parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(CBOR_CONTENT_TYPE));
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(cbor))
+        }
+        Err(e) => {
+            error!("Failed to encode response as CBOR: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_header_parsing() {
+        assert_eq!(SerializationFormat::from_header("application/cbor"), Some(SerializationFormat::Cbor));
+        assert_eq!(
+            SerializationFormat::from_header("text/html, application/cbor;q=0.9, application/json"),
+            Some(SerializationFormat::Cbor)
+        );
+        assert_eq!(SerializationFormat::from_header("application/json; charset=utf-8"), Some(SerializationFormat::Json));
+        assert_eq!(SerializationFormat::from_header("*/*"), None);
+    }
+
+    #[test]
+    fn test_transcoding_round_trip() {
+        let value = json!({ "success": true, "data": { "id": "tx-1", "bytes": [0, 1, 255] }, "error": null });
+        let json = serde_json::to_vec(&value).unwrap();
+
+        let cbor = json_to_cbor(&json).unwrap();
+        assert_eq!(SerializationFormat::Cbor.decode::<serde_json::Value>(&cbor).unwrap(), value);
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&cbor_to_json(&cbor).unwrap()).unwrap(), value);
+        assert!(cbor_to_json(b"\xff\xff").is_err());
+    }
+
+    #[test]
+    fn test_cbor_is_smaller_for_binary_payloads() {
+        let data = vec![0xabu8; 1024];
+        let json = SerializationFormat::Json.encode(&data).unwrap();
+        let cbor = SerializationFormat::Cbor.encode(&data).unwrap();
+        assert!(cbor.len() < json.len());
+        assert_eq!(SerializationFormat::Cbor.decode::<Vec<u8>>(&cbor).unwrap(), data);
+    }
+}
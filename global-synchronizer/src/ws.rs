@@ -0,0 +1,376 @@
+//! WebSocket subscriptions
+//!
+//! `/ws` speaks JSON-RPC 2.0. Clients call `subscribeNewBlocks` or
+//! `subscribeTransaction` (`{"tx_id": ...}`) and receive the subscription ID;
+//! notifications then arrive as `newBlock` / `transactionStatus` messages
+//! carrying `{"subscription", "result"}`. A transaction subscription ends
+//! after the transaction reaches a terminal status. `unsubscribe`
+//! (`{"subscription": id}`) cancels early, and all subscriptions of a
+//! connection are dropped when it closes.
+//!
+//! Block notifications come from [`GlobalStorage::subscribe_blocks`], so a
+//! block is only announced once RPC reads can see it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use garp_common::types::TransactionId;
+
+use crate::storage::{BlockInfo, GlobalStorage};
+use crate::synchronizer::{ActiveTransaction, TransactionStatus, TransactionStatusUpdate};
+
+const RPC_INVALID_PARAMS: i32 = -32602;
+const RPC_METHOD_NOT_FOUND: i32 = -32601;
+const RPC_PARSE_ERROR: i32 = -32700;
+const RPC_SUBSCRIPTION_LIMIT: i32 = -32005;
+
+/// Notifications queued per connection before subscription tasks wait on
+/// the client
+const OUTBOUND_CAPACITY: usize = 256;
+
+/// Compact header pushed to `subscribeNewBlocks` subscribers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockNotification {
+    pub height: u64,
+    pub hash: String,
+    pub parent_hash: String,
+    pub tx_count: u32,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl From<&BlockInfo> for BlockNotification {
+    fn from(block: &BlockInfo) -> Self {
+        Self {
+            height: block.height,
+            hash: hex::encode(&block.block_hash),
+            parent_hash: hex::encode(&block.parent_hash),
+            tx_count: block.transaction_count,
+            timestamp: block.timestamp.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0),
+        }
+    }
+}
+
+/// Event sources shared by all `/ws` connections
+#[derive(Clone)]
+pub struct WsState {
+    storage: Arc<GlobalStorage>,
+    active_transactions: Arc<RwLock<HashMap<TransactionId, ActiveTransaction>>>,
+    status_tx: broadcast::Sender<TransactionStatusUpdate>,
+    max_subscriptions: usize,
+}
+
+impl WsState {
+    pub fn new(
+        storage: Arc<GlobalStorage>,
+        active_transactions: Arc<RwLock<HashMap<TransactionId, ActiveTransaction>>>,
+        status_tx: broadcast::Sender<TransactionStatusUpdate>,
+        max_subscriptions: usize,
+    ) -> Self {
+        Self { storage, active_transactions, status_tx, max_subscriptions }
+    }
+
+    async fn transaction_status(&self, transaction_id: &TransactionId) -> Option<TransactionStatus> {
+        self.active_transactions.read().await.get(transaction_id).map(|tx| tx.status.clone())
+    }
+}
+
+/// Router serving `/ws`
+pub fn router(state: WsState) -> Router {
+    Router::new().route("/ws", get(ws_upgrade)).with_state(state)
+}
+
+async fn ws_upgrade(ws: WebSocketUpgrade, State(state): State<WsState>) -> Response {
+    ws.on_upgrade(move |socket| serve_connection(socket, state))
+}
+
+#[derive(Deserialize)]
+struct WsRequest {
+    method: String,
+    params: Option<Value>,
+    id: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct SubscribeTransactionParams {
+    tx_id: String,
+}
+
+#[derive(Deserialize)]
+struct UnsubscribeParams {
+    subscription: u64,
+}
+
+fn rpc_result(result: Value, id: Option<Value>) -> String {
+    json!({ "jsonrpc": "2.0", "result": result, "id": id }).to_string()
+}
+
+fn rpc_error(code: i32, message: impl Into<String>, id: Option<Value>) -> String {
+    json!({ "jsonrpc": "2.0", "error": { "code": code, "message": message.into() }, "id": id }).to_string()
+}
+
+fn notification(method: &str, subscription: u64, result: impl Serialize) -> String {
+    json!({ "jsonrpc": "2.0", "method": method, "params": { "subscription": subscription, "result": result } }).to_string()
+}
+
+fn rpc_params<T: serde::de::DeserializeOwned>(params: Option<Value>) -> Result<T, String> {
+    serde_json::from_value(params.unwrap_or(Value::Null)).map_err(|e| format!("Invalid params: {}", e))
+}
+
+/// Subscriptions of one connection
+struct Connection {
+    state: WsState,
+    subscriptions: HashMap<u64, JoinHandle<()>>,
+    next_subscription: u64,
+    /// Notifications for the client
+    out_tx: mpsc::Sender<String>,
+    /// Subscriptions that ended on their own
+    done_tx: mpsc::UnboundedSender<u64>,
+}
+
+async fn serve_connection(socket: WebSocket, state: WsState) {
+    let (mut sink, mut stream) = socket.split();
+    let (out_tx, mut out_rx) = mpsc::channel(OUTBOUND_CAPACITY);
+    let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+    let mut conn = Connection { state, subscriptions: HashMap::new(), next_subscription: 1, out_tx, done_tx };
+
+    loop {
+        let outgoing = tokio::select! {
+            incoming = stream.next() => match incoming {
+                Some(Ok(Message::Text(text))) => conn.handle_request(&text).await,
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by axum; binary frames are not part of the protocol
+                Some(Ok(_)) => continue,
+            },
+            Some(notification) = out_rx.recv() => notification,
+            Some(subscription) = done_rx.recv() => {
+                conn.subscriptions.remove(&subscription);
+                continue;
+            }
+        };
+        if sink.send(Message::Text(outgoing)).await.is_err() {
+            break;
+        }
+    }
+
+    debug!("WebSocket closed; dropping {} subscriptions", conn.subscriptions.len());
+    for (_, task) in conn.subscriptions.drain() {
+        task.abort();
+    }
+}
+
+impl Connection {
+    async fn handle_request(&mut self, text: &str) -> String {
+        let req: WsRequest = match serde_json::from_str(text) {
+            Ok(req) => req,
+            Err(e) => return rpc_error(RPC_PARSE_ERROR, format!("Invalid request: {}", e), None),
+        };
+        match req.method.as_str() {
+            "subscribeNewBlocks" => match self.reserve_subscription() {
+                Ok(subscription) => {
+                    let blocks = self.state.storage.subscribe_blocks();
+                    let task = tokio::spawn(forward_blocks(subscription, blocks, self.out_tx.clone()));
+                    self.subscriptions.insert(subscription, task);
+                    rpc_result(json!(subscription), req.id)
+                }
+                Err(e) => e.with_id(req.id),
+            },
+            "subscribeTransaction" => {
+                let params: SubscribeTransactionParams = match rpc_params(req.params) {
+                    Ok(p) => p,
+                    Err(e) => return rpc_error(RPC_INVALID_PARAMS, e, req.id),
+                };
+                let transaction_id = match uuid::Uuid::parse_str(&params.tx_id) {
+                    Ok(uuid) => TransactionId(uuid),
+                    Err(e) => return rpc_error(RPC_INVALID_PARAMS, format!("Invalid tx_id: {}", e), req.id),
+                };
+                // Subscribe before reading the current status so no transition is missed
+                let updates = self.state.status_tx.subscribe();
+                let status = match self.state.transaction_status(&transaction_id).await {
+                    Some(status) => status,
+                    None => return rpc_error(RPC_INVALID_PARAMS, format!("Unknown transaction {}", params.tx_id), req.id),
+                };
+                match self.reserve_subscription() {
+                    Ok(subscription) => {
+                        let task = tokio::spawn(forward_transaction(
+                            subscription,
+                            transaction_id,
+                            status,
+                            updates,
+                            self.state.clone(),
+                            self.out_tx.clone(),
+                            self.done_tx.clone(),
+                        ));
+                        self.subscriptions.insert(subscription, task);
+                        rpc_result(json!(subscription), req.id)
+                    }
+                    Err(e) => e.with_id(req.id),
+                }
+            }
+            "unsubscribe" => {
+                let params: UnsubscribeParams = match rpc_params(req.params) {
+                    Ok(p) => p,
+                    Err(e) => return rpc_error(RPC_INVALID_PARAMS, e, req.id),
+                };
+                let removed = self.subscriptions.remove(&params.subscription).map(|task| task.abort()).is_some();
+                rpc_result(json!(removed), req.id)
+            }
+            _ => rpc_error(RPC_METHOD_NOT_FOUND, format!("Method not found: {}", req.method), req.id),
+        }
+    }
+
+    fn reserve_subscription(&mut self) -> Result<u64, SubscriptionLimit> {
+        if self.subscriptions.len() >= self.state.max_subscriptions {
+            return Err(SubscriptionLimit(self.state.max_subscriptions));
+        }
+        let subscription = self.next_subscription;
+        self.next_subscription += 1;
+        Ok(subscription)
+    }
+}
+
+struct SubscriptionLimit(usize);
+
+impl SubscriptionLimit {
+    fn with_id(self, id: Option<Value>) -> String {
+        rpc_error(RPC_SUBSCRIPTION_LIMIT, format!("At most {} subscriptions per connection", self.0), id)
+    }
+}
+
+async fn forward_blocks(subscription: u64, mut blocks: broadcast::Receiver<BlockInfo>, out_tx: mpsc::Sender<String>) {
+    loop {
+        match blocks.recv().await {
+            Ok(block) => {
+                if out_tx.send(notification("newBlock", subscription, BlockNotification::from(&block))).await.is_err() {
+                    break;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("WebSocket subscription {} missed {} blocks", subscription, skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+async fn forward_transaction(
+    subscription: u64,
+    transaction_id: TransactionId,
+    mut status: TransactionStatus,
+    mut updates: broadcast::Receiver<TransactionStatusUpdate>,
+    state: WsState,
+    out_tx: mpsc::Sender<String>,
+    done_tx: mpsc::UnboundedSender<u64>,
+) {
+    let mut update = Some(TransactionStatusUpdate {
+        transaction_id: transaction_id.clone(),
+        status: status.clone(),
+        timestamp: chrono::Utc::now(),
+    });
+    loop {
+        if let Some(update) = update.take() {
+            status = update.status.clone();
+            if out_tx.send(notification("transactionStatus", subscription, update)).await.is_err() {
+                break;
+            }
+        }
+        if status.is_terminal() {
+            break;
+        }
+        update = match updates.recv().await {
+            Ok(update) if update.transaction_id == transaction_id => Some(update),
+            Ok(_) => None,
+            // Resync from the current status after falling behind
+            Err(broadcast::error::RecvError::Lagged(_)) => match state.transaction_status(&transaction_id).await {
+                Some(current) if current != status => Some(TransactionStatusUpdate {
+                    transaction_id: transaction_id.clone(),
+                    status: current,
+                    timestamp: chrono::Utc::now(),
+                }),
+                _ => None,
+            },
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+    }
+    let _ = done_tx.send(subscription);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GlobalSyncConfig;
+    use std::time::{Duration, SystemTime};
+    use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+    fn block(height: u64, parent: u8) -> BlockInfo {
+        BlockInfo {
+            block_hash: vec![height as u8; 32],
+            height,
+            parent_hash: vec![parent; 32],
+            transaction_count: height as u32,
+            size: 100,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 + height),
+            difficulty: 0,
+            nonce: 0,
+            merkle_root: Vec::new(),
+            state_root: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_subscription_receives_consecutive_blocks() {
+        let storage = Arc::new(GlobalStorage::new(Arc::new(GlobalSyncConfig::default())).await.unwrap());
+        let state = WsState::new(storage.clone(), Arc::new(RwLock::new(HashMap::new())), broadcast::channel(16).0, 2);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await.unwrap() });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        let call = |method: &str, id: u64| {
+            ClientMessage::Text(json!({ "jsonrpc": "2.0", "method": method, "id": id }).to_string())
+        };
+        async fn next_json(client: &mut (impl StreamExt<Item = Result<ClientMessage, tokio_tungstenite::tungstenite::Error>> + Unpin)) -> Value {
+            let message = tokio::time::timeout(Duration::from_secs(5), client.next()).await.unwrap().unwrap().unwrap();
+            serde_json::from_str(message.to_text().unwrap()).unwrap()
+        }
+
+        client.send(call("subscribeNewBlocks", 1)).await.unwrap();
+        let subscription = next_json(&mut client).await["result"].as_u64().unwrap();
+
+        for (height, parent) in [(1, 0), (2, 1)] {
+            let info = block(height, parent);
+            storage.store_block(info.block_hash.clone(), info).await.unwrap();
+        }
+        for height in [1u64, 2] {
+            let message = next_json(&mut client).await;
+            assert_eq!(message["method"], "newBlock");
+            assert_eq!(message["params"]["subscription"], subscription);
+            let header: BlockNotification = serde_json::from_value(message["params"]["result"].clone()).unwrap();
+            assert_eq!(header, BlockNotification::from(&block(height, height as u8 - 1)));
+        }
+
+        // The per-connection cap applies
+        client.send(call("subscribeNewBlocks", 2)).await.unwrap();
+        assert!(next_json(&mut client).await["result"].is_u64());
+        client.send(call("subscribeNewBlocks", 3)).await.unwrap();
+        assert_eq!(next_json(&mut client).await["error"]["code"], RPC_SUBSCRIPTION_LIMIT);
+    }
+}
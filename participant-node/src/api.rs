@@ -1,9 +1,12 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
     http::StatusCode,
-    response::Json,
+    response::{Json, Response},
     routing::{get, post, put, delete},
-    Router,
+    Extension, Router,
 };
 use serde::{Deserialize, Serialize};
 use axum::middleware;
@@ -13,7 +16,6 @@ use tower::ServiceBuilder;
 use tower_http::{
     cors::CorsLayer,
     trace::TraceLayer,
-    limit::RequestBodyLimitLayer,
 };
 use tracing::{info, warn, error};
 use uuid::Uuid;
@@ -25,14 +27,25 @@ use garp_common::{
     Transaction, Contract, Asset, WalletBalance,
     TransactionCommand, CreateContractCommand, ExerciseContractCommand,
     ArchiveContractCommand, TransferAssetCommand, CreateAssetCommand,
-    GarpResult, GarpError,
+    GarpResult, GarpError, ErrorCode,
 };
 use crate::{
     node::ParticipantNode,
     config::ApiConfig,
-    eth_compatibility::EthCompatibilityLayer,
+    auth::{ApiKeyAuth, AuthenticatedParticipant, auth_middleware},
+    body_limit::{PerRouteBodyLimitLayer, content_type_middleware, route_body_limit_middleware},
+    rate_limit::{BucketLevel, InMemoryRateLimiter, RateLimiterBackend, rate_limit_middleware},
+    response_signing::{SignedResponseMiddleware, signed_response_middleware},
+    eth_compatibility::{EthCompatibilityLayer, eth_json_rpc},
 };
 use crate::merkle::{merkle_proof, merkle_root, MerkleProof};
+use crate::contract_registry::Template;
+use crate::contract_schema::{ArgumentSchema, TemplateSchemas};
+use crate::resync::ResyncProgress;
+use crate::tx_index::{TransactionFilter, TransactionIndexEntry};
+use crate::event_index::{EventConsumer, EventLogBounds};
+use crate::storage::ContractEvent;
+use crate::ledger_stream::{self, LedgerFrame, LedgerSubscribeRequest, LEDGER_POLL_INTERVAL};
 
 /// API server for participant node
 pub struct ApiServer {
@@ -46,6 +59,9 @@ pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    /// Stable code of `error`, for clients deciding whether to retry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -146,6 +162,14 @@ pub struct WalletQuery {
     pub asset_type: Option<String>,
 }
 
+/// Query parameters for wallet history
+#[derive(Debug, Deserialize)]
+pub struct WalletHistoryQueryParams {
+    pub asset_type: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
 /// Transaction response DTO
 #[derive(Debug, Serialize)]
 pub struct TransactionDto {
@@ -188,6 +212,18 @@ pub struct WalletBalanceDto {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Wallet history entry DTO
+#[derive(Debug, Serialize)]
+pub struct WalletHistoryEntryDto {
+    pub transaction_id: String,
+    pub counterparty: Option<String>,
+    pub asset_id: String,
+    pub asset_type: String,
+    pub amount: i64,
+    pub balance_after: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Node status response
 #[derive(Debug, Serialize)]
 pub struct NodeStatusDto {
@@ -205,6 +241,41 @@ pub struct NodeStatusDto {
     pub sync_last_applied_time: Option<DateTime<Utc>>,
 }
 
+/// Manual resync request
+#[derive(Debug, Deserialize)]
+pub struct ResyncRequest {
+    pub from_height: Option<u64>,
+}
+
+/// Sync status including any running resync
+#[derive(Debug, Serialize)]
+pub struct SyncStatusDto {
+    pub global_head_height: u64,
+    pub sync_last_applied_height: u64,
+    pub sync_last_applied_time: Option<DateTime<Utc>>,
+    pub resync: ResyncProgress,
+}
+
+/// Template version lookup; the latest version when omitted
+#[derive(Debug, Deserialize)]
+pub struct TemplateQuery {
+    pub version: Option<u32>,
+}
+
+/// A template version together with every registered version number
+#[derive(Debug, Serialize)]
+pub struct TemplateDto {
+    pub template: Template,
+    pub versions: Vec<u32>,
+}
+
+/// Register a new template version
+#[derive(Debug, Deserialize)]
+pub struct RegisterTemplateRequest {
+    pub version: u32,
+    pub schema: ArgumentSchema,
+}
+
 /// Block info DTO (synthetic for participant view)
 #[derive(Debug, Serialize)]
 pub struct BlockInfoDto {
@@ -257,6 +328,42 @@ pub struct MerkleProofDto {
     pub valid: bool,
 }
 
+impl MerkleProofDto {
+    fn from_proof(block_hash: &[u8], tx_id: String, proof: &MerkleProof, valid: bool) -> Self {
+        Self {
+            block_hash: hex::encode(block_hash),
+            tx_id,
+            leaf_hash: hex::encode(&proof.leaf),
+            root: hex::encode(&proof.root),
+            path: proof.path.iter().map(|p| hex::encode(p)).collect(),
+            directions: proof.directions.iter().map(|d| if *d { "right".to_string() } else { "left".to_string() }).collect(),
+            valid,
+        }
+    }
+}
+
+/// Batch transaction inclusion proof request
+#[derive(Debug, Deserialize)]
+pub struct TxProofsRequest {
+    pub tx_ids: Vec<String>,
+}
+
+/// Per-transaction result of a batch proof request
+#[derive(Debug, Serialize)]
+pub struct TxProofItemDto {
+    pub tx_id: String,
+    pub proof: Option<MerkleProofDto>,
+    pub error: Option<String>,
+}
+
+/// Batch transaction inclusion proofs for one block
+#[derive(Debug, Serialize)]
+pub struct TxProofsDto {
+    pub block_hash: String,
+    pub root: String,
+    pub proofs: Vec<TxProofItemDto>,
+}
+
 /// Node statistics response
 #[derive(Debug, Serialize)]
 pub struct NodeStatsDto {
@@ -266,6 +373,7 @@ pub struct NodeStatsDto {
     pub total_assets: u64,
     pub wallet_balances: u64,
     pub network_peers: u64,
+    pub rate_limit_buckets: Vec<BucketLevel>,
 }
 
 /// Contract event DTO
@@ -277,6 +385,7 @@ pub struct ContractEventDto {
     pub data: serde_json::Value,
     pub timestamp: DateTime<Utc>,
     pub emitter: String,
+    pub sequence: u64,
 }
 
 /// Query parameters for listing events
@@ -287,9 +396,23 @@ pub struct EventQueryParams {
     pub participant_id: Option<String>,
     pub from_timestamp: Option<DateTime<Utc>>,
     pub to_timestamp: Option<DateTime<Utc>>,
+    /// Only events after this sequence, oldest first
+    pub after_sequence: Option<u64>,
     pub limit: Option<u32>,
 }
 
+/// Register an event consumer
+#[derive(Debug, Deserialize)]
+pub struct RegisterEventConsumerRequest {
+    pub name: String,
+}
+
+/// Acknowledge every event up to `sequence`
+#[derive(Debug, Deserialize)]
+pub struct AckEventsRequest {
+    pub sequence: u64,
+}
+
 impl ApiServer {
     /// Create new API server
     pub fn new(node: Arc<ParticipantNode>, config: ApiConfig) -> Self {
@@ -299,8 +422,14 @@ impl ApiServer {
     /// Create router with all endpoints
     pub fn create_router(&self) -> Router {
         let eth_layer = EthCompatibilityLayer::new(self.node.clone());
+        let auth = Arc::new(ApiKeyAuth::new(&self.config.api_keys));
+        if !auth.is_enabled() {
+            warn!("No API keys configured; participant API is unauthenticated");
+        }
+        let limiter: Arc<dyn RateLimiterBackend> = Arc::new(InMemoryRateLimiter::from_config(&self.config));
+        let body_limits = Arc::new(self.config.body_limits.clone());
         
-        Router::new()
+        let router = Router::new()
             // JSON-RPC
             .route("/rpc", post(json_rpc))
             // Ethereum compatibility JSON-RPC
@@ -317,6 +446,7 @@ impl ApiServer {
             .route("/api/v1/blocks", get(list_blocks))
             .route("/api/v1/blocks/:number/summary", get(get_block_summary))
             .route("/api/v1/blocks/:number/tx/:tx_id/proof", get(get_tx_inclusion_proof))
+            .route("/api/v1/blocks/:number/proofs", post(get_tx_inclusion_proofs))
             .route("/api/v1/blocks/:number/state/:state_key/proof", get(get_state_change_proof))
             
             // Contract endpoints
@@ -340,13 +470,21 @@ impl ApiServer {
             .route("/api/v1/events", get(list_events))
             .route("/api/v1/events/contract/:contract_id", get(get_contract_events))
             .route("/api/v1/events/participant/:participant_id", get(get_participant_events))
+            .route("/api/v1/events/bounds", get(get_event_bounds))
+            .route("/api/v1/events/consumers", get(list_event_consumers))
+            .route("/api/v1/events/consumers", post(register_event_consumer))
+            .route("/api/v1/events/consumers/:name", get(get_event_consumer))
+            .route("/api/v1/events/consumers/:name/checkpoint", put(ack_events))
             
             // Node endpoints
             .route("/api/v1/node/status", get(get_node_status))
             .route("/api/v1/node/stats", get(get_node_stats))
             .route("/api/v1/node/peers", get(get_node_peers))
+            .route("/api/v1/node/resync", post(start_resync))
+            .route("/api/v1/node/sync-status", get(get_sync_status))
             // Ledger checkpoint endpoint
             .route("/api/v1/ledger/checkpoint", get(get_ledger_checkpoint))
+            .route("/ws/ledger", get(ledger_ws))
             // Mempool endpoints
             .route("/api/v1/mempool/submit", post(submit_mempool))
             .route("/api/v1/mempool/stats", get(get_mempool_stats))
@@ -354,6 +492,8 @@ impl ApiServer {
             // Template endpoints
             .route("/api/v1/templates", get(list_templates))
             .route("/api/v1/templates/:id", get(get_template))
+            .route("/api/v1/admin/templates/:id/schema", put(update_template_schema))
+            .route("/api/v1/admin/templates/:id/versions", post(register_template_version))
             
             // Health check
             .route("/health", get(health_check))
@@ -362,11 +502,24 @@ impl ApiServer {
                 ServiceBuilder::new()
                     .layer(TraceLayer::new_for_http())
                     .layer(CorsLayer::permissive())
-                    .layer(RequestBodyLimitLayer::new(1024 * 1024)) // 1MB limit
+                    .layer(PerRouteBodyLimitLayer::new(body_limits.default_bytes))
+                    .layer(middleware::from_fn(content_type_middleware))
             )
-            .layer(middleware::from_fn(auth_middleware))
+            .layer(middleware::from_fn_with_state(body_limits, route_body_limit_middleware))
+            .layer(middleware::from_fn_with_state(limiter.clone(), rate_limit_middleware))
+            .layer(middleware::from_fn_with_state(auth, auth_middleware))
             .layer(ConcurrencyLimitLayer::new(64))
-            .with_state(self.node.clone())
+            .layer(Extension(limiter))
+            .layer(Extension(eth_layer.filters()));
+        
+        let router = match &self.config.response_signing_key {
+            Some(key) => {
+                let signer = Arc::new(SignedResponseMiddleware::new(key.as_bytes()));
+                router.layer(middleware::from_fn_with_state(signer, signed_response_middleware))
+            }
+            None => router,
+        };
+        router.with_state(self.node.clone())
     }
 
     /// Start the API server
@@ -402,6 +555,7 @@ async fn submit_transaction(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }));
         }
@@ -417,12 +571,22 @@ async fn submit_transaction(
     };
 
     match node.submit_transaction(transaction.clone()).await {
+        Ok(validation) if !validation.valid => {
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Validation failed: {}", validation.errors.join("; "))),
+                error_code: Some(ErrorCode::ValidationFailed),
+                timestamp: Utc::now(),
+            }))
+        }
         Ok(_) => {
             let dto = convert_transaction_to_dto(&transaction);
             Ok(Json(ApiResponse {
                 success: true,
                 data: Some(dto),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -432,6 +596,7 @@ async fn submit_transaction(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -446,19 +611,37 @@ async fn list_transactions(
     let limit = query.limit.unwrap_or(50).min(1000);
     let offset = query.offset.unwrap_or(0);
 
-    match node.get_ledger_view().await {
-        Ok(view) => {
-            let transactions: Vec<TransactionDto> = view.transactions
-                .into_iter()
-                .skip(offset)
-                .take(limit)
-                .map(|tx| convert_transaction_to_dto(&tx))
+    let contract_id = match query.contract_id.as_deref().map(Uuid::parse_str).transpose() {
+        Ok(id) => id.map(ContractId),
+        Err(_) => {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Invalid contract ID".to_string()),
+                error_code: Some(ErrorCode::InvalidRequest),
+                timestamp: Utc::now(),
+            }));
+        }
+    };
+    let filter = TransactionFilter {
+        participant_id: query.participant_id.map(ParticipantId),
+        contract_id,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
+
+    match node.transaction_index().list_transactions(&filter, offset, limit).await {
+        Ok(indexed) => {
+            let transactions: Vec<TransactionDto> = indexed
+                .iter()
+                .map(|(entry, tx)| convert_indexed_transaction_to_dto(entry, tx))
                 .collect();
 
             Ok(Json(ApiResponse {
                 success: true,
                 data: Some(transactions),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -468,6 +651,7 @@ async fn list_transactions(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -486,29 +670,31 @@ async fn get_transaction(
                 success: false,
                 data: None,
                 error: Some("Invalid transaction ID".to_string()),
+                error_code: Some(ErrorCode::InvalidRequest),
                 timestamp: Utc::now(),
             }));
         }
     };
 
-    match node.get_ledger_view().await {
-        Ok(view) => {
-            if let Some(transaction) = view.transactions.iter().find(|tx| tx.id == transaction_id) {
-                let dto = convert_transaction_to_dto(transaction);
-                Ok(Json(ApiResponse {
-                    success: true,
-                    data: Some(dto),
-                    error: None,
-                    timestamp: Utc::now(),
-                }))
-            } else {
-                Ok(Json(ApiResponse {
-                    success: false,
-                    data: None,
-                    error: Some("Transaction not found".to_string()),
-                    timestamp: Utc::now(),
-                }))
-            }
+    match node.transaction_index().get_transaction(&transaction_id).await {
+        Ok(Some((entry, transaction))) => {
+            let dto = convert_indexed_transaction_to_dto(&entry, &transaction);
+            Ok(Json(ApiResponse {
+                success: true,
+                data: Some(dto),
+                error: None,
+                error_code: None,
+                timestamp: Utc::now(),
+            }))
+        }
+        Ok(None) => {
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Transaction not found".to_string()),
+                error_code: Some(ErrorCode::NotFound),
+                timestamp: Utc::now(),
+            }))
         }
         Err(e) => {
             error!("Failed to get transaction: {}", e);
@@ -516,6 +702,7 @@ async fn get_transaction(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -539,14 +726,14 @@ async fn get_latest_block(
                 gas_used: 0,
                 gas_limit: 0,
             };
-            Ok(Json(ApiResponse { success: true, data: Some(info), error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(info), error: None, error_code: None, timestamp: Utc::now() }))
         }
         Ok(None) => {
-            Ok(Json(ApiResponse { success: true, data: None, error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: None, error: None, error_code: None, timestamp: Utc::now() }))
         }
         Err(e) => {
             error!("Failed to get latest block: {}", e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
@@ -570,14 +757,14 @@ async fn get_block_by_number(
                 gas_used: 0,
                 gas_limit: 0,
             };
-            Ok(Json(ApiResponse { success: true, data: Some(BlockDetailsDto { info, transactions }), error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(BlockDetailsDto { info, transactions }), error: None, error_code: None, timestamp: Utc::now() }))
         }
         Ok(None) => {
-            Ok(Json(ApiResponse { success: true, data: None, error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: None, error: None, error_code: None, timestamp: Utc::now() }))
         }
         Err(e) => {
             error!("Failed to get block {}: {}", number, e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
@@ -601,18 +788,86 @@ async fn get_block_by_hash(
                 gas_used: 0,
                 gas_limit: 0,
             };
-            Ok(Json(ApiResponse { success: true, data: Some(BlockDetailsDto { info, transactions }), error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(BlockDetailsDto { info, transactions }), error: None, error_code: None, timestamp: Utc::now() }))
         }
         Ok(None) => {
-            Ok(Json(ApiResponse { success: true, data: None, error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: None, error: None, error_code: None, timestamp: Utc::now() }))
         }
         Err(e) => {
             error!("Failed to get block by hash {}: {}", hash, e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
 
+/// Stream stored and new blocks, with the caller's transactions, over a
+/// WebSocket. Keys bound to a participant see that participant's
+/// transactions; others see the node's own participant's.
+async fn ledger_ws(
+    State(node): State<Arc<ParticipantNode>>,
+    participant: Option<Extension<AuthenticatedParticipant>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let participant = participant
+        .map(|Extension(AuthenticatedParticipant(id))| ParticipantId(id))
+        .unwrap_or_else(|| node.get_participant_id());
+    ws.on_upgrade(move |socket| serve_ledger_subscription(node, participant, socket))
+}
+
+async fn send_ledger_frame(socket: &mut WebSocket, frame: &LedgerFrame) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(frame).expect("ledger frames serialize");
+    socket.send(Message::Text(text)).await
+}
+
+async fn serve_ledger_subscription(node: Arc<ParticipantNode>, participant: ParticipantId, mut socket: WebSocket) {
+    let storage = node.get_storage();
+    let request = match socket.recv().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<LedgerSubscribeRequest>(&text)
+            .map_err(|e| GarpError::ValidationError(format!("Invalid ledger subscription: {}", e))),
+        _ => return,
+    };
+    let start = match request {
+        Ok(request) => ledger_stream::start_slot(storage.as_ref(), &request).await,
+        Err(e) => Err(e),
+    };
+    let mut next_slot = match start {
+        Ok(slot) => slot,
+        Err(e) => {
+            let _ = send_ledger_frame(&mut socket, &LedgerFrame::from(e)).await;
+            return;
+        }
+    };
+    info!("Ledger subscription for {} from slot {}", participant.0, next_slot);
+
+    loop {
+        let (blocks, next) = match ledger_stream::blocks_from(storage.as_ref(), next_slot).await {
+            Ok(pass) => pass,
+            Err(e) => {
+                warn!("Ledger subscription for {} failed at slot {}: {}", participant.0, next_slot, e);
+                let _ = send_ledger_frame(&mut socket, &LedgerFrame::from(e)).await;
+                return;
+            }
+        };
+        for block in &blocks {
+            if send_ledger_frame(&mut socket, &ledger_stream::block_frame(block, &participant)).await.is_err() {
+                return;
+            }
+        }
+
+        // Caught up: wait for new blocks, noticing if the subscriber leaves
+        if next == next_slot {
+            tokio::select! {
+                _ = tokio::time::sleep(LEDGER_POLL_INTERVAL) => {}
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+        next_slot = next;
+    }
+}
+
 /// List blocks with pagination
 async fn list_blocks(
     State(node): State<Arc<ParticipantNode>>,
@@ -631,11 +886,11 @@ async fn list_blocks(
                 timestamp: b.timestamp,
                 transaction_count: b.transactions.len() as u32,
             }).collect();
-            Ok(Json(ApiResponse { success: true, data: Some(items), error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(items), error: None, error_code: None, timestamp: Utc::now() }))
         }
         Err(e) => {
             error!("Failed to list blocks: {}", e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
@@ -656,12 +911,12 @@ async fn get_block_summary(
                 timestamp: block.timestamp,
                 transaction_count: block.transactions.len() as u32,
             };
-            Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, error_code: None, timestamp: Utc::now() }))
         }
-        Ok(None) => Ok(Json(ApiResponse { success: true, data: None, error: None, timestamp: Utc::now() })),
+        Ok(None) => Ok(Json(ApiResponse { success: true, data: None, error: None, error_code: None, timestamp: Utc::now() })),
         Err(e) => {
             error!("Failed to get block summary {}: {}", number, e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
@@ -687,18 +942,62 @@ async fn get_tx_inclusion_proof(
                         directions: proof.directions.iter().map(|d| if *d { "right".to_string() } else { "left".to_string() }).collect(),
                         valid: crate::merkle::verify_proof(&proof),
                     };
-                    Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, timestamp: Utc::now() }))
+                    Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, error_code: None, timestamp: Utc::now() }))
                 } else {
-                    Ok(Json(ApiResponse { success: false, data: None, error: Some("Proof generation failed".to_string()), timestamp: Utc::now() }))
+                    Ok(Json(ApiResponse { success: false, data: None, error: Some("Proof generation failed".to_string()), error_code: Some(ErrorCode::InternalError), timestamp: Utc::now() }))
                 }
             } else {
-                Ok(Json(ApiResponse { success: false, data: None, error: Some("Transaction not in block".to_string()), timestamp: Utc::now() }))
+                Ok(Json(ApiResponse { success: false, data: None, error: Some("Transaction not in block".to_string()), error_code: Some(ErrorCode::NotFound), timestamp: Utc::now() }))
             }
         }
-        Ok(None) => Ok(Json(ApiResponse { success: false, data: None, error: Some("Block not found".to_string()), timestamp: Utc::now() })),
+        Ok(None) => Ok(Json(ApiResponse { success: false, data: None, error: Some("Block not found".to_string()), error_code: Some(ErrorCode::NotFound), timestamp: Utc::now() })),
         Err(e) => {
             error!("Failed to get tx proof in block {}: {}", number, e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
+        }
+    }
+}
+
+/// Get Merkle proofs for several transactions in a block, loading the block once
+async fn get_tx_inclusion_proofs(
+    State(node): State<Arc<ParticipantNode>>,
+    Path(number): Path<u64>,
+    Json(request): Json<TxProofsRequest>,
+) -> Result<Json<ApiResponse<TxProofsDto>>, StatusCode> {
+    let storage = node.get_storage();
+    match storage.get_block_by_slot(number).await {
+        Ok(Some(block)) => {
+            let leaves: Vec<Vec<u8>> = block.transactions.iter().map(|tx| tx.id.0.as_bytes().to_vec()).collect();
+            let positions: HashMap<String, usize> = block.transactions.iter()
+                .enumerate()
+                .map(|(i, tx)| (tx.id.0.to_string(), i))
+                .collect();
+
+            // Ids not in the block map past the last leaf and yield no proof
+            let indices: Vec<usize> = request.tx_ids.iter()
+                .map(|id| positions.get(id).copied().unwrap_or(usize::MAX))
+                .collect();
+            let proofs = crate::merkle::merkle_proofs(&leaves, &indices);
+
+            let items = request.tx_ids.into_iter().zip(proofs).map(|(tx_id, proof)| match proof {
+                Some(proof) => {
+                    let valid = crate::merkle::verify_proof(&proof);
+                    TxProofItemDto { proof: Some(MerkleProofDto::from_proof(&block.hash, tx_id.clone(), &proof, valid)), tx_id, error: None }
+                }
+                None => TxProofItemDto { tx_id, proof: None, error: Some("Transaction not in block".to_string()) },
+            }).collect();
+
+            let dto = TxProofsDto {
+                block_hash: hex::encode(&block.hash),
+                root: hex::encode(merkle_root(&leaves)),
+                proofs: items,
+            };
+            Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, error_code: None, timestamp: Utc::now() }))
+        }
+        Ok(None) => Ok(Json(ApiResponse { success: false, data: None, error: Some("Block not found".to_string()), error_code: Some(ErrorCode::NotFound), timestamp: Utc::now() })),
+        Err(e) => {
+            error!("Failed to get tx proofs in block {}: {}", number, e);
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
@@ -716,7 +1015,7 @@ async fn get_state_change_proof(
                 Ok(items) => items,
                 Err(e) => {
                     error!("Failed to load state changes for slot {}: {}", number, e);
-                    return Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }));
+                    return Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }));
                 }
             };
             // Find the first change matching the requested key
@@ -736,18 +1035,18 @@ async fn get_state_change_proof(
                         directions: proof.directions.iter().map(|d| if *d { "right".to_string() } else { "left".to_string() }).collect(),
                         valid,
                     };
-                    Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, timestamp: Utc::now() }))
+                    Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, error_code: None, timestamp: Utc::now() }))
                 } else {
-                    Ok(Json(ApiResponse { success: false, data: None, error: Some("Proof generation failed".to_string()), timestamp: Utc::now() }))
+                    Ok(Json(ApiResponse { success: false, data: None, error: Some("Proof generation failed".to_string()), error_code: Some(ErrorCode::InternalError), timestamp: Utc::now() }))
                 }
             } else {
-                Ok(Json(ApiResponse { success: false, data: None, error: Some("State key not changed in block".to_string()), timestamp: Utc::now() }))
+                Ok(Json(ApiResponse { success: false, data: None, error: Some("State key not changed in block".to_string()), error_code: Some(ErrorCode::NotFound), timestamp: Utc::now() }))
             }
         }
-        Ok(None) => Ok(Json(ApiResponse { success: false, data: None, error: Some("Block not found".to_string()), timestamp: Utc::now() })),
+        Ok(None) => Ok(Json(ApiResponse { success: false, data: None, error: Some("Block not found".to_string()), error_code: Some(ErrorCode::NotFound), timestamp: Utc::now() })),
         Err(e) => {
             error!("Failed to get state proof in block {}: {}", number, e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
@@ -790,6 +1089,7 @@ async fn create_contract(
                 success: true,
                 data: None, // Would contain the created contract
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -799,6 +1099,7 @@ async fn create_contract(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -826,6 +1127,7 @@ async fn list_contracts(
                 success: true,
                 data: Some(contracts),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -835,6 +1137,7 @@ async fn list_contracts(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -853,6 +1156,7 @@ async fn get_contract(
                 success: false,
                 data: None,
                 error: Some("Invalid contract ID".to_string()),
+                error_code: Some(ErrorCode::InvalidRequest),
                 timestamp: Utc::now(),
             }));
         }
@@ -866,6 +1170,7 @@ async fn get_contract(
                     success: true,
                     data: Some(dto),
                     error: None,
+                    error_code: None,
                     timestamp: Utc::now(),
                 }))
             } else {
@@ -873,6 +1178,7 @@ async fn get_contract(
                     success: false,
                     data: None,
                     error: Some("Contract not found".to_string()),
+                    error_code: Some(ErrorCode::NotFound),
                     timestamp: Utc::now(),
                 }))
             }
@@ -883,6 +1189,7 @@ async fn get_contract(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -902,6 +1209,7 @@ async fn exercise_contract(
                 success: false,
                 data: None,
                 error: Some("Invalid contract ID".to_string()),
+                error_code: Some(ErrorCode::InvalidRequest),
                 timestamp: Utc::now(),
             }));
         }
@@ -928,6 +1236,7 @@ async fn exercise_contract(
                 success: true,
                 data: Some(()),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -937,6 +1246,7 @@ async fn exercise_contract(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -955,6 +1265,7 @@ async fn archive_contract(
                 success: false,
                 data: None,
                 error: Some("Invalid contract ID".to_string()),
+                error_code: Some(ErrorCode::InvalidRequest),
                 timestamp: Utc::now(),
             }));
         }
@@ -979,6 +1290,7 @@ async fn archive_contract(
                 success: true,
                 data: Some(()),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -988,6 +1300,7 @@ async fn archive_contract(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -1020,6 +1333,7 @@ async fn create_asset(
                 success: true,
                 data: None, // Would contain the created asset
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -1029,6 +1343,7 @@ async fn create_asset(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -1051,6 +1366,7 @@ async fn list_assets(
                 success: true,
                 data: Some(assets),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -1060,6 +1376,7 @@ async fn list_assets(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -1081,6 +1398,7 @@ async fn get_asset(
                     success: true,
                     data: Some(dto),
                     error: None,
+                    error_code: None,
                     timestamp: Utc::now(),
                 }))
             } else {
@@ -1088,6 +1406,7 @@ async fn get_asset(
                     success: false,
                     data: None,
                     error: Some("Asset not found".to_string()),
+                    error_code: Some(ErrorCode::NotFound),
                     timestamp: Utc::now(),
                 }))
             }
@@ -1098,6 +1417,7 @@ async fn get_asset(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -1134,6 +1454,7 @@ async fn transfer_asset(
                 success: true,
                 data: Some(()),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -1143,6 +1464,7 @@ async fn transfer_asset(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -1165,6 +1487,7 @@ async fn get_wallet_balances(
                 success: true,
                 data: Some(balances),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -1174,19 +1497,54 @@ async fn get_wallet_balances(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
     }
 }
 
-/// Get wallet transaction history
+/// Get wallet balance history (credits/debits with running balances), oldest first
 async fn get_wallet_history(
     State(node): State<Arc<ParticipantNode>>,
-    Query(query): Query<TransactionQuery>,
-) -> Result<Json<ApiResponse<Vec<TransactionDto>>>, StatusCode> {
-    // This would filter transactions related to wallet operations
-    list_transactions(State(node), Query(query)).await
+    Query(query): Query<WalletHistoryQueryParams>,
+) -> Result<Json<ApiResponse<Vec<WalletHistoryEntryDto>>>, StatusCode> {
+    let limit = query.limit.unwrap_or(50).min(1000);
+
+    match node.get_wallet_history(query.asset_type, Some(limit), query.offset).await {
+        Ok(entries) => {
+            let items: Vec<WalletHistoryEntryDto> = entries
+                .into_iter()
+                .map(|e| WalletHistoryEntryDto {
+                    transaction_id: e.transaction_id.0.to_string(),
+                    counterparty: e.counterparty.map(|p| p.0),
+                    asset_id: e.asset_id,
+                    asset_type: e.asset_type,
+                    amount: e.amount,
+                    balance_after: e.balance_after,
+                    timestamp: e.timestamp,
+                })
+                .collect();
+
+            Ok(Json(ApiResponse {
+                success: true,
+                data: Some(items),
+                error: None,
+                error_code: None,
+                timestamp: Utc::now(),
+            }))
+        }
+        Err(e) => {
+            error!("Failed to get wallet history: {}", e);
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code()),
+                timestamp: Utc::now(),
+            }))
+        }
+    }
 }
 
 /// Get node status
@@ -1217,6 +1575,7 @@ async fn get_node_status(
                 success: true,
                 data: Some(status),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -1226,15 +1585,50 @@ async fn get_node_status(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
     }
 }
 
+/// Start a manual resync from the global synchronizer
+async fn start_resync(
+    State(node): State<Arc<ParticipantNode>>,
+    Json(request): Json<ResyncRequest>,
+) -> Result<Json<ApiResponse<ResyncProgress>>, StatusCode> {
+    let resync = node.resync();
+    match resync.start(request.from_height).await {
+        Ok(_) => {
+            info!("Resync started from height {}", request.from_height.unwrap_or(0));
+            Ok(Json(ApiResponse { success: true, data: Some(resync.progress().await), error: None, error_code: None, timestamp: Utc::now() }))
+        }
+        Err(e) => {
+            warn!("Resync not started: {}", e);
+            Err(StatusCode::CONFLICT)
+        }
+    }
+}
+
+/// Get sync progress, including any running resync
+async fn get_sync_status(
+    State(node): State<Arc<ParticipantNode>>,
+) -> Result<Json<ApiResponse<SyncStatusDto>>, StatusCode> {
+    let (global_head_height, _) = node.get_global_head().await;
+    let (last_h, last_t) = node.get_sync_last_applied().await;
+    let dto = SyncStatusDto {
+        global_head_height,
+        sync_last_applied_height: last_h,
+        sync_last_applied_time: last_t,
+        resync: node.resync().progress().await,
+    };
+    Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, error_code: None, timestamp: Utc::now() }))
+}
+
 /// Get node statistics
 async fn get_node_stats(
     State(node): State<Arc<ParticipantNode>>,
+    Extension(limiter): Extension<Arc<dyn RateLimiterBackend>>,
 ) -> Result<Json<ApiResponse<NodeStatsDto>>, StatusCode> {
     match node.get_node_stats().await {
         Ok(stats) => {
@@ -1245,12 +1639,14 @@ async fn get_node_stats(
                 total_assets: stats.total_assets,
                 wallet_balances: stats.wallet_balances,
                 network_peers: stats.network_peers,
+                rate_limit_buckets: limiter.bucket_levels().await,
             };
 
             Ok(Json(ApiResponse {
                 success: true,
                 data: Some(dto),
                 error: None,
+                error_code: None,
                 timestamp: Utc::now(),
             }))
         }
@@ -1260,6 +1656,7 @@ async fn get_node_stats(
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
             }))
         }
@@ -1275,35 +1672,104 @@ async fn get_node_peers(
         success: true,
         data: Some(vec![]), // Placeholder
         error: None,
+        error_code: None,
         timestamp: Utc::now(),
     }))
 }
 
-/// List contract templates
+/// List the latest version of each contract template
 async fn list_templates(
     State(node): State<Arc<ParticipantNode>>,
-) -> Result<Json<ApiResponse<Vec<serde_json::Value>>>, StatusCode> {
-    // This would return available contract templates
+) -> Result<Json<ApiResponse<Vec<Template>>>, StatusCode> {
     Ok(Json(ApiResponse {
         success: true,
-        data: Some(vec![]), // Placeholder
+        data: Some(node.template_registry().list_templates()),
         error: None,
+        error_code: None,
         timestamp: Utc::now(),
     }))
 }
 
-/// Get a specific template
+/// Get a template version, the latest by default
 async fn get_template(
-    State(_node): State<Arc<ParticipantNode>>,
-    Path(_id): Path<String>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
-    // This would return a specific template
-    Ok(Json(ApiResponse {
-        success: true,
-        data: None, // Placeholder
-        error: None,
-        timestamp: Utc::now(),
-    }))
+    State(node): State<Arc<ParticipantNode>>,
+    Path(id): Path<String>,
+    Query(query): Query<TemplateQuery>,
+) -> Result<Json<ApiResponse<TemplateDto>>, StatusCode> {
+    let registry = node.template_registry();
+    match registry.get_template(&id, query.version) {
+        Some(template) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(TemplateDto {
+                template,
+                versions: registry.get_versions(&id).iter().map(|t| t.version).collect(),
+            }),
+            error: None,
+            error_code: None,
+            timestamp: Utc::now(),
+        })),
+        None => Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Template not found".to_string()),
+            error_code: Some(ErrorCode::NotFound),
+            timestamp: Utc::now(),
+        })),
+    }
+}
+
+/// Register a new version of a contract template
+async fn register_template_version(
+    State(node): State<Arc<ParticipantNode>>,
+    Path(id): Path<String>,
+    Json(request): Json<RegisterTemplateRequest>,
+) -> Result<Json<ApiResponse<Template>>, StatusCode> {
+    match node.template_registry().register_template(&id, request.version, request.schema).await {
+        Ok(template) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(template),
+            error: None,
+            error_code: None,
+            timestamp: Utc::now(),
+        })),
+        Err(e) => {
+            warn!("Failed to register template {} version {}: {}", id, request.version, e);
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code()),
+                timestamp: Utc::now(),
+            }))
+        }
+    }
+}
+
+/// Register or replace the argument schemas of a template
+async fn update_template_schema(
+    State(node): State<Arc<ParticipantNode>>,
+    Path(id): Path<String>,
+    Json(schemas): Json<TemplateSchemas>,
+) -> Result<Json<ApiResponse<TemplateSchemas>>, StatusCode> {
+    match node.update_template_schemas(&id, schemas.clone()) {
+        Ok(()) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(schemas),
+            error: None,
+            error_code: None,
+            timestamp: Utc::now(),
+        })),
+        Err(e) => {
+            warn!("Failed to update schema for template {}: {}", id, e);
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code()),
+                timestamp: Utc::now(),
+            }))
+        }
+    }
 }
 
 /// Health check endpoint
@@ -1312,6 +1778,7 @@ async fn health_check() -> Result<Json<ApiResponse<String>>, StatusCode> {
         success: true,
         data: Some("OK".to_string()),
         error: None,
+        error_code: None,
         timestamp: Utc::now(),
     }))
 }
@@ -1326,7 +1793,7 @@ struct JsonRpcRequest {
 
 #[derive(Debug, Serialize)]
 struct JsonRpcError {
-    pub code: i32,
+    pub code: i64,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
@@ -1342,17 +1809,27 @@ struct JsonRpcResponse {
     pub id: Option<serde_json::Value>,
 }
 
-const RPC_INVALID_REQUEST: i32 = -32600;
-const RPC_METHOD_NOT_FOUND: i32 = -32601;
-const RPC_INVALID_PARAMS: i32 = -32602;
-const RPC_INTERNAL_ERROR: i32 = -32603;
-const RPC_SERVER_ERROR: i32 = -32000;
+impl JsonRpcError {
+    /// Error carrying `code`, with its name and retryability in `data`
+    fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self { code: code.code(), message: message.into(), data: Some(code.rpc_data()) }
+    }
+}
 
-fn rpc_error(code: i32, message: impl Into<String>, id: Option<serde_json::Value>) -> JsonRpcResponse {
+impl From<GarpError> for JsonRpcError {
+    fn from(error: GarpError) -> Self {
+        Self::new(error.code(), error.to_string())
+    }
+}
+
+/// Longest slot range a single `getSlotLeaders` call may cover
+const MAX_SLOT_LEADERS_RANGE: u64 = 10_000;
+
+fn rpc_error(code: ErrorCode, message: impl Into<String>, id: Option<serde_json::Value>) -> JsonRpcResponse {
     JsonRpcResponse {
         jsonrpc: "2.0".to_string(),
         result: None,
-        error: Some(JsonRpcError { code, message: message.into(), data: None }),
+        error: Some(JsonRpcError::new(code, message)),
         id,
     }
 }
@@ -1382,7 +1859,34 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
             if let Some(leader) = crate::consensus::leader_for_slot(slot, &validators) {
                 result = Some(serde_json::json!(leader.0));
             } else {
-                error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: "No validators configured".to_string(), data: None });
+                error = Some(JsonRpcError::new(ErrorCode::ConfigError, "No validators configured".to_string()));
+            }
+        }
+        "getSlotLeaders" => {
+            let range = req.params.as_ref().and_then(|p| {
+                Some((p.get("from_slot")?.as_u64()?, p.get("to_slot")?.as_u64()?))
+            });
+            match range {
+                Some((from_slot, to_slot)) if from_slot <= to_slot && to_slot - from_slot < MAX_SLOT_LEADERS_RANGE => {
+                    let validators = node.get_validators();
+                    if validators.is_empty() {
+                        error = Some(JsonRpcError::new(ErrorCode::ConfigError, "No validators configured".to_string()));
+                    } else {
+                        let leaders: Vec<serde_json::Value> = (from_slot..=to_slot)
+                            .filter_map(|slot| crate::consensus::leader_for_slot(slot, &validators).map(|leader| serde_json::json!({
+                                "slot": slot,
+                                "leader": leader.0,
+                            })))
+                            .collect();
+                        result = Some(serde_json::json!(leaders));
+                    }
+                }
+                _ => {
+                    error = Some(JsonRpcError::new(
+                        ErrorCode::InvalidParams,
+                        format!("Expected from_slot <= to_slot spanning at most {} slots", MAX_SLOT_LEADERS_RANGE),
+                    ));
+                }
             }
         }
         // Blocks
@@ -1405,8 +1909,8 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
                                 "transactions": txs,
                             }));
                         }
-                        Ok(None) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: "Block not found".to_string(), data: None }); }
-                        Err(e) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: e.to_string(), data: None }); }
+                        Ok(None) => { error = Some(JsonRpcError::new(ErrorCode::NotFound, "Block not found".to_string())); }
+                        Err(e) => { error = Some(JsonRpcError::from(e)); }
                     }
                 } else if let Some(hash_hex) = params.get("hash").and_then(|v| v.as_str()) {
                     match storage.get_block_by_hash_hex(hash_hex).await {
@@ -1424,14 +1928,14 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
                                 "transactions": txs,
                             }));
                         }
-                        Ok(None) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: "Block not found".to_string(), data: None }); }
-                        Err(e) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: e.to_string(), data: None }); }
+                        Ok(None) => { error = Some(JsonRpcError::new(ErrorCode::NotFound, "Block not found".to_string())); }
+                        Err(e) => { error = Some(JsonRpcError::from(e)); }
                     }
                 } else {
-                    error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing parameter: slot or hash".to_string(), data: None });
+                    error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing parameter: slot or hash".to_string()));
                 }
             } else {
-                error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing params".to_string(), data: None });
+                error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing params".to_string()));
             }
         }
         // Transactions
@@ -1450,17 +1954,17 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
                                         "command": tx.command,
                                     }));
                                 }
-                                Ok(None) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: "Transaction not found".to_string(), data: None }); }
-                                Err(e) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: e.to_string(), data: None }); }
+                                Ok(None) => { error = Some(JsonRpcError::new(ErrorCode::NotFound, "Transaction not found".to_string())); }
+                                Err(e) => { error = Some(JsonRpcError::from(e)); }
                             }
                         }
-                        Err(_) => error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Invalid signature".to_string(), data: None }),
+                        Err(_) => error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Invalid signature".to_string())),
                     }
                 } else {
-                    error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing parameter: signature".to_string(), data: None });
+                    error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing parameter: signature".to_string()));
                 }
             } else {
-                error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing params".to_string(), data: None });
+                error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing params".to_string()));
             }
         }
         "getBalance" => {
@@ -1479,13 +1983,13 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
                             }
                         }
                         Ok(None) => { result = Some(serde_json::json!({"balance": 0.0})); }
-                        Err(e) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: e.to_string(), data: None }); }
+                        Err(e) => { error = Some(JsonRpcError::from(e)); }
                     }
                 } else {
-                    error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing parameter: participantId".to_string(), data: None });
+                    error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing parameter: participantId".to_string()));
                 }
             } else {
-                error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing params".to_string(), data: None });
+                error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing params".to_string()));
             }
         }
         // Node info
@@ -1515,20 +2019,28 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
                                     Ok(vr) => {
                                         result = Some(serde_json::json!({"accepted": true, "status": format!("{:?}", vr)}));
                                     }
-                                    Err(e) => { error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: e.to_string(), data: None }); }
+                                    Err(e) => { error = Some(JsonRpcError::from(e)); }
                                 }
                             }
-                            Err(e) => error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: e.to_string(), data: None }),
+                            Err(e) => error = Some(JsonRpcError::new(ErrorCode::InvalidParams, e.to_string())),
                         }
                     }
-                    Err(_) => error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Invalid command".to_string(), data: None }),
+                    Err(_) => error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Invalid command".to_string())),
                 }
             } else {
-                error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing params".to_string(), data: None });
+                error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing params".to_string()));
             }
         }
         "simulateTransaction" => {
-            if let Some(params) = &req.params {
+            if let Some(command) = req.params.as_ref().and_then(|p| p.get("command")) {
+                match serde_json::from_value::<TransactionCommandDto>(command.clone()) {
+                    Ok(cmd_dto) => match simulate_command(&node, cmd_dto).await {
+                        Ok(sim) => result = Some(serde_json::to_value(sim).unwrap_or_default()),
+                        Err(e) => error = Some(JsonRpcError::from(e)),
+                    },
+                    Err(_) => error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Invalid command".to_string())),
+                }
+            } else if let Some(params) = &req.params {
                 match serde_json::from_value::<SimulationRequestDto>(params.clone()) {
                     Ok(sim) => {
                         match convert_simulation_request_to_tx_v2(sim) {
@@ -1541,20 +2053,20 @@ async fn handle_single_rpc(node: Arc<ParticipantNode>, req: JsonRpcRequest) -> J
                                             "logs": res.logs,
                                         }));
                                     }
-                                    Err(e) => error = Some(JsonRpcError { code: RPC_SERVER_ERROR, message: e.to_string(), data: None }),
+                                    Err(e) => error = Some(JsonRpcError::from(e)),
                                 }
                             }
-                            Err(e) => error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: e.to_string(), data: None }),
+                            Err(e) => error = Some(JsonRpcError::new(ErrorCode::InvalidParams, e.to_string())),
                         }
                     }
-                    Err(_) => error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Invalid simulation params".to_string(), data: None }),
+                    Err(_) => error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Invalid simulation params".to_string())),
                 }
             } else {
-                error = Some(JsonRpcError { code: RPC_INVALID_PARAMS, message: "Missing params".to_string(), data: None });
+                error = Some(JsonRpcError::new(ErrorCode::InvalidParams, "Missing params".to_string()));
             }
         }
         _ => {
-            error = Some(JsonRpcError { code: RPC_METHOD_NOT_FOUND, message: format!("Unknown method: {}", req.method), data: None });
+            error = Some(JsonRpcError::new(ErrorCode::MethodNotFound, format!("Unknown method: {}", req.method)));
         }
     }
 
@@ -1578,11 +2090,11 @@ async fn json_rpc(
             match req {
                 Ok(r) => {
                     let resp = handle_single_rpc(node.clone(), r).await;
-                    responses.push(serde_json::to_value(resp).unwrap_or_else(|_| serde_json::json!(rpc_error(RPC_INTERNAL_ERROR, "Failed to serialize response", None))));
+                    responses.push(serde_json::to_value(resp).unwrap_or_else(|_| serde_json::json!(rpc_error(ErrorCode::InternalError, "Failed to serialize response", None))));
                 }
                 Err(_) => {
                     let id = item.get("id").cloned();
-                    responses.push(serde_json::json!(rpc_error(RPC_INVALID_REQUEST, "Invalid request", id)));
+                    responses.push(serde_json::json!(rpc_error(ErrorCode::InvalidRequest, "Invalid request", id)));
                 }
             }
         }
@@ -1592,12 +2104,12 @@ async fn json_rpc(
         match req {
             Ok(r) => {
                 let resp = handle_single_rpc(node, r).await;
-                Ok(Json(serde_json::to_value(resp).unwrap_or_else(|_| serde_json::json!(rpc_error(RPC_INTERNAL_ERROR, "Failed to serialize response", None)))))
+                Ok(Json(serde_json::to_value(resp).unwrap_or_else(|_| serde_json::json!(rpc_error(ErrorCode::InternalError, "Failed to serialize response", None)))))
             }
-            Err(_) => Ok(Json(serde_json::json!(rpc_error(RPC_INVALID_REQUEST, "Invalid request", body.get("id").cloned())))),
+            Err(_) => Ok(Json(serde_json::json!(rpc_error(ErrorCode::InvalidRequest, "Invalid request", body.get("id").cloned())))),
         }
     } else {
-        Ok(Json(serde_json::json!(rpc_error(RPC_INVALID_REQUEST, "Invalid request payload", None))))
+        Ok(Json(serde_json::json!(rpc_error(ErrorCode::InvalidRequest, "Invalid request payload", None))))
     }
 }
 
@@ -1699,6 +2211,12 @@ fn convert_transaction_to_dto(transaction: &Transaction) -> TransactionDto {
     }
 }
 
+fn convert_indexed_transaction_to_dto(entry: &TransactionIndexEntry, transaction: &Transaction) -> TransactionDto {
+    let mut dto = convert_transaction_to_dto(transaction);
+    dto.status = entry.status.as_str().to_string();
+    dto
+}
+
 fn convert_contract_to_dto(contract: &Contract) -> ContractDto {
     ContractDto {
         id: contract.id.0.to_string(),
@@ -1730,57 +2248,79 @@ fn convert_wallet_balance_to_dto(balance: &WalletBalance) -> WalletBalanceDto {
     }
 }
 
-/// List events with query parameters
-async fn list_events(
-    State(node): State<Arc<ParticipantNode>>,
-    Query(query): Query<EventQueryParams>,
-) -> Result<Json<ApiResponse<Vec<ContractEventDto>>>, StatusCode> {
-    let event_query = crate::storage::EventQuery {
-        contract_id: query.contract_id.and_then(|id| Uuid::parse_str(&id).ok()).map(garp_common::ContractId),
-        event_type: query.event_type,
-        participant_id: query.participant_id.map(garp_common::ParticipantId),
-        from_timestamp: query.from_timestamp,
-        to_timestamp: query.to_timestamp,
-        limit: query.limit,
-    };
-
-    match node.get_storage().query_events(&event_query).await {
-        Ok(events) => {
-            let event_dtos: Vec<ContractEventDto> = events
-                .into_iter()
-                .map(|event| ContractEventDto {
-                    id: event.id,
-                    contract_id: event.contract_id.0.to_string(),
-                    event_type: event.event_type,
-                    data: event.data,
-                    timestamp: event.timestamp,
-                    emitter: event.emitter.0,
-                })
-                .collect();
+fn convert_event_to_dto(event: ContractEvent) -> ContractEventDto {
+    ContractEventDto {
+        id: event.id,
+        contract_id: event.contract_id.0.to_string(),
+        event_type: event.event_type,
+        data: event.data,
+        timestamp: event.timestamp,
+        emitter: event.emitter.0,
+        sequence: event.sequence,
+    }
+}
 
-            Ok(Json(ApiResponse {
-                success: true,
-                data: Some(event_dtos),
-                error: None,
-                timestamp: Utc::now(),
-            }))
-        }
+/// Wrap the result of an event operation, logging failures as `action`
+fn event_response<T>(result: GarpResult<T>, action: &str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(data) => Json(ApiResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+            error_code: None,
+            timestamp: Utc::now(),
+        }),
         Err(e) => {
-            error!("Failed to list events: {}", e);
-            Ok(Json(ApiResponse {
+            error!("Failed to {}: {}", action, e);
+            Json(ApiResponse {
                 success: false,
                 data: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code()),
                 timestamp: Utc::now(),
-            }))
+            })
         }
     }
 }
 
+/// Build a storage query from `params`; scoped endpoints set the contract
+/// or participant from their path instead. Without a cursor only the
+/// newest events are returned.
+fn event_query_from_params(
+    params: EventQueryParams,
+    contract_id: Option<garp_common::ContractId>,
+    participant_id: Option<garp_common::ParticipantId>,
+) -> crate::storage::EventQuery {
+    crate::storage::EventQuery {
+        contract_id: contract_id.or_else(|| params.contract_id.and_then(|id| Uuid::parse_str(&id).ok()).map(garp_common::ContractId)),
+        event_type: params.event_type,
+        participant_id: participant_id.or_else(|| params.participant_id.map(garp_common::ParticipantId)),
+        from_timestamp: params.from_timestamp,
+        to_timestamp: params.to_timestamp,
+        after_sequence: params.after_sequence,
+        limit: params.limit,
+    }
+}
+
+async fn query_event_dtos(node: &ParticipantNode, query: &crate::storage::EventQuery) -> GarpResult<Vec<ContractEventDto>> {
+    let events = node.get_storage().query_events(query).await?;
+    Ok(events.into_iter().map(convert_event_to_dto).collect())
+}
+
+/// List events with query parameters
+async fn list_events(
+    State(node): State<Arc<ParticipantNode>>,
+    Query(query): Query<EventQueryParams>,
+) -> Result<Json<ApiResponse<Vec<ContractEventDto>>>, StatusCode> {
+    let event_query = event_query_from_params(query, None, None);
+    Ok(event_response(query_event_dtos(&node, &event_query).await, "list events"))
+}
+
 /// Get events for a specific contract
 async fn get_contract_events(
     State(node): State<Arc<ParticipantNode>>,
     Path(contract_id): Path<String>,
+    Query(query): Query<EventQueryParams>,
 ) -> Result<Json<ApiResponse<Vec<ContractEventDto>>>, StatusCode> {
     let contract_uuid = match Uuid::parse_str(&contract_id) {
         Ok(uuid) => uuid,
@@ -1789,80 +2329,69 @@ async fn get_contract_events(
                 success: false,
                 data: None,
                 error: Some("Invalid contract ID".to_string()),
+                error_code: Some(ErrorCode::InvalidRequest),
                 timestamp: Utc::now(),
             }));
         }
     };
 
-    match node.get_storage().get_contract_events(&garp_common::ContractId(contract_uuid), Some(100)).await {
-        Ok(events) => {
-            let event_dtos: Vec<ContractEventDto> = events
-                .into_iter()
-                .map(|event| ContractEventDto {
-                    id: event.id,
-                    contract_id: event.contract_id.0.to_string(),
-                    event_type: event.event_type,
-                    data: event.data,
-                    timestamp: event.timestamp,
-                    emitter: event.emitter.0,
-                })
-                .collect();
-
-            Ok(Json(ApiResponse {
-                success: true,
-                data: Some(event_dtos),
-                error: None,
-                timestamp: Utc::now(),
-            }))
-        }
-        Err(e) => {
-            error!("Failed to get contract events: {}", e);
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                error: Some(e.to_string()),
-                timestamp: Utc::now(),
-            }))
-        }
-    }
+    let mut event_query = event_query_from_params(query, Some(garp_common::ContractId(contract_uuid)), None);
+    event_query.limit = event_query.limit.or(Some(100));
+    Ok(event_response(query_event_dtos(&node, &event_query).await, "get contract events"))
 }
 
 /// Get events emitted by a specific participant
 async fn get_participant_events(
     State(node): State<Arc<ParticipantNode>>,
     Path(participant_id): Path<String>,
+    Query(query): Query<EventQueryParams>,
 ) -> Result<Json<ApiResponse<Vec<ContractEventDto>>>, StatusCode> {
-    match node.get_storage().get_participant_events(&garp_common::ParticipantId(participant_id), Some(100)).await {
-        Ok(events) => {
-            let event_dtos: Vec<ContractEventDto> = events
-                .into_iter()
-                .map(|event| ContractEventDto {
-                    id: event.id,
-                    contract_id: event.contract_id.0.to_string(),
-                    event_type: event.event_type,
-                    data: event.data,
-                    timestamp: event.timestamp,
-                    emitter: event.emitter.0,
-                })
-                .collect();
+    let mut event_query = event_query_from_params(query, None, Some(garp_common::ParticipantId(participant_id)));
+    event_query.limit = event_query.limit.or(Some(100));
+    Ok(event_response(query_event_dtos(&node, &event_query).await, "get participant events"))
+}
 
-            Ok(Json(ApiResponse {
-                success: true,
-                data: Some(event_dtos),
-                error: None,
-                timestamp: Utc::now(),
-            }))
-        }
-        Err(e) => {
-            error!("Failed to get participant events: {}", e);
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                error: Some(e.to_string()),
-                timestamp: Utc::now(),
-            }))
-        }
-    }
+/// Earliest sequence still retained and the latest assigned; a consumer
+/// whose cursor is below the earliest minus one has missed pruned events
+async fn get_event_bounds(
+    State(node): State<Arc<ParticipantNode>>,
+) -> Result<Json<ApiResponse<EventLogBounds>>, StatusCode> {
+    Ok(event_response(node.event_index().bounds().await, "read event log bounds"))
+}
+
+/// List registered event consumers and their checkpoints
+async fn list_event_consumers(
+    State(node): State<Arc<ParticipantNode>>,
+) -> Result<Json<ApiResponse<Vec<EventConsumer>>>, StatusCode> {
+    Ok(event_response(node.event_index().list_consumers().await, "list event consumers"))
+}
+
+/// Register an event consumer; registering an existing name returns its checkpoint
+async fn register_event_consumer(
+    State(node): State<Arc<ParticipantNode>>,
+    Json(request): Json<RegisterEventConsumerRequest>,
+) -> Result<Json<ApiResponse<EventConsumer>>, StatusCode> {
+    Ok(event_response(node.event_index().register_consumer(&request.name).await, "register event consumer"))
+}
+
+/// Get an event consumer's checkpoint
+async fn get_event_consumer(
+    State(node): State<Arc<ParticipantNode>>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<EventConsumer>>, StatusCode> {
+    let result = node.event_index().get_consumer(&name).await.and_then(|consumer| {
+        consumer.ok_or_else(|| GarpError::NotFound(format!("Event consumer {} is not registered", name)))
+    });
+    Ok(event_response(result, "get event consumer"))
+}
+
+/// Advance an event consumer's checkpoint
+async fn ack_events(
+    State(node): State<Arc<ParticipantNode>>,
+    Path(name): Path<String>,
+    Json(request): Json<AckEventsRequest>,
+) -> Result<Json<ApiResponse<EventConsumer>>, StatusCode> {
+    Ok(event_response(node.event_index().ack(&name, request.sequence).await, "acknowledge events"))
 }
 
 /// Ledger state DTO
@@ -1890,31 +2419,14 @@ async fn get_ledger_checkpoint(
                 has_wallet_balance: state.wallet_balance.is_some(),
                 checkpoint_time: state.checkpoint_time,
             };
-            Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, error_code: None, timestamp: Utc::now() }))
         }
         Err(e) => {
             error!("Failed to get ledger checkpoint: {}", e);
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: Utc::now() }))
         }
     }
 }
-    async fn auth_middleware<B>(req: axum::http::Request<B>, next: middleware::Next<B>) -> Result<axum::response::Response, axum::http::StatusCode> {
-    let required = std::env::var("PARTICIPANT_API_TOKEN").ok();
-    if let Some(expected) = required {
-        if let Some(header) = req.headers().get(axum::http::header::AUTHORIZATION) {
-            if let Ok(hval) = header.to_str() {
-                if hval.starts_with("Bearer ") {
-                    let token = &hval[7..];
-                    if token == expected {
-                        return Ok(next.run(req).await);
-                    }
-                }
-            }
-        }
-        return Err(axum::http::StatusCode::UNAUTHORIZED);
-    }
-    Ok(next.run(req).await)
-}
 
 // -----------------------------------------------------------------------------
 // TxV2 Simulation DTOs and handler
@@ -2021,24 +2533,62 @@ fn convert_simulation_request_to_tx_v2(req: SimulationRequestDto) -> garp_common
 
 async fn simulate_transaction_v2(
     State(node): State<Arc<ParticipantNode>>,
-    Json(req): Json<SimulationRequestDto>,
-) -> Result<Json<ApiResponse<SimulationResponseDto>>, StatusCode> {
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
+    // Requests carrying a `command` are simulated against a forked ledger view
+    if body.get("command").is_some() {
+        let request: SubmitTransactionRequest = match serde_json::from_value(body) {
+            Ok(r) => r,
+            Err(e) => {
+                return Ok(Json(ApiResponse { success: false, data: None, error: Some(format!("bad request: {}", e)), error_code: Some(ErrorCode::InvalidRequest), timestamp: chrono::Utc::now() }));
+            }
+        };
+        return match simulate_command(&node, request.command).await {
+            Ok(sim) => Ok(Json(ApiResponse { success: true, data: Some(serde_json::to_value(sim).unwrap_or_default()), error: None, error_code: None, timestamp: chrono::Utc::now() })),
+            Err(e) => Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: chrono::Utc::now() })),
+        };
+    }
+
+    let req: SimulationRequestDto = match serde_json::from_value(body) {
+        Ok(r) => r,
+        Err(e) => {
+            return Ok(Json(ApiResponse { success: false, data: None, error: Some(format!("bad request: {}", e)), error_code: Some(ErrorCode::InvalidRequest), timestamp: chrono::Utc::now() }));
+        }
+    };
     let tx = match convert_simulation_request_to_tx_v2(req) {
         Ok(t) => t,
         Err(e) => {
-            return Ok(Json(ApiResponse { success: false, data: None, error: Some(format!("bad request: {}", e)), timestamp: chrono::Utc::now() })));
+            return Ok(Json(ApiResponse { success: false, data: None, error: Some(format!("bad request: {}", e)), error_code: Some(ErrorCode::InvalidRequest), timestamp: chrono::Utc::now() }));
         }
     };
     match node.simulate_transaction_v2(&tx).await {
         Ok(sim) => {
             let dto = SimulationResponseDto { accepted: sim.accepted, estimated_fee_lamports: sim.estimated_fee_lamports, logs: sim.logs };
-            Ok(Json(ApiResponse { success: true, data: Some(dto), error: None, timestamp: chrono::Utc::now() }))
+            Ok(Json(ApiResponse { success: true, data: Some(serde_json::to_value(dto).unwrap_or_default()), error: None, error_code: None, timestamp: chrono::Utc::now() }))
         }
         Err(e) => {
-            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), timestamp: chrono::Utc::now() }))
+            Ok(Json(ApiResponse { success: false, data: None, error: Some(e.to_string()), error_code: Some(e.code()), timestamp: chrono::Utc::now() }))
         }
     }
 }
+
+/// Simulate a transaction command as if submitted by this node
+async fn simulate_command(
+    node: &ParticipantNode,
+    command: TransactionCommandDto,
+) -> GarpResult<crate::simulation::SimulationResult> {
+    let command = convert_transaction_command(command)?;
+    let transaction = Transaction {
+        id: TransactionId(Uuid::new_v4()),
+        submitter: node.get_participant_id().clone(),
+        command,
+        created_at: Utc::now(),
+        signatures: Vec::new(),
+        encrypted_payload: None,
+    };
+    node.simulate_transaction(&transaction).await
+}
+
 /// Mempool submission request
 #[derive(Debug, Deserialize)]
 pub struct SubmitMempoolRequest {
@@ -2079,7 +2629,7 @@ async fn submit_mempool(
     }
 
     let resp = SubmitMempoolResponse { id: tx.id.0.to_string(), accepted: true };
-    Ok(Json(ApiResponse { success: true, data: Some(resp), error: None, timestamp: Utc::now() }))
+    Ok(Json(ApiResponse { success: true, data: Some(resp), error: None, error_code: None, timestamp: Utc::now() }))
 }
 
 /// Basic mempool stats (count only for now)
@@ -2092,5 +2642,5 @@ async fn get_mempool_stats(
     State(node): State<Arc<ParticipantNode>>,
 ) -> Result<Json<ApiResponse<MempoolStatsDto>>, StatusCode> {
     let count = node.get_mempool_batch(usize::MAX).await.len();
-    Ok(Json(ApiResponse { success: true, data: Some(MempoolStatsDto { count }), error: None, timestamp: Utc::now() }))
+    Ok(Json(ApiResponse { success: true, data: Some(MempoolStatsDto { count }), error: None, error_code: None, timestamp: Utc::now() }))
 }
\ No newline at end of file
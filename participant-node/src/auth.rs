@@ -0,0 +1,232 @@
+//! API key authentication for the participant API
+
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::{header::AUTHORIZATION, HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use ring::constant_time::verify_slices_are_equal;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::config::{ApiKeyConfig, ApiScope};
+
+/// Paths that never require authentication
+const UNAUTHENTICATED_PATHS: &[&str] = &["/health"];
+
+/// Admin-only paths outside the `/api/v1/admin` prefix
+const ADMIN_PATHS: &[&str] = &["/api/v1/node/resync"];
+
+/// Id of the API key that authenticated a request, stored in request extensions
+#[derive(Debug, Clone)]
+pub struct AuthenticatedKey(pub String);
+
+/// Participant the authenticating API key acts for, stored in request
+/// extensions when the key is bound to one
+#[derive(Debug, Clone)]
+pub struct AuthenticatedParticipant(pub String);
+
+/// Hash an API key for storage in configuration
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Scope required to access a route, or `None` if the route is public
+pub fn required_scope(method: &Method, path: &str) -> Option<ApiScope> {
+    if UNAUTHENTICATED_PATHS.contains(&path) {
+        return None;
+    }
+    if path.starts_with("/api/v1/admin") || ADMIN_PATHS.contains(&path) {
+        return Some(ApiScope::Admin);
+    }
+    if method == Method::GET || method == Method::HEAD {
+        Some(ApiScope::Read)
+    } else {
+        Some(ApiScope::Submit)
+    }
+}
+
+struct ApiKeyEntry {
+    id: String,
+    key_hash: Vec<u8>,
+    scopes: Vec<ApiScope>,
+    participant_id: Option<String>,
+}
+
+impl ApiKeyEntry {
+    fn has_scope(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&ApiScope::Admin) || self.scopes.contains(&scope)
+    }
+}
+
+/// API key verifier built from `ApiConfig::api_keys`
+pub struct ApiKeyAuth {
+    keys: Vec<ApiKeyEntry>,
+}
+
+impl ApiKeyAuth {
+    /// Build verifier from configured keys, skipping entries with malformed hashes
+    pub fn new(configs: &[ApiKeyConfig]) -> Self {
+        let keys = configs
+            .iter()
+            .filter_map(|c| match hex::decode(&c.key_hash) {
+                Ok(key_hash) => Some(ApiKeyEntry {
+                    id: c.id.clone(),
+                    key_hash,
+                    scopes: c.scopes.clone(),
+                    participant_id: c.participant_id.clone(),
+                }),
+                Err(_) => {
+                    warn!("Ignoring API key {} with malformed hash", c.id);
+                    None
+                }
+            })
+            .collect();
+        Self { keys }
+    }
+
+    /// Whether any keys are configured
+    pub fn is_enabled(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    /// Participant the key `key_id` is bound to
+    pub fn participant_of(&self, key_id: &str) -> Option<&str> {
+        self.keys.iter().find(|entry| entry.id == key_id)?.participant_id.as_deref()
+    }
+
+    /// Authorize a request. Returns the matching key id, or `None` for
+    /// public routes and when authentication is disabled.
+    pub fn authorize(&self, method: &Method, path: &str, headers: &HeaderMap) -> Result<Option<&str>, StatusCode> {
+        let scope = match required_scope(method, path) {
+            Some(scope) if self.is_enabled() => scope,
+            _ => return Ok(None),
+        };
+
+        let presented = headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        let presented_hash = Sha256::digest(presented.as_bytes());
+
+        // Compare against every key so timing does not reveal which one matched
+        let mut matched = None;
+        for entry in &self.keys {
+            if verify_slices_are_equal(&entry.key_hash, &presented_hash).is_ok() {
+                matched = Some(entry);
+            }
+        }
+
+        let entry = matched.ok_or(StatusCode::UNAUTHORIZED)?;
+        if !entry.has_scope(scope) {
+            warn!("API key {} lacks {:?} scope for {} {}", entry.id, scope, method, path);
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Ok(Some(&entry.id))
+    }
+}
+
+/// Axum middleware enforcing API key authentication
+pub async fn auth_middleware<B>(
+    State(auth): State<Arc<ApiKeyAuth>>,
+    mut req: Request<B>,
+    next: Next<B>,
+) -> Result<Response, StatusCode> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    match auth.authorize(&method, &path, req.headers()) {
+        Ok(Some(key_id)) => {
+            info!("API request {} {} by key {}", method, path, key_id);
+            if let Some(participant) = auth.participant_of(key_id) {
+                req.extensions_mut().insert(AuthenticatedParticipant(participant.to_string()));
+            }
+            let key_id = key_id.to_string();
+            req.extensions_mut().insert(AuthenticatedKey(key_id));
+        }
+        Ok(None) => {}
+        Err(code) => {
+            warn!("Rejected API request {} {} with status {}", method, path, code);
+            return Err(code);
+        }
+    }
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth() -> ApiKeyAuth {
+        let key = |id: &str, scopes: Vec<ApiScope>| ApiKeyConfig {
+            id: id.to_string(),
+            key_hash: hash_api_key(&format!("{}-secret", id)),
+            scopes,
+            participant_id: (id == "submitter").then(|| "alice".to_string()),
+        };
+        ApiKeyAuth::new(&[
+            key("reader", vec![ApiScope::Read]),
+            key("submitter", vec![ApiScope::Read, ApiScope::Submit]),
+            key("admin", vec![ApiScope::Admin]),
+        ])
+    }
+
+    fn headers(key: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(key) = key {
+            headers.insert(AUTHORIZATION, format!("Bearer {}", key).parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_health_is_exempt() {
+        let auth = auth();
+        assert_eq!(auth.authorize(&Method::GET, "/health", &headers(None)), Ok(None));
+    }
+
+    #[test]
+    fn test_missing_or_invalid_key_is_unauthorized() {
+        let auth = auth();
+        let path = "/api/v1/transactions";
+        assert_eq!(auth.authorize(&Method::GET, path, &headers(None)), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(auth.authorize(&Method::GET, path, &headers(Some("wrong"))), Err(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_scope_boundaries() {
+        let auth = auth();
+        let tx = "/api/v1/transactions";
+        let admin = "/api/v1/admin/keys";
+
+        assert_eq!(auth.authorize(&Method::GET, tx, &headers(Some("reader-secret"))), Ok(Some("reader")));
+        assert_eq!(auth.authorize(&Method::POST, tx, &headers(Some("reader-secret"))), Err(StatusCode::FORBIDDEN));
+
+        assert_eq!(auth.authorize(&Method::POST, tx, &headers(Some("submitter-secret"))), Ok(Some("submitter")));
+        assert_eq!(auth.authorize(&Method::GET, admin, &headers(Some("submitter-secret"))), Err(StatusCode::FORBIDDEN));
+
+        assert_eq!(auth.authorize(&Method::GET, admin, &headers(Some("admin-secret"))), Ok(Some("admin")));
+        assert_eq!(auth.authorize(&Method::POST, tx, &headers(Some("admin-secret"))), Ok(Some("admin")));
+
+        let resync = "/api/v1/node/resync";
+        assert_eq!(auth.authorize(&Method::POST, resync, &headers(Some("submitter-secret"))), Err(StatusCode::FORBIDDEN));
+        assert_eq!(auth.authorize(&Method::POST, resync, &headers(Some("admin-secret"))), Ok(Some("admin")));
+    }
+
+    #[test]
+    fn test_keys_may_be_bound_to_a_participant() {
+        let auth = auth();
+        assert_eq!(auth.participant_of("submitter"), Some("alice"));
+        assert_eq!(auth.participant_of("reader"), None);
+        assert_eq!(auth.participant_of("unknown"), None);
+    }
+
+    #[test]
+    fn test_no_keys_disables_auth() {
+        let auth = ApiKeyAuth::new(&[]);
+        assert_eq!(auth.authorize(&Method::POST, "/api/v1/transactions", &headers(None)), Ok(None));
+    }
+}
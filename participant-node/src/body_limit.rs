@@ -0,0 +1,237 @@
+//! Request body size limits and content types for the participant API
+//!
+//! `route_body_limit_middleware` resolves the body limit of a request's
+//! route from `ApiConfig::body_limits` into the request extensions, where
+//! `PerRouteBodyLimitLayer` picks it up: bodies declaring a larger size are
+//! rejected with 413 up front, and other bodies fail once they read past
+//! the limit. Write requests with a body must be JSON or CBOR.
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::{boxed, BoxBody, Bytes, HttpBody},
+    extract::State,
+    http::{header::{CONTENT_LENGTH, CONTENT_TYPE, TRANSFER_ENCODING}, HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use futures::future::BoxFuture;
+use http_body::Limited;
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::config::BodyLimitConfig;
+
+/// Content types accepted on write requests
+const ACCEPTED_CONTENT_TYPES: &[&str] = &["application/json", "application/cbor"];
+
+/// Body size limit of a request's route, stored in request extensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteBodyLimit(pub usize);
+
+/// Axum middleware storing the body limit of the request's route
+pub async fn route_body_limit_middleware<B>(
+    State(limits): State<Arc<BodyLimitConfig>>,
+    mut req: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let limit = limits.limit_for(req.uri().path());
+    req.extensions_mut().insert(RouteBodyLimit(limit));
+    next.run(req).await
+}
+
+/// Limits request bodies to the `RouteBodyLimit` in the request extensions,
+/// or to a default for requests without one
+#[derive(Debug, Clone, Copy)]
+pub struct PerRouteBodyLimitLayer {
+    default_limit: usize,
+}
+
+impl PerRouteBodyLimitLayer {
+    pub fn new(default_limit: usize) -> Self {
+        Self { default_limit }
+    }
+}
+
+impl<S> Layer<S> for PerRouteBodyLimitLayer {
+    type Service = PerRouteBodyLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PerRouteBodyLimit { inner, default_limit: self.default_limit }
+    }
+}
+
+/// Service applying `PerRouteBodyLimitLayer`
+#[derive(Debug, Clone)]
+pub struct PerRouteBodyLimit<S> {
+    inner: S,
+    default_limit: usize,
+}
+
+impl<S, B> Service<Request<B>> for PerRouteBodyLimit<S>
+where
+    S: Service<Request<BoxBody>, Response = Response>,
+    S::Future: Send + 'static,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let limit = req.extensions().get::<RouteBodyLimit>().map_or(self.default_limit, |l| l.0);
+        if let Some(size) = declared_body_size(&req) {
+            if size > limit as u64 {
+                warn!("Rejected {} byte body on {} {}, limit is {}", size, req.method(), req.uri().path(), limit);
+                return Box::pin(async { Ok(StatusCode::PAYLOAD_TOO_LARGE.into_response()) });
+            }
+        }
+        let req = req.map(|body| boxed(Limited::new(body, limit)));
+        Box::pin(self.inner.call(req))
+    }
+}
+
+/// Body size from the `Content-Length` header, or from the body itself
+/// when it knows its size
+fn declared_body_size<B: HttpBody>(req: &Request<B>) -> Option<u64> {
+    req.headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .or_else(|| req.body().size_hint().exact())
+}
+
+/// Whether requests with `method` must send JSON or CBOR
+pub fn requires_content_type(method: &Method) -> bool {
+    method == Method::POST || method == Method::PUT || method == Method::PATCH || method == Method::DELETE
+}
+
+/// Whether the `Content-Type` of a write request is acceptable. Parameters
+/// such as `charset` are ignored, and requests without a body need no
+/// content type.
+pub fn accepts_content_type(headers: &HeaderMap) -> bool {
+    match headers.get(CONTENT_TYPE) {
+        Some(value) => {
+            let media_type = value.to_str().unwrap_or_default().split(';').next().unwrap_or_default().trim();
+            ACCEPTED_CONTENT_TYPES.iter().any(|accepted| media_type.eq_ignore_ascii_case(accepted))
+        }
+        None => {
+            let empty = headers.get(CONTENT_LENGTH).and_then(|v| v.to_str().ok()).map_or(true, |v| v.trim() == "0");
+            empty && !headers.contains_key(TRANSFER_ENCODING)
+        }
+    }
+}
+
+/// Axum middleware rejecting write requests that are not JSON or CBOR with
+/// 415 Unsupported Media Type
+pub async fn content_type_middleware<B>(req: Request<B>, next: Next<B>) -> Response {
+    if requires_content_type(req.method()) && !accepts_content_type(req.headers()) {
+        warn!("Rejected {} {} with unsupported content type {:?}", req.method(), req.uri().path(), req.headers().get(CONTENT_TYPE));
+        return StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response();
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn router(limits: BodyLimitConfig) -> Router {
+        let default_limit = limits.default_bytes;
+        Router::new()
+            .route("/api/v1/transactions", post(|body: Bytes| async move { body.len().to_string() }))
+            .route("/api/v1/assets/:id/transfer", post(|body: Bytes| async move { body.len().to_string() }))
+            .route("/api/v1/contracts", post(|body: Bytes| async move { body.len().to_string() }))
+            .layer(PerRouteBodyLimitLayer::new(default_limit))
+            .layer(middleware::from_fn_with_state(Arc::new(limits), route_body_limit_middleware))
+    }
+
+    async fn post_body(router: &Router, path: &str, size: usize, declare_length: bool) -> StatusCode {
+        let mut request = Request::post(path).header(CONTENT_TYPE, "application/json");
+        if declare_length {
+            request = request.header(CONTENT_LENGTH, size);
+        }
+        let request = request.body(Body::from(vec![b'x'; size])).unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[test]
+    fn test_longest_prefix_limit_applies() {
+        let mut limits = BodyLimitConfig::default();
+        limits.routes.insert("/api/v1/admin/exports".to_string(), 64 * 1024 * 1024);
+
+        assert_eq!(limits.limit_for("/api/v1/transactions"), 512 * 1024);
+        assert_eq!(limits.limit_for("/api/v1/transactions/simulate"), 512 * 1024);
+        assert_eq!(limits.limit_for("/api/v1/contracts/c-1/exercise"), 2 * 1024 * 1024);
+        assert_eq!(limits.limit_for("/api/v1/assets/a-1/transfer"), 256 * 1024);
+        assert_eq!(limits.limit_for("/api/v1/admin/templates/t-1/schema"), 10 * 1024 * 1024);
+        assert_eq!(limits.limit_for("/api/v1/admin/exports"), 64 * 1024 * 1024);
+        // Prefixes match whole path segments only
+        assert_eq!(limits.limit_for("/api/v1/assetsx"), 1024 * 1024);
+        assert_eq!(limits.limit_for("/rpc"), 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_route_limits_are_enforced_independently() {
+        let router = router(BodyLimitConfig::default());
+        let transfer = "/api/v1/assets/a-1/transfer";
+
+        // 300 KB fits transactions and contracts but not asset transfers
+        let size = 300 * 1024;
+        assert_eq!(post_body(&router, "/api/v1/transactions", size, true).await, StatusCode::OK);
+        assert_eq!(post_body(&router, "/api/v1/contracts", size, true).await, StatusCode::OK);
+        assert_eq!(post_body(&router, transfer, size, true).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+        // 1.5 MB fits only contracts, with or without a Content-Length header
+        let size = 1536 * 1024;
+        for declare_length in [true, false] {
+            assert_eq!(post_body(&router, "/api/v1/contracts", size, declare_length).await, StatusCode::OK);
+            assert_eq!(post_body(&router, "/api/v1/transactions", size, declare_length).await, StatusCode::PAYLOAD_TOO_LARGE);
+        }
+
+        // Raising one route's limit leaves the others alone
+        let mut limits = BodyLimitConfig::default();
+        limits.routes.insert("/api/v1/assets".to_string(), 1024 * 1024);
+        let router = self::router(limits);
+        assert_eq!(post_body(&router, transfer, 300 * 1024, true).await, StatusCode::OK);
+        assert_eq!(post_body(&router, "/api/v1/transactions", 600 * 1024, true).await, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_write_requests_must_be_json_or_cbor() {
+        let headers = |content_type: Option<&str>, length: Option<&str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(content_type) = content_type {
+                headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+            }
+            if let Some(length) = length {
+                headers.insert(CONTENT_LENGTH, length.parse().unwrap());
+            }
+            headers
+        };
+
+        assert!(accepts_content_type(&headers(Some("application/json"), Some("2"))));
+        assert!(accepts_content_type(&headers(Some("application/json; charset=utf-8"), Some("2"))));
+        assert!(accepts_content_type(&headers(Some("Application/CBOR"), Some("2"))));
+        assert!(!accepts_content_type(&headers(Some("text/plain"), Some("2"))));
+        assert!(!accepts_content_type(&headers(Some("application/x-www-form-urlencoded"), Some("2"))));
+        assert!(!accepts_content_type(&headers(None, Some("2"))));
+        // Bodiless writes, such as archiving a contract
+        assert!(accepts_content_type(&headers(None, None)));
+        assert!(accepts_content_type(&headers(None, Some("0"))));
+
+        assert!(requires_content_type(&Method::POST));
+        assert!(requires_content_type(&Method::PUT));
+        assert!(requires_content_type(&Method::DELETE));
+        assert!(!requires_content_type(&Method::GET));
+    }
+}
@@ -1,7 +1,10 @@
 use garp_common::{ParticipantConfig, ParticipantId, SyncDomainId, GarpResult, GarpError, GenesisConfig, ChainParams};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
+use crate::contract_stdlib::GasSchedule;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub participant_config: ParticipantConfig,
@@ -14,6 +17,14 @@ pub struct Config {
     pub genesis: GenesisConfig,
     #[serde(default)]
     pub chain: ChainParams,
+    #[serde(default)]
+    pub execution: ExecutionLimits,
+    /// Steps charged per contract operation and the fee per step. Unlike
+    /// execution limits, templates cannot override it.
+    #[serde(default)]
+    pub gas: GasSchedule,
+    #[serde(default)]
+    pub event_retention: EventRetentionConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,7 +40,84 @@ pub struct ApiConfig {
     pub host: String,
     pub port: u16,
     pub cors_origins: Vec<String>,
+    /// Rate limit for read (GET) requests
     pub rate_limit: RateLimitConfig,
+    /// Rate limit for write requests; falls back to `rate_limit` when unset
+    #[serde(default)]
+    pub write_rate_limit: Option<RateLimitConfig>,
+    /// API keys accepted by the server; authentication is disabled when empty
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// HMAC key used to sign write responses; signing is disabled when unset
+    #[serde(default)]
+    pub response_signing_key: Option<String>,
+    /// Request body size limits, per route
+    #[serde(default)]
+    pub body_limits: BodyLimitConfig,
+}
+
+/// Request body size limits. A route takes the limit of the longest path
+/// prefix in `routes` it starts with, or `default_bytes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyLimitConfig {
+    pub default_bytes: usize,
+    #[serde(default)]
+    pub routes: HashMap<String, usize>,
+}
+
+impl BodyLimitConfig {
+    /// Body size limit for `path`
+    pub fn limit_for(&self, path: &str) -> usize {
+        self.routes
+            .iter()
+            .filter(|(prefix, _)| {
+                path.strip_prefix(prefix.as_str())
+                    .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, limit)| *limit)
+            .unwrap_or(self.default_bytes)
+    }
+}
+
+impl Default for BodyLimitConfig {
+    fn default() -> Self {
+        let routes = [
+            ("/api/v1/transactions", 512 * 1024),
+            ("/api/v1/contracts", 2 * 1024 * 1024),
+            ("/api/v1/assets", 256 * 1024),
+            ("/api/v1/admin", 10 * 1024 * 1024),
+        ];
+        Self {
+            default_bytes: 1024 * 1024,
+            routes: routes.into_iter().map(|(prefix, limit)| (prefix.to_string(), limit)).collect(),
+        }
+    }
+}
+
+/// API key definition. Only the SHA-256 hash of the key is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Identifier used in logs
+    pub id: String,
+    /// Hex-encoded SHA-256 hash of the key
+    pub key_hash: String,
+    pub scopes: Vec<ApiScope>,
+    /// Participant the key acts for; limits what `/ws/ledger` streams to it
+    #[serde(default)]
+    pub participant_id: Option<String>,
+}
+
+/// Access scope granted to an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiScope {
+    /// Read-only (GET) endpoints
+    Read,
+    /// Transaction, contract and asset submission
+    Submit,
+    /// Node management; implies all other scopes
+    Admin,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +152,49 @@ pub struct NetworkConfig {
     pub tls_key_file: Option<String>,
 }
 
+/// Budgets for a single contract execution, including nested calls.
+/// Templates may override these with their own limits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ExecutionLimits {
+    /// Metering steps available to the execution
+    pub step_budget: u64,
+    /// Wall-clock ceiling in milliseconds; 0 disables the ceiling
+    pub max_execution_ms: u64,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            step_budget: 1_000_000,
+            max_execution_ms: 5_000,
+        }
+    }
+}
+
+/// How long contract events are kept. Events are pruned oldest sequence
+/// first, so the retained log is always contiguous.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRetentionConfig {
+    /// Prune events older than this many seconds; unset keeps them regardless of age
+    #[serde(default)]
+    pub max_age_secs: Option<u64>,
+    /// Keep at most this many of the newest events; unset keeps them regardless of count
+    #[serde(default)]
+    pub max_events: Option<u64>,
+    /// Interval between pruning passes
+    pub prune_interval_secs: u64,
+}
+
+impl Default for EventRetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age_secs: None,
+            max_events: None,
+            prune_interval_secs: 300,
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from file with optional overrides
     pub fn load(
@@ -169,6 +300,13 @@ impl Config {
         if self.chain.rotation_interval_slots == 0 {
             return Err(GarpError::Config("rotation_interval_slots must be > 0".to_string()));
         }
+        if self.execution.step_budget == 0 {
+            return Err(GarpError::Config("execution.step_budget must be > 0".to_string()));
+        }
+
+        if self.event_retention.prune_interval_secs == 0 {
+            return Err(GarpError::Config("event_retention.prune_interval_secs must be > 0".to_string()));
+        }
 
         Ok(())
     }
@@ -198,6 +336,13 @@ impl Default for Config {
                     requests_per_minute: 100,
                     burst_size: 10,
                 },
+                write_rate_limit: Some(RateLimitConfig {
+                    requests_per_minute: 30,
+                    burst_size: 5,
+                }),
+                api_keys: Vec::new(),
+                response_signing_key: None,
+                body_limits: BodyLimitConfig::default(),
             },
             sync_domains: vec![
                 SyncDomainConfig {
@@ -233,6 +378,9 @@ impl Default for Config {
                 randomness_beacon: None,
                 rotation_interval_slots: 60,
             },
+            execution: ExecutionLimits::default(),
+            gas: GasSchedule::default(),
+            event_retention: EventRetentionConfig::default(),
         }
     }
 }
\ No newline at end of file
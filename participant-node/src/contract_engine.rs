@@ -3,9 +3,12 @@ use garp_common::{
     CreateContractCommand, ExerciseContractCommand, ArchiveContractCommand,
     CryptoService, GarpResult, GarpError, ContractError
 };
+use crate::config::ExecutionLimits;
 use crate::storage::StorageBackend;
+use crate::contract_stdlib::{self, ContractInvoker, GasSchedule, StdlibContext, StepMeter, DEFAULT_MAX_CALL_DEPTH};
 use crate::wasm_runtime::{WasmRuntime, WasmExecutionResult, WasmHostFunctions};
 use crate::contract_state::ContractStateManager; // Add this import
+use crate::contract_schema::{SchemaViolation, TemplateSchemas};
 use std::sync::Arc;
 use std::collections::HashMap;
 use serde_json::{Value, Map};
@@ -14,12 +17,16 @@ use tracing::{info, warn, error, debug};
 use uuid::Uuid;
 
 /// Contract execution engine
+#[derive(Clone)]
 pub struct ContractEngine {
     storage: Arc<dyn StorageBackend>,
     crypto_service: Arc<CryptoService>,
     template_registry: Arc<TemplateRegistry>,
     wasm_runtime: Arc<WasmRuntime>,
     contract_state_manager: Arc<ContractStateManager>, // Add contract state manager
+    max_call_depth: usize,
+    execution_limits: ExecutionLimits,
+    gas_schedule: GasSchedule,
 }
 
 /// Contract template registry
@@ -40,6 +47,10 @@ pub struct ContractTemplate {
     pub privacy_settings: PrivacySettings,
     pub wasm_bytecode: Option<Vec<u8>>, // Optional WASM bytecode for smart contracts
     pub upgrade_policy: UpgradePolicy, // Contract upgrade policy
+    /// Overrides the node's execution limits for this template's choices
+    pub execution_limits: Option<ExecutionLimits>,
+    /// Argument schemas checked before create/exercise transactions are accepted
+    pub schemas: TemplateSchemas,
 }
 
 /// Template parameter definition
@@ -72,6 +83,8 @@ pub struct TemplateChoice {
     pub parameters: Vec<TemplateParameter>,
     pub authorization: AuthorizationRule,
     pub effects: Vec<ContractEffect>,
+    /// Callable from other contracts by parties that cannot see the contract
+    pub public: bool,
 }
 
 /// Authorization rules for contract choices
@@ -107,6 +120,11 @@ pub enum ContractEffect {
         function_name: String,
         arguments: Value,
     },
+    CallContract {
+        contract_id: String, // Parameter name or literal contract ID
+        choice: String,
+        argument: Value,
+    },
 }
 
 /// Validation rules for contract parameters
@@ -172,6 +190,8 @@ pub struct ExecutionResult {
     pub archived_contracts: Vec<ContractId>,
     pub events: Vec<ContractEvent>,
     pub upgraded_contracts: Vec<ContractId>, // Contracts that were upgraded
+    /// Metering steps consumed, including nested calls
+    pub gas_used: u64,
 }
 
 /// Executed effect
@@ -217,12 +237,61 @@ impl ContractEngine {
             template_registry,
             wasm_runtime,
             contract_state_manager, // Add contract state manager
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            execution_limits: ExecutionLimits::default(),
+            gas_schedule: GasSchedule::default(),
         };
         
         engine.register_builtin_templates();
         engine
     }
 
+    /// Create an engine over a different storage backend, sharing the template registry.
+    /// Used to execute choices against a forked ledger view.
+    pub fn fork(&self, storage: Arc<dyn StorageBackend>) -> Self {
+        let wasm_runtime = Arc::new(WasmRuntime::new(
+            storage.clone(),
+            self.crypto_service.clone(),
+            1_000_000,
+            1024 * 1024
+        ));
+        let contract_state_manager = Arc::new(ContractStateManager::new(storage.clone()));
+
+        Self {
+            storage,
+            crypto_service: self.crypto_service.clone(),
+            template_registry: self.template_registry.clone(),
+            wasm_runtime,
+            contract_state_manager,
+            max_call_depth: self.max_call_depth,
+            execution_limits: self.execution_limits,
+            gas_schedule: self.gas_schedule,
+        }
+    }
+
+    /// Set the maximum depth of nested cross-contract calls
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Set the default execution limits for templates without their own
+    pub fn with_execution_limits(mut self, execution_limits: ExecutionLimits) -> Self {
+        self.execution_limits = execution_limits;
+        self
+    }
+
+    /// Set the steps charged per operation
+    pub fn with_gas_schedule(mut self, gas_schedule: GasSchedule) -> Self {
+        self.gas_schedule = gas_schedule;
+        self
+    }
+
+    /// Steps charged per operation, and the fee per step
+    pub fn gas_schedule(&self) -> &GasSchedule {
+        &self.gas_schedule
+    }
+
     /// Execute a contract choice
     pub async fn execute_contract(
         &self,
@@ -266,7 +335,8 @@ impl ContractEngine {
         self.validate_choice_arguments(&context, choice)?;
 
         // Execute effects
-        let result = self.execute_effects(&context, choice).await?;
+        let limits = template.execution_limits.unwrap_or(self.execution_limits);
+        let result = self.execute_effects(&context, choice, &limits).await?;
 
         info!("Contract execution completed: {} effects applied", result.effects.len());
         Ok(result)
@@ -351,6 +421,38 @@ impl ContractEngine {
         self.template_registry.register_template(template)
     }
 
+    /// Replace the argument schemas of a registered template
+    pub fn update_template_schemas(&self, template_id: &str, schemas: TemplateSchemas) -> GarpResult<()> {
+        self.template_registry.update_schemas(template_id, schemas)
+    }
+
+    /// Check a command's argument against its template schema.
+    ///
+    /// Unknown templates and choices are errors; schema mismatches are returned
+    /// as violations so callers can report every failing path at once.
+    pub async fn validate_command_arguments(&self, command: &TransactionCommand) -> GarpResult<Vec<SchemaViolation>> {
+        match command {
+            TransactionCommand::CreateContract(cmd) => {
+                let template = self.template_registry.get_template(&cmd.template_id)?;
+                Ok(template.schemas.argument
+                    .map(|schema| schema.validate(&cmd.argument))
+                    .unwrap_or_default())
+            }
+            TransactionCommand::ExerciseContract(cmd) => {
+                let contract = self.storage.get_contract(&cmd.contract_id).await?
+                    .ok_or_else(|| ContractError::ContractNotFound(cmd.contract_id.clone()))?;
+                let template = self.template_registry.get_template(&contract.template_id)?;
+                if !template.choices.iter().any(|c| c.name == cmd.choice) {
+                    return Err(ContractError::ChoiceNotFound(cmd.choice.clone()).into());
+                }
+                Ok(template.schemas.choices.get(&cmd.choice)
+                    .map(|schema| schema.validate(&cmd.argument))
+                    .unwrap_or_default())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
     /// Deploy a new contract from WASM bytecode
     pub async fn deploy_contract(
         &self,
@@ -383,6 +485,8 @@ impl ContractEngine {
             },
             wasm_bytecode: Some(wasm_bytecode.clone()),
             upgrade_policy: UpgradePolicy::Signatory, // Allow signatories to upgrade
+            execution_limits: None,
+            schemas: TemplateSchemas::default(),
         };
 
         // Register the template
@@ -682,6 +786,7 @@ impl ContractEngine {
         &self,
         context: &ExecutionContext,
         choice: &TemplateChoice,
+        limits: &ExecutionLimits,
     ) -> GarpResult<ExecutionResult> {
         let mut result = ExecutionResult {
             success: true,
@@ -692,10 +797,24 @@ impl ContractEngine {
             archived_contracts: Vec::new(),
             events: Vec::new(),
             upgraded_contracts: Vec::new(),
+            gas_used: 0,
         };
 
+        // Load the contract's persisted storage for stdlib-backed effects
+        let mut stdlib = StdlibContext::load(
+            self.storage.as_ref(),
+            &context.contract.id,
+            context.executor.0.clone(),
+            context.timestamp.timestamp(),
+            0,
+        ).await?
+        .with_invoker(Arc::new(self.clone()))
+        .with_max_call_depth(self.max_call_depth)
+        .with_meter(StepMeter::from_limits(limits))
+        .with_gas_schedule(self.gas_schedule);
+
         for effect in &choice.effects {
-            match self.execute_single_effect(context, effect).await {
+            match self.execute_single_effect(context, effect, &mut stdlib).await {
                 Ok(executed_effect) => {
                     result.effects.push(executed_effect);
                 }
@@ -704,6 +823,24 @@ impl ContractEngine {
                     result.errors.push(e.to_string());
                 }
             }
+
+            // An aborted execution runs no further effects
+            if stdlib.aborted().is_some() {
+                break;
+            }
+        }
+
+        if let Some(reason) = stdlib.aborted() {
+            result.success = false;
+            if !result.errors.iter().any(|e| e.contains(reason)) {
+                result.errors.push(reason.to_string());
+            }
+        }
+        result.gas_used = stdlib.meter.used();
+
+        // Storage writes and events from a failed execution are discarded
+        if result.success {
+            stdlib.commit(self.storage.as_ref(), &context.contract.id, &context.executor).await?;
         }
 
         Ok(result)
@@ -714,7 +851,12 @@ impl ContractEngine {
         &self,
         context: &ExecutionContext,
         effect: &ContractEffect,
+        stdlib: &mut StdlibContext,
     ) -> GarpResult<ExecutedEffect> {
+        if let Err(exhausted) = stdlib.charge(stdlib.gas_schedule.effect) {
+            return Err(ContractError::ExecutionFailed(exhausted.error.unwrap_or_default()).into());
+        }
+
         match effect {
             ContractEffect::CreateContract(template_id) => {
                 // This would create a new contract based on the template
@@ -746,7 +888,10 @@ impl ContractEngine {
                 })
             }
             ContractEffect::UpdateContractData { field, value } => {
-                // This would update contract data
+                let stored = contract_stdlib::storage::set(stdlib, field.clone(), value.clone());
+                if !stored.success {
+                    return Err(ContractError::ExecutionFailed(stored.error.unwrap_or_default()).into());
+                }
                 Ok(ExecutedEffect {
                     effect_type: "UpdateContractData".to_string(),
                     description: format!("Updated field {}", field),
@@ -757,19 +902,9 @@ impl ContractEngine {
                 })
             }
             ContractEffect::EmitEvent { event_type, data } => {
-                // Emit a contract event and store it
-                let event = ContractEvent {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    contract_id: context.contract.id.clone(),
-                    event_type: event_type.clone(),
-                    data: data.clone(),
-                    timestamp: chrono::Utc::now(),
-                    emitter: context.executor.clone(),
-                };
-                
-                // Store the event
-                self.storage.store_contract_event(&event).await?;
-                
+                // Stored in the event store when the execution commits
+                contract_stdlib::events::emit(stdlib, event_type.clone(), data.clone());
+
                 Ok(ExecutedEffect {
                     effect_type: "EmitEvent".to_string(),
                     description: format!("Emitted event {}", event_type),
@@ -818,6 +953,29 @@ impl ContractEngine {
                     }),
                 })
             }
+            ContractEffect::CallContract { contract_id, choice, argument } => {
+                let target = context.choice_arguments.get(contract_id)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(contract_id);
+                let target_id = Uuid::parse_str(target)
+                    .map(ContractId)
+                    .map_err(|_| ContractError::ExecutionFailed(format!("Invalid contract ID {}", target)))?;
+
+                let called = contract_stdlib::calls::call_contract(stdlib, &target_id, choice, argument.clone()).await;
+                if !called.success {
+                    return Err(ContractError::ExecutionFailed(called.error.unwrap_or_default()).into());
+                }
+
+                Ok(ExecutedEffect {
+                    effect_type: "CallContract".to_string(),
+                    description: format!("Called {} on contract {}", choice, target_id.0),
+                    data: serde_json::json!({
+                        "contract_id": target_id.0,
+                        "choice": choice,
+                        "result": called.value,
+                    }),
+                })
+            }
         }
     }
 
@@ -874,6 +1032,7 @@ impl ContractEngine {
                             asset: "payment".to_string(),
                         }
                     ],
+                    public: false,
                 },
                 TemplateChoice {
                     name: "ship_product".to_string(),
@@ -888,6 +1047,7 @@ impl ContractEngine {
                             value: serde_json::json!("shipped"),
                         }
                     ],
+                    public: false,
                 },
                 TemplateChoice {
                     name: "complete_purchase".to_string(),
@@ -907,6 +1067,7 @@ impl ContractEngine {
                         },
                         ContractEffect::ArchiveContract,
                     ],
+                    public: false,
                 },
             ],
             validation_rules: vec![
@@ -924,6 +1085,8 @@ impl ContractEngine {
             },
             wasm_bytecode: None,
             upgrade_policy: UpgradePolicy::None,
+            execution_limits: None,
+            schemas: TemplateSchemas::default(),
         };
 
         let _ = self.register_template(purchase_template);
@@ -932,6 +1095,56 @@ impl ContractEngine {
     }
 }
 
+#[async_trait::async_trait]
+impl ContractInvoker for ContractEngine {
+    async fn get_contract(&self, contract_id: &ContractId) -> GarpResult<Option<Contract>> {
+        self.storage.get_contract(contract_id).await
+    }
+
+    async fn load_storage(&self, contract_id: &ContractId) -> GarpResult<HashMap<String, Value>> {
+        self.storage.get_contract_storage(contract_id).await
+    }
+
+    fn is_public_choice(&self, contract: &Contract, choice: &str) -> bool {
+        self.template_registry.get_template(&contract.template_id)
+            .map(|template| template.choices.iter().any(|c| c.name == choice && c.public))
+            .unwrap_or(false)
+    }
+
+    async fn execute_choice(
+        &self,
+        ctx: &mut StdlibContext,
+        contract: &Contract,
+        choice_name: &str,
+        argument: Value,
+    ) -> GarpResult<Value> {
+        let template = self.template_registry.get_template(&contract.template_id)?;
+        let choice = template.choices.iter()
+            .find(|c| c.name == choice_name)
+            .ok_or_else(|| ContractError::ChoiceNotFound(choice_name.to_string()))?;
+
+        let context = ExecutionContext {
+            contract: contract.clone(),
+            choice_name: choice_name.to_string(),
+            choice_arguments: argument,
+            executor: ParticipantId(ctx.caller.clone()),
+            timestamp: Utc::now(),
+        };
+
+        if !choice.public {
+            self.validate_authorization(&context, choice)?;
+        }
+        self.validate_choice_arguments(&context, choice)?;
+
+        let mut results = Vec::new();
+        for effect in &choice.effects {
+            let executed = self.execute_single_effect(&context, effect, ctx).await?;
+            results.push(executed.data);
+        }
+        Ok(Value::Array(results))
+    }
+}
+
 impl TemplateRegistry {
     /// Create new template registry
     pub fn new() -> Self {
@@ -960,4 +1173,18 @@ impl TemplateRegistry {
         let templates = self.templates.read();
         templates.values().cloned().collect()
     }
+
+    /// Replace a template's argument schemas; every choice schema must name an existing choice
+    pub fn update_schemas(&self, template_id: &str, schemas: TemplateSchemas) -> GarpResult<()> {
+        let mut templates = self.templates.write();
+        let template = templates.get_mut(template_id)
+            .ok_or_else(|| ContractError::TemplateNotFound(template_id.to_string()))?;
+
+        if let Some(unknown) = schemas.choices.keys().find(|name| !template.choices.iter().any(|c| &c.name == *name)) {
+            return Err(ContractError::ChoiceNotFound(unknown.clone()).into());
+        }
+
+        template.schemas = schemas;
+        Ok(())
+    }
 }
\ No newline at end of file
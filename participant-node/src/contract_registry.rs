@@ -0,0 +1,217 @@
+//! Versioned registry of contract templates
+//!
+//! Each version pairs a template ID with the argument schema contracts are
+//! created against. Versions are immutable once registered and are persisted
+//! in node metadata under `template:{id}:{version}`.
+
+use chrono::{DateTime, Utc};
+use garp_common::{GarpError, GarpResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::contract_schema::{ArgumentSchema, SchemaViolation};
+use crate::storage::StorageBackend;
+
+const KEY_PREFIX: &str = "template:";
+
+/// A registered template version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Template {
+    pub template_id: String,
+    pub version: u32,
+    pub schema: ArgumentSchema,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Reasons an argument was rejected for a template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationErrors {
+    pub template_id: String,
+    pub version: Option<u32>,
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.violations.iter().map(|v| v.to_string()).collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+/// Template versions backed by node metadata storage
+pub struct TemplateRegistry {
+    storage: Arc<dyn StorageBackend>,
+    templates: parking_lot::RwLock<HashMap<String, BTreeMap<u32, Template>>>,
+}
+
+fn metadata_key(template_id: &str, version: u32) -> String {
+    format!("{}{}:{}", KEY_PREFIX, template_id, version)
+}
+
+impl TemplateRegistry {
+    pub fn new(storage: Arc<dyn StorageBackend>) -> Self {
+        Self {
+            storage,
+            templates: parking_lot::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Load persisted template versions, returning how many were loaded
+    pub async fn load(&self) -> GarpResult<usize> {
+        let entries = self.storage.list_metadata(KEY_PREFIX).await?;
+        let mut templates = self.templates.write();
+        let mut loaded = 0;
+        for (key, value) in entries {
+            let template: Template = serde_json::from_value(value)
+                .map_err(|e| GarpError::SerializationError(format!("Invalid template record {}: {}", key, e)))?;
+            templates
+                .entry(template.template_id.clone())
+                .or_default()
+                .insert(template.version, template);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Register a new template version; existing versions cannot be replaced
+    pub async fn register_template(&self, template_id: &str, version: u32, schema: ArgumentSchema) -> GarpResult<Template> {
+        if template_id.is_empty() || template_id.contains(':') {
+            return Err(GarpError::ValidationError(format!("Invalid template id: {:?}", template_id)));
+        }
+        if version == 0 {
+            return Err(GarpError::ValidationError("Template versions start at 1".to_string()));
+        }
+        if self.get_template(template_id, Some(version)).is_some() {
+            return Err(GarpError::ValidationError(format!(
+                "Template {} version {} is already registered",
+                template_id, version
+            )));
+        }
+
+        let template = Template {
+            template_id: template_id.to_string(),
+            version,
+            schema,
+            registered_at: Utc::now(),
+        };
+        let value = serde_json::to_value(&template).map_err(|e| GarpError::SerializationError(e.to_string()))?;
+        self.storage.put_metadata(&metadata_key(template_id, version), &value).await?;
+
+        self.templates
+            .write()
+            .entry(template.template_id.clone())
+            .or_default()
+            .insert(version, template.clone());
+        Ok(template)
+    }
+
+    /// A template version, or the latest version when `version` is `None`
+    pub fn get_template(&self, template_id: &str, version: Option<u32>) -> Option<Template> {
+        let templates = self.templates.read();
+        let versions = templates.get(template_id)?;
+        match version {
+            Some(v) => versions.get(&v).cloned(),
+            None => versions.values().next_back().cloned(),
+        }
+    }
+
+    /// Every version of a template, oldest first
+    pub fn get_versions(&self, template_id: &str) -> Vec<Template> {
+        self.templates
+            .read()
+            .get(template_id)
+            .map(|versions| versions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The latest version of every template, ordered by ID
+    pub fn list_templates(&self) -> Vec<Template> {
+        let templates = self.templates.read();
+        let mut latest: Vec<Template> = templates
+            .values()
+            .filter_map(|versions| versions.values().next_back().cloned())
+            .collect();
+        latest.sort_by(|a, b| a.template_id.cmp(&b.template_id));
+        latest
+    }
+
+    /// Check `arg` against a template version (the latest when `version` is `None`)
+    pub fn validate_argument(&self, template_id: &str, version: Option<u32>, arg: &Value) -> Result<(), ValidationErrors> {
+        let template = self.get_template(template_id, version).ok_or_else(|| ValidationErrors {
+            template_id: template_id.to_string(),
+            version,
+            violations: vec![SchemaViolation {
+                path: "$".to_string(),
+                message: match version {
+                    Some(v) => format!("template {} version {} is not registered", template_id, v),
+                    None => format!("template {} is not registered", template_id),
+                },
+            }],
+        })?;
+
+        let violations = template.schema.validate(arg);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors {
+                template_id: template_id.to_string(),
+                version: Some(template.version),
+                violations,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use serde_json::json;
+
+    fn schema(required: &[&str]) -> ArgumentSchema {
+        serde_json::from_value(json!({
+            "type": "object",
+            "properties": { "amount": { "type": "integer", "minimum": 0 }, "memo": { "type": "string" } },
+            "required": required,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_versions_are_immutable_and_latest_wins() {
+        let storage = Arc::new(MemoryStorage::new());
+        let registry = TemplateRegistry::new(storage.clone());
+
+        registry.register_template("iou", 1, schema(&["amount"])).await.unwrap();
+        registry.register_template("iou", 2, schema(&["amount", "memo"])).await.unwrap();
+        assert!(registry.register_template("iou", 2, schema(&[])).await.is_err());
+        assert!(registry.register_template("bad:id", 1, schema(&[])).await.is_err());
+
+        assert_eq!(registry.get_template("iou", None).unwrap().version, 2);
+        assert_eq!(registry.get_versions("iou").len(), 2);
+        assert!(storage.get_metadata("template:iou:1").await.unwrap().is_some());
+
+        let arg = json!({ "amount": 5 });
+        assert!(registry.validate_argument("iou", Some(1), &arg).is_ok());
+        let errors = registry.validate_argument("iou", None, &arg).unwrap_err();
+        assert_eq!(errors.version, Some(2));
+        assert_eq!(errors.violations.len(), 1);
+        assert!(registry.validate_argument("unknown", None, &arg).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_restores_persisted_versions() {
+        let storage = Arc::new(MemoryStorage::new());
+        TemplateRegistry::new(storage.clone())
+            .register_template("iou", 1, schema(&["amount"]))
+            .await
+            .unwrap();
+
+        let registry = TemplateRegistry::new(storage);
+        assert_eq!(registry.load().await.unwrap(), 1);
+        assert_eq!(registry.list_templates()[0].template_id, "iou");
+    }
+}
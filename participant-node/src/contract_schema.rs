@@ -0,0 +1,237 @@
+//! Lightweight argument schemas for contract templates
+//!
+//! Schemas use a subset of JSON Schema (`type`, `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`, `minimum`, `maximum`, `minLength`,
+//! `maxLength`) so they can be authored with ordinary JSON Schema tooling.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// JSON value type accepted by a schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl SchemaType {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            SchemaType::Null => value.is_null(),
+            SchemaType::Boolean => value.is_boolean(),
+            SchemaType::Integer => value.is_i64() || value.is_u64(),
+            SchemaType::Number => value.is_number(),
+            SchemaType::String => value.is_string(),
+            SchemaType::Array => value.is_array(),
+            SchemaType::Object => value.is_object(),
+        }
+    }
+}
+
+/// Schema for a contract or choice argument
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentSchema {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<SchemaType>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, ArgumentSchema>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+
+    /// Whether object properties not listed in `properties` are allowed
+    #[serde(rename = "additionalProperties", default = "default_additional_properties")]
+    pub additional_properties: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<ArgumentSchema>>,
+
+    #[serde(rename = "enum", default, skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<Value>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+
+    #[serde(rename = "minLength", default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+
+    #[serde(rename = "maxLength", default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+fn default_additional_properties() -> bool {
+    true
+}
+
+/// Schemas attached to a template: one for the create argument and one per choice
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSchemas {
+    #[serde(default)]
+    pub argument: Option<ArgumentSchema>,
+
+    #[serde(default)]
+    pub choices: HashMap<String, ArgumentSchema>,
+}
+
+/// A single schema violation, located by a `$`-rooted path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl ArgumentSchema {
+    /// Validate a value, collecting every violation rather than stopping at the first
+    pub fn validate(&self, value: &Value) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        self.validate_at("$", value, &mut violations);
+        violations
+    }
+
+    fn validate_at(&self, path: &str, value: &Value, violations: &mut Vec<SchemaViolation>) {
+        let mut violation = |message: String| violations.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        });
+
+        if let Some(expected) = self.schema_type {
+            if !expected.matches(value) {
+                violation(format!("expected {:?}, found {}", expected, type_name(value)).to_lowercase());
+                return;
+            }
+        }
+
+        if let Some(allowed) = &self.enum_values {
+            if !allowed.contains(value) {
+                violation(format!("value {} is not one of the allowed values", value));
+            }
+        }
+
+        if let Some(n) = value.as_f64() {
+            if let Some(min) = self.minimum.filter(|min| n < *min) {
+                violation(format!("must be >= {}", min));
+            }
+            if let Some(max) = self.maximum.filter(|max| n > *max) {
+                violation(format!("must be <= {}", max));
+            }
+        }
+
+        if let Some(s) = value.as_str() {
+            let len = s.chars().count();
+            if let Some(min) = self.min_length.filter(|min| len < *min) {
+                violation(format!("length must be >= {}", min));
+            }
+            if let Some(max) = self.max_length.filter(|max| len > *max) {
+                violation(format!("length must be <= {}", max));
+            }
+        }
+
+        if let Some(object) = value.as_object() {
+            for name in &self.required {
+                if !object.contains_key(name) {
+                    violations.push(SchemaViolation {
+                        path: format!("{}.{}", path, name),
+                        message: "required property is missing".to_string(),
+                    });
+                }
+            }
+            for (name, field) in object {
+                let field_path = format!("{}.{}", path, name);
+                match self.properties.get(name) {
+                    Some(schema) => schema.validate_at(&field_path, field, violations),
+                    None if !self.additional_properties => violations.push(SchemaViolation {
+                        path: field_path,
+                        message: "unknown property".to_string(),
+                    }),
+                    None => {}
+                }
+            }
+        }
+
+        if let (Some(items), Some(array)) = (&self.items, value.as_array()) {
+            for (i, item) in array.iter().enumerate() {
+                items.validate_at(&format!("{}[{}]", path, i), item, violations);
+            }
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn purchase_schema() -> ArgumentSchema {
+        serde_json::from_value(json!({
+            "type": "object",
+            "required": ["buyer", "price"],
+            "additionalProperties": false,
+            "properties": {
+                "buyer": { "type": "string", "minLength": 1 },
+                "price": { "type": "number", "minimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_valid_argument_passes() {
+        let violations = purchase_schema().validate(&json!({
+            "buyer": "alice",
+            "price": 10.5,
+            "tags": ["a", "b"]
+        }));
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_violations_report_paths() {
+        let violations = purchase_schema().validate(&json!({
+            "byuer": "alice",
+            "price": -1,
+            "tags": ["a", 2]
+        }));
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert!(paths.contains(&"$.buyer"));
+        assert!(paths.contains(&"$.byuer"));
+        assert!(paths.contains(&"$.price"));
+        assert!(paths.contains(&"$.tags[1]"));
+        assert_eq!(violations.len(), 4);
+    }
+
+    #[test]
+    fn test_type_mismatch_stops_descent() {
+        let violations = purchase_schema().validate(&json!("not an object"));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "$");
+    }
+}
@@ -3,9 +3,263 @@
 //! This module provides a set of standard functions and utilities that can be used
 //! by smart contracts running on the GARP blockchain.
 
+use async_trait::async_trait;
+use garp_common::{Contract, ContractId, GarpResult, ParticipantId};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::config::ExecutionLimits;
+use crate::storage::{ContractEvent, ContractStorageDiff, StorageBackend};
+
+/// Default maximum number of keys a contract may store
+pub const DEFAULT_MAX_STORAGE_KEYS: usize = 1024;
+
+/// Default maximum serialized size of a single stored value, in bytes
+pub const DEFAULT_MAX_STORAGE_VALUE_SIZE: usize = 64 * 1024;
+
+/// Default maximum depth of nested cross-contract calls
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 4;
+
+/// Error returned once an execution has used up its step budget
+pub const OUT_OF_GAS: &str = "out of gas";
+
+/// Error returned once an execution has run past its wall-clock ceiling
+pub const EXECUTION_TIMEOUT: &str = "execution time limit exceeded";
+
+/// Steps charged for any stdlib call, on top of its size-dependent cost
+pub const BASE_STEPS: u64 = 1;
+
+/// Steps charged per started 32-byte word of input
+pub const WORD_STEPS: u64 = 1;
+
+/// Steps charged per contract effect executed by the engine
+pub const EFFECT_STEPS: u64 = 10;
+
+/// Steps charged per storage read
+pub const STORAGE_READ_STEPS: u64 = 20;
+
+/// Steps charged per storage write or delete
+pub const STORAGE_WRITE_STEPS: u64 = 100;
+
+/// Steps charged per emitted event
+pub const EVENT_STEPS: u64 = 50;
+
+/// Steps charged per signature verification or public-key recovery
+pub const SIGNATURE_VERIFY_STEPS: u64 = 3_000;
+
+/// Steps charged per hash invocation for sha256, keccak256, blake2b, and HMAC
+pub const HASH_STEPS: u64 = 30;
+
+/// Steps charged per cross-contract call, on top of the callee's own usage
+pub const CALL_STEPS: u64 = 700;
+
+/// Size-proportional part of a cost: `WORD_STEPS` per started 32-byte word
+pub fn word_cost(len: usize) -> u64 {
+    (len as u64).div_ceil(32) * WORD_STEPS
+}
+
+/// Steps charged per operation. Defaults to the `*_STEPS` constants; a node
+/// sets its own under `gas` in its configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GasSchedule {
+    /// Any stdlib call without a cost of its own, e.g. math operations
+    pub base: u64,
+    /// Per started 32-byte word of input
+    pub word: u64,
+    pub effect: u64,
+    pub storage_read: u64,
+    /// Storage writes and deletes
+    pub storage_write: u64,
+    pub event: u64,
+    /// Signature verification and public-key recovery
+    pub signature_verify: u64,
+    pub hash: u64,
+    /// Cross-contract call, on top of the callee's own usage
+    pub call: u64,
+    /// Fee units charged per step used by a transaction
+    pub fee_per_step: u64,
+}
+
+impl GasSchedule {
+    /// Size-proportional part of a cost: `word` per started 32-byte word
+    pub fn word_cost(&self, len: usize) -> u64 {
+        (len as u64).div_ceil(32).saturating_mul(self.word)
+    }
+
+    /// Fee for a transaction that used `steps`
+    pub fn fee(&self, steps: u64) -> u64 {
+        steps.saturating_mul(self.fee_per_step)
+    }
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self {
+            base: BASE_STEPS,
+            word: WORD_STEPS,
+            effect: EFFECT_STEPS,
+            storage_read: STORAGE_READ_STEPS,
+            storage_write: STORAGE_WRITE_STEPS,
+            event: EVENT_STEPS,
+            signature_verify: SIGNATURE_VERIFY_STEPS,
+            hash: HASH_STEPS,
+            call: CALL_STEPS,
+            fee_per_step: 1,
+        }
+    }
+}
+
+/// Per-contract storage limits enforced by `storage::set`
+#[derive(Debug, Clone, Copy)]
+pub struct StorageQuota {
+    pub max_keys: usize,
+    pub max_value_size: usize,
+}
+
+impl Default for StorageQuota {
+    fn default() -> Self {
+        Self {
+            max_keys: DEFAULT_MAX_STORAGE_KEYS,
+            max_value_size: DEFAULT_MAX_STORAGE_VALUE_SIZE,
+        }
+    }
+}
+
+/// Why a `StepMeter` refused a charge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeterExhausted {
+    /// The step budget is used up
+    OutOfGas,
+    /// The wall-clock ceiling has passed
+    Timeout,
+}
+
+impl fmt::Display for MeterExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeterExhausted::OutOfGas => f.write_str(OUT_OF_GAS),
+            MeterExhausted::Timeout => f.write_str(EXECUTION_TIMEOUT),
+        }
+    }
+}
+
+/// Step budget and wall-clock ceiling for one execution, shared with its
+/// nested calls
+#[derive(Debug, Clone)]
+pub struct StepMeter {
+    limit: u64,
+    used: u64,
+    deadline: Option<Instant>,
+}
+
+impl StepMeter {
+    /// Create a meter whose wall-clock ceiling, if any, starts now
+    pub fn new(limit: u64, max_duration: Option<Duration>) -> Self {
+        Self {
+            limit,
+            used: 0,
+            deadline: max_duration.map(|d| Instant::now() + d),
+        }
+    }
+
+    /// Create a meter from configured limits
+    pub fn from_limits(limits: &ExecutionLimits) -> Self {
+        let max_duration = (limits.max_execution_ms > 0).then(|| Duration::from_millis(limits.max_execution_ms));
+        Self::new(limits.step_budget, max_duration)
+    }
+
+    /// Charge `steps`, leaving the meter unchanged if the budget or the
+    /// deadline would be exceeded
+    pub fn charge(&mut self, steps: u64) -> Result<(), MeterExhausted> {
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(MeterExhausted::Timeout);
+        }
+        match self.used.checked_add(steps) {
+            Some(used) if used <= self.limit => {
+                self.used = used;
+                Ok(())
+            }
+            _ => Err(MeterExhausted::OutOfGas),
+        }
+    }
+
+    /// Step budget, including nested calls
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Steps used so far, including nested calls
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// Steps left in the budget
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    /// Meter for a nested call: the remaining budget and the same deadline
+    fn nested(&self) -> Self {
+        Self {
+            limit: self.remaining(),
+            used: 0,
+            deadline: self.deadline,
+        }
+    }
+
+    /// Count a nested call's usage against this meter
+    fn absorb(&mut self, child: &StepMeter) {
+        self.used = self.used.saturating_add(child.used);
+    }
+}
+
+impl Default for StepMeter {
+    fn default() -> Self {
+        Self::from_limits(&ExecutionLimits::default())
+    }
+}
+
+/// Event emitted by a contract during execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmittedEvent {
+    pub name: String,
+    pub data: Value,
+    pub timestamp: i64,
+}
+
+/// Resolves and executes contracts on behalf of `calls::call_contract`
+#[async_trait]
+pub trait ContractInvoker: Send + Sync {
+    /// Look up a contract on the ledger
+    async fn get_contract(&self, contract_id: &ContractId) -> GarpResult<Option<Contract>>;
+
+    /// Load a contract's persisted storage
+    async fn load_storage(&self, contract_id: &ContractId) -> GarpResult<HashMap<String, Value>>;
+
+    /// Whether a choice may be called by parties that cannot see the contract
+    fn is_public_choice(&self, contract: &Contract, choice: &str) -> bool;
+
+    /// Execute a choice against a nested context, returning its result value
+    async fn execute_choice(
+        &self,
+        ctx: &mut StdlibContext,
+        contract: &Contract,
+        choice: &str,
+        argument: Value,
+    ) -> GarpResult<Value>;
+}
+
+/// Changes from a completed nested call, committed with the top-level execution
+struct PendingCommit {
+    contract_id: ContractId,
+    diff: ContractStorageDiff,
+    events: Vec<EmittedEvent>,
+}
 
 /// Standard library context for smart contracts
 pub struct StdlibContext {
@@ -17,6 +271,25 @@ pub struct StdlibContext {
     pub timestamp: i64,
     /// Contract balance
     pub balance: u64,
+    /// Storage limits
+    pub quota: StorageQuota,
+    /// Events emitted during this execution
+    pub events: Vec<EmittedEvent>,
+    /// Execution budget, including nested calls
+    pub meter: StepMeter,
+    /// Steps charged per operation
+    pub gas_schedule: GasSchedule,
+    /// Keys written or deleted since the context was created
+    dirty: HashSet<String>,
+    /// Contracts currently executing, outermost first
+    call_stack: Vec<ContractId>,
+    max_call_depth: usize,
+    invoker: Option<Arc<dyn ContractInvoker>>,
+    /// Changes from nested calls, in call order
+    pending: Vec<PendingCommit>,
+    /// Set when the whole transaction must fail (e.g. a call cycle or an
+    /// exhausted meter)
+    aborted: Option<String>,
 }
 
 impl StdlibContext {
@@ -27,8 +300,181 @@ impl StdlibContext {
             caller,
             timestamp,
             balance,
+            quota: StorageQuota::default(),
+            events: Vec::new(),
+            meter: StepMeter::default(),
+            gas_schedule: GasSchedule::default(),
+            dirty: HashSet::new(),
+            call_stack: Vec::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            invoker: None,
+            pending: Vec::new(),
+            aborted: None,
         }
     }
+
+    /// Create a context with the contract's persisted storage loaded
+    pub async fn load(
+        backend: &dyn StorageBackend,
+        contract_id: &ContractId,
+        caller: String,
+        timestamp: i64,
+        balance: u64,
+    ) -> GarpResult<Self> {
+        let mut ctx = Self::new(caller, timestamp, balance);
+        ctx.storage = backend.get_contract_storage(contract_id).await?;
+        ctx.call_stack.push(contract_id.clone());
+        Ok(ctx)
+    }
+
+    /// Override the default storage quota
+    pub fn with_quota(mut self, quota: StorageQuota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Enable cross-contract calls through `invoker`
+    pub fn with_invoker(mut self, invoker: Arc<dyn ContractInvoker>) -> Self {
+        self.invoker = Some(invoker);
+        self
+    }
+
+    /// Override the default nested call depth limit
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Override the default execution budget
+    pub fn with_meter(mut self, meter: StepMeter) -> Self {
+        self.meter = meter;
+        self
+    }
+
+    /// Override the default gas schedule
+    pub fn with_gas_schedule(mut self, gas_schedule: GasSchedule) -> Self {
+        self.gas_schedule = gas_schedule;
+        self
+    }
+
+    /// Step budget of the execution
+    pub fn gas_limit(&self) -> u64 {
+        self.meter.limit()
+    }
+
+    /// Steps used so far, including nested calls
+    pub fn gas_used(&self) -> u64 {
+        self.meter.used()
+    }
+
+    /// Steps left before the execution runs out of gas
+    pub fn remaining_gas(&self) -> u64 {
+        self.meter.remaining()
+    }
+
+    /// Charge `steps` against the meter. Exhausting the meter aborts the
+    /// whole transaction, so every later charge fails as well.
+    pub fn charge(&mut self, steps: u64) -> Result<(), StdlibResult> {
+        if let Some(reason) = &self.aborted {
+            return Err(StdlibResult::error(reason.clone()));
+        }
+        self.meter.charge(steps).map_err(|exhausted| {
+            self.aborted = Some(exhausted.to_string());
+            StdlibResult::error(exhausted.to_string())
+        })
+    }
+
+    /// Reason the whole transaction must fail, if any
+    pub fn aborted(&self) -> Option<&str> {
+        self.aborted.as_deref()
+    }
+
+    /// Current nested call depth (0 for the top-level contract)
+    pub fn call_depth(&self) -> usize {
+        self.call_stack.len().saturating_sub(1)
+    }
+
+    /// Context for a nested call into `contract_id`, sharing this context's
+    /// remaining budget
+    fn nested(&self, contract_id: ContractId, storage: HashMap<String, Value>) -> Self {
+        let mut call_stack = self.call_stack.clone();
+        call_stack.push(contract_id);
+        Self {
+            storage,
+            caller: self.caller.clone(),
+            timestamp: self.timestamp,
+            balance: 0,
+            quota: self.quota,
+            events: Vec::new(),
+            meter: self.meter.nested(),
+            gas_schedule: self.gas_schedule,
+            dirty: HashSet::new(),
+            call_stack,
+            max_call_depth: self.max_call_depth,
+            invoker: self.invoker.clone(),
+            pending: Vec::new(),
+            aborted: None,
+        }
+    }
+
+    /// Keep a successful nested call's changes for the top-level commit
+    fn absorb(&mut self, contract_id: ContractId, child: StdlibContext) {
+        let diff = child.storage_diff();
+        self.pending.extend(child.pending);
+        self.pending.push(PendingCommit { contract_id, diff, events: child.events });
+    }
+
+    /// Storage changes made since the context was created
+    pub fn storage_diff(&self) -> ContractStorageDiff {
+        let mut diff = ContractStorageDiff::default();
+        for key in &self.dirty {
+            match self.storage.get(key) {
+                Some(value) => {
+                    diff.set.insert(key.clone(), value.clone());
+                }
+                None => diff.deleted.push(key.clone()),
+            }
+        }
+        diff
+    }
+
+    /// Persist storage changes and emitted events after a successful execution.
+    ///
+    /// Changes from nested calls are written first, in call order. Dropping
+    /// the context without committing discards all changes.
+    pub async fn commit(
+        self,
+        backend: &dyn StorageBackend,
+        contract_id: &ContractId,
+        emitter: &ParticipantId,
+    ) -> GarpResult<()> {
+        let diff = self.storage_diff();
+        let mut commits = self.pending;
+        commits.push(PendingCommit { contract_id: contract_id.clone(), diff, events: self.events });
+
+        for commit in commits {
+            if commit.diff.is_empty() && commit.events.is_empty() {
+                continue;
+            }
+
+            let events: Vec<ContractEvent> = commit
+                .events
+                .into_iter()
+                .map(|event| ContractEvent {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    contract_id: commit.contract_id.clone(),
+                    event_type: event.name,
+                    data: event.data,
+                    timestamp: chrono::DateTime::from_timestamp(event.timestamp, 0).unwrap_or_else(chrono::Utc::now),
+                    emitter: emitter.clone(),
+                    sequence: 0,
+                })
+                .collect();
+
+            backend.commit_contract_storage(&commit.contract_id, &commit.diff, &events).await?;
+        }
+        Ok(())
+    }
 }
 
 /// Result type for standard library functions
@@ -69,34 +515,86 @@ impl StdlibResult {
             error: Some(message),
         }
     }
+
+    /// Create the error result for an exhausted step budget
+    pub fn out_of_gas() -> Self {
+        Self::error(OUT_OF_GAS.to_string())
+    }
+
+    /// Whether this result is an exhausted step budget
+    pub fn is_out_of_gas(&self) -> bool {
+        self.error.as_deref() == Some(OUT_OF_GAS)
+    }
+}
+
+/// Return early from a stdlib function if the charge fails
+macro_rules! charge {
+    ($ctx:expr, $steps:expr) => {
+        if let Err(exhausted) = $ctx.charge($steps) {
+            return exhausted;
+        }
+    };
 }
 
 /// Storage operations
 pub mod storage {
     use super::*;
 
+    /// Steps charged for writing `value` under `key`
+    pub fn write_cost(gas: &GasSchedule, key: &str, value: &Value) -> u64 {
+        write_steps(gas, key, value.to_string().len())
+    }
+
+    fn write_steps(gas: &GasSchedule, key: &str, value_size: usize) -> u64 {
+        gas.storage_write + gas.word_cost(key.len() + value_size)
+    }
+
     /// Get a value from contract storage
-    pub fn get(ctx: &StdlibContext, key: &str) -> StdlibResult {
+    pub fn get(ctx: &mut StdlibContext, key: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.storage_read + ctx.gas_schedule.word_cost(key.len()));
         match ctx.storage.get(key) {
             Some(value) => StdlibResult::success(value.clone()),
             None => StdlibResult::success(Value::Null),
         }
     }
 
-    /// Set a value in contract storage
+    /// Set a value in contract storage, enforcing the context's quota
     pub fn set(ctx: &mut StdlibContext, key: String, value: Value) -> StdlibResult {
+        if !ctx.storage.contains_key(&key) && ctx.storage.len() >= ctx.quota.max_keys {
+            return StdlibResult::error(format!(
+                "Storage quota exceeded: contract already holds {} keys (max {})",
+                ctx.storage.len(),
+                ctx.quota.max_keys
+            ));
+        }
+
+        let size = value.to_string().len();
+        if size > ctx.quota.max_value_size {
+            return StdlibResult::error(format!(
+                "Storage quota exceeded: value for key '{}' is {} bytes (max {})",
+                key, size, ctx.quota.max_value_size
+            ));
+        }
+
+        charge!(ctx, write_steps(&ctx.gas_schedule, &key, size));
+
+        ctx.dirty.insert(key.clone());
         ctx.storage.insert(key, value);
         StdlibResult::success_empty()
     }
 
     /// Delete a value from contract storage
     pub fn delete(ctx: &mut StdlibContext, key: &str) -> StdlibResult {
-        ctx.storage.remove(key);
+        charge!(ctx, ctx.gas_schedule.storage_write + ctx.gas_schedule.word_cost(key.len()));
+        if ctx.storage.remove(key).is_some() {
+            ctx.dirty.insert(key.to_string());
+        }
         StdlibResult::success_empty()
     }
 
     /// Check if a key exists in contract storage
-    pub fn exists(ctx: &StdlibContext, key: &str) -> StdlibResult {
+    pub fn exists(ctx: &mut StdlibContext, key: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.storage_read + ctx.gas_schedule.word_cost(key.len()));
         let exists = ctx.storage.contains_key(key);
         StdlibResult::success(Value::Bool(exists))
     }
@@ -107,7 +605,8 @@ pub mod math {
     use super::*;
 
     /// Add two numbers
-    pub fn add(a: i64, b: i64) -> StdlibResult {
+    pub fn add(ctx: &mut StdlibContext, a: i64, b: i64) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base);
         let result = a.checked_add(b);
         match result {
             Some(value) => StdlibResult::success(Value::Number(value.into())),
@@ -116,7 +615,8 @@ pub mod math {
     }
 
     /// Subtract two numbers
-    pub fn sub(a: i64, b: i64) -> StdlibResult {
+    pub fn sub(ctx: &mut StdlibContext, a: i64, b: i64) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base);
         let result = a.checked_sub(b);
         match result {
             Some(value) => StdlibResult::success(Value::Number(value.into())),
@@ -125,7 +625,8 @@ pub mod math {
     }
 
     /// Multiply two numbers
-    pub fn mul(a: i64, b: i64) -> StdlibResult {
+    pub fn mul(ctx: &mut StdlibContext, a: i64, b: i64) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base);
         let result = a.checked_mul(b);
         match result {
             Some(value) => StdlibResult::success(Value::Number(value.into())),
@@ -134,7 +635,8 @@ pub mod math {
     }
 
     /// Divide two numbers
-    pub fn div(a: i64, b: i64) -> StdlibResult {
+    pub fn div(ctx: &mut StdlibContext, a: i64, b: i64) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base);
         if b == 0 {
             return StdlibResult::error("Division by zero".to_string());
         }
@@ -145,8 +647,9 @@ pub mod math {
         }
     }
 
-    /// Calculate the power of a number
-    pub fn pow(base: i64, exp: u32) -> StdlibResult {
+    /// Calculate the power of a number. Costs one step per bit of the exponent.
+    pub fn pow(ctx: &mut StdlibContext, base: i64, exp: u32) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base + u64::from(u32::BITS - exp.leading_zeros()));
         let result = base.checked_pow(exp);
         match result {
             Some(value) => StdlibResult::success(Value::Number(value.into())),
@@ -155,42 +658,48 @@ pub mod math {
     }
 }
 
-/// String operations
+/// String operations. Each call costs `base` plus the word cost of its input.
 pub mod string {
     use super::*;
     use base64::{encode, decode};
 
     /// Concatenate two strings
-    pub fn concat(a: &str, b: &str) -> StdlibResult {
+    pub fn concat(ctx: &mut StdlibContext, a: &str, b: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base + ctx.gas_schedule.word_cost(a.len() + b.len()));
         let result = format!("{}{}", a, b);
         StdlibResult::success(Value::String(result))
     }
 
     /// Get the length of a string
-    pub fn len(s: &str) -> StdlibResult {
+    pub fn len(ctx: &mut StdlibContext, s: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base);
         StdlibResult::success(Value::Number(s.len().into()))
     }
 
     /// Convert string to uppercase
-    pub fn to_uppercase(s: &str) -> StdlibResult {
+    pub fn to_uppercase(ctx: &mut StdlibContext, s: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base + ctx.gas_schedule.word_cost(s.len()));
         let result = s.to_uppercase();
         StdlibResult::success(Value::String(result))
     }
 
     /// Convert string to lowercase
-    pub fn to_lowercase(s: &str) -> StdlibResult {
+    pub fn to_lowercase(ctx: &mut StdlibContext, s: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base + ctx.gas_schedule.word_cost(s.len()));
         let result = s.to_lowercase();
         StdlibResult::success(Value::String(result))
     }
 
     /// Encode string to base64
-    pub fn to_base64(s: &str) -> StdlibResult {
+    pub fn to_base64(ctx: &mut StdlibContext, s: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base + ctx.gas_schedule.word_cost(s.len()));
         let result = encode(s);
         StdlibResult::success(Value::String(result))
     }
 
     /// Decode string from base64
-    pub fn from_base64(s: &str) -> StdlibResult {
+    pub fn from_base64(ctx: &mut StdlibContext, s: &str) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.base + ctx.gas_schedule.word_cost(s.len()));
         match decode(s) {
             Ok(decoded) => match String::from_utf8(decoded) {
                 Ok(result) => StdlibResult::success(Value::String(result)),
@@ -206,9 +715,21 @@ pub mod crypto {
     use super::*;
     use sha2::{Sha256, Digest};
     use ring::signature;
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+    use secp256k1::{Message, PublicKey, Secp256k1};
+
+    /// Length of a message digest accepted by secp256k1 operations
+    pub const DIGEST_LEN: usize = 32;
+
+    /// Length of a compact `r || s` secp256k1 signature
+    pub const COMPACT_SIGNATURE_LEN: usize = 64;
+
+    /// Length of a recoverable `r || s || v` secp256k1 signature
+    pub const RECOVERABLE_SIGNATURE_LEN: usize = 65;
 
     /// Hash data using SHA-256
-    pub fn sha256(data: &[u8]) -> StdlibResult {
+    pub fn sha256(ctx: &mut StdlibContext, data: &[u8]) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.hash + ctx.gas_schedule.word_cost(data.len()));
         let mut hasher = Sha256::new();
         hasher.update(data);
         let result = hasher.finalize();
@@ -218,10 +739,12 @@ pub mod crypto {
 
     /// Verify an Ed25519 signature
     pub fn verify_ed25519_signature(
+        ctx: &mut StdlibContext,
         message: &[u8],
         signature: &[u8],
         public_key: &[u8],
     ) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.signature_verify + ctx.gas_schedule.word_cost(message.len()));
         let peer_public_key = match signature::UnparsedPublicKey::new(
             &signature::ED25519,
             public_key,
@@ -233,6 +756,126 @@ pub mod crypto {
         let result = peer_public_key.verify(message, signature);
         StdlibResult::success(Value::Bool(result.is_ok()))
     }
+
+    /// Hash data using Keccak-256 as used by Ethereum
+    pub fn keccak256(ctx: &mut StdlibContext, data: &[u8]) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.hash + ctx.gas_schedule.word_cost(data.len()));
+        StdlibResult::success(Value::String(hex::encode(keccak256_digest(data))))
+    }
+
+    /// Hash data using BLAKE2b-512
+    pub fn blake2b(ctx: &mut StdlibContext, data: &[u8]) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.hash + ctx.gas_schedule.word_cost(data.len()));
+        let digest = blake2::Blake2b512::digest(data);
+        StdlibResult::success(Value::String(hex::encode(digest)))
+    }
+
+    /// Compute an HMAC-SHA256 of `data` under `key`
+    pub fn hmac_sha256(ctx: &mut StdlibContext, key: &[u8], data: &[u8]) -> StdlibResult {
+        use hmac::{Hmac, Mac};
+
+        charge!(ctx, 2 * ctx.gas_schedule.hash + ctx.gas_schedule.word_cost(key.len() + data.len()));
+        let mut mac = match Hmac::<Sha256>::new_from_slice(key) {
+            Ok(mac) => mac,
+            Err(_) => return StdlibResult::error("Invalid HMAC key".to_string()),
+        };
+        mac.update(data);
+        StdlibResult::success(Value::String(hex::encode(mac.finalize().into_bytes())))
+    }
+
+    /// Verify a secp256k1 ECDSA signature over a 32-byte digest.
+    ///
+    /// Accepts a 64-byte compact or 65-byte recoverable signature and a
+    /// 33-byte compressed or 65-byte uncompressed public key.
+    pub fn verify_secp256k1_signature(
+        ctx: &mut StdlibContext,
+        digest: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.signature_verify);
+        let message = match parse_digest(digest) {
+            Ok(message) => message,
+            Err(e) => return StdlibResult::error(e),
+        };
+        let compact = match signature.len() {
+            COMPACT_SIGNATURE_LEN | RECOVERABLE_SIGNATURE_LEN => &signature[..COMPACT_SIGNATURE_LEN],
+            len => return StdlibResult::error(format!(
+                "Invalid secp256k1 signature length: expected {} or {} bytes, got {}",
+                COMPACT_SIGNATURE_LEN, RECOVERABLE_SIGNATURE_LEN, len
+            )),
+        };
+        let mut sig = match secp256k1::ecdsa::Signature::from_compact(compact) {
+            Ok(sig) => sig,
+            Err(_) => return StdlibResult::error("Invalid secp256k1 signature".to_string()),
+        };
+        sig.normalize_s();
+        let key = match PublicKey::from_slice(public_key) {
+            Ok(key) => key,
+            Err(_) => return StdlibResult::error(format!(
+                "Invalid secp256k1 public key ({} bytes)", public_key.len()
+            )),
+        };
+
+        let secp = Secp256k1::verification_only();
+        StdlibResult::success(Value::Bool(secp.verify_ecdsa(&message, &sig, &key).is_ok()))
+    }
+
+    /// Recover the signer address from a 65-byte `r || s || v` signature over a
+    /// 32-byte digest, returned as a `0x`-prefixed lowercase hex address.
+    ///
+    /// `v` may be given either as 0/1 or in the Ethereum 27/28 form.
+    pub fn ecrecover(ctx: &mut StdlibContext, digest: &[u8], signature: &[u8]) -> StdlibResult {
+        charge!(ctx, ctx.gas_schedule.signature_verify);
+        let message = match parse_digest(digest) {
+            Ok(message) => message,
+            Err(e) => return StdlibResult::error(e),
+        };
+        if signature.len() != RECOVERABLE_SIGNATURE_LEN {
+            return StdlibResult::error(format!(
+                "Invalid recoverable signature length: expected {} bytes, got {}",
+                RECOVERABLE_SIGNATURE_LEN, signature.len()
+            ));
+        }
+        let v = signature[COMPACT_SIGNATURE_LEN];
+        let recovery_id = match RecoveryId::from_i32(i32::from(if v >= 27 { v - 27 } else { v })) {
+            Ok(id) => id,
+            Err(_) => return StdlibResult::error(format!("Invalid recovery id: {}", v)),
+        };
+        let sig = match RecoverableSignature::from_compact(&signature[..COMPACT_SIGNATURE_LEN], recovery_id) {
+            Ok(sig) => sig,
+            Err(_) => return StdlibResult::error("Invalid secp256k1 signature".to_string()),
+        };
+
+        let secp = Secp256k1::verification_only();
+        match secp.recover_ecdsa(&message, &sig) {
+            Ok(key) => StdlibResult::success(Value::String(eth_address(&key))),
+            Err(_) => StdlibResult::error("Public key recovery failed".to_string()),
+        }
+    }
+
+    fn keccak256_digest(data: &[u8]) -> [u8; 32] {
+        use sha3::Keccak256;
+        Keccak256::digest(data).into()
+    }
+
+    fn parse_digest(digest: &[u8]) -> Result<Message, String> {
+        if digest.len() != DIGEST_LEN {
+            return Err(format!(
+                "Invalid digest length: expected {} bytes, got {}",
+                DIGEST_LEN, digest.len()
+            ));
+        }
+        Message::from_digest_slice(digest).map_err(|e| format!("Invalid digest: {}", e))
+    }
+
+    /// Ethereum address of a public key: last 20 bytes of keccak256 over the
+    /// uncompressed key without its 0x04 prefix
+    fn eth_address(key: &PublicKey) -> String {
+        let uncompressed = key.serialize_uncompressed();
+        let hash = keccak256_digest(&uncompressed[1..]);
+        format!("0x{}", hex::encode(&hash[12..]))
+    }
 }
 
 /// Utility functions
@@ -267,31 +910,111 @@ pub mod utils {
 /// Event emission
 pub mod events {
     use super::*;
-    use std::collections::HashMap;
 
-    /// Emit an event
+    /// Steps charged for emitting an event
+    pub fn emit_cost(gas: &GasSchedule, event_name: &str, data: &Value) -> u64 {
+        gas.event + gas.word_cost(event_name.len() + data.to_string().len())
+    }
+
+    /// Emit an event. Events are written to the node's event store when the
+    /// execution is committed.
     pub fn emit(ctx: &mut StdlibContext, event_name: String, data: Value) -> StdlibResult {
-        // In a real implementation, this would emit an event that can be indexed
-        // For now, we'll just store it in a special storage key
-        let events_key = "_events";
-        let mut events = match ctx.storage.get(events_key) {
-            Some(Value::Array(arr)) => arr.clone(),
-            _ => Vec::new(),
-        };
-        
-        let event = serde_json::json!({
-            "name": event_name,
-            "data": data,
-            "timestamp": ctx.timestamp
+        charge!(ctx, emit_cost(&ctx.gas_schedule, &event_name, &data));
+        ctx.events.push(EmittedEvent {
+            name: event_name,
+            data,
+            timestamp: ctx.timestamp,
         });
-        
-        events.push(event);
-        ctx.storage.insert(events_key.to_string(), Value::Array(events));
-        
         StdlibResult::success_empty()
     }
 }
 
+/// Cross-contract calls
+pub mod calls {
+    use super::*;
+
+    /// Execute `choice` on another contract in a nested context.
+    ///
+    /// The caller must be a signatory or observer of the target, unless the
+    /// choice is public. The callee's storage writes and events are committed
+    /// together with the calling execution, and its steps count against the
+    /// caller's meter. A call cycle or an exhausted meter aborts the whole
+    /// transaction.
+    pub async fn call_contract(
+        ctx: &mut StdlibContext,
+        contract_id: &ContractId,
+        choice: &str,
+        argument: Value,
+    ) -> StdlibResult {
+        if let Some(reason) = &ctx.aborted {
+            return StdlibResult::error(reason.clone());
+        }
+
+        if ctx.call_stack.contains(contract_id) {
+            let path: Vec<String> = ctx.call_stack.iter().map(|id| id.0.to_string()).collect();
+            let reason = format!("Call cycle detected: {} -> {}", path.join(" -> "), contract_id.0);
+            ctx.aborted = Some(reason.clone());
+            return StdlibResult::error(reason);
+        }
+
+        if ctx.call_depth() >= ctx.max_call_depth {
+            return StdlibResult::error(format!("Call depth limit of {} exceeded", ctx.max_call_depth));
+        }
+
+        charge!(ctx, ctx.gas_schedule.call);
+
+        let invoker = match &ctx.invoker {
+            Some(invoker) => invoker.clone(),
+            None => return StdlibResult::error("Cross-contract calls are not available".to_string()),
+        };
+
+        let target = match invoker.get_contract(contract_id).await {
+            Ok(Some(contract)) if !contract.archived => contract,
+            Ok(Some(_)) => return StdlibResult::error(format!("Contract {} is archived", contract_id.0)),
+            Ok(None) => return StdlibResult::error(format!("Contract {} not found", contract_id.0)),
+            Err(e) => return StdlibResult::error(e.to_string()),
+        };
+
+        let caller = ParticipantId(ctx.caller.clone());
+        let visible = target.signatories.contains(&caller) || target.observers.contains(&caller);
+        if !visible && !invoker.is_public_choice(&target, choice) {
+            return StdlibResult::error(format!(
+                "Caller {} may not exercise {} on contract {}",
+                caller.0, choice, contract_id.0
+            ));
+        }
+
+        let mut storage = match invoker.load_storage(contract_id).await {
+            Ok(storage) => storage,
+            Err(e) => return StdlibResult::error(e.to_string()),
+        };
+        // Earlier calls into the same contract are not committed yet
+        for commit in ctx.pending.iter().filter(|p| &p.contract_id == contract_id) {
+            for key in &commit.diff.deleted {
+                storage.remove(key);
+            }
+            storage.extend(commit.diff.set.clone());
+        }
+
+        let mut child = ctx.nested(contract_id.clone(), storage);
+        let outcome = invoker.execute_choice(&mut child, &target, choice, argument).await;
+        ctx.meter.absorb(&child.meter);
+
+        if let Some(reason) = child.aborted.take() {
+            ctx.aborted = Some(reason.clone());
+            return StdlibResult::error(reason);
+        }
+
+        match outcome {
+            Ok(value) => {
+                ctx.absorb(contract_id.clone(), child);
+                StdlibResult::success(value)
+            }
+            Err(e) => StdlibResult::error(format!("Call to contract {} failed: {}", contract_id.0, e)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,12 +1027,12 @@ mod tests {
         let result = storage::set(&mut ctx, "test_key".to_string(), Value::String("test_value".to_string()));
         assert!(result.success);
         
-        let result = storage::get(&ctx, "test_key");
+        let result = storage::get(&mut ctx, "test_key");
         assert!(result.success);
         assert_eq!(result.value, Some(Value::String("test_value".to_string())));
         
         // Test exists
-        let result = storage::exists(&ctx, "test_key");
+        let result = storage::exists(&mut ctx, "test_key");
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Bool(true)));
         
@@ -317,63 +1040,442 @@ mod tests {
         let result = storage::delete(&mut ctx, "test_key");
         assert!(result.success);
         
-        let result = storage::get(&ctx, "test_key");
+        let result = storage::get(&mut ctx, "test_key");
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Null));
     }
 
     #[test]
     fn test_math_operations() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+
         // Test addition
-        let result = math::add(5, 3);
+        let result = math::add(&mut ctx, 5, 3);
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Number(8.into())));
         
         // Test subtraction
-        let result = math::sub(5, 3);
+        let result = math::sub(&mut ctx, 5, 3);
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Number(2.into())));
         
         // Test multiplication
-        let result = math::mul(5, 3);
+        let result = math::mul(&mut ctx, 5, 3);
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Number(15.into())));
         
         // Test division
-        let result = math::div(6, 3);
+        let result = math::div(&mut ctx, 6, 3);
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Number(2.into())));
         
         // Test power
-        let result = math::pow(2, 3);
+        let result = math::pow(&mut ctx, 2, 3);
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Number(8.into())));
     }
 
     #[test]
     fn test_string_operations() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+
         // Test concatenation
-        let result = string::concat("hello", " world");
+        let result = string::concat(&mut ctx, "hello", " world");
         assert!(result.success);
         assert_eq!(result.value, Some(Value::String("hello world".to_string())));
         
         // Test length
-        let result = string::len("hello");
+        let result = string::len(&mut ctx, "hello");
         assert!(result.success);
         assert_eq!(result.value, Some(Value::Number(5.into())));
         
         // Test uppercase
-        let result = string::to_uppercase("hello");
+        let result = string::to_uppercase(&mut ctx, "hello");
         assert!(result.success);
         assert_eq!(result.value, Some(Value::String("HELLO".to_string())));
         
         // Test base64 encoding/decoding
-        let result = string::to_base64("hello");
+        let result = string::to_base64(&mut ctx, "hello");
         assert!(result.success);
         let encoded = result.value.clone().unwrap().as_str().unwrap().to_string();
         
-        let result = string::from_base64(&encoded);
+        let result = string::from_base64(&mut ctx, &encoded);
         assert!(result.success);
         assert_eq!(result.value, Some(Value::String("hello".to_string())));
     }
-}
\ No newline at end of file
+
+    fn hex_value(s: &str) -> Option<Value> {
+        Some(Value::String(s.to_string()))
+    }
+
+    #[test]
+    fn test_keccak256_vectors() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+
+        let result = crypto::keccak256(&mut ctx, b"");
+        assert_eq!(result.value, hex_value("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
+
+        let result = crypto::keccak256(&mut ctx, b"abc");
+        assert_eq!(result.value, hex_value("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"));
+    }
+
+    #[test]
+    fn test_blake2b_vector() {
+        // RFC 7693, Appendix A
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+        let result = crypto::blake2b(&mut ctx, b"abc");
+        assert_eq!(result.value, hex_value(
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        ));
+    }
+
+    #[test]
+    fn test_hmac_sha256_vector() {
+        // RFC 4231, test case 2
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+        let result = crypto::hmac_sha256(&mut ctx, b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(result.value, hex_value("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"));
+    }
+
+    #[test]
+    fn test_secp256k1_recover_and_verify() {
+        // Private key 1; its address is the well-known 0x7e5f...5bdf
+        let digest = hex::decode("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8").unwrap();
+        let signature = hex::decode(
+            "bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d\
+             109eb1d8c99e122fb6c9b2b1519c8240f50a6043edee2dea9f16a6a2b0475ed31b"
+        ).unwrap();
+        let public_key = hex::decode(
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        ).unwrap();
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+
+        let result = crypto::ecrecover(&mut ctx, &digest, &signature);
+        assert!(result.success);
+        assert_eq!(result.value, hex_value("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"));
+
+        let result = crypto::verify_secp256k1_signature(&mut ctx, &digest, &signature, &public_key);
+        assert_eq!(result.value, Some(Value::Bool(true)));
+
+        let mut tampered = digest.clone();
+        tampered[0] ^= 1;
+        let result = crypto::verify_secp256k1_signature(&mut ctx, &tampered, &signature, &public_key);
+        assert_eq!(result.value, Some(Value::Bool(false)));
+    }
+
+    #[test]
+    fn test_secp256k1_rejects_malformed_input() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000);
+
+        assert!(!crypto::ecrecover(&mut ctx, &[0u8; 31], &[0u8; 65]).success);
+        assert!(!crypto::ecrecover(&mut ctx, &[0u8; 32], &[0u8; 64]).success);
+
+        let mut bad_v = [1u8; 65];
+        bad_v[64] = 42;
+        assert!(!crypto::ecrecover(&mut ctx, &[1u8; 32], &bad_v).success);
+
+        assert!(!crypto::verify_secp256k1_signature(&mut ctx, &[1u8; 32], &[1u8; 10], &[2u8; 33]).success);
+        assert!(!crypto::verify_secp256k1_signature(&mut ctx, &[1u8; 32], &[1u8; 64], &[2u8; 12]).success);
+    }
+
+    #[test]
+    fn test_storage_quota() {
+        let quota = StorageQuota { max_keys: 2, max_value_size: 16 };
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 1000).with_quota(quota);
+
+        assert!(storage::set(&mut ctx, "a".to_string(), Value::from(1)).success);
+        assert!(storage::set(&mut ctx, "b".to_string(), Value::from(2)).success);
+
+        let result = storage::set(&mut ctx, "c".to_string(), Value::from(3));
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("max 2"));
+
+        // Overwriting an existing key does not count against the key limit
+        assert!(storage::set(&mut ctx, "a".to_string(), Value::from(10)).success);
+
+        let result = storage::set(&mut ctx, "b".to_string(), Value::String("x".repeat(32)));
+        assert!(!result.success);
+        assert_eq!(ctx.storage.get("b"), Some(&Value::from(2)));
+    }
+
+    /// Runaway loop: hash and store until the stdlib refuses
+    fn run_loop(ctx: &mut StdlibContext, iterations: usize) -> StdlibResult {
+        for i in 0..iterations {
+            let digest = crypto::sha256(ctx, format!("round {}", i).as_bytes());
+            if !digest.success {
+                return digest;
+            }
+            let stored = storage::set(ctx, "last".to_string(), digest.value.unwrap());
+            if !stored.success {
+                return stored;
+            }
+        }
+        StdlibResult::success_empty()
+    }
+
+    #[test]
+    fn test_meter_exhausts_runaway_contract() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 0)
+            .with_meter(StepMeter::new(5_000, None));
+
+        let result = run_loop(&mut ctx, usize::MAX);
+        assert!(result.is_out_of_gas());
+        assert_eq!(ctx.aborted(), Some(OUT_OF_GAS));
+        assert!(ctx.meter.used() <= 5_000);
+
+        // Once exhausted, even cheap calls are refused
+        assert!(!math::add(&mut ctx, 1, 2).success);
+    }
+
+    #[test]
+    fn test_meter_allows_bounded_contract() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 0)
+            .with_meter(StepMeter::new(5_000, None));
+
+        assert!(run_loop(&mut ctx, 10).success);
+        assert!(ctx.aborted().is_none());
+        assert!(ctx.meter.used() > 0);
+        assert_eq!(ctx.meter.used() + ctx.meter.remaining(), 5_000);
+    }
+
+    #[test]
+    fn test_meter_wall_clock_ceiling() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 0)
+            .with_meter(StepMeter::new(u64::MAX, Some(Duration::ZERO)));
+
+        let result = math::add(&mut ctx, 1, 2);
+        assert_eq!(result.error.as_deref(), Some(EXECUTION_TIMEOUT));
+        assert_eq!(ctx.aborted(), Some(EXECUTION_TIMEOUT));
+    }
+
+    #[test]
+    fn test_gas_charged_per_operation() {
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 0)
+            .with_meter(StepMeter::new(10_000, None));
+        let gas = ctx.gas_schedule;
+        assert_eq!(ctx.gas_limit(), 10_000);
+
+        let mut used = 0;
+        let mut check = |ctx: &StdlibContext, result: StdlibResult, steps: u64| {
+            assert!(result.success);
+            used += steps;
+            assert_eq!(ctx.gas_used(), used);
+            assert_eq!(ctx.remaining_gas(), 10_000 - used);
+        };
+
+        let result = math::add(&mut ctx, 1, 2);
+        check(&ctx, result, gas.base);
+        let result = math::div(&mut ctx, 6, 3);
+        check(&ctx, result, gas.base);
+        let result = storage::set(&mut ctx, "key".to_string(), Value::from(7));
+        check(&ctx, result, storage::write_cost(&gas, "key", &Value::from(7)));
+        let result = storage::get(&mut ctx, "key");
+        check(&ctx, result, gas.storage_read + gas.word);
+        let result = crypto::sha256(&mut ctx, &[0u8; 33]);
+        check(&ctx, result, gas.hash + 2 * gas.word);
+    }
+
+    #[test]
+    fn test_custom_gas_schedule() {
+        let gas = GasSchedule { storage_read: 1_000, fee_per_step: 3, ..GasSchedule::default() };
+        let mut ctx = StdlibContext::new("caller1".to_string(), 1234567890, 0)
+            .with_meter(StepMeter::new(2_500, None))
+            .with_gas_schedule(gas);
+
+        assert!(storage::get(&mut ctx, "a").success);
+        assert!(storage::get(&mut ctx, "a").success);
+        assert_eq!(ctx.gas_used(), 2 * (1_000 + gas.word));
+        assert_eq!(gas.fee(ctx.gas_used()), 3 * ctx.gas_used());
+
+        // A read that does not fit in the remaining budget is refused
+        let result = storage::get(&mut ctx, "a");
+        assert!(result.is_out_of_gas());
+        assert_eq!(ctx.remaining_gas(), 2_500 - 2 * (1_000 + gas.word));
+    }
+
+    #[tokio::test]
+    async fn test_storage_persists_between_executions() {
+        let backend = crate::storage::MemoryStorage::new();
+        let contract_id = ContractId(uuid::Uuid::new_v4());
+        let emitter = ParticipantId::new("caller1");
+
+        let mut ctx = StdlibContext::load(&backend, &contract_id, "caller1".to_string(), 1234567890, 0).await.unwrap();
+        storage::set(&mut ctx, "counter".to_string(), Value::from(1));
+        events::emit(&mut ctx, "Incremented".to_string(), serde_json::json!({ "counter": 1 }));
+        assert!(!ctx.storage.contains_key("_events"));
+        ctx.commit(&backend, &contract_id, &emitter).await.unwrap();
+
+        let mut ctx = StdlibContext::load(&backend, &contract_id, "caller1".to_string(), 1234567891, 0).await.unwrap();
+        assert_eq!(storage::get(&mut ctx, "counter").value, Some(Value::from(1)));
+
+        // Changes from an execution that is never committed are discarded
+        storage::set(&mut ctx, "counter".to_string(), Value::from(2));
+        storage::delete(&mut ctx, "counter");
+        drop(ctx);
+
+        let state = backend.get_contract_storage(&contract_id).await.unwrap();
+        assert_eq!(state.get("counter"), Some(&Value::from(1)));
+
+        let events = backend.get_contract_events(&contract_id, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "Incremented");
+    }
+
+    /// Invoker where each contract's choice writes a key, emits an event and
+    /// then calls the next contract in `links`, if any
+    struct LinkedInvoker {
+        contracts: HashMap<ContractId, Contract>,
+        links: HashMap<ContractId, ContractId>,
+        public: bool,
+    }
+
+    impl LinkedInvoker {
+        fn new(count: usize, signatory: &str) -> (Self, Vec<ContractId>) {
+            let ids: Vec<ContractId> = (0..count).map(|_| ContractId(uuid::Uuid::new_v4())).collect();
+            let contracts = ids
+                .iter()
+                .map(|id| {
+                    let contract = Contract {
+                        id: id.clone(),
+                        template_id: "linked".to_string(),
+                        signatories: vec![ParticipantId::new(signatory)],
+                        observers: Vec::new(),
+                        argument: Value::Null,
+                        created_at: chrono::Utc::now(),
+                        archived: false,
+                    };
+                    (id.clone(), contract)
+                })
+                .collect();
+            let links = ids.windows(2).map(|w| (w[0].clone(), w[1].clone())).collect();
+            (Self { contracts, links, public: false }, ids)
+        }
+    }
+
+    #[async_trait]
+    impl ContractInvoker for LinkedInvoker {
+        async fn get_contract(&self, contract_id: &ContractId) -> GarpResult<Option<Contract>> {
+            Ok(self.contracts.get(contract_id).cloned())
+        }
+
+        async fn load_storage(&self, _contract_id: &ContractId) -> GarpResult<HashMap<String, Value>> {
+            Ok(HashMap::new())
+        }
+
+        fn is_public_choice(&self, _contract: &Contract, _choice: &str) -> bool {
+            self.public
+        }
+
+        async fn execute_choice(
+            &self,
+            ctx: &mut StdlibContext,
+            contract: &Contract,
+            choice: &str,
+            _argument: Value,
+        ) -> GarpResult<Value> {
+            storage::set(ctx, "visited".to_string(), Value::Bool(true));
+            events::emit(ctx, "Visited".to_string(), Value::Null);
+
+            if let Some(next) = self.links.get(&contract.id) {
+                let result = calls::call_contract(ctx, next, choice, Value::Null).await;
+                if !result.success {
+                    return Err(garp_common::ContractError::ExecutionFailed(result.error.unwrap_or_default()).into());
+                }
+            }
+            Ok(Value::from(ctx.call_depth() as u64))
+        }
+    }
+
+    async fn root_context(
+        backend: &crate::storage::MemoryStorage,
+        invoker: LinkedInvoker,
+        root: &ContractId,
+        caller: &str,
+    ) -> StdlibContext {
+        StdlibContext::load(backend, root, caller.to_string(), 1234567890, 0)
+            .await
+            .unwrap()
+            .with_invoker(Arc::new(invoker))
+    }
+
+    #[tokio::test]
+    async fn test_nested_call_commits_with_parent() {
+        let backend = crate::storage::MemoryStorage::new();
+        let (invoker, ids) = LinkedInvoker::new(3, "alice");
+        let root = ContractId(uuid::Uuid::new_v4());
+        let mut ctx = root_context(&backend, invoker, &root, "alice").await;
+
+        let result = calls::call_contract(&mut ctx, &ids[0], "visit", Value::Null).await;
+        assert!(result.success);
+        assert_eq!(result.value, Some(Value::from(1u64)));
+
+        // Three nested calls, each writing once and emitting once
+        let gas = GasSchedule::default();
+        let per_call = gas.call
+            + storage::write_cost(&gas, "visited", &Value::Bool(true))
+            + events::emit_cost(&gas, "Visited", &Value::Null);
+        assert_eq!(ctx.meter.used(), 3 * per_call);
+
+        // Nothing is written until the top-level execution commits
+        assert!(backend.get_contract_storage(&ids[2]).await.unwrap().is_empty());
+
+        ctx.commit(&backend, &root, &ParticipantId::new("alice")).await.unwrap();
+        for id in &ids {
+            let state = backend.get_contract_storage(id).await.unwrap();
+            assert_eq!(state.get("visited"), Some(&Value::Bool(true)));
+            assert_eq!(backend.get_contract_events(id, None).await.unwrap().len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_cycle_aborts() {
+        let backend = crate::storage::MemoryStorage::new();
+        let (mut invoker, ids) = LinkedInvoker::new(2, "alice");
+        invoker.links.insert(ids[1].clone(), ids[0].clone());
+        let mut ctx = root_context(&backend, invoker, &ids[0], "alice").await;
+
+        let result = calls::call_contract(&mut ctx, &ids[1], "visit", Value::Null).await;
+        assert!(!result.success);
+        assert!(ctx.aborted().unwrap().contains("cycle"));
+
+        // Further calls are refused once aborted
+        let (_, other) = LinkedInvoker::new(1, "alice");
+        assert!(!calls::call_contract(&mut ctx, &other[0], "visit", Value::Null).await.success);
+    }
+
+    #[tokio::test]
+    async fn test_call_depth_limit() {
+        let backend = crate::storage::MemoryStorage::new();
+        let (invoker, ids) = LinkedInvoker::new(5, "alice");
+        let root = ContractId(uuid::Uuid::new_v4());
+
+        let mut ctx = root_context(&backend, invoker, &root, "alice").await;
+        let result = calls::call_contract(&mut ctx, &ids[0], "visit", Value::Null).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Call depth limit of 4 exceeded"));
+        assert!(ctx.aborted().is_none());
+
+        let (invoker, ids) = LinkedInvoker::new(5, "alice");
+        let mut ctx = root_context(&backend, invoker, &root, "alice").await.with_max_call_depth(5);
+        assert!(calls::call_contract(&mut ctx, &ids[0], "visit", Value::Null).await.success);
+    }
+
+    #[tokio::test]
+    async fn test_call_visibility() {
+        let backend = crate::storage::MemoryStorage::new();
+        let root = ContractId(uuid::Uuid::new_v4());
+
+        let (invoker, ids) = LinkedInvoker::new(1, "alice");
+        let mut ctx = root_context(&backend, invoker, &root, "mallory").await;
+        let result = calls::call_contract(&mut ctx, &ids[0], "visit", Value::Null).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("may not exercise"));
+
+        let (mut invoker, ids) = LinkedInvoker::new(1, "alice");
+        invoker.public = true;
+        let mut ctx = root_context(&backend, invoker, &root, "mallory").await;
+        assert!(calls::call_contract(&mut ctx, &ids[0], "visit", Value::Null).await.success);
+    }
+}
@@ -449,6 +449,7 @@ mod tests {
             archived_contracts: vec![],
             events: vec![],
             upgraded_contracts: vec![],
+            gas_used: 0,
         };
         
         // This should not panic
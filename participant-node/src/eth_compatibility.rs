@@ -5,9 +5,14 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use axum::Extension;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{info, warn, error};
+use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use serde_json::Value as JsonValue;
 
@@ -17,10 +22,18 @@ use garp_common::{
 };
 use crate::node::ParticipantNode;
 use crate::config::ApiConfig;
+use crate::storage::{ContractEvent, EventQuery, StorageBackend};
+
+/// Maximum number of blocks a single log query may span
+pub const MAX_LOG_BLOCK_RANGE: u64 = 10_000;
+
+/// Time after which a filter that has not been polled is uninstalled
+pub const FILTER_TIMEOUT: Duration = Duration::from_secs(300);
 
 /// Ethereum JSON-RPC compatibility layer
 pub struct EthCompatibilityLayer {
     node: Arc<ParticipantNode>,
+    filters: Arc<EthFilterRegistry>,
 }
 
 /// Ethereum JSON-RPC request
@@ -94,16 +107,387 @@ pub struct EthTransaction {
     pub s: String, // hex encoded
 }
 
+/// Ethereum log entry derived from a GARP contract event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String, // hex encoded
+    pub block_number: String, // hex encoded
+    pub block_hash: String, // hex encoded
+    pub transaction_hash: String, // hex encoded
+    pub transaction_index: String, // hex encoded
+    pub log_index: String, // hex encoded
+    pub removed: bool,
+}
+
+/// Block reference accepted by `fromBlock`/`toBlock`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTag {
+    Latest,
+    Number(u64),
+}
+
+impl BlockTag {
+    fn parse(value: &JsonValue) -> Result<Self, EthJsonRpcError> {
+        match value.as_str() {
+            Some("latest") | Some("pending") | Some("safe") | Some("finalized") => Ok(BlockTag::Latest),
+            Some("earliest") => Ok(BlockTag::Number(0)),
+            Some(hex) if hex.starts_with("0x") => u64::from_str_radix(&hex[2..], 16)
+                .map(BlockTag::Number)
+                .map_err(|_| invalid_params("Invalid block number")),
+            _ => Err(invalid_params("Invalid block number format")),
+        }
+    }
+
+    fn resolve(&self, latest: u64) -> u64 {
+        match self {
+            BlockTag::Latest => latest,
+            BlockTag::Number(n) => *n,
+        }
+    }
+}
+
+/// Log filter criteria shared by eth_getLogs and eth_newFilter
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub from_block: Option<BlockTag>,
+    pub to_block: Option<BlockTag>,
+    /// Lower-cased addresses; empty matches any address
+    pub addresses: Vec<String>,
+    /// Per-position topic alternatives; `None` matches any topic
+    pub topics: Vec<Option<Vec<String>>>,
+}
+
+impl LogFilter {
+    /// Parse the filter object from JSON-RPC params (`[filter]`)
+    pub fn from_params(params: Option<JsonValue>) -> Result<Self, EthJsonRpcError> {
+        let params = params.ok_or_else(|| invalid_params("Missing parameters"))?;
+        let object = params
+            .as_array()
+            .and_then(|arr| arr.first())
+            .and_then(|v| v.as_object())
+            .ok_or_else(|| invalid_params("Invalid parameters"))?;
+
+        let mut filter = LogFilter::default();
+        if let Some(v) = object.get("fromBlock").filter(|v| !v.is_null()) {
+            filter.from_block = Some(BlockTag::parse(v)?);
+        }
+        if let Some(v) = object.get("toBlock").filter(|v| !v.is_null()) {
+            filter.to_block = Some(BlockTag::parse(v)?);
+        }
+        match object.get("address") {
+            None | Some(JsonValue::Null) => {}
+            Some(JsonValue::String(address)) => filter.addresses.push(address.to_lowercase()),
+            Some(JsonValue::Array(addresses)) => {
+                for address in addresses {
+                    let address = address.as_str().ok_or_else(|| invalid_params("Invalid address"))?;
+                    filter.addresses.push(address.to_lowercase());
+                }
+            }
+            Some(_) => return Err(invalid_params("Invalid address")),
+        }
+        match object.get("topics") {
+            None | Some(JsonValue::Null) => {}
+            Some(JsonValue::Array(positions)) => {
+                for position in positions {
+                    let alternatives = match position {
+                        JsonValue::Null => None,
+                        JsonValue::String(topic) => Some(vec![topic.to_lowercase()]),
+                        JsonValue::Array(topics) => Some(
+                            topics
+                                .iter()
+                                .map(|t| t.as_str().map(str::to_lowercase).ok_or_else(|| invalid_params("Invalid topic")))
+                                .collect::<Result<Vec<_>, _>>()?,
+                        ),
+                        _ => return Err(invalid_params("Invalid topic")),
+                    };
+                    filter.topics.push(alternatives);
+                }
+            }
+            Some(_) => return Err(invalid_params("Invalid topics")),
+        }
+        Ok(filter)
+    }
+
+    /// Resolve the block range against the current head, enforcing the range limit
+    pub fn resolve_range(&self, latest: u64, max_range: u64) -> Result<(u64, u64), EthJsonRpcError> {
+        let from = self.from_block.unwrap_or(BlockTag::Latest).resolve(latest);
+        let to = self.to_block.unwrap_or(BlockTag::Latest).resolve(latest);
+        if from > to {
+            return Err(invalid_params("invalid block range params"));
+        }
+        if to - from >= max_range {
+            return Err(EthJsonRpcError {
+                code: -32005,
+                message: format!("query exceeds max block range {}", max_range),
+                data: Some(serde_json::json!({
+                    "fromBlock": format!("0x{:x}", from),
+                    "toBlock": format!("0x{:x}", to),
+                })),
+            });
+        }
+        Ok((from, to))
+    }
+
+    fn matches(&self, log: &EthLog) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false;
+        }
+        self.topics.iter().enumerate().all(|(i, alternatives)| match alternatives {
+            None => true,
+            Some(alternatives) => log.topics.get(i).map_or(false, |topic| alternatives.contains(topic)),
+        })
+    }
+}
+
+/// Server-side filters installed with eth_newFilter
+pub struct EthFilterRegistry {
+    filters: parking_lot::Mutex<HashMap<String, InstalledFilter>>,
+    timeout: Duration,
+}
+
+struct InstalledFilter {
+    filter: LogFilter,
+    /// First block not yet returned by eth_getFilterChanges
+    next_block: u64,
+    last_polled: Instant,
+}
+
+impl EthFilterRegistry {
+    /// Create a registry that uninstalls filters not polled within `timeout`
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            filters: parking_lot::Mutex::new(HashMap::new()),
+            timeout,
+        }
+    }
+
+    /// Install a filter, returning its ID
+    pub async fn install(&self, storage: &dyn StorageBackend, filter: LogFilter) -> Result<String, EthJsonRpcError> {
+        let latest = latest_block_number(storage).await?;
+        let next_block = match filter.from_block {
+            Some(BlockTag::Number(n)) => n,
+            // Like geth, a filter starting at "latest" reports blocks after installation
+            _ => latest + 1,
+        };
+        let id = format!("0x{}", Uuid::new_v4().simple());
+        let mut filters = self.filters.lock();
+        self.prune_expired(&mut filters);
+        filters.insert(id.clone(), InstalledFilter {
+            filter,
+            next_block,
+            last_polled: Instant::now(),
+        });
+        Ok(id)
+    }
+
+    /// Uninstall a filter, returning whether it existed
+    pub fn uninstall(&self, id: &str) -> bool {
+        let mut filters = self.filters.lock();
+        self.prune_expired(&mut filters);
+        filters.remove(id).is_some()
+    }
+
+    /// Return logs matching the filter since the previous poll
+    pub async fn poll(&self, storage: &dyn StorageBackend, id: &str, max_range: u64) -> Result<Vec<EthLog>, EthJsonRpcError> {
+        let (filter, next_block) = {
+            let mut filters = self.filters.lock();
+            self.prune_expired(&mut filters);
+            let installed = filters.get_mut(id).ok_or_else(filter_not_found)?;
+            installed.last_polled = Instant::now();
+            (installed.filter.clone(), installed.next_block)
+        };
+
+        let latest = latest_block_number(storage).await?;
+        let to = filter
+            .to_block
+            .map_or(latest, |tag| tag.resolve(latest))
+            .min(latest)
+            .min(next_block.saturating_add(max_range - 1));
+        if next_block > to {
+            return Ok(Vec::new());
+        }
+
+        let logs = collect_logs(storage, &filter, next_block, to).await.map_err(internal_error)?;
+        if let Some(installed) = self.filters.lock().get_mut(id) {
+            installed.next_block = to + 1;
+        }
+        Ok(logs)
+    }
+
+    fn prune_expired(&self, filters: &mut HashMap<String, InstalledFilter>) {
+        filters.retain(|id, installed| {
+            let live = installed.last_polled.elapsed() < self.timeout;
+            if !live {
+                info!("Uninstalled expired filter {}", id);
+            }
+            live
+        });
+    }
+}
+
+impl Default for EthFilterRegistry {
+    fn default() -> Self {
+        Self::new(FILTER_TIMEOUT)
+    }
+}
+
+/// Run a log query over the filter's block range
+pub async fn query_logs(storage: &dyn StorageBackend, filter: &LogFilter, max_range: u64) -> Result<Vec<EthLog>, EthJsonRpcError> {
+    let latest = latest_block_number(storage).await?;
+    let (from, to) = filter.resolve_range(latest, max_range)?;
+    collect_logs(storage, filter, from, to).await.map_err(internal_error)
+}
+
+/// Map contract events in blocks `from..=to` to logs and apply the filter.
+///
+/// Events carry no block reference, so each is attributed to the first block
+/// whose timestamp is at or after the event's.
+async fn collect_logs(storage: &dyn StorageBackend, filter: &LogFilter, from: u64, to: u64) -> GarpResult<Vec<EthLog>> {
+    let mut blocks = Vec::new();
+    for slot in from..=to {
+        if let Some(block) = storage.get_block_by_slot(slot).await? {
+            blocks.push(block);
+        }
+    }
+    let Some(last) = blocks.last() else {
+        return Ok(Vec::new());
+    };
+
+    let lower = match from.checked_sub(1) {
+        Some(prev) => storage.get_block_by_slot(prev).await?.map(|b| b.timestamp),
+        None => None,
+    };
+    let mut events = storage
+        .query_events(&EventQuery {
+            contract_id: None,
+            event_type: None,
+            participant_id: None,
+            from_timestamp: lower,
+            to_timestamp: Some(last.timestamp),
+            after_sequence: None,
+            limit: None,
+        })
+        .await?;
+    events.retain(|e| lower.map_or(true, |t| e.timestamp > t));
+    events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+
+    let mut logs = Vec::new();
+    let mut current_slot = None;
+    let mut log_index = 0u64;
+    for event in &events {
+        let Some(block) = blocks.get(blocks.partition_point(|b| b.timestamp < event.timestamp)) else {
+            continue;
+        };
+        if current_slot != Some(block.header.slot) {
+            current_slot = Some(block.header.slot);
+            log_index = 0;
+        }
+        let log = event_to_log(event, block, log_index);
+        log_index += 1;
+        if filter.matches(&log) {
+            logs.push(log);
+        }
+    }
+    Ok(logs)
+}
+
+fn event_to_log(event: &ContractEvent, block: &garp_common::Block, log_index: u64) -> EthLog {
+    EthLog {
+        address: contract_address(&event.contract_id),
+        topics: vec![event_topic(&event.event_type)],
+        data: encode_log_data(&event.data),
+        block_number: format!("0x{:x}", block.header.slot),
+        block_hash: format!("0x{}", hex::encode(&block.hash)),
+        transaction_hash: format!("0x{}", hex::encode(Keccak256::digest(event.id.as_bytes()))),
+        transaction_index: "0x0".to_string(),
+        log_index: format!("0x{:x}", log_index),
+        removed: false,
+    }
+}
+
+/// Ethereum-style address for a contract: the low 20 bytes of keccak256(contract id)
+pub fn contract_address(contract_id: &garp_common::ContractId) -> String {
+    let hash = Keccak256::digest(contract_id.0.as_bytes());
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+/// topic0 for an event: keccak256 of the event name
+pub fn event_topic(event_type: &str) -> String {
+    format!("0x{}", hex::encode(Keccak256::digest(event_type.as_bytes())))
+}
+
+/// Encode the JSON payload as a single ABI `bytes` value (offset, length, padded data)
+fn encode_log_data(data: &JsonValue) -> String {
+    let payload = serde_json::to_vec(data).unwrap_or_default();
+    let mut out = Vec::with_capacity(64 + payload.len() + 32);
+    out.extend_from_slice(&abi_word(32));
+    out.extend_from_slice(&abi_word(payload.len() as u64));
+    out.extend_from_slice(&payload);
+    out.resize(out.len() + (32 - payload.len() % 32) % 32, 0);
+    format!("0x{}", hex::encode(out))
+}
+
+fn abi_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+async fn latest_block_number(storage: &dyn StorageBackend) -> Result<u64, EthJsonRpcError> {
+    match storage.get_latest_block().await {
+        Ok(block) => Ok(block.map_or(0, |b| b.header.slot)),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+fn invalid_params(message: &str) -> EthJsonRpcError {
+    EthJsonRpcError {
+        code: -32602,
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+fn internal_error(e: GarpError) -> EthJsonRpcError {
+    EthJsonRpcError {
+        code: -32603,
+        message: format!("Internal error: {}", e),
+        data: None,
+    }
+}
+
+fn filter_not_found() -> EthJsonRpcError {
+    EthJsonRpcError {
+        code: -32000,
+        message: "filter not found".to_string(),
+        data: None,
+    }
+}
+
 impl EthCompatibilityLayer {
     /// Create new Ethereum compatibility layer
     pub fn new(node: Arc<ParticipantNode>) -> Self {
-        Self { node }
+        Self::with_filters(node, Arc::new(EthFilterRegistry::default()))
+    }
+
+    /// Create a layer sharing an existing filter registry
+    pub fn with_filters(node: Arc<ParticipantNode>, filters: Arc<EthFilterRegistry>) -> Self {
+        Self { node, filters }
+    }
+
+    /// Filter registry backing eth_newFilter; must outlive individual requests
+    pub fn filters(&self) -> Arc<EthFilterRegistry> {
+        self.filters.clone()
     }
 
     /// Create router with Ethereum JSON-RPC endpoints
     pub fn create_router(&self) -> Router {
         Router::new()
             .route("/eth", post(eth_json_rpc))
+            .layer(Extension(self.filters.clone()))
             .with_state(self.node.clone())
     }
 
@@ -133,6 +517,11 @@ impl EthCompatibilityLayer {
             "net_peerCount" => self.net_peer_count().await,
             "web3_clientVersion" => self.web3_client_version().await,
             "web3_sha3" => self.web3_sha3(params).await,
+            "eth_getLogs" => self.eth_get_logs(params).await,
+            "eth_newFilter" => self.eth_new_filter(params).await,
+            "eth_getFilterChanges" => self.eth_get_filter_changes(params).await,
+            "eth_getFilterLogs" => self.eth_get_filter_changes(params).await,
+            "eth_uninstallFilter" => self.eth_uninstall_filter(params).await,
             _ => Err(EthJsonRpcError {
                 code: -32601,
                 message: "Method not found".to_string(),
@@ -322,6 +711,33 @@ impl EthCompatibilityLayer {
         Ok(serde_json::Value::String("0x0000000000000000000000000000000000000000000000000000000000000000".to_string()))
     }
 
+    /// eth_getLogs: Returns logs matching the given filter object
+    async fn eth_get_logs(&self, params: Option<serde_json::Value>) -> Result<serde_json::Value, EthJsonRpcError> {
+        let filter = LogFilter::from_params(params)?;
+        let logs = query_logs(self.node.get_storage().as_ref(), &filter, MAX_LOG_BLOCK_RANGE).await?;
+        Ok(serde_json::to_value(logs).unwrap())
+    }
+
+    /// eth_newFilter: Installs a log filter and returns its ID
+    async fn eth_new_filter(&self, params: Option<serde_json::Value>) -> Result<serde_json::Value, EthJsonRpcError> {
+        let filter = LogFilter::from_params(params)?;
+        let id = self.filters.install(self.node.get_storage().as_ref(), filter).await?;
+        Ok(serde_json::Value::String(id))
+    }
+
+    /// eth_getFilterChanges: Returns logs matching a filter since it was last polled
+    async fn eth_get_filter_changes(&self, params: Option<serde_json::Value>) -> Result<serde_json::Value, EthJsonRpcError> {
+        let id = filter_id_param(params)?;
+        let logs = self.filters.poll(self.node.get_storage().as_ref(), &id, MAX_LOG_BLOCK_RANGE).await?;
+        Ok(serde_json::to_value(logs).unwrap())
+    }
+
+    /// eth_uninstallFilter: Removes a filter installed with eth_newFilter
+    async fn eth_uninstall_filter(&self, params: Option<serde_json::Value>) -> Result<serde_json::Value, EthJsonRpcError> {
+        let id = filter_id_param(params)?;
+        Ok(serde_json::Value::Bool(self.filters.uninstall(&id)))
+    }
+
     /// Convert GARP block to Ethereum block format
     async fn convert_to_eth_block(&self, block: crate::storage::Block) -> EthBlock {
         EthBlock {
@@ -348,14 +764,25 @@ impl EthCompatibilityLayer {
     }
 }
 
+fn filter_id_param(params: Option<serde_json::Value>) -> Result<String, EthJsonRpcError> {
+    params
+        .as_ref()
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| invalid_params("Invalid filter id"))
+}
+
 /// Ethereum JSON-RPC handler
-async fn eth_json_rpc(
+pub async fn eth_json_rpc(
     State(node): State<Arc<ParticipantNode>>,
+    Extension(filters): Extension<Arc<EthFilterRegistry>>,
     Json(request): Json<EthJsonRpcRequest>,
 ) -> Result<Json<EthJsonRpcResponse>, StatusCode> {
     info!("Ethereum JSON-RPC request: {} {:?}", request.method, request.params);
     
-    let eth_layer = EthCompatibilityLayer::new(node);
+    let eth_layer = EthCompatibilityLayer::with_filters(node, filters);
     
     let result = eth_layer.handle_eth_request(request.method, request.params).await;
     
@@ -377,4 +804,166 @@ async fn eth_json_rpc(
             }))
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use garp_common::{Block, BlockHeader, ContractId};
+    use serde_json::json;
+
+    fn block(slot: u64, timestamp: DateTime<Utc>) -> Block {
+        Block {
+            header: BlockHeader {
+                parent_hash: vec![0; 32],
+                slot,
+                epoch: 0,
+                proposer: ParticipantId::new("proposer"),
+                state_root: Vec::new(),
+                tx_root: Vec::new(),
+                receipt_root: Vec::new(),
+            },
+            hash: vec![slot as u8; 32],
+            timestamp,
+            transactions: Vec::new(),
+        }
+    }
+
+    fn event(contract_id: &ContractId, event_type: &str, timestamp: DateTime<Utc>) -> ContractEvent {
+        ContractEvent {
+            id: Uuid::new_v4().to_string(),
+            contract_id: contract_id.clone(),
+            event_type: event_type.to_string(),
+            data: json!({ "amount": 100 }),
+            timestamp,
+            emitter: ParticipantId::new("alice"),
+            sequence: 0,
+        }
+    }
+
+    /// Two contracts emitting events across blocks 1..=3
+    async fn setup() -> (MemoryStorage, ContractId, ContractId, DateTime<Utc>) {
+        let storage = MemoryStorage::new();
+        let t0 = Utc::now();
+        let token = ContractId::new();
+        let market = ContractId::new();
+        for slot in 1..=3 {
+            storage.store_block(&block(slot, t0 + chrono::Duration::seconds(10 * slot as i64))).await.unwrap();
+        }
+        let emitted = [
+            (&token, "Transfer", 5),
+            (&token, "Approval", 15),
+            (&market, "Transfer", 25),
+        ];
+        for (contract_id, event_type, offset) in emitted {
+            let event = event(contract_id, event_type, t0 + chrono::Duration::seconds(offset));
+            storage.commit_contract_storage(contract_id, &Default::default(), &[event]).await.unwrap();
+        }
+        (storage, token, market, t0)
+    }
+
+    fn filter(params: JsonValue) -> LogFilter {
+        LogFilter::from_params(Some(json!([params]))).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_filters_by_address_and_topic() {
+        let (storage, token, market, _) = setup().await;
+
+        let by_address = query_logs(&storage, &filter(json!({
+            "fromBlock": "0x1",
+            "toBlock": "latest",
+            "address": contract_address(&token),
+        })), MAX_LOG_BLOCK_RANGE).await.unwrap();
+        assert_eq!(by_address.len(), 2);
+        assert_eq!(by_address[0].block_number, "0x1");
+        assert_eq!(by_address[1].block_number, "0x2");
+
+        // toBlock defaults to "latest"
+        let by_topic = query_logs(&storage, &filter(json!({
+            "fromBlock": "earliest",
+            "topics": [event_topic("Transfer")],
+        })), MAX_LOG_BLOCK_RANGE).await.unwrap();
+        assert_eq!(by_topic.len(), 2);
+        assert_eq!(by_topic[1].address, contract_address(&market));
+        assert_eq!(by_topic[1].block_number, "0x3");
+
+        let combined = query_logs(&storage, &filter(json!({
+            "fromBlock": "0x1",
+            "toBlock": "0x3",
+            "address": [contract_address(&market)],
+            "topics": [[event_topic("Transfer"), event_topic("Approval")]],
+        })), MAX_LOG_BLOCK_RANGE).await.unwrap();
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].topics, vec![event_topic("Transfer")]);
+        assert_eq!(combined[0].log_index, "0x0");
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_single_block_and_data_encoding() {
+        let (storage, _, _, _) = setup().await;
+
+        let logs = query_logs(&storage, &filter(json!({ "fromBlock": "0x2", "toBlock": "0x2" })), MAX_LOG_BLOCK_RANGE)
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].topics, vec![event_topic("Approval")]);
+
+        let data = hex::decode(&logs[0].data[2..]).unwrap();
+        let payload = serde_json::to_vec(&json!({ "amount": 100 })).unwrap();
+        assert_eq!(data[31], 32);
+        assert_eq!(data[63] as usize, payload.len());
+        assert_eq!(&data[64..64 + payload.len()], payload.as_slice());
+        assert_eq!(data.len() % 32, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_rejects_excessive_range() {
+        let (storage, _, _, _) = setup().await;
+
+        let err = query_logs(&storage, &filter(json!({ "fromBlock": "0x0", "toBlock": "0x3" })), 3)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, -32005);
+        assert_eq!(err.message, "query exceeds max block range 3");
+
+        let err = query_logs(&storage, &filter(json!({ "fromBlock": "0x3", "toBlock": "0x1" })), 3)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_filter_changes_return_only_new_logs() {
+        let (storage, token, _, t0) = setup().await;
+        let registry = EthFilterRegistry::default();
+
+        let id = registry
+            .install(&storage, filter(json!({ "fromBlock": "0x1", "address": contract_address(&token) })))
+            .await
+            .unwrap();
+        assert_eq!(registry.poll(&storage, &id, MAX_LOG_BLOCK_RANGE).await.unwrap().len(), 2);
+        assert!(registry.poll(&storage, &id, MAX_LOG_BLOCK_RANGE).await.unwrap().is_empty());
+
+        let minted = event(&token, "Mint", t0 + chrono::Duration::seconds(35));
+        storage.commit_contract_storage(&token, &Default::default(), &[minted]).await.unwrap();
+        storage.store_block(&block(4, t0 + chrono::Duration::seconds(40))).await.unwrap();
+
+        let changes = registry.poll(&storage, &id, MAX_LOG_BLOCK_RANGE).await.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].topics, vec![event_topic("Mint")]);
+        assert_eq!(changes[0].block_number, "0x4");
+
+        assert!(registry.uninstall(&id));
+        assert_eq!(registry.poll(&storage, &id, MAX_LOG_BLOCK_RANGE).await.unwrap_err().message, "filter not found");
+    }
+
+    #[tokio::test]
+    async fn test_filters_expire_when_not_polled() {
+        let (storage, _, _, _) = setup().await;
+        let registry = EthFilterRegistry::new(Duration::ZERO);
+
+        let id = registry.install(&storage, LogFilter::default()).await.unwrap();
+        assert!(registry.poll(&storage, &id, MAX_LOG_BLOCK_RANGE).await.is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! Contract event log consumption
+//!
+//! Storage assigns every recorded event a sequence number, so the event log
+//! can be read from a cursor (`after_sequence`) in a stable order. This
+//! module keeps what consumers of that log need on top of storage: named
+//! consumer checkpoints, so an indexer that restarts resumes after the last
+//! sequence it acknowledged, and retention, which prunes the oldest events
+//! and records how far the log has been truncated. Both are persisted in
+//! node metadata.
+
+use chrono::{DateTime, Utc};
+use garp_common::{GarpError, GarpResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::config::EventRetentionConfig;
+use crate::storage::{EventQuery, StorageBackend};
+
+const CONSUMER_KEY_PREFIX: &str = "eventconsumer:";
+/// Highest sequence removed by retention
+const PRUNED_THROUGH_KEY: &str = "eventlog_pruned_through";
+
+/// Sequences still available in the event log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EventLogBounds {
+    /// Lowest sequence not removed by retention; a consumer whose cursor is
+    /// below `earliest_available_sequence - 1` has missed events
+    pub earliest_available_sequence: u64,
+    /// Highest sequence assigned so far, if any event was recorded
+    pub latest_sequence: Option<u64>,
+}
+
+impl EventLogBounds {
+    /// Whether events after `after_sequence` were pruned before being read
+    pub fn is_truncated(&self, after_sequence: u64) -> bool {
+        after_sequence.saturating_add(1) < self.earliest_available_sequence
+    }
+}
+
+/// Named reader of the event log and the last sequence it acknowledged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventConsumer {
+    pub name: String,
+    /// Events up to and including this sequence have been processed; 0
+    /// before the first acknowledgement
+    pub acked_sequence: u64,
+    pub registered_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn consumer_key(name: &str) -> String {
+    format!("{}{}", CONSUMER_KEY_PREFIX, name)
+}
+
+/// Consumer checkpoints and retention for the contract event log
+pub struct EventIndex {
+    storage: Arc<dyn StorageBackend>,
+    retention: EventRetentionConfig,
+    /// Serializes checkpoint updates so acknowledgements never regress
+    checkpoints: tokio::sync::Mutex<()>,
+}
+
+impl EventIndex {
+    pub fn new(storage: Arc<dyn StorageBackend>, retention: EventRetentionConfig) -> Self {
+        Self {
+            storage,
+            retention,
+            checkpoints: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn pruned_through(&self) -> GarpResult<u64> {
+        Ok(self.storage.get_metadata(PRUNED_THROUGH_KEY).await?.as_ref().and_then(Value::as_u64).unwrap_or(0))
+    }
+
+    /// Earliest and latest sequences of the event log
+    pub async fn bounds(&self) -> GarpResult<EventLogBounds> {
+        let pruned_through = self.pruned_through().await?;
+        let latest = self.storage.latest_event_sequence().await?;
+        Ok(EventLogBounds {
+            earliest_available_sequence: pruned_through + 1,
+            // Everything may have been pruned, but sequences are never reused
+            latest_sequence: match (latest, pruned_through) {
+                (None, 0) => None,
+                (latest, pruned_through) => Some(latest.unwrap_or(0).max(pruned_through)),
+            },
+        })
+    }
+
+    /// Register `name`, or return its checkpoint if it is already registered
+    pub async fn register_consumer(&self, name: &str) -> GarpResult<EventConsumer> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            return Err(GarpError::ValidationError(format!("Invalid event consumer name: {:?}", name)));
+        }
+        let _guard = self.checkpoints.lock().await;
+        if let Some(consumer) = self.get_consumer(name).await? {
+            return Ok(consumer);
+        }
+        let now = Utc::now();
+        let consumer = EventConsumer {
+            name: name.to_string(),
+            acked_sequence: 0,
+            registered_at: now,
+            updated_at: now,
+        };
+        self.put_consumer(&consumer).await?;
+        info!("Registered event consumer {}", name);
+        Ok(consumer)
+    }
+
+    pub async fn get_consumer(&self, name: &str) -> GarpResult<Option<EventConsumer>> {
+        match self.storage.get_metadata(&consumer_key(name)).await? {
+            Some(value) => serde_json::from_value(value)
+                .map(Some)
+                .map_err(|e| GarpError::SerializationError(format!("Invalid event consumer record {}: {}", name, e))),
+            None => Ok(None),
+        }
+    }
+
+    /// Registered consumers, ordered by name
+    pub async fn list_consumers(&self) -> GarpResult<Vec<EventConsumer>> {
+        self.storage
+            .list_metadata(CONSUMER_KEY_PREFIX)
+            .await?
+            .into_iter()
+            .map(|(key, value)| {
+                serde_json::from_value(value)
+                    .map_err(|e| GarpError::SerializationError(format!("Invalid event consumer record {}: {}", key, e)))
+            })
+            .collect()
+    }
+
+    /// Record that `name` has processed every event up to `sequence`.
+    /// Acknowledging the current checkpoint again is a no-op; moving it
+    /// backwards or past the latest sequence is rejected.
+    pub async fn ack(&self, name: &str, sequence: u64) -> GarpResult<EventConsumer> {
+        let _guard = self.checkpoints.lock().await;
+        let mut consumer = self
+            .get_consumer(name)
+            .await?
+            .ok_or_else(|| GarpError::NotFound(format!("Event consumer {} is not registered", name)))?;
+        if sequence < consumer.acked_sequence {
+            return Err(GarpError::ValidationError(format!(
+                "Event consumer {} already acknowledged sequence {}",
+                name, consumer.acked_sequence
+            )));
+        }
+        let latest = self.bounds().await?.latest_sequence.unwrap_or(0);
+        if sequence > latest {
+            return Err(GarpError::ValidationError(format!(
+                "Sequence {} has not been assigned yet; the latest is {}",
+                sequence, latest
+            )));
+        }
+        if sequence == consumer.acked_sequence {
+            return Ok(consumer);
+        }
+        consumer.acked_sequence = sequence;
+        consumer.updated_at = Utc::now();
+        self.put_consumer(&consumer).await?;
+        Ok(consumer)
+    }
+
+    async fn put_consumer(&self, consumer: &EventConsumer) -> GarpResult<()> {
+        let value = serde_json::to_value(consumer).map_err(|e| GarpError::SerializationError(e.to_string()))?;
+        self.storage.put_metadata(&consumer_key(&consumer.name), &value).await
+    }
+
+    /// Prune events past the configured age or count limit, oldest
+    /// sequence first, stopping at the first event within both limits.
+    /// Returns the number of events deleted.
+    pub async fn apply_retention(&self, now: DateTime<Utc>) -> GarpResult<u64> {
+        let pruned_through = self.pruned_through().await?;
+        let Some(latest) = self.storage.latest_event_sequence().await? else {
+            return Ok(0);
+        };
+
+        let mut keep_from = pruned_through + 1;
+        if let Some(max_events) = self.retention.max_events {
+            keep_from = keep_from.max((latest + 1).saturating_sub(max_events));
+        }
+        if let Some(max_age_secs) = self.retention.max_age_secs {
+            let first_recent = self
+                .storage
+                .query_events(&EventQuery {
+                    contract_id: None,
+                    event_type: None,
+                    participant_id: None,
+                    from_timestamp: Some(now - chrono::Duration::seconds(max_age_secs as i64)),
+                    to_timestamp: None,
+                    after_sequence: Some(pruned_through),
+                    limit: Some(1),
+                })
+                .await?;
+            keep_from = keep_from.max(first_recent.first().map_or(latest + 1, |event| event.sequence));
+        }
+        if keep_from <= pruned_through + 1 {
+            return Ok(0);
+        }
+
+        let deleted = self.storage.prune_events(keep_from).await?;
+        self.storage.put_metadata(PRUNED_THROUGH_KEY, &Value::from(keep_from - 1)).await?;
+        if deleted > 0 {
+            info!("Pruned {} contract events through sequence {}", deleted, keep_from - 1);
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{ContractEvent, MemoryStorage};
+    use garp_common::{ContractId, ParticipantId};
+    use uuid::Uuid;
+
+    fn event(contract_id: &ContractId, timestamp: DateTime<Utc>) -> ContractEvent {
+        ContractEvent {
+            id: Uuid::new_v4().to_string(),
+            contract_id: contract_id.clone(),
+            event_type: "Transfer".to_string(),
+            data: serde_json::json!({}),
+            timestamp,
+            emitter: ParticipantId::new("alice"),
+            sequence: 0,
+        }
+    }
+
+    fn after(sequence: u64, limit: u32) -> EventQuery {
+        EventQuery {
+            contract_id: None,
+            event_type: None,
+            participant_id: None,
+            from_timestamp: None,
+            to_timestamp: None,
+            after_sequence: Some(sequence),
+            limit: Some(limit),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_resumes_from_checkpoint_across_retention() {
+        let storage: Arc<dyn StorageBackend> = Arc::new(MemoryStorage::new());
+        let contract = ContractId(Uuid::new_v4());
+        let now = Utc::now();
+        for age in [50, 40, 30, 20, 10] {
+            storage.store_contract_event(&event(&contract, now - chrono::Duration::seconds(age))).await.unwrap();
+        }
+        // Re-recording an event keeps its original sequence
+        let first = storage.query_events(&after(0, 1)).await.unwrap().remove(0);
+        storage.store_contract_event(&first).await.unwrap();
+        assert_eq!(first.sequence, 1);
+        assert_eq!(storage.latest_event_sequence().await.unwrap(), Some(5));
+
+        let retention = EventRetentionConfig { max_age_secs: Some(35), max_events: Some(4), prune_interval_secs: 60 };
+        let index = EventIndex::new(storage.clone(), retention.clone());
+        assert!(index.register_consumer("bad name").await.is_err());
+        index.register_consumer("indexer").await.unwrap();
+
+        let page = storage.query_events(&after(0, 2)).await.unwrap();
+        assert_eq!(page.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![1, 2]);
+        index.ack("indexer", 2).await.unwrap();
+        assert!(index.ack("indexer", 1).await.is_err());
+        assert!(index.ack("indexer", 6).await.is_err());
+
+        // A restarted indexer picks up its checkpoint
+        let restarted = EventIndex::new(storage.clone(), retention);
+        let consumer = restarted.register_consumer("indexer").await.unwrap();
+        assert_eq!(consumer.acked_sequence, 2);
+        let page = storage.query_events(&after(consumer.acked_sequence, 10)).await.unwrap();
+        assert_eq!(page.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![3, 4, 5]);
+
+        // Events 1 and 2 are past the age limit, only 1 past the count limit
+        assert_eq!(restarted.apply_retention(now).await.unwrap(), 2);
+        assert_eq!(restarted.apply_retention(now).await.unwrap(), 0);
+        let bounds = restarted.bounds().await.unwrap();
+        assert_eq!(bounds, EventLogBounds { earliest_available_sequence: 3, latest_sequence: Some(5) });
+        assert!(!bounds.is_truncated(2));
+        assert!(bounds.is_truncated(1));
+        assert_eq!(restarted.list_consumers().await.unwrap().len(), 1);
+    }
+}
@@ -1,10 +1,13 @@
 use garp_common::{
     Contract, Transaction, TransactionId, ContractId, ParticipantId, Asset, WalletBalance,
     TransactionCommand, CreateContractCommand, ExerciseContractCommand, ArchiveContractCommand,
-    GarpResult, GarpError, TransactionError, CryptoService, DigitalSignature,
+    GarpResult, GarpError, TransactionError, CryptoService, DigitalSignature, Signature, SignatureSet,
     AccountId, ProgramId, TxV2, AccountMeta, RecentBlockhash,
 };
 use crate::storage::{StorageBackend, LedgerState};
+use crate::wallet::apply_balance_change;
+use crate::contract_engine::ContractEngine;
+use crate::simulation::{ForkedStorage, SimulationResult as TransactionSimulationResult};
 use chrono::{DateTime, Utc};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -15,6 +18,9 @@ use bytes::Bytes;
 use crate::poh::Poh;
 use tokio::time::Duration as TokioDuration;
 
+/// Metadata key prefix of registered participant signing keys
+const PARTICIPANT_KEY_PREFIX: &str = "participantkey:";
+
 /// Local ledger for a participant node
 pub struct LocalLedger {
     participant_id: ParticipantId,
@@ -140,10 +146,14 @@ impl LocalLedger {
         };
 
         // Validate signatures
-        if let Err(e) = self.validate_signatures(transaction).await {
-            result.valid = false;
-            result.errors.push(format!("Signature validation failed: {}", e));
-        }
+        let signatures = match self.validate_signatures(transaction).await {
+            Ok(signatures) => signatures,
+            Err(e) => {
+                result.valid = false;
+                result.errors.push(format!("Signature validation failed: {}", e));
+                SignatureSet::new()
+            }
+        };
 
         // Validate command-specific logic
         match &transaction.command {
@@ -165,11 +175,63 @@ impl LocalLedger {
         }
 
         // Check authorization
-        self.validate_authorization(transaction, &mut result).await?;
+        self.validate_authorization(&signatures, &mut result).await?;
 
         Ok(result)
     }
 
+    /// Simulate a transaction against a forked view of the ledger.
+    ///
+    /// Runs the same validation and effect application as `submit_transaction`,
+    /// plus contract choice execution, but all writes land in an overlay that is
+    /// discarded afterwards.
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        contract_engine: &ContractEngine,
+    ) -> GarpResult<TransactionSimulationResult> {
+        let forked = Arc::new(ForkedStorage::new(self.storage.clone()));
+        let ledger = LocalLedger::new(self.participant_id.clone(), forked.clone(), self.crypto_service.clone());
+
+        let validation = ledger.validate_transaction(transaction).await?;
+        if !validation.valid {
+            return Ok(TransactionSimulationResult::failed(validation.errors.join("; "), validation.warnings, forked.diff()));
+        }
+
+        if let Err(e) = ledger.apply_transaction_effects(transaction).await {
+            return Ok(TransactionSimulationResult::failed(e.to_string(), validation.warnings, forked.diff()));
+        }
+
+        let mut gas_used = 0;
+        if let TransactionCommand::ExerciseContract(cmd) = &transaction.command {
+            let engine = contract_engine.fork(forked.clone());
+            match engine.execute_contract(&cmd.contract_id, &cmd.choice_name, cmd.argument.clone(), &transaction.submitter).await {
+                Ok(execution) => {
+                    forked.record_events(&execution.events);
+                    if !execution.success {
+                        let mut failed = TransactionSimulationResult::failed(execution.errors.join("; "), validation.warnings, forked.diff());
+                        failed.gas_used = execution.gas_used;
+                        failed.fee = contract_engine.gas_schedule().fee(execution.gas_used);
+                        return Ok(failed);
+                    }
+                    gas_used = execution.gas_used;
+                }
+                Err(e) => {
+                    return Ok(TransactionSimulationResult::failed(e.to_string(), validation.warnings, forked.diff()));
+                }
+            }
+        }
+
+        Ok(TransactionSimulationResult {
+            success: true,
+            failure_reason: None,
+            warnings: validation.warnings,
+            diff: forked.diff(),
+            gas_used,
+            fee: contract_engine.gas_schedule().fee(gas_used),
+        })
+    }
+
     /// Get ledger view for the participant (privacy-preserving)
     pub async fn get_ledger_view(&self) -> GarpResult<LedgerView> {
         let contracts = self.storage.list_contracts(&self.participant_id, true).await?;
@@ -228,37 +290,47 @@ impl LocalLedger {
         Ok(state)
     }
 
-    /// Validate transaction signatures
-    async fn validate_signatures(&self, transaction: &Transaction) -> GarpResult<()> {
+    /// Verify each transaction signature against its signer's registered
+    /// key, rejecting signers who signed more than once
+    async fn validate_signatures(&self, transaction: &Transaction) -> GarpResult<SignatureSet> {
         if transaction.signatures.is_empty() {
-            return Err(TransactionError::MissingSignatures.into());
+            return Err(TransactionError::MissingSignature(transaction.submitter.clone()).into());
         }
 
-        // Create message to verify
-        let message = self.create_signature_message(transaction)?;
-
+        let message = transaction.signing_bytes()?;
+        let mut signatures = SignatureSet::new();
         for signature in &transaction.signatures {
-            if !self.crypto_service.verify_signature(&signature.public_key, &message, &signature.signature)? {
-                return Err(TransactionError::InvalidSignature(signature.signer.clone()).into());
-            }
+            let registered_key = self.registered_key(&signature.signer).await?
+                .ok_or_else(|| TransactionError::UnknownSigner(signature.signer.clone()))?;
+            let ed25519 = Signature {
+                algorithm: "Ed25519".to_string(),
+                signature: signature.signature.clone(),
+                public_key: signature.public_key.clone(),
+            };
+            signatures.insert_verified(signature.signer.clone(), ed25519, &message, &registered_key)?;
         }
 
-        Ok(())
+        Ok(signatures)
     }
 
-    /// Create message for signature verification
-    fn create_signature_message(&self, transaction: &Transaction) -> GarpResult<Vec<u8>> {
-        let mut message = Vec::new();
-        message.extend_from_slice(transaction.id.0.as_bytes());
-        message.extend_from_slice(transaction.submitter.0.as_bytes());
-        
-        let command_bytes = bincode::serialize(&transaction.command)
-            .map_err(|e| TransactionError::SerializationFailed(e.to_string()))?;
-        message.extend_from_slice(&command_bytes);
-        
-        message.extend_from_slice(&transaction.created_at.timestamp().to_le_bytes());
-        
-        Ok(message)
+    /// Register the public key `participant` signs transactions with
+    pub async fn register_participant_key(&self, participant: &ParticipantId, public_key: &[u8]) -> GarpResult<()> {
+        let key = format!("{}{}", PARTICIPANT_KEY_PREFIX, participant.0);
+        self.storage.put_metadata(&key, &serde_json::Value::String(hex::encode(public_key))).await
+    }
+
+    /// Registered public key of `participant`; this node's own participant
+    /// is registered with the node's key
+    async fn registered_key(&self, participant: &ParticipantId) -> GarpResult<Option<Vec<u8>>> {
+        let key = format!("{}{}", PARTICIPANT_KEY_PREFIX, participant.0);
+        match self.storage.get_metadata(&key).await? {
+            Some(serde_json::Value::String(public_key)) => hex::decode(&public_key).map(Some).map_err(|e| {
+                GarpError::StorageError(format!("Invalid registered key for {}: {}", participant.0, e))
+            }),
+            Some(_) => Err(GarpError::StorageError(format!("Invalid registered key for {}", participant.0))),
+            None if *participant == self.participant_id => Ok(Some(self.crypto_service.get_public_key()?)),
+            None => Ok(None),
+        }
     }
 
     /// Validate create contract command
@@ -382,20 +454,11 @@ impl LocalLedger {
         Ok(())
     }
 
-    /// Validate transaction authorization
-    async fn validate_authorization(&self, transaction: &Transaction, result: &mut ValidationResult) -> GarpResult<()> {
-        // Check if all required signatories have signed
-        let signed_by: HashSet<ParticipantId> = transaction.signatures.iter()
-            .map(|s| s.signer.clone())
-            .collect();
-
-        let required: HashSet<ParticipantId> = result.required_signatures.iter().cloned().collect();
-
-        for required_signer in &required {
-            if !signed_by.contains(required_signer) {
-                result.valid = false;
-                result.errors.push(format!("Missing signature from {}", required_signer.0));
-            }
+    /// Check that every required signatory has a verified signature
+    async fn validate_authorization(&self, signatures: &SignatureSet, result: &mut ValidationResult) -> GarpResult<()> {
+        for required_signer in signatures.missing(&result.required_signatures) {
+            result.valid = false;
+            result.errors.push(format!("Missing signature from {}", required_signer.0));
         }
 
         Ok(())
@@ -426,13 +489,13 @@ impl LocalLedger {
             }
             TransactionCommand::TransferAsset(cmd) => {
                 // Debit sender
-                self.storage.update_asset_balance(&cmd.from, &cmd.asset, -(cmd.asset.amount as i64)).await?;
+                apply_balance_change(self.storage.as_ref(), &cmd.from, transaction, Some(cmd.to.clone()), &cmd.asset, -(cmd.asset.amount as i64)).await?;
                 // Credit receiver
-                self.storage.update_asset_balance(&cmd.to, &cmd.asset, cmd.asset.amount as i64).await?;
+                apply_balance_change(self.storage.as_ref(), &cmd.to, transaction, Some(cmd.from.clone()), &cmd.asset, cmd.asset.amount as i64).await?;
             }
             TransactionCommand::CreateAsset(cmd) => {
                 // Credit the owner
-                self.storage.update_asset_balance(&cmd.owner, &cmd.asset, cmd.asset.amount as i64).await?;
+                apply_balance_change(self.storage.as_ref(), &cmd.owner, transaction, None, &cmd.asset, cmd.asset.amount as i64).await?;
             }
         }
 
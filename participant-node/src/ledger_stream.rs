@@ -0,0 +1,267 @@
+//! Per-participant ledger feed served on `/ws/ledger`
+//!
+//! A subscriber opens the socket and sends `{"from_slot": n}`, or `{}` to
+//! follow from the next block. The node answers with one frame per stored
+//! block from that slot on: stored history first, then new blocks as they
+//! land in storage. Each frame carries only the transactions the subscriber
+//! is a party to, so a frame may list none; the block itself is still sent
+//! so the subscriber knows which slot to resume from.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use garp_common::{Block, ErrorCode, GarpError, GarpResult, ParticipantId, Transaction, TransactionCommand};
+
+use crate::storage::StorageBackend;
+
+/// How often a caught-up subscription checks storage for new blocks
+pub const LEDGER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Most slots read from storage in one pass, so a long replay keeps
+/// interleaving with socket sends
+pub const MAX_SLOTS_PER_PASS: u64 = 256;
+
+/// First message of a subscription
+#[derive(Debug, Default, Deserialize)]
+pub struct LedgerSubscribeRequest {
+    /// First slot to send; `None` follows from the next block
+    #[serde(default)]
+    pub from_slot: Option<u64>,
+}
+
+/// Transaction summary listed in a block
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerBlockTx {
+    pub id: String,
+    pub submitter: String,
+    pub command_type: String,
+}
+
+/// Block header fields, with the subscriber's transactions
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerBlock {
+    pub slot: u64,
+    pub hash: String,
+    pub parent_hash: String,
+    pub timestamp_ms: i64,
+    pub leader: String,
+    pub transactions: Vec<LedgerBlockTx>,
+}
+
+/// Committed transaction the subscriber is a party to
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerTransaction {
+    pub id: String,
+    pub submitter: String,
+    pub status: String,
+    pub created_at: i64,
+}
+
+/// Message sent to a subscriber
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LedgerFrame {
+    Block {
+        block: LedgerBlock,
+        transactions: Vec<LedgerTransaction>,
+    },
+    /// Shaped like a JSON-RPC error; the subscription ends after it
+    Error {
+        code: i64,
+        message: String,
+        data: serde_json::Value,
+    },
+}
+
+impl LedgerFrame {
+    pub fn error(code: ErrorCode, message: impl Into<String>) -> Self {
+        LedgerFrame::Error { code: code.code(), message: message.into(), data: code.rpc_data() }
+    }
+}
+
+impl From<GarpError> for LedgerFrame {
+    fn from(error: GarpError) -> Self {
+        Self::error(error.code(), error.to_string())
+    }
+}
+
+/// Whether `participant` is a party to `tx`: its submitter, a signatory or
+/// observer of the contract it creates, or a side of the asset movement.
+/// Exercises and archives are only visible to their submitter, since the
+/// contract's stakeholders are not part of the transaction.
+pub fn is_visible_to(tx: &Transaction, participant: &ParticipantId) -> bool {
+    if &tx.submitter == participant {
+        return true;
+    }
+    match &tx.command {
+        TransactionCommand::CreateContract(cmd) => {
+            cmd.signatories.contains(participant) || cmd.observers.contains(participant)
+        }
+        TransactionCommand::TransferAsset(cmd) => &cmd.from == participant || &cmd.to == participant,
+        TransactionCommand::CreateAsset(cmd) => &cmd.owner == participant,
+        TransactionCommand::ExerciseContract(_) | TransactionCommand::ArchiveContract(_) => false,
+    }
+}
+
+fn command_type(command: &TransactionCommand) -> &'static str {
+    match command {
+        TransactionCommand::CreateContract(_) => "CreateContract",
+        TransactionCommand::ExerciseContract(_) => "ExerciseContract",
+        TransactionCommand::ArchiveContract(_) => "ArchiveContract",
+        TransactionCommand::TransferAsset(_) => "TransferAsset",
+        TransactionCommand::CreateAsset(_) => "CreateAsset",
+    }
+}
+
+/// Frame for `block` as seen by `participant`
+pub fn block_frame(block: &Block, participant: &ParticipantId) -> LedgerFrame {
+    let visible: Vec<&Transaction> = block.transactions.iter().filter(|tx| is_visible_to(tx, participant)).collect();
+    LedgerFrame::Block {
+        block: LedgerBlock {
+            slot: block.header.slot,
+            hash: hex::encode(&block.hash),
+            parent_hash: hex::encode(&block.header.parent_hash),
+            timestamp_ms: block.timestamp.timestamp_millis(),
+            leader: block.header.proposer.0.clone(),
+            transactions: visible
+                .iter()
+                .map(|tx| LedgerBlockTx {
+                    id: tx.id.0.to_string(),
+                    submitter: tx.submitter.0.clone(),
+                    command_type: command_type(&tx.command).to_string(),
+                })
+                .collect(),
+        },
+        transactions: visible
+            .iter()
+            .map(|tx| LedgerTransaction {
+                id: tx.id.0.to_string(),
+                submitter: tx.submitter.0.clone(),
+                status: "committed".to_string(),
+                created_at: tx.created_at.timestamp_millis(),
+            })
+            .collect(),
+    }
+}
+
+/// Slot a subscription starts at: the requested slot, or the one after the
+/// latest stored block
+pub async fn start_slot(storage: &dyn StorageBackend, request: &LedgerSubscribeRequest) -> GarpResult<u64> {
+    if let Some(from_slot) = request.from_slot {
+        return Ok(from_slot);
+    }
+    Ok(storage.get_latest_block().await?.map_or(0, |block| block.header.slot + 1))
+}
+
+/// Stored blocks from `next_slot` towards the latest, covering at most
+/// [`MAX_SLOTS_PER_PASS`] slots, and the slot to continue from
+pub async fn blocks_from(storage: &dyn StorageBackend, next_slot: u64) -> GarpResult<(Vec<Block>, u64)> {
+    let latest = match storage.get_latest_block().await? {
+        Some(block) if block.header.slot >= next_slot => block.header.slot,
+        _ => return Ok((Vec::new(), next_slot)),
+    };
+    let last = latest.min(next_slot.saturating_add(MAX_SLOTS_PER_PASS - 1));
+    let mut blocks = Vec::new();
+    for slot in next_slot..=last {
+        if let Some(block) = storage.get_block_by_slot(slot).await? {
+            blocks.push(block);
+        }
+    }
+    Ok((blocks, last + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use chrono::Utc;
+    use garp_common::{ArchiveContractCommand, Asset, AssetType, BlockHeader, ContractId, TransactionId, TransferAssetCommand};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn tx(submitter: &str, command: TransactionCommand) -> Transaction {
+        Transaction {
+            id: TransactionId(Uuid::new_v4()),
+            submitter: ParticipantId::new(submitter),
+            command,
+            created_at: Utc::now(),
+            signatures: Vec::new(),
+            encrypted_payload: None,
+        }
+    }
+
+    fn transfer(from: &str, to: &str) -> Transaction {
+        tx(
+            from,
+            TransactionCommand::TransferAsset(TransferAssetCommand {
+                from: ParticipantId::new(from),
+                to: ParticipantId::new(to),
+                asset: Asset { id: "USD".to_string(), asset_type: AssetType::Currency, amount: 5, metadata: HashMap::new() },
+            }),
+        )
+    }
+
+    fn block(slot: u64, transactions: Vec<Transaction>) -> Block {
+        Block {
+            header: BlockHeader {
+                parent_hash: vec![0; 32],
+                slot,
+                epoch: 0,
+                proposer: ParticipantId::new("validator"),
+                state_root: vec![0; 32],
+                tx_root: vec![0; 32],
+                receipt_root: vec![0; 32],
+            },
+            hash: vec![slot as u8; 32],
+            timestamp: Utc::now(),
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test_frames_only_carry_the_subscribers_transactions() {
+        let alice = ParticipantId::new("alice");
+        let archive = tx("bob", TransactionCommand::ArchiveContract(ArchiveContractCommand { contract_id: ContractId(Uuid::new_v4()) }));
+        let b = block(7, vec![transfer("alice", "bob"), transfer("bob", "alice"), transfer("bob", "carol"), archive]);
+
+        match block_frame(&b, &alice) {
+            LedgerFrame::Block { block, transactions } => {
+                assert_eq!(block.slot, 7);
+                assert_eq!(block.transactions.len(), 2);
+                assert_eq!(transactions.len(), 2);
+                assert!(transactions.iter().all(|t| t.status == "committed"));
+            }
+            other => panic!("unexpected frame {:?}", other),
+        }
+        match block_frame(&b, &ParticipantId::new("dave")) {
+            LedgerFrame::Block { transactions, .. } => assert!(transactions.is_empty()),
+            other => panic!("unexpected frame {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_walks_stored_slots_in_passes() {
+        let storage = MemoryStorage::new();
+        for slot in [1, 2, 4, MAX_SLOTS_PER_PASS + 10] {
+            storage.store_block(&block(slot, Vec::new())).await.unwrap();
+        }
+
+        // Following from the head starts after the latest block
+        assert_eq!(start_slot(&storage, &LedgerSubscribeRequest::default()).await.unwrap(), MAX_SLOTS_PER_PASS + 11);
+        assert_eq!(start_slot(&storage, &LedgerSubscribeRequest { from_slot: Some(2) }).await.unwrap(), 2);
+
+        let (blocks, next) = blocks_from(&storage, 2).await.unwrap();
+        assert_eq!(blocks.iter().map(|b| b.header.slot).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(next, MAX_SLOTS_PER_PASS + 2);
+
+        let (blocks, next) = blocks_from(&storage, next).await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(next, MAX_SLOTS_PER_PASS + 11);
+
+        // Caught up: nothing to send, stay put
+        let (blocks, caught_up) = blocks_from(&storage, next).await.unwrap();
+        assert!(blocks.is_empty());
+        assert_eq!(caught_up, next);
+    }
+}
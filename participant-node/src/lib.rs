@@ -1,11 +1,17 @@
 pub mod api;
+pub mod auth;
+pub mod body_limit;
 pub mod config;
 pub mod contract_compiler;
 pub mod contract_debug;
 pub mod contract_engine;
+pub mod contract_registry;
+pub mod contract_schema;
 pub mod contract_stdlib;
 pub mod contract_testing;
+pub mod event_index;
 pub mod ledger;
+pub mod ledger_stream;
 pub mod mempool;
 pub mod merkle;
 pub mod network_layer;
@@ -15,9 +21,14 @@ pub mod privacy_dsl;
 pub mod privacy_engine;
 pub mod private_state;
 pub mod private_transactions;
+pub mod rate_limit;
+pub mod resync;
+pub mod response_signing;
 pub mod secure_execution;
+pub mod simulation;
 pub mod state_commitments;
 pub mod storage;
+pub mod tx_index;
 pub mod wallet;
 pub mod wasm_runtime;
 pub mod zk_system;
@@ -72,6 +72,52 @@ pub fn merkle_proof(leaves: &[Vec<u8>], index: usize) -> Option<MerkleProof> {
     Some(MerkleProof { leaf: leaf_hash, root: level[0].to_vec(), path, directions: dirs })
 }
 
+/// Build proofs for several leaves of the same tree, hashing each level only once.
+/// Entries are `None` for indices outside `leaves`.
+pub fn merkle_proofs(leaves: &[Vec<u8>], indices: &[usize]) -> Vec<Option<MerkleProof>> {
+    if leaves.is_empty() {
+        return indices.iter().map(|_| None).collect();
+    }
+    let mut levels: Vec<Vec<[u8; 32]>> = vec![leaves.iter().map(|l| hash_leaf(l)).collect()];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = &levels[levels.len() - 1];
+        let mut next = Vec::with_capacity((level.len() + 1) / 2);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { left };
+            let mut hasher = Sha256::new();
+            hasher.update(left);
+            hasher.update(right);
+            next.push(hasher.finalize().into());
+            i += 2;
+        }
+        levels.push(next);
+    }
+    let root = levels[levels.len() - 1][0].to_vec();
+
+    indices
+        .iter()
+        .map(|&index| {
+            if index >= leaves.len() {
+                return None;
+            }
+            let mut idx = index;
+            let mut path = Vec::with_capacity(levels.len() - 1);
+            let mut dirs = Vec::with_capacity(levels.len() - 1);
+            for level in &levels[..levels.len() - 1] {
+                let is_right = idx % 2 == 1;
+                let sibling_idx = if is_right { idx - 1 } else { idx + 1 };
+                let sibling = if sibling_idx < level.len() { level[sibling_idx] } else { level[idx] };
+                path.push(sibling.to_vec());
+                dirs.push(is_right);
+                idx /= 2;
+            }
+            Some(MerkleProof { leaf: levels[0][index].to_vec(), root: root.clone(), path, directions: dirs })
+        })
+        .collect()
+}
+
 pub fn verify_proof(proof: &MerkleProof) -> bool {
     let mut current = proof.leaf.clone();
     for (i, sibling) in proof.path.iter().enumerate() {
@@ -88,4 +134,50 @@ pub fn verify_proof(proof: &MerkleProof) -> bool {
         current = hasher.finalize().to_vec();
     }
     current == proof.root
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shared with the SDK's proof tests so both sides agree on the encoding
+    const ABC_ROOT: &str = "d31a37ef6ac14a2db1470c4316beb5592e6afd4465022339adafda76a18ffabe";
+
+    fn abc() -> Vec<Vec<u8>> {
+        vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]
+    }
+
+    #[test]
+    fn test_known_vector() {
+        assert_eq!(hex::encode(merkle_root(&abc())), ABC_ROOT);
+
+        let proof = merkle_proof(&abc(), 2).unwrap();
+        assert_eq!(hex::encode(&proof.leaf), "2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6");
+        assert_eq!(
+            proof.path.iter().map(hex::encode).collect::<Vec<_>>(),
+            vec![
+                "2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6",
+                "e5a01fee14e0ed5c48714f22180f25ad8365b53f9779f79dc4a3d7e93963f94a",
+            ]
+        );
+        assert_eq!(proof.directions, vec![false, true]);
+        assert!(verify_proof(&proof));
+    }
+
+    #[test]
+    fn test_batch_proofs_match_single_proofs() {
+        let leaves: Vec<Vec<u8>> = (0u8..7).map(|i| vec![i]).collect();
+        let proofs = merkle_proofs(&leaves, &[0, 3, 6, 7]);
+        assert!(proofs[3].is_none());
+
+        for (proof, index) in proofs.iter().take(3).zip([0, 3, 6]) {
+            let proof = proof.as_ref().unwrap();
+            let single = merkle_proof(&leaves, index).unwrap();
+            assert_eq!(proof.leaf, single.leaf);
+            assert_eq!(proof.path, single.path);
+            assert_eq!(proof.directions, single.directions);
+            assert_eq!(proof.root, merkle_root(&leaves));
+            assert!(verify_proof(proof));
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use garp_common::{
     ParticipantId, SyncDomainId, Transaction, Contract, Asset, WalletBalance,
     NetworkMessage, NetworkManager, MessageHandler, PeerInfo,
-    CryptoService, GarpResult, GarpError, NetworkError,
+    CryptoService, GarpResult, GarpError, NetworkError, TransactionCommand,
 };
 use garp_common::timing::slot_at_time;
 use crate::consensus::{leader_for_slot, TowerBft, ForkGraph};
 use crate::mempool::{Mempool, MempoolConfig};
 use crate::block_builder::BlockBuilder;
+use crate::resync::{HttpBlockSource, ResyncManager};
 use crate::{
     config::Config,
     storage::{StorageBackend, Storage},
@@ -14,6 +15,10 @@ use crate::{
     api::ApiServer,
     wallet::WalletManager,
     contract_engine::ContractEngine,
+    contract_registry::TemplateRegistry,
+    contract_schema::TemplateSchemas,
+    tx_index::TransactionIndex,
+    event_index::EventIndex,
 };
 use std::sync::Arc;
 use tokio::sync::{RwLock, broadcast};
@@ -42,6 +47,12 @@ pub struct ParticipantNode {
     wallet: Arc<WalletManager>,
     /// Contract execution engine
     contract_engine: Arc<ContractEngine>,
+    /// Versioned contract templates
+    template_registry: Arc<TemplateRegistry>,
+    /// Transaction status index
+    transaction_index: Arc<TransactionIndex>,
+    /// Contract event consumer checkpoints and retention
+    event_index: Arc<EventIndex>,
     /// Network manager for peer communication
     network: Arc<NetworkManager>,
     /// Transaction mempool
@@ -64,6 +75,8 @@ pub struct ParticipantNode {
     sync_last_applied_height: Arc<RwLock<u64>>,
     /// Sync last applied time
     sync_last_applied_time: Arc<RwLock<Option<chrono::DateTime<Utc>>>>,
+    /// Manual resync from the global synchronizer
+    resync: Arc<ResyncManager>,
     // Consensus state
     consensus_tower: Arc<RwLock<TowerBft>>,
     fork_graph: Arc<RwLock<ForkGraph>>,
@@ -119,7 +132,15 @@ impl ParticipantNode {
         let contract_engine = Arc::new(ContractEngine::new(
             storage.clone(),
             crypto_service.clone(),
+        )
+        .with_execution_limits(config.execution)
+        .with_gas_schedule(config.gas));
+        let template_registry = Arc::new(TemplateRegistry::new(storage.clone()));
+        let transaction_index = Arc::new(TransactionIndex::new(
+            config.participant_config.participant_id.clone(),
+            storage.clone(),
         ));
+        let event_index = Arc::new(EventIndex::new(storage.clone(), config.event_retention.clone()));
 
         // Initialize network manager with real network layer
         let network_layer = Arc::new(RealNetworkLayer::new(config.network.clone()));
@@ -141,12 +162,22 @@ impl ParticipantNode {
         let consensus_tower = Arc::new(RwLock::new(TowerBft::new(voting_power, 0.667)));
         let fork_graph = Arc::new(RwLock::new(ForkGraph::new()));
 
+        let sync_last_applied_height = Arc::new(RwLock::new(0));
+        let resync = Arc::new(ResyncManager::new(
+            storage.clone(),
+            Arc::new(HttpBlockSource::new(std::env::var("SYNCHRONIZER_URL").unwrap_or_default())),
+            sync_last_applied_height.clone(),
+        ));
+
         let node = Self {
             participant_id: config.participant_config.participant_id.clone(),
             config,
             ledger,
             wallet,
             contract_engine,
+            template_registry,
+            transaction_index,
+            event_index,
             network,
             mempool,
             crypto_service,
@@ -156,8 +187,9 @@ impl ParticipantNode {
             shutdown_tx: None,
             global_head: Arc::new(RwLock::new(GlobalHead { height: 0, hash: String::new() })),
             highest_finalized_slot: Arc::new(RwLock::new(0)),
-            sync_last_applied_height: Arc::new(RwLock::new(0)),
+            sync_last_applied_height,
             sync_last_applied_time: Arc::new(RwLock::new(None)),
+            resync,
             consensus_tower,
             fork_graph,
         };
@@ -178,6 +210,12 @@ impl ParticipantNode {
         // Connect to sync domains
         self.connect_to_sync_domains().await?;
 
+        let templates = self.template_registry.load().await?;
+        info!("Loaded {} contract template versions", templates);
+
+        let indexed = self.transaction_index.load().await?;
+        info!("Loaded transaction index with {} transactions", indexed);
+
         // API server is spawned from main.rs using Arc<ParticipantNode>
 
         // Initialize local head height from file if present
@@ -254,6 +292,45 @@ impl ParticipantNode {
     pub async fn submit_transaction(&self, transaction: Transaction) -> GarpResult<ValidationResult> {
         debug!("Submitting transaction {}", transaction.id.0);
 
+        // Blocks above the resync height are being rewritten; hold off until they are rebuilt
+        if self.resync.is_active() {
+            let from_height = self.resync.progress().await.from_height;
+            return Ok(ValidationResult {
+                valid: false,
+                errors: vec![format!("Node is resyncing blocks above height {}", from_height)],
+                warnings: Vec::new(),
+                affected_contracts: Vec::new(),
+                required_signatures: Vec::new(),
+            });
+        }
+
+        // Contracts can only be created from a registered template version
+        if let TransactionCommand::CreateContract(cmd) = &transaction.command {
+            if let Err(errors) = self.template_registry.validate_argument(&cmd.template_id, None, &cmd.argument) {
+                warn!("Transaction {} rejected by template registry: {}", transaction.id.0, errors);
+                return Ok(ValidationResult {
+                    valid: false,
+                    errors: errors.violations.iter().map(|v| v.to_string()).collect(),
+                    warnings: Vec::new(),
+                    affected_contracts: Vec::new(),
+                    required_signatures: Vec::new(),
+                });
+            }
+        }
+
+        // Reject arguments that do not match the template schema up front
+        let violations = self.contract_engine.validate_command_arguments(&transaction.command).await?;
+        if !violations.is_empty() {
+            warn!("Transaction {} failed schema validation: {:?}", transaction.id.0, violations);
+            return Ok(ValidationResult {
+                valid: false,
+                errors: violations.iter().map(|v| v.to_string()).collect(),
+                warnings: Vec::new(),
+                affected_contracts: Vec::new(),
+                required_signatures: Vec::new(),
+            });
+        }
+
         // Validate transaction locally first
         let validation = self.ledger.validate_transaction(&transaction).await?;
         if !validation.valid {
@@ -271,12 +348,20 @@ impl ParticipantNode {
 
         // Broadcast to sync domains if valid
         if result.valid {
+            if let Err(e) = self.transaction_index.record_submitted(&transaction).await {
+                warn!("Failed to index transaction {}: {}", transaction.id.0, e);
+            }
             self.broadcast_transaction_to_sync_domains(transaction).await?;
         }
 
         Ok(result)
     }
 
+    /// Replace the argument schemas of a contract template
+    pub fn update_template_schemas(&self, template_id: &str, schemas: TemplateSchemas) -> GarpResult<()> {
+        self.contract_engine.update_template_schemas(template_id, schemas)
+    }
+
     /// Submit a transaction to the local mempool with a fee for prioritization
     pub async fn submit_to_mempool(&self, transaction: Transaction, fee: u64) -> GarpResult<()> {
         self.mempool.submit(transaction, fee).await
@@ -292,6 +377,11 @@ impl ParticipantNode {
         self.ledger.get_ledger_view().await
     }
 
+    /// Simulate a transaction against a forked ledger view without mutating state
+    pub async fn simulate_transaction(&self, transaction: &Transaction) -> GarpResult<crate::simulation::SimulationResult> {
+        self.ledger.simulate_transaction(transaction, &self.contract_engine).await
+    }
+
     /// Simulate TxV2 via ledger interface
     pub async fn simulate_transaction_v2(&self, tx: &garp_common::TxV2) -> GarpResult<crate::ledger::SimulationResult> {
         self.ledger.simulate_v2(tx).await
@@ -426,6 +516,28 @@ impl ParticipantNode {
             }
         }});
 
+        // Event retention task
+        let event_index = self.event_index.clone();
+        let prune_interval = Duration::from_secs(self.config.event_retention.prune_interval_secs);
+        tokio::spawn({
+            let mut shutdown_rx = shutdown_tx.subscribe();
+            async move {
+            let mut interval = interval(prune_interval);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = event_index.apply_retention(Utc::now()).await {
+                            error!("Failed to prune contract events: {}", e);
+                        }
+                    }
+                    _ = shutdown_rx.recv() => {
+                        debug!("Event retention task shutting down");
+                        break;
+                    }
+                }
+            }
+        }});
+
         // Sync task: poll global synchronizer for latest block and refresh local checkpoint
         let sync_interval = Duration::from_secs(30); // Sync every 30 seconds
         let synchronizer_url = std::env::var("SYNCHRONIZER_URL").unwrap_or_default();
@@ -433,6 +545,8 @@ impl ParticipantNode {
         let ledger_for_sync = self.ledger.clone();
         let sync_last_applied_height = self.sync_last_applied_height.clone();
         let sync_last_applied_time = self.sync_last_applied_time.clone();
+        let resync = self.resync.clone();
+        let transaction_index = self.transaction_index.clone();
         tokio::spawn({
             let mut shutdown_rx = shutdown_tx.subscribe();
             async move {
@@ -440,7 +554,9 @@ impl ParticipantNode {
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        if !synchronizer_url.is_empty() {
+                        if resync.is_active() {
+                            debug!("Resync in progress; skipping block sync poll");
+                        } else if !synchronizer_url.is_empty() {
                             if let Err(e) = poll_latest_block(&synchronizer_url, global_head.clone()).await {
                                 warn!("Sync poll error: {}", e);
                             } else {
@@ -448,7 +564,7 @@ impl ParticipantNode {
                                     error!("Failed to refresh ledger checkpoint after sync: {}", e);
                                 }
                                 // Apply any new finalized blocks to local ledger
-                                if let Err(e) = apply_finalized_blocks(&synchronizer_url, global_head.clone(), ledger_for_sync.clone(), sync_last_applied_height.clone()).await {
+                                if let Err(e) = apply_finalized_blocks(&synchronizer_url, global_head.clone(), ledger_for_sync.clone(), transaction_index.clone(), sync_last_applied_height.clone()).await {
                                     warn!("Apply finalized blocks error: {}", e);
                                 }
                                 {
@@ -503,6 +619,7 @@ impl ParticipantNode {
         let global_head = self.global_head.clone();
         let forks = self.fork_graph.clone();
         let storage = self.storage.clone();
+        let transaction_index = self.transaction_index.clone();
         tokio::spawn({
             let mut shutdown_rx = shutdown_tx.subscribe();
             async move {
@@ -538,6 +655,12 @@ impl ParticipantNode {
                         // Persist the proposed block
                         if let Err(e) = storage.store_block(&block).await {
                             warn!("Failed to persist proposed block: {}", e);
+                        } else {
+                            for (position, tx) in block.transactions.iter().enumerate() {
+                                if let Err(e) = transaction_index.record_sequenced(tx, block.header.slot, position as u32).await {
+                                    warn!("Failed to index sequenced transaction {}: {}", tx.id.0, e);
+                                }
+                            }
                         }
 
                         // Map proposal to block hash and broadcast proposal for voting
@@ -604,7 +727,7 @@ async fn poll_latest_block(base: &str, head: Arc<RwLock<GlobalHead>>) -> Result<
     Ok(())
 }
 
-async fn apply_finalized_blocks(base: &str, head: Arc<RwLock<GlobalHead>>, ledger: Arc<LocalLedger>, last_applied: Arc<RwLock<u64>>) -> Result<(), String> {
+async fn apply_finalized_blocks(base: &str, head: Arc<RwLock<GlobalHead>>, ledger: Arc<LocalLedger>, transaction_index: Arc<TransactionIndex>, last_applied: Arc<RwLock<u64>>) -> Result<(), String> {
     use hyper::{Client, Request};
     use hyper::body::to_bytes;
     use hyper::http::Uri;
@@ -622,7 +745,8 @@ async fn apply_finalized_blocks(base: &str, head: Arc<RwLock<GlobalHead>>, ledge
         let data = match v.get("data") { Some(d) => d, None => continue };
         // Get transaction IDs
         let txids: Vec<String> = data.get("transaction_ids").and_then(|a| a.as_array()).map(|arr| arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()).unwrap_or_default();
-        for txid in txids {
+        let slot = data.get("slot").and_then(|s| s.as_u64()).unwrap_or(h);
+        for (position, txid) in txids.into_iter().enumerate() {
             // Fetch transaction details
             let turl = format!("{}/api/v1/transactions/{}/details", base, txid);
             let turi: Uri = turl.parse().map_err(|e| e.to_string())?;
@@ -637,8 +761,13 @@ async fn apply_finalized_blocks(base: &str, head: Arc<RwLock<GlobalHead>>, ledge
                 let tx: Result<garp_common::types::Transaction, _> = serde_json::from_value(tj.clone());
                 if let Ok(tx) = tx {
                     // Apply transaction to local ledger without broadcasting
-                    if let Err(e) = ledger.submit_transaction(tx).await {
-                        warn!("Failed to apply transaction to local ledger: {}", e);
+                    match ledger.submit_transaction(tx.clone()).await {
+                        Ok(_) => {
+                            if let Err(e) = transaction_index.record_applied(&tx, slot, position as u32).await {
+                                warn!("Failed to index applied transaction {}: {}", tx.id.0, e);
+                            }
+                        }
+                        Err(e) => warn!("Failed to apply transaction to local ledger: {}", e),
                     }
                 }
             }
@@ -675,6 +804,11 @@ async fn apply_finalized_blocks(base: &str, head: Arc<RwLock<GlobalHead>>, ledge
         self.wallet.get_balance().await
     }
 
+    /// Get chronological wallet history with running balances
+    pub async fn get_wallet_history(&self, asset_type: Option<String>, limit: Option<u32>, offset: Option<u32>) -> GarpResult<Vec<crate::storage::WalletHistoryEntry>> {
+        self.wallet.get_history(asset_type, limit, offset).await
+    }
+
     /// Transfer asset to another participant
     pub async fn transfer_asset(&self, to: ParticipantId, asset: Asset) -> GarpResult<garp_common::TransactionId> {
         self.wallet.transfer_asset(to, asset).await
@@ -698,6 +832,26 @@ async fn apply_finalized_blocks(base: &str, head: Arc<RwLock<GlobalHead>>, ledge
         (*self.sync_last_applied_height.read().await, self.sync_last_applied_time.read().await.clone())
     }
 
+    /// Resync manager for rebuilding local blocks from the global synchronizer
+    pub fn resync(&self) -> Arc<ResyncManager> {
+        self.resync.clone()
+    }
+
+    /// Versioned contract template registry
+    pub fn template_registry(&self) -> Arc<TemplateRegistry> {
+        self.template_registry.clone()
+    }
+
+    /// Transaction status index
+    pub fn transaction_index(&self) -> Arc<TransactionIndex> {
+        self.transaction_index.clone()
+    }
+
+    /// Contract event consumer checkpoints and retention
+    pub fn event_index(&self) -> Arc<EventIndex> {
+        self.event_index.clone()
+    }
+
     /// Public node stats used by API
     pub async fn get_node_stats(&self) -> GarpResult<PublicNodeStats> {
         let ledger_stats = self.ledger.get_stats().await?;
@@ -0,0 +1,227 @@
+//! Token-bucket rate limiting for the participant API
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::{
+    extract::State,
+    http::{header::RETRY_AFTER, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::auth::AuthenticatedKey;
+use crate::config::{ApiConfig, RateLimitConfig};
+
+/// Route class used to select a rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteClass {
+    Read,
+    Write,
+}
+
+impl RouteClass {
+    /// Classify a request by HTTP method
+    pub fn from_method(method: &Method) -> Self {
+        if method == Method::GET || method == Method::HEAD {
+            RouteClass::Read
+        } else {
+            RouteClass::Write
+        }
+    }
+}
+
+/// Outcome of a rate limit check
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitDecision {
+    Allowed,
+    Limited { retry_after: Duration },
+}
+
+/// Snapshot of a single bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketLevel {
+    pub key: String,
+    pub class: RouteClass,
+    pub tokens: f64,
+    pub capacity: u32,
+}
+
+/// Storage backend for rate limiter state
+#[async_trait]
+pub trait RateLimiterBackend: Send + Sync {
+    /// Take one token from the bucket for `key` and `class`
+    async fn check(&self, key: &str, class: RouteClass) -> RateLimitDecision;
+
+    /// Current bucket levels, for observability
+    async fn bucket_levels(&self) -> Vec<BucketLevel>;
+}
+
+#[derive(Debug, Clone)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// In-process token-bucket limiter
+pub struct InMemoryRateLimiter {
+    read_limit: RateLimitConfig,
+    write_limit: RateLimitConfig,
+    buckets: parking_lot::Mutex<HashMap<(String, RouteClass), Bucket>>,
+}
+
+impl InMemoryRateLimiter {
+    /// Create limiter with separate read and write limits
+    pub fn new(read_limit: RateLimitConfig, write_limit: RateLimitConfig) -> Self {
+        Self { read_limit, write_limit, buckets: parking_lot::Mutex::new(HashMap::new()) }
+    }
+
+    /// Create limiter from API configuration
+    pub fn from_config(config: &ApiConfig) -> Self {
+        let write_limit = config.write_rate_limit.clone().unwrap_or_else(|| config.rate_limit.clone());
+        Self::new(config.rate_limit.clone(), write_limit)
+    }
+
+    fn limit_for(&self, class: RouteClass) -> &RateLimitConfig {
+        match class {
+            RouteClass::Read => &self.read_limit,
+            RouteClass::Write => &self.write_limit,
+        }
+    }
+
+    /// Check a bucket at an explicit point in time
+    pub fn check_at(&self, key: &str, class: RouteClass, now: Instant) -> RateLimitDecision {
+        let limit = self.limit_for(class);
+        let capacity = limit.burst_size.max(1) as f64;
+        let refill_per_sec = limit.requests_per_minute as f64 / 60.0;
+
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets
+            .entry((key.to_string(), class))
+            .or_insert(Bucket { tokens: capacity, last_refill: now });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return RateLimitDecision::Allowed;
+        }
+
+        let retry_after = if refill_per_sec > 0.0 {
+            Duration::from_secs_f64((1.0 - bucket.tokens) / refill_per_sec)
+        } else {
+            Duration::from_secs(60)
+        };
+        RateLimitDecision::Limited { retry_after }
+    }
+}
+
+#[async_trait]
+impl RateLimiterBackend for InMemoryRateLimiter {
+    async fn check(&self, key: &str, class: RouteClass) -> RateLimitDecision {
+        self.check_at(key, class, Instant::now())
+    }
+
+    async fn bucket_levels(&self) -> Vec<BucketLevel> {
+        let buckets = self.buckets.lock();
+        buckets
+            .iter()
+            .map(|((key, class), bucket)| BucketLevel {
+                key: key.clone(),
+                class: *class,
+                tokens: bucket.tokens,
+                capacity: self.limit_for(*class).burst_size,
+            })
+            .collect()
+    }
+}
+
+fn client_ip<B>(req: &Request<B>) -> String {
+    let headers = req.headers();
+    if let Some(ip) = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok()) {
+        ip.split(',').next().unwrap_or("unknown").trim().to_string()
+    } else if let Some(ip) = headers.get("x-real-ip").and_then(|v| v.to_str().ok()) {
+        ip.trim().to_string()
+    } else {
+        "unknown".to_string()
+    }
+}
+
+/// Axum middleware enforcing per-key (or per-IP) rate limits
+pub async fn rate_limit_middleware<B>(
+    State(limiter): State<Arc<dyn RateLimiterBackend>>,
+    req: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if req.uri().path() == "/health" {
+        return next.run(req).await;
+    }
+
+    let key = match req.extensions().get::<AuthenticatedKey>() {
+        Some(AuthenticatedKey(id)) => format!("key:{}", id),
+        None => format!("ip:{}", client_ip(&req)),
+    };
+    let class = RouteClass::from_method(req.method());
+
+    match limiter.check(&key, class).await {
+        RateLimitDecision::Allowed => next.run(req).await,
+        RateLimitDecision::Limited { retry_after } => {
+            let secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            warn!("Rate limit exceeded for {} on {}", key, req.uri().path());
+            (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, secs.to_string())]).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter() -> InMemoryRateLimiter {
+        InMemoryRateLimiter::new(
+            RateLimitConfig { requests_per_minute: 60, burst_size: 5 },
+            RateLimitConfig { requests_per_minute: 6, burst_size: 2 },
+        )
+    }
+
+    #[test]
+    fn test_burst_beyond_limit_is_rejected() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        assert_eq!(limiter.check_at("key:a", RouteClass::Write, now), RateLimitDecision::Allowed);
+        assert_eq!(limiter.check_at("key:a", RouteClass::Write, now), RateLimitDecision::Allowed);
+        match limiter.check_at("key:a", RouteClass::Write, now) {
+            RateLimitDecision::Limited { retry_after } => assert_eq!(retry_after, Duration::from_secs(10)),
+            other => panic!("expected limit, got {:?}", other),
+        }
+
+        // Reads and other clients use separate buckets
+        assert_eq!(limiter.check_at("key:a", RouteClass::Read, now), RateLimitDecision::Allowed);
+        assert_eq!(limiter.check_at("key:b", RouteClass::Write, now), RateLimitDecision::Allowed);
+    }
+
+    #[test]
+    fn test_bucket_recovers_after_window() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            assert_eq!(limiter.check_at("ip:1.2.3.4", RouteClass::Read, now), RateLimitDecision::Allowed);
+        }
+        assert!(matches!(limiter.check_at("ip:1.2.3.4", RouteClass::Read, now), RateLimitDecision::Limited { .. }));
+
+        let later = now + Duration::from_secs(5);
+        for _ in 0..5 {
+            assert_eq!(limiter.check_at("ip:1.2.3.4", RouteClass::Read, later), RateLimitDecision::Allowed);
+        }
+        assert!(matches!(limiter.check_at("ip:1.2.3.4", RouteClass::Read, later), RateLimitDecision::Limited { .. }));
+    }
+}
@@ -0,0 +1,130 @@
+//! HMAC-signed responses for write endpoints of the participant API
+//!
+//! Responses to `POST`, `PATCH` and `DELETE` requests carry
+//! `HMAC-SHA256(body || timestamp || request_id)` so clients holding the
+//! signing key can check offline that a response came from this node.
+
+use std::sync::Arc;
+
+use axum::{
+    body::{boxed, Full},
+    extract::State,
+    http::{HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::error;
+use uuid::Uuid;
+
+/// Hex-encoded HMAC-SHA256 of the response
+pub const SIGNATURE_HEADER: &str = "x-garp-response-signature";
+/// Unix timestamp (seconds) included in the signature
+pub const TIMESTAMP_HEADER: &str = "x-garp-timestamp";
+/// Request id included in the signature; taken from the request or generated
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Signs write responses with the key from `ApiConfig::response_signing_key`
+pub struct SignedResponseMiddleware {
+    key: Vec<u8>,
+}
+
+impl SignedResponseMiddleware {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self { key: key.into() }
+    }
+
+    /// Whether responses to `method` are signed
+    pub fn applies_to(method: &Method) -> bool {
+        method == Method::POST || method == Method::PATCH || method == Method::DELETE
+    }
+
+    /// Hex-encoded signature over `body || timestamp || request_id`
+    pub fn sign(&self, body: &[u8], timestamp: &str, request_id: &str) -> Result<String, String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).map_err(|e| e.to_string())?;
+        mac.update(body);
+        mac.update(timestamp.as_bytes());
+        mac.update(request_id.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+/// Axum middleware adding signature headers to write responses
+pub async fn signed_response_middleware<B>(
+    State(signer): State<Arc<SignedResponseMiddleware>>,
+    req: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if !SignedResponseMiddleware::applies_to(req.method()) {
+        return next.run(req).await;
+    }
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let (mut parts, body) = next.run(req).await.into_parts();
+    let body = match hyper::body::to_bytes(body).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!("Failed to buffer response for signing {} {}: {}", method, path, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let signature = match signer.sign(&body, &timestamp, &request_id) {
+        Ok(signature) => signature,
+        Err(e) => {
+            error!("Failed to sign response for {} {}: {}", method, path, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let headers = [
+        (SIGNATURE_HEADER, signature),
+        (TIMESTAMP_HEADER, timestamp),
+        (REQUEST_ID_HEADER, request_id),
+    ];
+    for (name, value) in headers {
+        match HeaderValue::from_str(&value) {
+            Ok(value) => {
+                parts.headers.insert(name, value);
+            }
+            Err(e) => {
+                error!("Invalid {} header for {} {}: {}", name, method, path, e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        }
+    }
+    Response::from_parts(parts, boxed(Full::from(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_write_methods_are_signed() {
+        assert!(SignedResponseMiddleware::applies_to(&Method::POST));
+        assert!(SignedResponseMiddleware::applies_to(&Method::PATCH));
+        assert!(SignedResponseMiddleware::applies_to(&Method::DELETE));
+        assert!(!SignedResponseMiddleware::applies_to(&Method::GET));
+        assert!(!SignedResponseMiddleware::applies_to(&Method::HEAD));
+    }
+
+    #[test]
+    fn test_signature_vector() {
+        // Shared with the SDK's verify_response_signature tests
+        let signer = SignedResponseMiddleware::new("secret");
+        let signature = signer.sign(br#"{"success":true}"#, "1700000000", "req-1").unwrap();
+        assert_eq!(signature, "32d1f6fbb009c43a589e61c507314b7f57fa25f668bc02778e7b236777235276");
+        assert_ne!(signer.sign(br#"{"success":true}"#, "1700000001", "req-1").unwrap(), signature);
+    }
+}
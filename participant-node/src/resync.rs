@@ -0,0 +1,268 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use garp_common::{Block, GarpError, GarpResult};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::storage::StorageBackend;
+
+/// Source of canonical finalized blocks (global synchronizer or sync domain)
+#[async_trait]
+pub trait BlockSource: Send + Sync {
+    /// Height of the latest finalized block
+    async fn latest_height(&self) -> GarpResult<u64>;
+    /// Finalized block at `height`, if the source has it
+    async fn fetch_block(&self, height: u64) -> GarpResult<Option<Block>>;
+}
+
+/// Block source backed by the global synchronizer HTTP API
+pub struct HttpBlockSource {
+    base_url: String,
+}
+
+impl HttpBlockSource {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self { base_url: base_url.into().trim_end_matches('/').to_string() }
+    }
+
+    async fn get_data(&self, path: &str) -> GarpResult<Option<serde_json::Value>> {
+        use hyper::{Client, Request};
+        use hyper::body::to_bytes;
+        use hyper::http::Uri;
+        if self.base_url.is_empty() {
+            return Err(GarpError::Config("SYNCHRONIZER_URL not set".to_string()));
+        }
+        let url = format!("{}{}", self.base_url, path);
+        let uri: Uri = url.parse().map_err(|e: hyper::http::uri::InvalidUri| GarpError::Internal(e.to_string()))?;
+        let req = Request::builder().method("GET").uri(uri).body(hyper::Body::empty())
+            .map_err(|e| GarpError::Internal(e.to_string()))?;
+        let resp = Client::new().request(req).await.map_err(|e| GarpError::Internal(e.to_string()))?;
+        if resp.status() == hyper::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(GarpError::Internal(format!("GET {} returned {}", url, resp.status())));
+        }
+        let body = to_bytes(resp.into_body()).await.map_err(|e| GarpError::Internal(e.to_string()))?;
+        let v: serde_json::Value = serde_json::from_slice(&body).map_err(|e| GarpError::Internal(e.to_string()))?;
+        Ok(v.get("data").filter(|d| !d.is_null()).cloned())
+    }
+}
+
+#[async_trait]
+impl BlockSource for HttpBlockSource {
+    async fn latest_height(&self) -> GarpResult<u64> {
+        let data = self.get_data("/api/v1/blocks/latest").await?;
+        Ok(data
+            .and_then(|d| d.get("height").or_else(|| d.get("number")).and_then(|h| h.as_u64()))
+            .unwrap_or(0))
+    }
+
+    async fn fetch_block(&self, height: u64) -> GarpResult<Option<Block>> {
+        match self.get_data(&format!("/api/v1/blocks/{}", height)).await? {
+            Some(data) => serde_json::from_value(data).map(Some).map_err(|e| GarpError::Internal(e.to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Progress of the current or most recent resync
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResyncProgress {
+    pub active: bool,
+    pub from_height: u64,
+    pub current_height: u64,
+    pub target_height: u64,
+    pub blocks_per_sec: f64,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+/// Rebuilds local blocks above a height from a canonical block source
+pub struct ResyncManager {
+    storage: Arc<dyn StorageBackend>,
+    source: Arc<dyn BlockSource>,
+    /// Shared with the node's sync loop
+    last_applied: Arc<RwLock<u64>>,
+    active: AtomicBool,
+    progress: RwLock<ResyncProgress>,
+}
+
+impl ResyncManager {
+    pub fn new(storage: Arc<dyn StorageBackend>, source: Arc<dyn BlockSource>, last_applied: Arc<RwLock<u64>>) -> Self {
+        Self {
+            storage,
+            source,
+            last_applied,
+            active: AtomicBool::new(false),
+            progress: RwLock::new(ResyncProgress::default()),
+        }
+    }
+
+    /// Whether a resync is running
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Snapshot of resync progress
+    pub async fn progress(&self) -> ResyncProgress {
+        self.progress.read().await.clone()
+    }
+
+    /// Start a resync in the background, clearing local blocks above `from_height`
+    /// (default 0). Fails if a resync is already running.
+    pub async fn start(self: &Arc<Self>, from_height: Option<u64>) -> GarpResult<tokio::task::JoinHandle<GarpResult<()>>> {
+        if self.active.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+            return Err(GarpError::Internal("Resync already in progress".to_string()));
+        }
+        let from_height = from_height.unwrap_or(0);
+        *self.progress.write().await = ResyncProgress {
+            active: true,
+            from_height,
+            current_height: from_height,
+            started_at: Some(Utc::now()),
+            ..Default::default()
+        };
+
+        let manager = self.clone();
+        Ok(tokio::spawn(async move {
+            let result = manager.run(from_height).await;
+            {
+                let mut progress = manager.progress.write().await;
+                progress.active = false;
+                progress.finished_at = Some(Utc::now());
+                if let Err(e) = &result {
+                    progress.error = Some(e.to_string());
+                }
+            }
+            manager.active.store(false, Ordering::SeqCst);
+            match &result {
+                Ok(()) => info!("Resync from height {} completed", from_height),
+                Err(e) => error!("Resync from height {} failed: {}", from_height, e),
+            }
+            result
+        }))
+    }
+
+    async fn run(&self, from_height: u64) -> GarpResult<()> {
+        let removed = self.storage.delete_blocks_above(from_height).await?;
+        info!("Resync cleared {} local blocks above height {}", removed, from_height);
+        *self.last_applied.write().await = from_height;
+
+        let target = self.source.latest_height().await?;
+        self.progress.write().await.target_height = target;
+
+        let started = Instant::now();
+        for height in (from_height + 1)..=target {
+            match self.source.fetch_block(height).await? {
+                Some(block) => {
+                    for tx in &block.transactions {
+                        self.storage.store_transaction(tx).await?;
+                    }
+                    self.storage.store_block(&block).await?;
+                }
+                None => warn!("Block {} missing from source during resync", height),
+            }
+
+            *self.last_applied.write().await = height;
+            let mut progress = self.progress.write().await;
+            progress.current_height = height;
+            let elapsed = started.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                progress.blocks_per_sec = (height - from_height) as f64 / elapsed;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use garp_common::{BlockHeader, ParticipantId};
+    use std::collections::HashMap;
+
+    struct StaticBlockSource {
+        blocks: HashMap<u64, Block>,
+    }
+
+    #[async_trait]
+    impl BlockSource for StaticBlockSource {
+        async fn latest_height(&self) -> GarpResult<u64> {
+            Ok(self.blocks.keys().copied().max().unwrap_or(0))
+        }
+
+        async fn fetch_block(&self, height: u64) -> GarpResult<Option<Block>> {
+            Ok(self.blocks.get(&height).cloned())
+        }
+    }
+
+    fn block(slot: u64, hash: u8) -> Block {
+        Block {
+            header: BlockHeader {
+                parent_hash: vec![hash.wrapping_sub(1); 32],
+                slot,
+                epoch: 0,
+                proposer: ParticipantId::new("validator"),
+                state_root: vec![0; 32],
+                tx_root: vec![0; 32],
+                receipt_root: vec![0; 32],
+            },
+            hash: vec![hash; 32],
+            timestamp: Utc::now(),
+            transactions: Vec::new(),
+        }
+    }
+
+    async fn setup() -> (Arc<ResyncManager>, Arc<MemoryStorage>, Arc<RwLock<u64>>) {
+        let canonical: HashMap<u64, Block> = (1..=5).map(|h| (h, block(h, h as u8))).collect();
+        let storage = Arc::new(MemoryStorage::new());
+        for b in canonical.values() {
+            storage.store_block(b).await.unwrap();
+        }
+        let last_applied = Arc::new(RwLock::new(5));
+        let manager = Arc::new(ResyncManager::new(
+            storage.clone(),
+            Arc::new(StaticBlockSource { blocks: canonical }),
+            last_applied.clone(),
+        ));
+        (manager, storage, last_applied)
+    }
+
+    #[tokio::test]
+    async fn test_resync_repairs_corrupted_block() {
+        let (manager, storage, last_applied) = setup().await;
+
+        // Corrupt block 3 locally
+        storage.store_block(&block(3, 0xee)).await.unwrap();
+        assert_eq!(storage.get_block_by_slot(3).await.unwrap().unwrap().hash, vec![0xee; 32]);
+
+        manager.start(Some(2)).await.unwrap().await.unwrap().unwrap();
+
+        assert_eq!(storage.get_block_by_slot(3).await.unwrap().unwrap().hash, vec![3; 32]);
+        assert!(storage.get_block_by_hash_hex(&hex::encode([0xee; 32])).await.unwrap().is_none());
+        assert_eq!(storage.get_block_by_slot(2).await.unwrap().unwrap().hash, vec![2; 32]);
+
+        let progress = manager.progress().await;
+        assert!(!progress.active);
+        assert_eq!((progress.from_height, progress.current_height, progress.target_height), (2, 5, 5));
+        assert!(progress.error.is_none());
+        assert_eq!(*last_applied.read().await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_resync_refuses_concurrent_start() {
+        let (manager, _storage, _) = setup().await;
+        manager.active.store(true, Ordering::SeqCst);
+        assert!(manager.start(None).await.is_err());
+
+        manager.active.store(false, Ordering::SeqCst);
+        assert!(manager.start(None).await.is_ok());
+    }
+}
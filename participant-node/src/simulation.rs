@@ -0,0 +1,385 @@
+//! Transaction simulation against a forked view of the ledger.
+//!
+//! `ForkedStorage` reads through to the live storage backend and keeps every
+//! write in a private overlay, so the regular validation and execution code
+//! can run unchanged without touching real state.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use garp_common::{
+    Asset, Block, Contract, ContractId, GarpResult, ParticipantId, Transaction, TransactionId, WalletBalance,
+};
+use serde::Serialize;
+
+use crate::storage::{
+    ContractEvent, ContractStorageDiff, EventQuery, LedgerState, MemoryStorage, StorageBackend, WalletHistoryEntry,
+    WalletHistoryQuery,
+};
+
+/// Balance change recorded during simulation
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceChange {
+    pub participant_id: ParticipantId,
+    pub asset_id: String,
+    pub delta: i64,
+    pub balance_after: u64,
+}
+
+/// State changes a simulated transaction would make
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StateDiff {
+    pub created_contracts: Vec<ContractId>,
+    pub archived_contracts: Vec<ContractId>,
+    pub balance_changes: Vec<BalanceChange>,
+    pub events: Vec<ContractEvent>,
+}
+
+/// Outcome of simulating a transaction
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationResult {
+    pub success: bool,
+    pub failure_reason: Option<String>,
+    pub warnings: Vec<String>,
+    pub diff: StateDiff,
+    /// Metering steps the contract execution consumed
+    pub gas_used: u64,
+    /// Fee the transaction would pay for `gas_used`
+    pub fee: u64,
+}
+
+impl SimulationResult {
+    pub(crate) fn failed(reason: impl Into<String>, warnings: Vec<String>, diff: StateDiff) -> Self {
+        Self { success: false, failure_reason: Some(reason.into()), warnings, diff, gas_used: 0, fee: 0 }
+    }
+}
+
+/// Copy-on-write storage layered over a live backend
+pub struct ForkedStorage {
+    base: Arc<dyn StorageBackend>,
+    overlay: MemoryStorage,
+    /// Full key/value state of contracts whose storage was written in the fork
+    contract_storage: parking_lot::Mutex<HashMap<ContractId, HashMap<String, serde_json::Value>>>,
+    diff: parking_lot::Mutex<StateDiff>,
+}
+
+impl ForkedStorage {
+    pub fn new(base: Arc<dyn StorageBackend>) -> Self {
+        Self {
+            base,
+            overlay: MemoryStorage::new(),
+            contract_storage: parking_lot::Mutex::new(HashMap::new()),
+            diff: parking_lot::Mutex::new(StateDiff::default()),
+        }
+    }
+
+    /// Snapshot of the changes recorded so far
+    pub fn diff(&self) -> StateDiff {
+        self.diff.lock().clone()
+    }
+
+    /// Record events produced outside of storage (e.g. by contract execution)
+    pub fn record_events(&self, events: &[ContractEvent]) {
+        self.diff.lock().events.extend_from_slice(events);
+    }
+
+    /// Copy a participant's balance into the overlay before it is modified
+    async fn ensure_balance_forked(&self, participant_id: &ParticipantId) -> GarpResult<()> {
+        if self.overlay.get_wallet_balance(participant_id).await?.is_none() {
+            if let Some(balance) = self.base.get_wallet_balance(participant_id).await? {
+                self.overlay.store_wallet_balance(&balance).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StorageBackend for ForkedStorage {
+    async fn store_contract(&self, contract: &Contract) -> GarpResult<()> {
+        if self.get_contract(&contract.id).await?.is_none() {
+            self.diff.lock().created_contracts.push(contract.id.clone());
+        }
+        self.overlay.store_contract(contract).await
+    }
+
+    async fn get_contract(&self, contract_id: &ContractId) -> GarpResult<Option<Contract>> {
+        match self.overlay.get_contract(contract_id).await? {
+            Some(contract) => Ok(Some(contract)),
+            None => self.base.get_contract(contract_id).await,
+        }
+    }
+
+    async fn archive_contract(&self, contract_id: &ContractId) -> GarpResult<()> {
+        if let Some(mut contract) = self.get_contract(contract_id).await? {
+            if !contract.archived {
+                self.diff.lock().archived_contracts.push(contract_id.clone());
+            }
+            contract.archived = true;
+            self.overlay.store_contract(&contract).await?;
+        }
+        Ok(())
+    }
+
+    async fn list_contracts(&self, participant_id: &ParticipantId, active_only: bool) -> GarpResult<Vec<Contract>> {
+        let mut contracts: HashMap<ContractId, Contract> = self
+            .base
+            .list_contracts(participant_id, false)
+            .await?
+            .into_iter()
+            .map(|c| (c.id.clone(), c))
+            .collect();
+        for contract in self.overlay.list_contracts(participant_id, false).await? {
+            contracts.insert(contract.id.clone(), contract);
+        }
+        Ok(contracts.into_values().filter(|c| !active_only || !c.archived).collect())
+    }
+
+    async fn store_transaction(&self, transaction: &Transaction) -> GarpResult<()> {
+        self.overlay.store_transaction(transaction).await
+    }
+
+    async fn get_transaction(&self, transaction_id: &TransactionId) -> GarpResult<Option<Transaction>> {
+        match self.overlay.get_transaction(transaction_id).await? {
+            Some(transaction) => Ok(Some(transaction)),
+            None => self.base.get_transaction(transaction_id).await,
+        }
+    }
+
+    async fn list_transactions(&self, participant_id: &ParticipantId, limit: Option<u32>) -> GarpResult<Vec<Transaction>> {
+        let mut transactions = self.overlay.list_transactions(participant_id, limit).await?;
+        transactions.extend(self.base.list_transactions(participant_id, limit).await?);
+        if let Some(limit) = limit {
+            transactions.truncate(limit as usize);
+        }
+        Ok(transactions)
+    }
+
+    async fn store_wallet_balance(&self, balance: &WalletBalance) -> GarpResult<()> {
+        self.overlay.store_wallet_balance(balance).await
+    }
+
+    async fn get_wallet_balance(&self, participant_id: &ParticipantId) -> GarpResult<Option<WalletBalance>> {
+        match self.overlay.get_wallet_balance(participant_id).await? {
+            Some(balance) => Ok(Some(balance)),
+            None => self.base.get_wallet_balance(participant_id).await,
+        }
+    }
+
+    async fn update_asset_balance(&self, participant_id: &ParticipantId, asset: &Asset, delta: i64) -> GarpResult<()> {
+        self.ensure_balance_forked(participant_id).await?;
+        self.overlay.update_asset_balance(participant_id, asset, delta).await
+    }
+
+    async fn store_wallet_history_entry(&self, entry: &WalletHistoryEntry) -> GarpResult<()> {
+        self.diff.lock().balance_changes.push(BalanceChange {
+            participant_id: entry.participant_id.clone(),
+            asset_id: entry.asset_id.clone(),
+            delta: entry.amount,
+            balance_after: entry.balance_after,
+        });
+        self.overlay.store_wallet_history_entry(entry).await
+    }
+
+    async fn list_wallet_history(&self, query: &WalletHistoryQuery) -> GarpResult<Vec<WalletHistoryEntry>> {
+        let mut entries = self.base.list_wallet_history(query).await?;
+        entries.extend(self.overlay.list_wallet_history(query).await?);
+        Ok(entries)
+    }
+
+    async fn get_ledger_state(&self, participant_id: &ParticipantId) -> GarpResult<LedgerState> {
+        self.base.get_ledger_state(participant_id).await
+    }
+
+    async fn store_ledger_checkpoint(&self, participant_id: &ParticipantId, state: &LedgerState) -> GarpResult<()> {
+        self.overlay.store_ledger_checkpoint(participant_id, state).await
+    }
+
+    async fn store_block(&self, block: &Block) -> GarpResult<()> {
+        self.overlay.store_block(block).await
+    }
+
+    async fn get_block_by_slot(&self, slot: u64) -> GarpResult<Option<Block>> {
+        self.base.get_block_by_slot(slot).await
+    }
+
+    async fn get_latest_block(&self) -> GarpResult<Option<Block>> {
+        self.base.get_latest_block().await
+    }
+
+    async fn get_block_by_hash_hex(&self, hash_hex: &str) -> GarpResult<Option<Block>> {
+        self.base.get_block_by_hash_hex(hash_hex).await
+    }
+
+    async fn list_blocks(&self, limit: Option<u32>, offset: Option<u32>) -> GarpResult<Vec<Block>> {
+        self.base.list_blocks(limit, offset).await
+    }
+
+    async fn list_blocks_filtered(&self, epoch: Option<u64>, proposer: Option<String>, limit: Option<u32>, offset: Option<u32>) -> GarpResult<Vec<Block>> {
+        self.base.list_blocks_filtered(epoch, proposer, limit, offset).await
+    }
+
+    async fn get_block_state_changes(&self, slot: u64) -> GarpResult<Vec<crate::state_commitments::StateChangeItem>> {
+        self.base.get_block_state_changes(slot).await
+    }
+
+    async fn delete_blocks_above(&self, slot: u64) -> GarpResult<u64> {
+        self.overlay.delete_blocks_above(slot).await
+    }
+
+    async fn store_contract_event(&self, event: &ContractEvent) -> GarpResult<()> {
+        self.diff.lock().events.push(event.clone());
+        self.overlay.store_contract_event(event).await
+    }
+
+    async fn get_contract_events(&self, contract_id: &ContractId, limit: Option<u32>) -> GarpResult<Vec<ContractEvent>> {
+        let mut events = self.overlay.get_contract_events(contract_id, limit).await?;
+        events.extend(self.base.get_contract_events(contract_id, limit).await?);
+        Ok(events)
+    }
+
+    async fn get_participant_events(&self, participant_id: &ParticipantId, limit: Option<u32>) -> GarpResult<Vec<ContractEvent>> {
+        let mut events = self.overlay.get_participant_events(participant_id, limit).await?;
+        events.extend(self.base.get_participant_events(participant_id, limit).await?);
+        Ok(events)
+    }
+
+    async fn query_events(&self, query: &EventQuery) -> GarpResult<Vec<ContractEvent>> {
+        let mut events = self.overlay.query_events(query).await?;
+        events.extend(self.base.query_events(query).await?);
+        Ok(events)
+    }
+
+    async fn latest_event_sequence(&self) -> GarpResult<Option<u64>> {
+        self.base.latest_event_sequence().await
+    }
+
+    async fn prune_events(&self, before_sequence: u64) -> GarpResult<u64> {
+        self.overlay.prune_events(before_sequence).await
+    }
+    async fn get_contract_storage(&self, contract_id: &ContractId) -> GarpResult<HashMap<String, serde_json::Value>> {
+        if let Some(state) = self.contract_storage.lock().get(contract_id) {
+            return Ok(state.clone());
+        }
+        self.base.get_contract_storage(contract_id).await
+    }
+
+    async fn commit_contract_storage(&self, contract_id: &ContractId, diff: &ContractStorageDiff, events: &[ContractEvent]) -> GarpResult<()> {
+        let mut state = self.get_contract_storage(contract_id).await?;
+        for key in &diff.deleted {
+            state.remove(key);
+        }
+        state.extend(diff.set.clone());
+        self.contract_storage.lock().insert(contract_id.clone(), state);
+
+        self.diff.lock().events.extend_from_slice(events);
+        self.overlay.commit_contract_storage(contract_id, &ContractStorageDiff::default(), events).await
+    }
+
+    async fn put_metadata(&self, key: &str, value: &serde_json::Value) -> GarpResult<()> {
+        self.overlay.put_metadata(key, value).await
+    }
+
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<serde_json::Value>> {
+        match self.overlay.get_metadata(key).await? {
+            Some(value) => Ok(Some(value)),
+            None => self.base.get_metadata(key).await,
+        }
+    }
+
+    async fn list_metadata(&self, prefix: &str) -> GarpResult<Vec<(String, serde_json::Value)>> {
+        let mut entries: std::collections::BTreeMap<String, serde_json::Value> =
+            self.base.list_metadata(prefix).await?.into_iter().collect();
+        entries.extend(self.overlay.list_metadata(prefix).await?);
+        Ok(entries.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::apply_balance_change;
+    use chrono::Utc;
+    use garp_common::{AssetType, TransactionCommand, TransferAssetCommand};
+    use uuid::Uuid;
+
+    fn usd(amount: u64) -> Asset {
+        Asset {
+            id: "USD".to_string(),
+            asset_type: AssetType::Currency,
+            amount,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forked_storage_does_not_mutate_base() {
+        let base = Arc::new(MemoryStorage::new());
+        let alice = ParticipantId::new("alice");
+        let bob = ParticipantId::new("bob");
+        base.update_asset_balance(&alice, &usd(50), 50).await.unwrap();
+
+        let forked = ForkedStorage::new(base.clone());
+        let tx = Transaction {
+            id: TransactionId(Uuid::new_v4()),
+            submitter: alice.clone(),
+            command: TransactionCommand::TransferAsset(TransferAssetCommand {
+                from: alice.clone(),
+                to: bob.clone(),
+                asset: usd(20),
+            }),
+            created_at: Utc::now(),
+            signatures: Vec::new(),
+            encrypted_payload: None,
+        };
+        apply_balance_change(&forked, &alice, &tx, Some(bob.clone()), &usd(20), -20).await.unwrap();
+        apply_balance_change(&forked, &bob, &tx, Some(alice.clone()), &usd(20), 20).await.unwrap();
+
+        // Fork sees the transfer
+        let forked_alice = forked.get_wallet_balance(&alice).await.unwrap().unwrap();
+        assert_eq!(forked_alice.assets[0].amount, 30);
+
+        // Base is untouched
+        let base_alice = base.get_wallet_balance(&alice).await.unwrap().unwrap();
+        assert_eq!(base_alice.assets[0].amount, 50);
+        assert!(base.get_wallet_balance(&bob).await.unwrap().is_none());
+
+        let diff = forked.diff();
+        let deltas: Vec<(String, i64, u64)> = diff
+            .balance_changes
+            .iter()
+            .map(|c| (c.participant_id.0.clone(), c.delta, c.balance_after))
+            .collect();
+        assert_eq!(deltas, vec![("alice".to_string(), -20, 30), ("bob".to_string(), 20, 20)]);
+    }
+
+    #[tokio::test]
+    async fn test_forked_storage_tracks_contract_changes() {
+        let base = Arc::new(MemoryStorage::new());
+        let alice = ParticipantId::new("alice");
+        let existing = Contract {
+            id: ContractId(Uuid::new_v4()),
+            template_id: "iou".to_string(),
+            signatories: vec![alice.clone()],
+            observers: Vec::new(),
+            argument: serde_json::json!({}),
+            created_at: Utc::now(),
+            archived: false,
+        };
+        base.store_contract(&existing).await.unwrap();
+
+        let forked = ForkedStorage::new(base.clone());
+        let created = Contract { id: ContractId(Uuid::new_v4()), ..existing.clone() };
+        forked.store_contract(&created).await.unwrap();
+        forked.archive_contract(&existing.id).await.unwrap();
+
+        let diff = forked.diff();
+        assert_eq!(diff.created_contracts, vec![created.id.clone()]);
+        assert_eq!(diff.archived_contracts, vec![existing.id.clone()]);
+        assert_eq!(forked.list_contracts(&alice, true).await.unwrap().len(), 1);
+
+        assert!(!base.get_contract(&existing.id).await.unwrap().unwrap().archived);
+        assert!(base.get_contract(&created.id).await.unwrap().is_none());
+    }
+}
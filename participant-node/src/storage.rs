@@ -28,6 +28,8 @@ pub trait StorageBackend: Send + Sync {
     async fn store_wallet_balance(&self, balance: &WalletBalance) -> GarpResult<()>;
     async fn get_wallet_balance(&self, participant_id: &ParticipantId) -> GarpResult<Option<WalletBalance>>;
     async fn update_asset_balance(&self, participant_id: &ParticipantId, asset: &Asset, delta: i64) -> GarpResult<()>;
+    async fn store_wallet_history_entry(&self, entry: &WalletHistoryEntry) -> GarpResult<()>;
+    async fn list_wallet_history(&self, query: &WalletHistoryQuery) -> GarpResult<Vec<WalletHistoryEntry>>;
 
     // Ledger operations
     async fn get_ledger_state(&self, participant_id: &ParticipantId) -> GarpResult<LedgerState>;
@@ -41,12 +43,42 @@ pub trait StorageBackend: Send + Sync {
     async fn list_blocks(&self, limit: Option<u32>, offset: Option<u32>) -> GarpResult<Vec<Block>>;
     async fn list_blocks_filtered(&self, epoch: Option<u64>, proposer: Option<String>, limit: Option<u32>, offset: Option<u32>) -> GarpResult<Vec<Block>>;
     async fn get_block_state_changes(&self, slot: u64) -> GarpResult<Vec<crate::state_commitments::StateChangeItem>>;
+    /// Delete blocks (and their state changes) with slot above `slot`, returning the number removed
+    async fn delete_blocks_above(&self, slot: u64) -> GarpResult<u64>;
 
     // Event operations
     async fn store_contract_event(&self, event: &ContractEvent) -> GarpResult<()>;
     async fn get_contract_events(&self, contract_id: &ContractId, limit: Option<u32>) -> GarpResult<Vec<ContractEvent>>;
     async fn get_participant_events(&self, participant_id: &ParticipantId, limit: Option<u32>) -> GarpResult<Vec<ContractEvent>>;
     async fn query_events(&self, query: &EventQuery) -> GarpResult<Vec<ContractEvent>>;
+    /// Highest sequence assigned to a stored event
+    async fn latest_event_sequence(&self) -> GarpResult<Option<u64>>;
+    /// Delete events with a sequence below `before_sequence`, returning how many were deleted
+    async fn prune_events(&self, before_sequence: u64) -> GarpResult<u64>;
+
+    // Contract storage operations
+    async fn get_contract_storage(&self, contract_id: &ContractId) -> GarpResult<HashMap<String, serde_json::Value>>;
+    /// Apply a storage diff and store the emitted events in a single atomic write
+    async fn commit_contract_storage(&self, contract_id: &ContractId, diff: &ContractStorageDiff, events: &[ContractEvent]) -> GarpResult<()>;
+
+    // Metadata operations
+    async fn put_metadata(&self, key: &str, value: &serde_json::Value) -> GarpResult<()>;
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<serde_json::Value>>;
+    /// Metadata entries whose key starts with `prefix`, ordered by key
+    async fn list_metadata(&self, prefix: &str) -> GarpResult<Vec<(String, serde_json::Value)>>;
+}
+
+/// Key/value changes made to a contract's storage by one execution
+#[derive(Debug, Clone, Default)]
+pub struct ContractStorageDiff {
+    pub set: HashMap<String, serde_json::Value>,
+    pub deleted: Vec<String>,
+}
+
+impl ContractStorageDiff {
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.deleted.is_empty()
+    }
 }
 
 /// Contract event
@@ -58,6 +90,10 @@ pub struct ContractEvent {
     pub data: serde_json::Value,
     pub timestamp: DateTime<Utc>,
     pub emitter: ParticipantId,
+    /// Position in the node's event log, assigned by storage when the event
+    /// is first recorded; 0 until then
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 /// Event query parameters
@@ -68,9 +104,36 @@ pub struct EventQuery {
     pub participant_id: Option<ParticipantId>,
     pub from_timestamp: Option<DateTime<Utc>>,
     pub to_timestamp: Option<DateTime<Utc>>,
+    /// Only events recorded after this sequence; results are then ordered
+    /// oldest first instead of newest first
+    pub after_sequence: Option<u64>,
     pub limit: Option<u32>,
 }
 
+/// Wallet history entry recording a single balance change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletHistoryEntry {
+    pub transaction_id: TransactionId,
+    pub participant_id: ParticipantId,
+    pub counterparty: Option<ParticipantId>,
+    pub asset_id: String,
+    pub asset_type: String,
+    /// Signed amount: positive for credits, negative for debits
+    pub amount: i64,
+    /// Balance of the asset after this entry was applied
+    pub balance_after: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Wallet history query parameters
+#[derive(Debug, Clone)]
+pub struct WalletHistoryQuery {
+    pub participant_id: ParticipantId,
+    pub asset_type: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
 /// PostgreSQL storage implementation
 pub struct PostgresStorage {
     pool: PgPool,
@@ -244,13 +307,25 @@ impl PostgresStorage {
                 event_type VARCHAR NOT NULL,
                 data JSONB NOT NULL,
                 timestamp TIMESTAMPTZ NOT NULL,
-                emitter VARCHAR NOT NULL
+                emitter VARCHAR NOT NULL,
+                sequence BIGSERIAL
             )
         "#)
         .execute(&self.pool)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
 
+        // Tables created before events were sequenced get one in insertion order
+        sqlx::query("ALTER TABLE contract_events ADD COLUMN IF NOT EXISTS sequence BIGSERIAL")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_contract_events_sequence ON contract_events (sequence)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_contract_events_contract_id ON contract_events (contract_id)")
             .execute(&self.pool)
             .await
@@ -271,6 +346,54 @@ impl PostgresStorage {
             .await
             .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
 
+        // Create wallet_history table (append-only index of balance changes)
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS wallet_history (
+                seq BIGSERIAL PRIMARY KEY,
+                participant_id VARCHAR NOT NULL,
+                transaction_id UUID NOT NULL,
+                counterparty VARCHAR,
+                asset_id VARCHAR NOT NULL,
+                asset_type VARCHAR NOT NULL,
+                amount BIGINT NOT NULL,
+                balance_after BIGINT NOT NULL,
+                timestamp TIMESTAMPTZ NOT NULL
+            )
+        "#)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_wallet_history_participant ON wallet_history (participant_id, seq)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        // Create contract_storage table (persistent key/value state per contract)
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS contract_storage (
+                contract_id UUID NOT NULL,
+                key VARCHAR NOT NULL,
+                value JSONB NOT NULL,
+                PRIMARY KEY (contract_id, key)
+            )
+        "#)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        // Create metadata table (node-level key/value records such as contract templates)
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS metadata (
+                key VARCHAR PRIMARY KEY,
+                value JSONB NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
         Ok(())
     }
 }
@@ -588,6 +711,56 @@ impl StorageBackend for PostgresStorage {
         Ok(())
     }
 
+    async fn store_wallet_history_entry(&self, entry: &WalletHistoryEntry) -> GarpResult<()> {
+        sqlx::query(r#"
+            INSERT INTO wallet_history (participant_id, transaction_id, counterparty, asset_id, asset_type, amount, balance_after, timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#)
+        .bind(&entry.participant_id.0)
+        .bind(entry.transaction_id.0)
+        .bind(entry.counterparty.as_ref().map(|p| p.0.clone()))
+        .bind(&entry.asset_id)
+        .bind(&entry.asset_type)
+        .bind(entry.amount)
+        .bind(entry.balance_after as i64)
+        .bind(entry.timestamp)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_wallet_history(&self, query: &WalletHistoryQuery) -> GarpResult<Vec<WalletHistoryEntry>> {
+        let rows = sqlx::query(r#"
+            SELECT participant_id, transaction_id, counterparty, asset_id, asset_type, amount, balance_after, timestamp
+            FROM wallet_history
+            WHERE participant_id = $1 AND ($2::VARCHAR IS NULL OR asset_type = $2)
+            ORDER BY seq ASC
+            LIMIT $3 OFFSET $4
+        "#)
+        .bind(&query.participant_id.0)
+        .bind(query.asset_type.clone())
+        .bind(query.limit.map(|l| l as i64).unwrap_or(i64::MAX))
+        .bind(query.offset.unwrap_or(0) as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        let entries = rows.into_iter().map(|row| WalletHistoryEntry {
+            transaction_id: TransactionId(row.get("transaction_id")),
+            participant_id: ParticipantId(row.get("participant_id")),
+            counterparty: row.get::<Option<String>, _>("counterparty").map(ParticipantId),
+            asset_id: row.get("asset_id"),
+            asset_type: row.get("asset_type"),
+            amount: row.get("amount"),
+            balance_after: row.get::<i64, _>("balance_after") as u64,
+            timestamp: row.get("timestamp"),
+        }).collect();
+
+        Ok(entries)
+    }
+
     async fn get_ledger_state(&self, participant_id: &ParticipantId) -> GarpResult<LedgerState> {
         let row = sqlx::query(r#"
             SELECT participant_id, active_contracts, total_transactions, last_transaction_id, wallet_balance, checkpoint_time
@@ -871,6 +1044,20 @@ impl StorageBackend for PostgresStorage {
         Ok(items)
     }
 
+    async fn delete_blocks_above(&self, slot: u64) -> GarpResult<u64> {
+        sqlx::query(r#"DELETE FROM block_state_changes WHERE slot > $1"#)
+            .bind(slot as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        let result = sqlx::query(r#"DELETE FROM blocks WHERE slot > $1"#)
+            .bind(slot as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
     async fn store_contract_event(&self, event: &ContractEvent) -> GarpResult<()> {
         let data_json = serde_json::to_value(&event.data)
             .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
@@ -896,14 +1083,14 @@ impl StorageBackend for PostgresStorage {
     async fn get_contract_events(&self, contract_id: &ContractId, limit: Option<u32>) -> GarpResult<Vec<ContractEvent>> {
         let query = match limit {
             Some(_) => r#"
-                SELECT id, contract_id, event_type, data, timestamp, emitter
+                SELECT id, contract_id, event_type, data, timestamp, emitter, sequence
                 FROM contract_events 
                 WHERE contract_id = $1
                 ORDER BY timestamp DESC 
                 LIMIT $2
             "#,
             None => r#"
-                SELECT id, contract_id, event_type, data, timestamp, emitter
+                SELECT id, contract_id, event_type, data, timestamp, emitter, sequence
                 FROM contract_events 
                 WHERE contract_id = $1
                 ORDER BY timestamp DESC
@@ -936,6 +1123,7 @@ impl StorageBackend for PostgresStorage {
                 data,
                 timestamp: row.get("timestamp"),
                 emitter: ParticipantId(row.get("emitter")),
+                sequence: row.get::<i64, _>("sequence") as u64,
             };
 
             events.push(event);
@@ -947,14 +1135,14 @@ impl StorageBackend for PostgresStorage {
     async fn get_participant_events(&self, participant_id: &ParticipantId, limit: Option<u32>) -> GarpResult<Vec<ContractEvent>> {
         let query = match limit {
             Some(_) => r#"
-                SELECT id, contract_id, event_type, data, timestamp, emitter
+                SELECT id, contract_id, event_type, data, timestamp, emitter, sequence
                 FROM contract_events 
                 WHERE emitter = $1
                 ORDER BY timestamp DESC 
                 LIMIT $2
             "#,
             None => r#"
-                SELECT id, contract_id, event_type, data, timestamp, emitter
+                SELECT id, contract_id, event_type, data, timestamp, emitter, sequence
                 FROM contract_events 
                 WHERE emitter = $1
                 ORDER BY timestamp DESC
@@ -987,6 +1175,7 @@ impl StorageBackend for PostgresStorage {
                 data,
                 timestamp: row.get("timestamp"),
                 emitter: ParticipantId(row.get("emitter")),
+                sequence: row.get::<i64, _>("sequence") as u64,
             };
 
             events.push(event);
@@ -996,7 +1185,7 @@ impl StorageBackend for PostgresStorage {
     }
 
     async fn query_events(&self, query: &EventQuery) -> GarpResult<Vec<ContractEvent>> {
-        let mut sql = "SELECT id, contract_id, event_type, data, timestamp, emitter FROM contract_events WHERE 1=1".to_string();
+        let mut sql = "SELECT id, contract_id, event_type, data, timestamp, emitter, sequence FROM contract_events WHERE 1=1".to_string();
         let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send>> = Vec::new();
         let mut param_index = 1;
 
@@ -1030,7 +1219,14 @@ impl StorageBackend for PostgresStorage {
             param_index += 1;
         }
 
-        sql.push_str(" ORDER BY timestamp DESC");
+        if let Some(after_sequence) = query.after_sequence {
+            sql.push_str(&format!(" AND sequence > ${}", param_index));
+            params.push(Box::new(after_sequence as i64));
+            param_index += 1;
+            sql.push_str(" ORDER BY sequence ASC");
+        } else {
+            sql.push_str(" ORDER BY timestamp DESC");
+        }
 
         if let Some(limit) = query.limit {
             sql.push_str(&format!(" LIMIT ${}", param_index));
@@ -1060,6 +1256,7 @@ impl StorageBackend for PostgresStorage {
                 data,
                 timestamp: row.get("timestamp"),
                 emitter: ParticipantId(row.get("emitter")),
+                sequence: row.get::<i64, _>("sequence") as u64,
             };
 
             events.push(event);
@@ -1067,6 +1264,122 @@ impl StorageBackend for PostgresStorage {
 
         Ok(events)
     }
+
+    async fn latest_event_sequence(&self) -> GarpResult<Option<u64>> {
+        let row = sqlx::query("SELECT MAX(sequence) AS latest FROM contract_events")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(row.get::<Option<i64>, _>("latest").map(|sequence| sequence as u64))
+    }
+
+    async fn prune_events(&self, before_sequence: u64) -> GarpResult<u64> {
+        let result = sqlx::query("DELETE FROM contract_events WHERE sequence < $1")
+            .bind(before_sequence as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_contract_storage(&self, contract_id: &ContractId) -> GarpResult<HashMap<String, serde_json::Value>> {
+        let rows = sqlx::query("SELECT key, value FROM contract_storage WHERE contract_id = $1")
+            .bind(contract_id.0)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        let mut state = HashMap::new();
+        for row in rows {
+            let key: String = row.get("key");
+            let value: serde_json::Value = row.get("value");
+            state.insert(key, value);
+        }
+        Ok(state)
+    }
+
+    async fn commit_contract_storage(&self, contract_id: &ContractId, diff: &ContractStorageDiff, events: &[ContractEvent]) -> GarpResult<()> {
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        for (key, value) in &diff.set {
+            sqlx::query(r#"
+                INSERT INTO contract_storage (contract_id, key, value)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (contract_id, key) DO UPDATE SET value = EXCLUDED.value
+            "#)
+            .bind(contract_id.0)
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        }
+
+        for key in &diff.deleted {
+            sqlx::query("DELETE FROM contract_storage WHERE contract_id = $1 AND key = $2")
+                .bind(contract_id.0)
+                .bind(key)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        }
+
+        for event in events {
+            sqlx::query(r#"
+                INSERT INTO contract_events (id, contract_id, event_type, data, timestamp, emitter)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (id) DO NOTHING
+            "#)
+            .bind(&event.id)
+            .bind(event.contract_id.0)
+            .bind(&event.event_type)
+            .bind(&event.data)
+            .bind(event.timestamp)
+            .bind(&event.emitter.0)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn put_metadata(&self, key: &str, value: &serde_json::Value) -> GarpResult<()> {
+        sqlx::query(r#"
+            INSERT INTO metadata (key, value, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
+        "#)
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<serde_json::Value>> {
+        let row = sqlx::query("SELECT value FROM metadata WHERE key = $1")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(row.map(|r| r.get("value")))
+    }
+
+    async fn list_metadata(&self, prefix: &str) -> GarpResult<Vec<(String, serde_json::Value)>> {
+        let rows = sqlx::query("SELECT key, value FROM metadata WHERE starts_with(key, $1) ORDER BY key")
+            .bind(prefix)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(rows.into_iter().map(|r| (r.get("key"), r.get("value"))).collect())
+    }
 }
 
 /// In-memory storage for testing
@@ -1079,6 +1392,11 @@ pub struct MemoryStorage {
     blocks_by_slot: parking_lot::RwLock<HashMap<u64, Block>>,
     block_state_changes_by_slot: parking_lot::RwLock<HashMap<u64, Vec<crate::state_commitments::StateChangeItem>>>,
     contract_events: parking_lot::RwLock<HashMap<String, ContractEvent>>,
+    /// Last sequence assigned to an event; only advanced under the `contract_events` write lock
+    event_sequence: std::sync::atomic::AtomicU64,
+    wallet_history: parking_lot::RwLock<Vec<WalletHistoryEntry>>,
+    contract_storage: parking_lot::RwLock<HashMap<ContractId, HashMap<String, serde_json::Value>>>,
+    metadata: parking_lot::RwLock<std::collections::BTreeMap<String, serde_json::Value>>,
 }
 
 impl MemoryStorage {
@@ -1092,7 +1410,21 @@ impl MemoryStorage {
             blocks_by_slot: parking_lot::RwLock::new(HashMap::new()),
             block_state_changes_by_slot: parking_lot::RwLock::new(HashMap::new()),
             contract_events: parking_lot::RwLock::new(HashMap::new()),
+            event_sequence: std::sync::atomic::AtomicU64::new(0),
+            wallet_history: parking_lot::RwLock::new(Vec::new()),
+            contract_storage: parking_lot::RwLock::new(HashMap::new()),
+            metadata: parking_lot::RwLock::new(std::collections::BTreeMap::new()),
+        }
+    }
+
+    /// Record `event` under the next sequence unless its ID is already stored
+    fn insert_event(&self, events: &mut HashMap<String, ContractEvent>, event: &ContractEvent) {
+        if events.contains_key(&event.id) {
+            return;
         }
+        let mut event = event.clone();
+        event.sequence = self.event_sequence.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        events.insert(event.id.clone(), event);
     }
 }
 
@@ -1197,6 +1529,24 @@ impl StorageBackend for MemoryStorage {
         Ok(())
     }
 
+    async fn store_wallet_history_entry(&self, entry: &WalletHistoryEntry) -> GarpResult<()> {
+        self.wallet_history.write().push(entry.clone());
+        Ok(())
+    }
+
+    async fn list_wallet_history(&self, query: &WalletHistoryQuery) -> GarpResult<Vec<WalletHistoryEntry>> {
+        let history = self.wallet_history.read();
+        let entries = history
+            .iter()
+            .filter(|e| e.participant_id == query.participant_id)
+            .filter(|e| match &query.asset_type { Some(t) => &e.asset_type == t, None => true })
+            .skip(query.offset.unwrap_or(0) as usize)
+            .take(query.limit.map(|l| l as usize).unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        Ok(entries)
+    }
+
     async fn get_ledger_state(&self, participant_id: &ParticipantId) -> GarpResult<LedgerState> {
         let states = self.ledger_states.read();
         Ok(states.get(participant_id).cloned().unwrap_or_else(|| LedgerState {
@@ -1277,9 +1627,23 @@ impl StorageBackend for MemoryStorage {
         Ok(map.get(&slot).cloned().unwrap_or_default())
     }
 
+    async fn delete_blocks_above(&self, slot: u64) -> GarpResult<u64> {
+        let removed: Vec<Block> = {
+            let mut by_slot = self.blocks_by_slot.write();
+            let slots: Vec<u64> = by_slot.keys().copied().filter(|s| *s > slot).collect();
+            slots.iter().filter_map(|s| by_slot.remove(s)).collect()
+        };
+        {
+            let mut by_hash = self.blocks_by_hash.write();
+            by_hash.retain(|_, b| b.header.slot <= slot);
+        }
+        self.block_state_changes_by_slot.write().retain(|s, _| *s <= slot);
+        Ok(removed.len() as u64)
+    }
+
     async fn store_contract_event(&self, event: &ContractEvent) -> GarpResult<()> {
         let mut events = self.contract_events.write();
-        events.insert(event.id.clone(), event.clone());
+        self.insert_event(&mut events, event);
         Ok(())
     }
 
@@ -1341,7 +1705,12 @@ impl StorageBackend for MemoryStorage {
             filtered.retain(|e| e.timestamp <= to_timestamp);
         }
         
-        filtered.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        if let Some(after_sequence) = query.after_sequence {
+            filtered.retain(|e| e.sequence > after_sequence);
+            filtered.sort_by_key(|e| e.sequence);
+        } else {
+            filtered.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        }
         
         if let Some(limit) = query.limit {
             filtered.truncate(limit as usize);
@@ -1349,6 +1718,58 @@ impl StorageBackend for MemoryStorage {
         
         Ok(filtered)
     }
+
+    async fn latest_event_sequence(&self) -> GarpResult<Option<u64>> {
+        Ok(self.contract_events.read().values().map(|e| e.sequence).max())
+    }
+
+    async fn prune_events(&self, before_sequence: u64) -> GarpResult<u64> {
+        let mut events = self.contract_events.write();
+        let before = events.len();
+        events.retain(|_, e| e.sequence >= before_sequence);
+        Ok((before - events.len()) as u64)
+    }
+
+    async fn get_contract_storage(&self, contract_id: &ContractId) -> GarpResult<HashMap<String, serde_json::Value>> {
+        let storage = self.contract_storage.read();
+        Ok(storage.get(contract_id).cloned().unwrap_or_default())
+    }
+
+    async fn commit_contract_storage(&self, contract_id: &ContractId, diff: &ContractStorageDiff, events: &[ContractEvent]) -> GarpResult<()> {
+        // Hold both locks so readers never observe the diff without its events
+        let mut storage = self.contract_storage.write();
+        let mut stored_events = self.contract_events.write();
+
+        let state = storage.entry(contract_id.clone()).or_default();
+        for key in &diff.deleted {
+            state.remove(key);
+        }
+        for (key, value) in &diff.set {
+            state.insert(key.clone(), value.clone());
+        }
+        for event in events {
+            self.insert_event(&mut stored_events, event);
+        }
+        Ok(())
+    }
+
+    async fn put_metadata(&self, key: &str, value: &serde_json::Value) -> GarpResult<()> {
+        self.metadata.write().insert(key.to_string(), value.clone());
+        Ok(())
+    }
+
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<serde_json::Value>> {
+        Ok(self.metadata.read().get(key).cloned())
+    }
+
+    async fn list_metadata(&self, prefix: &str) -> GarpResult<Vec<(String, serde_json::Value)>> {
+        let metadata = self.metadata.read();
+        Ok(metadata
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
 }
 
 /// Storage factory
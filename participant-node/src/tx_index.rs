@@ -0,0 +1,469 @@
+//! Transaction status index
+//!
+//! Maps every transaction visible to this participant to where it is in its
+//! lifecycle and, once sequenced, the block slot and position it landed at,
+//! so lookups load one transaction body instead of scanning the ledger.
+//! Entries are persisted in node metadata under `txindex:{id}`. When the
+//! index is missing or an entry cannot be read it is rebuilt from stored
+//! transactions and blocks.
+
+use chrono::{DateTime, Utc};
+use garp_common::{ContractId, GarpError, GarpResult, ParticipantId, Transaction, TransactionCommand, TransactionId};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::storage::StorageBackend;
+
+const KEY_PREFIX: &str = "txindex:";
+/// Written once the index is complete; its absence means the index is missing
+const BUILT_KEY: &str = "txindex_built";
+const INDEX_VERSION: u32 = 1;
+/// Blocks read per page while rebuilding
+const REBUILD_PAGE_SIZE: u32 = 500;
+
+/// Where a transaction is in its lifecycle; later stages never regress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// Accepted by the local ledger, not yet in a block
+    Submitted,
+    /// Included in a block
+    Sequenced,
+    /// Applied from a finalized block
+    Applied,
+}
+
+impl TransactionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionStatus::Submitted => "submitted",
+            TransactionStatus::Sequenced => "sequenced",
+            TransactionStatus::Applied => "applied",
+        }
+    }
+}
+
+/// Indexed state of one transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionIndexEntry {
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+    /// Slot of the block the transaction is in, once sequenced
+    pub slot: Option<u64>,
+    /// Position of the transaction in that block
+    pub position: Option<u32>,
+    pub submitter: ParticipantId,
+    /// Participants the transaction involves, the submitter included
+    pub participants: Vec<ParticipantId>,
+    pub contract_ids: Vec<ContractId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Filters for [`TransactionIndex::list_transactions`]
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub participant_id: Option<ParticipantId>,
+    pub contract_id: Option<ContractId>,
+    pub from_date: Option<DateTime<Utc>>,
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl TransactionFilter {
+    fn matches(&self, entry: &TransactionIndexEntry) -> bool {
+        self.participant_id.as_ref().map_or(true, |p| entry.participants.contains(p))
+            && self.contract_id.as_ref().map_or(true, |c| entry.contract_ids.contains(c))
+            && self.from_date.map_or(true, |from| entry.created_at >= from)
+            && self.to_date.map_or(true, |to| entry.created_at <= to)
+    }
+}
+
+fn metadata_key(transaction_id: &TransactionId) -> String {
+    format!("{}{}", KEY_PREFIX, transaction_id.0)
+}
+
+/// Transaction status index backed by node metadata storage
+pub struct TransactionIndex {
+    participant_id: ParticipantId,
+    storage: Arc<dyn StorageBackend>,
+    entries: parking_lot::RwLock<HashMap<TransactionId, TransactionIndexEntry>>,
+}
+
+impl TransactionIndex {
+    pub fn new(participant_id: ParticipantId, storage: Arc<dyn StorageBackend>) -> Self {
+        Self {
+            participant_id,
+            storage,
+            entries: parking_lot::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Load the persisted index, rebuilding it from storage if it is missing
+    /// or corrupt; returns the number of indexed transactions
+    pub async fn load(&self) -> GarpResult<usize> {
+        let built = self.storage.get_metadata(BUILT_KEY).await?;
+        if built.as_ref().and_then(|v| v.get("version")).and_then(Value::as_u64) != Some(INDEX_VERSION as u64) {
+            warn!("Transaction index is missing; rebuilding from storage");
+            return self.rebuild().await;
+        }
+
+        let mut entries = HashMap::new();
+        for (key, value) in self.storage.list_metadata(KEY_PREFIX).await? {
+            // Entries dropped by a rebuild are overwritten with null
+            if value.is_null() {
+                continue;
+            }
+            match serde_json::from_value::<TransactionIndexEntry>(value) {
+                Ok(entry) => {
+                    entries.insert(entry.transaction_id.clone(), entry);
+                }
+                Err(e) => {
+                    warn!("Transaction index entry {} is corrupt ({}); rebuilding from storage", key, e);
+                    return self.rebuild().await;
+                }
+            }
+        }
+        let loaded = entries.len();
+        *self.entries.write() = entries;
+        Ok(loaded)
+    }
+
+    /// Re-index every stored transaction and block, returning the number of
+    /// indexed transactions. Statuses past `Sequenced` are not recoverable
+    /// from storage and are rebuilt as `Sequenced`.
+    pub async fn rebuild(&self) -> GarpResult<usize> {
+        let now = Utc::now();
+        let mut entries: HashMap<TransactionId, TransactionIndexEntry> = HashMap::new();
+
+        let mut offset = 0;
+        loop {
+            let blocks = self.storage.list_blocks(Some(REBUILD_PAGE_SIZE), Some(offset)).await?;
+            for block in &blocks {
+                for (position, transaction) in block.transactions.iter().enumerate() {
+                    let location = Some((block.header.slot, position as u32));
+                    if let Some(entry) = self.entry_for(transaction, TransactionStatus::Sequenced, location, now).await? {
+                        entries.insert(entry.transaction_id.clone(), entry);
+                    }
+                }
+            }
+            if blocks.len() < REBUILD_PAGE_SIZE as usize {
+                break;
+            }
+            offset += REBUILD_PAGE_SIZE;
+        }
+
+        for transaction in self.storage.list_transactions(&self.participant_id, None).await? {
+            if entries.contains_key(&transaction.id) {
+                continue;
+            }
+            if let Some(entry) = self.entry_for(&transaction, TransactionStatus::Submitted, None, now).await? {
+                entries.insert(entry.transaction_id.clone(), entry);
+            }
+        }
+
+        // Blank out stale or corrupt entries so the next load does not trip on them
+        for (key, _) in self.storage.list_metadata(KEY_PREFIX).await? {
+            let indexed = Uuid::parse_str(&key[KEY_PREFIX.len()..])
+                .map_or(false, |id| entries.contains_key(&TransactionId(id)));
+            if !indexed {
+                self.storage.put_metadata(&key, &Value::Null).await?;
+            }
+        }
+        for entry in entries.values() {
+            self.persist(entry).await?;
+        }
+        self.storage.put_metadata(BUILT_KEY, &serde_json::json!({ "version": INDEX_VERSION, "built_at": now })).await?;
+
+        let rebuilt = entries.len();
+        *self.entries.write() = entries;
+        Ok(rebuilt)
+    }
+
+    /// Record a transaction accepted by the local ledger
+    pub async fn record_submitted(&self, transaction: &Transaction) -> GarpResult<()> {
+        self.record(transaction, TransactionStatus::Submitted, None).await
+    }
+
+    /// Record a transaction included at `position` of the block at `slot`
+    pub async fn record_sequenced(&self, transaction: &Transaction, slot: u64, position: u32) -> GarpResult<()> {
+        self.record(transaction, TransactionStatus::Sequenced, Some((slot, position))).await
+    }
+
+    /// Record a transaction applied from the finalized block at `slot`
+    pub async fn record_applied(&self, transaction: &Transaction, slot: u64, position: u32) -> GarpResult<()> {
+        self.record(transaction, TransactionStatus::Applied, Some((slot, position))).await
+    }
+
+    /// Index entry of a transaction
+    pub fn get_entry(&self, transaction_id: &TransactionId) -> Option<TransactionIndexEntry> {
+        self.entries.read().get(transaction_id).cloned()
+    }
+
+    /// A transaction and its index entry
+    pub async fn get_transaction(&self, transaction_id: &TransactionId) -> GarpResult<Option<(TransactionIndexEntry, Transaction)>> {
+        let Some(entry) = self.get_entry(transaction_id) else {
+            return Ok(None);
+        };
+        let transaction = self.load_body(&entry).await?;
+        Ok(transaction.map(|transaction| (entry, transaction)))
+    }
+
+    /// Indexed transactions matching `filter`, newest first
+    pub async fn list_transactions(
+        &self,
+        filter: &TransactionFilter,
+        offset: usize,
+        limit: usize,
+    ) -> GarpResult<Vec<(TransactionIndexEntry, Transaction)>> {
+        let page: Vec<TransactionIndexEntry> = {
+            let entries = self.entries.read();
+            let mut matching: Vec<&TransactionIndexEntry> = entries.values().filter(|e| filter.matches(e)).collect();
+            matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| a.transaction_id.0.cmp(&b.transaction_id.0)));
+            matching.into_iter().skip(offset).take(limit).cloned().collect()
+        };
+
+        let mut transactions = Vec::with_capacity(page.len());
+        for entry in page {
+            match self.load_body(&entry).await? {
+                Some(transaction) => transactions.push((entry, transaction)),
+                None => warn!("Indexed transaction {} is missing from storage", entry.transaction_id.0),
+            }
+        }
+        Ok(transactions)
+    }
+
+    async fn record(&self, transaction: &Transaction, status: TransactionStatus, location: Option<(u64, u32)>) -> GarpResult<()> {
+        let existing = self.get_entry(&transaction.id);
+        if existing.as_ref().map_or(false, |e| e.status > status) {
+            return Ok(());
+        }
+        let Some(mut entry) = self.entry_for(transaction, status, location, Utc::now()).await? else {
+            return Ok(());
+        };
+        // A transaction keeps its block location when re-submitted
+        if location.is_none() {
+            if let Some(existing) = existing {
+                entry.slot = existing.slot;
+                entry.position = existing.position;
+            }
+        }
+        self.persist(&entry).await?;
+        self.entries.write().insert(entry.transaction_id.clone(), entry);
+        Ok(())
+    }
+
+    async fn persist(&self, entry: &TransactionIndexEntry) -> GarpResult<()> {
+        let value = serde_json::to_value(entry).map_err(|e| GarpError::SerializationError(e.to_string()))?;
+        self.storage.put_metadata(&metadata_key(&entry.transaction_id), &value).await
+    }
+
+    /// Index entry for `transaction`, or `None` if it is not visible to this
+    /// participant
+    async fn entry_for(
+        &self,
+        transaction: &Transaction,
+        status: TransactionStatus,
+        location: Option<(u64, u32)>,
+        now: DateTime<Utc>,
+    ) -> GarpResult<Option<TransactionIndexEntry>> {
+        let mut participants = vec![transaction.submitter.clone()];
+        let mut contract_ids = Vec::new();
+        match &transaction.command {
+            TransactionCommand::CreateContract(cmd) => {
+                participants.extend(cmd.signatories.iter().cloned());
+                participants.extend(cmd.observers.iter().cloned());
+                contract_ids.push(cmd.contract_id.clone());
+            }
+            TransactionCommand::ExerciseContract(cmd) => {
+                participants.extend(self.contract_stakeholders(&cmd.contract_id).await?);
+                contract_ids.push(cmd.contract_id.clone());
+            }
+            TransactionCommand::ArchiveContract(cmd) => {
+                participants.extend(self.contract_stakeholders(&cmd.contract_id).await?);
+                contract_ids.push(cmd.contract_id.clone());
+            }
+            TransactionCommand::TransferAsset(cmd) => {
+                participants.push(cmd.from.clone());
+                participants.push(cmd.to.clone());
+            }
+            TransactionCommand::CreateAsset(cmd) => {
+                participants.push(cmd.owner.clone());
+            }
+        }
+        if !participants.contains(&self.participant_id) {
+            return Ok(None);
+        }
+        participants.sort_by(|a, b| a.0.cmp(&b.0));
+        participants.dedup();
+
+        Ok(Some(TransactionIndexEntry {
+            transaction_id: transaction.id.clone(),
+            status,
+            slot: location.map(|(slot, _)| slot),
+            position: location.map(|(_, position)| position),
+            submitter: transaction.submitter.clone(),
+            participants,
+            contract_ids,
+            created_at: transaction.created_at,
+            updated_at: now,
+        }))
+    }
+
+    async fn contract_stakeholders(&self, contract_id: &ContractId) -> GarpResult<Vec<ParticipantId>> {
+        Ok(self
+            .storage
+            .get_contract(contract_id)
+            .await?
+            .map(|contract| contract.signatories.into_iter().chain(contract.observers).collect())
+            .unwrap_or_default())
+    }
+
+    /// Load a transaction body from the transaction store, falling back to
+    /// the block it was sequenced in
+    async fn load_body(&self, entry: &TransactionIndexEntry) -> GarpResult<Option<Transaction>> {
+        if let Some(transaction) = self.storage.get_transaction(&entry.transaction_id).await? {
+            return Ok(Some(transaction));
+        }
+        let (Some(slot), Some(position)) = (entry.slot, entry.position) else {
+            return Ok(None);
+        };
+        Ok(self
+            .storage
+            .get_block_by_slot(slot)
+            .await?
+            .and_then(|block| block.transactions.into_iter().nth(position as usize))
+            .filter(|transaction| transaction.id == entry.transaction_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use garp_common::{Asset, AssetType, Block, BlockHeader, TransferAssetCommand};
+
+    fn transfer(from: &str, to: &str, created_at: DateTime<Utc>) -> Transaction {
+        Transaction {
+            id: TransactionId(Uuid::new_v4()),
+            submitter: ParticipantId::new(from),
+            command: TransactionCommand::TransferAsset(TransferAssetCommand {
+                from: ParticipantId::new(from),
+                to: ParticipantId::new(to),
+                asset: Asset {
+                    id: "USD".to_string(),
+                    asset_type: AssetType::Currency,
+                    amount: 5,
+                    metadata: HashMap::new(),
+                },
+            }),
+            created_at,
+            signatures: Vec::new(),
+            encrypted_payload: None,
+        }
+    }
+
+    fn block(slot: u64, transactions: Vec<Transaction>) -> Block {
+        Block {
+            header: BlockHeader {
+                parent_hash: vec![0; 32],
+                slot,
+                epoch: 0,
+                proposer: ParticipantId::new("validator"),
+                state_root: vec![0; 32],
+                tx_root: vec![0; 32],
+                receipt_root: vec![0; 32],
+            },
+            hash: vec![slot as u8; 32],
+            timestamp: Utc::now(),
+            transactions,
+        }
+    }
+
+    fn ids(results: &[(TransactionIndexEntry, Transaction)]) -> Vec<TransactionId> {
+        results.iter().map(|(_, tx)| tx.id.clone()).collect()
+    }
+
+    /// What the API serves for alice; `[to_bob, from_carol, pending, unrelated]`
+    async fn assert_lookups(index: &TransactionIndex, txs: &[Transaction; 4], from_carol_status: TransactionStatus) {
+        let [to_bob, from_carol, pending, unrelated] = txs;
+        let t0 = to_bob.created_at;
+
+        let (entry, tx) = index.get_transaction(&from_carol.id).await.unwrap().unwrap();
+        assert_eq!(tx.id, from_carol.id);
+        assert_eq!((entry.status, entry.slot, entry.position), (from_carol_status, Some(7), Some(2)));
+        assert!(index.get_transaction(&unrelated.id).await.unwrap().is_none());
+
+        let all = index.list_transactions(&TransactionFilter::default(), 0, 10).await.unwrap();
+        assert_eq!(ids(&all), vec![pending.id.clone(), from_carol.id.clone(), to_bob.id.clone()]);
+        assert_eq!(all[0].0.status, TransactionStatus::Submitted);
+
+        let with_bob = TransactionFilter { participant_id: Some(ParticipantId::new("bob")), ..Default::default() };
+        assert_eq!(ids(&index.list_transactions(&with_bob, 0, 10).await.unwrap()), vec![to_bob.id.clone()]);
+
+        let window = TransactionFilter {
+            from_date: Some(t0 + chrono::Duration::minutes(30)),
+            to_date: Some(t0 + chrono::Duration::minutes(150)),
+            ..Default::default()
+        };
+        assert_eq!(ids(&index.list_transactions(&window, 0, 10).await.unwrap()), vec![pending.id.clone(), from_carol.id.clone()]);
+        assert_eq!(ids(&index.list_transactions(&window, 1, 10).await.unwrap()), vec![from_carol.id.clone()]);
+    }
+
+    #[tokio::test]
+    async fn test_lookups_survive_restart_with_rebuild() {
+        let storage = Arc::new(MemoryStorage::new());
+        let alice = ParticipantId::new("alice");
+        let index = TransactionIndex::new(alice.clone(), storage.clone());
+        assert_eq!(index.load().await.unwrap(), 0);
+
+        let t0 = Utc::now() - chrono::Duration::hours(3);
+        let txs = [
+            transfer("alice", "bob", t0),
+            transfer("carol", "alice", t0 + chrono::Duration::hours(1)),
+            transfer("alice", "dave", t0 + chrono::Duration::hours(2)),
+            transfer("carol", "dave", t0),
+        ];
+        let [to_bob, from_carol, pending, unrelated] = &txs;
+        let sequenced = block(7, vec![unrelated.clone(), to_bob.clone(), from_carol.clone()]);
+
+        // Sequenced transfers are known from their block alone
+        storage.store_transaction(pending).await.unwrap();
+        storage.store_transaction(unrelated).await.unwrap();
+        storage.store_block(&sequenced).await.unwrap();
+        index.record_submitted(pending).await.unwrap();
+        index.record_submitted(unrelated).await.unwrap();
+        index.record_sequenced(to_bob, 7, 1).await.unwrap();
+        index.record_sequenced(from_carol, 7, 2).await.unwrap();
+        index.record_applied(from_carol, 7, 2).await.unwrap();
+        // Late submissions do not regress status
+        index.record_submitted(from_carol).await.unwrap();
+        assert_lookups(&index, &txs, TransactionStatus::Applied).await;
+
+        // Restart with the index intact
+        let restarted = TransactionIndex::new(alice.clone(), storage.clone());
+        assert_eq!(restarted.load().await.unwrap(), 3);
+        assert_lookups(&restarted, &txs, TransactionStatus::Applied).await;
+
+        // Restart with a corrupt entry: the index is rebuilt from storage
+        storage.put_metadata(&format!("{}{}", KEY_PREFIX, Uuid::new_v4()), &serde_json::json!("garbage")).await.unwrap();
+        let rebuilt = TransactionIndex::new(alice.clone(), storage.clone());
+        assert_eq!(rebuilt.load().await.unwrap(), 3);
+        assert_lookups(&rebuilt, &txs, TransactionStatus::Sequenced).await;
+
+        // The rebuild blanked the corrupt entry, so the next load is clean
+        let reloaded = TransactionIndex::new(alice.clone(), storage.clone());
+        assert_eq!(reloaded.load().await.unwrap(), 3);
+
+        // Restart with no index at all
+        let fresh = Arc::new(MemoryStorage::new());
+        fresh.store_transaction(pending).await.unwrap();
+        fresh.store_block(&sequenced).await.unwrap();
+        let missing = TransactionIndex::new(alice, fresh);
+        assert_eq!(missing.load().await.unwrap(), 3);
+        assert_lookups(&missing, &txs, TransactionStatus::Sequenced).await;
+    }
+}
@@ -3,7 +3,7 @@ use garp_common::{
     CreateAssetCommand, TransferAssetCommand, DigitalSignature, CryptoService,
     GarpResult, GarpError, TransactionError
 };
-use crate::storage::StorageBackend;
+use crate::storage::{StorageBackend, WalletHistoryEntry, WalletHistoryQuery};
 use std::sync::Arc;
 use chrono::Utc;
 use uuid::Uuid;
@@ -121,7 +121,7 @@ impl WalletManager {
         self.storage.store_transaction(&transaction).await?;
 
         // Update balance
-        self.storage.update_asset_balance(&self.participant_id, &asset, asset.amount as i64).await?;
+        apply_balance_change(self.storage.as_ref(), &self.participant_id, &transaction, None, &asset, asset.amount as i64).await?;
 
         info!("Asset {} created successfully", asset.id);
         Ok(transaction_id)
@@ -157,8 +157,8 @@ impl WalletManager {
         self.storage.store_transaction(&transaction).await?;
 
         // Update balances
-        self.storage.update_asset_balance(&self.participant_id, &asset, -(asset.amount as i64)).await?;
-        self.storage.update_asset_balance(&to, &asset, asset.amount as i64).await?;
+        apply_balance_change(self.storage.as_ref(), &self.participant_id, &transaction, Some(to.clone()), &asset, -(asset.amount as i64)).await?;
+        apply_balance_change(self.storage.as_ref(), &to, &transaction, Some(self.participant_id.clone()), &asset, asset.amount as i64).await?;
 
         info!("Asset transfer completed: {} -> {}", self.participant_id.0, to.0);
         Ok(transaction_id)
@@ -189,7 +189,7 @@ impl WalletManager {
         let transaction_id = TransactionId(Uuid::new_v4());
 
         // For burning, we can use a transfer to a special "burn" address
-        let burn_address = ParticipantId("BURN_ADDRESS".to_string());
+        let burn_address = burn_address_for_history();
         let command = TransactionCommand::TransferAsset(TransferAssetCommand {
             from: self.participant_id.clone(),
             to: burn_address,
@@ -203,7 +203,7 @@ impl WalletManager {
         self.storage.store_transaction(&transaction).await?;
 
         // Update balance (deduct from our balance)
-        self.storage.update_asset_balance(&self.participant_id, &asset, -(amount as i64)).await?;
+        apply_balance_change(self.storage.as_ref(), &self.participant_id, &transaction, Some(burn_address_for_history()), &asset, -(amount as i64)).await?;
 
         info!("Asset burn completed: {} units of {}", amount, asset_id);
         Ok(transaction_id)
@@ -223,6 +223,17 @@ impl WalletManager {
         Ok(asset_transactions)
     }
 
+    /// Get chronological balance history for this wallet, optionally filtered by asset type
+    pub async fn get_history(&self, asset_type: Option<String>, limit: Option<u32>, offset: Option<u32>) -> GarpResult<Vec<WalletHistoryEntry>> {
+        let query = WalletHistoryQuery {
+            participant_id: self.participant_id.clone(),
+            asset_type,
+            limit,
+            offset,
+        };
+        self.storage.list_wallet_history(&query).await
+    }
+
     /// Get wallet statistics
     pub async fn get_stats(&self) -> GarpResult<WalletStats> {
         let balance = self.get_balance().await?;
@@ -284,17 +295,7 @@ impl WalletManager {
 
     /// Create message for signature
     fn create_signature_message(&self, transaction: &Transaction) -> GarpResult<Vec<u8>> {
-        let mut message = Vec::new();
-        message.extend_from_slice(transaction.id.0.as_bytes());
-        message.extend_from_slice(transaction.submitter.0.as_bytes());
-        
-        let command_bytes = bincode::serialize(&transaction.command)
-            .map_err(|e| TransactionError::SerializationFailed(e.to_string()))?;
-        message.extend_from_slice(&command_bytes);
-        
-        message.extend_from_slice(&transaction.created_at.timestamp().to_le_bytes());
-        
-        Ok(message)
+        Ok(transaction.signing_bytes()?)
     }
 
     /// Convert transaction to asset transaction
@@ -520,6 +521,43 @@ impl WalletManager {
     }
 }
 
+/// Participant used as the counterparty for burned assets
+fn burn_address_for_history() -> ParticipantId {
+    ParticipantId("BURN_ADDRESS".to_string())
+}
+
+/// Apply a signed balance change and append the resulting balance to the wallet history.
+///
+/// Both the ledger and the wallet manager route balance updates through here so the
+/// history index always agrees with the stored wallet balance.
+pub(crate) async fn apply_balance_change(
+    storage: &dyn StorageBackend,
+    participant_id: &ParticipantId,
+    transaction: &Transaction,
+    counterparty: Option<ParticipantId>,
+    asset: &Asset,
+    delta: i64,
+) -> GarpResult<()> {
+    storage.update_asset_balance(participant_id, asset, delta).await?;
+
+    let balance_after = storage.get_wallet_balance(participant_id).await?
+        .and_then(|b| b.assets.into_iter().find(|a| a.id == asset.id))
+        .map(|a| a.amount)
+        .unwrap_or(0);
+
+    let entry = WalletHistoryEntry {
+        transaction_id: transaction.id.clone(),
+        participant_id: participant_id.clone(),
+        counterparty,
+        asset_id: asset.id.clone(),
+        asset_type: asset.asset_type.to_string(),
+        amount: delta,
+        balance_after,
+        timestamp: transaction.created_at,
+    };
+    storage.store_wallet_history_entry(&entry).await
+}
+
 /// Wallet export data structure
 #[derive(Debug, Clone)]
 pub struct WalletExport {
@@ -564,4 +602,79 @@ pub fn derive_account_from_seed(seed_hex: &str, account_id: &str) -> Result<Wall
 pub fn sign_bytes(_account_id: &str, _message: &[u8]) -> Result<Vec<u8>, String> {
     // Intentionally not implementing private key handling here.
     Err("signing not implemented; use KMS/HSM".into())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn usd(amount: u64) -> Asset {
+        Asset {
+            id: "USD".to_string(),
+            asset_type: AssetType::Currency,
+            amount,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn transfer_tx(from: &ParticipantId, to: &ParticipantId, amount: u64) -> Transaction {
+        Transaction {
+            id: TransactionId(Uuid::new_v4()),
+            submitter: from.clone(),
+            command: TransactionCommand::TransferAsset(TransferAssetCommand {
+                from: from.clone(),
+                to: to.clone(),
+                asset: usd(amount),
+            }),
+            created_at: Utc::now(),
+            signatures: Vec::new(),
+            encrypted_payload: None,
+        }
+    }
+
+    async fn apply_transfer(storage: &MemoryStorage, from: &ParticipantId, to: &ParticipantId, amount: u64) {
+        let tx = transfer_tx(from, to, amount);
+        apply_balance_change(storage, from, &tx, Some(to.clone()), &usd(amount), -(amount as i64)).await.unwrap();
+        apply_balance_change(storage, to, &tx, Some(from.clone()), &usd(amount), amount as i64).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wallet_history_running_balances() {
+        let storage = MemoryStorage::new();
+        let alice = ParticipantId::new("alice");
+        let bob = ParticipantId::new("bob");
+
+        // Mint 100 to alice
+        let mint = transfer_tx(&alice, &alice, 100);
+        apply_balance_change(&storage, &alice, &mint, None, &usd(100), 100).await.unwrap();
+
+        apply_transfer(&storage, &alice, &bob, 30).await;
+        apply_transfer(&storage, &bob, &alice, 10).await;
+        apply_transfer(&storage, &alice, &bob, 25).await;
+
+        let query = WalletHistoryQuery { participant_id: alice.clone(), asset_type: None, limit: None, offset: None };
+        let history = storage.list_wallet_history(&query).await.unwrap();
+        let amounts: Vec<i64> = history.iter().map(|e| e.amount).collect();
+        let balances: Vec<u64> = history.iter().map(|e| e.balance_after).collect();
+        assert_eq!(amounts, vec![100, -30, 10, -25]);
+        assert_eq!(balances, vec![100, 70, 80, 55]);
+        assert_eq!(history[1].counterparty, Some(bob.clone()));
+
+        // Final history entry agrees with the stored wallet balance
+        let wallet = storage.get_wallet_balance(&alice).await.unwrap().unwrap();
+        let stored = wallet.assets.iter().find(|a| a.id == "USD").unwrap().amount;
+        assert_eq!(history.last().unwrap().balance_after, stored);
+
+        let query = WalletHistoryQuery { participant_id: bob.clone(), asset_type: None, limit: None, offset: None };
+        let bob_history = storage.list_wallet_history(&query).await.unwrap();
+        assert_eq!(bob_history.last().unwrap().balance_after, 45);
+
+        // Pagination and asset type filtering
+        let query = WalletHistoryQuery { participant_id: alice.clone(), asset_type: None, limit: Some(2), offset: Some(1) };
+        let page = storage.list_wallet_history(&query).await.unwrap();
+        assert_eq!(page.iter().map(|e| e.balance_after).collect::<Vec<_>>(), vec![70, 80]);
+
+        let query = WalletHistoryQuery { participant_id: alice, asset_type: Some("Product".to_string()), limit: None, offset: None };
+        assert!(storage.list_wallet_history(&query).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{GarpClientTrait, SdkError, SimulationResult};
+
+/// Wire representation of a transaction command, matching the node's
+/// `TransactionCommandDto`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+enum TransactionCommand {
+    CreateContract {
+        template_id: String,
+        signatories: Vec<String>,
+        observers: Vec<String>,
+        argument: Value,
+    },
+    ExerciseContract {
+        contract_id: String,
+        choice: String,
+        argument: Value,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum CommandKind {
+    CreateContract { template_id: String },
+    ExerciseContract { contract_id: String, choice: String },
+}
+
+/// Fluent builder for transaction commands.
+///
+/// ```no_run
+/// # use garp_sdk::{GarpClient, TransactionBuilder};
+/// # use serde_json::json;
+/// # async fn demo(client: &GarpClient) -> Result<(), garp_sdk::SdkError> {
+/// let tx_id = TransactionBuilder::create_contract("iou")
+///     .with_signatories(vec!["alice".to_string()])
+///     .with_argument(json!({ "amount": 100 }))
+///     .build()
+///     .submit(client)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    kind: CommandKind,
+    signatories: Vec<String>,
+    observers: Vec<String>,
+    argument: Value,
+}
+
+impl TransactionBuilder {
+    fn new(kind: CommandKind) -> Self {
+        Self { kind, signatories: Vec::new(), observers: Vec::new(), argument: json!({}) }
+    }
+
+    /// Start a contract creation from a template
+    pub fn create_contract(template_id: impl Into<String>) -> Self {
+        Self::new(CommandKind::CreateContract { template_id: template_id.into() })
+    }
+
+    /// Start exercising a choice on an existing contract
+    pub fn exercise_contract(contract_id: impl Into<String>, choice: impl Into<String>) -> Self {
+        Self::new(CommandKind::ExerciseContract { contract_id: contract_id.into(), choice: choice.into() })
+    }
+
+    /// Set contract signatories (contract creation only)
+    pub fn with_signatories(mut self, signatories: Vec<String>) -> Self {
+        self.signatories = signatories;
+        self
+    }
+
+    /// Set contract observers (contract creation only)
+    pub fn with_observers(mut self, observers: Vec<String>) -> Self {
+        self.observers = observers;
+        self
+    }
+
+    /// Set the command argument
+    pub fn with_argument(mut self, argument: Value) -> Self {
+        self.argument = argument;
+        self
+    }
+
+    pub fn build(self) -> SubmittableTransaction {
+        let command = match self.kind {
+            CommandKind::CreateContract { template_id } => TransactionCommand::CreateContract {
+                template_id,
+                signatories: self.signatories,
+                observers: self.observers,
+                argument: self.argument,
+            },
+            CommandKind::ExerciseContract { contract_id, choice } => TransactionCommand::ExerciseContract {
+                contract_id,
+                choice,
+                argument: self.argument,
+            },
+        };
+        SubmittableTransaction { command }
+    }
+}
+
+/// A built transaction ready to be sent to a node
+#[derive(Debug, Clone)]
+pub struct SubmittableTransaction {
+    command: TransactionCommand,
+}
+
+impl SubmittableTransaction {
+    /// Submit via `sendTransaction`
+    pub async fn submit(&self, client: &impl GarpClientTrait) -> Result<String, SdkError> {
+        client.send_transaction_raw(&self.to_wire()?).await
+    }
+}
+
+/// A transaction that can be dry-run against a node
+pub trait SimulableTransaction {
+    /// Serialize to the JSON wire format expected by the node
+    fn to_wire(&self) -> Result<String, SdkError>;
+
+    /// Simulate via `simulateTransaction`
+    async fn simulate(&self, client: &impl GarpClientTrait) -> Result<SimulationResult, SdkError> {
+        client.simulate_transaction_raw(&self.to_wire()?).await
+    }
+}
+
+impl SimulableTransaction for SubmittableTransaction {
+    fn to_wire(&self) -> Result<String, SdkError> {
+        Ok(serde_json::to_string(&json!({ "command": self.command }))?)
+    }
+}
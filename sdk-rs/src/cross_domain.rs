@@ -0,0 +1,399 @@
+//! Typed cross-domain transactions for the global synchronizer.
+//!
+//! [`CrossDomainTxType`] mirrors the synchronizer's
+//! `CrossDomainTransactionType` and serializes the same way, externally
+//! tagged: `{"AssetTransfer": {"asset_id": ..., ...}}`. Byte fields are
+//! JSON arrays of numbers. Timestamps are RFC 3339 strings.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+/// Signature by one of the synchronizer's designated emergency keys
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EmergencySignature {
+    pub key_id: String,
+    pub signature: Vec<u8>,
+}
+
+/// What a cross-domain transaction does
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CrossDomainTxType {
+    AssetTransfer {
+        asset_id: String,
+        amount: u64,
+        from_address: String,
+        to_address: String,
+    },
+    ContractCall {
+        contract_address: String,
+        function_name: String,
+        parameters: Vec<u8>,
+    },
+    StateSynchronization {
+        state_key: String,
+        state_value: Vec<u8>,
+        version: u64,
+    },
+    AtomicSwap {
+        swap_id: String,
+        asset_a: String,
+        asset_b: String,
+        amount_a: u64,
+        amount_b: u64,
+    },
+    GovernanceProposal {
+        proposal_id: String,
+        proposal_type: String,
+        proposal_data: Vec<u8>,
+    },
+    /// Accepted only with a quorum of emergency key signatures
+    EmergencyAction {
+        action_type: String,
+        action_data: Vec<u8>,
+        justification: String,
+        #[serde(default)]
+        signatures: Vec<EmergencySignature>,
+    },
+    ChannelLock {
+        channel_id: String,
+        capacity: u64,
+    },
+    ChannelSettlement {
+        channel_id: String,
+        sequence: u64,
+        balance_a: u64,
+        balance_b: u64,
+    },
+    Compensation {
+        original_transaction_id: String,
+    },
+}
+
+/// Cross-domain transaction to submit with
+/// [`GarpClientTrait::submit_cross_domain_transaction`](crate::GarpClientTrait::submit_cross_domain_transaction).
+/// The synchronizer assigns the ID, timestamps and timeout.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CrossDomainTxRequest {
+    pub source_domain: String,
+    pub target_domains: Vec<String>,
+    pub transaction_type: CrossDomainTxType,
+    #[serde(default)]
+    pub data: Vec<u8>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Confirmations needed; every target domain when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_confirmations: Option<usize>,
+    /// RFC 3339 time before which the transaction is held back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<String>,
+    #[serde(default)]
+    pub max_fee: u64,
+    /// Domain the source submits on behalf of, under a governance-approved
+    /// delegation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegated_by: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl CrossDomainTxRequest {
+    pub fn new(
+        source_domain: impl Into<String>,
+        target_domains: impl IntoIterator<Item = impl Into<String>>,
+        transaction_type: CrossDomainTxType,
+    ) -> Self {
+        Self {
+            source_domain: source_domain.into(),
+            target_domains: target_domains.into_iter().map(Into::into).collect(),
+            transaction_type,
+            data: Vec::new(),
+            dependencies: Vec::new(),
+            required_confirmations: None,
+            not_before: None,
+            max_fee: 0,
+            delegated_by: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Hold the transaction until `transaction_id` is finalized
+    pub fn with_dependency(mut self, transaction_id: impl Into<String>) -> Self {
+        self.dependencies.push(transaction_id.into());
+        self
+    }
+
+    pub fn with_required_confirmations(mut self, confirmations: usize) -> Self {
+        self.required_confirmations = Some(confirmations);
+        self
+    }
+
+    /// Hold the transaction until `time`, in RFC 3339
+    pub fn not_before(mut self, time: impl Into<String>) -> Self {
+        self.not_before = Some(time.into());
+        self
+    }
+
+    pub fn with_max_fee(mut self, max_fee: u64) -> Self {
+        self.max_fee = max_fee;
+        self
+    }
+
+    /// Submit on behalf of `domain_id`
+    pub fn on_behalf_of(mut self, domain_id: impl Into<String>) -> Self {
+        self.delegated_by = Some(domain_id.into());
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Result of `submitCrossDomainTransaction`
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubmittedCrossDomainTx {
+    pub(crate) transaction_id: String,
+}
+
+/// Progress of a cross-domain transaction through the synchronizer
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CrossDomainTxStatus {
+    Received,
+    ConsensusInProgress,
+    ConsensusReached,
+    SettlementInProgress,
+    Finalized,
+    Failed(String),
+    TimedOut,
+}
+
+impl CrossDomainTxStatus {
+    /// Whether the transaction will not change status again
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Finalized | Self::Failed(_) | Self::TimedOut)
+    }
+}
+
+/// A target domain's answer to a cross-domain transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ConfirmationStatus {
+    Pending,
+    Confirmed,
+    Rejected,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DomainConfirmation {
+    pub domain_id: String,
+    pub status: ConfirmationStatus,
+    /// RFC 3339; `None` while pending
+    #[serde(default)]
+    pub confirmed_at: Option<String>,
+}
+
+/// `getCrossDomainStatus`, with a confirmation entry per target domain
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CrossDomainStatus {
+    pub transaction_id: String,
+    pub status: CrossDomainTxStatus,
+    pub required_confirmations: usize,
+    pub confirmations: Vec<DomainConfirmation>,
+}
+
+impl CrossDomainStatus {
+    /// Target domains that have confirmed so far
+    pub fn confirmed_count(&self) -> usize {
+        self.confirmations.iter().filter(|c| c.status == ConfirmationStatus::Confirmed).count()
+    }
+}
+
+/// `getDomainState`: a domain's state as tracked by the synchronizer
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DomainState {
+    pub domain_id: String,
+    pub version: u64,
+    pub state_data: Vec<u8>,
+    pub state_hash: Vec<u8>,
+    pub last_block_height: u64,
+    pub last_block_hash: Vec<u8>,
+    #[serde(default)]
+    pub pending_transactions: Vec<String>,
+    pub last_updated: SystemTime,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorCode, GarpClientTrait, MockGarpClient, MockResponder, SdkError};
+    use serde_json::json;
+
+    fn every_type() -> Vec<(CrossDomainTxType, serde_json::Value)> {
+        vec![
+            (
+                CrossDomainTxType::AssetTransfer {
+                    asset_id: "USD".to_string(),
+                    amount: 10,
+                    from_address: "alice".to_string(),
+                    to_address: "bob".to_string(),
+                },
+                json!({ "AssetTransfer": { "asset_id": "USD", "amount": 10, "from_address": "alice", "to_address": "bob" } }),
+            ),
+            (
+                CrossDomainTxType::ContractCall {
+                    contract_address: "c1".to_string(),
+                    function_name: "transfer".to_string(),
+                    parameters: vec![1, 2],
+                },
+                json!({ "ContractCall": { "contract_address": "c1", "function_name": "transfer", "parameters": [1, 2] } }),
+            ),
+            (
+                CrossDomainTxType::StateSynchronization { state_key: "k".to_string(), state_value: vec![3], version: 4 },
+                json!({ "StateSynchronization": { "state_key": "k", "state_value": [3], "version": 4 } }),
+            ),
+            (
+                CrossDomainTxType::AtomicSwap {
+                    swap_id: "s1".to_string(),
+                    asset_a: "A".to_string(),
+                    asset_b: "B".to_string(),
+                    amount_a: 1,
+                    amount_b: 2,
+                },
+                json!({ "AtomicSwap": { "swap_id": "s1", "asset_a": "A", "asset_b": "B", "amount_a": 1, "amount_b": 2 } }),
+            ),
+            (
+                CrossDomainTxType::GovernanceProposal {
+                    proposal_id: "p1".to_string(),
+                    proposal_type: "upgrade".to_string(),
+                    proposal_data: vec![],
+                },
+                json!({ "GovernanceProposal": { "proposal_id": "p1", "proposal_type": "upgrade", "proposal_data": [] } }),
+            ),
+            (
+                CrossDomainTxType::EmergencyAction {
+                    action_type: "halt".to_string(),
+                    action_data: vec![5],
+                    justification: "exploit".to_string(),
+                    signatures: vec![EmergencySignature { key_id: "k1".to_string(), signature: vec![6] }],
+                },
+                json!({ "EmergencyAction": {
+                    "action_type": "halt", "action_data": [5], "justification": "exploit",
+                    "signatures": [{ "key_id": "k1", "signature": [6] }],
+                } }),
+            ),
+            (
+                CrossDomainTxType::ChannelLock { channel_id: "ch1".to_string(), capacity: 100 },
+                json!({ "ChannelLock": { "channel_id": "ch1", "capacity": 100 } }),
+            ),
+            (
+                CrossDomainTxType::ChannelSettlement { channel_id: "ch1".to_string(), sequence: 3, balance_a: 60, balance_b: 40 },
+                json!({ "ChannelSettlement": { "channel_id": "ch1", "sequence": 3, "balance_a": 60, "balance_b": 40 } }),
+            ),
+            (
+                CrossDomainTxType::Compensation { original_transaction_id: "6f1c0b6e-5b0a-4f43-9d1e-0b1f7f6f3c2a".to_string() },
+                json!({ "Compensation": { "original_transaction_id": "6f1c0b6e-5b0a-4f43-9d1e-0b1f7f6f3c2a" } }),
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_every_transaction_type_submits_in_the_synchronizer_shape() {
+        let client = MockGarpClient::new(
+            MockResponder::new().on_any("submitCrossDomainTransaction", Ok(json!({ "transaction_id": "tx-1" }))),
+        );
+        for (transaction_type, wire) in every_type() {
+            let request = CrossDomainTxRequest::new("d1", ["d2"], transaction_type).with_max_fee(5);
+            assert_eq!(client.submit_cross_domain_transaction(&request).await.unwrap(), "tx-1");
+
+            let (method, params) = client.calls().pop().unwrap();
+            assert_eq!(method, "submitCrossDomainTransaction");
+            let params = params.unwrap();
+            assert_eq!(params["transaction_type"], wire);
+            assert_eq!(params["target_domains"], json!(["d2"]));
+            assert!(params.get("required_confirmations").is_none());
+            assert_eq!(serde_json::from_value::<CrossDomainTxRequest>(params).unwrap(), request);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_and_domain_state_decode_from_synchronizer_responses() {
+        let client = MockGarpClient::new(
+            MockResponder::new()
+                .on(
+                    "getCrossDomainStatus",
+                    Some(json!({ "transaction_id": "tx-1" })),
+                    Ok(json!({
+                        "transaction_id": "tx-1",
+                        "status": { "Failed": "rejected by d3" },
+                        "required_confirmations": 2,
+                        "confirmations": [
+                            { "domain_id": "d2", "status": "Confirmed", "confirmed_at": "2024-01-01T00:00:00Z" },
+                            { "domain_id": "d3", "status": "Rejected", "confirmed_at": "2024-01-01T00:00:01Z" },
+                        ],
+                    })),
+                )
+                .on("getCrossDomainStatus", Some(json!({ "transaction_id": "tx-2" })), Ok(json!(null)))
+                .on_any("listActiveDomains", Ok(json!(["d1", "d2"])))
+                .on(
+                    "getDomainState",
+                    Some(json!({ "domain_id": "d1" })),
+                    Ok(json!({
+                        "domain_id": "d1",
+                        "version": 3,
+                        "state_data": [1],
+                        "state_hash": [2],
+                        "last_block_height": 10,
+                        "last_block_hash": [3],
+                        "pending_transactions": [],
+                        "last_updated": { "secs_since_epoch": 1_700_000_000u64, "nanos_since_epoch": 0 },
+                        "metadata": {},
+                    })),
+                ),
+        );
+
+        let status = client.get_cross_domain_status("tx-1").await.unwrap().unwrap();
+        assert_eq!(status.status, CrossDomainTxStatus::Failed("rejected by d3".to_string()));
+        assert!(status.status.is_terminal());
+        assert_eq!(status.confirmed_count(), 1);
+        assert!(client.get_cross_domain_status("tx-2").await.unwrap().is_none());
+
+        assert_eq!(client.list_active_domains().await.unwrap(), vec!["d1", "d2"]);
+        let state = client.get_domain_state("d1").await.unwrap();
+        assert_eq!((state.version, state.last_block_height), (3, 10));
+    }
+
+    #[tokio::test]
+    async fn test_synchronizer_errors_map_to_typed_codes() {
+        let client = MockGarpClient::new(
+            MockResponder::new()
+                .on_any(
+                    "submitCrossDomainTransaction",
+                    Err(SdkError::Rpc { code: ErrorCode::MaintenanceMode, message: "upgrade".to_string(), retryable: true }),
+                )
+                .on_any(
+                    "getDomainState",
+                    Err(SdkError::Rpc { code: ErrorCode::NotFound, message: "no state".to_string(), retryable: false }),
+                ),
+        );
+        let request = CrossDomainTxRequest::new("d1", ["d2"], CrossDomainTxType::ChannelLock {
+            channel_id: "ch1".to_string(),
+            capacity: 1,
+        });
+        assert!(client.submit_cross_domain_transaction(&request).await.unwrap_err().is_retryable());
+        assert!(matches!(
+            client.get_domain_state("d9").await,
+            Err(SdkError::Rpc { code: ErrorCode::NotFound, retryable: false, .. })
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+//! Stable error codes reported by GARP nodes.
+//!
+//! JSON-RPC errors carry the numeric code in `error.code` and its name and
+//! retryability in `error.data`. The codes mirror `garp_common::ErrorCode`;
+//! a code added to a node after this SDK was built is kept as
+//! [`ErrorCode::Unknown`] and its retryability is taken from `error.data`.
+
+use serde_json::Value;
+
+macro_rules! error_codes {
+    ($($variant:ident = $code:literal, $name:literal, $retryable:literal;)*) => {
+        /// Error code of a failed JSON-RPC call
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($variant,)*
+            /// A code this SDK version does not know
+            Unknown(i64),
+        }
+
+        impl ErrorCode {
+            pub fn from_code(code: i64) -> Self {
+                match code { $($code => ErrorCode::$variant,)* other => ErrorCode::Unknown(other) }
+            }
+
+            pub fn code(self) -> i64 {
+                match self { $(ErrorCode::$variant => $code,)* ErrorCode::Unknown(code) => code }
+            }
+
+            /// Machine-readable name, e.g. `DOMAIN_UNAVAILABLE`
+            pub fn name(self) -> &'static str {
+                match self { $(ErrorCode::$variant => $name,)* ErrorCode::Unknown(_) => "UNKNOWN" }
+            }
+
+            /// Whether the same call may succeed if retried later. Unknown
+            /// codes are not retryable.
+            pub fn is_retryable(self) -> bool {
+                match self { $(ErrorCode::$variant => $retryable,)* ErrorCode::Unknown(_) => false }
+            }
+        }
+    };
+}
+
+error_codes! {
+    ParseError = -32700, "PARSE_ERROR", false;
+    InvalidRequest = -32600, "INVALID_REQUEST", false;
+    MethodNotFound = -32601, "METHOD_NOT_FOUND", false;
+    InvalidParams = -32602, "INVALID_PARAMS", false;
+    InternalError = -32603, "INTERNAL_ERROR", false;
+    Unauthorized = -32001, "UNAUTHORIZED", false;
+    ValidationFailed = 1000, "VALIDATION_FAILED", false;
+    InvalidTransaction = 1001, "INVALID_TRANSACTION", false;
+    InvalidSignature = 1002, "INVALID_SIGNATURE", false;
+    InsufficientBalance = 1003, "INSUFFICIENT_BALANCE", false;
+    FeeExceeded = 1004, "FEE_EXCEEDED", false;
+    DuplicateTransaction = 1005, "DUPLICATE_TRANSACTION", false;
+    Conflict = 1006, "CONFLICT", false;
+    PermissionDenied = 1007, "PERMISSION_DENIED", false;
+    StateMismatch = 1008, "STATE_MISMATCH", false;
+    NotFound = 1100, "NOT_FOUND", false;
+    ContractFailed = 1200, "CONTRACT_FAILED", false;
+    DomainUnavailable = 2000, "DOMAIN_UNAVAILABLE", true;
+    NetworkUnavailable = 2001, "NETWORK_UNAVAILABLE", true;
+    Timeout = 2002, "TIMEOUT", true;
+    StorageUnavailable = 2003, "STORAGE_UNAVAILABLE", true;
+    MaintenanceMode = 2004, "MAINTENANCE_MODE", true;
+    NetworkError = 3000, "NETWORK_ERROR", false;
+    ConsensusFailed = 3001, "CONSENSUS_FAILED", false;
+    StorageError = 3002, "STORAGE_ERROR", false;
+    ConfigError = 3003, "CONFIG_ERROR", false;
+    SerializationError = 3004, "SERIALIZATION_ERROR", false;
+    CryptoError = 3005, "CRYPTO_ERROR", false;
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorCode::Unknown(code) => write!(f, "{}", code),
+            known => f.write_str(known.name()),
+        }
+    }
+}
+
+/// Retryability of an error, preferring what the node reported in `data`
+pub(crate) fn is_retryable(code: ErrorCode, data: Option<&Value>) -> bool {
+    data.and_then(|data| data.get("retryable"))
+        .and_then(Value::as_bool)
+        .unwrap_or_else(|| code.is_retryable())
+}
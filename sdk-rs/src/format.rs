@@ -0,0 +1,77 @@
+//! Request and response encodings.
+//!
+//! JSON is the default. With CBOR, request bodies are sent as
+//! `application/cbor` and CBOR responses are requested via `Accept`; a
+//! server that answers in JSON anyway is still understood.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::SdkError;
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Wire encoding used by [`crate::GarpClient`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SerializationFormat {
+    #[default]
+    Json,
+    Cbor,
+}
+
+impl SerializationFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => JSON_CONTENT_TYPE,
+            Self::Cbor => CBOR_CONTENT_TYPE,
+        }
+    }
+
+    /// Format of a `Content-Type` header value; anything but CBOR is JSON
+    pub fn from_content_type(value: &str) -> Self {
+        match value.split(';').next().map(str::trim) {
+            Some(CBOR_CONTENT_TYPE) => Self::Cbor,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, SdkError> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(value)?),
+            Self::Cbor => {
+                let mut out = Vec::new();
+                ciborium::into_writer(value, &mut out).map_err(|e| SdkError::Cbor(e.to_string()))?;
+                Ok(out)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, SdkError> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(bytes)?),
+            Self::Cbor => ciborium::from_reader(bytes).map_err(|e| SdkError::Cbor(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_round_trip_both_formats() {
+        let value = json!({ "jsonrpc": "2.0", "id": 1, "result": { "data": [1, 2, 3], "ok": true } });
+        for format in [SerializationFormat::Json, SerializationFormat::Cbor] {
+            let bytes = format.encode(&value).unwrap();
+            assert_eq!(format.decode::<Value>(&bytes).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_content_type_detection() {
+        assert_eq!(SerializationFormat::from_content_type("application/cbor"), SerializationFormat::Cbor);
+        assert_eq!(SerializationFormat::from_content_type("application/json; charset=utf-8"), SerializationFormat::Json);
+        assert_eq!(SerializationFormat::from_content_type("text/plain"), SerializationFormat::Json);
+    }
+}
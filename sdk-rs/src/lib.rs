@@ -1,18 +1,58 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
+use async_trait::async_trait;
 use reqwest::Client as HttpClient;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 use thiserror::Error;
 
+pub mod builder;
+pub mod cross_domain;
+pub mod error_code;
+pub mod format;
+pub mod mock;
+pub mod proof;
+pub mod receipt;
+pub mod settlement;
+pub mod signature;
+pub mod subscription;
+
+pub use builder::{SimulableTransaction, SubmittableTransaction, TransactionBuilder};
+pub use cross_domain::{
+    ConfirmationStatus, CrossDomainStatus, CrossDomainTxRequest, CrossDomainTxStatus, CrossDomainTxType, DomainConfirmation,
+    DomainState,
+};
+pub use error_code::ErrorCode;
+pub use format::SerializationFormat;
+pub use mock::{MockGarpClient, MockResponder};
+pub use proof::{tx_leaf_hash, verify_merkle_proof, MerkleProof, TxProofItem, TxProofs};
+pub use receipt::{verify_receipt, FinalityCertificate, TransactionReceipt};
+pub use settlement::{verify_settlement_proof, FinalityReference, ProofSignature, SettlementProof};
+pub use signature::verify_response_signature;
+pub use subscription::LedgerUpdate;
+
 #[derive(Debug, Error)]
 pub enum SdkError {
     #[error("http error: {0}")]
     Http(#[from] reqwest::Error),
     #[error("rpc error {code}: {message}")]
-    Rpc { code: i64, message: String },
+    Rpc { code: ErrorCode, message: String, retryable: bool },
     #[error("serialization error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("cbor error: {0}")]
+    Cbor(String),
+    #[error("api error: {0}")]
+    Api(String),
+    #[error("websocket error: {0}")]
+    WebSocket(String),
+}
+
+impl SdkError {
+    /// Whether the node reported the call may succeed if retried later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, SdkError::Rpc { retryable: true, .. })
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -32,6 +72,14 @@ struct JsonRpcError {
     data: Option<Value>,
 }
 
+impl From<JsonRpcError> for SdkError {
+    fn from(error: JsonRpcError) -> Self {
+        let code = ErrorCode::from_code(error.code);
+        let retryable = error_code::is_retryable(code, error.data.as_ref());
+        SdkError::Rpc { code, message: error.message, retryable }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum JsonRpcResponse<T> {
@@ -39,6 +87,15 @@ enum JsonRpcResponse<T> {
     Err { jsonrpc: String, id: u64, error: JsonRpcError },
 }
 
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    #[serde(default)]
+    data: Option<T>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BlockTx {
     pub id: String,
@@ -62,6 +119,12 @@ pub struct BlockInfo {
     pub transactions: Option<Vec<BlockTx>>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SlotLeader {
+    pub slot: u64,
+    pub leader: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TransactionInfo {
     pub id: String,
@@ -75,128 +138,539 @@ pub struct TransactionInfo {
     pub error: Option<String>,
 }
 
+/// Transaction detail served by the global synchronizer's
+/// `getTransactionDetails`; timestamps are Unix milliseconds
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionDetails {
+    pub transaction_id: String,
+    pub transaction_type: String,
+    pub source_domain: String,
+    pub target_domains: Vec<String>,
+    pub status: String,
+    pub consensus: ConsensusDetails,
+    pub settlement: SettlementDetails,
+    #[serde(default)]
+    pub block_height: Option<u64>,
+    #[serde(default)]
+    pub block_hash: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub dependents: Vec<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub timeline: Vec<PhaseTransition>,
+    pub created_at_ms: u64,
+    pub updated_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConsensusDetails {
+    pub phase: String,
+    pub required_votes: u32,
+    pub votes: VoteSummary,
+    /// Present only when requested with `include_votes`
+    #[serde(default)]
+    pub vote_details: Option<HashMap<String, bool>>,
+    #[serde(default)]
+    pub result: Option<bool>,
+    pub has_proof: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VoteSummary {
+    pub approvals: u32,
+    pub rejections: u32,
+    #[serde(default)]
+    pub rejected_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SettlementDetails {
+    #[serde(default)]
+    pub settlement_id: Option<String>,
+    pub settlement_type: String,
+    #[serde(default)]
+    pub domains: Vec<DomainSettlementDetails>,
+    pub has_proof: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DomainSettlementDetails {
+    pub domain_id: String,
+    pub status: String,
+    pub has_proof: bool,
+    pub updated_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PhaseTransition {
+    pub phase: String,
+    pub at_ms: u64,
+}
+
+/// Governance proposal served by the global synchronizer; timestamps are
+/// RFC 3339
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GovernanceProposal {
+    pub proposal_id: String,
+    pub proposer: String,
+    /// Tagged by `type`, e.g. `update_config`, `add_validator` or `custom`
+    pub action: Value,
+    #[serde(default)]
+    pub description: String,
+    /// `Voting`, `Approved`, `Rejected`, `Executed`, `Failed` or `Recorded`
+    pub status: String,
+    pub eligible_weights: HashMap<String, u64>,
+    #[serde(default)]
+    pub votes: HashMap<String, GovernanceVote>,
+    pub created_at: String,
+    pub voting_ends_at: String,
+    #[serde(default)]
+    pub executed_at: Option<String>,
+    #[serde(default)]
+    pub failure_reason: Option<String>,
+    /// Set once the voting period ends
+    #[serde(default)]
+    pub tally: Option<ProposalTally>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GovernanceVote {
+    pub domain_id: String,
+    pub approve: bool,
+    pub weight: u64,
+    pub cast_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProposalTally {
+    pub approve_weight: u64,
+    pub reject_weight: u64,
+    pub total_weight: u64,
+    pub quorum_met: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SimulationResult {
+    pub ok: bool,
+    #[serde(default)]
+    pub logs: Option<Vec<String>>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct GarpClient {
     base_url: String,
     http: HttpClient,
     timeout: Duration,
+    format: SerializationFormat,
+    admin_token: Option<String>,
+    api_key: Option<String>,
 }
 
 impl GarpClient {
     pub fn new(base_url: impl Into<String>) -> Result<Self, SdkError> {
         let timeout = Duration::from_secs(10);
         let http = HttpClient::builder().timeout(timeout).build()?;
-        Ok(Self { base_url: base_url.into().trim_end_matches('/').to_string(), http, timeout })
+        Ok(Self { base_url: base_url.into().trim_end_matches('/').to_string(), http, timeout, format: SerializationFormat::Json, admin_token: None, api_key: None })
     }
 
     pub fn with_timeout(base_url: impl Into<String>, timeout: Duration) -> Result<Self, SdkError> {
         let http = HttpClient::builder().timeout(timeout).build()?;
-        Ok(Self { base_url: base_url.into().trim_end_matches('/').to_string(), http, timeout })
+        Ok(Self { base_url: base_url.into().trim_end_matches('/').to_string(), http, timeout, format: SerializationFormat::Json, admin_token: None, api_key: None })
     }
 
     pub fn with_http_client(base_url: impl Into<String>, http: HttpClient) -> Self {
         let timeout = Duration::from_secs(10);
-        Self { base_url: base_url.into().trim_end_matches('/').to_string(), http, timeout }
+        Self { base_url: base_url.into().trim_end_matches('/').to_string(), http, timeout, format: SerializationFormat::Json, admin_token: None, api_key: None }
+    }
+
+    /// Encode requests and ask for responses in `format`. CBOR needs a
+    /// server that accepts `application/cbor`, such as the global synchronizer.
+    pub fn with_format(mut self, format: SerializationFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Send `token` as `x-admin-token`, required by the synchronizer's
+    /// admin and domain methods such as `castVote`
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(token.into());
+        self
+    }
+
+    /// Send `key` as a bearer token, required by participant nodes with
+    /// `api.api_keys` configured. A key bound to a participant also scopes
+    /// [`Self::subscribe_ledger`] to that participant's transactions.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// POST `body` in the client's format and decode the response in
+    /// whichever format the server answered with
+    async fn post<B: Serialize, R: DeserializeOwned>(&self, path: &str, body: &B) -> Result<R, SdkError> {
+        let mut req = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .header(reqwest::header::CONTENT_TYPE, self.format.content_type())
+            .header(reqwest::header::ACCEPT, self.format.content_type());
+        if let Some(token) = &self.admin_token {
+            req = req.header("x-admin-token", token);
+        }
+        if let Some(key) = &self.api_key {
+            req = req.bearer_auth(key);
+        }
+        let resp = req.body(self.format.encode(body)?).send().await?;
+        let format = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map_or(SerializationFormat::Json, SerializationFormat::from_content_type);
+        let bytes = resp.bytes().await?;
+        format.decode(&bytes)
     }
 
     async fn rpc<R: DeserializeOwned>(&self, method: &str, params: Option<Value>) -> Result<R, SdkError> {
         let req = JsonRpcRequest { jsonrpc: "2.0", id: 1, method, params };
-        let resp = self
-            .http
-            .post(format!("{}/rpc", self.base_url))
-            .json(&req)
-            .send()
-            .await?;
-        let v = resp.json::<JsonRpcResponse<R>>().await?;
+        let v: JsonRpcResponse<R> = self.post("/rpc", &req).await?;
         match v {
             JsonRpcResponse::Ok { result, .. } => Ok(result),
-            JsonRpcResponse::Err { error, .. } => Err(SdkError::Rpc { code: error.code, message: error.message }),
+            JsonRpcResponse::Err { error, .. } => Err(error.into()),
         }
     }
 
+    /// Send `calls` as one JSON-RPC batch. Call `i` gets ID `i + 1`.
+    async fn post_batch(&self, calls: &[(&str, Option<Value>)]) -> Result<Vec<Value>, SdkError> {
+        let payload: Vec<JsonRpcRequest> = calls
+            .iter()
+            .zip(1u64..)
+            .map(|((method, params), id)| JsonRpcRequest { jsonrpc: "2.0", id, method, params: params.clone() })
+            .collect();
+        self.post("/rpc", &payload).await
+    }
+}
+
+/// Node calls made by SDK consumers, implemented by [`GarpClient`] against
+/// a running node and by [`mock::MockGarpClient`] for unit tests.
+///
+/// ```no_run
+/// use garp_sdk::{GarpClient, GarpClientTrait, SdkError};
+///
+/// async fn current_slot(client: &impl GarpClientTrait) -> Result<i64, SdkError> {
+///     client.get_slot().await
+/// }
+/// # async fn demo() -> Result<(), SdkError> {
+/// let slot = current_slot(&GarpClient::new("http://localhost:8080")?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait GarpClientTrait: Send + Sync {
     // Timing & consensus
-    pub async fn get_slot(&self) -> Result<i64, SdkError> {
+    async fn get_slot(&self) -> Result<i64, SdkError>;
+
+    async fn get_slot_leader(&self) -> Result<String, SdkError>;
+
+    async fn get_slot_leaders(&self, from_slot: u64, to_slot: u64) -> Result<Vec<SlotLeader>, SdkError>;
+
+    // Blocks
+    async fn get_block_by_slot(&self, slot: i64) -> Result<Option<BlockInfo>, SdkError>;
+
+    async fn get_block_by_hash(&self, hash_hex: &str) -> Result<Option<BlockInfo>, SdkError>;
+
+    // Inclusion proofs; check each with `verify_merkle_proof` against a trusted root
+    async fn get_tx_proofs(&self, block: u64, tx_ids: &[&str]) -> Result<TxProofs, SdkError>;
+
+    // Settlement proofs are served by the global synchronizer; check each with
+    // `verify_settlement_proof` against the validator public keys
+    async fn get_settlement_proof(&self, transaction_id: &str) -> Result<SettlementProof, SdkError>;
+
+    /// Fetch a settlement proof and reject it unless it verifies locally
+    async fn get_verified_settlement_proof(
+        &self,
+        transaction_id: &str,
+        validator_keys: &[Vec<u8>],
+    ) -> Result<SettlementProof, SdkError> {
+        let proof = self.get_settlement_proof(transaction_id).await?;
+        if !verify_settlement_proof(&proof, validator_keys) {
+            return Err(SdkError::Api(format!("settlement proof for {} failed verification", transaction_id)));
+        }
+        Ok(proof)
+    }
+
+    /// Receipt of a transaction from the global synchronizer, `None` until
+    /// its block is finalized; check it with `verify_receipt` against the
+    /// block's finality certificate
+    async fn get_receipt(&self, tx_id: &str) -> Result<Option<TransactionReceipt>, SdkError>;
+
+    // Governance
+    async fn list_proposals(&self) -> Result<Vec<GovernanceProposal>, SdkError>;
+
+    async fn get_proposal(&self, proposal_id: &str) -> Result<GovernanceProposal, SdkError>;
+
+    /// Vote for `domain_id` during the voting period; needs `with_admin_token`
+    async fn cast_vote(&self, proposal_id: &str, domain_id: &str, approve: bool) -> Result<GovernanceProposal, SdkError>;
+
+    // Cross-domain transactions, served by the global synchronizer
+    /// Submit to the synchronizer; returns the transaction ID it assigned
+    async fn submit_cross_domain_transaction(&self, request: &CrossDomainTxRequest) -> Result<String, SdkError>;
+
+    /// Status with per-domain confirmations; `None` for transactions the
+    /// synchronizer is not coordinating
+    async fn get_cross_domain_status(&self, transaction_id: &str) -> Result<Option<CrossDomainStatus>, SdkError>;
+
+    async fn list_active_domains(&self) -> Result<Vec<String>, SdkError>;
+
+    /// Latest state the synchronizer holds for `domain_id`
+    async fn get_domain_state(&self, domain_id: &str) -> Result<DomainState, SdkError>;
+
+    // Transactions
+    async fn get_transaction(&self, tx_id_hex: &str) -> Result<Option<TransactionInfo>, SdkError>;
+
+    /// Full consensus and settlement detail from the global synchronizer;
+    /// `None` for unknown transactions. Votes are summarized unless
+    /// `include_votes` is set.
+    async fn get_transaction_details(
+        &self,
+        transaction_id: &str,
+        include_votes: bool,
+    ) -> Result<Option<TransactionDetails>, SdkError>;
+
+    async fn send_transaction_raw(&self, serialized: &str) -> Result<String, SdkError>;
+
+    async fn simulate_transaction_raw(&self, serialized: &str) -> Result<SimulationResult, SdkError>;
+
+    // Wallets
+    /// Balance may be bigint or number; returned as a JSON value to avoid
+    /// precision loss
+    async fn get_balance(&self, address_hex: &str) -> Result<Value, SdkError>;
+
+    // Node info
+    async fn get_version(&self) -> Result<String, SdkError>;
+
+    async fn get_health(&self) -> Result<String, SdkError>;
+
+    /// Batch RPC with one result per call, in call order. A failed call does
+    /// not affect the others; the outer error is for the request as a whole.
+    async fn rpc_batch(&self, calls: Vec<(&str, Option<Value>)>) -> Result<Vec<Result<Value, SdkError>>, SdkError>;
+
+    /// [`Self::rpc_batch`] for calls that all return `R`
+    async fn rpc_batch_typed<R: DeserializeOwned + Send>(
+        &self,
+        calls: Vec<(&str, Option<Value>)>,
+    ) -> Result<Vec<Result<R, SdkError>>, SdkError>
+    where
+        Self: Sized,
+    {
+        Ok(self
+            .rpc_batch(calls)
+            .await?
+            .into_iter()
+            .map(|result| result.and_then(|value| Ok(serde_json::from_value(value)?)))
+            .collect())
+    }
+
+    /// [`Self::rpc_batch`] keyed by request ID, which is the call's index plus one
+    async fn rpc_batch_with_ids(
+        &self,
+        calls: Vec<(&str, Option<Value>)>,
+    ) -> Result<HashMap<u64, Result<Value, SdkError>>, SdkError> {
+        Ok((1u64..).zip(self.rpc_batch(calls).await?).collect())
+    }
+}
+
+#[async_trait]
+impl GarpClientTrait for GarpClient {
+    async fn get_slot(&self) -> Result<i64, SdkError> {
         self.rpc::<i64>("getSlot", None).await
     }
 
-    pub async fn get_slot_leader(&self) -> Result<String, SdkError> {
+    async fn get_slot_leader(&self) -> Result<String, SdkError> {
         self.rpc::<String>("getSlotLeader", None).await
     }
 
-    // Blocks
-    pub async fn get_block_by_slot(&self, slot: i64) -> Result<Option<BlockInfo>, SdkError> {
+    async fn get_slot_leaders(&self, from_slot: u64, to_slot: u64) -> Result<Vec<SlotLeader>, SdkError> {
+        self.rpc::<Vec<SlotLeader>>("getSlotLeaders", Some(json!({ "from_slot": from_slot, "to_slot": to_slot }))).await
+    }
+
+    async fn get_block_by_slot(&self, slot: i64) -> Result<Option<BlockInfo>, SdkError> {
         self.rpc::<Option<BlockInfo>>("getBlock", Some(json!([slot]))).await
     }
 
-    pub async fn get_block_by_hash(&self, hash_hex: &str) -> Result<Option<BlockInfo>, SdkError> {
+    async fn get_block_by_hash(&self, hash_hex: &str) -> Result<Option<BlockInfo>, SdkError> {
         self.rpc::<Option<BlockInfo>>("getBlock", Some(json!([hash_hex]))).await
     }
 
-    // Transactions
-    pub async fn get_transaction(&self, tx_id_hex: &str) -> Result<Option<TransactionInfo>, SdkError> {
+    async fn get_tx_proofs(&self, block: u64, tx_ids: &[&str]) -> Result<TxProofs, SdkError> {
+        let v: ApiResponse<TxProofs> =
+            self.post(&format!("/api/v1/blocks/{}/proofs", block), &json!({ "tx_ids": tx_ids })).await?;
+        match v {
+            ApiResponse { success: true, data: Some(proofs), .. } => Ok(proofs),
+            ApiResponse { error, .. } => Err(SdkError::Api(error.unwrap_or_else(|| "empty response".to_string()))),
+        }
+    }
+
+    async fn get_settlement_proof(&self, transaction_id: &str) -> Result<SettlementProof, SdkError> {
+        self.rpc::<SettlementProof>("getSettlementProof", Some(json!({ "transaction_id": transaction_id }))).await
+    }
+
+    async fn get_receipt(&self, tx_id: &str) -> Result<Option<TransactionReceipt>, SdkError> {
+        let resp = self.http.get(format!("{}/api/v1/transactions/{}/receipt", self.base_url, tx_id)).send().await?;
+        let v: ApiResponse<TransactionReceipt> = resp.json().await?;
+        match v {
+            ApiResponse { success: true, data, .. } => Ok(data),
+            ApiResponse { error, .. } => Err(SdkError::Api(error.unwrap_or_else(|| "empty response".to_string()))),
+        }
+    }
+
+    async fn list_proposals(&self) -> Result<Vec<GovernanceProposal>, SdkError> {
+        self.rpc::<Vec<GovernanceProposal>>("listProposals", None).await
+    }
+
+    async fn get_proposal(&self, proposal_id: &str) -> Result<GovernanceProposal, SdkError> {
+        self.rpc::<GovernanceProposal>("getProposal", Some(json!({ "proposal_id": proposal_id }))).await
+    }
+
+    async fn cast_vote(&self, proposal_id: &str, domain_id: &str, approve: bool) -> Result<GovernanceProposal, SdkError> {
+        self.rpc::<GovernanceProposal>(
+            "castVote",
+            Some(json!({ "proposal_id": proposal_id, "domain_id": domain_id, "approve": approve })),
+        )
+        .await
+    }
+
+    async fn submit_cross_domain_transaction(&self, request: &CrossDomainTxRequest) -> Result<String, SdkError> {
+        let submitted = self
+            .rpc::<cross_domain::SubmittedCrossDomainTx>("submitCrossDomainTransaction", Some(serde_json::to_value(request)?))
+            .await?;
+        Ok(submitted.transaction_id)
+    }
+
+    async fn get_cross_domain_status(&self, transaction_id: &str) -> Result<Option<CrossDomainStatus>, SdkError> {
+        self.rpc::<Option<CrossDomainStatus>>("getCrossDomainStatus", Some(json!({ "transaction_id": transaction_id }))).await
+    }
+
+    async fn list_active_domains(&self) -> Result<Vec<String>, SdkError> {
+        self.rpc::<Vec<String>>("listActiveDomains", None).await
+    }
+
+    async fn get_domain_state(&self, domain_id: &str) -> Result<DomainState, SdkError> {
+        self.rpc::<DomainState>("getDomainState", Some(json!({ "domain_id": domain_id }))).await
+    }
+
+    async fn get_transaction(&self, tx_id_hex: &str) -> Result<Option<TransactionInfo>, SdkError> {
         self.rpc::<Option<TransactionInfo>>("getTransaction", Some(json!([tx_id_hex]))).await
     }
 
-    pub async fn send_transaction_raw(&self, serialized: &str) -> Result<String, SdkError> {
-        self.rpc::<String>("sendTransaction", Some(json!([serialized]))).await
+    async fn get_transaction_details(
+        &self,
+        transaction_id: &str,
+        include_votes: bool,
+    ) -> Result<Option<TransactionDetails>, SdkError> {
+        self.rpc::<Option<TransactionDetails>>(
+            "getTransactionDetails",
+            Some(json!({ "transaction_id": transaction_id, "include_votes": include_votes })),
+        )
+        .await
     }
 
-    #[derive(Debug, Clone, Deserialize, Serialize)]
-    pub struct SimulationResult {
-        pub ok: bool,
-        #[serde(default)]
-        pub logs: Option<Vec<String>>,
-        #[serde(default)]
-        pub error: Option<String>,
+    async fn send_transaction_raw(&self, serialized: &str) -> Result<String, SdkError> {
+        self.rpc::<String>("sendTransaction", Some(json!([serialized]))).await
     }
 
-    pub async fn simulate_transaction_raw(&self, serialized: &str) -> Result<SimulationResult, SdkError> {
+    async fn simulate_transaction_raw(&self, serialized: &str) -> Result<SimulationResult, SdkError> {
         self.rpc::<SimulationResult>("simulateTransaction", Some(json!([serialized]))).await
     }
 
-    // Wallets
-    pub async fn get_balance(&self, address_hex: &str) -> Result<serde_json::Value, SdkError> {
-        // Balance may be bigint or number; return as JSON value to avoid precision loss
+    async fn get_balance(&self, address_hex: &str) -> Result<serde_json::Value, SdkError> {
         self.rpc::<serde_json::Value>("getBalance", Some(json!([address_hex]))).await
     }
 
-    // Node info
-    pub async fn get_version(&self) -> Result<String, SdkError> {
+    async fn get_version(&self) -> Result<String, SdkError> {
         self.rpc::<String>("getVersion", None).await
     }
 
-    pub async fn get_health(&self) -> Result<String, SdkError> {
+    async fn get_health(&self) -> Result<String, SdkError> {
         self.rpc::<String>("getHealth", None).await
     }
 
-    // Batch RPC: returns Vec<serde_json::Value> preserving order
-    pub async fn rpc_batch(&self, calls: Vec<(&str, Option<Value>)>) -> Result<Vec<Value>, SdkError> {
-        // Build batch payload
-        let mut id = 1u64;
-        let payload: Vec<JsonRpcRequest> = calls
-            .iter()
-            .map(|(m, p)| {
-                let req = JsonRpcRequest { jsonrpc: "2.0", id, method: m, params: p.clone() };
-                id += 1;
-                req
-            })
-            .collect();
-        let resp = self
-            .http
-            .post(format!("{}/rpc", self.base_url))
-            .json(&payload)
-            .send()
-            .await?;
-        let v: Vec<JsonRpcResponse<Value>> = resp.json().await?;
-        let mut out = Vec::with_capacity(v.len());
-        for item in v {
-            match item {
-                JsonRpcResponse::Ok { result, .. } => out.push(result),
-                JsonRpcResponse::Err { error, .. } => {
-                    return Err(SdkError::Rpc { code: error.code, message: error.message })
-                }
-            }
+    async fn rpc_batch(&self, calls: Vec<(&str, Option<Value>)>) -> Result<Vec<Result<Value, SdkError>>, SdkError> {
+        let items = self.post_batch(&calls).await?;
+        let mut by_id = batch_results(items);
+        Ok((1..=calls.len() as u64).map(|id| take_batch_result(&mut by_id, id)).collect())
+    }
+}
+
+/// Parse each batch response item on its own. Items that are not a
+/// response with a numeric ID cannot be matched to a call and are dropped.
+fn batch_results(items: Vec<Value>) -> HashMap<u64, Result<Value, SdkError>> {
+    items
+        .into_iter()
+        .filter_map(|item| serde_json::from_value::<JsonRpcResponse<Value>>(item).ok())
+        .map(|response| match response {
+            JsonRpcResponse::Ok { id, result, .. } => (id, Ok(result)),
+            JsonRpcResponse::Err { id, error, .. } => (id, Err(error.into())),
+        })
+        .collect()
+}
+
+fn take_batch_result(by_id: &mut HashMap<u64, Result<Value, SdkError>>, id: u64) -> Result<Value, SdkError> {
+    by_id.remove(&id).unwrap_or_else(|| Err(SdkError::Api(format!("no response for batch call {}", id))))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_results_are_independent_per_call() {
+        let items = vec![
+            json!({ "jsonrpc": "2.0", "id": 3, "result": "third" }),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": 7 }),
+            json!({ "jsonrpc": "2.0", "id": 2, "error": { "code": -32601, "message": "method not found" } }),
+            json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32600, "message": "invalid request" } }),
+        ];
+        let mut by_id = batch_results(items);
+        let results: Vec<_> = (1..=4).map(|id| take_batch_result(&mut by_id, id)).collect();
+
+        assert_eq!(results[0].as_ref().unwrap(), &json!(7));
+        assert!(matches!(&results[1], Err(SdkError::Rpc { code: ErrorCode::MethodNotFound, .. })));
+        assert_eq!(results[2].as_ref().unwrap(), &json!("third"));
+        assert!(matches!(&results[3], Err(SdkError::Api(_))), "call without a response");
+    }
+
+    #[test]
+    fn test_error_codes_match_garp_common() {
+        for code in garp_common::ErrorCode::ALL {
+            let observed = ErrorCode::from_code(code.code());
+            assert_eq!(observed.name(), code.name());
+            assert_eq!(observed.is_retryable(), code.is_retryable());
         }
-        Ok(out)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_node_errors_round_trip_to_typed_codes() {
+        // Error objects built the way the nodes build them
+        let rpc_error = |id: u64, error: garp_common::GarpError| {
+            let code = error.code();
+            json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code.code(), "message": error.to_string(), "data": code.rpc_data() } })
+        };
+        let items = vec![
+            rpc_error(1, garp_common::GarpError::Unavailable("Domain d1 is suspended for maintenance".to_string())),
+            rpc_error(2, garp_common::GarpError::ValidationError("Transaction data is empty".to_string())),
+            rpc_error(3, garp_common::GarpError::FeeExceeded { required: 10, max_fee: 5 }),
+            json!({ "jsonrpc": "2.0", "id": 4, "error": { "code": 9999, "message": "new", "data": { "name": "NEW", "retryable": true } } }),
+        ];
+        let mut by_id = batch_results(items);
+        let errors: Vec<SdkError> = (1..=4).map(|id| take_batch_result(&mut by_id, id).unwrap_err()).collect();
+
+        assert!(matches!(&errors[0], SdkError::Rpc { code: ErrorCode::DomainUnavailable, retryable: true, .. }));
+        assert!(matches!(&errors[1], SdkError::Rpc { code: ErrorCode::ValidationFailed, retryable: false, .. }));
+        assert!(matches!(&errors[2], SdkError::Rpc { code: ErrorCode::FeeExceeded, retryable: false, .. }));
+        assert!(matches!(&errors[3], SdkError::Rpc { code: ErrorCode::Unknown(9999), retryable: true, .. }));
+        assert_eq!(errors[0].to_string(), "rpc error DOMAIN_UNAVAILABLE: Unavailable: Domain d1 is suspended for maintenance");
+    }
+}
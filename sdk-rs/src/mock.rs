@@ -0,0 +1,333 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::cross_domain::SubmittedCrossDomainTx;
+use crate::{
+    BlockInfo, CrossDomainStatus, CrossDomainTxRequest, DomainState, ErrorCode, GarpClientTrait, GovernanceProposal,
+    SdkError, SettlementProof, SimulationResult, SlotLeader, TransactionDetails, TransactionInfo, TransactionReceipt,
+    TxProofs,
+};
+
+/// One configured response; `params` of `None` matches any parameters
+struct MockResponse {
+    method: String,
+    params: Option<Option<Value>>,
+    response: Result<Value, SdkError>,
+}
+
+/// Canned responses keyed by JSON-RPC method and parameters, used by
+/// [`MockGarpClient`].
+///
+/// Calls are matched by method name and the exact parameters the real
+/// client would send, falling back to a response registered for the method
+/// with [`Self::on_any`]. Calls that go through the REST API rather than
+/// JSON-RPC are matched as `getTxProofs` with `{ "block", "tx_ids" }` and
+/// `getReceipt` with `[tx_id]`. Calls with no configured response fail
+/// with [`ErrorCode::MethodNotFound`].
+#[derive(Default)]
+pub struct MockResponder {
+    responses: Vec<MockResponse>,
+}
+
+impl MockResponder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer `method` called with exactly `params`
+    pub fn on(mut self, method: impl Into<String>, params: Option<Value>, response: Result<Value, SdkError>) -> Self {
+        self.responses.push(MockResponse { method: method.into(), params: Some(params), response });
+        self
+    }
+
+    /// Answer `method` whatever its parameters
+    pub fn on_any(mut self, method: impl Into<String>, response: Result<Value, SdkError>) -> Self {
+        self.responses.push(MockResponse { method: method.into(), params: None, response });
+        self
+    }
+
+    /// Answer `getSlot` with `slot`
+    pub fn slot(self, slot: i64) -> Self {
+        self.on_any("getSlot", Ok(json!(slot)))
+    }
+
+    /// Answer `getBlock` by the block's slot and by its hash
+    pub fn block(self, block: BlockInfo) -> Self {
+        let value = serde_json::to_value(&block).expect("block info serializes");
+        self.on("getBlock", Some(json!([block.slot])), Ok(value.clone()))
+            .on("getBlock", Some(json!([block.hash])), Ok(value))
+    }
+
+    /// Answer `getTransaction` for the transaction's ID
+    pub fn tx(self, tx: TransactionInfo) -> Self {
+        let value = serde_json::to_value(&tx).expect("transaction info serializes");
+        self.on("getTransaction", Some(json!([tx.id])), Ok(value))
+    }
+
+    /// The response for `method` called with `params`. Exact matches win
+    /// over [`Self::on_any`] responses; later registrations win over
+    /// earlier ones.
+    pub fn respond(&self, method: &str, params: &Option<Value>) -> Result<Value, SdkError> {
+        let by_method = || self.responses.iter().rev().filter(|r| r.method == method);
+        let response = by_method()
+            .find(|r| r.params.as_ref() == Some(params))
+            .or_else(|| by_method().find(|r| r.params.is_none()));
+        match response {
+            Some(r) => replay(&r.response),
+            None => Err(SdkError::Rpc {
+                code: ErrorCode::MethodNotFound,
+                message: format!("no mock response for {} {}", method, params.as_ref().unwrap_or(&Value::Null)),
+                retryable: false,
+            }),
+        }
+    }
+}
+
+/// A copy of a configured response. HTTP errors cannot be cloned and are
+/// replayed as [`SdkError::Api`] with the same message.
+fn replay(response: &Result<Value, SdkError>) -> Result<Value, SdkError> {
+    match response {
+        Ok(value) => Ok(value.clone()),
+        Err(SdkError::Rpc { code, message, retryable }) => {
+            Err(SdkError::Rpc { code: *code, message: message.clone(), retryable: *retryable })
+        }
+        Err(SdkError::Cbor(message)) => Err(SdkError::Cbor(message.clone())),
+        Err(SdkError::Api(message)) => Err(SdkError::Api(message.clone())),
+        Err(SdkError::WebSocket(message)) => Err(SdkError::WebSocket(message.clone())),
+        Err(e @ (SdkError::Http(_) | SdkError::Serde(_))) => Err(SdkError::Api(e.to_string())),
+    }
+}
+
+/// In-memory [`GarpClientTrait`] for unit testing SDK consumers without a
+/// running node.
+///
+/// ```
+/// use garp_sdk::{GarpClientTrait, MockGarpClient};
+///
+/// # async fn demo() -> Result<(), garp_sdk::SdkError> {
+/// let client = MockGarpClient::with_slot(42);
+/// assert_eq!(client.get_slot().await?, 42);
+/// assert_eq!(client.calls(), vec![("getSlot".to_string(), None)]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct MockGarpClient {
+    responder: MockResponder,
+    calls: Mutex<Vec<(String, Option<Value>)>>,
+}
+
+impl MockGarpClient {
+    pub fn new(responder: MockResponder) -> Self {
+        Self { responder, calls: Mutex::new(Vec::new()) }
+    }
+
+    /// Mock answering `getSlot` with `slot`
+    pub fn with_slot(slot: i64) -> Self {
+        Self::new(MockResponder::new().slot(slot))
+    }
+
+    /// Mock serving `block` by slot and by hash
+    pub fn with_block(block: BlockInfo) -> Self {
+        Self::new(MockResponder::new().block(block))
+    }
+
+    /// Mock serving `tx` by its ID
+    pub fn with_tx(tx: TransactionInfo) -> Self {
+        Self::new(MockResponder::new().tx(tx))
+    }
+
+    /// Calls made so far as `(method, params)`, in order
+    pub fn calls(&self) -> Vec<(String, Option<Value>)> {
+        self.calls.lock().expect("mock call log poisoned").clone()
+    }
+
+    fn call_value(&self, method: &str, params: Option<Value>) -> Result<Value, SdkError> {
+        let response = self.responder.respond(method, &params);
+        self.calls.lock().expect("mock call log poisoned").push((method.to_string(), params));
+        response
+    }
+
+    fn call<R: DeserializeOwned>(&self, method: &str, params: Option<Value>) -> Result<R, SdkError> {
+        Ok(serde_json::from_value(self.call_value(method, params)?)?)
+    }
+}
+
+#[async_trait]
+impl GarpClientTrait for MockGarpClient {
+    async fn get_slot(&self) -> Result<i64, SdkError> {
+        self.call("getSlot", None)
+    }
+
+    async fn get_slot_leader(&self) -> Result<String, SdkError> {
+        self.call("getSlotLeader", None)
+    }
+
+    async fn get_slot_leaders(&self, from_slot: u64, to_slot: u64) -> Result<Vec<SlotLeader>, SdkError> {
+        self.call("getSlotLeaders", Some(json!({ "from_slot": from_slot, "to_slot": to_slot })))
+    }
+
+    async fn get_block_by_slot(&self, slot: i64) -> Result<Option<BlockInfo>, SdkError> {
+        self.call("getBlock", Some(json!([slot])))
+    }
+
+    async fn get_block_by_hash(&self, hash_hex: &str) -> Result<Option<BlockInfo>, SdkError> {
+        self.call("getBlock", Some(json!([hash_hex])))
+    }
+
+    async fn get_tx_proofs(&self, block: u64, tx_ids: &[&str]) -> Result<TxProofs, SdkError> {
+        self.call("getTxProofs", Some(json!({ "block": block, "tx_ids": tx_ids })))
+    }
+
+    async fn get_settlement_proof(&self, transaction_id: &str) -> Result<SettlementProof, SdkError> {
+        self.call("getSettlementProof", Some(json!({ "transaction_id": transaction_id })))
+    }
+
+    async fn get_receipt(&self, tx_id: &str) -> Result<Option<TransactionReceipt>, SdkError> {
+        self.call("getReceipt", Some(json!([tx_id])))
+    }
+
+    async fn list_proposals(&self) -> Result<Vec<GovernanceProposal>, SdkError> {
+        self.call("listProposals", None)
+    }
+
+    async fn get_proposal(&self, proposal_id: &str) -> Result<GovernanceProposal, SdkError> {
+        self.call("getProposal", Some(json!({ "proposal_id": proposal_id })))
+    }
+
+    async fn cast_vote(&self, proposal_id: &str, domain_id: &str, approve: bool) -> Result<GovernanceProposal, SdkError> {
+        self.call("castVote", Some(json!({ "proposal_id": proposal_id, "domain_id": domain_id, "approve": approve })))
+    }
+
+    async fn submit_cross_domain_transaction(&self, request: &CrossDomainTxRequest) -> Result<String, SdkError> {
+        let submitted: SubmittedCrossDomainTx =
+            self.call("submitCrossDomainTransaction", Some(serde_json::to_value(request)?))?;
+        Ok(submitted.transaction_id)
+    }
+
+    async fn get_cross_domain_status(&self, transaction_id: &str) -> Result<Option<CrossDomainStatus>, SdkError> {
+        self.call("getCrossDomainStatus", Some(json!({ "transaction_id": transaction_id })))
+    }
+
+    async fn list_active_domains(&self) -> Result<Vec<String>, SdkError> {
+        self.call("listActiveDomains", None)
+    }
+
+    async fn get_domain_state(&self, domain_id: &str) -> Result<DomainState, SdkError> {
+        self.call("getDomainState", Some(json!({ "domain_id": domain_id })))
+    }
+
+    async fn get_transaction(&self, tx_id_hex: &str) -> Result<Option<TransactionInfo>, SdkError> {
+        self.call("getTransaction", Some(json!([tx_id_hex])))
+    }
+
+    async fn get_transaction_details(
+        &self,
+        transaction_id: &str,
+        include_votes: bool,
+    ) -> Result<Option<TransactionDetails>, SdkError> {
+        self.call(
+            "getTransactionDetails",
+            Some(json!({ "transaction_id": transaction_id, "include_votes": include_votes })),
+        )
+    }
+
+    async fn send_transaction_raw(&self, serialized: &str) -> Result<String, SdkError> {
+        self.call("sendTransaction", Some(json!([serialized])))
+    }
+
+    async fn simulate_transaction_raw(&self, serialized: &str) -> Result<SimulationResult, SdkError> {
+        self.call("simulateTransaction", Some(json!([serialized])))
+    }
+
+    async fn get_balance(&self, address_hex: &str) -> Result<Value, SdkError> {
+        self.call_value("getBalance", Some(json!([address_hex])))
+    }
+
+    async fn get_version(&self) -> Result<String, SdkError> {
+        self.call("getVersion", None)
+    }
+
+    async fn get_health(&self) -> Result<String, SdkError> {
+        self.call("getHealth", None)
+    }
+
+    async fn rpc_batch(&self, calls: Vec<(&str, Option<Value>)>) -> Result<Vec<Result<Value, SdkError>>, SdkError> {
+        Ok(calls.into_iter().map(|(method, params)| self.call_value(method, params)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SimulableTransaction, TransactionBuilder};
+
+    fn block(slot: i64) -> BlockInfo {
+        BlockInfo {
+            slot,
+            hash: format!("hash-{}", slot),
+            parent_hash: None,
+            timestamp_ms: None,
+            leader: Some("leader-1".to_string()),
+            transactions: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_helpers_answer_the_matching_calls() {
+        let client = MockGarpClient::with_slot(42);
+        assert_eq!(client.get_slot().await.unwrap(), 42);
+
+        let client = MockGarpClient::with_block(block(7));
+        let by_slot = client.get_block_by_slot(7).await.unwrap().unwrap();
+        let by_hash = client.get_block_by_hash("hash-7").await.unwrap().unwrap();
+        assert_eq!((by_slot.hash.as_str(), by_hash.slot), ("hash-7", 7));
+
+        let tx = TransactionInfo {
+            id: "tx-1".to_string(),
+            submitter: Some("alice".to_string()),
+            status: Some("committed".to_string()),
+            created_at: None,
+            error: None,
+        };
+        let client = MockGarpClient::with_tx(tx);
+        let fetched = client.get_transaction("tx-1").await.unwrap().unwrap();
+        assert_eq!(fetched.status.as_deref(), Some("committed"));
+        assert!(matches!(
+            client.get_transaction("tx-2").await,
+            Err(SdkError::Rpc { code: ErrorCode::MethodNotFound, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exact_params_win_and_errors_replay() {
+        let client = MockGarpClient::new(
+            MockResponder::new()
+                .on_any("getBlock", Ok(Value::Null))
+                .on("getBlock", Some(json!([3])), Ok(serde_json::to_value(block(3)).unwrap()))
+                .on_any(
+                    "sendTransaction",
+                    Err(SdkError::Rpc { code: ErrorCode::DomainUnavailable, message: "suspended".to_string(), retryable: true }),
+                ),
+        );
+
+        assert!(client.get_block_by_slot(3).await.unwrap().is_some());
+        assert!(client.get_block_by_slot(4).await.unwrap().is_none());
+
+        // Consumer code goes through the trait, here via the transaction builder
+        let tx = TransactionBuilder::exercise_contract("c1", "Transfer").build();
+        for _ in 0..2 {
+            assert!(tx.submit(&client).await.unwrap_err().is_retryable());
+        }
+        let wire = tx.to_wire().unwrap();
+        let calls = client.calls();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[3], ("sendTransaction".to_string(), Some(json!([wire]))));
+
+        let results = client.rpc_batch(vec![("getBlock", Some(json!([3]))), ("getHealth", None)]).await.unwrap();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+}
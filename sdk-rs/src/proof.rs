@@ -0,0 +1,148 @@
+//! Offline verification of transaction inclusion proofs.
+//!
+//! Proofs use the participant node's encoding: SHA-256 over the leaf bytes,
+//! parents hashed as `sha256(left || right)` with the last node duplicated on
+//! odd levels, and every hash hex-encoded. `directions[i]` is `"right"` when
+//! the running hash is the right child at level `i` (so `path[i]` is hashed
+//! first) and `"left"` otherwise.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Inclusion proof for a single transaction
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MerkleProof {
+    pub block_hash: String,
+    pub tx_id: String,
+    pub leaf_hash: String,
+    pub root: String,
+    pub path: Vec<String>,
+    pub directions: Vec<String>,
+    /// Node-side verification result; not trusted by [`verify_merkle_proof`]
+    #[serde(default)]
+    pub valid: bool,
+}
+
+/// Per-transaction entry of a batch proof response
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxProofItem {
+    pub tx_id: String,
+    #[serde(default)]
+    pub proof: Option<MerkleProof>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Batch proof response for one block
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxProofs {
+    pub block_hash: String,
+    pub root: String,
+    pub proofs: Vec<TxProofItem>,
+}
+
+/// Leaf hash of a transaction id as committed by the node (SHA-256 over the
+/// 16 UUID bytes). Returns `None` if `tx_id` is not a UUID.
+pub fn tx_leaf_hash(tx_id: &str) -> Option<String> {
+    let digits: String = tx_id.chars().filter(|c| *c != '-').collect();
+    if digits.len() != 32 {
+        return None;
+    }
+    let bytes = hex::decode(digits).ok()?;
+    Some(hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Recompute the root from `proof` and compare it with `expected_root`.
+///
+/// Only the leaf hash, path and directions are used; malformed hex or an
+/// unknown direction makes the proof invalid.
+pub fn verify_merkle_proof(proof: &MerkleProof, expected_root: &str) -> bool {
+    if proof.path.len() != proof.directions.len() {
+        return false;
+    }
+    let Ok(mut current) = hex::decode(&proof.leaf_hash) else {
+        return false;
+    };
+    for (sibling, direction) in proof.path.iter().zip(&proof.directions) {
+        let Ok(sibling) = hex::decode(sibling) else {
+            return false;
+        };
+        let mut hasher = Sha256::new();
+        match direction.as_str() {
+            "right" => {
+                hasher.update(&sibling);
+                hasher.update(&current);
+            }
+            "left" => {
+                hasher.update(&current);
+                hasher.update(&sibling);
+            }
+            _ => return false,
+        }
+        current = hasher.finalize().to_vec();
+    }
+    match hex::decode(expected_root) {
+        Ok(root) => current == root,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same tree as the participant node's merkle tests: leaves "a", "b", "c"
+    const ABC_ROOT: &str = "d31a37ef6ac14a2db1470c4316beb5592e6afd4465022339adafda76a18ffabe";
+
+    fn proof_for_c() -> MerkleProof {
+        MerkleProof {
+            block_hash: String::new(),
+            tx_id: String::new(),
+            leaf_hash: "2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6".to_string(),
+            root: ABC_ROOT.to_string(),
+            path: vec![
+                "2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6".to_string(),
+                "e5a01fee14e0ed5c48714f22180f25ad8365b53f9779f79dc4a3d7e93963f94a".to_string(),
+            ],
+            directions: vec!["left".to_string(), "right".to_string()],
+            valid: true,
+        }
+    }
+
+    #[test]
+    fn test_verifies_node_vector() {
+        let proof = proof_for_c();
+        assert!(verify_merkle_proof(&proof, ABC_ROOT));
+        assert!(!verify_merkle_proof(&proof, &"00".repeat(32)));
+    }
+
+    #[test]
+    fn test_rejects_tampered_proofs() {
+        let mut flipped = proof_for_c();
+        flipped.directions[1] = "left".to_string();
+        assert!(!verify_merkle_proof(&flipped, ABC_ROOT));
+
+        let mut unknown = proof_for_c();
+        unknown.directions[0] = "up".to_string();
+        assert!(!verify_merkle_proof(&unknown, ABC_ROOT));
+
+        let mut truncated = proof_for_c();
+        truncated.path.pop();
+        assert!(!verify_merkle_proof(&truncated, ABC_ROOT));
+    }
+
+    #[test]
+    fn test_proof_json_round_trip() {
+        let proof = proof_for_c();
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: MerkleProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+    }
+
+    #[test]
+    fn test_tx_leaf_hash() {
+        let id = "00000000-0000-0000-0000-000000000000";
+        assert_eq!(tx_leaf_hash(id).unwrap(), hex::encode(Sha256::digest([0u8; 16])));
+        assert!(tx_leaf_hash("not-a-uuid").is_none());
+    }
+}
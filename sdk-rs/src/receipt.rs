@@ -0,0 +1,171 @@
+//! Offline verification of transaction receipts from the global synchronizer.
+//!
+//! A block's hash is `blake3(parent_hash || height_le || tx_root)`, where
+//! `tx_root` is the Merkle root checked by [`verify_merkle_proof`]. Given the
+//! block's finality certificate, a receipt is checked by recomputing the
+//! certified block hash from its parent hash and the root its inclusion proof
+//! leads to.
+
+use serde::{Deserialize, Serialize};
+
+use crate::proof::{tx_leaf_hash, MerkleProof};
+
+/// Finality certificate of a global synchronizer block
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FinalityCertificate {
+    pub height: u64,
+    pub block_hash: String,
+    /// `(validator, signature)` pairs
+    pub signatures: Vec<(String, Vec<u8>)>,
+    pub validator_set_hash: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub state_root: String,
+}
+
+/// Evidence that a transaction is in a finalized block
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionReceipt {
+    pub transaction_id: String,
+    pub block_hash: String,
+    pub block_height: u64,
+    pub parent_hash: String,
+    pub merkle_proof: MerkleProof,
+    /// Zero-knowledge settlement proof, verified against the synchronizer's
+    /// proving parameters rather than by this SDK
+    #[serde(default)]
+    pub settlement_proof: Option<serde_json::Value>,
+    pub finalized_at: String,
+    #[serde(default)]
+    pub domains_confirmed: Vec<String>,
+}
+
+/// Root the proof leads to, or `None` if it is malformed
+fn proof_root(proof: &MerkleProof) -> Option<Vec<u8>> {
+    use sha2::{Digest, Sha256};
+
+    if proof.path.len() != proof.directions.len() {
+        return None;
+    }
+    let mut current = hex::decode(&proof.leaf_hash).ok()?;
+    for (sibling, direction) in proof.path.iter().zip(&proof.directions) {
+        let sibling = hex::decode(sibling).ok()?;
+        let mut hasher = Sha256::new();
+        match direction.as_str() {
+            "right" => {
+                hasher.update(&sibling);
+                hasher.update(&current);
+            }
+            "left" => {
+                hasher.update(&current);
+                hasher.update(&sibling);
+            }
+            _ => return None,
+        }
+        current = hasher.finalize().to_vec();
+    }
+    Some(current)
+}
+
+/// Check that `receipt` proves its transaction is in the block certified by
+/// `certificate`. The certificate's signatures are not checked here.
+pub fn verify_receipt(receipt: &TransactionReceipt, certificate: &FinalityCertificate) -> bool {
+    if certificate.height != receipt.block_height
+        || !certificate.block_hash.eq_ignore_ascii_case(&receipt.block_hash)
+        || receipt.merkle_proof.block_hash != receipt.block_hash
+    {
+        return false;
+    }
+    if tx_leaf_hash(&receipt.transaction_id).as_deref() != Some(receipt.merkle_proof.leaf_hash.as_str()) {
+        return false;
+    }
+    let (Some(root), Ok(parent_hash), Ok(block_hash)) = (
+        proof_root(&receipt.merkle_proof),
+        hex::decode(&receipt.parent_hash),
+        hex::decode(&receipt.block_hash),
+    ) else {
+        return false;
+    };
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&parent_hash);
+    hasher.update(&receipt.block_height.to_le_bytes());
+    hasher.update(&root);
+    hasher.finalize().as_bytes().as_slice() == block_hash.as_slice()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    const TX_A: &str = "00000000-0000-0000-0000-00000000000a";
+    const TX_B: &str = "00000000-0000-0000-0000-00000000000b";
+
+    fn receipt() -> (TransactionReceipt, FinalityCertificate) {
+        let leaf_a = tx_leaf_hash(TX_A).unwrap();
+        let leaf_b = tx_leaf_hash(TX_B).unwrap();
+        let mut hasher = Sha256::new();
+        hasher.update(hex::decode(&leaf_a).unwrap());
+        hasher.update(hex::decode(&leaf_b).unwrap());
+        let root = hasher.finalize().to_vec();
+
+        let parent_hash = [7u8; 32];
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&parent_hash);
+        hasher.update(&3u64.to_le_bytes());
+        hasher.update(&root);
+        let block_hash = hex::encode(hasher.finalize().as_bytes());
+
+        let receipt = TransactionReceipt {
+            transaction_id: TX_B.to_string(),
+            block_hash: block_hash.clone(),
+            block_height: 3,
+            parent_hash: hex::encode(parent_hash),
+            merkle_proof: MerkleProof {
+                block_hash: block_hash.clone(),
+                tx_id: TX_B.to_string(),
+                leaf_hash: leaf_b,
+                root: hex::encode(&root),
+                path: vec![leaf_a],
+                directions: vec!["right".to_string()],
+                valid: true,
+            },
+            settlement_proof: None,
+            finalized_at: "2024-01-01T00:00:00Z".to_string(),
+            domains_confirmed: vec!["domain1".to_string()],
+        };
+        let certificate = FinalityCertificate {
+            height: 3,
+            block_hash,
+            signatures: Vec::new(),
+            validator_set_hash: String::new(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            state_root: String::new(),
+        };
+        (receipt, certificate)
+    }
+
+    #[test]
+    fn test_verifies_receipt() {
+        let (receipt, certificate) = receipt();
+        assert!(verify_receipt(&receipt, &certificate));
+    }
+
+    #[test]
+    fn test_rejects_tampered_receipts() {
+        let (receipt, certificate) = receipt();
+
+        let mut other_tx = receipt.clone();
+        other_tx.transaction_id = TX_A.to_string();
+        assert!(!verify_receipt(&other_tx, &certificate));
+
+        let mut other_parent = receipt.clone();
+        other_parent.parent_hash = hex::encode([8u8; 32]);
+        assert!(!verify_receipt(&other_parent, &certificate));
+
+        let mut other_block = certificate.clone();
+        other_block.height = 4;
+        assert!(!verify_receipt(&receipt, &other_block));
+    }
+}
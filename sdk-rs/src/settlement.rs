@@ -0,0 +1,155 @@
+//! Offline verification of settlement proofs from the global synchronizer.
+//!
+//! The synchronizer signs, with Ed25519, a SHA-256 digest over
+//! `"garp-settlement-proof-v1"` followed by the settlement id, the 16
+//! transaction UUID bytes, the domain count, each domain id and confirmation
+//! hash in domain order, and the finality height, block hash and validator
+//! set hash. Byte strings are prefixed with their length and integers are
+//! big-endian `u64`, matching `garp_common::settlement`.
+
+use std::collections::BTreeMap;
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const PROOF_DOMAIN_TAG: &[u8] = b"garp-settlement-proof-v1";
+
+/// Finality certificate of the block containing a settlement
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FinalityReference {
+    pub height: u64,
+    pub block_hash: String,
+    pub validator_set_hash: String,
+}
+
+/// Signature attached to a settlement proof
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProofSignature {
+    pub algorithm: String,
+    pub signature: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
+/// Evidence that a settlement completed across its domains
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SettlementProof {
+    pub settlement_id: String,
+    pub transaction_id: String,
+    pub domain_confirmations: BTreeMap<String, Vec<u8>>,
+    pub finality: FinalityReference,
+    pub signature: ProofSignature,
+    /// Not covered by the signature
+    pub created_at: String,
+}
+
+fn put(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_be_bytes());
+    hasher.update(bytes);
+}
+
+/// Digest the synchronizer signs. Returns `None` if the transaction id is
+/// not a UUID.
+pub fn settlement_proof_digest(proof: &SettlementProof) -> Option<Vec<u8>> {
+    let digits: String = proof.transaction_id.chars().filter(|c| *c != '-').collect();
+    if digits.len() != 32 {
+        return None;
+    }
+    let transaction_id = hex::decode(digits).ok()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(PROOF_DOMAIN_TAG);
+    put(&mut hasher, proof.settlement_id.as_bytes());
+    put(&mut hasher, &transaction_id);
+    hasher.update((proof.domain_confirmations.len() as u64).to_be_bytes());
+    for (domain_id, hash) in &proof.domain_confirmations {
+        put(&mut hasher, domain_id.as_bytes());
+        put(&mut hasher, hash);
+    }
+    hasher.update(proof.finality.height.to_be_bytes());
+    put(&mut hasher, proof.finality.block_hash.as_bytes());
+    put(&mut hasher, proof.finality.validator_set_hash.as_bytes());
+    Some(hasher.finalize().to_vec())
+}
+
+/// Check that `proof` was signed by one of `validator_keys` (raw 32-byte
+/// Ed25519 public keys) and has not been altered since.
+pub fn verify_settlement_proof(proof: &SettlementProof, validator_keys: &[Vec<u8>]) -> bool {
+    if proof.signature.algorithm != "Ed25519" || proof.domain_confirmations.is_empty() {
+        return false;
+    }
+    if !validator_keys.iter().any(|key| key == &proof.signature.public_key) {
+        return false;
+    }
+    let Ok(key_bytes) = <[u8; 32]>::try_from(proof.signature.public_key.as_slice()) else {
+        return false;
+    };
+    let Ok(public_key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return false;
+    };
+    let Ok(signature_bytes) = <[u8; 64]>::try_from(proof.signature.signature.as_slice()) else {
+        return false;
+    };
+    let Some(digest) = settlement_proof_digest(proof) else {
+        return false;
+    };
+    public_key.verify(&digest, &Signature::from_bytes(&signature_bytes)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_proof(key: &SigningKey) -> SettlementProof {
+        let mut proof = SettlementProof {
+            settlement_id: "s1".to_string(),
+            transaction_id: "00000000-0000-0000-0000-000000000000".to_string(),
+            domain_confirmations: [("a".to_string(), vec![1; 32]), ("b".to_string(), vec![2; 32])]
+                .into_iter()
+                .collect(),
+            finality: FinalityReference { height: 7, block_hash: "ab".to_string(), validator_set_hash: "cd".to_string() },
+            signature: ProofSignature {
+                algorithm: "Ed25519".to_string(),
+                signature: Vec::new(),
+                public_key: key.verifying_key().to_bytes().to_vec(),
+            },
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        let digest = settlement_proof_digest(&proof).unwrap();
+        proof.signature.signature = key.sign(&digest).to_bytes().to_vec();
+        proof
+    }
+
+    #[test]
+    fn test_verifies_against_validator_keys() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let proof = signed_proof(&key);
+        assert!(verify_settlement_proof(&proof, &[key.verifying_key().to_bytes().to_vec()]));
+
+        let other = SigningKey::from_bytes(&[8; 32]);
+        assert!(!verify_settlement_proof(&proof, &[other.verifying_key().to_bytes().to_vec()]));
+    }
+
+    #[test]
+    fn test_rejects_tampered_proofs() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let keys = [key.verifying_key().to_bytes().to_vec()];
+
+        let mut proof = signed_proof(&key);
+        proof.finality.height = 8;
+        assert!(!verify_settlement_proof(&proof, &keys));
+
+        let mut proof = signed_proof(&key);
+        proof.domain_confirmations.remove("b");
+        assert!(!verify_settlement_proof(&proof, &keys));
+
+        let mut proof = signed_proof(&key);
+        proof.transaction_id = "not-a-uuid".to_string();
+        assert!(!verify_settlement_proof(&proof, &keys));
+
+        let mut proof = signed_proof(&key);
+        proof.created_at = "2025-01-01T00:00:00Z".to_string();
+        assert!(verify_settlement_proof(&proof, &keys));
+    }
+}
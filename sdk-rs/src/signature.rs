@@ -0,0 +1,77 @@
+//! Offline verification of signed participant API responses.
+//!
+//! When the node has a response signing key configured, responses to
+//! `POST`, `PATCH` and `DELETE` requests carry `X-GARP-Response-Signature`,
+//! the hex-encoded `HMAC-SHA256(body || timestamp || request_id)` where the
+//! timestamp and request id are the `X-GARP-Timestamp` and `X-Request-Id`
+//! header values.
+
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "x-garp-response-signature";
+pub const TIMESTAMP_HEADER: &str = "x-garp-timestamp";
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Check the response signature in `headers` against `body` using `key`.
+///
+/// Missing headers or a malformed signature make the response invalid. The
+/// timestamp is not checked for freshness; callers that care should compare
+/// it with their own clock.
+pub fn verify_response_signature(body: &[u8], headers: &HeaderMap, key: &[u8]) -> bool {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let (Some(signature), Some(timestamp), Some(request_id)) =
+        (header(SIGNATURE_HEADER), header(TIMESTAMP_HEADER), header(REQUEST_ID_HEADER))
+    else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key) else {
+        return false;
+    };
+    mac.update(body);
+    mac.update(timestamp.as_bytes());
+    mac.update(request_id.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &[u8] = br#"{"success":true}"#;
+
+    // Same vector as the participant node's response_signing tests
+    fn signed_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SIGNATURE_HEADER,
+            "32d1f6fbb009c43a589e61c507314b7f57fa25f668bc02778e7b236777235276".parse().unwrap(),
+        );
+        headers.insert(TIMESTAMP_HEADER, "1700000000".parse().unwrap());
+        headers.insert(REQUEST_ID_HEADER, "req-1".parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_verifies_node_vector() {
+        assert!(verify_response_signature(BODY, &signed_headers(), b"secret"));
+        assert!(!verify_response_signature(BODY, &signed_headers(), b"other"));
+    }
+
+    #[test]
+    fn test_rejects_tampered_responses() {
+        assert!(!verify_response_signature(br#"{"success":false}"#, &signed_headers(), b"secret"));
+
+        let mut headers = signed_headers();
+        headers.insert(TIMESTAMP_HEADER, "1700000001".parse().unwrap());
+        assert!(!verify_response_signature(BODY, &headers, b"secret"));
+
+        let mut headers = signed_headers();
+        headers.remove(REQUEST_ID_HEADER);
+        assert!(!verify_response_signature(BODY, &headers, b"secret"));
+    }
+}
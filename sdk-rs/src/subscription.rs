@@ -0,0 +1,318 @@
+//! Ledger subscriptions over the participant node's `/ws/ledger` socket.
+//!
+//! The node sends one frame per block, listing only the transactions the
+//! caller's participant is a party to. The stream yields each block followed
+//! by its transactions. When the socket drops it reconnects with
+//! exponential backoff and resumes after the last block it yielded, so
+//! consumers see every block once and in order.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use futures::{SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::{BlockInfo, GarpClient, JsonRpcError, SdkError, TransactionInfo};
+
+/// Delay before the first reconnect attempt
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(250);
+
+/// Longest delay between reconnect attempts
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Item of [`GarpClient::subscribe_ledger`]
+#[derive(Debug, Clone)]
+pub enum LedgerUpdate {
+    /// A new block; its `transactions` lists only the subscriber's
+    Block(BlockInfo),
+    /// One of the subscriber's transactions, following its block
+    Transaction { slot: i64, transaction: TransactionInfo },
+}
+
+impl LedgerUpdate {
+    /// Slot of the block the update belongs to
+    pub fn slot(&self) -> i64 {
+        match self {
+            LedgerUpdate::Block(block) => block.slot,
+            LedgerUpdate::Transaction { slot, .. } => *slot,
+        }
+    }
+}
+
+/// Frame sent by the node on `/ws/ledger`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum LedgerFrame {
+    Block {
+        block: BlockInfo,
+        #[serde(default)]
+        transactions: Vec<TransactionInfo>,
+    },
+    Error(JsonRpcError),
+}
+
+/// Exponential backoff doubling from `initial` up to `max`
+#[derive(Debug, Clone)]
+pub(crate) struct Backoff {
+    initial: Duration,
+    max: Duration,
+    next: Duration,
+}
+
+impl Backoff {
+    pub(crate) fn new(initial: Duration, max: Duration) -> Self {
+        Self { initial, max, next: initial.min(max) }
+    }
+
+    /// Delay before the next attempt
+    pub(crate) fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.next = self.initial.min(self.max);
+    }
+}
+
+type LedgerSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Connection state of one subscription
+struct LedgerSubscription {
+    client: GarpClient,
+    /// Slot to request on (re)connecting; `None` follows from the next block
+    next_slot: Option<i64>,
+    socket: Option<LedgerSocket>,
+    /// Updates received but not yet yielded
+    pending: VecDeque<LedgerUpdate>,
+    backoff: Backoff,
+    finished: bool,
+}
+
+impl LedgerSubscription {
+    async fn connect(&self) -> Result<LedgerSocket, tungstenite::Error> {
+        let mut request = self.client.ws_url("/ws/ledger").into_client_request()?;
+        if let Some(key) = &self.client.api_key {
+            let value = format!("Bearer {}", key)
+                .parse()
+                .map_err(|e| tungstenite::Error::HttpFormat(tungstenite::http::Error::from(e)))?;
+            request.headers_mut().insert(tungstenite::http::header::AUTHORIZATION, value);
+        }
+        let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+        socket.send(Message::Text(json!({ "from_slot": self.next_slot }).to_string())).await?;
+        Ok(socket)
+    }
+
+    /// Drop the connection and wait before the next attempt
+    async fn reconnect_later(&mut self) {
+        self.socket = None;
+        tokio::time::sleep(self.backoff.next_delay()).await;
+    }
+
+    fn fail(&mut self, error: SdkError) -> Option<Result<LedgerUpdate, SdkError>> {
+        self.finished = true;
+        self.socket = None;
+        Some(Err(error))
+    }
+
+    async fn next_update(&mut self) -> Option<Result<LedgerUpdate, SdkError>> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return Some(Ok(update));
+            }
+            if self.finished {
+                return None;
+            }
+
+            if self.socket.is_none() {
+                match self.connect().await {
+                    Ok(socket) => self.socket = Some(socket),
+                    Err(e) if is_permanent(&e) => return self.fail(SdkError::WebSocket(e.to_string())),
+                    Err(_) => {
+                        self.reconnect_later().await;
+                        continue;
+                    }
+                }
+            }
+            let message = match self.socket.as_mut() {
+                Some(socket) => socket.next().await,
+                None => continue,
+            };
+
+            let text = match message {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                    self.reconnect_later().await;
+                    continue;
+                }
+                Some(Ok(_)) => continue,
+            };
+            match serde_json::from_str::<LedgerFrame>(&text) {
+                Ok(LedgerFrame::Block { block, transactions }) => {
+                    self.backoff.reset();
+                    self.next_slot = Some(block.slot + 1);
+                    self.pending.extend(block_updates(block, transactions));
+                }
+                Ok(LedgerFrame::Error(error)) => {
+                    let error = SdkError::from(error);
+                    if !error.is_retryable() {
+                        return self.fail(error);
+                    }
+                    self.reconnect_later().await;
+                }
+                Err(e) => return self.fail(e.into()),
+            }
+        }
+    }
+}
+
+/// Updates for one block frame: the block, then its transactions
+fn block_updates(block: BlockInfo, transactions: Vec<TransactionInfo>) -> Vec<LedgerUpdate> {
+    let slot = block.slot;
+    std::iter::once(LedgerUpdate::Block(block))
+        .chain(transactions.into_iter().map(|transaction| LedgerUpdate::Transaction { slot, transaction }))
+        .collect()
+}
+
+/// Whether retrying the connection cannot help: a malformed URL, or the
+/// node refusing the handshake, e.g. for a missing or unauthorized key
+fn is_permanent(error: &tungstenite::Error) -> bool {
+    match error {
+        tungstenite::Error::Url(_) | tungstenite::Error::HttpFormat(_) => true,
+        tungstenite::Error::Http(response) => response.status().is_client_error(),
+        _ => false,
+    }
+}
+
+impl GarpClient {
+    /// WebSocket URL of `path` on the client's node
+    fn ws_url(&self, path: &str) -> String {
+        let base = if let Some(rest) = self.base_url.strip_prefix("https://") {
+            format!("wss://{}", rest)
+        } else if let Some(rest) = self.base_url.strip_prefix("http://") {
+            format!("ws://{}", rest)
+        } else {
+            self.base_url.clone()
+        };
+        format!("{}{}", base, path)
+    }
+
+    /// Follow the participant node's ledger from `from_slot`, or from the
+    /// next block when `None`.
+    ///
+    /// Only transactions the participant bound to the client's API key (see
+    /// [`Self::with_api_key`]) is a party to are included. Dropped
+    /// connections are re-established with exponential backoff of up to 30
+    /// seconds, resuming after the last block yielded. The stream ends after
+    /// yielding an error the node reports as not retryable, or when the
+    /// node refuses the connection.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use garp_sdk::{GarpClient, LedgerUpdate};
+    ///
+    /// # async fn demo() -> Result<(), garp_sdk::SdkError> {
+    /// let client = GarpClient::new("http://localhost:8080")?.with_api_key("<key>");
+    /// let mut updates = Box::pin(client.subscribe_ledger(Some(100)));
+    /// while let Some(update) = updates.next().await {
+    ///     if let LedgerUpdate::Transaction { slot, transaction } = update? {
+    ///         println!("{} committed in slot {}", transaction.id, slot);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_ledger(&self, from_slot: Option<i64>) -> impl Stream<Item = Result<LedgerUpdate, SdkError>> {
+        let subscription = LedgerSubscription {
+            client: self.clone(),
+            next_slot: from_slot,
+            socket: None,
+            pending: VecDeque::new(),
+            backoff: Backoff::new(INITIAL_RECONNECT_DELAY, MAX_RECONNECT_DELAY),
+            finished: false,
+        };
+        futures::stream::unfold(subscription, |mut subscription| async move {
+            let update = subscription.next_update().await?;
+            Some((update, subscription))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap_and_resets() {
+        let mut backoff = Backoff::new(INITIAL_RECONNECT_DELAY, MAX_RECONNECT_DELAY);
+        let delays: Vec<Duration> = (0..10).map(|_| backoff.next_delay()).collect();
+        assert_eq!(delays[0], Duration::from_millis(250));
+        assert_eq!(delays[1], Duration::from_millis(500));
+        assert!(delays.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(delays[9], MAX_RECONNECT_DELAY);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), INITIAL_RECONNECT_DELAY);
+    }
+
+    #[test]
+    fn test_ws_url_follows_the_http_scheme() {
+        let client = GarpClient::new("https://node.example/").unwrap();
+        assert_eq!(client.ws_url("/ws/ledger"), "wss://node.example/ws/ledger");
+        let client = GarpClient::new("http://localhost:8080").unwrap();
+        assert_eq!(client.ws_url("/ws/ledger"), "ws://localhost:8080/ws/ledger");
+    }
+
+    fn block_frame(slot: i64, tx_ids: &[&str]) -> String {
+        json!({
+            "type": "block",
+            "block": { "slot": slot, "hash": format!("hash-{}", slot), "transactions": [] },
+            "transactions": tx_ids.iter().map(|id| json!({ "id": id, "status": "committed" })).collect::<Vec<_>>(),
+        })
+        .to_string()
+    }
+
+    /// Accept one subscription and return the slot it asked for
+    async fn accept(listener: &TcpListener) -> (WebSocketStream<TcpStream>, Option<i64>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = match socket.next().await {
+            Some(Ok(Message::Text(text))) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+            other => panic!("expected a subscription request, got {:?}", other),
+        };
+        (socket, request["from_slot"].as_i64())
+    }
+
+    #[tokio::test]
+    async fn test_subscription_resumes_after_the_last_yielded_block() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = GarpClient::new(format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, from_slot) = accept(&listener).await;
+            assert_eq!(from_slot, Some(5));
+            socket.send(Message::Text(block_frame(5, &["tx-1", "tx-2"]))).await.unwrap();
+            socket.send(Message::Text(block_frame(7, &[]))).await.unwrap();
+            drop(socket);
+
+            let (mut socket, from_slot) = accept(&listener).await;
+            assert_eq!(from_slot, Some(8));
+            socket.send(Message::Text(block_frame(8, &["tx-3"]))).await.unwrap();
+            let error = json!({ "type": "error", "code": -32602, "message": "bad subscription" });
+            socket.send(Message::Text(error.to_string())).await.unwrap();
+        });
+
+        let updates: Vec<Result<LedgerUpdate, SdkError>> = client.subscribe_ledger(Some(5)).collect().await;
+        server.await.unwrap();
+
+        let slots: Vec<i64> = updates.iter().filter_map(|u| u.as_ref().ok()).map(LedgerUpdate::slot).collect();
+        assert_eq!(slots, vec![5, 5, 5, 7, 8, 8]);
+        assert!(matches!(&updates[1], Ok(LedgerUpdate::Transaction { transaction, .. }) if transaction.id == "tx-1"));
+        assert!(matches!(updates.last(), Some(Err(SdkError::Rpc { retryable: false, .. }))));
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use garp_common::{GarpResult, GenesisConfig, ChainParams};
+use garp_common::{GarpResult, GenesisConfig, ChainParams, ReputationConfig};
 use anyhow::Context;
+use crate::mediator::ApprovalPolicy;
 
 /// Synchronization Domain configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +31,18 @@ pub struct SyncDomainConfig {
     /// Participant management
     pub participants: ParticipantConfig,
     
+    /// Transaction mediation
+    #[serde(default)]
+    pub mediator: MediatorConfig,
+    
+    /// Retries of timed-out mediation and consensus rounds
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+    
+    /// Participant reputation scoring and suspension
+    #[serde(default)]
+    pub reputation: ReputationConfig,
+    
     /// Performance tuning
     pub performance: PerformanceConfig,
     
@@ -108,6 +121,10 @@ pub struct KafkaConfig {
     
     /// Consumer configuration
     pub consumer: ConsumerConfig,
+    
+    /// Retries and dead-lettering of messages whose handler fails
+    #[serde(default)]
+    pub dead_letter: DeadLetterConfig,
 }
 
 /// SSL configuration for Kafka
@@ -200,6 +217,33 @@ pub struct ConsumerConfig {
     pub fetch_max_wait_ms: u32,
 }
 
+/// Handling of messages whose handler keeps failing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterConfig {
+    /// Topic failed messages are published to
+    pub topic: String,
+    
+    /// Handler attempts per message, including the first
+    pub max_attempts: u32,
+    
+    /// Backoff before the first retry, doubled for each further retry
+    pub initial_backoff_ms: u64,
+    
+    /// Upper bound of the backoff
+    pub max_backoff_ms: u64,
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            topic: "garp-dead-letter".to_string(),
+            max_attempts: 3,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 5000,
+        }
+    }
+}
+
 /// Database configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -226,6 +270,14 @@ pub struct DatabaseConfig {
     
     /// Redis configuration for caching
     pub redis: Option<RedisConfig>,
+    
+    /// Days to keep domain events in the event log
+    #[serde(default = "default_event_retention_days")]
+    pub event_retention_days: u32,
+}
+
+fn default_event_retention_days() -> u32 {
+    30
 }
 
 /// Redis configuration
@@ -379,6 +431,63 @@ pub struct ParticipantConfig {
     pub timeout_threshold: u64,
 }
 
+/// Transaction mediation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediatorConfig {
+    /// Time participants have to consent, in seconds
+    pub mediation_timeout_seconds: u64,
+    
+    /// Maximum mediation sessions in progress
+    pub max_concurrent_sessions: usize,
+    
+    /// Enable auto-consent from participant preferences
+    pub enable_auto_consent: bool,
+    
+    /// Amount up to which participants may auto-consent
+    pub auto_consent_threshold: Option<u64>,
+    
+    /// Require consent from every contract signatory
+    pub require_all_signatories: bool,
+    
+    /// Allow a transaction to proceed without every consent
+    pub allow_partial_consent: bool,
+    
+    /// Consent cache TTL in seconds
+    pub consent_cache_ttl_seconds: u64,
+    
+    /// Approval policy for transactions whose metadata names none
+    #[serde(default)]
+    pub approval_policy: ApprovalPolicy,
+}
+
+/// Retries of mediation and consensus rounds that time out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationConfig {
+    /// Attempts per round, including the first, before the transaction fails
+    pub max_attempts: u32,
+    
+    /// Backoff before the first retry, doubled for each further retry
+    pub initial_backoff_ms: u64,
+    
+    /// Upper bound of the backoff
+    pub max_backoff_ms: u64,
+    
+    /// Leave participants that never responded out of retries, when the
+    /// round can still reach quorum without them
+    pub exclude_unresponsive: bool,
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+            exclude_unresponsive: true,
+        }
+    }
+}
+
 /// Performance tuning configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceConfig {
@@ -439,6 +548,30 @@ pub struct MonitoringConfig {
     
     /// Enable structured logging
     pub structured_logging: bool,
+    
+    /// Consumer lag at which the domain reports itself unhealthy
+    #[serde(default)]
+    pub consumer_lag: ConsumerLagThresholds,
+}
+
+/// Consumer lag thresholds, in messages behind the high watermark of the
+/// furthest-behind partition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsumerLagThresholds {
+    /// Lag above which the domain is degraded
+    pub degraded_lag: i64,
+    
+    /// Lag above which the domain goes into maintenance
+    pub maintenance_lag: i64,
+}
+
+impl Default for ConsumerLagThresholds {
+    fn default() -> Self {
+        Self {
+            degraded_lag: 10_000,
+            maintenance_lag: 1_000_000,
+        }
+    }
 }
 
 impl SyncDomainConfig {
@@ -504,6 +637,17 @@ impl SyncDomainConfig {
             return Err(anyhow::anyhow!("Max concurrent transactions must be greater than 0").into());
         }
 
+        // Validate escalation settings
+        if self.escalation.max_attempts == 0 {
+            return Err(anyhow::anyhow!("Escalation max attempts must be greater than 0").into());
+        }
+
+        // Validate reputation settings
+        let reputation = &self.reputation;
+        if reputation.max_score <= 0.0 || !(0.0..=reputation.max_score).contains(&reputation.initial_score) {
+            return Err(anyhow::anyhow!("Reputation initial score must be between 0 and a positive max score").into());
+        }
+
         Ok(())
     }
 }
@@ -566,6 +710,7 @@ impl Default for SyncDomainConfig {
                     fetch_min_bytes: 1,
                     fetch_max_wait_ms: 500,
                 },
+                dead_letter: DeadLetterConfig::default(),
             },
             database: DatabaseConfig {
                 url: "postgresql://localhost/garp_sync_domain".to_string(),
@@ -581,6 +726,7 @@ impl Default for SyncDomainConfig {
                     timeout: 5,
                     default_ttl: 3600,
                 }),
+                event_retention_days: default_event_retention_days(),
             },
             api: ApiConfig {
                 host: "0.0.0.0".to_string(),
@@ -650,7 +796,11 @@ impl Default for SyncDomainConfig {
                 tracing_endpoint: None,
                 log_level: "info".to_string(),
                 structured_logging: true,
+                consumer_lag: ConsumerLagThresholds::default(),
             },
+            mediator: MediatorConfig::default(),
+            escalation: EscalationConfig::default(),
+            reputation: ReputationConfig::default(),
         }
     }
 }
\ No newline at end of file
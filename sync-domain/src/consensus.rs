@@ -6,8 +6,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
-use garp_common::{GarpResult, TransactionId, ParticipantId};
-use crate::config::ConsensusConfig;
+use garp_common::{GarpResult, TransactionId, ParticipantId, ReputationSignal};
+use crate::config::{ConsensusConfig, EscalationConfig};
+use crate::escalation::{Escalation, RoundPhase, TimeoutEscalator};
+use crate::reputation::ParticipantReputation;
 use crate::storage::{StorageBackend, ConsensusState, ConsensusPhase, ConsensusVote, ConsensusResult};
 use crate::kafka::{KafkaClient, MessageHandler, KafkaMessage, ConsensusOutcome};
 
@@ -28,6 +30,12 @@ pub struct ConsensusManager {
     /// Registered validators
     validators: Arc<RwLock<HashMap<ParticipantId, ValidatorInfo>>>,
     
+    /// Retries of timed-out sessions
+    escalator: Arc<TimeoutEscalator>,
+    
+    /// Reputations of the validators, if tracked
+    reputation: Option<Arc<ParticipantReputation>>,
+    
     /// Shutdown signal
     shutdown_tx: Option<mpsc::Sender<()>>,
     
@@ -64,6 +72,51 @@ pub struct ConsensusSession {
     
     /// Consensus result
     pub result: Option<ConsensusResult>,
+    
+    /// Participants left out of retries after never voting
+    pub excluded_participants: HashSet<ParticipantId>,
+}
+
+impl ConsensusSession {
+    /// Decide the session once every required participant has voted:
+    /// approved if all approve, rejected with their reasons otherwise
+    fn decide(&mut self) -> Option<ConsensusResult> {
+        if self.result.is_some() || self.votes.len() < self.required_participants.len() {
+            return None;
+        }
+        
+        let all_approved = self.votes.values().all(|v| v.vote);
+        let result = if all_approved {
+            self.phase = ConsensusPhase::Committed;
+            ConsensusResult::Approved
+        } else {
+            self.phase = ConsensusPhase::Aborted;
+            let rejection_reasons: Vec<String> = self.votes.values()
+                .filter(|v| !v.vote)
+                .filter_map(|v| v.reason.clone())
+                .collect();
+            ConsensusResult::Rejected {
+                reason: rejection_reasons.join("; "),
+            }
+        };
+        
+        self.result = Some(result.clone());
+        Some(result)
+    }
+    
+    /// Session as stored in the database
+    fn to_state(&self) -> ConsensusState {
+        ConsensusState {
+            transaction_id: self.transaction_id.clone(),
+            phase: self.phase.clone(),
+            required_participants: self.required_participants.clone(),
+            votes: self.votes.clone(),
+            result: self.result.clone(),
+            timeout: self.timeout,
+            created_at: self.created_at,
+            domain_id: self.domain_id.clone(),
+        }
+    }
 }
 
 /// Validator information
@@ -143,11 +196,27 @@ impl ConsensusManager {
             kafka,
             sessions: Arc::new(RwLock::new(HashMap::new())),
             validators: Arc::new(RwLock::new(HashMap::new())),
+            escalator: Arc::new(TimeoutEscalator::new(EscalationConfig::default())),
+            reputation: None,
             shutdown_tx: None,
             metrics: Arc::new(RwLock::new(ConsensusMetrics::default())),
         })
     }
     
+    /// Retry timed-out sessions through `escalator`, shared with the
+    /// mediator
+    pub fn with_escalator(mut self, escalator: Arc<TimeoutEscalator>) -> Self {
+        self.escalator = escalator;
+        self
+    }
+    
+    /// Record invalid vote signatures and approved transactions in the
+    /// validators' reputations
+    pub fn with_reputation(mut self, reputation: Arc<ParticipantReputation>) -> Self {
+        self.reputation = Some(reputation);
+        self
+    }
+    
     /// Start the consensus manager
     pub async fn start(&mut self) -> GarpResult<()> {
         // Register consensus handler with Kafka
@@ -160,9 +229,13 @@ impl ConsensusManager {
         self.shutdown_tx = Some(shutdown_tx);
         
         // Start session timeout monitor
+        let config = self.config.clone();
         let sessions = Arc::clone(&self.sessions);
+        let storage = Arc::clone(&self.storage);
         let kafka = Arc::clone(&self.kafka);
         let metrics = Arc::clone(&self.metrics);
+        let escalator = Arc::clone(&self.escalator);
+        let reputation = self.reputation.clone();
         
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(10));
@@ -170,7 +243,7 @@ impl ConsensusManager {
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        Self::check_session_timeouts(&sessions, &kafka, &metrics).await;
+                        Self::check_session_timeouts(&config, &sessions, &storage, &kafka, &metrics, &escalator, &reputation).await;
                     }
                     _ = shutdown_rx.recv() => {
                         break;
@@ -228,6 +301,7 @@ impl ConsensusManager {
             domain_id: domain_id.clone(),
             encrypted_data,
             result: None,
+            excluded_participants: HashSet::new(),
         };
         
         // Store session
@@ -277,6 +351,9 @@ impl ConsensusManager {
     ) -> GarpResult<()> {
         // Verify signature
         if !self.verify_vote_signature(participant_id, transaction_id, vote, &signature).await? {
+            if let Some(reputation) = &self.reputation {
+                reputation.record(participant_id, ReputationSignal::InvalidSignature).await;
+            }
             return Err(anyhow::anyhow!("Invalid vote signature"));
         }
         
@@ -311,44 +388,16 @@ impl ConsensusManager {
                 session_updated = true;
                 
                 // Check if consensus reached
-                if session.votes.len() == session.required_participants.len() {
-                    let all_approved = session.votes.values().all(|v| v.vote);
-                    
-                    if all_approved {
-                        session.phase = ConsensusPhase::Committed;
-                        consensus_result = Some(ConsensusResult::Approved);
-                    } else {
-                        session.phase = ConsensusPhase::Aborted;
-                        let rejection_reasons: Vec<String> = session.votes.values()
-                            .filter(|v| !v.vote)
-                            .filter_map(|v| v.reason.clone())
-                            .collect();
-                        consensus_result = Some(ConsensusResult::Rejected {
-                            reason: rejection_reasons.join("; "),
-                        });
-                    }
-                    
-                    session.result = consensus_result.clone();
-                    consensus_reached = true;
-                }
+                consensus_result = session.decide();
+                consensus_reached = consensus_result.is_some();
             }
         }
         
         if session_updated {
             // Update database
-            if let Some(session) = self.sessions.read().await.get(transaction_id) {
-                let consensus_state = ConsensusState {
-                    transaction_id: transaction_id.clone(),
-                    phase: session.phase.clone(),
-                    required_participants: session.required_participants.clone(),
-                    votes: session.votes.clone(),
-                    result: session.result.clone(),
-                    timeout: session.timeout,
-                    created_at: session.created_at,
-                    domain_id: session.domain_id.clone(),
-                };
-                
-                self.storage.update_consensus_state(&consensus_state).await?;
+            let session = self.sessions.read().await.get(transaction_id).cloned();
+            if let Some(session) = &session {
+                self.storage.update_consensus_state(&session.to_state()).await?;
             }
             
             // Update metrics
@@ -359,32 +408,8 @@ impl ConsensusManager {
             
             // If consensus reached, send result
             if consensus_reached {
-                if let Some(result) = consensus_result {
-                    let outcome = match result {
-                        ConsensusResult::Approved => ConsensusOutcome::Approved,
-                        ConsensusResult::Rejected { reason } => ConsensusOutcome::Rejected { reason },
-                        ConsensusResult::Timeout => ConsensusOutcome::Timeout,
-                    };
-                    
-                    self.kafka.send_consensus_result(transaction_id.clone(), outcome).await?;
-                    
-                    // Update metrics
-                    {
-                        let mut metrics = self.metrics.write().await;
-                        metrics.active_sessions -= 1;
-                        
-                        match result {
-                            ConsensusResult::Approved => metrics.successful_consensus += 1,
-                            ConsensusResult::Rejected { .. } => metrics.failed_consensus += 1,
-                            ConsensusResult::Timeout => metrics.timed_out_consensus += 1,
-                        }
-                    }
-                    
-                    tracing::info!(
-                        "Consensus reached for transaction {}: {:?}",
-                        transaction_id,
-                        result
-                    );
+                if let (Some(session), Some(result)) = (session, consensus_result) {
+                    Self::publish_result(&self.kafka, &self.metrics, &self.escalator, &self.reputation, &session, result).await?;
                 }
             }
         }
@@ -392,6 +417,52 @@ impl ConsensusManager {
         Ok(())
     }
     
+    /// Send the result of a decided session
+    async fn publish_result(
+        kafka: &Arc<KafkaClient>,
+        metrics: &Arc<RwLock<ConsensusMetrics>>,
+        escalator: &Arc<TimeoutEscalator>,
+        reputation: &Option<Arc<ParticipantReputation>>,
+        session: &ConsensusSession,
+        result: ConsensusResult,
+    ) -> GarpResult<()> {
+        let transaction_id = &session.transaction_id;
+        escalator.on_completed(RoundPhase::Consensus, transaction_id, &session.required_participants).await;
+        
+        // Credit the validators that voted for an approved transaction
+        if let (Some(reputation), ConsensusResult::Approved) = (reputation, &result) {
+            reputation.record_all(session.votes.keys(), ReputationSignal::TransactionFinalized).await;
+        }
+        
+        let outcome = match result.clone() {
+            ConsensusResult::Approved => ConsensusOutcome::Approved,
+            ConsensusResult::Rejected { reason } => ConsensusOutcome::Rejected { reason },
+            ConsensusResult::Timeout => ConsensusOutcome::Timeout,
+        };
+        
+        kafka.send_consensus_result(transaction_id.clone(), outcome).await?;
+        
+        // Update metrics
+        {
+            let mut metrics = metrics.write().await;
+            metrics.active_sessions -= 1;
+            
+            match result {
+                ConsensusResult::Approved => metrics.successful_consensus += 1,
+                ConsensusResult::Rejected { .. } => metrics.failed_consensus += 1,
+                ConsensusResult::Timeout => metrics.timed_out_consensus += 1,
+            }
+        }
+        
+        tracing::info!(
+            "Consensus reached for transaction {}: {:?}",
+            transaction_id,
+            result
+        );
+        
+        Ok(())
+    }
+    
     /// Register validator
     pub async fn register_validator(&self, validator: ValidatorInfo) -> GarpResult<()> {
         let mut validators = self.validators.write().await;
@@ -439,11 +510,33 @@ impl ConsensusManager {
         Ok(signature == expected_signature)
     }
     
-    /// Check session timeouts
+    /// Whether a session can still be decided if the `absent` participants
+    /// never vote. Unanimous sessions need everyone; others need
+    /// `vote_threshold` of the participants they started with, and at least
+    /// `min_validators`.
+    fn quorum_without(config: &ConsensusConfig, session: &ConsensusSession, absent: &HashSet<ParticipantId>) -> bool {
+        if absent.is_empty() {
+            return true;
+        }
+        if config.require_unanimous {
+            return false;
+        }
+        
+        let started_with = session.required_participants.len() + session.excluded_participants.len();
+        let remaining = session.required_participants.difference(absent).count();
+        remaining >= config.min_validators as usize && remaining as f64 >= config.vote_threshold * started_with as f64
+    }
+    
+    /// Check session timeouts, retrying timed-out sessions after their
+    /// backoff until the escalator gives up on them
     async fn check_session_timeouts(
+        config: &ConsensusConfig,
         sessions: &Arc<RwLock<HashMap<TransactionId, ConsensusSession>>>,
+        storage: &Arc<dyn StorageBackend>,
         kafka: &Arc<KafkaClient>,
         metrics: &Arc<RwLock<ConsensusMetrics>>,
+        escalator: &Arc<TimeoutEscalator>,
+        reputation: &Option<Arc<ParticipantReputation>>,
     ) {
         let now = Utc::now();
         let mut timed_out_sessions = Vec::new();
@@ -460,6 +553,61 @@ impl ConsensusManager {
         
         // Handle timed out sessions
         for transaction_id in timed_out_sessions {
+            let Some(mut session) = sessions.read().await.get(&transaction_id).cloned() else {
+                continue;
+            };
+            let unresponsive: HashSet<ParticipantId> = session.required_participants.iter()
+                .filter(|participant_id| !session.votes.contains_key(*participant_id))
+                .cloned()
+                .collect();
+            let escalation = escalator.on_timeout(
+                RoundPhase::Consensus,
+                &transaction_id,
+                session.created_at,
+                &session.required_participants,
+                &unresponsive,
+                |absent| Self::quorum_without(config, &session, absent),
+            ).await;
+            
+            if let Escalation::Retry { attempt, backoff, excluded } = escalation {
+                session.required_participants.retain(|participant_id| !excluded.contains(participant_id));
+                session.excluded_participants.extend(excluded);
+                session.timeout = now
+                    + chrono::Duration::from_std(backoff).unwrap_or_else(|_| chrono::Duration::zero())
+                    + chrono::Duration::seconds(config.consensus_timeout_seconds as i64);
+                
+                // Leaving out the silent participants may decide the session
+                let result = session.decide();
+                if let Err(e) = storage.update_consensus_state(&session.to_state()).await {
+                    tracing::error!("Failed to store retried consensus for {}: {}", transaction_id, e);
+                }
+                sessions.write().await.insert(transaction_id.clone(), session.clone());
+                
+                if let Some(result) = result {
+                    if let Err(e) = Self::publish_result(kafka, metrics, escalator, reputation, &session, result).await {
+                        tracing::error!("Failed to send consensus result for {}: {}", transaction_id, e);
+                    }
+                    continue;
+                }
+                
+                // Ask the validators still missing again once the backoff has passed
+                let event_data = serde_json::json!({
+                    "type": "consensus_retry",
+                    "transaction_id": transaction_id,
+                    "attempt": attempt,
+                    "participants": unresponsive.difference(&session.excluded_participants).collect::<Vec<_>>(),
+                    "timestamp": now
+                });
+                let kafka = Arc::clone(kafka);
+                tokio::spawn(async move {
+                    tokio::time::sleep(backoff).await;
+                    if let Err(e) = kafka.send_domain_event(session.domain_id, "consensus_retry".to_string(), event_data).await {
+                        tracing::error!("Failed to announce consensus retry for {}: {}", transaction_id, e);
+                    }
+                });
+                continue;
+            }
+            
             {
                 let mut sessions_write = sessions.write().await;
                 if let Some(session) = sessions_write.get_mut(&transaction_id) {
@@ -493,6 +641,8 @@ impl Clone for ConsensusManager {
             kafka: Arc::clone(&self.kafka),
             sessions: Arc::clone(&self.sessions),
             validators: Arc::clone(&self.validators),
+            escalator: Arc::clone(&self.escalator),
+            reputation: self.reputation.clone(),
             shutdown_tx: None, // Don't clone shutdown channel
             metrics: Arc::clone(&self.metrics),
         }
@@ -626,7 +776,7 @@ mod tests {
         let config = ConsensusConfig::default();
         let storage = Arc::new(MemoryStorage::new());
         let kafka_config = KafkaConfig::default();
-        let kafka = Arc::new(KafkaClient::new(kafka_config).await.unwrap());
+        let kafka = Arc::new(KafkaClient::new(kafka_config, storage.clone()).await.unwrap());
         
         let manager = ConsensusManager::new(config, storage, kafka).await.unwrap();
         
@@ -652,7 +802,7 @@ mod tests {
         let config = ConsensusConfig::default();
         let storage = Arc::new(MemoryStorage::new());
         let kafka_config = KafkaConfig::default();
-        let kafka = Arc::new(KafkaClient::new(kafka_config).await.unwrap());
+        let kafka = Arc::new(KafkaClient::new(kafka_config, storage.clone()).await.unwrap());
         
         let manager = ConsensusManager::new(config, storage, kafka).await.unwrap();
         
@@ -683,6 +833,82 @@ mod tests {
         assert_eq!(session.votes.len(), 1);
         assert_eq!(session.phase, ConsensusPhase::Committed);
     }
+    
+    #[tokio::test]
+    async fn test_votes_feed_validator_reputation() {
+        use garp_common::{ReputationConfig, ReputationTracker};
+        
+        let storage = Arc::new(MemoryStorage::new());
+        let kafka = Arc::new(KafkaClient::new(KafkaConfig::default(), storage.clone()).await.unwrap());
+        let tracker = Arc::new(ReputationTracker::new(ReputationConfig::default()));
+        let manager = ConsensusManager::new(ConsensusConfig::default(), storage, kafka).await.unwrap()
+            .with_reputation(Arc::new(ParticipantReputation::new(tracker.clone())));
+        manager.register_validator(ValidatorInfo {
+            participant_id: "participant-1".to_string(),
+            public_key: "test-key-1".to_string(),
+            endpoint: "http://localhost:8001".to_string(),
+            weight: 1,
+            last_seen: Utc::now(),
+            status: ValidatorStatus::Active,
+        }).await.unwrap();
+        
+        let transaction_id = "test-tx-1".to_string();
+        let participants = vec!["participant-1".to_string()].into_iter().collect();
+        manager.start_consensus(transaction_id.clone(), participants, "test-domain".to_string(), vec![]).await.unwrap();
+        
+        let forged = "sig_other-key_test-tx-1:participant-1:true".to_string();
+        assert!(manager.handle_vote(&transaction_id, &"participant-1".to_string(), true, None, forged).await.is_err());
+        let score = tracker.get("participant-1").await;
+        assert_eq!(score.signals.get(&ReputationSignal::InvalidSignature), Some(&1));
+        
+        let signature = "sig_test-key-1_test-tx-1:participant-1:true".to_string();
+        manager.handle_vote(&transaction_id, &"participant-1".to_string(), true, None, signature).await.unwrap();
+        let credited = tracker.get("participant-1").await;
+        assert_eq!(credited.signals.get(&ReputationSignal::TransactionFinalized), Some(&1));
+        assert!(credited.score > score.score);
+    }
+    
+    #[tokio::test]
+    async fn test_timeout_leaves_out_silent_validators_when_quorum_allows() {
+        let storage = Arc::new(MemoryStorage::new());
+        let kafka = Arc::new(KafkaClient::new(KafkaConfig::default(), storage.clone()).await.unwrap());
+        let manager = ConsensusManager::new(ConsensusConfig::default(), storage, kafka).await.unwrap();
+        let validators = ["v1", "v2", "v3", "v4"];
+        for validator in validators {
+            manager.register_validator(ValidatorInfo {
+                participant_id: validator.to_string(),
+                public_key: format!("key-{}", validator),
+                endpoint: "http://localhost:8001".to_string(),
+                weight: 1,
+                last_seen: Utc::now(),
+                status: ValidatorStatus::Active,
+            }).await.unwrap();
+        }
+        
+        let transaction_id = "test-tx-retry".to_string();
+        let participants = validators.iter().map(|v| v.to_string()).collect();
+        manager.start_consensus(transaction_id.clone(), participants, "test-domain".to_string(), vec![]).await.unwrap();
+        for validator in &validators[..3] {
+            let signature = format!("sig_key-{}_{}:{}:true", validator, transaction_id, validator);
+            manager.handle_vote(&transaction_id, &validator.to_string(), true, None, signature).await.unwrap();
+        }
+        
+        // Three of four clear the two-thirds threshold without v4
+        manager.sessions.write().await.get_mut(&transaction_id).unwrap().timeout = Utc::now() - chrono::Duration::seconds(1);
+        ConsensusManager::check_session_timeouts(
+            &manager.config, &manager.sessions, &manager.storage, &manager.kafka, &manager.metrics, &manager.escalator, &manager.reputation,
+        ).await;
+        
+        let session = manager.get_session(&transaction_id).await.unwrap();
+        assert_eq!(session.phase, ConsensusPhase::Committed);
+        assert!(session.excluded_participants.contains("v4"));
+        assert_eq!(session.required_participants.len(), 3);
+        
+        // Unanimous sessions wait for everyone
+        let unanimous = ConsensusConfig { require_unanimous: true, ..ConsensusConfig::default() };
+        assert!(!ConsensusManager::quorum_without(&unanimous, &session, &["v3".to_string()].into_iter().collect()));
+        assert!(ConsensusManager::quorum_without(&unanimous, &session, &HashSet::new()));
+    }
 }
 
 impl Default for ConsensusConfig {
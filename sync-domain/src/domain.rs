@@ -1,14 +1,17 @@
 use crate::{
-    config::SyncDomainConfig,
-    storage::{Storage, StorageBackend},
+    config::{ConsumerLagThresholds, SyncDomainConfig},
+    storage::{Storage, StorageBackend, EventLogTable, ParticipantStatus as StoredParticipantStatus},
     sequencer::{TransactionSequencer, SequencerFactory},
-    kafka::{KafkaClient, MessageHandler, KafkaMessage},
+    kafka::{ConsumerLag, KafkaClient, MessageHandler, KafkaMessage},
     consensus::{ConsensusManager, ConsensusHandler},
     mediator::{TransactionMediator, MediationHandler},
+    escalation::{EscalationEvent, FailureReason, RoundAttempt, RoundPhase, TimeoutEscalator},
+    reputation::{MetadataReputationStore, ParticipantReputation},
     vector_clock::{ClockManager, EventType},
     api::ApiServer,
+    event_log::{EventLog, EventFilter, EventPage, PageToken},
 };
-use garp_common::{GarpResult, GarpError, ParticipantId, TransactionId};
+use garp_common::{GarpResult, GarpError, ParticipantId, ReputationScore, ReputationTracker, ReputationUpdate, TransactionId};
 use std::sync::Arc;
 use tokio::sync::{RwLock, mpsc, oneshot};
 use tokio::time::{Duration, interval};
@@ -43,9 +46,21 @@ pub struct SyncDomain {
     /// API server
     api_server: Arc<ApiServer>,
     
+    /// Queryable history of emitted domain events
+    event_log: Arc<EventLog>,
+    
     /// Domain state
     state: Arc<RwLock<DomainState>>,
     
+    /// Retries and failures of timed-out rounds, until the domain starts
+    escalation_rx: Option<mpsc::UnboundedReceiver<EscalationEvent>>,
+    
+    /// Participant reputations, fed by consensus, mediation and escalation
+    reputation: Arc<ParticipantReputation>,
+    
+    /// Participants to suspend for low reputation, until the domain starts
+    suspension_rx: Option<mpsc::UnboundedReceiver<ReputationUpdate>>,
+    
     /// Shutdown signal
     shutdown_tx: Option<oneshot::Sender<()>>,
     
@@ -76,7 +91,7 @@ pub struct DomainState {
 }
 
 /// Domain status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DomainStatus {
     /// Domain is initializing
     Initializing,
@@ -84,6 +99,9 @@ pub enum DomainStatus {
     /// Domain is active and processing transactions
     Active,
     
+    /// Domain is processing transactions but falling behind
+    Degraded,
+    
     /// Domain is in maintenance mode
     Maintenance,
     
@@ -120,7 +138,7 @@ pub struct ParticipantInfo {
 }
 
 /// Participant status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ParticipantStatus {
     Active,
     Inactive,
@@ -154,6 +172,14 @@ pub struct TransactionInfo {
     
     /// Mediation status
     pub mediation_status: Option<MediationStatus>,
+    
+    /// Attempts at mediation and consensus rounds that were retried
+    #[serde(default)]
+    pub round_attempts: Vec<RoundAttempt>,
+    
+    /// Why the transaction failed, if its rounds kept timing out
+    #[serde(default)]
+    pub failure_reason: Option<FailureReason>,
 }
 
 /// Transaction status
@@ -218,6 +244,15 @@ pub struct DomainStatistics {
     /// Domain uptime
     pub uptime_seconds: u64,
     
+    /// Kafka consumer lag summed over partitions
+    pub consumer_lag: i64,
+    
+    /// Kafka consumer lag of the furthest-behind partition
+    pub max_partition_lag: i64,
+    
+    /// Whether the Kafka broker answered the last health check
+    pub broker_reachable: bool,
+    
     /// Last statistics update
     pub last_updated: DateTime<Utc>,
 }
@@ -259,6 +294,12 @@ pub enum DomainEventType {
     /// Participant deregistered
     ParticipantDeregistered,
     
+    /// Participant suspended for low reputation
+    ParticipantSuspended,
+    
+    /// Participant suspension lifted
+    ParticipantReinstated,
+    
     /// Transaction submitted
     TransactionSubmitted,
     
@@ -311,7 +352,7 @@ impl SyncDomain {
         
         // Initialize Kafka client
         let kafka_client = Arc::new(
-            KafkaClient::new(&config.kafka).await?
+            KafkaClient::new(config.kafka.clone(), storage.clone()).await?
         );
         
         // Initialize sequencer
@@ -319,6 +360,22 @@ impl SyncDomain {
             SequencerFactory::create_sequencer(&config.sequencer, storage.clone()).await?
         );
         
+        // Initialize participant reputation, persisted in storage metadata
+        let (suspension_tx, suspension_rx) = mpsc::unbounded_channel();
+        let reputation_store = Arc::new(MetadataReputationStore::new(storage.clone()));
+        let reputation_tracker = ReputationTracker::load(config.reputation.clone(), reputation_store).await?;
+        let reputation = Arc::new(
+            ParticipantReputation::new(Arc::new(reputation_tracker)).with_suspensions(suspension_tx)
+        );
+        
+        // Initialize timeout escalation, shared by consensus and mediation
+        let (escalation_tx, escalation_rx) = mpsc::unbounded_channel();
+        let escalator = Arc::new(
+            TimeoutEscalator::new(config.escalation.clone())
+                .with_events(escalation_tx)
+                .with_reputation(reputation.clone())
+        );
+        
         // Initialize consensus manager
         let consensus_manager = Arc::new(
             ConsensusManager::new(
@@ -326,6 +383,8 @@ impl SyncDomain {
                 storage.clone(),
                 kafka_client.clone(),
             ).await?
+            .with_escalator(escalator.clone())
+            .with_reputation(reputation.clone())
         );
         
         // Initialize mediator
@@ -336,6 +395,8 @@ impl SyncDomain {
                 kafka_client.clone(),
                 consensus_manager.clone(),
             ).await?
+            .with_escalator(escalator)
+            .with_reputation(reputation.clone())
         );
         
         // Initialize API server
@@ -350,6 +411,12 @@ impl SyncDomain {
             ).await?
         );
         
+        // Initialize event log
+        let event_log = Arc::new(EventLog::new(
+            Arc::new(EventLogTable::new()),
+            config.database.event_retention_days,
+        ));
+        
         // Initialize domain state
         let state = Arc::new(RwLock::new(DomainState {
             domain_id: config.domain.domain_id.clone(),
@@ -369,7 +436,11 @@ impl SyncDomain {
             mediator,
             clock_manager,
             api_server,
+            event_log,
             state,
+            escalation_rx: Some(escalation_rx),
+            reputation,
+            suspension_rx: Some(suspension_rx),
             shutdown_tx: None,
             task_handles: Vec::new(),
         })
@@ -441,13 +512,57 @@ impl SyncDomain {
             let state = self.state.clone();
             let kafka_client = self.kafka_client.clone();
             let clock_manager = self.clock_manager.clone();
+            let lag_thresholds = self.config.monitoring.consumer_lag.clone();
             
             tokio::spawn(async move {
-                Self::health_checker(state, kafka_client, clock_manager).await;
+                Self::health_checker(state, kafka_client, clock_manager, lag_thresholds).await;
             })
         };
         self.task_handles.push(health_handle);
         
+        // Start escalation handler
+        if let Some(escalation_rx) = self.escalation_rx.take() {
+            let state = self.state.clone();
+            let event_log = self.event_log.clone();
+            let clock_manager = self.clock_manager.clone();
+            let kafka_client = self.kafka_client.clone();
+            let domain_id = self.config.domain.domain_id.clone();
+            
+            let escalation_handle = tokio::spawn(async move {
+                Self::escalation_handler(escalation_rx, state, event_log, clock_manager, kafka_client, domain_id).await;
+            });
+            self.task_handles.push(escalation_handle);
+        }
+        
+        // Start reputation handler
+        if let Some(suspension_rx) = self.suspension_rx.take() {
+            let state = self.state.clone();
+            let storage = self.storage.clone();
+            let event_log = self.event_log.clone();
+            let clock_manager = self.clock_manager.clone();
+            let kafka_client = self.kafka_client.clone();
+            let domain_id = self.config.domain.domain_id.clone();
+            let suspension = Duration::from_secs(self.config.reputation.suspension_duration_secs);
+            
+            let reputation_handle = tokio::spawn(async move {
+                Self::reputation_handler(
+                    suspension_rx,
+                    state,
+                    storage,
+                    event_log,
+                    clock_manager,
+                    kafka_client,
+                    domain_id,
+                    suspension,
+                ).await;
+            });
+            self.task_handles.push(reputation_handle);
+        }
+        
+        // Start event log retention
+        let retention_handle = self.event_log.clone().spawn_retention(Duration::from_secs(3600));
+        self.task_handles.push(retention_handle);
+        
         // Emit domain started event
         self.emit_domain_event(DomainEventType::DomainStarted, serde_json::json!({})).await?;
         
@@ -562,8 +677,9 @@ impl SyncDomain {
         ).await?;
         
         // Emit event
-        self.emit_domain_event(
+        self.emit_participant_event(
             DomainEventType::ParticipantRegistered,
+            &participant_id,
             serde_json::json!({
                 "participant_id": participant_id,
                 "endpoint": participant_info.endpoint,
@@ -596,8 +712,9 @@ impl SyncDomain {
         }
         
         // Emit event
-        self.emit_domain_event(
+        self.emit_participant_event(
             DomainEventType::ParticipantDeregistered,
+            participant_id,
             serde_json::json!({
                 "participant_id": participant_id
             })
@@ -617,6 +734,31 @@ impl SyncDomain {
         self.state.read().await.stats.clone()
     }
     
+    /// Query the history of emitted domain events
+    pub async fn query_event_log(&self, filter: EventFilter, pagination: PageToken) -> GarpResult<EventPage> {
+        self.event_log.query(&filter, &pagination).await
+    }
+    
+    /// Event log, for mounting `event_log::router` on the domain API
+    pub fn event_log(&self) -> Arc<EventLog> {
+        self.event_log.clone()
+    }
+    
+    /// Kafka client, for mounting `kafka::router` on the domain API
+    pub fn kafka_client(&self) -> Arc<KafkaClient> {
+        self.kafka_client.clone()
+    }
+    
+    /// Current reputation of a participant
+    pub async fn get_reputation(&self, participant_id: &ParticipantId) -> ReputationScore {
+        self.reputation.tracker().get(participant_id).await
+    }
+    
+    /// Reputation scores, for mounting `reputation::router` on the domain API
+    pub fn reputation(&self) -> Arc<ReputationTracker> {
+        self.reputation.tracker()
+    }
+    
     /// Register message handlers
     async fn register_message_handlers(&self) -> GarpResult<()> {
         // Register domain message handler
@@ -650,18 +792,56 @@ impl SyncDomain {
         event_type: DomainEventType,
         data: serde_json::Value,
     ) -> GarpResult<()> {
-        let event = DomainEvent {
+        self.publish_event(DomainEvent {
             event_id: uuid::Uuid::new_v4().to_string(),
             event_type,
             timestamp: Utc::now(),
             data,
             transaction_id: None,
             participant_id: None,
-        };
+        }).await
+    }
+    
+    /// Emit domain event related to a participant
+    async fn emit_participant_event(
+        &self,
+        event_type: DomainEventType,
+        participant_id: &ParticipantId,
+        data: serde_json::Value,
+    ) -> GarpResult<()> {
+        self.publish_event(DomainEvent {
+            event_id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            timestamp: Utc::now(),
+            data,
+            transaction_id: None,
+            participant_id: Some(participant_id.clone()),
+        }).await
+    }
+    
+    /// Record an event in the event log and publish it
+    async fn publish_event(&self, event: DomainEvent) -> GarpResult<()> {
+        Self::publish(
+            &self.event_log,
+            &self.clock_manager,
+            &self.kafka_client,
+            &self.config.domain.domain_id,
+            event,
+        ).await
+    }
+    
+    async fn publish(
+        event_log: &Arc<EventLog>,
+        clock_manager: &Arc<RwLock<ClockManager>>,
+        kafka_client: &Arc<KafkaClient>,
+        domain_id: &str,
+        event: DomainEvent,
+    ) -> GarpResult<()> {
+        event_log.record(&event).await;
         
         // Generate clock event
         {
-            let mut clock_manager = self.clock_manager.write().await;
+            let mut clock_manager = clock_manager.write().await;
             let _clock_event = clock_manager.generate_event(
                 EventType::DomainEvent,
                 serde_json::to_value(&event)?
@@ -669,8 +849,8 @@ impl SyncDomain {
         }
         
         // Send to Kafka
-        self.kafka_client.send_domain_event(
-            &self.config.domain.domain_id,
+        kafka_client.send_domain_event(
+            domain_id,
             &event.event_id,
             &event
         ).await?;
@@ -678,6 +858,164 @@ impl SyncDomain {
         Ok(())
     }
     
+    /// Escalation handler task: records retried rounds on their
+    /// transactions, and tells the submitters of failed ones why they failed
+    async fn escalation_handler(
+        mut events: mpsc::UnboundedReceiver<EscalationEvent>,
+        state: Arc<RwLock<DomainState>>,
+        event_log: Arc<EventLog>,
+        clock_manager: Arc<RwLock<ClockManager>>,
+        kafka_client: Arc<KafkaClient>,
+        domain_id: String,
+    ) {
+        while let Some(event) = events.recv().await {
+            let Some((transaction_id, reason)) = Self::apply_escalation(&state, event).await else {
+                continue;
+            };
+            
+            let event = DomainEvent {
+                event_id: uuid::Uuid::new_v4().to_string(),
+                event_type: DomainEventType::TransactionFailed,
+                timestamp: Utc::now(),
+                data: serde_json::json!({
+                    "transaction_id": transaction_id,
+                    "reason": reason
+                }),
+                transaction_id: Some(transaction_id.clone()),
+                participant_id: None,
+            };
+            if let Err(e) = Self::publish(&event_log, &clock_manager, &kafka_client, &domain_id, event).await {
+                error!("Failed to emit failure of transaction {}: {}", transaction_id, e);
+            }
+            
+            if let Err(e) = kafka_client.send_transaction_failed(transaction_id.clone(), domain_id.clone(), reason).await {
+                error!("Failed to notify submitter of failed transaction {}: {}", transaction_id, e);
+            }
+        }
+    }
+    
+    /// Record an escalation on the transaction it concerns. Returns the
+    /// failure to announce if the transaction failed.
+    async fn apply_escalation(
+        state: &Arc<RwLock<DomainState>>,
+        event: EscalationEvent,
+    ) -> Option<(TransactionId, FailureReason)> {
+        let mut state = state.write().await;
+        state.last_updated = Utc::now();
+        
+        match event {
+            EscalationEvent::Attempted { transaction_id, attempt } => {
+                if let Some(transaction) = state.active_transactions.get_mut(&transaction_id) {
+                    transaction.round_attempts.push(attempt);
+                }
+                None
+            }
+            EscalationEvent::Failed { transaction_id, reason } => {
+                if let Some(transaction) = state.active_transactions.get_mut(&transaction_id) {
+                    transaction.status = TransactionStatus::Failed;
+                    match reason.phase {
+                        RoundPhase::Mediation => transaction.mediation_status = Some(MediationStatus::Timeout),
+                        RoundPhase::Consensus => transaction.consensus_status = Some(ConsensusStatus::Timeout),
+                    }
+                    transaction.failure_reason = Some(reason.clone());
+                }
+                warn!("Transaction {} failed after {} {:?} attempts", transaction_id, reason.attempts, reason.phase);
+                Some((transaction_id, reason))
+            }
+        }
+    }
+    
+    /// Reputation handler task: suspends participants whose reputation fell
+    /// below the threshold, and reinstates them once `suspension` has passed
+    #[allow(clippy::too_many_arguments)]
+    async fn reputation_handler(
+        mut updates: mpsc::UnboundedReceiver<ReputationUpdate>,
+        state: Arc<RwLock<DomainState>>,
+        storage: Arc<dyn StorageBackend>,
+        event_log: Arc<EventLog>,
+        clock_manager: Arc<RwLock<ClockManager>>,
+        kafka_client: Arc<KafkaClient>,
+        domain_id: String,
+        suspension: Duration,
+    ) {
+        while let Some(update) = updates.recv().await {
+            let participant_id: ParticipantId = update.score.subject.clone();
+            if !Self::transition_participant(&state, &participant_id, ParticipantStatus::Active, ParticipantStatus::Suspended).await {
+                continue;
+            }
+            if let Err(e) = storage.update_participant_status(&participant_id, StoredParticipantStatus::Suspended).await {
+                error!("Failed to store suspension of participant {}: {}", participant_id, e);
+            }
+            warn!("Suspended participant {} with reputation {:.1}", participant_id, update.score.score);
+            
+            let event = DomainEvent {
+                event_id: uuid::Uuid::new_v4().to_string(),
+                event_type: DomainEventType::ParticipantSuspended,
+                timestamp: Utc::now(),
+                data: serde_json::json!({
+                    "participant_id": participant_id,
+                    "reputation": update.score.score,
+                    "suspended_for_secs": suspension.as_secs()
+                }),
+                transaction_id: None,
+                participant_id: Some(participant_id.clone()),
+            };
+            if let Err(e) = Self::publish(&event_log, &clock_manager, &kafka_client, &domain_id, event).await {
+                error!("Failed to emit suspension of participant {}: {}", participant_id, e);
+            }
+            
+            // Reinstate the participant once the suspension has run its course
+            let state = state.clone();
+            let storage = storage.clone();
+            let event_log = event_log.clone();
+            let clock_manager = clock_manager.clone();
+            let kafka_client = kafka_client.clone();
+            let domain_id = domain_id.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(suspension).await;
+                if !Self::transition_participant(&state, &participant_id, ParticipantStatus::Suspended, ParticipantStatus::Active).await {
+                    return;
+                }
+                if let Err(e) = storage.update_participant_status(&participant_id, StoredParticipantStatus::Active).await {
+                    error!("Failed to store reinstatement of participant {}: {}", participant_id, e);
+                }
+                info!("Reinstated participant {}", participant_id);
+                
+                let event = DomainEvent {
+                    event_id: uuid::Uuid::new_v4().to_string(),
+                    event_type: DomainEventType::ParticipantReinstated,
+                    timestamp: Utc::now(),
+                    data: serde_json::json!({ "participant_id": participant_id }),
+                    transaction_id: None,
+                    participant_id: Some(participant_id.clone()),
+                };
+                if let Err(e) = Self::publish(&event_log, &clock_manager, &kafka_client, &domain_id, event).await {
+                    error!("Failed to emit reinstatement of participant {}: {}", participant_id, e);
+                }
+            });
+        }
+    }
+    
+    /// Move a registered participant from status `from` to `to`. Returns
+    /// false if the participant is unknown or not in `from`.
+    async fn transition_participant(
+        state: &Arc<RwLock<DomainState>>,
+        participant_id: &ParticipantId,
+        from: ParticipantStatus,
+        to: ParticipantStatus,
+    ) -> bool {
+        let mut state = state.write().await;
+        let Some(participant) = state.participants.get_mut(participant_id) else {
+            return false;
+        };
+        if participant.status != from {
+            return false;
+        }
+        participant.status = to;
+        state.last_updated = Utc::now();
+        true
+    }
+    
     /// Statistics updater task
     async fn statistics_updater(
         state: Arc<RwLock<DomainState>>,
@@ -732,6 +1070,10 @@ impl SyncDomain {
             consensus_success_rate: consensus_metrics.success_rate,
             mediation_success_rate: mediator_metrics.success_rate,
             uptime_seconds: domain_stats.uptime_seconds,
+            // Kafka health is measured by the health checker
+            consumer_lag: state_guard.stats.consumer_lag,
+            max_partition_lag: state_guard.stats.max_partition_lag,
+            broker_reachable: state_guard.stats.broker_reachable,
             last_updated: Utc::now(),
         };
         
@@ -750,6 +1092,7 @@ impl SyncDomain {
         state: Arc<RwLock<DomainState>>,
         kafka_client: Arc<KafkaClient>,
         clock_manager: Arc<RwLock<ClockManager>>,
+        lag_thresholds: ConsumerLagThresholds,
     ) {
         let mut interval = interval(Duration::from_secs(60));
         
@@ -762,6 +1105,13 @@ impl SyncDomain {
                 clock_manager.check_suspected_nodes(chrono::Duration::minutes(5));
             }
             
+            // Check the consumer is keeping up
+            let lag = kafka_client.get_consumer_lag().await;
+            if let Err(e) = &lag {
+                error!("Failed to measure consumer lag: {}", e);
+            }
+            Self::apply_consumer_health(&state, lag.as_ref().ok(), &lag_thresholds).await;
+            
             // Send health ping
             if let Err(e) = Self::send_health_ping(&state, &kafka_client, &clock_manager).await {
                 error!("Failed to send health ping: {}", e);
@@ -769,6 +1119,59 @@ impl SyncDomain {
         }
     }
     
+    /// Record consumer lag in the statistics and move the domain between
+    /// Active, Degraded and Maintenance to match it. `None` means the broker
+    /// could not be reached.
+    async fn apply_consumer_health(
+        state: &Arc<RwLock<DomainState>>,
+        lag: Option<&ConsumerLag>,
+        thresholds: &ConsumerLagThresholds,
+    ) {
+        let mut state = state.write().await;
+        state.stats.broker_reachable = lag.is_some();
+        if let Some(lag) = lag {
+            state.stats.consumer_lag = lag.total_lag;
+            state.stats.max_partition_lag = lag.max_partition_lag;
+        }
+        
+        let status = Self::health_status(&state.status, lag, thresholds);
+        if status != state.status {
+            match &status {
+                DomainStatus::Active => info!(
+                    max_partition_lag = state.stats.max_partition_lag,
+                    "Domain recovered, {:?} -> Active", state.status
+                ),
+                _ => warn!(
+                    max_partition_lag = state.stats.max_partition_lag,
+                    broker_reachable = state.stats.broker_reachable,
+                    "Domain unhealthy, {:?} -> {:?}", state.status, status
+                ),
+            }
+            state.status = status;
+            state.last_updated = Utc::now();
+        }
+    }
+    
+    /// Status the Kafka consumer's health calls for: Maintenance while the
+    /// broker is unreachable or lag is past `maintenance_lag`, Degraded past
+    /// `degraded_lag`, Active otherwise. A domain initializing, shutting
+    /// down or failed keeps its status.
+    fn health_status(
+        current: &DomainStatus,
+        lag: Option<&ConsumerLag>,
+        thresholds: &ConsumerLagThresholds,
+    ) -> DomainStatus {
+        if !matches!(current, DomainStatus::Active | DomainStatus::Degraded | DomainStatus::Maintenance) {
+            return current.clone();
+        }
+        match lag {
+            None => DomainStatus::Maintenance,
+            Some(lag) if lag.max_partition_lag > thresholds.maintenance_lag => DomainStatus::Maintenance,
+            Some(lag) if lag.max_partition_lag > thresholds.degraded_lag => DomainStatus::Degraded,
+            Some(_) => DomainStatus::Active,
+        }
+    }
+    
     /// Send health ping
     async fn send_health_ping(
         state: &Arc<RwLock<DomainState>>,
@@ -852,6 +1255,9 @@ impl Default for DomainStatistics {
             consensus_success_rate: 0.0,
             mediation_success_rate: 0.0,
             uptime_seconds: 0,
+            consumer_lag: 0,
+            max_partition_lag: 0,
+            broker_reachable: true,
             last_updated: Utc::now(),
         }
     }
@@ -897,4 +1303,123 @@ mod tests {
         assert_eq!(stats.total_transactions, 0);
         assert_eq!(stats.active_participants, 0);
     }
+    
+    #[tokio::test]
+    async fn test_escalation_is_recorded_on_the_transaction() {
+        use crate::escalation::AttemptOutcome;
+        use std::collections::BTreeSet;
+        
+        let domain = SyncDomain::new(SyncDomainConfig::default()).await.unwrap();
+        domain.state.write().await.active_transactions.insert("tx-1".to_string(), TransactionInfo {
+            transaction_id: "tx-1".to_string(),
+            submitter: "alice".to_string(),
+            participants: vec!["alice".to_string(), "bob".to_string()],
+            status: TransactionStatus::InConsensus,
+            submitted_at: Utc::now(),
+            sequence_number: Some(1),
+            consensus_status: Some(ConsensusStatus::InProgress),
+            mediation_status: Some(MediationStatus::Consented),
+            round_attempts: Vec::new(),
+            failure_reason: None,
+        });
+        
+        let bob: BTreeSet<ParticipantId> = ["bob".to_string()].into_iter().collect();
+        let attempt = RoundAttempt {
+            phase: RoundPhase::Consensus,
+            attempt: 1,
+            started_at: Utc::now(),
+            ended_at: Utc::now(),
+            participants: ["alice".to_string(), "bob".to_string()].into_iter().collect(),
+            unresponsive: bob.clone(),
+            excluded: BTreeSet::new(),
+            outcome: AttemptOutcome::Exhausted,
+        };
+        let reason = FailureReason { phase: RoundPhase::Consensus, attempts: 1, unresponsive_participants: bob };
+        
+        let attempted = EscalationEvent::Attempted { transaction_id: "tx-1".to_string(), attempt: attempt.clone() };
+        assert_eq!(SyncDomain::apply_escalation(&domain.state, attempted).await, None);
+        let failed = EscalationEvent::Failed { transaction_id: "tx-1".to_string(), reason: reason.clone() };
+        assert_eq!(SyncDomain::apply_escalation(&domain.state, failed).await, Some(("tx-1".to_string(), reason.clone())));
+        
+        let transaction = domain.get_state().await.active_transactions["tx-1"].clone();
+        assert!(matches!(transaction.status, TransactionStatus::Failed));
+        assert!(matches!(transaction.consensus_status, Some(ConsensusStatus::Timeout)));
+        assert_eq!(transaction.round_attempts, vec![attempt]);
+        assert_eq!(transaction.failure_reason, Some(reason));
+    }
+    
+    #[tokio::test]
+    async fn test_low_reputation_suspends_the_participant_for_a_while() {
+        let domain = SyncDomain::new(SyncDomainConfig::default()).await.unwrap();
+        let participant_id = "participant1".to_string();
+        domain.register_participant(
+            participant_id.clone(),
+            "http://localhost:8080".to_string(),
+            vec![1, 2, 3, 4],
+            Vec::new(),
+        ).await.unwrap();
+        
+        let (updates_tx, updates_rx) = mpsc::unbounded_channel();
+        tokio::spawn(SyncDomain::reputation_handler(
+            updates_rx,
+            domain.state.clone(),
+            domain.storage.clone(),
+            domain.event_log.clone(),
+            domain.clock_manager.clone(),
+            domain.kafka_client.clone(),
+            "test-domain".to_string(),
+            Duration::from_millis(100),
+        ));
+        let status = || async { domain.get_state().await.participants[&participant_id].status.clone() };
+        
+        // Unknown participants are ignored
+        let stranger = domain.get_reputation(&"stranger".to_string()).await;
+        updates_tx.send(ReputationUpdate { score: stranger, fell_below_threshold: true }).unwrap();
+        
+        let score = domain.get_reputation(&participant_id).await;
+        updates_tx.send(ReputationUpdate { score, fell_below_threshold: true }).unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(status().await, ParticipantStatus::Suspended);
+        assert!(!domain.get_state().await.participants.contains_key("stranger"));
+        
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(status().await, ParticipantStatus::Active);
+    }
+    
+    #[tokio::test]
+    async fn test_consumer_lag_drives_domain_status() {
+        use crate::kafka::PartitionLag;
+        
+        let thresholds = ConsumerLagThresholds { degraded_lag: 100, maintenance_lag: 10_000 };
+        let lag = |behind: i64| ConsumerLag::from_partitions(vec![
+            PartitionLag::new("garp-transactions", 0, Some(0), 0, behind),
+            PartitionLag::new("garp-transactions", 1, Some(0), 0, 5),
+        ]);
+        let domain = SyncDomain::new(SyncDomainConfig::default()).await.unwrap();
+        domain.state.write().await.status = DomainStatus::Active;
+        
+        let mut statuses = Vec::new();
+        for measurement in [Some(lag(50)), Some(lag(5_000)), Some(lag(2_000_000)), None, Some(lag(0))] {
+            SyncDomain::apply_consumer_health(&domain.state, measurement.as_ref(), &thresholds).await;
+            let state = domain.get_state().await;
+            statuses.push((state.status, state.stats.broker_reachable));
+        }
+        assert_eq!(statuses, vec![
+            (DomainStatus::Active, true),
+            (DomainStatus::Degraded, true),
+            (DomainStatus::Maintenance, true),
+            (DomainStatus::Maintenance, false),
+            (DomainStatus::Active, true),
+        ]);
+        
+        let stats = domain.get_statistics().await;
+        assert_eq!(stats.consumer_lag, 5);
+        assert_eq!(stats.max_partition_lag, 5);
+        
+        // A shutting-down domain is not brought back by a healthy consumer
+        assert_eq!(
+            SyncDomain::health_status(&DomainStatus::ShuttingDown, Some(&lag(0)), &thresholds),
+            DomainStatus::ShuttingDown,
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,523 @@
+//! Escalation of mediation and consensus rounds that time out
+//!
+//! A timed-out round is retried after an exponential backoff until it has
+//! timed out `max_attempts` times, after which the transaction fails with a
+//! [`FailureReason`] naming the phase and the participants that never
+//! responded. As many of the participants silent through an attempt as the
+//! round can reach quorum without are left out of the retries, those with
+//! the most missed rounds first, and every silence counts against the
+//! participant's reputation.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, RwLock};
+
+use garp_common::{ParticipantId, ReputationSignal, TransactionId};
+use garp_common::validator::ValidatorSet;
+
+use crate::config::EscalationConfig;
+use crate::reputation::ParticipantReputation;
+
+/// Round of a transaction's processing that can time out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RoundPhase {
+    /// Participants consenting to the transaction
+    Mediation,
+
+    /// Validators voting on the transaction
+    Consensus,
+}
+
+/// How an attempt at a round ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttemptOutcome {
+    /// The round was decided
+    Completed,
+
+    /// The attempt timed out and the round was retried
+    Retried,
+
+    /// The last attempt timed out
+    Exhausted,
+}
+
+/// One attempt at a round
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundAttempt {
+    /// Round attempted
+    pub phase: RoundPhase,
+
+    /// Attempt number, from 1
+    pub attempt: u32,
+
+    /// Start of the attempt
+    pub started_at: DateTime<Utc>,
+
+    /// End of the attempt
+    pub ended_at: DateTime<Utc>,
+
+    /// Participants asked to respond
+    pub participants: BTreeSet<ParticipantId>,
+
+    /// Participants that had not responded when the attempt timed out
+    pub unresponsive: BTreeSet<ParticipantId>,
+
+    /// Unresponsive participants left out of the next attempt
+    pub excluded: BTreeSet<ParticipantId>,
+
+    /// How the attempt ended
+    pub outcome: AttemptOutcome,
+}
+
+/// Why a transaction failed once its retries were used up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureReason {
+    /// Round that kept timing out
+    pub phase: RoundPhase,
+
+    /// Attempts made
+    pub attempts: u32,
+
+    /// Participants that failed to respond in any attempt
+    pub unresponsive_participants: BTreeSet<ParticipantId>,
+}
+
+/// What to do with a timed-out round
+#[derive(Debug, Clone, PartialEq)]
+pub enum Escalation {
+    /// Start attempt `attempt` after `backoff`, without the `excluded`
+    /// participants
+    Retry {
+        attempt: u32,
+        backoff: Duration,
+        excluded: BTreeSet<ParticipantId>,
+    },
+
+    /// Fail the transaction
+    Fail(FailureReason),
+}
+
+/// Attempts and failures, for the domain to record on the transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum EscalationEvent {
+    /// An attempt at a round ended
+    Attempted {
+        transaction_id: TransactionId,
+        attempt: RoundAttempt,
+    },
+
+    /// A round timed out on its last attempt
+    Failed {
+        transaction_id: TransactionId,
+        reason: FailureReason,
+    },
+}
+
+/// Rounds a participant let time out
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresponsiveStats {
+    /// Attempts the participant did not respond to
+    pub missed_rounds: u64,
+
+    /// When it last failed to respond
+    pub last_missed: Option<DateTime<Utc>>,
+}
+
+/// Round being retried
+struct RoundState {
+    /// Current attempt
+    attempt: u32,
+
+    /// Start of the current attempt
+    started_at: DateTime<Utc>,
+
+    /// Participants unresponsive in any attempt so far
+    unresponsive: BTreeSet<ParticipantId>,
+}
+
+/// Decides whether timed-out rounds are retried or their transaction fails
+pub struct TimeoutEscalator {
+    /// Configuration
+    config: EscalationConfig,
+
+    /// Rounds that have timed out at least once
+    rounds: RwLock<HashMap<(TransactionId, RoundPhase), RoundState>>,
+
+    /// Missed rounds per participant
+    unresponsive: RwLock<HashMap<ParticipantId, UnresponsiveStats>>,
+
+    /// Validator reputations charged for missed rounds
+    validator_set: Option<Arc<ValidatorSet>>,
+
+    /// Participant reputations charged for missed rounds
+    reputation: Option<Arc<ParticipantReputation>>,
+
+    /// Receiver of attempts and failures
+    events: Option<mpsc::UnboundedSender<EscalationEvent>>,
+}
+
+impl TimeoutEscalator {
+    /// Create new timeout escalator
+    pub fn new(config: EscalationConfig) -> Self {
+        Self {
+            config,
+            rounds: RwLock::new(HashMap::new()),
+            unresponsive: RwLock::new(HashMap::new()),
+            validator_set: None,
+            reputation: None,
+            events: None,
+        }
+    }
+
+    /// Report attempts and failures on `events`
+    pub fn with_events(mut self, events: mpsc::UnboundedSender<EscalationEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Record missed rounds as missed votes of the validators in
+    /// `validator_set`
+    pub fn with_validator_set(mut self, validator_set: Arc<ValidatorSet>) -> Self {
+        self.validator_set = Some(validator_set);
+        self
+    }
+
+    /// Record missed consensus rounds as missed votes, and missed mediation
+    /// rounds as mediation timeouts, in participants' reputations
+    pub fn with_reputation(mut self, reputation: Arc<ParticipantReputation>) -> Self {
+        self.reputation = Some(reputation);
+        self
+    }
+
+    /// Backoff before attempt `attempt`; the first retry is attempt 2
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let doublings = attempt.saturating_sub(2).min(32);
+        let backoff = self.config.initial_backoff_ms.saturating_mul(1u64 << doublings);
+        Duration::from_millis(backoff.min(self.config.max_backoff_ms))
+    }
+
+    /// Decide what to do with a round that timed out. `started_at` is the
+    /// start of the round's first attempt, and `quorum_without` tells
+    /// whether the round can still be decided if the given participants
+    /// never respond.
+    pub async fn on_timeout(
+        &self,
+        phase: RoundPhase,
+        transaction_id: &TransactionId,
+        started_at: DateTime<Utc>,
+        participants: &HashSet<ParticipantId>,
+        unresponsive: &HashSet<ParticipantId>,
+        quorum_without: impl Fn(&HashSet<ParticipantId>) -> bool,
+    ) -> Escalation {
+        let now = Utc::now();
+        self.record_unresponsive(phase, unresponsive, now).await;
+        let excludable = if self.config.exclude_unresponsive {
+            self.exclusions(unresponsive, quorum_without).await
+        } else {
+            BTreeSet::new()
+        };
+
+        let key = (transaction_id.clone(), phase);
+        let mut rounds = self.rounds.write().await;
+        let round = rounds.entry(key.clone()).or_insert_with(|| RoundState {
+            attempt: 1,
+            started_at,
+            unresponsive: BTreeSet::new(),
+        });
+        round.unresponsive.extend(unresponsive.iter().cloned());
+
+        let exhausted = round.attempt >= self.config.max_attempts;
+        let excluded = if exhausted { BTreeSet::new() } else { excludable };
+        self.emit(EscalationEvent::Attempted {
+            transaction_id: transaction_id.clone(),
+            attempt: RoundAttempt {
+                phase,
+                attempt: round.attempt,
+                started_at: round.started_at,
+                ended_at: now,
+                participants: participants.iter().cloned().collect(),
+                unresponsive: unresponsive.iter().cloned().collect(),
+                excluded: excluded.clone(),
+                outcome: if exhausted { AttemptOutcome::Exhausted } else { AttemptOutcome::Retried },
+            },
+        });
+
+        if !exhausted {
+            round.attempt += 1;
+            let attempt = round.attempt;
+            let backoff = self.backoff(attempt);
+            round.started_at = now + chrono::Duration::from_std(backoff).unwrap_or_else(|_| chrono::Duration::zero());
+            tracing::info!(
+                "{:?} of transaction {} timed out, retrying in {:?} (attempt {} of {})",
+                phase, transaction_id, backoff, attempt, self.config.max_attempts
+            );
+            return Escalation::Retry { attempt, backoff, excluded };
+        }
+
+        let round = rounds.remove(&key).expect("round was just inserted");
+        let reason = FailureReason {
+            phase,
+            attempts: round.attempt,
+            unresponsive_participants: round.unresponsive,
+        };
+        tracing::warn!(
+            "{:?} of transaction {} timed out {} times, unresponsive: {:?}",
+            phase, transaction_id, reason.attempts, reason.unresponsive_participants
+        );
+        self.emit(EscalationEvent::Failed {
+            transaction_id: transaction_id.clone(),
+            reason: reason.clone(),
+        });
+        Escalation::Fail(reason)
+    }
+
+    /// Record the attempt that decided a round. Rounds decided on their
+    /// first attempt leave no history.
+    pub async fn on_completed(
+        &self,
+        phase: RoundPhase,
+        transaction_id: &TransactionId,
+        participants: &HashSet<ParticipantId>,
+    ) {
+        let Some(round) = self.rounds.write().await.remove(&(transaction_id.clone(), phase)) else {
+            return;
+        };
+        self.emit(EscalationEvent::Attempted {
+            transaction_id: transaction_id.clone(),
+            attempt: RoundAttempt {
+                phase,
+                attempt: round.attempt,
+                started_at: round.started_at,
+                ended_at: Utc::now(),
+                participants: participants.iter().cloned().collect(),
+                unresponsive: BTreeSet::new(),
+                excluded: BTreeSet::new(),
+                outcome: AttemptOutcome::Completed,
+            },
+        });
+    }
+
+    /// Missed rounds per participant
+    pub async fn unresponsive_stats(&self) -> HashMap<ParticipantId, UnresponsiveStats> {
+        self.unresponsive.read().await.clone()
+    }
+
+    /// Largest set of `unresponsive` participants the round reaches quorum
+    /// without, built up from those with the most missed rounds
+    async fn exclusions(
+        &self,
+        unresponsive: &HashSet<ParticipantId>,
+        quorum_without: impl Fn(&HashSet<ParticipantId>) -> bool,
+    ) -> BTreeSet<ParticipantId> {
+        let mut candidates: Vec<ParticipantId> = unresponsive.iter().cloned().collect();
+        {
+            let stats = self.unresponsive.read().await;
+            let missed = |participant_id: &ParticipantId| stats.get(participant_id).map_or(0, |s| s.missed_rounds);
+            candidates.sort_by(|a, b| missed(b).cmp(&missed(a)).then_with(|| a.cmp(b)));
+        }
+
+        let mut excluded = HashSet::new();
+        for participant_id in candidates {
+            excluded.insert(participant_id.clone());
+            if !quorum_without(&excluded) {
+                excluded.remove(&participant_id);
+            }
+        }
+        excluded.into_iter().collect()
+    }
+
+    async fn record_unresponsive(&self, phase: RoundPhase, unresponsive: &HashSet<ParticipantId>, now: DateTime<Utc>) {
+        {
+            let mut stats = self.unresponsive.write().await;
+            for participant_id in unresponsive {
+                let stats = stats.entry(participant_id.clone()).or_default();
+                stats.missed_rounds += 1;
+                stats.last_missed = Some(now);
+            }
+        }
+
+        if let Some(validator_set) = &self.validator_set {
+            for participant_id in unresponsive {
+                if let Err(e) = validator_set.record_missed_vote(participant_id).await {
+                    tracing::warn!("Failed to record missed round of {}: {}", participant_id, e);
+                }
+            }
+        }
+
+        if let Some(reputation) = &self.reputation {
+            let signal = match phase {
+                RoundPhase::Mediation => ReputationSignal::MediationTimeout,
+                RoundPhase::Consensus => ReputationSignal::MissedVote,
+            };
+            reputation.record_all(unresponsive, signal).await;
+        }
+    }
+
+    fn emit(&self, event: EscalationEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use garp_common::validator::{ValidatorInfo, ValidatorStatus};
+
+    fn set(participants: &[&str]) -> HashSet<ParticipantId> {
+        participants.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn escalator(max_attempts: u32) -> (TimeoutEscalator, mpsc::UnboundedReceiver<EscalationEvent>) {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let config = EscalationConfig {
+            max_attempts,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 250,
+            exclude_unresponsive: true,
+        };
+        (TimeoutEscalator::new(config).with_events(events_tx), events_rx)
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap() {
+        let (escalator, _) = escalator(5);
+        let backoffs: Vec<u64> = (2..=5).map(|attempt| escalator.backoff(attempt).as_millis() as u64).collect();
+        assert_eq!(backoffs, vec![100, 200, 250, 250]);
+        assert_eq!(escalator.backoff(u32::MAX), Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn test_round_is_retried_then_fails_with_the_unresponsive_participants() {
+        let (escalator, mut events) = escalator(3);
+        let tx = "tx-1".to_string();
+        let participants = set(&["a", "b", "c"]);
+
+        // Quorum without the silent participant: it is left out of the retry
+        let escalation = escalator.on_timeout(RoundPhase::Mediation, &tx, Utc::now(), &participants, &set(&["c"]), |absent| absent.len() <= 1).await;
+        assert_eq!(escalation, Escalation::Retry {
+            attempt: 2,
+            backoff: Duration::from_millis(100),
+            excluded: ["c".to_string()].into_iter().collect(),
+        });
+
+        // No quorum without them: everyone is asked again
+        let escalation = escalator.on_timeout(RoundPhase::Mediation, &tx, Utc::now(), &set(&["a", "b"]), &set(&["b"]), |_| false).await;
+        assert!(matches!(escalation, Escalation::Retry { attempt: 3, ref excluded, .. } if excluded.is_empty()));
+
+        let escalation = escalator.on_timeout(RoundPhase::Mediation, &tx, Utc::now(), &set(&["a", "b"]), &set(&["b"]), |_| true).await;
+        let reason = FailureReason {
+            phase: RoundPhase::Mediation,
+            attempts: 3,
+            unresponsive_participants: ["b".to_string(), "c".to_string()].into_iter().collect(),
+        };
+        assert_eq!(escalation, Escalation::Fail(reason.clone()));
+
+        let mut outcomes = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            match event {
+                EscalationEvent::Attempted { attempt, .. } => outcomes.push((attempt.attempt, attempt.outcome)),
+                EscalationEvent::Failed { reason: failed, .. } => assert_eq!(failed, reason),
+            }
+        }
+        assert_eq!(outcomes, vec![
+            (1, AttemptOutcome::Retried),
+            (2, AttemptOutcome::Retried),
+            (3, AttemptOutcome::Exhausted),
+        ]);
+
+        let stats = escalator.unresponsive_stats().await;
+        assert_eq!(stats["b"].missed_rounds, 2);
+        assert_eq!(stats["c"].missed_rounds, 1);
+        assert!(!stats.contains_key("a"));
+    }
+
+    #[tokio::test]
+    async fn test_participants_with_the_most_missed_rounds_are_excluded_first() {
+        let (escalator, _events) = escalator(3);
+        escalator.on_timeout(RoundPhase::Consensus, &"tx-1".to_string(), Utc::now(), &set(&["x"]), &set(&["x"]), |_| false).await;
+
+        let escalation = escalator.on_timeout(
+            RoundPhase::Consensus, &"tx-2".to_string(), Utc::now(), &set(&["v", "w", "x"]), &set(&["w", "x"]), |absent| absent.len() <= 1,
+        ).await;
+        assert!(matches!(escalation, Escalation::Retry { ref excluded, .. } if *excluded == BTreeSet::from(["x".to_string()])));
+    }
+
+    #[tokio::test]
+    async fn test_completed_retry_is_recorded_and_resets_the_round() {
+        let (escalator, mut events) = escalator(2);
+        let tx = "tx-1".to_string();
+        let participants = set(&["a", "b"]);
+
+        // Decided on the first attempt: nothing to record
+        escalator.on_completed(RoundPhase::Consensus, &tx, &participants).await;
+        assert!(events.try_recv().is_err());
+
+        escalator.on_timeout(RoundPhase::Consensus, &tx, Utc::now(), &participants, &set(&["b"]), |_| false).await;
+        escalator.on_completed(RoundPhase::Consensus, &tx, &participants).await;
+        let _retried = events.try_recv().unwrap();
+        match events.try_recv().unwrap() {
+            EscalationEvent::Attempted { attempt, .. } => {
+                assert_eq!((attempt.attempt, attempt.outcome), (2, AttemptOutcome::Completed));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        // A later round of the same phase starts from the first attempt
+        let escalation = escalator.on_timeout(RoundPhase::Consensus, &tx, Utc::now(), &participants, &set(&["b"]), |_| false).await;
+        assert!(matches!(escalation, Escalation::Retry { attempt: 2, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_missed_rounds_lower_validator_reputation() {
+        let validator_set = Arc::new(ValidatorSet::new(1));
+        validator_set.add_validator(ValidatorInfo {
+            id: "b".to_string(),
+            public_key_hex: String::new(),
+            voting_power: 100,
+            status: ValidatorStatus::Active,
+            joined_at: Utc::now(),
+            metadata: HashMap::new(),
+            reputation_score: 50,
+            successful_proposals: 0,
+            failed_proposals: 0,
+            missed_votes: 0,
+            last_seen: Utc::now(),
+            slashing_history: Vec::new(),
+            delegators: HashMap::new(),
+            commission_rate_bp: 0,
+            total_delegated: 0,
+            self_bonded: 1000,
+        }).await.unwrap();
+        let escalator = TimeoutEscalator::new(EscalationConfig::default()).with_validator_set(validator_set.clone());
+
+        escalator.on_timeout(RoundPhase::Consensus, &"tx-1".to_string(), Utc::now(), &set(&["a", "b"]), &set(&["b"]), |_| false).await;
+        let validator = validator_set.get_validator(&"b".to_string()).await.unwrap();
+        assert_eq!((validator.missed_votes, validator.reputation_score), (1, 49));
+    }
+
+    #[tokio::test]
+    async fn test_missed_rounds_lower_participant_reputation() {
+        use garp_common::{ReputationConfig, ReputationTracker};
+
+        let tracker = Arc::new(ReputationTracker::new(ReputationConfig::default()));
+        let reputation = Arc::new(ParticipantReputation::new(tracker.clone()));
+        let escalator = TimeoutEscalator::new(EscalationConfig::default()).with_reputation(reputation);
+
+        escalator.on_timeout(RoundPhase::Consensus, &"tx-1".to_string(), Utc::now(), &set(&["a", "b"]), &set(&["b"]), |_| false).await;
+        escalator.on_timeout(RoundPhase::Mediation, &"tx-2".to_string(), Utc::now(), &set(&["a", "b"]), &set(&["a", "b"]), |_| false).await;
+
+        let a = tracker.get("a").await;
+        assert_eq!(a.signals.get(&ReputationSignal::MediationTimeout), Some(&1));
+        assert_eq!(a.signals.get(&ReputationSignal::MissedVote), None);
+        let b = tracker.get("b").await;
+        assert_eq!(b.signals.get(&ReputationSignal::MediationTimeout), Some(&1));
+        assert_eq!(b.signals.get(&ReputationSignal::MissedVote), Some(&1));
+        assert!(b.score < a.score);
+    }
+}
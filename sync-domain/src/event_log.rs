@@ -0,0 +1,309 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use garp_common::{GarpResult, ParticipantId};
+use serde::{Deserialize, Serialize};
+use std::ops::Bound;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, error, info};
+
+use crate::domain::DomainEvent;
+use crate::storage::{EventLogKey, EventLogRecord, EventLogTable};
+
+/// Page size used when the caller does not specify one
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Largest page a single query may return
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Queryable history of domain events
+pub struct EventLog {
+    /// Backing table
+    table: Arc<EventLogTable>,
+
+    /// How long events are kept
+    retention: chrono::Duration,
+}
+
+/// Event log query filter; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    /// Event type name, e.g. `ParticipantRegistered`
+    pub event_type: Option<String>,
+
+    /// Related participant
+    pub participant_id: Option<ParticipantId>,
+
+    /// Earliest event time (inclusive)
+    pub from: Option<DateTime<Utc>>,
+
+    /// Latest event time (inclusive)
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Pagination cursor for event log queries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageToken {
+    /// Resume strictly after this record; `None` starts from the beginning
+    pub after: Option<EventLogKey>,
+
+    /// Page size
+    pub limit: Option<usize>,
+}
+
+/// One page of event log results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventPage {
+    /// Matching events in time order
+    pub events: Vec<EventLogRecord>,
+
+    /// Token for the next page, if more events match
+    pub next_page_token: Option<String>,
+}
+
+impl PageToken {
+    /// Token for the first page
+    pub fn first(limit: usize) -> Self {
+        Self { after: None, limit: Some(limit) }
+    }
+
+    /// Encode the cursor position as an opaque string
+    pub fn encode(&self) -> String {
+        match &self.after {
+            Some(key) => format!(
+                "{}.{}.{}",
+                key.timestamp.timestamp(),
+                key.timestamp.timestamp_subsec_nanos(),
+                key.sequence
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Decode a token produced by [`PageToken::encode`]
+    pub fn decode(token: &str, limit: Option<usize>) -> GarpResult<Self> {
+        if token.is_empty() {
+            return Ok(Self { after: None, limit });
+        }
+
+        let invalid = || anyhow::anyhow!("Invalid page token: {}", token);
+        let parts: Vec<&str> = token.split('.').collect();
+        let [secs, nanos, sequence] = parts.as_slice() else {
+            return Err(invalid().into());
+        };
+        let secs: i64 = secs.parse().map_err(|_| invalid())?;
+        let nanos: u32 = nanos.parse().map_err(|_| invalid())?;
+        let sequence: u64 = sequence.parse().map_err(|_| invalid())?;
+        let timestamp = Utc.timestamp_opt(secs, nanos).single().ok_or_else(invalid)?;
+
+        Ok(Self {
+            after: Some(EventLogKey { timestamp, sequence }),
+            limit,
+        })
+    }
+}
+
+impl EventLog {
+    /// Create an event log keeping events for `retention_days`
+    pub fn new(table: Arc<EventLogTable>, retention_days: u32) -> Self {
+        Self {
+            table,
+            retention: chrono::Duration::days(retention_days as i64),
+        }
+    }
+
+    /// Record an emitted domain event
+    pub async fn record(&self, event: &DomainEvent) -> EventLogKey {
+        self.table.append(
+            event.timestamp,
+            event.event_id.clone(),
+            format!("{:?}", event.event_type),
+            event.participant_id.clone(),
+            event.transaction_id.clone(),
+            event.data.clone(),
+        ).await
+    }
+
+    /// Query events matching `filter`, one page at a time
+    pub async fn query(&self, filter: &EventFilter, page: &PageToken) -> GarpResult<EventPage> {
+        let limit = page.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let start = match (page.after, filter.from) {
+            (Some(after), _) => Bound::Excluded(after),
+            (None, Some(from)) => Bound::Included(EventLogKey { timestamp: from, sequence: 0 }),
+            (None, None) => Bound::Unbounded,
+        };
+
+        let (events, has_more) = self.table.scan(start, filter.to, limit, |record| {
+            filter.from.map_or(true, |from| record.key.timestamp >= from)
+                && filter.event_type.as_ref().map_or(true, |t| &record.event_type == t)
+                && filter.participant_id.as_ref().map_or(true, |p| record.participant_id.as_ref() == Some(p))
+        }).await;
+
+        let next_page_token = match events.last() {
+            Some(last) if has_more => Some(PageToken { after: Some(last.key), limit: page.limit }.encode()),
+            _ => None,
+        };
+
+        Ok(EventPage { events, next_page_token })
+    }
+
+    /// Drop events older than the retention period, returning the number removed
+    pub async fn prune_expired(&self, now: DateTime<Utc>) -> u64 {
+        self.table.prune_before(now - self.retention).await
+    }
+
+    /// Spawn the periodic retention task
+    pub fn spawn_retention(self: Arc<Self>, check_interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(check_interval);
+
+            loop {
+                interval.tick().await;
+
+                let pruned = self.prune_expired(Utc::now()).await;
+                if pruned > 0 {
+                    info!("Pruned {} expired events from the event log", pruned);
+                }
+            }
+        })
+    }
+}
+
+/// Query parameters for `GET /api/v1/events`
+#[derive(Debug, Deserialize)]
+pub struct EventLogParams {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    pub participant: Option<String>,
+    pub page_token: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Routes exposing the event log; merged into the domain API router
+pub fn router(event_log: Arc<EventLog>) -> Router {
+    Router::new()
+        .route("/api/v1/events", get(list_events))
+        .with_state(event_log)
+}
+
+/// List domain events
+async fn list_events(
+    State(event_log): State<Arc<EventLog>>,
+    Query(params): Query<EventLogParams>,
+) -> Result<Json<EventPage>, StatusCode> {
+    let page = PageToken::decode(params.page_token.as_deref().unwrap_or(""), params.limit)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let filter = EventFilter {
+        event_type: params.event_type,
+        participant_id: params.participant.as_deref().map(ParticipantId::new),
+        from: params.from,
+        to: params.to,
+    };
+
+    debug!("Event log query: {:?}", filter);
+    match event_log.query(&filter, &page).await {
+        Ok(page) => Ok(Json(page)),
+        Err(e) => {
+            error!("Event log query failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DomainEventType;
+
+    fn event(event_type: DomainEventType, participant: Option<&str>, timestamp: DateTime<Utc>) -> DomainEvent {
+        DomainEvent {
+            event_id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            timestamp,
+            data: serde_json::json!({}),
+            transaction_id: None,
+            participant_id: participant.map(ParticipantId::new),
+        }
+    }
+
+    async fn populated_log() -> (EventLog, DateTime<Utc>) {
+        let log = EventLog::new(Arc::new(EventLogTable::new()), 30);
+        let t0 = Utc::now();
+        let events = [
+            event(DomainEventType::DomainStarted, None, t0),
+            event(DomainEventType::ParticipantRegistered, Some("alice"), t0 + chrono::Duration::seconds(1)),
+            event(DomainEventType::ParticipantRegistered, Some("bob"), t0 + chrono::Duration::seconds(2)),
+            event(DomainEventType::ParticipantDeregistered, Some("alice"), t0 + chrono::Duration::seconds(3)),
+        ];
+        for event in &events {
+            log.record(event).await;
+        }
+        (log, t0)
+    }
+
+    #[tokio::test]
+    async fn test_query_filters_by_type_participant_and_time() {
+        let (log, t0) = populated_log().await;
+
+        let by_type = EventFilter {
+            event_type: Some("ParticipantRegistered".to_string()),
+            ..Default::default()
+        };
+        let page = log.query(&by_type, &PageToken::default()).await.unwrap();
+        assert_eq!(page.events.len(), 2);
+        assert!(page.next_page_token.is_none());
+
+        let by_participant = EventFilter {
+            participant_id: Some(ParticipantId::new("alice")),
+            ..Default::default()
+        };
+        let page = log.query(&by_participant, &PageToken::default()).await.unwrap();
+        let types: Vec<&str> = page.events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, vec!["ParticipantRegistered", "ParticipantDeregistered"]);
+
+        let by_time = EventFilter {
+            from: Some(t0 + chrono::Duration::seconds(1)),
+            to: Some(t0 + chrono::Duration::seconds(2)),
+            ..Default::default()
+        };
+        let page = log.query(&by_time, &PageToken::default()).await.unwrap();
+        assert_eq!(page.events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_paginates_with_tokens() {
+        let (log, _) = populated_log().await;
+        let filter = EventFilter::default();
+
+        let first = log.query(&filter, &PageToken::first(3)).await.unwrap();
+        assert_eq!(first.events.len(), 3);
+        let token = first.next_page_token.expect("more events remain");
+
+        let second = log.query(&filter, &PageToken::decode(&token, Some(3)).unwrap()).await.unwrap();
+        assert_eq!(second.events.len(), 1);
+        assert_eq!(second.events[0].event_type, "ParticipantDeregistered");
+        assert!(second.next_page_token.is_none());
+
+        assert!(PageToken::decode("not-a-token", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired_respects_retention() {
+        let table = Arc::new(EventLogTable::new());
+        let log = EventLog::new(table.clone(), 7);
+        let now = Utc::now();
+        log.record(&event(DomainEventType::DomainStarted, None, now - chrono::Duration::days(8))).await;
+        log.record(&event(DomainEventType::DomainStopped, None, now - chrono::Duration::days(1))).await;
+
+        assert_eq!(log.prune_expired(now).await, 1);
+        assert_eq!(table.len().await, 1);
+    }
+}
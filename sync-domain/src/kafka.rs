@@ -1,11 +1,13 @@
 use async_trait::async_trait;
+use axum::{extract::State, http::StatusCode, response::Json, routing::get, Router};
 use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
-    message::{BorrowedMessage, Message},
+    error::{KafkaError, RDKafkaErrorCode},
+    message::Message,
     producer::{FutureProducer, FutureRecord},
     util::Timeout,
-    ClientContext, TopicPartitionList,
+    ClientContext, Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -14,9 +16,16 @@ use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use garp_common::{GarpResult, TransactionId, ParticipantId};
-use crate::config::{KafkaConfig, TopicConfig};
-use crate::storage::SequencedTransaction;
+use garp_common::{GarpError, GarpResult, TransactionId, ParticipantId};
+use crate::config::{DeadLetterConfig, KafkaConfig, TopicConfig};
+use crate::escalation::FailureReason;
+use crate::storage::{SequencedTransaction, StorageBackend};
+
+/// Timeout for broker metadata and seek requests
+const OFFSET_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Handler recorded for dead letters whose payload could not be decoded
+const UNDECODABLE_HANDLER: &str = "undecodable";
 
 /// Kafka message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +38,9 @@ pub enum KafkaMessage {
         participants: Vec<ParticipantId>,
         domain_id: String,
         timestamp: DateTime<Utc>,
+        /// Submitter-supplied settings, e.g. `approval_policy`
+        #[serde(default)]
+        metadata: HashMap<String, String>,
     },
     
     /// Transaction sequenced
@@ -40,6 +52,14 @@ pub enum KafkaMessage {
         timestamp: DateTime<Utc>,
     },
     
+    /// Transaction failed after its timed-out rounds were retried
+    TransactionFailed {
+        transaction_id: TransactionId,
+        domain_id: String,
+        reason: FailureReason,
+        timestamp: DateTime<Utc>,
+    },
+    
     /// Consensus vote
     ConsensusVote {
         transaction_id: TransactionId,
@@ -117,20 +137,247 @@ pub trait MessageHandler: Send + Sync {
     fn name(&self) -> &str;
 }
 
+/// Where to reposition a partition when its offsets are reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OffsetResetStrategy {
+    /// Oldest message still retained
+    Earliest,
+    
+    /// Next message to be produced
+    Latest,
+}
+
+impl OffsetResetStrategy {
+    /// Strategy named by the consumer's `auto_offset_reset` setting
+    pub fn from_config(auto_offset_reset: &str) -> Self {
+        match auto_offset_reset {
+            "earliest" | "smallest" | "beginning" => Self::Earliest,
+            _ => Self::Latest,
+        }
+    }
+    
+    fn offset(self) -> Offset {
+        match self {
+            Self::Earliest => Offset::Beginning,
+            Self::Latest => Offset::End,
+        }
+    }
+}
+
+/// Consumer positions committed to domain storage.
+///
+/// The domain tracks its own offsets rather than relying on the consumer
+/// group's, so a restarted domain resumes after the last message it
+/// processed. The stored value is the next offset to consume, following the
+/// Kafka convention.
+pub struct OffsetTracker {
+    storage: Arc<dyn StorageBackend>,
+}
+
+impl OffsetTracker {
+    pub fn new(storage: Arc<dyn StorageBackend>) -> Self {
+        Self { storage }
+    }
+    
+    /// Storage key of a partition's offset
+    pub fn key(topic: &str, partition: i32) -> String {
+        format!("kafka:offset:{}:{}", topic, partition)
+    }
+    
+    /// Record that the message at `offset` has been processed
+    pub async fn commit(&self, topic: &str, partition: i32, offset: i64) -> GarpResult<()> {
+        self.store(topic, partition, offset + 1).await
+    }
+    
+    /// Store the next offset to consume
+    pub async fn store(&self, topic: &str, partition: i32, next_offset: i64) -> GarpResult<()> {
+        let value = serde_json::to_vec(&next_offset)?;
+        self.storage.put_metadata(&Self::key(topic, partition), &value).await
+    }
+    
+    /// Next offset to consume, if one was committed
+    pub async fn next_offset(&self, topic: &str, partition: i32) -> GarpResult<Option<i64>> {
+        match self.storage.get_metadata(&Self::key(topic, partition)).await? {
+            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            None => Ok(None),
+        }
+    }
+    
+    /// Starting position of every partition of `topics`: the committed
+    /// offset where there is one, `default` otherwise
+    pub async fn start_positions(
+        &self,
+        topics: &[&str],
+        partitions: i32,
+        default: OffsetResetStrategy,
+    ) -> GarpResult<TopicPartitionList> {
+        let mut positions = TopicPartitionList::new();
+        for topic in topics {
+            for partition in 0..partitions {
+                let offset = match self.next_offset(topic, partition).await? {
+                    Some(next_offset) => Offset::Offset(next_offset),
+                    None => default.offset(),
+                };
+                positions.add_partition_offset(topic, partition, offset)?;
+            }
+        }
+        Ok(positions)
+    }
+    
+    /// Whether a committed position lies within a partition's retained
+    /// messages, given its low and high watermarks
+    pub fn in_range(next_offset: i64, low: i64, high: i64) -> bool {
+        (low..=high).contains(&next_offset)
+    }
+}
+
+/// Consumer position of one partition against the broker's high watermark
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionLag {
+    /// Topic name
+    pub topic: String,
+    
+    /// Partition number
+    pub partition: i32,
+    
+    /// Next offset to consume, if one was committed
+    pub committed_offset: Option<i64>,
+    
+    /// Offset the next produced message will get
+    pub high_watermark: i64,
+    
+    /// Messages produced but not yet processed
+    pub lag: i64,
+}
+
+impl PartitionLag {
+    /// Lag of a partition with the given watermarks. Without a committed
+    /// offset, or with one the broker no longer retains, every retained
+    /// message is behind.
+    pub fn new(topic: &str, partition: i32, committed_offset: Option<i64>, low: i64, high: i64) -> Self {
+        let position = committed_offset.unwrap_or(low).max(low);
+        Self {
+            topic: topic.to_string(),
+            partition,
+            committed_offset,
+            high_watermark: high,
+            lag: (high - position).max(0),
+        }
+    }
+}
+
+/// Consumer lag across every consumed partition
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsumerLag {
+    /// Lag of each partition
+    pub partitions: Vec<PartitionLag>,
+    
+    /// Sum of partition lags
+    pub total_lag: i64,
+    
+    /// Lag of the furthest-behind partition
+    pub max_partition_lag: i64,
+}
+
+impl ConsumerLag {
+    pub fn from_partitions(partitions: Vec<PartitionLag>) -> Self {
+        Self {
+            total_lag: partitions.iter().map(|p| p.lag).sum(),
+            max_partition_lag: partitions.iter().map(|p| p.lag).max().unwrap_or(0),
+            partitions,
+        }
+    }
+}
+
+/// Message given up on after its handler kept failing, as published to
+/// the dead-letter topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterMessage {
+    /// Dead letter ID, used to replay it
+    pub dead_letter_id: Uuid,
+    
+    /// Handler that failed
+    pub handler: String,
+    
+    /// Topic the message was consumed from
+    pub topic: String,
+    
+    /// Partition the message was consumed from
+    pub partition: i32,
+    
+    /// Offset of the message
+    pub offset: i64,
+    
+    /// Raw message payload
+    pub payload: Vec<u8>,
+    
+    /// Last handler error
+    pub error: String,
+    
+    /// Handler attempts made so far, including replays
+    pub attempts: u32,
+    
+    /// When the message was dead-lettered
+    pub failed_at: DateTime<Utc>,
+    
+    /// When a replay last succeeded
+    pub replayed_at: Option<DateTime<Utc>>,
+}
+
+/// Dead letters kept in domain metadata so they can be replayed after a restart
+pub struct DeadLetterStore {
+    storage: Arc<dyn StorageBackend>,
+}
+
+impl DeadLetterStore {
+    pub fn new(storage: Arc<dyn StorageBackend>) -> Self {
+        Self { storage }
+    }
+    
+    fn key(dead_letter_id: &Uuid) -> String {
+        format!("kafka:dead_letter:{}", dead_letter_id)
+    }
+    
+    pub async fn save(&self, dead_letter: &DeadLetterMessage) -> GarpResult<()> {
+        let value = serde_json::to_vec(dead_letter)?;
+        self.storage.put_metadata(&Self::key(&dead_letter.dead_letter_id), &value).await
+    }
+    
+    pub async fn load(&self, dead_letter_id: &Uuid) -> GarpResult<Option<DeadLetterMessage>> {
+        match self.storage.get_metadata(&Self::key(dead_letter_id)).await? {
+            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Handler failure after every attempt was used up
+#[derive(Debug)]
+struct HandlerFailure {
+    error: String,
+    attempts: u32,
+}
+
 /// Kafka client wrapper
 pub struct KafkaClient {
     /// Producer for sending messages
     producer: FutureProducer,
     
     /// Consumer for receiving messages
-    consumer: StreamConsumer,
+    consumer: Arc<StreamConsumer<MetricsContext>>,
     
     /// Configuration
     config: KafkaConfig,
     
+    /// Committed consumer offsets
+    offsets: Arc<OffsetTracker>,
+    
     /// Message handlers
     handlers: Arc<RwLock<HashMap<String, Arc<dyn MessageHandler>>>>,
     
+    /// Messages given up on, for replay
+    dead_letters: Arc<DeadLetterStore>,
+    
     /// Shutdown signal
     shutdown_tx: Option<mpsc::Sender<()>>,
     
@@ -159,6 +406,18 @@ pub struct KafkaMetrics {
     /// Consumer lag
     pub consumer_lag: i64,
     
+    /// Lag of each partition as last measured
+    pub partition_lag: Vec<PartitionLag>,
+    
+    /// When consumer lag was last measured
+    pub lag_measured_at: Option<DateTime<Utc>>,
+    
+    /// Last broker connectivity error, cleared once the broker responds again
+    pub broker_error: Option<String>,
+    
+    /// Messages sent to the dead-letter topic, by handler
+    pub dead_lettered: HashMap<String, u64>,
+    
     /// Last updated
     pub last_updated: DateTime<Utc>,
 }
@@ -168,11 +427,19 @@ struct MetricsContext {
     metrics: Arc<RwLock<KafkaMetrics>>,
 }
 
-impl ClientContext for MetricsContext {}
+impl ClientContext for MetricsContext {
+    fn error(&self, error: KafkaError, reason: &str) {
+        tracing::error!("Kafka client error: {}: {}", error, reason);
+        // Called from librdkafka's thread; skip the update rather than block it
+        if let Ok(mut metrics) = self.metrics.try_write() {
+            metrics.broker_error = Some(format!("{}: {}", error, reason));
+        }
+    }
+}
 
 impl KafkaClient {
     /// Create new Kafka client
-    pub async fn new(config: KafkaConfig) -> GarpResult<Self> {
+    pub async fn new(config: KafkaConfig, storage: Arc<dyn StorageBackend>) -> GarpResult<Self> {
         let metrics = Arc::new(RwLock::new(KafkaMetrics::default()));
         
         // Create producer configuration
@@ -267,33 +534,52 @@ impl KafkaClient {
         
         Ok(Self {
             producer,
-            consumer,
+            consumer: Arc::new(consumer),
             config,
+            offsets: Arc::new(OffsetTracker::new(storage.clone())),
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            dead_letters: Arc::new(DeadLetterStore::new(storage)),
             shutdown_tx: None,
             metrics,
         })
     }
     
-    /// Start the Kafka client
+    /// Topics the domain consumes
+    fn consumed_topics(&self) -> Vec<String> {
+        vec![
+            self.config.topics.transaction_topic.clone(),
+            self.config.topics.consensus_topic.clone(),
+            self.config.topics.participant_topic.clone(),
+            self.config.topics.event_topic.clone(),
+        ]
+    }
+    
+    /// Start the Kafka client, resuming each partition after the last
+    /// message processed before the previous shutdown
     pub async fn start(&mut self) -> GarpResult<()> {
-        // Subscribe to topics
-        let topics = vec![
-            self.config.topics.transaction_topic.as_str(),
-            self.config.topics.consensus_topic.as_str(),
-            self.config.topics.participant_topic.as_str(),
-            self.config.topics.event_topic.as_str(),
-        ];
+        let topics = self.consumed_topics();
+        let topic_refs: Vec<&str> = topics.iter().map(String::as_str).collect();
         
-        self.consumer.subscribe(&topics)?;
+        // Partitions are assigned directly at their committed offsets
+        let positions = self.offsets.start_positions(
+            &topic_refs,
+            self.config.topics.partitions,
+            OffsetResetStrategy::from_config(&self.config.consumer.auto_offset_reset),
+        ).await?;
+        self.consumer.assign(&positions)?;
         
         let (shutdown_tx, mut shutdown_rx) = mpsc::channel(1);
         self.shutdown_tx = Some(shutdown_tx);
         
         // Start message consumption loop
         let consumer = self.consumer.clone();
+        let offsets = self.offsets.clone();
+        let partitions = self.config.topics.partitions;
         let handlers = Arc::clone(&self.handlers);
         let metrics = Arc::clone(&self.metrics);
+        let producer = self.producer.clone();
+        let dead_letters = Arc::clone(&self.dead_letters);
+        let policy = self.config.dead_letter.clone();
         
         tokio::spawn(async move {
             loop {
@@ -301,9 +587,29 @@ impl KafkaClient {
                     message_result = consumer.recv() => {
                         match message_result {
                             Ok(message) => {
-                                if let Err(e) = Self::handle_received_message(&message, &handlers, &metrics).await {
-                                    tracing::error!("Error handling message: {}", e);
+                                let dead_letter = Self::handle_received_message(
+                                    message.topic(),
+                                    message.partition(),
+                                    message.offset(),
+                                    message.payload().unwrap_or_default(),
+                                    &handlers,
+                                    &metrics,
+                                    &policy,
+                                ).await;
+                                // Messages that keep failing are set aside so the partition moves on
+                                if let Some(dead_letter) = dead_letter {
+                                    Self::record_dead_letter(&dead_letters, &metrics, &dead_letter).await;
+                                    Self::publish_dead_letter(&producer, &policy.topic, &dead_letter).await;
                                 }
+                                if let Err(e) = offsets.commit(message.topic(), message.partition(), message.offset()).await {
+                                    tracing::error!(
+                                        "Failed to commit offset {} of {}/{}: {}",
+                                        message.offset(), message.topic(), message.partition(), e
+                                    );
+                                }
+                            }
+                            Err(KafkaError::MessageConsumption(RDKafkaErrorCode::OffsetOutOfRange)) => {
+                                Self::recover_out_of_range(&consumer, &offsets, &topics, partitions).await;
                             }
                             Err(e) => {
                                 tracing::error!("Error receiving message: {}", e);
@@ -339,6 +645,109 @@ impl KafkaClient {
         Ok(())
     }
     
+    /// Re-seek to the earliest retained message every partition whose
+    /// committed offset is no longer retained by the broker
+    async fn recover_out_of_range(
+        consumer: &Arc<StreamConsumer<MetricsContext>>,
+        offsets: &Arc<OffsetTracker>,
+        topics: &[String],
+        partitions: i32,
+    ) {
+        for topic in topics {
+            for partition in 0..partitions {
+                let next_offset = match offsets.next_offset(topic, partition).await {
+                    Ok(Some(next_offset)) => next_offset,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::error!("Failed to read offset of {}/{}: {}", topic, partition, e);
+                        continue;
+                    }
+                };
+                let (low, high) = match tokio::task::block_in_place(|| {
+                    consumer.fetch_watermarks(topic, partition, OFFSET_REQUEST_TIMEOUT)
+                }) {
+                    Ok(watermarks) => watermarks,
+                    Err(e) => {
+                        tracing::error!("Failed to fetch watermarks of {}/{}: {}", topic, partition, e);
+                        continue;
+                    }
+                };
+                if OffsetTracker::in_range(next_offset, low, high) {
+                    continue;
+                }
+                
+                tracing::warn!(
+                    "Offset {} of {}/{} is outside the retained range {}..{}; re-seeking to earliest",
+                    next_offset, topic, partition, low, high
+                );
+                if let Err(e) = consumer.seek(topic, partition, Offset::Offset(low), OFFSET_REQUEST_TIMEOUT) {
+                    tracing::error!("Failed to seek {}/{}: {}", topic, partition, e);
+                    continue;
+                }
+                if let Err(e) = offsets.store(topic, partition, low).await {
+                    tracing::error!("Failed to store offset of {}/{}: {}", topic, partition, e);
+                }
+            }
+        }
+    }
+    
+    /// Reposition a partition at its earliest or latest offset and record
+    /// the new position so a restart resumes from it
+    pub async fn reset_offsets(&self, topic: &str, partition: i32, strategy: OffsetResetStrategy) -> GarpResult<i64> {
+        let (low, high) = tokio::task::block_in_place(|| {
+            self.consumer.fetch_watermarks(topic, partition, OFFSET_REQUEST_TIMEOUT)
+        })?;
+        let next_offset = match strategy {
+            OffsetResetStrategy::Earliest => low,
+            OffsetResetStrategy::Latest => high,
+        };
+        
+        // Only partitions already assigned can be seeked
+        let assigned = self.consumer.assignment()?
+            .find_partition(topic, partition)
+            .is_some();
+        if assigned {
+            self.consumer.seek(topic, partition, Offset::Offset(next_offset), OFFSET_REQUEST_TIMEOUT)?;
+        }
+        self.offsets.store(topic, partition, next_offset).await?;
+        
+        tracing::info!("Reset offset of {}/{} to {} ({:?})", topic, partition, next_offset, strategy);
+        Ok(next_offset)
+    }
+    
+    /// Measure the lag of every consumed partition, its committed offset
+    /// against the broker's high watermark, and record it in the metrics.
+    /// Fails with `Unavailable` when the broker cannot be reached.
+    pub async fn get_consumer_lag(&self) -> GarpResult<ConsumerLag> {
+        let mut partitions = Vec::new();
+        for topic in self.consumed_topics() {
+            for partition in 0..self.config.topics.partitions as i32 {
+                let watermarks = tokio::task::block_in_place(|| {
+                    self.consumer.fetch_watermarks(&topic, partition, OFFSET_REQUEST_TIMEOUT)
+                });
+                let (low, high) = match watermarks {
+                    Ok(watermarks) => watermarks,
+                    Err(e) => {
+                        self.metrics.write().await.broker_error = Some(e.to_string());
+                        return Err(GarpError::Unavailable(format!(
+                            "Failed to fetch watermarks of {}/{}: {}", topic, partition, e
+                        )));
+                    }
+                };
+                let committed_offset = self.offsets.next_offset(&topic, partition).await?;
+                partitions.push(PartitionLag::new(&topic, partition, committed_offset, low, high));
+            }
+        }
+        
+        let lag = ConsumerLag::from_partitions(partitions);
+        let mut metrics = self.metrics.write().await;
+        metrics.broker_error = None;
+        metrics.consumer_lag = lag.total_lag;
+        metrics.partition_lag = lag.partitions.clone();
+        metrics.lag_measured_at = Some(Utc::now());
+        Ok(lag)
+    }
+    
     /// Stop the Kafka client
     pub async fn stop(&mut self) -> GarpResult<()> {
         if let Some(shutdown_tx) = self.shutdown_tx.take() {
@@ -366,13 +775,14 @@ impl KafkaClient {
         let send_latency = start_time.elapsed();
         let mut metrics = self.metrics.write().await;
         
-        match result {
+        match &result {
             Ok(_) => {
                 metrics.messages_sent += 1;
                 metrics.avg_send_latency = (metrics.avg_send_latency + send_latency) / 2;
             }
-            Err(_) => {
+            Err((e, _)) => {
                 metrics.messages_failed += 1;
+                metrics.broker_error = Some(e.to_string());
             }
         }
         
@@ -403,6 +813,7 @@ impl KafkaClient {
         match message {
             KafkaMessage::TransactionSubmitted { transaction_id, .. } => transaction_id.clone(),
             KafkaMessage::TransactionSequenced { transaction_id, .. } => transaction_id.clone(),
+            KafkaMessage::TransactionFailed { transaction_id, .. } => transaction_id.clone(),
             KafkaMessage::ConsensusVote { transaction_id, .. } => transaction_id.clone(),
             KafkaMessage::ConsensusResult { transaction_id, .. } => transaction_id.clone(),
             KafkaMessage::ParticipantJoined { participant_id, .. } => participant_id.clone(),
@@ -413,28 +824,54 @@ impl KafkaClient {
         }
     }
     
-    /// Handle received message
+    /// Dispatch a received message to its handler, retrying failures.
+    /// Returns the dead letter to publish if the handler never succeeded.
+    #[allow(clippy::too_many_arguments)]
     async fn handle_received_message(
-        message: &BorrowedMessage<'_>,
+        topic: &str,
+        partition: i32,
+        offset: i64,
+        payload: &[u8],
         handlers: &Arc<RwLock<HashMap<String, Arc<dyn MessageHandler>>>>,
         metrics: &Arc<RwLock<KafkaMetrics>>,
-    ) -> GarpResult<()> {
+        policy: &DeadLetterConfig,
+    ) -> Option<DeadLetterMessage> {
         let start_time = std::time::Instant::now();
+        let dead_letter = |handler: String, failure: HandlerFailure| DeadLetterMessage {
+            dead_letter_id: Uuid::new_v4(),
+            handler,
+            topic: topic.to_string(),
+            partition,
+            offset,
+            payload: payload.to_vec(),
+            error: failure.error,
+            attempts: failure.attempts,
+            failed_at: Utc::now(),
+            replayed_at: None,
+        };
         
-        // Deserialize message
-        let payload = message.payload().ok_or_else(|| anyhow::anyhow!("Empty message payload"))?;
-        let kafka_message: KafkaMessage = serde_json::from_slice(payload)?;
+        // A payload that cannot be decoded fails the same way every time
+        let kafka_message: KafkaMessage = match serde_json::from_slice(payload) {
+            Ok(kafka_message) => kafka_message,
+            Err(e) => {
+                let mut metrics = metrics.write().await;
+                metrics.messages_received += 1;
+                metrics.messages_failed += 1;
+                let failure = HandlerFailure { error: format!("Failed to decode message: {}", e), attempts: 0 };
+                return Some(dead_letter(UNDECODABLE_HANDLER.to_string(), failure));
+            }
+        };
         
         // Get appropriate handler
-        let handlers = handlers.read().await;
         let handler_name = Self::get_handler_name(&kafka_message);
-        
-        if let Some(handler) = handlers.get(&handler_name) {
-            // Handle message
-            handler.handle_message(kafka_message).await?;
-        } else {
-            tracing::warn!("No handler found for message type: {}", handler_name);
-        }
+        let handler = handlers.read().await.get(&handler_name).cloned();
+        let outcome = match handler {
+            Some(handler) => Self::run_handler(&handler, &kafka_message, policy).await,
+            None => {
+                tracing::warn!("No handler found for message type: {}", handler_name);
+                Ok(())
+            }
+        };
         
         // Update metrics
         let receive_latency = start_time.elapsed();
@@ -442,7 +879,106 @@ impl KafkaClient {
         metrics.messages_received += 1;
         metrics.avg_receive_latency = (metrics.avg_receive_latency + receive_latency) / 2;
         
-        Ok(())
+        outcome.err().map(|failure| {
+            metrics.messages_failed += 1;
+            tracing::error!(
+                handler = %handler_name, attempts = failure.attempts,
+                "Dead-lettering message at {}/{}:{}: {}", topic, partition, offset, failure.error
+            );
+            dead_letter(handler_name, failure)
+        })
+    }
+    
+    /// Run a handler on a message, retrying errors and panics with
+    /// exponential backoff until `max_attempts` are used up. Each attempt
+    /// runs in its own task so a panic cannot take down the consumer.
+    async fn run_handler(
+        handler: &Arc<dyn MessageHandler>,
+        message: &KafkaMessage,
+        policy: &DeadLetterConfig,
+    ) -> Result<(), HandlerFailure> {
+        let max_attempts = policy.max_attempts.max(1);
+        let mut backoff = Duration::from_millis(policy.initial_backoff_ms);
+        let mut attempt = 1;
+        loop {
+            let task = tokio::spawn({
+                let handler = handler.clone();
+                let message = message.clone();
+                async move { handler.handle_message(message).await }
+            });
+            let error = match task.await {
+                Ok(Ok(())) => return Ok(()),
+                Ok(Err(e)) => e.to_string(),
+                Err(e) if e.is_panic() => format!("Handler panicked: {}", panic_message(e.into_panic())),
+                Err(e) => format!("Handler task failed: {}", e),
+            };
+            if attempt >= max_attempts {
+                return Err(HandlerFailure { error, attempts: attempt });
+            }
+            
+            tracing::warn!(
+                handler = handler.name(), attempt,
+                "Handler failed, retrying in {:?}: {}", backoff, error
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_millis(policy.max_backoff_ms));
+            attempt += 1;
+        }
+    }
+    
+    /// Keep a dead letter for replay and count it against its handler
+    async fn record_dead_letter(
+        dead_letters: &Arc<DeadLetterStore>,
+        metrics: &Arc<RwLock<KafkaMetrics>>,
+        dead_letter: &DeadLetterMessage,
+    ) {
+        if let Err(e) = dead_letters.save(dead_letter).await {
+            tracing::error!("Failed to store dead letter {}: {}", dead_letter.dead_letter_id, e);
+        }
+        let mut metrics = metrics.write().await;
+        *metrics.dead_lettered.entry(dead_letter.handler.clone()).or_insert(0) += 1;
+    }
+    
+    /// Publish a dead letter to the dead-letter topic
+    async fn publish_dead_letter(producer: &FutureProducer, topic: &str, dead_letter: &DeadLetterMessage) {
+        let payload = match serde_json::to_vec(dead_letter) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to encode dead letter {}: {}", dead_letter.dead_letter_id, e);
+                return;
+            }
+        };
+        let key = dead_letter.dead_letter_id.to_string();
+        let record = FutureRecord::to(topic).payload(&payload).key(&key);
+        if let Err((e, _)) = producer.send(record, Timeout::After(Duration::from_secs(30))).await {
+            tracing::error!("Failed to publish dead letter {} to {}: {}", dead_letter.dead_letter_id, topic, e);
+        }
+    }
+    
+    /// Run a dead-lettered message through its handler again, with the
+    /// usual retries. The dead letter records the outcome either way.
+    pub async fn replay_dead_letter(&self, dead_letter_id: Uuid) -> GarpResult<()> {
+        let mut dead_letter = self.dead_letters.load(&dead_letter_id).await?
+            .ok_or_else(|| GarpError::NotFound(format!("Dead letter {}", dead_letter_id)))?;
+        let message: KafkaMessage = serde_json::from_slice(&dead_letter.payload)?;
+        let handler = self.handlers.read().await.get(&dead_letter.handler).cloned()
+            .ok_or_else(|| GarpError::NotFound(format!("Message handler {}", dead_letter.handler)))?;
+        
+        let outcome = Self::run_handler(&handler, &message, &self.config.dead_letter).await;
+        let result = match outcome {
+            Ok(()) => {
+                dead_letter.replayed_at = Some(Utc::now());
+                Ok(())
+            }
+            Err(failure) => {
+                dead_letter.attempts += failure.attempts;
+                dead_letter.error = failure.error.clone();
+                Err(GarpError::InternalError(format!("Replay of dead letter {} failed: {}", dead_letter_id, failure.error)))
+            }
+        };
+        self.dead_letters.save(&dead_letter).await?;
+        tracing::info!(handler = %dead_letter.handler, replayed = result.is_ok(), "Replayed dead letter {}", dead_letter_id);
+        result
     }
     
     /// Get handler name for message type
@@ -450,6 +986,7 @@ impl KafkaClient {
         match message {
             KafkaMessage::TransactionSubmitted { .. } => "transaction_handler".to_string(),
             KafkaMessage::TransactionSequenced { .. } => "sequencer_handler".to_string(),
+            KafkaMessage::TransactionFailed { .. } => "transaction_handler".to_string(),
             KafkaMessage::ConsensusVote { .. } => "consensus_handler".to_string(),
             KafkaMessage::ConsensusResult { .. } => "consensus_handler".to_string(),
             KafkaMessage::ParticipantJoined { .. } => "participant_handler".to_string(),
@@ -467,6 +1004,7 @@ impl KafkaClient {
         encrypted_data: Vec<u8>,
         participants: Vec<ParticipantId>,
         domain_id: String,
+        metadata: HashMap<String, String>,
     ) -> GarpResult<()> {
         let message = KafkaMessage::TransactionSubmitted {
             transaction_id,
@@ -474,6 +1012,7 @@ impl KafkaClient {
             participants,
             domain_id,
             timestamp: Utc::now(),
+            metadata,
         };
         
         self.send_message(&self.config.topics.transaction_topic, &message).await
@@ -495,6 +1034,23 @@ impl KafkaClient {
         self.send_message(&self.config.topics.transaction_topic, &message).await
     }
     
+    /// Send transaction failed message, telling the submitter why
+    pub async fn send_transaction_failed(
+        &self,
+        transaction_id: TransactionId,
+        domain_id: String,
+        reason: FailureReason,
+    ) -> GarpResult<()> {
+        let message = KafkaMessage::TransactionFailed {
+            transaction_id,
+            domain_id,
+            reason,
+            timestamp: Utc::now(),
+        };
+        
+        self.send_message(&self.config.topics.transaction_topic, &message).await
+    }
+    
     /// Send consensus vote message
     pub async fn send_consensus_vote(
         &self,
@@ -614,6 +1170,74 @@ impl KafkaClient {
     }
 }
 
+/// Routes exposing consumer lag and broker connectivity; merged into the
+/// domain API router
+pub fn router(kafka_client: Arc<KafkaClient>) -> Router {
+    Router::new()
+        .route("/api/v1/kafka/lag", get(consumer_lag))
+        .route("/metrics", get(prometheus_metrics))
+        .with_state(kafka_client)
+}
+
+/// Consumer lag response
+#[derive(Debug, Serialize)]
+struct ConsumerLagResponse {
+    #[serde(flatten)]
+    lag: ConsumerLag,
+    measured_at: Option<DateTime<Utc>>,
+    broker_reachable: bool,
+    broker_error: Option<String>,
+}
+
+/// Consumer lag as last measured by the health checker
+async fn consumer_lag(State(kafka_client): State<Arc<KafkaClient>>) -> Json<ConsumerLagResponse> {
+    let metrics = kafka_client.get_metrics().await;
+    Json(ConsumerLagResponse {
+        lag: ConsumerLag::from_partitions(metrics.partition_lag),
+        measured_at: metrics.lag_measured_at,
+        broker_reachable: metrics.broker_error.is_none(),
+        broker_error: metrics.broker_error,
+    })
+}
+
+/// Kafka metrics in the Prometheus text format
+async fn prometheus_metrics(State(kafka_client): State<Arc<KafkaClient>>) -> (StatusCode, String) {
+    let metrics = kafka_client.get_metrics().await;
+    (StatusCode::OK, render_prometheus(&metrics))
+}
+
+fn render_prometheus(metrics: &KafkaMetrics) -> String {
+    let mut out = String::new();
+    out.push_str("# TYPE garp_kafka_consumer_lag gauge\n");
+    for p in &metrics.partition_lag {
+        out.push_str(&format!(
+            "garp_kafka_consumer_lag{{topic=\"{}\",partition=\"{}\"}} {}\n", p.topic, p.partition, p.lag
+        ));
+    }
+    out.push_str("# TYPE garp_kafka_consumer_lag_total gauge\n");
+    out.push_str(&format!("garp_kafka_consumer_lag_total {}\n", metrics.consumer_lag));
+    out.push_str("# TYPE garp_kafka_broker_reachable gauge\n");
+    out.push_str(&format!("garp_kafka_broker_reachable {}\n", u8::from(metrics.broker_error.is_none())));
+    out.push_str("# TYPE garp_kafka_messages_received_total counter\n");
+    out.push_str(&format!("garp_kafka_messages_received_total {}\n", metrics.messages_received));
+    out.push_str("# TYPE garp_kafka_messages_failed_total counter\n");
+    out.push_str(&format!("garp_kafka_messages_failed_total {}\n", metrics.messages_failed));
+    out.push_str("# TYPE garp_kafka_dead_lettered_total counter\n");
+    let mut dead_lettered: Vec<_> = metrics.dead_lettered.iter().collect();
+    dead_lettered.sort();
+    for (handler, count) in dead_lettered {
+        out.push_str(&format!("garp_kafka_dead_lettered_total{{handler=\"{}\"}} {}\n", handler, count));
+    }
+    out
+}
+
+/// Text of a panic payload, when it has one
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload.downcast_ref::<&str>().map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
 /// Topic manager for creating and managing Kafka topics
 pub struct TopicManager {
     config: KafkaConfig,
@@ -634,6 +1258,7 @@ impl TopicManager {
         tracing::info!("  - {}", self.config.topics.consensus_topic);
         tracing::info!("  - {}", self.config.topics.participant_topic);
         tracing::info!("  - {}", self.config.topics.event_topic);
+        tracing::info!("  - {}", self.config.dead_letter.topic);
         
         Ok(())
     }
@@ -642,6 +1267,7 @@ impl TopicManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::MemoryStorage;
     use std::sync::atomic::{AtomicUsize, Ordering};
     
     struct TestHandler {
@@ -677,7 +1303,7 @@ mod tests {
     #[tokio::test]
     async fn test_message_key_generation() {
         let config = KafkaConfig::default();
-        let client = KafkaClient::new(config).await.unwrap();
+        let client = KafkaClient::new(config, Arc::new(MemoryStorage::new())).await.unwrap();
         
         let message = KafkaMessage::TransactionSubmitted {
             transaction_id: "test-tx-1".to_string(),
@@ -685,12 +1311,80 @@ mod tests {
             participants: vec!["participant-1".to_string()],
             domain_id: "test-domain".to_string(),
             timestamp: Utc::now(),
+            metadata: HashMap::new(),
         };
         
         let key = client.generate_message_key(&message);
         assert_eq!(key, "test-tx-1");
     }
     
+    #[tokio::test]
+    async fn test_offsets_resume_after_crash_mid_batch() {
+        let storage: Arc<dyn StorageBackend> = Arc::new(MemoryStorage::new());
+        let topic = "garp-transactions";
+        
+        // Five messages arrive on partition 1; the domain crashes after processing three
+        {
+            let offsets = OffsetTracker::new(storage.clone());
+            for offset in 0..5 {
+                if offset == 3 {
+                    break;
+                }
+                offsets.commit(topic, 1, offset).await.unwrap();
+            }
+        }
+        assert_eq!(storage.get_metadata("kafka:offset:garp-transactions:1").await.unwrap(), Some(b"3".to_vec()));
+        
+        // The restarted domain resumes at the first unprocessed message
+        let offsets = OffsetTracker::new(storage);
+        let positions = offsets.start_positions(&[topic, "garp-events"], 3, OffsetResetStrategy::Earliest).await.unwrap();
+        assert_eq!(positions.count(), 6);
+        assert_eq!(positions.find_partition(topic, 1).unwrap().offset(), Offset::Offset(3));
+        assert_eq!(positions.find_partition(topic, 0).unwrap().offset(), Offset::Beginning);
+        assert_eq!(positions.find_partition("garp-events", 1).unwrap().offset(), Offset::Beginning);
+        
+        // Administrative resets move the stored position
+        offsets.store(topic, 1, 42).await.unwrap();
+        assert_eq!(offsets.next_offset(topic, 1).await.unwrap(), Some(42));
+    }
+    
+    #[test]
+    fn test_offset_range_check() {
+        assert!(OffsetTracker::in_range(10, 5, 20));
+        assert!(OffsetTracker::in_range(20, 5, 20));
+        assert!(!OffsetTracker::in_range(3, 5, 20));
+        assert!(!OffsetTracker::in_range(21, 5, 20));
+        assert_eq!(OffsetResetStrategy::from_config("earliest"), OffsetResetStrategy::Earliest);
+        assert_eq!(OffsetResetStrategy::from_config("latest"), OffsetResetStrategy::Latest);
+    }
+    
+    #[test]
+    fn test_consumer_lag_against_high_watermark() {
+        let lag = ConsumerLag::from_partitions(vec![
+            PartitionLag::new("garp-transactions", 0, Some(90), 0, 100),
+            // Nothing committed yet: every retained message is behind
+            PartitionLag::new("garp-transactions", 1, None, 40, 100),
+            // Committed offset fell out of retention
+            PartitionLag::new("garp-transactions", 2, Some(10), 50, 100),
+            PartitionLag::new("garp-events", 0, Some(100), 0, 100),
+        ]);
+        let lags: Vec<i64> = lag.partitions.iter().map(|p| p.lag).collect();
+        assert_eq!(lags, vec![10, 60, 50, 0]);
+        assert_eq!(lag.total_lag, 120);
+        assert_eq!(lag.max_partition_lag, 60);
+        
+        let metrics = KafkaMetrics {
+            consumer_lag: lag.total_lag,
+            partition_lag: lag.partitions,
+            broker_error: Some("all brokers down".to_string()),
+            ..Default::default()
+        };
+        let text = render_prometheus(&metrics);
+        assert!(text.contains("garp_kafka_consumer_lag{topic=\"garp-transactions\",partition=\"1\"} 60\n"));
+        assert!(text.contains("garp_kafka_consumer_lag_total 120\n"));
+        assert!(text.contains("garp_kafka_broker_reachable 0\n"));
+    }
+    
     #[test]
     fn test_handler_name_mapping() {
         let message = KafkaMessage::ConsensusVote {
@@ -704,6 +1398,130 @@ mod tests {
         
         let handler_name = KafkaClient::get_handler_name(&message);
         assert_eq!(handler_name, "consensus_handler");
+    }    
+    /// Fails its first `failures` calls, or every call while `broken` is set
+    struct FlakyHandler {
+        failures: usize,
+        broken: std::sync::atomic::AtomicBool,
+        panics: bool,
+        calls: AtomicUsize,
+    }
+    
+    impl FlakyHandler {
+        fn new(failures: usize) -> Arc<Self> {
+            Arc::new(Self { failures, broken: false.into(), panics: false, calls: AtomicUsize::new(0) })
+        }
+        
+        fn broken(panics: bool) -> Arc<Self> {
+            Arc::new(Self { failures: 0, broken: true.into(), panics, calls: AtomicUsize::new(0) })
+        }
+    }
+    
+    #[async_trait]
+    impl MessageHandler for FlakyHandler {
+        async fn handle_message(&self, _message: KafkaMessage) -> GarpResult<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.broken.load(Ordering::SeqCst) && self.panics {
+                panic!("handler bug");
+            }
+            if self.broken.load(Ordering::SeqCst) || call < self.failures {
+                return Err(GarpError::Unavailable("downstream unavailable".to_string()));
+            }
+            Ok(())
+        }
+        
+        fn name(&self) -> &str {
+            "transaction_handler"
+        }
+    }
+    
+    fn fast_retries() -> KafkaConfig {
+        let mut config = KafkaConfig::default();
+        config.dead_letter.initial_backoff_ms = 1;
+        config.dead_letter.max_backoff_ms = 2;
+        config
+    }
+    
+    fn submitted_payload() -> Vec<u8> {
+        serde_json::to_vec(&KafkaMessage::TransactionSubmitted {
+            transaction_id: "test-tx-1".to_string(),
+            encrypted_data: vec![1, 2, 3],
+            participants: vec!["participant-1".to_string()],
+            domain_id: "test-domain".to_string(),
+            timestamp: Utc::now(),
+            metadata: HashMap::new(),
+        }).unwrap()
+    }
+    
+    async fn deliver(client: &KafkaClient, offset: i64, payload: &[u8]) -> Option<DeadLetterMessage> {
+        let dead_letter = KafkaClient::handle_received_message(
+            "garp-transactions", 0, offset, payload, &client.handlers, &client.metrics, &client.config.dead_letter,
+        ).await;
+        if let Some(dead_letter) = &dead_letter {
+            KafkaClient::record_dead_letter(&client.dead_letters, &client.metrics, dead_letter).await;
+        }
+        dead_letter
+    }
+    
+    #[tokio::test]
+    async fn test_handler_succeeding_on_retry_is_not_dead_lettered() {
+        let client = KafkaClient::new(fast_retries(), Arc::new(MemoryStorage::new())).await.unwrap();
+        let handler = FlakyHandler::new(2);
+        client.register_handler(handler.clone()).await;
+        
+        assert!(deliver(&client, 7, &submitted_payload()).await.is_none());
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+        
+        let metrics = client.get_metrics().await;
+        assert_eq!(metrics.messages_failed, 0);
+        assert!(metrics.dead_lettered.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_failing_handler_is_dead_lettered_and_replayable() {
+        let client = KafkaClient::new(fast_retries(), Arc::new(MemoryStorage::new())).await.unwrap();
+        let handler = FlakyHandler::broken(false);
+        client.register_handler(handler.clone()).await;
+        
+        let dead_letter = deliver(&client, 7, &submitted_payload()).await.expect("message was dead-lettered");
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(dead_letter.handler, "transaction_handler");
+        assert_eq!(dead_letter.attempts, 3);
+        assert_eq!(dead_letter.offset, 7);
+        assert!(dead_letter.error.contains("downstream unavailable"));
+        assert_eq!(client.get_metrics().await.dead_lettered.get("transaction_handler"), Some(&1));
+        
+        // Still broken: the replay fails and the attempts add up
+        assert!(client.replay_dead_letter(dead_letter.dead_letter_id).await.is_err());
+        let stored = client.dead_letters.load(&dead_letter.dead_letter_id).await.unwrap().unwrap();
+        assert_eq!(stored.attempts, 6);
+        assert!(stored.replayed_at.is_none());
+        
+        handler.broken.store(false, Ordering::SeqCst);
+        client.replay_dead_letter(dead_letter.dead_letter_id).await.unwrap();
+        let stored = client.dead_letters.load(&dead_letter.dead_letter_id).await.unwrap().unwrap();
+        assert!(stored.replayed_at.is_some());
+        
+        assert!(matches!(client.replay_dead_letter(Uuid::new_v4()).await, Err(GarpError::NotFound(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_poison_messages_do_not_stop_the_consumer() {
+        let client = KafkaClient::new(fast_retries(), Arc::new(MemoryStorage::new())).await.unwrap();
+        let handler = FlakyHandler::broken(true);
+        client.register_handler(handler.clone()).await;
+        
+        let dead_letter = deliver(&client, 1, &submitted_payload()).await.expect("panicking handler was dead-lettered");
+        assert_eq!(dead_letter.attempts, 3);
+        assert!(dead_letter.error.contains("handler bug"));
+        
+        let dead_letter = deliver(&client, 2, b"not json").await.expect("undecodable payload was dead-lettered");
+        assert_eq!(dead_letter.handler, UNDECODABLE_HANDLER);
+        assert_eq!(dead_letter.payload, b"not json");
+        
+        let metrics = client.get_metrics().await;
+        assert_eq!(metrics.dead_lettered.get("transaction_handler"), Some(&1));
+        assert_eq!(metrics.dead_lettered.get(UNDECODABLE_HANDLER), Some(&1));
     }
 }
 
@@ -745,6 +1563,7 @@ impl Default for KafkaConfig {
                 fetch_min_bytes: 1,
                 fetch_max_wait_ms: 500,
             },
+            dead_letter: crate::config::DeadLetterConfig::default(),
         }
     }
 }
\ No newline at end of file
@@ -6,4 +6,7 @@ pub mod storage;
 pub mod api;
 pub mod consensus;
 pub mod mediator;
-pub mod vector_clock;
\ No newline at end of file
+pub mod escalation;
+pub mod reputation;
+pub mod vector_clock;
+pub mod event_log;
\ No newline at end of file
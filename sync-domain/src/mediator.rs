@@ -6,12 +6,20 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
-use garp_common::{GarpResult, TransactionId, ParticipantId, ContractId};
-use crate::config::MediatorConfig;
+use garp_common::{GarpError, GarpResult, TransactionId, ParticipantId, ContractId, ReputationSignal, Signature, SignatureSet, TransactionError};
+use crate::config::{EscalationConfig, MediatorConfig};
+use crate::escalation::{Escalation, RoundPhase, TimeoutEscalator};
+use crate::reputation::ParticipantReputation;
 use crate::storage::{StorageBackend, SequencedTransaction};
 use crate::kafka::{KafkaClient, MessageHandler, KafkaMessage};
 use crate::consensus::{ConsensusManager, ConsensusSession};
 
+/// Transaction metadata key naming the approval policy, as JSON
+pub const APPROVAL_POLICY_METADATA_KEY: &str = "approval_policy";
+
+/// Storage metadata key prefix of persisted mediation sessions
+const SESSION_KEY_PREFIX: &str = "mediation:session:";
+
 /// Transaction mediator for ensuring consent and coordination
 pub struct TransactionMediator {
     /// Configuration
@@ -35,6 +43,12 @@ pub struct TransactionMediator {
     /// Contract registry
     contracts: Arc<RwLock<HashMap<ContractId, ContractInfo>>>,
     
+    /// Retries of timed-out mediations
+    escalator: Arc<TimeoutEscalator>,
+    
+    /// Reputations of the participants, if tracked
+    reputation: Option<Arc<ParticipantReputation>>,
+    
     /// Shutdown signal
     shutdown_tx: Option<mpsc::Sender<()>>,
     
@@ -43,7 +57,7 @@ pub struct TransactionMediator {
 }
 
 /// Mediation session for a transaction
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediationSession {
     /// Transaction ID
     pub transaction_id: TransactionId,
@@ -57,6 +71,10 @@ pub struct MediationSession {
     /// Received consents
     pub consents: HashMap<ParticipantId, ConsentInfo>,
     
+    /// Verified signatures of the received consents
+    #[serde(default)]
+    pub consent_signatures: SignatureSet,
+    
     /// Affected contracts
     pub affected_contracts: HashSet<ContractId>,
     
@@ -80,6 +98,152 @@ pub struct MediationSession {
     
     /// Priority level
     pub priority: MediationPriority,
+    
+    /// Consents needed to approve the transaction
+    #[serde(default)]
+    pub approval_policy: ApprovalPolicy,
+    
+    /// Participants left out of retries after never responding
+    #[serde(default)]
+    pub excluded_participants: HashSet<ParticipantId>,
+}
+
+/// Which consents approve a transaction. Policies are evaluated as each
+/// consent or rejection arrives, so a session completes as soon as its
+/// policy is satisfied and fails as soon as it no longer can be.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApprovalPolicy {
+    /// Every required participant consents
+    #[default]
+    AllOf,
+    
+    /// At least `n` required participants consent
+    AnyN { n: usize },
+    
+    /// Consenting participants' weights add up to `threshold`. Participants
+    /// without a weight count for nothing.
+    Weighted {
+        weights: HashMap<ParticipantId, u64>,
+        threshold: u64,
+    },
+    
+    /// Every `mandatory` participant consents, and at least `n` of the
+    /// others
+    MandatoryPlusAnyN {
+        mandatory: HashSet<ParticipantId>,
+        n: usize,
+    },
+}
+
+/// Outcome of an approval policy for the consents received so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyOutcome {
+    /// Enough participants consented
+    Satisfied,
+    
+    /// Undecided participants could still satisfy the policy
+    Pending,
+    
+    /// The policy can no longer be satisfied
+    Impossible,
+}
+
+impl ApprovalPolicy {
+    /// Policy named in transaction metadata, if any
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> GarpResult<Option<Self>> {
+        metadata.get(APPROVAL_POLICY_METADATA_KEY)
+            .map(|policy| serde_json::from_str(policy).map_err(|e| {
+                GarpError::ValidationError(format!("Invalid approval policy {}: {}", policy, e))
+            }))
+            .transpose()
+    }
+    
+    /// Evaluate the policy against the consents received from `required`
+    /// participants. Participants outside `required` can never consent.
+    pub fn evaluate(
+        &self,
+        required: &HashSet<ParticipantId>,
+        consents: &HashMap<ParticipantId, ConsentInfo>,
+    ) -> PolicyOutcome {
+        match self {
+            ApprovalPolicy::AllOf => {
+                Self::threshold_outcome(required.iter(), |_| 1, required.len() as u64, required, consents)
+            }
+            ApprovalPolicy::AnyN { n } => {
+                Self::threshold_outcome(required.iter(), |_| 1, *n as u64, required, consents)
+            }
+            ApprovalPolicy::Weighted { weights, threshold } => Self::threshold_outcome(
+                required.iter(),
+                |participant_id| weights.get(participant_id).copied().unwrap_or(0),
+                *threshold,
+                required,
+                consents,
+            ),
+            ApprovalPolicy::MandatoryPlusAnyN { mandatory, n } => {
+                let mandatory_outcome = Self::threshold_outcome(
+                    mandatory.iter(), |_| 1, mandatory.len() as u64, required, consents,
+                );
+                let others_outcome = Self::threshold_outcome(
+                    required.difference(mandatory), |_| 1, *n as u64, required, consents,
+                );
+                match (mandatory_outcome, others_outcome) {
+                    (PolicyOutcome::Satisfied, PolicyOutcome::Satisfied) => PolicyOutcome::Satisfied,
+                    (PolicyOutcome::Impossible, _) | (_, PolicyOutcome::Impossible) => PolicyOutcome::Impossible,
+                    _ => PolicyOutcome::Pending,
+                }
+            }
+        }
+    }
+    
+    /// Whether the policy can still be satisfied if the `absent`
+    /// participants never respond. Leaving anyone out of an `AllOf` policy
+    /// would change what it requires, so it never can.
+    pub fn quorum_without(
+        &self,
+        required: &HashSet<ParticipantId>,
+        consents: &HashMap<ParticipantId, ConsentInfo>,
+        absent: &HashSet<ParticipantId>,
+    ) -> bool {
+        if absent.is_empty() {
+            return true;
+        }
+        if *self == ApprovalPolicy::AllOf {
+            return false;
+        }
+        let present: HashSet<ParticipantId> = required.difference(absent).cloned().collect();
+        self.evaluate(&present, consents) != PolicyOutcome::Impossible
+    }
+    
+    /// Satisfied once the weight of consenting participants reaches
+    /// `threshold`, impossible once that plus the weight of those still
+    /// undecided falls short of it
+    fn threshold_outcome<'a>(
+        participants: impl Iterator<Item = &'a ParticipantId>,
+        weight: impl Fn(&ParticipantId) -> u64,
+        threshold: u64,
+        required: &HashSet<ParticipantId>,
+        consents: &HashMap<ParticipantId, ConsentInfo>,
+    ) -> PolicyOutcome {
+        let mut approved = 0u64;
+        let mut undecided = 0u64;
+        for participant_id in participants {
+            match consents.get(participant_id) {
+                Some(consent) if consent.consent => approved += weight(participant_id),
+                Some(_) => {}
+                None if required.contains(participant_id) => undecided += weight(participant_id),
+                None => {}
+            }
+        }
+        
+        if approved >= threshold {
+            PolicyOutcome::Satisfied
+        } else if approved + undecided < threshold {
+            PolicyOutcome::Impossible
+        } else {
+            PolicyOutcome::Pending
+        }
+    }
 }
 
 /// Consent information from a participant
@@ -94,7 +258,7 @@ pub struct ConsentInfo {
     /// Reason for consent/rejection
     pub reason: Option<String>,
     
-    /// Digital signature
+    /// Hex-encoded Ed25519 signature over [`ConsentInfo::signing_message`]
     pub signature: String,
     
     /// Timestamp
@@ -104,6 +268,13 @@ pub struct ConsentInfo {
     pub conditions: Vec<ConsentCondition>,
 }
 
+impl ConsentInfo {
+    /// Bytes the participant signs, binding the decision to one transaction
+    pub fn signing_message(&self, transaction_id: &TransactionId) -> Vec<u8> {
+        format!("{}:{}:{}:{}", transaction_id, self.participant_id, self.consent, self.timestamp.to_rfc3339()).into_bytes()
+    }
+}
+
 /// Consent condition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsentCondition {
@@ -139,7 +310,7 @@ pub enum MediationStatus {
     /// Waiting for consents
     WaitingForConsent,
     
-    /// All consents received, validating
+    /// Approval policy satisfied, validating consent conditions
     Validating,
     
     /// Mediation successful
@@ -199,7 +370,7 @@ pub struct ParticipantInfo {
     /// Participant ID
     pub participant_id: ParticipantId,
     
-    /// Public key
+    /// Hex-encoded Ed25519 public key consents are verified against
     pub public_key: String,
     
     /// Endpoint
@@ -325,11 +496,26 @@ impl TransactionMediator {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             participants: Arc::new(RwLock::new(HashMap::new())),
             contracts: Arc::new(RwLock::new(HashMap::new())),
+            escalator: Arc::new(TimeoutEscalator::new(EscalationConfig::default())),
+            reputation: None,
             shutdown_tx: None,
             metrics: Arc::new(RwLock::new(MediatorMetrics::default())),
         })
     }
     
+    /// Retry timed-out mediations through `escalator`, shared with the
+    /// consensus manager
+    pub fn with_escalator(mut self, escalator: Arc<TimeoutEscalator>) -> Self {
+        self.escalator = escalator;
+        self
+    }
+    
+    /// Record invalid consent signatures in the participants' reputations
+    pub fn with_reputation(mut self, reputation: Arc<ParticipantReputation>) -> Self {
+        self.reputation = Some(reputation);
+        self
+    }
+    
     /// Start the mediator
     pub async fn start(&mut self) -> GarpResult<()> {
         // Register mediation handler with Kafka
@@ -343,8 +529,11 @@ impl TransactionMediator {
         
         // Start session timeout monitor
         let sessions = Arc::clone(&self.sessions);
+        let storage = Arc::clone(&self.storage);
         let kafka = Arc::clone(&self.kafka);
         let metrics = Arc::clone(&self.metrics);
+        let escalator = Arc::clone(&self.escalator);
+        let round_timeout = chrono::Duration::seconds(self.config.mediation_timeout_seconds as i64);
         
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(10));
@@ -352,7 +541,7 @@ impl TransactionMediator {
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        Self::check_session_timeouts(&sessions, &kafka, &metrics).await;
+                        Self::check_session_timeouts(&sessions, &storage, &kafka, &metrics, &escalator, round_timeout).await;
                     }
                     _ = shutdown_rx.recv() => {
                         break;
@@ -392,7 +581,8 @@ impl TransactionMediator {
         Ok(())
     }
     
-    /// Start mediation for a transaction
+    /// Start mediation for a transaction. Without an approval policy the
+    /// domain's default applies.
     pub async fn start_mediation(
         &self,
         transaction_id: TransactionId,
@@ -401,14 +591,23 @@ impl TransactionMediator {
         affected_contracts: HashSet<ContractId>,
         domain_id: String,
         priority: MediationPriority,
+        approval_policy: Option<ApprovalPolicy>,
     ) -> GarpResult<()> {
         let timeout = Utc::now() + chrono::Duration::seconds(self.config.mediation_timeout_seconds as i64);
+        let approval_policy = approval_policy.unwrap_or_else(|| self.config.approval_policy.clone());
+        if approval_policy.evaluate(&required_participants, &HashMap::new()) == PolicyOutcome::Impossible {
+            return Err(GarpError::ValidationError(format!(
+                "Approval policy {:?} cannot be satisfied by participants of transaction {}",
+                approval_policy, transaction_id
+            )));
+        }
         
         let session = MediationSession {
             transaction_id: transaction_id.clone(),
             encrypted_data,
             required_participants: required_participants.clone(),
             consents: HashMap::new(),
+            consent_signatures: SignatureSet::new(),
             affected_contracts,
             status: MediationStatus::WaitingForConsent,
             created_at: Utc::now(),
@@ -417,12 +616,15 @@ impl TransactionMediator {
             result: None,
             dependencies: HashSet::new(),
             priority,
+            approval_policy,
+            excluded_participants: HashSet::new(),
         };
         
         // Store session
+        Self::persist_session(&self.storage, &session).await?;
         {
             let mut sessions = self.sessions.write().await;
-            sessions.insert(transaction_id.clone(), session);
+            sessions.insert(transaction_id.clone(), session.clone());
         }
         
         // Update metrics
@@ -452,10 +654,7 @@ impl TransactionMediator {
         transaction_id: &TransactionId,
         consent_info: ConsentInfo,
     ) -> GarpResult<()> {
-        // Verify signature
-        if !self.verify_consent_signature(&consent_info).await? {
-            return Err(anyhow::anyhow!("Invalid consent signature"));
-        }
+        let (signature, registered_key) = self.consent_signature(&consent_info).await?;
         
         let mut session_updated = false;
         let mut mediation_complete = false;
@@ -470,20 +669,37 @@ impl TransactionMediator {
                     return Err(anyhow::anyhow!("Participant not required for this mediation"));
                 }
                 
-                // Check if already consented
-                if session.consents.contains_key(&consent_info.participant_id) {
-                    return Err(anyhow::anyhow!("Participant already provided consent"));
+                // Consents arriving after the policy was decided change nothing
+                if session.status != MediationStatus::WaitingForConsent {
+                    return Err(GarpError::StateMismatch(format!(
+                        "Mediation for transaction {} is already {:?}",
+                        transaction_id, session.status
+                    )));
+                }
+                
+                // Verify the signature; a participant consents only once
+                let message = consent_info.signing_message(transaction_id);
+                if let Err(e) = session.consent_signatures.insert_verified(
+                    consent_info.participant_id.clone(), signature, &message, &registered_key,
+                ) {
+                    if let (TransactionError::InvalidSignature(_), Some(reputation)) = (&e, &self.reputation) {
+                        reputation.record(&consent_info.participant_id, ReputationSignal::InvalidSignature).await;
+                    }
+                    return Err(e.into());
                 }
                 
                 // Add consent
                 session.consents.insert(consent_info.participant_id.clone(), consent_info.clone());
                 session_updated = true;
                 
-                // Check if all consents received
-                if session.consents.len() == session.required_participants.len() {
-                    let all_consented = session.consents.values().all(|c| c.consent);
-                    
-                    if all_consented && self.validate_consent_conditions(session).await? {
+                // Check whether the approval policy is decided
+                let outcome = session.approval_policy.evaluate(&session.required_participants, &session.consents);
+                if outcome != PolicyOutcome::Pending {
+                    if outcome == PolicyOutcome::Satisfied {
+                        session.status = MediationStatus::Validating;
+                    }
+                
+                    if outcome == PolicyOutcome::Satisfied && self.validate_consent_conditions(session).await? {
                         session.status = MediationStatus::Approved;
                         mediation_result = Some(MediationResult::Approved {
                             approved_at: Utc::now(),
@@ -496,10 +712,14 @@ impl TransactionMediator {
                             .map(|(id, _)| id.clone())
                             .collect();
                         
-                        let reasons: Vec<String> = session.consents.values()
+                        let mut reasons: Vec<String> = session.consents.values()
                             .filter(|c| !c.consent)
                             .filter_map(|c| c.reason.clone())
                             .collect();
+                        reasons.push(match outcome {
+                            PolicyOutcome::Satisfied => "Consent conditions not met".to_string(),
+                            _ => format!("Approval policy {:?} can no longer be satisfied", session.approval_policy),
+                        });
                         
                         mediation_result = Some(MediationResult::Rejected {
                             rejected_at: Utc::now(),
@@ -515,6 +735,10 @@ impl TransactionMediator {
         }
         
         if session_updated {
+            if let Some(session) = self.sessions.read().await.get(transaction_id) {
+                Self::persist_session(&self.storage, session).await?;
+            }
+            
             // Update metrics
             {
                 let mut metrics = self.metrics.write().await;
@@ -527,6 +751,10 @@ impl TransactionMediator {
             
             // If mediation complete, proceed to consensus or finalization
             if mediation_complete {
+                if let Some(session) = self.sessions.read().await.get(transaction_id) {
+                    self.escalator.on_completed(RoundPhase::Mediation, transaction_id, &session.required_participants).await;
+                }
+                
                 if let Some(result) = mediation_result {
                     match result {
                         MediationResult::Approved { .. } => {
@@ -588,9 +816,26 @@ impl TransactionMediator {
         Ok(())
     }
     
-    /// Get mediation session
+    /// Get mediation session, from storage if it is no longer in memory
     pub async fn get_session(&self, transaction_id: &TransactionId) -> Option<MediationSession> {
-        self.sessions.read().await.get(transaction_id).cloned()
+        if let Some(session) = self.sessions.read().await.get(transaction_id) {
+            return Some(session.clone());
+        }
+        
+        match self.storage.get_metadata(&format!("{}{}", SESSION_KEY_PREFIX, transaction_id)).await {
+            Ok(Some(bytes)) => serde_json::from_slice(&bytes).ok(),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Failed to load mediation session {}: {}", transaction_id, e);
+                None
+            }
+        }
+    }
+    
+    /// Save a session with its policy, consents and status
+    async fn persist_session(storage: &Arc<dyn StorageBackend>, session: &MediationSession) -> GarpResult<()> {
+        let key = format!("{}{}", SESSION_KEY_PREFIX, session.transaction_id);
+        storage.put_metadata(&key, &serde_json::to_vec(session)?).await
     }
     
     /// Get mediator metrics
@@ -605,7 +850,16 @@ impl TransactionMediator {
         participant_id: &ParticipantId,
         domain_id: &str,
     ) -> GarpResult<()> {
-        // Send consent request message via Kafka
+        Self::request_consent(&self.kafka, transaction_id, participant_id, domain_id).await
+    }
+    
+    /// Send consent request message via Kafka
+    async fn request_consent(
+        kafka: &Arc<KafkaClient>,
+        transaction_id: &TransactionId,
+        participant_id: &ParticipantId,
+        domain_id: &str,
+    ) -> GarpResult<()> {
         let event_data = serde_json::json!({
             "type": "consent_request",
             "transaction_id": transaction_id,
@@ -614,7 +868,7 @@ impl TransactionMediator {
             "timestamp": Utc::now()
         });
         
-        self.kafka.send_domain_event(
+        kafka.send_domain_event(
             domain_id.to_string(),
             "consent_request".to_string(),
             event_data,
@@ -649,20 +903,24 @@ impl TransactionMediator {
         ).await
     }
     
-    /// Verify consent signature
-    async fn verify_consent_signature(&self, consent: &ConsentInfo) -> GarpResult<bool> {
-        // Get participant info
+    /// Signature of a consent and the registered key of its participant.
+    /// A signature that is not valid hex is returned empty and fails
+    /// verification.
+    async fn consent_signature(&self, consent: &ConsentInfo) -> GarpResult<(Signature, Vec<u8>)> {
         let participants = self.participants.read().await;
         let participant = participants.get(&consent.participant_id)
-            .ok_or_else(|| anyhow::anyhow!("Unknown participant"))?;
+            .ok_or_else(|| TransactionError::UnknownSigner(consent.participant_id.clone()))?;
+        let registered_key = hex::decode(&participant.public_key)
+            .map_err(|e| GarpError::ValidationError(format!(
+                "Participant {} has an invalid public key: {}", consent.participant_id, e
+            )))?;
         
-        // Create message to verify
-        let message = format!("{}:{}:{}", consent.participant_id, consent.consent, consent.timestamp);
-        
-        // Verify signature (simplified - in production use proper crypto)
-        let expected_signature = format!("consent_sig_{}_{}", participant.public_key, message);
-        
-        Ok(consent.signature == expected_signature)
+        let signature = Signature {
+            algorithm: "Ed25519".to_string(),
+            signature: hex::decode(&consent.signature).unwrap_or_default(),
+            public_key: registered_key.clone(),
+        };
+        Ok((signature, registered_key))
     }
     
     /// Validate consent conditions
@@ -719,11 +977,15 @@ impl TransactionMediator {
         met_conditions
     }
     
-    /// Check session timeouts
+    /// Check session timeouts, retrying timed-out sessions `round_timeout`
+    /// after their backoff until the escalator gives up on them
     async fn check_session_timeouts(
         sessions: &Arc<RwLock<HashMap<TransactionId, MediationSession>>>,
+        storage: &Arc<dyn StorageBackend>,
         kafka: &Arc<KafkaClient>,
         metrics: &Arc<RwLock<MediatorMetrics>>,
+        escalator: &Arc<TimeoutEscalator>,
+        round_timeout: chrono::Duration,
     ) {
         let now = Utc::now();
         let mut timed_out_sessions = Vec::new();
@@ -740,21 +1002,61 @@ impl TransactionMediator {
         
         // Handle timed out sessions
         for (transaction_id, mut session) in timed_out_sessions {
-            session.status = MediationStatus::TimedOut;
-            
-            let missing_consents: Vec<ParticipantId> = session.required_participants
+            let unresponsive: HashSet<ParticipantId> = session.required_participants
                 .difference(&session.consents.keys().cloned().collect())
                 .cloned()
                 .collect();
+            let escalation = escalator.on_timeout(
+                RoundPhase::Mediation,
+                &transaction_id,
+                session.created_at,
+                &session.required_participants,
+                &unresponsive,
+                |absent| session.approval_policy.quorum_without(&session.required_participants, &session.consents, absent),
+            ).await;
+            
+            if let Escalation::Retry { backoff, excluded, .. } = escalation {
+                session.required_participants.retain(|participant_id| !excluded.contains(participant_id));
+                session.excluded_participants.extend(excluded);
+                session.timeout = now + chrono::Duration::from_std(backoff).unwrap_or_else(|_| chrono::Duration::zero()) + round_timeout;
+                
+                if let Err(e) = Self::persist_session(storage, &session).await {
+                    tracing::error!("Failed to persist retried session {}: {}", transaction_id, e);
+                }
+                let missing: Vec<ParticipantId> = session.required_participants.iter()
+                    .filter(|participant_id| !session.consents.contains_key(*participant_id))
+                    .cloned()
+                    .collect();
+                let domain_id = session.domain_id.clone();
+                sessions.write().await.insert(transaction_id.clone(), session);
+                
+                // Ask the participants still missing again once the backoff has passed
+                let kafka = Arc::clone(kafka);
+                tokio::spawn(async move {
+                    tokio::time::sleep(backoff).await;
+                    for participant_id in missing {
+                        if let Err(e) = Self::request_consent(&kafka, &transaction_id, &participant_id, &domain_id).await {
+                            tracing::error!("Failed to resend consent request for {} to {}: {}", transaction_id, participant_id, e);
+                        }
+                    }
+                });
+                continue;
+            }
+            
+            session.status = MediationStatus::TimedOut;
             
             let result = MediationResult::TimedOut {
                 timed_out_at: now,
-                missing_consents,
+                missing_consents: unresponsive.into_iter().collect(),
             };
             
             session.result = Some(result.clone());
             
             // Update session
+            if let Err(e) = Self::persist_session(storage, &session).await {
+                tracing::error!("Failed to persist timed out session {}: {}", transaction_id, e);
+            }
+            let domain_id = session.domain_id.clone();
             {
                 let mut sessions_write = sessions.write().await;
                 sessions_write.insert(transaction_id.clone(), session);
@@ -769,7 +1071,7 @@ impl TransactionMediator {
             });
             
             if let Err(e) = kafka.send_domain_event(
-                "unknown".to_string(), // Would get from session
+                domain_id,
                 "mediation_timeout".to_string(),
                 event_data,
             ).await {
@@ -808,6 +1110,8 @@ impl Clone for TransactionMediator {
             sessions: Arc::clone(&self.sessions),
             participants: Arc::clone(&self.participants),
             contracts: Arc::clone(&self.contracts),
+            escalator: Arc::clone(&self.escalator),
+            reputation: self.reputation.clone(),
             shutdown_tx: None, // Don't clone shutdown channel
             metrics: Arc::clone(&self.metrics),
         }
@@ -833,11 +1137,13 @@ impl MessageHandler for MediationHandler {
                 participants,
                 domain_id,
                 encrypted_data,
+                metadata,
                 ..
             } => {
                 // Start mediation for new transaction
                 let required_participants: HashSet<ParticipantId> = participants.into_iter().collect();
                 let affected_contracts = HashSet::new(); // Would be determined from transaction
+                let approval_policy = ApprovalPolicy::from_metadata(&metadata)?;
                 
                 self.mediator.start_mediation(
                     transaction_id,
@@ -846,6 +1152,7 @@ impl MessageHandler for MediationHandler {
                     affected_contracts,
                     domain_id,
                     MediationPriority::Normal,
+                    approval_policy,
                 ).await?;
             }
             _ => {
@@ -883,6 +1190,7 @@ impl Default for MediatorConfig {
             require_all_signatories: true,
             allow_partial_consent: false,
             consent_cache_ttl_seconds: 3600, // 1 hour
+            approval_policy: ApprovalPolicy::AllOf,
         }
     }
 }
@@ -899,7 +1207,7 @@ mod tests {
         let config = MediatorConfig::default();
         let storage = Arc::new(MemoryStorage::new());
         let kafka_config = KafkaConfig::default();
-        let kafka = Arc::new(KafkaClient::new(kafka_config).await.unwrap());
+        let kafka = Arc::new(KafkaClient::new(kafka_config, storage.clone()).await.unwrap());
         let consensus_config = crate::config::ConsensusConfig::default();
         let consensus = Arc::new(ConsensusManager::new(consensus_config, Arc::clone(&storage), Arc::clone(&kafka)).await.unwrap());
         
@@ -919,6 +1227,7 @@ mod tests {
             affected_contracts,
             domain_id,
             MediationPriority::Normal,
+            None,
         ).await.unwrap();
         
         let session = mediator.get_session(&transaction_id).await;
@@ -935,7 +1244,7 @@ mod tests {
         let config = MediatorConfig::default();
         let storage = Arc::new(MemoryStorage::new());
         let kafka_config = KafkaConfig::default();
-        let kafka = Arc::new(KafkaClient::new(kafka_config).await.unwrap());
+        let kafka = Arc::new(KafkaClient::new(kafka_config, storage.clone()).await.unwrap());
         let consensus_config = crate::config::ConsensusConfig::default();
         let consensus = Arc::new(ConsensusManager::new(consensus_config, Arc::clone(&storage), Arc::clone(&kafka)).await.unwrap());
         
@@ -944,7 +1253,7 @@ mod tests {
         // Register participant
         let participant = ParticipantInfo {
             participant_id: "participant-1".to_string(),
-            public_key: "test-key-1".to_string(),
+            public_key: hex::encode(participant_key("participant-1").verifying_key().to_bytes()),
             endpoint: "http://localhost:8001".to_string(),
             status: ParticipantStatus::Active,
             last_seen: Utc::now(),
@@ -966,22 +1275,216 @@ mod tests {
             affected_contracts,
             domain_id,
             MediationPriority::Normal,
+            None,
         ).await.unwrap();
         
+        // A consent signed for another transaction or by another key is refused
+        let replayed = signed_consent("test-tx-2", "participant-1", true);
+        assert!(matches!(
+            mediator.handle_consent(&transaction_id, replayed).await,
+            Err(GarpError::Transaction(TransactionError::InvalidSignature(_)))
+        ));
+        let mut forged = signed_consent(&transaction_id, "participant-2", true);
+        forged.participant_id = "participant-1".to_string();
+        assert!(mediator.handle_consent(&transaction_id, forged).await.is_err());
+        assert!(mediator.get_session(&transaction_id).await.unwrap().consents.is_empty());
+        
         // Submit consent
-        let consent = ConsentInfo {
-            participant_id: "participant-1".to_string(),
-            consent: true,
-            reason: None,
-            signature: "consent_sig_test-key-1_participant-1:true:2024-01-01T00:00:00Z".to_string(),
+        let consent = signed_consent(&transaction_id, "participant-1", true);
+        mediator.handle_consent(&transaction_id, consent).await.unwrap();
+        
+        let session = mediator.get_session(&transaction_id).await.unwrap();
+        assert_eq!(session.consents.len(), 1);
+        assert_eq!(session.status, MediationStatus::Approved);
+    }
+    
+    async fn mediator_with_participants(participants: &[&str]) -> TransactionMediator {
+        let storage = Arc::new(MemoryStorage::new());
+        let kafka = Arc::new(KafkaClient::new(KafkaConfig::default(), storage.clone()).await.unwrap());
+        let consensus = Arc::new(ConsensusManager::new(crate::config::ConsensusConfig::default(), Arc::clone(&storage), Arc::clone(&kafka)).await.unwrap());
+        let mediator = TransactionMediator::new(MediatorConfig::default(), storage, kafka, consensus).await.unwrap();
+        
+        for participant_id in participants {
+            mediator.register_participant(ParticipantInfo {
+                participant_id: participant_id.to_string(),
+                public_key: hex::encode(participant_key(participant_id).verifying_key().to_bytes()),
+                endpoint: "http://localhost:8001".to_string(),
+                status: ParticipantStatus::Active,
+                last_seen: Utc::now(),
+                consent_preferences: ConsentPreferences::default(),
+            }).await.unwrap();
+        }
+        mediator
+    }
+    
+    async fn start(mediator: &TransactionMediator, transaction_id: &str, participants: &[&str], policy: ApprovalPolicy) {
+        mediator.start_mediation(
+            transaction_id.to_string(),
+            vec![1, 2, 3, 4],
+            participants.iter().map(|p| p.to_string()).collect(),
+            HashSet::new(),
+            "test-domain".to_string(),
+            MediationPriority::Normal,
+            Some(policy),
+        ).await.unwrap();
+    }
+    
+    fn participant_key(participant_id: &str) -> ed25519_dalek::SigningKey {
+        let mut seed = [0u8; 32];
+        let len = participant_id.len().min(32);
+        seed[..len].copy_from_slice(&participant_id.as_bytes()[..len]);
+        ed25519_dalek::SigningKey::from_bytes(&seed)
+    }
+    
+    fn signed_consent(transaction_id: &str, participant_id: &str, consent: bool) -> ConsentInfo {
+        use ed25519_dalek::Signer;
+        let mut info = ConsentInfo {
+            participant_id: participant_id.to_string(),
+            consent,
+            reason: (!consent).then(|| format!("{} declines", participant_id)),
+            signature: String::new(),
             timestamp: Utc::now(),
             conditions: vec![],
         };
+        let signature = participant_key(participant_id).sign(&info.signing_message(&transaction_id.to_string()));
+        info.signature = hex::encode(signature.to_bytes());
+        info
+    }
+    
+    #[tokio::test]
+    async fn test_any_n_policy_completes_early() {
+        let participants = ["alice", "bob", "carol"];
+        let mediator = mediator_with_participants(&participants).await;
+        let transaction_id = "test-tx-any-n".to_string();
+        let policy: ApprovalPolicy = serde_json::from_str(r#"{"type":"any_n","n":2}"#).unwrap();
+        start(&mediator, &transaction_id, &participants, policy.clone()).await;
         
-        mediator.handle_consent(&transaction_id, consent).await.unwrap();
+        mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "alice", true)).await.unwrap();
+        assert_eq!(mediator.get_session(&transaction_id).await.unwrap().status, MediationStatus::WaitingForConsent);
+        mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "bob", true)).await.unwrap();
         
+        // Approved without carol, whose consent now changes nothing
         let session = mediator.get_session(&transaction_id).await.unwrap();
-        assert_eq!(session.consents.len(), 1);
         assert_eq!(session.status, MediationStatus::Approved);
+        assert!(matches!(
+            mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "carol", false)).await,
+            Err(GarpError::StateMismatch(_))
+        ));
+        
+        // The policy is persisted with the session
+        mediator.sessions.write().await.clear();
+        let persisted = mediator.get_session(&transaction_id).await.unwrap();
+        assert_eq!(persisted.approval_policy, policy);
+        assert_eq!(persisted.status, MediationStatus::Approved);
+    }
+    
+    #[tokio::test]
+    async fn test_policy_fails_once_it_cannot_be_satisfied() {
+        let participants = ["issuer", "bob", "carol"];
+        let mediator = mediator_with_participants(&participants).await;
+        let transaction_id = "test-tx-mandatory".to_string();
+        let policy = ApprovalPolicy::MandatoryPlusAnyN {
+            mandatory: ["issuer".to_string()].into_iter().collect(),
+            n: 1,
+        };
+        start(&mediator, &transaction_id, &participants, policy).await;
+        
+        // The others consenting is not enough without the issuer ...
+        mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "bob", true)).await.unwrap();
+        assert_eq!(mediator.get_session(&transaction_id).await.unwrap().status, MediationStatus::WaitingForConsent);
+        
+        // ... and the issuer rejecting fails the session with carol undecided
+        let _ = mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "issuer", false)).await;
+        let session = mediator.get_session(&transaction_id).await.unwrap();
+        assert_eq!(session.status, MediationStatus::Rejected);
+        assert!(matches!(
+            session.result,
+            Some(MediationResult::Rejected { ref rejecting_participants, .. }) if rejecting_participants == &vec!["issuer".to_string()]
+        ));
+        
+        // A policy the participants could never satisfy is refused up front
+        let result = mediator.start_mediation(
+            "test-tx-any-4".to_string(),
+            vec![],
+            participants.iter().map(|p| p.to_string()).collect(),
+            HashSet::new(),
+            "test-domain".to_string(),
+            MediationPriority::Normal,
+            Some(ApprovalPolicy::AnyN { n: 4 }),
+        ).await;
+        assert!(matches!(result, Err(GarpError::ValidationError(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_timed_out_mediation_is_retried_without_unresponsive_participants() {
+        use crate::escalation::{EscalationEvent, FailureReason};
+        
+        let participants = ["alice", "bob", "carol", "dave"];
+        let mediator = mediator_with_participants(&participants).await;
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        let config = EscalationConfig { max_attempts: 2, ..EscalationConfig::default() };
+        let escalator = Arc::new(TimeoutEscalator::new(config).with_events(events_tx));
+        let transaction_id = "test-tx-retry".to_string();
+        start(&mediator, &transaction_id, &participants, ApprovalPolicy::AnyN { n: 2 }).await;
+        mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "alice", true)).await.unwrap();
+        
+        let expire = || async {
+            mediator.sessions.write().await.get_mut(&transaction_id).unwrap().timeout = Utc::now() - chrono::Duration::seconds(1);
+            TransactionMediator::check_session_timeouts(
+                &mediator.sessions, &mediator.storage, &mediator.kafka, &mediator.metrics, &escalator, chrono::Duration::seconds(60),
+            ).await;
+        };
+        
+        // Two consents can still come from alice and dave
+        expire().await;
+        let session = mediator.get_session(&transaction_id).await.unwrap();
+        assert_eq!(session.status, MediationStatus::WaitingForConsent);
+        assert!(session.timeout > Utc::now());
+        let excluded: HashSet<ParticipantId> = ["bob".to_string(), "carol".to_string()].into_iter().collect();
+        assert_eq!(session.excluded_participants, excluded);
+        assert_eq!(session.required_participants.len(), 2);
+        
+        // An excluded participant's consent is no longer needed or accepted
+        assert!(mediator.handle_consent(&transaction_id, signed_consent(&transaction_id, "bob", true)).await.is_err());
+        
+        expire().await;
+        let session = mediator.get_session(&transaction_id).await.unwrap();
+        assert_eq!(session.status, MediationStatus::TimedOut);
+        let mut failure = None;
+        while let Ok(event) = events.try_recv() {
+            if let EscalationEvent::Failed { reason, .. } = event {
+                failure = Some(reason);
+            }
+        }
+        assert_eq!(failure, Some(FailureReason {
+            phase: RoundPhase::Mediation,
+            attempts: 2,
+            unresponsive_participants: ["bob", "carol", "dave"].iter().map(|p| p.to_string()).collect(),
+        }));
+    }
+    
+    #[test]
+    fn test_weighted_policy() {
+        let required: HashSet<ParticipantId> = ["issuer", "bob", "carol"].iter().map(|p| p.to_string()).collect();
+        let policy = ApprovalPolicy::Weighted {
+            weights: [("issuer".to_string(), 3), ("bob".to_string(), 1), ("carol".to_string(), 1)].into_iter().collect(),
+            threshold: 4,
+        };
+        let consents = |decisions: &[(&str, bool)]| -> HashMap<ParticipantId, ConsentInfo> {
+            decisions.iter().map(|(p, c)| (p.to_string(), signed_consent("test-tx-weighted", p, *c))).collect()
+        };
+        
+        assert_eq!(policy.evaluate(&required, &consents(&[])), PolicyOutcome::Pending);
+        assert_eq!(policy.evaluate(&required, &consents(&[("bob", true), ("carol", true)])), PolicyOutcome::Pending);
+        assert_eq!(policy.evaluate(&required, &consents(&[("issuer", true), ("bob", true)])), PolicyOutcome::Satisfied);
+        assert_eq!(policy.evaluate(&required, &consents(&[("issuer", true), ("bob", false)])), PolicyOutcome::Pending);
+        assert_eq!(policy.evaluate(&required, &consents(&[("issuer", false)])), PolicyOutcome::Impossible);
+        
+        let policy = ApprovalPolicy::from_metadata(&[(
+            APPROVAL_POLICY_METADATA_KEY.to_string(),
+            r#"{"type":"weighted","weights":{"issuer":3},"threshold":3}"#.to_string(),
+        )].into_iter().collect()).unwrap().unwrap();
+        assert_eq!(policy.evaluate(&required, &consents(&[("issuer", true)])), PolicyOutcome::Satisfied);
+        assert!(ApprovalPolicy::from_metadata(&HashMap::new()).unwrap().is_none());
     }
 }
\ No newline at end of file
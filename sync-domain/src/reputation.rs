@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use garp_common::{
+    GarpResult, ParticipantId, ReputationScore, ReputationSignal, ReputationStore, ReputationTracker, ReputationUpdate,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::storage::StorageBackend;
+
+/// Metadata key holding every participant's score
+pub const REPUTATION_METADATA_KEY: &str = "reputation:scores";
+
+/// Keeps reputation scores in the domain's metadata table
+pub struct MetadataReputationStore {
+    storage: Arc<dyn StorageBackend>,
+}
+
+impl MetadataReputationStore {
+    pub fn new(storage: Arc<dyn StorageBackend>) -> Self {
+        Self { storage }
+    }
+
+    async fn scores(&self) -> GarpResult<HashMap<String, ReputationScore>> {
+        match self.storage.get_metadata(REPUTATION_METADATA_KEY).await? {
+            Some(value) => Ok(serde_json::from_slice(&value)?),
+            None => Ok(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ReputationStore for MetadataReputationStore {
+    async fn load_all(&self) -> GarpResult<Vec<ReputationScore>> {
+        Ok(self.scores().await?.into_values().collect())
+    }
+
+    async fn save(&self, score: &ReputationScore) -> GarpResult<()> {
+        let mut scores = self.scores().await?;
+        scores.insert(score.subject.clone(), score.clone());
+        self.storage.put_metadata(REPUTATION_METADATA_KEY, &serde_json::to_vec(&scores)?).await
+    }
+}
+
+/// Records the behaviour of domain participants, reporting those whose
+/// score falls below the suspension threshold
+pub struct ParticipantReputation {
+    /// Scores by participant
+    tracker: Arc<ReputationTracker>,
+
+    /// Receiver of participants to suspend
+    suspensions: Option<mpsc::UnboundedSender<ReputationUpdate>>,
+}
+
+impl ParticipantReputation {
+    pub fn new(tracker: Arc<ReputationTracker>) -> Self {
+        Self { tracker, suspensions: None }
+    }
+
+    /// Report participants falling below the suspension threshold on
+    /// `suspensions`
+    pub fn with_suspensions(mut self, suspensions: mpsc::UnboundedSender<ReputationUpdate>) -> Self {
+        self.suspensions = Some(suspensions);
+        self
+    }
+
+    /// Scores by participant
+    pub fn tracker(&self) -> Arc<ReputationTracker> {
+        self.tracker.clone()
+    }
+
+    /// Record a signal for a participant. Failing to persist the score is
+    /// logged rather than failing the round that observed the signal.
+    pub async fn record(&self, participant_id: &ParticipantId, signal: ReputationSignal) {
+        match self.tracker.record(participant_id, signal).await {
+            Ok(update) if update.fell_below_threshold => {
+                warn!("Participant {} fell to reputation {:.1} after {:?}", participant_id, update.score.score, signal);
+                if let Some(suspensions) = &self.suspensions {
+                    let _ = suspensions.send(update);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to record {:?} for participant {}: {}", signal, participant_id, e),
+        }
+    }
+
+    /// Record a signal for each of `participants`
+    pub async fn record_all<'a>(&self, participants: impl IntoIterator<Item = &'a ParticipantId>, signal: ReputationSignal) {
+        for participant_id in participants {
+            self.record(participant_id, signal).await;
+        }
+    }
+}
+
+/// Routes exposing participant reputation; merged into the domain API router
+pub fn router(tracker: Arc<ReputationTracker>) -> Router {
+    Router::new()
+        .route("/api/v1/reputation/:participant_id", get(get_reputation))
+        .with_state(tracker)
+}
+
+/// Current reputation of a participant
+async fn get_reputation(
+    State(tracker): State<Arc<ReputationTracker>>,
+    Path(participant_id): Path<String>,
+) -> Json<ReputationScore> {
+    Json(tracker.get(&participant_id).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use garp_common::ReputationConfig;
+
+    #[tokio::test]
+    async fn test_scores_persist_in_metadata_and_suspensions_are_reported() {
+        let storage: Arc<dyn StorageBackend> = Arc::new(MemoryStorage::new());
+        let config = ReputationConfig { suspension_threshold: Some(45.0), ..ReputationConfig::default() };
+        let store = Arc::new(MetadataReputationStore::new(storage.clone()));
+        let tracker = Arc::new(ReputationTracker::load(config.clone(), store).await.unwrap());
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let reputation = ParticipantReputation::new(tracker).with_suspensions(tx);
+        let alice = "alice".to_string();
+        reputation.record(&alice, ReputationSignal::MediationTimeout).await;
+        assert!(rx.try_recv().is_err());
+        reputation.record(&alice, ReputationSignal::MissedVote).await;
+        let update = rx.try_recv().unwrap();
+        assert_eq!(update.score.subject, "alice");
+        assert!(update.score.score < 45.0);
+
+        // A restarted domain picks up the stored scores
+        let store = Arc::new(MetadataReputationStore::new(storage));
+        let reloaded = ReputationTracker::load(config, store).await.unwrap();
+        let score = reloaded.get("alice").await;
+        assert_eq!(score.signals[&ReputationSignal::MediationTimeout], 1);
+        assert_eq!(score.signals[&ReputationSignal::MissedVote], 1);
+    }
+}
@@ -1,16 +1,23 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock, Semaphore};
 use tokio::time::{Duration, Instant};
 use uuid::Uuid;
-use garp_common::{GarpResult, Transaction, TransactionId, ParticipantId};
-use crate::storage::{StorageBackend, SequencedTransaction, TransactionMetadata, SequenceStatus};
+use garp_common::{GarpError, GarpResult, Transaction, TransactionId, ParticipantId};
+use crate::storage::{StorageBackend, SequencedTransaction, SkippedSequence, TransactionMetadata, SequenceStatus};
 use crate::config::PerformanceConfig;
 
-/// Transaction sequencer that orders transactions without decrypting them
+/// Metadata key holding the last sequence number checked by restart recovery
+const RECOVERED_THROUGH_KEY: &str = "sequencer.recovered_through";
+
+/// Transaction sequencer that orders transactions without decrypting them.
+///
+/// Sequence numbers are dense: a batch's numbers are persisted together
+/// with the advanced counter, so a crash either keeps the whole batch or
+/// none of it, and a number is never handed out twice.
 pub struct TransactionSequencer {
     /// Storage backend
     storage: Arc<dyn StorageBackend>,
@@ -162,6 +169,16 @@ pub struct SequencerMetrics {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Result of checking a range of sequence numbers for holes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SequenceGaps {
+    /// Numbers with neither a stored transaction nor a skip record
+    pub missing: Vec<u64>,
+    
+    /// Numbers deliberately skipped, with the reason
+    pub skipped: Vec<SkippedSequence>,
+}
+
 /// Sequencing strategy
 #[derive(Debug, Clone)]
 pub enum SequencingStrategy {
@@ -236,6 +253,7 @@ impl TransactionSequencer {
         
         let batch_processor = Arc::new(BatchProcessor::new(batch_config));
         let sequence_number = storage.get_next_sequence_number().await?;
+        Self::skip_unfinalized(&storage, sequence_number).await?;
         
         Ok(Self {
             storage,
@@ -374,6 +392,84 @@ impl TransactionSequencer {
         self.metrics.read().await.clone()
     }
     
+    /// Sequence up to `transaction_batch_size` transactions at once, giving
+    /// them a contiguous range of sequence numbers in a single storage write
+    pub async fn sequence_batch(&self, transactions: Vec<PendingTransaction>) -> GarpResult<Vec<SequencedTransaction>> {
+        if transactions.len() > self.config.transaction_batch_size {
+            return Err(GarpError::ValidationError(format!(
+                "Batch of {} transactions exceeds the limit of {}",
+                transactions.len(), self.config.transaction_batch_size
+            )));
+        }
+        
+        let sequenced = assign_sequence_range(&self.storage, &self.sequence_generator, Uuid::new_v4(), &transactions).await?;
+        self.metrics.write().await.total_transactions += sequenced.len() as u64;
+        Ok(sequenced)
+    }
+    
+    /// Report the sequence numbers in `from..=to` that have no transaction.
+    /// Numbers not assigned yet are not gaps.
+    pub async fn get_sequence_gaps(&self, from: u64, to: u64) -> GarpResult<SequenceGaps> {
+        let from = from.max(1);
+        let to = to.min(self.sequence_generator.read().await.saturating_sub(1));
+        if from > to {
+            return Ok(SequenceGaps::default());
+        }
+        
+        let stored: HashSet<u64> = self.storage.get_transactions_in_range(from, to).await?
+            .into_iter()
+            .map(|transaction| transaction.sequence_number)
+            .collect();
+        let skipped = self.storage.get_skipped_sequences(from, to).await?;
+        let skipped_numbers: HashSet<u64> = skipped.iter().map(|entry| entry.sequence_number).collect();
+        let missing = (from..=to)
+            .filter(|number| !stored.contains(number) && !skipped_numbers.contains(number))
+            .collect();
+        
+        Ok(SequenceGaps { missing, skipped })
+    }
+    
+    /// Mark numbers assigned before a restart whose transactions never
+    /// reached a final status as skipped. Only numbers assigned since the
+    /// last recovery are checked.
+    async fn skip_unfinalized(storage: &Arc<dyn StorageBackend>, next_sequence: u64) -> GarpResult<Vec<SkippedSequence>> {
+        let recovered_through = match storage.get_metadata(RECOVERED_THROUGH_KEY).await? {
+            Some(bytes) => u64::from_be_bytes(bytes.as_slice().try_into().map_err(|_| {
+                GarpError::StorageError(format!("Malformed {} metadata", RECOVERED_THROUGH_KEY))
+            })?),
+            None => 0,
+        };
+        let last_assigned = next_sequence.saturating_sub(1);
+        if last_assigned <= recovered_through {
+            return Ok(Vec::new());
+        }
+        
+        let start = recovered_through + 1;
+        let already_skipped: HashSet<u64> = storage.get_skipped_sequences(start, last_assigned).await?
+            .into_iter()
+            .map(|entry| entry.sequence_number)
+            .collect();
+        let skipped_at = Utc::now();
+        let skipped: Vec<SkippedSequence> = storage.get_transactions_in_range(start, last_assigned).await?
+            .into_iter()
+            .filter(|transaction| matches!(transaction.status, SequenceStatus::Sequenced | SequenceStatus::InConsensus))
+            .filter(|transaction| !already_skipped.contains(&transaction.sequence_number))
+            .map(|transaction| SkippedSequence {
+                sequence_number: transaction.sequence_number,
+                transaction_id: Some(transaction.transaction_id),
+                reason: "not finalized before restart".to_string(),
+                skipped_at,
+            })
+            .collect();
+        
+        storage.record_skipped_sequences(&skipped).await?;
+        storage.put_metadata(RECOVERED_THROUGH_KEY, &last_assigned.to_be_bytes()).await?;
+        if !skipped.is_empty() {
+            tracing::warn!(skipped = skipped.len(), through = last_assigned, "Skipped sequence numbers left unfinalized by restart");
+        }
+        Ok(skipped)
+    }
+    
     /// Process transactions from queues
     async fn process_transactions(
         storage: &Arc<dyn StorageBackend>,
@@ -529,35 +625,9 @@ impl BatchProcessor {
         // Sort transactions by priority within the batch
         batch.transactions.sort_by(|a, b| b.priority.cmp(&a.priority));
         
-        let mut sequenced_count = 0;
-        
-        for transaction in &batch.transactions {
-            // Get next sequence number
-            let sequence_number = {
-                let mut seq_gen = sequence_generator.write().await;
-                let current = *seq_gen;
-                *seq_gen += 1;
-                current
-            };
-            
-            // Create sequenced transaction
-            let sequenced_transaction = SequencedTransaction {
-                sequence_number,
-                transaction_id: transaction.transaction_id.clone(),
-                encrypted_data: transaction.encrypted_data.clone(),
-                metadata: transaction.metadata.clone(),
-                sequenced_at: Utc::now(),
-                domain_id: transaction.domain_id.clone(),
-                batch_id: Some(batch.batch_id),
-                status: SequenceStatus::Sequenced,
-            };
-            
-            // Store in database
-            storage.store_sequenced_transaction(&sequenced_transaction).await?;
-            storage.increment_transaction_count().await?;
-            
-            sequenced_count += 1;
-        }
+        let sequenced_count = assign_sequence_range(storage, sequence_generator, batch.batch_id, &batch.transactions)
+            .await?
+            .len() as u64;
         
         // Update batch status
         batch.status = BatchStatus::Completed;
@@ -586,6 +656,42 @@ impl BatchProcessor {
     }
 }
 
+/// Number `transactions` contiguously from the next sequence number and
+/// store them in one write. The counter only advances once the write has
+/// succeeded, so a failed batch does not leave a gap.
+async fn assign_sequence_range(
+    storage: &Arc<dyn StorageBackend>,
+    sequence_generator: &Arc<RwLock<u64>>,
+    batch_id: Uuid,
+    transactions: &[PendingTransaction],
+) -> GarpResult<Vec<SequencedTransaction>> {
+    // Held across the write so concurrent batches cannot interleave
+    let mut next_sequence = sequence_generator.write().await;
+    let sequenced_at = Utc::now();
+    let sequenced: Vec<SequencedTransaction> = transactions.iter().enumerate()
+        .map(|(offset, transaction)| SequencedTransaction {
+            sequence_number: *next_sequence + offset as u64,
+            transaction_id: transaction.transaction_id.clone(),
+            encrypted_data: transaction.encrypted_data.clone(),
+            metadata: transaction.metadata.clone(),
+            sequenced_at,
+            domain_id: transaction.domain_id.clone(),
+            batch_id: Some(batch_id),
+            status: SequenceStatus::Sequenced,
+        })
+        .collect();
+    
+    if let Err(e) = storage.append_sequenced_batch(&sequenced).await {
+        // The write may have landed before failing; never reuse its numbers
+        if let Ok(persisted) = storage.get_next_sequence_number().await {
+            *next_sequence = persisted.max(*next_sequence);
+        }
+        return Err(e);
+    }
+    *next_sequence += sequenced.len() as u64;
+    Ok(sequenced)
+}
+
 /// Sequencer factory
 pub struct SequencerFactory;
 
@@ -679,4 +785,108 @@ mod tests {
         // Should boost priority for urgent expiry, small size, and TransferAsset type
         assert!(priority > 50);
     }
-}
\ No newline at end of file
+    
+    fn test_config() -> PerformanceConfig {
+        PerformanceConfig {
+            transaction_batch_size: 10,
+            batch_timeout_ms: 1000,
+            max_concurrent_transactions: 100,
+            sequencer_buffer_size: 1000,
+            enable_compression: false,
+            compression_algorithm: "none".to_string(),
+            transaction_pool_size: 1000,
+            enable_parallel_processing: true,
+            worker_threads: None,
+        }
+    }
+    
+    fn pending_batch(prefix: &str, count: usize) -> Vec<PendingTransaction> {
+        (0..count).map(|i| PendingTransaction {
+            transaction_id: format!("{}-{}", prefix, i),
+            encrypted_data: vec![i as u8],
+            metadata: TransactionMetadata {
+                participants: vec!["participant-1".to_string()],
+                transaction_type: "TransferAsset".to_string(),
+                priority: 0,
+                size: 1,
+                hash: format!("hash-{}-{}", prefix, i),
+                dependencies: vec![],
+                expires_at: None,
+            },
+            received_at: Utc::now(),
+            domain_id: "test-domain".to_string(),
+            priority: 0,
+            estimated_processing_time: Duration::from_millis(10),
+        }).collect()
+    }
+    
+    fn sequence_numbers(sequenced: &[SequencedTransaction]) -> Vec<u64> {
+        sequenced.iter().map(|transaction| transaction.sequence_number).collect()
+    }
+    
+    #[tokio::test]
+    async fn test_restart_mid_batch_never_reissues_numbers() {
+        let storage = Arc::new(MemoryStorage::new()) as Arc<dyn StorageBackend>;
+        let sequencer = Arc::new(TransactionSequencer::new(storage.clone(), test_config()).await.unwrap());
+        
+        let first = sequencer.sequence_batch(pending_batch("a", 3)).await.unwrap();
+        assert_eq!(sequence_numbers(&first), vec![1, 2, 3]);
+        storage.update_transaction_status(1, SequenceStatus::Committed).await.unwrap();
+        storage.update_transaction_status(2, SequenceStatus::Rejected).await.unwrap();
+        let second = sequencer.sequence_batch(pending_batch("b", 2)).await.unwrap();
+        assert_eq!(sequence_numbers(&second), vec![4, 5]);
+        
+        // Kill the node while it sequences a third batch
+        let in_flight = tokio::spawn({
+            let sequencer = sequencer.clone();
+            async move { sequencer.sequence_batch(pending_batch("c", 4)).await }
+        });
+        in_flight.abort();
+        assert!(in_flight.await.unwrap_err().is_cancelled());
+        drop(sequencer);
+        
+        // Everything assigned but not finalized is skipped on restart
+        let restarted = TransactionSequencer::new(storage.clone(), test_config()).await.unwrap();
+        let skipped = storage.get_skipped_sequences(1, 100).await.unwrap();
+        assert_eq!(skipped.iter().map(|entry| entry.sequence_number).collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(skipped[0].transaction_id.as_deref(), Some("a-2"));
+        assert_eq!(storage.get_sequenced_transaction(4).await.unwrap().unwrap().status, SequenceStatus::Skipped);
+        assert_eq!(storage.get_sequenced_transaction(1).await.unwrap().unwrap().status, SequenceStatus::Committed);
+        
+        let third = restarted.sequence_batch(pending_batch("d", 2)).await.unwrap();
+        assert_eq!(sequence_numbers(&third), vec![6, 7]);
+        
+        let all = storage.get_transactions_in_range(1, 100).await.unwrap();
+        assert_eq!(sequence_numbers(&all), (1..=7).collect::<Vec<_>>());
+        let ids: HashSet<_> = all.iter().map(|transaction| transaction.transaction_id.clone()).collect();
+        assert_eq!(ids.len(), 7);
+        
+        let gaps = restarted.get_sequence_gaps(1, 100).await.unwrap();
+        assert!(gaps.missing.is_empty());
+        assert_eq!(gaps.skipped, skipped);
+        
+        // A second restart does not skip again what recovery already handled
+        drop(restarted);
+        let restarted = TransactionSequencer::new(storage.clone(), test_config()).await.unwrap();
+        assert_eq!(storage.get_skipped_sequences(1, 100).await.unwrap().len(), 5);
+        assert_eq!(sequence_numbers(&restarted.sequence_batch(pending_batch("e", 1)).await.unwrap()), vec![8]);
+    }
+    
+    #[tokio::test]
+    async fn test_sequence_gaps_report_missing_numbers() {
+        let storage = Arc::new(MemoryStorage::new()) as Arc<dyn StorageBackend>;
+        let sequencer = TransactionSequencer::new(storage.clone(), test_config()).await.unwrap();
+        
+        assert!(sequencer.sequence_batch(pending_batch("too-many", 11)).await.is_err());
+        sequencer.sequence_batch(pending_batch("a", 4)).await.unwrap();
+        
+        // Lose the stored transactions, as after pruning or data loss
+        storage.cleanup_old_transactions(Utc::now() + chrono::Duration::seconds(1)).await.unwrap();
+        sequencer.sequence_batch(pending_batch("b", 1)).await.unwrap();
+        
+        let gaps = sequencer.get_sequence_gaps(2, 100).await.unwrap();
+        assert_eq!(gaps.missing, vec![2, 3, 4]);
+        assert!(gaps.skipped.is_empty());
+        assert_eq!(sequencer.get_sequence_gaps(6, 10).await.unwrap(), SequenceGaps::default());
+    }
+}
@@ -1,12 +1,13 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Row};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use garp_common::{GarpResult, Transaction, TransactionId, ParticipantId};
+use garp_common::{GarpError, GarpResult, Transaction, TransactionId, ParticipantId};
 use crate::config::DatabaseConfig;
 
 /// Transaction sequence entry
@@ -79,6 +80,26 @@ pub enum SequenceStatus {
     
     /// Transaction expired
     Expired,
+    
+    /// Sequence number given up without the transaction being finalized
+    Skipped,
+}
+
+/// Sequence number deliberately left without a finalized transaction, so
+/// consumers can tell it apart from missing data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkippedSequence {
+    /// Skipped sequence number
+    pub sequence_number: u64,
+    
+    /// Transaction that held the number, if one was stored
+    pub transaction_id: Option<TransactionId>,
+    
+    /// Why the number was skipped
+    pub reason: String,
+    
+    /// When the number was skipped
+    pub skipped_at: DateTime<Utc>,
 }
 
 /// Consensus state for a transaction
@@ -251,7 +272,14 @@ pub trait StorageBackend: Send + Sync {
     async fn get_transactions_by_batch(&self, batch_id: Uuid) -> GarpResult<Vec<SequencedTransaction>>;
     async fn get_transactions_in_range(&self, start: u64, end: u64) -> GarpResult<Vec<SequencedTransaction>>;
     async fn update_transaction_status(&self, sequence_number: u64, status: SequenceStatus) -> GarpResult<()>;
+    /// Next sequence number to assign, without reserving it
     async fn get_next_sequence_number(&self) -> GarpResult<u64>;
+    /// Store transactions numbered contiguously from the next sequence
+    /// number and advance it past them, all in one atomic write
+    async fn append_sequenced_batch(&self, transactions: &[SequencedTransaction]) -> GarpResult<()>;
+    /// Record sequence numbers as skipped, marking stored transactions `Skipped`
+    async fn record_skipped_sequences(&self, skipped: &[SkippedSequence]) -> GarpResult<()>;
+    async fn get_skipped_sequences(&self, start: u64, end: u64) -> GarpResult<Vec<SkippedSequence>>;
     
     // Consensus management
     async fn store_consensus_state(&self, state: &ConsensusState) -> GarpResult<()>;
@@ -275,6 +303,10 @@ pub trait StorageBackend: Send + Sync {
     async fn increment_transaction_count(&self) -> GarpResult<()>;
     async fn record_tps_sample(&self, tps: f64) -> GarpResult<()>;
     
+    // Key-value metadata
+    async fn put_metadata(&self, key: &str, value: &[u8]) -> GarpResult<()>;
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<Vec<u8>>>;
+    
     // Maintenance
     async fn cleanup_old_transactions(&self, older_than: DateTime<Utc>) -> GarpResult<u64>;
     async fn compact_storage(&self) -> GarpResult<()>;
@@ -358,6 +390,25 @@ impl PostgresStorage {
             )
         "#).execute(&self.pool).await?;
         
+        // Create skipped_sequences table
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS skipped_sequences (
+                sequence_number BIGINT PRIMARY KEY,
+                transaction_id TEXT,
+                reason TEXT NOT NULL,
+                skipped_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#).execute(&self.pool).await?;
+        
+        // Create domain_metadata table
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS domain_metadata (
+                key TEXT PRIMARY KEY,
+                value BYTEA NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#).execute(&self.pool).await?;
+        
         // Insert initial stats record if not exists
         sqlx::query(r#"
             INSERT INTO domain_stats (id, total_transactions, transactions_24h, avg_tps, current_sequence, uptime_seconds)
@@ -497,13 +548,121 @@ impl StorageBackend for PostgresStorage {
     }
     
     async fn get_next_sequence_number(&self) -> GarpResult<u64> {
-        let row = sqlx::query("SELECT COALESCE(MAX(sequence_number), 0) + 1 as next_seq FROM sequenced_transactions")
-            .fetch_one(&self.pool)
-            .await?;
+        // Rows written before the counter was maintained still count
+        let row = sqlx::query(r#"
+            SELECT GREATEST(
+                (SELECT current_sequence FROM domain_stats WHERE id = 1),
+                (SELECT COALESCE(MAX(sequence_number), 0) FROM sequenced_transactions)
+            ) + 1 AS next_seq
+        "#)
+        .fetch_one(&self.pool)
+        .await?;
         
         Ok(row.get::<i64, _>("next_seq") as u64)
     }
     
+    async fn append_sequenced_batch(&self, transactions: &[SequencedTransaction]) -> GarpResult<()> {
+        let (Some(first), Some(last)) = (transactions.first(), transactions.last()) else {
+            return Ok(());
+        };
+        let mut tx = self.pool.begin().await?;
+        
+        // Claim the range; fails if any of it was already handed out
+        let claimed = sqlx::query(r#"
+            UPDATE domain_stats
+            SET current_sequence = $2,
+                total_transactions = total_transactions + $3,
+                transactions_24h = transactions_24h + $3,
+                updated_at = NOW()
+            WHERE id = 1 AND current_sequence < $1
+        "#)
+        .bind(first.sequence_number as i64)
+        .bind(last.sequence_number as i64)
+        .bind(transactions.len() as i64)
+        .execute(&mut *tx)
+        .await?;
+        if claimed.rows_affected() == 0 {
+            return Err(GarpError::StateMismatch(format!(
+                "Sequence number {} has already been assigned",
+                first.sequence_number
+            )));
+        }
+        
+        let mut metadata = Vec::with_capacity(transactions.len());
+        let mut statuses = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            metadata.push(serde_json::to_value(&transaction.metadata)?);
+            statuses.push(serde_json::to_string(&transaction.status)?);
+        }
+        sqlx::query(r#"
+            INSERT INTO sequenced_transactions
+            (sequence_number, transaction_id, encrypted_data, metadata, sequenced_at, domain_id, batch_id, status)
+            SELECT * FROM UNNEST($1::BIGINT[], $2::TEXT[], $3::BYTEA[], $4::JSONB[], $5::TIMESTAMPTZ[], $6::TEXT[], $7::UUID[], $8::TEXT[])
+        "#)
+        .bind(transactions.iter().map(|t| t.sequence_number as i64).collect::<Vec<_>>())
+        .bind(transactions.iter().map(|t| t.transaction_id.to_string()).collect::<Vec<_>>())
+        .bind(transactions.iter().map(|t| t.encrypted_data.clone()).collect::<Vec<_>>())
+        .bind(metadata)
+        .bind(transactions.iter().map(|t| t.sequenced_at).collect::<Vec<_>>())
+        .bind(transactions.iter().map(|t| t.domain_id.clone()).collect::<Vec<_>>())
+        .bind(transactions.iter().map(|t| t.batch_id).collect::<Vec<_>>())
+        .bind(statuses)
+        .execute(&mut *tx)
+        .await?;
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    async fn record_skipped_sequences(&self, skipped: &[SkippedSequence]) -> GarpResult<()> {
+        if skipped.is_empty() {
+            return Ok(());
+        }
+        let sequence_numbers: Vec<i64> = skipped.iter().map(|s| s.sequence_number as i64).collect();
+        let mut tx = self.pool.begin().await?;
+        
+        sqlx::query(r#"
+            INSERT INTO skipped_sequences (sequence_number, transaction_id, reason, skipped_at)
+            SELECT * FROM UNNEST($1::BIGINT[], $2::TEXT[], $3::TEXT[], $4::TIMESTAMPTZ[])
+            ON CONFLICT (sequence_number) DO NOTHING
+        "#)
+        .bind(&sequence_numbers)
+        .bind(skipped.iter().map(|s| s.transaction_id.as_ref().map(|id| id.to_string())).collect::<Vec<_>>())
+        .bind(skipped.iter().map(|s| s.reason.clone()).collect::<Vec<_>>())
+        .bind(skipped.iter().map(|s| s.skipped_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+        
+        sqlx::query("UPDATE sequenced_transactions SET status = $1, updated_at = NOW() WHERE sequence_number = ANY($2)")
+            .bind(serde_json::to_string(&SequenceStatus::Skipped)?)
+            .bind(&sequence_numbers)
+            .execute(&mut *tx)
+            .await?;
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    async fn get_skipped_sequences(&self, start: u64, end: u64) -> GarpResult<Vec<SkippedSequence>> {
+        let rows = sqlx::query(r#"
+            SELECT sequence_number, transaction_id, reason, skipped_at
+            FROM skipped_sequences
+            WHERE sequence_number >= $1 AND sequence_number <= $2
+            ORDER BY sequence_number
+        "#)
+        .bind(start as i64)
+        .bind(end as i64)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(rows.into_iter().map(|row| SkippedSequence {
+            sequence_number: row.get::<i64, _>("sequence_number") as u64,
+            transaction_id: row.get("transaction_id"),
+            reason: row.get("reason"),
+            skipped_at: row.get("skipped_at"),
+        }).collect())
+    }
+    
     async fn store_consensus_state(&self, state: &ConsensusState) -> GarpResult<()> {
         sqlx::query(r#"
             INSERT INTO consensus_states 
@@ -794,6 +953,29 @@ impl StorageBackend for PostgresStorage {
         Ok(())
     }
     
+    async fn put_metadata(&self, key: &str, value: &[u8]) -> GarpResult<()> {
+        sqlx::query(r#"
+            INSERT INTO domain_metadata (key, value, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
+        "#)
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<Vec<u8>>> {
+        let row = sqlx::query("SELECT value FROM domain_metadata WHERE key = $1")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        Ok(row.map(|row| row.get("value")))
+    }
+    
     async fn cleanup_old_transactions(&self, older_than: DateTime<Utc>) -> GarpResult<u64> {
         let result = sqlx::query("DELETE FROM sequenced_transactions WHERE sequenced_at < $1")
             .bind(older_than)
@@ -817,7 +999,9 @@ pub struct MemoryStorage {
     transactions: Arc<RwLock<HashMap<u64, SequencedTransaction>>>,
     consensus_states: Arc<RwLock<HashMap<TransactionId, ConsensusState>>>,
     participants: Arc<RwLock<HashMap<ParticipantId, DomainParticipant>>>,
+    metadata: Arc<RwLock<HashMap<String, Vec<u8>>>>,
     next_sequence: Arc<RwLock<u64>>,
+    skipped: Arc<RwLock<BTreeMap<u64, SkippedSequence>>>,
     stats: Arc<RwLock<DomainStats>>,
 }
 
@@ -827,7 +1011,9 @@ impl MemoryStorage {
             transactions: Arc::new(RwLock::new(HashMap::new())),
             consensus_states: Arc::new(RwLock::new(HashMap::new())),
             participants: Arc::new(RwLock::new(HashMap::new())),
+            metadata: Arc::new(RwLock::new(HashMap::new())),
             next_sequence: Arc::new(RwLock::new(1)),
+            skipped: Arc::new(RwLock::new(BTreeMap::new())),
             stats: Arc::new(RwLock::new(DomainStats {
                 total_transactions: 0,
                 transactions_24h: 0,
@@ -890,10 +1076,52 @@ impl StorageBackend for MemoryStorage {
     }
     
     async fn get_next_sequence_number(&self) -> GarpResult<u64> {
-        let mut next_seq = self.next_sequence.write().await;
-        let current = *next_seq;
-        *next_seq += 1;
-        Ok(current)
+        Ok(*self.next_sequence.read().await)
+    }
+    
+    async fn append_sequenced_batch(&self, transactions: &[SequencedTransaction]) -> GarpResult<()> {
+        let mut next_sequence = self.next_sequence.write().await;
+        for (offset, transaction) in transactions.iter().enumerate() {
+            let expected = *next_sequence + offset as u64;
+            if transaction.sequence_number != expected {
+                return Err(GarpError::StateMismatch(format!(
+                    "Expected sequence number {}, got {}",
+                    expected, transaction.sequence_number
+                )));
+            }
+        }
+        
+        let mut stored = self.transactions.write().await;
+        for transaction in transactions {
+            stored.insert(transaction.sequence_number, transaction.clone());
+        }
+        *next_sequence += transactions.len() as u64;
+        
+        let mut stats = self.stats.write().await;
+        stats.total_transactions += transactions.len() as u64;
+        stats.transactions_24h += transactions.len() as u64;
+        stats.current_sequence = *next_sequence - 1;
+        Ok(())
+    }
+    
+    async fn record_skipped_sequences(&self, skipped: &[SkippedSequence]) -> GarpResult<()> {
+        let mut transactions = self.transactions.write().await;
+        let mut records = self.skipped.write().await;
+        for entry in skipped {
+            if let Some(transaction) = transactions.get_mut(&entry.sequence_number) {
+                transaction.status = SequenceStatus::Skipped;
+            }
+            records.entry(entry.sequence_number).or_insert_with(|| entry.clone());
+        }
+        Ok(())
+    }
+    
+    async fn get_skipped_sequences(&self, start: u64, end: u64) -> GarpResult<Vec<SkippedSequence>> {
+        if start > end {
+            return Ok(Vec::new());
+        }
+        let records = self.skipped.read().await;
+        Ok(records.range(start..=end).map(|(_, entry)| entry.clone()).collect())
     }
     
     async fn store_consensus_state(&self, state: &ConsensusState) -> GarpResult<()> {
@@ -1030,6 +1258,17 @@ impl StorageBackend for MemoryStorage {
         Ok(())
     }
     
+    async fn put_metadata(&self, key: &str, value: &[u8]) -> GarpResult<()> {
+        let mut metadata = self.metadata.write().await;
+        metadata.insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+    
+    async fn get_metadata(&self, key: &str) -> GarpResult<Option<Vec<u8>>> {
+        let metadata = self.metadata.read().await;
+        Ok(metadata.get(key).cloned())
+    }
+    
     async fn cleanup_old_transactions(&self, older_than: DateTime<Utc>) -> GarpResult<u64> {
         let mut transactions = self.transactions.write().await;
         let mut count = 0;
@@ -1052,6 +1291,119 @@ impl StorageBackend for MemoryStorage {
     }
 }
 
+/// Position of a record in the event log; orders by time, then append order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EventLogKey {
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    
+    /// Append sequence, unique within the table
+    pub sequence: u64,
+}
+
+/// Domain event as recorded in the event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLogRecord {
+    /// Position in the log
+    pub key: EventLogKey,
+    
+    /// Event ID
+    pub event_id: String,
+    
+    /// Event type name
+    pub event_type: String,
+    
+    /// Related participant (if any)
+    pub participant_id: Option<ParticipantId>,
+    
+    /// Related transaction (if any)
+    pub transaction_id: Option<TransactionId>,
+    
+    /// Event data
+    pub data: serde_json::Value,
+}
+
+/// Time-ordered, append-only table of domain events.
+///
+/// Records are only ever removed by retention pruning.
+pub struct EventLogTable {
+    records: RwLock<BTreeMap<EventLogKey, EventLogRecord>>,
+    next_sequence: RwLock<u64>,
+}
+
+impl EventLogTable {
+    pub fn new() -> Self {
+        Self {
+            records: RwLock::new(BTreeMap::new()),
+            next_sequence: RwLock::new(1),
+        }
+    }
+    
+    /// Append a record, assigning its sequence number
+    pub async fn append(
+        &self,
+        timestamp: DateTime<Utc>,
+        event_id: String,
+        event_type: String,
+        participant_id: Option<ParticipantId>,
+        transaction_id: Option<TransactionId>,
+        data: serde_json::Value,
+    ) -> EventLogKey {
+        let mut records = self.records.write().await;
+        let mut next_sequence = self.next_sequence.write().await;
+        let key = EventLogKey { timestamp, sequence: *next_sequence };
+        *next_sequence += 1;
+        
+        records.insert(key, EventLogRecord {
+            key,
+            event_id,
+            event_type,
+            participant_id,
+            transaction_id,
+            data,
+        });
+        key
+    }
+    
+    /// Scan records in key order starting at `start` and ending at `end` (inclusive),
+    /// returning up to `limit` matches and whether more matches remain
+    pub async fn scan<F>(
+        &self,
+        start: Bound<EventLogKey>,
+        end: Option<DateTime<Utc>>,
+        limit: usize,
+        predicate: F,
+    ) -> (Vec<EventLogRecord>, bool)
+    where
+        F: Fn(&EventLogRecord) -> bool,
+    {
+        let records = self.records.read().await;
+        let mut matches = records
+            .range((start, Bound::Unbounded))
+            .map(|(_, record)| record)
+            .take_while(|record| end.map_or(true, |end| record.key.timestamp <= end))
+            .filter(|record| predicate(record));
+        
+        let page: Vec<EventLogRecord> = matches.by_ref().take(limit).cloned().collect();
+        let has_more = matches.next().is_some();
+        (page, has_more)
+    }
+    
+    /// Remove records older than `cutoff`, returning the number removed
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> u64 {
+        let mut records = self.records.write().await;
+        let retained = records.split_off(&EventLogKey { timestamp: cutoff, sequence: 0 });
+        let removed = records.len() as u64;
+        *records = retained;
+        removed
+    }
+    
+    /// Number of records in the table
+    pub async fn len(&self) -> usize {
+        self.records.read().await.len()
+    }
+}
+
 /// Storage factory
 pub struct Storage;
 